@@ -51,6 +51,7 @@ impl ChatPage {
                     },
                     msg: "test".into(),
                     channel: NetChatMsgPlayerChannel::GameTeam,
+                    highlighted: false,
                 }),
                 add_time: Duration::MAX,
             },
@@ -76,6 +77,7 @@ impl ChatPage {
                             feet_color: ubvec4::new(255, 255, 255, 255),
                         },
                     }),
+                    highlighted: true,
                 }),
                 add_time: Duration::MAX,
             },
@@ -104,6 +106,7 @@ impl ChatPage {
                     skin_info: NetworkSkinInfo::Original,
                     msg: "short".into(),
                     channel: NetChatMsgPlayerChannel::Global,
+                    highlighted: false,
                 }),
                 add_time: Duration::MAX,
             });
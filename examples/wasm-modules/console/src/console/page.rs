@@ -78,6 +78,7 @@ impl Console {
                     render_custom_matches: &|_, _, _, _, _, _| {},
                     skin_container: &mut self.skin_container,
                     render_tee: &self.render_tee,
+                    logs: None,
                 },
             },
             ui_state,
@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use client_ui::demo_player::user_data::{DemoViewerEvent, DemoViewerUiState};
+use client_ui::demo_player::user_data::{DemoCameraMode, DemoViewerEvent, DemoViewerUiState};
 use graphics::{
     graphics::graphics::Graphics,
     handles::{canvas::canvas::GraphicsCanvasHandle, stream::stream::GraphicsStreamHandle},
@@ -13,6 +13,7 @@ pub struct DemoPlayerPage {
     stream_handle: GraphicsStreamHandle,
 
     paused: bool,
+    camera_mode: DemoCameraMode,
     state: DemoViewerUiState,
 }
 
@@ -23,6 +24,7 @@ impl DemoPlayerPage {
             stream_handle: graphics.stream_handle.clone(),
 
             paused: false,
+            camera_mode: Default::default(),
             state: Default::default(),
         }
     }
@@ -48,13 +50,22 @@ impl DemoPlayerPage {
                     speed: &Default::default(),
                     state: &mut self.state,
                     name: "example_demo",
+                    camera_mode: &self.camera_mode,
+                    camera_players: &[],
+                    encode_progress: None,
                 },
             ),
             ui_state,
         );
         for event in events {
-            if let DemoViewerEvent::ResumeToggle = event {
-                self.paused = !self.paused;
+            match event {
+                DemoViewerEvent::ResumeToggle => {
+                    self.paused = !self.paused;
+                }
+                DemoViewerEvent::CameraMode(mode) => {
+                    self.camera_mode = mode;
+                }
+                _ => {}
             }
         }
     }
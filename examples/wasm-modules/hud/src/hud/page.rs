@@ -47,6 +47,8 @@ impl HudPage {
                 player_info: None,
                 browser_score: PoolNetworkString::new_without_pool(),
                 browser_eye: TeeEye::Happy,
+                is_bot: false,
+                server_rank: None,
                 account_name: Some(PoolNetworkString::from_without_pool(
                     "testname".try_into().unwrap(),
                 )),
@@ -101,6 +103,8 @@ impl HudPage {
                         },
                         round_time_type: MatchRoundTimeType::Normal,
                         unbalanced: false,
+                        balance_suggestion: Vec::new(),
+                        balance_at_round_end: false,
                     }),
                     /*game: Some(&GameRenderInfo::Race {}),*/
                     skin_container: &mut self.skin_container,
@@ -113,6 +117,7 @@ impl HudPage {
                         time: PoolString::new_str_without_pool("22:14:14"),
                         date: PoolString::new_str_without_pool("Saturday, 27. September 2025"),
                     }),
+                    race_splits: &None,
                 },
             ),
             ui_state,
@@ -1,4 +1,4 @@
-use std::{collections::BTreeMap, path::Path, sync::Arc};
+use std::{collections::BTreeMap, net::SocketAddr, path::Path, sync::Arc, time::Duration};
 
 use api::IO;
 use api_ui_game::render::{
@@ -28,8 +28,9 @@ use client_ui::{
         votes::Votes,
     },
     main_menu::{
-        monitors::UiMonitors, profiles_interface::ProfilesInterface,
-        theme_container::ThemeContainer, user_data::MainMenuInterface,
+        game_files::GameFilesState, monitors::UiMonitors, page::MAP_THUMBNAIL_CONTAINER_PATH,
+        profiles_interface::ProfilesInterface, theme_container::ThemeContainer,
+        user_data::MainMenuInterface,
     },
     thumbnail_container::{DEFAULT_THUMBNAIL_CONTAINER_PATH, ThumbnailContainer},
 };
@@ -62,8 +63,20 @@ struct MenuImpl {}
 
 impl MainMenuInterface for MenuImpl {
     fn refresh(&mut self) {}
+    fn refresh_server_info(&mut self, _addr: SocketAddr, _cur_time: &Duration) {}
+    fn can_refresh_server_info(&self, _cur_time: &Duration) -> bool {
+        false
+    }
     fn refresh_demo_list(&mut self, _path: &Path) {}
     fn refresh_demo_info(&mut self, _file: Option<&Path>) {}
+
+    fn refresh_local_stats(&mut self) {}
+
+    fn verify_game_files(&mut self) {}
+    fn repair_game_files(&mut self, _download: bool) {}
+    fn game_files_state(&self) -> GameFilesState {
+        GameFilesState::Unchecked
+    }
 }
 
 pub struct IngameMenu {
@@ -94,6 +107,7 @@ pub struct IngameMenu {
     theme_container: ThemeContainer,
     map_vote_thumbnail_container: ThumbnailContainer,
     icons_container: ThumbnailContainer,
+    map_thumbnail_container: ThumbnailContainer,
     map_render: MapGraphics,
     tile_layer_visuals: Option<TileLayerVisuals>,
 }
@@ -139,6 +153,10 @@ impl IngameMenu {
                 DEFAULT_THUMBNAIL_CONTAINER_PATH,
                 "community-icons",
             ),
+            map_thumbnail_container: create_thumbnail_container(
+                MAP_THUMBNAIL_CONTAINER_PATH,
+                "map-thumbnails",
+            ),
             map_render: MapGraphics::new(&graphics.backend_handle),
             tile_layer_visuals: None,
         }
@@ -186,7 +204,9 @@ impl IngameMenu {
                         ddnet_info: &Default::default(),
                         demos: &Default::default(),
                         demo_info: &None,
+                        local_stats: &Default::default(),
                         icons: &mut self.icons_container,
+                        map_thumbnails: &mut self.map_thumbnail_container,
 
                         server_info: &Default::default(),
                         render_options: client_ui::main_menu::user_data::RenderOptions {
@@ -1,4 +1,4 @@
-use std::{path::Path, sync::Arc, time::Duration};
+use std::{net::SocketAddr, path::Path, sync::Arc, time::Duration};
 
 use api::IO;
 use api_ui_game::render::{
@@ -24,8 +24,9 @@ use client_ui::{
     main_menu::{
         constants::{MENU_SETTINGS_NAME, MENU_UI_PAGE_QUERY},
         demo_list::{DemoList, DemoListEntry},
+        game_files::GameFilesState,
         monitors::{UiMonitor, UiMonitorVideoMode, UiMonitors},
-        page::MainMenuUi,
+        page::{MAP_THUMBNAIL_CONTAINER_PATH, MainMenuUi},
         profiles_interface::ProfilesInterface,
         settings::constants::{SETTINGS_SUB_UI_PAGE_QUERY, SETTINGS_UI_PAGE_QUERY},
         theme_container::ThemeContainer,
@@ -61,9 +62,21 @@ struct MenuImpl {}
 
 impl MainMenuInterface for MenuImpl {
     fn refresh(&mut self) {}
+    fn refresh_server_info(&mut self, _addr: SocketAddr, _cur_time: &Duration) {}
+    fn can_refresh_server_info(&self, _cur_time: &Duration) -> bool {
+        false
+    }
 
     fn refresh_demo_list(&mut self, _path: &Path) {}
     fn refresh_demo_info(&mut self, _file: Option<&Path>) {}
+
+    fn refresh_local_stats(&mut self) {}
+
+    fn verify_game_files(&mut self) {}
+    fn repair_game_files(&mut self, _download: bool) {}
+    fn game_files_state(&self) -> GameFilesState {
+        GameFilesState::Unchecked
+    }
 }
 
 pub struct MainMenu {
@@ -93,6 +106,7 @@ pub struct MainMenu {
     ctf_container: CtfContainer,
     theme_container: ThemeContainer,
     icons_container: ThumbnailContainer,
+    map_thumbnail_container: ThumbnailContainer,
 
     map_render: MapGraphics,
     tile_layer_visuals: Option<TileLayerVisuals>,
@@ -263,6 +277,10 @@ impl MainMenu {
                 DEFAULT_THUMBNAIL_CONTAINER_PATH,
                 "community-icons",
             ),
+            map_thumbnail_container: create_thumbnail_container(
+                MAP_THUMBNAIL_CONTAINER_PATH,
+                "map-thumbnails",
+            ),
 
             map_render: MapGraphics::new(&graphics.backend_handle),
             tile_layer_visuals: None,
@@ -290,7 +308,9 @@ impl MainMenu {
                     ddnet_info: &Default::default(),
                     demos: &self.demos,
                     demo_info: &None,
+                    local_stats: &Default::default(),
                     icons: &mut self.icons_container,
+                    map_thumbnails: &mut self.map_thumbnail_container,
 
                     server_info: &Default::default(),
                     render_options: client_ui::main_menu::user_data::RenderOptions {
@@ -1,4 +1,7 @@
-use client_ui::motd::user_data::UserData;
+use client_ui::{
+    markup::parse_markup,
+    motd::user_data::{MOTD_MAX_CHARS, UserData},
+};
 use ui_base::types::{UiRenderPipe, UiState};
 use ui_generic::traits::UiPageInterface;
 
@@ -26,8 +29,12 @@ impl MotdPage {
             &mut UiRenderPipe {
                 cur_time: pipe.cur_time,
                 user_data: &mut UserData {
-                    msg: "This is an example motd\n\
-                        `commonmark attributes` should _just_ __work__.",
+                    lines: &parse_markup(
+                        "[center][b]This is an example motd[/b][/center]\n\
+                        [color=yellow]markup[/color] should [b]just[/b] \
+                        [url=https://ddnet.org]work[/url].",
+                        MOTD_MAX_CHARS,
+                    ),
                 },
             },
             ui_state,
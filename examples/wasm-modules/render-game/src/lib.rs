@@ -20,6 +20,7 @@ fn mod_render_game_new(
         map_file,
         config,
         props,
+        None,
     )?;
     Ok(Box::new(state))
 }
@@ -101,6 +101,8 @@ impl Scoreboard {
                     }),
                     browser_score: PoolNetworkString::from_without_pool("999".try_into().unwrap()),
                     browser_eye: TeeEye::Normal,
+                    is_bot: false,
+                    server_rank: None,
                     account_name: Some(PoolNetworkString::from_without_pool(
                         "testname".try_into().unwrap(),
                     )),
@@ -167,6 +169,8 @@ impl Scoreboard {
                     }),
                     browser_score: PoolNetworkString::from_without_pool("999".try_into().unwrap()),
                     browser_eye: TeeEye::Normal,
+                    is_bot: false,
+                    server_rank: None,
                     account_name: Some(PoolNetworkString::from_without_pool(
                         "testname".try_into().unwrap(),
                     )),
@@ -229,6 +233,8 @@ impl Scoreboard {
                     }),
                     browser_score: PoolNetworkString::from_without_pool("999".try_into().unwrap()),
                     browser_eye: TeeEye::Angry,
+                    is_bot: false,
+                    server_rank: None,
                     account_name: Some(PoolNetworkString::from_without_pool(
                         "testname".try_into().unwrap(),
                     )),
@@ -279,6 +285,7 @@ impl Scoreboard {
                     flags_container: &mut self.flags_container,
 
                     own_character_id: character_infos.front().unwrap().0,
+                    round_stats: None,
                 },
             ),
             ui_state,
@@ -138,6 +138,12 @@ impl GameStateInterface for ApiState {
     #[guest_func_call_from_host_auto(option)]
     fn network_stats(&mut self, stats: PoolFxLinkedHashMap<PlayerId, PlayerNetworkStats>) {}
 
+    #[guest_func_call_from_host_auto(option)]
+    fn player_handover(&self, player_id: &PlayerId) -> Option<Vec<u8>> {}
+
+    #[guest_func_call_from_host_auto(option)]
+    fn apply_player_handover(&mut self, player_id: &PlayerId, handover: Vec<u8>) {}
+
     #[guest_func_call_from_host_auto(option)]
     fn settings(&self) -> GameStateSettings {}
 
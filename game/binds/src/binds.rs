@@ -40,6 +40,8 @@ pub enum BindActionsLocalPlayer {
     PhasedFreeCam,
     ToggleDummyCopyMoves,
     ToggleDummyHammerFly,
+    /// Shows or hides the ghosts of race runs
+    ToggleGhost,
     VoteYes,
     VoteNo,
     ZoomOut,
@@ -47,7 +49,7 @@ pub enum BindActionsLocalPlayer {
     ZoomReset,
 }
 
-const LOCAL_PLAYER_ACTIONS: [(&str, BindActionsLocalPlayer); 46] = [
+const LOCAL_PLAYER_ACTIONS: [(&str, BindActionsLocalPlayer); 47] = [
     (
         "+left",
         BindActionsLocalPlayer::Character(BindActionsCharacter::MoveLeft),
@@ -193,6 +195,7 @@ const LOCAL_PLAYER_ACTIONS: [(&str, BindActionsLocalPlayer); 46] = [
         "dummy_hammer_fly",
         BindActionsLocalPlayer::ToggleDummyHammerFly,
     ),
+    ("toggle_ghost", BindActionsLocalPlayer::ToggleGhost),
     ("zoom-", BindActionsLocalPlayer::ZoomOut),
     ("zoom+", BindActionsLocalPlayer::ZoomIn),
     ("zoom", BindActionsLocalPlayer::ZoomReset),
@@ -478,6 +481,48 @@ pub fn str_list_to_binds_lossy(
         .collect()
 }
 
+/// The keys of the first bind that triggers the action,
+/// e.g. `space` for `+jump`, `None` if the action is not bound.
+///
+/// The binds are in the format of the config, e.g. `bind space +jump`.
+pub fn bound_keys_of_action<'a>(binds: &'a [String], action: &str) -> Option<&'a str> {
+    binds.iter().find_map(|bind| {
+        let (keys, actions) = bind
+            .trim()
+            .strip_prefix("bind ")?
+            .trim_start()
+            .split_once(char::is_whitespace)?;
+        actions
+            .split(';')
+            .any(|bind_action| bind_action.trim() == action)
+            .then_some(keys)
+    })
+}
+
+/// Replaces every `{bind:<action>}` in the text with the keys
+/// bound to the action, e.g. `{bind:+jump}` with `[space]`.
+///
+/// See [`bound_keys_of_action`].
+pub fn substitute_bind_keys(text: &str, binds: &[String]) -> String {
+    const PREFIX: &str = "{bind:";
+    let mut res = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(PREFIX) {
+        let Some(len) = rest[start + PREFIX.len()..].find('}') else {
+            break;
+        };
+        res.push_str(&rest[..start]);
+        let action = rest[start + PREFIX.len()..start + PREFIX.len() + len].trim();
+        match bound_keys_of_action(binds, action) {
+            Some(keys) => res.push_str(&format!("[{keys}]")),
+            None => res.push_str(&format!("[{action}: unbound]")),
+        }
+        rest = &rest[start + PREFIX.len() + len + 1..];
+    }
+    res.push_str(rest);
+    res
+}
+
 #[cfg(test)]
 mod test {
     use command_parser::parser::{Command, Syn};
@@ -485,7 +530,8 @@ mod test {
 
     use crate::binds::{
         BindAction, BindActionsCharacter, BindActionsLocalPlayer, bind_to_str,
-        gen_local_player_action_hash_map, gen_local_player_action_hash_map_rev, syn_to_bind,
+        gen_local_player_action_hash_map, gen_local_player_action_hash_map_rev,
+        substitute_bind_keys, syn_to_bind,
     };
 
     #[test]
@@ -569,4 +615,34 @@ mod test {
         );
         assert!(res.is_ok(), "{:?}", res);
     }
+
+    #[test]
+    fn bind_key_substitution() {
+        let binds = [
+            "bind space +jump".to_string(),
+            "bind mouse_left +fire".to_string(),
+            "bind control_left+t say hi;+hook".to_string(),
+            "bind w +jump".to_string(),
+        ];
+        let sub = |text: &str| substitute_bind_keys(text, &binds);
+
+        // the first bind wins
+        assert_eq!(
+            sub("press {bind:+jump} twice to double jump"),
+            "press [space] twice to double jump"
+        );
+        assert_eq!(
+            sub("{bind:+fire}{bind: +hook }"),
+            "[mouse_left][control_left+t]"
+        );
+        assert_eq!(sub("{bind:+dummy_fire}"), "[+dummy_fire: unbound]");
+        // no bind syntax is kept as is
+        assert_eq!(sub("no binds {jump} {bind:"), "no binds {jump} {bind:");
+        assert_eq!(sub("{bind:+jump} {bind:+fire"), "[space] {bind:+fire");
+        assert_eq!(sub("ünïcode {bind:+jump}!"), "ünïcode [space]!");
+        assert_eq!(
+            substitute_bind_keys("{bind:+jump}", &[]),
+            "[+jump: unbound]"
+        );
+    }
 }
@@ -3,7 +3,7 @@ use std::collections::VecDeque;
 use base::steady_clock::SteadyClock;
 use client_containers::skins::SkinContainer;
 use client_render_base::render::tee::RenderTee;
-use client_types::console::ConsoleEntry;
+use client_types::{console::ConsoleEntry, log::LogView};
 use client_ui::console::{page::ConsoleUi, user_data::UserData};
 use command_parser::parser::ParserCache;
 use egui::Color32;
@@ -27,6 +27,8 @@ pub struct ConsoleRenderPipe<'a> {
         &'a dyn Fn(&str, &str, &mut egui::Ui, &mut UiState, &mut SkinContainer, &RenderTee),
     pub skin_container: &'a mut SkinContainer,
     pub render_tee: &'a RenderTee,
+    /// The captured logs, shown in a separate tab of the console.
+    pub logs: Option<&'a mut LogView>,
 }
 
 pub trait ConsoleEvents<E> {
@@ -102,6 +104,7 @@ impl<E, T> ConsoleRender<E, T> {
             render_custom_matches: pipe.render_custom_matches,
             skin_container: pipe.skin_container,
             render_tee: pipe.render_tee,
+            logs: pipe.logs.as_deref_mut(),
         };
         let mut ui_pipe = UiRenderPipe::new(pipe.time.now(), &mut user_data);
 
@@ -29,7 +29,10 @@ use game_config::config::ConfigGame;
 use hiarc::{Hiarc, hiarc_safer_rc_refcell};
 use ui_base::ui::UiCreator;
 
-use super::console::ConsoleRender;
+use super::{
+    console::ConsoleRender,
+    watch::{ConsoleWatches, MAX_WATCHES, config_var_value, matches_pattern},
+};
 
 #[derive(Debug, Hiarc)]
 pub enum LocalConsoleEvent {
@@ -74,6 +77,8 @@ pub enum LocalConsoleEvent {
         name: String,
     },
     LocalPlayerAction(BindActionsLocalPlayer),
+    /// Export the recorded input timings as csv
+    ExportInputTimings,
     Quit,
 }
 
@@ -109,6 +114,7 @@ pub struct LocalConsoleBuilder {
     pub entries: Vec<ConsoleEntry>,
     pub console_events: LocalConsoleEvents,
     pub parser_cache: Rc<ParserCache>,
+    pub watches: ConsoleWatches,
 }
 
 impl Default for LocalConsoleBuilder {
@@ -155,12 +161,15 @@ impl Default for LocalConsoleBuilder {
             Default::default(),
         );
         let parser_cache = Rc::new(ParserCache::default());
+        let watches = ConsoleWatches::new(MAX_WATCHES);
         Self::register_commands(console_events.clone(), &mut entries, parser_cache.clone());
+        Self::register_watch_commands(&mut entries, watches.clone());
 
         Self {
             console_events,
             entries,
             parser_cache,
+            watches,
         }
     }
 }
@@ -935,6 +944,20 @@ impl LocalConsoleBuilder {
             allows_partial_cmds: false,
         }));
 
+        let console_events_cmd = console_events.clone();
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "input_timings_export".into(),
+            usage: "input_timings_export".into(),
+            description: "Saves the recorded input timings (see dbg.input_timings) as csv file."
+                .into(),
+            cmd: Rc::new(move |_, _, _, _| {
+                console_events_cmd.push(LocalConsoleEvent::ExportInputTimings);
+                Ok("".to_string())
+            }),
+            args: Default::default(),
+            allows_partial_cmds: false,
+        }));
+
         list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
             name: "quit".into(),
             usage: "quit the client".into(),
@@ -948,6 +971,139 @@ impl LocalConsoleBuilder {
         }));
     }
 
+    /// Registers `find` & the watch commands,
+    /// `find` only knows the entries that were registered before.
+    fn register_watch_commands(list: &mut Vec<ConsoleEntry>, watches: ConsoleWatches) {
+        // name & the description of commands
+        let find_entries: Vec<(String, Option<String>)> = list
+            .iter()
+            .map(|entry| match entry {
+                ConsoleEntry::Var(var) => (var.full_name.clone(), None),
+                ConsoleEntry::Cmd(cmd) => (cmd.name.clone(), Some(cmd.description.clone())),
+            })
+            .collect();
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "find".into(),
+            usage: "find <pattern>".into(),
+            description: "Lists all config variables & commands that contain the text, \
+                or match the pattern with the wildcards * and ?."
+                .into(),
+            cmd: Rc::new(move |config_engine, config_game, _, path| {
+                let Syn::Text(pattern) = &path[0].0 else {
+                    panic!("Command parser returned a non requested command arg");
+                };
+                let mut default_engine = ConfigEngine::default();
+                let mut default_game = ConfigGame::default();
+                let res: Vec<_> = find_entries
+                    .iter()
+                    .filter(|(name, _)| matches_pattern(pattern, name))
+                    .map(|(name, description)| match description {
+                        Some(description) => format!("{name}: {description}"),
+                        None => match (
+                            config_var_value(config_engine, config_game, name),
+                            config_var_value(&mut default_engine, &mut default_game, name),
+                        ) {
+                            (Some(val), Some(default)) => {
+                                format!("{name} = {val} (default: {default})")
+                            }
+                            // e.g. the entries of arrays
+                            _ => name.clone(),
+                        },
+                    })
+                    .collect();
+                if res.is_empty() {
+                    Ok(format!("Nothing matches {pattern}"))
+                } else {
+                    Ok(res.join("\n"))
+                }
+            }),
+            args: vec![CommandArg {
+                ty: CommandArgType::Text,
+                user_ty: None,
+            }],
+            allows_partial_cmds: false,
+        }));
+
+        let watches_cmd = watches.clone();
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "watch".into(),
+            usage: "watch <var>".into(),
+            description: "Shows the live value of a config variable in an overlay.".into(),
+            cmd: Rc::new(move |config_engine, config_game, _, path| {
+                let path = syn_vec_to_config_val(path).unwrap_or_default();
+                anyhow::ensure!(
+                    config_var_value(config_engine, config_game, &path).is_some(),
+                    "No variable with that name found"
+                );
+                if watches_cmd.watch(path.clone())? {
+                    Ok(format!("Watching {path}"))
+                } else {
+                    Ok(format!("{path} is already watched"))
+                }
+            }),
+            args: vec![CommandArg {
+                ty: CommandArgType::CommandIdent,
+                user_ty: None,
+            }],
+            allows_partial_cmds: false,
+        }));
+        let watches_cmd = watches.clone();
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "unwatch".into(),
+            usage: "unwatch <var>".into(),
+            description: "Removes a config variable from the watch overlay.".into(),
+            cmd: Rc::new(move |_, _, _, path| {
+                let path = syn_vec_to_config_val(path).unwrap_or_default();
+                if watches_cmd.unwatch(&path) {
+                    Ok(format!("Stopped watching {path}"))
+                } else {
+                    Err(anyhow!("{path} is not watched"))
+                }
+            }),
+            args: vec![CommandArg {
+                ty: CommandArgType::CommandIdent,
+                user_ty: None,
+            }],
+            allows_partial_cmds: false,
+        }));
+        let watches_cmd = watches.clone();
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "unwatch_all".into(),
+            usage: "unwatch_all".into(),
+            description: "Removes all config variables from the watch overlay.".into(),
+            cmd: Rc::new(move |_, _, _, _| {
+                Ok(format!(
+                    "Stopped watching {} variables",
+                    watches_cmd.clear()
+                ))
+            }),
+            args: vec![],
+            allows_partial_cmds: false,
+        }));
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "watches".into(),
+            usage: "watches".into(),
+            description: "Lists the watched config variables & their values.".into(),
+            cmd: Rc::new(move |config_engine, config_game, _, _| {
+                let vars = watches.list();
+                if vars.is_empty() {
+                    return Ok("No variables are watched".into());
+                }
+                Ok(vars
+                    .into_iter()
+                    .map(|name| {
+                        let val =
+                            config_var_value(config_engine, config_game, &name).unwrap_or_default();
+                        format!("{name} = {val}")
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"))
+            }),
+            args: vec![],
+            allows_partial_cmds: false,
+        }));
+    }
+
     pub fn build(self, creator: &UiCreator) -> LocalConsole {
         ConsoleRender::new(
             creator,
@@ -1,3 +1,4 @@
 pub mod console;
 pub mod local_console;
 pub mod remote_console;
+pub mod watch;
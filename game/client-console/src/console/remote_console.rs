@@ -2,7 +2,7 @@ use std::{collections::HashMap, rc::Rc};
 
 use base::network_string::NetworkString;
 use client_types::console::{ConsoleEntry, ConsoleEntryCmd};
-use command_parser::parser::{CommandArg, CommandArgType, format_args};
+use command_parser::parser::{CommandArg, CommandArgType, Syn, format_args};
 use egui::Color32;
 use game_interface::rcon_entries::RconEntry;
 use hiarc::{Hiarc, hiarc_safer_rc_refcell};
@@ -18,6 +18,8 @@ pub enum RemoteConsoleEvent {
         /// The args of the command
         args: String,
     },
+    /// Login to the remote console with a password.
+    Login { password: String },
 }
 
 #[hiarc_safer_rc_refcell]
@@ -76,12 +78,37 @@ impl RemoteConsole {
         usage
     }
 
+    /// The login is always available, even before the
+    /// server sent any rcon entries.
+    fn login_entry(events: RemoteConsoleEvents) -> ConsoleEntry {
+        ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "login".into(),
+            usage: "login <password>".into(),
+            description: "Login to the remote console with the admin or moderator password.".into(),
+            cmd: Rc::new(move |_, _, _, path| {
+                let Syn::Text(password) = &path[0].0 else {
+                    panic!("Command parser returned a non requested command arg");
+                };
+                events.push(RemoteConsoleEvent::Login {
+                    password: password.clone(),
+                });
+                Ok("Logging in...".into())
+            }),
+            args: vec![CommandArg {
+                ty: CommandArgType::Text,
+                user_ty: None,
+            }],
+            allows_partial_cmds: false,
+        })
+    }
+
     pub fn fill_entries(
         &mut self,
         cmds: HashMap<NetworkString<65536>, RconEntry>,
         vars: HashMap<NetworkString<65536>, RconEntry>,
     ) {
         self.entries.clear();
+        self.entries.push(Self::login_entry(self.user.clone()));
         for (name, cmd) in cmds.into_iter().chain(vars.into_iter()) {
             let cmds = self.user.clone();
             self.entries.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
@@ -112,7 +139,7 @@ pub struct RemoteConsoleBuilder {}
 impl RemoteConsoleBuilder {
     pub fn build(creator: &UiCreator) -> RemoteConsole {
         let console_events: RemoteConsoleEvents = Default::default();
-        let entries: Vec<ConsoleEntry> = Vec::new();
+        let entries: Vec<ConsoleEntry> = vec![RemoteConsole::login_entry(console_events.clone())];
         ConsoleRender::new(
             creator,
             entries,
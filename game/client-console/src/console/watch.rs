@@ -0,0 +1,172 @@
+use config::{
+    config::ConfigEngine,
+    traits::{ConfigFromStrOperation, ConfigInterface},
+};
+use game_config::config::ConfigGame;
+use hiarc::{Hiarc, hiarc_safer_rc_refcell};
+
+/// How many config variables can be watched at once.
+pub const MAX_WATCHES: usize = 8;
+
+/// Whether the name matches the pattern of the `find` command.
+///
+/// Patterns with wildcards (`*` for any amount of chars, `?` for a single char)
+/// must match the whole name, other patterns only a part of it.
+/// The case is ignored.
+pub fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let name = name.to_lowercase();
+    if !pattern.contains(['*', '?']) {
+        return name.contains(&pattern);
+    }
+
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // the last `*` & the position in the name it currently covers
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => {
+                let Some((star_p, star_n)) = star else {
+                    return false;
+                };
+                // let the `*` cover one more char
+                star = Some((star_p, star_n + 1));
+                p = star_p + 1;
+                n = star_n + 1;
+            }
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// The current value of a config variable, `None` if no such variable exists.
+pub fn config_var_value(
+    config_engine: &mut ConfigEngine,
+    config_game: &mut ConfigGame,
+    name: &str,
+) -> Option<String> {
+    config_engine
+        .try_set_from_str(
+            name.to_string(),
+            None,
+            None,
+            None,
+            ConfigFromStrOperation::Set,
+        )
+        .or_else(|_| {
+            config_game.try_set_from_str(
+                name.to_string(),
+                None,
+                None,
+                None,
+                ConfigFromStrOperation::Set,
+            )
+        })
+        .ok()
+}
+
+/// The config variables that are pinned to the watch overlay.
+///
+/// Watches only last for the current session.
+#[hiarc_safer_rc_refcell]
+#[derive(Debug, Hiarc)]
+pub struct ConsoleWatches {
+    vars: Vec<String>,
+    max: usize,
+}
+
+#[hiarc_safer_rc_refcell]
+impl ConsoleWatches {
+    pub fn new(max: usize) -> Self {
+        Self {
+            vars: Default::default(),
+            max,
+        }
+    }
+
+    /// Returns `false` if the variable was already watched.
+    pub fn watch(&mut self, name: String) -> anyhow::Result<bool> {
+        if self.vars.contains(&name) {
+            return Ok(false);
+        }
+        anyhow::ensure!(
+            self.vars.len() < self.max,
+            "At most {} variables can be watched at once.",
+            self.max
+        );
+        self.vars.push(name);
+        Ok(true)
+    }
+
+    /// Returns `false` if the variable was not watched.
+    pub fn unwatch(&mut self, name: &str) -> bool {
+        let len = self.vars.len();
+        self.vars.retain(|var| var != name);
+        len != self.vars.len()
+    }
+
+    /// Removes all watches & returns how many there were.
+    pub fn clear(&mut self) -> usize {
+        std::mem::take(&mut self.vars).len()
+    }
+
+    /// The watched variables in the order they were added.
+    pub fn list(&self) -> Vec<String> {
+        self.vars.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ConsoleWatches, matches_pattern};
+
+    #[test]
+    fn patterns() {
+        // substrings without wildcards
+        assert!(matches_pattern("vol", "snd.global_volume"));
+        assert!(matches_pattern("GFX", "gfx.vsync"));
+        assert!(!matches_pattern("volume", "snd.vol"));
+        assert!(matches_pattern("", "cl.show_fps"));
+
+        // wildcards must match the whole name
+        assert!(matches_pattern("gfx.*", "gfx.vsync"));
+        assert!(!matches_pattern("gfx.*", "dbg.gfx"));
+        assert!(matches_pattern("*.show_*", "cl.show_fps"));
+        assert!(matches_pattern("cl.*fps", "cl.show_fps"));
+        assert!(!matches_pattern("cl.*fps", "cl.show_fps_graph"));
+        assert!(matches_pattern("snd.?", "snd.a"));
+        assert!(!matches_pattern("snd.?", "snd.ab"));
+        assert!(matches_pattern("*a*a*", "banana"));
+        assert!(!matches_pattern("*a*a*a*a*", "banana"));
+        assert!(matches_pattern("**", ""));
+    }
+
+    #[test]
+    fn watch_list() {
+        let watches = ConsoleWatches::new(2);
+        assert!(watches.watch("cl.show_fps".into()).unwrap());
+        // watching twice keeps a single entry
+        assert!(!watches.watch("cl.show_fps".into()).unwrap());
+        assert!(watches.watch("snd.global_volume".into()).unwrap());
+        assert!(watches.watch("gfx.vsync".into()).is_err());
+        assert_eq!(watches.list(), ["cl.show_fps", "snd.global_volume"]);
+
+        assert!(watches.unwatch("cl.show_fps"));
+        assert!(!watches.unwatch("cl.show_fps"));
+        assert!(watches.watch("gfx.vsync".into()).unwrap());
+        assert_eq!(watches.list(), ["snd.global_volume", "gfx.vsync"]);
+
+        assert_eq!(watches.clear(), 2);
+        assert!(watches.list().is_empty());
+    }
+}
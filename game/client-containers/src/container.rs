@@ -18,7 +18,10 @@ use tracing::instrument;
 
 use anyhow::anyhow;
 use base::hash::{Hash, fmt_hash};
-use base_io_traits::{fs_traits::FileSystemInterface, http_traits::HttpClientInterface};
+use base_io_traits::{
+    fs_traits::{FileSystemInterface, FileSystemWatcherItemInterface},
+    http_traits::HttpClientInterface,
+};
 
 use base_io::{io::Io, path_to_url::relative_path_to_url, runtime::IoRuntimeTask};
 use either::Either;
@@ -47,6 +50,70 @@ use url::Url;
 const CONTAINER_MAX_DOWNLOAD_TASKS: usize = 2;
 const CONTAINER_MAX_TASKS: usize = 16;
 
+/// Parses a tar ball on the thread pool, if any.
+///
+/// The entries are parsed in place from the read file,
+/// so they are the only copy of the data.
+async fn read_tar(
+    thread_pool: Option<&Arc<rayon::ThreadPool>>,
+    file: impl AsRef<[u8]> + Send + 'static,
+) -> anyhow::Result<HashMap<PathBuf, Vec<u8>>> {
+    match thread_pool {
+        Some(thread_pool) => {
+            let (sender, receiver) = tokio::sync::oneshot::channel();
+            thread_pool.spawn(move || {
+                let _ = sender.send(read_tar_files(file.as_ref().into()));
+            });
+            receiver.await?
+        }
+        None => read_tar_files(file.as_ref().into()),
+    }
+}
+
+/// Loads an item from the local files, without any hash.
+///
+/// A single file (`name.png`) is preferred, except for the default item.
+/// A tar ball (`name.tar`) is preferred over a directory with the same name.
+async fn load_local_item(
+    fs: &dyn FileSystemInterface,
+    thread_pool: Option<&Arc<rayon::ThreadPool>>,
+    base_path: &Path,
+    name: &str,
+) -> Option<ContainerLoadedItem> {
+    // png (or .ogg etc., which currently are not supported)
+    let single_file = fs
+        .read_file(&base_path.join(format!("{name}.png")))
+        .await
+        .ok()
+        .map(ContainerLoadedItem::SingleFile);
+    if single_file.is_some() && name != "default" {
+        return single_file;
+    }
+
+    if let Ok(file) = fs.read_file(&base_path.join(format!("{name}.tar"))).await
+        && let Ok(tar_files) = read_tar(thread_pool, file).await
+    {
+        Some(ContainerLoadedItem::Directory(ContainerLoadedItemDir::new(
+            tar_files,
+        )))
+    } else if let Ok(dir_files) = fs.files_in_dir_recursive(&base_path.join(name)).await {
+        Some(ContainerLoadedItem::Directory(ContainerLoadedItemDir::new(
+            dir_files,
+        )))
+    } else {
+        single_file
+    }
+}
+
+/// Notifies about changes of the local items, e.g. a replaced tar ball.
+struct ContainerFsWatcher(Box<dyn FileSystemWatcherItemInterface>);
+
+impl std::fmt::Debug for ContainerFsWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ContainerFsWatcher").finish()
+    }
+}
+
 #[derive(Debug, Hiarc)]
 pub struct ContainerMaxItems<'a> {
     /// How many items at most are allowed
@@ -64,6 +131,83 @@ pub struct ContainerItemLoadData {
     pub data: GraphicsBackendMemory,
 }
 
+/// The estimated memory of a loaded item.
+#[derive(Debug, Hiarc, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ContainerItemMemory {
+    /// Bytes of the textures, which live on the GPU.
+    pub gpu_bytes: usize,
+    /// Bytes of the sound buffers, which live in main memory.
+    pub cpu_bytes: usize,
+}
+
+impl ContainerItemMemory {
+    pub fn total(&self) -> usize {
+        self.gpu_bytes + self.cpu_bytes
+    }
+}
+
+impl std::ops::Add for ContainerItemMemory {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            gpu_bytes: self.gpu_bytes + rhs.gpu_bytes,
+            cpu_bytes: self.cpu_bytes + rhs.cpu_bytes,
+        }
+    }
+}
+
+impl std::iter::Sum for ContainerItemMemory {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |a, b| a + b)
+    }
+}
+
+/// Estimates the memory a loaded item, or a part of it,
+/// uses after it was uploaded.
+pub trait ContainerItemMemoryUsage {
+    fn memory_usage(&self) -> ContainerItemMemory;
+}
+
+impl ContainerItemMemoryUsage for ContainerItemLoadData {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        // all textures are uploaded as rgba
+        ContainerItemMemory {
+            gpu_bytes: self.width as usize * self.height as usize * self.depth as usize * 4,
+            cpu_bytes: 0,
+        }
+    }
+}
+
+impl ContainerItemMemoryUsage for SoundBackendMemory {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        ContainerItemMemory {
+            gpu_bytes: 0,
+            cpu_bytes: self.as_slice().len(),
+        }
+    }
+}
+
+impl<T: ContainerItemMemoryUsage> ContainerItemMemoryUsage for [T] {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        self.iter().map(|item| item.memory_usage()).sum()
+    }
+}
+
+impl<T: ContainerItemMemoryUsage> ContainerItemMemoryUsage for Option<T> {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        self.iter().map(|item| item.memory_usage()).sum()
+    }
+}
+
+impl<K, T: ContainerItemMemoryUsage, S> ContainerItemMemoryUsage
+    for std::collections::HashMap<K, T, S>
+{
+    fn memory_usage(&self) -> ContainerItemMemory {
+        self.values().map(|item| item.memory_usage()).sum()
+    }
+}
+
 #[derive(Debug, Hiarc, Clone)]
 pub struct ContainerLoadedItemDir {
     /// key is the relative path
@@ -93,6 +237,32 @@ pub enum ContainerLoadedItem {
 struct ContainerItem<A> {
     item: A,
     used_last_in: Duration,
+    memory: ContainerItemMemory,
+}
+
+/// The loaded items with their memory, the biggest first.
+fn items_by_memory<A>(
+    items: &LinkedHashMap<ContainerKey, ContainerItem<A>>,
+) -> Vec<(&ContainerKey, ContainerItemMemory)> {
+    let mut res: Vec<_> = items.iter().map(|(key, item)| (key, item.memory)).collect();
+    res.sort_by(|(key_a, a), (key_b, b)| {
+        b.total()
+            .cmp(&a.total())
+            .then_with(|| key_a.name.as_str().cmp(key_b.name.as_str()))
+    });
+    res
+}
+
+/// Removes a loaded item, the default item is always kept.
+fn evict_item<A>(
+    items: &mut LinkedHashMap<ContainerKey, ContainerItem<A>>,
+    default_key: &ContainerKey,
+    name: &ContainerKey,
+) -> Option<ContainerItemMemory> {
+    if name == default_key {
+        return None;
+    }
+    items.remove(name).map(|item| item.memory)
 }
 
 pub type ContainerKey = ResourceKey;
@@ -162,7 +332,8 @@ struct DefaultItem<L> {
 ///   must ensure to load proper default values for other resources of an item (sounds etc.)
 /// - Directory: A directory with many different resources was loaded. Missing resources must be filled
 ///   with values of the default item. A directory might be archieved in a .tar ball, which is automatically
-///   unpacked and processed.
+///   unpacked and processed. If both exist, the .tar ball is preferred.
+///   Changes to the local files are watched and cause the items to be reloaded.
 #[derive(Debug, Hiarc)]
 pub struct Container<A, L> {
     items: LinkedHashMap<ContainerKey, ContainerItem<A>>,
@@ -227,14 +398,21 @@ pub struct Container<A, L> {
         Option<IoRuntimeTask<anyhow::Result<ResourceIndex>>>,
     >,
 
+    /// Watches the local items, to reload them on changes.
+    #[hiarc_skip_unsafe]
+    fs_change_watcher: ContainerFsWatcher,
+
     /// last time the container was updated by [Self::update]
     last_update_time: Option<Duration>,
     last_update_interval_time: Option<Duration>,
+
+    /// Items that use more bytes log a warning when they are loaded.
+    item_memory_warn_threshold: Option<usize>,
 }
 
 pub trait ContainerLoad<A>
 where
-    Self: Sized,
+    Self: Sized + ContainerItemMemoryUsage,
 {
     fn load(
         item_name: &str,
@@ -282,6 +460,7 @@ where
         options: ContainerLoadOptions,
     ) -> Self {
         let items = LinkedHashMap::new();
+        let fs_change_watcher = ContainerFsWatcher(io.fs.watch_for_change(base_path, None));
         Self {
             items,
             http_download_tasks: Arc::new(Semaphore::const_new(CONTAINER_MAX_DOWNLOAD_TASKS)),
@@ -355,8 +534,29 @@ where
             cached_http_download_meta: Default::default(),
             http_download_meta_task: None,
 
+            fs_change_watcher,
+
             last_update_time: None,
             last_update_interval_time: None,
+
+            item_memory_warn_threshold: None,
+        }
+    }
+
+    /// Warns about items that use more memory than the threshold,
+    /// see [`Self::set_item_memory_warn_threshold`].
+    fn check_item_memory(&self, name: &ContainerKey, memory: &ContainerItemMemory) {
+        if self
+            .item_memory_warn_threshold
+            .is_some_and(|threshold| memory.total() > threshold)
+        {
+            log::warn!(
+                target: &self.container_name,
+                "Item \"{}\" uses {:.2} MiB of GPU & {:.2} MiB of CPU memory",
+                name.name.as_str(),
+                memory.gpu_bytes as f64 / 1024.0 / 1024.0,
+                memory.cpu_bytes as f64 / 1024.0 / 1024.0
+            );
         }
     }
 
@@ -376,11 +576,14 @@ where
                 })
                 .unwrap();
             self.default_loaded_item = Arc::new(default_loaded_item);
+            let memory = default_item.memory_usage();
+            self.check_item_memory(&self.default_key, &memory);
             self.items.insert(
                 (*self.default_key).clone(),
                 ContainerItem {
                     item: default_item.convert(&self.texture_handle, &self.sound_object_handle),
                     used_last_in: Duration::ZERO,
+                    memory,
                 },
             );
         }
@@ -412,6 +615,13 @@ where
 
             self.check_default_loaded();
 
+            // local items changed, e.g. a tar ball was replaced,
+            // reload them the next time they are used
+            if self.fs_change_watcher.0.has_file_change() {
+                self.clear_except_default();
+                self.resource_dir_index = Either::Right(None);
+            }
+
             // make sure these entries are always kept loaded
             for force_used_item in force_used_items {
                 if let Some(item) = self.items.to_back(force_used_item) {
@@ -559,6 +769,7 @@ where
         container_name: String,
         fs: Arc<dyn FileSystemInterface>,
         http: Arc<dyn HttpClientInterface>,
+        tar_thread_pool: Option<Arc<rayon::ThreadPool>>,
         http_download_tasks: Arc<Semaphore>,
         base_path: PathBuf,
         downloaded_path: PathBuf,
@@ -568,6 +779,7 @@ where
         allows_single_audio_or_txt_files: bool,
     ) -> anyhow::Result<ContainerLoadedItem> {
         let allow_hq_assets = false;
+        let tar_thread_pool = tar_thread_pool.as_ref();
 
         let save_to_disk = |name: &str, file: &[u8]| {
             let name = name.to_string();
@@ -597,35 +809,35 @@ where
         // else it will be ignored
 
         if let Some(hash) = key.hash {
-            // try to load dir with that name
+            // try to load tar with that name
             let mut files = None;
 
-            if let Ok(dir_files) = fs
-                .files_in_dir_recursive(&download_base_path.join(format!(
-                    "{}_{}",
+            if let Ok(file) = fs
+                .read_file(&download_base_path.join(format!(
+                    "{}_{}.tar",
                     key.name.as_str(),
                     fmt_hash(&hash)
                 )))
                 .await
+                && let Ok(tar_files) = read_tar(tar_thread_pool, file).await
             {
                 files = Some(ContainerLoadedItem::Directory(ContainerLoadedItemDir::new(
-                    dir_files,
+                    tar_files,
                 )));
             }
 
-            // else try to load tar with that name
+            // else try to load dir with that name
             if files.is_none()
-                && let Ok(file) = fs
-                    .read_file(&download_base_path.join(format!(
-                        "{}_{}.tar",
+                && let Ok(dir_files) = fs
+                    .files_in_dir_recursive(&download_base_path.join(format!(
+                        "{}_{}",
                         key.name.as_str(),
                         fmt_hash(&hash)
                     )))
                     .await
-                && let Ok(tar_files) = read_tar_files(file.into())
             {
                 files = Some(ContainerLoadedItem::Directory(ContainerLoadedItemDir::new(
-                    tar_files,
+                    dir_files,
                 )));
             }
 
@@ -652,7 +864,7 @@ where
                 }) {
                     let _g = http_download_tasks.acquire().await?;
                     if let Ok(file) = http.download_binary(game_server_http, &hash).await
-                        && let Ok(tar_files) = read_tar_files(file.as_ref().into())
+                        && let Ok(tar_files) = read_tar(tar_thread_pool, file.clone()).await
                     {
                         let mut verified = true;
                         for (name, file) in &tar_files {
@@ -732,38 +944,9 @@ where
                     None
                 };
 
-            let mut files = None;
-
             // first try to load from local files without any hash from entry
-            {
-                // first try png (or .ogg etc., which currently are not supported)
-                if let Ok(file) = fs
-                    .read_file(&base_path.join(format!("{}.png", key.name.as_str())))
-                    .await
-                {
-                    files = Some(ContainerLoadedItem::SingleFile(file.to_vec()));
-                }
-                // else try tar
-                else if let Ok(file) = fs
-                    .read_file(&base_path.join(format!("{}.tar", key.name.as_str())))
-                    .await
-                    && let Ok(tar_files) = read_tar_files(file.into())
-                {
-                    files = Some(ContainerLoadedItem::Directory(ContainerLoadedItemDir::new(
-                        tar_files,
-                    )));
-                }
-                // if still not found, try directory
-                if (files.is_none() || key.name.as_str() == "default")
-                    && let Ok(dir_files) = fs
-                        .files_in_dir_recursive(&base_path.join(key.name.as_str()))
-                        .await
-                {
-                    files = Some(ContainerLoadedItem::Directory(ContainerLoadedItemDir::new(
-                        dir_files,
-                    )));
-                }
-            }
+            let mut files =
+                load_local_item(&*fs, tar_thread_pool, &base_path, key.name.as_str()).await;
 
             // else if an entry exists, first try to load from disk using the entries hash
             if let Some((entry, _)) = files.is_none().then_some(http_entry.as_ref()).flatten()
@@ -781,7 +964,7 @@ where
                     .await
             {
                 if entry.ty == "tar" {
-                    if let Ok(tar_files) = read_tar_files(file.into()) {
+                    if let Ok(tar_files) = read_tar(tar_thread_pool, file).await {
                         files = Some(ContainerLoadedItem::Directory(ContainerLoadedItemDir::new(
                             tar_files,
                         )));
@@ -790,7 +973,7 @@ where
                     || (allows_single_audio_or_txt_files && entry.ty == "ogg")
                     || (allows_single_audio_or_txt_files && entry.ty == "txt")
                 {
-                    files = Some(ContainerLoadedItem::SingleFile(file.to_vec()));
+                    files = Some(ContainerLoadedItem::SingleFile(file));
                 }
             }
 
@@ -816,7 +999,7 @@ where
                 match res {
                     Ok(file) => {
                         let write_to_disk = if ty == "tar" {
-                            if let Ok(tar_files) = read_tar_files(file.as_ref().into()) {
+                            if let Ok(tar_files) = read_tar(tar_thread_pool, file.clone()).await {
                                 let mut verified = true;
                                 for (name, file) in &tar_files {
                                     if !Self::verify_resource(
@@ -898,6 +1081,7 @@ where
                 container_name_dummy,
                 fs,
                 http,
+                None,
                 Arc::new(Semaphore::const_new(1)),
                 base_path,
                 "".into(),
@@ -938,6 +1122,7 @@ where
                 container_name,
                 fs,
                 http,
+                Some(runtime_thread_pool.clone()),
                 http_download_tasks,
                 base_path,
                 downloaded_path,
@@ -1029,6 +1214,8 @@ where
                     let loaded_item = load_item.get();
                     match loaded_item {
                         Ok(item) => {
+                            let memory = item.memory_usage();
+                            self.check_item_memory(&name, &memory);
                             let new_item =
                                 item.convert(&self.texture_handle, &self.sound_object_handle);
                             self.items.insert(
@@ -1036,6 +1223,7 @@ where
                                 ContainerItem {
                                     item: new_item,
                                     used_last_in: self.last_update_time.unwrap_or_default(),
+                                    memory,
                                 },
                             );
                             if should_return_new_item {
@@ -1095,6 +1283,72 @@ where
         }
     }
 
+    /// The graphics device was recreated & all textures are gone.
+    ///
+    /// Removes all items, the default item is loaded again from its files.
+    #[instrument(level = "trace", skip_all)]
+    pub fn reload_after_gpu_reset(&mut self) {
+        // not loaded yet, nothing uploaded
+        if self.default_item.is_some() {
+            self.clear_except_default();
+            return;
+        }
+        self.items.clear();
+        self.loading_tasks.clear();
+        self.failed_tasks.clear();
+
+        let default_files = (*self.default_loaded_item).clone();
+        let runtime_thread_pool = self.runtime_thread_pool.clone();
+        let graphics_mt = self.graphics_mt.clone();
+        let sound_mt = self.sound_mt.clone();
+        self.default_item = Some(DefaultItem {
+            task: self
+                .io
+                .rt
+                .spawn(async move {
+                    L::load(
+                        "default",
+                        ContainerLoadedItem::Directory(default_files.clone()),
+                        // dummy
+                        &ContainerLoadedItemDir::new(Default::default()),
+                        &runtime_thread_pool,
+                        &graphics_mt,
+                        &sound_mt,
+                    )
+                    .map(|item| (item, default_files))
+                })
+                .abortable(),
+            notifier: None,
+        });
+    }
+
+    /// Loaded items that use more than `threshold` bytes log a warning.
+    /// `None` disables the warning.
+    pub fn set_item_memory_warn_threshold(&mut self, threshold: Option<usize>) {
+        self.item_memory_warn_threshold = threshold;
+    }
+
+    /// The summed memory of all loaded items.
+    pub fn memory_usage(&self) -> ContainerItemMemory {
+        self.items.values().map(|item| item.memory).sum()
+    }
+
+    /// The loaded items with their memory, the biggest first.
+    pub fn items_by_memory(&self) -> Vec<(&ContainerKey, ContainerItemMemory)> {
+        items_by_memory(&self.items)
+    }
+
+    /// Unloads the given item, it's loaded again the next time it's used.
+    ///
+    /// Returns the memory of the item, `None` if it was not loaded
+    /// or is the default item, which can not be evicted.
+    pub fn evict<Q>(&mut self, name: &Q) -> Option<ContainerItemMemory>
+    where
+        Q: Borrow<ContainerKey>,
+    {
+        evict_item(&mut self.items, &self.default_key, name.borrow())
+    }
+
     /// Checks if default already loaded without initiating the loading process
     #[instrument(level = "trace", skip_all)]
     pub fn is_default_loaded(&self) -> bool {
@@ -1830,3 +2084,226 @@ pub fn load_file_part_and_convert_3d_and_upload(
         from_default: part_img.from_default,
     })
 }
+
+#[cfg(test)]
+mod test {
+    use std::{path::Path, sync::Arc};
+
+    use assets_base::tar::{new_tar, tar_add_file};
+    use base_fs::filesys::FileSystem;
+    use base_io::io::IoFileSys;
+
+    use graphics_types::{
+        commands::TexFlags,
+        types::{
+            GraphicsBackendMemory, GraphicsBackendMemoryAllocation, GraphicsMemoryAllocationType,
+        },
+    };
+    use hashlink::LinkedHashMap;
+    use rustc_hash::FxHashMap;
+    use sound::sound_mt_types::SoundBackendMemory;
+
+    use super::{
+        ContainerItem, ContainerItemLoadData, ContainerItemMemory, ContainerItemMemoryUsage,
+        ContainerKey, ContainerLoadedItem, evict_item, items_by_memory, load_local_item,
+    };
+
+    fn create_io() -> IoFileSys {
+        let workspace_root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../");
+        std::env::set_current_dir(workspace_root).unwrap();
+        IoFileSys::new(|rt| {
+            Arc::new(
+                FileSystem::new(rt, "ddnet-test", "ddnet-test", "ddnet-test", "ddnet-test")
+                    .unwrap(),
+            )
+        })
+    }
+
+    fn tar(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut tar = new_tar();
+        for (name, file) in files {
+            tar_add_file(&mut tar, name, file);
+        }
+        tar.into_inner().unwrap()
+    }
+
+    #[test]
+    fn tar_and_directory_load_equally() {
+        let io = create_io();
+        let thread_pool = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(1)
+                .build()
+                .unwrap(),
+        );
+        let base_path: &Path = "downloaded/test/containers/skins".as_ref();
+        let files: [(&str, &[u8]); 3] = [
+            ("body.png", b"body"),
+            ("feet.png", b"feet"),
+            ("audio/ground_jump.ogg", b"jump"),
+        ];
+        let packed = tar(&files);
+        let replacement = tar(&[("body.png", b"other body")]);
+
+        let fs = io.fs.clone();
+        io.rt
+            .spawn(async move {
+                let _ = fs.remove_file(&base_path.join("dir_skin.tar")).await;
+                for (name, file) in files {
+                    let path = base_path.join("dir_skin").join(name);
+                    fs.create_dir(path.parent().unwrap()).await?;
+                    fs.write_file(&path, file.to_vec()).await?;
+                }
+                fs.write_file(&base_path.join("tar_skin.tar"), packed)
+                    .await?;
+
+                let load = async |name: &str| match load_local_item(
+                    &*fs,
+                    Some(&thread_pool),
+                    base_path,
+                    name,
+                )
+                .await
+                {
+                    Some(ContainerLoadedItem::Directory(dir)) => dir.files,
+                    item => panic!("expected a directory for {name}, got {item:?}"),
+                };
+                let dir_files = load("dir_skin").await;
+                let tar_files = load("tar_skin").await;
+                assert_eq!(dir_files.len(), files.len());
+                assert_eq!(dir_files, tar_files);
+
+                // if both exist, the tar is preferred
+                fs.write_file(&base_path.join("dir_skin.tar"), replacement)
+                    .await?;
+                let replaced = load("dir_skin").await;
+                assert_eq!(replaced.len(), 1);
+                assert_eq!(
+                    replaced
+                        .get(Path::new("body.png"))
+                        .map(|file| file.as_slice()),
+                    Some(b"other body".as_slice())
+                );
+                fs.remove_file(&base_path.join("dir_skin.tar")).await?;
+
+                assert!(
+                    load_local_item(&*fs, None, base_path, "missing")
+                        .await
+                        .is_none()
+                );
+                anyhow::Ok(())
+            })
+            .get()
+            .unwrap();
+    }
+
+    fn texture(width: u32, height: u32, depth: u32) -> ContainerItemLoadData {
+        ContainerItemLoadData {
+            width,
+            height,
+            depth,
+            data: GraphicsBackendMemory::new(
+                GraphicsBackendMemoryAllocation::Vector(vec![
+                    0;
+                    (width * height * depth * 4) as usize
+                ]),
+                GraphicsMemoryAllocationType::TextureRgbaU8 {
+                    width: (width as usize).try_into().unwrap(),
+                    height: ((height * depth) as usize).try_into().unwrap(),
+                    flags: TexFlags::empty(),
+                },
+            ),
+        }
+    }
+
+    fn sound(len: usize) -> SoundBackendMemory {
+        SoundBackendMemory::Vector { data: vec![0; len] }
+    }
+
+    #[test]
+    fn item_memory() {
+        assert_eq!(
+            texture(64, 32, 1).memory_usage(),
+            ContainerItemMemory {
+                gpu_bytes: 64 * 32 * 4,
+                cpu_bytes: 0
+            }
+        );
+        // e.g. the entities are a 2d array
+        assert_eq!(
+            texture(16, 16, 256).memory_usage().gpu_bytes,
+            16 * 16 * 256 * 4
+        );
+        assert_eq!(
+            sound(1000).memory_usage(),
+            ContainerItemMemory {
+                gpu_bytes: 0,
+                cpu_bytes: 1000
+            }
+        );
+
+        // the parts of an item are summed
+        let textures = [texture(64, 32, 1), texture(32, 32, 1)];
+        let sounds = vec![sound(1000), sound(24)];
+        let mut physics = FxHashMap::default();
+        physics.insert("ddrace".to_string(), texture(16, 16, 256));
+        let missing: Option<ContainerItemLoadData> = None;
+        let memory = textures.memory_usage()
+            + sounds.memory_usage()
+            + physics.memory_usage()
+            + missing.memory_usage();
+        assert_eq!(
+            memory,
+            ContainerItemMemory {
+                gpu_bytes: (64 * 32 + 32 * 32 + 16 * 16 * 256) * 4,
+                cpu_bytes: 1024
+            }
+        );
+        assert_eq!(memory.total(), memory.gpu_bytes + 1024);
+    }
+
+    #[test]
+    fn evict() {
+        let key = |name: &str| -> ContainerKey { name.try_into().unwrap() };
+        let item = |gpu_bytes: usize| ContainerItem {
+            item: (),
+            used_last_in: Default::default(),
+            memory: ContainerItemMemory {
+                gpu_bytes,
+                cpu_bytes: 0,
+            },
+        };
+        let mut items = LinkedHashMap::new();
+        items.insert(key("default"), item(100));
+        items.insert(key("small"), item(10));
+        items.insert(key("big"), item(1000));
+
+        let sorted: Vec<_> = items_by_memory(&items)
+            .into_iter()
+            .map(|(key, memory)| (key.name.as_str().to_string(), memory.gpu_bytes))
+            .collect();
+        assert_eq!(
+            sorted,
+            [
+                ("big".to_string(), 1000),
+                ("default".to_string(), 100),
+                ("small".to_string(), 10)
+            ]
+        );
+
+        // the default item is always kept
+        assert_eq!(
+            evict_item(&mut items, &key("default"), &key("default")),
+            None
+        );
+        assert!(items.contains_key(&key("default")));
+
+        assert_eq!(
+            evict_item(&mut items, &key("default"), &key("big")).map(|m| m.gpu_bytes),
+            Some(1000)
+        );
+        assert!(!items.contains_key(&key("big")));
+        assert_eq!(evict_item(&mut items, &key("default"), &key("big")), None);
+        assert_eq!(items.len(), 2);
+    }
+}
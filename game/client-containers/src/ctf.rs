@@ -14,7 +14,8 @@ use crate::container::{
 };
 
 use super::container::{
-    Container, ContainerItemLoadData, ContainerLoad, load_file_part_and_upload,
+    Container, ContainerItemLoadData, ContainerItemMemory, ContainerItemMemoryUsage, ContainerLoad,
+    load_file_part_and_upload,
 };
 
 #[derive(Debug, Clone)]
@@ -130,6 +131,22 @@ impl LoadCtf {
     }
 }
 
+impl ContainerItemMemoryUsage for LoadCtf {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        [
+            self.flag_red.memory_usage(),
+            self.flag_blue.memory_usage(),
+            self.capture.memory_usage(),
+            self.collect_friendly.memory_usage(),
+            self.collect_opponents.memory_usage(),
+            self.drop.memory_usage(),
+            self.return_sound.memory_usage(),
+        ]
+        .into_iter()
+        .sum()
+    }
+}
+
 impl ContainerLoad<Ctf> for LoadCtf {
     fn load(
         item_name: &str,
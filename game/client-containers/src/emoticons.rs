@@ -15,7 +15,8 @@ use sound::{sound_handle::SoundObjectHandle, sound_mt::SoundMultiThreaded};
 use crate::container::{ContainerLoadedItem, ContainerLoadedItemDir};
 
 use super::container::{
-    Container, ContainerItemLoadData, ContainerLoad, load_file_part_and_upload,
+    Container, ContainerItemLoadData, ContainerItemMemory, ContainerItemMemoryUsage, ContainerLoad,
+    load_file_part_and_upload,
 };
 
 #[derive(Debug, Clone)]
@@ -117,6 +118,12 @@ impl LoadEmoticons {
     }
 }
 
+impl ContainerItemMemoryUsage for LoadEmoticons {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        self.emoticons.memory_usage()
+    }
+}
+
 impl ContainerLoad<Emoticons> for LoadEmoticons {
     fn load(
         item_name: &str,
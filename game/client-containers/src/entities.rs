@@ -14,7 +14,8 @@ use sound::{sound_handle::SoundObjectHandle, sound_mt::SoundMultiThreaded};
 use crate::container::{ContainerLoadedItem, ContainerLoadedItemDir};
 
 use super::container::{
-    Container, ContainerItemLoadData, ContainerLoad, load_file_part_and_convert_3d_and_upload,
+    Container, ContainerItemLoadData, ContainerItemMemory, ContainerItemMemoryUsage, ContainerLoad,
+    load_file_part_and_convert_3d_and_upload,
 };
 
 #[derive(Debug, Hiarc, Clone)]
@@ -155,6 +156,20 @@ impl LoadEntities {
     }
 }
 
+impl ContainerItemMemoryUsage for LoadEntities {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        [
+            self.physics.memory_usage(),
+            self.speedup.memory_usage(),
+            self.text_overlay_top.memory_usage(),
+            self.text_overlay_bottom.memory_usage(),
+            self.text_overlay_center.memory_usage(),
+        ]
+        .into_iter()
+        .sum()
+    }
+}
+
 impl ContainerLoad<Entities> for LoadEntities {
     fn load(
         item_name: &str,
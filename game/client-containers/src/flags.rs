@@ -10,7 +10,9 @@ use sound::{sound_handle::SoundObjectHandle, sound_mt::SoundMultiThreaded};
 
 use crate::container::{ContainerLoadedItem, ContainerLoadedItemDir, load_file_part_and_upload};
 
-use super::container::{Container, ContainerItemLoadData, ContainerLoad};
+use super::container::{
+    Container, ContainerItemLoadData, ContainerItemMemory, ContainerItemMemoryUsage, ContainerLoad,
+};
 
 #[derive(Debug, Hiarc, Clone)]
 pub struct Flags {
@@ -101,6 +103,12 @@ impl LoadFlags {
     }
 }
 
+impl ContainerItemMemoryUsage for LoadFlags {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        self.flags.memory_usage()
+    }
+}
+
 impl ContainerLoad<Flags> for LoadFlags {
     fn load(
         item_name: &str,
@@ -15,7 +15,7 @@ use crate::{
     skins::{LoadSkin, Skin},
 };
 
-use super::container::{Container, ContainerLoad};
+use super::container::{Container, ContainerItemMemory, ContainerItemMemoryUsage, ContainerLoad};
 
 #[derive(Debug)]
 pub struct Freeze {
@@ -65,6 +65,12 @@ impl LoadFreeze {
     }
 }
 
+impl ContainerItemMemoryUsage for LoadFreeze {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        self.skin.memory_usage() + self.attacks.memory_usage()
+    }
+}
+
 impl ContainerLoad<Freeze> for LoadFreeze {
     fn load(
         item_name: &str,
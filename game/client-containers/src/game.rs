@@ -14,7 +14,8 @@ use crate::container::{
 };
 
 use super::container::{
-    Container, ContainerItemLoadData, ContainerLoad, load_file_part_and_upload,
+    Container, ContainerItemLoadData, ContainerItemMemory, ContainerItemMemoryUsage, ContainerLoad,
+    load_file_part_and_upload,
 };
 
 #[derive(Debug, Clone)]
@@ -34,6 +35,9 @@ pub struct Game {
     pub lose_laser: TextureContainer,
     pub lose_ninja: TextureContainer,
     pub lose_shotgun: TextureContainer,
+
+    /// Played for chat messages that match a notification rule.
+    pub chat_highlights: Vec<SoundObject>,
 }
 
 #[derive(Debug)]
@@ -54,6 +58,8 @@ pub struct LoadGame {
     pub lose_ninja: ContainerItemLoadData,
     pub lose_shotgun: ContainerItemLoadData,
 
+    pub chat_highlights: Vec<SoundBackendMemory>,
+
     game_name: String,
 }
 
@@ -160,6 +166,17 @@ impl LoadGame {
             )?
             .img,
 
+            // older game packs have no chat sounds
+            chat_highlights: load_sound_file_part_list_and_upload(
+                sound_mt,
+                &files,
+                default_files,
+                game_name,
+                &["audio", "chat"],
+                "highlight",
+            )
+            .unwrap_or_default(),
+
             game_name: game_name.to_string(),
         })
     }
@@ -173,6 +190,34 @@ impl LoadGame {
     }
 }
 
+impl ContainerItemMemoryUsage for LoadPickup {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        [
+            self.tex.memory_usage(),
+            self.spawns.memory_usage(),
+            self.collects.memory_usage(),
+        ]
+        .into_iter()
+        .sum()
+    }
+}
+
+impl ContainerItemMemoryUsage for LoadGame {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        [
+            self.heart.memory_usage(),
+            self.shield.memory_usage(),
+            self.lose_grenade.memory_usage(),
+            self.lose_laser.memory_usage(),
+            self.lose_ninja.memory_usage(),
+            self.lose_shotgun.memory_usage(),
+            self.chat_highlights.memory_usage(),
+        ]
+        .into_iter()
+        .sum()
+    }
+}
+
 impl ContainerLoad<Game> for LoadGame {
     fn load(
         item_name: &str,
@@ -251,6 +296,12 @@ impl ContainerLoad<Game> for LoadGame {
                 self.lose_shotgun,
                 &self.game_name,
             ),
+
+            chat_highlights: self
+                .chat_highlights
+                .into_iter()
+                .map(|obj| sound_object_handle.create(obj))
+                .collect::<Vec<_>>(),
         }
     }
 }
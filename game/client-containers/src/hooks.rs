@@ -15,7 +15,8 @@ use crate::container::{
 };
 
 use super::container::{
-    Container, ContainerItemLoadData, ContainerLoad, load_file_part_and_upload,
+    Container, ContainerItemLoadData, ContainerItemMemory, ContainerItemMemoryUsage, ContainerLoad,
+    load_file_part_and_upload,
 };
 
 #[derive(Debug, Hiarc, Clone)]
@@ -106,6 +107,20 @@ impl LoadHook {
     }
 }
 
+impl ContainerItemMemoryUsage for LoadHook {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        [
+            self.hook_chain.memory_usage(),
+            self.hook_head.memory_usage(),
+            self.hit_hookable.memory_usage(),
+            self.hit_player.memory_usage(),
+            self.hit_unhookable.memory_usage(),
+        ]
+        .into_iter()
+        .sum()
+    }
+}
+
 impl ContainerLoad<Hook> for LoadHook {
     fn load(
         item_name: &str,
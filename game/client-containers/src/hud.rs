@@ -12,7 +12,8 @@ use sound::{sound_handle::SoundObjectHandle, sound_mt::SoundMultiThreaded};
 use crate::container::{ContainerLoadedItem, ContainerLoadedItemDir};
 
 use super::container::{
-    Container, ContainerItemLoadData, ContainerLoad, load_file_part_and_upload,
+    Container, ContainerItemLoadData, ContainerItemMemory, ContainerItemMemoryUsage, ContainerLoad,
+    load_file_part_and_upload,
 };
 
 #[derive(Debug, Clone)]
@@ -545,6 +546,53 @@ impl LoadHud {
     }
 }
 
+impl ContainerItemMemoryUsage for LoadHudVanilla {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        [
+            self.heart.memory_usage(),
+            self.heart_empty.memory_usage(),
+            self.shield.memory_usage(),
+            self.shield_empty.memory_usage(),
+        ]
+        .into_iter()
+        .sum()
+    }
+}
+
+impl ContainerItemMemoryUsage for LoadHudDdrace {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        [
+            self.jump.memory_usage(),
+            self.jump_used.memory_usage(),
+            self.solo.memory_usage(),
+            self.collision_off.memory_usage(),
+            self.endless_jump.memory_usage(),
+            self.endless_hook.memory_usage(),
+            self.jetpack.memory_usage(),
+            self.disabled_hook_others.memory_usage(),
+            self.disabled_weapons.memory_usage(),
+            self.tele_grenade.memory_usage(),
+            self.tele_pistol.memory_usage(),
+            self.tele_laser.memory_usage(),
+            self.deep_frozen.memory_usage(),
+            self.live_frozen.memory_usage(),
+            self.disabled_finish.memory_usage(),
+            self.dummy_hammer.memory_usage(),
+            self.dummy_copy.memory_usage(),
+            self.stage_locked.memory_usage(),
+            self.team0_mode.memory_usage(),
+        ]
+        .into_iter()
+        .sum()
+    }
+}
+
+impl ContainerItemMemoryUsage for LoadHud {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        self.vanilla.memory_usage() + self.ddrace.memory_usage()
+    }
+}
+
 impl ContainerLoad<Hud> for LoadHud {
     fn load(
         item_name: &str,
@@ -19,7 +19,8 @@ use crate::{
 };
 
 use super::container::{
-    Container, ContainerItemLoadData, ContainerLoad, load_file_part_and_upload,
+    Container, ContainerItemLoadData, ContainerItemMemory, ContainerItemMemoryUsage, ContainerLoad,
+    load_file_part_and_upload,
 };
 
 #[derive(Debug, Hiarc)]
@@ -142,6 +143,23 @@ impl LoadNinja {
     }
 }
 
+impl ContainerItemMemoryUsage for LoadNinja {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        [
+            self.cursor.memory_usage(),
+            self.muzzles.memory_usage(),
+            self.weapon.memory_usage(),
+            self.skin.memory_usage(),
+            self.spawn.memory_usage(),
+            self.collect.memory_usage(),
+            self.attacks.memory_usage(),
+            self.hits.memory_usage(),
+        ]
+        .into_iter()
+        .sum()
+    }
+}
+
 impl ContainerLoad<Ninja> for LoadNinja {
     fn load(
         item_name: &str,
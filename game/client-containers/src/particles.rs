@@ -14,7 +14,9 @@ use crate::container::{
     ContainerLoadedItem, ContainerLoadedItemDir, load_file_part_list_and_upload,
 };
 
-use super::container::{Container, ContainerItemLoadData, ContainerLoad};
+use super::container::{
+    Container, ContainerItemLoadData, ContainerItemMemory, ContainerItemMemoryUsage, ContainerLoad,
+};
 
 #[derive(Debug, Hiarc, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ParticleType {
@@ -252,6 +254,26 @@ impl LoadParticle {
     }
 }
 
+impl ContainerItemMemoryUsage for LoadParticle {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        [
+            self.slice.memory_usage(),
+            self.ball.memory_usage(),
+            self.splats.memory_usage(),
+            self.smoke.memory_usage(),
+            self.shell.memory_usage(),
+            self.explosions.memory_usage(),
+            self.airjump.memory_usage(),
+            self.hits.memory_usage(),
+            self.stars.memory_usage(),
+            self.snowflakes.memory_usage(),
+            self.sparkle.memory_usage(),
+        ]
+        .into_iter()
+        .sum()
+    }
+}
+
 impl ContainerLoad<Particle> for LoadParticle {
     fn load(
         item_name: &str,
@@ -25,7 +25,10 @@ use crate::container::{
     ContainerLoadedItem, ContainerLoadedItemDir, load_sound_file_part_list_and_upload,
 };
 
-use super::container::{Container, ContainerItemLoadData, ContainerLoad, load_file_part_as_png};
+use super::container::{
+    Container, ContainerItemLoadData, ContainerItemMemory, ContainerItemMemoryUsage, ContainerLoad,
+    load_file_part_as_png,
+};
 
 #[derive(Debug, Hiarc, Clone)]
 pub struct SkinMetricVariable {
@@ -1015,6 +1018,61 @@ impl LoadSkin {
     }
 }
 
+impl ContainerItemMemoryUsage for LoadSkinSounds {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        [
+            self.ground_jump.memory_usage(),
+            self.air_jump.memory_usage(),
+            self.spawn.memory_usage(),
+            self.death.memory_usage(),
+            self.pain_short.memory_usage(),
+            self.pain_long.memory_usage(),
+            self.hit_weak.memory_usage(),
+            self.hit_strong.memory_usage(),
+            self.skid.memory_usage(),
+        ]
+        .into_iter()
+        .sum()
+    }
+}
+
+impl ContainerItemMemoryUsage for LoadSkinTextures {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        [
+            self.body.memory_usage(),
+            self.body_outline.memory_usage(),
+            self.marking.memory_usage(),
+            self.marking_outline.memory_usage(),
+            self.decoration.memory_usage(),
+            self.decoration_outline.memory_usage(),
+            self.left_hand.memory_usage(),
+            self.left_hand_outline.memory_usage(),
+            self.right_hand.memory_usage(),
+            self.right_hand_outline.memory_usage(),
+            self.left_foot.memory_usage(),
+            self.left_foot_outline.memory_usage(),
+            self.right_foot.memory_usage(),
+            self.right_foot_outline.memory_usage(),
+            self.left_eyes.memory_usage(),
+            self.right_eyes.memory_usage(),
+        ]
+        .into_iter()
+        .sum()
+    }
+}
+
+impl ContainerItemMemoryUsage for LoadSkin {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        [
+            self.textures.memory_usage(),
+            self.grey_scaled_textures.memory_usage(),
+            self.sound.memory_usage(),
+        ]
+        .into_iter()
+        .sum()
+    }
+}
+
 impl ContainerLoad<Rc<Skin>> for LoadSkin {
     fn load(
         item_name: &str,
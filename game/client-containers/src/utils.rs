@@ -289,4 +289,20 @@ impl RenderGameContainers {
         self.game_container.clear_except_default();
         self.flags_container.clear_except_default();
     }
+
+    /// See [`crate::container::Container::reload_after_gpu_reset`].
+    pub fn reload_after_gpu_reset(&mut self) {
+        self.skin_container.reload_after_gpu_reset();
+        self.weapon_container.reload_after_gpu_reset();
+        self.hook_container.reload_after_gpu_reset();
+        self.ctf_container.reload_after_gpu_reset();
+        self.ninja_container.reload_after_gpu_reset();
+        self.freeze_container.reload_after_gpu_reset();
+        self.entities_container.reload_after_gpu_reset();
+        self.hud_container.reload_after_gpu_reset();
+        self.emoticons_container.reload_after_gpu_reset();
+        self.particles_container.reload_after_gpu_reset();
+        self.game_container.reload_after_gpu_reset();
+        self.flags_container.reload_after_gpu_reset();
+    }
 }
@@ -17,7 +17,8 @@ use crate::container::{
 };
 
 use super::container::{
-    Container, ContainerItemLoadData, ContainerLoad, load_file_part_and_upload,
+    Container, ContainerItemLoadData, ContainerItemMemory, ContainerItemMemoryUsage, ContainerLoad,
+    load_file_part_and_upload,
 };
 
 #[derive(Debug, Hiarc, Clone)]
@@ -761,6 +762,107 @@ impl LoadWeapons {
     }
 }
 
+impl ContainerItemMemoryUsage for LoadProjectile {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        self.projectile.memory_usage()
+    }
+}
+
+impl ContainerItemMemoryUsage for LoadMuzzles {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        self.muzzles.memory_usage()
+    }
+}
+
+impl ContainerItemMemoryUsage for LoadWeapon {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        [
+            self.tex.memory_usage(),
+            self.cursor_tex.memory_usage(),
+            self.fire.memory_usage(),
+            self.switch.memory_usage(),
+            self.noammo.memory_usage(),
+        ]
+        .into_iter()
+        .sum()
+    }
+}
+
+impl ContainerItemMemoryUsage for LoadGun {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        [
+            self.weapon.memory_usage(),
+            self.projectile.memory_usage(),
+            self.muzzles.memory_usage(),
+        ]
+        .into_iter()
+        .sum()
+    }
+}
+
+impl ContainerItemMemoryUsage for LoadGrenade {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        [
+            self.weapon.memory_usage(),
+            self.spawn.memory_usage(),
+            self.collect.memory_usage(),
+            self.explosions.memory_usage(),
+            self.projectile.memory_usage(),
+        ]
+        .into_iter()
+        .sum()
+    }
+}
+
+impl ContainerItemMemoryUsage for LoadLaser {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        [
+            self.weapon.memory_usage(),
+            self.spawn.memory_usage(),
+            self.collect.memory_usage(),
+            self.bounces.memory_usage(),
+            self.heads.memory_usage(),
+            self.projectile.memory_usage(),
+        ]
+        .into_iter()
+        .sum()
+    }
+}
+
+impl ContainerItemMemoryUsage for LoadShotgun {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        [
+            self.weapon.memory_usage(),
+            self.projectile.memory_usage(),
+            self.muzzles.memory_usage(),
+            self.spawn.memory_usage(),
+            self.collect.memory_usage(),
+        ]
+        .into_iter()
+        .sum()
+    }
+}
+
+impl ContainerItemMemoryUsage for LoadHammer {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        self.weapon.memory_usage() + self.hits.memory_usage()
+    }
+}
+
+impl ContainerItemMemoryUsage for LoadWeapons {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        [
+            self.hammer.memory_usage(),
+            self.gun.memory_usage(),
+            self.shotgun.memory_usage(),
+            self.grenade.memory_usage(),
+            self.laser.memory_usage(),
+        ]
+        .into_iter()
+        .sum()
+    }
+}
+
 impl ContainerLoad<Weapons> for LoadWeapons {
     fn load(
         item_name: &str,
@@ -0,0 +1,186 @@
+use camera::Camera;
+use client_render_game::render_game::RenderPlayerCameraMode;
+use client_ui::demo_player::user_data::{DemoCameraMode, DemoCameraPlayer};
+use game_interface::types::{
+    id_types::{CharacterId, StageId},
+    render::{character::CharacterInfo, stage::StageRenderInfo},
+};
+use math::math::vector::vec2;
+use pool::datatypes::{PoolFxHashSet, PoolFxLinkedHashMap};
+
+/// All characters that appeared in the demo so far.
+///
+/// Characters are never removed, so the camera can keep following
+/// a player that left & joins again later.
+#[derive(Debug, Default)]
+pub struct DemoCameraPlayers {
+    players: Vec<DemoCameraPlayer>,
+}
+
+impl DemoCameraPlayers {
+    /// Updates the list with the characters of the current snapshot.
+    pub fn update<'a>(&mut self, characters: impl IntoIterator<Item = (&'a CharacterId, &'a str)>) {
+        self.players
+            .iter_mut()
+            .for_each(|player| player.present = false);
+        for (id, name) in characters {
+            match self.players.iter_mut().find(|player| player.id == *id) {
+                Some(player) => {
+                    player.present = true;
+                    if player.name != name {
+                        player.name = name.to_string();
+                    }
+                }
+                None => self.players.push(DemoCameraPlayer {
+                    id: *id,
+                    name: name.to_string(),
+                    present: true,
+                }),
+            }
+        }
+    }
+
+    pub fn players(&self) -> &[DemoCameraPlayer] {
+        &self.players
+    }
+}
+
+/// The camera of a demo session.
+#[derive(Debug, Default)]
+pub struct DemoCamera {
+    pub mode: DemoCameraMode,
+    pub players: DemoCameraPlayers,
+    /// The position of the free camera.
+    ///
+    /// In the other modes it follows the current view,
+    /// so the free camera starts where the view was.
+    free_pos: vec2,
+}
+
+impl DemoCamera {
+    fn character_pos(
+        id: &CharacterId,
+        character_infos: &PoolFxLinkedHashMap<CharacterId, CharacterInfo>,
+        stages: &PoolFxLinkedHashMap<StageId, StageRenderInfo>,
+    ) -> Option<vec2> {
+        character_infos
+            .get(id)
+            .and_then(|info| info.stage_id)
+            .and_then(|stage_id| stages.get(&stage_id))
+            .and_then(|stage| stage.world.characters.get(id))
+            .map(|character| character.lerped_pos)
+    }
+
+    /// Updates the player list & the free camera position
+    /// with the current snapshot.
+    pub fn update(
+        &mut self,
+        original: &CharacterId,
+        character_infos: &PoolFxLinkedHashMap<CharacterId, CharacterInfo>,
+        stages: &PoolFxLinkedHashMap<StageId, StageRenderInfo>,
+    ) {
+        self.players.update(
+            character_infos
+                .iter()
+                .map(|(id, info)| (id, info.info.name.as_str())),
+        );
+
+        let target = match &self.mode {
+            DemoCameraMode::Original => Some(original),
+            DemoCameraMode::Follow(id) => Some(id),
+            DemoCameraMode::Free => None,
+        };
+        if let Some(pos) = target.and_then(|id| Self::character_pos(id, character_infos, stages)) {
+            self.free_pos = pos;
+        }
+    }
+
+    /// Moves the free camera by the given amount of pixels.
+    pub fn move_free(&mut self, delta: vec2, canvas_width: f32, canvas_height: f32) {
+        let mut width = 0.0;
+        let mut height = 0.0;
+        Camera::calc_canvas_params(
+            canvas_width / canvas_height.max(1.0),
+            1.0,
+            &mut width,
+            &mut height,
+        );
+        // dragging moves the world, not the camera
+        self.free_pos -= delta * (height / canvas_height.max(1.0));
+    }
+
+    pub fn render_mode(&self) -> RenderPlayerCameraMode {
+        match self.mode {
+            DemoCameraMode::Original => RenderPlayerCameraMode::Default,
+            DemoCameraMode::Follow(id) => RenderPlayerCameraMode::OnCharacters {
+                character_ids: {
+                    let mut character_ids = PoolFxHashSet::new_without_pool();
+                    character_ids.insert(id);
+                    character_ids
+                },
+                fallback_pos: self.free_pos,
+            },
+            DemoCameraMode::Free => RenderPlayerCameraMode::AtPos {
+                pos: self.free_pos,
+                locked_ingame: false,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use game_interface::types::{id_gen::IdGenerator, id_types::CharacterId};
+
+    use super::DemoCameraPlayers;
+
+    #[test]
+    fn players_from_snapshots() {
+        let id_gen = IdGenerator::new();
+        let ids: Vec<CharacterId> = (0..3).map(|_| id_gen.next_id()).collect();
+        let mut players = DemoCameraPlayers::default();
+        let list = |players: &DemoCameraPlayers| {
+            players
+                .players()
+                .iter()
+                .map(|player| (player.id, player.name.clone(), player.present))
+                .collect::<Vec<_>>()
+        };
+
+        players.update([(&ids[0], "nameless tee"), (&ids[1], "brainless tee")]);
+        assert_eq!(
+            list(&players),
+            vec![
+                (ids[0], "nameless tee".to_string(), true),
+                (ids[1], "brainless tee".to_string(), true),
+            ]
+        );
+
+        // a player leaves & another one joins mid-demo
+        players.update([(&ids[1], "brainless tee"), (&ids[2], "joined")]);
+        assert_eq!(
+            list(&players),
+            vec![
+                (ids[0], "nameless tee".to_string(), false),
+                (ids[1], "brainless tee".to_string(), true),
+                (ids[2], "joined".to_string(), true),
+            ]
+        );
+
+        // rejoining keeps the position in the list, renames are applied
+        players.update([(&ids[2], "joined"), (&ids[0], "renamed")]);
+        assert_eq!(
+            list(&players),
+            vec![
+                (ids[0], "renamed".to_string(), true),
+                (ids[1], "brainless tee".to_string(), false),
+                (ids[2], "joined".to_string(), true),
+            ]
+        );
+
+        // an empty snapshot keeps everyone in the list
+        players.update([]);
+        assert!(list(&players).iter().all(|(_, _, present)| !present));
+        assert_eq!(players.players().len(), 3);
+    }
+}
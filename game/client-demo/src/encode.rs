@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+use client_ui::demo_player::user_data::DemoEncodeProgress;
+
+/// The fixed timestep of a demo that is encoded to a video.
+///
+/// Every frame advances the demo by exactly one video frame,
+/// no matter how long encoding the frame took in real time.
+#[derive(Debug)]
+pub struct DemoEncodeClock {
+    fps: u32,
+    frames: u64,
+    total_frames: u64,
+    /// The real time when the first frame was encoded.
+    started_at: Option<Duration>,
+}
+
+impl DemoEncodeClock {
+    pub fn new(fps: u32, demo_len: Duration) -> Self {
+        let fps = fps.max(1);
+        Self {
+            fps,
+            frames: 0,
+            total_frames: (demo_len.as_nanos() * fps as u128)
+                .div_ceil(Duration::from_secs(1).as_nanos()) as u64,
+            started_at: None,
+        }
+    }
+
+    fn frame_time(&self, frame: u64) -> Duration {
+        Duration::from_nanos(
+            (frame as u128 * Duration::from_secs(1).as_nanos() / self.fps as u128) as u64,
+        )
+    }
+
+    /// Advances by one frame.
+    ///
+    /// Returns the demo time of the new frame and of the previous frame.
+    pub fn next_frame(&mut self, real_time: Duration) -> (Duration, Duration) {
+        self.started_at.get_or_insert(real_time);
+        let last_time = self.frame_time(self.frames);
+        self.frames += 1;
+        (self.frame_time(self.frames), last_time)
+    }
+
+    /// The amount of frames that were handed to the encoder so far.
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    pub fn progress(&self, real_time: Duration) -> DemoEncodeProgress {
+        let frames = self.frames.min(self.total_frames);
+        DemoEncodeProgress {
+            frames,
+            total_frames: self.total_frames,
+            eta: self.started_at.filter(|_| frames > 0).map(|started_at| {
+                real_time
+                    .saturating_sub(started_at)
+                    .mul_f64((self.total_frames - frames) as f64 / frames as f64)
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::DemoEncodeClock;
+
+    #[test]
+    fn fixed_timestep() {
+        // a tiny demo of one second, encoded with 30 fps
+        let demo_len = Duration::from_secs(1);
+        let mut clock = DemoEncodeClock::new(30, demo_len);
+        assert_eq!(clock.progress(Duration::ZERO).total_frames, 30);
+        assert!(clock.progress(Duration::ZERO).eta.is_none());
+
+        // the demo time advances the same way the demo viewer does,
+        // while the real time is much slower than the demo time
+        let mut demo_time = Duration::ZERO;
+        let mut real_time = Duration::from_secs(100);
+        while demo_time < demo_len {
+            let (cur_time, last_time) = clock.next_frame(real_time);
+            demo_time += cur_time - last_time;
+            real_time += Duration::from_millis(200);
+
+            if clock.frames() == 15 {
+                let progress = clock.progress(real_time);
+                assert_eq!(progress.frames, 15);
+                assert_eq!(progress.eta, Some(Duration::from_secs(3)));
+            }
+        }
+
+        assert_eq!(clock.frames(), 30);
+        assert_eq!(demo_time, demo_len);
+        let progress = clock.progress(real_time);
+        assert_eq!(progress.frames, progress.total_frames);
+        assert_eq!(progress.eta, Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn partial_frames() {
+        // the last partial frame is still encoded
+        let clock = DemoEncodeClock::new(60, Duration::from_millis(1010));
+        assert_eq!(clock.progress(Duration::ZERO).total_frames, 61);
+        // invalid fps don't divide by zero
+        let clock = DemoEncodeClock::new(0, Duration::from_secs(2));
+        assert_eq!(clock.progress(Duration::ZERO).total_frames, 2);
+    }
+}
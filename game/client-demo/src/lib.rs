@@ -1,5 +1,7 @@
 #![allow(clippy::too_many_arguments)]
 
+pub mod demo_camera;
+pub mod encode;
 pub mod ui;
 
 use std::{
@@ -18,19 +20,23 @@ use av_encoder::{AvEncoder, traits::AudioVideoEncoder};
 use base::steady_clock::SteadyClock;
 use base_io::{io::Io, runtime::IoRuntimeTask};
 use client_map::client_map::{ClientMapFile, ClientMapLoading, GameMap, RenderGameWasmManager};
-use client_render_base::map::render_pipe::GameTimeInfo;
+use client_render_base::map::{hq_assets::MapHqAssets, render_pipe::GameTimeInfo};
 use client_render_game::render_game::{
     RenderForPlayer, RenderGameCreateOptions, RenderGameForPlayer, RenderGameInput,
     RenderGameInterface, RenderGameSettings, RenderModTy, RenderPlayerCameraMode,
 };
-use client_ui::demo_player::user_data::{DemoViewerEvent, DemoViewerUiState, UserData};
+use client_ui::demo_player::user_data::{
+    DemoEncodeProgress, DemoViewerEvent, DemoViewerUiState, UserData,
+};
 use config::config::ConfigEngine;
 use demo::{
     ChunkHeader, DemoEvent, DemoEvents, DemoHeader, DemoHeaderExt, DemoSnapshot, DemoTail,
     recorder::{DemoRecorder, DemoRecorderCreateProps, DemoRecorderCreatePropsBase},
     utils::{decomp, deser, deser_ex},
 };
+use demo_camera::DemoCamera;
 use egui::{FontDefinitions, Rect};
+use encode::DemoEncodeClock;
 use game_base::{assets_url::HTTP_RESOURCE_URL, game_types::intra_tick_time_to_ratio};
 use game_config::config::{ConfigGame, ConfigMap, ConfigRender, ConfigSoundRender};
 use game_interface::{interface::GameStateInterface, types::game::GameTickType};
@@ -248,6 +254,7 @@ impl DemoViewerInner {
 struct DemoEncoder {
     enc: AvEncoder,
     settings: DemoVideoEncodeProperties,
+    clock: DemoEncodeClock,
 
     // offscreen params come last
     offscreen_canvas: OffscreenCanvas,
@@ -303,6 +310,7 @@ pub struct DemoViewerImpl {
 
     events: Vec<DemoViewerEvent>,
     ui_state: DemoViewerUiState,
+    camera: DemoCamera,
 }
 
 impl DemoViewerImpl {
@@ -320,16 +328,17 @@ impl DemoViewerImpl {
         sub_dir: PathBuf,
         name: String,
         encoder_offair_id: Option<u128>,
-    ) -> Self {
-        Self {
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
             data: DemoStaticData {
                 canvas_handle: graphics.canvas_handle.clone(),
                 stream_handle: graphics.stream_handle.clone(),
 
                 offscreen_canvas: { graphics.canvas_handle.offscreen_canvas(64, 64, 0.25, None) },
 
-                av_encoder: encode_to_video.zip(encoder_offair_id).map(
-                    |(props, encoder_offair_id)| {
+                av_encoder: encode_to_video
+                    .zip(encoder_offair_id)
+                    .map(|(props, encoder_offair_id)| {
                         let file_name = io.fs.get_save_path().join(&props.file_name);
                         let offscreen_canvas = graphics.canvas_handle.offscreen_canvas(
                             props.encoder_settings.width,
@@ -338,7 +347,7 @@ impl DemoViewerImpl {
                             None,
                         );
 
-                        DemoEncoder {
+                        anyhow::Ok(DemoEncoder {
                             enc: AvEncoder::new(
                                 offscreen_canvas.get_index_unsafe(),
                                 encoder_offair_id,
@@ -347,13 +356,17 @@ impl DemoViewerImpl {
                                 sound_backend,
                                 props.encoder_settings.clone(),
                             )
-                            .unwrap(),
+                            .map_err(|err| anyhow!("Failed to start the video encoder: {err}"))?,
+                            clock: DemoEncodeClock::new(
+                                props.encoder_settings.fps,
+                                demo.header.len,
+                            ),
                             settings: props,
                             offscreen_canvas,
                             _offair_id: encoder_offair_id,
-                        }
-                    },
-                ),
+                        })
+                    })
+                    .transpose()?,
                 config_map: Default::default(),
             },
 
@@ -399,7 +412,8 @@ impl DemoViewerImpl {
 
             events: Default::default(),
             ui_state: Default::default(),
-        }
+            camera: Default::default(),
+        })
     }
 
     pub fn is_finished(&self) -> bool {
@@ -410,6 +424,12 @@ impl DemoViewerImpl {
         self.inner.is_closed()
     }
 
+    /// Sets the playback speed, e.g. `0.5` for slow motion.
+    pub fn set_speed(&mut self, speed: f64) {
+        self.inner.speed =
+            ffixed::from_num(speed).clamp(ffixed::from_num(0.03125), ffixed::from_num(4096.0));
+    }
+
     fn set_time_and_reset_state(
         client_map: &mut ClientMapLoading,
         inner: &mut DemoViewerInner,
@@ -433,6 +453,7 @@ impl DemoViewerImpl {
         cur_time: Duration,
         last_time: Duration,
         last_monotonic_tick: &mut Option<GameTickType>,
+        camera: Option<&mut DemoCamera>,
         for_video_encode: bool,
         config_render: &ConfigRender,
         config_snd: &ConfigSoundRender,
@@ -494,29 +515,38 @@ impl DemoViewerImpl {
         let (player_id, _) = local_players.iter().next().unwrap();
         let intra_tick_time = viewer.intra_tick_time(monotonic_tick, prev_tick, next_tick);
 
+        let game_time_info = GameTimeInfo {
+            ticks_per_second: game.game_tick_speed(),
+            intra_tick_time,
+        };
+        let intra_tick_ratio =
+            intra_tick_time_to_ratio(intra_tick_time, viewer.demo.header_ext.ticks_per_second);
+
+        let character_infos = game.collect_characters_info();
+
+        let stages = game.all_stages(intra_tick_ratio);
+
+        let cam_mode = match camera {
+            Some(camera) => {
+                camera.update(player_id, &character_infos, &stages);
+                camera.render_mode()
+            }
+            None => RenderPlayerCameraMode::Default,
+        };
+
         let render_for_player = RenderForPlayer {
             chat_info: None,
             emote_wheel_input: None,
             spectator_selection_input: None,
+            scoreboard_input: None,
             scoreboard_active: false,
             chat_show_all: false,
 
             local_player_info: game.collect_character_local_render_info(player_id),
 
             zoom: 1.0,
-            cam_mode: RenderPlayerCameraMode::Default,
-        };
-
-        let game_time_info = GameTimeInfo {
-            ticks_per_second: game.game_tick_speed(),
-            intra_tick_time,
+            cam_mode,
         };
-        let intra_tick_ratio =
-            intra_tick_time_to_ratio(intra_tick_time, viewer.demo.header_ext.ticks_per_second);
-
-        let character_infos = game.collect_characters_info();
-
-        let stages = game.all_stages(intra_tick_ratio);
 
         let scoreboard_info = None; // game.collect_scoreboard_info();
         let date_time = None;
@@ -562,7 +592,10 @@ impl DemoViewerImpl {
             stages,
             scoreboard_info,
             date_time,
+            race_splits: None,
             chat_msgs,
+            chat_notify: Vec::new(),
+            auto_show_motd: true,
             game_time_info,
             settings: if let Some(DemoEncoder {
                 settings: encoder_game_settings,
@@ -628,6 +661,7 @@ impl DemoViewerImpl {
         cur_time: Duration,
         last_time: Duration,
         last_monotonic_tick: &mut Option<GameTickType>,
+        camera: Option<&mut DemoCamera>,
         for_video_encode: bool,
         conf_render: &ConfigRender,
         conf_snd: &ConfigSoundRender,
@@ -640,6 +674,7 @@ impl DemoViewerImpl {
             cur_time,
             last_time,
             last_monotonic_tick,
+            camera,
             for_video_encode,
             conf_render,
             conf_snd,
@@ -673,25 +708,18 @@ impl DemoViewerImpl {
         let do_encoding = self.data.av_encoder.is_some();
         let (cur_time, last_time) = if let Some(DemoEncoder {
             enc,
-            settings,
+            clock,
             offscreen_canvas,
             ..
-        }) = &self.data.av_encoder
+        }) = &mut self.data.av_encoder
         {
             // skip this frame
             if enc.overloaded() {
                 offscreen_canvas.skip_fetching_once();
                 return Ok(());
             }
-            let cur_time = self.last_time.unwrap_or_default()
-                + Duration::from_nanos(
-                    (Duration::from_secs(1).as_nanos() / settings.encoder_settings.fps as u128)
-                        as u64,
-                );
-            (
-                cur_time,
-                self.last_time.replace(cur_time).unwrap_or_default(),
-            )
+            // the demo time is independent of the real time while encoding
+            clock.next_frame(self.time.now())
         } else {
             let cur_time = self.time.now();
             (
@@ -706,6 +734,7 @@ impl DemoViewerImpl {
             cur_time,
             last_time,
             &mut self.last_monotonic_tick,
+            Some(&mut self.camera),
             do_encoding,
             render,
             snd,
@@ -724,6 +753,9 @@ impl DemoViewerImpl {
                     events: &mut self.events,
                     state: &mut self.ui_state,
                     name: &self.demo_name,
+                    camera_mode: &self.camera.mode,
+                    camera_players: self.camera.players.players(),
+                    encode_progress: self.encode_progress(),
                 },
             },
             input,
@@ -856,7 +888,18 @@ impl DemoViewerImpl {
                     );
                     self.should_show_preview = Some(rect);
                 }
-                DemoViewerEvent::Close => {
+                DemoViewerEvent::CameraMode(mode) => {
+                    self.camera.mode = mode;
+                }
+                DemoViewerEvent::FreeCameraMove { delta } => {
+                    let ppp = self.data.canvas_handle.pixels_per_point();
+                    self.camera.move_free(
+                        vec2::new(delta.x, delta.y) * ppp,
+                        self.data.canvas_handle.canvas_width() as f32,
+                        self.data.canvas_handle.canvas_height() as f32,
+                    );
+                }
+                DemoViewerEvent::CancelEncoding | DemoViewerEvent::Close => {
                     self.inner.is_closed = true;
                 }
             }
@@ -893,6 +936,7 @@ impl DemoViewerImpl {
                 Duration::ZERO,
                 Duration::ZERO,
                 &mut Default::default(),
+                None,
                 false,
                 &render,
                 snd,
@@ -956,6 +1000,7 @@ impl DemoViewerImpl {
             time,
             time,
             &mut self.last_monotonic_tick,
+            Some(&mut self.camera),
             false,
             &Default::default(),
             &Default::default(),
@@ -968,6 +1013,23 @@ impl DemoViewerImpl {
     pub fn is_encoder(&self) -> bool {
         self.data.av_encoder.is_some()
     }
+
+    pub fn encode_progress(&self) -> Option<DemoEncodeProgress> {
+        self.data
+            .av_encoder
+            .as_ref()
+            .map(|encoder| encoder.clock.progress(self.time.now()))
+    }
+
+    /// The video file & the amount of encoded frames,
+    /// if the whole demo was encoded.
+    pub fn finished_video(&self) -> Option<(&Path, u64)> {
+        self.data
+            .av_encoder
+            .as_ref()
+            .filter(|_| self.is_finished())
+            .map(|encoder| (encoder.settings.file_name.as_path(), encoder.clock.frames()))
+    }
 }
 
 pub struct DemoViewerLoading {
@@ -1189,6 +1251,7 @@ impl DemoViewer {
                             demo_container.header_ext.physics_mod.clone(),
                             false,
                             &config.dbg,
+                            &config.net,
                             demo_container.header_ext.game_options.clone(),
                             RenderGameCreateOptions {
                                 physics_group_name: demo_container
@@ -1213,6 +1276,7 @@ impl DemoViewer {
                                     .header_ext
                                     .client_local_infos
                                     .clone(),
+                                hq_assets: MapHqAssets::new(&config_game.map),
                             },
                             Default::default(),
                         )
@@ -1292,7 +1356,7 @@ impl DemoViewer {
                         sub_dir,
                         name,
                         encoder_offair_id,
-                    )));
+                    )?));
                 } else {
                     *self = Self::LoadingComponents(Box::new(DemoViewerLoadingComponents {
                         client_map,
@@ -1,33 +1,32 @@
-use std::{
-    collections::{HashMap, VecDeque},
-    path::PathBuf,
-    rc::Rc,
-    sync::Arc,
-    time::Duration,
-};
+pub mod splits;
+
+use std::{collections::VecDeque, path::PathBuf, time::Duration};
 
 use base::{
     hash::{Hash, fmt_hash},
-    reduced_ascii_str::ReducedAsciiString,
-    steady_clock::SteadyClock,
+    network_string::PoolNetworkString,
 };
 use base_io::{io::Io, runtime::IoRuntimeTask};
-use base_io_traits::fs_traits::FileSystemEntryTy;
-use client_demo::DemoViewer;
 use client_render_game::render_game::RenderGameInput;
-use config::config::ConfigEngine;
 use game_config::config::ConfigGame;
-use game_interface::types::{
-    game::GameEntityId,
-    id_gen::IdGenerator,
-    render::{game::GameRenderInfo, stage::StageRenderInfo, world::WorldRenderInfo},
+use game_interface::{
+    events::{GameEvents, GameWorldAction, GameWorldEvent, GameWorldNotificationEvent},
+    types::{
+        character_info::NetworkCharacterInfo,
+        game::{GameEntityId, GameTickType, NonZeroGameTickType},
+        id_gen::IdGenerator,
+        id_types::PlayerId,
+        render::{
+            character::{CharacterHookRenderInfo, CharacterInfo, CharacterRenderInfo, TeeEye},
+            game::GameRenderInfo,
+            stage::StageRenderInfo,
+            world::WorldRenderInfo,
+        },
+    },
 };
-use graphics::graphics::graphics::Graphics;
-use graphics_backend::backend::GraphicsBackend;
-use pool::datatypes::PoolFxLinkedHashMap;
-use sound::sound::SoundManager;
-use sound_backend::sound_backend::SoundBackend;
-use ui_base::{font_data::FontDefinitions, ui::UiCreator};
+use ghost::file::{GhostFile, GhostHeader, GhostTick};
+use math::math::vector::dvec2;
+use pool::{datatypes::PoolFxLinkedHashMap, rc::PoolRc};
 
 struct GhostIds {
     usable_ids: VecDeque<GameEntityId>,
@@ -58,64 +57,119 @@ impl GhostIds {
     }
 }
 
-pub struct GhostViewer {
-    ghosts: HashMap<String, DemoViewer>,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GhostKind {
+    /// The own best run, recorded by the client.
+    PersonalBest,
+    /// The run of the map record,
+    /// e.g. downloaded from the server.
+    MapRecord,
+}
+
+impl GhostKind {
+    const ALL: [Self; 2] = [Self::PersonalBest, Self::MapRecord];
+
+    fn file_name(&self) -> &'static str {
+        match self {
+            GhostKind::PersonalBest => "pb.ghost",
+            GhostKind::MapRecord => "record.ghost",
+        }
+    }
+}
+
+struct Ghost {
+    kind: GhostKind,
+    file: GhostFile,
+    info: PoolRc<NetworkCharacterInfo>,
+}
+
+impl Ghost {
+    fn new(kind: GhostKind, file: GhostFile) -> Self {
+        let info = PoolRc::from_item_without_pool(file.header.character_info.clone());
+        Self { kind, file, info }
+    }
+}
+
+/// The run of the local character that is currently recorded.
+struct GhostRun {
+    character_info: NetworkCharacterInfo,
+    ticks: Vec<GhostTick>,
+}
+
+impl GhostRun {
+    /// Returns `false` if the race restarted.
+    fn add_tick(&mut self, race_tick: GameTickType, tick: GhostTick) -> bool {
+        let index = race_tick as usize;
+        if index + 1 < self.ticks.len() {
+            return false;
+        }
+        // fill ticks that were skipped by slow frames,
+        // the latest frame of a tick replaces the previous ones.
+        let last = self.ticks.last().copied().unwrap_or(tick);
+        self.ticks.resize(index, last);
+        self.ticks.push(tick);
+        true
+    }
+}
 
-    sound: SoundManager,
-    graphics: Graphics,
-    backend: Rc<GraphicsBackend>,
-    sound_backend: Rc<SoundBackend>,
-    time: SteadyClock,
+/// Records the race runs of the local player & replays
+/// the personal best and map record as translucent ghosts.
+///
+/// Ghosts are stored in `ghosts/{map}_{hash}`.
+pub struct GhostViewer {
+    ghosts: Vec<Ghost>,
+    run: Option<GhostRun>,
 
     ids: GhostIds,
 
+    map_hash: Hash,
+    ticks_per_second: NonZeroGameTickType,
     base_path: PathBuf,
-    fonts: FontDefinitions,
 
-    task: Option<IoRuntimeTask<Vec<String>>>,
+    task: Option<IoRuntimeTask<Vec<Ghost>>>,
+    save_task: Option<IoRuntimeTask<()>>,
     io: Io,
-    tp: Arc<rayon::ThreadPool>,
 }
 
 impl GhostViewer {
-    #[allow(clippy::too_many_arguments)]
     pub fn new(
         io: &Io,
-        tp: &Arc<rayon::ThreadPool>,
-        sound: &SoundManager,
-        graphics: &Graphics,
-        backend: &Rc<GraphicsBackend>,
-        sound_backend: &Rc<SoundBackend>,
-        time: &SteadyClock,
-        map_name: &ReducedAsciiString,
+        map_name: &str,
         map_hash: Hash,
-        fonts: FontDefinitions,
+        ticks_per_second: NonZeroGameTickType,
     ) -> Self {
         let id_generator = IdGenerator::new();
         id_generator.reverse();
 
         let fs = io.fs.clone();
-        let map_name = map_name.to_string();
-        let base_path = format!("ghosts/{}_{}", map_name, fmt_hash(&map_hash));
+        let base_path: PathBuf = format!("ghosts/{}_{}", map_name, fmt_hash(&map_hash)).into();
         let base_path_task = base_path.clone();
         let task = io.rt.spawn(async move {
-            let entries = fs.entries_in_dir(base_path_task.as_ref()).await?;
-            Ok(entries
-                .into_iter()
-                .filter_map(|(name, ty)| match ty {
-                    FileSystemEntryTy::File { .. } => Some(name),
-                    FileSystemEntryTy::Directory => None,
-                })
-                .collect())
+            let mut ghosts = Vec::new();
+            for kind in GhostKind::ALL {
+                let path = base_path_task.join(kind.file_name());
+                // most maps simply have no ghosts yet
+                let Ok(file) = fs.read_file(&path).await else {
+                    continue;
+                };
+                match GhostFile::from_bytes(&file) {
+                    Ok(file) if file.header.map_hash == map_hash => {
+                        ghosts.push(Ghost::new(kind, file));
+                    }
+                    Ok(_) => {
+                        log::warn!("ghost {path:?} was recorded on another map");
+                    }
+                    Err(err) => {
+                        log::warn!("failed to load ghost {path:?}: {err}");
+                    }
+                }
+            }
+            Ok(ghosts)
         });
 
         Self {
             ghosts: Default::default(),
-            sound: sound.clone(),
-            graphics: graphics.clone(),
-            backend: backend.clone(),
-            sound_backend: sound_backend.clone(),
-            time: time.clone(),
+            run: None,
 
             ids: GhostIds {
                 usable_ids: Default::default(),
@@ -123,105 +177,261 @@ impl GhostViewer {
                 id_generator,
             },
 
-            base_path: base_path.into(),
-            fonts,
+            map_hash,
+            ticks_per_second,
+            base_path,
 
             task: Some(task),
-
+            save_task: None,
             io: io.clone(),
-            tp: tp.clone(),
         }
     }
 
+    fn finish_run(&mut self, finish_time: Duration) {
+        let Some(mut run) = self.run.take() else {
+            return;
+        };
+        if self.task.is_some() {
+            log::info!("ghosts are still loading, can't compare the run to the personal best");
+            return;
+        }
+        let finish_tick = (finish_time.as_secs_f64() * self.ticks_per_second.get() as f64).ceil();
+        if run.ticks.len() <= finish_tick as usize {
+            log::info!("ghost run is incomplete, not saving it");
+            return;
+        }
+        run.ticks.truncate(finish_tick as usize + 1);
+
+        if self.ghosts.iter().any(|ghost| {
+            ghost.kind == GhostKind::PersonalBest && ghost.file.header.finish_time <= finish_time
+        }) {
+            return;
+        }
+
+        let file = GhostFile {
+            header: GhostHeader {
+                map_hash: self.map_hash,
+                ticks_per_second: self.ticks_per_second,
+                character_info: run.character_info,
+                finish_time,
+            },
+            ticks: run.ticks,
+        };
+        let data = match file.to_bytes() {
+            Ok(data) => data,
+            Err(err) => {
+                log::error!("failed to serialize ghost: {err}");
+                return;
+            }
+        };
+        self.ghosts
+            .retain(|ghost| ghost.kind != GhostKind::PersonalBest);
+        self.ghosts.push(Ghost::new(GhostKind::PersonalBest, file));
+
+        let fs = self.io.fs.clone();
+        let base_path = self.base_path.clone();
+        self.save_task = Some(self.io.rt.spawn(async move {
+            fs.create_dir(&base_path).await?;
+            fs.write_file(&base_path.join(GhostKind::PersonalBest.file_name()), data)
+                .await?;
+            Ok(())
+        }));
+    }
+
+    /// Finished races of the local player save the run
+    /// if it is a new personal best.
+    pub fn on_event(&mut self, events: &GameEvents, player_id: &PlayerId) {
+        for world in events.worlds.values() {
+            for event in world.events.values() {
+                if let GameWorldEvent::Notification(GameWorldNotificationEvent::Action(action)) =
+                    event
+                {
+                    match action {
+                        GameWorldAction::RaceFinish {
+                            character,
+                            finish_time,
+                        } if character == player_id => {
+                            self.finish_run(*finish_time);
+                        }
+                        GameWorldAction::RaceTeamFinish {
+                            characters,
+                            finish_time,
+                            ..
+                        } if characters.contains(player_id) => {
+                            self.finish_run(*finish_time);
+                        }
+                        _ => {
+                            // ignore
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Records the local character & adds the ghosts
+    /// to the render input.
+    ///
+    /// Ghosts that finished their run are not rendered anymore.
     pub fn update(
         &mut self,
-        config: &ConfigEngine,
         config_game: &ConfigGame,
-        ui_creator: &UiCreator,
-        race_time: Duration,
+        player_id: Option<&PlayerId>,
         input: &mut RenderGameInput,
     ) {
         if self.task.as_ref().is_some_and(|task| task.is_finished()) {
             let task = self.task.take().unwrap();
             match task.get() {
                 Ok(ghosts) => {
-                    self.ghosts.extend(ghosts.into_iter().map(|ghost| {
-                        let demo_path = self.base_path.join(&ghost);
-                        (
-                            ghost,
-                            DemoViewer::new(
-                                &self.io,
-                                &self.tp,
-                                demo_path.as_ref(),
-                                self.fonts.clone(),
-                                None,
-                            ),
-                        )
-                    }));
+                    self.ghosts = ghosts;
                 }
                 Err(err) => {
                     log::error!("failed to fetch ghosts: {err}")
                 }
             }
         }
+        if self
+            .save_task
+            .as_ref()
+            .is_some_and(|task| task.is_finished())
+            && let Err(err) = self.save_task.take().unwrap().get()
+        {
+            log::error!("failed to save ghost: {err}");
+        }
 
-        self.ghosts.retain(|_, ghost| {
-            if let Err(err) = ghost.continue_loading(
-                &self.sound,
-                &self.graphics,
-                &self.backend,
-                &self.sound_backend,
-                config,
-                config_game,
-                &self.time,
-                ui_creator,
-            ) {
-                log::warn!("failed to render ghost: {err}");
-                return false;
+        let Some((char_info, character)) = player_id.and_then(|player_id| {
+            input.character_infos.get(player_id).and_then(|char_info| {
+                char_info
+                    .stage_id
+                    .and_then(|stage_id| input.stages.get(&stage_id))
+                    .filter(|stage| matches!(stage.game, GameRenderInfo::Race {}))
+                    .and_then(|stage| stage.world.characters.get(player_id))
+                    .map(|character| (char_info, character))
+            })
+        }) else {
+            self.run = None;
+            return;
+        };
+        let race_tick = character.game_ticks_passed;
+
+        if config_game.cl.ghost.record {
+            let tick = GhostTick {
+                pos: character.lerped_pos,
+                vel: character.lerped_vel,
+                cursor: character.lerped_cursor_pos,
+                move_dir: character.move_dir,
+                weapon: character.cur_weapon,
+                hook: character.lerped_hook.map(|hook| hook.pos),
+            };
+            if self
+                .run
+                .as_mut()
+                .is_some_and(|run| !run.add_tick(race_tick, tick))
+            {
+                self.run = None;
             }
-            if let Some(demo_viewer) = ghost.try_get_mut() {
-                match demo_viewer.get_render_input_for_time(race_time) {
-                    Ok(mut render_input) => {
-                        let stage_id = self
-                            .ids
-                            .next_id(|id| !input.stages.contains_key(&id.into()));
-                        let render_stage = input
-                            .stages
-                            .entry(stage_id.into())
-                            .or_insert_with_keep_order(|| StageRenderInfo {
-                                world: WorldRenderInfo {
-                                    projectiles: PoolFxLinkedHashMap::new_without_pool(),
-                                    ctf_flags: PoolFxLinkedHashMap::new_without_pool(),
-                                    lasers: PoolFxLinkedHashMap::new_without_pool(),
-                                    pickups: PoolFxLinkedHashMap::new_without_pool(),
-                                    characters: PoolFxLinkedHashMap::new_without_pool(),
-                                },
-                                game: GameRenderInfo::Race {},
-                                game_ticks_passed: 0,
-                            });
-
-                        for (real_id, char_info) in render_input.character_infos.drain() {
-                            let id = self
-                                .ids
-                                .next_id(|id| !input.character_infos.contains_key(&id.into()));
-                            if let Some(stage) = char_info
-                                .stage_id
-                                .and_then(|stage_id| render_input.stages.get_mut(&stage_id))
-                                && let Some(render_char) = stage.world.characters.remove(&real_id)
-                            {
-                                render_stage.world.characters.insert(id.into(), render_char);
-                            }
-                            input.character_infos.insert(id.into(), char_info);
-                        }
-                    }
-                    Err(err) => {
-                        log::warn!("cancelled ghost rendering: {err}");
-                        return false;
-                    }
-                }
+            // only runs that are recorded from the start are complete
+            if self.run.is_none() && race_tick <= 1 {
+                let mut run = GhostRun {
+                    character_info: (*char_info.info).clone(),
+                    ticks: Default::default(),
+                };
+                run.add_tick(race_tick, tick);
+                self.run = Some(run);
             }
-            true
-        });
+        } else {
+            self.run = None;
+        }
+
+        if !config_game.cl.ghost.show {
+            return;
+        }
+
+        let race_time =
+            Duration::from_secs_f64(race_tick as f64 / self.ticks_per_second.get() as f64)
+                + input.game_time_info.intra_tick_time;
+        let mut ghost_stage_id = None;
+        for ghost in self
+            .ghosts
+            .iter()
+            .filter(|ghost| config_game.cl.ghost.show_record || ghost.kind != GhostKind::MapRecord)
+        {
+            let Some(tick) = ghost.file.at(race_time) else {
+                continue;
+            };
+            let stage_id = *ghost_stage_id.get_or_insert_with(|| {
+                let stage_id = self
+                    .ids
+                    .next_id(|id| !input.stages.contains_key(&id.into()))
+                    .into();
+                input.stages.insert(
+                    stage_id,
+                    StageRenderInfo {
+                        world: WorldRenderInfo {
+                            projectiles: PoolFxLinkedHashMap::new_without_pool(),
+                            ctf_flags: PoolFxLinkedHashMap::new_without_pool(),
+                            lasers: PoolFxLinkedHashMap::new_without_pool(),
+                            pickups: PoolFxLinkedHashMap::new_without_pool(),
+                            characters: PoolFxLinkedHashMap::new_without_pool(),
+                        },
+                        game: GameRenderInfo::Race {},
+                        game_ticks_passed: race_tick,
+                    },
+                );
+                stage_id
+            });
+
+            let id = self
+                .ids
+                .next_id(|id| !input.character_infos.contains_key(&id.into()))
+                .into();
+            input.character_infos.insert(
+                id,
+                CharacterInfo {
+                    info: ghost.info.clone(),
+                    skin_info: ghost.info.skin_info,
+                    laser_info: ghost.info.laser_info,
+                    stage_id: Some(stage_id),
+                    side: None,
+                    player_info: None,
+                    account_name: None,
+                    browser_score: PoolNetworkString::new_without_pool(),
+                    browser_eye: TeeEye::Normal,
+                    is_bot: false,
+                    server_rank: None,
+                },
+            );
+            if let Some(stage) = input.stages.get_mut(&stage_id) {
+                stage.world.characters.insert(
+                    id,
+                    CharacterRenderInfo {
+                        lerped_pos: tick.pos,
+                        lerped_vel: tick.vel,
+                        lerped_hook: tick.hook.map(|pos| CharacterHookRenderInfo {
+                            pos,
+                            hooked_char: None,
+                        }),
+                        hook_collision: None,
+                        has_air_jump: true,
+                        lerped_cursor_pos: tick.cursor,
+                        lerped_dyn_cam_offset: dvec2::default(),
+                        move_dir: tick.move_dir,
+                        cur_weapon: tick.weapon,
+                        recoil_ticks_passed: None,
+                        left_eye: ghost.info.default_eyes,
+                        right_eye: ghost.info.default_eyes,
+                        buffs: PoolFxLinkedHashMap::new_without_pool(),
+                        debuffs: PoolFxLinkedHashMap::new_without_pool(),
+                        animation_ticks_passed: race_tick,
+                        game_ticks_passed: race_tick,
+                        emoticon: None,
+                        // renders the ghost translucent
+                        phased: true,
+                        practice: false,
+                    },
+                );
+            }
+        }
         self.ids.swap();
     }
 }
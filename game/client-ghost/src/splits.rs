@@ -0,0 +1,229 @@
+use std::{path::PathBuf, time::Duration};
+
+use base::hash::{Hash, fmt_hash};
+use base_io::{io::Io, runtime::IoRuntimeTask};
+use client_render_game::render_game::RenderGameInput;
+use client_ui::hud::user_data::{
+    RACE_FINISH_SHOW_TIME, RACE_SPLIT_SHOW_TIME, RenderRaceFinish, RenderRaceSplit,
+    RenderRaceSplits,
+};
+use game_interface::{
+    events::{GameEvents, GameWorldAction, GameWorldEvent, GameWorldNotificationEvent},
+    types::id_types::PlayerId,
+};
+use ghost::splits::BestSplits;
+
+const SPLITS_FILE_NAME: &str = "best.splits";
+
+/// Compares the checkpoint times of the local player
+/// to the best times at these checkpoints.
+///
+/// The best splits are only updated by finished runs and
+/// are stored next to the ghosts in `ghosts/{map}_{hash}`.
+pub struct RaceSplitsTracker {
+    best: BestSplits,
+    /// Checkpoints of the current run.
+    run: Vec<RenderRaceSplit>,
+
+    /// The last split & when it was reached.
+    last: Option<(RenderRaceSplit, Duration)>,
+    /// The last finished run & when it finished.
+    finish: Option<(RenderRaceFinish, Duration)>,
+
+    base_path: PathBuf,
+
+    task: Option<IoRuntimeTask<BestSplits>>,
+    save_task: Option<IoRuntimeTask<()>>,
+    io: Io,
+}
+
+impl RaceSplitsTracker {
+    pub fn new(io: &Io, map_name: &str, map_hash: Hash) -> Self {
+        let fs = io.fs.clone();
+        let base_path: PathBuf = format!("ghosts/{}_{}", map_name, fmt_hash(&map_hash)).into();
+        let path = base_path.join(SPLITS_FILE_NAME);
+        let task = io.rt.spawn(async move {
+            // most maps were simply never finished
+            let Ok(file) = fs.read_file(&path).await else {
+                return Ok(BestSplits::new(map_hash));
+            };
+            match BestSplits::from_bytes(&file) {
+                Ok(splits) if splits.map_hash == map_hash => Ok(splits),
+                Ok(_) => {
+                    log::warn!("splits {path:?} were driven on another map");
+                    Ok(BestSplits::new(map_hash))
+                }
+                Err(err) => {
+                    log::warn!("failed to load splits {path:?}: {err}");
+                    Ok(BestSplits::new(map_hash))
+                }
+            }
+        });
+
+        Self {
+            best: BestSplits::new(map_hash),
+            run: Default::default(),
+
+            last: None,
+            finish: None,
+
+            base_path,
+
+            task: Some(task),
+            save_task: None,
+            io: io.clone(),
+        }
+    }
+
+    fn checkpoint(&mut self, index: u8, time: Duration, cur_time: Duration) {
+        // the race restarted
+        if self.run.last().is_some_and(|split| split.time > time) {
+            self.run.clear();
+        }
+        if self
+            .run
+            .iter()
+            .any(|split| split.index == index && split.time == time)
+        {
+            return;
+        }
+        let split = RenderRaceSplit {
+            index,
+            time,
+            best: self.best.best(index),
+        };
+        self.run.push(split);
+        self.last = Some((split, cur_time));
+    }
+
+    fn finish_run(&mut self, finish_time: Duration, cur_time: Duration) {
+        let splits = std::mem::take(&mut self.run);
+        let best = self.best.finish_time;
+        if self.task.is_some() {
+            log::info!("splits are still loading, can't update the best splits");
+        } else if self.best.finish_run(
+            &splits
+                .iter()
+                .map(|split| (split.index, split.time))
+                .collect::<Vec<_>>(),
+            finish_time,
+        ) {
+            self.save();
+        }
+        self.last = None;
+        self.finish = Some((
+            RenderRaceFinish {
+                splits,
+                time: finish_time,
+                best,
+            },
+            cur_time,
+        ));
+    }
+
+    fn save(&mut self) {
+        let data = match self.best.to_bytes() {
+            Ok(data) => data,
+            Err(err) => {
+                log::error!("failed to serialize splits: {err}");
+                return;
+            }
+        };
+        let fs = self.io.fs.clone();
+        let base_path = self.base_path.clone();
+        self.save_task = Some(self.io.rt.spawn(async move {
+            fs.create_dir(&base_path).await?;
+            fs.write_file(&base_path.join(SPLITS_FILE_NAME), data)
+                .await?;
+            Ok(())
+        }));
+    }
+
+    /// Checkpoints & finished races of the local player.
+    pub fn on_event(&mut self, events: &GameEvents, player_id: &PlayerId, cur_time: &Duration) {
+        for world in events.worlds.values() {
+            for event in world.events.values() {
+                if let GameWorldEvent::Notification(GameWorldNotificationEvent::Action(action)) =
+                    event
+                {
+                    match action {
+                        GameWorldAction::RaceCheckpoint {
+                            character,
+                            index,
+                            time,
+                        } if character == player_id => {
+                            self.checkpoint(*index, *time, *cur_time);
+                        }
+                        GameWorldAction::RaceFinish {
+                            character,
+                            finish_time,
+                        } if character == player_id => {
+                            self.finish_run(*finish_time, *cur_time);
+                        }
+                        GameWorldAction::RaceTeamFinish {
+                            characters,
+                            finish_time,
+                            ..
+                        } if characters.contains(player_id) => {
+                            self.finish_run(*finish_time, *cur_time);
+                        }
+                        _ => {
+                            // ignore
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Adds the recent split & finish summary to the render input.
+    pub fn update(&mut self, cur_time: &Duration, input: &mut RenderGameInput) {
+        if self.task.as_ref().is_some_and(|task| task.is_finished()) {
+            let task = self.task.take().unwrap();
+            match task.get() {
+                Ok(best) => {
+                    self.best = best;
+                }
+                Err(err) => {
+                    log::error!("failed to fetch splits: {err}")
+                }
+            }
+        }
+        if self
+            .save_task
+            .as_ref()
+            .is_some_and(|task| task.is_finished())
+            && let Err(err) = self.save_task.take().unwrap().get()
+        {
+            log::error!("failed to save splits: {err}");
+        }
+
+        if self
+            .last
+            .as_ref()
+            .is_some_and(|(_, time)| cur_time.saturating_sub(*time) > RACE_SPLIT_SHOW_TIME)
+        {
+            self.last = None;
+        }
+        if self
+            .finish
+            .as_ref()
+            .is_some_and(|(_, time)| cur_time.saturating_sub(*time) > RACE_FINISH_SHOW_TIME)
+        {
+            self.finish = None;
+        }
+        if self.last.is_none() && self.finish.is_none() {
+            return;
+        }
+
+        input.race_splits = Some(RenderRaceSplits {
+            last: self
+                .last
+                .map(|(split, time)| (split, cur_time.saturating_sub(time))),
+            finish: self
+                .finish
+                .as_ref()
+                .map(|(finish, time)| (finish.clone(), cur_time.saturating_sub(*time))),
+        });
+    }
+}
@@ -0,0 +1,189 @@
+//! The certificates of the game servers the client connected to before,
+//! to notice when a server suddenly presents a different one.
+//!
+//! The first connect to a server pins its certificate (trust on first use),
+//! later connects must present the same certificate, or the user must
+//! confirm the new one first.
+
+use std::{collections::BTreeMap, net::SocketAddr, path::Path};
+
+use base::hash::{Hash, fmt_hash};
+use base_io_traits::fs_traits::FileSystemInterface;
+use serde::{Deserialize, Serialize};
+
+use crate::download_trust::is_local_addr;
+
+pub const CERT_PINS_PATH: &str = "cert_pins.json";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CertPinCheck {
+    /// The server was never seen before, its certificate was pinned now.
+    FirstUse,
+    /// The certificate matches the pinned one.
+    Match,
+    /// The server presents a different certificate than before.
+    /// Nothing is pinned until the user confirmed the new one.
+    Mismatch {
+        /// The fingerprint of the previously pinned certificate.
+        pinned: String,
+    },
+    /// Servers in the local network usually create a new certificate
+    /// on every start, so they are never pinned.
+    Local,
+}
+
+/// The pinned certificate fingerprints, by the server address.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct CertPins {
+    servers: BTreeMap<String, String>,
+
+    #[serde(skip)]
+    changed: bool,
+}
+
+impl CertPins {
+    /// Loads the pins, a missing or invalid file
+    /// starts without pins.
+    pub async fn load(fs: &dyn FileSystemInterface, path: &Path) -> Self {
+        fs.read_file(path)
+            .await
+            .ok()
+            .and_then(|file| serde_json::from_slice(&file).ok())
+            .unwrap_or_default()
+    }
+
+    pub async fn save(&self, fs: &dyn FileSystemInterface, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs.create_dir(dir).await?;
+        }
+        fs.write_file(path, serde_json::to_vec_pretty(self)?)
+            .await?;
+        Ok(())
+    }
+
+    /// Checks the fingerprint of the certificate a server presents,
+    /// pinning it if the server was not seen before.
+    pub fn check(&mut self, addr: &SocketAddr, fingerprint: &Hash) -> CertPinCheck {
+        if is_local_addr(addr) {
+            return CertPinCheck::Local;
+        }
+        let fingerprint = fmt_hash(fingerprint);
+        match self.servers.get(&addr.to_string()) {
+            Some(pinned) if *pinned == fingerprint => CertPinCheck::Match,
+            Some(pinned) => CertPinCheck::Mismatch {
+                pinned: pinned.clone(),
+            },
+            None => {
+                self.servers.insert(addr.to_string(), fingerprint);
+                self.changed = true;
+                CertPinCheck::FirstUse
+            }
+        }
+    }
+
+    /// Pins the certificate the user confirmed after a mismatch.
+    pub fn pin(&mut self, addr: &SocketAddr, fingerprint: &Hash) {
+        self.servers.insert(addr.to_string(), fmt_hash(fingerprint));
+        self.changed = true;
+    }
+
+    /// Whether the pins changed since the last call & should be saved.
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{net::SocketAddr, path::Path, sync::Arc};
+
+    use base::hash::{fmt_hash, generate_hash_for};
+    use base_fs::filesys::FileSystem;
+    use base_io::io::IoFileSys;
+
+    use super::{CertPinCheck, CertPins};
+
+    fn create_io() -> IoFileSys {
+        let workspace_root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../");
+        std::env::set_current_dir(workspace_root).unwrap();
+        IoFileSys::new(|rt| {
+            Arc::new(
+                FileSystem::new(rt, "ddnet-test", "ddnet-test", "ddnet-test", "ddnet-test")
+                    .unwrap(),
+            )
+        })
+    }
+
+    #[test]
+    fn trust_on_first_use() {
+        let io = create_io();
+        let path: &Path = "downloaded/test/cert_pins/pins.json".as_ref();
+        let remote: SocketAddr = "1.2.3.4:8303".parse().unwrap();
+        let other: SocketAddr = "1.2.3.5:8303".parse().unwrap();
+        let cert = generate_hash_for(b"server cert");
+        let other_cert = generate_hash_for(b"other server cert");
+
+        let fs = io.fs.clone();
+        io.rt
+            .spawn(async move {
+                let _ = fs.remove_file(path).await;
+
+                let mut pins = CertPins::load(&*fs, path).await;
+                assert_eq!(pins.check(&remote, &cert), CertPinCheck::FirstUse);
+                assert!(pins.take_changed());
+                assert_eq!(pins.check(&remote, &cert), CertPinCheck::Match);
+                assert!(!pins.take_changed());
+                // pins are per server
+                assert_eq!(pins.check(&other, &other_cert), CertPinCheck::FirstUse);
+                pins.save(&*fs, path).await?;
+
+                let mut pins = CertPins::load(&*fs, path).await;
+                assert_eq!(pins.check(&remote, &cert), CertPinCheck::Match);
+                assert_eq!(pins.check(&other, &other_cert), CertPinCheck::Match);
+                assert!(!pins.take_changed());
+
+                // local servers are never pinned
+                let local: SocketAddr = "127.0.0.1:8303".parse().unwrap();
+                assert_eq!(pins.check(&local, &cert), CertPinCheck::Local);
+                assert_eq!(pins.check(&local, &other_cert), CertPinCheck::Local);
+                assert!(!pins.take_changed());
+                anyhow::Ok(())
+            })
+            .get()
+            .unwrap();
+    }
+
+    #[test]
+    fn mismatch_until_confirmed() {
+        let remote: SocketAddr = "1.2.3.4:8303".parse().unwrap();
+        let cert = generate_hash_for(b"server cert");
+        let new_cert = generate_hash_for(b"new server cert");
+
+        let mut pins = CertPins::default();
+        assert_eq!(pins.check(&remote, &cert), CertPinCheck::FirstUse);
+        pins.take_changed();
+
+        // a changed certificate is not pinned by checking it,
+        // so every further connect stops again
+        for _ in 0..2 {
+            assert_eq!(
+                pins.check(&remote, &new_cert),
+                CertPinCheck::Mismatch {
+                    pinned: fmt_hash(&cert)
+                }
+            );
+            assert!(!pins.take_changed());
+        }
+
+        // until the user confirmed it
+        pins.pin(&remote, &new_cert);
+        assert!(pins.take_changed());
+        assert_eq!(pins.check(&remote, &new_cert), CertPinCheck::Match);
+        assert_eq!(
+            pins.check(&remote, &cert),
+            CertPinCheck::Mismatch {
+                pinned: fmt_hash(&new_cert)
+            }
+        );
+    }
+}
@@ -1,10 +1,10 @@
-use std::{collections::BTreeMap, path::Path, rc::Rc, sync::Arc};
+use std::{collections::BTreeMap, path::Path, rc::Rc, sync::Arc, time::SystemTime};
 
 use anyhow::anyhow;
 use base_io::{io::Io, path_to_url::relative_path_to_url, runtime::IoRuntimeTask};
 use client_render_base::map::render_map_base::{ClientMapRender, RenderMapLoading};
 use client_render_game::render_game::{RenderGameCreateOptions, RenderGameInterface, RenderModTy};
-use config::config::ConfigDebug;
+use config::config::{ConfigDebug, ConfigNetwork};
 
 use game_database::dummy::DummyDb;
 use game_interface::{
@@ -24,7 +24,7 @@ use game_state_wasm::game::state_wasm_manager::{
 use graphics_backend::backend::GraphicsBackend;
 use map::{
     file::MapFileReader,
-    map::{Map, PngValidatorOptions},
+    map::{Map, metadata::ThemeAmbiance},
 };
 use rayon::ThreadPool;
 pub use render_game_wasm::render::render_wasm_manager::RenderGameWasmManager;
@@ -34,6 +34,8 @@ use game_base::{connecting_log::ConnectingLog, network::messages::GameModificati
 use sound::sound::SoundManager;
 use tracing::instrument;
 
+use crate::partial_download::{download_resumable, max_size_err, remove_stale_partial_downloads};
+
 #[derive(Debug)]
 pub enum ClientGameStateModTask {
     Native,
@@ -63,7 +65,8 @@ impl ClientGameStateModTask {
 
 #[derive(Debug)]
 pub struct ClientMapLoadingFile {
-    pub task: IoRuntimeTask<Vec<u8>>,
+    /// The map file & the theme ambiance the map prefers.
+    pub task: IoRuntimeTask<(Vec<u8>, Option<ThemeAmbiance>)>,
     io: Io,
     thread_pool: Arc<rayon::ThreadPool>,
     as_menu_map: bool,
@@ -94,11 +97,19 @@ impl ClientMapLoadingFile {
         game_mod: GameModification,
         as_menu_map: bool,
         config_debug: &ConfigDebug,
+        config_net: &ConfigNetwork,
         game_options: GameStateCreateOptions,
-        props: RenderGameCreateOptions,
+        mut props: RenderGameCreateOptions,
         log: ConnectingLog,
     ) -> Self {
-        let load_hq_assets = false;
+        // textures that are already in use reduce the budget of the new map
+        props.hq_assets = props.hq_assets.with_used_texture_memory(
+            backend
+                .memory_usage()
+                .texture_memory_usage
+                .load(std::sync::atomic::Ordering::Relaxed),
+        );
+        let png_validator_options = props.hq_assets.png_validator_options();
         let downloaded_path: Option<&Path> = (!as_menu_map).then_some("downloaded".as_ref());
         let download_map_file_name = if let Some(map_hash) = map_hash {
             base_path.join(format!(
@@ -119,8 +130,25 @@ impl ClientMapLoadingFile {
         let http = io.http.clone();
         let log_load = log.clone();
         let resource_download_server_thread = props.resource_download_server.clone();
+        let partial_download_max_age = config_net.partial_download_max_age;
+        let max_download_size = config_net.max_download_size;
         Self {
             task: io.rt.spawn(async move {
+                let now = SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .unwrap_or_default();
+                if let Some(dir) = map_file_name.parent()
+                    && let Err(err) = remove_stale_partial_downloads(
+                        &*file_system,
+                        dir,
+                        now,
+                        partial_download_max_age,
+                    )
+                    .await
+                {
+                    log::debug!("could not remove stale partial map downloads: {err}");
+                }
+
                 log_load.log(format!(
                     "Ready map file from file system: {map_file_name:?}"
                 ));
@@ -138,35 +166,26 @@ impl ClientMapLoadingFile {
                                     .and_then(|name| url.join(&name).ok())
                             })
                         {
-                            let file = http
-                                .download_binary(
-                                    resource_download_server,
-                                    &map_hash.unwrap_or_default(),
-                                )
-                                .await
-                                .map_err(|err| anyhow!("failed to download map: {err}"))?
-                                .to_vec();
-                            // maps are allowed to be arbitrary, but all maps should still start
-                            // with the twmap header.
-                            Map::validate_downloaded_map_file(
-                                &MapFileReader::new(file.clone())?,
-                                if load_hq_assets {
-                                    PngValidatorOptions {
-                                        max_width: 4096.try_into().unwrap(),
-                                        max_height: 4096.try_into().unwrap(),
-                                        ..Default::default()
-                                    }
-                                } else {
-                                    Default::default()
+                            // resumes a previously interrupted download
+                            let file = download_resumable(
+                                &*file_system,
+                                &*http,
+                                resource_download_server,
+                                &map_hash.unwrap_or_default(),
+                                &map_file_name,
+                                now,
+                                max_download_size,
+                                |file| {
+                                    // maps are allowed to be arbitrary, but all maps should
+                                    // still start with the twmap header.
+                                    Map::validate_downloaded_map_file(
+                                        &MapFileReader::new(file.to_vec())?,
+                                        png_validator_options,
+                                    )
                                 },
-                            )?;
-                            let file_path: &Path = map_file_name.as_ref();
-                            if let Some(dir) = file_path.parent() {
-                                file_system.create_dir(dir).await?;
-                            }
-                            file_system
-                                .write_file(map_file_name.as_ref(), file.clone())
-                                .await?;
+                            )
+                            .await
+                            .map_err(|err| anyhow!("failed to download map: {err}"))?;
                             log_load.log("Map downloaded successfully and saved to disk.");
                             Ok(file)
                         } else {
@@ -175,7 +194,17 @@ impl ClientMapLoadingFile {
                     }
                 }?;
 
-                Ok(file)
+                // the menu theme shown while loading can depend on the map
+                let theme_ambiance = if as_menu_map {
+                    None
+                } else {
+                    MapFileReader::new(file.clone())
+                        .and_then(|reader| Map::read_meta(&reader))
+                        .ok()
+                        .and_then(|meta| meta.theme_ambiance)
+                };
+
+                Ok((file, theme_ambiance))
             }),
             io: io.clone(),
             thread_pool: thread_pool.clone(),
@@ -233,6 +262,12 @@ impl ClientMapLoadingFile {
                                                 anyhow!("failed to download mod: {err}")
                                             })?
                                             .to_vec();
+                                        if file.len() as u64 > max_download_size {
+                                            return Err(anyhow!(
+                                                "failed to download mod: {}",
+                                                max_size_err(max_download_size)
+                                            ));
+                                        }
 
                                         // ensure that downloaded file is valid wasm file
                                         wasmparser::validate(&file)?;
@@ -280,6 +315,7 @@ pub struct ClientMapPreparing {
     map_name: NetworkReducedAsciiString<MAX_MAP_NAME_LEN>,
     game_mod: GameStateMod,
     game_options: GameStateCreateOptions,
+    theme_ambiance: Option<ThemeAmbiance>,
 }
 
 pub struct GameCreateProps {
@@ -341,6 +377,8 @@ impl ClientMapComponentLoading {
                     graphics,
                     config,
                     None,
+                    props.hq_assets,
+                    Some(log.clone()),
                 )))
             } else {
                 let fs = io.fs.clone();
@@ -499,6 +537,8 @@ pub struct GameMap {
     /// unpredicted local game (similar to how a demo works)
     /// for non-anti-ping calculations
     pub unpredicted_game: GameUnpredicted,
+    /// The ambiance of the menu theme the map prefers.
+    pub theme_ambiance: Option<ThemeAmbiance>,
 }
 
 pub enum ClientMapFile {
@@ -534,6 +574,7 @@ impl ClientMapLoading {
         game_mod: GameModification,
         as_menu_map: bool,
         config_debug: &ConfigDebug,
+        config_net: &ConfigNetwork,
         game_options: GameStateCreateOptions,
         props: RenderGameCreateOptions,
         log: ConnectingLog,
@@ -551,6 +592,7 @@ impl ClientMapLoading {
             game_mod,
             as_menu_map,
             config_debug,
+            config_net,
             game_options,
             props,
             log,
@@ -573,6 +615,18 @@ impl ClientMapLoading {
         }
     }
 
+    /// The ambiance of the menu theme the map prefers,
+    /// known as soon as the map file was read.
+    pub fn theme_ambiance(&self) -> Option<ThemeAmbiance> {
+        match self {
+            Self::PrepareComponents(prepare) => prepare.theme_ambiance,
+            Self::Map(ClientMapFile::Game(game)) => game.theme_ambiance,
+            Self::File(_) | Self::Map(ClientMapFile::Menu { .. }) | Self::Err(_) | Self::None => {
+                None
+            }
+        }
+    }
+
     pub fn err(&self) -> anyhow::Result<(), String> {
         if let Self::Err(err) = self {
             Err(err.to_string())
@@ -596,7 +650,7 @@ impl ClientMapLoading {
             Self::File(file) => {
                 if file.task.is_finished() && file.game_mod_task.is_finished() {
                     match file.task.get() {
-                        Ok(map_file) => {
+                        Ok((map_file, theme_ambiance)) => {
                             let game_mod = file.game_mod_task.to_game_state_mod();
 
                             let loading = ClientMapComponentLoading::new(
@@ -619,6 +673,7 @@ impl ClientMapLoading {
                                 map_name: file.map_name,
                                 game_mod,
                                 game_options: file.game_options,
+                                theme_ambiance,
                             }))
                         }
                         Err(err) => *self = Self::Err(err),
@@ -645,6 +700,7 @@ impl ClientMapLoading {
                                             &props.config,
                                             file,
                                             props.props,
+                                            Some(prepare.render.log.clone()),
                                         ) {
                                             Ok(game) => load_game = GameLoading::Game(game),
                                             Err(err) => load_game = GameLoading::Err(err),
@@ -671,6 +727,7 @@ impl ClientMapLoading {
                                     map_name: prepare.map_name,
                                     game_mod: prepare.game_mod,
                                     game_options: prepare.game_options,
+                                    theme_ambiance: prepare.theme_ambiance,
                                 }))
                             }
                             GameLoading::Game(mut load_game) => {
@@ -711,6 +768,7 @@ impl ClientMapLoading {
                                                                 cur: None,
                                                                 state: unpredicted_game,
                                                             },
+                                                            theme_ambiance: prepare.theme_ambiance,
                                                         }),
                                                     ));
                                                 }
@@ -736,6 +794,7 @@ impl ClientMapLoading {
                                                     map_name: prepare.map_name,
                                                     game_mod: prepare.game_mod,
                                                     game_options: prepare.game_options,
+                                                    theme_ambiance: prepare.theme_ambiance,
                                                 },
                                             ))
                                         }
@@ -765,6 +824,7 @@ impl ClientMapLoading {
                                         map_name: prepare.map_name,
                                         game_mod: prepare.game_mod,
                                         game_options: prepare.game_options,
+                                        theme_ambiance: prepare.theme_ambiance,
                                     }))
                                 }
                             }
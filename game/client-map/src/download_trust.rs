@@ -0,0 +1,190 @@
+//! Which game servers the user trusts to download
+//! maps & wasm modules from.
+//!
+//! Servers of the trusted communities & servers in the local
+//! network are always trusted, for all others the user is asked once.
+
+use std::{
+    collections::BTreeMap,
+    net::{IpAddr, SocketAddr},
+    path::Path,
+};
+
+use base::hash::{Hash, fmt_hash};
+use base_io_traits::fs_traits::FileSystemInterface;
+use serde::{Deserialize, Serialize};
+
+pub const DOWNLOAD_TRUST_PATH: &str = "download_trust.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadTrustCheck {
+    /// Downloads can start without asking.
+    Trusted,
+    /// The user declined downloads from this server before.
+    Declined,
+    /// The user must be asked first.
+    Prompt,
+}
+
+/// Whether the server runs on this machine or in the local network.
+pub fn is_local_addr(addr: &SocketAddr) -> bool {
+    let is_local_v4 = |ip: std::net::Ipv4Addr| {
+        ip.is_loopback() || ip.is_private() || ip.is_link_local() || ip.is_unspecified()
+    };
+    match addr.ip() {
+        IpAddr::V4(ip) => is_local_v4(ip),
+        IpAddr::V6(ip) => {
+            ip.is_loopback()
+                || ip.is_unique_local()
+                || ip.is_unicast_link_local()
+                || ip.is_unspecified()
+                || ip.to_ipv4_mapped().is_some_and(is_local_v4)
+        }
+    }
+}
+
+/// The remembered decisions of the user,
+/// by the fingerprint of the server certificate.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct DownloadTrust {
+    servers: BTreeMap<String, bool>,
+}
+
+impl DownloadTrust {
+    /// Loads the decisions, a missing or invalid file
+    /// starts without decisions.
+    pub async fn load(fs: &dyn FileSystemInterface, path: &Path) -> Self {
+        fs.read_file(path)
+            .await
+            .ok()
+            .and_then(|file| serde_json::from_slice(&file).ok())
+            .unwrap_or_default()
+    }
+
+    pub async fn save(&self, fs: &dyn FileSystemInterface, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs.create_dir(dir).await?;
+        }
+        fs.write_file(path, serde_json::to_vec_pretty(self)?)
+            .await?;
+        Ok(())
+    }
+
+    /// `fingerprint` is the hash of the server certificate,
+    /// without one no decision can be remembered.
+    pub fn check(
+        &self,
+        addr: &SocketAddr,
+        fingerprint: Option<&Hash>,
+        trusted_community: bool,
+    ) -> DownloadTrustCheck {
+        if trusted_community || is_local_addr(addr) {
+            return DownloadTrustCheck::Trusted;
+        }
+        match fingerprint.and_then(|fingerprint| self.servers.get(&fmt_hash(fingerprint))) {
+            Some(true) => DownloadTrustCheck::Trusted,
+            Some(false) => DownloadTrustCheck::Declined,
+            None => DownloadTrustCheck::Prompt,
+        }
+    }
+
+    pub fn remember(&mut self, fingerprint: &Hash, trusted: bool) {
+        self.servers.insert(fmt_hash(fingerprint), trusted);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{net::SocketAddr, path::Path, sync::Arc};
+
+    use base::hash::generate_hash_for;
+    use base_fs::filesys::FileSystem;
+    use base_io::io::IoFileSys;
+
+    use super::{DownloadTrust, DownloadTrustCheck, is_local_addr};
+
+    fn create_io() -> IoFileSys {
+        let workspace_root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../");
+        std::env::set_current_dir(workspace_root).unwrap();
+        IoFileSys::new(|rt| {
+            Arc::new(
+                FileSystem::new(rt, "ddnet-test", "ddnet-test", "ddnet-test", "ddnet-test")
+                    .unwrap(),
+            )
+        })
+    }
+
+    #[test]
+    fn local_addresses() {
+        for addr in [
+            "127.0.0.1:8303",
+            "192.168.0.10:8303",
+            "10.1.2.3:8303",
+            "[::1]:8303",
+            "[fd00::1]:8303",
+            "[::ffff:192.168.1.1]:8303",
+        ] {
+            assert!(is_local_addr(&addr.parse().unwrap()), "{addr}");
+        }
+        for addr in ["1.2.3.4:8303", "[2001:db8::1]:8303"] {
+            assert!(!is_local_addr(&addr.parse().unwrap()), "{addr}");
+        }
+    }
+
+    #[test]
+    fn decisions_persist() {
+        let io = create_io();
+        let path: &Path = "downloaded/test/download_trust/trust.json".as_ref();
+        let remote: SocketAddr = "1.2.3.4:8303".parse().unwrap();
+        let trusted = generate_hash_for(b"trusted server");
+        let declined = generate_hash_for(b"declined server");
+        let unknown = generate_hash_for(b"unknown server");
+
+        let fs = io.fs.clone();
+        io.rt
+            .spawn(async move {
+                let _ = fs.remove_file(path).await;
+
+                let mut trust = DownloadTrust::load(&*fs, path).await;
+                assert_eq!(
+                    trust.check(&remote, Some(&trusted), false),
+                    DownloadTrustCheck::Prompt
+                );
+                trust.remember(&trusted, true);
+                trust.remember(&declined, false);
+                trust.save(&*fs, path).await?;
+
+                let trust = DownloadTrust::load(&*fs, path).await;
+                assert_eq!(
+                    trust.check(&remote, Some(&trusted), false),
+                    DownloadTrustCheck::Trusted
+                );
+                assert_eq!(
+                    trust.check(&remote, Some(&declined), false),
+                    DownloadTrustCheck::Declined
+                );
+                assert_eq!(
+                    trust.check(&remote, Some(&unknown), false),
+                    DownloadTrustCheck::Prompt
+                );
+                // nothing can be remembered without a certificate
+                assert_eq!(
+                    trust.check(&remote, None, false),
+                    DownloadTrustCheck::Prompt
+                );
+
+                // community & local servers never prompt
+                assert_eq!(
+                    trust.check(&remote, Some(&declined), true),
+                    DownloadTrustCheck::Trusted
+                );
+                assert_eq!(
+                    trust.check(&"127.0.0.1:8303".parse().unwrap(), None, false),
+                    DownloadTrustCheck::Trusted
+                );
+                anyhow::Ok(())
+            })
+            .get()
+            .unwrap();
+    }
+}
@@ -1,3 +1,6 @@
 #![allow(clippy::too_many_arguments)]
 
+pub mod cert_pins;
 pub mod client_map;
+pub mod download_trust;
+pub mod partial_download;
@@ -0,0 +1,560 @@
+//! Downloads of large files that can be resumed after
+//! the transfer was interrupted.
+//!
+//! The received data is kept in a `<file>.part` file next to the
+//! destination, together with a small `<file>.part.json` sidecar
+//! that records the expected hash & size of the whole file.
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::anyhow;
+use base::hash::{Hash, generate_hash_for};
+use base_io_traits::{
+    fs_traits::{FileSystemEntryTy, FileSystemInterface},
+    http_traits::{HttpClientInterface, HttpError},
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// How often an interrupted transfer is resumed
+/// before the download fails.
+const MAX_DOWNLOAD_ATTEMPTS: usize = 3;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PartialDownloadInfo {
+    hash: Hash,
+    /// The size of the whole file, if known.
+    size: Option<u64>,
+    /// Seconds since the unix epoch when the download was started.
+    started: u64,
+}
+
+fn partial_paths(file_path: &Path) -> (PathBuf, PathBuf) {
+    let mut part_path = file_path.as_os_str().to_owned();
+    part_path.push(".part");
+    let mut info_path = part_path.clone();
+    info_path.push(".json");
+    (part_path.into(), info_path.into())
+}
+
+async fn read_info(fs: &dyn FileSystemInterface, info_path: &Path) -> Option<PartialDownloadInfo> {
+    let file = fs.read_file(info_path).await.ok()?;
+    serde_json::from_slice(&file).ok()
+}
+
+async fn write_info(
+    fs: &dyn FileSystemInterface,
+    info_path: &Path,
+    info: &PartialDownloadInfo,
+) -> anyhow::Result<()> {
+    fs.write_file(info_path, serde_json::to_vec(info)?).await?;
+    Ok(())
+}
+
+async fn remove_partial(fs: &dyn FileSystemInterface, part_path: &Path, info_path: &Path) {
+    // the files might not exist
+    let _ = fs.remove_file(part_path).await;
+    let _ = fs.remove_file(info_path).await;
+}
+
+/// The error of downloads that exceed the size limit.
+pub fn max_size_err(max_size: u64) -> anyhow::Error {
+    anyhow!(
+        "the file is larger than the download size limit of {:.2} MiB",
+        max_size as f64 / (1024.0 * 1024.0)
+    )
+}
+
+/// Downloads the file with the given hash to `file_path`.
+///
+/// If a previous download of the same file was interrupted,
+/// only the missing part is requested from the server.
+/// The file is only moved to `file_path` if its hash matches
+/// and `validate` succeeded.
+/// Files larger than `max_size` bytes are aborted & removed.
+///
+/// `now` is the time since the unix epoch.
+pub async fn download_resumable(
+    fs: &dyn FileSystemInterface,
+    http: &dyn HttpClientInterface,
+    url: Url,
+    hash: &Hash,
+    file_path: &Path,
+    now: Duration,
+    max_size: u64,
+    validate: impl FnOnce(&[u8]) -> anyhow::Result<()>,
+) -> anyhow::Result<Vec<u8>> {
+    let (part_path, info_path) = partial_paths(file_path);
+    if let Some(dir) = file_path.parent() {
+        fs.create_dir(dir).await?;
+    }
+
+    let (mut info, mut data) = match read_info(fs, &info_path)
+        .await
+        .filter(|info| info.hash == *hash)
+    {
+        Some(info) => {
+            let data = fs.read_file(&part_path).await.unwrap_or_default();
+            (info, data)
+        }
+        None => (
+            PartialDownloadInfo {
+                hash: *hash,
+                size: None,
+                started: now.as_secs(),
+            },
+            Vec::new(),
+        ),
+    };
+    if data.is_empty() {
+        fs.write_file(&part_path, Vec::new()).await?;
+        write_info(fs, &info_path, &info).await?;
+    }
+
+    let mut complete = false;
+    for _ in 0..MAX_DOWNLOAD_ATTEMPTS {
+        let offset = data.len() as u64;
+        let res = match http.download_binary_range(url.clone(), hash, offset).await {
+            Ok(res) => res,
+            // the partial file does not fit to the file on the server
+            Err(HttpError::Status(416)) if offset > 0 => {
+                data.clear();
+                fs.write_file(&part_path, Vec::new()).await?;
+                continue;
+            }
+            Err(err) => {
+                return Err(anyhow!("failed to download file: {err}"));
+            }
+        };
+
+        let received_len = res.offset + res.data.len() as u64;
+        if res.total_len.is_some_and(|size| size > max_size) || received_len > max_size {
+            remove_partial(fs, &part_path, &info_path).await;
+            return Err(max_size_err(max_size));
+        }
+
+        if res.offset == offset {
+            fs.append_file(&part_path, res.data.to_vec()).await?;
+            data.extend_from_slice(&res.data);
+        } else if res.offset == 0 {
+            // the server does not support ranges, start over
+            fs.write_file(&part_path, res.data.to_vec()).await?;
+            data = res.data.to_vec();
+        } else {
+            remove_partial(fs, &part_path, &info_path).await;
+            return Err(anyhow!(
+                "server sent an unexpected range starting at {}, expected {offset}",
+                res.offset
+            ));
+        }
+
+        if res.total_len.is_some() && res.total_len != info.size {
+            info.size = res.total_len;
+            write_info(fs, &info_path, &info).await?;
+        }
+
+        complete = res.complete || info.size.is_some_and(|size| data.len() as u64 >= size);
+        if complete {
+            break;
+        }
+    }
+    anyhow::ensure!(
+        complete,
+        "download was interrupted, {} of {} bytes are kept for resuming",
+        data.len(),
+        info.size
+            .map(|size| size.to_string())
+            .unwrap_or_else(|| "?".to_string())
+    );
+
+    if generate_hash_for(&data) != *hash {
+        remove_partial(fs, &part_path, &info_path).await;
+        return Err(anyhow!("file hash mismatched for {url}"));
+    }
+    if let Err(err) = validate(&data) {
+        remove_partial(fs, &part_path, &info_path).await;
+        return Err(err);
+    }
+
+    fs.rename_file(&part_path, file_path).await?;
+    let _ = fs.remove_file(&info_path).await;
+    Ok(data)
+}
+
+/// Removes partial downloads in `dir` that were started
+/// longer than `max_age` ago, or whose sidecar is missing.
+///
+/// `now` is the time since the unix epoch.
+pub async fn remove_stale_partial_downloads(
+    fs: &dyn FileSystemInterface,
+    dir: &Path,
+    now: Duration,
+    max_age: Duration,
+) -> anyhow::Result<()> {
+    let entries = fs.entries_in_dir(dir).await?;
+    for name in entries.iter().filter_map(|(name, ty)| {
+        (matches!(ty, FileSystemEntryTy::File { .. }) && name.ends_with(".part")).then_some(name)
+    }) {
+        let part_path = dir.join(name);
+        let (_, info_path) = partial_paths(&part_path.with_extension(""));
+        let is_stale = read_info(fs, &info_path)
+            .await
+            .is_none_or(|info| now.saturating_sub(Duration::from_secs(info.started)) > max_age);
+        if is_stale {
+            log::info!("removing stale partial download {part_path:?}");
+            remove_partial(fs, &part_path, &info_path).await;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        path::Path,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use async_trait::async_trait;
+    use base::hash::{Hash, generate_hash_for};
+    use base_fs::filesys::FileSystem;
+    use base_io::io::IoFileSys;
+    use base_io_traits::http_traits::{
+        HttpBinaryRange, HttpClientInterface, HttpError, HttpHeaderValue,
+    };
+    use bytes::Bytes;
+    use url::Url;
+
+    use super::{
+        PartialDownloadInfo, download_resumable, partial_paths, remove_stale_partial_downloads,
+    };
+
+    /// The amount of bytes sent before a transfer is interrupted.
+    const INTERRUPTED_LEN: usize = 1000;
+
+    /// Serves a single file, the first `interruptions`
+    /// transfers are interrupted.
+    #[derive(Debug)]
+    struct FakeHttp {
+        file: Vec<u8>,
+        supports_ranges: bool,
+        interruptions: Mutex<usize>,
+        requested_offsets: Mutex<Vec<u64>>,
+    }
+
+    impl FakeHttp {
+        fn new(file: Vec<u8>, supports_ranges: bool, interruptions: usize) -> Self {
+            Self {
+                file,
+                supports_ranges,
+                interruptions: Mutex::new(interruptions),
+                requested_offsets: Default::default(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl HttpClientInterface for FakeHttp {
+        async fn download_text(&self, _url: Url) -> anyhow::Result<String, HttpError> {
+            Err(HttpError::Request)
+        }
+
+        async fn download_binary_secure(&self, _url: Url) -> anyhow::Result<Bytes, HttpError> {
+            Err(HttpError::Request)
+        }
+
+        async fn download_binary(
+            &self,
+            _url: Url,
+            _hash: &Hash,
+        ) -> anyhow::Result<Bytes, HttpError> {
+            Ok(self.file.clone().into())
+        }
+
+        async fn download_binary_range(
+            &self,
+            _url: Url,
+            _hash: &Hash,
+            offset: u64,
+        ) -> anyhow::Result<HttpBinaryRange, HttpError> {
+            self.requested_offsets.lock().unwrap().push(offset);
+            let offset = if self.supports_ranges { offset } else { 0 };
+            let mut data = &self.file[offset as usize..];
+            let mut interruptions = self.interruptions.lock().unwrap();
+            let interrupted = *interruptions > 0;
+            if interrupted {
+                *interruptions -= 1;
+                data = &data[..INTERRUPTED_LEN];
+            }
+            Ok(HttpBinaryRange {
+                offset,
+                total_len: Some(self.file.len() as u64),
+                data: data.to_vec().into(),
+                complete: !interrupted,
+            })
+        }
+
+        async fn post_json(&self, _url: Url, _data: Vec<u8>) -> anyhow::Result<Vec<u8>, HttpError> {
+            Err(HttpError::Request)
+        }
+
+        async fn custom_request(
+            &self,
+            _url: Url,
+            _headers: Vec<HttpHeaderValue>,
+            _content: Option<Vec<u8>>,
+        ) -> anyhow::Result<Bytes, HttpError> {
+            Err(HttpError::Request)
+        }
+    }
+
+    fn create_io() -> IoFileSys {
+        let workspace_root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../");
+        std::env::set_current_dir(workspace_root).unwrap();
+        IoFileSys::new(|rt| {
+            Arc::new(
+                FileSystem::new(rt, "ddnet-test", "ddnet-test", "ddnet-test", "ddnet-test")
+                    .unwrap(),
+            )
+        })
+    }
+
+    fn test_file() -> Vec<u8> {
+        (0..100_000u32).flat_map(|i| i.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn interrupted_and_resumed() {
+        let io = create_io();
+        let file = test_file();
+        let hash = generate_hash_for(&file);
+        let url: Url = "http://localhost/map.twmap.tar".try_into().unwrap();
+        let file_path: &Path = "downloaded/test/partial_download/resumed.twmap.tar".as_ref();
+        let (part_path, info_path) = partial_paths(file_path);
+
+        let fs = io.fs.clone();
+        io.rt
+            .spawn(async move {
+                let _ = fs.remove_file(file_path).await;
+                let _ = fs.remove_file(&part_path).await;
+                let _ = fs.remove_file(&info_path).await;
+
+                // the first transfer is interrupted and retried
+                // with a range request for the missing part
+                let http = FakeHttp::new(file.clone(), true, 1);
+                let res = download_resumable(
+                    &*fs,
+                    &http,
+                    url.clone(),
+                    &hash,
+                    file_path,
+                    Duration::ZERO,
+                    u64::MAX,
+                    |_| Ok(()),
+                )
+                .await?;
+                assert_eq!(res, file);
+                assert_eq!(*http.requested_offsets.lock().unwrap(), vec![0, 1000]);
+                assert_eq!(fs.read_file(file_path).await?, file);
+                assert!(!fs.file_exists(&part_path).await);
+                assert!(!fs.file_exists(&info_path).await);
+
+                // a server without range support sends the whole file again
+                let http = FakeHttp::new(file.clone(), false, 1);
+                let res = download_resumable(
+                    &*fs,
+                    &http,
+                    url.clone(),
+                    &hash,
+                    file_path,
+                    Duration::ZERO,
+                    u64::MAX,
+                    |_| Ok(()),
+                )
+                .await?;
+                assert_eq!(res, file);
+                assert_eq!(*http.requested_offsets.lock().unwrap(), vec![0, 1000]);
+                anyhow::Ok(())
+            })
+            .get()
+            .unwrap();
+    }
+
+    #[test]
+    fn resume_across_attempts() {
+        let io = create_io();
+        let file = test_file();
+        let hash = generate_hash_for(&file);
+        let url: Url = "http://localhost/map.twmap.tar".try_into().unwrap();
+        let file_path: &Path = "downloaded/test/partial_download/across.twmap.tar".as_ref();
+        let (part_path, info_path) = partial_paths(file_path);
+
+        let fs = io.fs.clone();
+        io.rt
+            .spawn(async move {
+                let _ = fs.remove_file(file_path).await;
+
+                // simulate a download that was interrupted in a previous session
+                fs.create_dir(file_path.parent().unwrap()).await?;
+                fs.write_file(&part_path, file[..5000].to_vec()).await?;
+                fs.write_file(
+                    &info_path,
+                    serde_json::to_vec(&PartialDownloadInfo {
+                        hash,
+                        size: Some(file.len() as u64),
+                        started: 0,
+                    })?,
+                )
+                .await?;
+
+                let http = FakeHttp::new(file.clone(), true, 0);
+                let res = download_resumable(
+                    &*fs,
+                    &http,
+                    url.clone(),
+                    &hash,
+                    file_path,
+                    Duration::ZERO,
+                    u64::MAX,
+                    |_| Ok(()),
+                )
+                .await?;
+                assert_eq!(res, file);
+                assert_eq!(*http.requested_offsets.lock().unwrap(), vec![5000]);
+
+                // a corrupted partial file fails the hash check and is removed
+                fs.write_file(&part_path, vec![0; 5000]).await?;
+                fs.write_file(
+                    &info_path,
+                    serde_json::to_vec(&PartialDownloadInfo {
+                        hash,
+                        size: Some(file.len() as u64),
+                        started: 0,
+                    })?,
+                )
+                .await?;
+                let http = FakeHttp::new(file.clone(), true, 0);
+                assert!(
+                    download_resumable(
+                        &*fs,
+                        &http,
+                        url.clone(),
+                        &hash,
+                        file_path,
+                        Duration::ZERO,
+                        u64::MAX,
+                        |_| Ok(()),
+                    )
+                    .await
+                    .is_err()
+                );
+                assert!(!fs.file_exists(&part_path).await);
+                anyhow::Ok(())
+            })
+            .get()
+            .unwrap();
+    }
+
+    #[test]
+    fn size_limit() {
+        let io = create_io();
+        let file = test_file();
+        let hash = generate_hash_for(&file);
+        let url: Url = "http://localhost/map.twmap.tar".try_into().unwrap();
+        let file_path: &Path = "downloaded/test/partial_download/limit.twmap.tar".as_ref();
+        let (part_path, info_path) = partial_paths(file_path);
+
+        let fs = io.fs.clone();
+        io.rt
+            .spawn(async move {
+                let _ = fs.remove_file(file_path).await;
+
+                // the file is larger than the limit, nothing is kept
+                let http = FakeHttp::new(file.clone(), true, 1);
+                let err = download_resumable(
+                    &*fs,
+                    &http,
+                    url.clone(),
+                    &hash,
+                    file_path,
+                    Duration::ZERO,
+                    file.len() as u64 - 1,
+                    |_| Ok(()),
+                )
+                .await
+                .unwrap_err();
+                assert!(err.to_string().contains("download size limit"));
+                assert_eq!(*http.requested_offsets.lock().unwrap(), vec![0]);
+                assert!(!fs.file_exists(file_path).await);
+                assert!(!fs.file_exists(&part_path).await);
+                assert!(!fs.file_exists(&info_path).await);
+
+                // exactly at the limit is fine
+                let http = FakeHttp::new(file.clone(), true, 1);
+                let res = download_resumable(
+                    &*fs,
+                    &http,
+                    url.clone(),
+                    &hash,
+                    file_path,
+                    Duration::ZERO,
+                    file.len() as u64,
+                    |_| Ok(()),
+                )
+                .await?;
+                assert_eq!(res, file);
+                anyhow::Ok(())
+            })
+            .get()
+            .unwrap();
+    }
+
+    #[test]
+    fn stale_partials() {
+        let io = create_io();
+        let file = test_file();
+        let hash = generate_hash_for(&file);
+        let url: Url = "http://localhost/map.twmap.tar".try_into().unwrap();
+        let dir: &Path = "downloaded/test/partial_download/stale".as_ref();
+
+        let fs = io.fs.clone();
+        io.rt
+            .spawn(async move {
+                let day = Duration::from_secs(60 * 60 * 24);
+                for (name, started) in [
+                    ("old.twmap.tar", Duration::ZERO),
+                    ("new.twmap.tar", day * 9),
+                ] {
+                    // every attempt is interrupted, so the partial download is kept
+                    let http = FakeHttp::new(file.clone(), true, usize::MAX);
+                    let res = download_resumable(
+                        &*fs,
+                        &http,
+                        url.clone(),
+                        &hash,
+                        &dir.join(name),
+                        started,
+                        u64::MAX,
+                        |_| Ok(()),
+                    )
+                    .await;
+                    assert!(res.is_err());
+                }
+
+                remove_stale_partial_downloads(&*fs, dir, day * 10, day * 7).await?;
+                let (old_part, old_info) = partial_paths(&dir.join("old.twmap.tar"));
+                let (new_part, new_info) = partial_paths(&dir.join("new.twmap.tar"));
+                assert!(!fs.file_exists(&old_part).await);
+                assert!(!fs.file_exists(&old_info).await);
+                assert!(fs.file_exists(&new_part).await);
+                assert!(fs.file_exists(&new_info).await);
+                anyhow::Ok(())
+            })
+            .get()
+            .unwrap();
+    }
+}
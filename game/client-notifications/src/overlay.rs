@@ -1,8 +1,7 @@
 use std::time::Duration;
 
 use base::steady_clock::SteadyClock;
-use egui::{Color32, WidgetText};
-use egui_notify::{Toast, Toasts};
+use egui::{Align, Align2, Color32, Frame, Layout, Order, Rect, RichText, Stroke};
 use graphics::{
     graphics::graphics::Graphics,
     handles::{
@@ -16,7 +15,11 @@ use ui_base::{
     ui::{UiContainer, UiCreator},
     ui_render::render_ui,
 };
-use ui_generic::{generic_ui_renderer, traits::UiPageInterface};
+
+use crate::toasts::{Toast, ToastQueue, ToastSeverity};
+
+/// How many toasts there should be visible at most at once.
+const MAX_VISIBLE_TOASTS: usize = 5;
 
 /// Notifications, e.g. popups, for warnings, errors or similar events.
 pub struct ClientNotifications {
@@ -24,7 +27,12 @@ pub struct ClientNotifications {
 
     time: SteadyClock,
 
-    toasts: Toasts,
+    toasts: ToastQueue,
+    /// The input of the toasts, see [`Self::consume_input`].
+    input: egui::RawInput,
+    /// The rects of the buttons of the last frame,
+    /// in the coordinates of the raw input.
+    button_rects: Vec<Rect>,
 
     pub backend_handle: GraphicsBackendHandle,
     pub canvas_handle: GraphicsCanvasHandle,
@@ -41,7 +49,9 @@ impl ClientNotifications {
             ui,
             time: time.clone(),
 
-            toasts: Toasts::new().with_anchor(egui_notify::Anchor::BottomRight),
+            toasts: ToastQueue::new(MAX_VISIBLE_TOASTS),
+            input: Default::default(),
+            button_rects: Default::default(),
 
             backend_handle: graphics.backend_handle.clone(),
             canvas_handle: graphics.canvas_handle.clone(),
@@ -50,48 +60,68 @@ impl ClientNotifications {
         }
     }
 
+    /// Takes the clicks on the buttons of the toasts out of the input.
+    ///
+    /// All other input is only copied, so toasts never
+    /// steal the input of the game or other uis.
+    pub fn consume_input(&mut self, input: &mut egui::RawInput) {
+        if self.toasts.is_empty() {
+            return;
+        }
+        let button_rects = &self.button_rects;
+        let events = &mut self.input.events;
+        input.events.retain(|ev| match ev {
+            egui::Event::PointerMoved(_) | egui::Event::PointerGone => {
+                events.push(ev.clone());
+                true
+            }
+            egui::Event::PointerButton { pos, .. }
+                if button_rects.iter().any(|rect| rect.contains(*pos)) =>
+            {
+                events.push(ev.clone());
+                false
+            }
+            _ => true,
+        });
+    }
+
     #[instrument(level = "trace", skip_all)]
     pub fn render(&mut self) {
+        let cur_time = self.time.now();
+        self.toasts.update(&cur_time);
+        let input = std::mem::take(&mut self.input);
+        self.button_rects.clear();
         if self.toasts.is_empty() {
             return;
         }
 
-        struct Render;
-
-        impl UiPageInterface<&mut Toasts> for Render {
-            fn render(
-                &mut self,
-                ui: &mut egui::Ui,
-                pipe: &mut UiRenderPipe<&mut Toasts>,
-                _ui_state: &mut ui_base::types::UiState,
-            ) {
-                pipe.user_data.show(ui.ctx());
-            }
-        }
-        generic_ui_renderer::render(
-            &self.backend_handle,
-            &self.texture_handle,
-            &self.stream_handle,
-            &self.canvas_handle,
-            &mut self.ui,
-            &mut Render,
-            &mut UiRenderPipe::new(self.time.now(), &mut &mut self.toasts),
-            Default::default(),
-        );
         let canvas_width = self.canvas_handle.canvas_width();
         let canvas_height = self.canvas_handle.canvas_height();
         let pixels_per_point = self.canvas_handle.pixels_per_point();
+        let toasts = &mut self.toasts;
+        let mut button_rects = Vec::new();
         let (screen_rect, full_output, zoom_level) = self.ui.render(
             canvas_width,
             canvas_height,
             pixels_per_point,
             |ui, _, _| {
-                self.toasts.show(ui.ctx());
+                render_toasts(ui, toasts, &cur_time, &mut button_rects);
             },
-            &mut UiRenderPipe::new(self.time.now(), &mut ()),
-            Default::default(),
+            &mut UiRenderPipe::new(cur_time, &mut ()),
+            input,
             false,
         );
+        // the ui scales the input down by the zoom
+        let zoom_diff = zoom_level / pixels_per_point;
+        self.button_rects = button_rects
+            .into_iter()
+            .map(|rect| {
+                Rect::from_min_max(
+                    (rect.min.to_vec2() * zoom_diff).to_pos2(),
+                    (rect.max.to_vec2() * zoom_diff).to_pos2(),
+                )
+            })
+            .collect();
         render_ui(
             &mut self.ui,
             full_output,
@@ -102,47 +132,99 @@ impl ClientNotifications {
             &self.stream_handle,
             false,
         );
-        self.truncate();
     }
 
-    fn truncate(&mut self) {
-        // how many toasts there should be visible at most at once
-        if self.toasts.len() > 10 {
-            self.toasts.dismiss_oldest_toast();
-        }
+    /// Adds a toast, e.g. with an action button.
+    #[instrument(level = "trace", skip_all)]
+    pub fn add(&mut self, toast: Toast) {
+        self.toasts.push(toast, &self.time.now());
+    }
+
+    /// The names of the actions of the toasts that were clicked
+    /// since the last call.
+    pub fn take_activated_actions(&mut self) -> Vec<String> {
+        self.toasts.take_activated_actions()
     }
 
     #[instrument(level = "trace", skip_all)]
-    pub fn add_info(&mut self, text: impl Into<WidgetText>, duration: Duration) {
-        // upper limit in case of abuse
-        if self.toasts.len() >= 1000 {
-            return;
-        }
-        self.toasts.info(text).duration(Some(duration));
-        self.truncate();
+    pub fn add_info(&mut self, text: impl Into<String>, duration: Duration) {
+        self.add(Toast::new(ToastSeverity::Info, text, duration));
     }
 
     #[instrument(level = "trace", skip_all)]
-    pub fn add_warn(&mut self, text: impl Into<WidgetText>, duration: Duration) {
-        // upper limit in case of abuse
-        if self.toasts.len() >= 1000 {
-            return;
-        }
-        let mut toast = Toast::warning(text);
-        toast.duration(Some(duration));
-        self.toasts.add(toast);
-        self.truncate();
+    pub fn add_success(&mut self, text: impl Into<String>, duration: Duration) {
+        self.add(Toast::new(ToastSeverity::Success, text, duration));
     }
 
     #[instrument(level = "trace", skip_all)]
-    pub fn add_err(&mut self, text: impl Into<WidgetText>, duration: Duration) {
-        // upper limit in case of abuse
-        if self.toasts.len() >= 1000 {
-            return;
-        }
-        let mut toast = Toast::error(text);
-        toast.duration(Some(duration));
-        self.toasts.add(toast);
-        self.truncate();
+    pub fn add_warn(&mut self, text: impl Into<String>, duration: Duration) {
+        self.add(Toast::new(ToastSeverity::Warning, text, duration));
+    }
+
+    #[instrument(level = "trace", skip_all)]
+    pub fn add_err(&mut self, text: impl Into<String>, duration: Duration) {
+        self.add(Toast::new(ToastSeverity::Error, text, duration));
+    }
+}
+
+/// Renders the toasts in the bottom right corner,
+/// the most severe toast is closest to the corner.
+fn render_toasts(
+    ui: &mut egui::Ui,
+    toasts: &mut ToastQueue,
+    cur_time: &Duration,
+    button_rects: &mut Vec<Rect>,
+) {
+    let mut activated = None;
+    egui::Area::new(egui::Id::new("client-toasts"))
+        .anchor(Align2::RIGHT_BOTTOM, egui::vec2(-10.0, -10.0))
+        .order(Order::Foreground)
+        .show(ui.ctx(), |ui| {
+            ui.with_layout(Layout::bottom_up(Align::Max), |ui| {
+                let overflow = toasts.overflow();
+                if overflow > 0 {
+                    ui.label(RichText::new(format!("+{overflow} more")).weak());
+                }
+                for queued in toasts.visible() {
+                    let toast = &queued.toast;
+                    Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.set_max_width(300.0);
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(toast.icon()).color(toast.severity.color()));
+                            ui.label(&toast.text);
+                            if queued.count > 1 {
+                                ui.label(RichText::new(format!("x{}", queued.count)).weak());
+                            }
+                            if let Some(action) = &toast.action {
+                                let res = ui.button(&action.label);
+                                button_rects.push(res.rect);
+                                if res.clicked() {
+                                    activated = Some(queued.id);
+                                }
+                            } else if toast.duration.is_none() {
+                                // sticky toasts must be closeable
+                                let res = ui.button("\u{f00d}");
+                                button_rects.push(res.rect);
+                                if res.clicked() {
+                                    activated = Some(queued.id);
+                                }
+                            }
+                        });
+                        if let Some(remaining) = queued.remaining(cur_time) {
+                            let rect = ui.min_rect();
+                            ui.painter().line_segment(
+                                [
+                                    rect.left_bottom(),
+                                    rect.left_bottom() + egui::vec2(rect.width() * remaining, 0.0),
+                                ],
+                                Stroke::new(2.0, toast.severity.color()),
+                            );
+                        }
+                    });
+                }
+            });
+        });
+    if let Some(id) = activated {
+        toasts.activate(id);
     }
 }
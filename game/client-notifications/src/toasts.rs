@@ -0,0 +1,328 @@
+use std::{collections::VecDeque, time::Duration};
+
+use egui::Color32;
+
+/// Upper limit of queued toasts, in case of abuse.
+const MAX_QUEUED: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ToastSeverity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastSeverity {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            ToastSeverity::Info => "\u{f05a}",
+            ToastSeverity::Success => "\u{f058}",
+            ToastSeverity::Warning => "\u{f071}",
+            ToastSeverity::Error => "\u{f057}",
+        }
+    }
+
+    pub fn color(&self) -> Color32 {
+        match self {
+            ToastSeverity::Info => Color32::LIGHT_BLUE,
+            ToastSeverity::Success => Color32::LIGHT_GREEN,
+            ToastSeverity::Warning => Color32::YELLOW,
+            ToastSeverity::Error => Color32::LIGHT_RED,
+        }
+    }
+}
+
+/// A button on the toast, clicking it dismisses the toast
+/// and reports the action's name,
+/// see [`ToastQueue::take_activated_actions`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToastAction {
+    pub label: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Toast {
+    pub severity: ToastSeverity,
+    pub text: String,
+    /// Overrides the icon of the severity.
+    pub icon: Option<String>,
+    pub action: Option<ToastAction>,
+    /// `None` keeps the toast until it's dismissed.
+    pub duration: Option<Duration>,
+}
+
+impl Toast {
+    pub fn new(severity: ToastSeverity, text: impl Into<String>, duration: Duration) -> Self {
+        Self {
+            severity,
+            text: text.into(),
+            icon: None,
+            action: None,
+            duration: Some(duration),
+        }
+    }
+
+    pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    pub fn with_action(mut self, label: impl Into<String>, name: impl Into<String>) -> Self {
+        self.action = Some(ToastAction {
+            label: label.into(),
+            name: name.into(),
+        });
+        self
+    }
+
+    /// The toast stays until it's dismissed.
+    pub fn sticky(mut self) -> Self {
+        self.duration = None;
+        self
+    }
+
+    pub fn icon(&self) -> &str {
+        self.icon.as_deref().unwrap_or(self.severity.icon())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct QueuedToast {
+    pub id: u64,
+    pub toast: Toast,
+    /// How often the toast was added, see [`ToastQueue::push`].
+    pub count: usize,
+    /// The expire timer only runs while the toast is visible.
+    shown_at: Option<Duration>,
+}
+
+impl QueuedToast {
+    /// The remaining time in `[0..1]`, `None` for sticky toasts
+    /// or toasts that are not visible yet.
+    pub fn remaining(&self, cur_time: &Duration) -> Option<f32> {
+        let shown_at = self.shown_at?;
+        let duration = self.toast.duration?;
+        Some(
+            1.0 - (cur_time.saturating_sub(shown_at).as_secs_f32()
+                / duration.as_secs_f32().max(f32::EPSILON))
+            .clamp(0.0, 1.0),
+        )
+    }
+}
+
+/// The queue of all toasts.
+///
+/// Only [`ToastQueue::max_visible`] toasts are visible at once,
+/// more severe toasts are shown first, toasts of the same severity
+/// in the order they were added.
+#[derive(Debug)]
+pub struct ToastQueue {
+    toasts: VecDeque<QueuedToast>,
+    next_id: u64,
+    activated_actions: Vec<String>,
+
+    pub max_visible: usize,
+}
+
+impl ToastQueue {
+    pub fn new(max_visible: usize) -> Self {
+        Self {
+            toasts: Default::default(),
+            next_id: 0,
+            activated_actions: Default::default(),
+
+            max_visible,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.toasts.len()
+    }
+
+    /// Adds the toast to the queue.
+    ///
+    /// An identical toast that is still queued is reused instead,
+    /// only its counter increases and its timer restarts.
+    pub fn push(&mut self, toast: Toast, cur_time: &Duration) {
+        if let Some(queued) = self.toasts.iter_mut().find(|queued| queued.toast == toast) {
+            queued.count += 1;
+            if queued.shown_at.is_some() {
+                queued.shown_at = Some(*cur_time);
+            }
+            return;
+        }
+        if self.toasts.len() >= MAX_QUEUED {
+            return;
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        let index = self
+            .toasts
+            .partition_point(|queued| queued.toast.severity >= toast.severity);
+        self.toasts.insert(
+            index,
+            QueuedToast {
+                id,
+                toast,
+                count: 1,
+                shown_at: None,
+            },
+        );
+    }
+
+    /// Removes expired toasts & starts the timers of newly visible toasts.
+    pub fn update(&mut self, cur_time: &Duration) {
+        self.toasts.retain(|queued| {
+            queued
+                .shown_at
+                .zip(queued.toast.duration)
+                .is_none_or(|(shown_at, duration)| cur_time.saturating_sub(shown_at) < duration)
+        });
+        for (index, queued) in self.toasts.iter_mut().enumerate() {
+            if index < self.max_visible {
+                queued.shown_at.get_or_insert(*cur_time);
+            } else {
+                // e.g. more severe toasts were added
+                queued.shown_at = None;
+            }
+        }
+    }
+
+    pub fn visible(&self) -> impl Iterator<Item = &QueuedToast> {
+        self.toasts.iter().take(self.max_visible)
+    }
+
+    /// The amount of toasts that are queued, but not visible.
+    pub fn overflow(&self) -> usize {
+        self.toasts.len().saturating_sub(self.max_visible)
+    }
+
+    pub fn dismiss(&mut self, id: u64) {
+        self.toasts.retain(|queued| queued.id != id);
+    }
+
+    /// Dismisses the toast & reports its action.
+    pub fn activate(&mut self, id: u64) {
+        if let Some(index) = self.toasts.iter().position(|queued| queued.id == id) {
+            let queued = self.toasts.remove(index).unwrap();
+            self.activated_actions
+                .extend(queued.toast.action.map(|action| action.name));
+        }
+    }
+
+    /// The names of the actions of the toasts that were clicked.
+    pub fn take_activated_actions(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.activated_actions)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{Toast, ToastQueue, ToastSeverity};
+
+    fn texts(queue: &ToastQueue) -> Vec<&str> {
+        queue
+            .visible()
+            .map(|queued| queued.toast.text.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn ordering_and_overflow() {
+        let mut queue = ToastQueue::new(3);
+        let secs = Duration::from_secs;
+        queue.push(Toast::new(ToastSeverity::Info, "info 1", secs(5)), &secs(0));
+        queue.push(Toast::new(ToastSeverity::Info, "info 2", secs(5)), &secs(0));
+        queue.push(Toast::new(ToastSeverity::Error, "error", secs(5)), &secs(0));
+        queue.push(
+            Toast::new(ToastSeverity::Success, "done", secs(5)),
+            &secs(0),
+        );
+        queue.push(Toast::new(ToastSeverity::Info, "info 3", secs(5)), &secs(0));
+
+        // more severe first, else in order
+        assert_eq!(texts(&queue), ["error", "done", "info 1"]);
+        assert_eq!(queue.overflow(), 2);
+
+        let id = queue.visible().next().unwrap().id;
+        queue.dismiss(id);
+        assert_eq!(texts(&queue), ["done", "info 1", "info 2"]);
+        assert_eq!(queue.overflow(), 1);
+    }
+
+    #[test]
+    fn coalescing() {
+        let mut queue = ToastQueue::new(3);
+        let secs = Duration::from_secs;
+        let toast = Toast::new(ToastSeverity::Warning, "server full", secs(5));
+        queue.push(toast.clone(), &secs(0));
+        queue.push(Toast::new(ToastSeverity::Info, "other", secs(5)), &secs(0));
+        queue.push(toast.clone(), &secs(0));
+        queue.push(toast.clone(), &secs(0));
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.visible().next().unwrap().count, 3);
+
+        // toasts with other actions are not identical
+        queue.push(toast.clone().with_action("Retry", "retry"), &secs(0));
+        assert_eq!(queue.len(), 3);
+
+        // duplicates restart the timer
+        queue.update(&secs(0));
+        queue.push(toast.clone(), &secs(4));
+        queue.update(&secs(6));
+        assert_eq!(texts(&queue), ["server full"]);
+        queue.update(&secs(9));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn expiry() {
+        let mut queue = ToastQueue::new(1);
+        let secs = Duration::from_secs;
+        queue.push(Toast::new(ToastSeverity::Info, "first", secs(2)), &secs(0));
+        queue.push(Toast::new(ToastSeverity::Info, "second", secs(2)), &secs(0));
+        queue.push(
+            Toast::new(ToastSeverity::Info, "sticky", secs(2)).sticky(),
+            &secs(0),
+        );
+
+        queue.update(&secs(0));
+        assert_eq!(texts(&queue), ["first"]);
+        assert_eq!(
+            queue.visible().next().unwrap().remaining(&secs(1)),
+            Some(0.5)
+        );
+        // hidden toasts don't expire
+        queue.update(&secs(3));
+        assert_eq!(texts(&queue), ["second"]);
+        queue.update(&secs(4));
+        assert_eq!(texts(&queue), ["second"]);
+        queue.update(&secs(5));
+        assert_eq!(texts(&queue), ["sticky"]);
+        queue.update(&secs(1000));
+        assert_eq!(texts(&queue), ["sticky"]);
+
+        let id = queue.visible().next().unwrap().id;
+        queue.activate(id);
+        assert!(queue.is_empty());
+        // no action, nothing to report
+        assert!(queue.take_activated_actions().is_empty());
+
+        queue.push(
+            Toast::new(ToastSeverity::Success, "update", secs(2)).with_action("Open", "open"),
+            &secs(0),
+        );
+        let id = queue.visible().next().unwrap().id;
+        queue.activate(id);
+        assert_eq!(queue.take_activated_actions(), ["open"]);
+        assert!(queue.take_activated_actions().is_empty());
+    }
+}
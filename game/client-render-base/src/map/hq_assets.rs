@@ -0,0 +1,107 @@
+use game_config::config::ConfigMap;
+use image_utils::png::PngValidatorOptions;
+use serde::{Deserialize, Serialize};
+
+/// Whether the high quality variants of map images are loaded,
+/// if a map references any.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MapHqAssets {
+    pub enabled: bool,
+    /// The texture memory in bytes that may be used
+    /// by the high quality variants of a single map.
+    pub texture_memory_budget: u64,
+}
+
+impl MapHqAssets {
+    pub fn new(config: &ConfigMap) -> Self {
+        Self {
+            enabled: config.hq_assets,
+            texture_memory_budget: config.hq_assets_texture_memory as u64 * 1024 * 1024,
+        }
+    }
+
+    /// Reduces the budget by the texture memory that is already in use.
+    pub fn with_used_texture_memory(self, used: u64) -> Self {
+        Self {
+            texture_memory_budget: self.texture_memory_budget.saturating_sub(used),
+            ..self
+        }
+    }
+
+    /// The validation of downloaded images.
+    pub fn png_validator_options(&self) -> PngValidatorOptions {
+        if self.enabled {
+            PngValidatorOptions::hq()
+        } else {
+            Default::default()
+        }
+    }
+
+    /// Selects which high quality variants are used.
+    ///
+    /// `texture_sizes` contains the texture memory of every variant
+    /// in resource order, `None` if the variant could not be loaded.
+    /// Variants that don't fit into the budget anymore fall back
+    /// to the normal variant.
+    pub fn select(&self, texture_sizes: &[Option<u64>]) -> Vec<bool> {
+        let mut budget = self.enabled.then_some(self.texture_memory_budget);
+        texture_sizes
+            .iter()
+            .map(|size| {
+                if let Some((left, size)) = budget.as_mut().zip(*size)
+                    && size <= *left
+                {
+                    *left -= size;
+                    true
+                } else {
+                    false
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MapHqAssets;
+
+    #[test]
+    fn hq_selection() {
+        let hq = MapHqAssets {
+            enabled: true,
+            texture_memory_budget: 100,
+        };
+        // the second variant doesn't fit anymore, but the smaller third does
+        assert_eq!(
+            hq.select(&[Some(60), Some(50), Some(40)]),
+            vec![true, false, true]
+        );
+        // textures already in use reduce the budget
+        assert_eq!(
+            hq.with_used_texture_memory(50)
+                .select(&[Some(60), Some(40)]),
+            vec![false, true]
+        );
+        assert_eq!(hq.with_used_texture_memory(200).texture_memory_budget, 0);
+
+        let disabled = MapHqAssets {
+            enabled: false,
+            ..hq
+        };
+        assert_eq!(disabled.select(&[Some(1), Some(1)]), vec![false, false]);
+    }
+
+    #[test]
+    fn hq_missing_fallback() {
+        let hq = MapHqAssets {
+            enabled: true,
+            texture_memory_budget: 100,
+        };
+        // a missing variant falls back to the normal one
+        // and doesn't use any of the budget
+        assert_eq!(
+            hq.select(&[None, Some(100), None]),
+            vec![false, true, false]
+        );
+    }
+}
@@ -18,7 +18,10 @@ use super::{
     },
     map_pipeline::{EditorTileLayerRenderProps, MapGraphics, QuadRenderInfo, TileLayerDrawInfo},
     map_sound::MapSoundProcess,
-    map_with_visual::{MapVisual, MapVisualLayerBase},
+    map_with_visual::{MapVisual, MapVisualLayer, MapVisualLayerBase},
+    occlusion::{
+        ScreenCoverageGrid, TileLayerOcclusion, TileLayerScreenMapping, visible_row_spans,
+    },
     render_pipe::{RenderPipeline, RenderPipelineBase},
     render_tools::RenderTools,
 };
@@ -48,7 +51,7 @@ use map::{
         animations::{AnimBase, AnimPoint},
         groups::{
             MapGroupAttr, MapGroupAttrClipping,
-            layers::design::{Quad, Sound, SoundShape},
+            layers::design::{MapLayerBlendMode, Quad, Sound, SoundShape},
         },
     },
     skeleton::{
@@ -56,7 +59,11 @@ use map::{
         resources::MapResourcesSkeleton,
     },
 };
-use pool::{datatypes::PoolFxHashMap, mixed_pool::Pool as MixedPool, pool::Pool};
+use pool::{
+    datatypes::{PoolFxHashMap, PoolVec},
+    mixed_pool::Pool as MixedPool,
+    pool::Pool,
+};
 use rustc_hash::FxHashMap;
 use serde::de::DeserializeOwned;
 
@@ -68,6 +75,16 @@ use math::math::{
 use graphics_types::rendering::{BlendType, ColorRgba, State};
 use sound::sound_object::SoundObject;
 
+/// The pipeline blend state that renders a design layer with the given blend mode.
+pub fn layer_blend_type(blend_mode: MapLayerBlendMode) -> BlendType {
+    match blend_mode {
+        MapLayerBlendMode::Normal => BlendType::Alpha,
+        MapLayerBlendMode::Multiply => BlendType::Multiply,
+        MapLayerBlendMode::Additive => BlendType::AlphaAdditive,
+        MapLayerBlendMode::Screen => BlendType::Screen,
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum RenderLayerType {
     Background,
@@ -99,6 +116,7 @@ pub struct RenderMap {
     stream_handle: GraphicsStreamHandle,
 
     tile_layer_render_info_pool: MixedPool<Vec<TileLayerDrawInfo>>,
+    tile_layer_occlusion_pool: Pool<Vec<Option<TileLayerOcclusion>>>,
     pos_anims: Pool<FxHashMap<(usize, time::Duration), fvec3>>,
     color_anims: Pool<FxHashMap<(usize, time::Duration), nfvec4>>,
 
@@ -122,6 +140,7 @@ impl RenderMap {
             stream_handle: stream_handle.clone(),
 
             tile_layer_render_info_pool,
+            tile_layer_occlusion_pool: Pool::with_capacity(2),
 
             pos_anims: Pool::with_capacity(8),
             color_anims: Pool::with_capacity(8),
@@ -188,6 +207,7 @@ impl RenderMap {
         color_anim_offset: &time::Duration,
         animations: &AnimationsSkeleton<AN, AS>,
         mut color: ColorRgba,
+        occlusion: Option<&TileLayerOcclusion>,
     ) {
         let (screen_x0, screen_y0, screen_x1, screen_y1) = state.get_canvas_mapping();
         let channels = if let Some(anim) = {
@@ -297,31 +317,34 @@ impl RenderMap {
                     if x0 > x1 {
                         continue;
                     }
-                    let xr = x1 - 1;
-
-                    if visuals.tiles_of_layer[(y * width + xr) as usize].quad_offset()
-                        < visuals.tiles_of_layer[(y * width + x0) as usize].quad_offset()
-                    {
-                        panic!("Tile count wrong.");
-                    }
-
-                    let num_quads = (visuals.tiles_of_layer[(y * width + xr) as usize]
-                        .quad_offset()
-                        - visuals.tiles_of_layer[(y * width + x0) as usize].quad_offset())
-                        + (if visuals.tiles_of_layer[(y * width + xr) as usize].drawable() {
-                            1
-                        } else {
-                            0
-                        });
+                    // occluded chunks split the row into multiple draws
+                    visible_row_spans(x0, x1, y, occlusion, |x0, x1| {
+                        let xr = x1 - 1;
+
+                        if visuals.tiles_of_layer[(y * width + xr) as usize].quad_offset()
+                            < visuals.tiles_of_layer[(y * width + x0) as usize].quad_offset()
+                        {
+                            panic!("Tile count wrong.");
+                        }
 
-                    if num_quads > 0 {
-                        draws.push(TileLayerDrawInfo {
-                            quad_offset: visuals.tiles_of_layer[(y * width + x0) as usize]
-                                .quad_offset(),
-                            quad_count: num_quads,
-                            pos_y: y as f32,
-                        });
-                    }
+                        let num_quads = (visuals.tiles_of_layer[(y * width + xr) as usize]
+                            .quad_offset()
+                            - visuals.tiles_of_layer[(y * width + x0) as usize].quad_offset())
+                            + (if visuals.tiles_of_layer[(y * width + xr) as usize].drawable() {
+                                1
+                            } else {
+                                0
+                            });
+
+                        if num_quads > 0 {
+                            draws.push(TileLayerDrawInfo {
+                                quad_offset: visuals.tiles_of_layer[(y * width + x0) as usize]
+                                    .quad_offset(),
+                                quad_count: num_quads,
+                                pos_y: y as f32,
+                            });
+                        }
+                    });
                 }
 
                 color.r *= channels.r().to_num::<f32>();
@@ -1205,6 +1228,43 @@ impl RenderMap {
     ) where
         T: Borrow<TileLayerVisuals>,
         Q: Borrow<QuadLayerVisuals>,
+    {
+        self.render_layer_impl(
+            animations,
+            resources,
+            config,
+            camera,
+            cur_time,
+            cur_anim_time,
+            include_last_anim_point,
+            group_attr,
+            layer,
+            forced_texture,
+            None,
+        )
+    }
+
+    fn render_layer_impl<T, Q, AN: HiarcTrait, AS: HiarcTrait, S, A>(
+        &self,
+        animations: &AnimationsSkeleton<AN, AS>,
+        resources: &MapResourcesSkeleton<
+            (),
+            impl Borrow<TextureContainer>,
+            impl Borrow<TextureContainer2dArray>,
+            impl Borrow<SoundObject>,
+        >,
+        config: &ConfigMap,
+        camera: &dyn CameraInterface,
+        cur_time: &Duration,
+        cur_anim_time: &Duration,
+        include_last_anim_point: bool,
+        group_attr: &MapGroupAttr,
+        layer: &MapVisualLayerBase<T, Q, S, A>,
+        forced_texture: Option<ForcedTexture>,
+        occlusion: Option<&TileLayerOcclusion>,
+    ) where
+        T: Borrow<TileLayerVisuals>,
+        Q: Borrow<QuadLayerVisuals>,
     {
         // skip rendering if detail layers if not wanted
         if layer.high_detail() && !config.high_detail {
@@ -1250,7 +1310,11 @@ impl RenderMap {
                         / 100.0,
                 };
 
-                state.blend(BlendType::Alpha);
+                state.blend(if forced_texture.is_some() {
+                    BlendType::Alpha
+                } else {
+                    layer_blend_type(layer.attr.blend_mode)
+                });
 
                 let (buffer_object, shader_storage) =
                     if matches!(forced_texture, Some(ForcedTexture::TileLayerTileIndex(_))) {
@@ -1283,6 +1347,7 @@ impl RenderMap {
                     &layer.attr.color_anim_offset,
                     animations,
                     color,
+                    occlusion,
                 );
             }
             MapVisualLayerBase::Quad(layer) => {
@@ -1299,7 +1364,11 @@ impl RenderMap {
                 };
 
                 if config.show_quads {
-                    state.blend(BlendType::Alpha);
+                    state.blend(if forced_texture.is_some() {
+                        BlendType::Alpha
+                    } else {
+                        layer_blend_type(layer.attr.blend_mode)
+                    });
                     self.render_quad_layer(
                         &state,
                         texture.into(),
@@ -1429,6 +1498,7 @@ impl RenderMap {
             &time::Duration::ZERO,
             animations,
             color,
+            None,
         );
         for overlay in layer.user().borrow().overlays.iter() {
             let texture = match overlay.ty {
@@ -1449,22 +1519,122 @@ impl RenderMap {
                 &time::Duration::ZERO,
                 animations,
                 color,
+                None,
             );
         }
     }
 
+    /// Front to back pass over all design tile layers, which finds the chunks
+    /// of background tile layers that are hidden behind opaque chunks
+    /// of layers rendered after them.
+    ///
+    /// Only layers that are guaranteed to be fully opaque (no color animation,
+    /// no transparent color, no clipping, normal blending) can occlude other layers.
+    fn calc_background_occlusion(
+        &self,
+        map: &MapVisual,
+        pipe: &RenderPipelineBase,
+        background_render_layers: &[MapRenderLayer],
+        foreground_render_layers: &[MapRenderLayer],
+    ) -> Option<PoolVec<Option<TileLayerOcclusion>>> {
+        if !pipe.config.occlusion_culling
+            || pipe.config.physics_layer_opacity != 0
+            || !pipe.config.background_show_tile_layers
+        {
+            return None;
+        }
+
+        let tile_layer = |layer_ty: RenderLayerType, render_layer: &MapRenderLayer| {
+            let MapRenderLayer::Tile(render_info) = render_layer else {
+                return None;
+            };
+            let groups = if matches!(layer_ty, RenderLayerType::Background) {
+                &map.groups.background
+            } else {
+                &map.groups.foreground
+            };
+            let group = &groups[render_info.group_index];
+            let MapVisualLayer::Tile(layer) = &group.layers[render_info.layer_index] else {
+                return None;
+            };
+            if layer.layer.attr.high_detail && !pipe.config.high_detail {
+                return None;
+            }
+            let mut state = State::new();
+            pipe.camera
+                .project(&self.canvas_handle, &mut state, Some(&group.attr));
+            let mapping = TileLayerScreenMapping {
+                canvas: state.get_canvas_mapping(),
+            };
+            let can_occlude = group.attr.clipping.is_none()
+                && layer.layer.attr.blend_mode == MapLayerBlendMode::Normal
+                && layer.layer.attr.color_anim.is_none()
+                && layer.layer.attr.color.a().to_num::<f32>() >= 1.0
+                && layer.layer.attr.image_array.is_some();
+            Some((mapping, &layer.user.base.base, can_occlude))
+        };
+
+        // returns true if the layer can occlude others
+        let cover = |grid: &mut ScreenCoverageGrid,
+                     mapping: TileLayerScreenMapping,
+                     visuals: &TileLayerVisualsBase,
+                     can_occlude: bool| {
+            if let Some(chunk_opacity) = can_occlude
+                .then_some(visuals.chunk_opacity.as_ref())
+                .flatten()
+            {
+                mapping.cover(
+                    grid,
+                    chunk_opacity,
+                    visuals.width as usize,
+                    visuals.height as usize,
+                );
+                true
+            } else {
+                false
+            }
+        };
+
+        let mut grid = ScreenCoverageGrid::new();
+        let mut any_covered = false;
+        for render_layer in foreground_render_layers.iter().rev() {
+            if let Some((mapping, visuals, can_occlude)) =
+                tile_layer(RenderLayerType::Foreground, render_layer)
+            {
+                any_covered |= cover(&mut grid, mapping, visuals, can_occlude);
+            }
+        }
+
+        let mut res = self.tile_layer_occlusion_pool.new();
+        res.resize(background_render_layers.len(), None);
+        for (index, render_layer) in background_render_layers.iter().enumerate().rev() {
+            if let Some((mapping, visuals, can_occlude)) =
+                tile_layer(RenderLayerType::Background, render_layer)
+            {
+                if any_covered {
+                    res[index] =
+                        mapping.occlusion(&grid, visuals.width as usize, visuals.height as usize);
+                }
+                any_covered |= cover(&mut grid, mapping, visuals, can_occlude);
+            }
+        }
+
+        Some(res)
+    }
+
     fn render_design_impl<'a>(
         &self,
         map: &MapVisual,
         pipe: &RenderPipelineBase,
         render_layers: impl Iterator<Item = &'a MapRenderLayer>,
         layer_ty: RenderLayerType,
+        occlusion: Option<&[Option<TileLayerOcclusion>]>,
     ) {
         if pipe.config.physics_layer_opacity == 100 {
             return;
         }
 
-        for render_layer in render_layers.filter(|render_layer| {
+        for (index, render_layer) in render_layers.enumerate().filter(|(_, render_layer)| {
             if let MapRenderLayer::Tile(_) = render_layer
                 && matches!(layer_ty, RenderLayerType::Background)
                 && !pipe.config.background_show_tile_layers
@@ -1481,7 +1651,7 @@ impl RenderMap {
             };
             let group = &groups[render_info.group_index];
 
-            self.render_layer(
+            self.render_layer_impl(
                 &map.animations,
                 &map.resources,
                 pipe.config,
@@ -1492,6 +1662,9 @@ impl RenderMap {
                 &group.attr,
                 &group.layers[render_info.layer_index],
                 None,
+                occlusion
+                    .and_then(|occlusion| occlusion.get(index))
+                    .and_then(|occlusion| occlusion.as_ref()),
             );
         }
     }
@@ -1519,11 +1692,18 @@ impl RenderMap {
     }
 
     pub fn render_background(&self, pipe: &RenderPipeline) {
+        let occlusion = self.calc_background_occlusion(
+            pipe.base.map,
+            &pipe.base,
+            &pipe.buffered_map.render.background_render_layers,
+            &pipe.buffered_map.render.foreground_render_layers,
+        );
         self.render_design_impl(
             pipe.base.map,
             &pipe.base,
             pipe.buffered_map.render.background_render_layers.iter(),
             RenderLayerType::Background,
+            occlusion.as_deref().map(|occlusion| occlusion.as_slice()),
         );
         self.sound.handle_background(
             pipe.base.cur_time,
@@ -1533,6 +1713,7 @@ impl RenderMap {
             pipe.buffered_map,
             pipe.base.camera,
             pipe.base.map_sound_volume,
+            pipe.base.music_intensity,
         );
     }
 
@@ -1542,6 +1723,7 @@ impl RenderMap {
             &pipe.base,
             pipe.buffered_map.render.foreground_render_layers.iter(),
             RenderLayerType::Foreground,
+            None,
         );
         self.sound.handle_foreground(
             pipe.base.cur_time,
@@ -1551,6 +1733,7 @@ impl RenderMap {
             pipe.buffered_map,
             pipe.base.camera,
             pipe.base.map_sound_volume,
+            pipe.base.music_intensity,
         );
     }
 
@@ -1561,12 +1744,42 @@ impl RenderMap {
             &pipe.base,
             pipe.buffered_map.render.background_render_layers.iter(),
             RenderLayerType::Background,
+            None,
         );
         self.render_design_impl(
             map,
             &pipe.base,
             pipe.buffered_map.render.foreground_render_layers.iter(),
             RenderLayerType::Foreground,
+            None,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use graphics_types::rendering::BlendType;
+    use map::map::groups::layers::design::MapLayerBlendMode;
+
+    use super::layer_blend_type;
+
+    #[test]
+    fn blend_mode_pipeline() {
+        assert_eq!(
+            layer_blend_type(MapLayerBlendMode::default()),
+            BlendType::Alpha
+        );
+        assert_eq!(
+            layer_blend_type(MapLayerBlendMode::Multiply),
+            BlendType::Multiply
+        );
+        assert_eq!(
+            layer_blend_type(MapLayerBlendMode::Additive),
+            BlendType::AlphaAdditive
+        );
+        assert_eq!(
+            layer_blend_type(MapLayerBlendMode::Screen),
+            BlendType::Screen
         );
     }
 }
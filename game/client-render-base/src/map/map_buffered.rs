@@ -37,7 +37,7 @@ use map::{
     types::NonZeroU16MinusOne,
 };
 use rayon::{
-    iter::IntoParallelRefIterator,
+    iter::{IntoParallelRefIterator, IntoParallelRefMutIterator},
     prelude::{IndexedParallelIterator, ParallelIterator},
     slice::ParallelSliceMut,
 };
@@ -61,6 +61,7 @@ use crate::map::{
         MapVisualLayerSound, MapVisualLayerTile, MapVisualMetadata, MapVisualPhysicsLayer,
         MapVisualProps,
     },
+    occlusion::{TileLayerChunkOpacity, TileOpacity},
 };
 
 use self::{
@@ -130,6 +131,10 @@ pub struct TileLayerVisualsBase {
     pub width: u32,
     pub height: u32,
     pub is_textured: bool,
+
+    /// Opacity of the tile chunks, used for occlusion culling.
+    /// `None` if unknown (e.g. the texture was not analyzed).
+    pub chunk_opacity: Option<TileLayerChunkOpacity>,
 }
 
 impl TileLayerVisualsBase {
@@ -2814,6 +2819,50 @@ impl ClientMapBuffered {
         }
     }
 
+    /// Calculates the per chunk opacity of all design tile layers.
+    ///
+    /// `tile_opacities` are the opacities of the 2d array textures
+    /// in the order of the map's image array resources.
+    pub fn calc_chunk_opacities(
+        upload_data: &mut ClientMapBufferUploadData,
+        tile_opacities: &[Option<&TileOpacity>],
+    ) {
+        let map = &upload_data.map;
+        let calc = |groups: &Vec<MapGroup>, uploads: &mut Vec<MapBufferTileLayer>| {
+            uploads.par_iter_mut().for_each(|upload| {
+                let MapRenderInfo {
+                    group_index,
+                    layer_index,
+                } = upload.render_info;
+                if let Some(MapLayer::Tile(layer)) = groups
+                    .get(group_index)
+                    .and_then(|group| group.layers.get(layer_index))
+                    && let Some(tile_opacity) = layer
+                        .attr
+                        .image_array
+                        .and_then(|image| tile_opacities.get(image).copied().flatten())
+                {
+                    let visuals = &mut upload.base.visuals;
+                    visuals.chunk_opacity = Some(TileLayerChunkOpacity::new(
+                        &layer.tiles,
+                        visuals.width as usize,
+                        visuals.height as usize,
+                        tile_opacity,
+                    ))
+                    .filter(|opacity| opacity.has_opaque_chunks());
+                }
+            });
+        };
+        calc(
+            &map.groups.background,
+            &mut upload_data.bg_tile_layer_uploads,
+        );
+        calc(
+            &map.groups.foreground,
+            &mut upload_data.fg_tile_layer_uploads,
+        );
+    }
+
     pub fn tile_set_preview(
         graphics_mt: &GraphicsMultiThreaded,
         shader_storage_handle: &GraphicsShaderStorageHandle,
@@ -2,6 +2,8 @@ use graphics_types::types::GraphicsBackendMemory;
 use hiarc::Hiarc;
 use sound::sound_mt_types::SoundBackendMemory;
 
+use super::occlusion::TileOpacity;
+
 #[derive(Debug, Hiarc)]
 pub struct ClientMapImageLoading {
     pub width: u32,
@@ -9,6 +11,8 @@ pub struct ClientMapImageLoading {
     pub depth: u32,
     pub mem: GraphicsBackendMemory,
     pub name: String,
+    /// Only for 2d array textures
+    pub tile_opacity: Option<TileOpacity>,
 }
 
 #[derive(Debug, Hiarc)]
@@ -22,6 +22,7 @@ use super::{
     map::RenderMap,
     map_buffered::{ClientMapBuffered, MapSoundProcessInfo, SoundLayerSounds},
     map_with_visual::{MapVisual, MapVisualLayer},
+    music_intensity::MusicIntensity,
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -127,11 +128,16 @@ impl MapSoundProcess {
         layer: &MapLayerSoundSkeleton<S>,
         camera: &dyn CameraInterface,
         map_sound_volume: f64,
+        music_intensity: Option<&MusicIntensity>,
     ) where
         S: Borrow<SoundLayerSounds>,
     {
         if let Some(sound_index) = layer.layer.attr.sound {
             let sound_object: &SoundObject = sounds[sound_index].user.borrow();
+            let map_sound_volume = map_sound_volume
+                * music_intensity
+                    .map(|music| music.layer_volume(&layer.layer.name, *cur_time))
+                    .unwrap_or(1.0);
             for (index, sound) in layer.layer.sounds.iter().enumerate() {
                 let mut pos = sound.pos;
                 let mut rot = 0.0;
@@ -226,6 +232,7 @@ impl MapSoundProcess {
         layer_ty: SoundLayerType,
         camera: &dyn CameraInterface,
         map_sound_volume: f64,
+        music_intensity: Option<&MusicIntensity>,
     ) {
         let groups = match layer_ty {
             SoundLayerType::Background => &map.groups.background,
@@ -246,6 +253,7 @@ impl MapSoundProcess {
                 layer,
                 camera,
                 map_sound_volume,
+                music_intensity,
             );
         }
     }
@@ -259,6 +267,7 @@ impl MapSoundProcess {
         buffered_map: &ClientMapBuffered,
         camera: &dyn CameraInterface,
         map_sound_volume: f64,
+        music_intensity: Option<&MusicIntensity>,
     ) {
         map.user.sound_scene.stay_active();
         self.handle_impl(
@@ -270,6 +279,7 @@ impl MapSoundProcess {
             SoundLayerType::Background,
             camera,
             map_sound_volume,
+            music_intensity,
         )
     }
     pub fn handle_foreground(
@@ -281,6 +291,7 @@ impl MapSoundProcess {
         buffered_map: &ClientMapBuffered,
         camera: &dyn CameraInterface,
         map_sound_volume: f64,
+        music_intensity: Option<&MusicIntensity>,
     ) {
         map.user.sound_scene.stay_active();
         self.handle_impl(
@@ -292,6 +303,7 @@ impl MapSoundProcess {
             SoundLayerType::Foreground,
             camera,
             map_sound_volume,
+            music_intensity,
         )
     }
 }
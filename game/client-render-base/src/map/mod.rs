@@ -1,10 +1,13 @@
 pub mod map_buffered;
 
+pub mod hq_assets;
 pub mod map;
 pub mod map_image;
 pub mod map_pipeline;
 pub mod map_sound;
 pub mod map_with_visual;
+pub mod music_intensity;
+pub mod occlusion;
 pub mod render_map_base;
 pub mod render_pipe;
 pub mod render_tools;
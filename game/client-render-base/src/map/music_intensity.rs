@@ -0,0 +1,320 @@
+//! Dynamic map music: sound layers named `music_intensity_<level>` are stems
+//! of the map music, which fade in once the game is intense enough.
+//!
+//! All other sound layers (the base music) play as usual, so maps
+//! without stems sound exactly as before.
+
+use std::{f64::consts::FRAC_PI_2, time::Duration};
+
+use hiarc::Hiarc;
+use serde::{Deserialize, Serialize};
+
+/// The highest intensity level a stem can have.
+pub const MAX_MUSIC_INTENSITY: u8 = 3;
+
+/// The name prefix of sound layers that are intensity stems.
+pub const MUSIC_INTENSITY_STEM_PREFIX: &str = "music_intensity_";
+
+/// Game events that make the music more intense.
+#[derive(Debug, Hiarc, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MusicIntensityEvent {
+    /// A flag is carried by a character.
+    FlagTaken,
+    /// The round is about to end.
+    LowTimeRemaining,
+    /// The next score decides the round.
+    SuddenDeath,
+}
+
+impl MusicIntensityEvent {
+    const ALL: [Self; 3] = [Self::FlagTaken, Self::LowTimeRemaining, Self::SuddenDeath];
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|ev| *ev == self).unwrap()
+    }
+}
+
+/// Maps the game events to intensity levels.
+#[derive(Debug, Hiarc, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MusicIntensityTable {
+    pub flag_taken: u8,
+    pub low_time_remaining: u8,
+    pub sudden_death: u8,
+    /// The remaining round time below which
+    /// [`MusicIntensityEvent::LowTimeRemaining`] is triggered.
+    pub low_time: Duration,
+    /// How long an event keeps its intensity after it was
+    /// triggered the last time.
+    pub hold: Duration,
+    /// How long a stem takes to fade in or out.
+    pub crossfade: Duration,
+}
+
+impl Default for MusicIntensityTable {
+    fn default() -> Self {
+        Self {
+            flag_taken: 1,
+            low_time_remaining: 2,
+            sudden_death: 3,
+            low_time: Duration::from_secs(30),
+            hold: Duration::from_secs(5),
+            crossfade: Duration::from_secs(2),
+        }
+    }
+}
+
+impl MusicIntensityTable {
+    pub fn level(&self, ev: MusicIntensityEvent) -> u8 {
+        match ev {
+            MusicIntensityEvent::FlagTaken => self.flag_taken,
+            MusicIntensityEvent::LowTimeRemaining => self.low_time_remaining,
+            MusicIntensityEvent::SuddenDeath => self.sudden_death,
+        }
+        .min(MAX_MUSIC_INTENSITY)
+    }
+}
+
+/// The intensity level of a sound layer, if it is a stem.
+pub fn stem_level(layer_name: &str) -> Option<u8> {
+    layer_name
+        .strip_prefix(MUSIC_INTENSITY_STEM_PREFIX)
+        .and_then(|level| level.parse().ok())
+        .filter(|level| (1..=MAX_MUSIC_INTENSITY).contains(level))
+}
+
+/// The volume envelope of a stem that fades from one volume to another.
+///
+/// Uses an equal power curve, so the overall loudness stays
+/// roughly the same while stems swap.
+#[derive(Debug, Hiarc, Clone, Copy, PartialEq)]
+pub struct Crossfade {
+    pub start: Duration,
+    pub duration: Duration,
+    pub from: f64,
+    pub to: f64,
+}
+
+impl Crossfade {
+    pub fn constant(volume: f64) -> Self {
+        Self {
+            start: Duration::ZERO,
+            duration: Duration::ZERO,
+            from: volume,
+            to: volume,
+        }
+    }
+
+    pub fn volume_at(&self, time: Duration) -> f64 {
+        if self.is_finished(time) {
+            return self.to;
+        }
+        let t = time.saturating_sub(self.start).as_secs_f64() / self.duration.as_secs_f64();
+        let shape = if self.to >= self.from {
+            (t * FRAC_PI_2).sin()
+        } else {
+            1.0 - (t * FRAC_PI_2).cos()
+        };
+        self.from + (self.to - self.from) * shape
+    }
+
+    pub fn is_finished(&self, time: Duration) -> bool {
+        time.saturating_sub(self.start) >= self.duration
+    }
+}
+
+/// Tracks the current intensity of the map music.
+///
+/// Game events are [triggered](Self::trigger) every frame they are active,
+/// [`Self::update`] then fades the stems to the new level.
+#[derive(Debug, Hiarc, Clone)]
+pub struct MusicIntensity {
+    /// Until when the events keep their intensity.
+    active_until: [Option<Duration>; MusicIntensityEvent::ALL.len()],
+    level: u8,
+    /// One envelope per stem, the first one is level `1`.
+    stems: [Crossfade; MAX_MUSIC_INTENSITY as usize],
+}
+
+impl Default for MusicIntensity {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MusicIntensity {
+    pub fn new() -> Self {
+        Self {
+            active_until: Default::default(),
+            level: 0,
+            stems: [Crossfade::constant(0.0); MAX_MUSIC_INTENSITY as usize],
+        }
+    }
+
+    pub fn trigger(
+        &mut self,
+        ev: MusicIntensityEvent,
+        table: &MusicIntensityTable,
+        time: Duration,
+    ) {
+        self.active_until[ev.index()] = Some(time + table.hold);
+    }
+
+    /// The level the events ask for, ignoring running fades.
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    /// Recalculates the level from the triggered events
+    /// & starts the fades of all stems that changed.
+    pub fn update(&mut self, table: &MusicIntensityTable, time: Duration) {
+        let mut level = 0;
+        for (ev, until) in MusicIntensityEvent::ALL
+            .into_iter()
+            .zip(self.active_until.iter_mut())
+        {
+            if until.is_some_and(|until| until < time) {
+                *until = None;
+            }
+            if until.is_some() {
+                level = level.max(table.level(ev));
+            }
+        }
+        if level == self.level {
+            return;
+        }
+        self.level = level;
+        for (index, stem) in self.stems.iter_mut().enumerate() {
+            let to = if index < level as usize { 1.0 } else { 0.0 };
+            if stem.to != to {
+                // start from the current volume, so a fade
+                // that is interrupted does not jump
+                *stem = Crossfade {
+                    start: time,
+                    duration: table.crossfade,
+                    from: stem.volume_at(time),
+                    to,
+                };
+            }
+        }
+    }
+
+    /// The volume multiplier of a stem of the given level.
+    pub fn stem_volume(&self, stem_level: u8, time: Duration) -> f64 {
+        stem_level
+            .checked_sub(1)
+            .and_then(|index| self.stems.get(index as usize))
+            .map(|stem| stem.volume_at(time))
+            .unwrap_or(1.0)
+    }
+
+    /// The volume multiplier of a sound layer.
+    ///
+    /// `1.0` for all layers that are not stems.
+    pub fn layer_volume(&self, layer_name: &str, time: Duration) -> f64 {
+        stem_level(layer_name)
+            .map(|level| self.stem_volume(level, time))
+            .unwrap_or(1.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{Crossfade, MusicIntensity, MusicIntensityEvent, MusicIntensityTable, stem_level};
+
+    #[test]
+    fn crossfade_envelope() {
+        let secs = Duration::from_secs_f64;
+        let fade_in = Crossfade {
+            start: secs(1.0),
+            duration: secs(2.0),
+            from: 0.0,
+            to: 1.0,
+        };
+        assert_eq!(fade_in.volume_at(secs(0.0)), 0.0);
+        assert_eq!(fade_in.volume_at(secs(1.0)), 0.0);
+        assert!((fade_in.volume_at(secs(2.0)) - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-9);
+        assert_eq!(fade_in.volume_at(secs(3.0)), 1.0);
+        assert_eq!(fade_in.volume_at(secs(10.0)), 1.0);
+        assert!(!fade_in.is_finished(secs(2.0)));
+        assert!(fade_in.is_finished(secs(3.0)));
+
+        let fade_out = Crossfade {
+            from: 1.0,
+            to: 0.0,
+            ..fade_in
+        };
+        // equal power: both fades together keep the loudness
+        for t in [1.0, 1.5, 2.0, 2.5, 3.0] {
+            let a = fade_in.volume_at(secs(t));
+            let b = fade_out.volume_at(secs(t));
+            assert!((a * a + b * b - 1.0).abs() < 1e-9);
+        }
+        let mut prev = 1.0;
+        for i in 0..=20 {
+            let v = fade_out.volume_at(secs(1.0 + i as f64 / 10.0));
+            assert!(v <= prev);
+            prev = v;
+        }
+        assert_eq!(Crossfade::constant(0.5).volume_at(secs(5.0)), 0.5);
+    }
+
+    #[test]
+    fn intensity_state_machine() {
+        let secs = Duration::from_secs_f64;
+        let table = MusicIntensityTable::default();
+        let mut music = MusicIntensity::new();
+
+        // nothing happens, stems are silent, the base plays
+        music.update(&table, secs(0.0));
+        assert_eq!(music.level(), 0);
+        assert_eq!(music.stem_volume(1, secs(0.0)), 0.0);
+        assert_eq!(music.layer_volume("music", secs(0.0)), 1.0);
+
+        music.trigger(MusicIntensityEvent::FlagTaken, &table, secs(1.0));
+        music.update(&table, secs(1.0));
+        assert_eq!(music.level(), 1);
+        assert_eq!(music.stem_volume(1, secs(1.0)), 0.0);
+        assert_eq!(music.stem_volume(1, secs(3.0)), 1.0);
+        assert_eq!(music.stem_volume(2, secs(3.0)), 0.0);
+
+        // the strongest event wins
+        music.trigger(MusicIntensityEvent::FlagTaken, &table, secs(3.0));
+        music.trigger(MusicIntensityEvent::SuddenDeath, &table, secs(3.0));
+        music.update(&table, secs(3.0));
+        assert_eq!(music.level(), 3);
+        assert_eq!(music.layer_volume("music_intensity_3", secs(5.0)), 1.0);
+
+        // sudden death stops, the flag is still carried
+        music.trigger(MusicIntensityEvent::FlagTaken, &table, secs(8.5));
+        music.update(&table, secs(8.5));
+        assert_eq!(music.level(), 1);
+        assert_eq!(music.stem_volume(1, secs(8.5)), 1.0);
+        assert_eq!(music.stem_volume(3, secs(8.5)), 1.0);
+        assert_eq!(music.stem_volume(3, secs(10.5)), 0.0);
+
+        // the hold expires
+        music.update(&table, secs(14.0));
+        assert_eq!(music.level(), 0);
+
+        // interrupted fades continue from the current volume
+        let mid = music.stem_volume(1, secs(15.0));
+        assert!(mid > 0.0 && mid < 1.0);
+        music.trigger(MusicIntensityEvent::FlagTaken, &table, secs(15.0));
+        music.update(&table, secs(15.0));
+        assert_eq!(music.level(), 1);
+        assert_eq!(music.stem_volume(1, secs(15.0)), mid);
+        assert!(music.stem_volume(1, secs(16.0)) > mid);
+        assert_eq!(music.stem_volume(1, secs(17.0)), 1.0);
+    }
+
+    #[test]
+    fn stem_names() {
+        assert_eq!(stem_level("music_intensity_1"), Some(1));
+        assert_eq!(stem_level("music_intensity_3"), Some(3));
+        assert_eq!(stem_level("music_intensity_0"), None);
+        assert_eq!(stem_level("music_intensity_4"), None);
+        assert_eq!(stem_level("music"), None);
+    }
+}
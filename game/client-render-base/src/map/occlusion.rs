@@ -0,0 +1,473 @@
+use hiarc::Hiarc;
+use map::map::groups::layers::tiles::TileBase;
+
+/// The size (in tiles) of a single chunk in a tile layer,
+/// that is tested for occlusion.
+pub const OCCLUSION_CHUNK_SIZE: usize = 16;
+/// The amount of cells per axis of the screen space coverage grid.
+pub const COVERAGE_GRID_SIZE: usize = 32;
+
+/// Which tiles of a 2d array texture are fully opaque (no pixel with alpha < 255).
+#[derive(Debug, Hiarc, Clone)]
+pub struct TileOpacity {
+    fully_opaque: [bool; 256],
+}
+
+impl TileOpacity {
+    /// `img` is expected to be in the 2d array layout (256 tiles of `single_width` x `single_height`).
+    pub fn new(img: &[u8], single_width: usize, single_height: usize) -> Self {
+        let single_size = single_width * single_height * 4;
+        let mut fully_opaque = [false; 256];
+        if single_size == 0 || img.len() < single_size * 256 {
+            return Self { fully_opaque };
+        }
+        for (i, fully_opaque) in fully_opaque.iter_mut().enumerate() {
+            // tile index 0 is always air
+            if i == 0 {
+                continue;
+            }
+            *fully_opaque = img[i * single_size..(i + 1) * single_size]
+                .chunks_exact(4)
+                .all(|pixel| pixel[3] == 255);
+        }
+        Self { fully_opaque }
+    }
+
+    pub fn is_fully_opaque(&self, index: u8) -> bool {
+        self.fully_opaque[index as usize]
+    }
+}
+
+/// Per chunk opacity of a tile layer, a chunk is opaque if
+/// all its tiles are fully opaque.
+#[derive(Debug, Hiarc, Clone, Default)]
+pub struct TileLayerChunkOpacity {
+    pub chunks_x: usize,
+    pub chunks_y: usize,
+    opaque: Vec<bool>,
+}
+
+impl TileLayerChunkOpacity {
+    pub fn new(tiles: &[TileBase], width: usize, height: usize, opacity: &TileOpacity) -> Self {
+        let chunks_x = width.div_ceil(OCCLUSION_CHUNK_SIZE);
+        let chunks_y = height.div_ceil(OCCLUSION_CHUNK_SIZE);
+        let mut opaque = vec![true; chunks_x * chunks_y];
+        for (i, tile) in tiles.iter().enumerate().take(width * height) {
+            let x = i % width;
+            let y = i / width;
+            if !opacity.is_fully_opaque(tile.index) {
+                opaque[(y / OCCLUSION_CHUNK_SIZE) * chunks_x + x / OCCLUSION_CHUNK_SIZE] = false;
+            }
+        }
+        Self {
+            chunks_x,
+            chunks_y,
+            opaque,
+        }
+    }
+
+    pub fn is_opaque(&self, chunk_x: usize, chunk_y: usize) -> bool {
+        chunk_x < self.chunks_x
+            && chunk_y < self.chunks_y
+            && self.opaque[chunk_y * self.chunks_x + chunk_x]
+    }
+
+    /// Whether any chunk of this layer can occlude others at all.
+    pub fn has_opaque_chunks(&self) -> bool {
+        self.opaque.iter().any(|opaque| *opaque)
+    }
+}
+
+/// A coarse grid over the screen (normalized coordinates from 0 to 1),
+/// which tracks which parts of the screen are already fully covered
+/// by opaque chunks.
+///
+/// Covering is conservative: a cell is only marked covered
+/// if it lies completely inside the covering rect. Likewise a rect is only
+/// considered hidden if every cell it touches is covered.
+#[derive(Debug, Hiarc, Clone)]
+pub struct ScreenCoverageGrid {
+    cells: Vec<bool>,
+}
+
+impl Default for ScreenCoverageGrid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScreenCoverageGrid {
+    pub fn new() -> Self {
+        Self {
+            cells: vec![false; COVERAGE_GRID_SIZE * COVERAGE_GRID_SIZE],
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.fill(false);
+    }
+
+    fn to_cells_inner(v0: f32, v1: f32) -> (usize, usize) {
+        let size = COVERAGE_GRID_SIZE as f32;
+        (
+            (v0 * size).ceil().clamp(0.0, size) as usize,
+            (v1 * size).floor().clamp(0.0, size) as usize,
+        )
+    }
+
+    fn to_cells_outer(v0: f32, v1: f32) -> (usize, usize) {
+        let size = COVERAGE_GRID_SIZE as f32;
+        (
+            (v0 * size).floor().clamp(0.0, size) as usize,
+            (v1 * size).ceil().clamp(0.0, size) as usize,
+        )
+    }
+
+    /// Marks all cells fully inside the given rect as covered.
+    pub fn cover(&mut self, x0: f32, y0: f32, x1: f32, y1: f32) {
+        let (cx0, cx1) = Self::to_cells_inner(x0, x1);
+        let (cy0, cy1) = Self::to_cells_inner(y0, y1);
+        for y in cy0..cy1 {
+            self.cells[y * COVERAGE_GRID_SIZE + cx0..y * COVERAGE_GRID_SIZE + cx1.max(cx0)]
+                .fill(true);
+        }
+    }
+
+    /// Whether all visible parts of the given rect are covered.
+    /// Rects that are completely off screen count as covered.
+    pub fn is_covered(&self, x0: f32, y0: f32, x1: f32, y1: f32) -> bool {
+        let (cx0, cx1) = Self::to_cells_outer(x0, x1);
+        let (cy0, cy1) = Self::to_cells_outer(y0, y1);
+        (cy0..cy1).all(|y| {
+            self.cells[y * COVERAGE_GRID_SIZE + cx0..y * COVERAGE_GRID_SIZE + cx1.max(cx0)]
+                .iter()
+                .all(|covered| *covered)
+        })
+    }
+
+    pub fn is_cell_covered(&self, x: usize, y: usize) -> bool {
+        self.cells[y * COVERAGE_GRID_SIZE + x]
+    }
+
+    pub fn cover_cell(&mut self, x: usize, y: usize) {
+        self.cells[y * COVERAGE_GRID_SIZE + x] = true;
+    }
+
+    pub fn is_fully_covered(&self) -> bool {
+        self.cells.iter().all(|covered| *covered)
+    }
+}
+
+/// The result of the occlusion pass for a single tile layer
+/// for the current frame.
+#[derive(Debug, Hiarc, Clone, Default)]
+pub struct TileLayerOcclusion {
+    occluded: Vec<bool>,
+    chunks_x: usize,
+}
+
+impl TileLayerOcclusion {
+    pub fn is_chunk_occluded(&self, chunk_x: usize, chunk_y: usize) -> bool {
+        self.occluded
+            .get(chunk_y * self.chunks_x + chunk_x)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn occluded_chunks(&self) -> usize {
+        self.occluded.iter().filter(|occluded| **occluded).count()
+    }
+}
+
+/// Helper to map tile coordinates of a layer to normalized screen coordinates.
+#[derive(Debug, Clone, Copy)]
+pub struct TileLayerScreenMapping {
+    /// The canvas mapping of the group in tiles (top left x & y, bottom right x & y).
+    pub canvas: (f32, f32, f32, f32),
+}
+
+impl TileLayerScreenMapping {
+    fn chunk_rect(&self, chunk_x: usize, chunk_y: usize, width: usize, height: usize) -> [f32; 4] {
+        let (tl_x, tl_y, br_x, br_y) = self.canvas;
+        let x0 = (chunk_x * OCCLUSION_CHUNK_SIZE) as f32;
+        let y0 = (chunk_y * OCCLUSION_CHUNK_SIZE) as f32;
+        let x1 = ((chunk_x + 1) * OCCLUSION_CHUNK_SIZE).min(width) as f32;
+        let y1 = ((chunk_y + 1) * OCCLUSION_CHUNK_SIZE).min(height) as f32;
+        [
+            (x0 - tl_x) / (br_x - tl_x),
+            (y0 - tl_y) / (br_y - tl_y),
+            (x1 - tl_x) / (br_x - tl_x),
+            (y1 - tl_y) / (br_y - tl_y),
+        ]
+    }
+
+    /// Marks all cells of the coverage grid as covered,
+    /// that lie completely inside opaque chunks of the layer.
+    pub fn cover(
+        &self,
+        grid: &mut ScreenCoverageGrid,
+        opacity: &TileLayerChunkOpacity,
+        width: usize,
+        height: usize,
+    ) {
+        let (tl_x, tl_y, br_x, br_y) = self.canvas;
+        let size = COVERAGE_GRID_SIZE as f32;
+        let chunk_size = OCCLUSION_CHUNK_SIZE as f32;
+        let to_tiles = |cell: usize, tl: f32, br: f32| tl + (cell as f32 / size) * (br - tl);
+        for y in 0..COVERAGE_GRID_SIZE {
+            let ty0 = to_tiles(y, tl_y, br_y);
+            let ty1 = to_tiles(y + 1, tl_y, br_y);
+            if ty0 < 0.0 || ty1 > height as f32 {
+                continue;
+            }
+            let chunks_y = (ty0 / chunk_size).floor() as usize..(ty1 / chunk_size).ceil() as usize;
+            for x in 0..COVERAGE_GRID_SIZE {
+                if grid.is_cell_covered(x, y) {
+                    continue;
+                }
+                let tx0 = to_tiles(x, tl_x, br_x);
+                let tx1 = to_tiles(x + 1, tl_x, br_x);
+                if tx0 < 0.0 || tx1 > width as f32 {
+                    continue;
+                }
+                let chunks_x =
+                    (tx0 / chunk_size).floor() as usize..(tx1 / chunk_size).ceil() as usize;
+                if chunks_y.clone().all(|chunk_y| {
+                    chunks_x
+                        .clone()
+                        .all(|chunk_x| opacity.is_opaque(chunk_x, chunk_y))
+                }) {
+                    grid.cover_cell(x, y);
+                }
+            }
+        }
+    }
+
+    /// Tests all chunks of a layer against the coverage grid.
+    ///
+    /// Returns `None` if no chunk is occluded.
+    pub fn occlusion(
+        &self,
+        grid: &ScreenCoverageGrid,
+        width: usize,
+        height: usize,
+    ) -> Option<TileLayerOcclusion> {
+        let chunks_x = width.div_ceil(OCCLUSION_CHUNK_SIZE);
+        let chunks_y = height.div_ceil(OCCLUSION_CHUNK_SIZE);
+        let mut occluded = vec![false; chunks_x * chunks_y];
+        let mut any_occluded = false;
+        // chunks outside of the screen are culled by the renderer anyway
+        let (tl_x, tl_y, br_x, br_y) = self.canvas;
+        let visible_chunks = |v0: f32, v1: f32, max: usize| {
+            let chunk_size = OCCLUSION_CHUNK_SIZE as f32;
+            ((v0 / chunk_size).floor().max(0.0) as usize).min(max)
+                ..((v1 / chunk_size).ceil().max(0.0) as usize).min(max)
+        };
+        for y in visible_chunks(tl_y, br_y, chunks_y) {
+            for x in visible_chunks(tl_x, br_x, chunks_x) {
+                let [rx0, ry0, rx1, ry1] = self.chunk_rect(x, y, width, height);
+                if grid.is_covered(rx0, ry0, rx1, ry1) {
+                    occluded[y * chunks_x + x] = true;
+                    any_occluded = true;
+                }
+            }
+        }
+        any_occluded.then_some(TileLayerOcclusion { occluded, chunks_x })
+    }
+}
+
+/// Splits the visible tile range `x0..x1` of row `y` into spans
+/// of tiles that are not occluded.
+pub fn visible_row_spans(
+    x0: i32,
+    x1: i32,
+    y: i32,
+    occlusion: Option<&TileLayerOcclusion>,
+    mut span: impl FnMut(i32, i32),
+) {
+    let Some(occlusion) = occlusion else {
+        if x0 < x1 {
+            span(x0, x1);
+        }
+        return;
+    };
+    let chunk_y = y as usize / OCCLUSION_CHUNK_SIZE;
+    let mut span_start = None;
+    let mut x = x0;
+    while x < x1 {
+        let chunk_x = x as usize / OCCLUSION_CHUNK_SIZE;
+        let chunk_end = (((chunk_x + 1) * OCCLUSION_CHUNK_SIZE) as i32).min(x1);
+        if occlusion.is_chunk_occluded(chunk_x, chunk_y) {
+            if let Some(start) = span_start.take() {
+                span(start, x);
+            }
+        } else if span_start.is_none() {
+            span_start = Some(x);
+        }
+        x = chunk_end;
+    }
+    if let Some(start) = span_start {
+        span(start, x1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Instant;
+
+    use map::map::groups::layers::tiles::TileBase;
+
+    use super::{
+        COVERAGE_GRID_SIZE, OCCLUSION_CHUNK_SIZE, ScreenCoverageGrid, TileLayerChunkOpacity,
+        TileLayerScreenMapping, TileOpacity, visible_row_spans,
+    };
+
+    fn opacity_table() -> TileOpacity {
+        // tile 1 is fully opaque, tile 2 has a single transparent pixel
+        let (w, h) = (4, 4);
+        let mut img = vec![0u8; w * h * 4 * 256];
+        img[w * h * 4..w * h * 4 * 2].fill(255);
+        img[w * h * 4 * 2..w * h * 4 * 3].fill(255);
+        img[w * h * 4 * 2 + 3] = 254;
+        TileOpacity::new(&img, w, h)
+    }
+
+    #[test]
+    fn tile_opacity() {
+        let table = opacity_table();
+        assert!(!table.is_fully_opaque(0));
+        assert!(table.is_fully_opaque(1));
+        assert!(!table.is_fully_opaque(2));
+    }
+
+    #[test]
+    fn chunk_opacity() {
+        let table = opacity_table();
+        let (w, h) = (40, 20);
+        let mut tiles = vec![
+            TileBase {
+                index: 1,
+                ..Default::default()
+            };
+            w * h
+        ];
+        // single semi transparent tile in the second chunk
+        tiles[3 * w + 20].index = 2;
+        let opacity = TileLayerChunkOpacity::new(&tiles, w, h, &table);
+        assert_eq!(opacity.chunks_x, 3);
+        assert_eq!(opacity.chunks_y, 2);
+        assert!(opacity.is_opaque(0, 0));
+        assert!(!opacity.is_opaque(1, 0));
+        assert!(opacity.is_opaque(2, 1));
+        assert!(!opacity.is_opaque(3, 0));
+    }
+
+    #[test]
+    fn coverage_grid() {
+        let mut grid = ScreenCoverageGrid::new();
+        assert!(!grid.is_covered(0.0, 0.0, 0.5, 0.5));
+        // off screen is always covered
+        assert!(grid.is_covered(1.5, 1.5, 2.0, 2.0));
+
+        grid.cover(0.0, 0.0, 0.5, 0.5);
+        assert!(grid.is_covered(0.0, 0.0, 0.5, 0.5));
+        assert!(grid.is_covered(0.1, 0.1, 0.2, 0.2));
+        assert!(!grid.is_covered(0.4, 0.4, 0.6, 0.6));
+
+        // partially covered cells must not count as covered
+        let cell = 1.0 / COVERAGE_GRID_SIZE as f32;
+        grid.cover(0.5 + cell * 0.5, 0.0, 0.75, 0.5);
+        assert!(!grid.is_covered(0.5, 0.0, 0.5 + cell, 0.5));
+        assert!(grid.is_covered(0.5 + cell, 0.0, 0.75, 0.5));
+
+        grid.cover(-1.0, -1.0, 2.0, 2.0);
+        assert!(grid.is_fully_covered());
+        grid.clear();
+        assert!(!grid.is_covered(0.0, 0.0, 0.1, 0.1));
+    }
+
+    #[test]
+    fn row_spans() {
+        let mut spans = Vec::new();
+        visible_row_spans(3, 50, 0, None, |x0, x1| spans.push((x0, x1)));
+        assert_eq!(spans, vec![(3, 50)]);
+
+        let table = opacity_table();
+        let size = OCCLUSION_CHUNK_SIZE * 4;
+        let tiles = vec![
+            TileBase {
+                index: 1,
+                ..Default::default()
+            };
+            size * size
+        ];
+        let opacity = TileLayerChunkOpacity::new(&tiles, size, size, &table);
+        let mut grid = ScreenCoverageGrid::new();
+        // cover the left half of the screen
+        grid.cover(0.0, 0.0, 0.5, 1.0);
+        let mapping = TileLayerScreenMapping {
+            canvas: (0.0, 0.0, size as f32, size as f32),
+        };
+        let occlusion = mapping.occlusion(&grid, size, size).unwrap();
+        assert_eq!(occlusion.occluded_chunks(), 8);
+        spans.clear();
+        visible_row_spans(0, size as i32, 5, Some(&occlusion), |x0, x1| {
+            spans.push((x0, x1))
+        });
+        assert_eq!(spans, vec![((size / 2) as i32, size as i32)]);
+
+        // the layer covers the whole screen itself
+        let mut grid = ScreenCoverageGrid::new();
+        mapping.cover(&mut grid, &opacity, size, size);
+        assert!(grid.is_fully_covered());
+    }
+
+    /// Simulates a heavy map with many stacked background layers
+    /// and an opaque foreground and compares the amount of draws.
+    #[test]
+    fn bench_draw_counts() {
+        let table = opacity_table();
+        let size = 500;
+        let bg_layers = 20;
+        let fg_tiles = vec![
+            TileBase {
+                index: 1,
+                ..Default::default()
+            };
+            size * size
+        ];
+        let fg_opacity = TileLayerChunkOpacity::new(&fg_tiles, size, size, &table);
+        // a camera looking at the center of the map
+        let mapping = TileLayerScreenMapping {
+            canvas: (200.0, 220.0, 260.0, 254.0),
+        };
+
+        let count_draws = |cull: bool| {
+            let mut grid = ScreenCoverageGrid::new();
+            if cull {
+                mapping.cover(&mut grid, &fg_opacity, size, size);
+            }
+            let mut draws = 0;
+            for _ in 0..bg_layers {
+                let occlusion = cull.then(|| mapping.occlusion(&grid, size, size)).flatten();
+                for y in 220..254 {
+                    visible_row_spans(200, 260, y, occlusion.as_ref(), |_, _| draws += 1);
+                }
+            }
+            draws
+        };
+
+        let now = Instant::now();
+        let draws_without = count_draws(false);
+        let time_without = now.elapsed();
+        let now = Instant::now();
+        let draws_with = count_draws(true);
+        let time_with = now.elapsed();
+        println!(
+            "draws without culling: {draws_without} ({time_without:?}), \
+            with culling: {draws_with} ({time_with:?})"
+        );
+        assert_eq!(draws_without, bg_layers * 34);
+        assert!(draws_with < draws_without);
+    }
+}
@@ -1,16 +1,21 @@
 use std::{
     collections::{HashMap, HashSet},
     path::Path,
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
 };
 
 use super::{
+    hq_assets::MapHqAssets,
     map::RenderMap,
     map_buffered::{ClientMapBufferUploadData, ClientMapBuffered},
     map_image::{
         ClientMapImageLoading, ClientMapImagesLoading, ClientMapSoundLoading,
         ClientMapSoundsLoading,
     },
+    occlusion::TileOpacity,
 };
 use anyhow::anyhow;
 use assets_base::verify::ogg_vorbis::verify_ogg_vorbis;
@@ -21,8 +26,10 @@ use base::{
 };
 use base_io::{io::Io, path_to_url::relative_path_to_url, runtime::IoRuntimeTask};
 use config::config::ConfigDebug;
+use game_base::connecting_log::ConnectingLog;
 use graphics::{
     graphics::graphics::Graphics,
+    graphics_mt::GraphicsMultiThreaded,
     handles::{
         backend::backend::GraphicsBackendHandle,
         buffer_object::buffer_object::GraphicsBufferObjectHandle,
@@ -32,12 +39,21 @@ use graphics::{
         texture::texture::{GraphicsTextureHandle, TextureContainer, TextureContainer2dArray},
     },
 };
-use graphics_types::{commands::TexFlags, types::GraphicsMemoryAllocationType};
+use graphics_types::{
+    commands::TexFlags,
+    types::{GraphicsBackendMemory, GraphicsMemoryAllocationType},
+};
 use image_utils::{
     png::{PngValidatorOptions, is_png_image_valid, load_png_image_as_rgba, resize_rgba},
     utils::{highest_bit, texture_2d_to_3d},
 };
-use map::{file::MapFileReader, map::Map};
+use map::{
+    file::MapFileReader,
+    map::{
+        Map,
+        resources::{MapResourceMetaData, MapResourceRef},
+    },
+};
 use math::math::vector::vec2;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use sound::{commands::SoundSceneCreateProps, scene_handle::SoundSceneHandle, sound::SoundManager};
@@ -87,22 +103,34 @@ impl RenderMapLoading {
         graphics: &Graphics,
         config: &ConfigDebug,
         downloaded_path: Option<&Path>,
+        hq_assets: MapHqAssets,
+        log: Option<ConnectingLog>,
     ) -> Self {
-        let file_system = io.fs.clone();
-        let http = io.http.clone();
+        let io_task = io.clone();
         let do_benchmark = config.bench;
         let runtime_tp = thread_pool;
         let graphics_mt = graphics.get_graphics_mt();
         let sound_mt = sound.get_sound_mt();
         let downloaded_path = downloaded_path.map(|p| p.to_path_buf());
-        let load_hq_assets = false;
         Self {
             task: io.rt.spawn(async move {
                 let benchmark = Benchmark::new(do_benchmark);
                 let map_reader = MapFileReader::new(file)?;
-                // open the map file
-                let resources = Map::read_resources_and_header(&map_reader)?;
+                let log_progress = |msg: String| {
+                    if let Some(log) = &log {
+                        log.log(msg);
+                    }
+                };
+                // parse the whole map file first, section by section,
+                // so a corrupt map fails before any resource is read.
+                let map = runtime_tp.install(|| {
+                    Map::read_with_progress(&map_reader, &runtime_tp, &|section| {
+                        log_progress(format!("Reading map: {section}"))
+                    })
+                })?;
+                drop(map_reader);
                 benchmark.bench("opening the full map file");
+                let resources = &map.resources;
 
                 // read content files
                 let mut file_map: HashSet<Hash> = Default::default();
@@ -123,103 +151,59 @@ impl RenderMapLoading {
                             .map(|i| (i, ReadFileTy::Image)),
                     )
                     .chain(resources.sounds.iter().map(|s| (s, ReadFileTy::Sound)))
-                    .filter(|(i, _)| {
-                        let meta = if let Some(hq_meta) =
-                            load_hq_assets.then_some(i.hq_meta.as_ref()).flatten()
-                        {
-                            hq_meta
-                        } else {
-                            &i.meta
-                        };
-
-                        if file_map.contains(&meta.blake3_hash) {
-                            false
-                        } else {
-                            file_map.insert(meta.blake3_hash);
-                            true
-                        }
-                    })
+                    .filter(|(i, _)| file_map.insert(i.meta.blake3_hash))
                     .map(|(res, ty)| {
-                        let meta = if let Some(hq_meta) =
-                            load_hq_assets.then_some(res.hq_meta.as_ref()).flatten()
-                        {
-                            hq_meta
-                        } else {
-                            &res.meta
-                        };
-
-                        let download_read_file_path = format!(
-                            "map/resources/{}/{}_{}.{}",
-                            if ty == ReadFileTy::Image {
-                                "images"
-                            } else {
-                                "sounds"
-                            },
-                            res.name.as_str(),
-                            fmt_hash(&meta.blake3_hash),
-                            meta.ty.as_str()
+                        let file = Self::read_resource(
+                            &io_task,
+                            resource_download_server.clone(),
+                            downloaded_path.as_deref(),
+                            ty == ReadFileTy::Image,
+                            res,
+                            &res.meta,
+                            false,
+                        );
+                        let hash = res.meta.blake3_hash;
+                        async move { (hash, file.await, ty) }
+                    });
+                // high quality variants only exist for images
+                // and are optional, so missing ones are ignored.
+                let mut hq_file_map: HashSet<Hash> = Default::default();
+                let hq_file_futures = resources
+                    .images
+                    .iter()
+                    .chain(resources.image_arrays.iter())
+                    .filter(|_| hq_assets.enabled)
+                    .filter_map(|res| res.hq_meta.as_ref().map(|meta| (res, meta)))
+                    .filter(|(_, meta)| hq_file_map.insert(meta.blake3_hash))
+                    .map(|(res, meta)| {
+                        let file = Self::read_resource(
+                            &io_task,
+                            resource_download_server.clone(),
+                            downloaded_path.as_deref(),
+                            true,
+                            res,
+                            meta,
+                            true,
                         );
-                        let read_file_path = if let Some(downloaded_path) = &downloaded_path {
-                            downloaded_path.join(&download_read_file_path)
-                        } else {
-                            download_read_file_path.as_str().into()
-                        };
                         let hash = meta.blake3_hash;
-                        let file_ty = meta.ty.clone();
-                        let file_name = res.name.clone();
-                        let fs = file_system.clone();
-                        let http = http.clone();
-                        let resource_download_server = resource_download_server.clone();
+                        let name = res.name.clone();
                         async move {
-                            let file = fs.read_file(Path::new(&read_file_path)).await;
-
-                            let file = match file {
-                                Ok(file) => Ok(file),
-                                Err(err) => {
-                                    async move {
-                                        // try to download file
-                                        if let Some(resource_download_server) =
-                                            resource_download_server.and_then(|url| {
-                                                relative_path_to_url(
-                                                    download_read_file_path.as_ref(),
-                                                )
-                                                .ok()
-                                                .and_then(|name| url.join(&name).ok())
-                                            })
-                                        {
-                                            let file = http
-                                                .download_binary(resource_download_server, &hash)
-                                                .await
-                                                .map_err(|err| {
-                                                    anyhow!("failed to download map: {err}")
-                                                })?
-                                                .to_vec();
-                                            Self::verify_resource(
-                                                file_ty.as_str(),
-                                                file_name.as_str(),
-                                                &file,
-                                                load_hq_assets,
-                                            )?;
-                                            let file_path: &Path = read_file_path.as_ref();
-                                            if let Some(dir) = file_path.parent() {
-                                                fs.create_dir(dir).await?;
-                                            }
-                                            fs.write_file(read_file_path.as_ref(), file.clone())
-                                                .await?;
-                                            anyhow::Ok(file)
-                                        } else {
-                                            Err(anyhow!(err))
-                                        }
-                                    }
-                                    .await
-                                }
-                            }
-                            .map_err(|err| anyhow!(err));
-
-                            (hash, file, ty)
+                            let file = file
+                                .await
+                                .inspect_err(|err| {
+                                    log::info!(
+                                        "high quality variant of {} is not available: {err}",
+                                        name.as_str()
+                                    );
+                                })
+                                .ok();
+                            (hash, file)
                         }
                     });
-                let task_read = futures::future::join_all(file_futures);
+                let task_read = futures::future::join(
+                    futures::future::join_all(file_futures),
+                    futures::future::join_all(hq_file_futures),
+                );
 
                 // poll once with a small hack
                 let task_read = futures::future::maybe_done(task_read);
@@ -227,7 +211,7 @@ impl RenderMapLoading {
                 futures::future::FutureExt::now_or_never(&mut task_read);
 
                 task_read.as_mut().await;
-                let files = task_read.as_mut().take_output().unwrap();
+                let (files, hq_files) = task_read.as_mut().take_output().unwrap();
                 let mut img_files: HashMap<Hash, Vec<u8>> = Default::default();
                 let mut sound_files: HashMap<Hash, Vec<u8>> = Default::default();
                 for (file_hash, file, ty) in files {
@@ -243,6 +227,12 @@ impl RenderMapLoading {
                 }
 
                 let resources_clone = resources.clone();
+                let image_count = resources.images.len() + resources.image_arrays.len();
+                let images_loaded = AtomicUsize::new(0);
+                let log_image_loaded = |name: &str| {
+                    let loaded = images_loaded.fetch_add(1, Ordering::Relaxed) + 1;
+                    log_progress(format!("Loaded map image {name} ({loaded}/{image_count})"));
+                };
 
                 let generate_3d_data = |w: usize, h: usize, img_data: &[u8]| {
                     // first check image dimensions
@@ -304,99 +294,163 @@ impl RenderMapLoading {
                         panic!("fatal error, could not convert 2d texture to 2d array texture");
                     }
 
+                    let tile_opacity =
+                        TileOpacity::new(tex_3d.as_slice(), image_3d_width, image_3d_height);
+
                     if let Err(err) = graphics_mt.try_flush_mem(&mut tex_3d, false) {
                         // Ignore the error, but log it.
                         log::debug!("err while flushing memory: {err}");
                     }
 
-                    (image_3d_width, image_3d_height, 256, tex_3d)
+                    (image_3d_width, image_3d_height, 256, tex_3d, tile_opacity)
                 };
 
                 // load images, external images and do map buffering
                 let (images_loading, sounds_loading, map_prepare) = runtime_tp.install(|| {
                     join_all!(
                         || {
-                            let img_files = img_files
+                            let decode = |file: &[u8]| {
+                                let mut img_data: Vec<u8> = Default::default();
+                                let img = load_png_image_as_rgba(
+                                    file,
+                                    |width, height, color_channel_count| {
+                                        img_data.resize(
+                                            width * height * color_channel_count,
+                                            Default::default(),
+                                        );
+                                        &mut img_data
+                                    },
+                                )?;
+                                let (width, height) = (img.width, img.height);
+                                anyhow::Ok((img_data, width, height))
+                            };
+                            let hq_img_files = hq_files
                                 .into_par_iter()
-                                .map(|(hash, file)| {
-                                    let mut img_data: Vec<u8> = Default::default();
-                                    let img = load_png_image_as_rgba(
-                                        &file,
-                                        |width, height, color_channel_count| {
-                                            img_data.resize(
-                                                width * height * color_channel_count,
-                                                Default::default(),
-                                            );
-                                            &mut img_data
-                                        },
-                                    )?;
-                                    anyhow::Ok((hash, (img.data.to_vec(), img.width, img.height)))
+                                .filter_map(|(hash, file)| {
+                                    decode(&file?)
+                                        .inspect_err(|err| {
+                                            log::info!("high quality variant is invalid: {err}");
+                                        })
+                                        .ok()
+                                        .map(|img| (hash, img))
                                 })
-                                .collect::<anyhow::Result<HashMap<Hash, (Vec<u8>, u32, u32)>>>()?;
+                                .collect::<HashMap<Hash, (Vec<u8>, u32, u32)>>();
+
+                            // use the high quality variants that fit into the budget
+                            let mut hq_seen: HashSet<Hash> = Default::default();
+                            let hq_candidates: Vec<Hash> = resources_clone
+                                .images
+                                .iter()
+                                .chain(resources_clone.image_arrays.iter())
+                                .filter_map(|img| img.hq_meta.as_ref())
+                                .map(|meta| meta.blake3_hash)
+                                .filter(|hash| hq_seen.insert(*hash))
+                                .collect();
+                            let texture_sizes: Vec<_> = hq_candidates
+                                .iter()
+                                .map(|hash| {
+                                    hq_img_files.get(hash).map(|(_, width, height)| {
+                                        *width as u64 * *height as u64 * 4
+                                    })
+                                })
+                                .collect();
+                            let hq_selected: HashSet<Hash> = hq_candidates
+                                .into_iter()
+                                .zip(hq_assets.select(&texture_sizes))
+                                .filter_map(|(hash, selected)| selected.then_some(hash))
+                                .collect();
+                            let hq_img_file = |img: &MapResourceRef| {
+                                img.hq_meta
+                                    .as_ref()
+                                    .filter(|meta| hq_selected.contains(&meta.blake3_hash))
+                                    .and_then(|meta| hq_img_files.get(&meta.blake3_hash))
+                            };
+                            let img_file = |img: &MapResourceRef| {
+                                img_files
+                                    .get(&img.meta.blake3_hash)
+                                    .ok_or(anyhow!("img with that name not found"))
+                            };
 
                             let images_loading = ClientMapImagesLoading {
                                 images: resources_clone
                                     .images
                                     .into_par_iter()
                                     .map(|img| {
-                                        let meta = if let Some(hq_meta) =
-                                            load_hq_assets.then_some(img.hq_meta.as_ref()).flatten()
-                                        {
-                                            hq_meta
-                                        } else {
-                                            &img.meta
-                                        };
-                                        let (img_data, width, height) = img_files
-                                            .get(&meta.blake3_hash)
-                                            .ok_or(anyhow!("img with that name not found"))?;
-                                        let mut loading_img = ClientMapImageLoading {
-                                            mem: graphics_mt.mem_alloc(
-                                                GraphicsMemoryAllocationType::TextureRgbaU8 {
-                                                    width: (*width as usize).try_into().unwrap(),
-                                                    height: (*height as usize).try_into().unwrap(),
-                                                    flags: TexFlags::empty(),
-                                                },
-                                            ),
-                                            width: *width,
-                                            height: *height,
-                                            depth: 1,
-                                            name: img.name.to_string(),
-                                        };
-                                        loading_img.mem.as_mut_slice().copy_from_slice(img_data);
-                                        if graphics_mt
-                                            .try_flush_mem(&mut loading_img.mem, false)
-                                            .is_err()
-                                        {
+                                        // decode directly into the texture memory,
+                                        // no image is kept decoded longer than needed.
+                                        let (mut mem, width, height) =
+                                            if let Some((img_data, width, height)) =
+                                                hq_img_file(&img)
+                                            {
+                                                let mut mem = Self::alloc_image_mem(
+                                                    &graphics_mt,
+                                                    *width,
+                                                    *height,
+                                                );
+                                                mem.as_mut_slice().copy_from_slice(img_data);
+                                                (mem, *width, *height)
+                                            } else {
+                                                let mut mem = None;
+                                                let img = load_png_image_as_rgba(
+                                                    img_file(&img)?,
+                                                    |width, height, _| {
+                                                        mem.insert(Self::alloc_image_mem(
+                                                            &graphics_mt,
+                                                            width as u32,
+                                                            height as u32,
+                                                        ))
+                                                        .as_mut_slice()
+                                                    },
+                                                )?;
+                                                let (width, height) = (img.width, img.height);
+                                                (
+                                                    mem.ok_or(anyhow!("img was not allocated"))?,
+                                                    width,
+                                                    height,
+                                                )
+                                            };
+                                        if graphics_mt.try_flush_mem(&mut mem, false).is_err() {
                                             // TODO: handle/log ?
                                         }
-                                        anyhow::Ok(loading_img)
+                                        log_image_loaded(img.name.as_str());
+                                        anyhow::Ok(ClientMapImageLoading {
+                                            mem,
+                                            width,
+                                            height,
+                                            depth: 1,
+                                            name: img.name.to_string(),
+                                            tile_opacity: None,
+                                        })
                                     })
                                     .collect::<anyhow::Result<Vec<ClientMapImageLoading>>>()?,
                                 images_2d_array: resources_clone
                                     .image_arrays
                                     .into_par_iter()
                                     .map(|img| {
-                                        let meta = if let Some(hq_meta) =
-                                            load_hq_assets.then_some(img.hq_meta.as_ref()).flatten()
-                                        {
-                                            hq_meta
-                                        } else {
-                                            &img.meta
+                                        let decoded;
+                                        let (img_data, width, height) = match hq_img_file(&img) {
+                                            Some((img_data, width, height)) => {
+                                                (img_data, *width, *height)
+                                            }
+                                            None => {
+                                                decoded = decode(img_file(&img)?)?;
+                                                (&decoded.0, decoded.1, decoded.2)
+                                            }
                                         };
-                                        let (img_data, width, height) = img_files
-                                            .get(&meta.blake3_hash)
-                                            .ok_or(anyhow!("img with that name not found"))?;
-                                        let (width, height, depth, mem) = generate_3d_data(
-                                            *width as usize,
-                                            *height as usize,
-                                            img_data,
-                                        );
+                                        let (width, height, depth, mem, tile_opacity) =
+                                            generate_3d_data(
+                                                width as usize,
+                                                height as usize,
+                                                img_data,
+                                            );
+                                        log_image_loaded(img.name.as_str());
                                         anyhow::Ok(ClientMapImageLoading {
                                             mem,
                                             width: width as u32,
                                             height: height as u32,
                                             depth: depth as u32,
                                             name: img.name.to_string(),
+                                            tile_opacity: Some(tile_opacity),
                                         })
                                     })
                                     .collect::<anyhow::Result<Vec<ClientMapImageLoading>>>()?,
@@ -406,38 +460,26 @@ impl RenderMapLoading {
                             anyhow::Ok(images_loading)
                         },
                         || {
-                            benchmark.bench_multi("decompressing all sounds");
-                            anyhow::Ok(
-                                resources_clone
-                                    .sounds
-                                    .into_par_iter()
-                                    .map(|img| {
-                                        let meta = if let Some(hq_meta) =
-                                            load_hq_assets.then_some(img.hq_meta.as_ref()).flatten()
-                                        {
-                                            hq_meta
-                                        } else {
-                                            &img.meta
-                                        };
-                                        let file = sound_files
-                                            .get(&meta.blake3_hash)
-                                            .ok_or(anyhow!("sound with that hash not found"))?;
+                            let sounds = resources_clone
+                                .sounds
+                                .into_par_iter()
+                                .map(|img| {
+                                    let file = sound_files
+                                        .get(&img.meta.blake3_hash)
+                                        .ok_or(anyhow!("sound with that hash not found"))?;
 
-                                        let mut mem = sound_mt.mem_alloc(file.len());
-                                        mem.as_mut_slice().copy_from_slice(file);
-                                        let _ = sound_mt.try_flush_mem(&mut mem); // ignore error on purpose
+                                    let mut mem = sound_mt.mem_alloc(file.len());
+                                    mem.as_mut_slice().copy_from_slice(file);
+                                    let _ = sound_mt.try_flush_mem(&mut mem); // ignore error on purpose
 
-                                        anyhow::Ok(ClientMapSoundLoading { mem })
-                                    })
-                                    .collect::<anyhow::Result<Vec<_>>>()?,
-                            )
+                                    anyhow::Ok(ClientMapSoundLoading { mem })
+                                })
+                                .collect::<anyhow::Result<Vec<_>>>()?;
+                            log_progress(format!("Loaded map sounds ({})", sounds.len()));
+                            benchmark.bench_multi("decompressing all sounds");
+                            anyhow::Ok(sounds)
                         },
                         || {
-                            let map =
-                                Map::read_with_resources(resources, &map_reader, &runtime_tp)?;
-
-                            benchmark.bench_multi("initialzing the map layers");
-
                             let benchmark = Benchmark::new(do_benchmark);
                             let physics_group = map.groups.physics.clone();
                             let (collision, upload_data) = runtime_tp.join(
@@ -461,11 +503,23 @@ impl RenderMapLoading {
 
                 benchmark.bench("loading the full map (excluding opening it)");
 
-                let (collision, upload_data) = map_prepare?;
+                let (collision, mut upload_data) = map_prepare?;
+                let images = images_loading?;
+
+                let tile_opacities: Vec<_> = images
+                    .images_2d_array
+                    .iter()
+                    .map(|img| img.tile_opacity.as_ref())
+                    .collect();
+                runtime_tp.install(|| {
+                    ClientMapBuffered::calc_chunk_opacities(&mut upload_data, &tile_opacities)
+                });
+                benchmark.bench("calculating the tile layer chunk opacities");
+
                 Ok(ClientMapFileProcessed {
                     collision,
                     upload_data,
-                    images: images_loading?,
+                    images,
                     sounds: sounds_loading?,
                 })
             }),
@@ -483,22 +537,89 @@ impl RenderMapLoading {
         }
     }
 
+    fn alloc_image_mem(
+        graphics_mt: &GraphicsMultiThreaded,
+        width: u32,
+        height: u32,
+    ) -> GraphicsBackendMemory {
+        graphics_mt.mem_alloc(GraphicsMemoryAllocationType::TextureRgbaU8 {
+            width: (width as usize).try_into().unwrap(),
+            height: (height as usize).try_into().unwrap(),
+            flags: TexFlags::empty(),
+        })
+    }
+
+    /// Reads a resource file from disk, or downloads it
+    /// from the resource server if it does not exist yet.
+    fn read_resource(
+        io: &Io,
+        resource_download_server: Option<Url>,
+        downloaded_path: Option<&Path>,
+        is_image: bool,
+        res: &MapResourceRef,
+        meta: &MapResourceMetaData,
+        is_hq: bool,
+    ) -> impl Future<Output = anyhow::Result<Vec<u8>>> + use<> {
+        let download_read_file_path = format!(
+            "map/resources/{}/{}_{}.{}",
+            if is_image { "images" } else { "sounds" },
+            res.name.as_str(),
+            fmt_hash(&meta.blake3_hash),
+            meta.ty.as_str()
+        );
+        let read_file_path = if let Some(downloaded_path) = downloaded_path {
+            downloaded_path.join(&download_read_file_path)
+        } else {
+            download_read_file_path.as_str().into()
+        };
+        let hash = meta.blake3_hash;
+        let file_ty = meta.ty.clone();
+        let file_name = res.name.clone();
+        let fs = io.fs.clone();
+        let http = io.http.clone();
+        async move {
+            match fs.read_file(&read_file_path).await {
+                Ok(file) => Ok(file),
+                Err(err) => {
+                    // try to download file
+                    if let Some(resource_download_server) =
+                        resource_download_server.and_then(|url| {
+                            relative_path_to_url(download_read_file_path.as_ref())
+                                .ok()
+                                .and_then(|name| url.join(&name).ok())
+                        })
+                    {
+                        let file = http
+                            .download_binary(resource_download_server, &hash)
+                            .await
+                            .map_err(|err| anyhow!("failed to download map: {err}"))?
+                            .to_vec();
+                        Self::verify_resource(file_ty.as_str(), file_name.as_str(), &file, is_hq)?;
+                        if let Some(dir) = read_file_path.parent() {
+                            fs.create_dir(dir).await?;
+                        }
+                        fs.write_file(&read_file_path, file.clone()).await?;
+                        anyhow::Ok(file)
+                    } else {
+                        Err(anyhow!(err))
+                    }
+                }
+            }
+        }
+    }
+
     fn verify_resource(
         file_ty: &str,
         file_name: &str,
         file: &[u8],
-        allow_hq_assets: bool,
+        is_hq: bool,
     ) -> anyhow::Result<()> {
         match file_ty {
             "png" => {
                 if let Err(err) = is_png_image_valid(
                     file,
-                    if allow_hq_assets {
-                        PngValidatorOptions {
-                            max_width: 4096.try_into().unwrap(),
-                            max_height: 4096.try_into().unwrap(),
-                            ..Default::default()
-                        }
+                    if is_hq {
+                        PngValidatorOptions::hq()
                     } else {
                         Default::default()
                     },
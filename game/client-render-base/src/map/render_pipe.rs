@@ -7,7 +7,9 @@ use game_interface::types::game::NonZeroGameTickType;
 use hiarc::Hiarc;
 use serde::{Deserialize, Serialize};
 
-use super::{map_buffered::ClientMapBuffered, map_with_visual::MapVisual};
+use super::{
+    map_buffered::ClientMapBuffered, map_with_visual::MapVisual, music_intensity::MusicIntensity,
+};
 
 #[derive(Debug, Hiarc, Serialize, Deserialize)]
 pub struct GameTimeInfo {
@@ -25,6 +27,8 @@ pub struct RenderPipelineBase<'a> {
     pub camera: &'a dyn CameraInterface,
 
     pub map_sound_volume: f64,
+    /// Fades the music stems of the map, `None` plays them like any other sound.
+    pub music_intensity: Option<&'a MusicIntensity>,
 }
 
 pub struct RenderPipeline<'a> {
@@ -42,6 +46,7 @@ impl<'a> RenderPipeline<'a> {
         include_last_anim_point: bool,
         camera: &'a dyn CameraInterface,
         map_sound_volume: f64,
+        music_intensity: Option<&'a MusicIntensity>,
     ) -> RenderPipeline<'a> {
         RenderPipeline {
             base: RenderPipelineBase {
@@ -52,6 +57,7 @@ impl<'a> RenderPipeline<'a> {
                 include_last_anim_point,
                 camera,
                 map_sound_volume,
+                music_intensity,
             },
             buffered_map,
         }
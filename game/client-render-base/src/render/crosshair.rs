@@ -0,0 +1,381 @@
+use game_config::config::{ConfigCrosshairShape, ConfigCrosshairStyle};
+use math::math::vector::{ubvec4, vec2};
+
+/// An axis aligned rectangle in whole pixels,
+/// relative to the pixel the crosshair is centered on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrosshairRect {
+    pub x: i32,
+    pub y: i32,
+    pub w: u32,
+    pub h: u32,
+}
+
+impl CrosshairRect {
+    fn new(x: i32, y: i32, w: u32, h: u32) -> Self {
+        Self { x, y, w, h }
+    }
+
+    /// Grows the rect by `by` pixels on every side.
+    pub fn expand(&self, by: u32) -> Self {
+        Self {
+            x: self.x - by as i32,
+            y: self.y - by as i32,
+            w: self.w + by * 2,
+            h: self.h + by * 2,
+        }
+    }
+
+    /// The corners of the rect in the order top left,
+    /// top right, bottom right & bottom left.
+    pub fn vertices(&self, center: vec2) -> [vec2; 4] {
+        let (x, y) = (center.x + self.x as f32, center.y + self.y as f32);
+        let (w, h) = (self.w as f32, self.h as f32);
+        [
+            vec2::new(x, y),
+            vec2::new(x + w, y),
+            vec2::new(x + w, y + h),
+            vec2::new(x, y + h),
+        ]
+    }
+}
+
+/// A rect of the crosshair together with its color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrosshairQuad {
+    pub rect: CrosshairRect,
+    pub color: ubvec4,
+}
+
+fn arms(
+    thickness: u32,
+    size: u32,
+    gap: u32,
+    with_top: bool,
+    with_center: bool,
+) -> Vec<CrosshairRect> {
+    // the band in which the center lines are
+    let band = -(thickness as i32 / 2);
+    let near = band - gap as i32 - size as i32;
+    let far = band + thickness as i32 + gap as i32;
+
+    let mut rects = vec![
+        CrosshairRect::new(near, band, size, thickness),
+        CrosshairRect::new(far, band, size, thickness),
+        CrosshairRect::new(band, far, thickness, size),
+    ];
+    if with_top {
+        rects.push(CrosshairRect::new(band, near, thickness, size));
+    }
+    if with_center {
+        rects.push(CrosshairRect::new(band, band, thickness, thickness));
+    }
+    rects
+}
+
+/// Rasterizes a ring around the pixel corner at the center,
+/// every row becomes one or two rects.
+/// Rows with the same spans are merged into a single rect.
+fn ring(radius: u32, thickness: u32) -> Vec<CrosshairRect> {
+    let outer = radius as f64;
+    let inner = radius.saturating_sub(thickness) as f64;
+    let half_width = |r: f64, y: f64| {
+        if y.abs() < r {
+            (r * r - y * y).sqrt().round() as i32
+        } else {
+            0
+        }
+    };
+
+    let mut rects: Vec<CrosshairRect> = Vec::new();
+    let mut prev_row: Vec<CrosshairRect> = Vec::new();
+    for y in -(radius as i32)..radius as i32 {
+        // the center of the pixel row
+        let yc = y as f64 + 0.5;
+        let xo = half_width(outer, yc);
+        let xi = half_width(inner, yc);
+        let row: Vec<_> = if xo <= 0 {
+            Vec::new()
+        } else if xi <= 0 {
+            vec![CrosshairRect::new(-xo, y, xo as u32 * 2, 1)]
+        } else {
+            vec![
+                CrosshairRect::new(-xo, y, (xo - xi) as u32, 1),
+                CrosshairRect::new(xi, y, (xo - xi) as u32, 1),
+            ]
+        };
+
+        let same_spans = row.len() == prev_row.len()
+            && row
+                .iter()
+                .zip(prev_row.iter())
+                .all(|(r, p)| r.x == p.x && r.w == p.w);
+        if same_spans && !row.is_empty() {
+            let merged = rects.len() - row.len();
+            for rect in &mut rects[merged..] {
+                rect.h += 1;
+            }
+        } else {
+            rects.extend(row.iter().copied());
+        }
+        prev_row = row;
+    }
+    rects
+}
+
+/// The rects of the crosshair without the outline.
+///
+/// `scale` multiplies all sizes of the style and must be a whole number,
+/// so that every rect stays on the pixel grid.
+pub fn crosshair_rects(style: &ConfigCrosshairStyle, scale: u32) -> Vec<CrosshairRect> {
+    let scale = scale.max(1);
+    let thickness = style.thickness.max(1) * scale;
+    let size = style.size.max(1) * scale;
+    let gap = style.gap * scale;
+    match style.shape {
+        ConfigCrosshairShape::Cross => arms(thickness, size, gap, true, gap == 0),
+        ConfigCrosshairShape::CrossDot => arms(thickness, size, gap, true, true),
+        ConfigCrosshairShape::T => arms(thickness, size, gap, false, gap == 0),
+        ConfigCrosshairShape::Dot => {
+            let band = -(thickness as i32 / 2);
+            vec![CrosshairRect::new(band, band, thickness, thickness)]
+        }
+        ConfigCrosshairShape::Circle => ring(size, thickness),
+    }
+}
+
+/// All quads of the crosshair in render order,
+/// the outline first, then the crosshair itself.
+pub fn crosshair_quads(style: &ConfigCrosshairStyle, scale: u32) -> Vec<CrosshairQuad> {
+    let rects = crosshair_rects(style, scale);
+    let alpha = (style.alpha.clamp(0.0, 1.0) * 255.0).round() as u8;
+    let color = ubvec4::new(style.color.r, style.color.g, style.color.b, alpha);
+    let outline_color = ubvec4::new(
+        style.outline_color.r,
+        style.outline_color.g,
+        style.outline_color.b,
+        alpha,
+    );
+    let outline = style.outline * scale.max(1);
+
+    let mut quads = Vec::with_capacity(rects.len() * 2);
+    if outline > 0 {
+        quads.extend(rects.iter().map(|rect| CrosshairQuad {
+            rect: rect.expand(outline),
+            color: outline_color,
+        }));
+    }
+    quads.extend(rects.iter().map(|&rect| CrosshairQuad { rect, color }));
+    quads
+}
+
+#[cfg(test)]
+mod test {
+    use config::types::ConfRgb;
+    use game_config::config::{ConfigCrosshairShape, ConfigCrosshairStyle};
+    use math::math::vector::{ubvec4, vec2};
+
+    use super::{CrosshairRect, crosshair_quads, crosshair_rects};
+
+    fn style(shape: ConfigCrosshairShape) -> ConfigCrosshairStyle {
+        ConfigCrosshairStyle {
+            shape,
+            size: 6,
+            thickness: 2,
+            gap: 3,
+            color: ConfRgb { r: 255, g: 0, b: 0 },
+            alpha: 1.0,
+            outline: 1,
+            outline_color: ConfRgb::black(),
+        }
+    }
+
+    /// Every pixel covered by the rects, rects must not overlap.
+    fn pixels(rects: &[CrosshairRect]) -> Vec<(i32, i32)> {
+        let mut pixels: Vec<_> = rects
+            .iter()
+            .flat_map(|r| {
+                (r.y..r.y + r.h as i32)
+                    .flat_map(move |y| (r.x..r.x + r.w as i32).map(move |x| (x, y)))
+            })
+            .collect();
+        let count = pixels.len();
+        pixels.sort();
+        pixels.dedup();
+        assert_eq!(count, pixels.len(), "rects overlap");
+        pixels
+    }
+
+    #[test]
+    fn cross() {
+        let rects = crosshair_rects(&style(ConfigCrosshairShape::Cross), 1);
+        assert_eq!(
+            rects,
+            vec![
+                CrosshairRect {
+                    x: -10,
+                    y: -1,
+                    w: 6,
+                    h: 2
+                },
+                CrosshairRect {
+                    x: 4,
+                    y: -1,
+                    w: 6,
+                    h: 2
+                },
+                CrosshairRect {
+                    x: -1,
+                    y: 4,
+                    w: 2,
+                    h: 6
+                },
+                CrosshairRect {
+                    x: -1,
+                    y: -10,
+                    w: 2,
+                    h: 6
+                },
+            ]
+        );
+        // the arms are symmetric around the center
+        let pixels = pixels(&rects);
+        for (x, y) in pixels.iter() {
+            assert!(pixels.contains(&(-x - 1, *y)));
+            assert!(pixels.contains(&(*x, -y - 1)));
+            assert!(pixels.contains(&(*y, *x)));
+        }
+
+        // without a gap the center is filled
+        let mut gapless = style(ConfigCrosshairShape::Cross);
+        gapless.gap = 0;
+        let pixels = pixels(&crosshair_rects(&gapless, 1));
+        assert!(pixels.contains(&(0, 0)) && pixels.contains(&(-1, -1)));
+    }
+
+    #[test]
+    fn cross_dot_and_t() {
+        let rects = crosshair_rects(&style(ConfigCrosshairShape::CrossDot), 1);
+        assert_eq!(rects.len(), 5);
+        assert_eq!(
+            rects[4],
+            CrosshairRect {
+                x: -1,
+                y: -1,
+                w: 2,
+                h: 2
+            }
+        );
+        pixels(&rects);
+
+        let rects = crosshair_rects(&style(ConfigCrosshairShape::T), 1);
+        assert_eq!(rects.len(), 3);
+        assert!(rects.iter().all(|r| r.y >= -1));
+    }
+
+    #[test]
+    fn dot() {
+        let rects = crosshair_rects(&style(ConfigCrosshairShape::Dot), 1);
+        assert_eq!(
+            rects,
+            vec![CrosshairRect {
+                x: -1,
+                y: -1,
+                w: 2,
+                h: 2
+            }]
+        );
+
+        // odd sizes can't be centered, the extra pixel goes to the bottom right
+        let mut odd = style(ConfigCrosshairShape::Dot);
+        odd.thickness = 3;
+        let rects = crosshair_rects(&odd, 1);
+        assert_eq!(
+            rects,
+            vec![CrosshairRect {
+                x: -1,
+                y: -1,
+                w: 3,
+                h: 3
+            }]
+        );
+    }
+
+    #[test]
+    fn circle() {
+        let circle = style(ConfigCrosshairShape::Circle);
+        let rects = crosshair_rects(&circle, 1);
+        let pixels = pixels(&rects);
+
+        for (x, y) in pixels.iter() {
+            // symmetric in all directions
+            assert!(pixels.contains(&(-x - 1, *y)));
+            assert!(pixels.contains(&(*x, -y - 1)));
+            assert!(pixels.contains(&(*y, *x)));
+
+            // inside the ring
+            let dist = ((*x as f64 + 0.5).powi(2) + (*y as f64 + 0.5).powi(2)).sqrt();
+            assert!(dist <= circle.size as f64 + 1.0);
+            assert!(dist >= (circle.size - circle.thickness) as f64 - 1.0);
+        }
+        // the hole in the middle stays empty
+        assert!(!pixels.contains(&(0, 0)));
+        // rows with equal spans are merged
+        assert!(rects.len() < circle.size as usize * 2 * 2);
+    }
+
+    #[test]
+    fn scale_and_vertices() {
+        let cross = style(ConfigCrosshairShape::Cross);
+        let rects = crosshair_rects(&cross, 1);
+        let scaled = crosshair_rects(&cross, 2);
+        for (rect, scaled) in rects.iter().zip(scaled.iter()) {
+            assert_eq!(
+                *scaled,
+                CrosshairRect {
+                    x: rect.x * 2,
+                    y: rect.y * 2,
+                    w: rect.w * 2,
+                    h: rect.h * 2,
+                }
+            );
+        }
+
+        for shape in [
+            ConfigCrosshairShape::Cross,
+            ConfigCrosshairShape::CrossDot,
+            ConfigCrosshairShape::T,
+            ConfigCrosshairShape::Dot,
+            ConfigCrosshairShape::Circle,
+        ] {
+            let quads = crosshair_quads(&style(shape), 2);
+            // outlines first
+            let (outlines, fills) = quads.split_at(quads.len() / 2);
+            assert!(
+                outlines
+                    .iter()
+                    .all(|q| q.color == ubvec4::new(0, 0, 0, 255))
+            );
+            assert!(fills.iter().all(|q| q.color == ubvec4::new(255, 0, 0, 255)));
+            for (outline, fill) in outlines.iter().zip(fills.iter()) {
+                assert_eq!(outline.rect, fill.rect.expand(2));
+            }
+
+            // all vertices are on the pixel grid
+            for quad in quads.iter() {
+                let [tl, tr, br, bl] = quad.rect.vertices(vec2::new(100.0, 50.0));
+                for v in [tl, tr, br, bl] {
+                    assert_eq!(v.x.fract(), 0.0);
+                    assert_eq!(v.y.fract(), 0.0);
+                }
+                assert_eq!(tr.x - tl.x, quad.rect.w as f32);
+                assert_eq!(bl.y - tl.y, quad.rect.h as f32);
+                assert_eq!((br.x, br.y), (tr.x, bl.y));
+            }
+        }
+
+        let mut no_outline = style(ConfigCrosshairShape::Dot);
+        no_outline.outline = 0;
+        assert_eq!(crosshair_quads(&no_outline, 1).len(), 1);
+    }
+}
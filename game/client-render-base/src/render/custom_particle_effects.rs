@@ -0,0 +1,163 @@
+use std::{collections::HashMap, fmt::Debug};
+
+use game_interface::{
+    events::{CUSTOM_EFFECT_PAYLOAD_LEN, GameCustomEffectEvent},
+    types::id_types::CharacterId,
+};
+use math::math::{Rng, vector::vec2};
+
+use super::{particle::Particle, particle_manager::ParticleGroup};
+
+/// A particle effect of a render mod.
+pub trait CustomParticleEffect: Debug {
+    /// Pushes the particles of the effect at `pos` into `particles`.
+    fn spawn(
+        &self,
+        pos: &vec2,
+        owner: Option<CharacterId>,
+        payload: &[u8; CUSTOM_EFFECT_PAYLOAD_LEN],
+        rng: &mut Rng,
+        particles: &mut Vec<(ParticleGroup, Particle)>,
+    );
+}
+
+/// The particle effects for the ids of [`GameCustomEffectEvent`]s.
+#[derive(Debug, Default)]
+pub struct CustomParticleEffects {
+    effects: HashMap<u32, Box<dyn CustomParticleEffect>>,
+}
+
+impl CustomParticleEffects {
+    /// Registers the effect for the id.
+    ///
+    /// Returns the effect that was previously registered for the id.
+    pub fn register(
+        &mut self,
+        id: u32,
+        effect: Box<dyn CustomParticleEffect>,
+    ) -> Option<Box<dyn CustomParticleEffect>> {
+        self.effects.insert(id, effect)
+    }
+
+    pub fn unregister(&mut self, id: u32) -> Option<Box<dyn CustomParticleEffect>> {
+        self.effects.remove(&id)
+    }
+
+    /// Spawns the particles of the effect registered for the event.
+    ///
+    /// Returns `false` if no effect is registered for its id.
+    pub fn spawn(
+        &self,
+        pos: &vec2,
+        owner: Option<CharacterId>,
+        ev: &GameCustomEffectEvent,
+        rng: &mut Rng,
+        particles: &mut Vec<(ParticleGroup, Particle)>,
+    ) -> bool {
+        let Some(effect) = self.effects.get(&ev.id) else {
+            return false;
+        };
+        effect.spawn(pos, owner, &ev.payload, rng, particles);
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use game_interface::{
+        events::{CUSTOM_EFFECT_PAYLOAD_LEN, GameCustomEffectEvent},
+        types::id_types::CharacterId,
+    };
+    use math::math::{Rng, vector::vec2};
+
+    use crate::render::{particle::Particle, particle_manager::ParticleGroup};
+
+    use super::{CustomParticleEffect, CustomParticleEffects};
+
+    /// Spawns as many particles as the first byte of the payload says.
+    #[derive(Debug)]
+    struct Burst(ParticleGroup);
+
+    impl CustomParticleEffect for Burst {
+        fn spawn(
+            &self,
+            pos: &vec2,
+            owner: Option<CharacterId>,
+            payload: &[u8; CUSTOM_EFFECT_PAYLOAD_LEN],
+            _rng: &mut Rng,
+            particles: &mut Vec<(ParticleGroup, Particle)>,
+        ) {
+            particles.extend((0..payload[0]).map(|_| {
+                (
+                    self.0,
+                    Particle {
+                        pos: *pos,
+                        owner,
+                        ..Default::default()
+                    },
+                )
+            }));
+        }
+    }
+
+    fn event(id: u32, count: u8) -> GameCustomEffectEvent {
+        let mut payload = [0; CUSTOM_EFFECT_PAYLOAD_LEN];
+        payload[0] = count;
+        GameCustomEffectEvent { id, payload }
+    }
+
+    #[test]
+    fn register_and_dispatch() {
+        let mut effects = CustomParticleEffects::default();
+        let mut rng = Rng::new(0);
+        let mut particles = Vec::new();
+        let pos = vec2::new(1.0, 2.0);
+
+        // unknown ids are ignored
+        assert!(!effects.spawn(&pos, None, &event(1, 3), &mut rng, &mut particles));
+        assert!(particles.is_empty());
+
+        assert!(
+            effects
+                .register(1, Box::new(Burst(ParticleGroup::Explosions)))
+                .is_none()
+        );
+        assert!(
+            effects
+                .register(2, Box::new(Burst(ParticleGroup::Extra)))
+                .is_none()
+        );
+        assert!(effects.spawn(&pos, None, &event(1, 3), &mut rng, &mut particles));
+        assert_eq!(particles.len(), 3);
+        assert!(
+            particles
+                .iter()
+                .all(|(group, p)| *group == ParticleGroup::Explosions && p.pos == pos)
+        );
+
+        // the event is dispatched to the effect of its id
+        particles.clear();
+        assert!(effects.spawn(&pos, None, &event(2, 2), &mut rng, &mut particles));
+        assert_eq!(particles.len(), 2);
+        assert!(
+            particles
+                .iter()
+                .all(|(group, _)| *group == ParticleGroup::Extra)
+        );
+
+        // registering again replaces the effect
+        assert!(
+            effects
+                .register(2, Box::new(Burst(ParticleGroup::General)))
+                .is_some()
+        );
+        particles.clear();
+        assert!(effects.spawn(&pos, None, &event(2, 1), &mut rng, &mut particles));
+        assert_eq!(particles[0].0, ParticleGroup::General);
+
+        assert!(effects.unregister(1).is_some());
+        particles.clear();
+        assert!(!effects.spawn(&pos, None, &event(1, 3), &mut rng, &mut particles));
+        assert!(particles.is_empty());
+    }
+}
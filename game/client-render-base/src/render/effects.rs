@@ -331,6 +331,23 @@ impl<'a> Effects<'a> {
         self.particle_manager.add(ParticleGroup::Explosions, p, 0.0);
     }
 
+    /// A small impact where a laser or a projectile bounced off a wall.
+    pub fn wall_impact(&mut self, pos: &vec2, owner: Option<CharacterId>) {
+        let rng = &mut self.particle_manager.rng;
+        let p = Particle {
+            ty: ParticleType::Hits,
+            rng: rng.random_int(),
+            owner,
+            pos: *pos,
+            life_span: 0.15,
+            start_size: 48.0 / 32.0,
+            end_size: 0.0,
+            rot: rng.random_float() * PI * 2.0,
+            ..Default::default()
+        };
+        self.particle_manager.add(ParticleGroup::Explosions, p, 0.0);
+    }
+
     pub fn damage_ind(&mut self, pos: &vec2, vel: &vec2, owner: Option<CharacterId>) {
         let rng = &mut self.particle_manager.rng;
         // add the explosion
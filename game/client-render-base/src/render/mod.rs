@@ -1,5 +1,7 @@
 pub mod animation;
 pub mod canvas_mapping;
+pub mod crosshair;
+pub mod custom_particle_effects;
 pub mod default_anim;
 pub mod effects;
 pub mod particle;
@@ -9,7 +9,10 @@ use client_containers::{
     container::ContainerKey,
     particles::{ParticleType, ParticlesContainer},
 };
-use game_interface::types::{id_types::CharacterId, render::character::CharacterInfo};
+use game_interface::{
+    events::GameCustomEffectEvent,
+    types::{id_types::CharacterId, render::character::CharacterInfo},
+};
 use graphics::{
     graphics::graphics::Graphics,
     handles::{
@@ -34,11 +37,12 @@ use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 use vanilla::collision::collision::Collision;
 
-use super::particle::Particle;
+use super::{custom_particle_effects::CustomParticleEffects, particle::Particle};
 
-const MAX_PARTICLES: usize = 1024 * 8;
+/// The maximum amount of particles per group by default.
+const DEFAULT_GROUP_CAPS: [usize; ParticleGroup::Count as usize] = [4096, 2048, 2048, 4096];
 
-#[derive(Copy, Hiarc, Clone, PartialEq, FromPrimitive)]
+#[derive(Debug, Copy, Hiarc, Clone, PartialEq, FromPrimitive)]
 pub enum ParticleGroup {
     ProjectileTrail = 0,
     Explosions,
@@ -49,6 +53,35 @@ pub enum ParticleGroup {
     Count,
 }
 
+/// Decides which particles are actually emitted.
+#[derive(Debug, Hiarc, Clone)]
+pub struct ParticleEmission {
+    /// The fraction of particles that are emitted, in `[0..1]`.
+    /// Particles are dropped randomly, so effects still look alike.
+    pub amount: f32,
+    /// The maximum amount of particles alive per group,
+    /// further particles are dropped.
+    pub group_caps: [usize; ParticleGroup::Count as usize],
+}
+
+impl Default for ParticleEmission {
+    fn default() -> Self {
+        Self {
+            amount: 1.0,
+            group_caps: DEFAULT_GROUP_CAPS,
+        }
+    }
+}
+
+impl ParticleEmission {
+    /// Whether a particle is emitted into a group,
+    /// that currently contains `group_len` particles.
+    pub fn emit(&self, rng: &mut Rng, group: ParticleGroup, group_len: usize) -> bool {
+        group_len < self.group_caps[group as usize]
+            && (self.amount >= 1.0 || rng.random_float() < self.amount)
+    }
+}
+
 #[derive(Debug, Hiarc)]
 pub struct ParticleManager {
     particle_quad_container: QuadContainer,
@@ -71,6 +104,13 @@ pub struct ParticleManager {
     pub last_100_time: Duration,
 
     pub rng: Rng,
+    emission: ParticleEmission,
+
+    #[hiarc_skip_unsafe]
+    pub custom_effects: CustomParticleEffects,
+    /// Reused for the particles of custom effects.
+    #[hiarc_skip_unsafe]
+    custom_particles: Vec<(ParticleGroup, Particle)>,
 }
 
 impl ParticleManager {
@@ -84,7 +124,11 @@ impl ParticleManager {
             canvas_mapping: CanvasMappingIngame::new(graphics),
             stream_handle: graphics.stream_handle.clone(),
 
-            particle_groups: Default::default(),
+            // the groups never grow beyond their caps,
+            // so adding particles never allocates
+            particle_groups: std::array::from_fn(|group| {
+                VecDeque::with_capacity(DEFAULT_GROUP_CAPS[group])
+            }),
             friction_fraction: 0.0,
 
             last_time: *cur_time,
@@ -106,17 +150,58 @@ impl ParticleManager {
             ),
 
             rng: Rng::new(0),
+            emission: Default::default(),
+
+            custom_effects: Default::default(),
+            custom_particles: Default::default(),
         }
     }
 
+    /// See [`ParticleEmission::amount`].
+    pub fn set_amount(&mut self, amount: f32) {
+        self.emission.amount = amount.clamp(0.0, 1.0);
+    }
+
+    /// See [`ParticleEmission::group_caps`].
+    pub fn set_group_cap(&mut self, group: ParticleGroup, cap: usize) {
+        self.emission.group_caps[group as usize] = cap;
+        let particles = &mut self.particle_groups[group as usize];
+        particles.truncate(cap);
+        particles.reserve(cap.saturating_sub(particles.len()));
+    }
+
     pub fn reset(&mut self) {
         // reset particles
         self.particle_groups.iter_mut().for_each(|p| p.clear());
     }
 
     pub fn add(&mut self, group: ParticleGroup, mut part: Particle, time_passed: f32) {
+        let particles = &mut self.particle_groups[group as usize];
+        if !self.emission.emit(&mut self.rng, group, particles.len()) {
+            return;
+        }
         part.life = time_passed;
-        self.particle_groups[group as usize].push_back(part);
+        particles.push_back(part);
+    }
+
+    /// Adds the particles of the custom effect registered for the event.
+    ///
+    /// Returns `false` if no effect is registered for its id.
+    pub fn add_custom_effect(
+        &mut self,
+        pos: &vec2,
+        owner: Option<CharacterId>,
+        ev: &GameCustomEffectEvent,
+    ) -> bool {
+        let mut particles = std::mem::take(&mut self.custom_particles);
+        let spawned = self
+            .custom_effects
+            .spawn(pos, owner, ev, &mut self.rng, &mut particles);
+        for (group, part) in particles.drain(..) {
+            self.add(group, part, 0.0);
+        }
+        self.custom_particles = particles;
+        spawned
     }
 
     pub fn update_rates(&mut self) {
@@ -382,3 +467,48 @@ impl ParticleManager {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use math::math::Rng;
+
+    use super::{ParticleEmission, ParticleGroup};
+
+    #[test]
+    fn emission_amount() {
+        const TRIES: usize = 100_000;
+        let mut rng = Rng::new(0);
+        let mut emitted = |amount: f32| {
+            let emission = ParticleEmission {
+                amount,
+                ..Default::default()
+            };
+            (0..TRIES)
+                .filter(|_| emission.emit(&mut rng, ParticleGroup::General, 0))
+                .count()
+        };
+
+        assert_eq!(emitted(1.0), TRIES);
+        assert_eq!(emitted(0.0), 0);
+        // the dropped particles are spread evenly
+        for amount in [0.1, 0.25, 0.5, 0.75] {
+            let ratio = emitted(amount) as f32 / TRIES as f32;
+            assert!(
+                (ratio - amount).abs() < 0.01,
+                "amount: {amount}, ratio: {ratio}"
+            );
+        }
+    }
+
+    #[test]
+    fn emission_caps() {
+        let mut rng = Rng::new(0);
+        let mut emission = ParticleEmission::default();
+        emission.group_caps[ParticleGroup::Explosions as usize] = 10;
+
+        assert!(emission.emit(&mut rng, ParticleGroup::Explosions, 9));
+        assert!(!emission.emit(&mut rng, ParticleGroup::Explosions, 10));
+        // other groups have their own cap
+        assert!(emission.emit(&mut rng, ParticleGroup::General, 10));
+    }
+}
@@ -1,12 +1,21 @@
 use camera::Camera;
 use client_containers::{container::ContainerKey, ninja::NinjaContainer, weapons::WeaponContainer};
 use client_render_base::render::{
-    canvas_mapping::CanvasMappingIngame, toolkit::get_sprite_scale_impl,
+    canvas_mapping::CanvasMappingIngame, crosshair::crosshair_quads, toolkit::get_sprite_scale_impl,
 };
+use game_config::config::{ConfigCrosshair, ConfigCrosshairMode};
+use std::time::Duration;
+
 use game_interface::types::weapons::WeaponType;
 use graphics::{
-    graphics::graphics::Graphics, handles::quad_container::quad_container::QuadContainer,
-    quad_container::Quad, streaming::quad_scope_begin,
+    graphics::graphics::Graphics,
+    handles::{
+        canvas::canvas::GraphicsCanvasHandle, quad_container::quad_container::QuadContainer,
+        stream::stream::GraphicsStreamHandle, stream_types::StreamedQuad,
+        texture::texture::TextureType,
+    },
+    quad_container::Quad,
+    streaming::quad_scope_begin,
 };
 use graphics_types::rendering::State;
 use math::math::vector::{dvec2, vec2};
@@ -20,30 +29,83 @@ pub struct RenderCursorPipe<'a> {
     pub cur_weapon: WeaponType,
     pub is_ninja: bool,
     pub camera: &'a Camera,
+    pub cur_time: &'a Duration,
+    pub crosshair: &'a ConfigCrosshair,
+    /// The crosshair is scaled by this, rounded to whole pixels.
+    pub pixels_per_point: f32,
+}
+
+/// How long a hit marker is visible.
+const HIT_MARKER_DURATION: Duration = Duration::from_millis(300);
+
+#[derive(Debug, Clone, Copy)]
+struct HitMarker {
+    start_time: Duration,
+    damage: u32,
+    killing_blow: bool,
 }
 
 pub struct RenderCursor {
     cursor_quad_container: QuadContainer,
     canvas_mapping: CanvasMappingIngame,
+    canvas_handle: GraphicsCanvasHandle,
+    stream_handle: GraphicsStreamHandle,
+
+    hit_marker: Option<HitMarker>,
 }
 
 impl RenderCursor {
     pub fn new(graphics: &Graphics) -> Self {
         let (scale_x, scale_y) = get_sprite_scale_impl(2, 2);
 
-        let cursor_quad_container = graphics.quad_container_handle.create_quad_container(
-            [Quad::new()
+        let mut quads = vec![
+            Quad::new()
                 .from_width_and_height_centered(2.0 * scale_x, 2.0 * scale_y)
-                .with_uv_from_points(&vec2::new(0.0, 0.0), &vec2::new(1.0, 1.0))]
-            .into(),
+                .with_uv_from_points(&vec2::new(0.0, 0.0), &vec2::new(1.0, 1.0)),
+        ];
+        // the four arms of the hit marker, diagonal around the cursor
+        quads.extend(
+            [(1.0, 1.0), (-1.0, 1.0), (-1.0, -1.0), (1.0, -1.0)].map(|(x, y)| {
+                let (len, thickness) = (0.5, 0.1);
+                let offset = 0.75 / std::f32::consts::SQRT_2;
+                Quad::new()
+                    .from_rect(
+                        x * offset - len / 2.0,
+                        y * offset - thickness / 2.0,
+                        len,
+                        thickness,
+                    )
+                    .with_rotation(if x * y > 0.0 {
+                        std::f32::consts::FRAC_PI_4
+                    } else {
+                        -std::f32::consts::FRAC_PI_4
+                    })
+            }),
         );
 
+        let cursor_quad_container = graphics
+            .quad_container_handle
+            .create_quad_container(quads.into());
+
         Self {
             cursor_quad_container,
             canvas_mapping: CanvasMappingIngame::new(graphics),
+            canvas_handle: graphics.canvas_handle.clone(),
+            stream_handle: graphics.stream_handle.clone(),
+
+            hit_marker: None,
         }
     }
 
+    /// Shows a hit marker around the cursor, scaled by the damage.
+    pub fn add_hit(&mut self, cur_time: &Duration, damage: u32, killing_blow: bool) {
+        self.hit_marker = Some(HitMarker {
+            start_time: *cur_time,
+            damage,
+            killing_blow,
+        });
+    }
+
     pub fn render(&self, pipe: &mut RenderCursorPipe) {
         let mut state = State::default();
         let camera = Camera::new(
@@ -74,14 +136,76 @@ impl RenderCursor {
         let c = pipe.mouse_cursor;
         let c = vec2::new(c.x as f32, c.y as f32);
 
-        self.cursor_quad_container.render_quad_container_as_sprite(
-            0,
-            c.x,
-            c.y,
-            1.0,
-            1.0,
-            draw_scope,
-            texture.into(),
+        let mode = pipe.crosshair.mode;
+        if mode != ConfigCrosshairMode::Crosshair {
+            self.cursor_quad_container.render_quad_container_as_sprite(
+                0,
+                c.x,
+                c.y,
+                1.0,
+                1.0,
+                draw_scope,
+                texture.into(),
+            );
+        }
+        if mode != ConfigCrosshairMode::WeaponCursor {
+            self.render_crosshair(pipe, &state, c);
+        }
+
+        if let Some(hit) = self.hit_marker {
+            let elapsed = pipe.cur_time.saturating_sub(hit.start_time);
+            if elapsed < HIT_MARKER_DURATION {
+                let alpha = 1.0 - elapsed.as_secs_f32() / HIT_MARKER_DURATION.as_secs_f32();
+                let scale = 1.0 + hit.damage.min(10) as f32 / 10.0;
+                if hit.killing_blow {
+                    draw_scope.set_colors_from_single(1.0, 0.2, 0.2, alpha);
+                } else {
+                    draw_scope.set_colors_from_single(1.0, 1.0, 1.0, alpha);
+                }
+                for index in 1..=4 {
+                    self.cursor_quad_container.render_quad_container_as_sprite(
+                        index,
+                        c.x,
+                        c.y,
+                        scale,
+                        scale,
+                        draw_scope,
+                        TextureType::None,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Renders the crosshair in screen pixels, so it stays crisp
+    /// regardless of the zoom.
+    fn render_crosshair(&self, pipe: &RenderCursorPipe, ingame_state: &State, cursor: vec2) {
+        let style = pipe.crosshair.style(pipe.cur_weapon, pipe.is_ninja);
+        let scale = pipe.pixels_per_point.round().max(1.0) as u32;
+
+        let (w, h) = (
+            self.canvas_handle.canvas_width() as f32,
+            self.canvas_handle.canvas_height() as f32,
+        );
+        let (tl_x, tl_y, br_x, br_y) = ingame_state.get_canvas_mapping();
+        let center = vec2::new(
+            ((cursor.x - tl_x) / (br_x - tl_x) * w).round(),
+            ((cursor.y - tl_y) / (br_y - tl_y) * h).round(),
         );
+
+        let mut state = State::default();
+        state.map_canvas(0.0, 0.0, w, h);
+
+        let quads: Vec<_> = crosshair_quads(style, scale)
+            .into_iter()
+            .map(|quad| {
+                let [tl, tr, br, bl] = quad.rect.vertices(center);
+                StreamedQuad::default()
+                    .pos_free_form(tl, tr, br, bl)
+                    .color(quad.color)
+            })
+            .collect();
+        self.stream_handle
+            .render_quads(&quads, state, TextureType::None);
     }
 }
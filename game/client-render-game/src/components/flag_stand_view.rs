@@ -0,0 +1,285 @@
+use std::time::Duration;
+
+use game_interface::types::{
+    flag::FlagType,
+    id_types::CharacterId,
+    render::{flag::FlagRenderInfo, game::game_match::MatchSide},
+};
+use graphics::{
+    graphics::graphics::Graphics,
+    handles::{
+        canvas::canvas::{GraphicsCanvasHandle, OffscreenCanvas},
+        stream::stream::GraphicsStreamHandle,
+        stream_types::StreamedQuad,
+        texture::texture::TextureType,
+    },
+};
+use graphics_types::rendering::{BlendType, ColorMaskMode, State};
+use legacy_map::mapdef_06::EntityTiles;
+use math::math::vector::{ubvec4, vec2};
+use vanilla::collision::collision::Collision;
+
+/// How often the view is rendered again, the ingame view is
+/// only rendered once, so a lower rate is cheaper.
+pub const FLAG_STAND_VIEW_UPDATE_INTERVAL: Duration = Duration::from_millis(100);
+/// Distance of the view to the screen edges in pixels.
+const MARGIN: f32 = 10.0;
+/// Width of the frame around the view in pixels.
+const FRAME: f32 = 2.0;
+
+/// The positions of the flag stands in the map, in tiles.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FlagStands {
+    pub red: Option<vec2>,
+    pub blue: Option<vec2>,
+}
+
+impl FlagStands {
+    /// Finds the flag spawn tiles in the game layer.
+    /// If a side has multiple, the first one counts.
+    pub fn new(collision: &Collision) -> Self {
+        let mut res = Self::default();
+        for y in 0..collision.get_playfield_height() as i32 {
+            for x in 0..collision.get_playfield_width() as i32 {
+                let index = collision.get_tile(x * 32, y * 32);
+                let stand = if index == EntityTiles::FlagSpawnRed as u8 {
+                    &mut res.red
+                } else if index == EntityTiles::FlagSpawnBlue as u8 {
+                    &mut res.blue
+                } else {
+                    continue;
+                };
+                stand.get_or_insert(vec2::new(x as f32 + 0.5, y as f32 + 0.5));
+            }
+        }
+        res
+    }
+
+    pub fn of(&self, side: MatchSide) -> Option<vec2> {
+        match side {
+            MatchSide::Red => self.red,
+            MatchSide::Blue => self.blue,
+        }
+    }
+}
+
+/// The position the flag stand view should show,
+/// or `None` if it should not be shown.
+///
+/// The view is only active in sided games, while the character
+/// carries the flag of the other side.
+pub fn flag_stand_view_target<'a>(
+    character_id: &CharacterId,
+    side: Option<MatchSide>,
+    flags: impl IntoIterator<Item = &'a FlagRenderInfo>,
+    stands: &FlagStands,
+) -> Option<vec2> {
+    let side = side?;
+    let own_flag = match side {
+        MatchSide::Red => FlagType::Red,
+        MatchSide::Blue => FlagType::Blue,
+    };
+    let carries_enemy_flag = flags
+        .into_iter()
+        .any(|flag| flag.owner_id == Some(*character_id) && flag.ty != own_flag);
+    if carries_enemy_flag {
+        stands.of(side)
+    } else {
+        None
+    }
+}
+
+/// A small view of the own flag stand in a corner of the screen.
+///
+/// The view is rendered into an offscreen canvas at a reduced rate
+/// & drawn on top of the hud every frame.
+pub struct FlagStandView {
+    canvas_handle: GraphicsCanvasHandle,
+    stream_handle: GraphicsStreamHandle,
+
+    canvas: Option<OffscreenCanvas>,
+    last_update: Option<Duration>,
+    stands: Option<FlagStands>,
+}
+
+impl FlagStandView {
+    pub fn new(graphics: &Graphics) -> Self {
+        Self {
+            canvas_handle: graphics.canvas_handle.clone(),
+            stream_handle: graphics.stream_handle.clone(),
+
+            canvas: None,
+            last_update: None,
+            stands: None,
+        }
+    }
+
+    /// The flag stands of the map, only searched once.
+    pub fn stands(&mut self, collision: &Collision) -> FlagStands {
+        *self
+            .stands
+            .get_or_insert_with(|| FlagStands::new(collision))
+    }
+
+    /// Size of the view for a screen of the given size.
+    fn view_size(width: u32, height: u32, size: f32) -> (u32, u32) {
+        (
+            ((width as f32 * size).round() as u32).max(1),
+            ((height as f32 * size).round() as u32).max(1),
+        )
+    }
+
+    /// Returns the canvas to render the view into,
+    /// if it's time to update the view.
+    pub fn begin_update(
+        &mut self,
+        cur_time: &Duration,
+        width: u32,
+        height: u32,
+        size: f32,
+    ) -> Option<OffscreenCanvas> {
+        let (w, h) = Self::view_size(width, height, size);
+        let canvas = match self.canvas.take() {
+            Some(canvas) if canvas.width() == w && canvas.height() == h => {
+                if self.last_update.is_some_and(|last_update| {
+                    cur_time.saturating_sub(last_update) < FLAG_STAND_VIEW_UPDATE_INTERVAL
+                }) {
+                    self.canvas = Some(canvas);
+                    return None;
+                }
+                canvas
+            }
+            _ => self.canvas_handle.offscreen_canvas(w, h, 1.0, None),
+        };
+        self.last_update = Some(*cur_time);
+        self.canvas = Some(canvas.clone());
+        Some(canvas)
+    }
+
+    /// Frees the canvas while the view is not shown.
+    pub fn deactivate(&mut self) {
+        self.canvas = None;
+        self.last_update = None;
+    }
+
+    /// Draws the last rendered view in the top right corner
+    /// of a screen of the given size.
+    pub fn render(&self, width: u32, height: u32) {
+        let Some(canvas) = &self.canvas else {
+            return;
+        };
+        let (w, h) = (canvas.width() as f32, canvas.height() as f32);
+        let pos = vec2::new(width as f32 - w - MARGIN, MARGIN);
+
+        let mut state = State::new();
+        state.map_canvas(0.0, 0.0, width as f32, height as f32);
+        self.stream_handle.render_quads(
+            &[StreamedQuad::default()
+                .from_pos_and_size(
+                    pos - vec2::new(FRAME, FRAME),
+                    vec2::new(w + FRAME * 2.0, h + FRAME * 2.0),
+                )
+                .color(ubvec4::new(0, 0, 0, 150))],
+            state,
+            TextureType::None,
+        );
+
+        state.set_color_mask(ColorMaskMode::WriteColorOnly);
+        state.blend(BlendType::None);
+        self.stream_handle.render_quads(
+            &[StreamedQuad::default()
+                .from_pos_and_size(pos, vec2::new(w, h))
+                .color(ubvec4::new(255, 255, 255, 255))
+                .tex_free_form(
+                    vec2::new(0.0, 0.0),
+                    vec2::new(1.0, 0.0),
+                    vec2::new(1.0, 1.0),
+                    vec2::new(0.0, 1.0),
+                )],
+            state,
+            TextureType::ColorAttachmentOfOffscreen(canvas.clone()),
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use game_interface::types::{
+        flag::FlagType,
+        id_gen::IdGenerator,
+        id_types::CharacterId,
+        render::{flag::FlagRenderInfo, game::game_match::MatchSide},
+    };
+    use math::math::vector::vec2;
+
+    use super::{FlagStands, flag_stand_view_target};
+
+    fn flag(ty: FlagType, owner_id: Option<CharacterId>) -> FlagRenderInfo {
+        FlagRenderInfo {
+            pos: Default::default(),
+            ty,
+            owner_id,
+            return_time_left: None,
+            phased: false,
+        }
+    }
+
+    #[test]
+    fn activation() {
+        let id_gen = IdGenerator::default();
+        let carrier = id_gen.next_id();
+        let other = id_gen.next_id();
+        let stands = FlagStands {
+            red: Some(vec2::new(2.5, 3.5)),
+            blue: Some(vec2::new(40.5, 3.5)),
+        };
+
+        // carrying the enemy flag shows the own stand
+        let flags = [
+            flag(FlagType::Red, None),
+            flag(FlagType::Blue, Some(carrier)),
+        ];
+        assert_eq!(
+            flag_stand_view_target(&carrier, Some(MatchSide::Red), &flags, &stands),
+            stands.red
+        );
+        // only for the carrier
+        assert_eq!(
+            flag_stand_view_target(&other, Some(MatchSide::Red), &flags, &stands),
+            None
+        );
+        // not in games without sides
+        assert_eq!(
+            flag_stand_view_target(&carrier, None, &flags, &stands),
+            None
+        );
+        // not for the own flag
+        let flags = [
+            flag(FlagType::Red, Some(carrier)),
+            flag(FlagType::Blue, None),
+        ];
+        assert_eq!(
+            flag_stand_view_target(&carrier, Some(MatchSide::Red), &flags, &stands),
+            None
+        );
+        // not without a flag
+        assert_eq!(
+            flag_stand_view_target(&carrier, Some(MatchSide::Blue), std::iter::empty(), &stands),
+            None
+        );
+        // not if the map has no stand for the own side
+        let flags = [flag(FlagType::Red, Some(carrier))];
+        assert_eq!(
+            flag_stand_view_target(
+                &carrier,
+                Some(MatchSide::Blue),
+                &flags,
+                &FlagStands {
+                    red: stands.red,
+                    blue: None
+                }
+            ),
+            None
+        );
+    }
+}
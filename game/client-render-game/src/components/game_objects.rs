@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{f32::consts::PI, time::Duration};
 
 use base::linked_hash_map_view::FxLinkedHashMap;
 use camera::CameraInterface;
@@ -403,6 +403,58 @@ impl GameObjectsRender {
             quad_scope,
             texture.into(),
         );
+
+        if let Some(time_left) = flag.return_time_left.filter(|_| flag.owner_id.is_none()) {
+            let color = match ty {
+                FlagType::Red => vec4::new(1.0, 0.3, 0.3, 0.8 * phased_alpha),
+                FlagType::Blue => vec4::new(0.3, 0.5, 1.0, 0.8 * phased_alpha),
+            };
+            self.render_return_ring(
+                &vec2::new(pos.x, pos.y - size * 0.75),
+                size * 0.6,
+                time_left,
+                color,
+                base_state,
+            );
+        }
+    }
+
+    /// Renders the remaining return time of a dropped flag
+    /// as a ring that shrinks clockwise, starting at the top.
+    fn render_return_ring(
+        &self,
+        center: &vec2,
+        radius: f32,
+        time_left: f32,
+        color: vec4,
+        base_state: &State,
+    ) {
+        const SEGMENTS: usize = 32;
+        const THICKNESS: f32 = 3.0 / 32.0;
+
+        let time_left = time_left.clamp(0.0, 1.0);
+        let full_angle = time_left * 2.0 * PI;
+        let segment_angle = 2.0 * PI / SEGMENTS as f32;
+        let start_angle = -PI / 2.0;
+        let point =
+            |angle: f32, radius: f32| *center + vec2::new(angle.cos(), angle.sin()) * radius;
+
+        let quads: Vec<_> = (0..(time_left * SEGMENTS as f32).ceil() as usize)
+            .map(|i| {
+                let a1 = start_angle + i as f32 * segment_angle;
+                let a2 = start_angle + ((i + 1) as f32 * segment_angle).min(full_angle);
+                StreamedQuad::default()
+                    .pos_free_form(
+                        point(a1, radius),
+                        point(a2, radius),
+                        point(a2, radius - THICKNESS),
+                        point(a1, radius - THICKNESS),
+                    )
+                    .colorf(color)
+            })
+            .collect();
+        self.stream_handle
+            .render_quads(&quads, *base_state, TextureType::None);
     }
 
     pub fn render_laser(
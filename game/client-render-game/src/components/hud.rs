@@ -10,7 +10,8 @@ use client_containers::{
 };
 use client_render::hud::page::{HudRender, HudRenderPipe};
 use client_render_base::render::tee::RenderTee;
-use client_ui::hud::user_data::RenderDateTime;
+use client_ui::hud::user_data::{RenderDateTime, RenderRaceSplits};
+use game_config::config::{ConfigHud, ConfigHudStatusStyle};
 use game_interface::types::{
     emoticons::{EnumCount, IntoEnumIterator},
     game::{GameTickType, NonZeroGameTickType},
@@ -28,17 +29,54 @@ use graphics::{
     handles::{
         canvas::canvas::GraphicsCanvasHandle,
         quad_container::quad_container::{QuadContainer, QuadContainerRenderCount},
+        stream::stream::GraphicsStreamHandle,
+        stream_types::StreamedQuad,
+        texture::texture::{TextureContainer, TextureType},
     },
     quad_container::Quad,
-    streaming::quad_scope_begin,
+    streaming::{DrawScope, quad_scope_begin},
+};
+use graphics_types::rendering::{BlendType, State};
+use math::math::{
+    PI,
+    vector::{vec2, vec4},
 };
-use graphics_types::rendering::State;
-use math::math::{PI, vector::vec2};
 use ui_base::ui::UiCreator;
 use vanilla::weapons::definitions::weapon_def::{get_weapon_sprite_scale, get_weapon_visual_scale};
 
+use super::hud_status::{
+    STATUS_MAX_POINTS, StatusLayout, StatusRow, StatusRowAnim, fire_delay_progress,
+    status_bar_fill, status_pips,
+};
+
 const GRID_SIZE: f32 = 24.0;
 
+/// The colors of the bars, if the status is shown as bars.
+const HEALTH_BAR_COLOR: vec4 = vec4 {
+    x: 0.85,
+    y: 0.15,
+    z: 0.15,
+    w: 1.0,
+};
+const ARMOR_BAR_COLOR: vec4 = vec4 {
+    x: 0.95,
+    y: 0.65,
+    z: 0.1,
+    w: 1.0,
+};
+const AMMO_BAR_COLOR: vec4 = vec4 {
+    x: 0.8,
+    y: 0.8,
+    z: 0.8,
+    w: 1.0,
+};
+const BAR_BACKGROUND_COLOR: vec4 = vec4 {
+    x: 0.0,
+    y: 0.0,
+    z: 0.0,
+    w: 0.4,
+};
+
 pub struct RenderHudPipe<'a> {
     pub hud_container: &'a mut HudContainer,
     pub hud_key: Option<&'a ContainerKey>,
@@ -55,6 +93,8 @@ pub struct RenderHudPipe<'a> {
     pub ctf_container: &'a mut CtfContainer,
     pub character_infos: &'a FxLinkedHashMap<CharacterId, CharacterInfo>,
     pub date_time: &'a Option<RenderDateTime>,
+    pub race_splits: &'a Option<RenderRaceSplits>,
+    pub settings: &'a ConfigHud,
 }
 
 pub struct RenderOffsetsVanilla {
@@ -94,7 +134,12 @@ pub struct RenderHud {
     vanilla_offsets: RenderOffsetsVanilla,
     ddrace_offsets: RenderOffsetsDdrace,
 
+    status_layout: StatusLayout,
+    health_anim: StatusRowAnim,
+    armor_anim: StatusRowAnim,
+
     canvas_handle: GraphicsCanvasHandle,
+    stream_handle: GraphicsStreamHandle,
 }
 
 impl RenderHud {
@@ -102,18 +147,22 @@ impl RenderHud {
         let mut quads: Vec<Quad> = Default::default();
 
         let x = GRID_SIZE / 3.0;
-        let y = GRID_SIZE / 3.0;
 
+        let status_layout = StatusLayout {
+            origin: vec2::new(GRID_SIZE / 3.0, GRID_SIZE / 3.0),
+            cell_size: GRID_SIZE,
+        };
         let vanilla_offsets = {
             // ammo of the different weapons
+            let ammo_pos = status_layout.row_pos(StatusRow::Ammo);
             let weapon_ammo_offsets = (0..WeaponType::COUNT)
                 .map(|_| {
                     let offset = quads.len();
-                    quads.extend((0..10).map(|index| {
+                    quads.extend((0..STATUS_MAX_POINTS).map(|index| {
                         Quad::new()
                             .from_rect(
-                                x + index as f32 * GRID_SIZE + 1.0,
-                                y + GRID_SIZE * 2.0,
+                                ammo_pos.x + index as f32 * GRID_SIZE + 1.0,
+                                ammo_pos.y,
                                 GRID_SIZE * 10.0 / 12.0,
                                 GRID_SIZE * 10.0 / 12.0,
                             )
@@ -125,19 +174,26 @@ impl RenderHud {
 
             // hearts
             let heart_offset = quads.len();
-            quads.extend((0..10).map(|index| {
+            let health_pos = status_layout.row_pos(StatusRow::Health);
+            quads.extend((0..STATUS_MAX_POINTS).map(|index| {
                 Quad::new()
-                    .from_rect(x + index as f32 * GRID_SIZE, y, GRID_SIZE, GRID_SIZE)
+                    .from_rect(
+                        health_pos.x + index as f32 * GRID_SIZE,
+                        health_pos.y,
+                        GRID_SIZE,
+                        GRID_SIZE,
+                    )
                     .with_uv_from_points(&vec2::new(0.0, 0.0), &vec2::new(1.0, 1.0))
             }));
 
             // shields
             let shield_offset = quads.len();
-            quads.extend((0..10).map(|index| {
+            let armor_pos = status_layout.row_pos(StatusRow::Armor);
+            quads.extend((0..STATUS_MAX_POINTS).map(|index| {
                 Quad::new()
                     .from_rect(
-                        x + index as f32 * GRID_SIZE,
-                        y + GRID_SIZE,
+                        armor_pos.x + index as f32 * GRID_SIZE,
+                        armor_pos.y,
                         GRID_SIZE,
                         GRID_SIZE,
                     )
@@ -357,76 +413,167 @@ impl RenderHud {
             vanilla_offsets,
             ddrace_offsets,
 
+            status_layout,
+            health_anim: Default::default(),
+            armor_anim: Default::default(),
+
             canvas_handle: graphics.canvas_handle.clone(),
+            stream_handle: graphics.stream_handle.clone(),
 
             ui: HudRender::new(graphics, creator),
         }
     }
 
-    fn render_vanilla(
-        &mut self,
-        state: &State,
-        info: &LocalCharacterVanilla,
-        hud: &Hud,
-        weapons: &Weapons,
-        cur_weapon: WeaponType,
+    /// Renders a row of pips, `full` of them with the `full` texture
+    /// & the rest with the `empty` texture.
+    #[allow(clippy::too_many_arguments)]
+    fn render_pips(
+        &self,
+        offset: usize,
+        full: usize,
+        empty: usize,
+        draw_scope: DrawScope<4>,
+        full_texture: &TextureContainer,
+        empty_texture: Option<&TextureContainer>,
+        flash: f32,
     ) {
-        let mut draw_scope = quad_scope_begin();
-        draw_scope.set_state(state);
-        draw_scope.set_colors_from_single(1.0, 1.0, 1.0, 1.0);
-
-        // render heart
-        let cur_health = info.health.min(10) as usize;
-        let texture = &hud.vanilla.heart;
         self.quad_container.render_quad_container(
-            self.vanilla_offsets.heart_offset,
-            &QuadContainerRenderCount::Count(cur_health),
+            offset,
+            &QuadContainerRenderCount::Count(full),
             0.0,
             0.0,
             1.0,
             1.0,
             draw_scope,
-            texture.into(),
-        );
-        let texture = &hud.vanilla.heart_empty;
-        self.quad_container.render_quad_container(
-            self.vanilla_offsets.heart_offset + cur_health,
-            &QuadContainerRenderCount::Count(10 - cur_health),
-            0.0,
-            0.0,
-            1.0,
-            1.0,
-            draw_scope,
-            texture.into(),
+            full_texture.into(),
         );
+        if let Some(empty_texture) = empty_texture {
+            self.quad_container.render_quad_container(
+                offset + full,
+                &QuadContainerRenderCount::Count(empty),
+                0.0,
+                0.0,
+                1.0,
+                1.0,
+                draw_scope,
+                empty_texture.into(),
+            );
+        }
+        if flash > 0.0 {
+            let mut draw_scope = draw_scope;
+            draw_scope.blend(BlendType::AlphaAdditive);
+            draw_scope.set_colors_from_single(1.0, 1.0, 1.0, flash * 0.8);
+            self.quad_container.render_quad_container(
+                offset,
+                &QuadContainerRenderCount::Count(full),
+                0.0,
+                0.0,
+                1.0,
+                1.0,
+                draw_scope,
+                full_texture.into(),
+            );
+        }
+    }
 
-        // render shields
-        let cur_armor = info.armor.min(10) as usize;
-        let texture = &hud.vanilla.shield;
+    /// Renders the icon of a row & a bar that is filled by `fill`.
+    ///
+    /// The bar itself is untextured, so it works with any hud skin.
+    #[allow(clippy::too_many_arguments)]
+    fn render_bar(
+        &self,
+        state: &State,
+        row: StatusRow,
+        icon_offset: usize,
+        icon: &TextureContainer,
+        fill: f32,
+        color: vec4,
+        flash: f32,
+    ) {
+        let mut draw_scope = quad_scope_begin();
+        draw_scope.set_state(state);
+        draw_scope.set_colors_from_single(1.0, 1.0, 1.0, 1.0);
         self.quad_container.render_quad_container(
-            self.vanilla_offsets.shield_offset,
-            &QuadContainerRenderCount::Count(cur_armor),
+            icon_offset,
+            &QuadContainerRenderCount::Count(1),
             0.0,
             0.0,
             1.0,
             1.0,
             draw_scope,
-            texture.into(),
+            icon.into(),
         );
 
-        let texture = &hud.vanilla.shield_empty;
-        self.quad_container.render_quad_container(
-            self.vanilla_offsets.shield_offset + cur_armor,
-            &QuadContainerRenderCount::Count(10 - cur_armor),
-            0.0,
-            0.0,
-            1.0,
-            1.0,
-            draw_scope,
-            texture.into(),
+        let cell = self.status_layout.cell_size;
+        let pos = self.status_layout.row_pos(row) + vec2::new(cell * 1.2, cell * 0.25);
+        let size = vec2::new(self.status_layout.row_width() - cell * 1.2, cell * 0.5);
+        let mut quads = vec![
+            StreamedQuad::default()
+                .from_pos_and_size(pos, size)
+                .colorf(BAR_BACKGROUND_COLOR),
+            StreamedQuad::default()
+                .from_pos_and_size(pos, vec2::new(size.x * fill, size.y))
+                .colorf(color),
+        ];
+        if flash > 0.0 {
+            quads.push(
+                StreamedQuad::default()
+                    .from_pos_and_size(pos, vec2::new(size.x * fill, size.y))
+                    .colorf(vec4::new(1.0, 1.0, 1.0, flash * 0.6)),
+            );
+        }
+        self.stream_handle
+            .render_quads(&quads, *state, TextureType::None);
+    }
+
+    /// A thin bar below the ammo that fills up until
+    /// the active weapon can fire again.
+    fn render_fire_delay(&self, state: &State, progress: f32) {
+        let cell = self.status_layout.cell_size;
+        let pos = self.status_layout.row_pos(StatusRow::Ammo) + vec2::new(0.0, cell * 0.9);
+        let size = vec2::new(self.status_layout.row_width() * progress, cell / 8.0);
+        self.stream_handle.render_quads(
+            &[StreamedQuad::default()
+                .from_pos_and_size(pos, size)
+                .colorf(vec4::new(1.0, 1.0, 1.0, 0.7))],
+            *state,
+            TextureType::None,
         );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn render_vanilla(
+        &mut self,
+        state: &State,
+        info: &LocalCharacterVanilla,
+        hud: &Hud,
+        weapons: &Weapons,
+        cur_weapon: WeaponType,
+        settings: &ConfigHud,
+        cur_time: Duration,
+    ) {
+        self.health_anim
+            .update(info.health, settings.health_warning, cur_time);
+        self.armor_anim
+            .update(info.armor, settings.armor_warning, cur_time);
+        let flash = |anim: &StatusRowAnim| {
+            if settings.flash_on_change {
+                anim.flash.intensity(cur_time)
+            } else {
+                0.0
+            }
+        };
+        // pulses towards red
+        let warning_color = |anim: &StatusRowAnim, color: vec4| {
+            let pulse = anim.warning.pulse(cur_time);
+            vec4::new(
+                color.r(),
+                color.g() * (1.0 - pulse * 0.6),
+                color.b() * (1.0 - pulse * 0.6),
+                color.a(),
+            )
+        };
 
-        // render ammo
         let projectile = match cur_weapon {
             WeaponType::Hammer => None,
             WeaponType::Gun => Some(&weapons.gun.projectile),
@@ -434,24 +581,93 @@ impl RenderHud {
             WeaponType::Grenade => Some(&weapons.grenade.projectile),
             WeaponType::Laser => Some(&weapons.laser.projectile),
         };
-        if let Some(projectile) = info
-            .ammo_of_weapon
-            .is_some()
-            .then_some(projectile)
-            .flatten()
-        {
-            let cur_ammo_of_weapon = info.ammo_of_weapon.unwrap().min(10) as usize;
-            let texture = &projectile.projectile;
-            self.quad_container.render_quad_container(
-                self.vanilla_offsets.weapon_ammo_offsets[cur_weapon as usize],
-                &QuadContainerRenderCount::Count(cur_ammo_of_weapon),
-                0.0,
-                0.0,
-                1.0,
-                1.0,
-                draw_scope,
-                texture.into(),
-            );
+        let ammo = info.ammo_of_weapon.zip(projectile);
+
+        match settings.style {
+            ConfigHudStatusStyle::Pips => {
+                let mut draw_scope = quad_scope_begin();
+                draw_scope.set_state(state);
+                draw_scope.set_colors_from_single(1.0, 1.0, 1.0, 1.0);
+
+                // render heart
+                let (full, empty) = status_pips(info.health);
+                let color = warning_color(&self.health_anim, vec4::new(1.0, 1.0, 1.0, 1.0));
+                let mut health_scope = draw_scope;
+                health_scope.set_colors_from_single(color.r(), color.g(), color.b(), color.a());
+                self.render_pips(
+                    self.vanilla_offsets.heart_offset,
+                    full,
+                    empty,
+                    health_scope,
+                    &hud.vanilla.heart,
+                    Some(&hud.vanilla.heart_empty),
+                    flash(&self.health_anim),
+                );
+
+                // render shields
+                let (full, empty) = status_pips(info.armor);
+                let color = warning_color(&self.armor_anim, vec4::new(1.0, 1.0, 1.0, 1.0));
+                let mut armor_scope = draw_scope;
+                armor_scope.set_colors_from_single(color.r(), color.g(), color.b(), color.a());
+                self.render_pips(
+                    self.vanilla_offsets.shield_offset,
+                    full,
+                    empty,
+                    armor_scope,
+                    &hud.vanilla.shield,
+                    Some(&hud.vanilla.shield_empty),
+                    flash(&self.armor_anim),
+                );
+
+                // render ammo
+                if let Some((ammo, projectile)) = ammo {
+                    let (full, _) = status_pips(ammo);
+                    self.render_pips(
+                        self.vanilla_offsets.weapon_ammo_offsets[cur_weapon as usize],
+                        full,
+                        0,
+                        draw_scope,
+                        &projectile.projectile,
+                        None,
+                        0.0,
+                    );
+                }
+            }
+            ConfigHudStatusStyle::Bars => {
+                self.render_bar(
+                    state,
+                    StatusRow::Health,
+                    self.vanilla_offsets.heart_offset,
+                    &hud.vanilla.heart,
+                    status_bar_fill(info.health),
+                    warning_color(&self.health_anim, HEALTH_BAR_COLOR),
+                    flash(&self.health_anim),
+                );
+                self.render_bar(
+                    state,
+                    StatusRow::Armor,
+                    self.vanilla_offsets.shield_offset,
+                    &hud.vanilla.shield,
+                    status_bar_fill(info.armor),
+                    warning_color(&self.armor_anim, ARMOR_BAR_COLOR),
+                    flash(&self.armor_anim),
+                );
+                if let Some((ammo, projectile)) = ammo {
+                    self.render_bar(
+                        state,
+                        StatusRow::Ammo,
+                        self.vanilla_offsets.weapon_ammo_offsets[cur_weapon as usize],
+                        &projectile.projectile,
+                        status_bar_fill(ammo),
+                        AMMO_BAR_COLOR,
+                        0.0,
+                    );
+                }
+            }
+        }
+
+        if settings.fire_delay_indicator && info.fire_delay.is_some() {
+            self.render_fire_delay(state, fire_delay_progress(info.fire_delay));
         }
     }
 
@@ -806,8 +1022,17 @@ impl RenderHud {
             ctf_container: pipe.ctf_container,
             character_infos: pipe.character_infos,
             date_time: pipe.date_time,
+            race_splits: pipe.race_splits,
         });
 
+        if !matches!(
+            pipe.local_player_render_info,
+            LocalCharacterRenderInfo::Vanilla(_)
+        ) {
+            self.health_anim.reset();
+            self.armor_anim.reset();
+        }
+
         let hud = pipe.hud_container.get_or_default_opt(pipe.hud_key);
         let weapon = pipe.weapon_container.get_or_default_opt(pipe.weapon_key);
         let mut state = State::default();
@@ -826,7 +1051,15 @@ impl RenderHud {
 
         match pipe.local_player_render_info {
             LocalCharacterRenderInfo::Vanilla(info) => {
-                self.render_vanilla(&state, info, hud, weapon, pipe.cur_weapon);
+                self.render_vanilla(
+                    &state,
+                    info,
+                    hud,
+                    weapon,
+                    pipe.cur_weapon,
+                    pipe.settings,
+                    *pipe.cur_time,
+                );
             }
             LocalCharacterRenderInfo::Ddrace(info) => {
                 self.render_ddrace(&state, info, hud, weapon, pipe.cur_weapon);
@@ -0,0 +1,237 @@
+//! The status area of the vanilla HUD: health, armor & the ammo of
+//! the active weapon, either as pips or as continuous bars.
+
+use std::time::Duration;
+
+use game_interface::types::game::{GameTickType, NonZeroGameTickType};
+use math::math::{PI, vector::vec2};
+
+/// How many points the status area shows at most.
+pub const STATUS_MAX_POINTS: u32 = 10;
+
+/// How long the flash after a change lasts.
+pub const STATUS_FLASH_DURATION: Duration = Duration::from_millis(250);
+
+/// How often per second the low state warning pulses.
+const WARNING_PULSES_PER_SEC: f32 = 2.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusRow {
+    Health,
+    Armor,
+    Ammo,
+}
+
+/// Where the rows of the status area are placed.
+#[derive(Debug, Clone, Copy)]
+pub struct StatusLayout {
+    /// The top left corner of the health row.
+    pub origin: vec2,
+    /// The size of one pip, the rows are this far apart.
+    pub cell_size: f32,
+}
+
+impl StatusLayout {
+    pub fn row_pos(&self, row: StatusRow) -> vec2 {
+        let index = match row {
+            StatusRow::Health => 0.0,
+            StatusRow::Armor => 1.0,
+            StatusRow::Ammo => 2.0,
+        };
+        vec2::new(self.origin.x, self.origin.y + index * self.cell_size)
+    }
+
+    /// The width of a full row of pips, bars are as wide.
+    pub fn row_width(&self) -> f32 {
+        STATUS_MAX_POINTS as f32 * self.cell_size
+    }
+}
+
+/// How many pips are full & how many are empty.
+pub fn status_pips(value: u32) -> (usize, usize) {
+    let full = value.min(STATUS_MAX_POINTS) as usize;
+    (full, STATUS_MAX_POINTS as usize - full)
+}
+
+/// How much of a bar is filled, in `[0..1]`.
+pub fn status_bar_fill(value: u32) -> f32 {
+    value.min(STATUS_MAX_POINTS) as f32 / STATUS_MAX_POINTS as f32
+}
+
+/// How much of the fire delay of the active weapon passed, in `[0..1]`.
+///
+/// `1.0` if the weapon can fire.
+pub fn fire_delay_progress(fire_delay: Option<(GameTickType, NonZeroGameTickType)>) -> f32 {
+    fire_delay
+        .map(|(ticks_left, len)| 1.0 - (ticks_left.min(len.get()) as f32 / len.get() as f32))
+        .unwrap_or(1.0)
+}
+
+/// A warning that pulses while a value is low.
+///
+/// Starts once the value falls below the threshold & only stops once it
+/// rose above it again, so a value that is exactly at the threshold
+/// does not make the warning flicker.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LowStateWarning {
+    /// Since when the warning is active.
+    active_since: Option<Duration>,
+}
+
+impl LowStateWarning {
+    /// A threshold of `0` disables the warning.
+    pub fn update(&mut self, value: u32, threshold: u32, cur_time: Duration) {
+        if value < threshold {
+            self.active_since.get_or_insert(cur_time);
+        } else if value > threshold {
+            self.active_since = None;
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active_since.is_some()
+    }
+
+    /// The strength of the pulse in `[0..1]`, `0` if inactive.
+    ///
+    /// Every warning starts at full strength.
+    pub fn pulse(&self, cur_time: Duration) -> f32 {
+        self.active_since
+            .map(|since| {
+                let secs = cur_time.saturating_sub(since).as_secs_f32();
+                ((secs * WARNING_PULSES_PER_SEC * 2.0 * PI).cos() + 1.0) / 2.0
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// A brief flash whenever a value changes.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChangeFlash {
+    last_value: Option<u32>,
+    changed_at: Option<Duration>,
+}
+
+impl ChangeFlash {
+    pub fn update(&mut self, value: u32, cur_time: Duration) {
+        // the first value (e.g. after a spawn) is no change
+        if self
+            .last_value
+            .is_some_and(|last_value| last_value != value)
+        {
+            self.changed_at = Some(cur_time);
+        }
+        self.last_value = Some(value);
+    }
+
+    /// Forgets the value, e.g. if the character died.
+    pub fn reset(&mut self) {
+        *self = Default::default();
+    }
+
+    /// The strength of the flash in `[0..1]`, fading out linearly.
+    pub fn intensity(&self, cur_time: Duration) -> f32 {
+        self.changed_at
+            .map(|changed_at| {
+                let passed = cur_time.saturating_sub(changed_at);
+                1.0 - (passed.as_secs_f32() / STATUS_FLASH_DURATION.as_secs_f32()).min(1.0)
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// The animation state of a health or armor row.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StatusRowAnim {
+    pub warning: LowStateWarning,
+    pub flash: ChangeFlash,
+}
+
+impl StatusRowAnim {
+    pub fn update(&mut self, value: u32, warning_threshold: u32, cur_time: Duration) {
+        self.warning.update(value, warning_threshold, cur_time);
+        self.flash.update(value, cur_time);
+    }
+
+    pub fn reset(&mut self) {
+        self.warning = Default::default();
+        self.flash.reset();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{ChangeFlash, LowStateWarning, fire_delay_progress, status_bar_fill, status_pips};
+
+    #[test]
+    fn pips_and_bars() {
+        assert_eq!(status_pips(0), (0, 10));
+        assert_eq!(status_pips(3), (3, 7));
+        assert_eq!(status_pips(10), (10, 0));
+        assert_eq!(status_pips(u32::MAX), (10, 0));
+
+        assert_eq!(status_bar_fill(0), 0.0);
+        assert_eq!(status_bar_fill(5), 0.5);
+        assert_eq!(status_bar_fill(25), 1.0);
+
+        assert_eq!(fire_delay_progress(None), 1.0);
+        assert_eq!(fire_delay_progress(Some((25, 25.try_into().unwrap()))), 0.0);
+        assert_eq!(fire_delay_progress(Some((5, 20.try_into().unwrap()))), 0.75);
+        // a longer delay than the last shot (e.g. after a weapon switch)
+        assert_eq!(fire_delay_progress(Some((30, 20.try_into().unwrap()))), 0.0);
+    }
+
+    #[test]
+    fn warning_hysteresis() {
+        let secs = Duration::from_secs_f32;
+        let mut warning = LowStateWarning::default();
+
+        warning.update(5, 4, secs(0.0));
+        assert!(!warning.is_active());
+        // exactly at the threshold does not start the warning
+        warning.update(4, 4, secs(0.0));
+        assert!(!warning.is_active());
+
+        warning.update(3, 4, secs(1.0));
+        assert!(warning.is_active());
+        assert_eq!(warning.pulse(secs(1.0)), 1.0);
+        assert!(warning.pulse(secs(1.25)) < 0.01);
+        // & does not stop it either
+        warning.update(4, 4, secs(2.0));
+        assert!(warning.is_active());
+        // the pulse keeps its phase
+        assert!(warning.pulse(secs(2.0)) > 0.99);
+        warning.update(3, 4, secs(3.0));
+        assert!(warning.is_active());
+
+        warning.update(5, 4, secs(4.0));
+        assert!(!warning.is_active());
+        assert_eq!(warning.pulse(secs(4.0)), 0.0);
+
+        // a threshold of 0 never warns
+        warning.update(0, 0, secs(5.0));
+        assert!(!warning.is_active());
+    }
+
+    #[test]
+    fn change_flash() {
+        let secs = Duration::from_secs_f32;
+        let mut flash = ChangeFlash::default();
+
+        flash.update(10, secs(0.0));
+        assert_eq!(flash.intensity(secs(0.0)), 0.0);
+        flash.update(10, secs(1.0));
+        assert_eq!(flash.intensity(secs(1.0)), 0.0);
+
+        flash.update(7, secs(2.0));
+        assert_eq!(flash.intensity(secs(2.0)), 1.0);
+        assert!((flash.intensity(secs(2.125)) - 0.5).abs() < 0.01);
+        assert_eq!(flash.intensity(secs(3.0)), 0.0);
+
+        flash.reset();
+        flash.update(3, secs(4.0));
+        assert_eq!(flash.intensity(secs(4.0)), 0.0);
+    }
+}
@@ -1,4 +1,6 @@
 pub mod cursor;
+pub mod flag_stand_view;
 pub mod game_objects;
 pub mod hud;
+pub mod hud_status;
 pub mod players;
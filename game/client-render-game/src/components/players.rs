@@ -30,7 +30,7 @@ use client_render_base::{
 };
 use graphics::graphics::graphics::Graphics;
 
-use graphics_types::rendering::State;
+use graphics_types::rendering::{ColorRgba, State};
 use pool::datatypes::PoolFxLinkedHashMap;
 
 use vanilla::collision::collision::Collision;
@@ -74,10 +74,34 @@ pub struct PlayerRenderPipe<'a> {
     pub phased: bool,
 }
 
+/// How long a character fades in, when it appears
+/// (e.g. because the server started to send it).
+const APPEAR_FADE_DURATION: Duration = Duration::from_millis(200);
+/// Characters that were not rendered for this long
+/// fade in again when they appear.
+const APPEAR_FORGET_DURATION: Duration = Duration::from_secs(1);
+/// How long a character is tinted, after a damage tile hurt it.
+const TILE_DAMAGE_TINT_DURATION: Duration = Duration::from_millis(300);
+const TILE_DAMAGE_TINT_COLOR: ColorRgba = ColorRgba {
+    r: 1.0,
+    g: 0.35,
+    b: 0.1,
+    a: 1.0,
+};
+
+/// The alpha of spawn protected characters, which shimmers over time.
+fn spawn_protection_alpha(cur_time: &Duration) -> f32 {
+    0.7 + 0.3 * (cur_time.as_secs_f32() * 8.0).sin()
+}
+
 /// The player component renders all hooks
 /// all weapons, and all players
 pub struct Players {
     canvas_mapping: CanvasMappingIngame,
+    /// When a character appeared & when it was rendered the last time.
+    appeared: FxLinkedHashMap<CharacterId, (Duration, Duration)>,
+    /// When a damage tile hurt a character the last time.
+    tile_damaged: FxLinkedHashMap<CharacterId, Duration>,
 
     pub tee_renderer: RenderTee,
     pub(crate) nameplate_renderer: NameplateRender,
@@ -94,6 +118,8 @@ impl Players {
 
         Self {
             canvas_mapping: CanvasMappingIngame::new(graphics),
+            appeared: Default::default(),
+            tile_damaged: Default::default(),
 
             tee_renderer,
             nameplate_renderer,
@@ -109,6 +135,47 @@ impl Players {
         base_state
     }
 
+    fn update_appeared(
+        &mut self,
+        render_infos: &FxLinkedHashMap<CharacterId, CharacterRenderInfo>,
+        cur_time: &Duration,
+    ) {
+        self.appeared.retain(|_, (_, last_rendered)| {
+            cur_time.saturating_sub(*last_rendered) < APPEAR_FORGET_DURATION
+        });
+        for id in render_infos.keys() {
+            self.appeared.entry(*id).or_insert((*cur_time, *cur_time)).1 = *cur_time;
+        }
+    }
+
+    fn appear_alpha(&self, id: &CharacterId, cur_time: &Duration) -> f32 {
+        self.appeared
+            .get(id)
+            .map(|(appeared, _)| {
+                (cur_time.saturating_sub(*appeared).as_secs_f32()
+                    / APPEAR_FADE_DURATION.as_secs_f32())
+                .min(1.0)
+            })
+            .unwrap_or(1.0)
+    }
+
+    /// Tints the character for a short time.
+    pub fn on_tile_damage(&mut self, id: CharacterId, cur_time: &Duration) {
+        self.tile_damaged.insert(id, *cur_time);
+    }
+
+    /// How strong the character is tinted, in `[0..1]`.
+    fn tile_damage_tint(&self, id: &CharacterId, cur_time: &Duration) -> f32 {
+        self.tile_damaged
+            .get(id)
+            .map(|damaged| {
+                1.0 - (cur_time.saturating_sub(*damaged).as_secs_f32()
+                    / TILE_DAMAGE_TINT_DURATION.as_secs_f32())
+                .min(1.0)
+            })
+            .unwrap_or_default()
+    }
+
     fn render_info_iter<'a>(
         render_infos: &'a FxLinkedHashMap<CharacterId, CharacterRenderInfo>,
         own_character: &'a Option<&'a CharacterId>,
@@ -154,6 +221,10 @@ impl Players {
         let phased_alpha = *phased_alpha;
         let phased = *phased;
 
+        self.update_appeared(render_infos, cur_time);
+        self.tile_damaged
+            .retain(|_, damaged| cur_time.saturating_sub(*damaged) < TILE_DAMAGE_TINT_DURATION);
+
         let state = self.base_state(*camera);
 
         const RENDER_TEE_SIZE: f32 = 2.0;
@@ -172,6 +243,21 @@ impl Players {
             }
         }
 
+        fn tinted(color: TeeRenderSkinColor, tint: f32) -> TeeRenderSkinColor {
+            if tint <= 0.0 {
+                return color;
+            }
+            let color = color.unwrap(1.0);
+            let mix = |from: f32, to: f32| from + (to - from) * tint;
+            ColorRgba::new(
+                mix(color.r, TILE_DAMAGE_TINT_COLOR.r),
+                mix(color.g, TILE_DAMAGE_TINT_COLOR.g),
+                mix(color.b, TILE_DAMAGE_TINT_COLOR.b),
+                color.a,
+            )
+            .into()
+        }
+
         fn skin<'a>(
             character_info: Option<&'a CharacterInfo>,
             ninja_skin: Option<Option<&NetworkResourceKey<MAX_ASSET_NAME_LEN>>>,
@@ -199,6 +285,10 @@ impl Players {
                 phased_alpha
             } else {
                 1.0
+            } * if Some(character_id) != *own_character {
+                self.appear_alpha(character_id, cur_time)
+            } else {
+                1.0
             };
 
             let pos = character_render_info.lerped_pos;
@@ -265,6 +355,17 @@ impl Players {
                 phased_alpha
             } else {
                 1.0
+            } * if Some(character_id) != *own_character {
+                self.appear_alpha(character_id, cur_time)
+            } else {
+                1.0
+            } * if character_render_info
+                .buffs
+                .contains_key(&CharacterBuff::SpawnProtection)
+            {
+                spawn_protection_alpha(cur_time)
+            } else {
+                1.0
             };
 
             // dir to hook
@@ -385,6 +486,8 @@ impl Players {
             }
 
             let (color_body, color_feet) = skin_colors(character_info);
+            let tint = self.tile_damage_tint(character_id, cur_time);
+            let (color_body, color_feet) = (tinted(color_body, tint), tinted(color_feet, tint));
 
             let tee_render_info = TeeRenderInfo {
                 color_body,
@@ -502,6 +605,7 @@ impl Players {
                             } else {
                                 1.0
                             },
+                            practice: player_render_info.practice,
                         })
                 },
             ),
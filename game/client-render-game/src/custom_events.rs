@@ -0,0 +1,101 @@
+use std::{collections::HashMap, fmt::Debug, time::Duration};
+
+use game_interface::{events::GameWorldCustomEvent, types::id_types::StageId};
+
+/// Interprets the custom events of a game mod, e.g. to show
+/// a message when a boss spawned.
+pub trait CustomGameEventHandler: Debug {
+    fn handle(&mut self, cur_time: &Duration, stage_id: &StageId, payload: &[u8]);
+}
+
+/// The handlers for the ids of [`GameWorldCustomEvent`]s.
+#[derive(Debug, Default)]
+pub struct CustomGameEvents {
+    handlers: HashMap<u32, Box<dyn CustomGameEventHandler>>,
+}
+
+impl CustomGameEvents {
+    /// Registers the handler for the id.
+    ///
+    /// Returns the handler that was previously registered for the id.
+    pub fn register(
+        &mut self,
+        id: u32,
+        handler: Box<dyn CustomGameEventHandler>,
+    ) -> Option<Box<dyn CustomGameEventHandler>> {
+        self.handlers.insert(id, handler)
+    }
+
+    pub fn unregister(&mut self, id: u32) -> Option<Box<dyn CustomGameEventHandler>> {
+        self.handlers.remove(&id)
+    }
+
+    /// Passes the event to the handler registered for its id.
+    ///
+    /// Returns `false` if no handler is registered for its id.
+    pub fn handle(
+        &mut self,
+        cur_time: &Duration,
+        stage_id: &StageId,
+        ev: &GameWorldCustomEvent,
+    ) -> bool {
+        let Some(handler) = self.handlers.get_mut(&ev.id) else {
+            return false;
+        };
+        handler.handle(cur_time, stage_id, &ev.payload);
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc, time::Duration};
+
+    use game_interface::{
+        events::{GameCustomEventPayload, GameWorldCustomEvent},
+        types::{id_gen::IdGenerator, id_types::StageId},
+    };
+
+    use super::{CustomGameEventHandler, CustomGameEvents};
+
+    /// Remembers the payloads it handled.
+    #[derive(Debug, Default)]
+    struct Recorder(Rc<RefCell<Vec<Vec<u8>>>>);
+
+    impl CustomGameEventHandler for Recorder {
+        fn handle(&mut self, _cur_time: &Duration, _stage_id: &StageId, payload: &[u8]) {
+            self.0.borrow_mut().push(payload.to_vec());
+        }
+    }
+
+    fn event(id: u32, payload: &[u8]) -> GameWorldCustomEvent {
+        GameWorldCustomEvent {
+            id,
+            payload: GameCustomEventPayload::new(payload).unwrap(),
+        }
+    }
+
+    #[test]
+    fn register_and_dispatch() {
+        let mut events = CustomGameEvents::default();
+        let stage_id: StageId = IdGenerator::new().next_id();
+        let cur_time = Duration::ZERO;
+
+        // unknown ids are ignored
+        assert!(!events.handle(&cur_time, &stage_id, &event(1, b"boss")));
+
+        let handled: Rc<RefCell<Vec<Vec<u8>>>> = Default::default();
+        assert!(
+            events
+                .register(1, Box::new(Recorder(handled.clone())))
+                .is_none()
+        );
+        assert!(events.handle(&cur_time, &stage_id, &event(1, b"boss")));
+        assert!(!events.handle(&cur_time, &stage_id, &event(2, b"other")));
+        assert_eq!(*handled.borrow(), vec![b"boss".to_vec()]);
+
+        assert!(events.unregister(1).is_some());
+        assert!(!events.handle(&cur_time, &stage_id, &event(1, b"boss")));
+        assert_eq!(handled.borrow().len(), 1);
+    }
+}
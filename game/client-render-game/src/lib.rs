@@ -1,4 +1,6 @@
 #![allow(clippy::too_many_arguments)]
 
 pub mod components;
+pub mod custom_events;
 pub mod render_game;
+pub mod skin_overrides;
@@ -6,11 +6,16 @@ use std::{
     time::Duration,
 };
 
-use crate::components::{
-    cursor::{RenderCursor, RenderCursorPipe},
-    game_objects::{GameObjectsRender, GameObjectsRenderPipe},
-    hud::{RenderHud, RenderHudPipe},
-    players::{PlayerRenderPipe, Players},
+use crate::{
+    components::{
+        cursor::{RenderCursor, RenderCursorPipe},
+        flag_stand_view::{FlagStandView, flag_stand_view_target},
+        game_objects::{GameObjectsRender, GameObjectsRenderPipe},
+        hud::{RenderHud, RenderHudPipe},
+        players::{PlayerRenderPipe, Players},
+    },
+    custom_events::CustomGameEvents,
+    skin_overrides::SkinOverrides,
 };
 use base::{
     hash::Hash, linked_hash_map_view::FxLinkedHashMap, network_string::NetworkReducedAsciiString,
@@ -18,10 +23,14 @@ use base::{
 };
 use base_io::io::Io;
 use camera::Camera;
-use client_containers::utils::{RenderGameContainers, load_containers};
+use client_containers::{
+    container::ContainerKey,
+    utils::{RenderGameContainers, load_containers},
+};
 pub use client_render::emote_wheel::render::EmoteWheelInput;
 use client_render::{
     actionfeed::render::{ActionfeedRender, ActionfeedRenderPipe},
+    broadcast::page::{BroadcastRender, BroadcastRenderPipe},
     chat::render::{ChatRender, ChatRenderOptions, ChatRenderPipe},
     emote_wheel::render::{EmoteWheelRender, EmoteWheelRenderPipe},
     motd::page::{MotdRender, MotdRenderPipe},
@@ -31,23 +40,29 @@ use client_render::{
 };
 use client_render_base::{
     map::{
+        hq_assets::MapHqAssets,
         map::RenderMap,
+        music_intensity::{MusicIntensity, MusicIntensityEvent, MusicIntensityTable},
         render_map_base::{ClientMapRender, RenderMapLoading},
         render_pipe::{GameTimeInfo, RenderPipeline, RenderPipelinePhysics},
     },
     render::{
+        custom_particle_effects::CustomParticleEffects,
         effects::Effects,
         particle_manager::{ParticleGroup, ParticleManager},
     },
 };
 use client_types::{
     actionfeed::{Action, ActionInFeed, ActionKill, ActionPlayer},
-    chat::{ChatMsg, MsgSystem, ServerMsg, SystemMsgPlayerSkin},
+    chat::{
+        ChatMsg, ChatNotifyKeyword, MsgSystem, ServerMsg, SystemMsgPlayerSkin, msg_contains_keyword,
+    },
 };
 use client_ui::{
     chat::user_data::{ChatEvent, ChatMode, MsgInChat},
     emote_wheel::user_data::EmoteWheelEvent,
-    hud::user_data::RenderDateTime,
+    hud::user_data::{RenderDateTime, RenderRaceSplits},
+    scoreboard::user_data::{ScoreboardEvent, ScoreboardPlayerContext},
     spectator_selection::user_data::SpectatorSelectionEvent,
     thumbnail_container::{
         DEFAULT_THUMBNAIL_CONTAINER_PATH, ThumbnailContainer, load_thumbnail_container,
@@ -57,12 +72,17 @@ use client_ui::{
 };
 use config::config::ConfigDebug;
 use egui::{FontDefinitions, Rect};
-use game_base::network::{
-    messages::{RenderModification, RequiredResources},
-    types::chat::NetChatMsg,
+use game_base::{
+    connecting_log::ConnectingLog,
+    local_stats::LocalStatsEvent,
+    network::{
+        messages::{RenderModification, RequiredResources},
+        types::chat::NetChatMsg,
+    },
 };
 use game_config::config::{
-    ConfigDummyScreenAnchor, ConfigGame, ConfigMap, ConfigRender, ConfigSoundRender,
+    ConfigCrosshair, ConfigDummyScreenAnchor, ConfigGame, ConfigHud, ConfigMap, ConfigRender,
+    ConfigSoundRender,
 };
 use game_interface::{
     chat_commands::ChatCommands,
@@ -70,10 +90,11 @@ use game_interface::{
         GameBuffNinjaEventSound, GameBuffSoundEvent, GameCharacterEffectEvent,
         GameCharacterEventEffect, GameCharacterEventSound, GameCharacterSoundEvent,
         GameDebuffFrozenEventSound, GameDebuffSoundEvent, GameEvents, GameFlagEventSound,
-        GameGrenadeEventEffect, GameGrenadeEventSound, GameLaserEventSound,
+        GameGrenadeEventEffect, GameGrenadeEventSound, GameLaserEventEffect, GameLaserEventSound,
         GamePickupArmorEventSound, GamePickupHeartEventSound, GamePickupSoundEvent,
-        GameShotgunEventSound, GameWorldAction, GameWorldEffectEvent, GameWorldEntityEffectEvent,
-        GameWorldEntitySoundEvent, GameWorldEvent, GameWorldNotificationEvent, GameWorldSoundEvent,
+        GameShotgunEventSound, GameWorldAction, GameWorldActionKillWeapon, GameWorldEffectEvent,
+        GameWorldEntityEffectEvent, GameWorldEntitySoundEvent, GameWorldEvent,
+        GameWorldNotificationEvent, GameWorldPlayerStats, GameWorldSoundEvent,
         GameWorldSystemMessage,
     },
     interface::MAX_PHYSICS_GROUP_NAME_LEN,
@@ -85,18 +106,22 @@ use game_interface::{
         player_info::{PlayerBanReason, PlayerDropReason, PlayerKickReason},
         render::{
             character::{CharacterBuff, CharacterInfo, LocalCharacterRenderInfo},
-            game::game_match::MatchSide,
+            game::{GameRenderInfo, MatchRoundTimeType, game_match::MatchSide},
             scoreboard::Scoreboard,
             stage::StageRenderInfo,
         },
+        weapons::WeaponType,
     },
     votes::{VoteState, VoteType, Voted},
 };
 use graphics::{
     graphics::graphics::Graphics,
-    handles::{backend::backend::GraphicsBackendHandle, canvas::canvas::GraphicsCanvasHandle},
+    handles::{
+        backend::backend::GraphicsBackendHandle,
+        canvas::canvas::{GraphicsCanvasHandle, GraphicsCanvasMode},
+    },
 };
-use graphics_types::rendering::ColorRgba;
+use graphics_types::{gpu::GpuTimerPass, rendering::ColorRgba};
 use math::math::{Rng, RngSlice, vector::vec2};
 use pool::{
     datatypes::{
@@ -120,6 +145,9 @@ pub enum PlayerFeedbackEvent {
     Chat(ChatEvent),
     EmoteWheel(EmoteWheelEvent),
     SpectatorSelection(SpectatorSelectionEvent),
+    Scoreboard(ScoreboardEvent),
+    /// Counts to the local stats of the player.
+    Stats(LocalStatsEvent),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -203,6 +231,9 @@ pub struct RenderGameCreateOptions {
     /// The implementation can use this information to speed up
     /// loading of the resources _likely_ to be used.
     pub client_local_infos: ClientLocalInfos,
+    /// Whether the high quality variants of the map images
+    /// are loaded and how much texture memory they may use.
+    pub hq_assets: MapHqAssets,
 }
 
 #[derive(Default, Serialize, Deserialize)]
@@ -236,11 +267,19 @@ pub struct SpectatorSelectionInput {
     pub into_phased: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScoreboardInput {
+    pub inp: Option<egui::RawInput>,
+    pub player_context: ScoreboardPlayerContext,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RenderForPlayer {
     pub chat_info: Option<(ChatMode, String, Option<egui::RawInput>)>,
     pub emote_wheel_input: Option<EmoteWheelInput>,
     pub spectator_selection_input: Option<SpectatorSelectionInput>,
+    /// If `Some`, the scoreboard allows actions on the players.
+    pub scoreboard_input: Option<ScoreboardInput>,
     pub local_player_info: LocalCharacterRenderInfo,
     pub chat_show_all: bool,
     pub scoreboard_active: bool,
@@ -317,7 +356,7 @@ pub enum AllowPredictionEventType {
     All,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RenderGameSettings {
     pub spatial_sound: bool,
     pub sound_playback_speed: f64,
@@ -325,6 +364,8 @@ pub struct RenderGameSettings {
     pub map_sound_volume: f64,
     /// For all the various sounds ingame
     pub ingame_sound_volume: f64,
+    /// For notifications, e.g. chat highlights
+    pub notification_sound_volume: f64,
 
     pub nameplates: bool,
     pub nameplate_own: bool,
@@ -348,6 +389,18 @@ pub struct RenderGameSettings {
     /// Whether to enable dynamic camera while spectating another
     /// character.
     pub spec_dyncam: bool,
+    pub crosshair: ConfigCrosshair,
+    /// The health, armor & ammo of the local character.
+    pub hud: ConfigHud,
+    /// The fraction of particles that are rendered, in `[0..1]`.
+    pub particle_amount: f32,
+    /// The height of the view of the own flag stand while carrying the
+    /// enemy flag, relative to the screen height. `None` if disabled.
+    pub flag_stand_view: Option<f32>,
+    pub skin_overrides: SkinOverrides,
+    /// How game events drive the music stems of the map.
+    /// `None` if the stems play like any other map sound.
+    pub music_intensity: Option<MusicIntensityTable>,
 }
 
 impl RenderGameSettings {
@@ -366,6 +419,7 @@ impl RenderGameSettings {
             nameplate_own: render.own_nameplate,
             ingame_sound_volume: snd.ingame_sound_volume * global_volume,
             map_sound_volume: snd.map_sound_volume * global_volume,
+            notification_sound_volume: snd.notification_sound_volume * global_volume,
             pixels_per_point: window_pixels_per_point
                 .max(render.ingame_ui_min_pixels_per_point as f32)
                 * render.ingame_ui_scale as f32,
@@ -380,6 +434,21 @@ impl RenderGameSettings {
                 .use_ingame_aspect_ratio
                 .then_some(render.ingame_aspect_ratio as f32),
             spec_dyncam: render.spec_dyncam,
+            crosshair: render.crosshair,
+            hud: render.hud,
+            particle_amount: render.particles as f32 / 100.0,
+            flag_stand_view: render
+                .flag_stand_view
+                .then_some(render.flag_stand_view_size as f32 / 100.0),
+            skin_overrides: SkinOverrides::new(&render.skin_overrides),
+            music_intensity: snd.music_intensity.then(|| MusicIntensityTable {
+                flag_taken: snd.music_intensity_flag_taken,
+                low_time_remaining: snd.music_intensity_low_time,
+                sudden_death: snd.music_intensity_sudden_death,
+                low_time: Duration::from_secs(snd.music_intensity_low_time_secs as u64),
+                crossfade: Duration::from_millis(snd.music_intensity_crossfade_ms as u64),
+                ..Default::default()
+            }),
         }
     }
 }
@@ -396,6 +465,12 @@ pub struct RenderGameInput {
     /// from the server.
     pub events: PoolBTreeMap<(GameTickType, bool), GameEvents>,
     pub chat_msgs: PoolVecDeque<NetChatMsg>,
+    /// The notification rules that are currently active for
+    /// the chat messages.
+    pub chat_notify: Vec<ChatNotifyKeyword>,
+    /// Whether a received message of the day is shown automatically,
+    /// else it's only stored.
+    pub auto_show_motd: bool,
     /// Vote state
     pub vote: Option<(PoolRc<VoteState>, Option<Voted>, Duration)>,
 
@@ -404,6 +479,8 @@ pub struct RenderGameInput {
     pub scoreboard_info: Option<Scoreboard>,
 
     pub date_time: Option<RenderDateTime>,
+    /// Checkpoint times of the local player's race run.
+    pub race_splits: Option<RenderRaceSplits>,
 
     pub game_time_info: GameTimeInfo,
 
@@ -432,12 +509,22 @@ pub struct RenderGame {
     emote_wheel: EmoteWheelRender,
     vote: VoteRender,
     motd: MotdRender,
+    broadcast: BroadcastRender,
     spectator_selection: SpectatorSelectionRender,
+    flag_stand_view: FlagStandView,
+    music_intensity: MusicIntensity,
+    custom_events: CustomGameEvents,
 
     // chat commands
     chat_commands: ChatCommands,
 
     last_event_monotonic_tick: Option<GameTickType>,
+    /// The statistics of the last round,
+    /// only shown while the round is over.
+    round_stats: Option<Vec<GameWorldPlayerStats>>,
+    /// Events for the local stats of the local players,
+    /// reported with the other feedback events of the player.
+    local_stats_events: FxLinkedHashMap<PlayerId, Vec<LocalStatsEvent>>,
 
     // map
     map: ClientMapRender,
@@ -451,6 +538,7 @@ pub struct RenderGame {
 
     // helpers
     helper: Pool<Vec<RenderPlayerHelper>>,
+    character_infos_pool: Pool<FxLinkedHashMap<CharacterId, CharacterInfo>>,
 
     world_sound_scene: SceneObject,
     world_sound_listeners: HashMap<PlayerId, SoundListener>,
@@ -468,6 +556,7 @@ impl RenderGame {
         map_file: Vec<u8>,
         config: &ConfigDebug,
         props: RenderGameCreateOptions,
+        log: Option<ConnectingLog>,
     ) -> Result<Self, String> {
         let scene = sound.scene_handle.create(props.sound_props.clone());
 
@@ -482,6 +571,8 @@ impl RenderGame {
             graphics,
             config,
             Some("downloaded".as_ref()),
+            props.hq_assets,
+            log,
         ));
 
         let mut containers = load_containers(
@@ -514,7 +605,9 @@ impl RenderGame {
         let emote_wheel = EmoteWheelRender::new(graphics, &creator);
         let vote = VoteRender::new(graphics, &creator);
         let motd = MotdRender::new(graphics, &creator);
+        let broadcast = BroadcastRender::new(graphics, &creator);
         let spectator_selection = SpectatorSelectionRender::new(graphics, &creator);
+        let flag_stand_view = FlagStandView::new(graphics);
 
         let mut map_vote_thumbnails_container = load_thumbnail_container(
             io.clone(),
@@ -551,12 +644,18 @@ impl RenderGame {
             emote_wheel,
             vote,
             motd,
+            broadcast,
             spectator_selection,
+            flag_stand_view,
+            music_intensity: Default::default(),
+            custom_events: Default::default(),
 
             // chat commands
             chat_commands: Default::default(),
 
             last_event_monotonic_tick: None,
+            round_stats: None,
+            local_stats_events: Default::default(),
 
             map,
             physics_group_name,
@@ -567,6 +666,7 @@ impl RenderGame {
             client_local_infos: props.client_local_infos,
 
             helper: Pool::with_capacity(1),
+            character_infos_pool: Pool::with_capacity(1),
 
             world_sound_scene: scene,
             world_sound_listeners: Default::default(),
@@ -575,6 +675,67 @@ impl RenderGame {
         })
     }
 
+    /// The particle effects that are spawned for custom effect events,
+    /// render mods register their own effects here.
+    pub fn custom_particle_effects(&mut self) -> &mut CustomParticleEffects {
+        &mut self.particles.custom_effects
+    }
+
+    /// The handlers of the custom events of the game mod,
+    /// render mods register their own handlers here.
+    pub fn custom_events(&mut self) -> &mut CustomGameEvents {
+        &mut self.custom_events
+    }
+
+    /// The intensity of the map music, render mods can
+    /// [trigger](MusicIntensity::trigger) their own events here.
+    pub fn music_intensity(&mut self) -> &mut MusicIntensity {
+        &mut self.music_intensity
+    }
+
+    /// Triggers the intensity events of the stage the camera is in.
+    fn update_music_intensity(
+        music_intensity: &mut MusicIntensity,
+        cur_time: &Duration,
+        render_info: &RenderGameInput,
+        stage: Option<&StageRenderInfo>,
+    ) {
+        let Some(table) = &render_info.settings.music_intensity else {
+            return;
+        };
+        if let Some(stage) = stage {
+            let mut trigger = |ev| music_intensity.trigger(ev, table, *cur_time);
+            if stage
+                .world
+                .ctf_flags
+                .values()
+                .any(|flag| flag.owner_id.is_some())
+            {
+                trigger(MusicIntensityEvent::FlagTaken);
+            }
+            if let GameRenderInfo::Match {
+                round_time_type, ..
+            } = &stage.game
+            {
+                let low_time_ticks =
+                    table.low_time.as_secs() * render_info.game_time_info.ticks_per_second.get();
+                match round_time_type {
+                    MatchRoundTimeType::TimeLimit { ticks_left }
+                    | MatchRoundTimeType::Overtime { ticks_left, .. }
+                        if *ticks_left <= low_time_ticks =>
+                    {
+                        trigger(MusicIntensityEvent::LowTimeRemaining);
+                    }
+                    MatchRoundTimeType::SuddenDeath => {
+                        trigger(MusicIntensityEvent::SuddenDeath);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        music_intensity.update(table, *cur_time);
+    }
+
     fn render_ingame(
         &mut self,
 
@@ -582,9 +743,13 @@ impl RenderGame {
         cur_time: &Duration,
 
         render_info: &RenderGameInput,
+        ingame_character_infos: Option<&PoolFxLinkedHashMap<CharacterId, CharacterInfo>>,
         player_info: Option<(&PlayerId, &RenderForPlayer)>,
     ) {
         let map = self.map.try_get().unwrap();
+        self.particles
+            .set_amount(render_info.settings.particle_amount);
+        let character_infos = ingame_character_infos.unwrap_or(&render_info.character_infos);
 
         let mut cam = Camera::new(
             Default::default(),
@@ -613,7 +778,7 @@ impl RenderGame {
             .unwrap_or_default();
 
         let camera_character_info =
-            camera_player.and_then(|(player_id, _)| render_info.character_infos.get(player_id));
+            camera_player.and_then(|(player_id, _)| character_infos.get(player_id));
 
         let camera_character_render_info = camera_character_info
             .zip(camera_player)
@@ -682,6 +847,20 @@ impl RenderGame {
             };
         }
 
+        Self::update_music_intensity(
+            &mut self.music_intensity,
+            cur_time,
+            render_info,
+            camera_character_info
+                .and_then(|c| c.stage_id)
+                .and_then(|stage_id| render_info.stages.get(&stage_id)),
+        );
+        let music_intensity = render_info
+            .settings
+            .music_intensity
+            .is_some()
+            .then_some(&self.music_intensity);
+
         let render_map = map;
 
         // map + ingame objects
@@ -694,14 +873,20 @@ impl RenderGame {
             false,
             &cam,
             render_info.settings.map_sound_volume,
+            music_intensity,
         );
+        self.backend_handle
+            .gpu_timer_marker(GpuTimerPass::MapBackground);
         render_map.render.render_background(&render_pipe);
+        self.backend_handle
+            .gpu_timer_marker(GpuTimerPass::Particles);
         self.particles.render_group(
             ParticleGroup::ProjectileTrail,
             &mut self.containers.particles_container,
-            &render_info.character_infos,
+            character_infos,
             &cam,
         );
+        self.backend_handle.gpu_timer_marker(GpuTimerPass::Entities);
         for ((_, stage), local_characters_stage) in render_info
             .stages
             .iter()
@@ -726,7 +911,7 @@ impl RenderGame {
                 flags: &stage.world.ctf_flags,
                 pickups: &stage.world.pickups,
                 lasers: &stage.world.lasers,
-                character_infos: &render_info.character_infos,
+                character_infos,
 
                 ctf_container: &mut self.containers.ctf_container,
                 game_container: &mut self.containers.game_container,
@@ -744,7 +929,7 @@ impl RenderGame {
                 cur_time,
                 game_time_info: &render_info.game_time_info,
                 render_infos: &stage.world.characters,
-                character_infos: &render_info.character_infos,
+                character_infos,
 
                 particle_manager: &mut self.particles,
 
@@ -777,6 +962,7 @@ impl RenderGame {
             false,
             &cam,
             render_info.settings.map_sound_volume,
+            music_intensity,
         );
         render_map.render.render_physics_layers(
             &mut RenderPipelinePhysics::new(
@@ -797,7 +983,7 @@ impl RenderGame {
                 cur_time,
                 &cam,
                 &stage.world.characters,
-                &render_info.character_infos,
+                character_infos,
                 render_info.settings.nameplates,
                 render_info.settings.nameplate_own,
                 player_info.map(|(player_id, _)| player_id),
@@ -806,12 +992,15 @@ impl RenderGame {
             );
         }
 
+        self.backend_handle
+            .gpu_timer_marker(GpuTimerPass::Particles);
         self.particles.render_groups(
             ParticleGroup::Explosions,
             &mut self.containers.particles_container,
-            &render_info.character_infos,
+            character_infos,
             &cam,
         );
+        self.backend_handle.gpu_timer_marker(GpuTimerPass::Ui);
         // cursor
         if let Some((player, (_, true))) = camera_character_render_info.zip(camera_player) {
             self.cursor_render.render(&mut RenderCursorPipe {
@@ -823,6 +1012,9 @@ impl RenderGame {
                 ninja_container: &mut self.containers.ninja_container,
                 ninja_key: camera_character_info.map(|c| c.info.ninja.borrow()),
                 camera: &cam,
+                cur_time,
+                crosshair: &render_info.settings.crosshair,
+                pixels_per_point: render_info.settings.pixels_per_point,
             });
         }
     }
@@ -965,9 +1157,9 @@ impl RenderGame {
 
         // hud + scoreboard
         if let Some((player_id, render_for_game)) = player_info {
-            let local_render_info = &render_for_game.render_for_player;
+            let local_render_info = &mut render_for_game.render_for_player;
 
-            let cam_player_id = match &render_for_game.render_for_player.cam_mode {
+            let cam_player_id = match &local_render_info.cam_mode {
                 RenderPlayerCameraMode::Default | RenderPlayerCameraMode::AtPos { .. } => player_id,
                 RenderPlayerCameraMode::OnCharacters { character_ids, .. } => {
                     if !character_ids.is_empty() {
@@ -1012,6 +1204,17 @@ impl RenderGame {
                 ctf_container: &mut self.containers.ctf_container,
                 character_infos: &render_info.character_infos,
                 date_time: &render_info.date_time,
+                race_splits: &render_info.race_splits,
+                settings: &render_info.settings.hud,
+            });
+            let is_game_over = stage.is_some_and(|s| {
+                matches!(
+                    s.game,
+                    GameRenderInfo::Match {
+                        round_time_type: MatchRoundTimeType::GameOver { .. },
+                        ..
+                    }
+                )
             });
             if let Some(scoreboard_info) = local_render_info
                 .scoreboard_active
@@ -1019,7 +1222,7 @@ impl RenderGame {
                 .and(render_info.scoreboard_info.as_ref())
             {
                 // scoreboard after hud
-                self.scoreboard.render(&mut ScoreboardRenderPipe {
+                let evs = self.scoreboard.render(&mut ScoreboardRenderPipe {
                     cur_time,
                     scoreboard: scoreboard_info,
                     character_infos: &render_info.character_infos,
@@ -1029,10 +1232,19 @@ impl RenderGame {
 
                     // for scoreboard this should remain the "real" player's id
                     own_character_id: player_id,
+                    round_stats: self.round_stats.as_deref().filter(|_| is_game_over),
+                    interactive: local_render_info
+                        .scoreboard_input
+                        .as_mut()
+                        .map(|input| (&mut input.inp, &input.player_context)),
                 });
+                res.extend(evs.into_iter().map(PlayerFeedbackEvent::Scoreboard));
             }
         }
 
+        // server broadcast
+        self.broadcast.render(&mut BroadcastRenderPipe { cur_time });
+
         // message of the day
         self.motd.render(&mut MotdRenderPipe { cur_time });
 
@@ -1281,6 +1493,9 @@ impl RenderGame {
                     add_time: *cur_time,
                 });
             }
+            GameWorldAction::RaceCheckpoint { .. } => {
+                // split times are shown in the hud instead
+            }
             GameWorldAction::Custom(_) => todo!(),
         }
     }
@@ -1688,6 +1903,11 @@ impl RenderGame {
                 GameCharacterEventEffect::HammerHit => {
                     Effects::new(&mut self.particles, *cur_time).hammer_hit(&pos, id);
                 }
+                GameCharacterEventEffect::TileDamage => {
+                    if let Some(id) = id {
+                        self.players.on_tile_damage(id, cur_time);
+                    }
+                }
             },
         }
     }
@@ -1761,6 +1981,23 @@ impl RenderGame {
             GameGrenadeEventEffect::Explosion => {
                 Effects::new(&mut self.particles, *cur_time).explosion(&pos, id);
             }
+            GameGrenadeEventEffect::Bounce { .. } => {
+                Effects::new(&mut self.particles, *cur_time).wall_impact(&pos, id);
+            }
+        }
+    }
+
+    fn handle_laser_effect_event(
+        &mut self,
+        cur_time: &Duration,
+        pos: vec2,
+        ev: GameLaserEventEffect,
+        id: Option<CharacterId>,
+    ) {
+        match ev {
+            GameLaserEventEffect::Bounce { .. } => {
+                Effects::new(&mut self.particles, *cur_time).wall_impact(&pos, id);
+            }
         }
     }
 
@@ -2074,8 +2311,11 @@ impl RenderGame {
         local_players: &PoolFxLinkedHashMap<PlayerId, RenderGameForPlayer>,
         local_dummies: &PoolFxLinkedHashSet<PlayerId>,
         settings: &RenderGameSettings,
-        GameWorldSoundEvent { owner_id, ev, pos }: GameWorldSoundEvent,
+        sound_ev: GameWorldSoundEvent,
     ) {
+        let GameWorldSoundEvent {
+            owner_id, ev, pos, ..
+        } = sound_ev;
         if !self.sound_or_effect_event_precond(
             is_prediction,
             event_tick_unknown,
@@ -2087,6 +2327,17 @@ impl RenderGame {
         ) {
             return;
         }
+        // merged sounds are played once, but slightly louder
+        let batched_settings;
+        let settings = if sound_ev.count > 1 {
+            batched_settings = RenderGameSettings {
+                ingame_sound_volume: settings.ingame_sound_volume * sound_ev.batch_volume(),
+                ..settings.clone()
+            };
+            &batched_settings
+        } else {
+            settings
+        };
         match ev {
             GameWorldEntitySoundEvent::Character(ev) => {
                 self.handle_character_sound_event(character_infos, settings, pos, ev, owner_id);
@@ -2157,6 +2408,71 @@ impl RenderGame {
             GameWorldEntityEffectEvent::Grenade(ev) => {
                 self.handle_grenade_effect_event(cur_time, pos, ev, owner_id);
             }
+            GameWorldEntityEffectEvent::Laser(ev) => {
+                self.handle_laser_effect_event(cur_time, pos, ev, owner_id);
+            }
+            GameWorldEntityEffectEvent::Custom(ev) => {
+                // effects that no render mod registered are ignored
+                self.particles.add_custom_effect(&pos, owner_id, &ev);
+            }
+        }
+    }
+
+    /// The event for the local stats, if the sound counts to them.
+    fn local_stats_sound_event(ev: &GameWorldEntitySoundEvent) -> Option<LocalStatsEvent> {
+        match ev {
+            GameWorldEntitySoundEvent::Character(GameCharacterSoundEvent::Sound(ev)) => match ev {
+                GameCharacterEventSound::HammerFire => {
+                    Some(LocalStatsEvent::Fire(WeaponType::Hammer))
+                }
+                GameCharacterEventSound::GunFire => Some(LocalStatsEvent::Fire(WeaponType::Gun)),
+                GameCharacterEventSound::ShotgunFire => {
+                    Some(LocalStatsEvent::Fire(WeaponType::Shotgun))
+                }
+                GameCharacterEventSound::GrenadeFire => {
+                    Some(LocalStatsEvent::Fire(WeaponType::Grenade))
+                }
+                GameCharacterEventSound::LaserFire => {
+                    Some(LocalStatsEvent::Fire(WeaponType::Laser))
+                }
+                _ => None,
+            },
+            GameWorldEntitySoundEvent::Flag(GameFlagEventSound::Capture) => {
+                Some(LocalStatsEvent::Capture)
+            }
+            _ => None,
+        }
+    }
+
+    fn add_local_stats_kill(
+        &mut self,
+        local_players: &PoolFxLinkedHashMap<PlayerId, RenderGameForPlayer>,
+        ev: &GameWorldAction,
+    ) {
+        let GameWorldAction::Kill {
+            killer, victims, ..
+        } = ev
+        else {
+            return;
+        };
+        if let Some(killer) = killer
+            && local_players.contains_key(killer)
+        {
+            // suicides don't count as kill
+            let kills = victims.iter().filter(|victim| *victim != killer).count();
+            self.local_stats_events
+                .entry(*killer)
+                .or_default()
+                .extend(std::iter::repeat_n(LocalStatsEvent::Kill, kills));
+        }
+        for victim in victims
+            .iter()
+            .filter(|victim| local_players.contains_key(*victim))
+        {
+            self.local_stats_events
+                .entry(*victim)
+                .or_default()
+                .push(LocalStatsEvent::Death);
         }
     }
 
@@ -2172,15 +2488,28 @@ impl RenderGame {
                 }
                 for (_, ev) in world.events.drain() {
                     match ev {
-                        GameWorldEvent::Sound(ev) => self.handle_sound_event(
-                            *by_prediction,
-                            event_tick_unknown,
-                            &input.character_infos,
-                            &input.players,
-                            &input.dummies,
-                            &input.settings,
-                            ev,
-                        ),
+                        GameWorldEvent::Sound(ev) => {
+                            // only the server's events count to the stats,
+                            // mispredicted shots never happened.
+                            if !*by_prediction
+                                && let Some(owner_id) = ev.owner_id
+                                && input.players.contains_key(&owner_id)
+                                && let Some(stats_ev) = Self::local_stats_sound_event(&ev.ev)
+                            {
+                                self.local_stats_events.entry(owner_id).or_default().extend(
+                                    std::iter::repeat_n(stats_ev, ev.count.max(1) as usize),
+                                );
+                            }
+                            self.handle_sound_event(
+                                *by_prediction,
+                                event_tick_unknown,
+                                &input.character_infos,
+                                &input.players,
+                                &input.dummies,
+                                &input.settings,
+                                ev,
+                            )
+                        }
                         GameWorldEvent::Effect(ev) => self.handle_effect_event(
                             *by_prediction,
                             event_tick_unknown,
@@ -2190,6 +2519,51 @@ impl RenderGame {
                             &input.settings,
                             ev,
                         ),
+                        GameWorldEvent::Hit(ev) => {
+                            // hits are only confirmed by the server.
+                            if !*by_prediction {
+                                let info = input.character_infos.get(&ev.target);
+                                let sounds = &self
+                                    .containers
+                                    .skin_container
+                                    .get_or_default_opt(info.map(|i| &i.info.skin))
+                                    .sounds;
+                                let hits = if ev.killing_blow {
+                                    sounds.hit_strong.as_slice()
+                                } else {
+                                    sounds.hit_weak.as_slice()
+                                };
+                                hits.random_entry(&mut self.rng)
+                                    .play(
+                                        SoundPlayProps::new_with_pos_opt(None)
+                                            .with_with_spatial(input.settings.spatial_sound)
+                                            .with_playback_speed(
+                                                input.settings.sound_playback_speed,
+                                            )
+                                            .with_volume(input.settings.ingame_sound_volume),
+                                    )
+                                    .detatch();
+                                self.cursor_render
+                                    .add_hit(cur_time, ev.damage, ev.killing_blow);
+                                if ev.attacker != ev.target
+                                    && input.players.contains_key(&ev.attacker)
+                                    && let GameWorldActionKillWeapon::Weapon { weapon } = ev.weapon
+                                {
+                                    self.local_stats_events
+                                        .entry(ev.attacker)
+                                        .or_default()
+                                        .push(LocalStatsEvent::Hit(weapon));
+                                }
+                            }
+                        }
+                        GameWorldEvent::Custom(ev) => {
+                            // the game mod might not predict its events,
+                            // so only the server's are handled.
+                            // events without a handler are ignored
+                            if !*by_prediction {
+                                self.custom_events.handle(cur_time, &stage_id, &ev);
+                            }
+                        }
                         GameWorldEvent::Notification(ev) => {
                             // don't rely on prediction for global events.
                             if !*by_prediction {
@@ -2243,6 +2617,7 @@ impl RenderGame {
                                         })
                                     }
                                     GameWorldNotificationEvent::Action(ev) => {
+                                        self.add_local_stats_kill(&input.players, &ev);
                                         self.handle_action_feed(
                                             cur_time,
                                             &input.character_infos,
@@ -2251,7 +2626,20 @@ impl RenderGame {
                                     }
                                     GameWorldNotificationEvent::Motd { msg } => {
                                         self.motd.msg = msg.to_string();
-                                        self.motd.started_at = Some(*cur_time);
+                                        if input.auto_show_motd {
+                                            self.motd.started_at = Some(*cur_time);
+                                        }
+                                    }
+                                    GameWorldNotificationEvent::Broadcast { msg } => {
+                                        self.broadcast.msg = msg.to_string();
+                                        self.broadcast.started_at = Some(*cur_time);
+                                    }
+                                    GameWorldNotificationEvent::Hint { .. } => {
+                                        // shown by the client as toast,
+                                        // which can be dismissed
+                                    }
+                                    GameWorldNotificationEvent::GameOver { stats, .. } => {
+                                        self.round_stats = Some(stats.iter().cloned().collect());
                                     }
                                 }
                             }
@@ -2282,19 +2670,81 @@ impl RenderGame {
             skin_info: msg.sender.skin_info,
             msg: msg.msg,
             channel: msg.channel,
+            highlighted: false,
         }
     }
 
+    /// Checks the message against the notification rules.
+    ///
+    /// Returns `None` if no rule matched, else whether a sound should be played.
+    fn chat_msg_notification(
+        character_infos: &PoolFxLinkedHashMap<CharacterId, CharacterInfo>,
+        local_players: &PoolFxLinkedHashMap<PlayerId, RenderGameForPlayer>,
+        rules: &[ChatNotifyKeyword],
+        msg: &NetChatMsg,
+    ) -> Option<bool> {
+        // own messages never notify
+        if local_players.contains_key(&msg.sender.id) {
+            return None;
+        }
+        rules
+            .iter()
+            .filter(|rule| {
+                if rule.keyword.is_empty() {
+                    local_players.keys().any(|id| {
+                        character_infos
+                            .get(id)
+                            .is_some_and(|c| msg_contains_keyword(&msg.msg, c.info.name.as_str()))
+                    })
+                } else {
+                    msg_contains_keyword(&msg.msg, &rule.keyword)
+                }
+            })
+            .fold(None, |sound, rule| {
+                Some(sound.unwrap_or(false) || rule.sound)
+            })
+    }
+
     fn handle_chat_msgs(&mut self, cur_time: &Duration, game: &mut RenderGameInput) {
-        let it = game.chat_msgs.drain(..).map(|msg| MsgInChat {
-            msg: ServerMsg::Chat(Self::from_net_msg(&game.character_infos, msg)),
-            add_time: *cur_time,
+        let mut play_sound = false;
+        let it = game.chat_msgs.drain(..).map(|msg| {
+            let notification = Self::chat_msg_notification(
+                &game.character_infos,
+                &game.players,
+                &game.chat_notify,
+                &msg,
+            );
+            play_sound |= notification.unwrap_or(false);
+            let mut msg = Self::from_net_msg(&game.character_infos, msg);
+            msg.highlighted = notification.is_some();
+            MsgInChat {
+                msg: ServerMsg::Chat(msg),
+                add_time: *cur_time,
+            }
         });
         for msg in it {
             // push_front is intentionally used over extend or similar, so msgs are
             // only mutable accessed if a new msg is actually added
             self.chat.msgs.push_front(msg);
         }
+
+        if play_sound {
+            let sounds = &self
+                .containers
+                .game_container
+                .get_or_default_opt::<ContainerKey>(None)
+                .chat_highlights;
+            if !sounds.is_empty() {
+                sounds
+                    .random_entry(&mut self.rng)
+                    .play(
+                        SoundPlayProps::new_with_pos_opt(None)
+                            .with_playback_speed(game.settings.sound_playback_speed)
+                            .with_volume(game.settings.notification_sound_volume),
+                    )
+                    .detatch();
+            }
+        }
     }
 
     fn calc_players_per_row(player_count: usize) -> usize {
@@ -2338,6 +2788,7 @@ impl RenderGame {
         config_map: &ConfigMap,
         cur_time: &Duration,
         input: &mut RenderGameInput,
+        ingame_character_infos: Option<&PoolFxLinkedHashMap<CharacterId, CharacterInfo>>,
         player_vote_rect: Option<Rect>,
     ) {
         let (top_left, top_right, bottom_left, bottom_right) = {
@@ -2400,12 +2851,14 @@ impl RenderGame {
                         config_map,
                         cur_time,
                         input,
+                        ingame_character_infos,
                         Some((
                             &player_id,
                             &RenderForPlayer {
                                 chat_info: None,
                                 emote_wheel_input: None,
                                 spectator_selection_input: None,
+                                scoreboard_input: None,
                                 local_player_info,
                                 chat_show_all: false,
                                 scoreboard_active: false,
@@ -2445,12 +2898,14 @@ impl RenderGame {
                             config_map,
                             cur_time,
                             input,
+                            ingame_character_infos,
                             Some((
                                 &player_id,
                                 &RenderForPlayer {
                                     chat_info: None,
                                     emote_wheel_input: None,
                                     spectator_selection_input: None,
+                                    scoreboard_input: None,
                                     local_player_info: LocalCharacterRenderInfo::Unavailable,
                                     chat_show_all: false,
                                     scoreboard_active: false,
@@ -2468,6 +2923,77 @@ impl RenderGame {
         }
     }
 
+    /// Renders the view of the own flag stand for the main player,
+    /// if the view is active & due for an update.
+    ///
+    /// Returns the player on whose screen the view is shown.
+    fn render_flag_stand_view(
+        &mut self,
+        config_map: &ConfigMap,
+        cur_time: &Duration,
+        input: &RenderGameInput,
+        ingame_character_infos: Option<&PoolFxLinkedHashMap<CharacterId, CharacterInfo>>,
+        width: u32,
+        height: u32,
+    ) -> Option<PlayerId> {
+        let map = self.map.try_get().unwrap();
+        let stands = self.flag_stand_view.stands(&map.data.collision);
+        let target = input
+            .settings
+            .flag_stand_view
+            .zip(input.players.keys().next())
+            .and_then(|(size, player_id)| {
+                let character = input.character_infos.get(player_id)?;
+                let stage = input.stages.get(&character.stage_id?)?;
+                flag_stand_view_target(
+                    player_id,
+                    character.side,
+                    stage.world.ctf_flags.values(),
+                    &stands,
+                )
+                .map(|pos| (size, player_id, pos))
+            });
+        let Some((size, player_id, pos)) = target else {
+            self.flag_stand_view.deactivate();
+            return None;
+        };
+
+        if let Some(offscreen_canvas) = self
+            .flag_stand_view
+            .begin_update(cur_time, width, height, size)
+        {
+            self.canvas_handle
+                .switch_canvas(GraphicsCanvasMode::Offscreen { offscreen_canvas });
+            self.render_ingame(
+                config_map,
+                cur_time,
+                input,
+                ingame_character_infos,
+                Some((
+                    player_id,
+                    &RenderForPlayer {
+                        chat_info: None,
+                        emote_wheel_input: None,
+                        spectator_selection_input: None,
+                        scoreboard_input: None,
+                        local_player_info: LocalCharacterRenderInfo::Unavailable,
+                        chat_show_all: false,
+                        scoreboard_active: false,
+
+                        zoom: 1.0,
+                        cam_mode: RenderPlayerCameraMode::AtPos {
+                            pos,
+                            locked_ingame: true,
+                        },
+                    },
+                )),
+            );
+            self.canvas_handle
+                .switch_canvas(GraphicsCanvasMode::Onscreen);
+        }
+        Some(*player_id)
+    }
+
     fn check_required_containers_loaded(&mut self) -> bool {
         let loaded = self.client_local_infos.iter().all(|i| {
             self.containers.skin_container.is_loaded_or_failed(&i.skin)
@@ -2586,14 +3112,52 @@ impl RenderGame {
         &mut self,
         cur_time: &Duration,
         character_infos: &PoolFxLinkedHashMap<CharacterId, CharacterInfo>,
+        ingame_character_infos: Option<&PoolFxLinkedHashMap<CharacterId, CharacterInfo>>,
     ) {
         Self::update_containers_impl(
             &mut self.containers,
             &mut self.map_vote_thumbnails_container,
             cur_time,
-            character_infos.values().map(|i| &***i.info),
+            character_infos
+                .values()
+                .chain(
+                    ingame_character_infos
+                        .into_iter()
+                        .flat_map(|infos| infos.values()),
+                )
+                .map(|i| &***i.info),
         );
     }
+
+    /// The character infos with the client side skin overrides applied,
+    /// `None` if no overrides are active.
+    ///
+    /// Only used for the ingame rendering, uis like the scoreboard
+    /// keep showing the original skins.
+    fn ingame_character_infos(
+        &self,
+        input: &RenderGameInput,
+    ) -> Option<PoolFxLinkedHashMap<CharacterId, CharacterInfo>> {
+        let overrides = &input.settings.skin_overrides;
+        if !overrides.is_active() {
+            return None;
+        }
+        let own_side = input
+            .players
+            .keys()
+            .next()
+            .and_then(|player_id| input.character_infos.get(player_id))
+            .and_then(|c| c.side);
+
+        let mut character_infos = self.character_infos_pool.new();
+        character_infos.extend(input.character_infos.iter().map(|(id, info)| {
+            let is_own = input.players.contains_key(id) || input.dummies.contains(id);
+            let mut info = info.clone();
+            overrides.apply(id, &mut info, own_side, is_own);
+            (*id, info)
+        }));
+        Some(character_infos)
+    }
 }
 
 impl RenderGameInterface for RenderGame {
@@ -2603,8 +3167,14 @@ impl RenderGameInterface for RenderGame {
         cur_time: &Duration,
         mut input: RenderGameInput,
     ) -> RenderGameResult {
+        let ingame_character_infos = self.ingame_character_infos(&input);
+
         // as a first step, update all containers
-        self.update_containers(cur_time, &input.character_infos);
+        self.update_containers(
+            cur_time,
+            &input.character_infos,
+            ingame_character_infos.as_ref(),
+        );
 
         // keep scene active
         self.world_sound_scene.stay_active();
@@ -2698,13 +3268,41 @@ impl RenderGameInterface for RenderGame {
 
         let player_count = input.players.len();
         if player_count == 0 {
-            self.render_ingame(config_map, cur_time, &input, None);
+            self.render_ingame(
+                config_map,
+                cur_time,
+                &input,
+                ingame_character_infos.as_ref(),
+                None,
+            );
             self.backend_handle.consumble_multi_samples();
             let _ = self.render_uis(cur_time, &input, None, &local_player_ids, &mut None, false);
+            self.flag_stand_view.deactivate();
         } else {
             let players_per_row = Self::calc_players_per_row(player_count);
             let window_props = self.canvas_handle.window_props();
 
+            // the flag stand view is shown on the screen of the main player
+            let (_, _, main_w, main_h) = if player_count == 1 {
+                (0, 0, window_props.canvas_width, window_props.canvas_height)
+            } else {
+                Self::player_render_area(
+                    0,
+                    window_props.canvas_width,
+                    window_props.canvas_height,
+                    players_per_row,
+                    player_count,
+                )
+            };
+            let flag_stand_view_player = self.render_flag_stand_view(
+                config_map,
+                cur_time,
+                &input,
+                ingame_character_infos.as_ref(),
+                main_w,
+                main_h,
+            );
+
             let mut helper = self.helper.new();
             let has_viewport_updates = if player_count == 1 {
                 let (player_id, render_for_player_game) = input.players.drain().next().unwrap();
@@ -2741,6 +3339,7 @@ impl RenderGameInterface for RenderGame {
                     config_map,
                     cur_time,
                     &input,
+                    ingame_character_infos.as_ref(),
                     Some((player_id, &render_for_player_game.render_for_player)),
                 );
             }
@@ -2768,7 +3367,7 @@ impl RenderGameInterface for RenderGame {
                         }
                     });
                 let mut player_vote_rect = None;
-                let res_render = self.render_uis(
+                let mut res_render = self.render_uis(
                     cur_time,
                     &input,
                     Some((player_id, render_for_player_game)),
@@ -2776,8 +3375,15 @@ impl RenderGameInterface for RenderGame {
                     &mut player_vote_rect,
                     expected_vote_miniscreen,
                 );
+                if let Some(evs) = self.local_stats_events.remove(player_id) {
+                    res_render.extend(evs.into_iter().map(PlayerFeedbackEvent::Stats));
+                }
                 res.player_events.insert(*player_id, res_render);
 
+                if flag_stand_view_player == Some(*player_id) {
+                    self.flag_stand_view.render(*w, *h);
+                }
+
                 // render observers
                 self.render_observers(
                     &mut render_for_player_game.observed_players,
@@ -2789,6 +3395,7 @@ impl RenderGameInterface for RenderGame {
                     config_map,
                     cur_time,
                     &mut input,
+                    ingame_character_infos.as_ref(),
                     player_vote_rect,
                 );
             }
@@ -2797,6 +3404,7 @@ impl RenderGameInterface for RenderGame {
             }
         }
         self.particles.update_rates();
+        self.local_stats_events.clear();
 
         res
     }
@@ -0,0 +1,325 @@
+//! Client side overrides of the skins of other players,
+//! e.g. to make enemies better visible.
+
+use std::hash::{Hash, Hasher};
+
+use game_config::config::{ConfigForcedSkins, ConfigSkinOverrides};
+use game_interface::types::{
+    character_info::{MAX_ASSET_NAME_LEN, NetworkSkinInfo},
+    id_types::CharacterId,
+    render::{character::CharacterInfo, game::game_match::MatchSide},
+    resource_key::NetworkResourceKey,
+};
+use math::math::vector::ubvec4;
+use pool::rc::PoolRc;
+use serde::{Deserialize, Serialize};
+
+/// Collects the raw id of a character,
+/// to derive a color from it.
+#[derive(Default)]
+struct IdHasher(u64);
+
+impl Hasher for IdHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = self.0.rotate_left(8) ^ *byte as u64;
+        }
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.0 = self.0.rotate_left(8) ^ i;
+    }
+}
+
+/// Converts a hue in `[0..1]` with full saturation and
+/// a bit reduced lightness to a rgb color.
+fn hue_to_rgb(hue: f32) -> ubvec4 {
+    let channel = |offset: f32| {
+        let k = (offset + hue * 6.0) % 6.0;
+        let val = 1.0 - (k.min(4.0 - k).clamp(0.0, 1.0));
+        // keep the colors a bit darker, so the skin's details stay visible
+        (val * 0.8 * 255.0).round() as u8 + 25
+    };
+    ubvec4::new(channel(5.0), channel(3.0), channel(1.0), 255)
+}
+
+/// A color for the character, that is always the same for the same id.
+///
+/// Consecutive ids get hues that are spread as far apart as possible
+/// (golden ratio), so many players are still distinguishable.
+pub fn random_skin_colors(id: &CharacterId) -> NetworkSkinInfo {
+    let mut hasher = IdHasher::default();
+    id.hash(&mut hasher);
+    // fibonacci hashing
+    let hue = hasher.finish().wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 40;
+    let color = hue_to_rgb(hue as f32 / (1u64 << 24) as f32);
+    NetworkSkinInfo::Custom {
+        body_color: color,
+        feet_color: color,
+    }
+}
+
+/// The overrides for the rendering of characters.
+///
+/// Only applied ingame, the scoreboard & other uis
+/// still show the original skins.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SkinOverrides {
+    pub force: ConfigForcedSkins,
+    /// The skin of forced characters, `None` keeps their skin.
+    pub skin: Option<NetworkResourceKey<MAX_ASSET_NAME_LEN>>,
+    pub team_colors: NetworkSkinInfo,
+    pub enemy_colors: NetworkSkinInfo,
+    pub randomize_colors: bool,
+}
+
+impl SkinOverrides {
+    pub fn new(config: &ConfigSkinOverrides) -> Self {
+        let colors = |color| NetworkSkinInfo::Custom {
+            body_color: color,
+            feet_color: color,
+        };
+        Self {
+            force: config.force,
+            skin: (!config.skin.is_empty())
+                .then(|| config.skin.as_str().try_into().ok())
+                .flatten(),
+            team_colors: colors(config.team_color.into()),
+            enemy_colors: colors(config.enemy_color.into()),
+            randomize_colors: config.randomize_colors,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.force != ConfigForcedSkins::None || self.randomize_colors
+    }
+
+    /// Whether the character is forced to the configured skin
+    /// and the colors it's forced to.
+    ///
+    /// With vanilla teams, enemies are all characters of the other side.
+    /// Else all characters that aren't own characters are enemies.
+    fn forced_colors(
+        &self,
+        info: &CharacterInfo,
+        own_side: Option<MatchSide>,
+        is_own: bool,
+    ) -> Option<NetworkSkinInfo> {
+        let is_enemy = match (own_side, info.side) {
+            (Some(own_side), Some(side)) => own_side != side,
+            _ => !is_own,
+        };
+        match self.force {
+            ConfigForcedSkins::None => None,
+            ConfigForcedSkins::Enemies => is_enemy.then_some(self.enemy_colors),
+            ConfigForcedSkins::All => Some(if is_enemy {
+                self.enemy_colors
+            } else {
+                self.team_colors
+            }),
+        }
+    }
+
+    /// The colors the character is rendered with.
+    ///
+    /// Forced colors are preferred over random ones,
+    /// so teams always look alike.
+    /// Random colors are only used for characters without custom colors.
+    pub fn skin_info(
+        &self,
+        id: &CharacterId,
+        info: &CharacterInfo,
+        own_side: Option<MatchSide>,
+        is_own: bool,
+    ) -> NetworkSkinInfo {
+        if let Some(colors) = self.forced_colors(info, own_side, is_own) {
+            colors
+        } else if self.randomize_colors && info.skin_info == NetworkSkinInfo::Original {
+            random_skin_colors(id)
+        } else {
+            info.skin_info
+        }
+    }
+
+    /// Applies the overrides to the character.
+    pub fn apply(
+        &self,
+        id: &CharacterId,
+        info: &mut CharacterInfo,
+        own_side: Option<MatchSide>,
+        is_own: bool,
+    ) {
+        if let Some(skin) = &self.skin
+            && self.forced_colors(info, own_side, is_own).is_some()
+            && info.info.skin != *skin
+        {
+            let mut char_info = (*info.info).clone();
+            char_info.skin = skin.clone();
+            info.info = PoolRc::from_item_without_pool(char_info);
+        }
+        info.skin_info = self.skin_info(id, info, own_side, is_own);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use base::network_string::PoolNetworkString;
+    use game_config::config::{ConfigForcedSkins, ConfigSkinOverrides};
+    use game_interface::types::{
+        character_info::{MAX_ASSET_NAME_LEN, NetworkCharacterInfo, NetworkSkinInfo},
+        id_gen::IdGenerator,
+        id_types::CharacterId,
+        render::{
+            character::{CharacterInfo, TeeEye},
+            game::game_match::MatchSide,
+        },
+        resource_key::NetworkResourceKey,
+    };
+    use math::math::vector::ubvec4;
+    use pool::rc::PoolRc;
+
+    use super::{SkinOverrides, random_skin_colors};
+
+    fn ids(count: usize) -> Vec<CharacterId> {
+        let id_gen = IdGenerator::default();
+        (0..count).map(|_| id_gen.next_id()).collect()
+    }
+
+    fn character(side: Option<MatchSide>, skin_info: NetworkSkinInfo) -> CharacterInfo {
+        CharacterInfo {
+            info: PoolRc::from_item_without_pool(NetworkCharacterInfo::explicit_default()),
+            skin_info,
+            laser_info: Default::default(),
+            stage_id: None,
+            side,
+            player_info: None,
+            account_name: None,
+            browser_score: PoolNetworkString::new_without_pool(),
+            browser_eye: TeeEye::Normal,
+            is_bot: false,
+            server_rank: None,
+        }
+    }
+
+    fn skin(name: &str) -> NetworkResourceKey<MAX_ASSET_NAME_LEN> {
+        name.try_into().unwrap()
+    }
+
+    #[test]
+    fn random_colors() {
+        let ids = ids(64);
+
+        // the same id always gets the same color
+        assert_eq!(random_skin_colors(&ids[5]), random_skin_colors(&ids[5]));
+
+        // many players are still distinguishable
+        let colors: HashSet<_> = ids
+            .iter()
+            .map(|id| match random_skin_colors(id) {
+                NetworkSkinInfo::Original => panic!("random colors must be custom colors"),
+                NetworkSkinInfo::Custom { body_color, .. } => body_color,
+            })
+            .collect();
+        assert_eq!(colors.len(), ids.len());
+
+        let overrides = SkinOverrides::new(&ConfigSkinOverrides {
+            randomize_colors: true,
+            ..Default::default()
+        });
+        let original = character(None, NetworkSkinInfo::Original);
+        assert_eq!(
+            overrides.skin_info(&ids[1], &original, None, false),
+            random_skin_colors(&ids[1])
+        );
+        // custom colors are kept
+        let custom = NetworkSkinInfo::Custom {
+            body_color: ubvec4::new(1, 2, 3, 255),
+            feet_color: ubvec4::new(1, 2, 3, 255),
+        };
+        assert_eq!(
+            overrides.skin_info(&ids[1], &character(None, custom), None, false),
+            custom
+        );
+    }
+
+    #[test]
+    fn forced_colors_precedence() {
+        let ids = ids(8);
+        let config = ConfigSkinOverrides {
+            force: ConfigForcedSkins::Enemies,
+            randomize_colors: true,
+            ..Default::default()
+        };
+        let overrides = SkinOverrides::new(&config);
+        let red = character(Some(MatchSide::Red), NetworkSkinInfo::Original);
+        let blue = character(Some(MatchSide::Blue), NetworkSkinInfo::Original);
+
+        // forced enemy colors win over random colors
+        for id in &ids {
+            assert_eq!(
+                overrides.skin_info(id, &blue, Some(MatchSide::Red), false),
+                overrides.enemy_colors
+            );
+        }
+        // team mates are not forced and thus randomized
+        assert_eq!(
+            overrides.skin_info(&ids[3], &red, Some(MatchSide::Red), false),
+            random_skin_colors(&ids[3])
+        );
+
+        // forcing all players keeps the teams consistent
+        let overrides = SkinOverrides::new(&ConfigSkinOverrides {
+            force: ConfigForcedSkins::All,
+            ..config
+        });
+        for id in &ids {
+            assert_eq!(
+                overrides.skin_info(id, &red, Some(MatchSide::Red), false),
+                overrides.team_colors
+            );
+            assert_eq!(
+                overrides.skin_info(id, &blue, Some(MatchSide::Red), false),
+                overrides.enemy_colors
+            );
+        }
+
+        // without sides, all other players are enemies
+        let solo = character(None, NetworkSkinInfo::Original);
+        assert_eq!(
+            overrides.skin_info(&ids[1], &solo, None, true),
+            overrides.team_colors
+        );
+        assert_eq!(
+            overrides.skin_info(&ids[2], &solo, None, false),
+            overrides.enemy_colors
+        );
+    }
+
+    #[test]
+    fn forced_skin() {
+        let ids = ids(2);
+        let overrides = SkinOverrides::new(&ConfigSkinOverrides {
+            force: ConfigForcedSkins::Enemies,
+            skin: "santa".to_string(),
+            ..Default::default()
+        });
+        let original = character(None, NetworkSkinInfo::Original);
+        let mut enemy = original.clone();
+        overrides.apply(&ids[0], &mut enemy, None, false);
+        assert_eq!(enemy.info.skin, skin("santa"));
+        assert_eq!(enemy.skin_info, overrides.enemy_colors);
+        // the original info is untouched
+        assert_eq!(original.info.skin, skin("default"));
+
+        let mut own = character(None, NetworkSkinInfo::Original);
+        overrides.apply(&ids[1], &mut own, None, true);
+        assert_eq!(own.info.skin, skin("default"));
+        assert_eq!(own.skin_info, NetworkSkinInfo::Original);
+    }
+}
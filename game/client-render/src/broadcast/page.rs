@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use client_ui::{
+    broadcast::{
+        page::BroadcastUi,
+        user_data::{BROADCAST_MAX_CHARS, UserData},
+    },
+    markup::{MarkupLine, parse_markup},
+};
+use egui::Color32;
+use graphics::{
+    graphics::graphics::Graphics,
+    handles::{
+        backend::backend::GraphicsBackendHandle, canvas::canvas::GraphicsCanvasHandle,
+        stream::stream::GraphicsStreamHandle, texture::texture::GraphicsTextureHandle,
+    },
+};
+use ui_base::{
+    types::UiRenderPipe,
+    ui::{UiContainer, UiCreator},
+};
+use ui_generic::generic_ui_renderer;
+
+pub struct BroadcastRenderPipe<'a> {
+    pub cur_time: &'a Duration,
+}
+
+pub struct BroadcastRender {
+    pub ui: UiContainer,
+    broadcast_ui: BroadcastUi,
+
+    backend_handle: GraphicsBackendHandle,
+    canvas_handle: GraphicsCanvasHandle,
+    stream_handle: GraphicsStreamHandle,
+    texture_handle: GraphicsTextureHandle,
+
+    pub msg: String,
+    pub started_at: Option<Duration>,
+
+    /// The parsed markup of the last rendered message.
+    lines: (String, Vec<MarkupLine>),
+}
+
+impl BroadcastRender {
+    pub fn new(graphics: &Graphics, creator: &UiCreator) -> Self {
+        let mut ui = UiContainer::new(creator);
+        ui.set_main_panel_color(&Color32::TRANSPARENT);
+        Self {
+            ui,
+            broadcast_ui: BroadcastUi::new(),
+
+            backend_handle: graphics.backend_handle.clone(),
+            canvas_handle: graphics.canvas_handle.clone(),
+            stream_handle: graphics.stream_handle.clone(),
+            texture_handle: graphics.texture_handle.clone(),
+
+            msg: Default::default(),
+            started_at: None,
+
+            lines: Default::default(),
+        }
+    }
+
+    pub fn render(&mut self, pipe: &mut BroadcastRenderPipe) {
+        // an empty broadcast clears the current one
+        if self.msg.is_empty()
+            || self.started_at.is_none_or(|started_at| {
+                pipe.cur_time.saturating_sub(started_at) > Duration::from_secs(10)
+            })
+        {
+            return;
+        }
+
+        if self.lines.0 != self.msg {
+            self.lines = (
+                self.msg.clone(),
+                parse_markup(&self.msg, BROADCAST_MAX_CHARS),
+            );
+        }
+
+        let mut user_data = UserData {
+            lines: &self.lines.1,
+        };
+        let mut dummy_pipe = UiRenderPipe::new(*pipe.cur_time, &mut user_data);
+
+        generic_ui_renderer::render(
+            &self.backend_handle,
+            &self.texture_handle,
+            &self.stream_handle,
+            &self.canvas_handle,
+            &mut self.ui,
+            &mut self.broadcast_ui,
+            &mut dummy_pipe,
+            Default::default(),
+        );
+    }
+}
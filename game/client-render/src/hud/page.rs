@@ -5,7 +5,7 @@ use client_containers::{ctf::CtfContainer, skins::SkinContainer};
 use client_render_base::render::tee::RenderTee;
 use client_ui::hud::{
     page::HudUi,
-    user_data::{RenderDateTime, UserData},
+    user_data::{RenderDateTime, RenderRaceSplits, UserData},
 };
 use egui::Color32;
 use game_interface::types::{
@@ -36,6 +36,7 @@ pub struct HudRenderPipe<'a> {
     pub ctf_container: &'a mut CtfContainer,
     pub character_infos: &'a FxLinkedHashMap<CharacterId, CharacterInfo>,
     pub date_time: &'a Option<RenderDateTime>,
+    pub race_splits: &'a Option<RenderRaceSplits>,
 }
 
 pub struct HudRender {
@@ -75,6 +76,7 @@ impl HudRender {
             canvas_handle: &self.canvas_handle,
             stream_handle: &self.stream_handle,
             date_time: pipe.date_time,
+            race_splits: pipe.race_splits,
         };
         let mut dummy_pipe = UiRenderPipe::new(*pipe.cur_time, &mut user_data);
 
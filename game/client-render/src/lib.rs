@@ -1,6 +1,7 @@
 #![allow(clippy::too_many_arguments)]
 
 pub mod actionfeed;
+pub mod broadcast;
 pub mod chat;
 pub mod emote_wheel;
 pub mod emoticons;
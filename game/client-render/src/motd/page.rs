@@ -1,6 +1,12 @@
 use std::time::Duration;
 
-use client_ui::motd::{page::MotdUi, user_data::UserData};
+use client_ui::{
+    markup::{MarkupLine, parse_markup},
+    motd::{
+        page::MotdUi,
+        user_data::{MOTD_MAX_CHARS, UserData},
+    },
+};
 use egui::Color32;
 use graphics::{
     graphics::graphics::Graphics,
@@ -30,6 +36,9 @@ pub struct MotdRender {
 
     pub msg: String,
     pub started_at: Option<Duration>,
+
+    /// The parsed markup of the last rendered message.
+    lines: (String, Vec<MarkupLine>),
 }
 
 impl MotdRender {
@@ -47,6 +56,8 @@ impl MotdRender {
 
             msg: Default::default(),
             started_at: None,
+
+            lines: Default::default(),
         }
     }
 
@@ -57,7 +68,13 @@ impl MotdRender {
             return;
         }
 
-        let mut user_data = UserData { msg: &self.msg };
+        if self.lines.0 != self.msg {
+            self.lines = (self.msg.clone(), parse_markup(&self.msg, MOTD_MAX_CHARS));
+        }
+
+        let mut user_data = UserData {
+            lines: &self.lines.1,
+        };
         let mut dummy_pipe = UiRenderPipe::new(*pipe.cur_time, &mut user_data);
 
         generic_ui_renderer::render(
@@ -17,10 +17,15 @@ use ui_base::{
     ui_render::render_ui,
 };
 
+/// Wrench icon followed by a space.
+const PRACTICE_ICON: &str = "\u{f0ad} ";
+
 pub struct NameplatePlayer<'a> {
     pub name: &'a str,
     pub pos: &'a vec2,
     pub phased_alpha: f32,
+    /// Shows the practice marker in front of the name.
+    pub practice: bool,
 }
 
 pub struct NameplateRenderPipe<'a> {
@@ -74,6 +79,7 @@ impl NameplateRender {
                     name,
                     pos,
                     phased_alpha,
+                    practice,
                 } in &mut *pipe.players
                 {
                     ui.set_opacity(phased_alpha);
@@ -90,6 +96,18 @@ impl NameplateRender {
                         ..Default::default()
                     };
                     let font_size = 1.0 * height_scale;
+                    if practice {
+                        job.append(
+                            PRACTICE_ICON,
+                            0.0,
+                            TextFormat {
+                                color: Color32::LIGHT_BLUE,
+                                font_id: FontId::proportional(font_size * 0.8),
+                                valign: egui::Align::Center,
+                                ..Default::default()
+                            },
+                        );
+                    }
                     job.append(
                         name,
                         0.0,
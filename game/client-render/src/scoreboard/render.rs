@@ -3,7 +3,10 @@ use std::time::Duration;
 use base::linked_hash_map_view::FxLinkedHashMap;
 use client_containers::{flags::FlagsContainer, skins::SkinContainer};
 use client_render_base::render::tee::RenderTee;
-use client_ui::scoreboard::{page::ScoreboardUi, user_data::UserData};
+use client_ui::scoreboard::{
+    page::ScoreboardUi,
+    user_data::{ScoreboardEvent, ScoreboardPlayerContext, UserData},
+};
 use egui::Color32;
 use graphics::{
     graphics::graphics::Graphics,
@@ -13,9 +16,12 @@ use graphics::{
     },
 };
 
-use game_interface::types::{
-    id_types::CharacterId,
-    render::{character::CharacterInfo, scoreboard::Scoreboard},
+use game_interface::{
+    events::GameWorldPlayerStats,
+    types::{
+        id_types::CharacterId,
+        render::{character::CharacterInfo, scoreboard::Scoreboard},
+    },
 };
 use ui_base::{
     types::UiRenderPipe,
@@ -33,6 +39,10 @@ pub struct ScoreboardRenderPipe<'a> {
     pub flags_container: &'a mut FlagsContainer,
 
     pub own_character_id: &'a CharacterId,
+    pub round_stats: Option<&'a [GameWorldPlayerStats]>,
+
+    /// Input & player context, if the scoreboard is interactive.
+    pub interactive: Option<(&'a mut Option<egui::RawInput>, &'a ScoreboardPlayerContext)>,
 }
 
 pub struct ScoreboardRender {
@@ -59,8 +69,14 @@ impl ScoreboardRender {
         }
     }
 
-    pub fn render(&mut self, pipe: &mut ScoreboardRenderPipe) {
-        generic_ui_renderer::render(
+    pub fn render(&mut self, pipe: &mut ScoreboardRenderPipe) -> Vec<ScoreboardEvent> {
+        let mut events = Vec::new();
+        let (input, player_context) = match &mut pipe.interactive {
+            Some((input, player_context)) => (input.take(), Some(*player_context)),
+            None => (None, None),
+        };
+        let is_interactive = player_context.is_some();
+        let platform_output = generic_ui_renderer::render(
             &self.backend_handle,
             &self.texture_handle,
             &self.stream_handle,
@@ -79,9 +95,17 @@ impl ScoreboardRender {
                     flags_container: pipe.flags_container,
 
                     own_character_id: pipe.own_character_id,
+                    round_stats: pipe.round_stats,
+
+                    player_context,
+                    events: &mut events,
                 },
             ),
-            Default::default(),
+            input.unwrap_or_default(),
         );
+        if is_interactive {
+            events.push(ScoreboardEvent::PlatformOutput(platform_output));
+        }
+        events
     }
 }
@@ -1,5 +1,6 @@
 use std::{
     collections::BTreeMap,
+    ops::RangeBounds,
     sync::{Arc, mpsc::sync_channel},
 };
 
@@ -76,6 +77,12 @@ impl Replay {
     }
 
     pub fn to_demo(&mut self) -> anyhow::Result<DemoViewer> {
+        self.to_demo_range(..)
+    }
+
+    /// Like [`Self::to_demo`], but only contains the given monotonic ticks,
+    /// e.g. for the highlight of a round.
+    pub fn to_demo_range(&mut self, ticks: impl RangeBounds<u64>) -> anyhow::Result<DemoViewer> {
         let (sender, receiver) = sync_channel(1);
 
         let mut recorder = DemoRecorder::new(
@@ -89,13 +96,18 @@ impl Replay {
             Some("replay".to_string()),
         );
 
-        for (monotonic_tick, events) in self.events.clone() {
-            for event in events {
-                recorder.add_event(monotonic_tick, event);
+        let (start, end) = (ticks.start_bound().cloned(), ticks.end_bound().cloned());
+        anyhow::ensure!(
+            self.snapshots.range((start, end)).next().is_some(),
+            "the replay contains no snapshots for these ticks"
+        );
+        for (monotonic_tick, events) in self.events.range((start, end)) {
+            for event in events.clone() {
+                recorder.add_event(*monotonic_tick, event);
             }
         }
-        for (monotonic_tick, snapshot) in self.snapshots.clone() {
-            recorder.add_snapshot(monotonic_tick, snapshot);
+        for (monotonic_tick, snapshot) in self.snapshots.range((start, end)) {
+            recorder.add_snapshot(*monotonic_tick, snapshot.clone());
         }
 
         drop(recorder);
@@ -10,6 +10,8 @@ pub struct ChatMsg {
     pub skin_info: NetworkSkinInfo,
     pub msg: String,
     pub channel: NetChatMsgPlayerChannel,
+    /// The message matched a notification rule.
+    pub highlighted: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,3 +32,76 @@ pub enum ServerMsg {
     Chat(ChatMsg),
     System(MsgSystem),
 }
+
+/// A notification rule that is currently active.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatNotifyKeyword {
+    /// An empty keyword matches the names of the own players.
+    pub keyword: String,
+    /// Whether a notification sound should be played.
+    pub sound: bool,
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Whether the keyword appears in the message, ignoring the case.
+///
+/// Keywords must not be part of a longer word,
+/// e.g. `tee` does not match `teeworlds`.
+pub fn msg_contains_keyword(msg: &str, keyword: &str) -> bool {
+    let keyword = keyword.trim().to_lowercase();
+    if keyword.is_empty() {
+        return false;
+    }
+    // keywords that start or end with e.g. a bracket
+    // don't need a word boundary at that side.
+    let check_start = keyword.starts_with(is_word_char);
+    let check_end = keyword.ends_with(is_word_char);
+
+    let msg = msg.to_lowercase();
+    msg.match_indices(&keyword).any(|(index, _)| {
+        (!check_start || !msg[..index].ends_with(is_word_char))
+            && (!check_end || !msg[index + keyword.len()..].starts_with(is_word_char))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::msg_contains_keyword;
+
+    #[test]
+    fn keyword_matcher() {
+        assert!(msg_contains_keyword("hello tee", "tee"));
+        assert!(msg_contains_keyword("TEE: hi", "tee"));
+        assert!(msg_contains_keyword("hi Tee!", "tEE"));
+        assert!(msg_contains_keyword("(tee)", "tee"));
+        assert!(!msg_contains_keyword("teeworlds", "tee"));
+        assert!(!msg_contains_keyword("my_tee", "tee"));
+        assert!(!msg_contains_keyword("tee2", "tee"));
+        // the second occurrence is a whole word
+        assert!(msg_contains_keyword("teeworlds tee", "tee"));
+
+        // multiple words & special characters
+        assert!(msg_contains_keyword("gg, nameless tee!", "nameless tee"));
+        assert!(msg_contains_keyword("hi [clan]member", "[clan]"));
+        assert!(!msg_contains_keyword("hi [clan]member", "[clan]m"));
+
+        // empty keywords never match
+        assert!(!msg_contains_keyword("hello", ""));
+        assert!(!msg_contains_keyword("hello", "  "));
+    }
+
+    #[test]
+    fn keyword_matcher_unicode() {
+        assert!(msg_contains_keyword("Grüße an JÜRGEN", "jürgen"));
+        assert!(!msg_contains_keyword("jürgens", "jürgen"));
+        assert!(msg_contains_keyword("привет, Вася", "вася"));
+        assert!(!msg_contains_keyword("Васятка", "вася"));
+        // non latin letters are also part of a word
+        assert!(!msg_contains_keyword("teeé", "tee"));
+        assert!(msg_contains_keyword("こんにちは ★星★", "★星★"));
+        assert!(msg_contains_keyword("gg 🐸", "🐸"));
+    }
+}
@@ -2,3 +2,4 @@ pub mod actionfeed;
 pub mod cert;
 pub mod chat;
 pub mod console;
+pub mod log;
@@ -0,0 +1,392 @@
+use std::{
+    collections::{BTreeSet, VecDeque},
+    sync::{
+        Arc, LazyLock, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use log::{Level, LevelFilter};
+
+/// How many log records are kept by default.
+pub const LOG_BUFFER_CAPACITY: usize = 2048;
+
+/// The categories of log targets the log viewer can toggle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogTarget {
+    Vulkan,
+    FsWatch,
+    Network,
+    Other,
+}
+
+impl LogTarget {
+    pub const ALL: [LogTarget; 4] = [
+        LogTarget::Vulkan,
+        LogTarget::FsWatch,
+        LogTarget::Network,
+        LogTarget::Other,
+    ];
+
+    /// The category of a log target, which is either the explicit
+    /// target of the log macro or the module path of the caller.
+    pub fn from_target(target: &str) -> Self {
+        let target = target.to_lowercase();
+        let crate_name = target.split("::").next().unwrap_or_default();
+        if target.contains("vulkan") || crate_name == "ash" {
+            Self::Vulkan
+        } else if target.starts_with("fs-watch") || crate_name == "notify" {
+            Self::FsWatch
+        } else if crate_name.starts_with("network")
+            || crate_name.starts_with("quinn")
+            || crate_name == "rustls"
+        {
+            Self::Network
+        } else {
+            Self::Other
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LogTarget::Vulkan => "Vulkan",
+            LogTarget::FsWatch => "Fs watch",
+            LogTarget::Network => "Network",
+            LogTarget::Other => "Other",
+        }
+    }
+}
+
+/// A single captured log record.
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    /// Increases with every record, never reused.
+    pub id: u64,
+    /// Time since the log buffer was created.
+    pub time: Duration,
+    pub level: Level,
+    pub target: String,
+    pub category: LogTarget,
+    pub msg: String,
+}
+
+impl LogEntry {
+    /// The record in the format it is copied to the clipboard.
+    pub fn to_line(&self) -> String {
+        format!(
+            "[{:>8.3}] {:<5} {}: {}",
+            self.time.as_secs_f64(),
+            self.level,
+            self.target,
+            self.msg
+        )
+    }
+}
+
+#[derive(Debug)]
+struct LogBufferInner {
+    records: Mutex<VecDeque<LogEntry>>,
+    capacity: usize,
+    next_id: AtomicU64,
+    start: Instant,
+}
+
+/// A bounded ring buffer of log records that is shared
+/// between the log sink and the log viewer.
+///
+/// Records are formatted before the lock is taken,
+/// so the lock is only held for the push itself.
+#[derive(Debug, Clone)]
+pub struct LogBuffer(Arc<LogBufferInner>);
+
+static GLOBAL_LOG_BUFFER: LazyLock<LogBuffer> =
+    LazyLock::new(|| LogBuffer::new(LOG_BUFFER_CAPACITY));
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self(Arc::new(LogBufferInner {
+            records: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+            next_id: AtomicU64::new(0),
+            start: Instant::now(),
+        }))
+    }
+
+    /// The buffer that the [`LogCapture`] of the process writes to.
+    pub fn global() -> &'static LogBuffer {
+        &GLOBAL_LOG_BUFFER
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.0.capacity
+    }
+
+    pub fn push(&self, level: Level, target: &str, msg: String) {
+        let mut entry = LogEntry {
+            id: 0,
+            time: self.0.start.elapsed(),
+            level,
+            target: target.to_string(),
+            category: LogTarget::from_target(target),
+            msg,
+        };
+        let mut records = self.0.records.lock().unwrap_or_else(|err| err.into_inner());
+        // assigned under the lock, so the records stay ordered by id
+        entry.id = self.0.next_id.fetch_add(1, Ordering::Relaxed);
+        if records.len() >= self.0.capacity {
+            records.pop_front();
+        }
+        records.push_back(entry);
+    }
+
+    /// Appends all records with an id of at least `first_id` to `res`
+    /// and returns the id the next call should start at.
+    pub fn records_since(&self, first_id: u64, res: &mut Vec<LogEntry>) -> u64 {
+        let records = self.0.records.lock().unwrap_or_else(|err| err.into_inner());
+        let start = records.partition_point(|entry| entry.id < first_id);
+        res.extend(records.range(start..).cloned());
+        records.back().map(|entry| entry.id + 1).unwrap_or(first_id)
+    }
+}
+
+/// A log sink that forwards records to the inner logger
+/// and additionally captures them into a [`LogBuffer`].
+pub struct LogCapture<L> {
+    inner: L,
+    inner_filter: LevelFilter,
+    capture_filter: LevelFilter,
+    buffer: LogBuffer,
+}
+
+impl<L: log::Log> LogCapture<L> {
+    /// `inner_filter` is the max level the inner logger is interested in,
+    /// records up to `capture_filter` are captured in any case.
+    pub fn new(
+        inner: L,
+        inner_filter: LevelFilter,
+        capture_filter: LevelFilter,
+        buffer: LogBuffer,
+    ) -> Self {
+        Self {
+            inner,
+            inner_filter,
+            capture_filter,
+            buffer,
+        }
+    }
+
+    /// The max level that must be passed to [`log::set_max_level`].
+    pub fn max_level(&self) -> LevelFilter {
+        self.inner_filter.max(self.capture_filter)
+    }
+}
+
+impl<L: log::Log> log::Log for LogCapture<L> {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= self.capture_filter || self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.inner.enabled(record.metadata()) {
+            self.inner.log(record);
+        }
+        if record.level() <= self.capture_filter {
+            self.buffer
+                .push(record.level(), record.target(), record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush()
+    }
+}
+
+/// The filters of the log viewer.
+#[derive(Debug, Clone)]
+pub struct LogFilter {
+    /// Records with a less severe level are hidden.
+    pub level: LevelFilter,
+    pub hidden_targets: BTreeSet<LogTarget>,
+    /// Case insensitive text that must be part of the target or message.
+    pub search: String,
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self {
+            level: LevelFilter::Trace,
+            hidden_targets: Default::default(),
+            search: Default::default(),
+        }
+    }
+}
+
+impl LogFilter {
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        if entry.level > self.level || self.hidden_targets.contains(&entry.category) {
+            return false;
+        }
+        let search = self.search.trim().to_lowercase();
+        search.is_empty()
+            || entry.msg.to_lowercase().contains(&search)
+            || entry.target.to_lowercase().contains(&search)
+    }
+}
+
+/// The state of the log viewer, a copy of the captured records
+/// plus the filters and the selection of the user.
+#[derive(Debug)]
+pub struct LogView {
+    buffer: LogBuffer,
+    next_id: u64,
+    pub records: VecDeque<LogEntry>,
+    pub filter: LogFilter,
+    /// Don't follow new records.
+    pub paused: bool,
+    /// The ids of the selected records.
+    pub selected: BTreeSet<u64>,
+    /// Whether the log viewer is shown instead of the console.
+    pub active: bool,
+}
+
+impl LogView {
+    pub fn new(buffer: LogBuffer) -> Self {
+        Self {
+            buffer,
+            next_id: 0,
+            records: Default::default(),
+            filter: Default::default(),
+            paused: false,
+            selected: Default::default(),
+            active: false,
+        }
+    }
+
+    /// Copies the new records of the log buffer.
+    pub fn update(&mut self) {
+        let mut new_records = Vec::new();
+        self.next_id = self.buffer.records_since(self.next_id, &mut new_records);
+        self.records.extend(new_records);
+        while self.records.len() > self.buffer.capacity() {
+            if let Some(entry) = self.records.pop_front() {
+                self.selected.remove(&entry.id);
+            }
+        }
+    }
+
+    pub fn filtered(&self) -> impl Iterator<Item = &LogEntry> {
+        self.records
+            .iter()
+            .filter(|entry| self.filter.matches(entry))
+    }
+
+    /// The selected records, one line each.
+    pub fn selection_text(&self) -> String {
+        self.records
+            .iter()
+            .filter(|entry| self.selected.contains(&entry.id))
+            .map(|entry| entry.to_line())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use log::{Level, LevelFilter};
+
+    use super::{LogBuffer, LogFilter, LogTarget, LogView};
+
+    #[test]
+    fn ring_buffer() {
+        let buffer = LogBuffer::new(3);
+        for i in 0..5 {
+            buffer.push(Level::Info, "test", format!("msg {i}"));
+        }
+        let mut records = Vec::new();
+        let next_id = buffer.records_since(0, &mut records);
+        assert_eq!(next_id, 5);
+        // the oldest records were dropped
+        let msgs: Vec<_> = records.iter().map(|entry| entry.msg.as_str()).collect();
+        assert_eq!(msgs, ["msg 2", "msg 3", "msg 4"]);
+
+        records.clear();
+        assert_eq!(buffer.records_since(next_id, &mut records), 5);
+        assert!(records.is_empty());
+        buffer.push(Level::Warn, "test", "msg 5".into());
+        assert_eq!(buffer.records_since(next_id, &mut records), 6);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].id, 5);
+
+        // the view keeps at most as many records as the buffer
+        let mut view = LogView::new(buffer.clone());
+        view.update();
+        view.selected.insert(3);
+        for i in 6..9 {
+            buffer.push(Level::Info, "test", format!("msg {i}"));
+        }
+        view.update();
+        assert_eq!(view.records.len(), 3);
+        assert_eq!(view.records.front().unwrap().id, 6);
+        assert!(view.selected.is_empty());
+    }
+
+    #[test]
+    fn filtering() {
+        let buffer = LogBuffer::new(16);
+        buffer.push(Level::Error, "vulkan", "device lost".into());
+        buffer.push(Level::Info, "fs-watch", "could not watch directory".into());
+        buffer.push(Level::Debug, "network", "connecting to 127.0.0.1".into());
+        buffer.push(Level::Warn, "ddnet_rs::client", "Device changed".into());
+        let mut view = LogView::new(buffer);
+        view.update();
+        let msgs = |view: &LogView| -> Vec<String> {
+            view.filtered().map(|entry| entry.msg.clone()).collect()
+        };
+        assert_eq!(msgs(&view).len(), 4);
+
+        view.filter.level = LevelFilter::Warn;
+        assert_eq!(msgs(&view), ["device lost", "Device changed"]);
+
+        view.filter = LogFilter::default();
+        view.filter.hidden_targets.insert(LogTarget::Vulkan);
+        view.filter.hidden_targets.insert(LogTarget::Network);
+        assert_eq!(msgs(&view), ["could not watch directory", "Device changed"]);
+
+        // the search ignores the case & also looks at the target
+        view.filter = LogFilter::default();
+        view.filter.search = "DEVICE".into();
+        assert_eq!(msgs(&view), ["device lost", "Device changed"]);
+        view.filter.search = "client".into();
+        assert_eq!(msgs(&view), ["Device changed"]);
+
+        view.selected
+            .extend(view.records.iter().map(|entry| entry.id).take(2));
+        let text = view.selection_text();
+        assert_eq!(text.lines().count(), 2);
+        assert!(text.contains("ERROR vulkan: device lost"), "{text}");
+    }
+
+    #[test]
+    fn target_extraction() {
+        assert_eq!(LogTarget::from_target("vulkan"), LogTarget::Vulkan);
+        assert_eq!(
+            LogTarget::from_target("graphics_backend::backends::vulkan::swapchain"),
+            LogTarget::Vulkan
+        );
+        assert_eq!(LogTarget::from_target("fs-watch"), LogTarget::FsWatch);
+        assert_eq!(
+            LogTarget::from_target("notify::inotify"),
+            LogTarget::FsWatch
+        );
+        assert_eq!(LogTarget::from_target("network"), LogTarget::Network);
+        assert_eq!(LogTarget::from_target("network_logic"), LogTarget::Network);
+        assert_eq!(
+            LogTarget::from_target("quinn_proto::connection"),
+            LogTarget::Network
+        );
+        assert_eq!(LogTarget::from_target("fs"), LogTarget::Other);
+        assert_eq!(LogTarget::from_target("ddnet_rs::client"), LogTarget::Other);
+    }
+}
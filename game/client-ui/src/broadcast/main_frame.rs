@@ -0,0 +1,34 @@
+use egui::{Align2, Color32, Frame, Vec2, Window};
+
+use tracing::instrument;
+use ui_base::types::{UiRenderPipe, UiState};
+
+use crate::markup::render_markup;
+
+use super::user_data::UserData;
+
+/// not required
+#[instrument(level = "trace", skip_all)]
+pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &mut UiState) {
+    ui.style_mut().animation_time = 0.0;
+    ui.set_clip_rect(ui.available_rect_before_wrap());
+
+    let res = Window::new("")
+        .resizable(false)
+        .title_bar(false)
+        .interactable(false)
+        .frame(
+            Frame::default()
+                .fill(Color32::from_black_alpha(100))
+                .corner_radius(5.0)
+                .inner_margin(5.0),
+        )
+        .anchor(Align2::CENTER_TOP, Vec2::new(0.0, 60.0))
+        .max_width(500.0)
+        .show(ui.ctx(), |ui| {
+            render_markup(ui, pipe.user_data.lines, Color32::WHITE);
+        });
+    if let Some(res) = res {
+        ui_state.add_blur_rect(res.response.rect, 5.0);
+    }
+}
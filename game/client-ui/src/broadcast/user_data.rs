@@ -0,0 +1,8 @@
+use crate::markup::MarkupLine;
+
+/// A broadcast is cut after this many characters.
+pub const BROADCAST_MAX_CHARS: usize = 256;
+
+pub struct UserData<'a> {
+    pub lines: &'a [MarkupLine],
+}
@@ -28,7 +28,7 @@ pub fn render(
         NetChatMsgPlayerChannel::GameTeam => (Stroke::new(2.0, Color32::LIGHT_GREEN), None),
         NetChatMsgPlayerChannel::Whisper(to) => (Stroke::new(2.0, Color32::RED), Some(to)),
     };
-    entry_frame(ui, stroke, |ui| {
+    entry_frame(ui, stroke, msg.highlighted, |ui| {
         ui.add_space(MARGIN);
         let response = ui.horizontal(|ui| {
             ui.add_space(MARGIN);
@@ -5,8 +5,18 @@ use egui::{Color32, Stroke, epaint::Shadow};
 pub const MARGIN: f32 = 5.0;
 pub const TEE_SIZE: f32 = 25.0;
 pub const MARGIN_FROM_TEE: f32 = 5.0;
-pub fn entry_frame(ui: &mut egui::Ui, stroke: Stroke, f: impl FnOnce(&mut egui::Ui)) {
-    let color_frame = Color32::from_rgba_unmultiplied(0, 0, 0, 15);
+/// Highlighted entries matched a notification rule.
+pub fn entry_frame(
+    ui: &mut egui::Ui,
+    stroke: Stroke,
+    highlighted: bool,
+    f: impl FnOnce(&mut egui::Ui),
+) {
+    let color_frame = if highlighted {
+        Color32::from_rgba_unmultiplied(255, 200, 0, 40)
+    } else {
+        Color32::from_rgba_unmultiplied(0, 0, 0, 15)
+    };
 
     let style = ui.style();
     egui::Frame::default()
@@ -20,7 +20,7 @@ pub fn render(
     ui_state: &mut UiState,
     msg: &MsgSystem,
 ) {
-    entry_frame(ui, Stroke::NONE, |ui| {
+    entry_frame(ui, Stroke::NONE, false, |ui| {
         ui.add_space(MARGIN);
         let response = ui.horizontal(|ui| {
             ui.add_space(MARGIN);
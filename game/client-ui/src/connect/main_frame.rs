@@ -1,5 +1,6 @@
 use egui::{Align2, Color32, FontId, Frame, RichText, ScrollArea, Vec2, Window, vec2};
 
+use base::hash::fmt_hash;
 use game_base::connecting_log::ConnectModes;
 use tracing::instrument;
 use ui_base::{
@@ -51,6 +52,83 @@ pub fn render_modes(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
                     }
                 });
             }
+            ConnectModes::DownloadPrompt { downloads } => {
+                ui.vertical(|ui| {
+                    ui.label(format!(
+                        "{} is not part of a trusted community.\n\
+                        Joining requires downloading:",
+                        pipe.user_data.config.storage::<String>("server-addr")
+                    ));
+                    for download in downloads {
+                        ui.label(format!("- {download}"));
+                    }
+                    let mut remember = pipe
+                        .user_data
+                        .config
+                        .storage::<bool>("download-prompt-remember");
+                    if ui
+                        .checkbox(&mut remember, "Remember my choice for this server")
+                        .changed()
+                    {
+                        pipe.user_data
+                            .config
+                            .set_storage("download-prompt-remember", &remember);
+                    }
+                    ui.horizontal(|ui| {
+                        if ui.button("Download").clicked() {
+                            pipe.user_data.events.push(UiEvent::DownloadsAccepted {
+                                accepted: true,
+                                remember,
+                            });
+                        }
+                        if ui.button("Cancel").clicked() {
+                            pipe.user_data.events.push(UiEvent::DownloadsAccepted {
+                                accepted: false,
+                                remember,
+                            });
+                            pipe.user_data.config.engine.ui.path.route("");
+                        }
+                    });
+                });
+            }
+            ConnectModes::CertMismatch {
+                addr,
+                pinned,
+                presented,
+            } => {
+                ui.vertical(|ui| {
+                    ui.label(
+                        RichText::new("The certificate of the server changed!")
+                            .color(Color32::from_rgb(255, 150, 50)),
+                    );
+                    ui.label(format!(
+                        "{addr} presents a different certificate than \
+                        on your last connect.\n\
+                        This can be a regular renewal by the server owner, \
+                        but also someone pretending to be this server."
+                    ));
+                    ui.label(
+                        RichText::new(format!(
+                            "Remembered: {pinned}\nPresented: {}",
+                            fmt_hash(&presented)
+                        ))
+                        .font(FontId::monospace(10.0)),
+                    );
+                    ui.horizontal(|ui| {
+                        if ui.button("Trust new certificate").clicked() {
+                            pipe.user_data
+                                .events
+                                .push(UiEvent::CertChangeAccepted { accepted: true });
+                        }
+                        if ui.button("Cancel").clicked() {
+                            pipe.user_data
+                                .events
+                                .push(UiEvent::CertChangeAccepted { accepted: false });
+                            pipe.user_data.config.engine.ui.path.route("");
+                        }
+                    });
+                });
+            }
             ConnectModes::DisconnectErr { msg } => {
                 ui.vertical(|ui| {
                     ui.label(format!(
@@ -0,0 +1,127 @@
+use client_types::log::{LogTarget, LogView};
+use egui::{Color32, RichText};
+use egui_extras::{Size, StripBuilder};
+use log::{Level, LevelFilter};
+use tracing::instrument;
+
+fn level_color(level: Level) -> Color32 {
+    match level {
+        Level::Error => Color32::from_rgb(255, 90, 90),
+        Level::Warn => Color32::from_rgb(255, 200, 60),
+        Level::Info => Color32::from_rgb(220, 220, 220),
+        Level::Debug => Color32::from_rgb(130, 170, 255),
+        Level::Trace => Color32::GRAY,
+    }
+}
+
+/// Filters, search & the copy button.
+fn render_toolbar(ui: &mut egui::Ui, logs: &mut LogView) {
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_salt("log-level-filter")
+            .selected_text(logs.filter.level.as_str())
+            .show_ui(ui, |ui| {
+                for level in [
+                    LevelFilter::Error,
+                    LevelFilter::Warn,
+                    LevelFilter::Info,
+                    LevelFilter::Debug,
+                    LevelFilter::Trace,
+                ] {
+                    ui.selectable_value(&mut logs.filter.level, level, level.as_str());
+                }
+            });
+
+        for target in LogTarget::ALL {
+            let mut shown = !logs.filter.hidden_targets.contains(&target);
+            if ui.checkbox(&mut shown, target.name()).changed() {
+                if shown {
+                    logs.filter.hidden_targets.remove(&target);
+                } else {
+                    logs.filter.hidden_targets.insert(target);
+                }
+            }
+        }
+
+        ui.add(
+            egui::TextEdit::singleline(&mut logs.filter.search)
+                .hint_text("Search")
+                .desired_width(150.0),
+        );
+
+        let mut autoscroll = !logs.paused;
+        if ui.checkbox(&mut autoscroll, "Autoscroll").changed() {
+            logs.paused = !autoscroll;
+        }
+
+        if ui
+            .add_enabled(
+                !logs.selected.is_empty(),
+                egui::Button::new("Copy selection"),
+            )
+            .clicked()
+        {
+            ui.ctx().copy_text(logs.selection_text());
+        }
+        if ui
+            .add_enabled(
+                !logs.selected.is_empty(),
+                egui::Button::new("Clear selection"),
+            )
+            .clicked()
+        {
+            logs.selected.clear();
+        }
+    });
+}
+
+/// The captured log records, a click selects a record.
+fn render_records(ui: &mut egui::Ui, logs: &mut LogView) {
+    let rows: Vec<usize> = logs
+        .records
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| logs.filter.matches(entry))
+        .map(|(index, _)| index)
+        .collect();
+    let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+    let mut toggled = None;
+    egui::ScrollArea::both()
+        .stick_to_bottom(!logs.paused)
+        .auto_shrink([false, false])
+        .show_rows(ui, row_height, rows.len(), |ui, visible_rows| {
+            for &index in &rows[visible_rows] {
+                let entry = &logs.records[index];
+                let text = RichText::new(entry.to_line()).color(level_color(entry.level));
+                if ui
+                    .selectable_label(logs.selected.contains(&entry.id), text)
+                    .clicked()
+                {
+                    toggled = Some(entry.id);
+                }
+            }
+        });
+    if let Some(id) = toggled
+        && !logs.selected.remove(&id)
+    {
+        logs.selected.insert(id);
+    }
+}
+
+/// The log viewer, shown instead of the console list & input.
+#[instrument(level = "trace", skip_all)]
+pub fn render(ui: &mut egui::Ui, logs: &mut LogView) {
+    logs.update();
+    StripBuilder::new(ui)
+        .size(Size::exact(25.0))
+        .size(Size::remainder())
+        .vertical(|mut strip| {
+            strip.cell(|ui| {
+                ui.style_mut().spacing.item_spacing.x = 8.0;
+                render_toolbar(ui, logs);
+            });
+            strip.cell(|ui| {
+                ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Extend);
+                render_records(ui, logs);
+            });
+        });
+}
@@ -26,6 +26,51 @@ fn console_style() -> Style {
     style
 }
 
+/// the console entries, suggestions & the input
+fn render_console(
+    ui: &mut egui::Ui,
+    pipe: &mut UiRenderPipe<UserData>,
+    ui_state: &mut UiState,
+    has_text_selection: &mut bool,
+) {
+    StripBuilder::new(ui)
+        .size(Size::exact(0.0))
+        .size(Size::remainder())
+        .size(Size::exact(50.0))
+        .size(Size::exact(15.0))
+        .size(Size::exact(25.0))
+        .size(Size::exact(0.0))
+        .vertical(|mut strip| {
+            strip.empty();
+            strip.cell(|ui| {
+                ui.style_mut().wrap_mode = None;
+                super::console_list::render(ui, pipe, has_text_selection);
+            });
+
+            let msg = pipe.user_data.msg.clone();
+
+            let cmds = parser::parse(
+                &msg,
+                &entries_to_parser(pipe.user_data.entries),
+                pipe.user_data.cache,
+            );
+
+            strip.cell(|ui| {
+                ui.style_mut().wrap_mode = None;
+                super::suggestions::render(ui, ui_state, pipe, &cmds);
+            });
+            strip.cell(|ui| {
+                ui.style_mut().wrap_mode = None;
+                super::input::render(ui, pipe, ui_state, *has_text_selection, &cmds);
+            });
+            strip.cell(|ui| {
+                ui.style_mut().wrap_mode = None;
+                super::input_err::render(ui, pipe.user_data.msg, &cmds);
+            });
+            strip.empty();
+        });
+}
+
 /// square, fills most of the screen
 pub fn render(
     ui: &mut egui::Ui,
@@ -58,41 +103,35 @@ pub fn render(
                     Vec2::new(width, height),
                 )),
                 |ui| {
+                    let has_logs = pipe.user_data.logs.is_some();
                     StripBuilder::new(ui)
-                        .size(Size::exact(0.0))
+                        .size(Size::exact(if has_logs { 20.0 } else { 0.0 }))
                         .size(Size::remainder())
-                        .size(Size::exact(50.0))
-                        .size(Size::exact(15.0))
-                        .size(Size::exact(25.0))
-                        .size(Size::exact(0.0))
                         .vertical(|mut strip| {
-                            strip.empty();
-                            strip.cell(|ui| {
-                                ui.style_mut().wrap_mode = None;
-                                super::console_list::render(ui, pipe, &mut has_text_selection);
-                            });
-
-                            let msg = pipe.user_data.msg.clone();
-
-                            let cmds = parser::parse(
-                                &msg,
-                                &entries_to_parser(pipe.user_data.entries),
-                                pipe.user_data.cache,
-                            );
-
-                            strip.cell(|ui| {
-                                ui.style_mut().wrap_mode = None;
-                                super::suggestions::render(ui, ui_state, pipe, &cmds);
-                            });
                             strip.cell(|ui| {
-                                ui.style_mut().wrap_mode = None;
-                                super::input::render(ui, pipe, ui_state, has_text_selection, &cmds);
+                                if let Some(logs) = pipe.user_data.logs.as_deref_mut() {
+                                    ui.horizontal(|ui| {
+                                        if ui.selectable_label(!logs.active, "Console").clicked() {
+                                            logs.active = false;
+                                        }
+                                        if ui.selectable_label(logs.active, "Logs").clicked() {
+                                            logs.active = true;
+                                        }
+                                    });
+                                }
                             });
                             strip.cell(|ui| {
-                                ui.style_mut().wrap_mode = None;
-                                super::input_err::render(ui, pipe.user_data.msg, &cmds);
+                                if let Some(logs) = pipe
+                                    .user_data
+                                    .logs
+                                    .as_deref_mut()
+                                    .filter(|logs| logs.active)
+                                {
+                                    super::logs::render(ui, logs);
+                                } else {
+                                    render_console(ui, pipe, ui_state, &mut has_text_selection);
+                                }
                             });
-                            strip.empty();
                         });
                 },
             );
@@ -1,6 +1,7 @@
 pub mod console_list;
 pub mod input;
 pub mod input_err;
+pub mod logs;
 pub mod main_frame;
 pub mod page;
 pub mod suggestions;
@@ -2,7 +2,7 @@ use std::collections::VecDeque;
 
 use client_containers::skins::SkinContainer;
 use client_render_base::render::tee::RenderTee;
-use client_types::console::ConsoleEntry;
+use client_types::{console::ConsoleEntry, log::LogView};
 use command_parser::parser::ParserCache;
 use game_config::config::Config;
 use ui_base::types::UiState;
@@ -28,4 +28,7 @@ pub struct UserData<'a> {
     pub cache: &'a mut ParserCache,
 
     pub can_change_client_config: bool,
+
+    /// The captured logs, if this console can show them.
+    pub logs: Option<&'a mut LogView>,
 }
@@ -2,8 +2,8 @@ use std::time::Duration;
 
 use base::duration_ext::DurationToRaceStr;
 use egui::{
-    Align2, Button, Color32, CornerRadius, FontId, Frame, Grid, Layout, Rect, Shadow, Stroke,
-    TopBottomPanel, Vec2, Window,
+    Align2, Button, Color32, CornerRadius, FontId, Frame, Grid, Layout, ProgressBar, Rect,
+    RichText, ScrollArea, Shadow, Stroke, TopBottomPanel, Vec2, Window,
 };
 
 use tracing::instrument;
@@ -12,13 +12,89 @@ use ui_base::{
     utils::{add_horizontal_margins, text_sized},
 };
 
-use crate::demo_player::user_data::{DemoViewerEvent, DemoViewerEventExport};
+use crate::demo_player::user_data::{
+    DemoCameraMode, DemoEncodeProgress, DemoViewerEvent, DemoViewerEventExport,
+};
 
 use super::user_data::UserData;
 
+fn render_camera_menu(ui: &mut egui::Ui, user_data: &mut UserData) {
+    let mode = *user_data.camera_mode;
+    let mut select = |ui: &mut egui::Ui, selected: DemoCameraMode, text: RichText| {
+        if ui.selectable_label(mode == selected, text).clicked() {
+            user_data.events.push(DemoViewerEvent::CameraMode(selected));
+            ui.close();
+        }
+    };
+    select(ui, DemoCameraMode::Original, RichText::new("Original view"));
+    select(ui, DemoCameraMode::Free, RichText::new("Free camera"));
+    ui.separator();
+    ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+        if user_data.camera_players.is_empty() {
+            ui.weak("No players");
+        }
+        for player in user_data.camera_players.iter() {
+            let mut text = RichText::new(&player.name);
+            // players that left can still be selected,
+            // the camera follows them again once they are back
+            if !player.present {
+                text = text.weak();
+            }
+            select(ui, DemoCameraMode::Follow(player.id), text);
+        }
+    });
+}
+
+fn render_encode_progress(
+    ui: &mut egui::Ui,
+    events: &mut Vec<DemoViewerEvent>,
+    progress: DemoEncodeProgress,
+    ui_state: &mut UiState,
+) {
+    let res = Window::new("Rendering video")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(Align2::CENTER_CENTER, Vec2::ZERO)
+        .show(ui.ctx(), |ui| {
+            ui.add(
+                ProgressBar::new(progress.frames as f32 / progress.total_frames.max(1) as f32)
+                    .desired_width(300.0)
+                    .show_percentage(),
+            );
+            ui.label(format!(
+                "Frame {} of {}",
+                progress.frames, progress.total_frames
+            ));
+            ui.label(match progress.eta {
+                Some(eta) => format!("Remaining: {}", eta.to_race_string()),
+                None => "Remaining: calculating...".to_string(),
+            });
+            if ui.button("Cancel").clicked() {
+                events.push(DemoViewerEvent::CancelEncoding);
+            }
+        });
+    if let Some(res) = res {
+        ui_state.add_blur_rect(res.response.rect, 5.0);
+    }
+}
+
 /// not required
 #[instrument(level = "trace", skip_all)]
 pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &mut UiState) {
+    // the free camera is moved by dragging the view
+    if let DemoCameraMode::Free = pipe.user_data.camera_mode
+        && !pipe.user_data.state.pointer_on_timeline
+        && !ui.ctx().is_pointer_over_area()
+        && let Some(delta) = ui.input(|i| {
+            (i.pointer.primary_down() && i.pointer.delta() != Vec2::ZERO)
+                .then_some(i.pointer.delta())
+        })
+    {
+        pipe.user_data
+            .events
+            .push(DemoViewerEvent::FreeCameraMove { delta });
+    }
+
     let res = TopBottomPanel::bottom("demo-main")
         .exact_height(40.0)
         .frame(
@@ -254,6 +330,14 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
                             pipe.user_data.events.push(DemoViewerEvent::SpeedFaster);
                         }
 
+                        ui.add_space(15.0);
+
+                        ui.menu_button(text_sized("\u{f030}", FONT_SIZE), |ui| {
+                            render_camera_menu(ui, pipe.user_data)
+                        })
+                        .response
+                        .on_hover_text("Camera");
+
                         ui.add_space(15.0);
                         ui.colored_label(Color32::WHITE, pipe.user_data.name);
                     });
@@ -343,4 +427,8 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
             });
         });
     ui_state.add_blur_rect(res.response.rect, 0.0);
+
+    if let Some(progress) = pipe.user_data.encode_progress {
+        render_encode_progress(ui, pipe.user_data.events, progress, ui_state);
+    }
 }
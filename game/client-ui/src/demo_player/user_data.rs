@@ -1,6 +1,7 @@
 use std::time::Duration;
 
-use egui::Rect;
+use egui::{Rect, Vec2};
+use game_interface::types::id_types::CharacterId;
 use graphics::handles::{
     canvas::canvas::GraphicsCanvasHandle, stream::stream::GraphicsStreamHandle,
 };
@@ -14,6 +15,37 @@ pub struct DemoViewerEventExport {
     pub remove_chat: bool,
 }
 
+/// What the camera of the demo player looks at.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DemoCameraMode {
+    /// The view of the player that recorded the demo.
+    #[default]
+    Original,
+    /// Follows any character of the demo.
+    Follow(CharacterId),
+    /// Moves freely like a spectator, by dragging the view.
+    Free,
+}
+
+/// A character that appeared in the demo so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DemoCameraPlayer {
+    pub id: CharacterId,
+    pub name: String,
+    /// `false` if the character is not part of the current snapshot,
+    /// e.g. because the player left.
+    pub present: bool,
+}
+
+/// The progress of encoding a demo to a video.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DemoEncodeProgress {
+    pub frames: u64,
+    pub total_frames: u64,
+    /// The estimated real time until the video is finished.
+    pub eta: Option<Duration>,
+}
+
 #[derive(Debug, Clone)]
 pub enum DemoViewerEvent {
     ResumeToggle,
@@ -34,8 +66,22 @@ pub enum DemoViewerEvent {
 
     Export(DemoViewerEventExport),
 
-    SkipTo { time: Duration },
-    PreviewAt { rect: Rect, time: Duration },
+    SkipTo {
+        time: Duration,
+    },
+    PreviewAt {
+        rect: Rect,
+        time: Duration,
+    },
+
+    CameraMode(DemoCameraMode),
+    /// Moves the free camera by the given amount of ui points.
+    FreeCameraMove {
+        delta: Vec2,
+    },
+
+    /// Stops encoding the demo to a video.
+    CancelEncoding,
 
     Close,
 }
@@ -58,6 +104,10 @@ pub struct UserData<'a> {
     pub max_duration: &'a Duration,
     pub speed: &'a ffixed,
     pub name: &'a str,
+    pub camera_mode: &'a DemoCameraMode,
+    pub camera_players: &'a [DemoCameraPlayer],
+    /// `Some` if the demo is encoded to a video.
+    pub encode_progress: Option<DemoEncodeProgress>,
 
     pub events: &'a mut Vec<DemoViewerEvent>,
 
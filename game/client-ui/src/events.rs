@@ -28,6 +28,8 @@ pub enum UiEvent {
         rcon_secret: Option<[u8; 32]>,
         can_start_internal_server: bool,
         can_connect_internal_server: bool,
+        /// Join the spectators right after connecting.
+        as_spectator: bool,
     },
     Disconnect,
     ConnectLocalPlayer {
@@ -68,6 +70,27 @@ pub enum UiEvent {
     },
     /// `None` = disconnect
     PasswordEntered(Option<String>),
+    /// The user decided whether to download the
+    /// files of an untrusted server.
+    DownloadsAccepted {
+        accepted: bool,
+        remember: bool,
+    },
+    /// The user decided whether to trust the
+    /// changed certificate of a server.
+    CertChangeAccepted {
+        accepted: bool,
+    },
+    /// Writes the favorites & connect history
+    /// to a shareable file in `server_lists/`.
+    ExportServerList {
+        name: String,
+    },
+    /// Merges the favorites & connect history
+    /// of a file in `server_lists/`.
+    ImportServerList {
+        name: String,
+    },
 }
 
 #[hiarc_safer_rc_refcell]
@@ -2,8 +2,8 @@ use std::{borrow::Borrow, time::Duration};
 
 use base::duration_ext::DurationToRaceStr;
 use egui::{
-    Align2, Color32, CornerRadius, FontId, Frame, Layout, Margin, Rect, RichText, UiBuilder, Vec2,
-    Window,
+    Align2, Color32, CornerRadius, FontId, Frame, Grid, Layout, Margin, Rect, RichText, UiBuilder,
+    Vec2, Window,
 };
 
 use egui_extras::{Size, StripBuilder};
@@ -27,7 +27,9 @@ use ui_base::{
 
 use crate::utils::{render_tee_for_ui, render_texture_for_ui};
 
-use super::user_data::UserData;
+use super::user_data::{
+    RACE_FINISH_SHOW_TIME, RACE_SPLIT_SHOW_TIME, RenderRaceSplit, RenderRaceSplits, UserData,
+};
 
 /// not required
 #[instrument(level = "trace", skip_all)]
@@ -48,11 +50,27 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
             GameRenderInfo::Match {
                 round_time_type,
                 unbalanced,
+                balance_suggestion,
+                balance_at_round_end,
                 ..
             } => {
                 let balance_msg = unbalanced.then(|| {
+                    let names: Vec<_> = balance_suggestion
+                        .iter()
+                        .filter_map(|id| pipe.user_data.character_infos.get(id))
+                        .map(|c| c.info.name.as_str())
+                        .collect();
                     (
-                        "Please balance the teams!".to_string(),
+                        if names.is_empty() {
+                            "Please balance the teams!".to_string()
+                        } else if *balance_at_round_end {
+                            format!("{} will be moved at round end", names.join(", "))
+                        } else {
+                            format!(
+                                "Please balance the teams, or {} will be moved",
+                                names.join(", ")
+                            )
+                        },
                         if (pipe.cur_time.subsec_millis()) < 500 {
                             Color32::LIGHT_YELLOW
                         } else {
@@ -83,6 +101,45 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
                         )
                     }
                     MatchRoundTimeType::Normal => (time_str, Color32::WHITE, balance_msg, None),
+                    MatchRoundTimeType::Warmup {
+                        ticks_left,
+                        ready_players,
+                        total_players,
+                    } => {
+                        let secs = ticks_left / pipe.user_data.ticks_per_second.get();
+                        (
+                            format!(
+                                "Warmup {} - {}/{} ready",
+                                Duration::from_secs(secs).to_race_string(),
+                                ready_players,
+                                total_players
+                            ),
+                            Color32::LIGHT_BLUE,
+                            balance_msg,
+                            None,
+                        )
+                    }
+                    MatchRoundTimeType::Overtime {
+                        ticks_left,
+                        overtime,
+                    } => {
+                        let secs = ticks_left / pipe.user_data.ticks_per_second.get();
+                        let time = Duration::from_secs(secs).to_race_string();
+                        (
+                            if *overtime > 1 {
+                                format!("Overtime {overtime} - {time}")
+                            } else {
+                                format!("Overtime - {time}")
+                            },
+                            if secs < 10 {
+                                Color32::LIGHT_RED
+                            } else {
+                                Color32::WHITE
+                            },
+                            balance_msg,
+                            None,
+                        )
+                    }
                     MatchRoundTimeType::SuddenDeath => (
                         "Sudden Death".to_string(),
                         Color32::WHITE,
@@ -567,6 +624,15 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
             render_side(pipe, ui, ui_state, Side::Bottom(rect));
         });
 
+    if let Some(race_splits) = pipe.user_data.race_splits {
+        let offset_y = res
+            .as_ref()
+            .map(|r| r.response.rect.height())
+            .unwrap_or_default()
+            + 10.0;
+        render_race_splits(ui, ui_state, race_splits, offset_y);
+    }
+
     if let Some((balance_msg, color)) = balance_msg {
         ui.scope_builder(
             UiBuilder::default().max_rect(
@@ -590,3 +656,98 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
         );
     }
 }
+
+/// Faster than the best time is green, slower is red.
+fn race_delta(time: &Duration, best: &Option<Duration>) -> (String, Color32) {
+    match best {
+        Some(best) => (
+            time.to_race_delta_string(best),
+            match time.cmp(best) {
+                std::cmp::Ordering::Less => Color32::GREEN,
+                std::cmp::Ordering::Equal => Color32::WHITE,
+                std::cmp::Ordering::Greater => Color32::RED,
+            },
+        ),
+        None => (time.to_race_string(), Color32::WHITE),
+    }
+}
+
+fn render_race_splits(
+    ui: &mut egui::Ui,
+    ui_state: &mut UiState,
+    race_splits: &RenderRaceSplits,
+    offset_y: f32,
+) {
+    // fades out during the last second
+    let alpha = |age: &Duration, show_time: Duration| {
+        show_time.saturating_sub(*age).as_secs_f32().clamp(0.0, 1.0)
+    };
+    let alpha = if let Some((_, age)) = &race_splits.finish {
+        alpha(age, RACE_FINISH_SHOW_TIME)
+    } else if let Some((_, age)) = &race_splits.last {
+        alpha(age, RACE_SPLIT_SHOW_TIME)
+    } else {
+        return;
+    };
+
+    let res = Window::new("race_splits_overlay")
+        .order(egui::Order::Tooltip)
+        .interactable(false)
+        .title_bar(false)
+        .resizable(false)
+        .frame(
+            Frame::new()
+                .fill(Color32::from_black_alpha(50).gamma_multiply(alpha))
+                .inner_margin(5)
+                .corner_radius(5),
+        )
+        .anchor(Align2::CENTER_TOP, Vec2::new(0.0, offset_y))
+        .show(ui.ctx(), |ui| {
+            if let Some((finish, _)) = &race_splits.finish {
+                Grid::new("race_splits_summary")
+                    .num_columns(3)
+                    .spacing([10.0, 2.0])
+                    .show(ui, |ui| {
+                        for RenderRaceSplit { index, time, best } in finish.splits.iter() {
+                            ui.label(
+                                RichText::new(format!("CP {index}"))
+                                    .color(Color32::LIGHT_GRAY.gamma_multiply(alpha)),
+                            );
+                            ui.label(
+                                RichText::new(time.to_race_string())
+                                    .color(Color32::WHITE.gamma_multiply(alpha)),
+                            );
+                            let (delta, color) = race_delta(time, best);
+                            if best.is_some() {
+                                ui.label(RichText::new(delta).color(color.gamma_multiply(alpha)));
+                            }
+                            ui.end_row();
+                        }
+                        ui.label(
+                            RichText::new("Finish")
+                                .color(Color32::LIGHT_GRAY.gamma_multiply(alpha)),
+                        );
+                        ui.label(
+                            RichText::new(finish.time.to_race_string())
+                                .color(Color32::WHITE.gamma_multiply(alpha)),
+                        );
+                        let (delta, color) = race_delta(&finish.time, &finish.best);
+                        if finish.best.is_some() {
+                            ui.label(RichText::new(delta).color(color.gamma_multiply(alpha)));
+                        }
+                        ui.end_row();
+                    });
+            } else if let Some((split, _)) = &race_splits.last {
+                let (text, color) = race_delta(&split.time, &split.best);
+                ui.label(
+                    RichText::new(text)
+                        .font(FontId::proportional(18.0))
+                        .color(color.gamma_multiply(alpha)),
+                );
+            }
+        });
+
+    if let Some(res) = res {
+        ui_state.add_blur_rect(res.response.rect, 5.0);
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use base::linked_hash_map_view::FxLinkedHashMap;
 use client_containers::{ctf::CtfContainer, skins::SkinContainer};
 use client_render_base::render::tee::RenderTee;
@@ -18,6 +20,36 @@ pub struct RenderDateTime {
     pub date: PoolString,
 }
 
+/// How long the difference to the best split is shown.
+pub const RACE_SPLIT_SHOW_TIME: Duration = Duration::from_secs(3);
+/// How long the summary of a finished run is shown.
+pub const RACE_FINISH_SHOW_TIME: Duration = Duration::from_secs(5);
+
+/// The time of the local player at a race checkpoint.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RenderRaceSplit {
+    pub index: u8,
+    pub time: Duration,
+    /// The best time at this checkpoint before the current run.
+    pub best: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderRaceFinish {
+    pub splits: Vec<RenderRaceSplit>,
+    pub time: Duration,
+    /// The best finish time before the current run.
+    pub best: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RenderRaceSplits {
+    /// The last reached checkpoint & how long ago it was reached.
+    pub last: Option<(RenderRaceSplit, Duration)>,
+    /// The summary of the finished run & how long ago the run finished.
+    pub finish: Option<(RenderRaceFinish, Duration)>,
+}
+
 pub struct UserData<'a> {
     pub canvas_handle: &'a GraphicsCanvasHandle,
     pub stream_handle: &'a GraphicsStreamHandle,
@@ -33,4 +65,5 @@ pub struct UserData<'a> {
     pub character_infos: &'a FxLinkedHashMap<CharacterId, CharacterInfo>,
 
     pub date_time: &'a Option<RenderDateTime>,
+    pub race_splits: &'a Option<RenderRaceSplits>,
 }
@@ -48,14 +48,21 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
                         btn("Misc");
                     });
 
-                    match current_active.as_str() {
+                    let current_active = current_active.clone();
+
+                    // only one vote can run at a time
+                    let vote_active = pipe.user_data.votes.is_vote_active();
+                    if vote_active {
+                        ui.label("A vote is already running.");
+                    }
+                    ui.add_enabled_ui(!vote_active, |ui| match current_active.as_str() {
                         "Map" => super::map::render(ui, pipe, ui_state),
                         "Player" => super::players::render(ui, pipe),
                         // Misc
                         _ => {
                             super::misc::render(ui, pipe);
                         }
-                    }
+                    });
                 },
             );
         });
@@ -22,8 +22,11 @@ use ui_base::{
 };
 
 use crate::{
-    events::UiEvent, ingame_menu::user_data::UserData, sort::sortable_header,
-    time_display::TimeDisplay, utils::render_texture_for_ui,
+    events::UiEvent,
+    ingame_menu::{user_data::UserData, votes::matches_search},
+    sort::sortable_header,
+    time_display::TimeDisplay,
+    utils::render_texture_for_ui,
 };
 
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
@@ -592,10 +595,10 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
                                                     .iter()
                                                     .enumerate()
                                                     .filter(|(_, (key, _))| {
-                                                        key.name
-                                                            .as_str()
-                                                            .to_lowercase()
-                                                            .contains(&map_search.to_lowercase())
+                                                        matches_search(
+                                                            key.name.as_str(),
+                                                            &map_search,
+                                                        )
                                                     })
                                                     .collect();
                                                 render_table(
@@ -15,7 +15,11 @@ use ui_base::{
     utils::{add_margins, get_margin},
 };
 
-use crate::{events::UiEvent, ingame_menu::user_data::UserData, sort::sortable_header};
+use crate::{
+    events::UiEvent,
+    ingame_menu::{user_data::UserData, votes::matches_search},
+    sort::sortable_header,
+};
 
 const MISC_VOTE_DIR_STORAGE_NAME: &str = "misc-vote-sort-dir";
 
@@ -189,10 +193,10 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
                                             .iter()
                                             .enumerate()
                                             .filter(|(_, (key, _))| {
-                                                key.display_name
-                                                    .as_str()
-                                                    .to_lowercase()
-                                                    .contains(&misc_search.to_lowercase())
+                                                matches_search(
+                                                    key.display_name.as_str(),
+                                                    &misc_search,
+                                                )
                                             })
                                             .collect();
                                         render_table(ui, &misc_infos, index, config);
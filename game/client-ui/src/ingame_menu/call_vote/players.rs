@@ -1,6 +1,8 @@
-use egui::{Frame, Sense, Shadow};
+use egui::{Button, Color32, Frame, Sense, Shadow};
 use egui_extras::{Column, Size, StripBuilder, TableBuilder};
-use game_interface::votes::{MAX_VOTE_REASON_LEN, PlayerVoteKey};
+use game_interface::votes::{
+    MAX_VOTE_REASON_LEN, PlayerVoteKey, VoteReasonError, validate_vote_reason,
+};
 use ui_base::{
     components::clearable_edit_field::clearable_edit_field,
     style::bg_frame_color,
@@ -12,26 +14,24 @@ use crate::{events::UiEvent, ingame_menu::user_data::UserData};
 
 pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
     pipe.user_data.server_players.request_player_infos();
-    let server_players: Vec<_> = pipe
-        .user_data
-        .server_players
-        .collect()
-        .into_iter()
-        .collect();
+    pipe.user_data.votes.request_player_votes();
+    let server_players = pipe.user_data.server_players.collect();
+    let rules = pipe.user_data.votes.collect_player_votes();
 
     const VOTE_PLAYER_INDEX: &str = "vote-player-index";
-    let mut index_entry = pipe
-        .user_data
-        .browser_menu
-        .config
-        .engine
-        .ui
-        .path
-        .query
+    const VOTE_PLAYER_SEARCH: &str = "vote-player-search";
+    let query = &mut pipe.user_data.browser_menu.config.engine.ui.path.query;
+    let mut index_entry = query
         .entry(VOTE_PLAYER_INDEX.to_string())
         .or_default()
         .clone();
     let index: usize = index_entry.parse().unwrap_or_default();
+    let mut player_search = query
+        .entry(VOTE_PLAYER_SEARCH.to_string())
+        .or_default()
+        .clone();
+
+    let candidates = rules.candidates(&server_players, &player_search);
 
     Frame::default()
         .fill(bg_frame_color())
@@ -42,6 +42,7 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
                 StripBuilder::new(ui)
                     .size(Size::remainder())
                     .size(Size::exact(20.0))
+                    .size(Size::exact(20.0))
                     .vertical(|mut strip| {
                         strip.cell(|ui| {
                             ui.style_mut().wrap_mode = None;
@@ -62,9 +63,9 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
                                         });
                                     })
                                     .body(|body| {
-                                        body.rows(25.0, server_players.len(), |mut row| {
+                                        body.rows(25.0, candidates.len(), |mut row| {
                                             row.set_selected(index == row.index());
-                                            let (_, char) = &server_players[row.index()];
+                                            let (_, char) = &candidates[row.index()];
                                             row.col(|ui| {
                                                 ui.label(char.name.as_str());
                                             });
@@ -75,6 +76,14 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
                                     });
                             });
                         });
+                        strip.cell(|ui| {
+                            ui.style_mut().wrap_mode = None;
+                            ui.horizontal_centered(|ui| {
+                                // Search
+                                ui.label("\u{1f50d}");
+                                clearable_edit_field(ui, &mut player_search, Some(200.0), None);
+                            });
+                        });
                         strip.cell(|ui| {
                             ui.style_mut().wrap_mode = None;
                             ui.horizontal(|ui| {
@@ -89,47 +98,67 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
                                     .entry("player-vote-reason-str".to_string())
                                     .or_default();
 
-                                ui.label("Reason:");
+                                ui.label(if rules.reason_required {
+                                    "Reason (required):"
+                                } else {
+                                    "Reason:"
+                                });
                                 clearable_edit_field(
                                     ui,
                                     reason,
                                     Some(100.0),
                                     Some(MAX_VOTE_REASON_LEN),
                                 );
-                                let reason = reason.to_string();
+                                let reason = validate_vote_reason(reason, rules.reason_required);
+                                let selected = candidates.get(index).map(|(id, _)| **id);
 
-                                if ui.button("Kick").clicked()
-                                    && let Some((id, _)) = server_players.get(index)
+                                let can_vote = selected.is_some() && reason.is_ok();
+                                if ui.add_enabled(can_vote, Button::new("Kick")).clicked()
+                                    && let Some(id) = selected
+                                    && let Ok(reason) = &reason
                                 {
                                     pipe.user_data.browser_menu.events.push(
                                         UiEvent::VoteKickPlayer(PlayerVoteKey {
-                                            voted_player_id: *id,
-                                            reason: reason.as_str().try_into().unwrap(),
+                                            voted_player_id: id,
+                                            reason: reason.clone(),
                                         }),
                                     );
                                 }
-                                if ui.button("Move to spec").clicked()
-                                    && let Some((id, _)) = server_players.get(index)
+                                if ui
+                                    .add_enabled(can_vote, Button::new("Move to spec"))
+                                    .clicked()
+                                    && let Some(id) = selected
+                                    && let Ok(reason) = &reason
                                 {
                                     pipe.user_data.browser_menu.events.push(
                                         UiEvent::VoteSpecPlayer(PlayerVoteKey {
-                                            voted_player_id: *id,
-                                            reason: reason.try_into().unwrap(),
+                                            voted_player_id: id,
+                                            reason: reason.clone(),
                                         }),
                                     );
                                 }
+                                match reason {
+                                    Err(VoteReasonError::Missing) => {
+                                        ui.colored_label(
+                                            Color32::LIGHT_RED,
+                                            "This server requires a reason.",
+                                        );
+                                    }
+                                    Err(VoteReasonError::TooLong) => {
+                                        ui.colored_label(
+                                            Color32::LIGHT_RED,
+                                            "The reason is too long.",
+                                        );
+                                    }
+                                    Ok(_) => {}
+                                }
                             });
                         });
                     });
             });
         });
 
-    pipe.user_data
-        .browser_menu
-        .config
-        .engine
-        .ui
-        .path
-        .query
-        .insert(VOTE_PLAYER_INDEX.to_string(), index_entry);
+    let query = &mut pipe.user_data.browser_menu.config.engine.ui.path.query;
+    query.insert(VOTE_PLAYER_INDEX.to_string(), index_entry);
+    query.insert(VOTE_PLAYER_SEARCH.to_string(), player_search);
 }
@@ -1,13 +1,48 @@
 use std::collections::BTreeSet;
 
-use game_interface::types::render::character::{PlayerCameraMode, PlayerIngameMode};
+use game_interface::types::render::{
+    character::{PlayerCameraMode, PlayerIngameMode},
+    game::game_match::MatchSide,
+};
 use hiarc::{Hiarc, hiarc_safer_rc_refcell};
 
+#[derive(Debug, Hiarc, Clone)]
+pub struct SideInfo {
+    pub name: String,
+    pub players: usize,
+    /// `None` if nobody plays on this side.
+    pub average_score: Option<f64>,
+}
+
+impl SideInfo {
+    pub fn new(name: String, scores: impl IntoIterator<Item = i64>) -> Self {
+        let (players, total) = scores.into_iter().fold((0, 0), |(players, total), score| {
+            (players + 1, total + score)
+        });
+        Self {
+            name,
+            players,
+            average_score: (players > 0).then(|| total as f64 / players as f64),
+        }
+    }
+}
+
+/// The sides of the stage the local player is in.
+#[derive(Debug, Hiarc, Clone)]
+pub struct SidesInfo {
+    pub red: SideInfo,
+    pub blue: SideInfo,
+    /// `None` if the local player spectates.
+    pub own_side: Option<MatchSide>,
+}
+
 #[derive(Debug, Hiarc, Clone)]
 pub struct ActiveClientInfo {
     pub ingame_mode: PlayerIngameMode,
     pub stage_names: BTreeSet<String>,
     pub camera_mode: PlayerCameraMode,
+    /// Only for games with red & blue sides.
+    pub sides: Option<SidesInfo>,
 }
 
 impl Default for ActiveClientInfo {
@@ -16,6 +51,7 @@ impl Default for ActiveClientInfo {
             ingame_mode: PlayerIngameMode::Spectator,
             stage_names: Default::default(),
             camera_mode: PlayerCameraMode::Default,
+            sides: None,
         }
     }
 }
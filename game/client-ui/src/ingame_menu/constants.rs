@@ -1,3 +1,4 @@
 pub const INGAME_MENU_UI_PAGE_QUERY: &str = "game";
 pub const INGAME_MENU_VOTE_QUERY: &str = "vote";
 pub const INGAME_MENU_FALLBACK_QUERY: &str = "Server info";
+pub const INGAME_MENU_SIDE_SELECT_QUERY: &str = "side_select";
@@ -12,7 +12,10 @@ use ui_base::{
     types::{UiRenderPipe, UiState},
 };
 
-use crate::{events::UiEvent, ingame_menu::user_data::UserData};
+use crate::{
+    events::UiEvent,
+    ingame_menu::{constants::INGAME_MENU_SIDE_SELECT_QUERY, user_data::UserData},
+};
 
 #[instrument(level = "trace", skip_all)]
 pub fn render(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPipe<UserData>) {
@@ -113,21 +116,11 @@ pub fn render(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPipe
                         });
                     }
 
-                    if options.use_vanilla_sides {
-                        ui.menu_button("Pick side", |ui| {
-                            if ui.button("Red side").clicked() {
-                                pipe.user_data
-                                    .browser_menu
-                                    .events
-                                    .push(UiEvent::JoinVanillaSide { is_red_side: true });
-                            }
-                            if ui.button("Blue side").clicked() {
-                                pipe.user_data
-                                    .browser_menu
-                                    .events
-                                    .push(UiEvent::JoinVanillaSide { is_red_side: false });
-                            }
-                        });
+                    if options.use_vanilla_sides && ui.button("Pick side").clicked() {
+                        config.path().add_query((
+                            INGAME_MENU_SIDE_SELECT_QUERY.to_string(),
+                            "1".to_string(),
+                        ));
                     }
                 });
 
@@ -250,6 +243,7 @@ pub fn render(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPipe
                                     rcon_secret: config.storage("rcon-secret"),
                                     can_start_internal_server: true,
                                     can_connect_internal_server: true,
+                                    as_spectator: false,
                                 });
                             }
                             if ui
@@ -386,4 +380,16 @@ pub fn render(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPipe
                 );
             });
         });
+
+    let show_side_select = pipe
+        .user_data
+        .browser_menu
+        .config
+        .path()
+        .query
+        .get(INGAME_MENU_SIDE_SELECT_QUERY)
+        .is_some_and(|v| v == "1");
+    if show_side_select && let Some(sides) = &active_client_info.sides {
+        super::side_select::render(ui, ui_state, pipe, sides, options.balanced_sides);
+    }
 }
@@ -1 +1,2 @@
 pub mod main_frame;
+pub mod side_select;
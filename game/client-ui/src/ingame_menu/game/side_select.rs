@@ -0,0 +1,155 @@
+use egui::{Align2, Button, Color32, RichText, Sense, Vec2, Window};
+use game_interface::{
+    client_commands::can_join_side,
+    types::{
+        character_info::NetworkSkinInfo,
+        render::{character::TeeEye, game::game_match::MatchSide},
+        resource_key::ResourceKey,
+    },
+};
+use math::math::vector::{ubvec4, vec2};
+use ui_base::types::{UiRenderPipe, UiState};
+
+use crate::{
+    events::UiEvent,
+    ingame_menu::{
+        client_info::{SideInfo, SidesInfo},
+        constants::INGAME_MENU_SIDE_SELECT_QUERY,
+        user_data::UserData,
+    },
+    utils::render_tee_for_ui,
+};
+
+/// The tee colors of the sides.
+pub fn side_color(side: MatchSide) -> ubvec4 {
+    match side {
+        MatchSide::Red => ubvec4::new(255, 107, 107, 255),
+        MatchSide::Blue => ubvec4::new(107, 159, 255, 255),
+    }
+}
+
+fn render_side(
+    ui: &mut egui::Ui,
+    ui_state: &mut UiState,
+    pipe: &mut UiRenderPipe<UserData>,
+    sides: &SidesInfo,
+    side: MatchSide,
+    balanced_sides: bool,
+) -> bool {
+    let (info, other): (&SideInfo, &SideInfo) = match side {
+        MatchSide::Red => (&sides.red, &sides.blue),
+        MatchSide::Blue => (&sides.blue, &sides.red),
+    };
+    let (red, blue) = match side {
+        MatchSide::Red => (info.players, other.players),
+        MatchSide::Blue => (other.players, info.players),
+    };
+    let color = side_color(side);
+    let mut joined = false;
+
+    ui.vertical(|ui| {
+        let joinable = can_join_side(side, sides.own_side, red, blue, balanced_sides);
+        let res = ui.add_enabled(
+            joinable.is_ok(),
+            Button::new(RichText::new(&info.name).color(Color32::BLACK))
+                .fill(Color32::from_rgb(color.x, color.y, color.z))
+                .min_size(egui::vec2(150.0, 30.0)),
+        );
+        match joinable {
+            Ok(()) => {
+                if res.clicked() {
+                    pipe.user_data
+                        .browser_menu
+                        .events
+                        .push(UiEvent::JoinVanillaSide {
+                            is_red_side: side == MatchSide::Red,
+                        });
+                    joined = true;
+                }
+            }
+            Err(err) => {
+                res.on_disabled_hover_text(err.to_string());
+                ui.label(RichText::new(err.to_string()).weak());
+            }
+        }
+
+        ui.label(format!("Players: {}", info.players));
+        ui.label(match info.average_score {
+            Some(score) => format!("Average score: {score:.1}"),
+            None => "Average score: -".to_string(),
+        });
+
+        // preview of the own tee in the side's colors
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(150.0, 64.0), Sense::hover());
+        let browser_menu = &mut pipe.user_data.browser_menu;
+        let skin = browser_menu
+            .config
+            .game
+            .players
+            .get(browser_menu.config.game.profiles.main as usize)
+            .map(|player| player.skin.name.to_string())
+            .unwrap_or_default();
+        render_tee_for_ui(
+            browser_menu.canvas_handle,
+            browser_menu.skin_container,
+            browser_menu.render_tee,
+            ui,
+            ui_state,
+            ui.ctx().screen_rect(),
+            Some(ui.clip_rect()),
+            &ResourceKey::from_str_lossy(&skin),
+            Some(&NetworkSkinInfo::Custom {
+                body_color: color,
+                feet_color: color,
+            }),
+            vec2::new(rect.center().x, rect.center().y),
+            64.0,
+            TeeEye::Normal,
+        );
+    });
+
+    joined
+}
+
+/// Overlay to pick a side, shown when joining a server with
+/// red & blue sides or when picking a side in the menu.
+pub fn render(
+    ui: &mut egui::Ui,
+    ui_state: &mut UiState,
+    pipe: &mut UiRenderPipe<UserData>,
+    sides: &SidesInfo,
+    balanced_sides: bool,
+) {
+    let mut open = true;
+    let mut close = false;
+    Window::new("Pick a side")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(Align2::CENTER_CENTER, Vec2::ZERO)
+        .show(ui.ctx(), |ui| {
+            ui.horizontal(|ui| {
+                for side in [MatchSide::Red, MatchSide::Blue] {
+                    close |= render_side(ui, ui_state, pipe, sides, side, balanced_sides);
+                }
+            });
+            if sides.own_side.is_some() {
+                ui.separator();
+                if ui.button("Spectate").clicked() {
+                    pipe.user_data
+                        .browser_menu
+                        .events
+                        .push(UiEvent::JoinSpectators);
+                    close = true;
+                }
+            }
+        });
+    if !open || close {
+        pipe.user_data
+            .browser_menu
+            .config
+            .path()
+            .query
+            .remove(INGAME_MENU_SIDE_SELECT_QUERY);
+    }
+}
@@ -114,9 +114,11 @@ fn render_content(
                                     browser_data: pipe.user_data.browser_menu.browser_data,
                                     ddnet_info: pipe.user_data.browser_menu.ddnet_info,
                                     icons: pipe.user_data.browser_menu.icons,
+                                    map_thumbnails: pipe.user_data.browser_menu.map_thumbnails,
 
                                     demos: pipe.user_data.browser_menu.demos,
                                     demo_info: pipe.user_data.browser_menu.demo_info,
+                                    local_stats: pipe.user_data.browser_menu.local_stats,
                                     server_info: pipe.user_data.browser_menu.server_info,
                                     render_options: pipe.user_data.browser_menu.render_options,
                                     main_menu: pipe.user_data.browser_menu.main_menu,
@@ -202,7 +204,7 @@ pub fn render<'a>(
     pipe: &'a mut UiRenderPipe<'a, UserData<'a>>,
     ui_state: &mut UiState,
 ) {
-    if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+    if ui_state.focus.take_back() {
         ui_state.is_ui_open = false;
     }
 
@@ -117,10 +117,14 @@ impl IngameMenuUi {
         }
     }
 
-    pub fn update(&mut self, cur_time: &Duration) {
-        self.main_menu.update(cur_time);
+    pub fn update(&mut self, cur_time: &Duration, item_memory_warn_mib: u64) {
+        self.main_menu.update(cur_time, item_memory_warn_mib);
 
-        MainMenuUi::update_container(&mut self.map_vote_thumbnail_container, cur_time);
+        MainMenuUi::update_container(
+            &mut self.map_vote_thumbnail_container,
+            cur_time,
+            item_memory_warn_mib,
+        );
     }
 }
 
@@ -142,7 +146,10 @@ impl UiPageInterface<Config> for IngameMenuUi {
             ui_state,
         );
 
-        self.update(&pipe.cur_time);
+        self.update(
+            &pipe.cur_time,
+            pipe.user_data.game.dbg.container_item_warn_mib,
+        );
     }
 
     fn unmount(&mut self) {
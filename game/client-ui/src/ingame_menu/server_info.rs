@@ -3,9 +3,41 @@ pub mod main_frame;
 use game_interface::interface::GameStateServerOptions;
 use hiarc::{Hiarc, hiarc_safer_rc_refcell};
 
+/// How the identity of the connected server was verified.
+#[derive(Debug, Hiarc, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionSecurity {
+    /// The certificate was not checked.
+    #[default]
+    Unverified,
+    /// A local server, or the certificate was known in advance.
+    Verified,
+    /// First connect to this server, the certificate was pinned.
+    FirstUse,
+    /// The certificate matches the pinned one.
+    Pinned,
+}
+
+impl ConnectionSecurity {
+    pub fn is_secure(&self) -> bool {
+        !matches!(self, Self::Unverified)
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Unverified => "The identity of the server was not verified.",
+            Self::Verified => "The identity of the server is verified.",
+            Self::FirstUse => {
+                "First connect to this server, its certificate is remembered from now on."
+            }
+            Self::Pinned => "The certificate matches the one from the last connect.",
+        }
+    }
+}
+
 #[derive(Debug, Hiarc, Default, Clone)]
 pub struct GameInfo {
     pub map_name: String,
+    pub security: ConnectionSecurity,
 }
 
 #[hiarc_safer_rc_refcell]
@@ -19,6 +19,9 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
             Grid::new("server-info-grid").num_columns(2).show(ui, |ui| {
                 ui.label("Map:");
                 ui.label(&game_info.map_name);
+                ui.end_row();
+                ui.label("Connection:");
+                ui.label(game_info.security.description());
             });
         });
     ui_state.add_blur_rect(res.response.rect, 5.0);
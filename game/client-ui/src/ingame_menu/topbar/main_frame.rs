@@ -1,4 +1,4 @@
-use egui::Frame;
+use egui::{Color32, Frame, RichText};
 
 use tracing::instrument;
 use ui_base::{
@@ -115,6 +115,14 @@ pub fn render(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPipe
                             "Call vote".to_string(),
                         ));
                 }
+                let security = pipe.user_data.game_server_info.game_info().security;
+                let (icon, color) = if security.is_secure() {
+                    ("\u{f023}", Color32::from_rgb(100, 200, 100))
+                } else {
+                    ("\u{f09c}", Color32::from_rgb(255, 150, 50))
+                };
+                ui.label(RichText::new(icon).color(color))
+                    .on_hover_text(security.description());
                 render_right_buttons(
                     ui,
                     pipe.user_data.browser_menu.events,
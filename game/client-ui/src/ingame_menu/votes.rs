@@ -1,11 +1,48 @@
 use std::collections::BTreeMap;
 
-use base::network_string::NetworkString;
-use game_interface::votes::{MAX_CATEGORY_NAME_LEN, MapVote, MapVoteKey, MiscVote, MiscVoteKey};
+use base::{linked_hash_map_view::FxLinkedHashMap, network_string::NetworkString};
+use game_interface::{
+    types::{character_info::NetworkCharacterInfo, id_types::PlayerId},
+    votes::{MAX_CATEGORY_NAME_LEN, MapVote, MapVoteKey, MiscVote, MiscVoteKey},
+};
 use hiarc::{Hiarc, hiarc_safer_rc_refcell};
 use tracing::instrument;
 use url::Url;
 
+/// Whether the name of a vote list entry matches the search, ignoring case.
+pub fn matches_search(name: &str, search: &str) -> bool {
+    name.to_lowercase().contains(&search.to_lowercase())
+}
+
+/// The rules of the server for kick & spectator votes.
+#[derive(Debug, Hiarc, Default, Clone)]
+pub struct PlayerVoteRules {
+    /// The players of this client.
+    pub local_players: Vec<PlayerId>,
+    /// Players the server does not allow to be voted.
+    pub immune_players: Vec<PlayerId>,
+    /// Whether a vote must have a reason.
+    pub reason_required: bool,
+}
+
+impl PlayerVoteRules {
+    /// The players that can be voted and match the search.
+    pub fn candidates<'a>(
+        &self,
+        players: &'a FxLinkedHashMap<PlayerId, NetworkCharacterInfo>,
+        search: &str,
+    ) -> Vec<(&'a PlayerId, &'a NetworkCharacterInfo)> {
+        players
+            .iter()
+            .filter(|(id, info)| {
+                !self.local_players.contains(id)
+                    && !self.immune_players.contains(id)
+                    && matches_search(info.name.as_str(), search)
+            })
+            .collect()
+    }
+}
+
 #[hiarc_safer_rc_refcell]
 #[derive(Debug, Hiarc, Default)]
 pub struct Votes {
@@ -16,6 +53,11 @@ pub struct Votes {
 
     misc_votes: BTreeMap<NetworkString<MAX_CATEGORY_NAME_LEN>, BTreeMap<MiscVoteKey, MiscVote>>,
     need_misc_votes: bool,
+
+    player_votes: PlayerVoteRules,
+    need_player_votes: bool,
+
+    vote_active: bool,
 }
 
 #[hiarc_safer_rc_refcell]
@@ -91,4 +133,105 @@ impl Votes {
     ) -> BTreeMap<NetworkString<MAX_CATEGORY_NAME_LEN>, BTreeMap<MiscVoteKey, MiscVote>> {
         self.misc_votes.clone()
     }
+
+    #[instrument(level = "trace", skip_all)]
+    pub fn request_player_votes(&mut self) {
+        self.need_player_votes = true;
+    }
+
+    /// Automatically resets the "need" state, so
+    /// another [`Votes::request_player_votes`] has to
+    /// be called.
+    #[instrument(level = "trace", skip_all)]
+    pub fn needs_player_votes(&mut self) -> bool {
+        std::mem::replace(&mut self.need_player_votes, false)
+    }
+
+    #[instrument(level = "trace", skip_all)]
+    pub fn fill_player_votes(
+        &mut self,
+        local_players: Vec<PlayerId>,
+        immune_players: Vec<PlayerId>,
+        reason_required: bool,
+    ) {
+        self.player_votes = PlayerVoteRules {
+            local_players,
+            immune_players,
+            reason_required,
+        };
+    }
+
+    #[instrument(level = "trace", skip_all)]
+    pub fn collect_player_votes(&self) -> PlayerVoteRules {
+        self.player_votes.clone()
+    }
+
+    /// Whether a vote is currently running on the server.
+    #[instrument(level = "trace", skip_all)]
+    pub fn set_vote_active(&mut self, vote_active: bool) {
+        self.vote_active = vote_active;
+    }
+
+    #[instrument(level = "trace", skip_all)]
+    pub fn is_vote_active(&self) -> bool {
+        self.vote_active
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use base::linked_hash_map_view::FxLinkedHashMap;
+    use game_interface::types::{
+        character_info::NetworkCharacterInfo, id_gen::IdGenerator, id_types::PlayerId,
+    };
+
+    use super::{PlayerVoteRules, matches_search};
+
+    fn player(name: &str) -> NetworkCharacterInfo {
+        let mut info = NetworkCharacterInfo::explicit_default();
+        info.name = name.try_into().unwrap();
+        info
+    }
+
+    fn names(candidates: Vec<(&PlayerId, &NetworkCharacterInfo)>) -> Vec<String> {
+        candidates
+            .into_iter()
+            .map(|(_, info)| info.name.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn search() {
+        assert!(matches_search("ctf5", ""));
+        assert!(matches_search("ctf5", "CTF"));
+        assert!(matches_search("Restart round", "art r"));
+        assert!(!matches_search("ctf5", "dm1"));
+    }
+
+    #[test]
+    fn player_candidates() {
+        let id_gen = IdGenerator::new();
+        let ids: Vec<PlayerId> = (0..4).map(|_| id_gen.next_id()).collect();
+        let mut players: FxLinkedHashMap<PlayerId, NetworkCharacterInfo> = Default::default();
+        players.insert(ids[0], player("me"));
+        players.insert(ids[1], player("my dummy"));
+        players.insert(ids[2], player("Admin"));
+        players.insert(ids[3], player("nameless tee"));
+
+        let rules = PlayerVoteRules {
+            local_players: vec![ids[0], ids[1]],
+            immune_players: vec![ids[2]],
+            reason_required: false,
+        };
+        assert_eq!(names(rules.candidates(&players, "")), ["nameless tee"]);
+        assert_eq!(names(rules.candidates(&players, "TEE")), ["nameless tee"]);
+        assert!(rules.candidates(&players, "admin").is_empty());
+        assert!(rules.candidates(&players, "my").is_empty());
+
+        let rules = PlayerVoteRules::default();
+        assert_eq!(
+            names(rules.candidates(&players, "m")),
+            ["me", "my dummy", "Admin", "nameless tee"]
+        );
+    }
 }
@@ -2,6 +2,7 @@
 #![allow(clippy::module_inception)]
 
 pub mod actionfeed;
+pub mod broadcast;
 pub mod chat;
 pub mod connect;
 pub mod console;
@@ -11,6 +12,7 @@ pub mod events;
 pub mod hud;
 pub mod ingame_menu;
 pub mod main_menu;
+pub mod markup;
 pub mod motd;
 pub mod scoreboard;
 pub mod sort;
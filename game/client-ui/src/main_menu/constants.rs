@@ -4,10 +4,12 @@ pub const MENU_DEMO_NAME: &str = "\u{e131}";
 pub const MENU_SETTINGS_NAME: &str = "\u{f013}";
 pub const MENU_QUIT_NAME: &str = "\u{f011}";
 pub const MENU_PROFILE_NAME: &str = "\u{f007}";
+pub const MENU_STATS_NAME: &str = "\u{f080}";
 
 pub const MENU_INTERNET_NAME: &str = "\u{f0ac}";
 pub const MENU_LAN_NAME: &str = "\u{f6ff}";
 pub const MENU_FAVORITES_NAME: &str = "\u{f005}";
+pub const MENU_HISTORY_NAME: &str = "\u{f1da}";
 pub const MENU_EXPLORE_COMMUNITIES_NAME: &str = "\u{e595}";
 
 pub const MENU_COMMUNITY_PREFIX: &str = "internal::community_";
@@ -2,16 +2,43 @@ use std::net::SocketAddr;
 
 use egui::{Button, Color32};
 
+use game_base::browser_server_list::ServerAddr;
 use ui_base::{style::default_style, types::UiRenderPipe};
 
 use crate::{events::UiEvent, main_menu::user_data::UserData};
 
+/// Connects to the selected server.
+///
+/// Legacy servers can't be joined as spectator.
+pub fn connect(pipe: &mut UiRenderPipe<UserData>, addr: SocketAddr, as_spectator: bool) {
+    let is_legacy_server: bool = pipe.user_data.config.storage("server-is-legacy");
+    if is_legacy_server {
+        pipe.user_data.events.push(UiEvent::ConnectLegacy {
+            addr,
+            can_show_warning: true,
+        });
+    } else {
+        pipe.user_data.events.push(UiEvent::Connect {
+            addr,
+            cert_hash: pipe.user_data.config.storage("server-cert"),
+            rcon_secret: pipe.user_data.config.storage("rcon-secret"),
+            can_start_internal_server: pipe.user_data.config.storage("server-is-internal"),
+            can_connect_internal_server: pipe.user_data.config.storage("server-is-internal"),
+            as_spectator,
+        });
+    }
+}
+
 /// connect & refresh button
 pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
     ui.horizontal(|ui| {
         ui.set_clip_rect(ui.ctx().screen_rect());
         let server_addr_str = pipe.user_data.config.storage::<String>("server-addr");
-        let server_addr: Result<SocketAddr, _> = server_addr_str.parse();
+        let server_addr: Result<ServerAddr, _> = server_addr_str.parse();
+        let resolve_err = pipe
+            .user_data
+            .config
+            .storage::<String>("server-addr-resolve-err");
 
         let mut button_style = default_style();
         let btn_color = if server_addr.is_ok() {
@@ -32,6 +59,9 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
         if (ui
             .add(Button::new("\u{f2f6}"))
             .on_hover_text(match &server_addr {
+                Ok(addr) if !resolve_err.is_empty() => {
+                    format!("canno't connect to {addr}: {resolve_err}")
+                }
                 Ok(addr) => format!("connect to {addr}"),
                 Err(err) => format!("canno't connect to {server_addr_str}: {err}"),
             })
@@ -39,23 +69,17 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
             || enter_clicked)
             && let Ok(addr) = server_addr
         {
-            let is_legacy_server: bool = pipe.user_data.config.storage("server-is-legacy");
-            if is_legacy_server {
-                pipe.user_data.events.push(UiEvent::ConnectLegacy {
-                    addr,
-                    can_show_warning: true,
-                });
-            } else {
-                pipe.user_data.events.push(UiEvent::Connect {
-                    addr,
-                    cert_hash: pipe.user_data.config.storage("server-cert"),
-                    rcon_secret: pipe.user_data.config.storage("rcon-secret"),
-                    can_start_internal_server: pipe.user_data.config.storage("server-is-internal"),
-                    can_connect_internal_server: pipe
-                        .user_data
+            // host names are resolved only when connecting
+            match addr.resolve() {
+                Ok(addr) => {
+                    pipe.user_data.config.rem_storage("server-addr-resolve-err");
+                    connect(pipe, addr, false);
+                }
+                Err(err) => {
+                    pipe.user_data
                         .config
-                        .storage("server-is-internal"),
-                });
+                        .set_storage("server-addr-resolve-err", &err.to_string());
+                }
             }
         }
     });
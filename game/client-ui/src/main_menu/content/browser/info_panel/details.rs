@@ -0,0 +1,192 @@
+use std::net::SocketAddr;
+
+use client_containers::container::ContainerKey;
+use egui::{Button, Color32, Grid, RichText};
+use game_base::server_browser::ServerBrowserServer;
+use math::math::vector::vec2;
+use ui_base::types::{UiRenderPipe, UiState};
+
+use crate::{
+    main_menu::{content::browser::connect_refresh::connect, user_data::UserData},
+    utils::render_texture_for_ui,
+};
+
+/// Height of the map thumbnail, if the thumbnail was cached.
+pub const MAP_THUMBNAIL_HEIGHT: f32 = 100.0;
+
+fn map_thumbnail_key(server: &ServerBrowserServer) -> Option<ContainerKey> {
+    (!server.info.map.name.is_empty()).then(|| ContainerKey {
+        name: server.info.map.name.clone().into(),
+        hash: None,
+    })
+}
+
+/// Whether the thumbnail of the server's map is cached locally.
+///
+/// Starts loading the thumbnail otherwise.
+pub fn has_map_thumbnail(pipe: &mut UiRenderPipe<UserData>, server: &ServerBrowserServer) -> bool {
+    map_thumbnail_key(server).is_some_and(|key| {
+        pipe.user_data.map_thumbnails.get_or_default(&key);
+        pipe.user_data.map_thumbnails.contains_key(&key)
+    })
+}
+
+fn render_map_thumbnail(
+    ui: &mut egui::Ui,
+    pipe: &mut UiRenderPipe<UserData>,
+    ui_state: &mut UiState,
+    server: &ServerBrowserServer,
+) {
+    let Some(key) = map_thumbnail_key(server) else {
+        return;
+    };
+    let thumbnail = pipe.user_data.map_thumbnails.get_or_default(&key);
+    let mut rect = ui.available_rect_before_wrap();
+    rect.set_height(MAP_THUMBNAIL_HEIGHT);
+    ui.add_space(rect.height());
+
+    let width = thumbnail.width as f32;
+    let height = thumbnail.height as f32;
+    let scale = (rect.width() / width).min(rect.height() / height).min(1.0);
+    let center = rect.center();
+    render_texture_for_ui(
+        pipe.user_data.stream_handle,
+        pipe.user_data.canvas_handle,
+        &thumbnail.thumbnail,
+        ui,
+        ui_state,
+        ui.ctx().screen_rect(),
+        Some(ui.clip_rect()),
+        vec2::new(center.x, center.y),
+        vec2::new(width * scale, height * scale),
+        None,
+    );
+}
+
+fn render_info(ui: &mut egui::Ui, pipe: &UiRenderPipe<UserData>, server: &ServerBrowserServer) {
+    let community = pipe
+        .user_data
+        .ddnet_info
+        .communities
+        .values()
+        .find(|community| {
+            server
+                .addresses
+                .iter()
+                .any(|addr| community.servers.contains(addr))
+        });
+    let row = |ui: &mut egui::Ui, name: &str, value: &str| {
+        ui.label(RichText::new(name).size(10.0));
+        ui.label(RichText::new(value).size(10.0));
+        ui.end_row();
+    };
+    Grid::new("server-details-short")
+        .num_columns(2)
+        .show(ui, |ui| {
+            row(ui, "Version:", server.info.version.as_str());
+            row(ui, "Game type:", server.info.game_type.as_str());
+            row(ui, "Map:", server.info.map.name.as_str());
+            let players = server.info.players.len();
+            row(
+                ui,
+                "Players:",
+                &match server.info.max_ingame_players {
+                    // e.g. the internal server
+                    u32::MAX => players.to_string(),
+                    max_players => format!("{players}/{max_players}"),
+                },
+            );
+            row(ui, "Location:", server.location.as_str());
+            if let Some(community) = community {
+                row(ui, "Community:", &community.name);
+            }
+        });
+}
+
+fn render_buttons(
+    ui: &mut egui::Ui,
+    pipe: &mut UiRenderPipe<UserData>,
+    server: &ServerBrowserServer,
+) {
+    let addr_str = pipe.user_data.config.storage::<String>("server-addr");
+    let Ok(addr) = addr_str.parse::<SocketAddr>() else {
+        return;
+    };
+    ui.horizontal(|ui| {
+        if ui
+            .button("\u{f2f6}")
+            .on_hover_text("Join the server")
+            .clicked()
+        {
+            connect(pipe, addr, false);
+        }
+        if ui
+            .add_enabled(!server.legacy_server, Button::new("\u{f06e}"))
+            .on_hover_text("Join the server as spectator")
+            .on_disabled_hover_text("Legacy servers can't be joined as spectator")
+            .clicked()
+        {
+            connect(pipe, addr, true);
+        }
+
+        let favorites = &mut pipe.user_data.config.game.menu.favorite_servers;
+        let is_favorite = favorites.contains(&addr_str);
+        let favorite = ui
+            .button(RichText::new("\u{f005}").color(if is_favorite {
+                Color32::GOLD
+            } else {
+                Color32::GRAY
+            }))
+            .on_hover_text(if is_favorite {
+                "Remove from favorites"
+            } else {
+                "Add to favorites"
+            });
+        if favorite.clicked() {
+            if is_favorite {
+                favorites.retain(|favorite| *favorite != addr_str);
+            } else {
+                favorites.push(addr_str.clone());
+            }
+            pipe.user_data.browser_data.clear_filter_cache();
+        }
+
+        if ui
+            .button("\u{f0c5}")
+            .on_hover_text("Copy the address")
+            .clicked()
+        {
+            ui.ctx().copy_text(addr_str.clone());
+        }
+
+        let can_refresh = !server.legacy_server
+            && pipe
+                .user_data
+                .main_menu
+                .can_refresh_server_info(&pipe.cur_time);
+        if ui
+            .add_enabled(can_refresh, Button::new("\u{f2f9}"))
+            .on_hover_text("Refresh the server's info")
+            .clicked()
+        {
+            pipe.user_data
+                .main_menu
+                .refresh_server_info(addr, &pipe.cur_time);
+        }
+    });
+}
+
+/// Info, map thumbnail & actions of the selected server.
+pub fn render(
+    ui: &mut egui::Ui,
+    pipe: &mut UiRenderPipe<UserData>,
+    ui_state: &mut UiState,
+    server: &ServerBrowserServer,
+    has_map_thumbnail: bool,
+) {
+    render_info(ui, pipe, server);
+    if has_map_thumbnail {
+        render_map_thumbnail(ui, pipe, ui_state, server);
+    }
+    render_buttons(ui, pipe, server);
+}
@@ -1,4 +1,4 @@
-use egui::{Frame, Grid, Layout, Rect, RichText};
+use egui::{Frame, Layout, Rect};
 use egui_extras::{Size, StripBuilder};
 use game_base::server_browser::ServerBrowserServer;
 
@@ -7,16 +7,20 @@ use ui_base::{
     types::{UiRenderPipe, UiState},
 };
 
-use super::player_list::list::entry::EntryData;
+use crate::main_menu::user_data::UserData;
+
+use super::{details::MAP_THUMBNAIL_HEIGHT, player_list::list::entry::EntryData};
 
 /// big box, rounded edges
 pub fn render(
     ui: &mut egui::Ui,
     full_rect: &Rect,
-    pipe: &mut UiRenderPipe<EntryData>,
+    pipe: &mut UiRenderPipe<UserData>,
     ui_state: &mut UiState,
     cur_server: Option<&ServerBrowserServer>,
 ) {
+    let has_map_thumbnail =
+        cur_server.is_some_and(|server| super::details::has_map_thumbnail(pipe, server));
     let res = Frame::default()
         .fill(bg_frame_color())
         .corner_radius(5.0)
@@ -30,7 +34,12 @@ pub fn render(
                     strip.empty();
                     strip.cell(|ui| {
                         ui.style_mut().wrap_mode = None;
-                        let server_details_height = 70.0;
+                        // header, info & buttons
+                        let mut server_details_height =
+                            if cur_server.is_some() { 180.0 } else { 70.0 };
+                        if has_map_thumbnail {
+                            server_details_height += MAP_THUMBNAIL_HEIGHT;
+                        }
                         StripBuilder::new(ui)
                             .size(Size::exact(0.0))
                             .size(Size::exact(server_details_height))
@@ -59,38 +68,13 @@ pub fn render(
                                             strip.cell(|ui| {
                                                 ui.style_mut().wrap_mode = None;
                                                 if let Some(cur_server) = cur_server {
-                                                    Grid::new("server-details-short")
-                                                        .num_columns(2)
-                                                        .show(ui, |ui| {
-                                                            ui.label(
-                                                                RichText::new("Version:")
-                                                                    .size(10.0),
-                                                            );
-                                                            ui.label(
-                                                                RichText::new(
-                                                                    cur_server
-                                                                        .info
-                                                                        .version
-                                                                        .as_str(),
-                                                                )
-                                                                .size(10.0),
-                                                            );
-                                                            ui.end_row();
-                                                            ui.label(
-                                                                RichText::new("Game type:")
-                                                                    .size(10.0),
-                                                            );
-                                                            ui.label(
-                                                                RichText::new(
-                                                                    cur_server
-                                                                        .info
-                                                                        .game_type
-                                                                        .as_str(),
-                                                                )
-                                                                .size(10.0),
-                                                            );
-                                                            ui.end_row();
-                                                        });
+                                                    super::details::render(
+                                                        ui,
+                                                        pipe,
+                                                        ui_state,
+                                                        cur_server,
+                                                        has_map_thumbnail,
+                                                    );
                                                 } else {
                                                     ui.label("No server selected");
                                                 }
@@ -101,7 +85,20 @@ pub fn render(
                                     ui.style_mut().wrap_mode = None;
                                     if let Some(cur_server) = cur_server {
                                         super::player_list::table::render(
-                                            ui, full_rect, pipe, ui_state, cur_server,
+                                            ui,
+                                            full_rect,
+                                            &mut UiRenderPipe {
+                                                cur_time: pipe.cur_time,
+                                                user_data: &mut EntryData {
+                                                    stream_handle: pipe.user_data.stream_handle,
+                                                    canvas_handle: pipe.user_data.canvas_handle,
+                                                    skin_container: pipe.user_data.skin_container,
+                                                    render_tee: pipe.user_data.render_tee,
+                                                    flags_container: pipe.user_data.flags_container,
+                                                },
+                                            },
+                                            ui_state,
+                                            cur_server,
                                         );
                                     }
                                 });
@@ -1,2 +1,3 @@
+pub mod details;
 pub mod main_frame;
 pub mod player_list;
@@ -1,6 +1,42 @@
+use std::net::SocketAddr;
+
+use base::hash::fmt_hash;
 use egui::Button;
 use egui_extras::TableRow;
 use game_base::server_browser::ServerBrowserServer;
+use game_config::config::ConfigGame;
+
+/// Context menu to save the current settings as profile for this server.
+fn profile_menu(
+    ui: &mut egui::Ui,
+    server: &ServerBrowserServer,
+    addr: &SocketAddr,
+    local_server: bool,
+    config: &mut ConfigGame,
+) {
+    let addr = addr.to_string();
+    // the local server has no stable certificate
+    let cert = (!local_server).then(|| fmt_hash(&server.info.cert_sha256_fingerprint));
+    if ui.button("Save current settings for this server").clicked() {
+        let profile = config.current_server_profile();
+        if let Some(cert) = cert.clone() {
+            config.cl.server_profiles_certs.insert(cert, profile);
+        } else {
+            config
+                .cl
+                .server_profiles_addrs
+                .insert(addr.clone(), profile);
+        }
+    }
+    if config.server_profile(&addr, cert.as_deref()).is_some()
+        && ui.button("Remove settings for this server").clicked()
+    {
+        if let Some(cert) = &cert {
+            config.cl.server_profiles_certs.remove(cert);
+        }
+        config.cl.server_profiles_addrs.remove(&addr);
+    }
+}
 
 /// Single server list entry
 ///
@@ -8,7 +44,9 @@ use game_base::server_browser::ServerBrowserServer;
 pub fn render(
     mut row: TableRow<'_, '_>,
     server: &ServerBrowserServer,
+    addr: &SocketAddr,
     local_server: bool,
+    config: &mut ConfigGame,
 ) -> (bool, bool) {
     let mut clicked_restart = false;
     let mut clicked = false;
@@ -31,7 +69,9 @@ pub fn render(
         .clicked();
     clicked |= row
         .col(|ui| {
-            clicked |= ui.label(server.info.name.as_str()).clicked();
+            let name = ui.label(server.info.name.as_str());
+            clicked |= name.clicked();
+            name.context_menu(|ui| profile_menu(ui, server, addr, local_server, config));
         })
         .1
         .clicked();
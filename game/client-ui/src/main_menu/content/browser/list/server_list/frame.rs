@@ -9,7 +9,7 @@ use game_base::{
     },
 };
 
-use ui_base::types::UiRenderPipe;
+use ui_base::{focus::register_list, types::UiRenderPipe};
 
 use crate::{
     events::UiEvent,
@@ -19,6 +19,14 @@ use crate::{
     },
 };
 
+fn get_addr(addresses: &[SocketAddr]) -> &SocketAddr {
+    // generally prefer ipv4
+    addresses
+        .iter()
+        .find(|addr| addr.is_ipv4())
+        .unwrap_or(&addresses[0])
+}
+
 /// server list frame (scrollable)
 pub fn render(mut body: TableBody<'_>, pipe: &mut UiRenderPipe<UserData>, cur_page: &str) {
     let ddnet_info = &pipe.user_data.ddnet_info;
@@ -83,7 +91,7 @@ pub fn render(mut body: TableBody<'_>, pipe: &mut UiRenderPipe<UserData>, cur_pa
                 (None, None, None, None, true)
             }
         };
-    let lan_server = [LanServer {
+    let mut lan_server = vec![LanServer {
         server: ServerBrowserServer {
             info: {
                 let mut info = server_browser_info.unwrap_or_else(|| ServerBrowserInfo {
@@ -122,32 +130,47 @@ pub fn render(mut body: TableBody<'_>, pipe: &mut UiRenderPipe<UserData>, cur_pa
         rcon_secret,
         is_internal_server: true,
     }];
+    // the servers found in the local network, except for the internal server
+    lan_server.extend(
+        pipe.user_data
+            .browser_data
+            .lan_servers()
+            .iter()
+            .filter(|server| Some(server.info.cert_sha256_fingerprint) != server_cert_hash)
+            .map(|server| LanServer {
+                server: server.clone(),
+                rcon_secret: None,
+                is_internal_server: false,
+            }),
+    );
 
     if cur_page == MENU_LAN_NAME {
         pipe.user_data.events.push(UiEvent::CheckLocalServer);
     }
 
-    let select_prev = body
-        .ui_mut()
-        .ctx()
-        .input(|i| i.key_pressed(egui::Key::ArrowUp))
-        && body.ui_mut().ctx().memory(|m| m.focused().is_none());
-    let select_next = body
-        .ui_mut()
-        .ctx()
-        .input(|i| i.key_pressed(egui::Key::ArrowDown))
-        && body.ui_mut().ctx().memory(|m| m.focused().is_none());
-    let select_first = body
-        .ui_mut()
-        .ctx()
-        .input(|i| i.key_pressed(egui::Key::PageUp))
-        && body.ui_mut().ctx().memory(|m| m.focused().is_none());
-    let select_last = body
-        .ui_mut()
-        .ctx()
-        .input(|i| i.key_pressed(egui::Key::PageDown))
-        && body.ui_mut().ctx().memory(|m| m.focused().is_none());
-
+    let list_servers: Vec<&ServerBrowserServer> = if cur_page != MENU_LAN_NAME {
+        servers.iter().collect()
+    } else {
+        lan_server.iter().map(|s| &s.server).collect()
+    };
+    let list_rect = body.ui_mut().clip_rect();
+    let nav = register_list(body.ui_mut(), egui::Id::new("server-list"), list_rect);
+    let cur_addr = pipe.user_data.config.storage::<String>("server-addr");
+    // the selected server might not be visible, so select it here
+    if let Some(server) = nav
+        .next_selection(
+            list_servers
+                .iter()
+                .position(|server| get_addr(&server.addresses).to_string() == cur_addr),
+            list_servers.iter().map(|server| server.info.name.as_str()),
+            (list_rect.height() / 30.0) as usize,
+        )
+        .and_then(|index| list_servers.get(index))
+    {
+        pipe.user_data
+            .config
+            .set_storage("server-addr", get_addr(&server.addresses));
+    }
     let cur_addr = pipe.user_data.config.storage::<String>("server-addr");
 
     body.rows(
@@ -169,48 +192,19 @@ pub fn render(mut body: TableBody<'_>, pipe: &mut UiRenderPipe<UserData>, cur_pa
                 )
             };
 
-            let select_index = if select_prev {
-                Some(row_index + 1)
-            } else if select_next {
-                Some(row_index.saturating_sub(1))
-            } else if select_first {
-                Some(0)
-            } else if select_last {
-                Some(if cur_page != MENU_LAN_NAME {
-                    servers.len().saturating_sub(1)
-                } else {
-                    lan_server.len().saturating_sub(1)
-                })
-            } else {
-                None
-            };
-
-            fn get_addr(addresses: &[SocketAddr]) -> &SocketAddr {
-                // generally prefer ipv4
-                addresses
-                    .iter()
-                    .find(|addr| addr.is_ipv4())
-                    .unwrap_or(&addresses[0])
-            }
             let server_addr = get_addr(&server.addresses);
             let is_selected = server_addr.to_string() == cur_addr;
             row.set_selected(is_selected);
-            let (clicked, restart_clicked) =
-                super::entry::render(row, server, cur_page == MENU_LAN_NAME);
-            let clicked = clicked
-                || (cur_page == MENU_LAN_NAME && lan_server.len() == 1)
-                || select_index
-                    .and_then(|index| {
-                        if cur_page != MENU_LAN_NAME {
-                            servers.get(index)
-                        } else {
-                            lan_server.get(index).map(|s| &s.server)
-                        }
-                    })
-                    .is_some_and(|s| get_addr(&s.addresses).to_string() == cur_addr);
+            let (clicked, restart_clicked) = super::entry::render(
+                row,
+                server,
+                server_addr,
+                is_internal_server,
+                &mut pipe.user_data.config.game,
+            );
+            let clicked = clicked || (cur_page == MENU_LAN_NAME && lan_server.len() == 1);
 
             if clicked || is_selected {
-                // extra check here, bcs the server addr might be changed by keyboard
                 if clicked {
                     pipe.user_data
                         .config
@@ -218,7 +212,7 @@ pub fn render(mut body: TableBody<'_>, pipe: &mut UiRenderPipe<UserData>, cur_pa
                 }
                 pipe.user_data.config.set_storage(
                     "server-cert",
-                    &if cur_page != MENU_LAN_NAME {
+                    &if !is_internal_server {
                         Some(server.info.cert_sha256_fingerprint)
                     } else {
                         server_cert_hash
@@ -8,8 +8,6 @@ use ui_base::{
 
 use crate::main_menu::user_data::UserData;
 
-use super::info_panel::player_list::list::entry::EntryData;
-
 /// big box, rounded edges
 pub fn render(
     ui: &mut egui::Ui,
@@ -79,16 +77,7 @@ pub fn render(
                             super::info_panel::main_frame::render(
                                 ui,
                                 &ui.ctx().screen_rect().clone(),
-                                &mut UiRenderPipe {
-                                    cur_time: pipe.cur_time,
-                                    user_data: &mut EntryData {
-                                        stream_handle: pipe.user_data.stream_handle,
-                                        canvas_handle: pipe.user_data.canvas_handle,
-                                        skin_container: pipe.user_data.skin_container,
-                                        render_tee: pipe.user_data.render_tee,
-                                        flags_container: pipe.user_data.flags_container,
-                                    },
-                                },
+                                pipe,
                                 ui_state,
                                 server.as_ref(),
                             );
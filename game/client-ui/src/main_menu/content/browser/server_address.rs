@@ -15,5 +15,6 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
         pipe.user_data
             .config
             .set_storage("server-addr", &cur_address);
+        pipe.user_data.config.rem_storage("server-addr-resolve-err");
     }
 }
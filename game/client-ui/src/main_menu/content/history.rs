@@ -0,0 +1,156 @@
+use base::hash::decode_hash;
+use egui::{Button, Frame, Grid, ScrollArea};
+use game_base::browser_server_list::{ConnectHistory, ServerAddr, ServerListEntry};
+use ui_base::{
+    components::clearable_edit_field::clearable_edit_field,
+    style::bg_frame_color,
+    types::{UiRenderPipe, UiState},
+};
+
+use crate::{
+    events::UiEvent,
+    main_menu::{content::browser::connect_refresh::connect, user_data::UserData},
+    time_display::TimeDisplay,
+};
+
+/// Selects the server, like clicking it in the server browser.
+fn select(pipe: &mut UiRenderPipe<UserData>, entry: &ServerListEntry) {
+    let config = &mut *pipe.user_data.config;
+    config.set_storage("server-addr", &entry.addr);
+    config.rem_storage("server-addr-resolve-err");
+    config.set_storage(
+        "server-cert",
+        &entry.fingerprint.as_deref().and_then(decode_hash),
+    );
+    config.set_storage("server-is-internal", &false);
+    config.set_storage(
+        "server-is-legacy",
+        &pipe
+            .user_data
+            .browser_data
+            .find_str(&entry.addr)
+            .is_some_and(|server| server.legacy_server),
+    );
+}
+
+fn render_history(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
+    let history = pipe
+        .user_data
+        .config
+        .storage::<ConnectHistory>("connect-history");
+    if history.is_empty() {
+        ui.label("Servers you connected to are listed here.");
+        return;
+    }
+    let selected = pipe.user_data.config.storage::<String>("server-addr");
+    Grid::new("connect-history")
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            for entry in &history {
+                let name = pipe
+                    .user_data
+                    .browser_data
+                    .find_str(&entry.addr)
+                    .map(|server| server.info.name.to_string());
+                let res = ui.selectable_label(
+                    selected == entry.addr,
+                    match &name {
+                        Some(name) => format!("{name} ({})", entry.addr),
+                        None => entry.addr.clone(),
+                    },
+                );
+                if res.clicked() {
+                    select(pipe, entry);
+                }
+                ui.label(
+                    entry
+                        .last_connect
+                        .and_then(|secs| chrono::DateTime::from_timestamp(secs as i64, 0))
+                        .map(|time| time.to_local_time_string(false))
+                        .unwrap_or_default(),
+                );
+                let addr = entry.addr.parse::<ServerAddr>();
+                if ui
+                    .add_enabled(addr.is_ok(), Button::new("\u{f2f6}"))
+                    .on_hover_text("Join the server")
+                    .clicked()
+                    || res.double_clicked()
+                {
+                    select(pipe, entry);
+                    match addr.and_then(|addr| addr.resolve()) {
+                        Ok(addr) => connect(pipe, addr, false),
+                        Err(err) => {
+                            pipe.user_data
+                                .config
+                                .set_storage("server-addr-resolve-err", &err.to_string());
+                        }
+                    }
+                }
+                ui.end_row();
+            }
+        });
+}
+
+fn render_exchange(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
+    ui.horizontal(|ui| {
+        ui.label("File:");
+        let mut name = pipe
+            .user_data
+            .config
+            .storage_opt::<String>("server-list-file")
+            .unwrap_or_else(|| "servers".to_string());
+        if clearable_edit_field(ui, &mut name, Some(150.0), None)
+            .map(|res| res.changed())
+            .unwrap_or_default()
+        {
+            pipe.user_data.config.set_storage("server-list-file", &name);
+        }
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if ui
+            .add_enabled(valid, Button::new("Export"))
+            .on_hover_text(format!(
+                "Write the favorites & history to server_lists/{name}.json"
+            ))
+            .clicked()
+        {
+            pipe.user_data
+                .events
+                .push(UiEvent::ExportServerList { name: name.clone() });
+        }
+        if ui
+            .add_enabled(valid, Button::new("Import"))
+            .on_hover_text(format!(
+                "Add the favorites & history of server_lists/{name}.json"
+            ))
+            .clicked()
+        {
+            pipe.user_data
+                .events
+                .push(UiEvent::ImportServerList { name });
+        }
+    });
+}
+
+/// The servers the user connected to last & the exchange
+/// of favorites & history through files.
+pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &mut UiState) {
+    let rect = Frame::default()
+        .fill(bg_frame_color())
+        .corner_radius(5.0)
+        .inner_margin(5.0)
+        .show(ui, |ui| {
+            ui.style_mut().wrap_mode = None;
+            ScrollArea::vertical()
+                .max_height((ui.available_height() - 40.0).max(0.0))
+                .show(ui, |ui| {
+                    render_history(ui, pipe);
+                });
+            ui.separator();
+            render_exchange(ui, pipe);
+        });
+    ui_state.add_blur_rect(rect.response.rect, 5.0);
+}
@@ -4,7 +4,7 @@ use ui_base::types::{UiRenderPipe, UiState};
 use crate::main_menu::{
     constants::{
         MENU_COMMUNITY_PREFIX, MENU_EXPLORE_COMMUNITIES_NAME, MENU_FAVORITES_NAME,
-        MENU_INTERNET_NAME, MENU_LAN_NAME,
+        MENU_HISTORY_NAME, MENU_INTERNET_NAME, MENU_LAN_NAME,
     },
     user_data::UserData,
 };
@@ -24,6 +24,8 @@ pub fn render(
         || cur_page.starts_with(MENU_COMMUNITY_PREFIX)
     {
         super::browser::main_frame::render(ui, pipe, ui_state, cur_page);
+    } else if cur_page == MENU_HISTORY_NAME {
+        super::history::render(ui, pipe, ui_state);
     } else if cur_page == MENU_EXPLORE_COMMUNITIES_NAME {
         super::super::communities::main_frame::render(ui, pipe, ui_state);
     }
@@ -1,2 +1,3 @@
 pub mod browser;
+pub mod history;
 pub mod main_frame;
@@ -4,7 +4,7 @@ use egui_extras::TableBody;
 use game_config::config::Config;
 
 use game_base::server_browser::{SortDir, TableSort};
-use ui_base::types::UiRenderPipe;
+use ui_base::{focus::register_list, types::UiRenderPipe};
 
 use crate::{
     events::UiEvent,
@@ -76,46 +76,27 @@ pub fn render(mut body: TableBody<'_>, pipe: &mut UiRenderPipe<UserData>) {
         demos_filtered.insert(0, &back);
     }
 
-    let select_prev = body
-        .ui_mut()
-        .ctx()
-        .input(|i| i.key_pressed(egui::Key::ArrowUp))
-        && body.ui_mut().ctx().memory(|m| m.focused().is_none());
-    let select_next = body
-        .ui_mut()
-        .ctx()
-        .input(|i| i.key_pressed(egui::Key::ArrowDown))
-        && body.ui_mut().ctx().memory(|m| m.focused().is_none());
-    let select_first = body
-        .ui_mut()
-        .ctx()
-        .input(|i| i.key_pressed(egui::Key::PageUp))
-        && body.ui_mut().ctx().memory(|m| m.focused().is_none());
-    let select_last = body
-        .ui_mut()
-        .ctx()
-        .input(|i| i.key_pressed(egui::Key::PageDown))
-        && body.ui_mut().ctx().memory(|m| m.focused().is_none());
-
     let selected_demo: String = pipe.user_data.config.storage::<String>("selected-demo");
+    let list_rect = body.ui_mut().clip_rect();
+    let nav = register_list(body.ui_mut(), egui::Id::new("demo-list"), list_rect);
+    let names: Vec<&str> = demos_filtered
+        .iter()
+        .map(|demo| match demo {
+            DemoListEntry::File { name, .. } => name.as_str(),
+            DemoListEntry::Directory { name } => name.as_str(),
+        })
+        .collect();
+    let select_index = nav.next_selection(
+        names.iter().position(|name| *name == selected_demo),
+        names.iter().copied(),
+        (list_rect.height() / 30.0) as usize,
+    );
 
     body.rows(30.0, demos_filtered.len(), |mut row| {
         let row_index = row.index();
 
         let demo = &demos_filtered[row_index];
 
-        let select_index = if select_prev {
-            Some(row_index + 1)
-        } else if select_next {
-            Some(row_index.saturating_sub(1))
-        } else if select_first {
-            Some(0)
-        } else if select_last {
-            Some(demos_filtered.len().saturating_sub(1))
-        } else {
-            None
-        };
-
         let is_selected = match demo {
             DemoListEntry::File { name, .. } => name,
             DemoListEntry::Directory { name } => name,
@@ -124,19 +105,13 @@ pub fn render(mut body: TableBody<'_>, pipe: &mut UiRenderPipe<UserData>) {
         row.set_selected(is_selected);
         let response = super::entry::render(row, demo);
 
-        let clicked = response.clicked()
-            || select_index
-                .and_then(|index| demos_filtered.get(index).copied())
-                .is_some_and(|s| {
-                    match s {
-                        DemoListEntry::File { name, .. } => name,
-                        DemoListEntry::Directory { name } => name,
-                    }
-                    .eq(&selected_demo)
-                });
+        let selected_by_keyboard = select_index == Some(row_index);
+        if selected_by_keyboard {
+            response.scroll_to_me(None);
+        }
 
         // extra check here, bcs the demo might be changed by keyboard
-        if clicked {
+        if response.clicked() || selected_by_keyboard {
             let (file, is_file) = match demo {
                 DemoListEntry::File { name, .. } => (name, true),
                 DemoListEntry::Directory { name } => (name, false),
@@ -150,7 +125,7 @@ pub fn render(mut body: TableBody<'_>, pipe: &mut UiRenderPipe<UserData>) {
                 pipe.user_data.main_menu.refresh_demo_info(None);
             }
         }
-        if response.double_clicked() {
+        if response.double_clicked() || (nav.activate && is_selected) {
             let cur_path: String = pipe.user_data.config.storage("demo-path");
             let cur_path: PathBuf = cur_path.into();
 
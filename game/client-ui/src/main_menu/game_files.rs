@@ -0,0 +1,150 @@
+use std::{
+    path::Path,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use base_io::{io::Io, path_to_url::relative_path_to_url, runtime::IoRuntimeTask};
+use base_io_traits::{fs_traits::FileSystemInterface, http_traits::HttpClientInterface};
+use game_base::{
+    assets_url::HTTP_RESOURCE_URL,
+    data_manifest::{DataFileMismatch, DataManifest, restore_data_file},
+};
+use url::Url;
+
+#[derive(Debug, Clone, Default)]
+pub enum GameFilesState {
+    #[default]
+    Unchecked,
+    Verifying {
+        checked: usize,
+        total: usize,
+    },
+    Repairing {
+        repaired: usize,
+        total: usize,
+    },
+    /// The files that did not match the manifest
+    /// after the last check or repair.
+    Checked(Vec<DataFileMismatch>),
+    Err(String),
+}
+
+/// Verifies the game files against the manifest of the build
+/// & repairs the files that don't match.
+#[derive(Debug, Default)]
+pub struct GameFilesCheck {
+    task: Option<IoRuntimeTask<Vec<DataFileMismatch>>>,
+    repairing: bool,
+    progress: Arc<AtomicUsize>,
+    total: usize,
+    res: GameFilesState,
+}
+
+impl GameFilesCheck {
+    pub fn verify(&mut self, io: &Io) {
+        if self.task.is_some() {
+            return;
+        }
+        let manifest = DataManifest::bundled();
+        let fs = io.fs.clone();
+        let progress: Arc<AtomicUsize> = Default::default();
+        self.progress = progress.clone();
+        self.total = manifest.entries.len();
+        self.repairing = false;
+        self.task = Some(
+            io.rt
+                .spawn(async move { Ok(manifest.verify(fs.as_ref(), &progress).await) })
+                .cancelable(),
+        );
+    }
+
+    async fn download_data_file(
+        http: &Arc<dyn HttpClientInterface>,
+        fs: &Arc<dyn FileSystemInterface>,
+        mismatch: &DataFileMismatch,
+    ) -> anyhow::Result<()> {
+        let url = Url::parse(HTTP_RESOURCE_URL)?.join(&relative_path_to_url(
+            &Path::new("data").join(&mismatch.path),
+        )?)?;
+        let file = http.download_binary(url, &mismatch.hash).await?;
+        if let Some(dir) = mismatch.path.parent() {
+            fs.create_dir(dir).await?;
+        }
+        fs.write_file(&mismatch.path, file.to_vec()).await?;
+        Ok(())
+    }
+
+    /// Restores the files that have an intact copy in the data directory.
+    /// If `download` is `true`, all other files are downloaded again.
+    pub fn repair(&mut self, io: &Io, download: bool) {
+        let GameFilesState::Checked(mismatches) = &self.res else {
+            return;
+        };
+        if self.task.is_some() {
+            return;
+        }
+        let mismatches = mismatches.clone();
+        let fs = io.fs.clone();
+        let http = io.http.clone();
+        let progress: Arc<AtomicUsize> = Default::default();
+        self.progress = progress.clone();
+        self.total = mismatches
+            .iter()
+            .filter(|mismatch| mismatch.restorable || download)
+            .count();
+        self.repairing = true;
+        self.task = Some(
+            io.rt
+                .spawn(async move {
+                    let mut failed = Vec::new();
+                    for mismatch in mismatches {
+                        let res = if mismatch.restorable {
+                            restore_data_file(fs.as_ref(), &mismatch).await
+                        } else if download {
+                            Self::download_data_file(&http, &fs, &mismatch).await
+                        } else {
+                            failed.push(mismatch);
+                            continue;
+                        };
+                        if let Err(err) = res {
+                            log::warn!("failed to repair {:?}: {err}", mismatch.path);
+                            failed.push(mismatch);
+                        }
+                        progress.fetch_add(1, Ordering::Relaxed);
+                    }
+                    Ok(failed)
+                })
+                .cancelable(),
+        );
+    }
+
+    pub fn check_task(&mut self) {
+        if let Some(task) = &self.task
+            && task.is_finished()
+        {
+            self.res = match self.task.take().unwrap().get() {
+                Ok(mismatches) => GameFilesState::Checked(mismatches),
+                Err(err) => GameFilesState::Err(err.to_string()),
+            };
+        }
+    }
+
+    pub fn state(&self) -> GameFilesState {
+        if self.task.is_none() {
+            self.res.clone()
+        } else if self.repairing {
+            GameFilesState::Repairing {
+                repaired: self.progress.load(Ordering::Relaxed),
+                total: self.total,
+            }
+        } else {
+            GameFilesState::Verifying {
+                checked: self.progress.load(Ordering::Relaxed),
+                total: self.total,
+            }
+        }
+    }
+}
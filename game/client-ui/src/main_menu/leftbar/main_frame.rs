@@ -13,14 +13,15 @@ use graphics::handles::{
 };
 use math::math::vector::vec2;
 use tracing::instrument;
-use ui_base::{style::bg_frame_color, types::UiState};
+use ui_base::{focus::FocusableResponse, style::bg_frame_color, types::UiState};
 
 use crate::{
     main_menu::{
         communities::IconUrlHash,
         constants::{
             MENU_COMMUNITY_PREFIX, MENU_EXPLORE_COMMUNITIES_NAME, MENU_FAVORITES_NAME,
-            MENU_INTERNET_NAME, MENU_LAN_NAME, MENU_PROFILE_NAME, MENU_SETTINGS_NAME,
+            MENU_HISTORY_NAME, MENU_INTERNET_NAME, MENU_LAN_NAME, MENU_PROFILE_NAME,
+            MENU_SETTINGS_NAME,
         },
         user_data::{PROFILE_SKIN_PREVIEW, ProfileSkin, UserData},
     },
@@ -106,6 +107,7 @@ pub fn render(
                         };
                         let clicked = ui
                             .button(RichText::new(text).font(FontId::proportional(18.0)))
+                            .focusable(ui)
                             .clicked();
                         match icon {
                             CustomRender::Icon(Thumbnail {
@@ -246,6 +248,19 @@ pub fn render(
                                 ui_state,
                                 ui_page_query_name,
                             );
+                            round_btn(
+                                ui,
+                                MENU_HISTORY_NAME,
+                                "",
+                                CustomRender::None,
+                                &current_active,
+                                size,
+                                path,
+                                user_data.stream_handle,
+                                user_data.canvas_handle,
+                                ui_state,
+                                ui_page_query_name,
+                            );
 
                             for community in user_data.ddnet_info.communities.values() {
                                 let key = ResourceKey {
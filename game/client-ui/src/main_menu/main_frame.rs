@@ -1,10 +1,14 @@
 use egui_extras::{Size, StripBuilder};
 
 use tracing::instrument;
-use ui_base::types::{UiRenderPipe, UiState};
+use ui_base::{
+    focus::begin_section,
+    types::{UiRenderPipe, UiState},
+};
 
 use super::{
     constants::{MENU_INTERNET_NAME, MENU_UI_PAGE_QUERY},
+    setup_wizard::{SETUP_WIZARD_STORAGE, SetupWizardStep},
     user_data::UserData,
 };
 
@@ -28,6 +32,7 @@ fn render_content_impl(
     super::content::main_frame::render(ui, pipe, ui_state, &cur_page);
     super::settings::main_frame::render(ui, pipe, ui_state, &cur_page);
     super::demo::main_frame::render(ui, ui_state, pipe, &cur_page);
+    super::stats::main_frame::render(ui, pipe, &cur_page);
     super::profile::main_frame::render(
         ui,
         pipe,
@@ -58,6 +63,7 @@ pub fn render_left_bar_and_content<'a, U, F>(
         .size(Size::remainder())
         .horizontal(|mut strip| {
             strip.cell(|ui| {
+                begin_section(ui);
                 ui.style_mut().wrap_mode = None;
                 ui.style_mut().spacing.item_spacing.x = x;
                 super::leftbar::main_frame::render(
@@ -74,6 +80,17 @@ pub fn render_left_bar_and_content<'a, U, F>(
                 content(ui, pipe, ui_state, ui_page_query_name);
             });
         });
+
+    // back leads to the fallback page, unless the content handled it already
+    let path = pipe.user_data.as_mut().config.path();
+    if path
+        .query
+        .get(ui_page_query_name)
+        .is_some_and(|page| *page != fallback_query)
+        && ui_state.focus.take_back()
+    {
+        path.add_query((ui_page_query_name.to_string(), fallback_query.to_string()));
+    }
 }
 
 pub fn render_content(
@@ -89,6 +106,7 @@ pub fn render_content(
         .size(Size::exact(10.0))
         .vertical(|mut strip| {
             strip.cell(|ui| {
+                begin_section(ui);
                 ui.style_mut().wrap_mode = None;
                 super::topbar::main_frame::render(ui, ui_state, pipe, ui_page_query_name);
             });
@@ -101,6 +119,7 @@ pub fn render_content(
                     .horizontal(|mut strip| {
                         strip.empty();
                         strip.cell(|ui| {
+                            begin_section(ui);
                             ui.style_mut().wrap_mode = None;
                             render_content_impl(ui, pipe, ui_state, ui_page_query_name);
                         });
@@ -117,6 +136,14 @@ pub fn render<'a>(
     pipe: &'a mut UiRenderPipe<'a, UserData<'a>>,
     ui_state: &mut UiState,
 ) {
+    if let Some(step) = pipe
+        .user_data
+        .config
+        .storage_opt::<SetupWizardStep>(SETUP_WIZARD_STORAGE)
+    {
+        super::setup_wizard::main_frame::render(ui, pipe, ui_state, step);
+        return;
+    }
     render_left_bar_and_content(
         ui,
         pipe,
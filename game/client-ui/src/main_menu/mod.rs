@@ -5,6 +5,7 @@ pub mod ddnet_info;
 pub mod demo;
 pub mod demo_list;
 pub mod features;
+pub mod game_files;
 pub mod leftbar;
 pub mod legacy_server_list;
 pub mod main_frame;
@@ -14,7 +15,9 @@ pub mod player_settings_ntfy;
 pub mod profile;
 pub mod profiles_interface;
 pub mod settings;
+pub mod setup_wizard;
 pub mod spatial_chat;
+pub mod stats;
 pub mod theme_container;
 pub mod topbar;
 pub mod user_data;
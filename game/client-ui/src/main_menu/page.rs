@@ -1,5 +1,11 @@
-use std::{net::SocketAddr, path::Path, sync::Arc, time::Duration};
+use std::{
+    net::SocketAddr,
+    path::Path,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
+use anyhow::anyhow;
 use base_io::{io::Io, runtime::IoRuntimeTask};
 use base_io_traits::{fs_traits::FileSystemEntryTy, http_traits::HttpClientInterface};
 use client_containers::{
@@ -18,6 +24,7 @@ use demo::{
 };
 use game_base::{
     assets_url::HTTP_RESOURCE_URL,
+    local_stats::{LOCAL_STATS_PATH, LocalStats, stats_day},
     server_browser::{
         ServerBrowserData, ServerBrowserInfo, ServerBrowserInfoMap, ServerBrowserPlayer,
         ServerBrowserServer, ServerBrowserSkin,
@@ -60,23 +67,35 @@ use super::{
     ddnet_info::DdnetInfo,
     demo_list::{DemoList, DemoListEntry},
     features::EnabledFeatures,
+    game_files::{GameFilesCheck, GameFilesState},
     legacy_server_list::LegacyServerList,
     main_frame,
     monitors::UiMonitors,
     player_settings_ntfy::PlayerSettingsSync,
     profiles_interface::ProfilesInterface,
     spatial_chat::SpatialChat,
+    stats::LocalStatsState,
     theme_container::{THEME_CONTAINER_PATH, ThemeContainer},
     user_data::{ProfileTasks, RenderOptions, UserData},
 };
 
+/// Thumbnails of maps that were cached locally, the browser never downloads them.
+pub const MAP_THUMBNAIL_CONTAINER_PATH: &str = "map-thumbnails/";
+
+/// How long to wait between two requests for the live info of a single server.
+pub const SERVER_INFO_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
 pub struct MainMenuIo {
     pub(crate) io: Io,
     cur_servers_task: Option<IoRuntimeTask<Vec<ServerBrowserServer>>>,
+    cur_server_info_task: Option<IoRuntimeTask<ServerBrowserServer>>,
+    last_server_info_request: Option<Duration>,
     cur_ddnet_info_task: Option<IoRuntimeTask<DdnetInfo>>,
     cur_demos_task: Option<IoRuntimeTask<DemoList>>,
     cur_demo_info_task: Option<IoRuntimeTask<(DemoHeader, DemoHeaderExt)>>,
     remove_demo_info: bool,
+    cur_local_stats_task: Option<IoRuntimeTask<(LocalStats, u32)>>,
+    game_files: GameFilesCheck,
 }
 
 impl MainMenuInterface for MainMenuIo {
@@ -84,6 +103,20 @@ impl MainMenuInterface for MainMenuIo {
         self.cur_servers_task = Some(MainMenuUi::req_server_list(&self.io));
     }
 
+    fn refresh_server_info(&mut self, addr: SocketAddr, cur_time: &Duration) {
+        if self.can_refresh_server_info(cur_time) {
+            self.last_server_info_request = Some(*cur_time);
+            self.cur_server_info_task = Some(MainMenuUi::req_server_info(&self.io, addr));
+        }
+    }
+
+    fn can_refresh_server_info(&self, cur_time: &Duration) -> bool {
+        self.cur_server_info_task.is_none()
+            && self.last_server_info_request.is_none_or(|last_request| {
+                cur_time.saturating_sub(last_request) >= SERVER_INFO_REFRESH_INTERVAL
+            })
+    }
+
     fn refresh_demo_list(&mut self, path: &Path) {
         self.cur_demos_task = Some(MainMenuUi::req_demo_list(&self.io, path));
     }
@@ -96,6 +129,24 @@ impl MainMenuInterface for MainMenuIo {
             self.remove_demo_info = true;
         }
     }
+
+    fn refresh_local_stats(&mut self) {
+        if self.cur_local_stats_task.is_none() {
+            self.cur_local_stats_task = Some(MainMenuUi::req_local_stats(&self.io));
+        }
+    }
+
+    fn verify_game_files(&mut self) {
+        self.game_files.verify(&self.io);
+    }
+
+    fn repair_game_files(&mut self, download: bool) {
+        self.game_files.repair(&self.io, download);
+    }
+
+    fn game_files_state(&self) -> GameFilesState {
+        self.game_files.state()
+    }
 }
 
 pub struct MainMenuUi {
@@ -106,6 +157,7 @@ pub struct MainMenuUi {
 
     pub(crate) demos: DemoList,
     pub(crate) demo_info: Option<(DemoHeader, DemoHeaderExt)>,
+    pub(crate) local_stats: LocalStatsState,
 
     menu_io: MainMenuIo,
     io: Io,
@@ -124,6 +176,7 @@ pub struct MainMenuUi {
     pub containers: RenderGameContainers,
     pub theme_container: ThemeContainer,
     pub community_icon_container: ThumbnailContainer,
+    pub map_thumbnail_container: ThumbnailContainer,
 
     pub render_tee: RenderTee,
     pub toolkit_render: ToolkitRender,
@@ -145,6 +198,21 @@ pub struct MainMenuUi {
 }
 
 impl MainMenuUi {
+    fn req_local_stats(io: &Io) -> IoRuntimeTask<(LocalStats, u32)> {
+        let fs = io.fs.clone();
+        io.rt.spawn(async move {
+            let today = stats_day(
+                SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .unwrap_or_default(),
+            );
+            Ok((
+                LocalStats::load(&*fs, LOCAL_STATS_PATH.as_ref()).await?,
+                today,
+            ))
+        })
+    }
+
     fn req_demo_list(io: &Io, path: &Path) -> IoRuntimeTask<DemoList> {
         let fs = io.fs.clone();
         let path = path.to_path_buf();
@@ -199,6 +267,18 @@ impl MainMenuUi {
         )
     }
 
+    /// Downloads the live info of a single server from the master server.
+    pub async fn download_server_info(
+        http: &Arc<dyn HttpClientInterface>,
+        addr: SocketAddr,
+    ) -> anyhow::Result<ServerBrowserServer> {
+        let mut url: Url = "https://pg.ddnet.org:4444/ddnet/15/server"
+            .try_into()
+            .unwrap();
+        url.query_pairs_mut().append_pair("addr", &addr.to_string());
+        Self::json_to_server_info(&http.download_text(url).await?, addr)
+    }
+
     pub fn legacy_json_to_server_browser(
         servers_raw: &str,
     ) -> anyhow::Result<Vec<ServerBrowserServer>> {
@@ -312,6 +392,13 @@ impl MainMenuUi {
         )
     }
 
+    pub fn req_server_info(io: &Io, addr: SocketAddr) -> IoRuntimeTask<ServerBrowserServer> {
+        let http = io.http.clone();
+        io.rt
+            .spawn(async move { Self::download_server_info(&http, addr).await })
+            .cancelable()
+    }
+
     pub fn req_server_list(io: &Io) -> IoRuntimeTask<Vec<ServerBrowserServer>> {
         let http = io.http.clone();
         io.rt
@@ -411,6 +498,11 @@ impl MainMenuUi {
             "community-icon-container",
             Some(ddnet_info_req.url().clone()),
         );
+        let map_thumbnail_container = load_thumbnail_container_short(
+            MAP_THUMBNAIL_CONTAINER_PATH,
+            "map-thumbnail-container",
+            None,
+        );
 
         let tile_layer_visuals = None;
         Self {
@@ -421,14 +513,19 @@ impl MainMenuUi {
             ddnet_info: DdnetInfo::default(),
             demos: DemoList::default(),
             demo_info: None,
+            local_stats: LocalStatsState::default(),
 
             menu_io: MainMenuIo {
                 io: io.clone(),
                 cur_ddnet_info_task: Some(cur_ddnet_info_task),
                 cur_servers_task: Some(cur_servers_task),
+                cur_server_info_task: None,
+                last_server_info_request: None,
                 cur_demos_task: None,
                 cur_demo_info_task: None,
                 remove_demo_info: false,
+                cur_local_stats_task: None,
+                game_files: Default::default(),
             },
             io: io.clone(),
             scene,
@@ -448,6 +545,7 @@ impl MainMenuUi {
             containers,
             theme_container,
             community_icon_container,
+            map_thumbnail_container,
             map_render: MapGraphics::new(&graphics.backend_handle),
             tile_layer_visuals,
 
@@ -475,10 +573,12 @@ impl MainMenuUi {
             client_info: &self.client_info,
             ddnet_info: &self.ddnet_info,
             icons: &mut self.community_icon_container,
+            map_thumbnails: &mut self.map_thumbnail_container,
 
             browser_data: &mut self.browser_data,
             demos: &self.demos,
             demo_info: &self.demo_info,
+            local_stats: &self.local_stats,
 
             render_options: RenderOptions {
                 hide_buttons_icons: hide_buttons_right,
@@ -582,6 +682,17 @@ impl MainMenuUi {
         Ok(parsed_servers)
     }
 
+    /// The entry of the server with the given address in the server list.
+    pub fn json_to_server_info(
+        servers_raw: &str,
+        addr: SocketAddr,
+    ) -> anyhow::Result<ServerBrowserServer> {
+        Self::json_to_server_browser(servers_raw)?
+            .into_iter()
+            .find(|server| server.addresses.contains(&addr))
+            .ok_or_else(|| anyhow!("server {addr} is not part of the server list"))
+    }
+
     pub fn check_tasks(&mut self, cur_time: &Duration) {
         if let Some(server_task) = &self.menu_io.cur_servers_task
             && server_task.is_finished()
@@ -595,11 +706,30 @@ impl MainMenuUi {
                 }
             }
         }
+        if let Some(task) = &self.menu_io.cur_server_info_task
+            && task.is_finished()
+        {
+            match self.menu_io.cur_server_info_task.take().unwrap().get() {
+                Ok(server) => {
+                    self.browser_data.update_server(server);
+                }
+                Err(err) => {
+                    log::error!("failed to download server info: {err}");
+                }
+            }
+        }
         if let Some(server_task) = &self.menu_io.cur_ddnet_info_task
             && server_task.is_finished()
         {
             match self.menu_io.cur_ddnet_info_task.take().unwrap().get() {
                 Ok(ddnet_info) => {
+                    self.browser_data.set_community_addresses(
+                        ddnet_info
+                            .communities
+                            .values()
+                            .flat_map(|community| community.servers.iter().copied())
+                            .collect(),
+                    );
                     self.ddnet_info = ddnet_info;
                 }
                 Err(err) => {
@@ -631,15 +761,32 @@ impl MainMenuUi {
                 }
             }
         }
+        if let Some(task) = &self.menu_io.cur_local_stats_task
+            && task.is_finished()
+        {
+            self.local_stats = match self.menu_io.cur_local_stats_task.take().unwrap().get() {
+                Ok((stats, today)) => LocalStatsState::Loaded { stats, today },
+                Err(err) => LocalStatsState::Err(err.to_string()),
+            };
+        }
         if std::mem::take(&mut self.menu_io.remove_demo_info) {
             self.demo_info = None;
         }
+        self.menu_io.game_files.check_task();
     }
 
-    pub(crate) fn update_container<A, L>(container: &mut Container<A, L>, cur_time: &Duration)
-    where
+    /// `item_memory_warn_mib` is the size of an item in MiB above which
+    /// a warning is logged, `0` disables it.
+    pub(crate) fn update_container<A, L>(
+        container: &mut Container<A, L>,
+        cur_time: &Duration,
+        item_memory_warn_mib: u64,
+    ) where
         L: client_containers::container::ContainerLoad<A> + Sync + Send + 'static,
     {
+        container.set_item_memory_warn_threshold(
+            (item_memory_warn_mib > 0).then_some(item_memory_warn_mib as usize * 1024 * 1024),
+        );
         let el = Duration::from_secs(10);
         let ui = Duration::from_secs(1);
         let max_items_el = Duration::from_millis(100);
@@ -653,21 +800,78 @@ impl MainMenuUi {
         }
     }
 
-    pub fn update(&mut self, cur_time: &Duration) {
-        Self::update_container(&mut self.containers.ctf_container, cur_time);
-        Self::update_container(&mut self.containers.emoticons_container, cur_time);
-        Self::update_container(&mut self.containers.entities_container, cur_time);
-        Self::update_container(&mut self.containers.freeze_container, cur_time);
-        Self::update_container(&mut self.containers.game_container, cur_time);
-        Self::update_container(&mut self.containers.hook_container, cur_time);
-        Self::update_container(&mut self.containers.hud_container, cur_time);
-        Self::update_container(&mut self.containers.ninja_container, cur_time);
-        Self::update_container(&mut self.containers.particles_container, cur_time);
-        Self::update_container(&mut self.containers.weapon_container, cur_time);
-        Self::update_container(&mut self.containers.flags_container, cur_time);
-        Self::update_container(&mut self.containers.skin_container, cur_time);
-        Self::update_container(&mut self.theme_container, cur_time);
-        Self::update_container(&mut self.community_icon_container, cur_time);
+    pub fn update(&mut self, cur_time: &Duration, item_memory_warn_mib: u64) {
+        Self::update_container(
+            &mut self.containers.ctf_container,
+            cur_time,
+            item_memory_warn_mib,
+        );
+        Self::update_container(
+            &mut self.containers.emoticons_container,
+            cur_time,
+            item_memory_warn_mib,
+        );
+        Self::update_container(
+            &mut self.containers.entities_container,
+            cur_time,
+            item_memory_warn_mib,
+        );
+        Self::update_container(
+            &mut self.containers.freeze_container,
+            cur_time,
+            item_memory_warn_mib,
+        );
+        Self::update_container(
+            &mut self.containers.game_container,
+            cur_time,
+            item_memory_warn_mib,
+        );
+        Self::update_container(
+            &mut self.containers.hook_container,
+            cur_time,
+            item_memory_warn_mib,
+        );
+        Self::update_container(
+            &mut self.containers.hud_container,
+            cur_time,
+            item_memory_warn_mib,
+        );
+        Self::update_container(
+            &mut self.containers.ninja_container,
+            cur_time,
+            item_memory_warn_mib,
+        );
+        Self::update_container(
+            &mut self.containers.particles_container,
+            cur_time,
+            item_memory_warn_mib,
+        );
+        Self::update_container(
+            &mut self.containers.weapon_container,
+            cur_time,
+            item_memory_warn_mib,
+        );
+        Self::update_container(
+            &mut self.containers.flags_container,
+            cur_time,
+            item_memory_warn_mib,
+        );
+        Self::update_container(
+            &mut self.containers.skin_container,
+            cur_time,
+            item_memory_warn_mib,
+        );
+        Self::update_container(&mut self.theme_container, cur_time, item_memory_warn_mib);
+        Self::update_container(
+            &mut self.community_icon_container,
+            cur_time,
+            item_memory_warn_mib,
+        );
+        Self::update_container(
+            &mut self.map_thumbnail_container,
+            cur_time,
+            item_memory_warn_mib,
+        );
     }
 }
 
@@ -689,14 +893,84 @@ impl UiPageInterface<Config> for MainMenuUi {
             ui_state,
         );
 
-        self.update(&pipe.cur_time);
+        self.update(
+            &pipe.cur_time,
+            pipe.user_data.game.dbg.container_item_warn_mib,
+        );
     }
 
     fn unmount(&mut self) {
         self.containers.clear_except_default();
         self.theme_container.clear_except_default();
         self.community_icon_container.clear_except_default();
+        self.map_thumbnail_container.clear_except_default();
         self.profile_tasks = Default::default();
         self.menu_io.cur_servers_task = None;
+        self.menu_io.cur_server_info_task = None;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::SocketAddr;
+
+    use super::MainMenuUi;
+
+    const SERVERS: &str = r#"{
+        "servers": [
+            {
+                "addresses": ["ddrs-0.1+quic://127.0.0.1:8303", "tw-0.6+udp://127.0.0.1:8304"],
+                "location": "eu:de",
+                "info": {
+                    "name": "first",
+                    "game_type": "ddnet",
+                    "version": "0.1",
+                    "map": { "name": "ctf1" },
+                    "players": [
+                        { "score": "5", "name": "nameless tee", "clan": "", "flag": "de" },
+                        { "time": "12", "name": "brainless tee" }
+                    ],
+                    "max_players": 64
+                }
+            },
+            {
+                "addresses": ["ddrs-0.1+quic://[::1]:8310"],
+                "info": { "name": "second" }
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn server_info() {
+        let addr: SocketAddr = "127.0.0.1:8303".parse().unwrap();
+        let server = MainMenuUi::json_to_server_info(SERVERS, addr).unwrap();
+        assert_eq!(server.info.name.as_str(), "first");
+        assert_eq!(server.info.map.name.as_str(), "ctf1");
+        assert_eq!(server.location.as_str(), "eu:de");
+        // only addresses of the own protocol are kept
+        assert_eq!(server.addresses, [addr]);
+        assert!(!server.legacy_server);
+
+        let scores: Vec<_> = server
+            .info
+            .players
+            .iter()
+            .map(|player| (player.name.as_str(), player.score.as_str()))
+            .collect();
+        assert_eq!(scores, [("nameless tee", "5"), ("brainless tee", "12")]);
+
+        let server = MainMenuUi::json_to_server_info(SERVERS, "[::1]:8310".parse().unwrap());
+        assert_eq!(server.unwrap().info.name.as_str(), "second");
+    }
+
+    #[test]
+    fn server_info_missing() {
+        // legacy addresses of the server are not part of the list
+        assert!(
+            MainMenuUi::json_to_server_info(SERVERS, "127.0.0.1:8304".parse().unwrap()).is_err()
+        );
+        assert!(MainMenuUi::json_to_server_info(SERVERS, "127.0.0.1:1".parse().unwrap()).is_err());
+        assert!(MainMenuUi::json_to_server_info("{}", "127.0.0.1:8303".parse().unwrap()).is_err());
+        assert!(MainMenuUi::json_to_server_info("", "127.0.0.1:8303".parse().unwrap()).is_err());
     }
 }
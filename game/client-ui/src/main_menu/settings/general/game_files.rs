@@ -0,0 +1,84 @@
+use egui::{ProgressBar, ScrollArea};
+use game_base::data_manifest::DataFileMismatchKind;
+use ui_base::types::UiRenderPipe;
+
+use crate::main_menu::{game_files::GameFilesState, user_data::UserData};
+
+/// Verifies the game files & offers to repair the broken ones.
+pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
+    let main_menu = &mut pipe.user_data.main_menu;
+    ui.horizontal(|ui| match main_menu.game_files_state() {
+        GameFilesState::Unchecked => {
+            if ui.button("Verify game files").clicked() {
+                main_menu.verify_game_files();
+            }
+        }
+        GameFilesState::Verifying { checked, total } => {
+            ui.label("Verifying game files");
+            ui.add(
+                ProgressBar::new(checked as f32 / total.max(1) as f32)
+                    .desired_width(200.0)
+                    .text(format!("{checked}/{total}")),
+            );
+        }
+        GameFilesState::Repairing { repaired, total } => {
+            ui.label("Repairing game files");
+            ui.add(
+                ProgressBar::new(repaired as f32 / total.max(1) as f32)
+                    .desired_width(200.0)
+                    .text(format!("{repaired}/{total}")),
+            );
+        }
+        GameFilesState::Checked(mismatches) => {
+            if ui.button("Verify game files").clicked() {
+                main_menu.verify_game_files();
+            }
+            if mismatches.is_empty() {
+                ui.label("All game files are intact.");
+                return;
+            }
+            ui.vertical(|ui| {
+                ui.label(format!("{} game files are broken:", mismatches.len()));
+                ScrollArea::vertical()
+                    .id_salt("game-files-mismatches")
+                    .max_height(100.0)
+                    .show(ui, |ui| {
+                        for mismatch in &mismatches {
+                            ui.label(format!(
+                                "{} ({})",
+                                mismatch.path.to_string_lossy(),
+                                match mismatch.kind {
+                                    DataFileMismatchKind::Missing => "missing",
+                                    DataFileMismatchKind::Corrupted => "modified",
+                                }
+                            ));
+                        }
+                    });
+                ui.horizontal(|ui| {
+                    let restorable = mismatches.iter().filter(|m| m.restorable).count();
+                    if restorable > 0 && ui.button(format!("Repair {restorable} files")).clicked() {
+                        main_menu.repair_game_files(false);
+                    }
+                    let downloads = mismatches.len() - restorable;
+                    if downloads > 0
+                        && ui
+                            .button(format!("Download {downloads} files"))
+                            .on_hover_text(
+                                "The installed copies of these files are broken, \
+                                they are downloaded again.",
+                            )
+                            .clicked()
+                    {
+                        main_menu.repair_game_files(true);
+                    }
+                });
+            });
+        }
+        GameFilesState::Err(err) => {
+            if ui.button("Verify game files").clicked() {
+                main_menu.verify_game_files();
+            }
+            ui.label(format!("Failed to verify the game files: {err}"));
+        }
+    });
+}
@@ -1,8 +1,14 @@
-use crate::main_menu::user_data::UserData;
+use crate::main_menu::{setup_wizard::launch_setup_wizard, user_data::UserData};
 use tracing::instrument;
 use ui_base::types::{UiRenderPipe, UiState};
 
 #[instrument(level = "trace", skip_all)]
 pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &mut UiState) {
+    super::game_files::render(ui, pipe);
+    ui.separator();
+    if ui.button("Run setup wizard").clicked() {
+        launch_setup_wizard(pipe.user_data.config);
+    }
+    ui.separator();
     super::themes::theme_list(ui, pipe, ui_state)
 }
@@ -1,2 +1,3 @@
+pub mod game_files;
 pub mod main_frame;
 pub mod themes;
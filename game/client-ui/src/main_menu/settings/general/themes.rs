@@ -1,12 +1,31 @@
 use std::collections::BTreeMap;
 
+use game_config::config::ConfigThemeVariant;
 use game_interface::types::resource_key::{NetworkResourceKey, ResourceKey};
 use math::math::vector::vec2;
 use ui_base::types::{UiRenderPipe, UiState};
 
 use crate::{main_menu::user_data::UserData, utils::render_texture_for_ui};
 
+fn theme_variant(ui: &mut egui::Ui, variant: &mut ConfigThemeVariant) {
+    ui.horizontal(|ui| {
+        ui.label("Variant");
+        egui::ComboBox::new("theme-variant", "")
+            .selected_text(match variant {
+                ConfigThemeVariant::Auto => "By local time",
+                ConfigThemeVariant::Day => "Day",
+                ConfigThemeVariant::Night => "Night",
+            })
+            .show_ui(ui, |ui| {
+                ui.selectable_value(variant, ConfigThemeVariant::Auto, "By local time");
+                ui.selectable_value(variant, ConfigThemeVariant::Day, "Day");
+                ui.selectable_value(variant, ConfigThemeVariant::Night, "Night");
+            });
+    });
+}
+
 pub fn theme_list(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &mut UiState) {
+    theme_variant(ui, &mut pipe.user_data.config.game.menu.theme_variant);
     let entries = pipe.user_data.theme_container.entries_index();
     let entries_sorted = entries.into_iter().collect::<BTreeMap<_, _>>();
     let setting = &mut pipe.user_data.config.game.menu.background_map;
@@ -0,0 +1,116 @@
+use client_containers::container::{Container, ContainerItemMemory, ContainerLoad};
+use egui::{Grid, Layout, ScrollArea};
+use ui_base::types::UiRenderPipe;
+
+use crate::main_menu::user_data::UserData;
+
+/// How many of the biggest items are listed per container.
+const TOP_ITEMS: usize = 10;
+
+fn mib(bytes: usize) -> String {
+    format!("{:.2}", bytes as f64 / 1024.0 / 1024.0)
+}
+
+/// The biggest loaded items of a container, each with a button to unload it.
+fn render_container<A, L>(
+    ui: &mut egui::Ui,
+    name: &str,
+    container: &mut Container<A, L>,
+) -> ContainerItemMemory
+where
+    L: ContainerLoad<A> + Sync + Send + 'static,
+{
+    let memory = container.memory_usage();
+    let items = container.items_by_memory();
+    let mut evict = None;
+    ui.collapsing(
+        format!(
+            "{name}: {} items, GPU {} MiB, CPU {} MiB",
+            items.len(),
+            mib(memory.gpu_bytes),
+            mib(memory.cpu_bytes)
+        ),
+        |ui| {
+            Grid::new(format!("loaded-assets-{name}"))
+                .num_columns(4)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Name");
+                    ui.label("GPU MiB");
+                    ui.label("CPU MiB");
+                    ui.label("");
+                    ui.end_row();
+
+                    for (key, item_memory) in items.iter().take(TOP_ITEMS) {
+                        ui.label(key.name.as_str());
+                        ui.label(mib(item_memory.gpu_bytes));
+                        ui.label(mib(item_memory.cpu_bytes));
+                        if ui
+                            .add_enabled(
+                                **key != *container.default_key,
+                                egui::Button::new("\u{f1f8}"),
+                            )
+                            .on_hover_text("Unload, it's loaded again when used")
+                            .clicked()
+                        {
+                            evict = Some((*key).clone());
+                        }
+                        ui.end_row();
+                    }
+                });
+            if items.len() > TOP_ITEMS {
+                ui.label(format!("and {} more…", items.len() - TOP_ITEMS));
+            }
+        },
+    );
+    if let Some(key) = evict {
+        container.evict(&key);
+    }
+    memory
+}
+
+pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
+    ui.with_layout(Layout::top_down(egui::Align::Min), |ui| {
+        ui.label(
+            "The estimated memory of the loaded assets. \
+            Unused assets are unloaded automatically after a while.",
+        );
+        ui.add_space(5.0);
+
+        let data = &mut *pipe.user_data;
+        let mut total = ContainerItemMemory::default();
+        ScrollArea::vertical()
+            .max_height(ui.available_height() - 60.0)
+            .show(ui, |ui| {
+                total = [
+                    render_container(ui, "Skins", data.skin_container),
+                    render_container(ui, "Weapons", data.weapons_container),
+                    render_container(ui, "Hooks", data.hook_container),
+                    render_container(ui, "Entities", data.entities_container),
+                    render_container(ui, "Freezes", data.freeze_container),
+                    render_container(ui, "Emoticons", data.emoticons_container),
+                    render_container(ui, "Particles", data.particles_container),
+                    render_container(ui, "Ninjas", data.ninja_container),
+                    render_container(ui, "Game", data.game_container),
+                    render_container(ui, "HUD", data.hud_container),
+                    render_container(ui, "CTF", data.ctf_container),
+                    render_container(ui, "Flags", data.flags_container),
+                    render_container(ui, "Themes", data.theme_container),
+                ]
+                .into_iter()
+                .sum();
+            });
+        ui.add_space(5.0);
+        ui.label(format!(
+            "Total: GPU {} MiB, CPU {} MiB",
+            mib(total.gpu_bytes),
+            mib(total.cpu_bytes)
+        ));
+
+        let dbg = &mut pipe.user_data.config.game.dbg;
+        ui.horizontal(|ui| {
+            ui.label("Warn about single assets bigger than (MiB, 0 = off):");
+            ui.add(egui::DragValue::new(&mut dbg.container_item_warn_mib).range(0..=4096));
+        });
+    });
+}
@@ -1,13 +1,53 @@
-use config::traits::ConfigValue;
-use egui::{Button, Color32, DragValue, Grid, Id, Layout, Modal, ScrollArea, Stroke, TextEdit};
+use client_render_base::render::crosshair::crosshair_quads;
+use config::{traits::ConfigValue, types::ConfRgb};
+use egui::{
+    Button, Color32, DragValue, Frame, Grid, Id, Layout, Modal, Rect, ScrollArea, Sense, Slider,
+    Stroke, TextEdit,
+};
 use egui_extras::{Size, StripBuilder};
-use game_config::config::ConfigRender;
+use game_config::config::{
+    ConfigCrosshair, ConfigCrosshairMode, ConfigCrosshairShape, ConfigCrosshairStyle,
+    ConfigGraphicsPreset, ConfigHud, ConfigHudStatusStyle, ConfigRender,
+};
+use game_interface::types::weapons::WeaponType;
 use graphics_types::gpu::{Gpu, GpuType};
 use num_traits::FromPrimitive;
 use tracing::instrument;
 use ui_base::types::UiRenderPipe;
 
-use crate::{events::UiEvent, main_menu::user_data::UserData};
+use crate::{
+    events::UiEvent,
+    main_menu::{settings::constants::SETTINGS_SUB_UI_PAGE_QUERY, user_data::UserData},
+};
+
+/// Shown while the graphics run in safe mode,
+/// because they failed to start before.
+fn render_safe_mode_banner(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
+    let gfx = &mut pipe.user_data.config.engine.gfx;
+    if !gfx.safe_mode {
+        return;
+    }
+    Frame::default()
+        .fill(ui.visuals().warn_fg_color.gamma_multiply(0.2))
+        .stroke(Stroke::new(1.0, ui.visuals().warn_fg_color))
+        .corner_radius(5.0)
+        .inner_margin(5.0)
+        .show(ui, |ui| {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                "The graphics run in safe mode, because they failed to start before. \
+                Multisampling, threaded rendering & debug extensions are disabled.",
+            );
+            if ui
+                .button("Retry full initialization")
+                .on_hover_text("Takes effect after restarting the client.")
+                .clicked()
+            {
+                gfx.safe_mode = false;
+            }
+        });
+    ui.add_space(10.0);
+}
 
 fn render_settings(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
     let config = &mut pipe.user_data.config.engine;
@@ -60,6 +100,26 @@ fn render_settings(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
             });
         ui.end_row();
 
+        ui.label("Preset");
+        let mut preset = config_game.cl.graphics_preset;
+        egui::ComboBox::new("graphics_preset", "")
+            .selected_text(format!("{preset:?}"))
+            .show_ui(ui, |ui| {
+                for preset_option in [
+                    ConfigGraphicsPreset::Low,
+                    ConfigGraphicsPreset::Medium,
+                    ConfigGraphicsPreset::High,
+                    ConfigGraphicsPreset::Custom,
+                ] {
+                    ui.selectable_value(&mut preset, preset_option, format!("{preset_option:?}"));
+                }
+            });
+        if preset != config_game.cl.graphics_preset {
+            config_game.apply_graphics_preset(config, preset);
+            pipe.user_data.events.push(UiEvent::MsaaChanged);
+        }
+        ui.end_row();
+
         ui.label("V-sync");
         if ui.checkbox(&mut config.gl.vsync, "").changed() {
             pipe.user_data.events.push(UiEvent::VsyncChanged);
@@ -207,6 +267,260 @@ fn render_settings(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
             });
             ui.end_row();
         }
+
+        ui.label("High quality map images")
+            .on_hover_text("Applies to the next map that is loaded.");
+        ui.checkbox(&mut config_game.map.hq_assets, "");
+        ui.end_row();
+
+        if config_game.map.hq_assets {
+            ui.label("Texture memory limit (MiB)");
+            ui.add(DragValue::new(&mut config_game.map.hq_assets_texture_memory).range(0..=65536));
+            ui.end_row();
+        }
+
+        ui.label("Particles");
+        ui.add(Slider::new(&mut config_game.cl.render.particles, 0..=100).suffix("%"));
+        ui.end_row();
+
+        ui.label("Flag stand view")
+            .on_hover_text("Shows the own flag stand while carrying the enemy flag.");
+        ui.checkbox(&mut config_game.cl.render.flag_stand_view, "");
+        ui.end_row();
+
+        if config_game.cl.render.flag_stand_view {
+            ui.label("Flag stand view size");
+            ui.add(
+                Slider::new(&mut config_game.cl.render.flag_stand_view_size, 10..=50).suffix("%"),
+            );
+            ui.end_row();
+        }
+
+        ui.label("Background tile layers");
+        ui.checkbox(&mut config_game.map.background_show_tile_layers, "");
+        ui.end_row();
+
+        ui.label("Quads");
+        ui.checkbox(&mut config_game.map.show_quads, "");
+        ui.end_row();
+    });
+
+    // changing any setting of the preset makes it a custom one
+    config_game.update_graphics_preset(config);
+}
+
+/// Renders the crosshair the same way as ingame,
+/// in whole pixels around the center of the preview.
+fn render_crosshair_preview(ui: &mut egui::Ui, style: &ConfigCrosshairStyle) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(100.0, 100.0), Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 5.0, Color32::from_gray(60));
+
+    let pixels_per_point = ui.ctx().pixels_per_point();
+    let center = (rect.center().to_vec2() * pixels_per_point).round();
+    let center = math::math::vector::vec2::new(center.x, center.y);
+    let scale = pixels_per_point.round().max(1.0) as u32;
+    for quad in crosshair_quads(style, scale) {
+        let [tl, _, br, _] = quad.rect.vertices(center);
+        painter.rect_filled(
+            Rect::from_min_max(
+                egui::pos2(tl.x, tl.y) / pixels_per_point,
+                egui::pos2(br.x, br.y) / pixels_per_point,
+            ),
+            0.0,
+            Color32::from_rgba_unmultiplied(
+                quad.color.r(),
+                quad.color.g(),
+                quad.color.b(),
+                quad.color.a(),
+            ),
+        );
+    }
+}
+
+fn color_edit(ui: &mut egui::Ui, color: &mut ConfRgb) {
+    let mut rgb = [color.r, color.g, color.b];
+    ui.color_edit_button_srgb(&mut rgb);
+    *color = ConfRgb {
+        r: rgb[0],
+        g: rgb[1],
+        b: rgb[2],
+    };
+}
+
+fn render_crosshair_style(ui: &mut egui::Ui, style: &mut ConfigCrosshairStyle) {
+    Grid::new("crosshair-style").num_columns(2).show(ui, |ui| {
+        ui.label("Shape");
+        egui::ComboBox::new("crosshair-shape", "")
+            .selected_text(format!("{:?}", style.shape))
+            .show_ui(ui, |ui| {
+                for shape in [
+                    ConfigCrosshairShape::Cross,
+                    ConfigCrosshairShape::CrossDot,
+                    ConfigCrosshairShape::T,
+                    ConfigCrosshairShape::Dot,
+                    ConfigCrosshairShape::Circle,
+                ] {
+                    ui.selectable_value(&mut style.shape, shape, format!("{shape:?}"));
+                }
+            });
+        ui.end_row();
+
+        ui.label("Size");
+        ui.add(DragValue::new(&mut style.size).range(1..=128));
+        ui.end_row();
+
+        ui.label("Thickness");
+        ui.add(DragValue::new(&mut style.thickness).range(1..=32));
+        ui.end_row();
+
+        ui.label("Gap");
+        ui.add(DragValue::new(&mut style.gap).range(0..=64));
+        ui.end_row();
+
+        ui.label("Color");
+        color_edit(ui, &mut style.color);
+        ui.end_row();
+
+        ui.label("Opacity");
+        ui.add(Slider::new(&mut style.alpha, 0.0..=1.0));
+        ui.end_row();
+
+        ui.label("Outline");
+        ui.add(DragValue::new(&mut style.outline).range(0..=8));
+        ui.end_row();
+
+        if style.outline > 0 {
+            ui.label("Outline color");
+            color_edit(ui, &mut style.outline_color);
+            ui.end_row();
+        }
+    });
+}
+
+fn render_crosshair(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
+    const CROSSHAIR_WEAPON: &str = "crosshair-weapon";
+    const WEAPONS: [(&str, Option<WeaponType>); 6] = [
+        ("Hammer", Some(WeaponType::Hammer)),
+        ("Gun", Some(WeaponType::Gun)),
+        ("Shotgun", Some(WeaponType::Shotgun)),
+        ("Grenade", Some(WeaponType::Grenade)),
+        ("Laser", Some(WeaponType::Laser)),
+        ("Ninja", None),
+    ];
+
+    let query = &mut pipe.user_data.config.engine.ui.path.query;
+    let weapon_index: usize = query
+        .get(CROSSHAIR_WEAPON)
+        .and_then(|index| index.parse().ok())
+        .unwrap_or_default()
+        .min(WEAPONS.len() - 1);
+    let mut new_weapon_index = weapon_index;
+    let crosshair: &mut ConfigCrosshair = &mut pipe.user_data.config.game.cl.render.crosshair;
+
+    ui.heading("Crosshair");
+    Grid::new("crosshair-settings")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Mode");
+            egui::ComboBox::new("crosshair-mode", "")
+                .selected_text(match crosshair.mode {
+                    ConfigCrosshairMode::WeaponCursor => "Weapon cursors",
+                    ConfigCrosshairMode::Combined => "Both",
+                    ConfigCrosshairMode::Crosshair => "Crosshair",
+                })
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(
+                        &mut crosshair.mode,
+                        ConfigCrosshairMode::WeaponCursor,
+                        "Weapon cursors",
+                    );
+                    ui.selectable_value(&mut crosshair.mode, ConfigCrosshairMode::Combined, "Both");
+                    ui.selectable_value(
+                        &mut crosshair.mode,
+                        ConfigCrosshairMode::Crosshair,
+                        "Crosshair",
+                    );
+                });
+            ui.end_row();
+
+            ui.label("Per weapon");
+            ui.checkbox(&mut crosshair.per_weapon, "");
+            ui.end_row();
+
+            if crosshair.per_weapon {
+                ui.label("Weapon");
+                egui::ComboBox::new("crosshair-weapon", "")
+                    .selected_text(WEAPONS[weapon_index].0)
+                    .show_ui(ui, |ui| {
+                        for (index, (name, _)) in WEAPONS.iter().enumerate() {
+                            ui.selectable_value(&mut new_weapon_index, index, *name);
+                        }
+                    });
+                ui.end_row();
+            }
+        });
+
+    let style = if !crosshair.per_weapon {
+        &mut crosshair.style
+    } else {
+        match WEAPONS[weapon_index].1 {
+            Some(WeaponType::Hammer) => &mut crosshair.hammer,
+            Some(WeaponType::Gun) => &mut crosshair.gun,
+            Some(WeaponType::Shotgun) => &mut crosshair.shotgun,
+            Some(WeaponType::Grenade) => &mut crosshair.grenade,
+            Some(WeaponType::Laser) => &mut crosshair.laser,
+            None => &mut crosshair.ninja,
+        }
+    };
+    ui.horizontal(|ui| {
+        render_crosshair_style(ui, style);
+        render_crosshair_preview(ui, style);
+    });
+
+    pipe.user_data
+        .config
+        .engine
+        .ui
+        .path
+        .query
+        .insert(CROSSHAIR_WEAPON.to_string(), new_weapon_index.to_string());
+}
+
+fn render_hud(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
+    let hud: &mut ConfigHud = &mut pipe.user_data.config.game.cl.render.hud;
+
+    ui.heading("HUD");
+    Grid::new("hud-settings").num_columns(2).show(ui, |ui| {
+        ui.label("Health & armor");
+        egui::ComboBox::new("hud-status-style", "")
+            .selected_text(match hud.style {
+                ConfigHudStatusStyle::Pips => "Pips",
+                ConfigHudStatusStyle::Bars => "Bars",
+            })
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut hud.style, ConfigHudStatusStyle::Pips, "Pips");
+                ui.selectable_value(&mut hud.style, ConfigHudStatusStyle::Bars, "Bars");
+            });
+        ui.end_row();
+
+        ui.label("Low health warning");
+        ui.add(Slider::new(&mut hud.health_warning, 0..=10))
+            .on_hover_text("Health below this value pulses. 0 disables the warning.");
+        ui.end_row();
+
+        ui.label("Low armor warning");
+        ui.add(Slider::new(&mut hud.armor_warning, 0..=10))
+            .on_hover_text("Armor below this value pulses. 0 disables the warning.");
+        ui.end_row();
+
+        ui.label("Flash on change");
+        ui.checkbox(&mut hud.flash_on_change, "");
+        ui.end_row();
+
+        ui.label("Fire delay indicator");
+        ui.checkbox(&mut hud.fire_delay_indicator, "");
+        ui.end_row();
     });
 }
 
@@ -341,6 +655,22 @@ fn render_monitors(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
 
 #[instrument(level = "trace", skip_all)]
 pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
+    let cur_sub = pipe
+        .user_data
+        .config
+        .engine
+        .ui
+        .path
+        .query
+        .get(SETTINGS_SUB_UI_PAGE_QUERY)
+        .map(|path| path.as_ref())
+        .unwrap_or("")
+        .to_string();
+    if cur_sub == "Loaded Assets" {
+        super::loaded_assets::main_frame::render(ui, pipe);
+        return;
+    }
+
     let wnd_old = pipe.user_data.config.engine.wnd.clone();
 
     ui.with_layout(Layout::top_down(egui::Align::Min), |ui| {
@@ -350,7 +680,14 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
             .horizontal(|mut strip| {
                 strip.cell(|ui| {
                     ui.style_mut().wrap_mode = None;
-                    render_settings(ui, pipe);
+                    ScrollArea::vertical().show(ui, |ui| {
+                        render_safe_mode_banner(ui, pipe);
+                        render_settings(ui, pipe);
+                        ui.add_space(10.0);
+                        render_crosshair(ui, pipe);
+                        ui.add_space(10.0);
+                        render_hud(ui, pipe);
+                    });
                 });
                 strip.cell(|ui| {
                     ui.style_mut().wrap_mode = None;
@@ -1 +1,2 @@
+pub mod loaded_assets;
 pub mod main_frame;
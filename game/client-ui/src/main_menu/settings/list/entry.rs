@@ -1,6 +1,6 @@
 use egui::{Color32, Frame, Label, Layout, RichText, Sense, UiBuilder};
 use math::math::vector::vec2;
-use ui_base::style::bg_frame_color;
+use ui_base::{focus::FocusableResponse, style::bg_frame_color};
 
 /// single list entry
 pub fn render(
@@ -19,6 +19,7 @@ pub fn render(
 
     let entry_size = entry_visual_size + 25.0;
     let (rect, sense) = ui.allocate_exact_size(egui::vec2(entry_size, entry_size), Sense::click());
+    let sense = sense.focusable(ui);
 
     ui.scope_builder(UiBuilder::new().max_rect(rect), |ui| {
         ui.with_layout(
@@ -137,22 +137,35 @@ fn render_nav(
                 ui.style_mut().spacing.item_spacing.y = old_spacing_y;
 
                 ui.add_space(10.0);
+                let old_spacing_y =
+                    std::mem::replace(&mut ui.style_mut().spacing.item_spacing.y, 0.0);
                 add_btn(ui, "Graphics", None);
+                ui.horizontal(|ui| {
+                    ui.add_space(8.0);
+                    ui.with_layout(
+                        Layout::top_down(egui::Align::Min).with_cross_justify(true),
+                        |ui| {
+                            add_btn(ui, "Loaded Assets", Some("Graphics"));
+                        },
+                    );
+                });
+                ui.style_mut().spacing.item_spacing.y = old_spacing_y;
 
                 let old_spacing_y =
                     std::mem::replace(&mut ui.style_mut().spacing.item_spacing.y, 0.0);
                 add_btn(ui, "Sound", None);
-                if pipe.user_data.features.spatial_chat {
-                    ui.horizontal(|ui| {
-                        ui.add_space(8.0);
-                        ui.with_layout(
-                            Layout::top_down(egui::Align::Min).with_cross_justify(true),
-                            |ui| {
+                ui.horizontal(|ui| {
+                    ui.add_space(8.0);
+                    ui.with_layout(
+                        Layout::top_down(egui::Align::Min).with_cross_justify(true),
+                        |ui| {
+                            add_btn(ui, "Chat Notifications", Some("Sound"));
+                            if pipe.user_data.features.spatial_chat {
                                 add_btn(ui, "Spatial Chat", Some("Sound"));
-                            },
-                        );
-                    });
-                }
+                            }
+                        },
+                    );
+                });
                 ui.style_mut().spacing.item_spacing.y = old_spacing_y;
 
                 ui.add_space(10.0);
@@ -397,6 +397,10 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
                     "Spectate list:",
                     BindAction::LocalPlayer(BindActionsLocalPlayer::ShowSpectatorSelection),
                 );
+                inp(
+                    "Toggle ghost:",
+                    BindAction::LocalPlayer(BindActionsLocalPlayer::ToggleGhost),
+                );
                 /*inp("Spectate next:", BindActions::LocalPlayer(BindActionsLocalPlayer::SpectateNext));
                 inp("Spectate previous:", BindActions::LocalPlayer(BindActionsLocalPlayer::SpectatePrev));
                 inp("Client console:", BindActions::LocalPlayer(BindActionsLocalPlayer::LocalConsole));
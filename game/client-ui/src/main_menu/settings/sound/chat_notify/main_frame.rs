@@ -0,0 +1,62 @@
+use egui::{Grid, Layout, ScrollArea, TextEdit};
+use game_config::config::ConfigChatNotifyRule;
+use ui_base::types::UiRenderPipe;
+
+use crate::main_menu::user_data::UserData;
+
+pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
+    ui.with_layout(Layout::top_down(egui::Align::Min), |ui| {
+        let rules = &mut pipe.user_data.config.game.cl.chat.notify_rules;
+
+        ui.label(
+            "Chat messages of other players that contain one of the keywords \
+            are highlighted and play a notification sound.",
+        );
+        ui.label("An empty keyword matches your own name.");
+        ui.add_space(5.0);
+
+        let mut remove = None;
+        ScrollArea::vertical()
+            .max_height(ui.available_height() - 30.0)
+            .show(ui, |ui| {
+                Grid::new("chat-notify-rules")
+                    .num_columns(5)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("Keyword");
+                        ui.label("Ingame");
+                        ui.label("In menu");
+                        ui.label("Mute when focused");
+                        ui.end_row();
+
+                        for (index, rule) in rules.iter_mut().enumerate() {
+                            ui.add(
+                                TextEdit::singleline(&mut rule.keyword)
+                                    .char_limit(64)
+                                    .hint_text("Own name"),
+                            );
+                            ui.checkbox(&mut rule.ingame, "");
+                            ui.checkbox(&mut rule.menu, "");
+                            ui.checkbox(&mut rule.mute_when_focused, "");
+                            if ui.button("\u{f1f8}").on_hover_text("Remove rule").clicked() {
+                                remove = Some(index);
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+        if let Some(index) = remove {
+            rules.remove(index);
+        }
+
+        if ui
+            .add_enabled(rules.len() < 32, egui::Button::new("Add rule"))
+            .clicked()
+        {
+            rules.push(ConfigChatNotifyRule {
+                keyword: "keyword".to_string(),
+                ..Default::default()
+            });
+        }
+    });
+}
@@ -22,6 +22,9 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
         "Spatial Chat" => {
             super::spatial_chat::main_frame::render(ui, pipe);
         }
+        "Chat Notifications" => {
+            super::chat_notify::main_frame::render(ui, pipe);
+        }
         // Sound itself is selected
         _ => {
             ui.with_layout(Layout::top_down(egui::Align::Min), |ui| {
@@ -47,6 +50,13 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
                                 .max_decimals(2),
                         );
                         ui.end_row();
+
+                        ui.label("Notification sound volume:");
+                        ui.add(
+                            Slider::new(&mut config.render.notification_sound_volume, 0.0..=1.0)
+                                .max_decimals(2),
+                        );
+                        ui.end_row();
                     });
             });
         }
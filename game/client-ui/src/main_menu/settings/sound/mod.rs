@@ -1,3 +1,4 @@
+pub mod chat_notify;
 pub mod main_frame;
 pub mod spatial_chat;
 pub mod utils;
@@ -0,0 +1,292 @@
+pub mod main_frame;
+
+use game_config::config::{Config, ConfigGraphicsPreset};
+use game_interface::types::character_info::MAX_CHARACTER_NAME_LEN;
+use serde::{Deserialize, Serialize};
+
+use super::constants::{MENU_INTERNET_NAME, MENU_UI_PAGE_QUERY};
+
+/// The ui storage key of the current [`SetupWizardStep`].
+///
+/// The setup wizard is shown as long as this key exists.
+pub const SETUP_WIZARD_STORAGE: &str = "setup-wizard";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SetupWizardStep {
+    #[default]
+    Name,
+    Skin,
+    Controls,
+    Graphics,
+}
+
+impl SetupWizardStep {
+    pub const ALL: [Self; 4] = [Self::Name, Self::Skin, Self::Controls, Self::Graphics];
+
+    pub fn next(self) -> Option<Self> {
+        match self {
+            Self::Name => Some(Self::Skin),
+            Self::Skin => Some(Self::Controls),
+            Self::Controls => Some(Self::Graphics),
+            Self::Graphics => None,
+        }
+    }
+
+    pub fn prev(self) -> Option<Self> {
+        match self {
+            Self::Name => None,
+            Self::Skin => Some(Self::Name),
+            Self::Controls => Some(Self::Skin),
+            Self::Graphics => Some(Self::Controls),
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Name => "Name",
+            Self::Skin => "Skin",
+            Self::Controls => "Controls",
+            Self::Graphics => "Graphics",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayerNameError {
+    Empty,
+    TooLong,
+    SurroundingWhitespace,
+    ControlCharacter,
+}
+
+impl PlayerNameError {
+    pub fn message(&self) -> String {
+        match self {
+            Self::Empty => "The name must not be empty.".to_string(),
+            Self::TooLong => {
+                format!("The name must not be longer than {MAX_CHARACTER_NAME_LEN} characters.")
+            }
+            Self::SurroundingWhitespace => {
+                "The name must not start or end with a space.".to_string()
+            }
+            Self::ControlCharacter => "The name contains invalid characters.".to_string(),
+        }
+    }
+}
+
+/// Checks if the name can be used as player name on servers.
+pub fn validate_player_name(name: &str) -> Result<(), PlayerNameError> {
+    if name.trim().is_empty() {
+        Err(PlayerNameError::Empty)
+    } else if name.chars().count() > MAX_CHARACTER_NAME_LEN {
+        Err(PlayerNameError::TooLong)
+    } else if name.trim() != name {
+        Err(PlayerNameError::SurroundingWhitespace)
+    } else if name.chars().any(|c| c.is_control()) {
+        Err(PlayerNameError::ControlCharacter)
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ControlsPreset {
+    /// The default binds of the client.
+    #[default]
+    Classic,
+    /// Everything important is on the mouse, only a few
+    /// keys near the right hand are used.
+    OneHanded,
+    /// Keeps the current binds, they can be changed in the settings.
+    Custom,
+}
+
+impl ControlsPreset {
+    pub const ALL: [Self; 3] = [Self::Classic, Self::OneHanded, Self::Custom];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Classic => "Classic",
+            Self::OneHanded => "One-handed",
+            Self::Custom => "Custom",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Classic => "Move with A & D, jump with space, fire & hook with the mouse.",
+            Self::OneHanded => {
+                "Move with the side buttons of the mouse, jump with the middle mouse button."
+            }
+            Self::Custom => "Keep the current binds & change them later in the settings.",
+        }
+    }
+
+    /// The binds of the preset.
+    ///
+    /// An empty list makes the client use its default binds,
+    /// `None` keeps the current binds.
+    pub fn binds(self) -> Option<Vec<String>> {
+        match self {
+            Self::Classic => Some(Vec::new()),
+            Self::OneHanded => Some(
+                [
+                    "bind mouse_left +fire",
+                    "bind mouse_right +hook",
+                    "bind mouse_middle +jump",
+                    "bind mouse_back +left",
+                    "bind mouse_forward +right",
+                    "bind wheel_up +nextweapon",
+                    "bind wheel_down +prevweapon",
+                    "bind space +jump",
+                    "bind escape ingame_menu",
+                    "bind enter chat_all",
+                    "bind tab +scoreboard",
+                ]
+                .into_iter()
+                .map(|bind| bind.to_string())
+                .collect(),
+            ),
+            Self::Custom => None,
+        }
+    }
+}
+
+/// Seeds the binds of the main player with the binds of the preset.
+pub fn apply_controls_preset(config: &mut Config, preset: ControlsPreset) {
+    let Some(binds) = preset.binds() else {
+        return;
+    };
+    let main = config.game.profiles.main as usize;
+    if let Some(player) = config.game.players.get_mut(main) {
+        player.binds = binds;
+    }
+}
+
+/// Shows the setup wizard, starting with the first step.
+pub fn launch_setup_wizard(config: &mut Config) {
+    config.set_storage(SETUP_WIZARD_STORAGE, &SetupWizardStep::default());
+}
+
+/// Closes the setup wizard & lands on the server browser.
+///
+/// Everything the wizard did not change keeps its defaults,
+/// so this is also used to skip the wizard.
+pub fn close_setup_wizard(config: &mut Config) {
+    config.rem_storage(SETUP_WIZARD_STORAGE);
+    config.path().add_query((
+        MENU_UI_PAGE_QUERY.to_string(),
+        MENU_INTERNET_NAME.to_string(),
+    ));
+}
+
+/// Applies the choices of the wizard & closes it.
+pub fn finish_setup_wizard(
+    config: &mut Config,
+    controls: ControlsPreset,
+    graphics: ConfigGraphicsPreset,
+) {
+    apply_controls_preset(config, controls);
+    if graphics != config.game.cl.graphics_preset {
+        config
+            .game
+            .apply_graphics_preset(&mut config.engine, graphics);
+    }
+    close_setup_wizard(config);
+}
+
+#[cfg(test)]
+mod test {
+    use game_config::config::{Config, ConfigGraphicsPreset};
+
+    use crate::main_menu::constants::{MENU_INTERNET_NAME, MENU_UI_PAGE_QUERY};
+
+    use super::{
+        ControlsPreset, PlayerNameError, SETUP_WIZARD_STORAGE, SetupWizardStep,
+        finish_setup_wizard, launch_setup_wizard, validate_player_name,
+    };
+
+    #[test]
+    fn name_validation() {
+        assert_eq!(validate_player_name("nameless tee"), Ok(()));
+        assert_eq!(validate_player_name("ü∂ tee"), Ok(()));
+        // exactly the max length, counted in characters
+        assert_eq!(validate_player_name(&"ü".repeat(16)), Ok(()));
+
+        assert_eq!(validate_player_name(""), Err(PlayerNameError::Empty));
+        assert_eq!(validate_player_name("   "), Err(PlayerNameError::Empty));
+        assert_eq!(
+            validate_player_name(&"a".repeat(17)),
+            Err(PlayerNameError::TooLong)
+        );
+        assert_eq!(
+            validate_player_name(" tee"),
+            Err(PlayerNameError::SurroundingWhitespace)
+        );
+        assert_eq!(
+            validate_player_name("tee "),
+            Err(PlayerNameError::SurroundingWhitespace)
+        );
+        assert_eq!(
+            validate_player_name("te\ne"),
+            Err(PlayerNameError::ControlCharacter)
+        );
+    }
+
+    #[test]
+    fn preset_side_effects() {
+        let mut config = Config::default();
+        let main = config.game.profiles.main as usize;
+        config.game.players[main].binds = vec!["bind a +left".to_string()];
+
+        launch_setup_wizard(&mut config);
+        assert_eq!(
+            config.storage_opt::<SetupWizardStep>(SETUP_WIZARD_STORAGE),
+            Some(SetupWizardStep::Name)
+        );
+
+        // custom keeps the binds & the same preset keeps the settings
+        let msaa_samples = config.engine.gl.msaa_samples;
+        let preset = config.game.cl.graphics_preset;
+        finish_setup_wizard(&mut config, ControlsPreset::Custom, preset);
+        assert_eq!(config.game.players[main].binds, vec!["bind a +left"]);
+        assert_eq!(config.engine.gl.msaa_samples, msaa_samples);
+        // closed & landed in the server browser
+        assert!(
+            config
+                .storage_opt::<SetupWizardStep>(SETUP_WIZARD_STORAGE)
+                .is_none()
+        );
+        assert_eq!(
+            config
+                .path()
+                .query
+                .get(MENU_UI_PAGE_QUERY)
+                .map(|page| page.as_str()),
+            Some(MENU_INTERNET_NAME)
+        );
+
+        // one-handed seeds the binds
+        launch_setup_wizard(&mut config);
+        finish_setup_wizard(
+            &mut config,
+            ControlsPreset::OneHanded,
+            ConfigGraphicsPreset::Low,
+        );
+        assert!(
+            config.game.players[main]
+                .binds
+                .contains(&"bind mouse_middle +jump".to_string())
+        );
+        assert_eq!(config.game.cl.graphics_preset, ConfigGraphicsPreset::Low);
+
+        // classic lets the client apply its default binds
+        finish_setup_wizard(
+            &mut config,
+            ControlsPreset::Classic,
+            ConfigGraphicsPreset::High,
+        );
+        assert!(config.game.players[main].binds.is_empty());
+        assert_eq!(config.game.cl.graphics_preset, ConfigGraphicsPreset::High);
+    }
+}
@@ -0,0 +1,214 @@
+use egui::{Button, Color32, ComboBox, Frame, Layout, RichText, UiBuilder};
+use game_config::config::ConfigGraphicsPreset;
+use game_interface::types::character_info::{MAX_CHARACTER_NAME_LEN, NetworkSkinInfo};
+use tracing::instrument;
+use ui_base::{
+    components::clearable_edit_field::clearable_edit_field,
+    types::{UiRenderPipe, UiState},
+};
+
+use crate::{
+    events::UiEvent,
+    main_menu::{settings::player::tee::main_frame::render_skin, user_data::UserData},
+};
+
+use super::{
+    ControlsPreset, SETUP_WIZARD_STORAGE, SetupWizardStep, close_setup_wizard, finish_setup_wizard,
+    validate_player_name,
+};
+
+/// The ui storage key of the controls preset picked in the wizard.
+const SETUP_WIZARD_CONTROLS_STORAGE: &str = "setup-wizard-controls";
+
+fn render_name(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) -> bool {
+    ui.label("How should other players see you?");
+    ui.add_space(5.0);
+    let config = &mut pipe.user_data.config.game;
+    let player = &mut config.players[config.profiles.main as usize];
+    if clearable_edit_field(
+        ui,
+        &mut player.name,
+        Some(200.0),
+        Some(MAX_CHARACTER_NAME_LEN),
+    )
+    .is_some_and(|i| i.changed())
+    {
+        pipe.user_data
+            .player_settings_sync
+            .set_player_info_changed();
+    }
+    match validate_player_name(&player.name) {
+        Ok(()) => {
+            ui.label("");
+            true
+        }
+        Err(err) => {
+            ui.colored_label(Color32::RED, err.message());
+            false
+        }
+    }
+}
+
+fn render_skin_step(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &mut UiState) {
+    let config = &mut pipe.user_data.config.game;
+    let player = &mut config.players[config.profiles.main as usize];
+    let name = player.skin.name.clone();
+    let eye = player.eyes;
+    let skin_info: NetworkSkinInfo = (&player.skin).into();
+    let player_settings_sync = pipe.user_data.player_settings_sync;
+    render_skin(
+        ui,
+        pipe.user_data.canvas_handle,
+        pipe.user_data.skin_container,
+        pipe.user_data.render_tee,
+        ui_state,
+        &mut pipe.user_data.config.engine,
+        || {
+            player_settings_sync.set_player_info_changed();
+        },
+        &name,
+        |name| player.skin.name = name,
+        eye,
+        |eye| {
+            if player.eyes != eye {
+                player_settings_sync.set_player_info_changed();
+            }
+            player.eyes = eye;
+        },
+        skin_info,
+        player.skin.custom_colors,
+        player.skin.body_color,
+        player.skin.feet_color,
+        |custom_colors, body_color, feet_color| {
+            if player.skin.custom_colors != custom_colors
+                || player.skin.body_color != body_color
+                || player.skin.feet_color != feet_color
+            {
+                player_settings_sync.set_player_info_changed();
+            }
+            player.skin.custom_colors = custom_colors;
+            player.skin.body_color = body_color;
+            player.skin.feet_color = feet_color;
+        },
+    );
+}
+
+fn render_controls(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
+    ui.label("Which controls do you want to start with?");
+    ui.add_space(5.0);
+    let config = &mut *pipe.user_data.config;
+    let mut preset = config.storage::<ControlsPreset>(SETUP_WIZARD_CONTROLS_STORAGE);
+    for preset_option in ControlsPreset::ALL {
+        ui.radio_value(&mut preset, preset_option, preset_option.name());
+        ui.label(RichText::new(preset_option.description()).weak());
+        ui.add_space(5.0);
+    }
+    config.set_storage(SETUP_WIZARD_CONTROLS_STORAGE, &preset);
+}
+
+fn render_graphics(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
+    let detected = ConfigGraphicsPreset::detect(&pipe.user_data.backend_handle.gpus().cur);
+    ui.label(format!(
+        "The recommended preset for your graphics card is {detected:?}."
+    ));
+    ui.add_space(5.0);
+    let config = &mut *pipe.user_data.config;
+    let mut preset = config.game.cl.graphics_preset;
+    ComboBox::new("setup-wizard-graphics-preset", "")
+        .selected_text(format!("{preset:?}"))
+        .show_ui(ui, |ui| {
+            for preset_option in [
+                ConfigGraphicsPreset::Low,
+                ConfigGraphicsPreset::Medium,
+                ConfigGraphicsPreset::High,
+            ] {
+                let text = if preset_option == detected {
+                    format!("{preset_option:?} (recommended)")
+                } else {
+                    format!("{preset_option:?}")
+                };
+                ui.selectable_value(&mut preset, preset_option, text);
+            }
+        });
+    if preset != config.game.cl.graphics_preset {
+        config
+            .game
+            .apply_graphics_preset(&mut config.engine, preset);
+        pipe.user_data.events.push(UiEvent::MsaaChanged);
+    }
+}
+
+/// Guides new players through the most important settings,
+/// shown instead of the main menu.
+#[instrument(level = "trace", skip_all)]
+pub fn render(
+    ui: &mut egui::Ui,
+    pipe: &mut UiRenderPipe<UserData>,
+    ui_state: &mut UiState,
+    step: SetupWizardStep,
+) {
+    Frame::default()
+        .fill(ui.style().visuals.window_fill)
+        .inner_margin(20.0)
+        .show(ui, |ui| {
+            ui.set_min_size(ui.available_size());
+            ui.heading("Welcome!");
+            ui.horizontal(|ui| {
+                for (index, step_option) in SetupWizardStep::ALL.into_iter().enumerate() {
+                    let text = format!("{}. {}", index + 1, step_option.title());
+                    if step_option == step {
+                        ui.strong(text);
+                    } else {
+                        ui.weak(text);
+                    }
+                }
+            });
+            ui.separator();
+
+            let mut can_continue = true;
+            let rect = ui.available_rect_before_wrap();
+            let content_rect = rect.with_max_y((rect.max.y - 30.0).max(rect.min.y));
+            ui.scope_builder(UiBuilder::new().max_rect(content_rect), |ui| {
+                ui.with_layout(Layout::top_down(egui::Align::Min), |ui| match step {
+                    SetupWizardStep::Name => can_continue = render_name(ui, pipe),
+                    SetupWizardStep::Skin => render_skin_step(ui, pipe, ui_state),
+                    SetupWizardStep::Controls => render_controls(ui, pipe),
+                    SetupWizardStep::Graphics => render_graphics(ui, pipe),
+                })
+            });
+
+            let buttons_rect = rect.with_min_y(content_rect.max.y);
+            ui.scope_builder(UiBuilder::new().max_rect(buttons_rect), |ui| {
+                ui.horizontal(|ui| {
+                    let config = &mut *pipe.user_data.config;
+                    if ui.button("Skip").clicked() {
+                        config.rem_storage(SETUP_WIZARD_CONTROLS_STORAGE);
+                        close_setup_wizard(config);
+                    }
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        match step.next() {
+                            Some(next) => {
+                                if ui.add_enabled(can_continue, Button::new("Next")).clicked() {
+                                    config.set_storage(SETUP_WIZARD_STORAGE, &next);
+                                }
+                            }
+                            None => {
+                                if ui.button("Finish").clicked() {
+                                    let controls = config
+                                        .storage::<ControlsPreset>(SETUP_WIZARD_CONTROLS_STORAGE);
+                                    config.rem_storage(SETUP_WIZARD_CONTROLS_STORAGE);
+                                    let graphics = config.game.cl.graphics_preset;
+                                    finish_setup_wizard(config, controls, graphics);
+                                }
+                            }
+                        }
+                        if let Some(prev) = step.prev()
+                            && ui.button("Back").clicked()
+                        {
+                            config.set_storage(SETUP_WIZARD_STORAGE, &prev);
+                        }
+                    });
+                });
+            });
+        });
+}
@@ -0,0 +1,220 @@
+use egui::{Color32, ComboBox, Frame, Grid, Rect, RichText, ScrollArea, Sense, pos2, vec2};
+use game_base::local_stats::{LOCAL_STATS_DAYS, LocalStatsCounters};
+use tracing::instrument;
+use ui_base::{style::bg_frame_color, types::UiRenderPipe};
+
+use crate::main_menu::{constants::MENU_STATS_NAME, user_data::UserData};
+
+use super::{LocalStatsState, format_playtime};
+
+type ChartValue<'a> = (&'a str, Color32, fn(&LocalStatsCounters) -> f64);
+
+/// One group of bars per day, oldest day first.
+fn bar_chart(
+    ui: &mut egui::Ui,
+    title: &str,
+    days: &[LocalStatsCounters],
+    values: &[ChartValue],
+    fmt_value: impl Fn(f64) -> String,
+) {
+    ui.horizontal(|ui| {
+        ui.label(title);
+        for (name, color, _) in values {
+            ui.label(RichText::new(format!("\u{25a0} {name}")).color(*color));
+        }
+    });
+    let (rect, res) = ui.allocate_exact_size(vec2(ui.available_width(), 80.0), Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 5.0, Color32::from_black_alpha(50));
+
+    let max = days
+        .iter()
+        .flat_map(|day| values.iter().map(|(_, _, value)| value(day)))
+        .fold(0.0, f64::max);
+    let day_width = rect.width() / days.len().max(1) as f32;
+    let bar_width = (day_width - 2.0).max(1.0) / values.len().max(1) as f32;
+    if max > 0.0 {
+        for (index, day) in days.iter().enumerate() {
+            for (value_index, (_, color, value)) in values.iter().enumerate() {
+                let height = (value(day) / max) as f32 * (rect.height() - 4.0);
+                let x =
+                    rect.min.x + index as f32 * day_width + 1.0 + value_index as f32 * bar_width;
+                painter.rect_filled(
+                    Rect::from_min_max(
+                        pos2(x, rect.max.y - height),
+                        pos2(x + bar_width, rect.max.y),
+                    ),
+                    0.0,
+                    *color,
+                );
+            }
+        }
+    }
+
+    if let Some(pos) = res.hover_pos() {
+        let index = (((pos.x - rect.min.x) / day_width) as usize).min(days.len() - 1);
+        let day = &days[index];
+        let days_ago = days.len() - 1 - index;
+        res.on_hover_ui_at_pointer(|ui| {
+            ui.label(match days_ago {
+                0 => "Today".to_string(),
+                1 => "Yesterday".to_string(),
+                days_ago => format!("{days_ago} days ago"),
+            });
+            for (name, _, value) in values {
+                ui.label(format!("{name}: {}", fmt_value(value(day))));
+            }
+        });
+    }
+}
+
+fn render_totals(ui: &mut egui::Ui, total: &LocalStatsCounters) {
+    Grid::new("local-stats-totals")
+        .num_columns(2)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("Time played:");
+            ui.label(format_playtime(total.playtime));
+            ui.end_row();
+            ui.label("Kills:");
+            ui.label(total.kills.to_string());
+            ui.end_row();
+            ui.label("Deaths:");
+            ui.label(total.deaths.to_string());
+            ui.end_row();
+            ui.label("K/D:");
+            ui.label(format!("{:.2}", total.kd()));
+            ui.end_row();
+            ui.label("Captures:");
+            ui.label(total.captures.to_string());
+            ui.end_row();
+            ui.label("Accuracy:");
+            ui.label(
+                total
+                    .accuracy()
+                    .map(|accuracy| format!("{:.1}%", accuracy * 100.0))
+                    .unwrap_or_else(|| "-".to_string()),
+            );
+            ui.end_row();
+            for (weapon, accuracy) in &total.weapons {
+                ui.label(format!("    {weapon:?}:"));
+                ui.label(match accuracy.accuracy() {
+                    Some(value) => format!(
+                        "{:.1}% ({} hits, {} shots)",
+                        value * 100.0,
+                        accuracy.hits,
+                        accuracy.shots
+                    ),
+                    None => format!("{} hits", accuracy.hits),
+                });
+                ui.end_row();
+            }
+        });
+}
+
+/// The stats of the local player, per server & over all servers.
+#[instrument(level = "trace", skip_all)]
+pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, cur_page: &str) {
+    if cur_page != MENU_STATS_NAME {
+        return;
+    }
+    Frame::default()
+        .fill(bg_frame_color())
+        .corner_radius(5.0)
+        .inner_margin(10.0)
+        .show(ui, |ui| {
+            ui.set_min_size(ui.available_size());
+            let local_stats = pipe.user_data.local_stats;
+            let (stats, today) = match local_stats {
+                LocalStatsState::Loading => {
+                    // e.g. if the page was still open from the last start
+                    pipe.user_data.main_menu.refresh_local_stats();
+                    ui.label("Loading the stats\u{2026}");
+                    return;
+                }
+                LocalStatsState::Err(err) => {
+                    ui.colored_label(Color32::RED, format!("The stats could not be read: {err}"));
+                    return;
+                }
+                LocalStatsState::Loaded { stats, today } => (stats, *today),
+            };
+            ui.label(
+                RichText::new("Your stats are only saved on this computer & never sent anywhere.")
+                    .weak(),
+            );
+            if stats.servers.is_empty() {
+                ui.label("Play on a server to collect stats.");
+                return;
+            }
+
+            let config = &mut *pipe.user_data.config;
+            let mut server = config
+                .storage::<Option<String>>("local-stats-server")
+                .filter(|addr| stats.servers.contains_key(addr));
+            let server_name = |addr: &str| {
+                stats
+                    .servers
+                    .get(addr)
+                    .filter(|server| !server.name.is_empty())
+                    .map(|server| format!("{} ({addr})", server.name))
+                    .unwrap_or_else(|| addr.to_string())
+            };
+            ui.horizontal(|ui| {
+                ui.label("Server:");
+                ComboBox::new("local-stats-server", "")
+                    .selected_text(
+                        server
+                            .as_deref()
+                            .map(server_name)
+                            .unwrap_or_else(|| "All servers".to_string()),
+                    )
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut server, None, "All servers");
+                        for addr in stats.servers.keys() {
+                            ui.selectable_value(&mut server, Some(addr.clone()), server_name(addr));
+                        }
+                    });
+            });
+            config.set_storage("local-stats-server", &server);
+            ui.separator();
+
+            ScrollArea::vertical().show(ui, |ui| {
+                ui.heading("All time");
+                render_totals(ui, &stats.total(server.as_deref()));
+                ui.add_space(10.0);
+
+                ui.heading(format!("Last {LOCAL_STATS_DAYS} days"));
+                let days = stats.daily(server.as_deref(), today);
+                bar_chart(
+                    ui,
+                    "Time played",
+                    &days,
+                    &[("Minutes", Color32::LIGHT_BLUE, |day| {
+                        day.playtime.as_secs_f64() / 60.0
+                    })],
+                    |minutes| format_playtime(std::time::Duration::from_secs_f64(minutes * 60.0)),
+                );
+                ui.add_space(5.0);
+                bar_chart(
+                    ui,
+                    "Kills & deaths",
+                    &days,
+                    &[
+                        ("Kills", Color32::LIGHT_GREEN, |day| day.kills as f64),
+                        ("Deaths", Color32::LIGHT_RED, |day| day.deaths as f64),
+                    ],
+                    |value| format!("{value:.0}"),
+                );
+                ui.add_space(5.0);
+                bar_chart(
+                    ui,
+                    "Accuracy",
+                    &days,
+                    &[("Accuracy", Color32::GOLD, |day| {
+                        day.accuracy().unwrap_or_default() * 100.0
+                    })],
+                    |value| format!("{value:.1}%"),
+                );
+            });
+        });
+}
@@ -0,0 +1,29 @@
+pub mod main_frame;
+
+use std::time::Duration;
+
+use game_base::local_stats::LocalStats;
+
+#[derive(Debug, Default)]
+pub enum LocalStatsState {
+    /// Not read yet, or still reading.
+    #[default]
+    Loading,
+    Loaded {
+        stats: LocalStats,
+        /// The day the stats were read, see
+        /// [`game_base::local_stats::stats_day`].
+        today: u32,
+    },
+    Err(String),
+}
+
+/// E.g. `3h 05min`.
+pub fn format_playtime(playtime: Duration) -> String {
+    let minutes = playtime.as_secs() / 60;
+    if minutes >= 60 {
+        format!("{}h {:02}min", minutes / 60, minutes % 60)
+    } else {
+        format!("{minutes}min")
+    }
+}
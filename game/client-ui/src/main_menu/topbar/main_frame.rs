@@ -14,8 +14,8 @@ use ui_base::{
 
 use crate::events::UiEvents;
 use crate::main_menu::constants::{
-    MENU_COMMUNITY_PREFIX, MENU_EXPLORE_COMMUNITIES_NAME, MENU_FAVORITES_NAME, MENU_INTERNET_NAME,
-    MENU_LAN_NAME, MENU_PROFILE_NAME, MENU_SETTINGS_NAME,
+    MENU_COMMUNITY_PREFIX, MENU_EXPLORE_COMMUNITIES_NAME, MENU_FAVORITES_NAME, MENU_HISTORY_NAME,
+    MENU_INTERNET_NAME, MENU_LAN_NAME, MENU_PROFILE_NAME, MENU_SETTINGS_NAME, MENU_STATS_NAME,
 };
 use crate::main_menu::user_data::MainMenuInterface;
 use crate::main_menu::user_data::UserData;
@@ -61,6 +61,14 @@ pub fn render_right_buttons(
                 .path()
                 .add_query((query_name.to_string(), MENU_DEMO_NAME.to_string()));
         }
+        if menu_top_button_icon(ui, MenuTopButtonProps::new(MENU_STATS_NAME, current_active))
+            .clicked()
+        {
+            main_menu.refresh_local_stats();
+            config
+                .path()
+                .add_query((query_name.to_string(), MENU_STATS_NAME.to_string()));
+        }
         if menu_top_button_icon(ui, MenuTopButtonProps::new("\u{f279}", current_active)).clicked() {
             events.push(UiEvent::StartEditor)
         }
@@ -122,6 +130,12 @@ pub fn render(
                                     "Change the settings of your client here.",
                                 );
                             }
+                            MENU_STATS_NAME => {
+                                ui.label("Stats \u{f05a}").on_hover_text_at_pointer(
+                                    "Your kills, deaths, accuracy & time played, \
+                                            only saved on this computer.",
+                                );
+                            }
                             MENU_PROFILE_NAME => {
                                 ui.label("Profiles \u{f05a}").on_hover_text_at_pointer(
                                     "Here you can manage your accounts, \
@@ -134,6 +148,12 @@ pub fn render(
                                             that you marked with a \u{f005}.",
                                 );
                             }
+                            MENU_HISTORY_NAME => {
+                                ui.label("History \u{f05a}").on_hover_text_at_pointer(
+                                    "The history tab shows the last servers \
+                                            you connected to.",
+                                );
+                            }
                             MENU_EXPLORE_COMMUNITIES_NAME => {
                                 ui.label("Explore communities \u{f05a}")
                                     .on_hover_text_at_pointer(
@@ -1,4 +1,4 @@
-use std::{path::Path, sync::Arc};
+use std::{net::SocketAddr, path::Path, sync::Arc, time::Duration};
 
 use base::network_string::NetworkReducedAsciiString;
 use base_io::{io::Io, runtime::IoRuntimeTask};
@@ -43,12 +43,14 @@ use super::{
     ddnet_info::DdnetInfo,
     demo_list::DemoList,
     features::EnabledFeatures,
+    game_files::{GameFilesCheck, GameFilesState},
     monitors::UiMonitors,
     player_settings_ntfy::PlayerSettingsSync,
     profiles_interface::{
         AccountInfo, AccountTokenError, CredentialAuthTokenError, ProfileData, ProfilesInterface,
     },
     spatial_chat::SpatialChat,
+    stats::LocalStatsState,
     theme_container::ThemeContainer,
 };
 
@@ -59,11 +61,28 @@ pub struct RenderOptions {
 
 pub trait MainMenuInterface {
     fn refresh(&mut self);
+    /// Requests the live info of a single server,
+    /// ignored if [`MainMenuInterface::can_refresh_server_info`] is `false`.
+    fn refresh_server_info(&mut self, addr: SocketAddr, cur_time: &Duration);
+    /// The live info of a server can only be requested every few seconds.
+    fn can_refresh_server_info(&self, cur_time: &Duration) -> bool;
 
     fn refresh_demo_list(&mut self, path: &Path);
     /// A path of `None` here means that a directory is selected
     /// or the selection otherwise got removed.
     fn refresh_demo_info(&mut self, file: Option<&Path>);
+
+    /// Reads the stats of the local player, that the client saved.
+    ///
+    /// Ignored while the stats are still being read.
+    fn refresh_local_stats(&mut self);
+
+    /// Checks the game files against the manifest of the build.
+    fn verify_game_files(&mut self);
+    /// Repairs the mismatched files of the last check,
+    /// see [`GameFilesCheck::repair`].
+    fn repair_game_files(&mut self, download: bool);
+    fn game_files_state(&self) -> GameFilesState;
 }
 
 #[derive(Debug, Clone)]
@@ -596,10 +615,13 @@ pub struct UserData<'a> {
 
     pub ddnet_info: &'a DdnetInfo,
     pub icons: &'a mut ThumbnailContainer,
+    pub map_thumbnails: &'a mut ThumbnailContainer,
 
     pub demos: &'a DemoList,
     pub demo_info: &'a Option<(DemoHeader, DemoHeaderExt)>,
 
+    pub local_stats: &'a LocalStatsState,
+
     pub render_options: RenderOptions,
 
     pub main_menu: &'a mut dyn MainMenuInterface,
@@ -0,0 +1,469 @@
+//! A restricted markup for server messages like the message of the day
+//! or broadcasts.
+//!
+//! Supported tags are `[b]`, `[color=red]` or `[color=#ff0000]`,
+//! `[center]` and `[url]https://...[/url]` or `[url=https://...]text[/url]`.
+//! Unknown, unclosed or misnested tags are kept as literal text.
+
+use egui::{Align, Color32, Layout, RichText};
+
+/// Tags nested deeper than this are kept as literal text.
+pub const MAX_MARKUP_DEPTH: usize = 8;
+/// Links longer than this are not considered safe.
+const MAX_LINK_LEN: usize = 256;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarkupStyle {
+    pub bold: bool,
+    pub color: Option<Color32>,
+    /// Only contains links that passed [`safe_link`].
+    pub link: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkupSpan {
+    pub text: String,
+    pub style: MarkupStyle,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarkupLine {
+    pub spans: Vec<MarkupSpan>,
+    pub centered: bool,
+}
+
+/// Only absolute http(s) links without whitespace are allowed,
+/// everything else is rendered as text.
+pub fn safe_link(url: &str) -> Option<&str> {
+    let rest = ["https://", "http://"].iter().find_map(|scheme| {
+        url.get(..scheme.len())
+            .filter(|prefix| prefix.eq_ignore_ascii_case(scheme))
+            .map(|_| &url[scheme.len()..])
+    })?;
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    (!host.is_empty()
+        && url.len() <= MAX_LINK_LEN
+        && !url.chars().any(|c| c.is_whitespace() || c.is_control()))
+    .then_some(url)
+}
+
+fn parse_color(color: &str) -> Option<Color32> {
+    if let Some(hex) = color.strip_prefix('#') {
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+        return Some(Color32::from_rgb(channel(0)?, channel(2)?, channel(4)?));
+    }
+    Some(match color.to_ascii_lowercase().as_str() {
+        "red" => Color32::RED,
+        "green" => Color32::GREEN,
+        "blue" => Color32::LIGHT_BLUE,
+        "yellow" => Color32::YELLOW,
+        "orange" => Color32::ORANGE,
+        "gold" => Color32::GOLD,
+        "purple" => Color32::from_rgb(180, 120, 255),
+        "white" => Color32::WHITE,
+        "gray" | "grey" => Color32::GRAY,
+        "black" => Color32::BLACK,
+        _ => return None,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TagName {
+    Bold,
+    Color,
+    Center,
+    Url,
+}
+
+#[derive(Debug, Clone)]
+enum Tag {
+    Bold,
+    Color(Color32),
+    Center,
+    /// `None` if the link is the text inside the tag.
+    Url(Option<String>),
+}
+
+impl Tag {
+    fn name(&self) -> TagName {
+        match self {
+            Tag::Bold => TagName::Bold,
+            Tag::Color(_) => TagName::Color,
+            Tag::Center => TagName::Center,
+            Tag::Url(_) => TagName::Url,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Token<'a> {
+    Text(&'a str),
+    Open(Tag, &'a str),
+    Close(TagName, &'a str),
+}
+
+impl<'a> Token<'a> {
+    fn raw(&self) -> &'a str {
+        match self {
+            Token::Text(raw) | Token::Open(_, raw) | Token::Close(_, raw) => raw,
+        }
+    }
+}
+
+fn parse_tag<'a>(content: &str, raw: &'a str) -> Option<Token<'a>> {
+    let (name, arg) = match content.split_once('=') {
+        Some((name, arg)) => (name, Some(arg)),
+        None => (content, None),
+    };
+    let (close, name) = match name.strip_prefix('/') {
+        Some(name) => (true, name),
+        None => (false, name),
+    };
+    let name = match name.to_ascii_lowercase().as_str() {
+        "b" => TagName::Bold,
+        "color" => TagName::Color,
+        "center" => TagName::Center,
+        "url" => TagName::Url,
+        _ => return None,
+    };
+    if close {
+        return arg.is_none().then_some(Token::Close(name, raw));
+    }
+    let tag = match (name, arg) {
+        (TagName::Bold, None) => Tag::Bold,
+        (TagName::Color, Some(color)) => Tag::Color(parse_color(color)?),
+        (TagName::Center, None) => Tag::Center,
+        (TagName::Url, None) => Tag::Url(None),
+        (TagName::Url, Some(url)) => Tag::Url(Some(safe_link(url)?.to_string())),
+        _ => return None,
+    };
+    Some(Token::Open(tag, raw))
+}
+
+fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut text_start = 0;
+    let mut search_start = 0;
+    while let Some(open) = text[search_start..].find('[').map(|i| i + search_start) {
+        let Some(close) = text[open + 1..].find(']').map(|i| i + open + 1) else {
+            break;
+        };
+        // the content might contain another opening bracket
+        let content_start = text[open + 1..close]
+            .rfind('[')
+            .map(|i| i + open + 2)
+            .unwrap_or(open + 1);
+        let tag_start = content_start - 1;
+        // unknown tags stay part of the text
+        if let Some(token) = parse_tag(&text[content_start..close], &text[tag_start..=close]) {
+            if text_start < tag_start {
+                tokens.push(Token::Text(&text[text_start..tag_start]));
+            }
+            tokens.push(token);
+            text_start = close + 1;
+        }
+        search_start = close + 1;
+    }
+    if text_start < text.len() {
+        tokens.push(Token::Text(&text[text_start..]));
+    }
+    tokens
+}
+
+/// Tags without partner, misnested or too deeply nested tags
+/// are turned into text.
+fn match_tags(tokens: &mut [Token<'_>]) {
+    let mut stack: Vec<usize> = Vec::new();
+    let mut matched = vec![false; tokens.len()];
+    for index in 0..tokens.len() {
+        match &tokens[index] {
+            Token::Text(_) => {}
+            Token::Open(_, _) => {
+                if stack.len() < MAX_MARKUP_DEPTH {
+                    stack.push(index);
+                }
+            }
+            Token::Close(name, _) => {
+                let name = *name;
+                if let Some(&open) = stack.last()
+                    && let Token::Open(tag, _) = &tokens[open]
+                    && tag.name() == name
+                {
+                    stack.pop();
+                    matched[open] = true;
+                    matched[index] = true;
+
+                    // the text inside the tag is the link
+                    if let Token::Open(Tag::Url(None), _) = &tokens[open] {
+                        let link = tokens[open + 1..index]
+                            .iter()
+                            .all(|token| matches!(token, Token::Text(_)))
+                            .then(|| {
+                                tokens[open + 1..index]
+                                    .iter()
+                                    .map(|token| token.raw())
+                                    .collect::<String>()
+                            })
+                            .and_then(|link| safe_link(&link).map(|link| link.to_string()));
+                        match link {
+                            Some(link) => {
+                                if let Token::Open(tag, _) = &mut tokens[open] {
+                                    *tag = Tag::Url(Some(link));
+                                }
+                            }
+                            None => {
+                                matched[open] = false;
+                                matched[index] = false;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    for (token, matched) in tokens.iter_mut().zip(matched) {
+        if !matched && !matches!(token, Token::Text(_)) {
+            *token = Token::Text(token.raw());
+        }
+    }
+}
+
+/// Parses at most `max_chars` characters of the text into lines of styled spans.
+pub fn parse_markup(text: &str, max_chars: usize) -> Vec<MarkupLine> {
+    let text = text
+        .char_indices()
+        .nth(max_chars)
+        .map(|(index, _)| &text[..index])
+        .unwrap_or(text);
+    let mut tokens = tokenize(text);
+    match_tags(&mut tokens);
+
+    let mut lines = vec![MarkupLine::default()];
+    let mut tags: Vec<Tag> = Vec::new();
+    let style_of = |tags: &[Tag]| {
+        tags.iter().fold(MarkupStyle::default(), |mut style, tag| {
+            match tag {
+                Tag::Bold => style.bold = true,
+                Tag::Color(color) => style.color = Some(*color),
+                Tag::Center => {}
+                Tag::Url(link) => style.link = link.clone(),
+            }
+            style
+        })
+    };
+    for token in tokens {
+        match token {
+            Token::Text(text) => {
+                let style = style_of(&tags);
+                let centered = tags.iter().any(|tag| matches!(tag, Tag::Center));
+                for (index, part) in text.split('\n').enumerate() {
+                    if index > 0 {
+                        lines.push(MarkupLine::default());
+                    }
+                    if part.is_empty() {
+                        continue;
+                    }
+                    let line = lines.last_mut().unwrap();
+                    line.centered |= centered;
+                    match line.spans.last_mut() {
+                        Some(span) if span.style == style => span.text.push_str(part),
+                        _ => line.spans.push(MarkupSpan {
+                            text: part.to_string(),
+                            style: style.clone(),
+                        }),
+                    }
+                }
+            }
+            Token::Open(tag, _) => tags.push(tag),
+            Token::Close(_, _) => {
+                tags.pop();
+            }
+        }
+    }
+    lines
+}
+
+/// Renders the lines, links are opened in the browser
+/// and show their full target when hovered.
+pub fn render_markup(ui: &mut egui::Ui, lines: &[MarkupLine], default_color: Color32) {
+    for line in lines {
+        let layout = Layout::left_to_right(Align::Min).with_main_wrap(true);
+        let layout = if line.centered {
+            layout.with_main_align(Align::Center)
+        } else {
+            layout
+        };
+        ui.with_layout(layout, |ui| {
+            ui.style_mut().spacing.item_spacing.x = 0.0;
+            if line.spans.is_empty() {
+                ui.label("");
+            }
+            for span in line.spans.iter() {
+                let mut text =
+                    RichText::new(&span.text).color(span.style.color.unwrap_or(default_color));
+                if span.style.bold {
+                    text = text.strong();
+                }
+                match &span.style.link {
+                    Some(link) => {
+                        ui.hyperlink_to(text.underline(), link).on_hover_text(link);
+                    }
+                    None => {
+                        ui.label(text);
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use egui::Color32;
+
+    use super::{MAX_MARKUP_DEPTH, MarkupLine, MarkupSpan, MarkupStyle, parse_markup, safe_link};
+
+    fn span(text: &str, style: MarkupStyle) -> MarkupSpan {
+        MarkupSpan {
+            text: text.to_string(),
+            style,
+        }
+    }
+
+    fn plain(text: &str) -> MarkupSpan {
+        span(text, MarkupStyle::default())
+    }
+
+    fn bold() -> MarkupStyle {
+        MarkupStyle {
+            bold: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn nesting() {
+        let lines = parse_markup("a [b]b [color=red]c[/color][/b] d", 1024);
+        assert_eq!(
+            lines,
+            vec![MarkupLine {
+                spans: vec![
+                    plain("a "),
+                    span("b ", bold()),
+                    span(
+                        "c",
+                        MarkupStyle {
+                            bold: true,
+                            color: Some(Color32::RED),
+                            link: None,
+                        }
+                    ),
+                    plain(" d"),
+                ],
+                centered: false,
+            }]
+        );
+
+        // misnested tags are literal
+        let lines = parse_markup("[b][color=red]x[/b][/color]", 1024);
+        assert_eq!(
+            lines[0].spans,
+            vec![
+                plain("[b]"),
+                span(
+                    "x[/b]",
+                    MarkupStyle {
+                        color: Some(Color32::RED),
+                        ..Default::default()
+                    }
+                ),
+            ]
+        );
+
+        // too deep nesting
+        let deep = format!(
+            "{}x{}",
+            "[b]".repeat(MAX_MARKUP_DEPTH + 1),
+            "[/b]".repeat(MAX_MARKUP_DEPTH + 1)
+        );
+        let lines = parse_markup(&deep, 1024);
+        assert_eq!(lines[0].spans, vec![span("[b]x", bold()), plain("[/b]")]);
+    }
+
+    #[test]
+    fn malformed() {
+        for text in [
+            "[b]unclosed",
+            "closed[/b]",
+            "[unknown]tag[/unknown]",
+            "[color=nocolor]x[/color]",
+            "[color=#12345g]x[/color]",
+            "[b=1]x[/b]",
+            "[url=javascript:alert(1)]x[/url]",
+            "[url]ftp://example.com[/url]",
+            "[url]https://[/url]",
+            "[b",
+            "]b[",
+        ] {
+            assert_eq!(
+                parse_markup(text, 1024),
+                vec![MarkupLine {
+                    spans: vec![plain(text)],
+                    centered: false,
+                }],
+                "{text}"
+            );
+        }
+        // brackets in front of a tag
+        assert_eq!(
+            parse_markup("[[b]x[/b]]", 1024)[0].spans,
+            vec![plain("["), span("x", bold()), plain("]")]
+        );
+    }
+
+    #[test]
+    fn lines_and_links() {
+        let lines = parse_markup(
+            "[center]Welcome[/center]\n\n[url=https://ddnet.org/rules]rules[/url] [url]https://ddnet.org[/url]",
+            1024,
+        );
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].centered);
+        assert_eq!(lines[0].spans, vec![plain("Welcome")]);
+        assert_eq!(lines[1], MarkupLine::default());
+        let link = |link: &str| MarkupStyle {
+            link: Some(link.to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            lines[2].spans,
+            vec![
+                span("rules", link("https://ddnet.org/rules")),
+                plain(" "),
+                span("https://ddnet.org", link("https://ddnet.org")),
+            ]
+        );
+
+        assert!(safe_link("HTTPS://ddnet.org").is_some());
+        assert!(safe_link("https://ddnet.org/a b").is_none());
+        assert!(safe_link("file:///etc/passwd").is_none());
+        assert!(safe_link(&format!("https://{}", "a".repeat(300))).is_none());
+    }
+
+    #[test]
+    fn length_limit() {
+        // the closing tag is cut off
+        let lines = parse_markup("[b]bold[/b]", 9);
+        assert_eq!(lines[0].spans, vec![plain("[b]bold[/")]);
+
+        // multi byte characters are not split
+        let lines = parse_markup("äöü[b]x[/b]", 2);
+        assert_eq!(lines[0].spans, vec![plain("äö")]);
+
+        let lines = parse_markup(&"a\n".repeat(100), 10);
+        assert_eq!(lines.len(), 6);
+    }
+}
@@ -1,4 +1,4 @@
-use egui::{Align2, Frame, Vec2, Window, vec2};
+use egui::{Align2, Color32, Frame, ScrollArea, Vec2, Window};
 
 use tracing::instrument;
 use ui_base::{
@@ -7,6 +7,8 @@ use ui_base::{
     utils::add_margins,
 };
 
+use crate::markup::render_markup;
+
 use super::user_data::UserData;
 
 /// not required
@@ -20,12 +22,13 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
         .title_bar(false)
         .frame(Frame::default().fill(bg_frame_color()).corner_radius(5.0))
         .anchor(Align2::CENTER_CENTER, Vec2::new(0.0, 0.0))
-        .fixed_size(vec2(300.0, 400.0))
+        .min_width(300.0)
+        .max_width(400.0)
         .show(ui.ctx(), |ui| {
-            ui.style_mut().spacing.item_spacing.y = 0.0;
             add_margins(ui, |ui| {
-                let mut cache = egui_commonmark::CommonMarkCache::default();
-                egui_commonmark::CommonMarkViewer::new().show(ui, &mut cache, pipe.user_data.msg);
+                ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                    render_markup(ui, pipe.user_data.lines, Color32::WHITE);
+                });
             });
         });
     if let Some(res) = res {
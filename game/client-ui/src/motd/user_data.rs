@@ -1,3 +1,8 @@
+use crate::markup::MarkupLine;
+
+/// The message of the day is cut after this many characters.
+pub const MOTD_MAX_CHARS: usize = 1024;
+
 pub struct UserData<'a> {
-    pub msg: &'a str,
+    pub lines: &'a [MarkupLine],
 }
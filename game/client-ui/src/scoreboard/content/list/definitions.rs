@@ -7,15 +7,28 @@ pub const TABLE_CONTENT_TEE_SIZES: [f32; TABLE_FONT_SIZE_COUNT] = TABLE_CONTENT_
 pub const TABLE_CONTENT_COLUMN_SPACING: [f32; TABLE_FONT_SIZE_COUNT] =
     [2.0, 2.0, 3.0, 8.0, 8.0, 8.0, 8.0];
 
-pub const TABLE_CONTENT_WIDTH: [[f32; 6]; TABLE_FONT_SIZE_COUNT] = [
-    [30.0, 8.0, 60.0, 40.0, 20.0, 24.0],
-    [30.0, 8.0, 100.0, 70.0, 20.0, 24.0],
-    [35.0, 15.0, 120.0, 80.0, 30.0, 30.0],
-    [35.0, 15.0, 180.0, 120.0, 40.0, 30.0],
-    [35.0, 18.0, 240.0, 160.0, 40.0, 40.0],
-    [35.0, 22.0, 300.0, 200.0, 40.0, 60.0],
-    [35.0, 26.0, 360.0, 240.0, 50.0, 60.0],
+pub const TABLE_CONTENT_WIDTH: [[f32; 7]; TABLE_FONT_SIZE_COUNT] = [
+    [30.0, 8.0, 60.0, 40.0, 20.0, 24.0, 24.0],
+    [30.0, 8.0, 100.0, 70.0, 20.0, 24.0, 24.0],
+    [35.0, 15.0, 120.0, 80.0, 30.0, 30.0, 30.0],
+    [35.0, 15.0, 180.0, 120.0, 40.0, 30.0, 40.0],
+    [35.0, 18.0, 240.0, 160.0, 40.0, 40.0, 50.0],
+    [35.0, 22.0, 300.0, 200.0, 40.0, 60.0, 60.0],
+    [35.0, 26.0, 360.0, 240.0, 50.0, 60.0, 60.0],
 ];
 pub const TABLE_NAME_COLUMN_INDEX: usize = 2;
+pub const TABLE_PING_COLUMN_INDEX: usize = 5;
+/// Only shown if the server sent the rank of any player.
+pub const TABLE_RANK_COLUMN_INDEX: usize = 6;
+
+/// The widths of the columns that are shown.
+pub fn table_content_width(font_size_index: usize, show_rank: bool) -> &'static [f32] {
+    let widths = &TABLE_CONTENT_WIDTH[font_size_index];
+    if show_rank {
+        widths
+    } else {
+        &widths[..TABLE_RANK_COLUMN_INDEX]
+    }
+}
 
 pub const TABLE_CONTENT_MIN_COLUMNS: usize = 3;
@@ -7,7 +7,8 @@ use ui_base::types::{UiRenderPipe, UiState};
 
 use crate::scoreboard::{
     content::list::definitions::{
-        TABLE_CONTENT_COLUMN_SPACING, TABLE_CONTENT_WIDTH, TABLE_NAME_COLUMN_INDEX,
+        TABLE_CONTENT_COLUMN_SPACING, TABLE_NAME_COLUMN_INDEX, TABLE_PING_COLUMN_INDEX,
+        table_content_width,
     },
     user_data::UserData,
 };
@@ -19,9 +20,11 @@ pub fn render(
     pipe: &mut UiRenderPipe<UserData>,
     _ui_state: &mut UiState,
     font_size_index: usize,
+    show_rank: bool,
 ) {
     const FONT_SIZE: f32 = 10.0;
 
+    let widths = table_content_width(font_size_index, show_rank);
     let mut width_left = ui.available_width();
     let spacing_x = TABLE_CONTENT_COLUMN_SPACING[font_size_index];
     ui.style_mut().spacing.item_spacing.x = spacing_x;
@@ -29,8 +32,8 @@ pub fn render(
     let mut strip = StripBuilder::new(ui);
     let mut col_count = 0;
     while width_left > 0.0 {
-        if col_count < TABLE_CONTENT_WIDTH[font_size_index].len() {
-            let col_width = TABLE_CONTENT_WIDTH[font_size_index][col_count];
+        if col_count < widths.len() {
+            let col_width = widths[col_count];
             if width_left >= col_width {
                 width_left -= col_width + spacing_x;
                 if col_count == TABLE_NAME_COLUMN_INDEX {
@@ -89,7 +92,7 @@ pub fn render(
                 4 => {
                     strip.empty();
                 }
-                _ => {
+                TABLE_PING_COLUMN_INDEX => {
                     strip.cell(|ui| {
                         ui.style_mut().wrap_mode = None;
                         ui.with_layout(Layout::left_to_right(egui::Align::Center), |ui| {
@@ -98,6 +101,14 @@ pub fn render(
                         });
                     });
                 }
+                _ => {
+                    strip.cell(|ui| {
+                        ui.style_mut().wrap_mode = None;
+                        ui.with_layout(Layout::left_to_right(egui::Align::Center), |ui| {
+                            ui.label(RichText::new("rank").size(FONT_SIZE));
+                        });
+                    });
+                }
             }
         }
     });
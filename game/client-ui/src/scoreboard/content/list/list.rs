@@ -19,8 +19,8 @@ use crate::scoreboard::user_data::UserData;
 
 use super::{
     definitions::{
-        TABLE_CONTENT_COLUMN_SPACING, TABLE_CONTENT_ROW_HEIGHTS, TABLE_CONTENT_WIDTH,
-        TABLE_FONT_SIZE_COUNT,
+        TABLE_CONTENT_COLUMN_SPACING, TABLE_CONTENT_ROW_HEIGHTS, TABLE_FONT_SIZE_COUNT,
+        table_content_width,
     },
     player_list::entry::RenderPlayer,
 };
@@ -42,18 +42,20 @@ fn calc_font_size_index(
     num_rows: usize,
     cur_column_count: usize,
     cur_font_index: usize,
+    show_rank: bool,
 ) -> CalculatedFontSizeIndex {
     let min_columns_to_display = 3;
     let spacing_x_column = TABLE_CONTENT_COLUMN_SPACING[cur_font_index];
-    let min_table_width = TABLE_CONTENT_WIDTH[cur_font_index]
+    let widths = table_content_width(cur_font_index, show_rank);
+    let min_table_width = widths
         .iter()
         .enumerate()
         .filter(|(index, _)| *index < min_columns_to_display)
         .map(|(_, e)| e)
         .sum::<f32>()
         + (spacing_x_column * (min_columns_to_display - 1) as f32);
-    let max_table_width = TABLE_CONTENT_WIDTH[cur_font_index].iter().sum::<f32>()
-        + (spacing_x_column * (TABLE_CONTENT_WIDTH[cur_font_index].len() - 1) as f32);
+    let max_table_width =
+        widths.iter().sum::<f32>() + (spacing_x_column * (widths.len() - 1) as f32);
 
     let items_per_col = num_rows.div_ceil(cur_column_count) as f32;
     let spacing_of_items = spacing_y * items_per_col - spacing_y;
@@ -73,6 +75,7 @@ fn calc_font_size_index(
             num_rows,
             cur_column_count,
             cur_font_index - 1,
+            show_rank,
         )
     } else {
         // font is already smallest
@@ -89,6 +92,7 @@ fn calc_font_size_index(
                 num_rows,
                 next_col_count,
                 TABLE_FONT_SIZE_COUNT - 1,
+                show_rank,
             )
         }
         // check if column would fit with the current font size
@@ -101,6 +105,7 @@ fn calc_font_size_index(
                 num_rows,
                 cur_column_count,
                 cur_font_index - 1,
+                show_rank,
             )
         } else {
             let only_min_width_fits = required_size_for_full > width;
@@ -115,6 +120,7 @@ fn calc_font_size_index(
                     num_rows,
                     cur_column_count,
                     cur_font_index - 1,
+                    show_rank,
                 );
                 // but if the result also only allows min width, we can also just keep the current
                 if res.only_min_width_fits {
@@ -158,6 +164,9 @@ pub fn render<'a>(
 
     // for every stage calculate some extra height
     let items_to_render = player_count + stages.len();
+    let show_rank = character_infos
+        .values()
+        .any(|char| char.server_rank.is_some());
 
     let CalculatedFontSizeIndex {
         index: font_size_index,
@@ -171,6 +180,7 @@ pub fn render<'a>(
         items_to_render,
         1,
         TABLE_FONT_SIZE_COUNT - 1,
+        show_rank,
     );
     let items_per_height = player_count.div_ceil(columns);
 
@@ -211,7 +221,13 @@ pub fn render<'a>(
                             strip.cell(|ui| {
                                 ui.style_mut().wrap_mode = None;
                                 ui.set_clip_rect(ui.available_rect_before_wrap());
-                                super::header::render(ui, pipe, ui_state, font_size_index);
+                                super::header::render(
+                                    ui,
+                                    pipe,
+                                    ui_state,
+                                    font_size_index,
+                                    show_rank,
+                                );
                             });
                         }
                     });
@@ -252,6 +268,7 @@ pub fn render<'a>(
                                     full_ui_rect,
                                     font_size_index,
                                     spacing_y,
+                                    show_rank,
                                     &mut frame_rects,
                                 );
 
@@ -1,7 +1,7 @@
 use std::iter::Peekable;
 
 use base::{duration_ext::DurationToRaceStr, linked_hash_map_view::FxLinkedHashMap};
-use egui::{Color32, Layout, Rect, RichText, Shape, layers::ShapeIdx};
+use egui::{Color32, Layout, Rect, RichText, Sense, Shape, layers::ShapeIdx};
 use egui_extras::{Size, StripBuilder};
 
 use game_interface::types::{
@@ -19,8 +19,9 @@ use crate::{
     scoreboard::{
         content::list::definitions::{
             TABLE_CONTENT_COLUMN_SPACING, TABLE_CONTENT_FONT_SIZES, TABLE_CONTENT_TEE_SIZES,
-            TABLE_CONTENT_WIDTH, TABLE_NAME_COLUMN_INDEX,
+            TABLE_NAME_COLUMN_INDEX, TABLE_PING_COLUMN_INDEX, table_content_width,
         },
+        context_menu,
         user_data::UserData,
     },
     utils::{render_flag_for_ui, render_tee_for_ui},
@@ -45,6 +46,7 @@ pub fn render<'a>(
     full_ui_rect: &Rect,
     font_size_index: usize,
     spacing_y: f32,
+    show_rank: bool,
     frame_rect: &mut FxLinkedHashMap<StageId, FrameRect>,
 ) {
     let Some((stage, player)) = players.next() else {
@@ -70,6 +72,7 @@ pub fn render<'a>(
 
     ui.add_space(spacing_y / 2.0);
 
+    let widths = table_content_width(font_size_index, show_rank);
     let mut width_left = ui.available_width();
     let spacing_x = TABLE_CONTENT_COLUMN_SPACING[font_size_index];
     let style = ui.style_mut();
@@ -79,8 +82,8 @@ pub fn render<'a>(
     let mut strip = StripBuilder::new(ui);
     let mut col_count = 0;
     while width_left > 0.0 {
-        if col_count < TABLE_CONTENT_WIDTH[font_size_index].len() {
-            let col_width = TABLE_CONTENT_WIDTH[font_size_index][col_count];
+        if col_count < widths.len() {
+            let col_width = widths[col_count];
             if width_left >= col_width {
                 width_left -= col_width + spacing_x;
                 if col_count == TABLE_NAME_COLUMN_INDEX {
@@ -178,7 +181,7 @@ pub fn render<'a>(
                         );
                     });
                 }
-                _ => {
+                TABLE_PING_COLUMN_INDEX => {
                     strip.cell(|ui| {
                         ui.style_mut().wrap_mode = None;
                         ui.with_layout(Layout::left_to_right(egui::Align::Center), |ui| {
@@ -196,8 +199,31 @@ pub fn render<'a>(
                         });
                     });
                 }
+                _ => {
+                    strip.cell(|ui| {
+                        ui.style_mut().wrap_mode = None;
+                        ui.with_layout(Layout::left_to_right(egui::Align::Center), |ui| {
+                            if let Some(server_rank) = &char.server_rank {
+                                ui.label(
+                                    RichText::new(format!("#{}", server_rank.rank)).size(font_size),
+                                )
+                                .on_hover_text(format!("{} points", server_rank.points));
+                            }
+                        });
+                    });
+                }
             }
         }
     });
     ui.add_space(spacing_y / 2.0);
+
+    if pipe.user_data.player_context.is_some() {
+        let row = ui.interact(
+            rect.intersect(ui.clip_rect()),
+            ui.id().with(("scoreboard-player", player.id)),
+            Sense::click(),
+        );
+        let just_opened = context_menu::open_on_interact(ui, &row, player.id);
+        context_menu::render(ui, pipe, player, char, just_opened);
+    }
 }
@@ -31,6 +31,7 @@ pub fn render<'a>(
     full_ui_rect: &Rect,
     font_size_index: usize,
     spacing_y: f32,
+    show_rank: bool,
     frame_rect: &mut FxLinkedHashMap<StageId, FrameRect>,
 ) {
     let item_height = TABLE_CONTENT_ROW_HEIGHTS[font_size_index] + spacing_y;
@@ -53,6 +54,7 @@ pub fn render<'a>(
                     full_ui_rect,
                     font_size_index,
                     spacing_y,
+                    show_rank,
                     frame_rect,
                 );
             });
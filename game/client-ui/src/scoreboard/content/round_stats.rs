@@ -0,0 +1,118 @@
+use base::duration_ext::DurationToRaceStr;
+use egui::{Color32, CornerRadius, Grid, RichText, ScrollArea, Shape, epaint::RectShape};
+use game_interface::{events::GameWorldPlayerStats, types::weapons::WeaponType};
+use tracing::instrument;
+use ui_base::{
+    style::bg_frame_color,
+    types::{UiRenderPipe, UiState},
+    utils::add_margins,
+};
+
+use crate::scoreboard::user_data::UserData;
+
+/// The statistics of all players at the end of a round.
+#[instrument(level = "trace", skip_all)]
+pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &mut UiState) {
+    let Some(stats) = pipe.user_data.round_stats else {
+        return;
+    };
+    if stats.is_empty() {
+        return;
+    }
+
+    let rounding = CornerRadius::same(5);
+    ui.painter().add(Shape::Rect(RectShape::filled(
+        ui.available_rect_before_wrap(),
+        rounding,
+        bg_frame_color(),
+    )));
+    ui_state.add_blur_rect(ui.available_rect_before_wrap(), rounding);
+
+    // only games that count hook assistance show the column
+    let show_hook_assist = stats
+        .iter()
+        .any(|player| !player.hook_assist_time.is_zero());
+
+    const FONT_SIZE: f32 = 12.0;
+    add_margins(ui, |ui| {
+        ScrollArea::vertical().show(ui, |ui| {
+            Grid::new("round-stats")
+                .num_columns(if show_hook_assist { 10 } else { 9 })
+                .striped(true)
+                .show(ui, |ui| {
+                    for header in [
+                        "Name",
+                        "K/D",
+                        "Accuracy",
+                        "Best weapon",
+                        "Damage",
+                        "Flag time",
+                        "Captures",
+                        "Returns",
+                        "Killstreak",
+                    ] {
+                        ui.label(RichText::new(header).size(FONT_SIZE).strong());
+                    }
+                    if show_hook_assist {
+                        ui.label(RichText::new("Hook assist").size(FONT_SIZE).strong());
+                    }
+                    ui.end_row();
+
+                    for player in stats.iter() {
+                        let text = |text: String| {
+                            let text = RichText::new(text).size(FONT_SIZE);
+                            if player.disconnected {
+                                text.color(Color32::GRAY).italics()
+                            } else {
+                                text
+                            }
+                        };
+                        let (shots, hits) = player
+                            .weapons
+                            .iter()
+                            .fold((0, 0), |(shots, hits), w| (shots + w.shots, hits + w.hits));
+                        let best_weapon = [
+                            WeaponType::Hammer,
+                            WeaponType::Gun,
+                            WeaponType::Shotgun,
+                            WeaponType::Grenade,
+                            WeaponType::Laser,
+                        ]
+                        .into_iter()
+                        .zip(player.weapons.iter())
+                        .filter(|(_, w)| w.shots > 0)
+                        .max_by(|(_, w1), (_, w2)| w1.accuracy().total_cmp(&w2.accuracy()));
+
+                        ui.label(text(if player.disconnected {
+                            format!("{} (left)", player.name)
+                        } else {
+                            player.name.to_string()
+                        }));
+                        ui.label(text(format!("{}/{}", player.kills, player.deaths)));
+                        ui.label(text(if shots > 0 {
+                            format!("{:.0}%", (hits as f32 / shots as f32).min(1.0) * 100.0)
+                        } else {
+                            "-".to_string()
+                        }));
+                        ui.label(text(if let Some((weapon, stats)) = best_weapon {
+                            format!("{:?} {:.0}%", weapon, stats.accuracy() * 100.0)
+                        } else {
+                            "-".to_string()
+                        }));
+                        ui.label(text(format!(
+                            "{}/{}",
+                            player.damage_dealt, player.damage_taken
+                        )));
+                        ui.label(text(player.flag_carry_time.to_race_string()));
+                        ui.label(text(player.flag_captures.to_string()));
+                        ui.label(text(player.flag_returns.to_string()));
+                        ui.label(text(player.longest_killstreak.to_string()));
+                        if show_hook_assist {
+                            ui.label(text(player.hook_assist_time.to_race_string()));
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+    });
+}
@@ -0,0 +1,286 @@
+use egui::{Frame, Id, Key, Order, Pos2, Response};
+use game_base::browser_favorite_player::FavoritePlayer;
+use game_interface::types::{
+    id_types::CharacterId,
+    render::{
+        character::CharacterInfo,
+        scoreboard::{ScoreboardCharacterInfo, ScoreboardConnectionType},
+    },
+};
+use ui_base::types::UiRenderPipe;
+
+use super::user_data::{ScoreboardEvent, ScoreboardPlayerContext, UserData};
+
+/// An action of the player context menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayerContextAction {
+    MuteChat,
+    MuteSpatialChat,
+    Friend,
+    CopyName,
+    Spectate,
+    VoteKick,
+}
+
+/// The player the context menu was opened for.
+#[derive(Debug, Clone, Copy)]
+pub struct PlayerContextTarget {
+    pub id: CharacterId,
+    pub is_bot: bool,
+}
+
+/// The actions that are possible for the target player, in menu order.
+pub fn available_actions(
+    target: &PlayerContextTarget,
+    context: &ScoreboardPlayerContext,
+) -> Vec<PlayerContextAction> {
+    let is_local = context.local_players.contains(&target.id);
+    let is_human = !is_local && !target.is_bot;
+
+    let mut actions = Vec::new();
+    if is_human {
+        actions.push(PlayerContextAction::MuteChat);
+        if context.spatial_chat.contains_key(&target.id) {
+            actions.push(PlayerContextAction::MuteSpatialChat);
+        }
+        actions.push(PlayerContextAction::Friend);
+    }
+    actions.push(PlayerContextAction::CopyName);
+    if !is_local && context.spectate_allowed {
+        actions.push(PlayerContextAction::Spectate);
+    }
+    if is_human && !context.vote_kick_blocked && !context.vote_immune.contains(&target.id) {
+        actions.push(PlayerContextAction::VoteKick);
+    }
+    actions
+}
+
+#[derive(Debug, Clone, Copy)]
+struct OpenContextMenu {
+    id: CharacterId,
+    pos: Pos2,
+    /// Opened by keyboard or controller, so the first
+    /// entry should get the focus.
+    focus_first: bool,
+}
+
+fn menu_id() -> Id {
+    Id::new("scoreboard-player-context-menu")
+}
+
+/// Opens the context menu of a player row on a secondary click, a long press
+/// or if the focused row is activated. Returns `true` if it was opened.
+pub fn open_on_interact(ui: &egui::Ui, row: &Response, id: CharacterId) -> bool {
+    if !row.clicked() && !row.secondary_clicked() && !row.long_touched() {
+        return false;
+    }
+    let focus_first = row.has_focus() && !row.secondary_clicked();
+    let pos = row
+        .interact_pointer_pos()
+        .filter(|_| !focus_first)
+        .unwrap_or_else(|| row.rect.left_bottom());
+    ui.data_mut(|d| {
+        d.insert_temp(
+            menu_id(),
+            OpenContextMenu {
+                id,
+                pos,
+                focus_first,
+            },
+        )
+    });
+    true
+}
+
+/// The context menu of a player, if it is open.
+pub fn render(
+    ui: &egui::Ui,
+    pipe: &mut UiRenderPipe<UserData>,
+    player: &ScoreboardCharacterInfo,
+    char: &CharacterInfo,
+    just_opened: bool,
+) {
+    let Some(context) = pipe.user_data.player_context else {
+        return;
+    };
+    let Some(open) = ui
+        .data(|d| d.get_temp::<OpenContextMenu>(menu_id()))
+        .filter(|open| open.id == player.id)
+    else {
+        return;
+    };
+    let target = PlayerContextTarget {
+        id: player.id,
+        is_bot: matches!(player.ping, ScoreboardConnectionType::Bot),
+    };
+    let name = char.info.name.as_str();
+
+    let mut close = false;
+    let res = egui::Area::new(menu_id())
+        .order(Order::Foreground)
+        .fixed_pos(open.pos)
+        .show(ui.ctx(), |ui| {
+            Frame::menu(ui.style()).show(ui, |ui| {
+                ui.label(name);
+                ui.separator();
+                for (index, action) in available_actions(&target, context).into_iter().enumerate() {
+                    let (text, ev) = match action {
+                        PlayerContextAction::MuteChat => {
+                            let muted = context.chat_muted.iter().any(|n| n == name);
+                            (
+                                if muted { "Unmute chat" } else { "Mute chat" },
+                                Some(ScoreboardEvent::MuteChat {
+                                    name: name.to_string(),
+                                    muted: !muted,
+                                }),
+                            )
+                        }
+                        PlayerContextAction::MuteSpatialChat => {
+                            let muted = context
+                                .spatial_chat
+                                .get(&player.id)
+                                .copied()
+                                .unwrap_or_default();
+                            (
+                                if muted { "Unmute voice" } else { "Mute voice" },
+                                Some(ScoreboardEvent::MuteSpatialChat {
+                                    id: player.id,
+                                    muted: !muted,
+                                }),
+                            )
+                        }
+                        PlayerContextAction::Friend => {
+                            if context.friends.iter().any(|n| n == name) {
+                                (
+                                    "Remove friend",
+                                    Some(ScoreboardEvent::RemoveFriend {
+                                        name: name.to_string(),
+                                    }),
+                                )
+                            } else {
+                                (
+                                    "Add friend",
+                                    Some(ScoreboardEvent::AddFriend(FavoritePlayer {
+                                        name: char.info.name.clone(),
+                                        clan: char.info.clan.clone(),
+                                        skin: char.info.skin.clone(),
+                                        skin_info: char.info.skin_info,
+                                        flag: char.info.flag.clone(),
+                                    })),
+                                )
+                            }
+                        }
+                        PlayerContextAction::CopyName => ("Copy name", None),
+                        PlayerContextAction::Spectate => {
+                            ("Spectate", Some(ScoreboardEvent::Spectate(player.id)))
+                        }
+                        PlayerContextAction::VoteKick => {
+                            ("Call kick vote", Some(ScoreboardEvent::VoteKick(player.id)))
+                        }
+                    };
+                    let btn = ui.button(text);
+                    if index == 0 && open.focus_first {
+                        btn.request_focus();
+                    }
+                    if btn.clicked() {
+                        match ev {
+                            Some(ev) => pipe.user_data.events.push(ev),
+                            None => ui.ctx().copy_text(name.to_string()),
+                        }
+                        close = true;
+                    }
+                }
+            });
+        });
+
+    if open.focus_first {
+        ui.data_mut(|d| {
+            d.insert_temp(
+                menu_id(),
+                OpenContextMenu {
+                    focus_first: false,
+                    ..open
+                },
+            )
+        });
+    }
+    if close
+        || (!just_opened && res.response.clicked_elsewhere())
+        || ui.input(|i| i.key_pressed(Key::Escape))
+    {
+        ui.data_mut(|d| d.remove::<OpenContextMenu>(menu_id()));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use game_interface::types::{id_gen::IdGenerator, id_types::PlayerId};
+
+    use super::{PlayerContextAction, PlayerContextTarget, available_actions};
+    use crate::scoreboard::user_data::ScoreboardPlayerContext;
+
+    #[test]
+    fn action_matrix() {
+        let id_gen = IdGenerator::new();
+        let ids: Vec<PlayerId> = (0..4).map(|_| id_gen.next_id()).collect();
+        let (own, dummy, other, immune) = (ids[0], ids[1], ids[2], ids[3]);
+        let mut context = ScoreboardPlayerContext {
+            local_players: vec![own, dummy],
+            vote_immune: vec![immune],
+            ..Default::default()
+        };
+        let target = |id| PlayerContextTarget { id, is_bot: false };
+
+        // no self kick, no self mute
+        assert_eq!(
+            available_actions(&target(own), &context),
+            [PlayerContextAction::CopyName]
+        );
+        assert_eq!(
+            available_actions(&target(dummy), &context),
+            [PlayerContextAction::CopyName]
+        );
+        assert_eq!(
+            available_actions(&target(other), &context),
+            [
+                PlayerContextAction::MuteChat,
+                PlayerContextAction::Friend,
+                PlayerContextAction::CopyName,
+                PlayerContextAction::VoteKick,
+            ]
+        );
+        assert!(
+            !available_actions(&target(immune), &context).contains(&PlayerContextAction::VoteKick)
+        );
+        // bots don't chat & can't be kicked
+        assert_eq!(
+            available_actions(
+                &PlayerContextTarget {
+                    id: other,
+                    is_bot: true
+                },
+                &context
+            ),
+            [PlayerContextAction::CopyName]
+        );
+
+        // spectate only if dead or spectator
+        context.spectate_allowed = true;
+        context.spatial_chat.insert(other, false);
+        context.vote_kick_blocked = true;
+        assert_eq!(
+            available_actions(&target(other), &context),
+            [
+                PlayerContextAction::MuteChat,
+                PlayerContextAction::MuteSpatialChat,
+                PlayerContextAction::Friend,
+                PlayerContextAction::CopyName,
+                PlayerContextAction::Spectate,
+            ]
+        );
+        assert_eq!(
+            available_actions(&target(own), &context),
+            [PlayerContextAction::CopyName]
+        );
+    }
+}
@@ -52,4 +52,16 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
     ui.scope_builder(egui::UiBuilder::new().max_rect(spec_rect), |ui| {
         super::content::main_frame::render_spectators(ui, pipe, ui_state, available_rect)
     });
+
+    // the round statistics are next to the spectators
+    let stats_rect = Rect::from_min_size(
+        Pos2::new(
+            full_width / 2.0 - spacing_x / 2.0 - allowed_width_spec,
+            offset_y,
+        ),
+        Vec2::new(allowed_width_spec, allowed_height_spec),
+    );
+    ui.scope_builder(egui::UiBuilder::new().max_rect(stats_rect), |ui| {
+        super::content::round_stats::render(ui, pipe, ui_state)
+    });
 }
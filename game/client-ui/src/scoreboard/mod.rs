@@ -1,4 +1,5 @@
 pub mod content;
+pub mod context_menu;
 pub mod main_frame;
 pub mod page;
 pub mod user_data;
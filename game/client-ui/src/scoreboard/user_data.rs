@@ -1,13 +1,61 @@
+use std::collections::HashMap;
+
 use base::linked_hash_map_view::FxLinkedHashMap;
 use client_containers::{flags::FlagsContainer, skins::SkinContainer};
 use client_render_base::render::tee::RenderTee;
-use game_interface::types::{
-    id_types::CharacterId,
-    render::{character::CharacterInfo, scoreboard::Scoreboard},
+use game_base::browser_favorite_player::FavoritePlayer;
+use game_interface::{
+    events::GameWorldPlayerStats,
+    types::{
+        id_types::{CharacterId, PlayerId},
+        render::{character::CharacterInfo, scoreboard::Scoreboard},
+    },
 };
 use graphics::handles::{
     canvas::canvas::GraphicsCanvasHandle, stream::stream::GraphicsStreamHandle,
 };
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ScoreboardEvent {
+    /// Hide (or show again) the chat messages of the player.
+    MuteChat {
+        name: String,
+        muted: bool,
+    },
+    /// Mute (or unmute) the voice of the player in the spatial chat.
+    MuteSpatialChat {
+        id: PlayerId,
+        muted: bool,
+    },
+    AddFriend(FavoritePlayer),
+    RemoveFriend {
+        name: String,
+    },
+    Spectate(CharacterId),
+    VoteKick(PlayerId),
+    PlatformOutput(egui::PlatformOutput),
+}
+
+/// What the local client knows about the other players,
+/// required by the player context menu of the scoreboard.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ScoreboardPlayerContext {
+    /// The players of this client (main player & dummies).
+    pub local_players: Vec<PlayerId>,
+    /// Names of the players that were added as friend.
+    pub friends: Vec<String>,
+    /// Names of the players whose chat messages are hidden.
+    pub chat_muted: Vec<String>,
+    /// The players known to the spatial chat and whether they are muted.
+    pub spatial_chat: HashMap<PlayerId, bool>,
+    /// Players the server does not allow to be voted.
+    pub vote_immune: Vec<PlayerId>,
+    /// A vote is running or the server requires a reason for kick votes.
+    pub vote_kick_blocked: bool,
+    /// The local player is a spectator or dead.
+    pub spectate_allowed: bool,
+}
 
 pub struct UserData<'a> {
     pub scoreboard: &'a Scoreboard,
@@ -19,4 +67,11 @@ pub struct UserData<'a> {
     pub flags_container: &'a mut FlagsContainer,
 
     pub own_character_id: &'a CharacterId,
+    /// The statistics of the last round, if the round is over.
+    pub round_stats: Option<&'a [GameWorldPlayerStats]>,
+
+    /// `None` if the scoreboard is not interactive, e.g.
+    /// because it is forced to be visible.
+    pub player_context: Option<&'a ScoreboardPlayerContext>,
+    pub events: &'a mut Vec<ScoreboardEvent>,
 }
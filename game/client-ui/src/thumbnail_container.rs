@@ -17,7 +17,9 @@ use client_containers::container::{
     ContainerLoadOptions, ContainerLoadedItem, ContainerLoadedItemDir, load_file_part_and_upload,
 };
 
-use client_containers::container::{Container, ContainerItemLoadData, ContainerLoad};
+use client_containers::container::{
+    Container, ContainerItemLoadData, ContainerItemMemory, ContainerItemMemoryUsage, ContainerLoad,
+};
 use url::Url;
 
 #[derive(Debug, Hiarc, Clone)]
@@ -81,6 +83,12 @@ impl LoadThumbnail {
     }
 }
 
+impl ContainerItemMemoryUsage for LoadThumbnail {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        self.thumbnail.memory_usage()
+    }
+}
+
 impl ContainerLoad<Thumbnail> for LoadThumbnail {
     fn load(
         item_name: &str,
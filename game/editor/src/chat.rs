@@ -0,0 +1,223 @@
+use math::math::vector::vec2;
+
+const POS_LINK_PREFIX: &str = "[pos:";
+const POS_LINK_SUFFIX: char = ']';
+
+/// A received chat message, kept for the whole session.
+#[derive(Debug, Clone)]
+pub struct EditorChatMsg {
+    pub from: String,
+    pub msg: String,
+    /// The message mentions the own mapper name.
+    pub mentions_me: bool,
+}
+
+/// A link to a camera position of the map,
+/// clicking it moves the camera of the other users there.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MapPosLink {
+    pub pos: vec2,
+    pub zoom: f32,
+}
+
+impl MapPosLink {
+    /// The link as text that is sent in the chat message.
+    pub fn encode(&self) -> String {
+        format!(
+            "{POS_LINK_PREFIX}{:.2},{:.2},{:.2}{POS_LINK_SUFFIX}",
+            self.pos.x, self.pos.y, self.zoom
+        )
+    }
+
+    /// Decodes a whole link as generated by [`Self::encode`].
+    pub fn decode(text: &str) -> Option<Self> {
+        let mut values = text
+            .strip_prefix(POS_LINK_PREFIX)?
+            .strip_suffix(POS_LINK_SUFFIX)?
+            .split(',')
+            .map(|value| value.trim().parse::<f32>().ok().filter(|v| v.is_finite()));
+        let (x, y, zoom) = (values.next()??, values.next()??, values.next()??);
+        (values.next().is_none() && zoom > 0.0).then_some(Self {
+            pos: vec2::new(x, y),
+            zoom,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChatMsgPart<'a> {
+    Text(&'a str),
+    /// The mentioned name without the `@`.
+    Mention(&'a str),
+    PosLink(MapPosLink),
+}
+
+/// The longest of the names that the text starts with,
+/// followed by the end of the text or a char that can't be part of a name.
+fn mentioned_name_len(text: &str, names: &[&str]) -> Option<usize> {
+    names
+        .iter()
+        .filter(|name| !name.is_empty())
+        .filter(|name| {
+            text.get(..name.len())
+                .is_some_and(|start| start.eq_ignore_ascii_case(name))
+                && text[name.len()..]
+                    .chars()
+                    .next()
+                    .is_none_or(|c| !c.is_alphanumeric() && c != '_')
+        })
+        .map(|name| name.len())
+        .max()
+}
+
+/// Splits the message into text, mentions of the given mapper names
+/// & map position links.
+///
+/// Mapper names may contain spaces, so only known names are mentions.
+pub fn parse_msg<'a>(msg: &'a str, names: &[&str]) -> Vec<ChatMsgPart<'a>> {
+    let mut res = Vec::new();
+    let mut text_start = 0;
+    let mut index = 0;
+    while let Some(c) = msg[index..].chars().next() {
+        let rest = &msg[index..];
+        let part = if c == '@' {
+            mentioned_name_len(&rest[1..], names)
+                .map(|len| (ChatMsgPart::Mention(&rest[1..1 + len]), 1 + len))
+        } else if rest.starts_with(POS_LINK_PREFIX) {
+            rest.find(POS_LINK_SUFFIX).and_then(|end| {
+                MapPosLink::decode(&rest[..=end]).map(|link| (ChatMsgPart::PosLink(link), end + 1))
+            })
+        } else {
+            None
+        };
+        if let Some((part, len)) = part {
+            if text_start < index {
+                res.push(ChatMsgPart::Text(&msg[text_start..index]));
+            }
+            res.push(part);
+            index += len;
+            text_start = index;
+        } else {
+            index += c.len_utf8();
+        }
+    }
+    if text_start < msg.len() {
+        res.push(ChatMsgPart::Text(&msg[text_start..]));
+    }
+    res
+}
+
+/// Whether the message mentions the given mapper name.
+pub fn mentions(msg: &str, name: &str) -> bool {
+    parse_msg(msg, &[name])
+        .iter()
+        .any(|part| matches!(part, ChatMsgPart::Mention(_)))
+}
+
+/// The names that complete the mention that is currently typed
+/// at the end of the input, together with the byte offset of its `@`.
+pub fn mention_completions<'a>(
+    input: &str,
+    names: impl IntoIterator<Item = &'a str>,
+) -> Option<(usize, Vec<&'a str>)> {
+    let at = input.rfind('@')?;
+    let partial = &input[at + 1..];
+    let completions: Vec<_> = names
+        .into_iter()
+        .filter(|name| {
+            name.len() > partial.len()
+                && name
+                    .get(..partial.len())
+                    .is_some_and(|start| start.eq_ignore_ascii_case(partial))
+        })
+        .collect();
+    (!completions.is_empty()).then_some((at, completions))
+}
+
+/// Replaces the mention that is currently typed with the full name.
+pub fn complete_mention(input: &mut String, at: usize, name: &str) {
+    input.truncate(at);
+    input.push('@');
+    input.push_str(name);
+    input.push(' ');
+}
+
+#[cfg(test)]
+mod test {
+    use math::math::vector::vec2;
+
+    use super::{
+        ChatMsgPart, MapPosLink, complete_mention, mention_completions, mentions, parse_msg,
+    };
+
+    #[test]
+    fn mention_parsing() {
+        let names = ["alice", "bob", "bob the builder"];
+        assert_eq!(
+            parse_msg("hey @Bob the builder, look", &names),
+            [
+                ChatMsgPart::Text("hey "),
+                ChatMsgPart::Mention("Bob the builder"),
+                ChatMsgPart::Text(", look"),
+            ]
+        );
+        // names must end at a word boundary
+        assert_eq!(
+            parse_msg("@bobby & @bob", &names),
+            [ChatMsgPart::Text("@bobby & "), ChatMsgPart::Mention("bob")]
+        );
+        assert_eq!(
+            parse_msg("@alice. @ @unknown", &names),
+            [
+                ChatMsgPart::Mention("alice"),
+                ChatMsgPart::Text(". @ @unknown"),
+            ]
+        );
+        assert_eq!(parse_msg("äö@", &names), [ChatMsgPart::Text("äö@")]);
+
+        assert!(mentions("thanks @ALICE!", "alice"));
+        assert!(!mentions("thanks alice", "alice"));
+        assert!(!mentions("thanks @alice", ""));
+
+        let (at, completions) = mention_completions("hi @Bo", names).unwrap();
+        assert_eq!(at, 3);
+        assert_eq!(completions, ["bob", "bob the builder"]);
+        assert!(mention_completions("hi @bob the builder", names).is_none());
+        assert!(mention_completions("hi bob", names).is_none());
+        let mut input = "hi @Bo".to_string();
+        complete_mention(&mut input, at, completions[1]);
+        assert_eq!(input, "hi @bob the builder ");
+    }
+
+    #[test]
+    fn pos_links() {
+        let link = MapPosLink {
+            pos: vec2::new(12.5, -3.256),
+            zoom: 1.5,
+        };
+        let encoded = link.encode();
+        assert_eq!(encoded, "[pos:12.50,-3.26,1.50]");
+        let decoded = MapPosLink::decode(&encoded).unwrap();
+        assert_eq!(decoded.pos, vec2::new(12.5, -3.26));
+        assert_eq!(decoded.zoom, 1.5);
+
+        assert!(MapPosLink::decode("[pos:1,2]").is_none());
+        assert!(MapPosLink::decode("[pos:1,2,3,4]").is_none());
+        assert!(MapPosLink::decode("[pos:1,2,0]").is_none());
+        assert!(MapPosLink::decode("[pos:1,NaN,1]").is_none());
+        assert!(MapPosLink::decode("[pos:1,2,1").is_none());
+
+        assert_eq!(
+            parse_msg("broken tile [pos:1,2,1] & [pos:x] @bob", &["bob"]),
+            [
+                ChatMsgPart::Text("broken tile "),
+                ChatMsgPart::PosLink(MapPosLink {
+                    pos: vec2::new(1.0, 2.0),
+                    zoom: 1.0,
+                }),
+                ChatMsgPart::Text(" & [pos:x] "),
+                ChatMsgPart::Mention("bob"),
+            ]
+        );
+    }
+}
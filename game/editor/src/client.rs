@@ -1,5 +1,4 @@
 use std::{
-    collections::VecDeque,
     sync::{Arc, atomic::AtomicBool},
     time::Duration,
 };
@@ -22,14 +21,21 @@ use sound::sound_mt::SoundMultiThreaded;
 use crate::{
     action_logic::{redo_action, undo_action},
     actions::actions::{EditorAction, EditorActionGroup},
+    chat::{EditorChatMsg, mentions},
+    color_palette::EditorColorPalette,
     event::{
         ActionDbg, AdminChangeConfig, AdminConfigState, ClientProps, EditorCommand, EditorEvent,
         EditorEventAutoMap, EditorEventClientToServer, EditorEventGenerator, EditorEventLayerIndex,
-        EditorEventOverwriteMap, EditorEventRuleTy, EditorEventServerToClient, EditorNetEvent,
+        EditorEventOverwriteMap, EditorEventRuleTy, EditorEventServerToClient,
+        EditorEventSnapshotChunk, EditorNetEvent,
     },
+    guides::EditorGuides,
+    history::EditorHistoryInfo,
+    locks::{EditorLock, EditorLockLayer, EditorLockRect, find_conflict},
     map::{EditorLayer, EditorLayerTile, EditorMap},
     network::{EditorNetwork, NetworkState},
     notifications::{EditorNotification, EditorNotifications},
+    snapshot::SnapshotDownload,
     tab::{EditorAdminPanel, EditorAdminPanelState},
     tools::tile_layer::auto_mapper::{TileLayerAutoMapper, TileLayerAutoMapperRuleType},
 };
@@ -48,11 +54,25 @@ pub struct EditorClient {
     pub(crate) server_id: u64,
     pub(crate) allows_remote_admin: bool,
 
-    pub(crate) msgs: VecDeque<(String, String)>,
+    /// All chat messages of the session, oldest first.
+    pub(crate) msgs: Vec<EditorChatMsg>,
+
+    /// Locks of all users, including the own ones
+    pub(crate) locks: Vec<EditorLock>,
+
+    /// The history of actions, only received while subscribed
+    pub(crate) history: Option<EditorHistoryInfo>,
+    history_subscribed: bool,
 
     pub(crate) undo_label: Option<String>,
     pub(crate) redo_label: Option<String>,
 
+    /// The map snapshot that is currently downloaded
+    pub(crate) snapshot_download: Option<SnapshotDownload>,
+    /// A finished snapshot download that was not saved yet
+    pub(crate) downloaded_snapshot: Option<EditorEventOverwriteMap>,
+    snapshot_ids: u64,
+
     pub(crate) should_save: bool,
 
     last_keep_alive_id_and_time: (Option<u64>, Duration),
@@ -92,10 +112,18 @@ impl EditorClient {
             server_id: Default::default(),
             allows_remote_admin: false,
             msgs: Default::default(),
+            locks: Default::default(),
+
+            history: None,
+            history_subscribed: false,
 
             undo_label: None,
             redo_label: None,
 
+            snapshot_download: None,
+            downloaded_snapshot: None,
+            snapshot_ids: 0,
+
             mapper_name: mapper_name.unwrap_or_else(|| "mapper".to_string()),
             color: color.unwrap_or([255, 255, 255]),
 
@@ -286,17 +314,29 @@ impl EditorClient {
                                 self.allows_remote_admin = allows_remote_admin;
                             }
                             EditorEventServerToClient::Chat { from, msg } => {
-                                self.notifications
-                                    .push(EditorNotification::Info(format!("{from}: {msg}")));
-                                self.msgs.push_front((from, msg));
-                                self.msgs.truncate(30);
+                                let mentions_me = mentions(&msg, &self.mapper_name);
+                                self.notifications.push(if mentions_me {
+                                    EditorNotification::Warning(format!(
+                                        "{from} mentioned you: {msg}"
+                                    ))
+                                } else {
+                                    EditorNotification::Info(format!("{from}: {msg}"))
+                                });
+                                self.msgs.push(EditorChatMsg {
+                                    from,
+                                    msg,
+                                    mentions_me,
+                                });
                             }
                             EditorEventServerToClient::AdminAuthed => {
                                 admin_panel.state = match admin_panel.state.clone() {
                                     EditorAdminPanelState::NonAuthed(state) => {
                                         EditorAdminPanelState::Authed(AdminChangeConfig {
                                             password: state.password,
-                                            state: AdminConfigState { auto_save: None },
+                                            state: AdminConfigState {
+                                                auto_save: None,
+                                                backup_every_nth_action: None,
+                                            },
                                         })
                                     }
                                     EditorAdminPanelState::Authed(state) => {
@@ -346,6 +386,21 @@ impl EditorClient {
                             } => {
                                 self.set_live_edit_layer(map, layer_index, live_edit);
                             }
+                            EditorEventServerToClient::Locks(locks) => {
+                                self.locks = locks;
+                            }
+                            EditorEventServerToClient::History(history) => {
+                                self.history = Some(history);
+                            }
+                            EditorEventServerToClient::SnapshotChunk(chunk) => {
+                                self.on_snapshot_chunk(chunk);
+                            }
+                            EditorEventServerToClient::Guides(guides) => {
+                                map.user.options.guides = guides;
+                            }
+                            EditorEventServerToClient::Palette(palette) => {
+                                map.user.options.palette = palette;
+                            }
                         }
                     }
 
@@ -386,7 +441,24 @@ impl EditorClient {
         Ok(res)
     }
 
+    /// Rejects actions that modify areas locked by other users,
+    /// the server would reject them anyway.
+    fn check_locks(&self, actions: &[EditorAction]) -> bool {
+        if let Some(lock) = find_conflict(&self.locks, self.server_id, actions) {
+            self.notifications.push(EditorNotification::Warning(format!(
+                "This area is locked by {}.",
+                lock.owner_name
+            )));
+            false
+        } else {
+            true
+        }
+    }
+
     pub fn execute(&self, action: EditorAction, group_identifier: Option<&str>) {
+        if !self.check_locks(std::slice::from_ref(&action)) {
+            return;
+        }
         self.network
             .send(EditorEvent::Client(EditorEventClientToServer::Action(
                 EditorActionGroup {
@@ -397,6 +469,9 @@ impl EditorClient {
     }
 
     pub fn execute_group(&self, action_group: EditorActionGroup) {
+        if !self.check_locks(&action_group.actions) {
+            return;
+        }
         self.network
             .send(EditorEvent::Client(EditorEventClientToServer::Action(
                 action_group,
@@ -417,6 +492,93 @@ impl EditorClient {
             )));
     }
 
+    /// Undo or redo until the given history entry is the current one.
+    pub fn jump_history(&self, index: Option<usize>) {
+        self.network
+            .send(EditorEvent::Client(EditorEventClientToServer::Command(
+                EditorCommand::JumpTo { index },
+            )));
+    }
+
+    /// Subscribe to the history of actions, e.g. while the history panel is open.
+    pub fn subscribe_history(&mut self, subscribe: bool) {
+        if self.history_subscribed == subscribe {
+            return;
+        }
+        self.history_subscribed = subscribe;
+        if !subscribe {
+            self.history = None;
+        }
+        self.network.send(EditorEvent::Client(
+            EditorEventClientToServer::HistorySubscribe { subscribe },
+        ));
+    }
+
+    /// Requests the current map of the server,
+    /// see [`Self::downloaded_snapshot`].
+    pub fn request_snapshot(&mut self) {
+        let id = self.snapshot_ids;
+        self.snapshot_ids += 1;
+        self.snapshot_download = Some(SnapshotDownload::new(id));
+        self.network.send(EditorEvent::Client(
+            EditorEventClientToServer::RequestSnapshot { id },
+        ));
+    }
+
+    fn on_snapshot_chunk(&mut self, chunk: EditorEventSnapshotChunk) {
+        let Some(download) = &mut self.snapshot_download else {
+            return;
+        };
+        let snapshot = download.add(chunk).and_then(|data| {
+            data.map(|data| {
+                bincode::serde::decode_from_slice::<EditorEventOverwriteMap, _>(
+                    &data,
+                    bincode::config::standard().with_limit::<{ 1024 * 1024 * 1024 }>(),
+                )
+                .map(|(snapshot, _)| snapshot)
+            })
+            .transpose()
+            .map_err(|err| anyhow!(err))
+        });
+        match snapshot {
+            Ok(Some(snapshot)) => {
+                self.snapshot_download = None;
+                self.downloaded_snapshot = Some(snapshot);
+            }
+            Ok(None) => {}
+            Err(err) => {
+                self.snapshot_download = None;
+                self.notifications.push(EditorNotification::Error(format!(
+                    "Failed to download the map: {err}"
+                )));
+            }
+        }
+    }
+
+    /// Locks a whole layer (`rect` is `None`) or only a tile area of it
+    /// for all other users.
+    pub fn lock(&self, layer: EditorLockLayer, rect: Option<EditorLockRect>) {
+        self.network
+            .send(EditorEvent::Client(EditorEventClientToServer::Lock {
+                layer,
+                rect,
+            }));
+    }
+
+    pub fn unlock(&self, id: u64) {
+        self.network
+            .send(EditorEvent::Client(EditorEventClientToServer::Unlock {
+                id,
+            }));
+    }
+
+    /// The locks of the own user
+    pub fn own_locks(&self) -> impl Iterator<Item = &EditorLock> {
+        self.locks
+            .iter()
+            .filter(|lock| lock.owner == self.server_id)
+    }
+
     pub fn update_info(&self, cursor_world_pos: vec2) {
         if !self.network.is_connected() {
             return;
@@ -434,6 +596,22 @@ impl EditorClient {
             )));
     }
 
+    /// Shares the guides with all other users.
+    pub fn set_guides(&self, guides: EditorGuides) {
+        self.network
+            .send(EditorEvent::Client(EditorEventClientToServer::Guides(
+                guides,
+            )));
+    }
+
+    /// Shares the color palette with all other users.
+    pub fn set_palette(&self, palette: EditorColorPalette) {
+        self.network
+            .send(EditorEvent::Client(EditorEventClientToServer::Palette(
+                palette,
+            )));
+    }
+
     pub fn send_chat(&self, msg: String) {
         self.network
             .send(EditorEvent::Client(EditorEventClientToServer::Chat { msg }));
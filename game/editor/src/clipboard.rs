@@ -0,0 +1,446 @@
+use map::map::{
+    groups::layers::design::{Quad, Sound},
+    resources::MapResourceRef,
+};
+use math::math::vector::{ffixed, fvec2, vec2};
+use serde::{Deserialize, Serialize};
+
+use crate::map::EditorMap;
+
+/// Identifies the json payload, other clipboard contents are ignored.
+const CLIPBOARD_TAG: &str = "ddnet-rs-editor-clipboard-v1";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AnimTy {
+    Pos,
+    Color,
+    Sound,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EditorClipboardItems {
+    Quads(Vec<Quad>),
+    Sounds(Vec<Sound>),
+}
+
+impl EditorClipboardItems {
+    /// Calls `f` for every animation index the items reference.
+    fn visit_anims(&mut self, mut f: impl FnMut(AnimTy, &mut Option<usize>)) {
+        match self {
+            Self::Quads(quads) => {
+                for quad in quads.iter_mut() {
+                    f(AnimTy::Pos, &mut quad.pos_anim);
+                    f(AnimTy::Color, &mut quad.color_anim);
+                }
+            }
+            Self::Sounds(sounds) => {
+                for sound in sounds.iter_mut() {
+                    f(AnimTy::Pos, &mut sound.pos_anim);
+                    f(AnimTy::Sound, &mut sound.sound_anim);
+                }
+            }
+        }
+    }
+
+    /// The top left of all points.
+    fn min_pos(&self) -> Option<fvec2> {
+        let points: Vec<fvec2> = match self {
+            Self::Quads(quads) => quads.iter().flat_map(|q| q.points).collect(),
+            Self::Sounds(sounds) => sounds.iter().map(|s| s.pos).collect(),
+        };
+        points
+            .into_iter()
+            .reduce(|a, b| fvec2::new(a.x.min(b.x), a.y.min(b.y)))
+    }
+
+    fn translate(&mut self, offset: fvec2) {
+        match self {
+            Self::Quads(quads) => {
+                for point in quads.iter_mut().flat_map(|q| q.points.iter_mut()) {
+                    point.x += offset.x;
+                    point.y += offset.y;
+                }
+            }
+            Self::Sounds(sounds) => {
+                for sound in sounds.iter_mut() {
+                    sound.pos.x += offset.x;
+                    sound.pos.y += offset.y;
+                }
+            }
+        }
+    }
+}
+
+/// The resources & animations of the map a clipboard is copied from
+/// or pasted into, for the active quad or sound layer.
+#[derive(Debug, Default)]
+pub struct EditorClipboardLayer<'a> {
+    /// The images or sounds of the map, depending on the layer type.
+    pub resources: Vec<&'a MapResourceRef>,
+    /// The image or sound of the layer.
+    pub layer_resource: Option<usize>,
+
+    pub pos_anims: Vec<&'a str>,
+    pub color_anims: Vec<&'a str>,
+    pub sound_anims: Vec<&'a str>,
+}
+
+impl<'a> EditorClipboardLayer<'a> {
+    pub fn new(map: &'a EditorMap, is_sound_layer: bool, layer_resource: Option<usize>) -> Self {
+        Self {
+            resources: if is_sound_layer {
+                map.resources.sounds.iter().map(|r| &r.def).collect()
+            } else {
+                map.resources.images.iter().map(|r| &r.def).collect()
+            },
+            layer_resource,
+            pos_anims: map
+                .animations
+                .pos
+                .iter()
+                .map(|a| a.def.name.as_str())
+                .collect(),
+            color_anims: map
+                .animations
+                .color
+                .iter()
+                .map(|a| a.def.name.as_str())
+                .collect(),
+            sound_anims: map
+                .animations
+                .sound
+                .iter()
+                .map(|a| a.def.name.as_str())
+                .collect(),
+        }
+    }
+
+    fn anims(&self, ty: AnimTy) -> &[&'a str] {
+        match ty {
+            AnimTy::Pos => &self.pos_anims,
+            AnimTy::Color => &self.color_anims,
+            AnimTy::Sound => &self.sound_anims,
+        }
+    }
+
+    /// Finds the resource by its hash, or by its name otherwise.
+    fn find_resource(&self, res: &MapResourceRef) -> Option<usize> {
+        self.resources
+            .iter()
+            .position(|r| r.meta.blake3_hash == res.meta.blake3_hash)
+            .or_else(|| self.resources.iter().position(|r| r.name == res.name))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EditorClipboardPasteMode {
+    /// The top left of the items is placed at the given world position.
+    AtCursor(vec2),
+    /// The items keep the coordinates they were copied from.
+    AtOriginalPos,
+}
+
+/// The items to add to the layer, with all references
+/// resolved for the target map.
+#[derive(Debug, Clone)]
+pub struct EditorClipboardPaste {
+    pub items: EditorClipboardItems,
+    /// Human readable messages about references that could not be resolved.
+    pub warnings: Vec<String>,
+}
+
+/// Quads or sound sources in the OS clipboard as json,
+/// so they can be pasted into other editor instances.
+///
+/// Animations are referenced by name, since indices differ between maps.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EditorClipboard {
+    tag: String,
+
+    /// Animation indices of the items point into the name lists of this clipboard.
+    pub items: EditorClipboardItems,
+    /// The image or sound of the layer the items were copied from.
+    pub resource: Option<MapResourceRef>,
+
+    pub pos_anims: Vec<String>,
+    pub color_anims: Vec<String>,
+    pub sound_anims: Vec<String>,
+}
+
+impl EditorClipboard {
+    pub fn new(mut items: EditorClipboardItems, layer: &EditorClipboardLayer) -> Self {
+        let mut pos_anims: Vec<String> = Default::default();
+        let mut color_anims: Vec<String> = Default::default();
+        let mut sound_anims: Vec<String> = Default::default();
+        items.visit_anims(|ty, index| {
+            let names = match ty {
+                AnimTy::Pos => &mut pos_anims,
+                AnimTy::Color => &mut color_anims,
+                AnimTy::Sound => &mut sound_anims,
+            };
+            *index = index
+                .and_then(|index| layer.anims(ty).get(index))
+                .map(|name| {
+                    names.iter().position(|n| n == name).unwrap_or_else(|| {
+                        names.push(name.to_string());
+                        names.len() - 1
+                    })
+                });
+        });
+        Self {
+            tag: CLIPBOARD_TAG.to_string(),
+            items,
+            resource: layer
+                .layer_resource
+                .and_then(|index| layer.resources.get(index))
+                .map(|&res| res.clone()),
+            pos_anims,
+            color_anims,
+            sound_anims,
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// `None` if the text is not an editor clipboard.
+    pub fn decode(text: &str) -> Option<Self> {
+        serde_json::from_str::<Self>(text)
+            .ok()
+            .filter(|clipboard| clipboard.tag == CLIPBOARD_TAG)
+    }
+
+    fn anims(&self, ty: AnimTy) -> &[String] {
+        match ty {
+            AnimTy::Pos => &self.pos_anims,
+            AnimTy::Color => &self.color_anims,
+            AnimTy::Sound => &self.sound_anims,
+        }
+    }
+
+    /// Resolves the references of the items for the target layer.
+    ///
+    /// Animations that don't exist in the target map are unassigned.
+    pub fn paste(
+        &self,
+        layer: &EditorClipboardLayer,
+        mode: EditorClipboardPasteMode,
+    ) -> EditorClipboardPaste {
+        let mut warnings: Vec<String> = Default::default();
+        let mut warn = |msg: String| {
+            if !warnings.contains(&msg) {
+                warnings.push(msg);
+            }
+        };
+
+        if let Some(res) = &self.resource {
+            let ty = match self.items {
+                EditorClipboardItems::Quads(_) => "image",
+                EditorClipboardItems::Sounds(_) => "sound",
+            };
+            match layer.find_resource(res) {
+                Some(index) if Some(index) == layer.layer_resource => {}
+                Some(_) => warn(format!(
+                    "The copied items used the {ty} \"{}\", \
+                    which is not the {ty} of this layer.",
+                    res.name.as_str()
+                )),
+                None => warn(format!(
+                    "The {ty} \"{}\" of the copied items does not exist in this map.",
+                    res.name.as_str()
+                )),
+            }
+        }
+
+        let mut items = self.items.clone();
+        items.visit_anims(|ty, index| {
+            let Some(name) = index.and_then(|index| self.anims(ty).get(index)) else {
+                *index = None;
+                return;
+            };
+            *index = (!name.is_empty())
+                .then(|| layer.anims(ty).iter().position(|n| n == name))
+                .flatten();
+            if index.is_none() {
+                warn(if name.is_empty() {
+                    "Unnamed animations can't be matched, they were unassigned.".to_string()
+                } else {
+                    format!(
+                        "The animation \"{name}\" does not exist in this map, it was unassigned."
+                    )
+                });
+            }
+        });
+
+        if let EditorClipboardPasteMode::AtCursor(pos) = mode
+            && let Some(min) = items.min_pos()
+        {
+            items.translate(fvec2::new(
+                ffixed::from_num(pos.x) - min.x,
+                ffixed::from_num(pos.y) - min.y,
+            ));
+        }
+
+        EditorClipboardPaste { items, warnings }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use base::{hash::generate_hash_for, reduced_ascii_str::ReducedAsciiString};
+    use map::map::{
+        groups::layers::design::{Quad, Sound, SoundShape},
+        resources::{MapResourceMetaData, MapResourceRef},
+    };
+    use math::math::vector::{ffixed, fvec2, uffixed, vec2};
+
+    use super::{
+        EditorClipboard, EditorClipboardItems, EditorClipboardLayer, EditorClipboardPasteMode,
+    };
+
+    fn res(name: &str, file: &[u8]) -> MapResourceRef {
+        MapResourceRef {
+            name: ReducedAsciiString::from_str_lossy(name),
+            meta: MapResourceMetaData {
+                blake3_hash: generate_hash_for(file),
+                ty: ReducedAsciiString::from_str_lossy("png"),
+            },
+            hq_meta: None,
+        }
+    }
+
+    fn quad(x: f32, y: f32, pos_anim: Option<usize>, color_anim: Option<usize>) -> Quad {
+        let mut quad = Quad {
+            pos_anim,
+            color_anim,
+            ..Default::default()
+        };
+        for (i, point) in quad.points.iter_mut().enumerate() {
+            *point = fvec2::new(
+                ffixed::from_num(x + (i % 2) as f32),
+                ffixed::from_num(y + (i / 2) as f32),
+            );
+        }
+        quad
+    }
+
+    #[test]
+    fn payload_round_trip() {
+        let grass = res("grass", b"grass");
+        let layer = EditorClipboardLayer {
+            resources: vec![&grass],
+            layer_resource: Some(0),
+            pos_anims: vec!["a", "b"],
+            color_anims: vec!["c"],
+            ..Default::default()
+        };
+        let clipboard = EditorClipboard::new(
+            EditorClipboardItems::Quads(vec![
+                quad(1.5, -2.0, Some(1), None),
+                quad(3.0, 4.0, Some(1), Some(0)),
+            ]),
+            &layer,
+        );
+        // only the used animations are part of the payload
+        assert_eq!(clipboard.pos_anims, ["b"]);
+        assert_eq!(clipboard.color_anims, ["c"]);
+        assert_eq!(clipboard.resource.as_ref(), Some(&grass));
+        let EditorClipboardItems::Quads(quads) = &clipboard.items else {
+            panic!("expected quads");
+        };
+        assert_eq!(quads[0].pos_anim, Some(0));
+        assert_eq!(quads[1].color_anim, Some(0));
+
+        let text = clipboard.encode();
+        assert_eq!(EditorClipboard::decode(&text), Some(clipboard));
+
+        let sounds = EditorClipboard::new(
+            EditorClipboardItems::Sounds(vec![Sound {
+                pos: fvec2::new(ffixed::from_num(2.0), ffixed::from_num(3.0)),
+                looped: true,
+                panning: false,
+                time_delay: Default::default(),
+                falloff: Default::default(),
+                pos_anim: None,
+                pos_anim_offset: Default::default(),
+                sound_anim: None,
+                sound_anim_offset: Default::default(),
+                shape: SoundShape::Circle {
+                    radius: uffixed::from_num(10.0),
+                },
+            }]),
+            &EditorClipboardLayer::default(),
+        );
+        assert_eq!(EditorClipboard::decode(&sounds.encode()), Some(sounds));
+
+        // other clipboard contents are ignored
+        assert!(EditorClipboard::decode("some text").is_none());
+        assert!(EditorClipboard::decode(&text.replace("-v1", "-v0")).is_none());
+    }
+
+    #[test]
+    fn resource_matching() {
+        let grass = res("grass", b"grass");
+        let src = EditorClipboardLayer {
+            resources: vec![&grass],
+            layer_resource: Some(0),
+            pos_anims: vec!["move", ""],
+            color_anims: vec!["blink"],
+            ..Default::default()
+        };
+        let clipboard = EditorClipboard::new(
+            EditorClipboardItems::Quads(vec![
+                quad(10.0, 20.0, Some(0), Some(0)),
+                quad(12.0, 18.0, Some(1), None),
+            ]),
+            &src,
+        );
+
+        // same image with another name & the same animation names at other indices
+        let renamed_grass = res("grass_renamed", b"grass");
+        let stone = res("stone", b"stone");
+        let dst = EditorClipboardLayer {
+            resources: vec![&stone, &renamed_grass],
+            layer_resource: Some(1),
+            pos_anims: vec!["other", "move"],
+            color_anims: vec!["blink"],
+            ..Default::default()
+        };
+        let paste = clipboard.paste(&dst, EditorClipboardPasteMode::AtOriginalPos);
+        let EditorClipboardItems::Quads(quads) = &paste.items else {
+            panic!("expected quads");
+        };
+        assert_eq!(quads[0].pos_anim, Some(1));
+        assert_eq!(quads[0].color_anim, Some(0));
+        assert_eq!(quads[0].points[0].x, ffixed::from_num(10.0));
+        // unnamed animations can't be matched
+        assert_eq!(quads[1].pos_anim, None);
+        assert_eq!(paste.warnings.len(), 1);
+
+        // missing image & animations fall back to unassigned
+        let dst = EditorClipboardLayer {
+            resources: vec![&stone],
+            layer_resource: Some(0),
+            pos_anims: vec!["other"],
+            ..Default::default()
+        };
+        let paste = clipboard.paste(
+            &dst,
+            EditorClipboardPasteMode::AtCursor(vec2::new(-1.0, 2.0)),
+        );
+        let EditorClipboardItems::Quads(quads) = &paste.items else {
+            panic!("expected quads");
+        };
+        assert!(
+            quads
+                .iter()
+                .all(|q| q.pos_anim.is_none() && q.color_anim.is_none())
+        );
+        assert_eq!(paste.warnings.len(), 4);
+        assert!(paste.warnings[0].contains("\"grass\""));
+        // the top left of all quads is at the cursor
+        assert_eq!(quads[0].points[0].x, ffixed::from_num(-1.0));
+        assert_eq!(quads[1].points[0].y, ffixed::from_num(2.0));
+    }
+}
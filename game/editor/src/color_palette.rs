@@ -0,0 +1,209 @@
+use serde::{Deserialize, Serialize};
+
+/// How many recently used colors a palette remembers.
+pub const MAX_RECENT_COLORS: usize = 16;
+
+/// A color of the palette, that the mapper gave a name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditorColorSwatch {
+    pub name: String,
+    /// Unmultiplied srgba.
+    pub color: [u8; 4],
+}
+
+/// The colors used in a map, they are only known to the editor.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditorColorPalette {
+    /// The recently used colors, the newest first.
+    #[serde(default)]
+    pub recent: Vec<[u8; 4]>,
+    #[serde(default)]
+    pub swatches: Vec<EditorColorSwatch>,
+}
+
+impl EditorColorPalette {
+    /// Moves the color to the front of the recent colors.
+    ///
+    /// Returns `false` if it already was the newest color.
+    pub fn add_recent(&mut self, color: [u8; 4]) -> bool {
+        if self.recent.first() == Some(&color) {
+            return false;
+        }
+        self.recent.retain(|recent| *recent != color);
+        self.recent.insert(0, color);
+        self.recent.truncate(MAX_RECENT_COLORS);
+        true
+    }
+
+    /// Adds a named swatch, an existing swatch with the same name is overwritten.
+    pub fn set_swatch(&mut self, name: &str, color: [u8; 4]) {
+        match self.swatches.iter_mut().find(|swatch| swatch.name == name) {
+            Some(swatch) => swatch.color = color,
+            None => self.swatches.push(EditorColorSwatch {
+                name: name.to_string(),
+                color,
+            }),
+        }
+    }
+
+    pub fn remove_swatch(&mut self, name: &str) {
+        self.swatches.retain(|swatch| swatch.name != name);
+    }
+}
+
+/// Formats the color as `#rrggbbaa`.
+pub fn color_to_hex(color: [u8; 4]) -> String {
+    format!(
+        "#{:02x}{:02x}{:02x}{:02x}",
+        color[0], color[1], color[2], color[3]
+    )
+}
+
+/// Parses `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`, the `#` is optional.
+/// Colors without alpha are opaque.
+pub fn color_from_hex(text: &str) -> Option<[u8; 4]> {
+    let text = text.trim();
+    let text = text.strip_prefix('#').unwrap_or(text);
+    if !text.is_ascii() {
+        return None;
+    }
+    let digits: Vec<u8> = match text.len() {
+        3 | 4 => text
+            .chars()
+            .map(|c| c.to_digit(16).map(|d| d as u8 * 0x11))
+            .collect::<Option<_>>()?,
+        6 | 8 => (0..text.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&text[i..i + 2], 16).ok())
+            .collect::<Option<_>>()?,
+        _ => return None,
+    };
+    Some([
+        digits[0],
+        digits[1],
+        digits[2],
+        digits.get(3).copied().unwrap_or(u8::MAX),
+    ])
+}
+
+/// The color of a layer or quad is multiplied with its texture,
+/// this returns the texture's color of a rendered color.
+///
+/// Only the rgb channels are recovered, blending with what is below
+/// is not undone. `None` if the tint has a black channel, that channel's
+/// source value is lost.
+pub fn untint_color(rendered: [u8; 4], tint: [u8; 4]) -> Option<[u8; 4]> {
+    let mut source = rendered;
+    for (source, tint) in source.iter_mut().zip(tint).take(3) {
+        if tint == 0 {
+            return None;
+        }
+        *source = ((*source as u32 * 255 + tint as u32 / 2) / tint as u32).min(255) as u8;
+    }
+    Some(source)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::guides::EditorMapSidecar;
+
+    use super::{
+        EditorColorPalette, MAX_RECENT_COLORS, color_from_hex, color_to_hex, untint_color,
+    };
+
+    #[test]
+    fn palette_persistence() {
+        let mut palette = EditorColorPalette::default();
+        assert!(palette.add_recent([255, 0, 0, 255]));
+        assert!(palette.add_recent([0, 255, 0, 255]));
+        // already the newest
+        assert!(!palette.add_recent([0, 255, 0, 255]));
+        // moves to the front instead of being added twice
+        assert!(palette.add_recent([255, 0, 0, 255]));
+        assert_eq!(palette.recent, vec![[255, 0, 0, 255], [0, 255, 0, 255]]);
+        for i in 0..MAX_RECENT_COLORS as u8 * 2 {
+            palette.add_recent([i, i, i, 255]);
+        }
+        assert_eq!(palette.recent.len(), MAX_RECENT_COLORS);
+
+        palette.set_swatch("water", [20, 40, 200, 128]);
+        palette.set_swatch("grass", [20, 200, 40, 255]);
+        palette.set_swatch("water", [20, 40, 180, 128]);
+        assert_eq!(palette.swatches.len(), 2);
+        assert_eq!(palette.swatches[0].color, [20, 40, 180, 128]);
+
+        let sidecar = EditorMapSidecar {
+            palette: palette.clone(),
+            ..Default::default()
+        };
+        let file = sidecar.to_json().unwrap();
+        assert_eq!(EditorMapSidecar::from_json(&file).unwrap().palette, palette);
+
+        palette.remove_swatch("water");
+        assert_eq!(palette.swatches.len(), 1);
+        assert_eq!(palette.swatches[0].name, "grass");
+
+        // sidecars without a palette
+        assert_eq!(
+            EditorMapSidecar::from_json(br#"{"guides": {"guides": [], "locked": false}}"#)
+                .unwrap()
+                .palette,
+            EditorColorPalette::default()
+        );
+    }
+
+    #[test]
+    fn hex_with_alpha() {
+        assert_eq!(color_to_hex([255, 128, 0, 64]), "#ff800040");
+        assert_eq!(color_from_hex("#ff800040"), Some([255, 128, 0, 64]));
+        assert_eq!(color_from_hex(" FF8000 "), Some([255, 128, 0, 255]));
+        assert_eq!(color_from_hex("#f80"), Some([255, 136, 0, 255]));
+        assert_eq!(color_from_hex("f804"), Some([255, 136, 0, 68]));
+        assert_eq!(color_from_hex("#ff80"), Some([255, 255, 136, 0]));
+        assert_eq!(color_from_hex("#ff8000f"), None);
+        assert_eq!(color_from_hex("#gg8000"), None);
+        assert_eq!(color_from_hex("#ä8000"), None);
+        assert_eq!(color_from_hex(""), None);
+    }
+
+    #[test]
+    fn tint_unapply() {
+        // a white tint changes nothing
+        assert_eq!(
+            untint_color([10, 128, 250, 255], [255, 255, 255, 255]),
+            Some([10, 128, 250, 255])
+        );
+        // a half red tint halved the red channel
+        assert_eq!(
+            untint_color([100, 200, 50, 255], [128, 255, 255, 255]),
+            Some([199, 200, 50, 255])
+        );
+        // the tint's alpha does not matter, the rendered alpha is kept
+        assert_eq!(
+            untint_color([100, 200, 50, 255], [255, 255, 255, 0]),
+            Some([100, 200, 50, 255])
+        );
+        // brighter than the tint allows, e.g. blended with something else
+        assert_eq!(
+            untint_color([200, 0, 0, 255], [100, 255, 255, 255]),
+            Some([255, 0, 0, 255])
+        );
+        // a black channel can't be recovered
+        assert_eq!(untint_color([0, 200, 50, 255], [0, 255, 255, 255]), None);
+
+        // tinting the untinted color results in the rendered color again
+        let tint = [200, 150, 100, 255];
+        let source = [240, 120, 60, 255];
+        let rendered: [u8; 4] = std::array::from_fn(|i| {
+            if i < 3 {
+                ((source[i] as u32 * tint[i] as u32 + 127) / 255) as u8
+            } else {
+                source[i]
+            }
+        });
+        let untinted = untint_color(rendered, tint).unwrap();
+        for i in 0..3 {
+            assert!(untinted[i].abs_diff(source[i]) <= 1);
+        }
+    }
+}
@@ -402,6 +402,7 @@ fn add_tile_layer_invalid(map: &EditorMap) -> Vec<EditorAction> {
                         } else {
                             Some(rand::rng().next_u64() as usize)
                         },
+                        blend_mode: Default::default(),
                     },
                     tiles: vec![
                         Default::default();
@@ -445,6 +446,7 @@ fn add_quad_layer_invalid(map: &EditorMap) -> Vec<EditorAction> {
                             Some(rand::rng().next_u64() as usize)
                         },
                         high_detail: Default::default(),
+                        blend_mode: Default::default(),
                     },
                     quads: Default::default(),
                     name: Default::default(),
@@ -520,6 +522,7 @@ fn rem_design_layer_invalid(_map: &EditorMap) -> Vec<EditorAction> {
                         } else {
                             Some(rand::rng().next_u64() as usize)
                         },
+                        blend_mode: Default::default(),
                     },
                     tiles: vec![
                         Default::default();
@@ -544,6 +547,7 @@ fn rem_design_layer_invalid(_map: &EditorMap) -> Vec<EditorAction> {
                             Some(rand::rng().next_u64() as usize)
                         },
                         high_detail: Default::default(),
+                        blend_mode: Default::default(),
                     },
                     quads: Default::default(),
                     name: Default::default(),
@@ -832,6 +836,7 @@ fn change_layer_design_attr_invalid(map: &EditorMap) -> Vec<EditorAction> {
                             } else {
                                 Some(rand::rng().next_u64() as usize)
                             },
+                            blend_mode: Default::default(),
                         },
                         new_attr: MapTileLayerAttr {
                             width: (w as u16).try_into().unwrap(),
@@ -849,6 +854,7 @@ fn change_layer_design_attr_invalid(map: &EditorMap) -> Vec<EditorAction> {
                             } else {
                                 Some(rand::rng().next_u64() as usize)
                             },
+                            blend_mode: Default::default(),
                         },
                         old_tiles: vec![Default::default(); len],
                         new_tiles: vec![Default::default(); len],
@@ -866,6 +872,7 @@ fn change_layer_design_attr_invalid(map: &EditorMap) -> Vec<EditorAction> {
                         Some(rand::rng().next_u64() as usize)
                     },
                     high_detail: Default::default(),
+                    blend_mode: Default::default(),
                 },
                 new_attr: MapLayerQuadsAttrs {
                     image: if rand::rng().next_u64().is_multiple_of(2) {
@@ -874,6 +881,7 @@ fn change_layer_design_attr_invalid(map: &EditorMap) -> Vec<EditorAction> {
                         Some(rand::rng().next_u64() as usize)
                     },
                     high_detail: Default::default(),
+                    blend_mode: Default::default(),
                 },
             })),
             2 => Some(EditorAction::ChangeSoundLayerAttr(
@@ -1263,6 +1271,7 @@ fn set_metadata_invalid(_map: &EditorMap) -> Vec<EditorAction> {
             version: format!("{}", rand::rng().next_u64()),
             credits: format!("{}", rand::rng().next_u64()),
             memo: format!("{}", rand::rng().next_u64()),
+            theme_ambiance: Default::default(),
         },
         new_meta: Metadata {
             authors: {
@@ -1286,6 +1295,7 @@ fn set_metadata_invalid(_map: &EditorMap) -> Vec<EditorAction> {
             version: format!("{}", rand::rng().next_u64()),
             credits: format!("{}", rand::rng().next_u64()),
             memo: format!("{}", rand::rng().next_u64()),
+            theme_ambiance: Default::default(),
         },
     })]
 }
@@ -908,6 +908,7 @@ pub(crate) fn add_tile_layer_valid(map: &EditorMap) -> Vec<EditorAction> {
                                         % map.resources.image_arrays.len()
                                 })
                             },
+                            blend_mode: Default::default(),
                         },
                         tiles: vec![Default::default(); (w * h) as usize],
                         name: Default::default(),
@@ -951,6 +952,7 @@ pub(crate) fn add_quad_layer_valid(map: &EditorMap) -> Vec<EditorAction> {
                                 })
                             },
                             high_detail: Default::default(),
+                            blend_mode: Default::default(),
                         },
                         quads: Default::default(),
                         name: Default::default(),
@@ -1576,6 +1578,7 @@ pub(crate) fn change_layer_design_attr_valid(map: &EditorMap) -> Vec<EditorActio
                                             % map.resources.image_arrays.len()
                                     })
                                 },
+                                blend_mode: Default::default(),
                             },
                             old_tiles: layer.layer.tiles.clone(),
                             new_tiles: vec![Default::default(); len],
@@ -1597,6 +1600,7 @@ pub(crate) fn change_layer_design_attr_valid(map: &EditorMap) -> Vec<EditorActio
                                 })
                             },
                             high_detail: Default::default(),
+                            blend_mode: Default::default(),
                         },
                     }))
                 }
@@ -2040,6 +2044,7 @@ fn set_metadata_valid(map: &EditorMap) -> Vec<EditorAction> {
             version: format!("{}", rand::rng().next_u64()),
             credits: format!("{}", rand::rng().next_u64()),
             memo: format!("{}", rand::rng().next_u64()),
+            theme_ambiance: Default::default(),
         },
     })]
 }
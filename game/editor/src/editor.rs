@@ -31,7 +31,7 @@ use egui::{FontDefinitions, InputState, OutputCommand, Pos2, Rect, pos2, vec2};
 use egui_file_dialog::FileDialog;
 use game_config::config::ConfigMap;
 use graphics::{
-    graphics::graphics::Graphics,
+    graphics::graphics::{Graphics, PixelReadback},
     graphics_mt::GraphicsMultiThreaded,
     handles::{
         backend::backend::GraphicsBackendHandle,
@@ -89,10 +89,14 @@ use sound::{
 use ui_base::ui::UiCreator;
 
 use crate::{
+    actions::actions::EditorActionGroup,
     client::EditorClient,
     editor_ui::{EditorUiRender, EditorUiRenderPipe},
     event::EditorEventOverwriteMap,
     fs::{read_file_editor, write_file_editor},
+    group_file::{EditorGroupFile, EditorGroupImport, GROUP_FILE_EXTENSION},
+    group_templates::{GROUP_TEMPLATES_DIR, check_template_name, template_path},
+    guides::EditorMapSidecar,
     hotkeys::{BindsPerEvent, EditorBindsFile, EditorHotkeyEvent},
     image_store_container::{ImageStoreContainer, load_image_store_container},
     map::{
@@ -102,12 +106,12 @@ use crate::{
         EditorGroupPanelResources, EditorGroupPanelTab, EditorGroupPhysics, EditorGroupProps,
         EditorGroups, EditorGroupsProps, EditorImage, EditorImage2dArray, EditorLayer,
         EditorLayerArbitrary, EditorLayerQuad, EditorLayerSound, EditorLayerTile,
-        EditorLayerUnionRef, EditorMap, EditorMapInterface, EditorMapProps, EditorMetadata,
-        EditorPhysicsGroupProps, EditorPhysicsLayer, EditorPhysicsLayerProps, EditorPosAnimation,
-        EditorQuadLayerProps, EditorQuadLayerPropsPropsSelection, EditorResource,
-        EditorResourceTexture2dArray, EditorResources, EditorSound, EditorSoundAnimation,
-        EditorSoundLayerProps, EditorTileLayerProps, EditorTileLayerPropsSelection,
-        ResourceSelection,
+        EditorLayerUnionRef, EditorMap, EditorMapDiffState, EditorMapInterface, EditorMapProps,
+        EditorMetadata, EditorPhysicsGroupProps, EditorPhysicsLayer, EditorPhysicsLayerProps,
+        EditorPosAnimation, EditorQuadLayerProps, EditorQuadLayerPropsPropsSelection,
+        EditorResource, EditorResourceTexture2dArray, EditorResources, EditorSound,
+        EditorSoundAnimation, EditorSoundLayerProps, EditorTileLayerProps,
+        EditorTileLayerPropsSelection, ResourceSelection,
     },
     map_tools::{
         finish_design_quad_layer_buffer, finish_design_tile_layer_buffer,
@@ -117,6 +121,7 @@ use crate::{
     notifications::{EditorNotification, EditorNotifications},
     options::EditorOptions,
     physics_layers::PhysicsLayerOverlaysDdnet,
+    resource_optimizer::ResourceOptimizeReport,
     server::EditorServer,
     sound_store_container::{SoundStoreContainer, load_sound_store_container},
     tab::EditorTab,
@@ -126,7 +131,8 @@ use crate::{
         quad_layer::{brush::QuadBrush, selection::QuadSelection},
         sound_layer::brush::SoundBrush,
         tile_layer::{
-            auto_mapper::TileLayerAutoMapper, brush::TileBrush, selection::TileSelection,
+            auto_mapper::TileLayerAutoMapper, brush::TileBrush, measure::TileMeasure,
+            selection::TileSelection,
         },
         tool::{
             ActiveTool, ActiveToolQuads, ActiveToolSounds, ActiveToolTiles, ToolQuadLayer,
@@ -359,6 +365,7 @@ impl Editor {
                         &overlays,
                     ),
                     selection: TileSelection::new(),
+                    measure: TileMeasure::new(),
                 },
                 quads: ToolQuadLayer {
                     brush: QuadBrush::new(),
@@ -605,6 +612,7 @@ impl Editor {
                             version: Default::default(),
                             credits: Default::default(),
                             memo: Default::default(),
+                            theme_ambiance: Default::default(),
                         },
                         user: (),
                     },
@@ -1389,10 +1397,38 @@ impl Editor {
         } else {
             self.load_map_impl(path, options)
         };
-        if let Err(err) = res {
-            log::error!("{err}");
-            self.notifications_overlay
-                .add_err(err.to_string(), Duration::from_secs(10));
+        match res {
+            Ok(()) => self.load_map_sidecar(path),
+            Err(err) => {
+                log::error!("{err}");
+                self.notifications_overlay
+                    .add_err(err.to_string(), Duration::from_secs(10));
+            }
+        }
+    }
+
+    /// Loads the editor-only data that is saved next to the map, if there is any.
+    fn load_map_sidecar(&mut self, path: &Path) {
+        let fs = self.io.fs.clone();
+        let sidecar_path = EditorMapSidecar::path(path);
+        let Ok(file) = self
+            .io
+            .rt
+            .spawn(async move { read_file_editor(&fs, &sidecar_path).await })
+            .get()
+        else {
+            return;
+        };
+        match EditorMapSidecar::from_json(&file) {
+            Ok(sidecar) => {
+                if let Some(tab) = self.tabs.get_mut(&self.active_tab) {
+                    tab.map.user.options.guides = sidecar.guides;
+                    tab.map.user.options.palette = sidecar.palette;
+                }
+            }
+            Err(err) => {
+                log::warn!("failed to read the editor data of the map: {err}");
+            }
         }
     }
 
@@ -1401,11 +1437,12 @@ impl Editor {
         io: &Io,
         tp: &Arc<rayon::ThreadPool>,
         path: &Path,
+        optimized: Option<&ResourceOptimizeReport>,
     ) -> anyhow::Result<IoRuntimeTask<()>> {
         use map::map::resources::MapResourceRef;
 
         tab.auto_saver.path = Some(path.to_path_buf());
-        let (map, resources, path) = Self::save_map_tab_impl(tab, path);
+        let (map, resources, path) = Self::save_map_tab_impl(tab, path, optimized);
 
         let tp = tp.clone();
         let fs = io.fs.clone();
@@ -1442,15 +1479,9 @@ impl Editor {
         }))
     }
 
-    fn save_map_tab_impl(
-        tab: &mut EditorTab,
-        path: &Path,
-    ) -> (Map, HashMap<String, Vec<u8>>, PathBuf) {
-        tab.auto_saver.path = Some(path.to_path_buf());
-        let map: Map = tab.map.clone().into();
-        let resources = tab
-            .map
-            .resources
+    /// The files of all resources of the map by their path.
+    pub(crate) fn map_resource_files(map: &EditorMap) -> HashMap<String, Vec<u8>> {
+        map.resources
             .images
             .iter()
             .flat_map(|r| {
@@ -1483,7 +1514,7 @@ impl Editor {
                 )
                 .collect::<Vec<_>>()
             })
-            .chain(tab.map.resources.image_arrays.iter().flat_map(|r| {
+            .chain(map.resources.image_arrays.iter().flat_map(|r| {
                 [(
                     format!(
                         "map/resources/images/{}_{}.{}",
@@ -1513,7 +1544,7 @@ impl Editor {
                 )
                 .collect::<Vec<_>>()
             }))
-            .chain(tab.map.resources.sounds.iter().flat_map(|r| {
+            .chain(map.resources.sounds.iter().flat_map(|r| {
                 [(
                     format!(
                         "map/resources/sounds/{}_{}.{}",
@@ -1543,7 +1574,41 @@ impl Editor {
                 )
                 .collect::<Vec<_>>()
             }))
-            .collect::<HashMap<_, _>>();
+            .collect::<HashMap<_, _>>()
+    }
+
+    /// If `optimized` is given, the saved map uses the optimized images,
+    /// the images of the tab stay untouched.
+    fn save_map_tab_impl(
+        tab: &mut EditorTab,
+        path: &Path,
+        optimized: Option<&ResourceOptimizeReport>,
+    ) -> (Map, HashMap<String, Vec<u8>>, PathBuf) {
+        tab.auto_saver.path = Some(path.to_path_buf());
+        let mut map: Map = tab.map.clone().into();
+        let mut resources = Self::map_resource_files(&tab.map);
+        if let Some(report) = optimized {
+            for (old, new, file) in report.apply(&mut map.resources) {
+                // the original file might still be used by another image
+                let old_in_use = map
+                    .resources
+                    .images
+                    .iter()
+                    .chain(map.resources.image_arrays.iter())
+                    .any(|r| r.meta.blake3_hash == old.meta.blake3_hash);
+                if !old_in_use {
+                    resources.remove(&Self::map_resource_path(
+                        ReadFileTy::Image,
+                        old.name.as_str(),
+                        &old.meta,
+                    ));
+                }
+                resources.insert(
+                    Self::map_resource_path(ReadFileTy::Image, new.name.as_str(), &new.meta),
+                    file.to_vec(),
+                );
+            }
+        }
         (map, resources, path.to_path_buf())
     }
 
@@ -1555,10 +1620,24 @@ impl Editor {
         save_tasks: &mut Vec<IoRuntimeTask<()>>,
         notifications_overlay: &mut ClientNotifications,
         path: &Path,
+        optimized: Option<&ResourceOptimizeReport>,
     ) {
         tab.client.should_save = false;
+
+        let sidecar = EditorMapSidecar {
+            guides: tab.map.user.options.guides.clone(),
+            palette: tab.map.user.options.palette.clone(),
+        };
+        let sidecar_path = EditorMapSidecar::path(path);
+        let fs = io.fs.clone();
+        save_tasks.push(
+            io.rt.spawn(
+                async move { write_file_editor(&fs, &sidecar_path, sidecar.to_json()?).await },
+            ),
+        );
+
         if path.extension().is_some_and(|ext| ext == "map") {
-            match Self::save_map_legacy(tab, io, tp, path) {
+            match Self::save_map_legacy(tab, io, tp, path, optimized) {
                 Ok(task) => {
                     save_tasks.push(task);
                 }
@@ -1568,7 +1647,7 @@ impl Editor {
                 }
             }
         } else {
-            let (map, resources, path) = Self::save_map_tab_impl(tab, path);
+            let (map, resources, path) = Self::save_map_tab_impl(tab, path, optimized);
             let tp = tp.clone();
             let fs = io.fs.clone();
 
@@ -1589,7 +1668,53 @@ impl Editor {
         }
     }
 
-    pub fn save_map(&mut self, path: &Path) {
+    /// Saves a map snapshot downloaded from the server
+    /// as a new map, so existing maps are never overwritten.
+    fn save_local_copy(
+        io: &Io,
+        tp: &Arc<rayon::ThreadPool>,
+        tab_name: &str,
+        snapshot: EditorEventOverwriteMap,
+    ) -> IoRuntimeTask<()> {
+        let path = format!(
+            "map/maps/{tab_name}_copy_{}.twmap",
+            chrono::Local::now().format("%Y_%m_%d_%H_%M_%S")
+        );
+        let tp = tp.clone();
+        let fs = io.fs.clone();
+        io.rt.spawn(async move {
+            let map = Map::read(&MapFileReader::new(snapshot.map.clone())?, &tp)?;
+
+            fs.create_dir("map/maps".as_ref()).await?;
+            fs.create_dir("map/resources/images".as_ref()).await?;
+            fs.create_dir("map/resources/sounds".as_ref()).await?;
+
+            write_file_editor(&fs, path.as_ref(), snapshot.map).await?;
+
+            for (ty, r) in map
+                .resources
+                .images
+                .iter()
+                .chain(map.resources.image_arrays.iter())
+                .map(|r| (ReadFileTy::Image, r))
+                .chain(map.resources.sounds.iter().map(|r| (ReadFileTy::Sound, r)))
+            {
+                for meta in std::iter::once(&r.meta).chain(r.hq_meta.as_ref()) {
+                    let file = snapshot.resources.get(&meta.blake3_hash).ok_or_else(|| {
+                        anyhow!("resource {} is missing in the snapshot", r.name.as_str())
+                    })?;
+                    fs.write_file(
+                        Self::map_resource_path(ty, r.name.as_str(), meta).as_ref(),
+                        file.clone(),
+                    )
+                    .await?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    pub fn save_map(&mut self, path: &Path, optimized: Option<&ResourceOptimizeReport>) {
         if let Some(tab) = self.tabs.get_mut(&self.active_tab) {
             Self::save_map_tab(
                 tab,
@@ -1598,6 +1723,7 @@ impl Editor {
                 &mut self.save_tasks,
                 &mut self.notifications_overlay,
                 path,
+                optimized,
             );
         } else {
             let msg = "No map was loaded to be saved.";
@@ -1607,6 +1733,264 @@ impl Editor {
         }
     }
 
+    fn group_file(
+        &self,
+        is_background: bool,
+        group_index: usize,
+        layers: Option<&[usize]>,
+    ) -> anyhow::Result<EditorGroupFile> {
+        let tab = self
+            .tabs
+            .get(&self.active_tab)
+            .ok_or_else(|| anyhow!("No map was loaded to export a group from."))?;
+        let map: Map = tab.map.clone().into();
+        let group = if is_background {
+            &map.groups.background
+        } else {
+            &map.groups.foreground
+        }
+        .get(group_index)
+        .ok_or_else(|| anyhow!("The group to export does not exist anymore."))?;
+
+        let resource_files: HashMap<Hash, Vec<u8>> = tab
+            .map
+            .resources
+            .images
+            .iter()
+            .map(|r| (r.def.meta.blake3_hash, r.user.file.as_ref().clone()))
+            .chain(
+                tab.map
+                    .resources
+                    .image_arrays
+                    .iter()
+                    .map(|r| (r.def.meta.blake3_hash, r.user.file.as_ref().clone())),
+            )
+            .chain(
+                tab.map
+                    .resources
+                    .sounds
+                    .iter()
+                    .map(|r| (r.def.meta.blake3_hash, r.user.file.as_ref().clone())),
+            )
+            .collect();
+
+        EditorGroupFile::new(
+            &map.resources,
+            &map.animations,
+            group,
+            layers,
+            &resource_files,
+        )
+    }
+
+    fn export_group_impl(
+        &self,
+        path: &Path,
+        is_background: bool,
+        group_index: usize,
+        layers: Option<&[usize]>,
+    ) -> anyhow::Result<()> {
+        let file = self
+            .group_file(is_background, group_index, layers)?
+            .write()?;
+
+        let fs = self.io.fs.clone();
+        let path = path.to_path_buf();
+        self.io
+            .rt
+            .spawn(async move { write_file_editor(&fs, &path, file).await })
+            .get()?;
+        Ok(())
+    }
+
+    fn export_group(
+        &mut self,
+        path: &Path,
+        is_background: bool,
+        group_index: usize,
+        layers: Option<&[usize]>,
+    ) {
+        match self.export_group_impl(path, is_background, group_index, layers) {
+            Ok(()) => {
+                self.notifications_overlay.add_info(
+                    format!("Exported group to {path:?}"),
+                    Duration::from_secs(3),
+                );
+            }
+            Err(err) => {
+                log::error!("{err}");
+                self.notifications_overlay
+                    .add_err(err.to_string(), Duration::from_secs(10));
+            }
+        }
+    }
+
+    fn import_group_impl(&mut self, path: &Path, is_background: bool) -> anyhow::Result<()> {
+        let fs = self.io.fs.clone();
+        let path = path.to_path_buf();
+        let file = self
+            .io
+            .rt
+            .spawn(async move { read_file_editor(&fs, &path).await })
+            .get()?;
+        self.import_group_file(EditorGroupFile::read(&file)?, is_background)
+    }
+
+    /// Merges the group file into the map as a single undoable action.
+    fn import_group_file(
+        &mut self,
+        group_file: EditorGroupFile,
+        is_background: bool,
+    ) -> anyhow::Result<()> {
+        let tab = self
+            .tabs
+            .get_mut(&self.active_tab)
+            .ok_or_else(|| anyhow!("No map was loaded to import the group into."))?;
+        let map: Map = tab.map.clone().into();
+        let groups = if is_background {
+            &map.groups.background
+        } else {
+            &map.groups.foreground
+        };
+        let EditorGroupImport {
+            actions,
+            collisions,
+        } = group_file.import(&map.resources, &map.animations, groups, is_background)?;
+
+        tab.client.execute_group(EditorActionGroup {
+            actions,
+            identifier: None,
+        });
+
+        for collision in collisions {
+            self.notifications_overlay
+                .add_warn(collision, Duration::from_secs(10));
+        }
+        Ok(())
+    }
+
+    fn import_group(&mut self, path: &Path, is_background: bool) {
+        if let Err(err) = self.import_group_impl(path, is_background) {
+            log::error!("{err}");
+            self.notifications_overlay
+                .add_err(err.to_string(), Duration::from_secs(10));
+        }
+    }
+
+    /// Reloads the templates in the templates panel.
+    fn reload_group_templates(&mut self) {
+        if let Some(tab) = self.tabs.get_mut(&self.active_tab)
+            && let Some(state) = &mut tab.map.user.ui_values.group_templates_open
+        {
+            state.templates = None;
+        }
+    }
+
+    fn save_group_template_impl(
+        &self,
+        name: &str,
+        is_background: bool,
+        group_index: usize,
+    ) -> anyhow::Result<()> {
+        check_template_name(name)?;
+        let file = self.group_file(is_background, group_index, None)?.write()?;
+
+        let fs = self.io.fs.clone();
+        let path = template_path(name);
+        self.io
+            .rt
+            .spawn(async move {
+                fs.create_dir(GROUP_TEMPLATES_DIR.as_ref()).await?;
+                Ok(fs.write_file(&path, file).await?)
+            })
+            .get()?;
+        Ok(())
+    }
+
+    fn save_group_template(&mut self, name: &str, is_background: bool, group_index: usize) {
+        match self.save_group_template_impl(name, is_background, group_index) {
+            Ok(()) => {
+                self.notifications_overlay.add_info(
+                    format!("Saved group template \"{name}\""),
+                    Duration::from_secs(3),
+                );
+                self.reload_group_templates();
+            }
+            Err(err) => {
+                log::error!("{err}");
+                self.notifications_overlay
+                    .add_err(err.to_string(), Duration::from_secs(10));
+            }
+        }
+    }
+
+    fn delete_group_template(&mut self, name: &str) {
+        let fs = self.io.fs.clone();
+        let path = template_path(name);
+        if let Err(err) = self
+            .io
+            .rt
+            .spawn(async move { Ok(fs.remove_file(&path).await?) })
+            .get()
+        {
+            log::error!("{err}");
+            self.notifications_overlay
+                .add_err(err.to_string(), Duration::from_secs(10));
+        }
+        self.reload_group_templates();
+    }
+
+    /// Reads a legacy or new map file to compare the current map with.
+    fn diff_map_impl(&mut self, path: &Path) -> anyhow::Result<()> {
+        if !self.tabs.contains_key(&self.active_tab) {
+            return Err(anyhow!("No map was loaded to compare with the file."));
+        }
+        let fs = self.io.fs.clone();
+        let tp = self.thread_pool.clone();
+        let path_buf = path.to_path_buf();
+        let file = self
+            .io
+            .rt
+            .spawn(async move { read_file_editor(&fs, &path_buf).await })
+            .get()?;
+        let map = if path.extension().is_some_and(|ext| ext == "map") {
+            map_convert_lib::legacy_to_new::legacy_to_new_from_buf(
+                file,
+                path.file_stem()
+                    .ok_or(anyhow::anyhow!("wrong file name"))?
+                    .to_str()
+                    .ok_or(anyhow::anyhow!("file name not utf8"))?,
+                &self.io.clone().into(),
+                &tp,
+                true,
+            )
+            .map_err(|err| anyhow::anyhow!("Loading legacy map loading failed: {err}"))?
+            .map
+        } else {
+            Map::read(&MapFileReader::new(file)?, &tp)?
+        };
+
+        let tab = self
+            .tabs
+            .get_mut(&self.active_tab)
+            .ok_or_else(|| anyhow!("No map was loaded to compare with the file."))?;
+        tab.map.user.ui_values.map_diff_panel_open = Some(EditorMapDiffState {
+            path: path.to_path_buf(),
+            file: Rc::new(map),
+            diff: None,
+            hidden_layers: Default::default(),
+        });
+        Ok(())
+    }
+
+    fn diff_map(&mut self, path: &Path) {
+        if let Err(err) = self.diff_map_impl(path) {
+            log::error!("{err}");
+            self.notifications_overlay
+                .add_err(err.to_string(), Duration::from_secs(10));
+        }
+    }
+
     fn save_tab(&mut self, tab: &str) -> bool {
         if let Some((path, tab)) = self
             .tabs
@@ -1620,6 +2004,7 @@ impl Editor {
                 &mut self.save_tasks,
                 &mut self.notifications_overlay,
                 &path,
+                None,
             );
             true
         } else {
@@ -1649,6 +2034,7 @@ impl Editor {
                     &mut self.save_tasks,
                     &mut self.notifications_overlay,
                     &path,
+                    None,
                 );
             } else {
                 let msg = "Some maps have never been saved.\n\
@@ -1710,6 +2096,14 @@ impl Editor {
                         .set_live_edit_layer(&mut tab.map, layer_index, true);
                 }
             }
+            if let Some(snapshot) = tab.client.downloaded_snapshot.take() {
+                self.save_tasks.push(Self::save_local_copy(
+                    &self.io,
+                    &self.thread_pool,
+                    tab_name,
+                    snapshot,
+                ));
+            }
             if let Some(server) = &mut tab.server {
                 server.update(
                     &self.thread_pool,
@@ -1746,6 +2140,7 @@ impl Editor {
                             &mut self.save_tasks,
                             &mut self.notifications_overlay,
                             &path,
+                            None,
                         );
                     }
                 }
@@ -2049,6 +2444,7 @@ impl Editor {
                             layer,
                             &map.game_camera(),
                             0.3,
+                            None,
                         );
                     }
                 } else if let MapLayerSkeleton::Sound(layer) = layer {
@@ -2141,10 +2537,88 @@ impl Editor {
     }
 
     /// brushes, moving camera etc.
+    fn eyedropper_active(&self) -> bool {
+        self.tabs
+            .get(&self.active_tab)
+            .is_some_and(|tab| tab.map.user.ui_values.eyedropper.is_some())
+    }
+
+    /// Hands the read back pixel of the eyedropper to its color field.
+    fn update_eyedropper(&mut self) {
+        let Some(tab) = self.tabs.get_mut(&self.active_tab) else {
+            return;
+        };
+        let ui_values = &mut tab.map.user.ui_values;
+        let Some(res) = ui_values
+            .eyedropper
+            .as_ref()
+            .and_then(|eyedropper| eyedropper.readback.as_ref())
+            .and_then(|readback| readback.try_take())
+        else {
+            return;
+        };
+        match res {
+            Ok(color) => {
+                if let Some(eyedropper) = &mut ui_values.eyedropper {
+                    eyedropper.readback = None;
+                    eyedropper.picked = Some(color);
+                }
+            }
+            Err(err) => {
+                ui_values.eyedropper = None;
+                log::error!("failed to pick the color: {err}");
+                self.notifications_overlay.add_err(
+                    format!("Failed to pick the color: {err}"),
+                    Duration::from_secs(10),
+                );
+            }
+        }
+    }
+
+    /// A click into the map reads back the pixel below the cursor,
+    /// instead of using the tools.
+    fn handle_eyedropper(&mut self, pixels_per_point: f32) {
+        let Some(tab) = self.tabs.get_mut(&self.active_tab) else {
+            return;
+        };
+        let Some(eyedropper) = &mut tab.map.user.ui_values.eyedropper else {
+            return;
+        };
+        if eyedropper.readback.is_some()
+            || eyedropper.picked.is_some()
+            || !self.latest_pointer.primary_pressed()
+        {
+            return;
+        }
+        let pos = self.current_pointer_pos.to_vec2() * pixels_per_point;
+        match PixelReadback::new(
+            &self.graphics.backend_handle,
+            pos.x.max(0.0) as u32,
+            pos.y.max(0.0) as u32,
+        ) {
+            Ok(readback) => {
+                eyedropper.readback = Some(Rc::new(readback));
+            }
+            Err(err) => {
+                tab.map.user.ui_values.eyedropper = None;
+                log::error!("failed to pick the color: {err}");
+                self.notifications_overlay.add_err(
+                    format!("Failed to pick the color: {err}"),
+                    Duration::from_secs(10),
+                );
+            }
+        }
+    }
+
     fn handle_world(&mut self, ui_canvas: &UiCanvasSize, unused_rect: egui::Rect) {
+        let in_parallax_preview = self
+            .tabs
+            .get(&self.active_tab)
+            .is_some_and(|tab| tab.map.user.ui_values.parallax_preview.is_some());
         // handle middle mouse click
         if self.latest_pointer.middle_down()
-            || (self.latest_modifiers.ctrl && self.latest_pointer.primary_down())
+            || ((self.latest_modifiers.ctrl || in_parallax_preview)
+                && self.latest_pointer.primary_down())
         {
             let active_tab = self.tabs.get_mut(&self.active_tab);
             if let Some(tab) = active_tab {
@@ -2180,8 +2654,12 @@ impl Editor {
                         false,
                     );
 
-                    tab.map.groups.user.pos.x -= pos.x - old_pos.x;
-                    tab.map.groups.user.pos.y -= pos.y - old_pos.y;
+                    if let Some(preview) = &mut tab.map.user.ui_values.parallax_preview {
+                        preview.pan(&mut tab.map.groups.user, pos - old_pos);
+                    } else {
+                        tab.map.groups.user.pos.x -= pos.x - old_pos.x;
+                        tab.map.groups.user.pos.y -= pos.y - old_pos.y;
+                    }
                 }
                 self.middle_down_pointer_pos = Some(self.current_pointer_pos);
             }
@@ -2218,6 +2696,11 @@ impl Editor {
                 .clamp(0.2, 200.0);
             }
 
+            // the preview must never modify the map
+            if in_parallax_preview {
+                return;
+            }
+
             // change active tool set
             match tab.map.active_layer() {
                 Some(layer) => match layer {
@@ -2305,6 +2788,10 @@ impl Editor {
     fn render_tools(&mut self, ui_canvas: &UiCanvasSize) {
         let active_tab = self.tabs.get_mut(&self.active_tab);
         if let Some(tab) = active_tab {
+            if tab.map.user.ui_values.parallax_preview.is_some() {
+                return;
+            }
+
             // change active tool set
             match tab.map.active_layer() {
                 Some(layer) => match layer {
@@ -2381,6 +2868,12 @@ impl Editor {
                     &self.current_pointer_pos,
                 ),
             }
+
+            self.tools.tiles.measure.render(
+                &self.graphics.canvas_handle,
+                &self.graphics.stream_handle,
+                &tab.map,
+            );
         }
     }
 
@@ -2654,7 +3147,59 @@ impl Editor {
                 }
                 EditorUiEvent::OpenFile { name } => self.load_map(&name, Default::default()),
                 EditorUiEvent::SaveFile { name } => {
-                    self.save_map(&name);
+                    self.save_map(&name, None);
+                }
+                EditorUiEvent::SaveFileOptimized { name, report } => {
+                    self.save_map(&name, Some(&report));
+                }
+                EditorUiEvent::ExportGroupDialog {
+                    is_background,
+                    group_index,
+                    layers,
+                } => {
+                    self.ui.menu_dialog_mode = EditorMenuDialogMode::export_group(
+                        &self.io,
+                        is_background,
+                        group_index,
+                        layers,
+                    );
+                }
+                EditorUiEvent::ExportGroup {
+                    name,
+                    is_background,
+                    group_index,
+                    layers,
+                } => {
+                    self.export_group(&name, is_background, group_index, layers.as_deref());
+                }
+                EditorUiEvent::ImportGroup {
+                    name,
+                    is_background,
+                } => {
+                    self.import_group(&name, is_background);
+                }
+                EditorUiEvent::SaveGroupTemplate {
+                    name,
+                    is_background,
+                    group_index,
+                } => {
+                    self.save_group_template(&name, is_background, group_index);
+                }
+                EditorUiEvent::InsertGroupTemplate {
+                    file,
+                    is_background,
+                } => {
+                    if let Err(err) = self.import_group_file(*file, is_background) {
+                        log::error!("{err}");
+                        self.notifications_overlay
+                            .add_err(err.to_string(), Duration::from_secs(10));
+                    }
+                }
+                EditorUiEvent::DeleteGroupTemplate { name } => {
+                    self.delete_group_template(&name);
+                }
+                EditorUiEvent::DiffMap { name } => {
+                    self.diff_map(&name);
                 }
                 EditorUiEvent::SaveCurMap => {
                     self.save_tab(&self.active_tab.clone());
@@ -2664,6 +3209,11 @@ impl Editor {
                         self.tabs.remove(&self.active_tab);
                     }
                 }
+                EditorUiEvent::SaveLocalCopy => {
+                    if let Some(tab) = self.tabs.get_mut(&self.active_tab) {
+                        tab.client.request_snapshot();
+                    }
+                }
                 EditorUiEvent::SaveAll => {
                     self.save_all_tabs();
                 }
@@ -2832,6 +3382,13 @@ impl EditorInterface for Editor {
         // do an update
         self.update();
 
+        // the parallax preview can move the camera on its own
+        if let Some(tab) = self.tabs.get_mut(&self.active_tab)
+            && let Some(preview) = &tab.map.user.ui_values.parallax_preview
+        {
+            preview.update(&mut tab.map.groups.user, self.time.now());
+        }
+
         // then render the map
         self.render_world();
 
@@ -2843,7 +3400,11 @@ impl EditorInterface for Editor {
 
         // render the tools directly after the world
         // the handling/update of the tools & world happens after the UI tho
-        self.render_tools(&self.latest_canvas_rect.clone());
+        // the eyedropper picks colors of the map, without the tools above it
+        let eyedropper_active = self.eyedropper_active();
+        if !eyedropper_active {
+            self.render_tools(&self.latest_canvas_rect.clone());
+        }
 
         // then render the UI above it
         let (unused_rect, input_state, canvas_size, ui_output, forced_result) =
@@ -2860,14 +3421,17 @@ impl EditorInterface for Editor {
 
         // outside of the UI / inside of the world, handle brushes etc.
         // working with egui directly doesn't feel great... copy some interesting input values
-        if let Some((latest_pointer, scroll_delta, keys, modifiers)) = input_state.map(|inp| {
-            (
-                inp.pointer.clone(),
-                inp.raw_scroll_delta,
-                inp.keys_down.clone(),
-                inp.modifiers,
-            )
-        }) {
+        if let Some((latest_pointer, scroll_delta, keys, modifiers, pixels_per_point)) = input_state
+            .map(|inp| {
+                (
+                    inp.pointer.clone(),
+                    inp.raw_scroll_delta,
+                    inp.keys_down.clone(),
+                    inp.modifiers,
+                    inp.pixels_per_point,
+                )
+            })
+        {
             if unused_rect.is_some_and(|unused_rect| {
                 unused_rect.contains(
                     latest_pointer
@@ -2884,22 +3448,27 @@ impl EditorInterface for Editor {
                     .latest_pointer
                     .latest_pos()
                     .unwrap_or(self.current_pointer_pos);
-                self.handle_world(
-                    &canvas_size.unwrap_or_else(|| {
-                        Rect::from_min_size(
-                            pos2(0.0, 0.0),
-                            vec2(
-                                self.graphics.canvas_handle.canvas_width() as f32,
-                                self.graphics.canvas_handle.canvas_height() as f32,
-                            ),
-                        )
-                    }),
-                    self.latest_unused_rect,
-                );
+                if eyedropper_active {
+                    self.handle_eyedropper(pixels_per_point);
+                } else {
+                    self.handle_world(
+                        &canvas_size.unwrap_or_else(|| {
+                            Rect::from_min_size(
+                                pos2(0.0, 0.0),
+                                vec2(
+                                    self.graphics.canvas_handle.canvas_width() as f32,
+                                    self.graphics.canvas_handle.canvas_height() as f32,
+                                ),
+                            )
+                        }),
+                        self.latest_unused_rect,
+                    );
+                }
             } else {
                 self.current_scroll_delta = Default::default();
             }
         }
+        self.update_eyedropper();
 
         if let Some(text) = ui_output.commands.iter().find_map(|c| {
             if let OutputCommand::CopyText(t) = c {
@@ -3013,6 +3582,9 @@ impl EditorInterface for Editor {
                     }
                 }
             }
+            GROUP_FILE_EXTENSION => {
+                self.import_group(&file, false);
+            }
             "ogg" => {
                 if let Some(tab) = self.tabs.get_mut(&self.active_tab) {
                     let group_tab = &mut tab.map.user.ui_values.group_panel_active_tab;
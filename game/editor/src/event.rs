@@ -15,7 +15,13 @@ use network::network::{
 use serde::{Deserialize, Serialize};
 use tokio::sync::Mutex;
 
-use crate::actions::actions::EditorActionGroup;
+use crate::{
+    actions::actions::EditorActionGroup,
+    color_palette::EditorColorPalette,
+    guides::EditorGuides,
+    history::EditorHistoryInfo,
+    locks::{EditorLock, EditorLockLayer, EditorLockRect},
+};
 
 /// An editor command is the way the user expresses to
 /// issue a certain state change.
@@ -28,9 +34,15 @@ use crate::actions::actions::EditorActionGroup;
 pub enum EditorCommand {
     Undo,
     Redo,
+    /// Undo or redo until the given history entry is the current one.
+    ///
+    /// `None` undoes all entries.
+    JumpTo {
+        index: Option<usize>,
+    },
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EditorEventLayerIndex {
     pub is_background: bool,
     pub group_index: usize,
@@ -46,6 +58,18 @@ pub struct EditorEventOverwriteMap {
     pub live_edited_layers: Vec<EditorEventLayerIndex>,
 }
 
+/// A part of a serialized [`EditorEventOverwriteMap`],
+/// see [`EditorEventClientToServer::RequestSnapshot`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditorEventSnapshotChunk {
+    /// The id of the request
+    pub id: u64,
+    pub index: u32,
+    /// The amount of chunks of the snapshot
+    pub count: u32,
+    pub data: Vec<u8>,
+}
+
 /// The client props the server knows about.
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct ClientProps {
@@ -64,6 +88,8 @@ pub struct ClientProps {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdminConfigState {
     pub auto_save: Option<Duration>,
+    /// Write a backup every nth committed action.
+    pub backup_every_nth_action: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -134,6 +160,28 @@ pub enum EditorEventClientToServer {
     },
     AdminChangeConfig(AdminChangeConfig),
     DbgAction(ActionDbg),
+    /// Lock a whole layer or only a tile area of it.
+    Lock {
+        layer: EditorLockLayer,
+        rect: Option<EditorLockRect>,
+    },
+    Unlock {
+        id: u64,
+    },
+    /// Whether the client wants to receive the history
+    /// of actions whenever it changes.
+    HistorySubscribe {
+        subscribe: bool,
+    },
+    /// Request the current map of the server,
+    /// e.g. to save a local copy.
+    RequestSnapshot {
+        id: u64,
+    },
+    /// The guides of the client changed.
+    Guides(EditorGuides),
+    /// The color palette of the client changed.
+    Palette(EditorColorPalette),
 }
 
 /// editor events are a collection of either actions or commands
@@ -174,6 +222,16 @@ pub enum EditorEventServerToClient {
     AdminState {
         cur_state: AdminConfigState,
     },
+    /// All currently active locks
+    Locks(Vec<EditorLock>),
+    /// The history of actions, only sent to subscribed clients.
+    History(EditorHistoryInfo),
+    /// A chunk of a requested map snapshot.
+    SnapshotChunk(EditorEventSnapshotChunk),
+    /// The guides of the map, shared by all users.
+    Guides(EditorGuides),
+    /// The color palette of the map, shared by all users.
+    Palette(EditorColorPalette),
 }
 
 /// editor events are a collection of either actions or commands
@@ -13,7 +13,7 @@ Corner points:\n\
 - `Right click` opens the corner property panel.\n\n\
 ---\n\n\
 Press `right click` to unset the selection.  \n\
-If the grid is active, then hold `alt` while dragging to snap to the next grid point.\
+Hold `alt` while dragging to snap to the next grid point (if the grid is active) or to a nearby guide.\
 ";
 
 pub const TEXT_QUAD_SELECTION: &str = "\
@@ -29,7 +29,8 @@ First select your quad(s) using `left click`:\n\
 - `Shift + left click` on a center point to drag the center point of all quads.\n\
 - `R + left click` on a center point to rotate all quads.\n\n\
 Press `right click` on no quad to unset the selection.\n\n\
-If the grid is active, holding `alt`-key will always snap the above actions to the nearest grid point.\n\n\
+Holding `alt`-key will always snap the above actions to the nearest grid point (if the grid is active) \
+  or to a nearby guide, guides take precedence.\n\n\
 ### Animations\n\
 \n\
 If one or more quads are selected with at least one shared \
@@ -67,6 +68,22 @@ such as flipping and rotating, which can be found in the tool bar below.  \n\
 Press `right click` to unset the selection.\
 ";
 
+pub const TEXT_TILE_MEASURE: &str = "\
+# Measure\n\
+\n\
+Measures the distance between two tiles (`left click` & drag): the straight distance in tiles, \n\
+the distance on both axes and the angle.  \n\
+If a physics layer is active, the flight of a grenade and the reach of a laser fired along the line \n\
+are shown too, using the default tunings.  \n\
+Measurements stay visible until they are cleared.\
+";
+
+pub const TEXT_TILE_MEASURE_CLEAR: &str = "\
+# Clear measurements\n\
+\n\
+Removes all measurements.\
+";
+
 pub const TEXT_SOUND_BRUSH: &str = "\
 # Sound brush\n\
 \n\
@@ -90,6 +107,19 @@ pub const TEXT_ADD_SOUND: &str = "\
 Adds a new sound to the active sound layer.\
 ";
 
+pub const TEXT_CLIPBOARD: &str = "\
+# Copy quads or sounds\n\
+\n\
+Copies the selected quads or sounds into the clipboard, \
+so they can also be pasted into other editor instances.\n\
+\n\
+- `Ctrl + C` copies the selection.\n\
+- `Ctrl + V` pastes at the cursor.\n\
+- `Ctrl + Shift + V` pastes at the position the items were copied from.\n\
+\n\
+Animations are matched by name. Animations that don't exist in this map are unassigned.\
+";
+
 pub const TEXT_LAYER_PROPS_ANIM_COLOR: &str = "\
 # Layer's animation color\n\
 \n\
@@ -195,6 +225,20 @@ Allows to select and draw unused tiles, so those tiles are not known to the game
 (unused tiles can be used for modding purposes).\
 ";
 
+pub const TEXT_TILE_LOCK: &str = "\
+# Lock area\n\
+\n\
+Locks the selected tiles or, if nothing is selected, the whole active layer. \
+Other users can not modify locked areas until they are unlocked again. \
+Locks are released automatically after a few minutes of inactivity.\
+";
+
+pub const TEXT_TILE_UNLOCK: &str = "\
+# Unlock\n\
+\n\
+Releases all of your locks on the active layer.\
+";
+
 pub const AUTO_MAPPER_CREATOR_EXPLAIN: &str = "\
 # Auto mapper creator overview\
 \n\
@@ -242,6 +286,38 @@ To insert a new animation point press the `$ANIM_POINT_INSERT$` hotkey.  \n\
 To delete a point simply `right click` the point in the time graph.\
 ";
 
+pub const HISTORY_PANEL: &str = "\
+# History\
+\n\
+Lists all actions that can be undone or redone, the oldest first.  \n\
+Clicking an entry undoes or redoes all actions up to this entry, \
+other users will see the same changes.  \n\
+Very old entries are merged into a single entry to save memory.\
+";
+
+pub const NUMBER_USAGE_PANEL: &str = "\
+# Number usage\
+\n\
+Lists all numbers of the tele, switch or tune layer, \
+together with how many tiles use them as source (e.g. tele in) \
+and as destination (e.g. tele out).  \n\
+Numbers that miss either of them are highlighted.  \n\
+A number can be moved to another number, all tiles & names follow. \
+If the other number is already in use, both are merged.  \n\
+_Compact_ removes all gaps between the used numbers.\
+";
+
+pub const PARALLAX_PREVIEW: &str = "\
+# Parallax preview\
+\n\
+Shows the map through the camera of the game, \
+so all groups move with their parallax & offset exactly like ingame.  \n\
+Drag with the `left mouse button` to move the camera around, \
+or let it move along a _figure-eight_ automatically.  \n\
+Tools are disabled during the preview. \
+When leaving the preview, the editor camera is restored.\
+";
+
 pub const SERVER_COMMANDS_CONFIG_VAR: &str = "\
 # Server commands & config variables\
 \n\
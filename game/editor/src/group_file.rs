@@ -0,0 +1,601 @@
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::anyhow;
+use base::hash::Hash;
+use map::{
+    map::{
+        animations::Animations,
+        groups::{MapGroup, layers::design::MapLayer},
+        resources::{MapResourceRef, Resources},
+    },
+    utils::{compress, decompress, deserialize_twmap_bincode, serialize_twmap_bincode},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::actions::actions::{
+    ActAddColorAnim, ActAddGroup, ActAddImage, ActAddImage2dArray, ActAddPosAnim,
+    ActAddRemColorAnim, ActAddRemGroup, ActAddRemImage, ActAddRemPosAnim, ActAddRemSound,
+    ActAddRemSoundAnim, ActAddSound, ActAddSoundAnim, EditorAction,
+};
+
+/// The file extension of exported groups.
+pub const GROUP_FILE_EXTENSION: &str = "twgroup";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IndexTy {
+    Image,
+    ImageArray,
+    Sound,
+    PosAnim,
+    ColorAnim,
+    SoundAnim,
+}
+
+/// Calls `f` for every resource or animation index the group references.
+fn visit_indices(group: &mut MapGroup, mut f: impl FnMut(IndexTy, &mut usize)) {
+    for layer in group.layers.iter_mut() {
+        match layer {
+            MapLayer::Abritrary(_) => {}
+            MapLayer::Tile(layer) => {
+                if let Some(index) = &mut layer.attr.image_array {
+                    f(IndexTy::ImageArray, index);
+                }
+                if let Some(index) = &mut layer.attr.color_anim {
+                    f(IndexTy::ColorAnim, index);
+                }
+            }
+            MapLayer::Quad(layer) => {
+                if let Some(index) = &mut layer.attr.image {
+                    f(IndexTy::Image, index);
+                }
+                for quad in layer.quads.iter_mut() {
+                    if let Some(index) = &mut quad.pos_anim {
+                        f(IndexTy::PosAnim, index);
+                    }
+                    if let Some(index) = &mut quad.color_anim {
+                        f(IndexTy::ColorAnim, index);
+                    }
+                }
+            }
+            MapLayer::Sound(layer) => {
+                if let Some(index) = &mut layer.attr.sound {
+                    f(IndexTy::Sound, index);
+                }
+                for sound in layer.sounds.iter_mut() {
+                    if let Some(index) = &mut sound.pos_anim {
+                        f(IndexTy::PosAnim, index);
+                    }
+                    if let Some(index) = &mut sound.sound_anim {
+                        f(IndexTy::SoundAnim, index);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Maps old resource & animation indices to new ones.
+#[derive(Debug, Default)]
+struct IndexRemap {
+    images: BTreeMap<usize, usize>,
+    image_arrays: BTreeMap<usize, usize>,
+    sounds: BTreeMap<usize, usize>,
+    pos_anims: BTreeMap<usize, usize>,
+    color_anims: BTreeMap<usize, usize>,
+    sound_anims: BTreeMap<usize, usize>,
+}
+
+impl IndexRemap {
+    fn get_mut(&mut self, ty: IndexTy) -> &mut BTreeMap<usize, usize> {
+        match ty {
+            IndexTy::Image => &mut self.images,
+            IndexTy::ImageArray => &mut self.image_arrays,
+            IndexTy::Sound => &mut self.sounds,
+            IndexTy::PosAnim => &mut self.pos_anims,
+            IndexTy::ColorAnim => &mut self.color_anims,
+            IndexTy::SoundAnim => &mut self.sound_anims,
+        }
+    }
+
+    /// Collects all used indices of the group and maps them
+    /// to a compact range starting from 0, keeping their order.
+    fn compact(group: &mut MapGroup) -> Self {
+        let mut res = Self::default();
+        visit_indices(group, |ty, index| {
+            res.get_mut(ty).insert(*index, 0);
+        });
+        for ty in [
+            IndexTy::Image,
+            IndexTy::ImageArray,
+            IndexTy::Sound,
+            IndexTy::PosAnim,
+            IndexTy::ColorAnim,
+            IndexTy::SoundAnim,
+        ] {
+            for (new_index, index) in res.get_mut(ty).values_mut().enumerate() {
+                *index = new_index;
+            }
+        }
+        res
+    }
+
+    fn apply(&mut self, group: &mut MapGroup) -> anyhow::Result<()> {
+        let mut res = Ok(());
+        visit_indices(group, |ty, index| match self.get_mut(ty).get(&*index) {
+            Some(new_index) => *index = *new_index,
+            None => {
+                res = Err(anyhow!("{ty:?} index {index} is out of bounds"));
+            }
+        });
+        res
+    }
+}
+
+/// A resource embedded into the group file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditorGroupFileResource {
+    pub res: MapResourceRef,
+    pub file: Vec<u8>,
+}
+
+/// A standalone file containing a single design group,
+/// together with all resources & animations the group references.
+///
+/// All indices inside the group point into the lists of this file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditorGroupFile {
+    pub group: MapGroup,
+
+    pub images: Vec<EditorGroupFileResource>,
+    pub image_arrays: Vec<EditorGroupFileResource>,
+    pub sounds: Vec<EditorGroupFileResource>,
+
+    pub animations: Animations,
+}
+
+/// The actions required to import a group file into a map.
+#[derive(Debug, Clone)]
+pub struct EditorGroupImport {
+    pub actions: Vec<EditorAction>,
+    /// Human readable messages about names that already exist in the map.
+    pub collisions: Vec<String>,
+}
+
+impl EditorGroupFile {
+    /// Creates a group file from a group of a map.
+    ///
+    /// If `layers` is `Some`, only the given layers of the group are exported.
+    /// `resource_files` must contain the files of all resources the group uses.
+    pub fn new(
+        resources: &Resources,
+        animations: &Animations,
+        group: &MapGroup,
+        layers: Option<&[usize]>,
+        resource_files: &HashMap<Hash, Vec<u8>>,
+    ) -> anyhow::Result<Self> {
+        let mut group = group.clone();
+        if let Some(layers) = layers {
+            anyhow::ensure!(!layers.is_empty(), "no layers were selected for the export");
+            let mut index = 0;
+            group.layers.retain(|_| {
+                let keep = layers.contains(&index);
+                index += 1;
+                keep
+            });
+        }
+
+        let mut remap = IndexRemap::compact(&mut group);
+
+        let collect_resources = |remap: &BTreeMap<usize, usize>, res: &[MapResourceRef]| {
+            remap
+                .keys()
+                .map(|&index| {
+                    let res = res
+                        .get(index)
+                        .ok_or_else(|| anyhow!("resource index {index} is out of bounds"))?;
+                    let file = resource_files
+                        .get(&res.meta.blake3_hash)
+                        .ok_or_else(|| {
+                            anyhow!("file of resource \"{}\" was not found", res.name.as_str())
+                        })?
+                        .clone();
+                    anyhow::Ok(EditorGroupFileResource {
+                        res: MapResourceRef {
+                            name: res.name.clone(),
+                            meta: res.meta.clone(),
+                            hq_meta: None,
+                        },
+                        file,
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+        };
+        fn collect_anims<T: Clone>(
+            remap: &BTreeMap<usize, usize>,
+            anims: &[T],
+        ) -> anyhow::Result<Vec<T>> {
+            remap
+                .keys()
+                .map(|&index| {
+                    anims
+                        .get(index)
+                        .cloned()
+                        .ok_or_else(|| anyhow!("animation index {index} is out of bounds"))
+                })
+                .collect()
+        }
+
+        let images = collect_resources(&remap.images, &resources.images)?;
+        let image_arrays = collect_resources(&remap.image_arrays, &resources.image_arrays)?;
+        let sounds = collect_resources(&remap.sounds, &resources.sounds)?;
+        let animations = Animations {
+            pos: collect_anims(&remap.pos_anims, &animations.pos)?,
+            color: collect_anims(&remap.color_anims, &animations.color)?,
+            sound: collect_anims(&remap.sound_anims, &animations.sound)?,
+        };
+
+        remap.apply(&mut group)?;
+
+        Ok(Self {
+            group,
+            images,
+            image_arrays,
+            sounds,
+            animations,
+        })
+    }
+
+    pub fn read(file: &[u8]) -> anyhow::Result<Self> {
+        deserialize_twmap_bincode(&decompress(file)?)
+    }
+
+    pub fn write(&self) -> anyhow::Result<Vec<u8>> {
+        let mut file: Vec<u8> = Default::default();
+        serialize_twmap_bincode(self, &mut file)?;
+        compress(&file)
+    }
+
+    /// Generates the actions that merge this group into a map.
+    ///
+    /// Resources that already exist in the map (same hash) are reused,
+    /// all other resources & animations are appended to the map.
+    /// The group itself is appended as last group.
+    pub fn import(
+        self,
+        resources: &Resources,
+        animations: &Animations,
+        groups: &[MapGroup],
+        is_background: bool,
+    ) -> anyhow::Result<EditorGroupImport> {
+        let mut actions = Vec::new();
+        let mut collisions = Vec::new();
+        let mut remap = IndexRemap::default();
+
+        let mut import_resources =
+            |file_resources: Vec<EditorGroupFileResource>,
+             map_resources: &[MapResourceRef],
+             remap: &mut BTreeMap<usize, usize>,
+             ty: &str,
+             add: fn(ActAddRemImage) -> EditorAction| {
+                let mut next_index = map_resources.len();
+                for (index, res) in file_resources.into_iter().enumerate() {
+                    if let Some(existing_index) = map_resources
+                        .iter()
+                        .position(|r| r.meta.blake3_hash == res.res.meta.blake3_hash)
+                    {
+                        remap.insert(index, existing_index);
+                    } else {
+                        if map_resources.iter().any(|r| r.name == res.res.name) {
+                            collisions.push(format!(
+                                "A different {ty} named \"{}\" already exists.",
+                                res.res.name.as_str()
+                            ));
+                        }
+                        remap.insert(index, next_index);
+                        actions.push(add(ActAddRemImage {
+                            res: res.res,
+                            file: res.file,
+                            index: next_index,
+                        }));
+                        next_index += 1;
+                    }
+                }
+            };
+        import_resources(
+            self.images,
+            &resources.images,
+            &mut remap.images,
+            "image",
+            |base| EditorAction::AddImage(ActAddImage { base }),
+        );
+        import_resources(
+            self.image_arrays,
+            &resources.image_arrays,
+            &mut remap.image_arrays,
+            "image array",
+            |base| EditorAction::AddImage2dArray(ActAddImage2dArray { base }),
+        );
+        import_resources(
+            self.sounds,
+            &resources.sounds,
+            &mut remap.sounds,
+            "sound",
+            |base| {
+                EditorAction::AddSound(ActAddSound {
+                    base: ActAddRemSound {
+                        res: base.res,
+                        file: base.file,
+                        index: base.index,
+                    },
+                })
+            },
+        );
+
+        let mut anim_collision = |name: &str, existing: &mut dyn Iterator<Item = &str>| {
+            if !name.is_empty() && existing.any(|n| n == name) {
+                collisions.push(format!("An animation named \"{name}\" already exists."));
+            }
+        };
+        for (index, anim) in self.animations.pos.into_iter().enumerate() {
+            anim_collision(
+                &anim.name,
+                &mut animations.pos.iter().map(|a| a.name.as_str()),
+            );
+            let new_index = animations.pos.len() + index;
+            remap.pos_anims.insert(index, new_index);
+            actions.push(EditorAction::AddPosAnim(ActAddPosAnim {
+                base: ActAddRemPosAnim {
+                    index: new_index,
+                    anim,
+                },
+            }));
+        }
+        for (index, anim) in self.animations.color.into_iter().enumerate() {
+            anim_collision(
+                &anim.name,
+                &mut animations.color.iter().map(|a| a.name.as_str()),
+            );
+            let new_index = animations.color.len() + index;
+            remap.color_anims.insert(index, new_index);
+            actions.push(EditorAction::AddColorAnim(ActAddColorAnim {
+                base: ActAddRemColorAnim {
+                    index: new_index,
+                    anim,
+                },
+            }));
+        }
+        for (index, anim) in self.animations.sound.into_iter().enumerate() {
+            anim_collision(
+                &anim.name,
+                &mut animations.sound.iter().map(|a| a.name.as_str()),
+            );
+            let new_index = animations.sound.len() + index;
+            remap.sound_anims.insert(index, new_index);
+            actions.push(EditorAction::AddSoundAnim(ActAddSoundAnim {
+                base: ActAddRemSoundAnim {
+                    index: new_index,
+                    anim,
+                },
+            }));
+        }
+
+        let mut group = self.group;
+        remap.apply(&mut group)?;
+        if !group.name.is_empty() && groups.iter().any(|g| g.name == group.name) {
+            collisions.push(format!("A group named \"{}\" already exists.", group.name));
+        }
+        actions.push(EditorAction::AddGroup(ActAddGroup {
+            base: ActAddRemGroup {
+                is_background,
+                index: groups.len(),
+                group,
+            },
+        }));
+
+        Ok(EditorGroupImport {
+            actions,
+            collisions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use base::{hash::generate_hash_for, reduced_ascii_str::ReducedAsciiString};
+    use map::{
+        map::{
+            animations::{AnimBase, Animations},
+            groups::{
+                MapGroup, MapGroupAttr,
+                layers::{
+                    design::{MapLayer, MapLayerQuad, MapLayerQuadsAttrs, MapLayerTile, Quad},
+                    tiles::{MapTileLayerAttr, TileBase},
+                },
+            },
+            resources::{MapResourceMetaData, MapResourceRef, Resources},
+        },
+        types::NonZeroU16MinusOne,
+    };
+    use math::math::vector::{nffixed, nfvec4};
+
+    use crate::actions::actions::EditorAction;
+
+    use super::EditorGroupFile;
+
+    fn res(name: &str, file: &[u8]) -> MapResourceRef {
+        MapResourceRef {
+            name: ReducedAsciiString::from_str_lossy(name),
+            meta: MapResourceMetaData {
+                blake3_hash: generate_hash_for(file),
+                ty: ReducedAsciiString::from_str_lossy("png"),
+            },
+            hq_meta: None,
+        }
+    }
+
+    fn anim<T: serde::de::DeserializeOwned + PartialOrd>(name: &str) -> AnimBase<T> {
+        AnimBase {
+            points: Vec::new(),
+            synchronized: false,
+            name: name.to_string(),
+        }
+    }
+
+    fn quad_layer(
+        image: Option<usize>,
+        pos_anim: Option<usize>,
+        color_anim: Option<usize>,
+    ) -> MapLayer {
+        MapLayer::Quad(MapLayerQuad {
+            attr: MapLayerQuadsAttrs {
+                image,
+                high_detail: false,
+                blend_mode: Default::default(),
+            },
+            quads: vec![Quad {
+                pos_anim,
+                color_anim,
+                ..Default::default()
+            }],
+            name: Default::default(),
+        })
+    }
+
+    fn tile_layer(image_array: Option<usize>, color_anim: Option<usize>) -> MapLayer {
+        MapLayer::Tile(MapLayerTile {
+            attr: MapTileLayerAttr {
+                width: NonZeroU16MinusOne::new(2).unwrap(),
+                height: NonZeroU16MinusOne::new(2).unwrap(),
+                color: nfvec4::new(
+                    nffixed::from_num(1),
+                    nffixed::from_num(1),
+                    nffixed::from_num(1),
+                    nffixed::from_num(1),
+                ),
+                high_detail: false,
+                color_anim,
+                color_anim_offset: time::Duration::ZERO,
+                image_array,
+                blend_mode: Default::default(),
+            },
+            tiles: vec![TileBase::default(); 4],
+            name: Default::default(),
+        })
+    }
+
+    #[test]
+    fn export_import_remaps_indices() {
+        let files: HashMap<_, _> = [b"img0".to_vec(), b"img1".to_vec(), b"arr0".to_vec()]
+            .into_iter()
+            .map(|file| (generate_hash_for(&file), file))
+            .collect();
+
+        // source map
+        let src_resources = Resources {
+            images: vec![res("img0", b"img0"), res("img1", b"img1")],
+            image_arrays: vec![res("arr0", b"arr0")],
+            sounds: Vec::new(),
+        };
+        let src_animations = Animations {
+            pos: vec![anim("pos0"), anim("pos1"), anim("pos2")],
+            color: vec![anim("color0")],
+            sound: Vec::new(),
+        };
+        let src_group = MapGroup {
+            attr: MapGroupAttr::default(),
+            layers: vec![
+                quad_layer(Some(1), Some(2), Some(0)),
+                // not exported
+                quad_layer(Some(0), Some(0), None),
+                tile_layer(Some(0), Some(0)),
+            ],
+            name: "deco".to_string(),
+        };
+
+        let group_file = EditorGroupFile::new(
+            &src_resources,
+            &src_animations,
+            &src_group,
+            Some(&[0, 2]),
+            &files,
+        )
+        .unwrap();
+        // only referenced resources & animations are embedded
+        assert_eq!(group_file.images.len(), 1);
+        assert_eq!(group_file.images[0].res.name.as_str(), "img1");
+        assert_eq!(group_file.image_arrays.len(), 1);
+        assert_eq!(group_file.animations.pos.len(), 1);
+        assert_eq!(group_file.animations.pos[0].name, "pos2");
+        assert_eq!(group_file.animations.color.len(), 1);
+        assert_eq!(group_file.group.layers.len(), 2);
+
+        let group_file = EditorGroupFile::read(&group_file.write().unwrap()).unwrap();
+
+        // target map already contains `img1` (at a different index) & `color0`
+        let dst_resources = Resources {
+            images: vec![res("other", b"other"), res("img1", b"img1")],
+            image_arrays: vec![res("arr_other", b"arr_other")],
+            sounds: Vec::new(),
+        };
+        let dst_animations = Animations {
+            pos: vec![anim("a"), anim("b")],
+            color: vec![anim("color0")],
+            sound: Vec::new(),
+        };
+        let dst_groups = vec![MapGroup {
+            attr: MapGroupAttr::default(),
+            layers: Vec::new(),
+            name: "deco".to_string(),
+        }];
+
+        let import = group_file
+            .import(&dst_resources, &dst_animations, &dst_groups, true)
+            .unwrap();
+
+        // `img1` is deduplicated by hash
+        assert!(
+            !import
+                .actions
+                .iter()
+                .any(|act| matches!(act, EditorAction::AddImage(_)))
+        );
+        let Some(EditorAction::AddImage2dArray(add_arr)) = import
+            .actions
+            .iter()
+            .find(|act| matches!(act, EditorAction::AddImage2dArray(_)))
+        else {
+            panic!("image array was not added");
+        };
+        assert_eq!(add_arr.base.index, 1);
+        let Some(EditorAction::AddPosAnim(add_pos)) = import
+            .actions
+            .iter()
+            .find(|act| matches!(act, EditorAction::AddPosAnim(_)))
+        else {
+            panic!("pos anim was not added");
+        };
+        assert_eq!(add_pos.base.index, 2);
+
+        // group name & color anim name collide
+        assert_eq!(import.collisions.len(), 2);
+
+        let Some(EditorAction::AddGroup(add_group)) = import.actions.last() else {
+            panic!("group must be added last");
+        };
+        assert_eq!(add_group.base.index, 1);
+        assert!(add_group.base.is_background);
+        let MapLayer::Quad(quad_layer) = &add_group.base.group.layers[0] else {
+            panic!("expected quad layer");
+        };
+        assert_eq!(quad_layer.attr.image, Some(1));
+        assert_eq!(quad_layer.quads[0].pos_anim, Some(2));
+        assert_eq!(quad_layer.quads[0].color_anim, Some(1));
+        let MapLayer::Tile(tile_layer) = &add_group.base.group.layers[1] else {
+            panic!("expected tile layer");
+        };
+        assert_eq!(tile_layer.attr.image_array, Some(1));
+        assert_eq!(tile_layer.attr.color_anim, Some(1));
+    }
+}
@@ -0,0 +1,391 @@
+use std::{path::PathBuf, sync::Arc};
+
+use anyhow::anyhow;
+use base_io_traits::fs_traits::{FileSystemEntryTy, FileSystemInterface};
+use map::map::groups::{MapGroup, layers::design::MapLayer};
+
+use crate::group_file::{EditorGroupFile, GROUP_FILE_EXTENSION};
+
+/// The directory of the group templates.
+///
+/// Templates of the user are saved to the config dir,
+/// the same directory in the data dir contains the built-in templates.
+pub const GROUP_TEMPLATES_DIR: &str = "editor/templates";
+
+/// The longest side of a thumbnail in cells.
+const THUMBNAIL_SIZE: usize = 48;
+
+pub fn template_path(name: &str) -> PathBuf {
+    PathBuf::from(GROUP_TEMPLATES_DIR).join(format!("{name}.{GROUP_FILE_EXTENSION}"))
+}
+
+/// Template names are used as file names, so only a safe subset is allowed.
+pub fn check_template_name(name: &str) -> anyhow::Result<()> {
+    anyhow::ensure!(!name.trim().is_empty(), "The template name is empty.");
+    anyhow::ensure!(
+        name.chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ' ')),
+        "The template name may only contain letters, digits, spaces, `-` & `_`."
+    );
+    Ok(())
+}
+
+/// A low resolution preview of a group,
+/// tiles are drawn in the color of their layer, quads in the average color of their corners.
+#[derive(Debug, Clone)]
+pub struct EditorGroupThumbnail {
+    pub width: usize,
+    pub height: usize,
+    /// RGBA row by row, `[0, 0, 0, 0]` is empty.
+    pub pixels: Vec<[u8; 4]>,
+}
+
+impl EditorGroupThumbnail {
+    pub fn new(group: &MapGroup) -> Self {
+        // bounding box of all tiles & quads in tiles
+        let mut min = (f32::MAX, f32::MAX);
+        let mut max = (f32::MIN, f32::MIN);
+        let mut extend = |x: f32, y: f32| {
+            min = (min.0.min(x), min.1.min(y));
+            max = (max.0.max(x), max.1.max(y));
+        };
+        for layer in group.layers.iter() {
+            match layer {
+                MapLayer::Tile(layer) => {
+                    extend(0.0, 0.0);
+                    extend(
+                        layer.attr.width.get() as f32,
+                        layer.attr.height.get() as f32,
+                    );
+                }
+                MapLayer::Quad(layer) => {
+                    for quad in layer.quads.iter() {
+                        for point in quad.points[0..4].iter() {
+                            extend(point.x.to_num::<f32>(), point.y.to_num::<f32>());
+                        }
+                    }
+                }
+                MapLayer::Sound(_) | MapLayer::Abritrary(_) => {}
+            }
+        }
+        if min.0 >= max.0 || min.1 >= max.1 {
+            return Self {
+                width: 0,
+                height: 0,
+                pixels: Vec::new(),
+            };
+        }
+
+        let (bb_width, bb_height) = (max.0 - min.0, max.1 - min.1);
+        let cell = bb_width.max(bb_height) / THUMBNAIL_SIZE as f32;
+        let width = ((bb_width / cell).ceil() as usize).clamp(1, THUMBNAIL_SIZE);
+        let height = ((bb_height / cell).ceil() as usize).clamp(1, THUMBNAIL_SIZE);
+        let mut pixels = vec![[0.0f32; 4]; width * height];
+
+        // blends the color over the cells of the area given in tiles
+        let mut fill = |x0: f32, y0: f32, x1: f32, y1: f32, color: [f32; 4]| {
+            let to_cell = |val: f32, min: f32, len: usize| {
+                (((val - min) / cell) as usize).min(len.saturating_sub(1))
+            };
+            let (cx0, cx1) = (to_cell(x0, min.0, width), to_cell(x1, min.0, width));
+            let (cy0, cy1) = (to_cell(y0, min.1, height), to_cell(y1, min.1, height));
+            for y in cy0..=cy1 {
+                for x in cx0..=cx1 {
+                    let dst = &mut pixels[y * width + x];
+                    let a = color[3];
+                    for (dst, src) in dst[0..3].iter_mut().zip(color) {
+                        *dst = src * a + *dst * (1.0 - a);
+                    }
+                    dst[3] = a + dst[3] * (1.0 - a);
+                }
+            }
+        };
+
+        for layer in group.layers.iter() {
+            match layer {
+                MapLayer::Tile(layer) => {
+                    let color = layer.attr.color;
+                    let color = [
+                        color.r().to_num::<f32>(),
+                        color.g().to_num::<f32>(),
+                        color.b().to_num::<f32>(),
+                        color.a().to_num::<f32>(),
+                    ];
+                    let layer_width = layer.attr.width.get() as usize;
+                    for (index, tile) in layer.tiles.iter().enumerate() {
+                        if tile.index == 0 {
+                            continue;
+                        }
+                        let x = (index % layer_width) as f32;
+                        let y = (index / layer_width) as f32;
+                        fill(x, y, x + 0.99, y + 0.99, color);
+                    }
+                }
+                MapLayer::Quad(layer) => {
+                    for quad in layer.quads.iter() {
+                        let points = &quad.points[0..4];
+                        let (x0, x1) = points
+                            .iter()
+                            .map(|p| p.x.to_num::<f32>())
+                            .fold((f32::MAX, f32::MIN), |(min, max), x| {
+                                (min.min(x), max.max(x))
+                            });
+                        let (y0, y1) = points
+                            .iter()
+                            .map(|p| p.y.to_num::<f32>())
+                            .fold((f32::MAX, f32::MIN), |(min, max), y| {
+                                (min.min(y), max.max(y))
+                            });
+                        let mut color = [0.0; 4];
+                        for corner in quad.colors.iter() {
+                            color[0] += corner.r().to_num::<f32>() / 4.0;
+                            color[1] += corner.g().to_num::<f32>() / 4.0;
+                            color[2] += corner.b().to_num::<f32>() / 4.0;
+                            color[3] += corner.a().to_num::<f32>() / 4.0;
+                        }
+                        fill(x0, y0, x1, y1, color);
+                    }
+                }
+                MapLayer::Sound(_) | MapLayer::Abritrary(_) => {}
+            }
+        }
+
+        Self {
+            width,
+            height,
+            pixels: pixels
+                .into_iter()
+                .map(|pixel| pixel.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8))
+                .collect(),
+        }
+    }
+}
+
+/// A group file that can be inserted into any map.
+#[derive(Debug, Clone)]
+pub struct EditorGroupTemplate {
+    pub name: String,
+    /// Built-in templates come from the data dir & can't be deleted.
+    pub built_in: bool,
+    pub file: EditorGroupFile,
+    pub thumbnail: EditorGroupThumbnail,
+}
+
+impl EditorGroupTemplate {
+    pub fn new(name: String, built_in: bool, file: &[u8]) -> anyhow::Result<Self> {
+        let file = EditorGroupFile::read(file)?;
+        let thumbnail = EditorGroupThumbnail::new(&file.group);
+        Ok(Self {
+            name,
+            built_in,
+            file,
+            thumbnail,
+        })
+    }
+}
+
+/// Loads the templates of the user & the built-in ones, sorted by name.
+///
+/// If a user template has the same name as a built-in one, the user template wins.
+pub async fn load_group_templates(fs: &Arc<dyn FileSystemInterface>) -> Vec<EditorGroupTemplate> {
+    let entries = fs
+        .entries_in_dir(GROUP_TEMPLATES_DIR.as_ref())
+        .await
+        .unwrap_or_default();
+    let mut templates = Vec::new();
+    for (entry, ty) in entries {
+        let Some(name) = entry.strip_suffix(&format!(".{GROUP_FILE_EXTENSION}")) else {
+            continue;
+        };
+        if matches!(ty, FileSystemEntryTy::Directory) {
+            continue;
+        }
+        let path = template_path(name);
+        let built_in = !fs.file_exists(&path).await;
+        let template = fs
+            .read_file(&path)
+            .await
+            .map_err(|err| anyhow!(err))
+            .and_then(|file| EditorGroupTemplate::new(name.to_string(), built_in, &file));
+        match template {
+            Ok(template) => templates.push(template),
+            Err(err) => log::warn!("Failed to load group template {path:?}: {err}"),
+        }
+    }
+    templates.sort_by(|t1, t2| t1.name.cmp(&t2.name));
+    templates
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use base::{hash::generate_hash_for, reduced_ascii_str::ReducedAsciiString};
+    use map::{
+        map::{
+            animations::Animations,
+            groups::{
+                MapGroup, MapGroupAttr,
+                layers::{
+                    design::{MapLayer, MapLayerQuad, MapLayerQuadsAttrs, MapLayerTile, Quad},
+                    tiles::{MapTileLayerAttr, TileBase},
+                },
+            },
+            resources::{MapResourceMetaData, MapResourceRef, Resources},
+        },
+        types::NonZeroU16MinusOne,
+    };
+    use math::math::vector::{ffixed, fvec2, nffixed, nfvec4};
+
+    use crate::{actions::actions::EditorAction, group_file::EditorGroupFile};
+
+    use super::{EditorGroupTemplate, check_template_name};
+
+    fn res(name: &str, file: &[u8]) -> MapResourceRef {
+        MapResourceRef {
+            name: ReducedAsciiString::from_str_lossy(name),
+            meta: MapResourceMetaData {
+                blake3_hash: generate_hash_for(file),
+                ty: ReducedAsciiString::from_str_lossy("png"),
+            },
+            hq_meta: None,
+        }
+    }
+
+    fn color(r: f32, a: f32) -> nfvec4 {
+        nfvec4::new(
+            nffixed::from_num(r),
+            nffixed::from_num(0),
+            nffixed::from_num(0),
+            nffixed::from_num(a),
+        )
+    }
+
+    /// A 4x2 tile layer with a single tile in the top left corner &
+    /// a quad covering the right half.
+    fn group() -> MapGroup {
+        let mut tiles = vec![TileBase::default(); 8];
+        tiles[0].index = 1;
+        let point = |x: i32, y: i32| fvec2::new(ffixed::from_num(x), ffixed::from_num(y));
+        MapGroup {
+            attr: MapGroupAttr::default(),
+            layers: vec![
+                MapLayer::Tile(MapLayerTile {
+                    attr: MapTileLayerAttr {
+                        width: NonZeroU16MinusOne::new(4).unwrap(),
+                        height: NonZeroU16MinusOne::new(2).unwrap(),
+                        color: color(1.0, 1.0),
+                        high_detail: false,
+                        color_anim: None,
+                        color_anim_offset: time::Duration::ZERO,
+                        image_array: Some(0),
+                        blend_mode: Default::default(),
+                    },
+                    tiles,
+                    name: Default::default(),
+                }),
+                MapLayer::Quad(MapLayerQuad {
+                    attr: MapLayerQuadsAttrs {
+                        image: Some(0),
+                        high_detail: false,
+                        blend_mode: Default::default(),
+                    },
+                    quads: vec![Quad {
+                        points: [
+                            point(2, 0),
+                            point(4, 0),
+                            point(2, 2),
+                            point(4, 2),
+                            point(3, 1),
+                        ],
+                        colors: [color(0.0, 0.5); 4],
+                        ..Default::default()
+                    }],
+                    name: Default::default(),
+                }),
+            ],
+            name: "bush".to_string(),
+        }
+    }
+
+    #[test]
+    fn template_names() {
+        assert!(check_template_name("big bush_2-x").is_ok());
+        assert!(check_template_name("").is_err());
+        assert!(check_template_name("  ").is_err());
+        assert!(check_template_name("../bush").is_err());
+        assert!(check_template_name("bush.twgroup").is_err());
+    }
+
+    #[test]
+    fn save_and_insert_template() {
+        let files: HashMap<_, _> = [b"img".to_vec(), b"arr".to_vec()]
+            .into_iter()
+            .map(|file| (generate_hash_for(&file), file))
+            .collect();
+        let src_resources = Resources {
+            images: vec![res("img", b"img")],
+            image_arrays: vec![res("arr", b"arr")],
+            sounds: Vec::new(),
+        };
+
+        // save
+        let file = EditorGroupFile::new(
+            &src_resources,
+            &Animations::default(),
+            &group(),
+            None,
+            &files,
+        )
+        .unwrap()
+        .write()
+        .unwrap();
+        let template = EditorGroupTemplate::new("bush".to_string(), false, &file).unwrap();
+        assert_eq!(template.file.group.name, "bush");
+
+        // thumbnail: 4x2 tiles scaled to 48x24 cells
+        let thumbnail = &template.thumbnail;
+        assert_eq!((thumbnail.width, thumbnail.height), (48, 24));
+        assert_eq!(thumbnail.pixels[0], [255, 0, 0, 255]);
+        assert_eq!(thumbnail.pixels[12 + 12 * 48], [0, 0, 0, 0]);
+        assert_eq!(thumbnail.pixels[47 + 23 * 48], [0, 0, 0, 128]);
+
+        // insert into a map that already contains the image, but not the image array
+        let dst_resources = Resources {
+            images: vec![res("other", b"other"), res("img", b"img")],
+            image_arrays: Vec::new(),
+            sounds: Vec::new(),
+        };
+        let import = template
+            .file
+            .import(&dst_resources, &Animations::default(), &[], false)
+            .unwrap();
+        assert!(import.collisions.is_empty());
+        assert!(
+            !import
+                .actions
+                .iter()
+                .any(|act| matches!(act, EditorAction::AddImage(_)))
+        );
+        assert_eq!(
+            import
+                .actions
+                .iter()
+                .filter(|act| matches!(act, EditorAction::AddImage2dArray(_)))
+                .count(),
+            1
+        );
+        let Some(EditorAction::AddGroup(add_group)) = import.actions.last() else {
+            panic!("group must be added last");
+        };
+        assert!(!add_group.base.is_background);
+        let MapLayer::Quad(quad_layer) = &add_group.base.group.layers[1] else {
+            panic!("expected quad layer");
+        };
+        assert_eq!(quad_layer.attr.image, Some(1));
+        let MapLayer::Tile(tile_layer) = &add_group.base.group.layers[0] else {
+            panic!("expected tile layer");
+        };
+        assert_eq!(tile_layer.attr.image_array, Some(0));
+    }
+}
@@ -0,0 +1,175 @@
+use std::path::{Path, PathBuf};
+
+use math::math::vector::vec2;
+use serde::{Deserialize, Serialize};
+
+use crate::color_palette::EditorColorPalette;
+
+/// How close (in tiles, at a zoom of `1.0`) a dragged quad or sound
+/// has to come to a guide to snap onto it.
+pub const GUIDE_SNAP_DISTANCE: f32 = 0.25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EditorGuideAxis {
+    /// A line at a fixed y position.
+    Horizontal,
+    /// A line at a fixed x position.
+    Vertical,
+}
+
+/// A guide line, created by dragging it out of the
+/// ruler at the top or left edge of the canvas.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EditorGuide {
+    pub axis: EditorGuideAxis,
+    /// The position on the other axis, in tiles.
+    pub pos: f32,
+}
+
+/// The guide lines of a map, they are only known to the editor.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EditorGuides {
+    pub guides: Vec<EditorGuide>,
+    /// Locked guides can't be moved or removed.
+    pub locked: bool,
+}
+
+impl EditorGuides {
+    /// The position of the guide of the axis, that is nearest to `pos`
+    /// and not farther away than `max_distance`.
+    pub fn nearest(&self, axis: EditorGuideAxis, pos: f32, max_distance: f32) -> Option<f32> {
+        self.guides
+            .iter()
+            .filter(|guide| guide.axis == axis)
+            .map(|guide| guide.pos)
+            .filter(|guide_pos| (guide_pos - pos).abs() <= max_distance)
+            .min_by(|a, b| (a - pos).abs().total_cmp(&(b - pos).abs()))
+    }
+
+    /// Snaps the x coordinate to the vertical guides & the y coordinate to the
+    /// horizontal guides, independent of each other.
+    ///
+    /// `None` for a coordinate that is not close enough to any guide.
+    pub fn snap(&self, pos: &vec2, max_distance: f32) -> (Option<f32>, Option<f32>) {
+        (
+            self.nearest(EditorGuideAxis::Vertical, pos.x, max_distance),
+            self.nearest(EditorGuideAxis::Horizontal, pos.y, max_distance),
+        )
+    }
+}
+
+/// The editor-only data of a map, that is saved next to the map file.
+/// Collaborators can share it together with the map.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EditorMapSidecar {
+    #[serde(default)]
+    pub guides: EditorGuides,
+    #[serde(default)]
+    pub palette: EditorColorPalette,
+}
+
+impl EditorMapSidecar {
+    /// E.g. `map/maps/ctf1.twmap.tar.editor.json`.
+    pub fn path(map_path: &Path) -> PathBuf {
+        let mut path = map_path.as_os_str().to_owned();
+        path.push(".editor.json");
+        path.into()
+    }
+
+    pub fn from_json(file: &[u8]) -> anyhow::Result<Self> {
+        Ok(serde_json::from_slice(file)?)
+    }
+
+    pub fn to_json(&self) -> anyhow::Result<Vec<u8>> {
+        Ok(serde_json::to_vec_pretty(self)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use math::math::vector::vec2;
+
+    use super::{EditorGuide, EditorGuideAxis, EditorGuides, EditorMapSidecar};
+
+    fn guides() -> EditorGuides {
+        EditorGuides {
+            guides: vec![
+                EditorGuide {
+                    axis: EditorGuideAxis::Vertical,
+                    pos: 10.0,
+                },
+                EditorGuide {
+                    axis: EditorGuideAxis::Vertical,
+                    pos: 10.4,
+                },
+                EditorGuide {
+                    axis: EditorGuideAxis::Horizontal,
+                    pos: -3.0,
+                },
+            ],
+            locked: false,
+        }
+    }
+
+    #[test]
+    fn snap_distance() {
+        let guides = guides();
+
+        // the nearest guide wins
+        assert_eq!(
+            guides.nearest(EditorGuideAxis::Vertical, 10.3, 0.5),
+            Some(10.4)
+        );
+        assert_eq!(
+            guides.nearest(EditorGuideAxis::Vertical, 10.1, 0.5),
+            Some(10.0)
+        );
+        // too far away
+        assert_eq!(guides.nearest(EditorGuideAxis::Vertical, 11.0, 0.5), None);
+        // exactly at the snap distance still snaps
+        assert_eq!(
+            guides.nearest(EditorGuideAxis::Horizontal, -2.5, 0.5),
+            Some(-3.0)
+        );
+        // guides of the other axis are ignored
+        assert_eq!(guides.nearest(EditorGuideAxis::Horizontal, 10.0, 0.5), None);
+
+        // both axes snap independently
+        assert_eq!(
+            guides.snap(&vec2::new(9.8, -3.2), 0.5),
+            (Some(10.0), Some(-3.0))
+        );
+        assert_eq!(guides.snap(&vec2::new(9.8, 5.0), 0.5), (Some(10.0), None));
+        assert_eq!(
+            EditorGuides::default().snap(&vec2::new(9.8, -3.2), 0.5),
+            (None, None)
+        );
+    }
+
+    #[test]
+    fn sidecar_persistence() {
+        assert_eq!(
+            EditorMapSidecar::path(Path::new("map/maps/ctf1.twmap.tar")),
+            Path::new("map/maps/ctf1.twmap.tar.editor.json")
+        );
+
+        let sidecar = EditorMapSidecar {
+            guides: EditorGuides {
+                locked: true,
+                ..guides()
+            },
+            ..Default::default()
+        };
+        let file = sidecar.to_json().unwrap();
+        assert_eq!(EditorMapSidecar::from_json(&file).unwrap(), sidecar);
+
+        // sidecars of older editors might miss fields
+        assert_eq!(
+            EditorMapSidecar::from_json(b"{}").unwrap(),
+            EditorMapSidecar::default()
+        );
+        assert!(EditorMapSidecar::from_json(b"not json").is_err());
+    }
+}
@@ -0,0 +1,336 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    action_logic::merge_actions,
+    actions::actions::{EditorActionGroup, EditorActionInterface},
+};
+
+/// How many entries the history keeps, before the oldest entries
+/// are coalesced into a single entry.
+pub const HISTORY_MAX_ENTRIES: usize = 300;
+/// How many actions the coalesced oldest entry may contain,
+/// before it is dropped from the history.
+pub const HISTORY_MAX_COALESCED_ACTIONS: usize = 5000;
+
+#[derive(Debug, Clone)]
+struct EditorHistoryGroup {
+    group: EditorActionGroup,
+    /// When the group was committed (or last merged into).
+    time: Duration,
+    /// How many committed groups this group consists of.
+    coalesced: usize,
+}
+
+/// A single entry of the history as shown in the history panel.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditorHistoryEntry {
+    pub label: String,
+    pub identifier: Option<String>,
+    pub action_count: usize,
+    /// How many committed groups were coalesced into this entry.
+    pub coalesced: usize,
+    /// When the entry was committed, relative to the start of the server.
+    pub time: Duration,
+}
+
+/// A snapshot of the history, that the server sends to clients.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EditorHistoryInfo {
+    pub entries: Vec<EditorHistoryEntry>,
+    /// The entry that represents the current state of the map.
+    /// `None` if all entries are undone.
+    pub cur: Option<usize>,
+}
+
+/// The list of committed action groups of the server,
+/// together with the current position inside this list.
+///
+/// Everything after the current position can be redone,
+/// the current position and everything before it can be undone.
+#[derive(Debug, Default)]
+pub struct EditorHistory {
+    groups: Vec<EditorHistoryGroup>,
+    cur: Option<usize>,
+}
+
+impl EditorHistory {
+    pub fn cur(&self) -> Option<usize> {
+        self.cur
+    }
+
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Commits a new action group, dropping everything that could be redone.
+    ///
+    /// If the group has the same identifier as the current group, both are merged.
+    /// Returns `Ok(true)` if actions were merged.
+    pub fn push(&mut self, group: EditorActionGroup, time: Duration) -> anyhow::Result<bool> {
+        if let Some(cur) = self.cur {
+            self.groups.truncate(cur + 1);
+        } else {
+            self.groups.clear();
+        }
+
+        let res = if let Some(last) = self.groups.last_mut().filter(|last| {
+            last.group
+                .identifier
+                .as_ref()
+                // explicitly check for some here
+                .is_some_and(|identifier| Some(identifier) == group.identifier.as_ref())
+        }) {
+            last.time = time;
+            last.group.actions.append(&mut group.actions.clone());
+            merge_actions(&mut last.group.actions)
+        } else {
+            self.groups.push(EditorHistoryGroup {
+                group,
+                time,
+                coalesced: 1,
+            });
+            Ok(false)
+        };
+        self.cur = self.groups.len().checked_sub(1);
+
+        self.coalesce();
+
+        res
+    }
+
+    /// Make sure memory doesn't exhaust.
+    ///
+    /// Since the current position is always the last entry after a push,
+    /// coalescing the oldest entries never changes the current map state.
+    fn coalesce(&mut self) {
+        while self.groups.len() > HISTORY_MAX_ENTRIES {
+            let second = self.groups.remove(1);
+            let first = &mut self.groups[0];
+            first.group.actions.extend(second.group.actions);
+            first.group.identifier = None;
+            first.time = second.time;
+            first.coalesced += second.coalesced;
+            self.cur = self.cur.map(|index| index.saturating_sub(1));
+
+            if first.group.actions.len() > HISTORY_MAX_COALESCED_ACTIONS {
+                self.groups.remove(0);
+                self.cur = self.cur.and_then(|index| index.checked_sub(1));
+            }
+        }
+    }
+
+    /// The group that would be undone next.
+    pub fn undo_group(&self) -> Option<&EditorActionGroup> {
+        self.cur
+            .and_then(|index| self.groups.get(index))
+            .map(|g| &g.group)
+    }
+
+    /// The group that would be redone next.
+    pub fn redo_group(&self) -> Option<&EditorActionGroup> {
+        self.groups
+            .get(self.cur.map_or(0, |index| index + 1))
+            .map(|g| &g.group)
+    }
+
+    /// Moves the current position a single step and returns the
+    /// group that has to be undone or redone.
+    pub fn step(&mut self, is_undo: bool) -> Option<EditorActionGroup> {
+        if is_undo {
+            let group = self.undo_group().cloned()?;
+            self.cur = self.cur.and_then(|index| index.checked_sub(1));
+            Some(group)
+        } else {
+            let group = self.redo_group().cloned()?;
+            self.cur = Some(self.cur.map_or(0, |index| index + 1));
+            Some(group)
+        }
+    }
+
+    /// Which kind of step (`true` = undo) and how many steps are required
+    /// to move the current position to `target`.
+    ///
+    /// Returns `None` if the target does not exist.
+    pub fn jump_steps(&self, target: Option<usize>) -> Option<(bool, usize)> {
+        if target.is_some_and(|index| index >= self.groups.len()) {
+            return None;
+        }
+        let pos = |index: Option<usize>| index.map_or(0, |index| index + 1);
+        let (cur, target) = (pos(self.cur), pos(target));
+        Some(if target < cur {
+            (true, cur - target)
+        } else {
+            (false, target - cur)
+        })
+    }
+
+    /// All groups that are undone or redone when jumping to `target`.
+    pub fn jump_groups(&self, target: Option<usize>) -> Vec<&EditorActionGroup> {
+        let Some((is_undo, steps)) = self.jump_steps(target) else {
+            return Vec::new();
+        };
+        let start = if is_undo {
+            self.cur.map_or(0, |index| index + 1) - steps
+        } else {
+            self.cur.map_or(0, |index| index + 1)
+        };
+        self.groups[start..start + steps]
+            .iter()
+            .map(|g| &g.group)
+            .collect()
+    }
+
+    fn group_label(group: &EditorActionGroup, is_undo: bool) -> Option<String> {
+        let len = group.actions.len();
+        let act = if is_undo {
+            group.actions.last()
+        } else {
+            group.actions.first()
+        }?;
+        Some(format!(
+            "{}{}",
+            if is_undo {
+                act.undo_info()
+            } else {
+                act.redo_info()
+            },
+            if len > 1 {
+                format!(" + {len} more ")
+            } else {
+                "".to_string()
+            }
+        ))
+    }
+
+    pub fn undo_label(&self) -> Option<String> {
+        self.undo_group().and_then(|g| Self::group_label(g, true))
+    }
+
+    pub fn redo_label(&self) -> Option<String> {
+        self.redo_group().and_then(|g| Self::group_label(g, false))
+    }
+
+    pub fn info(&self) -> EditorHistoryInfo {
+        EditorHistoryInfo {
+            entries: self
+                .groups
+                .iter()
+                .map(|g| EditorHistoryEntry {
+                    label: Self::group_label(&g.group, false).unwrap_or_default(),
+                    identifier: g.group.identifier.clone(),
+                    action_count: g.group.actions.len(),
+                    coalesced: g.coalesced,
+                    time: g.time,
+                })
+                .collect(),
+            cur: self.cur,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::actions::actions::{ActChangeGroupName, EditorAction, EditorActionGroup};
+
+    use super::{EditorHistory, HISTORY_MAX_ENTRIES};
+
+    /// Applies a group to a list of group names, the only state the test actions modify.
+    fn apply(names: &mut [String], group: &EditorActionGroup, is_undo: bool) {
+        let apply_act = |names: &mut [String], act: &EditorAction| {
+            let EditorAction::ChangeGroupName(act) = act else {
+                panic!("unexpected action");
+            };
+            names[act.group_index] = if is_undo {
+                act.old_name.clone()
+            } else {
+                act.new_name.clone()
+            };
+        };
+        if is_undo {
+            group.actions.iter().rev().for_each(|a| apply_act(names, a));
+        } else {
+            group.actions.iter().for_each(|a| apply_act(names, a));
+        }
+    }
+
+    fn history_with_actions(count: usize) -> (EditorHistory, Vec<String>) {
+        let mut history = EditorHistory::default();
+        let mut names = vec![String::new(); 5];
+        for i in 0..count {
+            let group_index = i % names.len();
+            let group = EditorActionGroup {
+                actions: vec![EditorAction::ChangeGroupName(ActChangeGroupName {
+                    is_background: false,
+                    group_index,
+                    old_name: names[group_index].clone(),
+                    new_name: format!("name{i}"),
+                })],
+                identifier: None,
+            };
+            apply(&mut names, &group, false);
+            history.push(group, Duration::from_secs(i as u64)).unwrap();
+        }
+        (history, names)
+    }
+
+    fn jump(history: &mut EditorHistory, names: &mut [String], target: Option<usize>) {
+        let (is_undo, steps) = history.jump_steps(target).unwrap();
+        for _ in 0..steps {
+            let group = history.step(is_undo).unwrap();
+            apply(names, &group, is_undo);
+        }
+    }
+
+    #[test]
+    fn jump_equals_undo() {
+        let (mut jumped, mut jumped_names) = history_with_actions(50);
+        let (mut undone, mut undone_names) = history_with_actions(50);
+
+        // jump to entry 20, so 20 groups are still applied
+        assert_eq!(jumped.jump_groups(Some(19)).len(), 30);
+        jump(&mut jumped, &mut jumped_names, Some(19));
+        for _ in 0..30 {
+            let group = undone.step(true).unwrap();
+            apply(&mut undone_names, &group, true);
+        }
+
+        assert_eq!(jumped.cur(), Some(19));
+        assert_eq!(jumped.cur(), undone.cur());
+        assert_eq!(jumped_names, undone_names);
+        assert_eq!(jumped.undo_label(), undone.undo_label());
+        assert_eq!(jumped.redo_label(), undone.redo_label());
+
+        // jumping forward again restores the latest state
+        let (_, latest_names) = history_with_actions(50);
+        jump(&mut jumped, &mut jumped_names, Some(49));
+        assert_eq!(jumped_names, latest_names);
+
+        // jump to the initial state
+        jump(&mut jumped, &mut jumped_names, None);
+        assert!(jumped_names.iter().all(|name| name.is_empty()));
+        assert!(jumped.jump_steps(Some(50)).is_none());
+    }
+
+    #[test]
+    fn coalesce_old_entries() {
+        let (mut history, mut names) = history_with_actions(HISTORY_MAX_ENTRIES + 20);
+        assert_eq!(history.len(), HISTORY_MAX_ENTRIES);
+        assert_eq!(history.cur(), Some(HISTORY_MAX_ENTRIES - 1));
+
+        let info = history.info();
+        assert_eq!(info.entries[0].coalesced, 21);
+        assert_eq!(info.entries[0].action_count, 21);
+
+        // undoing everything still restores the initial state
+        jump(&mut history, &mut names, None);
+        assert!(names.iter().all(|name| name.is_empty()));
+    }
+}
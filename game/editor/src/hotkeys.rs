@@ -18,6 +18,7 @@ pub enum EditorHotkeyEventFile {
 pub enum EditorHotkeyEventEdit {
     Undo,
     Redo,
+    FindReplaceTiles,
 }
 
 #[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Serialize, Deserialize)]
@@ -86,6 +87,7 @@ pub enum EditorHotkeyEventTabs {
 pub enum EditorHotkeyEventPreferences {
     ShowTileLayerIndices,
     ToggleParallaxZoom,
+    ToggleParallaxPreview,
     IncreaseMapTimeSpeed,
     DecreaseMapTimeSpeed,
     ToggleGrid,
@@ -99,6 +101,8 @@ pub enum EditorHotkeyEventPanels {
     ToggleServerCommands,
     ToggleServerConfigVars,
     ToggleAssetsStore,
+    ToggleHistory,
+    ToggleNumberUsage,
 }
 
 #[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Serialize, Deserialize)]
@@ -214,6 +218,10 @@ impl EditorBindsFile {
             EditorHotkeyEvent::Edit(EditorHotkeyEventEdit::Undo),
             KeyboardShortcut::new(Modifiers::CTRL, Key::Y),
         );
+        hotkey(
+            EditorHotkeyEvent::Edit(EditorHotkeyEventEdit::FindReplaceTiles),
+            KeyboardShortcut::new(Modifiers::CTRL, Key::F),
+        );
         hotkey(
             EditorHotkeyEvent::Timeline(EditorHotkeyEventTimeline::InsertPoint),
             KeyboardShortcut::new(Default::default(), Key::I),
@@ -306,6 +314,10 @@ impl EditorBindsFile {
             EditorHotkeyEvent::Preferences(EditorHotkeyEventPreferences::ToggleParallaxZoom),
             KeyboardShortcut::new(Modifiers::CTRL, Key::P),
         );
+        hotkey(
+            EditorHotkeyEvent::Preferences(EditorHotkeyEventPreferences::ToggleParallaxPreview),
+            KeyboardShortcut::new(Modifiers::CTRL.plus(Modifiers::SHIFT), Key::P),
+        );
         hotkey(
             EditorHotkeyEvent::Preferences(EditorHotkeyEventPreferences::IncreaseMapTimeSpeed),
             KeyboardShortcut::new(Modifiers::CTRL, Key::Plus),
@@ -342,6 +354,14 @@ impl EditorBindsFile {
             EditorHotkeyEvent::Panels(EditorHotkeyEventPanels::ToggleAssetsStore),
             KeyboardShortcut::new(Modifiers::CTRL, Key::O),
         );
+        hotkey(
+            EditorHotkeyEvent::Panels(EditorHotkeyEventPanels::ToggleHistory),
+            KeyboardShortcut::new(Modifiers::CTRL, Key::H),
+        );
+        hotkey(
+            EditorHotkeyEvent::Panels(EditorHotkeyEventPanels::ToggleNumberUsage),
+            KeyboardShortcut::new(Modifiers::CTRL, Key::K),
+        );
         hotkey(
             EditorHotkeyEvent::Map(EditorHotkeyEventMap::MoveLayerUp),
             KeyboardShortcut::new(Modifiers::SHIFT, Key::ArrowUp),
@@ -18,7 +18,9 @@ use client_containers::container::{
     ContainerLoadOptions, ContainerLoadedItem, ContainerLoadedItemDir, load_file_part_and_upload,
 };
 
-use client_containers::container::{Container, ContainerItemLoadData, ContainerLoad};
+use client_containers::container::{
+    Container, ContainerItemLoadData, ContainerItemMemory, ContainerItemMemoryUsage, ContainerLoad,
+};
 
 #[derive(Debug, Hiarc, Clone)]
 pub struct ImageStore {
@@ -79,6 +81,17 @@ impl LoadImageStore {
     }
 }
 
+impl ContainerItemMemoryUsage for LoadImageStore {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        // the file is kept for the map
+        self.image.memory_usage()
+            + ContainerItemMemory {
+                gpu_bytes: 0,
+                cpu_bytes: self.file.len(),
+            }
+    }
+}
+
 impl ContainerLoad<ImageStore> for LoadImageStore {
     fn load(
         item_name: &str,
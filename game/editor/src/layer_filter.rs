@@ -0,0 +1,271 @@
+use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
+use map::skeleton::groups::layers::design::MapLayerSkeleton;
+
+use crate::map::{EditorLayer, EditorPhysicsLayer};
+
+/// Quick filter by the type of a layer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LayerTypeFilter {
+    #[default]
+    All,
+    Tile,
+    Quad,
+    Sound,
+}
+
+/// A resource that is used by a layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayerResource {
+    Image(usize),
+    ImageArray(usize),
+    Sound(usize),
+}
+
+/// Filters the groups & layers panel.
+#[derive(Debug, Clone, Default)]
+pub struct EditorLayerFilter {
+    /// Fuzzy matched against the names of the groups & layers.
+    pub text: String,
+    pub ty: LayerTypeFilter,
+    /// Only layers that have an animation.
+    pub animated: bool,
+    /// Only layers that use this resource.
+    pub resource: Option<LayerResource>,
+}
+
+/// What the filter checks of a layer.
+#[derive(Debug, Clone)]
+pub struct LayerFilterInfo<'a> {
+    /// The shown name of the layer.
+    pub name: &'a str,
+    pub ty: LayerTypeFilter,
+    pub animated: bool,
+    pub resource: Option<LayerResource>,
+}
+
+impl<'a> LayerFilterInfo<'a> {
+    pub fn design(layer: &EditorLayer, name: &'a str) -> Self {
+        let (ty, animated, resource) = match layer {
+            MapLayerSkeleton::Abritrary(_) => (LayerTypeFilter::All, false, None),
+            MapLayerSkeleton::Tile(layer) => (
+                LayerTypeFilter::Tile,
+                layer.layer.attr.color_anim.is_some(),
+                layer.layer.attr.image_array.map(LayerResource::ImageArray),
+            ),
+            MapLayerSkeleton::Quad(layer) => (
+                LayerTypeFilter::Quad,
+                layer
+                    .layer
+                    .quads
+                    .iter()
+                    .any(|q| q.pos_anim.is_some() || q.color_anim.is_some()),
+                layer.layer.attr.image.map(LayerResource::Image),
+            ),
+            MapLayerSkeleton::Sound(layer) => (
+                LayerTypeFilter::Sound,
+                layer
+                    .layer
+                    .sounds
+                    .iter()
+                    .any(|s| s.pos_anim.is_some() || s.sound_anim.is_some()),
+                layer.layer.attr.sound.map(LayerResource::Sound),
+            ),
+        };
+        Self {
+            name,
+            ty,
+            animated,
+            resource,
+        }
+    }
+
+    /// Physics layers are tile layers without animations or resources.
+    pub fn physics(_layer: &EditorPhysicsLayer, name: &'a str) -> Self {
+        Self {
+            name,
+            ty: LayerTypeFilter::Tile,
+            animated: false,
+            resource: None,
+        }
+    }
+}
+
+/// Which entries of a group are shown.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GroupFilterResult {
+    pub visible: bool,
+    /// The matched chars of the group name, for highlighting.
+    pub name_match: Vec<usize>,
+    /// `None` if the layer is hidden,
+    /// else the matched chars of the layer name.
+    pub layers: Vec<Option<Vec<usize>>>,
+}
+
+impl EditorLayerFilter {
+    pub fn is_active(&self) -> bool {
+        !self.text.is_empty()
+            || self.ty != LayerTypeFilter::All
+            || self.animated
+            || self.resource.is_some()
+    }
+
+    fn has_quick_filters(&self) -> bool {
+        self.ty != LayerTypeFilter::All || self.animated || self.resource.is_some()
+    }
+
+    /// The matched chars of the name, `None` if it does not match.
+    fn match_name(&self, matcher: &SkimMatcherV2, name: &str) -> Option<Vec<usize>> {
+        if self.text.is_empty() {
+            Some(Vec::new())
+        } else {
+            matcher
+                .fuzzy_indices(name, &self.text)
+                .map(|(_, indices)| indices)
+        }
+    }
+
+    fn passes_quick_filters(&self, layer: &LayerFilterInfo) -> bool {
+        (self.ty == LayerTypeFilter::All || self.ty == layer.ty)
+            && (!self.animated || layer.animated)
+            && (self.resource.is_none() || self.resource == layer.resource)
+    }
+
+    /// Layers are shown if they pass the quick filters & either their
+    /// name or the name of their group matches.
+    ///
+    /// Groups are shown if any of their layers is shown, so matched layers
+    /// keep their parent visible. Without quick filters, groups whose name
+    /// matches are shown even if they have no layers.
+    pub fn filter_group(&self, group_name: &str, layers: &[LayerFilterInfo]) -> GroupFilterResult {
+        let matcher = SkimMatcherV2::default();
+        let group_match = self.match_name(&matcher, group_name);
+        let layers: Vec<_> = layers
+            .iter()
+            .map(|layer| {
+                if !self.passes_quick_filters(layer) {
+                    return None;
+                }
+                self.match_name(&matcher, layer.name)
+                    .or_else(|| group_match.as_ref().map(|_| Vec::new()))
+            })
+            .collect();
+        let visible = layers.iter().any(|layer| layer.is_some())
+            || (group_match.is_some() && !self.has_quick_filters());
+        GroupFilterResult {
+            visible,
+            name_match: group_match.unwrap_or_default(),
+            layers,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{EditorLayerFilter, LayerFilterInfo, LayerResource, LayerTypeFilter};
+
+    fn layer(name: &str, ty: LayerTypeFilter) -> LayerFilterInfo<'_> {
+        LayerFilterInfo {
+            name,
+            ty,
+            animated: false,
+            resource: None,
+        }
+    }
+
+    #[test]
+    fn filter_predicate() {
+        let layers = [
+            layer("unhook_border_2", LayerTypeFilter::Tile),
+            layer("doodads", LayerTypeFilter::Quad),
+            LayerFilterInfo {
+                animated: true,
+                resource: Some(LayerResource::Image(1)),
+                ..layer("clouds", LayerTypeFilter::Quad)
+            },
+            layer("ambience", LayerTypeFilter::Sound),
+        ];
+
+        // no filter shows everything
+        let res = EditorLayerFilter::default().filter_group("Game", &layers);
+        assert!(res.visible);
+        assert!(res.layers.iter().all(|l| l.as_deref() == Some(&[][..])));
+
+        // fuzzy match with the matched chars
+        let filter = EditorLayerFilter {
+            text: "unhbord".into(),
+            ..Default::default()
+        };
+        let res = filter.filter_group("Game", &layers);
+        assert!(res.visible);
+        assert_eq!(res.layers[0].as_deref(), Some(&[0, 1, 2, 7, 8, 9, 10][..]));
+        assert!(res.layers[1..].iter().all(|l| l.is_none()));
+
+        // quick filters
+        let filter = EditorLayerFilter {
+            ty: LayerTypeFilter::Quad,
+            ..Default::default()
+        };
+        let res = filter.filter_group("Game", &layers);
+        let shown: Vec<_> = res.layers.iter().map(|l| l.is_some()).collect();
+        assert_eq!(shown, [false, true, true, false]);
+        let filter = EditorLayerFilter {
+            animated: true,
+            ..Default::default()
+        };
+        let res = filter.filter_group("Game", &layers);
+        let shown: Vec<_> = res.layers.iter().map(|l| l.is_some()).collect();
+        assert_eq!(shown, [false, false, true, false]);
+        let filter = EditorLayerFilter {
+            resource: Some(LayerResource::Image(0)),
+            ..Default::default()
+        };
+        assert!(!filter.filter_group("Game", &layers).visible);
+    }
+
+    #[test]
+    fn parent_visibility() {
+        let layers = [
+            layer("unhook", LayerTypeFilter::Tile),
+            layer("doodads", LayerTypeFilter::Quad),
+        ];
+
+        // a matched layer keeps its group visible
+        let filter = EditorLayerFilter {
+            text: "doodads".into(),
+            ..Default::default()
+        };
+        let res = filter.filter_group("Background", &layers);
+        assert!(res.visible);
+        assert!(res.name_match.is_empty());
+        assert!(res.layers[0].is_none());
+        assert!(res.layers[1].is_some());
+
+        // nothing matches, the group is hidden
+        let filter = EditorLayerFilter {
+            text: "xyz".into(),
+            ..Default::default()
+        };
+        assert!(!filter.filter_group("Background", &layers).visible);
+
+        // a matched group shows all its layers
+        let filter = EditorLayerFilter {
+            text: "backgr".into(),
+            ..Default::default()
+        };
+        let res = filter.filter_group("Background", &layers);
+        assert!(res.visible);
+        assert_eq!(res.name_match, [0, 1, 2, 3, 4, 5]);
+        assert!(res.layers.iter().all(|l| l.is_some()));
+        // even if it is empty
+        assert!(filter.filter_group("Background", &[]).visible);
+
+        // but the quick filters still apply to its layers
+        let filter = EditorLayerFilter {
+            text: "backgr".into(),
+            ty: LayerTypeFilter::Sound,
+            ..Default::default()
+        };
+        assert!(!filter.filter_group("Background", &layers).visible);
+        assert!(!filter.filter_group("Background", &[]).visible);
+    }
+}
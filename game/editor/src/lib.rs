@@ -3,25 +3,41 @@
 
 pub mod action_logic;
 pub mod actions;
+pub mod chat;
 pub mod client;
+pub mod clipboard;
+pub mod color_palette;
 pub mod dbg;
 pub mod editor;
 pub mod editor_ui;
 pub mod event;
 pub mod explain;
 pub mod fs;
+pub mod group_file;
+pub mod group_templates;
+pub mod guides;
+pub mod history;
 pub mod hotkeys;
 pub mod image_store_container;
+pub mod layer_filter;
+pub mod locks;
 pub mod map;
+pub mod map_diff;
 pub mod map_tools;
 pub mod network;
 pub mod notifications;
 pub mod options;
+pub mod parallax_preview;
+pub mod physics_from_design;
 pub mod physics_layers;
+pub mod physics_numbers;
+pub mod resource_optimizer;
 pub mod server;
+pub mod snapshot;
 pub mod sound_store_container;
 pub mod tab;
 pub mod tile_overlays;
+pub mod tile_replace;
 pub mod tools;
 pub mod ui;
 pub mod utils;
@@ -0,0 +1,582 @@
+use std::{collections::HashMap, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{actions::actions::EditorAction, event::EditorEventLayerIndex};
+
+/// Locks of users that did not edit anything for this long are
+/// automatically released.
+pub const LOCK_INACTIVITY_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// The layer a lock belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EditorLockLayer {
+    Design(EditorEventLayerIndex),
+    Physics { layer_index: usize },
+}
+
+/// A rectangular area in tile coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditorLockRect {
+    pub x: u16,
+    pub y: u16,
+    pub w: u16,
+    pub h: u16,
+}
+
+impl EditorLockRect {
+    pub fn overlaps(&self, other: &Self) -> bool {
+        let (x1, y1) = (self.x as u32, self.y as u32);
+        let (x2, y2) = (other.x as u32, other.y as u32);
+        x1 < x2 + other.w as u32
+            && x2 < x1 + self.w as u32
+            && y1 < y2 + other.h as u32
+            && y2 < y1 + self.h as u32
+    }
+}
+
+/// A lock that prevents all other users from modifying
+/// a layer or a tile area of it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditorLock {
+    /// unique id on the server
+    pub id: u64,
+    /// the server id of the client owning this lock
+    pub owner: u64,
+    pub owner_name: String,
+
+    pub layer: EditorLockLayer,
+    /// `None` locks the whole layer
+    pub rect: Option<EditorLockRect>,
+}
+
+impl EditorLock {
+    fn overlaps(&self, layer: &EditorLockLayer, rect: Option<&EditorLockRect>) -> bool {
+        self.layer == *layer
+            && self
+                .rect
+                .as_ref()
+                .zip(rect)
+                .is_none_or(|(r1, r2)| r1.overlaps(r2))
+    }
+}
+
+/// What part of the map an action modifies,
+/// used to check the action against the locks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorLockTarget {
+    /// Modifies a layer or only a tile area of it.
+    Layer {
+        layer: EditorLockLayer,
+        rect: Option<EditorLockRect>,
+    },
+    /// Modifies or shifts all design layers starting from `from` inside a group.
+    DesignLayers {
+        is_background: bool,
+        group_index: usize,
+        from: usize,
+    },
+    /// Modifies or shifts all design groups starting from `from`.
+    DesignGroups { is_background: bool, from: usize },
+    /// Modifies or shifts all physics layers starting from `from`.
+    PhysicsLayers { from: usize },
+}
+
+impl EditorLockTarget {
+    fn layer(is_background: bool, group_index: usize, layer_index: usize) -> Self {
+        Self::Layer {
+            layer: EditorLockLayer::Design(EditorEventLayerIndex {
+                is_background,
+                group_index,
+                layer_index,
+            }),
+            rect: None,
+        }
+    }
+
+    fn layers(is_background: bool, group_index: usize, from: usize) -> Self {
+        Self::DesignLayers {
+            is_background,
+            group_index,
+            from,
+        }
+    }
+
+    pub fn affects(&self, lock: &EditorLock) -> bool {
+        match (self, &lock.layer) {
+            (Self::Layer { layer, rect }, _) => lock.overlaps(layer, rect.as_ref()),
+            (
+                Self::DesignLayers {
+                    is_background,
+                    group_index,
+                    from,
+                },
+                EditorLockLayer::Design(index),
+            ) => {
+                index.is_background == *is_background
+                    && index.group_index == *group_index
+                    && index.layer_index >= *from
+            }
+            (
+                Self::DesignGroups {
+                    is_background,
+                    from,
+                },
+                EditorLockLayer::Design(index),
+            ) => index.is_background == *is_background && index.group_index >= *from,
+            (Self::PhysicsLayers { from }, EditorLockLayer::Physics { layer_index }) => {
+                *layer_index >= *from
+            }
+            _ => false,
+        }
+    }
+}
+
+/// All parts of the map the action modifies.
+///
+/// Actions that only modify global state (resources, animations,
+/// settings etc.) have no targets.
+pub fn lock_targets(act: &EditorAction) -> Vec<EditorLockTarget> {
+    match act {
+        EditorAction::MoveGroup(act) => vec![
+            EditorLockTarget::DesignGroups {
+                is_background: act.old_is_background,
+                from: act
+                    .old_group
+                    .min(if act.old_is_background == act.new_is_background {
+                        act.new_group
+                    } else {
+                        usize::MAX
+                    }),
+            },
+            EditorLockTarget::DesignGroups {
+                is_background: act.new_is_background,
+                from: act.new_group,
+            },
+        ],
+        EditorAction::MoveLayer(act) => vec![
+            EditorLockTarget::layers(
+                act.old_is_background,
+                act.old_group,
+                if act.old_is_background == act.new_is_background && act.old_group == act.new_group
+                {
+                    act.old_layer.min(act.new_layer)
+                } else {
+                    act.old_layer
+                },
+            ),
+            EditorLockTarget::layers(act.new_is_background, act.new_group, act.new_layer),
+        ],
+        EditorAction::LayerChangeImageIndex(act) => vec![EditorLockTarget::layer(
+            act.is_background,
+            act.group_index,
+            act.layer_index,
+        )],
+        EditorAction::LayerChangeSoundIndex(act) => vec![EditorLockTarget::layer(
+            act.is_background,
+            act.group_index,
+            act.layer_index,
+        )],
+        EditorAction::QuadLayerAddQuads(act) => vec![EditorLockTarget::layer(
+            act.base.is_background,
+            act.base.group_index,
+            act.base.layer_index,
+        )],
+        EditorAction::SoundLayerAddSounds(act) => vec![EditorLockTarget::layer(
+            act.base.is_background,
+            act.base.group_index,
+            act.base.layer_index,
+        )],
+        EditorAction::QuadLayerRemQuads(act) => vec![EditorLockTarget::layer(
+            act.base.is_background,
+            act.base.group_index,
+            act.base.layer_index,
+        )],
+        EditorAction::SoundLayerRemSounds(act) => vec![EditorLockTarget::layer(
+            act.base.is_background,
+            act.base.group_index,
+            act.base.layer_index,
+        )],
+        EditorAction::AddTileLayer(act) => vec![EditorLockTarget::layers(
+            act.base.is_background,
+            act.base.group_index,
+            act.base.index,
+        )],
+        EditorAction::AddQuadLayer(act) => vec![EditorLockTarget::layers(
+            act.base.is_background,
+            act.base.group_index,
+            act.base.index,
+        )],
+        EditorAction::AddSoundLayer(act) => vec![EditorLockTarget::layers(
+            act.base.is_background,
+            act.base.group_index,
+            act.base.index,
+        )],
+        EditorAction::RemTileLayer(act) => vec![EditorLockTarget::layers(
+            act.base.is_background,
+            act.base.group_index,
+            act.base.index,
+        )],
+        EditorAction::RemQuadLayer(act) => vec![EditorLockTarget::layers(
+            act.base.is_background,
+            act.base.group_index,
+            act.base.index,
+        )],
+        EditorAction::RemSoundLayer(act) => vec![EditorLockTarget::layers(
+            act.base.is_background,
+            act.base.group_index,
+            act.base.index,
+        )],
+        EditorAction::AddPhysicsTileLayer(act) => vec![EditorLockTarget::PhysicsLayers {
+            from: act.base.index,
+        }],
+        EditorAction::RemPhysicsTileLayer(act) => vec![EditorLockTarget::PhysicsLayers {
+            from: act.base.index,
+        }],
+        EditorAction::TileLayerReplaceTiles(act) => vec![EditorLockTarget::Layer {
+            layer: EditorLockLayer::Design(EditorEventLayerIndex {
+                is_background: act.base.is_background,
+                group_index: act.base.group_index,
+                layer_index: act.base.layer_index,
+            }),
+            rect: Some(EditorLockRect {
+                x: act.base.x,
+                y: act.base.y,
+                w: act.base.w.get(),
+                h: act.base.h.get(),
+            }),
+        }],
+        EditorAction::TilePhysicsLayerReplaceTiles(act) => vec![EditorLockTarget::Layer {
+            layer: EditorLockLayer::Physics {
+                layer_index: act.base.layer_index,
+            },
+            rect: Some(EditorLockRect {
+                x: act.base.x,
+                y: act.base.y,
+                w: act.base.w.get(),
+                h: act.base.h.get(),
+            }),
+        }],
+        EditorAction::AddGroup(act) => vec![EditorLockTarget::DesignGroups {
+            is_background: act.base.is_background,
+            from: act.base.index,
+        }],
+        EditorAction::RemGroup(act) => vec![EditorLockTarget::DesignGroups {
+            is_background: act.base.is_background,
+            from: act.base.index,
+        }],
+        EditorAction::ChangeGroupAttr(act) => vec![EditorLockTarget::layers(
+            act.is_background,
+            act.group_index,
+            0,
+        )],
+        EditorAction::ChangeGroupName(_) => Vec::new(),
+        // resizes all physics layers
+        EditorAction::ChangePhysicsGroupAttr(_) => {
+            vec![EditorLockTarget::PhysicsLayers { from: 0 }]
+        }
+        EditorAction::ChangeTileLayerDesignAttr(act) => vec![EditorLockTarget::layer(
+            act.is_background,
+            act.group_index,
+            act.layer_index,
+        )],
+        EditorAction::ChangeQuadLayerAttr(act) => vec![EditorLockTarget::layer(
+            act.is_background,
+            act.group_index,
+            act.layer_index,
+        )],
+        EditorAction::ChangeSoundLayerAttr(act) => vec![EditorLockTarget::layer(
+            act.is_background,
+            act.group_index,
+            act.layer_index,
+        )],
+        EditorAction::ChangeDesignLayerName(act) => vec![EditorLockTarget::layer(
+            act.is_background,
+            act.group_index,
+            act.layer_index,
+        )],
+        EditorAction::ChangeQuadAttr(act) => vec![EditorLockTarget::layer(
+            act.is_background,
+            act.group_index,
+            act.layer_index,
+        )],
+        EditorAction::ChangeSoundAttr(act) => vec![EditorLockTarget::layer(
+            act.is_background,
+            act.group_index,
+            act.layer_index,
+        )],
+        EditorAction::AddImage(_)
+        | EditorAction::AddImage2dArray(_)
+        | EditorAction::AddSound(_)
+        | EditorAction::RemImage(_)
+        | EditorAction::RemImage2dArray(_)
+        | EditorAction::RemSound(_)
+        | EditorAction::ChangeTeleporter(_)
+        | EditorAction::ChangeSwitch(_)
+        | EditorAction::ChangeTuneZone(_)
+        | EditorAction::AddPosAnim(_)
+        | EditorAction::ReplPosAnim(_)
+        | EditorAction::RemPosAnim(_)
+        | EditorAction::AddColorAnim(_)
+        | EditorAction::ReplColorAnim(_)
+        | EditorAction::RemColorAnim(_)
+        | EditorAction::AddSoundAnim(_)
+        | EditorAction::ReplSoundAnim(_)
+        | EditorAction::RemSoundAnim(_)
+        | EditorAction::SetCommands(_)
+        | EditorAction::SetConfigVariables(_)
+        | EditorAction::SetMetadata(_) => Vec::new(),
+    }
+}
+
+/// Finds the first lock of a different user that the given actions would modify.
+pub fn find_conflict<'a>(
+    locks: &'a [EditorLock],
+    owner: u64,
+    actions: &[EditorAction],
+) -> Option<&'a EditorLock> {
+    let targets: Vec<_> = actions.iter().flat_map(lock_targets).collect();
+    locks
+        .iter()
+        .filter(|lock| lock.owner != owner)
+        .find(|lock| targets.iter().any(|target| target.affects(lock)))
+}
+
+/// The authoritative list of locks, managed by the server.
+#[derive(Debug, Default)]
+pub struct EditorLocks {
+    locks: Vec<EditorLock>,
+    /// Last time an owner edited something or requested a lock
+    last_activity: HashMap<u64, Duration>,
+
+    lock_ids: u64,
+}
+
+impl EditorLocks {
+    pub fn locks(&self) -> &[EditorLock] {
+        &self.locks
+    }
+
+    /// Marks the owner as active, which delays the expiry of its locks.
+    pub fn touch(&mut self, owner: u64, now: Duration) {
+        self.last_activity.insert(owner, now);
+    }
+
+    /// Tries to lock the layer or the tile area of the layer.
+    ///
+    /// Fails if the lock overlaps a lock of a different user.
+    pub fn try_lock(
+        &mut self,
+        owner: u64,
+        owner_name: &str,
+        layer: EditorLockLayer,
+        rect: Option<EditorLockRect>,
+        now: Duration,
+    ) -> anyhow::Result<u64> {
+        if rect.is_some_and(|rect| rect.w == 0 || rect.h == 0) {
+            anyhow::bail!("The locked area must not be empty.");
+        }
+        if let Some(lock) = self
+            .locks
+            .iter()
+            .find(|lock| lock.owner != owner && lock.overlaps(&layer, rect.as_ref()))
+        {
+            anyhow::bail!("This area is already locked by {}.", lock.owner_name);
+        }
+
+        self.touch(owner, now);
+        let id = self.lock_ids;
+        self.lock_ids += 1;
+        self.locks.push(EditorLock {
+            id,
+            owner,
+            owner_name: owner_name.to_string(),
+            layer,
+            rect,
+        });
+        Ok(id)
+    }
+
+    /// Releases a lock of the owner, returns `true` if the lock existed.
+    pub fn unlock(&mut self, owner: u64, id: u64) -> bool {
+        let len = self.locks.len();
+        self.locks
+            .retain(|lock| lock.owner != owner || lock.id != id);
+        len != self.locks.len()
+    }
+
+    /// Releases all locks of the owner, returns `true` if any lock was released.
+    pub fn unlock_all(&mut self, owner: u64) -> bool {
+        self.last_activity.remove(&owner);
+        let len = self.locks.len();
+        self.locks.retain(|lock| lock.owner != owner);
+        len != self.locks.len()
+    }
+
+    /// Releases all locks of owners that were inactive for longer than `timeout`.
+    /// Returns `true` if any lock was released.
+    pub fn expire(&mut self, now: Duration, timeout: Duration) -> bool {
+        let last_activity = &self.last_activity;
+        let len = self.locks.len();
+        self.locks.retain(|lock| {
+            last_activity
+                .get(&lock.owner)
+                .is_some_and(|last| now.saturating_sub(*last) <= timeout)
+        });
+        let owners: Vec<_> = self.locks.iter().map(|lock| lock.owner).collect();
+        self.last_activity.retain(|owner, _| owners.contains(owner));
+        len != self.locks.len()
+    }
+
+    /// See [`find_conflict`].
+    pub fn conflict(&self, owner: u64, actions: &[EditorAction]) -> Option<&EditorLock> {
+        find_conflict(&self.locks, owner, actions)
+    }
+
+    /// Releases the locks whose layer indices are invalidated by the actions,
+    /// e.g. because a layer was moved or removed.
+    /// Returns `true` if any lock was released.
+    pub fn release_shifted(&mut self, actions: &[EditorAction]) -> bool {
+        let targets: Vec<_> = actions
+            .iter()
+            .filter(|act| {
+                matches!(
+                    act,
+                    EditorAction::MoveGroup(_)
+                        | EditorAction::MoveLayer(_)
+                        | EditorAction::AddTileLayer(_)
+                        | EditorAction::AddQuadLayer(_)
+                        | EditorAction::AddSoundLayer(_)
+                        | EditorAction::RemTileLayer(_)
+                        | EditorAction::RemQuadLayer(_)
+                        | EditorAction::RemSoundLayer(_)
+                        | EditorAction::AddPhysicsTileLayer(_)
+                        | EditorAction::RemPhysicsTileLayer(_)
+                        | EditorAction::AddGroup(_)
+                        | EditorAction::RemGroup(_)
+                )
+            })
+            .flat_map(lock_targets)
+            .collect();
+        let len = self.locks.len();
+        self.locks
+            .retain(|lock| !targets.iter().any(|target| target.affects(lock)));
+        len != self.locks.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::event::EditorEventLayerIndex;
+
+    use super::{EditorLockLayer, EditorLockRect, EditorLocks};
+
+    fn layer(layer_index: usize) -> EditorLockLayer {
+        EditorLockLayer::Design(EditorEventLayerIndex {
+            is_background: true,
+            group_index: 0,
+            layer_index,
+        })
+    }
+
+    fn rect(x: u16, y: u16, w: u16, h: u16) -> Option<EditorLockRect> {
+        Some(EditorLockRect { x, y, w, h })
+    }
+
+    #[test]
+    fn lock_conflicts() {
+        let now = Duration::ZERO;
+        let mut locks = EditorLocks::default();
+
+        let id = locks.try_lock(0, "a", layer(0), rect(0, 0, 10, 10), now);
+        assert!(id.is_ok());
+
+        // overlapping area of a different user
+        assert!(
+            locks
+                .try_lock(1, "b", layer(0), rect(5, 5, 10, 10), now)
+                .is_err()
+        );
+        // whole layer of a different user
+        assert!(locks.try_lock(1, "b", layer(0), None, now).is_err());
+        // touching, but not overlapping
+        assert!(
+            locks
+                .try_lock(1, "b", layer(0), rect(10, 0, 5, 5), now)
+                .is_ok()
+        );
+        // different layer
+        assert!(locks.try_lock(1, "b", layer(1), None, now).is_ok());
+        assert!(
+            locks
+                .try_lock(
+                    1,
+                    "b",
+                    EditorLockLayer::Physics { layer_index: 0 },
+                    None,
+                    now
+                )
+                .is_ok()
+        );
+        // the same user can lock overlapping areas
+        assert!(
+            locks
+                .try_lock(0, "a", layer(0), rect(2, 2, 2, 2), now)
+                .is_ok()
+        );
+        // empty areas are invalid
+        assert!(
+            locks
+                .try_lock(0, "a", layer(2), rect(2, 2, 0, 2), now)
+                .is_err()
+        );
+
+        // other users can't remove the lock
+        let id = id.unwrap();
+        assert!(!locks.unlock(1, id));
+        assert!(locks.unlock(0, id));
+        assert!(locks.unlock_all(0));
+        assert!(locks.try_lock(1, "b", layer(0), None, now).is_ok());
+        assert!(
+            locks
+                .try_lock(0, "a", layer(0), rect(0, 0, 1, 1), now)
+                .is_err()
+        );
+        assert!(locks.unlock_all(1));
+        assert!(locks.try_lock(0, "a", layer(0), None, now).is_ok());
+    }
+
+    #[test]
+    fn lock_expiry() {
+        let timeout = Duration::from_secs(60);
+        let mut locks = EditorLocks::default();
+
+        locks
+            .try_lock(0, "a", layer(0), None, Duration::ZERO)
+            .unwrap();
+        locks
+            .try_lock(1, "b", layer(1), None, Duration::from_secs(30))
+            .unwrap();
+
+        assert!(!locks.expire(Duration::from_secs(60), timeout));
+        assert_eq!(locks.locks().len(), 2);
+
+        // activity of the first owner keeps its lock alive
+        locks.touch(0, Duration::from_secs(70));
+        assert!(locks.expire(Duration::from_secs(100), timeout));
+        assert_eq!(locks.locks().len(), 1);
+        assert_eq!(locks.locks()[0].owner, 0);
+
+        assert!(locks.expire(Duration::from_secs(200), timeout));
+        assert!(locks.locks().is_empty());
+
+        // the expired area can be locked again
+        assert!(
+            locks
+                .try_lock(1, "b", layer(0), None, Duration::from_secs(200))
+                .is_ok()
+        );
+    }
+}
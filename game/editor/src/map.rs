@@ -1,6 +1,6 @@
 use std::{
     borrow::{Borrow, BorrowMut},
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt::Debug,
     path::PathBuf,
     rc::Rc,
@@ -16,10 +16,14 @@ use client_render_base::map::{
 };
 use egui_file_dialog::FileDialog;
 use egui_timeline::timeline::Timeline;
-use graphics::handles::texture::texture::{TextureContainer, TextureContainer2dArray};
+use graphics::{
+    graphics::graphics::PixelReadback,
+    handles::texture::texture::{TextureContainer, TextureContainer2dArray},
+};
 use hiarc::Hiarc;
 use map::{
     map::{
+        Map,
         animations::{
             AnimPointColor, AnimPointPos, AnimPointSound, ColorAnimation, PosAnimation,
             SoundAnimation,
@@ -58,7 +62,17 @@ use map::{
 use math::math::vector::{ffixed, fvec2, vec2};
 use sound::{scene_object::SceneObject, sound_listener::SoundListener, sound_object::SoundObject};
 
-use crate::event::EditorEventLayerIndex;
+use crate::{
+    color_palette::EditorColorPalette,
+    event::EditorEventLayerIndex,
+    group_templates::EditorGroupTemplate,
+    guides::EditorGuides,
+    layer_filter::EditorLayerFilter,
+    map_diff::{MapDiff, MapDiffLayerIndex},
+    parallax_preview::EditorParallaxPreview,
+    physics_numbers::NumberUsage,
+    tile_replace::TileOccurrence,
+};
 
 pub trait EditorCommonLayerOrGroupAttrInterface {
     fn editor_attr(&self) -> &EditorCommonGroupOrLayerAttr;
@@ -291,6 +305,17 @@ pub struct EditorGroupsProps {
     pub parallax_aware_zoom: bool,
 }
 
+impl EditorGroupsProps {
+    pub fn camera(&self) -> Camera {
+        Camera {
+            pos: self.pos,
+            zoom: self.zoom,
+            parallax_aware_zoom: self.parallax_aware_zoom,
+            forced_aspect_ratio: None,
+        }
+    }
+}
+
 #[derive(Debug, Hiarc, Clone)]
 pub struct EditorResource<U, P> {
     pub file: Rc<Vec<u8>>,
@@ -690,25 +715,145 @@ pub struct EditorChatState {
     pub msg: String,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct EditorNumberUsageState {
+    /// A tele, switch or tune layer of the physics group.
+    pub layer_index: Option<usize>,
+    pub remap_from: u8,
+    pub remap_to: u8,
+    /// A renumbering that would merge numbers and waits for the user's decision.
+    pub pending_merge: Option<BTreeMap<u8, u8>>,
+    /// The usage is only recalculated once in a while.
+    pub usage: Option<(Duration, usize, BTreeMap<u8, NumberUsage>)>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EditorTileReplaceState {
+    /// The searched tile indices as typed, e.g. `1, 5, 10-12`.
+    pub find: String,
+    pub replace_with: u8,
+    /// Keep the rotation & mirroring of the replaced tiles.
+    pub keep_flags: bool,
+    /// The found tiles by the name of their layer.
+    pub results: Vec<(String, Vec<TileOccurrence>)>,
+    /// The replaced & the skipped incompatible tiles of the last replace.
+    pub last_replace: Option<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EditorPhysicsFromDesignState {
+    /// The design tile layer the game layer is generated from.
+    pub layer: Option<EditorEventLayerIndex>,
+    /// Design tile indices that become unhookable tiles, e.g. `1, 5, 10-12`.
+    pub unhookable: String,
+    /// Design tile indices that become death tiles.
+    pub death: String,
+    pub offset_x: i32,
+    pub offset_y: i32,
+    pub clear_empty: bool,
+    /// The changed tiles of the last generation.
+    pub last_generate: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EditorMapDiffState {
+    /// The map file the current map is compared with.
+    pub path: PathBuf,
+    pub file: Rc<Map>,
+    /// The diff is only recalculated once in a while.
+    pub diff: Option<(Duration, Rc<MapDiff>)>,
+    /// Layers whose differences are not highlighted.
+    pub hidden_layers: Vec<MapDiffLayerIndex>,
+}
+
+#[derive(Default)]
+pub struct EditorGroupTemplatesState {
+    /// `None` until the templates were loaded, reset to reload them.
+    pub templates: Option<Rc<Vec<EditorGroupTemplate>>>,
+    pub loading_task: Option<IoRuntimeTask<Vec<EditorGroupTemplate>>>,
+    /// The design group that should be saved as template.
+    pub save_group: Option<(bool, usize)>,
+    /// The name the group is saved as.
+    pub name: String,
+    pub insert_as_background: bool,
+}
+
+impl Debug for EditorGroupTemplatesState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EditorGroupTemplatesState").finish()
+    }
+}
+
+impl Clone for EditorGroupTemplatesState {
+    fn clone(&self) -> Self {
+        Self {
+            templates: self.templates.clone(),
+            loading_task: None,
+            save_group: self.save_group,
+            name: self.name.clone(),
+            insert_as_background: self.insert_as_background,
+        }
+    }
+}
+
+/// A color field waits for a click into the map,
+/// that picks the color below the cursor.
+#[derive(Debug, Clone)]
+pub struct EditorEyedropper {
+    /// The color field that gets the picked color.
+    pub field: egui::Id,
+    /// The color of the layer or quad, that the field belongs to.
+    pub tint: Option<[u8; 4]>,
+    /// The pixel below the cursor, read back from the rendered frame.
+    pub readback: Option<Rc<PixelReadback>>,
+    /// Taken by the color field.
+    pub picked: Option<[u8; 4]>,
+}
+
 #[derive(Debug, Clone)]
 pub struct EditorMapPropsUiValues {
     pub group_panel_active_tab: EditorGroupPanelTab,
+    pub layer_filter: EditorLayerFilter,
     pub animations_panel_open: bool,
     pub server_commands_open: bool,
     pub server_config_variables_open: bool,
     pub chat_panel_open: Option<EditorChatState>,
+    pub history_panel_open: bool,
+    pub number_usage_panel_open: Option<EditorNumberUsageState>,
+    pub map_diff_panel_open: Option<EditorMapDiffState>,
+    pub tile_replace_open: Option<EditorTileReplaceState>,
+    pub physics_from_design_open: Option<EditorPhysicsFromDesignState>,
+    pub group_templates_open: Option<EditorGroupTemplatesState>,
+    pub parallax_preview: Option<EditorParallaxPreview>,
     pub timeline: Timeline,
+    /// The index of the guide that is currently dragged.
+    pub guide_drag: Option<usize>,
+    pub eyedropper: Option<EditorEyedropper>,
+    /// Whether the eyedropper removes the color of the layer
+    /// or quad from the picked color.
+    pub eyedropper_untint: bool,
 }
 
 impl Default for EditorMapPropsUiValues {
     fn default() -> Self {
         Self {
             group_panel_active_tab: EditorGroupPanelTab::GroupsAndLayers,
+            layer_filter: Default::default(),
             animations_panel_open: false,
             server_commands_open: false,
             server_config_variables_open: false,
             chat_panel_open: None,
+            history_panel_open: false,
+            number_usage_panel_open: None,
+            map_diff_panel_open: None,
+            tile_replace_open: None,
+            physics_from_design_open: None,
+            group_templates_open: None,
+            parallax_preview: None,
             timeline: Timeline::default(),
+            guide_drag: None,
+            eyedropper: None,
+            eyedropper_untint: false,
         }
     }
 }
@@ -722,6 +867,31 @@ pub struct EditorGlobalOptions {
     pub show_tile_numbers: bool,
     /// Whether to render a grid for aligning quads & sounds.
     pub render_grid: Option<f64>,
+    /// Guide lines for aligning quads & sounds.
+    pub guides: EditorGuides,
+    /// Recent & named colors of the color fields.
+    pub palette: EditorColorPalette,
+    /// Whether to show the previous & next keyframes
+    /// of the selected animated quads.
+    pub onion_skin: Option<EditorOnionSkin>,
+}
+
+/// How the keyframes around the current time of the animation panel are shown.
+#[derive(Debug, Clone, Copy)]
+pub struct EditorOnionSkin {
+    /// The number of keyframes shown before & after the current time.
+    pub count: usize,
+    /// The opacity of the nearest keyframes, farther ones fade out.
+    pub opacity: f32,
+}
+
+impl Default for EditorOnionSkin {
+    fn default() -> Self {
+        Self {
+            count: 1,
+            opacity: 0.5,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -1171,12 +1341,7 @@ impl EditorMapInterface for EditorMap {
     }
 
     fn game_camera(&self) -> Camera {
-        Camera {
-            pos: self.groups.user.pos,
-            zoom: self.groups.user.zoom,
-            parallax_aware_zoom: self.groups.user.parallax_aware_zoom,
-            forced_aspect_ratio: None,
-        }
+        self.groups.user.camera()
     }
 
     fn active_animations(&self) -> &EditorAnimations {
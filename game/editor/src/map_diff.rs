@@ -0,0 +1,823 @@
+use map::map::{
+    Map,
+    animations::Animations,
+    groups::{
+        MapGroup, MapGroupPhysics,
+        layers::{
+            design::{MapLayer, Quad, Sound},
+            physics::MapLayerPhysics,
+            tiles::{MapTileLayerPhysicsTiles, TileBase},
+        },
+    },
+    resources::Resources,
+};
+
+use crate::{
+    actions::actions::{
+        ActQuadLayerAddQuads, ActQuadLayerAddRemQuads, ActQuadLayerRemQuads,
+        ActSoundLayerAddRemSounds, ActSoundLayerAddSounds, ActSoundLayerRemSounds,
+        ActTileLayerReplTilesBase, ActTileLayerReplaceTiles, ActTilePhysicsLayerReplTilesBase,
+        ActTilePhysicsLayerReplaceTiles, EditorAction,
+    },
+    event::EditorEventLayerIndex,
+};
+
+/// How the current map differs from the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapDiffChange {
+    /// Only exists in the current map.
+    Added,
+    /// Only exists in the file.
+    Removed,
+    Changed,
+}
+
+/// A layer by its position, for physics layers the index of the map
+/// that contains it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapDiffLayerIndex {
+    Design(EditorEventLayerIndex),
+    Physics { layer_index: usize },
+}
+
+/// How many entries of a list differ, entries are compared by their index.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MapDiffCounts {
+    pub added: usize,
+    pub removed: usize,
+    pub modified: usize,
+}
+
+impl MapDiffCounts {
+    pub fn new<T: PartialEq>(cur: &[T], file: &[T]) -> Self {
+        Self {
+            added: cur.len().saturating_sub(file.len()),
+            removed: file.len().saturating_sub(cur.len()),
+            modified: cur.iter().zip(file.iter()).filter(|(a, b)| a != b).count(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added == 0 && self.removed == 0 && self.modified == 0
+    }
+
+    fn add(&mut self, other: Self) {
+        self.added += other.added;
+        self.removed += other.removed;
+        self.modified += other.modified;
+    }
+}
+
+/// A layer that differs between the current map and the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MapDiffLayer {
+    /// The position in the current map,
+    /// or in the file if the layer was removed.
+    pub index: MapDiffLayerIndex,
+    /// The position in the file, if the layer exists there.
+    pub file_index: Option<MapDiffLayerIndex>,
+    pub name: String,
+    pub change: MapDiffChange,
+    /// The tiles that differ in tile coordinates, for tile layers
+    /// that exist in both maps.
+    pub tiles: Vec<(u16, u16, MapDiffChange)>,
+    /// The width or height of the tile layer differs.
+    pub resized: bool,
+    /// The quads or sounds that differ.
+    pub entries: MapDiffCounts,
+    /// Other properties of the layer differ, e.g. the color,
+    /// the image or the tele names.
+    pub attr_changed: bool,
+}
+
+impl MapDiffLayer {
+    pub fn tile_count(&self, change: MapDiffChange) -> usize {
+        self.tiles.iter().filter(|(_, _, c)| *c == change).count()
+    }
+
+    /// Whether the content of the layer can be reverted to the file's version.
+    pub fn can_revert(&self) -> bool {
+        self.change == MapDiffChange::Changed
+            && !self.resized
+            && (!self.tiles.is_empty() || !self.entries.is_empty())
+    }
+}
+
+/// The differences between the current map & a map file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MapDiff {
+    pub layers: Vec<MapDiffLayer>,
+    /// Groups that differ in their name or attributes,
+    /// the physics group counts as group, too.
+    pub groups: MapDiffCounts,
+    pub images: MapDiffCounts,
+    pub image_arrays: MapDiffCounts,
+    pub sounds: MapDiffCounts,
+    pub animations: MapDiffCounts,
+}
+
+impl MapDiff {
+    pub fn new(cur: &Map, file: &Map) -> Self {
+        let mut res = Self::default();
+        res.resources(&cur.resources, &file.resources);
+        res.animations(&cur.animations, &file.animations);
+        res.physics(&cur.groups.physics, &file.groups.physics);
+        for is_background in [true, false] {
+            let (cur_groups, file_groups) = if is_background {
+                (&cur.groups.background, &file.groups.background)
+            } else {
+                (&cur.groups.foreground, &file.groups.foreground)
+            };
+            res.design_groups(is_background, cur_groups, file_groups);
+        }
+        res
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+            && self.groups.is_empty()
+            && self.images.is_empty()
+            && self.image_arrays.is_empty()
+            && self.sounds.is_empty()
+            && self.animations.is_empty()
+    }
+
+    fn resources(&mut self, cur: &Resources, file: &Resources) {
+        self.images = MapDiffCounts::new(&cur.images, &file.images);
+        self.image_arrays = MapDiffCounts::new(&cur.image_arrays, &file.image_arrays);
+        self.sounds = MapDiffCounts::new(&cur.sounds, &file.sounds);
+    }
+
+    fn animations(&mut self, cur: &Animations, file: &Animations) {
+        self.animations = MapDiffCounts::new(&cur.pos, &file.pos);
+        self.animations
+            .add(MapDiffCounts::new(&cur.color, &file.color));
+        self.animations
+            .add(MapDiffCounts::new(&cur.sound, &file.sound));
+    }
+
+    /// Compares the tiles of the area of both layers,
+    /// tiles outside of a layer count as air.
+    fn tile_changes<T: AsRef<TileBase> + PartialEq>(
+        cur: &[T],
+        (cur_w, cur_h): (u16, u16),
+        file: &[T],
+        (file_w, file_h): (u16, u16),
+    ) -> Vec<(u16, u16, MapDiffChange)> {
+        let tile = |tiles: &'_ [T], w: u16, h: u16, x: u16, y: u16| {
+            if x < w && y < h {
+                tiles.get(y as usize * w as usize + x as usize)
+            } else {
+                None
+            }
+            .filter(|tile| tile.as_ref().index != 0)
+        };
+        let mut res = Vec::new();
+        for y in 0..cur_h.max(file_h) {
+            for x in 0..cur_w.max(file_w) {
+                let change = match (
+                    tile(cur, cur_w, cur_h, x, y),
+                    tile(file, file_w, file_h, x, y),
+                ) {
+                    (None, None) => None,
+                    (Some(_), None) => Some(MapDiffChange::Added),
+                    (None, Some(_)) => Some(MapDiffChange::Removed),
+                    (Some(a), Some(b)) => (a != b).then_some(MapDiffChange::Changed),
+                };
+                if let Some(change) = change {
+                    res.push((x, y, change));
+                }
+            }
+        }
+        res
+    }
+
+    fn physics_layer_name(layer: &MapLayerPhysics) -> &'static str {
+        match layer {
+            MapLayerPhysics::Arbitrary(_) => "Arbitrary",
+            MapLayerPhysics::Game(_) => "Game",
+            MapLayerPhysics::Front(_) => "Front",
+            MapLayerPhysics::Tele(_) => "Tele",
+            MapLayerPhysics::Speedup(_) => "Speedup",
+            MapLayerPhysics::Switch(_) => "Switch",
+            MapLayerPhysics::Tune(_) => "Tune",
+        }
+    }
+
+    /// Physics layers are matched by their type, since every type exists once.
+    fn physics(&mut self, cur: &MapGroupPhysics, file: &MapGroupPhysics) {
+        if cur.attr != file.attr {
+            self.groups.modified += 1;
+        }
+        let cur_size = (cur.attr.width.get(), cur.attr.height.get());
+        let file_size = (file.attr.width.get(), file.attr.height.get());
+        for (layer_index, layer) in cur.layers.iter().enumerate() {
+            let index = MapDiffLayerIndex::Physics { layer_index };
+            let file_layer = file.layers.iter().enumerate().find(|(_, file_layer)| {
+                std::mem::discriminant(*file_layer) == std::mem::discriminant(layer)
+            });
+            let mut diff = MapDiffLayer {
+                index,
+                file_index: file_layer
+                    .map(|(layer_index, _)| MapDiffLayerIndex::Physics { layer_index }),
+                name: Self::physics_layer_name(layer).to_string(),
+                change: MapDiffChange::Changed,
+                tiles: Vec::new(),
+                resized: cur_size != file_size,
+                entries: Default::default(),
+                attr_changed: false,
+            };
+            let Some((_, file_layer)) = file_layer else {
+                diff.change = MapDiffChange::Added;
+                diff.resized = false;
+                self.layers.push(diff);
+                continue;
+            };
+            (diff.tiles, diff.attr_changed) = match (layer, file_layer) {
+                (MapLayerPhysics::Game(a), MapLayerPhysics::Game(b))
+                | (MapLayerPhysics::Front(a), MapLayerPhysics::Front(b)) => (
+                    Self::tile_changes(&a.tiles, cur_size, &b.tiles, file_size),
+                    false,
+                ),
+                (MapLayerPhysics::Tele(a), MapLayerPhysics::Tele(b)) => (
+                    Self::tile_changes(&a.base.tiles, cur_size, &b.base.tiles, file_size),
+                    a.tele_names != b.tele_names,
+                ),
+                (MapLayerPhysics::Speedup(a), MapLayerPhysics::Speedup(b)) => (
+                    Self::tile_changes(&a.tiles, cur_size, &b.tiles, file_size),
+                    false,
+                ),
+                (MapLayerPhysics::Switch(a), MapLayerPhysics::Switch(b)) => (
+                    Self::tile_changes(&a.base.tiles, cur_size, &b.base.tiles, file_size),
+                    a.switch_names != b.switch_names,
+                ),
+                (MapLayerPhysics::Tune(a), MapLayerPhysics::Tune(b)) => (
+                    Self::tile_changes(&a.base.tiles, cur_size, &b.base.tiles, file_size),
+                    a.tune_zones != b.tune_zones,
+                ),
+                (a, b) => (Vec::new(), a != b),
+            };
+            if !diff.tiles.is_empty() || diff.attr_changed || diff.resized {
+                self.layers.push(diff);
+            }
+        }
+        for (layer_index, file_layer) in file.layers.iter().enumerate() {
+            if !cur
+                .layers
+                .iter()
+                .any(|layer| std::mem::discriminant(layer) == std::mem::discriminant(file_layer))
+            {
+                let index = MapDiffLayerIndex::Physics { layer_index };
+                self.layers.push(MapDiffLayer {
+                    index,
+                    file_index: Some(index),
+                    name: Self::physics_layer_name(file_layer).to_string(),
+                    change: MapDiffChange::Removed,
+                    tiles: Vec::new(),
+                    resized: false,
+                    entries: Default::default(),
+                    attr_changed: false,
+                });
+            }
+        }
+    }
+
+    fn design_layer_name(group: &MapGroup, layer_index: usize, layer: &MapLayer) -> String {
+        let ty = match layer {
+            MapLayer::Abritrary(_) => "Arbitrary",
+            MapLayer::Tile(_) => "Tile",
+            MapLayer::Quad(_) => "Quad",
+            MapLayer::Sound(_) => "Sound",
+        };
+        let name = match layer.name() {
+            "" => ty.to_string(),
+            name => format!("{ty} \"{name}\""),
+        };
+        format!("{} #{layer_index} {name}", group.name)
+    }
+
+    /// The layers of groups at the same position are compared by their index.
+    fn design_groups(&mut self, is_background: bool, cur: &[MapGroup], file: &[MapGroup]) {
+        self.groups.add(MapDiffCounts {
+            added: cur.len().saturating_sub(file.len()),
+            removed: file.len().saturating_sub(cur.len()),
+            modified: cur
+                .iter()
+                .zip(file.iter())
+                .filter(|(a, b)| a.attr != b.attr || a.name != b.name)
+                .count(),
+        });
+
+        let layer_index = |group_index, layer_index| {
+            MapDiffLayerIndex::Design(EditorEventLayerIndex {
+                is_background,
+                group_index,
+                layer_index,
+            })
+        };
+        let groups = cur.len().max(file.len());
+        for group_index in 0..groups {
+            let cur_group = cur.get(group_index);
+            let file_group = file.get(group_index);
+            let layers = cur_group
+                .map(|g| g.layers.len())
+                .max(file_group.map(|g| g.layers.len()))
+                .unwrap_or_default();
+            for i in 0..layers {
+                let index = layer_index(group_index, i);
+                let cur_layer = cur_group.and_then(|g| g.layers.get(i).map(|l| (g, l)));
+                let file_layer = file_group.and_then(|g| g.layers.get(i).map(|l| (g, l)));
+                let same_ty = cur_layer.zip(file_layer).is_some_and(|((_, a), (_, b))| {
+                    std::mem::discriminant(a) == std::mem::discriminant(b)
+                });
+                let diff = |group, layer, change, file_index| MapDiffLayer {
+                    index,
+                    file_index,
+                    name: Self::design_layer_name(group, i, layer),
+                    change,
+                    tiles: Vec::new(),
+                    resized: false,
+                    entries: Default::default(),
+                    attr_changed: false,
+                };
+
+                if !same_ty {
+                    if let Some((group, layer)) = cur_layer {
+                        self.layers
+                            .push(diff(group, layer, MapDiffChange::Added, None));
+                    }
+                    if let Some((group, layer)) = file_layer {
+                        self.layers
+                            .push(diff(group, layer, MapDiffChange::Removed, Some(index)));
+                    }
+                    continue;
+                }
+                let (Some((group, layer)), Some((_, file_layer))) = (cur_layer, file_layer) else {
+                    continue;
+                };
+                let mut layer_diff = diff(group, layer, MapDiffChange::Changed, Some(index));
+                match (layer, file_layer) {
+                    (MapLayer::Tile(a), MapLayer::Tile(b)) => {
+                        let cur_size = (a.attr.width.get(), a.attr.height.get());
+                        let file_size = (b.attr.width.get(), b.attr.height.get());
+                        layer_diff.tiles =
+                            Self::tile_changes(&a.tiles, cur_size, &b.tiles, file_size);
+                        layer_diff.resized = cur_size != file_size;
+                        layer_diff.attr_changed = a.attr != b.attr || a.name != b.name;
+                    }
+                    (MapLayer::Quad(a), MapLayer::Quad(b)) => {
+                        layer_diff.entries = MapDiffCounts::new(&a.quads, &b.quads);
+                        layer_diff.attr_changed = a.attr != b.attr || a.name != b.name;
+                    }
+                    (MapLayer::Sound(a), MapLayer::Sound(b)) => {
+                        layer_diff.entries = MapDiffCounts::new(&a.sounds, &b.sounds);
+                        layer_diff.attr_changed = a.attr != b.attr || a.name != b.name;
+                    }
+                    (a, b) => {
+                        layer_diff.attr_changed = a != b;
+                    }
+                }
+                if !layer_diff.tiles.is_empty()
+                    || !layer_diff.entries.is_empty()
+                    || layer_diff.resized
+                    || layer_diff.attr_changed
+                {
+                    self.layers.push(layer_diff);
+                }
+            }
+        }
+    }
+}
+
+/// Animations the current map does not have are removed from the
+/// reverted quads & sounds.
+fn clamp_anim(anim: &mut Option<usize>, count: usize) {
+    if anim.is_some_and(|anim| anim >= count) {
+        *anim = None;
+    }
+}
+
+/// The actions that replace the tiles, quads or sounds of the layer
+/// with the ones of the file.
+///
+/// All actions should be executed as a single group,
+/// so they can be undone at once.
+/// The attributes of the layer are kept.
+pub fn revert_layer_actions(cur: &Map, file: &Map, layer: &MapDiffLayer) -> Vec<EditorAction> {
+    if !layer.can_revert() {
+        return Vec::new();
+    }
+    match (layer.index, layer.file_index) {
+        (
+            MapDiffLayerIndex::Physics { layer_index },
+            Some(MapDiffLayerIndex::Physics {
+                layer_index: file_layer_index,
+            }),
+        ) => {
+            let (Some(cur_layer), Some(file_layer)) = (
+                cur.groups.physics.layers.get(layer_index),
+                file.groups.physics.layers.get(file_layer_index),
+            ) else {
+                return Vec::new();
+            };
+            let tiles = |layer: &MapLayerPhysics| match layer {
+                MapLayerPhysics::Arbitrary(_) => None,
+                MapLayerPhysics::Game(layer) => {
+                    Some(MapTileLayerPhysicsTiles::Game(layer.tiles.clone()))
+                }
+                MapLayerPhysics::Front(layer) => {
+                    Some(MapTileLayerPhysicsTiles::Front(layer.tiles.clone()))
+                }
+                MapLayerPhysics::Tele(layer) => {
+                    Some(MapTileLayerPhysicsTiles::Tele(layer.base.tiles.clone()))
+                }
+                MapLayerPhysics::Speedup(layer) => {
+                    Some(MapTileLayerPhysicsTiles::Speedup(layer.tiles.clone()))
+                }
+                MapLayerPhysics::Switch(layer) => {
+                    Some(MapTileLayerPhysicsTiles::Switch(layer.base.tiles.clone()))
+                }
+                MapLayerPhysics::Tune(layer) => {
+                    Some(MapTileLayerPhysicsTiles::Tune(layer.base.tiles.clone()))
+                }
+            };
+            let (Some(old_tiles), Some(new_tiles)) = (tiles(cur_layer), tiles(file_layer)) else {
+                return Vec::new();
+            };
+            vec![EditorAction::TilePhysicsLayerReplaceTiles(
+                ActTilePhysicsLayerReplaceTiles {
+                    base: ActTilePhysicsLayerReplTilesBase {
+                        layer_index,
+                        old_tiles,
+                        new_tiles,
+                        x: 0,
+                        y: 0,
+                        w: cur.groups.physics.attr.width,
+                        h: cur.groups.physics.attr.height,
+                    },
+                },
+            )]
+        }
+        (MapDiffLayerIndex::Design(index), Some(MapDiffLayerIndex::Design(file_index))) => {
+            let layer = |map: &Map, index: EditorEventLayerIndex| {
+                if index.is_background {
+                    &map.groups.background
+                } else {
+                    &map.groups.foreground
+                }
+                .get(index.group_index)
+                .and_then(|group| group.layers.get(index.layer_index))
+                .cloned()
+            };
+            let (Some(cur_layer), Some(file_layer)) = (layer(cur, index), layer(file, file_index))
+            else {
+                return Vec::new();
+            };
+            let anims = &cur.animations;
+            match (cur_layer, file_layer) {
+                (MapLayer::Tile(cur_layer), MapLayer::Tile(file_layer)) => {
+                    vec![EditorAction::TileLayerReplaceTiles(
+                        ActTileLayerReplaceTiles {
+                            base: ActTileLayerReplTilesBase {
+                                is_background: index.is_background,
+                                group_index: index.group_index,
+                                layer_index: index.layer_index,
+                                old_tiles: cur_layer.tiles,
+                                new_tiles: file_layer.tiles,
+                                x: 0,
+                                y: 0,
+                                w: cur_layer.attr.width,
+                                h: cur_layer.attr.height,
+                            },
+                        },
+                    )]
+                }
+                (MapLayer::Quad(cur_layer), MapLayer::Quad(file_layer)) => {
+                    let quads: Vec<Quad> = file_layer
+                        .quads
+                        .into_iter()
+                        .map(|mut quad| {
+                            clamp_anim(&mut quad.pos_anim, anims.pos.len());
+                            clamp_anim(&mut quad.color_anim, anims.color.len());
+                            quad
+                        })
+                        .collect();
+                    let base = |quads| ActQuadLayerAddRemQuads {
+                        is_background: index.is_background,
+                        group_index: index.group_index,
+                        layer_index: index.layer_index,
+                        index: 0,
+                        quads,
+                    };
+                    (!cur_layer.quads.is_empty())
+                        .then(|| {
+                            EditorAction::QuadLayerRemQuads(ActQuadLayerRemQuads {
+                                base: base(cur_layer.quads),
+                            })
+                        })
+                        .into_iter()
+                        .chain((!quads.is_empty()).then(|| {
+                            EditorAction::QuadLayerAddQuads(ActQuadLayerAddQuads {
+                                base: base(quads),
+                            })
+                        }))
+                        .collect()
+                }
+                (MapLayer::Sound(cur_layer), MapLayer::Sound(file_layer)) => {
+                    let sounds: Vec<Sound> = file_layer
+                        .sounds
+                        .into_iter()
+                        .map(|mut sound| {
+                            clamp_anim(&mut sound.pos_anim, anims.pos.len());
+                            clamp_anim(&mut sound.sound_anim, anims.sound.len());
+                            sound
+                        })
+                        .collect();
+                    let base = |sounds| ActSoundLayerAddRemSounds {
+                        is_background: index.is_background,
+                        group_index: index.group_index,
+                        layer_index: index.layer_index,
+                        index: 0,
+                        sounds,
+                    };
+                    (!cur_layer.sounds.is_empty())
+                        .then(|| {
+                            EditorAction::SoundLayerRemSounds(ActSoundLayerRemSounds {
+                                base: base(cur_layer.sounds),
+                            })
+                        })
+                        .into_iter()
+                        .chain((!sounds.is_empty()).then(|| {
+                            EditorAction::SoundLayerAddSounds(ActSoundLayerAddSounds {
+                                base: base(sounds),
+                            })
+                        }))
+                        .collect()
+                }
+                _ => Vec::new(),
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use map::{
+        map::{
+            Map,
+            animations::Animations,
+            config::Config,
+            groups::{
+                MapGroup, MapGroupAttr, MapGroupPhysics, MapGroupPhysicsAttr, MapGroups,
+                layers::{
+                    design::{MapLayer, MapLayerQuad, MapLayerQuadsAttrs, MapLayerTile, Quad},
+                    physics::{MapLayerPhysics, MapLayerTilePhysicsBase},
+                    tiles::{MapTileLayerAttr, TileBase},
+                },
+            },
+            metadata::Metadata,
+            resources::Resources,
+        },
+        types::NonZeroU16MinusOne,
+    };
+    use math::math::vector::{nffixed, nfvec4};
+
+    use crate::{actions::actions::EditorAction, event::EditorEventLayerIndex};
+
+    use super::{MapDiff, MapDiffChange, MapDiffCounts, MapDiffLayerIndex, revert_layer_actions};
+
+    fn tiles(indices: &[u8]) -> Vec<TileBase> {
+        indices
+            .iter()
+            .map(|&index| TileBase {
+                index,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn tile_layer(w: u16, h: u16, indices: &[u8]) -> MapLayer {
+        MapLayer::Tile(MapLayerTile {
+            attr: MapTileLayerAttr {
+                width: NonZeroU16MinusOne::new(w).unwrap(),
+                height: NonZeroU16MinusOne::new(h).unwrap(),
+                color: nfvec4::new(
+                    nffixed::from_num(1),
+                    nffixed::from_num(1),
+                    nffixed::from_num(1),
+                    nffixed::from_num(1),
+                ),
+                high_detail: false,
+                color_anim: None,
+                color_anim_offset: time::Duration::ZERO,
+                image_array: None,
+                blend_mode: Default::default(),
+            },
+            tiles: tiles(indices),
+            name: Default::default(),
+        })
+    }
+
+    fn quad_layer(quads: usize, pos_anim: Option<usize>) -> MapLayer {
+        MapLayer::Quad(MapLayerQuad {
+            attr: MapLayerQuadsAttrs {
+                image: None,
+                high_detail: false,
+                blend_mode: Default::default(),
+            },
+            quads: vec![
+                Quad {
+                    pos_anim,
+                    ..Default::default()
+                };
+                quads
+            ],
+            name: Default::default(),
+        })
+    }
+
+    fn map(game: &[u8], layers: Vec<MapLayer>) -> Map {
+        Map {
+            resources: Resources {
+                images: Vec::new(),
+                image_arrays: Vec::new(),
+                sounds: Vec::new(),
+            },
+            groups: MapGroups {
+                physics: MapGroupPhysics {
+                    attr: MapGroupPhysicsAttr {
+                        width: NonZeroU16MinusOne::new(2).unwrap(),
+                        height: NonZeroU16MinusOne::new(2).unwrap(),
+                    },
+                    layers: vec![MapLayerPhysics::Game(MapLayerTilePhysicsBase {
+                        tiles: tiles(game),
+                    })],
+                },
+                background: vec![MapGroup {
+                    attr: MapGroupAttr::default(),
+                    layers,
+                    name: "bg".to_string(),
+                }],
+                foreground: Vec::new(),
+            },
+            animations: Animations {
+                pos: Vec::new(),
+                color: Vec::new(),
+                sound: Vec::new(),
+            },
+            config: Config {
+                commands: Default::default(),
+                config_variables: Default::default(),
+            },
+            meta: Metadata {
+                authors: Default::default(),
+                licenses: Default::default(),
+                version: Default::default(),
+                credits: Default::default(),
+                memo: Default::default(),
+                theme_ambiance: Default::default(),
+            },
+        }
+    }
+
+    fn design(layer_index: usize) -> MapDiffLayerIndex {
+        MapDiffLayerIndex::Design(EditorEventLayerIndex {
+            is_background: true,
+            group_index: 0,
+            layer_index,
+        })
+    }
+
+    #[test]
+    fn equal_maps() {
+        let cur = map(&[1, 0, 0, 1], vec![tile_layer(2, 2, &[1, 2, 3, 4])]);
+        assert!(MapDiff::new(&cur, &cur.clone()).is_empty());
+    }
+
+    #[test]
+    fn per_layer_changes() {
+        let cur = map(
+            &[1, 1, 0, 0],
+            vec![
+                tile_layer(2, 2, &[1, 0, 3, 5]),
+                quad_layer(3, None),
+                tile_layer(1, 1, &[1]),
+            ],
+        );
+        let file = map(
+            &[1, 0, 0, 0],
+            vec![
+                tile_layer(2, 2, &[1, 2, 0, 4]),
+                quad_layer(2, None),
+                tile_layer(2, 1, &[1, 1]),
+                quad_layer(1, None),
+            ],
+        );
+        let diff = MapDiff::new(&cur, &file);
+        assert!(diff.groups.is_empty());
+        assert!(diff.images.is_empty());
+        assert_eq!(diff.layers.len(), 5);
+
+        let game = &diff.layers[0];
+        assert_eq!(game.index, MapDiffLayerIndex::Physics { layer_index: 0 });
+        assert_eq!(game.change, MapDiffChange::Changed);
+        assert_eq!(game.tiles, vec![(1, 0, MapDiffChange::Added)]);
+        assert!(game.can_revert());
+
+        let tiles = &diff.layers[1];
+        assert_eq!(tiles.index, design(0));
+        assert_eq!(
+            tiles.tiles,
+            vec![
+                (1, 0, MapDiffChange::Removed),
+                (0, 1, MapDiffChange::Added),
+                (1, 1, MapDiffChange::Changed),
+            ]
+        );
+        assert!(!tiles.attr_changed);
+
+        let quads = &diff.layers[2];
+        assert_eq!(quads.index, design(1));
+        assert_eq!(
+            quads.entries,
+            MapDiffCounts {
+                added: 1,
+                removed: 0,
+                modified: 0,
+            }
+        );
+        assert!(quads.tiles.is_empty());
+
+        // tiles outside of the smaller layer count as air
+        let resized = &diff.layers[3];
+        assert_eq!(resized.index, design(2));
+        assert!(resized.resized);
+        assert_eq!(resized.tiles, vec![(1, 0, MapDiffChange::Removed)]);
+        assert!(!resized.can_revert());
+
+        let removed = &diff.layers[4];
+        assert_eq!(removed.index, design(3));
+        assert_eq!(removed.change, MapDiffChange::Removed);
+        assert!(!removed.can_revert());
+    }
+
+    #[test]
+    fn replaced_layer_type() {
+        let cur = map(&[0; 4], vec![tile_layer(2, 2, &[0; 4])]);
+        let file = map(&[0; 4], vec![quad_layer(1, None)]);
+        let diff = MapDiff::new(&cur, &file);
+        let changes: Vec<_> = diff
+            .layers
+            .iter()
+            .map(|layer| (layer.index, layer.change))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (design(0), MapDiffChange::Added),
+                (design(0), MapDiffChange::Removed),
+            ]
+        );
+    }
+
+    #[test]
+    fn revert_actions() {
+        let cur = map(
+            &[1, 1, 0, 0],
+            vec![tile_layer(2, 2, &[1, 0, 3, 5]), quad_layer(3, None)],
+        );
+        let file = map(
+            &[1, 0, 0, 0],
+            vec![tile_layer(2, 2, &[1, 2, 0, 4]), quad_layer(2, Some(0))],
+        );
+        let diff = MapDiff::new(&cur, &file);
+
+        let actions = revert_layer_actions(&cur, &file, &diff.layers[0]);
+        assert_eq!(actions.len(), 1);
+        assert!(matches!(
+            &actions[0],
+            EditorAction::TilePhysicsLayerReplaceTiles(act) if act.base.layer_index == 0
+        ));
+
+        let actions = revert_layer_actions(&cur, &file, &diff.layers[1]);
+        let [EditorAction::TileLayerReplaceTiles(act)] = actions.as_slice() else {
+            panic!("expected a single tile replace action");
+        };
+        assert_eq!(act.base.old_tiles, tiles(&[1, 0, 3, 5]));
+        assert_eq!(act.base.new_tiles, tiles(&[1, 2, 0, 4]));
+
+        let actions = revert_layer_actions(&cur, &file, &diff.layers[2]);
+        let [
+            EditorAction::QuadLayerRemQuads(rem),
+            EditorAction::QuadLayerAddQuads(add),
+        ] = actions.as_slice()
+        else {
+            panic!("expected the quads to be replaced");
+        };
+        assert_eq!(rem.base.quads.len(), 3);
+        assert_eq!(add.base.quads.len(), 2);
+        // the current map has no position animations
+        assert!(add.base.quads.iter().all(|quad| quad.pos_anim.is_none()));
+    }
+}
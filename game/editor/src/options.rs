@@ -2,7 +2,10 @@ use std::{collections::VecDeque, sync::Arc};
 
 use egui::{Key, Modifiers};
 
-use crate::hotkeys::{EditorBindsFile, EditorHotkeyEvent};
+use crate::{
+    hotkeys::{EditorBindsFile, EditorHotkeyEvent},
+    resource_optimizer::ResourceOptimizeOptions,
+};
 
 #[derive(Debug)]
 pub struct EditorHotkeyEdit {
@@ -18,4 +21,6 @@ pub struct EditorOptions {
     pub hotkeys_open: bool,
     pub hotkeys_edit: Option<EditorHotkeyEdit>,
     pub hotkeys_write_in_order: Arc<tokio::sync::Mutex<VecDeque<EditorBindsFile>>>,
+    /// Whether saving a map optimizes its images first.
+    pub optimize_resources: Option<ResourceOptimizeOptions>,
 }
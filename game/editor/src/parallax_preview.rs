@@ -0,0 +1,195 @@
+use std::time::Duration;
+
+use camera::Camera;
+use math::math::vector::vec2;
+
+use crate::map::EditorGroupsProps;
+
+/// The zoom of the ingame camera, if the player did not zoom.
+pub const INGAME_ZOOM: f32 = 1.0;
+
+/// How long the camera needs for one figure-eight.
+const FIGURE_EIGHT_DURATION: Duration = Duration::from_secs(12);
+/// Half the width of the figure-eight in tiles.
+const FIGURE_EIGHT_WIDTH: f32 = 20.0;
+/// Half the height of the figure-eight in tiles.
+const FIGURE_EIGHT_HEIGHT: f32 = 10.0;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ParallaxPreviewPath {
+    pub start_time: Duration,
+    pub center: vec2,
+}
+
+/// Simulates the ingame camera, so the parallax & offset of the groups
+/// can be checked without starting the game.
+///
+/// The editor camera is restored when leaving the preview.
+#[derive(Debug, Clone)]
+pub struct EditorParallaxPreview {
+    editor_camera: EditorGroupsProps,
+    /// The camera automatically moves along a figure-eight.
+    pub path: Option<ParallaxPreviewPath>,
+}
+
+impl EditorParallaxPreview {
+    /// Saves the editor camera and replaces it with the ingame camera.
+    pub fn enter(groups: &mut EditorGroupsProps) -> Self {
+        let editor_camera = groups.clone();
+        groups.zoom = INGAME_ZOOM;
+        // the game always renders parallax aware
+        groups.parallax_aware_zoom = true;
+        Self {
+            editor_camera,
+            path: None,
+        }
+    }
+
+    pub fn leave(self, groups: &mut EditorGroupsProps) {
+        *groups = self.editor_camera;
+    }
+
+    /// The position on the figure-eight around `center` at the given time.
+    pub fn figure_eight(center: vec2, time: Duration) -> vec2 {
+        let t = (time.as_secs_f64() / FIGURE_EIGHT_DURATION.as_secs_f64()).fract()
+            * std::f64::consts::TAU;
+        center
+            + vec2::new(
+                FIGURE_EIGHT_WIDTH * t.sin() as f32,
+                FIGURE_EIGHT_HEIGHT * (t * 2.0).sin() as f32,
+            )
+    }
+
+    pub fn toggle_path(&mut self, groups: &mut EditorGroupsProps, cur_time: Duration) {
+        match self.path.take() {
+            Some(path) => groups.pos = path.center,
+            None => {
+                self.path = Some(ParallaxPreviewPath {
+                    start_time: cur_time,
+                    center: groups.pos,
+                });
+            }
+        }
+    }
+
+    /// Moves the camera by the given amount of tiles,
+    /// which moves the center of the figure-eight, if active.
+    pub fn pan(&mut self, groups: &mut EditorGroupsProps, delta: vec2) {
+        groups.pos -= delta;
+        if let Some(path) = &mut self.path {
+            path.center -= delta;
+        }
+    }
+
+    pub fn update(&self, groups: &mut EditorGroupsProps, cur_time: Duration) {
+        if let Some(path) = &self.path {
+            groups.pos = Self::figure_eight(path.center, cur_time.saturating_sub(path.start_time));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use camera::{Camera, CanvasType};
+    use math::math::vector::vec2;
+
+    use crate::map::EditorGroupsProps;
+
+    use super::{EditorParallaxPreview, FIGURE_EIGHT_DURATION, INGAME_ZOOM};
+
+    const ASPECT: f32 = 16.0 / 9.0;
+
+    fn points(camera: &Camera, parallax: vec2, offset: vec2) -> [f32; 4] {
+        Camera::canvas_points_of_group_attr(
+            CanvasType::Custom {
+                aspect_ratio: ASPECT,
+            },
+            camera.pos,
+            parallax,
+            offset,
+            camera.zoom,
+            camera.parallax_aware_zoom,
+        )
+    }
+
+    #[test]
+    fn matches_game_camera() {
+        let editor_camera = EditorGroupsProps {
+            pos: vec2::new(70.0, -12.5),
+            zoom: 3.5,
+            parallax_aware_zoom: false,
+        };
+        let mut groups = editor_camera.clone();
+        let mut preview = EditorParallaxPreview::enter(&mut groups);
+        preview.pan(&mut groups, vec2::new(-5.0, 2.5));
+
+        // same as the ingame camera of the render game
+        let game_camera = Camera::new(vec2::new(75.0, -15.0), INGAME_ZOOM, Some(ASPECT), true);
+        let preview_camera = groups.camera();
+        let editor_camera_projection = editor_camera.camera();
+        for (parallax, offset) in [
+            (vec2::new(100.0, 100.0), vec2::new(0.0, 0.0)),
+            (vec2::new(0.0, 0.0), vec2::new(-3.0, 4.0)),
+            (vec2::new(50.0, 50.0), vec2::new(0.0, 0.0)),
+            (vec2::new(20.0, 80.0), vec2::new(10.0, -2.5)),
+            (vec2::new(150.0, 200.0), vec2::new(0.0, 1.0)),
+        ] {
+            let game = points(&game_camera, parallax, offset);
+            let preview_points = points(&preview_camera, parallax, offset);
+            for (game, preview) in game.iter().zip(preview_points.iter()) {
+                assert!(
+                    (game - preview).abs() < 0.0001,
+                    "parallax {parallax:?}: {game} != {preview}"
+                );
+            }
+            assert_ne!(points(&editor_camera_projection, parallax, offset), game);
+        }
+
+        // the editor camera is restored
+        preview.leave(&mut groups);
+        assert_eq!(groups.pos, editor_camera.pos);
+        assert_eq!(groups.zoom, editor_camera.zoom);
+        assert_eq!(
+            groups.parallax_aware_zoom,
+            editor_camera.parallax_aware_zoom
+        );
+    }
+
+    #[test]
+    fn figure_eight() {
+        let center = vec2::new(10.0, 20.0);
+        let at =
+            |secs: f64| EditorParallaxPreview::figure_eight(center, Duration::from_secs_f64(secs));
+        let dist = |a: vec2, b: vec2| (a - b).x.abs() + (a - b).y.abs();
+        let period = FIGURE_EIGHT_DURATION.as_secs_f64();
+
+        assert!(dist(at(0.0), center) < 0.0001);
+        assert!(dist(at(period / 2.0), center) < 0.0001);
+        assert!(dist(at(period), center) < 0.0001);
+        // both loops of the eight are on different sides
+        assert!(at(period / 4.0).x > center.x);
+        assert!(at(period * 3.0 / 4.0).x < center.x);
+        assert!(dist(at(period / 8.0), at(period / 8.0 + period)) < 0.001);
+
+        let mut groups = EditorGroupsProps {
+            pos: center,
+            zoom: 1.0,
+            parallax_aware_zoom: false,
+        };
+        let mut preview = EditorParallaxPreview::enter(&mut groups);
+        preview.toggle_path(&mut groups, Duration::from_secs(100));
+        preview.update(&mut groups, Duration::from_secs_f64(100.0 + period / 4.0));
+        assert!(dist(groups.pos, at(period / 4.0)) < 0.0001);
+
+        // panning moves the whole path
+        preview.pan(&mut groups, vec2::new(1.0, 1.0));
+        preview.update(&mut groups, Duration::from_secs_f64(100.0 + period / 2.0));
+        assert!(dist(groups.pos, center - vec2::new(1.0, 1.0)) < 0.0001);
+
+        preview.toggle_path(&mut groups, Duration::from_secs(200));
+        assert!(preview.path.is_none());
+        assert_eq!(groups.pos, center - vec2::new(1.0, 1.0));
+    }
+}
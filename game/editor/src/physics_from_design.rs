@@ -0,0 +1,275 @@
+use anyhow::anyhow;
+use legacy_map::mapdef_06::DdraceTileNum;
+use map::{
+    map::groups::layers::{
+        design::MapLayerTile,
+        tiles::{MapTileLayerPhysicsTiles, TileBase, TileFlags},
+    },
+    types::NonZeroU16MinusOne,
+};
+
+use crate::actions::actions::{
+    ActTilePhysicsLayerReplTilesBase, ActTilePhysicsLayerReplaceTiles, EditorAction,
+};
+
+/// Which game tile a tile of the design layer becomes.
+///
+/// Every non-empty design tile, that is not listed,
+/// becomes a hookable tile.
+#[derive(Debug, Clone, Default)]
+pub struct PhysicsFromDesignMapping {
+    /// Design tile indices that become unhookable tiles.
+    pub unhookable: Vec<u8>,
+    /// Design tile indices that become death tiles.
+    pub death: Vec<u8>,
+}
+
+impl PhysicsFromDesignMapping {
+    /// The game tile index for the design tile index,
+    /// `None` for air.
+    pub fn game_index(&self, design_index: u8) -> Option<u8> {
+        if design_index == 0 {
+            None
+        } else if self.death.contains(&design_index) {
+            Some(DdraceTileNum::Death as u8)
+        } else if self.unhookable.contains(&design_index) {
+            Some(DdraceTileNum::NoHook as u8)
+        } else {
+            Some(DdraceTileNum::Solid as u8)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PhysicsFromDesignOptions {
+    pub mapping: PhysicsFromDesignMapping,
+    /// Where the top left tile of the design layer lands in the
+    /// physics group, in tiles.
+    pub offset_x: i32,
+    pub offset_y: i32,
+    /// Also removes the game tiles below empty tiles of the design layer,
+    /// otherwise they are kept.
+    pub clear_empty: bool,
+}
+
+/// The changes to the game layer.
+#[derive(Debug)]
+pub struct PhysicsFromDesign {
+    /// `None` if no tile changes.
+    pub action: Option<EditorAction>,
+    /// The number of game tiles that change.
+    pub changed: usize,
+}
+
+/// Generates the game layer from a design tile layer.
+///
+/// Fails if non-empty design tiles would be outside of the physics group,
+/// the offset or the size of the physics group have to be changed then.
+pub fn physics_from_design(
+    design: &MapLayerTile,
+    game_layer_index: usize,
+    game_tiles: &[TileBase],
+    width: NonZeroU16MinusOne,
+    height: NonZeroU16MinusOne,
+    options: &PhysicsFromDesignOptions,
+) -> anyhow::Result<PhysicsFromDesign> {
+    let design_w = design.attr.width.get() as i32;
+    let w = width.get() as i32;
+    let h = height.get() as i32;
+
+    let mut new_tiles = game_tiles.to_vec();
+    let mut outside = 0;
+    let mut changed = 0;
+    for (i, design_tile) in design.tiles.iter().enumerate() {
+        let x = i as i32 % design_w + options.offset_x;
+        let y = i as i32 / design_w + options.offset_y;
+        let game_index = options.mapping.game_index(design_tile.index);
+        if x < 0 || y < 0 || x >= w || y >= h {
+            if game_index.is_some() {
+                outside += 1;
+            }
+            continue;
+        }
+
+        let new_tile = match game_index {
+            Some(index) => TileBase {
+                index,
+                flags: TileFlags::empty(),
+            },
+            None if options.clear_empty => TileBase::default(),
+            None => continue,
+        };
+        let tile = &mut new_tiles[(y * w + x) as usize];
+        if *tile != new_tile {
+            *tile = new_tile;
+            changed += 1;
+        }
+    }
+
+    if outside > 0 {
+        return Err(anyhow!(
+            "{outside} tile(s) of the design layer ({}x{}) would be outside of the \
+            physics group ({w}x{h}), change the offset or resize the physics group.",
+            design.attr.width.get(),
+            design.attr.height.get(),
+        ));
+    }
+
+    let action = (changed > 0).then(|| {
+        EditorAction::TilePhysicsLayerReplaceTiles(ActTilePhysicsLayerReplaceTiles {
+            base: ActTilePhysicsLayerReplTilesBase {
+                layer_index: game_layer_index,
+                old_tiles: MapTileLayerPhysicsTiles::Game(game_tiles.to_vec()),
+                new_tiles: MapTileLayerPhysicsTiles::Game(new_tiles),
+                x: 0,
+                y: 0,
+                w: width,
+                h: height,
+            },
+        })
+    });
+    Ok(PhysicsFromDesign { action, changed })
+}
+
+#[cfg(test)]
+mod test {
+    use legacy_map::mapdef_06::DdraceTileNum;
+    use map::{
+        map::groups::layers::{
+            design::MapLayerTile,
+            tiles::{MapTileLayerAttr, MapTileLayerPhysicsTiles, TileBase, TileFlags},
+        },
+        types::NonZeroU16MinusOne,
+    };
+    use math::math::vector::{nffixed, nfvec4};
+
+    use crate::actions::actions::EditorAction;
+
+    use super::{PhysicsFromDesignMapping, PhysicsFromDesignOptions, physics_from_design};
+
+    fn tile(index: u8) -> TileBase {
+        TileBase {
+            index,
+            flags: TileFlags::empty(),
+        }
+    }
+
+    fn tile_layer(w: u16, h: u16, indices: &[u8]) -> MapLayerTile {
+        MapLayerTile {
+            attr: MapTileLayerAttr {
+                width: NonZeroU16MinusOne::new(w).unwrap(),
+                height: NonZeroU16MinusOne::new(h).unwrap(),
+                color: nfvec4::new(
+                    nffixed::from_num(1),
+                    nffixed::from_num(1),
+                    nffixed::from_num(1),
+                    nffixed::from_num(1),
+                ),
+                high_detail: false,
+                color_anim: None,
+                color_anim_offset: time::Duration::ZERO,
+                image_array: None,
+                blend_mode: Default::default(),
+            },
+            tiles: indices
+                .iter()
+                .map(|index| TileBase {
+                    index: *index,
+                    flags: TileFlags::ROTATE,
+                })
+                .collect(),
+            name: Default::default(),
+        }
+    }
+
+    fn new_game_tiles(action: &Option<EditorAction>) -> Vec<TileBase> {
+        let Some(EditorAction::TilePhysicsLayerReplaceTiles(act)) = action else {
+            panic!("expected a physics layer action");
+        };
+        assert_eq!(act.base.layer_index, 0);
+        let MapTileLayerPhysicsTiles::Game(tiles) = &act.base.new_tiles else {
+            panic!("expected game tiles");
+        };
+        tiles.clone()
+    }
+
+    #[test]
+    fn generate_game_tiles() {
+        let solid = DdraceTileNum::Solid as u8;
+        let no_hook = DdraceTileNum::NoHook as u8;
+        let death = DdraceTileNum::Death as u8;
+        let freeze = DdraceTileNum::Freeze as u8;
+
+        let design = tile_layer(2, 2, &[1, 0, 7, 9]);
+        let mut options = PhysicsFromDesignOptions {
+            mapping: PhysicsFromDesignMapping {
+                unhookable: vec![7],
+                death: vec![9],
+            },
+            offset_x: 1,
+            offset_y: 0,
+            clear_empty: false,
+        };
+        let size = NonZeroU16MinusOne::new(3).unwrap();
+        // a freeze below the empty design tile
+        let game = vec![
+            tile(0),
+            tile(0),
+            tile(freeze),
+            tile(0),
+            tile(0),
+            tile(0),
+            tile(0),
+            tile(0),
+            tile(0),
+        ];
+
+        let res = physics_from_design(&design, 0, &game, size, size, &options).unwrap();
+        assert_eq!(res.changed, 3);
+        assert_eq!(
+            new_game_tiles(&res.action),
+            vec![
+                tile(0),
+                tile(solid),
+                tile(freeze),
+                tile(0),
+                tile(no_hook),
+                tile(death),
+                tile(0),
+                tile(0),
+                tile(0),
+            ]
+        );
+
+        // regenerating changes nothing
+        let res = physics_from_design(
+            &design,
+            0,
+            &new_game_tiles(&res.action),
+            size,
+            size,
+            &options,
+        )
+        .unwrap();
+        assert_eq!(res.changed, 0);
+        assert!(res.action.is_none());
+
+        // clearing removes the freeze
+        options.clear_empty = true;
+        let res = physics_from_design(&design, 0, &game, size, size, &options).unwrap();
+        assert_eq!(res.changed, 4);
+        assert_eq!(new_game_tiles(&res.action)[2], tile(0));
+
+        // the design layer does not fit with this offset
+        options.offset_y = 2;
+        let err = physics_from_design(&design, 0, &game, size, size, &options).unwrap_err();
+        assert!(err.to_string().contains("2 tile(s)"), "{err}");
+
+        // empty tiles may be outside
+        let design = tile_layer(2, 2, &[0, 1, 0, 1]);
+        options.offset_x = -1;
+        options.offset_y = 0;
+        let res = physics_from_design(&design, 0, &game, size, size, &options).unwrap();
+        assert_eq!(new_game_tiles(&res.action)[0], tile(solid));
+    }
+}
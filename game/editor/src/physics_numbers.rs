@@ -0,0 +1,625 @@
+use std::collections::BTreeMap;
+
+use legacy_map::mapdef_06::{DdraceTileNum, TILE_SWITCHTIMEDOPEN};
+use map::{
+    map::groups::layers::{
+        physics::{
+            MapLayerTilePhysicsSwitch, MapLayerTilePhysicsTele, MapLayerTilePhysicsTune,
+            MapLayerTilePhysicsTuneZone,
+        },
+        tiles::MapTileLayerPhysicsTiles,
+    },
+    skeleton::groups::layers::physics::MapLayerPhysicsSkeleton,
+    types::NonZeroU16MinusOne,
+};
+
+use crate::{
+    actions::actions::{
+        ActChangeSwitch, ActChangeTeleporter, ActChangeTuneZone, ActTilePhysicsLayerReplTilesBase,
+        ActTilePhysicsLayerReplaceTiles, EditorAction,
+    },
+    map::EditorPhysicsLayer,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhysicsNumberKind {
+    Tele,
+    Switch,
+    Tune,
+}
+
+impl PhysicsNumberKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PhysicsNumberKind::Tele => "Tele",
+            PhysicsNumberKind::Switch => "Switch",
+            PhysicsNumberKind::Tune => "Tune zone",
+        }
+    }
+}
+
+/// How often a number is used in a physics layer.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NumberUsage {
+    /// Tiles that use the number, e.g. tele ins or switch triggers.
+    pub sources: usize,
+    /// Tiles the number leads to, e.g. tele outs or doors.
+    ///
+    /// For tune zones this is `1` if the zone has settings.
+    pub destinations: usize,
+    /// The number has a name or settings.
+    pub has_metadata: bool,
+}
+
+impl NumberUsage {
+    /// Sources without destinations or the other way around.
+    pub fn is_unreferenced(&self) -> bool {
+        self.sources == 0 || self.destinations == 0
+    }
+}
+
+/// A physics layer whose tiles reference each other by numbers.
+#[derive(Debug, Clone, Copy)]
+pub enum PhysicsNumberLayer<'a> {
+    Tele(&'a MapLayerTilePhysicsTele),
+    Switch(&'a MapLayerTilePhysicsSwitch),
+    Tune(&'a MapLayerTilePhysicsTune),
+}
+
+impl<'a> PhysicsNumberLayer<'a> {
+    pub fn from_layer(layer: &'a EditorPhysicsLayer) -> Option<Self> {
+        match layer {
+            MapLayerPhysicsSkeleton::Tele(layer) => Some(Self::Tele(&layer.layer)),
+            MapLayerPhysicsSkeleton::Switch(layer) => Some(Self::Switch(&layer.layer)),
+            MapLayerPhysicsSkeleton::Tune(layer) => Some(Self::Tune(&layer.layer)),
+            MapLayerPhysicsSkeleton::Arbitrary(_)
+            | MapLayerPhysicsSkeleton::Game(_)
+            | MapLayerPhysicsSkeleton::Front(_)
+            | MapLayerPhysicsSkeleton::Speedup(_) => None,
+        }
+    }
+
+    pub fn kind(&self) -> PhysicsNumberKind {
+        match self {
+            Self::Tele(_) => PhysicsNumberKind::Tele,
+            Self::Switch(_) => PhysicsNumberKind::Switch,
+            Self::Tune(_) => PhysicsNumberKind::Tune,
+        }
+    }
+
+    fn is_tele_destination(index: u8) -> bool {
+        index == DdraceTileNum::TeleOut as u8 || index == DdraceTileNum::TeleCheckOut as u8
+    }
+
+    fn is_switch_source(index: u8) -> bool {
+        index == TILE_SWITCHTIMEDOPEN
+            || index == DdraceTileNum::SwitchTimedClose as u8
+            || index == DdraceTileNum::SwitchOpen as u8
+            || index == DdraceTileNum::SwitchClose as u8
+    }
+
+    /// All numbers that are used by tiles or have metadata, sorted by number.
+    ///
+    /// The number `0` is never included, it means "no number".
+    pub fn usage(&self) -> BTreeMap<u8, NumberUsage> {
+        let mut res: BTreeMap<u8, NumberUsage> = Default::default();
+        let mut add_tile = |number: u8, is_destination: bool| {
+            if number == 0 {
+                return;
+            }
+            let usage = res.entry(number).or_default();
+            if is_destination {
+                usage.destinations += 1;
+            } else {
+                usage.sources += 1;
+            }
+        };
+        match self {
+            Self::Tele(layer) => {
+                for tile in layer.base.tiles.iter().filter(|tile| tile.base.index != 0) {
+                    add_tile(tile.number, Self::is_tele_destination(tile.base.index));
+                }
+                for number in layer.tele_names.keys().filter(|&&number| number != 0) {
+                    res.entry(*number).or_default().has_metadata = true;
+                }
+            }
+            Self::Switch(layer) => {
+                for tile in layer.base.tiles.iter().filter(|tile| tile.base.index != 0) {
+                    add_tile(tile.number, !Self::is_switch_source(tile.base.index));
+                }
+                for number in layer.switch_names.keys().filter(|&&number| number != 0) {
+                    res.entry(*number).or_default().has_metadata = true;
+                }
+            }
+            Self::Tune(layer) => {
+                for tile in layer.base.tiles.iter().filter(|tile| tile.base.index != 0) {
+                    add_tile(tile.number, false);
+                }
+                for number in layer.tune_zones.keys().filter(|&&number| number != 0) {
+                    let usage = res.entry(*number).or_default();
+                    usage.destinations = 1;
+                    usage.has_metadata = true;
+                }
+            }
+        }
+        res
+    }
+
+    /// Applies the mapping of old to new numbers to the metadata.
+    ///
+    /// If two numbers end up at the same number, the metadata of the
+    /// number that was not moved is kept.
+    fn remap_metadata<T: Clone>(
+        metadata: &BTreeMap<u8, T>,
+        mapping: &BTreeMap<u8, u8>,
+    ) -> BTreeMap<u8, T> {
+        let mut res: BTreeMap<u8, T> = metadata
+            .iter()
+            .filter(|(number, _)| !mapping.contains_key(number))
+            .map(|(number, val)| (*number, val.clone()))
+            .collect();
+        for (number, val) in metadata {
+            if let Some(new_number) = mapping.get(number) {
+                res.entry(*new_number).or_insert_with(|| val.clone());
+            }
+        }
+        res
+    }
+
+    /// The actions that change every number of the layer according
+    /// to the mapping of old to new numbers.
+    ///
+    /// All actions should be executed as a single group,
+    /// so they can be undone at once.
+    /// If a new number is already in use, the numbers are merged.
+    pub fn renumber_actions(
+        &self,
+        layer_index: usize,
+        width: NonZeroU16MinusOne,
+        height: NonZeroU16MinusOne,
+        mapping: &BTreeMap<u8, u8>,
+    ) -> Vec<EditorAction> {
+        fn remap_tiles<T: Copy + PartialEq>(
+            tiles: &[T],
+            mapping: &BTreeMap<u8, u8>,
+            number: impl Fn(&mut T) -> Option<&mut u8>,
+        ) -> Option<Vec<T>> {
+            let mut new_tiles = tiles.to_vec();
+            new_tiles.iter_mut().for_each(|tile| {
+                if let Some(number) = number(tile)
+                    && let Some(new_number) = mapping.get(&*number)
+                {
+                    *number = *new_number;
+                }
+            });
+            (new_tiles != tiles).then_some(new_tiles)
+        }
+        fn names_actions(
+            names: &BTreeMap<u8, String>,
+            new_names: &BTreeMap<u8, String>,
+            to_action: impl Fn(u8, String, String) -> EditorAction,
+        ) -> Vec<EditorAction> {
+            let mut numbers: Vec<u8> = names.keys().chain(new_names.keys()).copied().collect();
+            numbers.sort();
+            numbers.dedup();
+            numbers
+                .into_iter()
+                .filter(|number| names.get(number) != new_names.get(number))
+                .map(|number| {
+                    to_action(
+                        number,
+                        names.get(&number).cloned().unwrap_or_default(),
+                        new_names.get(&number).cloned().unwrap_or_default(),
+                    )
+                })
+                .collect()
+        }
+
+        let mapping: BTreeMap<u8, u8> = mapping
+            .iter()
+            .filter(|(from, to)| **from != 0 && **to != 0 && from != to)
+            .map(|(from, to)| (*from, *to))
+            .collect();
+        if mapping.is_empty() {
+            return Vec::new();
+        }
+
+        let (tiles, metadata_actions) = match self {
+            Self::Tele(layer) => {
+                let names: BTreeMap<u8, String> = layer
+                    .tele_names
+                    .iter()
+                    .map(|(number, name)| (*number, name.clone()))
+                    .collect();
+                let new_names = Self::remap_metadata(&names, &mapping);
+                (
+                    remap_tiles(&layer.base.tiles, &mapping, |tile| {
+                        (tile.base.index != 0).then_some(&mut tile.number)
+                    })
+                    .map(|tiles| {
+                        (
+                            MapTileLayerPhysicsTiles::Tele(layer.base.tiles.clone()),
+                            MapTileLayerPhysicsTiles::Tele(tiles),
+                        )
+                    }),
+                    names_actions(&names, &new_names, |index, old_name, new_name| {
+                        EditorAction::ChangeTeleporter(ActChangeTeleporter {
+                            index,
+                            old_name,
+                            new_name,
+                        })
+                    }),
+                )
+            }
+            Self::Switch(layer) => {
+                let names: BTreeMap<u8, String> = layer
+                    .switch_names
+                    .iter()
+                    .map(|(number, name)| (*number, name.clone()))
+                    .collect();
+                let new_names = Self::remap_metadata(&names, &mapping);
+                (
+                    remap_tiles(&layer.base.tiles, &mapping, |tile| {
+                        (tile.base.index != 0).then_some(&mut tile.number)
+                    })
+                    .map(|tiles| {
+                        (
+                            MapTileLayerPhysicsTiles::Switch(layer.base.tiles.clone()),
+                            MapTileLayerPhysicsTiles::Switch(tiles),
+                        )
+                    }),
+                    names_actions(&names, &new_names, |index, old_name, new_name| {
+                        EditorAction::ChangeSwitch(ActChangeSwitch {
+                            index,
+                            old_name,
+                            new_name,
+                        })
+                    }),
+                )
+            }
+            Self::Tune(layer) => {
+                let zones: BTreeMap<u8, MapLayerTilePhysicsTuneZone> = layer
+                    .tune_zones
+                    .iter()
+                    .map(|(number, zone)| (*number, zone.clone()))
+                    .collect();
+                let new_zones = Self::remap_metadata(&zones, &mapping);
+                let mut numbers: Vec<u8> = zones.keys().chain(new_zones.keys()).copied().collect();
+                numbers.sort();
+                numbers.dedup();
+                (
+                    remap_tiles(&layer.base.tiles, &mapping, |tile| {
+                        (tile.base.index != 0).then_some(&mut tile.number)
+                    })
+                    .map(|tiles| {
+                        (
+                            MapTileLayerPhysicsTiles::Tune(layer.base.tiles.clone()),
+                            MapTileLayerPhysicsTiles::Tune(tiles),
+                        )
+                    }),
+                    numbers
+                        .into_iter()
+                        .filter(|number| zones.get(number) != new_zones.get(number))
+                        .map(|index| {
+                            let zone = zones.get(&index);
+                            let new_zone = new_zones.get(&index);
+                            EditorAction::ChangeTuneZone(ActChangeTuneZone {
+                                index,
+                                old_name: zone.map(|z| z.name.clone()).unwrap_or_default(),
+                                new_name: new_zone.map(|z| z.name.clone()).unwrap_or_default(),
+                                old_tunes: zone.map(|z| z.tunes.clone()).unwrap_or_default(),
+                                new_tunes: new_zone.map(|z| z.tunes.clone()).unwrap_or_default(),
+                                old_enter_msg: zone.and_then(|z| z.enter_msg.clone()),
+                                new_enter_msg: new_zone.and_then(|z| z.enter_msg.clone()),
+                                old_leave_msg: zone.and_then(|z| z.leave_msg.clone()),
+                                new_leave_msg: new_zone.and_then(|z| z.leave_msg.clone()),
+                            })
+                        })
+                        .collect(),
+                )
+            }
+        };
+
+        tiles
+            .map(|(old_tiles, new_tiles)| {
+                EditorAction::TilePhysicsLayerReplaceTiles(ActTilePhysicsLayerReplaceTiles {
+                    base: ActTilePhysicsLayerReplTilesBase {
+                        layer_index,
+                        old_tiles,
+                        new_tiles,
+                        x: 0,
+                        y: 0,
+                        w: width,
+                        h: height,
+                    },
+                })
+            })
+            .into_iter()
+            .chain(metadata_actions)
+            .collect()
+    }
+}
+
+/// Moves a single number to another number.
+pub fn remap_mapping(from: u8, to: u8) -> BTreeMap<u8, u8> {
+    [(from, to)].into_iter().collect()
+}
+
+/// Moves all used numbers to `1..=n`, without changing their order.
+pub fn compact_mapping(usage: &BTreeMap<u8, NumberUsage>) -> BTreeMap<u8, u8> {
+    usage
+        .keys()
+        .zip(1..=u8::MAX)
+        .filter(|(from, to)| *from != to)
+        .map(|(from, to)| (*from, to))
+        .collect()
+}
+
+/// The new numbers of the mapping that are already in use
+/// by numbers that are not moved themself.
+///
+/// These numbers would be merged.
+pub fn mapping_conflicts(usage: &BTreeMap<u8, NumberUsage>, mapping: &BTreeMap<u8, u8>) -> Vec<u8> {
+    let mut res: Vec<u8> = mapping
+        .iter()
+        .filter(|(from, to)| from != to && usage.contains_key(*to) && !mapping.contains_key(*to))
+        .map(|(_, to)| *to)
+        .collect();
+    res.sort();
+    res.dedup();
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use base::linked_hash_map_view::FxLinkedHashMap;
+    use legacy_map::mapdef_06::DdraceTileNum;
+    use map::{
+        map::groups::layers::{
+            physics::{
+                MapLayerTilePhysicsBase, MapLayerTilePhysicsTele, MapLayerTilePhysicsTune,
+                MapLayerTilePhysicsTuneZone,
+            },
+            tiles::{MapTileLayerPhysicsTiles, TeleTile, TileBase, TuneTile},
+        },
+        types::NonZeroU16MinusOne,
+    };
+
+    use crate::actions::actions::EditorAction;
+
+    use super::{
+        NumberUsage, PhysicsNumberLayer, compact_mapping, mapping_conflicts, remap_mapping,
+    };
+
+    fn tele(index: DdraceTileNum, number: u8) -> TeleTile {
+        TeleTile {
+            base: TileBase {
+                index: index as u8,
+                flags: Default::default(),
+            },
+            number,
+        }
+    }
+
+    /// A 4x2 tele layer:
+    /// - 3: two tele ins, one tele out
+    /// - 5: only a tele in
+    /// - 9: a checkpoint tele out
+    fn tele_layer() -> MapLayerTilePhysicsTele {
+        let mut tele_names: FxLinkedHashMap<u8, String> = Default::default();
+        tele_names.insert(3, "start".to_string());
+        tele_names.insert(9, "checkpoint".to_string());
+        MapLayerTilePhysicsTele {
+            base: MapLayerTilePhysicsBase {
+                tiles: vec![
+                    tele(DdraceTileNum::TeleIn, 3),
+                    tele(DdraceTileNum::TeleInEvil, 3),
+                    tele(DdraceTileNum::TeleOut, 3),
+                    tele(DdraceTileNum::Air, 7),
+                    tele(DdraceTileNum::TeleIn, 5),
+                    tele(DdraceTileNum::Air, 0),
+                    tele(DdraceTileNum::TeleCheckOut, 9),
+                    tele(DdraceTileNum::Air, 0),
+                ],
+            },
+            tele_names,
+        }
+    }
+
+    fn apply_tele(layer: &mut MapLayerTilePhysicsTele, actions: Vec<EditorAction>) {
+        for action in actions {
+            match action {
+                EditorAction::TilePhysicsLayerReplaceTiles(act) => {
+                    assert_eq!(
+                        act.base.old_tiles,
+                        MapTileLayerPhysicsTiles::Tele(layer.base.tiles.clone())
+                    );
+                    assert_eq!((act.base.x, act.base.y), (0, 0));
+                    assert_eq!((act.base.w.get(), act.base.h.get()), (4, 2));
+                    let MapTileLayerPhysicsTiles::Tele(tiles) = act.base.new_tiles else {
+                        panic!("not tele tiles");
+                    };
+                    layer.base.tiles = tiles;
+                }
+                EditorAction::ChangeTeleporter(act) => {
+                    assert_eq!(
+                        layer
+                            .tele_names
+                            .get(&act.index)
+                            .cloned()
+                            .unwrap_or_default(),
+                        act.old_name
+                    );
+                    if act.new_name.is_empty() {
+                        layer.tele_names.remove(&act.index);
+                    } else {
+                        layer.tele_names.insert(act.index, act.new_name);
+                    }
+                }
+                _ => panic!("unexpected action"),
+            }
+        }
+    }
+
+    fn renumber(layer: &MapLayerTilePhysicsTele, mapping: &BTreeMap<u8, u8>) -> Vec<EditorAction> {
+        PhysicsNumberLayer::Tele(layer).renumber_actions(
+            0,
+            NonZeroU16MinusOne::new(4).unwrap(),
+            NonZeroU16MinusOne::new(2).unwrap(),
+            mapping,
+        )
+    }
+
+    fn numbers(layer: &MapLayerTilePhysicsTele) -> Vec<u8> {
+        layer.base.tiles.iter().map(|tile| tile.number).collect()
+    }
+
+    #[test]
+    fn usage() {
+        let layer = tele_layer();
+        let usage = PhysicsNumberLayer::Tele(&layer).usage();
+        assert_eq!(usage.keys().copied().collect::<Vec<_>>(), vec![3, 5, 9]);
+        assert_eq!(
+            usage[&3],
+            NumberUsage {
+                sources: 2,
+                destinations: 1,
+                has_metadata: true,
+            }
+        );
+        assert!(!usage[&3].is_unreferenced());
+        assert!(usage[&5].is_unreferenced());
+        assert!(!usage[&5].has_metadata);
+        assert!(usage[&9].is_unreferenced());
+        assert_eq!(usage[&9].destinations, 1);
+    }
+
+    #[test]
+    fn remap() {
+        let mut layer = tele_layer();
+        let usage = PhysicsNumberLayer::Tele(&layer).usage();
+        let mapping = remap_mapping(3, 4);
+        assert!(mapping_conflicts(&usage, &mapping).is_empty());
+
+        let actions = renumber(&layer, &mapping);
+        assert_eq!(actions.len(), 3);
+        apply_tele(&mut layer, actions);
+        assert_eq!(numbers(&layer), vec![4, 4, 4, 7, 5, 0, 9, 0]);
+        assert_eq!(layer.tele_names.get(&3), None);
+        assert_eq!(layer.tele_names.get(&4).map(|s| s.as_str()), Some("start"));
+        assert_eq!(
+            layer.tele_names.get(&9).map(|s| s.as_str()),
+            Some("checkpoint")
+        );
+
+        // nothing to do
+        assert!(renumber(&layer, &remap_mapping(4, 4)).is_empty());
+        assert!(renumber(&layer, &remap_mapping(0, 4)).is_empty());
+    }
+
+    #[test]
+    fn merge() {
+        let mut layer = tele_layer();
+        let usage = PhysicsNumberLayer::Tele(&layer).usage();
+        let mapping = remap_mapping(9, 3);
+        assert_eq!(mapping_conflicts(&usage, &mapping), vec![3]);
+
+        apply_tele(&mut layer, renumber(&layer, &mapping));
+        assert_eq!(numbers(&layer), vec![3, 3, 3, 7, 5, 0, 3, 0]);
+        // the name of the target number is kept
+        assert_eq!(layer.tele_names.len(), 1);
+        assert_eq!(layer.tele_names.get(&3).map(|s| s.as_str()), Some("start"));
+
+        let usage = PhysicsNumberLayer::Tele(&layer).usage();
+        assert_eq!(usage[&3].sources, 2);
+        assert_eq!(usage[&3].destinations, 2);
+    }
+
+    #[test]
+    fn compact() {
+        let mut layer = tele_layer();
+        let usage = PhysicsNumberLayer::Tele(&layer).usage();
+        let mapping = compact_mapping(&usage);
+        assert_eq!(
+            mapping,
+            [(3, 1), (5, 2), (9, 3)]
+                .into_iter()
+                .collect::<BTreeMap<_, _>>()
+        );
+        // all targets that are in use are moved themself
+        assert!(mapping_conflicts(&usage, &mapping).is_empty());
+
+        apply_tele(&mut layer, renumber(&layer, &mapping));
+        // air tiles keep their number
+        assert_eq!(numbers(&layer), vec![1, 1, 1, 7, 2, 0, 3, 0]);
+        assert_eq!(layer.tele_names.get(&1).map(|s| s.as_str()), Some("start"));
+        assert_eq!(
+            layer.tele_names.get(&3).map(|s| s.as_str()),
+            Some("checkpoint")
+        );
+        assert_eq!(layer.tele_names.len(), 2);
+
+        // already compact
+        let usage = PhysicsNumberLayer::Tele(&layer).usage();
+        assert!(compact_mapping(&usage).is_empty());
+    }
+
+    #[test]
+    fn tune_zones() {
+        let mut tune_zones: FxLinkedHashMap<u8, MapLayerTilePhysicsTuneZone> = Default::default();
+        tune_zones.insert(
+            2,
+            MapLayerTilePhysicsTuneZone {
+                name: "slow".to_string(),
+                tunes: Default::default(),
+                enter_msg: Some("welcome".to_string()),
+                leave_msg: None,
+            },
+        );
+        let tune = |number| TuneTile {
+            base: TileBase {
+                index: DdraceTileNum::Tune as u8,
+                flags: Default::default(),
+            },
+            number,
+        };
+        let layer = MapLayerTilePhysicsTune {
+            base: MapLayerTilePhysicsBase {
+                tiles: vec![tune(2), tune(2), tune(6), Default::default()],
+            },
+            tune_zones,
+        };
+        let usage = PhysicsNumberLayer::Tune(&layer).usage();
+        assert!(!usage[&2].is_unreferenced());
+        assert!(usage[&6].is_unreferenced());
+
+        let actions = PhysicsNumberLayer::Tune(&layer).renumber_actions(
+            0,
+            NonZeroU16MinusOne::new(2).unwrap(),
+            NonZeroU16MinusOne::new(2).unwrap(),
+            &compact_mapping(&usage),
+        );
+        let [
+            EditorAction::TilePhysicsLayerReplaceTiles(tiles),
+            EditorAction::ChangeTuneZone(add),
+            EditorAction::ChangeTuneZone(remove),
+        ] = actions.as_slice()
+        else {
+            panic!("unexpected actions: {actions:?}");
+        };
+        assert_eq!(
+            tiles.base.new_tiles,
+            MapTileLayerPhysicsTiles::Tune(vec![tune(1), tune(1), tune(2), Default::default()])
+        );
+        assert_eq!(add.index, 1);
+        assert!(add.old_name.is_empty());
+        assert_eq!(add.new_name, "slow");
+        assert_eq!(add.new_enter_msg.as_deref(), Some("welcome"));
+        assert_eq!(remove.index, 2);
+        assert_eq!(remove.old_name, "slow");
+        assert!(remove.new_name.is_empty());
+        assert!(remove.new_enter_msg.is_none());
+    }
+}
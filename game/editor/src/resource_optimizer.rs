@@ -0,0 +1,356 @@
+use std::sync::Arc;
+
+use base::hash::{Hash, generate_hash_for};
+use image_utils::png::optimize_png;
+use map::map::resources::{MapResourceMetaData, MapResourceRef, Resources};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::map::EditorMap;
+
+/// How the images of a map are optimized before saving.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceOptimizeOptions {
+    /// Quad images larger than this are downscaled.
+    /// Image arrays are never downscaled, since the tiles must keep their size.
+    pub max_size: Option<u32>,
+    /// Images with at most 256 colors are saved as indexed png.
+    pub palette: bool,
+}
+
+impl Default for ResourceOptimizeOptions {
+    fn default() -> Self {
+        Self {
+            max_size: Some(1024),
+            palette: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceOptimizeTy {
+    Image,
+    ImageArray,
+}
+
+/// An image of the map that should be optimized.
+#[derive(Debug, Clone)]
+pub struct ResourceOptimizeSource {
+    pub ty: ResourceOptimizeTy,
+    pub index: usize,
+    pub name: String,
+    pub hash: Hash,
+    pub file: Vec<u8>,
+}
+
+/// The optimized version of a single image.
+#[derive(Debug, Clone)]
+pub struct ResourceOptimizeEntry {
+    pub ty: ResourceOptimizeTy,
+    pub index: usize,
+    pub name: String,
+    pub old_hash: Hash,
+    pub old_size: usize,
+    pub old_width: u32,
+    pub old_height: u32,
+    pub file: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ResourceOptimizeEntry {
+    /// How many bytes the optimized file saves.
+    pub fn savings(&self) -> usize {
+        self.old_size.saturating_sub(self.file.len())
+    }
+}
+
+/// The projected result of optimizing the images of a map.
+///
+/// Only images that actually got smaller are part of the report.
+#[derive(Debug, Clone, Default)]
+pub struct ResourceOptimizeReport {
+    pub entries: Vec<ResourceOptimizeEntry>,
+    /// Images that could not be optimized & the reason.
+    pub errors: Vec<(String, String)>,
+}
+
+impl ResourceOptimizeReport {
+    /// Copies the png images of the map, so the optimization can
+    /// run without access to the map.
+    pub fn sources(map: &EditorMap) -> Vec<ResourceOptimizeSource> {
+        let images = map
+            .resources
+            .images
+            .iter()
+            .enumerate()
+            .map(|(index, r)| (ResourceOptimizeTy::Image, index, &r.def, &r.user.file));
+        let image_arrays = map
+            .resources
+            .image_arrays
+            .iter()
+            .enumerate()
+            .map(|(index, r)| (ResourceOptimizeTy::ImageArray, index, &r.def, &r.user.file));
+        images
+            .chain(image_arrays)
+            .filter(|(_, _, def, _)| def.meta.ty.as_str() == "png")
+            .map(|(ty, index, def, file)| ResourceOptimizeSource {
+                ty,
+                index,
+                name: def.name.as_str().to_string(),
+                hash: def.meta.blake3_hash,
+                file: file.as_ref().clone(),
+            })
+            .collect()
+    }
+
+    /// Optimizes all images on the thread pool.
+    pub fn new(
+        sources: Vec<ResourceOptimizeSource>,
+        options: ResourceOptimizeOptions,
+        tp: &Arc<rayon::ThreadPool>,
+    ) -> Self {
+        let results: Vec<_> = tp.install(|| {
+            sources
+                .into_par_iter()
+                .map(|source| {
+                    let max_size = match source.ty {
+                        ResourceOptimizeTy::Image => options.max_size,
+                        ResourceOptimizeTy::ImageArray => None,
+                    };
+                    match optimize_png(&source.file, max_size, options.palette) {
+                        Ok(img) => Ok(ResourceOptimizeEntry {
+                            ty: source.ty,
+                            index: source.index,
+                            name: source.name,
+                            old_hash: source.hash,
+                            old_size: source.file.len(),
+                            old_width: img.original_width,
+                            old_height: img.original_height,
+                            file: img.file,
+                            width: img.width,
+                            height: img.height,
+                        }),
+                        Err(err) => Err((source.name, err.to_string())),
+                    }
+                })
+                .collect()
+        });
+
+        let mut res = Self::default();
+        for result in results {
+            match result {
+                Ok(entry) => {
+                    if entry.file.len() < entry.old_size {
+                        res.entries.push(entry);
+                    }
+                }
+                Err(err) => res.errors.push(err),
+            }
+        }
+        res
+    }
+
+    pub fn total_old_size(&self) -> usize {
+        self.entries.iter().map(|entry| entry.old_size).sum()
+    }
+
+    pub fn total_savings(&self) -> usize {
+        self.entries.iter().map(|entry| entry.savings()).sum()
+    }
+
+    /// Points the resources of the map to the optimized images.
+    ///
+    /// Returns the old & new resource ref together with the new file,
+    /// resources that changed since the report was created are skipped.
+    pub fn apply<'a>(
+        &'a self,
+        resources: &mut Resources,
+    ) -> Vec<(MapResourceRef, MapResourceRef, &'a [u8])> {
+        let mut res = Vec::new();
+        for entry in self.entries.iter() {
+            let list = match entry.ty {
+                ResourceOptimizeTy::Image => &mut resources.images,
+                ResourceOptimizeTy::ImageArray => &mut resources.image_arrays,
+            };
+            let Some(r) = list
+                .get_mut(entry.index)
+                .filter(|r| r.meta.blake3_hash == entry.old_hash)
+            else {
+                continue;
+            };
+            let old = r.clone();
+            r.meta = MapResourceMetaData {
+                blake3_hash: generate_hash_for(&entry.file),
+                ty: r.meta.ty.clone(),
+            };
+            res.push((old, r.clone(), entry.file.as_slice()));
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use base::{hash::generate_hash_for, reduced_ascii_str::ReducedAsciiString};
+    use image_utils::png::{downscaled_size, load_png_image_as_rgba, save_png_image};
+    use map::{
+        file::MapFileReader,
+        map::{
+            Map,
+            resources::{MapResourceMetaData, MapResourceRef},
+        },
+    };
+
+    use super::{
+        ResourceOptimizeOptions, ResourceOptimizeReport, ResourceOptimizeSource, ResourceOptimizeTy,
+    };
+
+    fn tp() -> Arc<rayon::ThreadPool> {
+        Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(1)
+                .build()
+                .unwrap(),
+        )
+    }
+
+    /// A png with a transparent left half and a gradient on the right half.
+    fn test_png(width: u32, height: u32) -> Vec<u8> {
+        let mut data = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                if x < width / 2 {
+                    data.extend([255, 0, 0, 0]);
+                } else {
+                    data.extend([(x % 256) as u8, (y % 256) as u8, 128, 255]);
+                }
+            }
+        }
+        save_png_image(&data, width, height).unwrap()
+    }
+
+    fn source(ty: ResourceOptimizeTy, index: usize, file: Vec<u8>) -> ResourceOptimizeSource {
+        ResourceOptimizeSource {
+            ty,
+            index,
+            name: format!("img{index}"),
+            hash: generate_hash_for(&file),
+            file,
+        }
+    }
+
+    fn decode(file: &[u8]) -> (Vec<u8>, u32, u32) {
+        let mut mem = Vec::new();
+        let img = load_png_image_as_rgba(file, |w, h, ppp| {
+            mem.resize(w * h * ppp, 0);
+            &mut mem
+        })
+        .unwrap();
+        let (width, height) = (img.width, img.height);
+        (img.data.to_vec(), width, height)
+    }
+
+    #[test]
+    fn downscale_dimensions() {
+        assert_eq!(downscaled_size(4096, 2048, 1024), (1024, 512));
+        assert_eq!(downscaled_size(300, 3000, 1000), (100, 1000));
+        assert_eq!(downscaled_size(512, 256, 1024), (512, 256));
+        assert_eq!(downscaled_size(5000, 1, 1000), (1000, 1));
+
+        let report = ResourceOptimizeReport::new(
+            vec![
+                source(ResourceOptimizeTy::Image, 0, test_png(2048, 1024)),
+                // image arrays keep their size
+                source(ResourceOptimizeTy::ImageArray, 0, test_png(2048, 1024)),
+            ],
+            ResourceOptimizeOptions {
+                max_size: Some(512),
+                palette: false,
+            },
+            &tp(),
+        );
+        assert!(report.errors.is_empty());
+        let image = &report.entries[0];
+        assert_eq!((image.old_width, image.old_height), (2048, 1024));
+        assert_eq!((image.width, image.height), (512, 256));
+        assert_eq!(decode(&image.file)[1..], [512, 256]);
+        if let Some(array) = report.entries.get(1) {
+            assert_eq!((array.width, array.height), (2048, 1024));
+        }
+        assert!(report.total_savings() > 0);
+    }
+
+    #[test]
+    fn alpha_preserved() {
+        let report = ResourceOptimizeReport::new(
+            vec![source(ResourceOptimizeTy::Image, 0, test_png(256, 256))],
+            ResourceOptimizeOptions {
+                max_size: Some(128),
+                palette: true,
+            },
+            &tp(),
+        );
+        let (data, width, _) = decode(&report.entries[0].file);
+        // fully transparent on the left, opaque on the right
+        assert_eq!(data[3], 0);
+        assert_eq!(data[(width as usize - 1) * 4 + 3], 255);
+
+        // few colors use a palette, including the transparent color
+        let mut data = Vec::new();
+        let mut seed: u32 = 1;
+        for _ in 0..256 * 256 {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            data.extend(if (seed >> 16) % 3 == 0 {
+                [0, 0, 0, 0]
+            } else {
+                [10, 200, 30, 128]
+            });
+        }
+        let file = save_png_image(&data, 256, 256).unwrap();
+        let report = ResourceOptimizeReport::new(
+            vec![source(ResourceOptimizeTy::Image, 0, file)],
+            ResourceOptimizeOptions::default(),
+            &tp(),
+        );
+        let (optimized, _, _) = decode(&report.entries[0].file);
+        assert_eq!(optimized, data);
+    }
+
+    #[test]
+    fn saved_map_loads() {
+        let tp = tp();
+        let file = include_bytes!("../../../data/map/maps/ctf1.twmap.tar");
+        let mut map = Map::read(&MapFileReader::new(file.to_vec()).unwrap(), &tp).unwrap();
+
+        let png = test_png(2048, 2048);
+        map.resources.images.push(MapResourceRef {
+            name: ReducedAsciiString::from_str_lossy("big"),
+            meta: MapResourceMetaData {
+                blake3_hash: generate_hash_for(&png),
+                ty: ReducedAsciiString::from_str_lossy("png"),
+            },
+            hq_meta: None,
+        });
+        let index = map.resources.images.len() - 1;
+
+        let report = ResourceOptimizeReport::new(
+            vec![source(ResourceOptimizeTy::Image, index, png)],
+            ResourceOptimizeOptions::default(),
+            &tp,
+        );
+        let applied = report.apply(&mut map.resources);
+        assert_eq!(applied.len(), 1);
+        let (old, new, new_file) = &applied[0];
+        assert_ne!(old.meta.blake3_hash, new.meta.blake3_hash);
+        assert_eq!(new.meta.blake3_hash, generate_hash_for(new_file));
+
+        let written = map.write(&tp).unwrap();
+        let loaded = Map::read(&MapFileReader::new(written).unwrap(), &tp).unwrap();
+        assert_eq!(loaded.resources.images[index], *new);
+
+        // applying twice does nothing, the hash changed
+        assert!(report.apply(&mut map.resources).is_empty());
+    }
+}
@@ -32,18 +32,22 @@ use rand::{RngCore, seq::SliceRandom};
 use sound::sound_mt::SoundMultiThreaded;
 
 use crate::{
-    action_logic::{check_and_copy_tiles, do_action, merge_actions, redo_action, undo_action},
+    action_logic::{check_and_copy_tiles, do_action, redo_action, undo_action},
     actions::actions::{
         ActTileLayerReplaceTiles, EditorAction, EditorActionGroup, EditorActionInterface,
     },
     dbg::{invalid::random_invalid_action, valid::random_valid_action},
+    editor::Editor,
     event::{
         AdminConfigState, ClientProps, EditorCommand, EditorEvent, EditorEventAutoMap,
         EditorEventClientToServer, EditorEventGenerator, EditorEventLayerIndex,
         EditorEventOverwriteMap, EditorEventRuleTy, EditorEventServerToClient, EditorNetEvent,
     },
+    history::{EditorHistory, EditorHistoryInfo},
+    locks::{EditorLockLayer, EditorLocks, LOCK_INACTIVITY_TIMEOUT},
     map::{EditorLayer, EditorMap, EditorMapGroupsInterface},
     network::EditorNetwork,
+    snapshot::{MapBackups, SNAPSHOT_CHUNK_SIZE, split_into_chunks},
     tools::{
         auto_saver::AutoSaver,
         tile_layer::{
@@ -60,6 +64,7 @@ struct Client {
     is_authed: bool,
     is_admin: bool,
     is_local_client: bool,
+    history_subscribed: bool,
     props: ClientProps,
 }
 
@@ -69,8 +74,7 @@ struct Client {
 /// Additionally it makes the event list act like
 /// an undo/redo manager
 pub struct EditorServer {
-    action_groups: Vec<EditorActionGroup>,
-    cur_action_group: Option<usize>,
+    history: EditorHistory,
 
     network: EditorNetwork,
 
@@ -90,11 +94,17 @@ pub struct EditorServer {
 
     auto_mapper_rules: HashMap<(String, String, Hash), TileLayerAutoMapperRuleType>,
 
+    locks: EditorLocks,
+
     client_ids: u64,
 
     time: SteadyClock,
     last_client_infos: Duration,
     needs_client_info_update: bool,
+    last_history_update: Duration,
+    needs_history_update: bool,
+
+    backups: MapBackups,
 
     io: Io,
 }
@@ -114,8 +124,7 @@ impl EditorServer {
         let (network, cert, port) =
             EditorNetwork::new_server(time, event_generator.clone(), cert_mode, port)?;
         Ok(Self {
-            action_groups: Default::default(),
-            cur_action_group: None,
+            history: Default::default(),
 
             has_events,
             event_generator,
@@ -131,12 +140,18 @@ impl EditorServer {
 
             auto_mapper_rules: Default::default(),
 
+            locks: Default::default(),
+
             client_ids: 0,
 
             needs_client_info_update: false,
             last_client_infos: time.now(),
+            needs_history_update: false,
+            last_history_update: time.now(),
             time: time.clone(),
 
+            backups: Default::default(),
+
             io,
         })
     }
@@ -148,6 +163,128 @@ impl EditorServer {
             )));
     }
 
+    fn send_history(&self) {
+        let history = self.history.info();
+        self.clients
+            .iter()
+            .filter(|(_, client)| client.history_subscribed)
+            .for_each(|(id, _)| {
+                self.network.send_to(
+                    id,
+                    EditorEvent::Server(EditorEventServerToClient::History(history.clone())),
+                );
+            });
+    }
+
+    fn broadcast_locks(&self) {
+        self.network
+            .send(EditorEvent::Server(EditorEventServerToClient::Locks(
+                self.locks.locks().to_vec(),
+            )));
+    }
+
+    /// The current map & all its resources, as it is sent to joining clients.
+    fn map_snapshot(
+        map: &EditorMap,
+        tp: &Arc<rayon::ThreadPool>,
+    ) -> anyhow::Result<EditorEventOverwriteMap> {
+        let resources: HashMap<_, _> = map
+            .resources
+            .images
+            .iter()
+            .flat_map(|r| {
+                [(r.def.meta.blake3_hash, r.user.file.as_ref().clone())]
+                    .into_iter()
+                    .chain(
+                        r.def
+                            .hq_meta
+                            .as_ref()
+                            .zip(r.user.hq.as_ref())
+                            .map(|(s, (file, _))| (s.blake3_hash, file.as_ref().clone())),
+                    )
+            })
+            .chain(map.resources.image_arrays.iter().flat_map(|r| {
+                [(r.def.meta.blake3_hash, r.user.file.as_ref().clone())]
+                    .into_iter()
+                    .chain(
+                        r.def
+                            .hq_meta
+                            .as_ref()
+                            .zip(r.user.hq.as_ref())
+                            .map(|(s, (file, _))| (s.blake3_hash, file.as_ref().clone())),
+                    )
+            }))
+            .chain(map.resources.sounds.iter().flat_map(|r| {
+                [(r.def.meta.blake3_hash, r.user.file.as_ref().clone())]
+                    .into_iter()
+                    .chain(
+                        r.def
+                            .hq_meta
+                            .as_ref()
+                            .zip(r.user.hq.as_ref())
+                            .map(|(s, (file, _))| (s.blake3_hash, file.as_ref().clone())),
+                    )
+            }))
+            .collect();
+
+        let send_map: Map = map.clone().into();
+        Ok(EditorEventOverwriteMap {
+            map: send_map.write(tp)?,
+            resources,
+            live_edited_layers: map.groups.live_edited_layers(),
+        })
+    }
+
+    /// Writes the current map into the backup directory
+    /// & removes the oldest backups of this session.
+    fn write_backup(
+        &mut self,
+        map: &EditorMap,
+        tp: &Arc<rayon::ThreadPool>,
+        auto_saver: &AutoSaver,
+    ) {
+        let name = auto_saver
+            .path
+            .as_ref()
+            .and_then(|path| path.file_stem())
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "unnamed".to_string());
+        let dir = format!("map/backups/{name}");
+        let file = format!(
+            "{dir}/{}.twmap",
+            chrono::Local::now().format("%Y_%m_%d_%H_%M_%S")
+        );
+        let remove = self.backups.push(file.clone());
+
+        let backup_map: Map = map.clone().into();
+        let resources = Editor::map_resource_files(map);
+        let tp = tp.clone();
+        let fs = self.io.fs.clone();
+        self.io.rt.spawn_without_lifetime(async move {
+            let res = async {
+                fs.create_dir(dir.as_ref()).await?;
+                fs.create_dir("map/resources/images".as_ref()).await?;
+                fs.create_dir("map/resources/sounds".as_ref()).await?;
+                // resources are content addressed, so existing ones don't change
+                for (path, resource) in resources {
+                    if !fs.file_exists(path.as_ref()).await {
+                        fs.write_file(path.as_ref(), resource).await?;
+                    }
+                }
+                fs.write_file(file.as_ref(), backup_map.write(&tp)?).await?;
+                for path in remove {
+                    fs.remove_file(path.as_ref()).await?;
+                }
+                anyhow::Ok(())
+            }
+            .await;
+            if let Err(err) = &res {
+                log::error!("failed to write the map backup: {err}");
+            }
+            res
+        });
+    }
+
     fn auto_map(
         rule: &mut TileLayerAutoMapperRuleType,
         auto_map: EditorEventAutoMap,
@@ -284,6 +421,120 @@ impl EditorServer {
         }
     }
 
+    /// Undoes or redoes a single group of the history
+    /// and sends it to all remote clients.
+    ///
+    /// Returns `true` if a group was undone or redone.
+    fn history_step(
+        &mut self,
+        id: &NetworkConnectionId,
+        is_undo: bool,
+        tp: &Arc<rayon::ThreadPool>,
+        sound_mt: &SoundMultiThreaded,
+        graphics_mt: &GraphicsMultiThreaded,
+        shader_storage_handle: &GraphicsShaderStorageHandle,
+        buffer_object_handle: &GraphicsBufferObjectHandle,
+        backend_handle: &GraphicsBackendHandle,
+        texture_handle: &GraphicsTextureHandle,
+        map: &mut EditorMap,
+        notifications: &mut ClientNotifications,
+    ) -> bool {
+        let Some(group) = self.history.step(is_undo) else {
+            return false;
+        };
+
+        let it: Box<dyn Iterator<Item = _>> = if is_undo {
+            Box::new(group.actions.iter().rev())
+        } else {
+            Box::new(group.actions.iter())
+        };
+        for act in it {
+            let act_label = format!(
+                "[{}] {}",
+                if is_undo { "UNDO" } else { "REDO" },
+                if is_undo {
+                    act.undo_info()
+                } else {
+                    act.redo_info()
+                }
+            );
+            let action_fn = if is_undo { undo_action } else { redo_action };
+            if let Err(act_err) = action_fn(
+                tp,
+                sound_mt,
+                graphics_mt,
+                shader_storage_handle,
+                buffer_object_handle,
+                backend_handle,
+                texture_handle,
+                act.clone(),
+                map,
+            ) {
+                let err = format!(
+                    "Failed to execute your action.\n\
+                    Since it was an {} command, this \
+                    probably indicates a bug in the code.\n\
+                    {act_err}",
+                    if is_undo { "undo" } else { "redo" }
+                );
+                log::error!("{err}{}", act_err.backtrace());
+                log::error!("current action: {act_label}");
+                log::error!(
+                    "latest action log starting with \
+                    the most recent:\n{}",
+                    self.action_log
+                        .iter()
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                );
+                log::error!(
+                    "current actions index: {:?}, \
+                    currently there is a history of size: {}",
+                    self.history.cur(),
+                    self.history.len()
+                );
+                notifications.add_err(&err, Duration::from_secs(10));
+                self.network.send_to(
+                    id,
+                    EditorEvent::Server(EditorEventServerToClient::Error(err)),
+                );
+            }
+
+            self.action_log.push_front(act_label);
+        }
+
+        if self.locks.release_shifted(&group.actions) {
+            self.broadcast_locks();
+        }
+        self.needs_history_update = true;
+
+        let undo_label = self.undo_label();
+        let redo_label = self.redo_label();
+        let act = if is_undo {
+            EditorEventServerToClient::UndoAction {
+                action: group,
+                redo_label,
+                undo_label,
+            }
+        } else {
+            EditorEventServerToClient::RedoAction {
+                action: group,
+                redo_label,
+                undo_label,
+            }
+        };
+        self.clients
+            .iter()
+            .filter(|(_, client)| !client.is_local_client)
+            .for_each(|(id, _)| {
+                self.network.send_to(id, EditorEvent::Server(act.clone()));
+            });
+
+        self.action_log.truncate(4000);
+        true
+    }
+
     fn handle_client_ev(
         &mut self,
         id: NetworkConnectionId,
@@ -327,45 +578,10 @@ impl EditorServer {
                     };
 
                     if !*is_local_client {
-                        let resources: HashMap<_, _> = map
-                            .resources
-                            .images
-                            .iter()
-                            .flat_map(|r| {
-                                [(r.def.meta.blake3_hash, r.user.file.as_ref().clone())]
-                                    .into_iter()
-                                    .chain(r.def.hq_meta.as_ref().zip(r.user.hq.as_ref()).map(
-                                        |(s, (file, _))| (s.blake3_hash, file.as_ref().clone()),
-                                    ))
-                            })
-                            .chain(map.resources.image_arrays.iter().flat_map(|r| {
-                                [(r.def.meta.blake3_hash, r.user.file.as_ref().clone())]
-                                    .into_iter()
-                                    .chain(r.def.hq_meta.as_ref().zip(r.user.hq.as_ref()).map(
-                                        |(s, (file, _))| (s.blake3_hash, file.as_ref().clone()),
-                                    ))
-                            }))
-                            .chain(map.resources.sounds.iter().flat_map(|r| {
-                                [(r.def.meta.blake3_hash, r.user.file.as_ref().clone())]
-                                    .into_iter()
-                                    .chain(r.def.hq_meta.as_ref().zip(r.user.hq.as_ref()).map(
-                                        |(s, (file, _))| (s.blake3_hash, file.as_ref().clone()),
-                                    ))
-                            }))
-                            .collect();
-
-                        let send_map: Map = map.clone().into();
-
-                        let map_bytes = send_map.write(tp).unwrap();
-
                         self.network.send_to(
                             &id,
                             EditorEvent::Server(EditorEventServerToClient::Map(
-                                EditorEventOverwriteMap {
-                                    map: map_bytes,
-                                    resources,
-                                    live_edited_layers: map.groups.live_edited_layers(),
-                                },
+                                Self::map_snapshot(map, tp).unwrap(),
                             )),
                         );
                     }
@@ -377,6 +593,24 @@ impl EditorServer {
                             allows_remote_admin: self.admin_password.is_some(),
                         }),
                     );
+                    self.network.send_to(
+                        &id,
+                        EditorEvent::Server(EditorEventServerToClient::Locks(
+                            self.locks.locks().to_vec(),
+                        )),
+                    );
+                    self.network.send_to(
+                        &id,
+                        EditorEvent::Server(EditorEventServerToClient::Guides(
+                            map.user.options.guides.clone(),
+                        )),
+                    );
+                    self.network.send_to(
+                        &id,
+                        EditorEvent::Server(EditorEventServerToClient::Palette(
+                            map.user.options.palette.clone(),
+                        )),
+                    );
                     self.broadcast_client_infos();
                 } else {
                     self.network.send_to(
@@ -387,8 +621,25 @@ impl EditorServer {
                     );
                 }
             } else if client.is_authed {
+                let server_id = client.props.server_id;
                 match ev {
                     EditorEventClientToServer::Action(act) => {
+                        // locks of other users are enforced here, even if the
+                        // client already checked them, since it might not know
+                        // about the most recent locks yet
+                        if let Some(lock) = self.locks.conflict(server_id, &act.actions) {
+                            self.network.send_to(
+                                &id,
+                                EditorEvent::Server(EditorEventServerToClient::Error(format!(
+                                    "Failed to execute your action, \
+                                    because it modifies an area locked by {}.",
+                                    lock.owner_name
+                                ))),
+                            );
+                            return;
+                        }
+                        self.locks.touch(server_id, self.time.now());
+
                         let mut valid_act = EditorActionGroup {
                             actions: Vec::new(),
                             identifier: act.identifier.clone(),
@@ -432,52 +683,36 @@ impl EditorServer {
                         }
                         if !valid_act.actions.is_empty() {
                             *should_save = true;
-                            if let Some(cur_action_group) = self.cur_action_group {
-                                self.action_groups.truncate(cur_action_group + 1);
-                            } else {
-                                self.action_groups.clear();
+                            // only network sessions need backups,
+                            // since there the host's crash affects all users
+                            if self.backups.on_committed_action()
+                                && self.clients.values().any(|c| !c.is_local_client)
+                            {
+                                self.write_backup(map, tp, auto_saver);
                             }
-
-                            if self.action_groups.last_mut().is_some_and(|group| {
-                                group
-                                    .identifier
-                                    .as_ref()
-                                    // explicitly check for some here
-                                    .is_some_and(|identifier| {
-                                        Some(identifier) == valid_act.identifier.as_ref()
-                                    })
-                            }) {
-                                let group = self.action_groups.last_mut().unwrap();
-                                group.actions.append(&mut valid_act.actions.clone());
-
-                                match merge_actions(&mut group.actions) {
-                                    Ok(had_merge) => {
-                                        if had_merge {
-                                            let merged_action = group.actions.last().unwrap();
-                                            self.action_log.push_front(format!(
-                                                "[MERGED] {}",
-                                                merged_action.redo_info()
-                                            ));
-                                        }
-                                    }
-                                    Err(err) => {
-                                        log::error!("{err}{}", err.backtrace());
-                                        notifications
-                                            .add_err(err.to_string(), Duration::from_secs(10));
+                            if self.locks.release_shifted(&valid_act.actions) {
+                                self.broadcast_locks();
+                            }
+                            match self.history.push(valid_act.clone(), self.time.now()) {
+                                Ok(had_merge) => {
+                                    if had_merge
+                                        && let Some(merged_action) = self
+                                            .history
+                                            .undo_group()
+                                            .and_then(|group| group.actions.last())
+                                    {
+                                        self.action_log.push_front(format!(
+                                            "[MERGED] {}",
+                                            merged_action.redo_info()
+                                        ));
                                     }
                                 }
-                            } else {
-                                let new_index = self.action_groups.len();
-                                self.action_groups.push(valid_act.clone());
-                                self.cur_action_group = Some(new_index);
-                            }
-
-                            // Make sure memory doesn't exhaust
-                            while self.action_groups.len() > 300 {
-                                self.action_groups.remove(0);
-                                self.cur_action_group =
-                                    self.cur_action_group.map(|index| index.saturating_sub(1));
+                                Err(err) => {
+                                    log::error!("{err}{}", err.backtrace());
+                                    notifications.add_err(err.to_string(), Duration::from_secs(10));
+                                }
                             }
+                            self.needs_history_update = true;
                             self.action_log.truncate(4000);
 
                             self.clients
@@ -501,129 +736,87 @@ impl EditorServer {
                         EditorCommand::Undo | EditorCommand::Redo => {
                             let is_undo = matches!(cmd, EditorCommand::Undo);
 
-                            if ((is_undo && self.cur_action_group.is_some())
-                                || (!is_undo
-                                    && self.cur_action_group.is_none_or(|index| {
-                                        index < self.action_groups.len().saturating_sub(1)
-                                    })))
-                                && !self.action_groups.is_empty()
+                            let group = if is_undo {
+                                self.history.undo_group()
+                            } else {
+                                self.history.redo_group()
+                            };
+                            if let Some(lock) = group
+                                .and_then(|group| self.locks.conflict(server_id, &group.actions))
                             {
-                                *should_save = true;
-                                if !is_undo {
-                                    self.cur_action_group =
-                                        match self.cur_action_group {
-                                            Some(index) => Some((index + 1).clamp(
-                                                0,
-                                                self.action_groups.len().saturating_sub(1),
-                                            )),
-                                            None => Some(0),
-                                        };
-                                }
-
-                                let group = if let Some(group) = self
-                                    .action_groups
-                                    .get(self.cur_action_group.unwrap_or_default())
-                                {
-                                    let it: Box<dyn Iterator<Item = _>> = if is_undo {
-                                        Box::new(group.actions.iter().rev())
-                                    } else {
-                                        Box::new(group.actions.iter())
-                                    };
-                                    for act in it {
-                                        let act_label = format!(
-                                            "[{}] {}",
-                                            if is_undo { "UNDO" } else { "REDO" },
-                                            if is_undo {
-                                                act.undo_info()
-                                            } else {
-                                                act.redo_info()
-                                            }
-                                        );
-                                        let action_fn =
-                                            if is_undo { undo_action } else { redo_action };
-                                        if let Err(act_err) = action_fn(
-                                            tp,
-                                            sound_mt,
-                                            graphics_mt,
-                                            shader_storage_handle,
-                                            buffer_object_handle,
-                                            backend_handle,
-                                            texture_handle,
-                                            act.clone(),
-                                            map,
-                                        ) {
-                                            let err = format!(
-                                                "Failed to execute your action.\n\
-                                                Since it was an {} command, this \
-                                                probably indicates a bug in the code.\n\
-                                                {act_err}",
-                                                if is_undo { "undo" } else { "redo" }
-                                            );
-                                            log::error!("{err}{}", act_err.backtrace());
-                                            log::error!("current action: {act_label}");
-                                            log::error!(
-                                                "latest action log starting with \
-                                                the most recent:\n{}",
-                                                self.action_log
-                                                    .iter()
-                                                    .cloned()
-                                                    .collect::<Vec<_>>()
-                                                    .join("\n")
-                                            );
-                                            log::error!(
-                                                "current actions index: {:?}, \
-                                                currently there is a history of size: {}",
-                                                self.cur_action_group,
-                                                self.action_groups.len()
-                                            );
-                                            notifications.add_err(&err, Duration::from_secs(10));
-                                            self.network.send_to(
-                                                &id,
-                                                EditorEvent::Server(
-                                                    EditorEventServerToClient::Error(err),
-                                                ),
-                                            );
-                                        }
+                                self.network.send_to(
+                                    &id,
+                                    EditorEvent::Server(EditorEventServerToClient::Error(format!(
+                                        "Failed to {} the last action, \
+                                        because it modifies an area locked by {}.",
+                                        if is_undo { "undo" } else { "redo" },
+                                        lock.owner_name
+                                    ))),
+                                );
+                                return;
+                            }
 
-                                        self.action_log.push_front(act_label);
-                                    }
-                                    group.clone()
-                                } else {
-                                    panic!("action group did not exists. logic bug")
-                                };
+                            if self.history_step(
+                                &id,
+                                is_undo,
+                                tp,
+                                sound_mt,
+                                graphics_mt,
+                                shader_storage_handle,
+                                buffer_object_handle,
+                                backend_handle,
+                                texture_handle,
+                                map,
+                                notifications,
+                            ) {
+                                *should_save = true;
+                            }
+                        }
+                        EditorCommand::JumpTo { index } => {
+                            let Some((is_undo, steps)) = self.history.jump_steps(index) else {
+                                self.network.send_to(
+                                    &id,
+                                    EditorEvent::Server(EditorEventServerToClient::Error(
+                                        "The history entry does not exist anymore.".to_string(),
+                                    )),
+                                );
+                                return;
+                            };
+                            if let Some(lock) = self
+                                .history
+                                .jump_groups(index)
+                                .into_iter()
+                                .find_map(|group| self.locks.conflict(server_id, &group.actions))
+                            {
+                                self.network.send_to(
+                                    &id,
+                                    EditorEvent::Server(EditorEventServerToClient::Error(format!(
+                                        "Failed to jump to the history entry, \
+                                        because it modifies an area locked by {}.",
+                                        lock.owner_name
+                                    ))),
+                                );
+                                return;
+                            }
 
-                                if is_undo {
-                                    self.cur_action_group = match self.cur_action_group {
-                                        Some(index) => index.checked_sub(1),
-                                        None => panic!(
-                                            "Undo while the action group was None is a bug!."
-                                        ),
-                                    };
+                            // every step is sent to the remote clients in order,
+                            // so they replay exactly the same undos/redos
+                            for _ in 0..steps {
+                                if self.history_step(
+                                    &id,
+                                    is_undo,
+                                    tp,
+                                    sound_mt,
+                                    graphics_mt,
+                                    shader_storage_handle,
+                                    buffer_object_handle,
+                                    backend_handle,
+                                    texture_handle,
+                                    map,
+                                    notifications,
+                                ) {
+                                    *should_save = true;
                                 }
-
-                                let undo_label = self.undo_label();
-                                let redo_label = self.redo_label();
-                                let act = if is_undo {
-                                    EditorEventServerToClient::UndoAction {
-                                        action: group,
-                                        redo_label,
-                                        undo_label,
-                                    }
-                                } else {
-                                    EditorEventServerToClient::RedoAction {
-                                        action: group,
-                                        redo_label,
-                                        undo_label,
-                                    }
-                                };
-                                self.clients
-                                    .iter()
-                                    .filter(|(_, client)| !client.is_local_client)
-                                    .for_each(|(id, _)| {
-                                        self.network.send_to(id, EditorEvent::Server(act.clone()));
-                                    });
-
-                                self.action_log.truncate(4000);
                             }
                         }
                     },
@@ -662,6 +855,7 @@ impl EditorServer {
                                                 .active
                                                 .then_some(auto_saver.interval)
                                                 .flatten(),
+                                            backup_every_nth_action: self.backups.every_nth_action,
                                         },
                                     }),
                                 );
@@ -672,6 +866,7 @@ impl EditorServer {
                         if self.admin_password == Some(state.password) {
                             auto_saver.active = state.state.auto_save.is_some();
                             auto_saver.interval = state.state.auto_save;
+                            self.backups.every_nth_action = state.state.backup_every_nth_action;
                             for (id, _) in self.clients.iter().filter(|(_, c)| c.is_admin) {
                                 self.network.send_to(
                                     id,
@@ -796,6 +991,120 @@ impl EditorServer {
                             }
                         }
                     }
+                    EditorEventClientToServer::Lock { layer, rect } => {
+                        let layer_exists = match layer {
+                            EditorLockLayer::Design(index) => if index.is_background {
+                                &map.groups.background
+                            } else {
+                                &map.groups.foreground
+                            }
+                            .get(index.group_index)
+                            .is_some_and(|group| group.layers.len() > index.layer_index),
+                            EditorLockLayer::Physics { layer_index } => {
+                                map.groups.physics.layers.len() > layer_index
+                            }
+                        };
+                        let res = if layer_exists {
+                            self.locks.try_lock(
+                                server_id,
+                                &client.props.mapper_name,
+                                layer,
+                                rect,
+                                self.time.now(),
+                            )
+                        } else {
+                            Err(anyhow!("The layer to lock does not exist."))
+                        };
+                        match res {
+                            Ok(_) => self.broadcast_locks(),
+                            Err(err) => {
+                                self.network.send_to(
+                                    &id,
+                                    EditorEvent::Server(EditorEventServerToClient::Error(
+                                        err.to_string(),
+                                    )),
+                                );
+                            }
+                        }
+                    }
+                    EditorEventClientToServer::Unlock { id: lock_id } => {
+                        if self.locks.unlock(server_id, lock_id) {
+                            self.broadcast_locks();
+                        }
+                    }
+                    EditorEventClientToServer::RequestSnapshot { id: snapshot_id } => {
+                        let data = Self::map_snapshot(map, tp).and_then(|snapshot| {
+                            Ok(bincode::serde::encode_to_vec(
+                                &snapshot,
+                                bincode::config::standard(),
+                            )?)
+                        });
+                        match data {
+                            Ok(data) => {
+                                for chunk in
+                                    split_into_chunks(snapshot_id, &data, SNAPSHOT_CHUNK_SIZE)
+                                {
+                                    self.network.send_to(
+                                        &id,
+                                        EditorEvent::Server(
+                                            EditorEventServerToClient::SnapshotChunk(chunk),
+                                        ),
+                                    );
+                                }
+                            }
+                            Err(err) => {
+                                self.network.send_to(
+                                    &id,
+                                    EditorEvent::Server(EditorEventServerToClient::Error(format!(
+                                        "Failed to create the map snapshot: {err}"
+                                    ))),
+                                );
+                            }
+                        }
+                    }
+                    EditorEventClientToServer::Guides(guides) => {
+                        map.user.options.guides = guides.clone();
+                        // the sender already has them
+                        for (other_id, _) in self
+                            .clients
+                            .iter()
+                            .filter(|(other_id, c)| **other_id != id && c.is_authed)
+                        {
+                            self.network.send_to(
+                                other_id,
+                                EditorEvent::Server(EditorEventServerToClient::Guides(
+                                    guides.clone(),
+                                )),
+                            );
+                        }
+                    }
+                    EditorEventClientToServer::Palette(palette) => {
+                        map.user.options.palette = palette.clone();
+                        // the sender already has it
+                        for (other_id, _) in self
+                            .clients
+                            .iter()
+                            .filter(|(other_id, c)| **other_id != id && c.is_authed)
+                        {
+                            self.network.send_to(
+                                other_id,
+                                EditorEvent::Server(EditorEventServerToClient::Palette(
+                                    palette.clone(),
+                                )),
+                            );
+                        }
+                    }
+                    EditorEventClientToServer::HistorySubscribe { subscribe } => {
+                        client.history_subscribed = subscribe;
+                        if subscribe {
+                            self.network.send_to(
+                                &id,
+                                EditorEvent::Server(EditorEventServerToClient::History(
+                                    self.history.info(),
+                                )),
+                            );
+                        }
+                    }
                     EditorEventClientToServer::LoadAutoMap {
                         resource_and_hash,
                         name,
@@ -1040,6 +1349,18 @@ impl EditorServer {
             self.last_client_infos = now;
         }
 
+        if self.needs_history_update
+            && now.saturating_sub(self.last_history_update) > Duration::from_millis(100)
+        {
+            self.send_history();
+            self.needs_history_update = false;
+            self.last_history_update = now;
+        }
+
+        if self.locks.expire(now, LOCK_INACTIVITY_TIMEOUT) {
+            self.broadcast_locks();
+        }
+
         if self.has_events.load(std::sync::atomic::Ordering::Relaxed) {
             let events = self.event_generator.take();
 
@@ -1073,7 +1394,12 @@ impl EditorServer {
                                 self.broadcast_client_infos();
                             }
                             NetworkEvent::Disconnected { .. } => {
-                                self.clients.remove(&id);
+                                if let Some(client) = self.clients.remove(&id)
+                                    && client.is_authed
+                                    && self.locks.unlock_all(client.props.server_id)
+                                {
+                                    self.broadcast_locks();
+                                }
 
                                 self.broadcast_client_infos();
                             }
@@ -1105,44 +1431,13 @@ impl EditorServer {
     }
 
     pub fn undo_label(&self) -> Option<String> {
-        self.cur_action_group
-            .and_then(|i| self.action_groups.get(i))
-            .and_then(|g| g.actions.last().map(|a| (a, g.actions.len())))
-            .map(|(a, len)| {
-                format!(
-                    "{}{}",
-                    a.undo_info(),
-                    if len > 1 {
-                        format!(" + {len} more ")
-                    } else {
-                        "".to_string()
-                    }
-                )
-            })
+        self.history.undo_label()
     }
     pub fn redo_label(&self) -> Option<String> {
-        (!self.action_groups.is_empty()
-            && self
-                .cur_action_group
-                .is_none_or(|i| i < self.action_groups.len().saturating_sub(1)))
-        .then(|| {
-            self.action_groups.get(match self.cur_action_group {
-                Some(val) => val + 1,
-                None => 0,
-            })
-        })
-        .flatten()
-        .and_then(|g| g.actions.first().map(|a| (a, g.actions.len())))
-        .map(|(a, len)| {
-            format!(
-                "{}{}",
-                a.redo_info(),
-                if len > 1 {
-                    format!(" + {len} more ")
-                } else {
-                    "".to_string()
-                }
-            )
-        })
+        self.history.redo_label()
+    }
+
+    pub fn history_info(&self) -> EditorHistoryInfo {
+        self.history.info()
     }
 }
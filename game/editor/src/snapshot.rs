@@ -0,0 +1,229 @@
+use std::collections::VecDeque;
+
+use anyhow::anyhow;
+
+use crate::event::EditorEventSnapshotChunk;
+
+/// The max size of the data of a single snapshot chunk.
+pub const SNAPSHOT_CHUNK_SIZE: usize = 64 * 1024;
+/// Snapshots of more chunks are rejected (4 GiB).
+const MAX_SNAPSHOT_CHUNKS: u32 = 64 * 1024;
+
+/// Splits a serialized snapshot into chunks,
+/// so large maps don't block the connection & the download can show its progress.
+pub fn split_into_chunks(id: u64, data: &[u8], chunk_size: usize) -> Vec<EditorEventSnapshotChunk> {
+    let chunks: Vec<_> = data.chunks(chunk_size.max(1)).collect();
+    // an empty snapshot still needs a chunk to finish the download
+    let count = chunks.len().max(1) as u32;
+    if chunks.is_empty() {
+        return vec![EditorEventSnapshotChunk {
+            id,
+            index: 0,
+            count,
+            data: Vec::new(),
+        }];
+    }
+    chunks
+        .into_iter()
+        .enumerate()
+        .map(|(index, data)| EditorEventSnapshotChunk {
+            id,
+            index: index as u32,
+            count,
+            data: data.to_vec(),
+        })
+        .collect()
+}
+
+/// Reassembles the chunks of a requested snapshot.
+#[derive(Debug)]
+pub struct SnapshotDownload {
+    id: u64,
+    chunks: Vec<Option<Vec<u8>>>,
+    received: usize,
+}
+
+impl SnapshotDownload {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id,
+            chunks: Default::default(),
+            received: 0,
+        }
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// From 0 to 1, the amount of chunks is only known after the first chunk arrived.
+    pub fn progress(&self) -> f32 {
+        if self.chunks.is_empty() {
+            0.0
+        } else {
+            self.received as f32 / self.chunks.len() as f32
+        }
+    }
+
+    /// Returns the whole snapshot once all chunks arrived.
+    ///
+    /// Chunks of other downloads are ignored, chunks may arrive in any order.
+    pub fn add(&mut self, chunk: EditorEventSnapshotChunk) -> anyhow::Result<Option<Vec<u8>>> {
+        if chunk.id != self.id {
+            return Ok(None);
+        }
+        anyhow::ensure!(
+            chunk.count > 0 && chunk.count <= MAX_SNAPSHOT_CHUNKS,
+            "The snapshot has an invalid amount of chunks: {}",
+            chunk.count
+        );
+        if self.chunks.is_empty() {
+            self.chunks.resize(chunk.count as usize, None);
+        }
+        anyhow::ensure!(
+            self.chunks.len() == chunk.count as usize,
+            "The amount of chunks of the snapshot changed."
+        );
+        let slot = self
+            .chunks
+            .get_mut(chunk.index as usize)
+            .ok_or_else(|| anyhow!("Snapshot chunk index {} is out of bounds", chunk.index))?;
+        if slot.is_none() {
+            self.received += 1;
+        }
+        *slot = Some(chunk.data);
+
+        Ok((self.received == self.chunks.len()).then(|| {
+            std::mem::take(&mut self.chunks)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .collect()
+        }))
+    }
+}
+
+/// Rotating backups the server writes every nth committed action.
+///
+/// Only the backups of the current session are rotated,
+/// older ones are never removed.
+#[derive(Debug)]
+pub struct MapBackups {
+    /// `None` disables backups.
+    pub every_nth_action: Option<u32>,
+    /// How many backups are kept.
+    pub max_backups: usize,
+
+    actions: u32,
+    files: VecDeque<String>,
+}
+
+impl Default for MapBackups {
+    fn default() -> Self {
+        Self {
+            every_nth_action: Some(100),
+            max_backups: 10,
+
+            actions: 0,
+            files: Default::default(),
+        }
+    }
+}
+
+impl MapBackups {
+    /// Returns `true` if a backup should be written now.
+    pub fn on_committed_action(&mut self) -> bool {
+        let Some(every_nth_action) = self.every_nth_action.filter(|&n| n > 0) else {
+            return false;
+        };
+        self.actions += 1;
+        if self.actions >= every_nth_action {
+            self.actions = 0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Adds a written backup & returns the backups that should be removed.
+    pub fn push(&mut self, file: String) -> Vec<String> {
+        self.files.retain(|f| *f != file);
+        self.files.push_back(file);
+        let remove = self.files.len().saturating_sub(self.max_backups.max(1));
+        self.files.drain(..remove).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::seq::SliceRandom;
+
+    use super::{MapBackups, SnapshotDownload, split_into_chunks};
+
+    #[test]
+    fn chunk_reassembly() {
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+
+        let mut chunks = split_into_chunks(3, &data, 1024);
+        assert_eq!(chunks.len(), 10);
+        assert!(chunks.iter().all(|chunk| chunk.count == 10));
+        chunks.shuffle(&mut rand::rng());
+        // a duplicated chunk does not count twice
+        chunks.insert(1, chunks[0].clone());
+
+        let mut download = SnapshotDownload::new(3);
+        assert_eq!(download.progress(), 0.0);
+        // chunks of an older request are ignored
+        for chunk in split_into_chunks(2, &[1, 2, 3], 1024) {
+            assert!(download.add(chunk).unwrap().is_none());
+        }
+        let mut res = None;
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            assert!(res.is_none());
+            res = download.add(chunk).unwrap();
+            if i == 1 {
+                assert_eq!(download.progress(), 0.1);
+            }
+        }
+        assert_eq!(res.unwrap(), data);
+
+        // empty snapshots still finish
+        let mut download = SnapshotDownload::new(0);
+        let chunks = split_into_chunks(0, &[], 1024);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(download.add(chunks[0].clone()).unwrap(), Some(Vec::new()));
+
+        // invalid chunks
+        let mut download = SnapshotDownload::new(1);
+        let mut chunks = split_into_chunks(1, &data, 4096);
+        chunks[1].index = 3;
+        assert!(download.add(chunks[0].clone()).unwrap().is_none());
+        assert!(download.add(chunks[1].clone()).is_err());
+        chunks[2].count = 4;
+        assert!(download.add(chunks[2].clone()).is_err());
+    }
+
+    #[test]
+    fn backup_rotation() {
+        let mut backups = MapBackups {
+            every_nth_action: Some(3),
+            max_backups: 2,
+            ..Default::default()
+        };
+        let due: Vec<_> = (0..7).map(|_| backups.on_committed_action()).collect();
+        assert_eq!(due, [false, false, true, false, false, true, false]);
+
+        assert!(backups.push("a".into()).is_empty());
+        assert!(backups.push("b".into()).is_empty());
+        assert_eq!(backups.push("c".into()), ["a"]);
+        // rewriting an existing backup does not remove another one
+        assert!(backups.push("c".into()).is_empty());
+        assert_eq!(backups.push("d".into()), ["b"]);
+
+        backups.max_backups = 1;
+        assert_eq!(backups.push("e".into()), ["c", "d"]);
+
+        backups.every_nth_action = None;
+        assert!((0..10).all(|_| !backups.on_committed_action()));
+    }
+}
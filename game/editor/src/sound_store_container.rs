@@ -18,7 +18,9 @@ use client_containers::container::{
     load_sound_file_part_and_upload,
 };
 
-use client_containers::container::{Container, ContainerLoad};
+use client_containers::container::{
+    Container, ContainerItemMemory, ContainerItemMemoryUsage, ContainerLoad,
+};
 
 #[derive(Debug, Hiarc, Clone)]
 pub struct SoundStore {
@@ -69,6 +71,17 @@ impl LoadSoundStore {
     }
 }
 
+impl ContainerItemMemoryUsage for LoadSoundStore {
+    fn memory_usage(&self) -> ContainerItemMemory {
+        // the file is kept for the map
+        self.sound.mem.memory_usage()
+            + ContainerItemMemory {
+                gpu_bytes: 0,
+                cpu_bytes: self.file.len(),
+            }
+    }
+}
+
 impl ContainerLoad<SoundStore> for LoadSoundStore {
     fn load(
         item_name: &str,
@@ -0,0 +1,657 @@
+use legacy_map::mapdef_06::{DdraceTileNum, TILE_SWITCHTIMEDOPEN, tile_can_rotate};
+use map::{
+    map::groups::layers::{
+        design::MapLayerTile,
+        physics::MapLayerPhysicsRef,
+        tiles::{MapTileLayerPhysicsTiles, TileBase, TileFlags},
+    },
+    types::NonZeroU16MinusOne,
+};
+
+use crate::{
+    actions::actions::{
+        ActTileLayerReplTilesBase, ActTileLayerReplaceTiles, ActTilePhysicsLayerReplTilesBase,
+        ActTilePhysicsLayerReplaceTiles, EditorAction,
+    },
+    event::EditorEventLayerIndex,
+    map::{EditorLayer, EditorLayerUnionRef},
+};
+
+const TELE_TILES: [u8; 9] = [
+    DdraceTileNum::TeleInEvil as u8,
+    DdraceTileNum::TeleInWeapon as u8,
+    DdraceTileNum::TeleInHook as u8,
+    DdraceTileNum::TeleIn as u8,
+    DdraceTileNum::TeleOut as u8,
+    DdraceTileNum::TeleCheck as u8,
+    DdraceTileNum::TeleCheckOut as u8,
+    DdraceTileNum::TeleCheckIn as u8,
+    DdraceTileNum::TeleCheckInEvil as u8,
+];
+
+const SWITCH_TILES: [u8; 4] = [
+    TILE_SWITCHTIMEDOPEN,
+    DdraceTileNum::SwitchTimedClose as u8,
+    DdraceTileNum::SwitchOpen as u8,
+    DdraceTileNum::SwitchClose as u8,
+];
+
+/// Which extra data a physics tile needs,
+/// tiles can only be replaced by tiles of the same class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhysicsTileClass {
+    /// Collision & game tiles, e.g. hookable or freeze.
+    Game,
+    /// Teleporters, they need a tele number.
+    Tele,
+    /// Speedups, they need a force & an angle.
+    Speedup,
+    /// Tiles that open or close a switch number.
+    Switch,
+    Tune,
+}
+
+impl PhysicsTileClass {
+    pub fn of(index: u8) -> Self {
+        if TELE_TILES.contains(&index) {
+            Self::Tele
+        } else if SWITCH_TILES.contains(&index) {
+            Self::Switch
+        } else if index == DdraceTileNum::Boost as u8 {
+            Self::Speedup
+        } else if index == DdraceTileNum::Tune as u8 {
+            Self::Tune
+        } else {
+            Self::Game
+        }
+    }
+}
+
+/// A found tile in tile coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileOccurrence {
+    pub x: u16,
+    pub y: u16,
+    pub index: u8,
+}
+
+/// The result of replacing tiles in multiple layers.
+#[derive(Debug, Default)]
+pub struct TileReplace {
+    /// All actions should be executed as a single group,
+    /// so they can be undone at once.
+    pub actions: Vec<EditorAction>,
+    pub replaced: usize,
+    /// Tiles of physics layers that can't be replaced by the new index.
+    pub incompatible: usize,
+}
+
+/// A tile layer that is searched.
+#[derive(Debug)]
+pub enum TileReplaceLayer<'a> {
+    Design {
+        index: EditorEventLayerIndex,
+        layer: &'a MapLayerTile,
+    },
+    Physics {
+        layer_index: usize,
+        layer: MapLayerPhysicsRef<'a>,
+        width: NonZeroU16MinusOne,
+        height: NonZeroU16MinusOne,
+    },
+}
+
+impl<'a> TileReplaceLayer<'a> {
+    /// `None` for quad, sound & arbitrary layers.
+    pub fn from_union(layer: &EditorLayerUnionRef<'a>) -> Option<Self> {
+        match *layer {
+            EditorLayerUnionRef::Design {
+                layer: EditorLayer::Tile(layer),
+                group_index,
+                layer_index,
+                is_background,
+                ..
+            } => Some(Self::Design {
+                index: EditorEventLayerIndex {
+                    is_background,
+                    group_index,
+                    layer_index,
+                },
+                layer: &layer.layer,
+            }),
+            EditorLayerUnionRef::Design { .. } => None,
+            EditorLayerUnionRef::Physics {
+                layer,
+                group_attr,
+                layer_index,
+            } => {
+                let layer = layer.layer_ref();
+                (!matches!(layer, MapLayerPhysicsRef::Arbitrary(_))).then_some(Self::Physics {
+                    layer_index,
+                    layer,
+                    width: group_attr.width,
+                    height: group_attr.height,
+                })
+            }
+        }
+    }
+
+    fn width(&self) -> u16 {
+        match self {
+            Self::Design { layer, .. } => layer.attr.width.get(),
+            Self::Physics { width, .. } => width.get(),
+        }
+    }
+
+    fn indices(&self) -> Vec<u8> {
+        fn indices<T: AsRef<TileBase>>(tiles: &[T]) -> Vec<u8> {
+            tiles.iter().map(|tile| tile.as_ref().index).collect()
+        }
+        match self {
+            Self::Design { layer, .. } => indices(&layer.tiles),
+            Self::Physics { layer, .. } => match layer {
+                MapLayerPhysicsRef::Arbitrary(_) => Vec::new(),
+                MapLayerPhysicsRef::Game(layer) | MapLayerPhysicsRef::Front(layer) => {
+                    indices(&layer.tiles)
+                }
+                MapLayerPhysicsRef::Tele(layer) => indices(&layer.base.tiles),
+                MapLayerPhysicsRef::Speedup(layer) => indices(&layer.tiles),
+                MapLayerPhysicsRef::Switch(layer) => indices(&layer.base.tiles),
+                MapLayerPhysicsRef::Tune(layer) => indices(&layer.base.tiles),
+            },
+        }
+    }
+
+    /// Whether tiles of the class can be placed in this layer.
+    fn accepts(&self, class: PhysicsTileClass) -> bool {
+        match self {
+            Self::Design { .. } => true,
+            Self::Physics { layer, .. } => match layer {
+                MapLayerPhysicsRef::Arbitrary(_) => false,
+                MapLayerPhysicsRef::Game(_) | MapLayerPhysicsRef::Front(_) => {
+                    class == PhysicsTileClass::Game
+                }
+                MapLayerPhysicsRef::Tele(_) => class == PhysicsTileClass::Tele,
+                MapLayerPhysicsRef::Speedup(_) => class == PhysicsTileClass::Speedup,
+                // switch layers also contain the doors, freezes etc. of a switch number
+                MapLayerPhysicsRef::Switch(_) => {
+                    class == PhysicsTileClass::Switch || class == PhysicsTileClass::Game
+                }
+                MapLayerPhysicsRef::Tune(_) => class == PhysicsTileClass::Tune,
+            },
+        }
+    }
+
+    /// Whether the tile index `from` can be replaced by `to` in this layer.
+    ///
+    /// Air can always be placed, physics tiles otherwise need the same class.
+    pub fn can_replace(&self, from: u8, to: u8) -> bool {
+        match self {
+            Self::Design { .. } => true,
+            Self::Physics { .. } => {
+                to == 0
+                    || (PhysicsTileClass::of(from) == PhysicsTileClass::of(to)
+                        && self.accepts(PhysicsTileClass::of(to)))
+            }
+        }
+    }
+
+    /// All tiles with one of the indices, ordered by row.
+    pub fn find(&self, indices: &[u8]) -> Vec<TileOccurrence> {
+        let width = self.width() as usize;
+        self.indices()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, index)| indices.contains(index))
+            .map(|(i, index)| TileOccurrence {
+                x: (i % width) as u16,
+                y: (i / width) as u16,
+                index,
+            })
+            .collect()
+    }
+
+    fn replace_tiles<T: AsRef<TileBase> + AsMut<TileBase> + Clone + Default>(
+        tiles: &[T],
+        find: &[u8],
+        can_replace: impl Fn(u8) -> bool,
+        new_tile: impl Fn(&TileBase) -> TileBase,
+        res: &mut TileReplace,
+    ) -> Option<Vec<T>> {
+        let mut new_tiles = tiles.to_vec();
+        let mut replaced = 0;
+        for tile in new_tiles.iter_mut() {
+            let old = *tile.as_ref();
+            let new = new_tile(&old);
+            if !find.contains(&old.index) || old == new {
+                continue;
+            }
+            if !can_replace(old.index) {
+                res.incompatible += 1;
+                continue;
+            }
+            if new.index == 0 {
+                // air has no extra data like tele numbers
+                *tile = T::default();
+            } else {
+                *tile.as_mut() = new;
+            }
+            replaced += 1;
+        }
+        res.replaced += replaced;
+        (replaced > 0).then_some(new_tiles)
+    }
+
+    /// Adds the action that replaces all found tiles of this layer
+    /// by the index `to`.
+    ///
+    /// If `keep_flags` is set, the rotation & mirroring of the old
+    /// tiles is kept.
+    pub fn replace(&self, find: &[u8], to: u8, keep_flags: bool, res: &mut TileReplace) {
+        // physics tiles can only be rotated if the new tile supports it
+        let keep_flags =
+            keep_flags && to != 0 && (matches!(self, Self::Design { .. }) || tile_can_rotate(to));
+        let new_tile = |old: &TileBase| TileBase {
+            index: to,
+            flags: if keep_flags {
+                old.flags & (TileFlags::XFLIP | TileFlags::YFLIP | TileFlags::ROTATE)
+            } else {
+                TileFlags::empty()
+            },
+        };
+        let can_replace = |from: u8| self.can_replace(from, to);
+
+        let action = match self {
+            Self::Design { index, layer } => {
+                Self::replace_tiles(&layer.tiles, find, can_replace, new_tile, res).map(
+                    |new_tiles| {
+                        EditorAction::TileLayerReplaceTiles(ActTileLayerReplaceTiles {
+                            base: ActTileLayerReplTilesBase {
+                                is_background: index.is_background,
+                                group_index: index.group_index,
+                                layer_index: index.layer_index,
+                                old_tiles: layer.tiles.clone(),
+                                new_tiles,
+                                x: 0,
+                                y: 0,
+                                w: layer.attr.width,
+                                h: layer.attr.height,
+                            },
+                        })
+                    },
+                )
+            }
+            Self::Physics {
+                layer_index,
+                layer,
+                width,
+                height,
+            } => {
+                let tiles = match layer {
+                    MapLayerPhysicsRef::Arbitrary(_) => None,
+                    MapLayerPhysicsRef::Game(layer) => {
+                        Self::replace_tiles(&layer.tiles, find, can_replace, new_tile, res).map(
+                            |tiles| {
+                                (
+                                    MapTileLayerPhysicsTiles::Game(layer.tiles.clone()),
+                                    MapTileLayerPhysicsTiles::Game(tiles),
+                                )
+                            },
+                        )
+                    }
+                    MapLayerPhysicsRef::Front(layer) => {
+                        Self::replace_tiles(&layer.tiles, find, can_replace, new_tile, res).map(
+                            |tiles| {
+                                (
+                                    MapTileLayerPhysicsTiles::Front(layer.tiles.clone()),
+                                    MapTileLayerPhysicsTiles::Front(tiles),
+                                )
+                            },
+                        )
+                    }
+                    MapLayerPhysicsRef::Tele(layer) => {
+                        Self::replace_tiles(&layer.base.tiles, find, can_replace, new_tile, res)
+                            .map(|tiles| {
+                                (
+                                    MapTileLayerPhysicsTiles::Tele(layer.base.tiles.clone()),
+                                    MapTileLayerPhysicsTiles::Tele(tiles),
+                                )
+                            })
+                    }
+                    MapLayerPhysicsRef::Speedup(layer) => {
+                        Self::replace_tiles(&layer.tiles, find, can_replace, new_tile, res).map(
+                            |tiles| {
+                                (
+                                    MapTileLayerPhysicsTiles::Speedup(layer.tiles.clone()),
+                                    MapTileLayerPhysicsTiles::Speedup(tiles),
+                                )
+                            },
+                        )
+                    }
+                    MapLayerPhysicsRef::Switch(layer) => {
+                        Self::replace_tiles(&layer.base.tiles, find, can_replace, new_tile, res)
+                            .map(|tiles| {
+                                (
+                                    MapTileLayerPhysicsTiles::Switch(layer.base.tiles.clone()),
+                                    MapTileLayerPhysicsTiles::Switch(tiles),
+                                )
+                            })
+                    }
+                    MapLayerPhysicsRef::Tune(layer) => {
+                        Self::replace_tiles(&layer.base.tiles, find, can_replace, new_tile, res)
+                            .map(|tiles| {
+                                (
+                                    MapTileLayerPhysicsTiles::Tune(layer.base.tiles.clone()),
+                                    MapTileLayerPhysicsTiles::Tune(tiles),
+                                )
+                            })
+                    }
+                };
+                tiles.map(|(old_tiles, new_tiles)| {
+                    EditorAction::TilePhysicsLayerReplaceTiles(ActTilePhysicsLayerReplaceTiles {
+                        base: ActTilePhysicsLayerReplTilesBase {
+                            layer_index: *layer_index,
+                            old_tiles,
+                            new_tiles,
+                            x: 0,
+                            y: 0,
+                            w: *width,
+                            h: *height,
+                        },
+                    })
+                })
+            }
+        };
+        res.actions.extend(action);
+    }
+}
+
+/// Replaces the tiles with one of the `find` indices in all layers
+/// in a single undoable group of actions.
+pub fn replace_tiles(
+    layers: &[TileReplaceLayer<'_>],
+    find: &[u8],
+    to: u8,
+    keep_flags: bool,
+) -> TileReplace {
+    let mut res = TileReplace::default();
+    for layer in layers {
+        layer.replace(find, to, keep_flags, &mut res);
+    }
+    res
+}
+
+/// Parses a list of tile indices like `1, 5, 10-12`,
+/// air is ignored.
+pub fn parse_tile_indices(text: &str) -> Option<Vec<u8>> {
+    let mut res = Vec::new();
+    for part in text
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+    {
+        match part.split_once('-') {
+            Some((from, to)) => {
+                let from: u8 = from.trim().parse().ok()?;
+                let to: u8 = to.trim().parse().ok()?;
+                if from > to {
+                    return None;
+                }
+                res.extend(from..=to);
+            }
+            None => res.push(part.parse().ok()?),
+        }
+    }
+    res.retain(|&index| index != 0);
+    res.sort();
+    res.dedup();
+    (!res.is_empty()).then_some(res)
+}
+
+#[cfg(test)]
+mod test {
+    use base::linked_hash_map_view::FxLinkedHashMap;
+    use legacy_map::mapdef_06::DdraceTileNum;
+    use map::{
+        map::groups::layers::{
+            design::MapLayerTile,
+            physics::{MapLayerPhysics, MapLayerTilePhysicsBase, MapLayerTilePhysicsTele},
+            tiles::{MapTileLayerAttr, MapTileLayerPhysicsTiles, TeleTile, TileBase, TileFlags},
+        },
+        types::NonZeroU16MinusOne,
+    };
+    use math::math::vector::{nffixed, nfvec4};
+
+    use crate::{actions::actions::EditorAction, event::EditorEventLayerIndex};
+
+    use super::{
+        PhysicsTileClass, TileOccurrence, TileReplaceLayer, parse_tile_indices, replace_tiles,
+    };
+
+    fn tile(index: u8, flags: TileFlags) -> TileBase {
+        TileBase { index, flags }
+    }
+
+    fn tile_layer(tiles: Vec<TileBase>) -> MapLayerTile {
+        MapLayerTile {
+            attr: MapTileLayerAttr {
+                width: NonZeroU16MinusOne::new(2).unwrap(),
+                height: NonZeroU16MinusOne::new(2).unwrap(),
+                color: nfvec4::new(
+                    nffixed::from_num(1),
+                    nffixed::from_num(1),
+                    nffixed::from_num(1),
+                    nffixed::from_num(1),
+                ),
+                high_detail: false,
+                color_anim: None,
+                color_anim_offset: time::Duration::ZERO,
+                image_array: None,
+                blend_mode: Default::default(),
+            },
+            tiles,
+            name: Default::default(),
+        }
+    }
+
+    fn design(layer_index: usize, layer: &MapLayerTile) -> TileReplaceLayer<'_> {
+        TileReplaceLayer::Design {
+            index: EditorEventLayerIndex {
+                is_background: true,
+                group_index: 0,
+                layer_index,
+            },
+            layer,
+        }
+    }
+
+    fn physics(layer_index: usize, layer: &MapLayerPhysics) -> TileReplaceLayer<'_> {
+        TileReplaceLayer::Physics {
+            layer_index,
+            layer: layer.as_ref(),
+            width: NonZeroU16MinusOne::new(2).unwrap(),
+            height: NonZeroU16MinusOne::new(2).unwrap(),
+        }
+    }
+
+    fn tele(index: DdraceTileNum, number: u8) -> TeleTile {
+        TeleTile {
+            base: TileBase {
+                index: index as u8,
+                flags: Default::default(),
+            },
+            number,
+        }
+    }
+
+    #[test]
+    fn parse_indices() {
+        assert_eq!(
+            parse_tile_indices("1, 5,10-12"),
+            Some(vec![1, 5, 10, 11, 12])
+        );
+        assert_eq!(parse_tile_indices("3,3, 0"), Some(vec![3]));
+        assert_eq!(parse_tile_indices(""), None);
+        assert_eq!(parse_tile_indices("0"), None);
+        assert_eq!(parse_tile_indices("5-1"), None);
+        assert_eq!(parse_tile_indices("256"), None);
+    }
+
+    #[test]
+    fn replace_across_layers() {
+        let rotated = TileFlags::ROTATE | TileFlags::XFLIP;
+        let a = tile_layer(vec![
+            tile(1, rotated),
+            tile(2, Default::default()),
+            tile(0, Default::default()),
+            tile(4, Default::default()),
+        ]);
+        let b = tile_layer(vec![
+            tile(4, Default::default()),
+            tile(4, Default::default()),
+            tile(1, Default::default()),
+            tile(4, Default::default()),
+        ]);
+        let untouched = tile_layer(vec![tile(4, Default::default()); 4]);
+        let game = MapLayerPhysics::Game(MapLayerTilePhysicsBase {
+            tiles: vec![
+                tile(DdraceTileNum::Solid as u8, Default::default()),
+                tile(DdraceTileNum::Freeze as u8, rotated),
+                tile(0, Default::default()),
+                tile(0, Default::default()),
+            ],
+        });
+        let layers = [
+            design(0, &a),
+            design(1, &b),
+            design(2, &untouched),
+            physics(0, &game),
+        ];
+
+        assert_eq!(
+            layers[0].find(&[1, 2]),
+            vec![
+                TileOccurrence {
+                    x: 0,
+                    y: 0,
+                    index: 1
+                },
+                TileOccurrence {
+                    x: 1,
+                    y: 0,
+                    index: 2
+                },
+            ]
+        );
+        assert_eq!(
+            layers[1].find(&[1, 2]),
+            vec![TileOccurrence {
+                x: 0,
+                y: 1,
+                index: 1
+            }]
+        );
+        assert!(layers[2].find(&[1, 2]).is_empty());
+
+        let res = replace_tiles(&layers, &[1, 2], 3, true);
+        // the solid game tile is replaced, too
+        assert_eq!(res.replaced, 4);
+        assert_eq!(res.incompatible, 0);
+        assert_eq!(res.actions.len(), 3);
+
+        let EditorAction::TileLayerReplaceTiles(act) = &res.actions[0] else {
+            panic!("expected a tile layer action");
+        };
+        assert_eq!(act.base.layer_index, 0);
+        assert_eq!(act.base.old_tiles, a.tiles);
+        assert_eq!(
+            act.base.new_tiles,
+            vec![
+                tile(3, rotated),
+                tile(3, Default::default()),
+                tile(0, Default::default()),
+                tile(4, Default::default()),
+            ]
+        );
+        let EditorAction::TileLayerReplaceTiles(act) = &res.actions[1] else {
+            panic!("expected a tile layer action");
+        };
+        assert_eq!(act.base.layer_index, 1);
+        assert_eq!(act.base.new_tiles[2], tile(3, Default::default()));
+
+        let EditorAction::TilePhysicsLayerReplaceTiles(act) = &res.actions[2] else {
+            panic!("expected a physics layer action");
+        };
+        let MapTileLayerPhysicsTiles::Game(tiles) = &act.base.new_tiles else {
+            panic!("expected game tiles");
+        };
+        assert_eq!(tiles[0], tile(3, Default::default()));
+        // not searched
+        assert_eq!(tiles[1], tile(DdraceTileNum::Freeze as u8, rotated));
+    }
+
+    #[test]
+    fn physics_compatibility() {
+        assert_eq!(
+            PhysicsTileClass::of(DdraceTileNum::TeleIn as u8),
+            PhysicsTileClass::Tele
+        );
+        assert_eq!(
+            PhysicsTileClass::of(DdraceTileNum::Solid as u8),
+            PhysicsTileClass::Game
+        );
+
+        let tele_layer = MapLayerPhysics::Tele(MapLayerTilePhysicsTele {
+            base: MapLayerTilePhysicsBase {
+                tiles: vec![
+                    tele(DdraceTileNum::TeleIn, 3),
+                    tele(DdraceTileNum::TeleIn, 4),
+                    tele(DdraceTileNum::TeleOut, 3),
+                    tele(DdraceTileNum::Air, 0),
+                ],
+            },
+            tele_names: FxLinkedHashMap::default(),
+        });
+        let game = MapLayerPhysics::Game(MapLayerTilePhysicsBase {
+            tiles: vec![tile(DdraceTileNum::Solid as u8, Default::default()); 4],
+        });
+        let layers = [physics(0, &game), physics(1, &tele_layer)];
+        let tele_in = DdraceTileNum::TeleIn as u8;
+        let solid = DdraceTileNum::Solid as u8;
+
+        assert!(!layers[1].can_replace(tele_in, solid));
+        assert!(layers[1].can_replace(tele_in, DdraceTileNum::TeleInEvil as u8));
+        assert!(layers[1].can_replace(tele_in, 0));
+        assert!(!layers[0].can_replace(solid, tele_in));
+        assert!(layers[0].can_replace(solid, DdraceTileNum::NoHook as u8));
+
+        // a tele can't become a hookable tile, the game layer still can
+        let res = replace_tiles(&layers, &[tele_in, solid], solid, false);
+        assert_eq!(res.incompatible, 2);
+        assert!(res.actions.is_empty());
+
+        // the numbers of the teles are kept
+        let res = replace_tiles(&layers, &[tele_in], DdraceTileNum::TeleInEvil as u8, false);
+        assert_eq!(res.replaced, 2);
+        let [EditorAction::TilePhysicsLayerReplaceTiles(act)] = res.actions.as_slice() else {
+            panic!("expected a single physics layer action");
+        };
+        assert_eq!(act.base.layer_index, 1);
+        let MapTileLayerPhysicsTiles::Tele(tiles) = &act.base.new_tiles else {
+            panic!("expected tele tiles");
+        };
+        assert_eq!(tiles[0], tele(DdraceTileNum::TeleInEvil, 3));
+        assert_eq!(tiles[1], tele(DdraceTileNum::TeleInEvil, 4));
+        assert_eq!(tiles[2], tele(DdraceTileNum::TeleOut, 3));
+
+        // air resets the tele number
+        let res = replace_tiles(&layers, &[tele_in], 0, false);
+        let [EditorAction::TilePhysicsLayerReplaceTiles(act)] = res.actions.as_slice() else {
+            panic!("expected a single physics layer action");
+        };
+        let MapTileLayerPhysicsTiles::Tele(tiles) = &act.base.new_tiles else {
+            panic!("expected tele tiles");
+        };
+        assert_eq!(tiles[0], TeleTile::default());
+    }
+}
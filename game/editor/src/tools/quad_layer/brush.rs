@@ -45,6 +45,9 @@ pub struct QuadBrushQuads {
     pub quads: Vec<Quad>,
     pub w: f32,
     pub h: f32,
+    /// The world position the quads were selected at,
+    /// the quads are relative to it.
+    pub origin: vec2,
 
     pub render: QuadLayerVisuals,
     pub map_render: MapGraphics,
@@ -225,6 +228,7 @@ impl QuadBrush {
                     quads,
                     w: x1 - x0,
                     h: y1 - y0,
+                    origin: pointer_down,
                     render,
                     map_render: MapGraphics::new(backend_handle),
                     texture: layer
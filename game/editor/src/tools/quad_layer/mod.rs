@@ -1,3 +1,4 @@
 pub mod brush;
+pub mod onion_skin;
 pub mod selection;
 pub mod shared;
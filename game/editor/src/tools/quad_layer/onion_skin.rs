@@ -0,0 +1,458 @@
+use std::time::Duration;
+
+use camera::CameraInterface;
+use client_render_base::map::render_tools::RenderTools;
+use graphics::handles::{
+    canvas::canvas::GraphicsCanvasHandle, stream::stream::GraphicsStreamHandle,
+    stream_types::StreamedLine,
+};
+use graphics_types::rendering::State;
+use map::map::{
+    animations::{AnimPointCurveType, AnimPointPos, PosAnimation},
+    groups::{MapGroupAttr, layers::design::Quad},
+};
+use math::math::{
+    distance,
+    vector::{fvec2, fvec3, ubvec4, vec2},
+};
+
+use crate::map::{
+    EditorLayer, EditorLayerUnionRef, EditorMap, EditorMapInterface, EditorOnionSkin,
+};
+
+use super::shared::{QuadSelectionQuads, apply_pos_anim};
+
+/// How many lines the motion path between two keyframes is made of.
+pub const PATH_SEGMENTS_PER_KEYFRAME: usize = 16;
+
+/// The time inside the animation that is shown at the given time,
+/// wrapped the same way [`RenderTools::render_eval_anim`] does.
+pub fn anim_local_time(
+    anim_points: &[AnimPointPos],
+    time: Duration,
+    include_last_point: bool,
+) -> Duration {
+    let (Some(first), Some(last)) = (anim_points.first(), anim_points.last()) else {
+        return Duration::ZERO;
+    };
+    let len = last.time.saturating_sub(first.time).as_nanos();
+    if len == 0 {
+        return first.time;
+    }
+    let len = if include_last_point { len + 1 } else { len };
+    first.time + Duration::from_nanos((time.as_nanos() % len) as u64)
+}
+
+/// Evaluates the animation at a time inside of it,
+/// with the evaluation the map rendering uses.
+fn eval_anim(anim_points: &[AnimPointPos], time: Duration) -> fvec3 {
+    let first = anim_points
+        .first()
+        .map(|point| point.time)
+        .unwrap_or_default();
+    RenderTools::render_eval_anim(
+        anim_points,
+        time::Duration::try_from(time.saturating_sub(first)).unwrap(),
+        true,
+    )
+}
+
+fn anim_offset(value: &fvec3) -> vec2 {
+    vec2::new(value.x.to_num(), value.y.to_num())
+}
+
+fn to_vec2(point: &fvec2) -> vec2 {
+    vec2::new(point.x.to_num(), point.y.to_num())
+}
+
+/// The positions the animation passes, relative to the animated quad.
+///
+/// Every keyframe is a sample, in between [`PATH_SEGMENTS_PER_KEYFRAME`] lines
+/// follow the curve.
+pub fn sample_motion_path(anim_points: &[AnimPointPos]) -> Vec<vec2> {
+    let mut path: Vec<vec2> = anim_points
+        .windows(2)
+        .flat_map(|points| {
+            let (start, len) = (
+                points[0].time,
+                points[1].time.saturating_sub(points[0].time),
+            );
+            (0..PATH_SEGMENTS_PER_KEYFRAME).map(move |segment| {
+                start + len * segment as u32 / PATH_SEGMENTS_PER_KEYFRAME as u32
+            })
+        })
+        .map(|time| anim_offset(&eval_anim(anim_points, time)))
+        .collect();
+    if let Some(last) = anim_points.last() {
+        path.push(anim_offset(&eval_anim(anim_points, last.time)));
+    }
+    path
+}
+
+/// The bezier handles of the keyframes as lines from the keyframe
+/// to the tip of the handle, relative to the animated quad.
+pub fn bezier_handles(anim_points: &[AnimPointPos]) -> Vec<[vec2; 2]> {
+    anim_points
+        .windows(2)
+        .filter_map(|points| match &points[0].curve_type {
+            AnimPointCurveType::Bezier(beziers) => {
+                let [x, y, _] = beziers.value;
+                let start = anim_offset(&points[0].value);
+                let end = anim_offset(&points[1].value);
+                Some([
+                    [
+                        start,
+                        start + vec2::new(x.out_tangent.y.to_num(), y.out_tangent.y.to_num()),
+                    ],
+                    [
+                        end,
+                        end + vec2::new(x.in_tangent.y.to_num(), y.in_tangent.y.to_num()),
+                    ],
+                ])
+            }
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
+
+/// The quad at one of the keyframes around the current time.
+#[derive(Debug, Clone, Copy)]
+pub struct OnionSkinGhost {
+    /// The index of the keyframe in the animation.
+    pub point_index: usize,
+    pub points: [fvec2; 5],
+    /// `false` for keyframes before the current time.
+    pub is_next: bool,
+    /// How many keyframes are between the ghost and the current time.
+    pub distance: usize,
+}
+
+impl OnionSkinGhost {
+    /// Farther ghosts fade out.
+    fn alpha(&self, onion_skin: &EditorOnionSkin) -> u8 {
+        let fade = (onion_skin.count - self.distance.min(onion_skin.count)) as f32
+            / onion_skin.count.max(1) as f32;
+        (onion_skin.opacity * fade * 255.0)
+            .round()
+            .clamp(0.0, 255.0) as u8
+    }
+}
+
+/// The ghosts of the quad at up to `count` keyframes before
+/// & after the time inside the animation.
+pub fn onion_skin_ghosts(
+    quad: &Quad,
+    anim_points: &[AnimPointPos],
+    time: Duration,
+    count: usize,
+) -> Vec<OnionSkinGhost> {
+    let ghost = |is_next: bool| {
+        move |(distance, (point_index, point)): (usize, (usize, &AnimPointPos))| {
+            let mut points = quad.points;
+            apply_pos_anim(&mut points, &eval_anim(anim_points, point.time));
+            OnionSkinGhost {
+                point_index,
+                points,
+                is_next,
+                distance,
+            }
+        }
+    };
+    let prev = anim_points
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, point)| point.time < time)
+        .take(count)
+        .enumerate()
+        .map(ghost(false));
+    let next = anim_points
+        .iter()
+        .enumerate()
+        .filter(|(_, point)| point.time > time)
+        .take(count)
+        .enumerate()
+        .map(ghost(true));
+    prev.chain(next).collect()
+}
+
+/// The selected quads that use the position animation
+/// that is edited in the animation panel.
+struct OnionSkinQuads<'a> {
+    onion_skin: EditorOnionSkin,
+    anim: &'a PosAnimation,
+    quads: Vec<&'a Quad>,
+    group_attr: &'a MapGroupAttr,
+    time: Duration,
+}
+
+impl<'a> OnionSkinQuads<'a> {
+    fn new(map: &'a EditorMap, range: &QuadSelectionQuads) -> Option<Self> {
+        let onion_skin = map
+            .user
+            .options
+            .onion_skin
+            .filter(|_| map.user.ui_values.animations_panel_open)?;
+        let (anim_index, anim, _) = map.animations.user.active_anims.pos.as_ref()?;
+        let Some(EditorLayerUnionRef::Design {
+            layer: EditorLayer::Quad(layer),
+            group,
+            ..
+        }) = map.active_layer()
+        else {
+            return None;
+        };
+        let quads: Vec<_> = range
+            .quads
+            .keys()
+            .filter_map(|index| layer.layer.quads.get(*index))
+            .filter(|quad| quad.pos_anim == Some(*anim_index))
+            .collect();
+        (!quads.is_empty()).then(|| Self {
+            onion_skin,
+            anim,
+            quads,
+            group_attr: &group.attr,
+            time: anim_local_time(
+                &anim.points,
+                map.user.render_time(),
+                map.user.include_last_anim_point(),
+            ),
+        })
+    }
+
+    fn ghosts(&self) -> impl Iterator<Item = OnionSkinGhost> + '_ {
+        self.quads.iter().flat_map(|quad| {
+            onion_skin_ghosts(quad, &self.anim.points, self.time, self.onion_skin.count)
+        })
+    }
+}
+
+/// The keyframe of the ghost whose center is in the radius around the position.
+pub fn onion_skin_keyframe_at(
+    map: &EditorMap,
+    range: &QuadSelectionQuads,
+    pos: &vec2,
+    radius: f32,
+) -> Option<usize> {
+    let quads = OnionSkinQuads::new(map, range)?;
+    quads
+        .ghosts()
+        .find(|ghost| distance(&to_vec2(&ghost.points[4]), pos) < radius)
+        .map(|ghost| ghost.point_index)
+}
+
+/// Renders the ghosts of the selected quads at the previous & next keyframes,
+/// their motion path & the bezier handles of the keyframes.
+pub fn render_onion_skin(
+    canvas_handle: &GraphicsCanvasHandle,
+    stream_handle: &GraphicsStreamHandle,
+    map: &EditorMap,
+    range: &QuadSelectionQuads,
+) {
+    let Some(quads) = OnionSkinQuads::new(map, range) else {
+        return;
+    };
+
+    let mut lines: Vec<StreamedLine> = Vec::new();
+    for ghost in quads.ghosts() {
+        let alpha = ghost.alpha(&quads.onion_skin);
+        let color = if ghost.is_next {
+            ubvec4::new(100, 255, 100, alpha)
+        } else {
+            ubvec4::new(255, 100, 100, alpha)
+        };
+        let line = StreamedLine::new().with_color(color);
+        let [top_left, top_right, bottom_left, bottom_right, _] = ghost.points.map(|p| to_vec2(&p));
+        lines.extend(
+            [
+                [top_left, top_right],
+                [top_right, bottom_right],
+                [bottom_right, bottom_left],
+                [bottom_left, top_left],
+            ]
+            .map(|pos| line.from_pos(pos)),
+        );
+    }
+
+    let path = sample_motion_path(&quads.anim.points);
+    let handles = bezier_handles(&quads.anim.points);
+    let alpha = (quads.onion_skin.opacity * 255.0).round().clamp(0.0, 255.0) as u8;
+    let path_line = StreamedLine::new().with_color(ubvec4::new(255, 255, 255, alpha));
+    let handle_line = StreamedLine::new().with_color(ubvec4::new(255, 200, 0, alpha));
+    for quad in &quads.quads {
+        let center = to_vec2(&quad.points[4]);
+        lines.extend(
+            path.windows(2)
+                .map(|points| path_line.from_pos([center + points[0], center + points[1]])),
+        );
+        lines.extend(
+            handles
+                .iter()
+                .map(|[start, end]| handle_line.from_pos([center + *start, center + *end])),
+        );
+    }
+
+    let mut state = State::new();
+    map.game_camera()
+        .project(canvas_handle, &mut state, Some(quads.group_attr));
+    stream_handle.render_lines(&lines, state);
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use client_render_base::map::render_tools::RenderTools;
+    use map::map::{
+        animations::{AnimBezier, AnimBezierPoint, AnimBeziers, AnimPointCurveType, AnimPointPos},
+        groups::layers::design::Quad,
+    };
+    use math::math::vector::{ffixed, fvec2, fvec3, vec2};
+
+    use super::{
+        PATH_SEGMENTS_PER_KEYFRAME, anim_local_time, bezier_handles, onion_skin_ghosts,
+        sample_motion_path,
+    };
+
+    fn point(secs: u64, x: f32, y: f32, curve_type: AnimPointCurveType<3>) -> AnimPointPos {
+        AnimPointPos {
+            time: Duration::from_secs(secs),
+            curve_type,
+            value: fvec3::new(
+                ffixed::from_num(x),
+                ffixed::from_num(y),
+                ffixed::from_num(0),
+            ),
+        }
+    }
+
+    fn anim_points() -> Vec<AnimPointPos> {
+        let bezier = |y: f32| AnimBezier {
+            out_tangent: AnimBezierPoint {
+                x: Duration::from_millis(200),
+                y: ffixed::from_num(y),
+            },
+            in_tangent: AnimBezierPoint {
+                x: Duration::from_millis(200),
+                y: ffixed::from_num(-y),
+            },
+        };
+        vec![
+            point(0, 0.0, 0.0, AnimPointCurveType::Linear),
+            point(
+                1,
+                64.0,
+                0.0,
+                AnimPointCurveType::Bezier(AnimBeziers {
+                    value: [bezier(16.0), bezier(32.0), bezier(0.0)],
+                }),
+            ),
+            point(2, 64.0, 64.0, AnimPointCurveType::Smooth),
+            point(4, 0.0, 64.0, AnimPointCurveType::Linear),
+        ]
+    }
+
+    fn eval(points: &[AnimPointPos], time: Duration) -> vec2 {
+        let value = RenderTools::render_eval_anim(points, time.try_into().unwrap(), true);
+        vec2::new(value.x.to_num(), value.y.to_num())
+    }
+
+    #[test]
+    fn motion_path() {
+        let points = anim_points();
+        let path = sample_motion_path(&points);
+        assert_eq!(
+            path.len(),
+            (points.len() - 1) * PATH_SEGMENTS_PER_KEYFRAME + 1
+        );
+
+        // what the path shows is what the animation plays
+        for (segment, window) in points.windows(2).enumerate() {
+            let len = window[1].time - window[0].time;
+            for step in 0..PATH_SEGMENTS_PER_KEYFRAME {
+                let time = window[0].time + len * step as u32 / PATH_SEGMENTS_PER_KEYFRAME as u32;
+                assert_eq!(
+                    path[segment * PATH_SEGMENTS_PER_KEYFRAME + step],
+                    eval(&points, time)
+                );
+            }
+        }
+        // the keyframes are part of the path
+        for (index, point) in points.iter().enumerate() {
+            assert_eq!(
+                path[index * PATH_SEGMENTS_PER_KEYFRAME],
+                vec2::new(point.value.x.to_num(), point.value.y.to_num())
+            );
+        }
+        // linear curves are straight lines
+        assert_eq!(path[PATH_SEGMENTS_PER_KEYFRAME / 2], vec2::new(32.0, 0.0));
+    }
+
+    #[test]
+    fn ghosts_and_handles() {
+        let points = anim_points();
+        let quad = Quad {
+            points: [
+                fvec2::new(ffixed::from_num(-16), ffixed::from_num(-16)),
+                fvec2::new(ffixed::from_num(16), ffixed::from_num(-16)),
+                fvec2::new(ffixed::from_num(-16), ffixed::from_num(16)),
+                fvec2::new(ffixed::from_num(16), ffixed::from_num(16)),
+                fvec2::new(ffixed::from_num(0), ffixed::from_num(0)),
+            ],
+            pos_anim: Some(0),
+            ..Default::default()
+        };
+
+        // wraps like the animation does
+        assert_eq!(
+            anim_local_time(&points, Duration::from_millis(5500), false),
+            Duration::from_millis(1500)
+        );
+        assert_eq!(
+            anim_local_time(&points, Duration::from_secs(4), true),
+            Duration::from_secs(4)
+        );
+
+        let time = Duration::from_millis(1500);
+        let ghosts = onion_skin_ghosts(&quad, &points, time, 1);
+        assert_eq!(ghosts.len(), 2);
+        assert_eq!(
+            (ghosts[0].point_index, ghosts[0].is_next, ghosts[0].distance),
+            (1, false, 0)
+        );
+        assert_eq!(
+            (ghosts[1].point_index, ghosts[1].is_next, ghosts[1].distance),
+            (2, true, 0)
+        );
+        // the ghost centers are at the evaluated keyframe positions
+        for ghost in &ghosts {
+            let center = vec2::new(ghost.points[4].x.to_num(), ghost.points[4].y.to_num());
+            assert_eq!(center, eval(&points, points[ghost.point_index].time));
+        }
+
+        let ghosts = onion_skin_ghosts(&quad, &points, time, 3);
+        let prev: Vec<_> = ghosts
+            .iter()
+            .filter(|ghost| !ghost.is_next)
+            .map(|ghost| ghost.point_index)
+            .collect();
+        let next: Vec<_> = ghosts
+            .iter()
+            .filter(|ghost| ghost.is_next)
+            .map(|ghost| ghost.point_index)
+            .collect();
+        assert_eq!(prev, [1, 0]);
+        assert_eq!(next, [2, 3]);
+
+        // only the bezier keyframe has handles
+        assert_eq!(
+            bezier_handles(&points),
+            [
+                [vec2::new(64.0, 0.0), vec2::new(80.0, 32.0)],
+                [vec2::new(64.0, 64.0), vec2::new(48.0, 32.0)],
+            ]
+        );
+    }
+}
@@ -10,7 +10,7 @@ use map::map::groups::layers::design::Quad;
 use math::math::vector::{dvec2, ffixed, ubvec4, vec2};
 
 use crate::{
-    actions::actions::{ActChangeQuadAttr, EditorAction},
+    actions::actions::{ActAddRemPosAnim, ActChangeQuadAttr, ActReplPosAnim, EditorAction},
     client::EditorClient,
     map::{EditorLayer, EditorLayerUnionRef, EditorMap, EditorMapInterface},
     tools::{
@@ -20,8 +20,11 @@ use crate::{
     utils::{UiCanvasSize, ui_pos_to_world_pos, ui_pos_to_world_pos_and_world_height},
 };
 
-use super::shared::{
-    QUAD_POINT_RADIUS_FACTOR, QuadPointerDownPoint, QuadSelectionQuads, render_quad_points,
+use super::{
+    onion_skin::{onion_skin_keyframe_at, render_onion_skin},
+    shared::{
+        QUAD_POINT_RADIUS_FACTOR, QuadPointerDownPoint, QuadSelectionQuads, render_quad_points,
+    },
 };
 
 #[derive(Debug, Hiarc)]
@@ -35,6 +38,11 @@ pub enum QuadPointerDownState {
     },
     /// selection of quads
     Selection(vec2),
+    /// keyframe of the onion skin
+    Keyframe {
+        point_index: usize,
+        cursor_in_world_pos: vec2,
+    },
 }
 
 impl QuadPointerDownState {
@@ -316,6 +324,31 @@ impl QuadSelection {
                     }
                 }
             }
+        } else if let Some(QuadPointerDownState::Keyframe {
+            point_index,
+            cursor_in_world_pos,
+        }) = is_primary_allowed_down.then_some(&mut self.pointer_down_state)
+        {
+            let new_pos = vec2::new(x, y);
+            let diff = new_pos - *cursor_in_world_pos;
+            *cursor_in_world_pos = new_pos;
+
+            if let Some((index, anim, _)) = &mut map.animations.user.active_anims.pos
+                && let Some(point) = anim.points.get_mut(*point_index)
+                && (diff.x != 0.0 || diff.y != 0.0)
+            {
+                point.value.x += ffixed::from_num(diff.x);
+                point.value.y += ffixed::from_num(diff.y);
+                client.execute(
+                    EditorAction::ReplPosAnim(ActReplPosAnim {
+                        base: ActAddRemPosAnim {
+                            index: *index,
+                            anim: anim.clone(),
+                        },
+                    }),
+                    Some(&format!("pos-anim-repl-anim-{index}")),
+                );
+            }
         } else {
             // check if the pointer clicked on one of the quad corner/center points
             let mut clicked_quad_point = false;
@@ -373,6 +406,37 @@ impl QuadSelection {
                     }
                 }
 
+                // the ghosts of the onion skin select & move their keyframe
+                if !clicked_quad_point && is_primary_allowed_pressed {
+                    let (pointer_cur, h) = ui_pos_to_world_pos_and_world_height(
+                        canvas_handle,
+                        ui_canvas,
+                        map.groups.user.zoom,
+                        pointer_cur,
+                        map.groups.user.pos.x,
+                        map.groups.user.pos.y,
+                        offset.x,
+                        offset.y,
+                        parallax.x,
+                        parallax.y,
+                        map.groups.user.parallax_aware_zoom,
+                    );
+                    let radius =
+                        QUAD_POINT_RADIUS_FACTOR * h / canvas_handle.canvas_height() as f32;
+                    if let Some(point_index) =
+                        onion_skin_keyframe_at(map, range, &pointer_cur, radius)
+                    {
+                        clicked_quad_point = true;
+                        if let Some((_, _, props)) = &mut map.animations.user.active_anims.pos {
+                            props.selected_points = [point_index].into();
+                        }
+                        self.pointer_down_state = QuadPointerDownState::Keyframe {
+                            point_index,
+                            cursor_in_world_pos: vec2::new(x, y),
+                        };
+                    }
+                }
+
                 if !clicked_quad_point && latest_pointer.secondary_pressed() {
                     self.range = None;
                     self.pointer_down_state = QuadPointerDownState::None;
@@ -552,6 +616,9 @@ impl QuadSelection {
             );
         } else {
             self.render_brush(canvas_handle, stream_handle, map);
+            if let Some(range) = &self.range {
+                render_onion_skin(canvas_handle, stream_handle, map, range);
+            }
         }
     }
 }
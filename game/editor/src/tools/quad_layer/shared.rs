@@ -10,7 +10,7 @@ use graphics::handles::{
 use graphics_types::rendering::State;
 use hiarc::{Hiarc, hi_closure};
 use map::map::groups::layers::design::Quad;
-use math::math::vector::{ffixed, fvec2, nffixed, nfvec4, ubvec4, vec2};
+use math::math::vector::{ffixed, fvec2, fvec3, nffixed, nfvec4, ubvec4, vec2};
 
 use crate::{
     map::{EditorLayer, EditorLayerQuad, EditorLayerUnionRef, EditorMap, EditorMapInterface},
@@ -64,6 +64,18 @@ pub fn in_box(pos: &fvec2, x0: f32, y0: f32, x1: f32, y1: f32) -> bool {
         && pos.y.to_num::<f32>() < y1
 }
 
+/// Rotates & moves the quad points by the value of a position animation.
+pub fn apply_pos_anim(points: &mut [fvec2; 5], anim_pos: &fvec3) {
+    let rot = anim_pos.z / ffixed::from_num(360.0) * ffixed::PI * ffixed::from_num(2.0);
+    let center = points[4];
+
+    rotate(&center, rot, points);
+
+    for point in points.iter_mut() {
+        *point += fvec2::new(ffixed::from_num(anim_pos.x), ffixed::from_num(anim_pos.y));
+    }
+}
+
 pub fn get_quad_points_animated(quad: &Quad, map: &EditorMap, time: Duration) -> [fvec2; 5] {
     let mut points = quad.points;
     if let Some(pos_anim) = quad.pos_anim {
@@ -73,14 +85,7 @@ pub fn get_quad_points_animated(quad: &Quad, map: &EditorMap, time: Duration) ->
             time::Duration::try_from(time).unwrap(),
             map.user.include_last_anim_point(),
         );
-        let rot = anim_pos.z / ffixed::from_num(360.0) * ffixed::PI * ffixed::from_num(2.0);
-        let center = points[4];
-
-        rotate(&center, rot, &mut points);
-
-        for point in points.iter_mut() {
-            *point += fvec2::new(ffixed::from_num(anim_pos.x), ffixed::from_num(anim_pos.y));
-        }
+        apply_pos_anim(&mut points, &anim_pos);
     }
     points
 }
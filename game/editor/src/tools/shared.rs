@@ -4,7 +4,7 @@ use math::math::{
     vector::{ffixed, fvec2, vec2},
 };
 
-use crate::map::EditorMap;
+use crate::{guides::GUIDE_SNAP_DISTANCE, map::EditorMap};
 
 pub fn in_radius(pos1: &fvec2, pos2: &vec2, radius: f32) -> bool {
     distance(&vec2::new(pos1.x.to_num(), pos1.y.to_num()), pos2) < radius
@@ -24,22 +24,40 @@ pub fn rotate(center: &fvec2, rotation: ffixed, points: &mut [fvec2]) {
     }
 }
 
-pub fn align_pos(map: &EditorMap, modifiers: &Modifiers, mut pos: vec2) -> Option<vec2> {
-    if let Some(grid_size) = modifiers
-        .alt
-        .then_some(map.user.options.render_grid)
-        .flatten()
-    {
+/// Snaps the position to the nearest grid point and to guides that are
+/// close enough, if `alt` is held.
+///
+/// Guides take precedence over the grid.
+pub fn align_pos(map: &EditorMap, modifiers: &Modifiers, pos: vec2) -> Option<vec2> {
+    if !modifiers.alt {
+        return None;
+    }
+    let grid_pos = map.user.options.render_grid.map(|grid_size| {
         let grid_size = grid_size as f32;
         fn round_mod(v: f32, rhs: f32) -> f32 {
             let r = v.rem_euclid(rhs);
 
             if r <= rhs / 2.0 { -r } else { rhs - r }
         }
-        pos.x += round_mod(pos.x, grid_size);
-        pos.y += round_mod(pos.y, grid_size);
-        Some(pos)
-    } else {
-        None
+        vec2::new(
+            pos.x + round_mod(pos.x, grid_size),
+            pos.y + round_mod(pos.y, grid_size),
+        )
+    });
+    let (guide_x, guide_y) = map
+        .user
+        .options
+        .guides
+        .snap(&pos, GUIDE_SNAP_DISTANCE * map.groups.user.zoom);
+    if grid_pos.is_none() && guide_x.is_none() && guide_y.is_none() {
+        return None;
+    }
+    let mut pos = grid_pos.unwrap_or(pos);
+    if let Some(x) = guide_x {
+        pos.x = x;
+    }
+    if let Some(y) = guide_y {
+        pos.y = y;
     }
+    Some(pos)
 }
@@ -28,6 +28,9 @@ pub struct SoundBrushSounds {
     pub sounds: Vec<Sound>,
     pub w: f32,
     pub h: f32,
+    /// The world position the sounds were selected at,
+    /// the sounds are relative to it.
+    pub origin: vec2,
 }
 
 #[derive(Debug, Hiarc)]
@@ -180,6 +183,7 @@ impl SoundBrush {
                     sounds,
                     w: x1 - x0,
                     h: y1 - y0,
+                    origin: pointer_down,
                 });
             } else {
                 self.brush = None;
@@ -0,0 +1,420 @@
+use std::fmt::Write;
+
+use graphics::handles::{
+    canvas::canvas::GraphicsCanvasHandle, stream::stream::GraphicsStreamHandle,
+    stream_types::StreamedLine,
+};
+use hiarc::Hiarc;
+use math::math::{
+    length,
+    vector::{ubvec4, vec2},
+};
+use vanilla::{collision::collision::Tunings, entities::entity::entity::calc_pos_and_vel};
+
+use crate::{
+    map::{EditorLayerUnionRef, EditorMap, EditorMapInterface},
+    tools::utils::render_rect_state,
+    utils::{UiCanvasSize, ui_pos_to_world_pos},
+};
+
+use super::{selection::TileBrushDownPos, shared::TILE_VISUAL_SIZE};
+
+/// The size of a tile in the units the game physics use.
+pub const TILE_PHYSICS_SIZE: f32 = 32.0;
+/// How many lines the grenade arc is made of.
+const GRENADE_ARC_SEGMENTS: usize = 32;
+
+/// A line between two tile centers, in the coordinates of the physics group.
+#[derive(Debug, Hiarc, Clone, Copy, PartialEq)]
+pub struct TileMeasurement {
+    pub start: vec2,
+    pub end: vec2,
+}
+
+impl TileMeasurement {
+    pub fn delta(&self) -> vec2 {
+        self.end - self.start
+    }
+
+    /// The straight distance in tiles.
+    pub fn length(&self) -> f32 {
+        length(&self.delta())
+    }
+
+    /// The angle in degrees, counter-clockwise from the right
+    /// like on a unit circle, in `(-180, 180]`.
+    pub fn angle(&self) -> f32 {
+        // the y axis points down
+        (self.start.y - self.end.y)
+            .atan2(self.end.x - self.start.x)
+            .to_degrees()
+    }
+
+    /// The direction the measurement points to in the physics,
+    /// `None` if start & end are the same.
+    fn physics_dir(&self) -> Option<(vec2, f32)> {
+        let delta = self.delta() * TILE_PHYSICS_SIZE;
+        let len = length(&delta);
+        (len > 0.0).then(|| (delta / len, len))
+    }
+
+    pub fn label(&self, tunings: Option<&Tunings>) -> String {
+        let delta = self.delta();
+        let mut res = format!(
+            "{:.2} ({:.0}x{:.0}) {:.1}°",
+            self.length(),
+            delta.x.abs(),
+            delta.y.abs(),
+            self.angle()
+        );
+        if let Some(tunings) = tunings {
+            match grenade_estimate(self, tunings) {
+                Some(estimate) if estimate.in_lifetime => {
+                    let _ = write!(
+                        res,
+                        "\ngrenade: {:.2}s, {:.2} below",
+                        estimate.time, estimate.drop
+                    );
+                }
+                _ => res.push_str("\ngrenade: out of reach"),
+            }
+            if self.length() <= laser_reach(tunings) {
+                res.push_str("\nlaser: in reach");
+            } else {
+                res.push_str("\nlaser: out of reach");
+            }
+        }
+        res
+    }
+}
+
+/// A grenade fired from the start of a measurement towards its end.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GrenadeEstimate {
+    /// Seconds until the grenade passes the end horizontally,
+    /// or vertically for vertical measurements.
+    pub time: f32,
+    /// How many tiles the grenade is below the end at that time.
+    pub drop: f32,
+    /// The grenade still exists at that time.
+    pub in_lifetime: bool,
+}
+
+/// Estimates the flight of a grenade with the same math the projectiles
+/// of the game use, see [`calc_pos_and_vel`].
+///
+/// Returns `None` if the grenade never gets there,
+/// e.g. it falls back down before reaching the end of a vertical line.
+pub fn grenade_estimate(
+    measurement: &TileMeasurement,
+    tunings: &Tunings,
+) -> Option<GrenadeEstimate> {
+    let (dir, len) = measurement.physics_dir()?;
+    if tunings.grenade_speed <= 0.0 {
+        return None;
+    }
+    let curvature = tunings.grenade_curvature / 10000.0;
+
+    // the grenade moves by `dir * u` & `curvature * u²` downwards,
+    // where `u` is the time multiplied by the speed.
+    let (u, drop) = if dir.x.abs() > f32::EPSILON {
+        // the horizontal movement is linear, so the end is passed after `len`
+        (len, curvature * len * len / TILE_PHYSICS_SIZE)
+    } else if curvature.abs() <= f32::EPSILON {
+        (len, 0.0)
+    } else {
+        // curvature * u² + dir.y * u - delta.y = 0
+        let delta_y = dir.y * len;
+        let discriminant = dir.y * dir.y + 4.0 * curvature * delta_y;
+        if discriminant < 0.0 {
+            return None;
+        }
+        let sqrt = discriminant.sqrt();
+        let u = [
+            (-dir.y - sqrt) / (2.0 * curvature),
+            (-dir.y + sqrt) / (2.0 * curvature),
+        ]
+        .into_iter()
+        .filter(|u| *u > 0.0)
+        .min_by(f32::total_cmp)?;
+        (u, 0.0)
+    };
+
+    let time = u / tunings.grenade_speed;
+    Some(GrenadeEstimate {
+        time,
+        drop,
+        in_lifetime: time <= tunings.grenade_lifetime,
+    })
+}
+
+/// The positions of a grenade fired along the measurement,
+/// until `time` seconds passed.
+pub fn grenade_arc(measurement: &TileMeasurement, tunings: &Tunings, time: f32) -> Vec<vec2> {
+    let Some((dir, _)) = measurement.physics_dir() else {
+        return Vec::new();
+    };
+    let start = measurement.start * TILE_PHYSICS_SIZE;
+    (0..=GRENADE_ARC_SEGMENTS)
+        .map(|i| {
+            let mut pos = start;
+            let mut vel = dir;
+            calc_pos_and_vel(
+                &mut pos,
+                &mut vel,
+                tunings.grenade_curvature,
+                tunings.grenade_speed,
+                time * i as f32 / GRENADE_ARC_SEGMENTS as f32,
+            );
+            pos / TILE_PHYSICS_SIZE
+        })
+        .collect()
+}
+
+/// The distance in tiles a laser travels before it would bounce.
+pub fn laser_reach(tunings: &Tunings) -> f32 {
+    tunings.laser_reach / TILE_PHYSICS_SIZE
+}
+
+/// Measures distances between tiles.
+///
+/// Every drawn measurement stays visible until the measurements are cleared.
+#[derive(Debug, Hiarc, Default)]
+pub struct TileMeasure {
+    pub pointer_down_state: Option<TileBrushDownPos>,
+    /// The measurement that is currently drawn.
+    pub cur: Option<TileMeasurement>,
+    pub pinned: Vec<TileMeasurement>,
+}
+
+impl TileMeasure {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.pinned.clear();
+    }
+
+    /// All measurements, including the one currently drawn.
+    pub fn measurements(&self) -> impl Iterator<Item = &TileMeasurement> {
+        self.pinned.iter().chain(self.cur.iter())
+    }
+
+    /// The tile center under the pointer, in the coordinates of the physics group.
+    fn pos_on_map(
+        ui_canvas: &UiCanvasSize,
+        canvas_handle: &GraphicsCanvasHandle,
+        map: &EditorMap,
+        current_pointer_pos: &egui::Pos2,
+    ) -> vec2 {
+        let pos = ui_pos_to_world_pos(
+            canvas_handle,
+            ui_canvas,
+            map.groups.user.zoom,
+            vec2::new(current_pointer_pos.x, current_pointer_pos.y),
+            map.groups.user.pos.x,
+            map.groups.user.pos.y,
+            0.0,
+            0.0,
+            100.0,
+            100.0,
+            map.groups.user.parallax_aware_zoom,
+        );
+        vec2::new(
+            (pos.x / TILE_VISUAL_SIZE).floor() + 0.5,
+            (pos.y / TILE_VISUAL_SIZE).floor() + 0.5,
+        ) * TILE_VISUAL_SIZE
+    }
+
+    pub fn update(
+        &mut self,
+        ui_canvas: &UiCanvasSize,
+        canvas_handle: &GraphicsCanvasHandle,
+        map: &EditorMap,
+        latest_pointer: &egui::PointerState,
+        current_pointer_pos: &egui::Pos2,
+    ) {
+        let pos = Self::pos_on_map(ui_canvas, canvas_handle, map, current_pointer_pos);
+        if let Some(TileBrushDownPos { world, .. }) = &self.pointer_down_state {
+            let measurement = TileMeasurement {
+                start: *world,
+                end: pos,
+            };
+            if latest_pointer.primary_down() {
+                self.cur = Some(measurement);
+            } else {
+                self.pointer_down_state = None;
+                self.cur = None;
+                if measurement.length() > 0.0 {
+                    self.pinned.push(measurement);
+                }
+            }
+        } else if latest_pointer.primary_down() {
+            self.pointer_down_state = Some(TileBrushDownPos {
+                world: pos,
+                ui: *current_pointer_pos,
+            });
+        }
+    }
+
+    /// Renders all measurements, on top of all layers.
+    ///
+    /// If a physics layer is active, the flight of grenades & lasers is shown too.
+    pub fn render(
+        &self,
+        canvas_handle: &GraphicsCanvasHandle,
+        stream_handle: &GraphicsStreamHandle,
+        map: &EditorMap,
+    ) {
+        if self.pinned.is_empty() && self.cur.is_none() {
+            return;
+        }
+        let tunings = map
+            .active_layer()
+            .is_some_and(|layer| matches!(layer, EditorLayerUnionRef::Physics { .. }))
+            .then(Tunings::default);
+
+        let state = render_rect_state(
+            canvas_handle,
+            map,
+            &vec2::new(100.0, 100.0),
+            &vec2::default(),
+        );
+
+        let mut lines: Vec<StreamedLine> = Vec::new();
+        for measurement in self.measurements() {
+            let TileMeasurement { start, end } = *measurement;
+
+            // the distance on both axes
+            let axis = StreamedLine::new().with_color(ubvec4::new(255, 255, 255, 100));
+            let corner = vec2::new(end.x, start.y);
+            lines.push(axis.from_pos([start, corner]));
+            lines.push(axis.from_pos([corner, end]));
+
+            if let Some(tunings) = &tunings {
+                let laser = StreamedLine::new().with_color(ubvec4::new(100, 150, 255, 200));
+                let reach = laser_reach(tunings).min(measurement.length());
+                if let Some((dir, _)) = measurement.physics_dir() {
+                    lines.push(laser.from_pos([start, start + dir * reach]));
+                }
+
+                let time = grenade_estimate(measurement, tunings)
+                    .map(|estimate| estimate.time)
+                    .unwrap_or(tunings.grenade_lifetime)
+                    .min(tunings.grenade_lifetime);
+                let grenade = StreamedLine::new().with_color(ubvec4::new(255, 100, 0, 200));
+                let arc = grenade_arc(measurement, tunings, time);
+                lines.extend(
+                    arc.windows(2)
+                        .map(|points| grenade.from_pos([points[0], points[1]])),
+                );
+            }
+
+            lines.push(
+                StreamedLine::new()
+                    .with_color(ubvec4::new(255, 255, 0, 255))
+                    .from_pos([start, end]),
+            );
+        }
+        stream_handle.render_lines(&lines, state);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use math::math::vector::vec2;
+    use vanilla::{collision::collision::Tunings, entities::entity::entity::calc_pos_and_vel};
+
+    use super::{TILE_PHYSICS_SIZE, TileMeasurement, grenade_estimate, laser_reach};
+
+    /// Where the game puts a grenade after `time` seconds.
+    fn grenade_pos(measurement: &TileMeasurement, tunings: &Tunings, time: f32) -> vec2 {
+        let delta = measurement.delta();
+        let mut pos = measurement.start * TILE_PHYSICS_SIZE;
+        let mut vel = delta / math::math::length(&delta);
+        calc_pos_and_vel(
+            &mut pos,
+            &mut vel,
+            tunings.grenade_curvature,
+            tunings.grenade_speed,
+            time,
+        );
+        pos / TILE_PHYSICS_SIZE
+    }
+
+    #[test]
+    fn measurement() {
+        let measurement = TileMeasurement {
+            start: vec2::new(0.5, 0.5),
+            end: vec2::new(3.5, -3.5),
+        };
+        assert_eq!(measurement.length(), 5.0);
+        assert!((measurement.angle() - 53.130_1).abs() < 0.01);
+
+        let left = TileMeasurement {
+            start: vec2::new(4.5, 0.5),
+            end: vec2::new(0.5, 0.5),
+        };
+        assert_eq!(left.angle(), 180.0);
+    }
+
+    #[test]
+    fn grenade_estimate_matches_projectiles() {
+        let tunings = Tunings::default();
+
+        // diagonal & horizontal shots
+        for end in [
+            vec2::new(10.5, -4.5),
+            vec2::new(20.5, 0.5),
+            vec2::new(-6.5, 3.5),
+        ] {
+            let measurement = TileMeasurement {
+                start: vec2::new(0.5, 0.5),
+                end,
+            };
+            let estimate = grenade_estimate(&measurement, &tunings).unwrap();
+            let pos = grenade_pos(&measurement, &tunings, estimate.time);
+            assert!((pos.x - end.x).abs() < 0.001);
+            assert!((pos.y - (end.y + estimate.drop)).abs() < 0.001);
+            assert!(estimate.drop > 0.0);
+            assert!(estimate.in_lifetime);
+        }
+
+        // straight down & a short way up
+        for end in [vec2::new(0.5, 10.5), vec2::new(0.5, -2.5)] {
+            let measurement = TileMeasurement {
+                start: vec2::new(0.5, 0.5),
+                end,
+            };
+            let estimate = grenade_estimate(&measurement, &tunings).unwrap();
+            let pos = grenade_pos(&measurement, &tunings, estimate.time);
+            assert!((pos.y - end.y).abs() < 0.001);
+            assert_eq!(estimate.drop, 0.0);
+        }
+
+        // too high up, the grenade falls back before
+        let measurement = TileMeasurement {
+            start: vec2::new(0.5, 0.5),
+            end: vec2::new(0.5, -100.5),
+        };
+        assert_eq!(grenade_estimate(&measurement, &tunings), None);
+
+        // far away, the grenade explodes before
+        let measurement = TileMeasurement {
+            start: vec2::new(0.5, 0.5),
+            end: vec2::new(200.5, 0.5),
+        };
+        assert!(
+            !grenade_estimate(&measurement, &tunings)
+                .unwrap()
+                .in_lifetime
+        );
+    }
+
+    #[test]
+    fn laser() {
+        let tunings = Tunings::default();
+        assert_eq!(laser_reach(&tunings), 25.0);
+    }
+}
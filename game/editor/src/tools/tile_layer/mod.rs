@@ -1,5 +1,6 @@
 pub mod auto_mapper;
 pub mod brush;
 pub mod legacy_rules;
+pub mod measure;
 pub mod selection;
 pub mod shared;
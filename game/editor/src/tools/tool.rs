@@ -19,13 +19,14 @@ use crate::{client::EditorClient, map::EditorMap, utils::UiCanvasSize};
 use super::{
     quad_layer::{brush::QuadBrush, selection::QuadSelection},
     sound_layer::brush::SoundBrush,
-    tile_layer::{brush::TileBrush, selection::TileSelection},
+    tile_layer::{brush::TileBrush, measure::TileMeasure, selection::TileSelection},
 };
 
 #[derive(Debug, Hiarc)]
 pub struct ToolTileLayer {
     pub brush: TileBrush,
     pub selection: TileSelection,
+    pub measure: TileMeasure,
 }
 
 impl ToolTileLayer {
@@ -75,6 +76,13 @@ impl ToolTileLayer {
                 latest_pointer,
                 current_pointer_pos,
             ),
+            ActiveToolTiles::Measure => self.measure.update(
+                ui_canvas,
+                canvas_handle,
+                map,
+                latest_pointer,
+                current_pointer_pos,
+            ),
         }
     }
 
@@ -125,6 +133,8 @@ impl ToolTileLayer {
                 latest_pointer,
                 current_pointer_pos,
             ),
+            // measurements are rendered for all tools, see `TileMeasure::render`
+            ActiveToolTiles::Measure => {}
         }
     }
 }
@@ -271,6 +281,7 @@ impl ToolSoundLayer {
 pub enum ActiveToolTiles {
     Brush,
     Selection,
+    Measure,
 }
 
 #[derive(Debug, Clone, Copy)]
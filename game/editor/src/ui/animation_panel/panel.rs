@@ -436,6 +436,24 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                     ui.end_row();
                 });
 
+            ui.menu_button("Onion skin", |ui| {
+                ui.label("Show the previous & next keyframes of the selected quads:");
+                let mut has_onion_skin = map.user.options.onion_skin.is_some();
+                ui.checkbox(&mut has_onion_skin, "");
+                if has_onion_skin && map.user.options.onion_skin.is_none() {
+                    map.user.options.onion_skin = Some(Default::default());
+                } else if !has_onion_skin && map.user.options.onion_skin.is_some() {
+                    map.user.options.onion_skin = None;
+                }
+                if let Some(onion_skin) = &mut map.user.options.onion_skin {
+                    ui.add_space(10.0);
+                    ui.label("Keyframes before & after:");
+                    ui.add(egui::DragValue::new(&mut onion_skin.count).range(1..=10));
+                    ui.label("Opacity:");
+                    ui.add(egui::Slider::new(&mut onion_skin.opacity, 0.0..=1.0));
+                }
+            });
+
             // init animations if not done yet
             fn try_init_group<'a, F, T, const CHANNELS: usize>(
                 anims: &'a [AnimBaseSkeleton<EditorAnimationProps, AnimPoint<T, CHANNELS>>],
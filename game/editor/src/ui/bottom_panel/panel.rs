@@ -4,11 +4,15 @@ use math::math::vector::vec2;
 use ui_base::types::{UiRenderPipe, UiState};
 
 use crate::{
-    explain::{ANIMATION_PANEL, SERVER_COMMANDS_CONFIG_VAR},
+    explain::{
+        ANIMATION_PANEL, HISTORY_PANEL, NUMBER_USAGE_PANEL, PARALLAX_PREVIEW,
+        SERVER_COMMANDS_CONFIG_VAR,
+    },
     hotkeys::{
         EditorHotkeyEvent, EditorHotkeyEventPanels, EditorHotkeyEventPreferences,
         EditorHotkeyEventTimeline,
     },
+    parallax_preview::EditorParallaxPreview,
     ui::user_data::{EditorUiEvent, UserDataWithTab},
     utils::ui_pos_to_world_pos,
 };
@@ -119,14 +123,88 @@ fn render_buttons(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
         editor_tab.map.user.ui_values.server_config_variables_open =
             !editor_tab.map.user.ui_values.server_config_variables_open;
     }
+    let by_hotkey = pipe
+        .user_data
+        .cur_hotkey_events
+        .remove(&EditorHotkeyEvent::Panels(
+            EditorHotkeyEventPanels::ToggleHistory,
+        ));
+    if ui
+        .add(Button::new("History").selected(editor_tab.map.user.ui_values.history_panel_open))
+        .on_hover_ui(|ui| {
+            let mut cache = egui_commonmark::CommonMarkCache::default();
+            egui_commonmark::CommonMarkViewer::new().show(
+                ui,
+                &mut cache,
+                &format!(
+                    "{}\n\nHotkey: `{}`",
+                    HISTORY_PANEL,
+                    binds.fmt_ev_bind(
+                        per_ev,
+                        &EditorHotkeyEvent::Panels(EditorHotkeyEventPanels::ToggleHistory),
+                    )
+                ),
+            );
+        })
+        .clicked()
+        || by_hotkey
+    {
+        editor_tab.map.user.ui_values.history_panel_open =
+            !editor_tab.map.user.ui_values.history_panel_open;
+    }
+    let by_hotkey = pipe
+        .user_data
+        .cur_hotkey_events
+        .remove(&EditorHotkeyEvent::Panels(
+            EditorHotkeyEventPanels::ToggleNumberUsage,
+        ));
+    if ui
+        .add(
+            Button::new("Numbers").selected(
+                editor_tab
+                    .map
+                    .user
+                    .ui_values
+                    .number_usage_panel_open
+                    .is_some(),
+            ),
+        )
+        .on_hover_ui(|ui| {
+            let mut cache = egui_commonmark::CommonMarkCache::default();
+            egui_commonmark::CommonMarkViewer::new().show(
+                ui,
+                &mut cache,
+                &format!(
+                    "{}\n\nHotkey: `{}`",
+                    NUMBER_USAGE_PANEL,
+                    binds.fmt_ev_bind(
+                        per_ev,
+                        &EditorHotkeyEvent::Panels(EditorHotkeyEventPanels::ToggleNumberUsage),
+                    )
+                ),
+            );
+        })
+        .clicked()
+        || by_hotkey
+    {
+        let open = &mut editor_tab.map.user.ui_values.number_usage_panel_open;
+        *open = match open {
+            Some(_) => None,
+            None => Some(Default::default()),
+        };
+    }
     let by_hotkey = pipe
         .user_data
         .cur_hotkey_events
         .remove(&EditorHotkeyEvent::Preferences(
             EditorHotkeyEventPreferences::ToggleParallaxZoom,
         ));
+    let in_parallax_preview = editor_tab.map.user.ui_values.parallax_preview.is_some();
     if ui
-        .add(Button::new("Parallax zoom").selected(editor_tab.map.groups.user.parallax_aware_zoom))
+        .add_enabled(
+            !in_parallax_preview,
+            Button::new("Parallax zoom").selected(editor_tab.map.groups.user.parallax_aware_zoom),
+        )
         .on_hover_ui(|ui| {
             let mut cache = egui_commonmark::CommonMarkCache::default();
             egui_commonmark::CommonMarkViewer::new().show(
@@ -144,11 +222,56 @@ fn render_buttons(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
             );
         })
         .clicked()
-        || by_hotkey
+        || (by_hotkey && !in_parallax_preview)
     {
         editor_tab.map.groups.user.parallax_aware_zoom =
             !editor_tab.map.groups.user.parallax_aware_zoom;
     }
+    let by_hotkey = pipe
+        .user_data
+        .cur_hotkey_events
+        .remove(&EditorHotkeyEvent::Preferences(
+            EditorHotkeyEventPreferences::ToggleParallaxPreview,
+        ));
+    if ui
+        .add(Button::new("Parallax preview").selected(in_parallax_preview))
+        .on_hover_ui(|ui| {
+            let mut cache = egui_commonmark::CommonMarkCache::default();
+            egui_commonmark::CommonMarkViewer::new().show(
+                ui,
+                &mut cache,
+                &format!(
+                    "{}\n\nHotkey: `{}`",
+                    PARALLAX_PREVIEW,
+                    binds.fmt_ev_bind(
+                        per_ev,
+                        &EditorHotkeyEvent::Preferences(
+                            EditorHotkeyEventPreferences::ToggleParallaxPreview,
+                        ),
+                    )
+                ),
+            );
+        })
+        .clicked()
+        || by_hotkey
+    {
+        let groups = &mut editor_tab.map.groups.user;
+        match editor_tab.map.user.ui_values.parallax_preview.take() {
+            Some(preview) => preview.leave(groups),
+            None => {
+                editor_tab.map.user.ui_values.parallax_preview =
+                    Some(EditorParallaxPreview::enter(groups));
+            }
+        }
+    }
+    if let Some(preview) = &mut editor_tab.map.user.ui_values.parallax_preview
+        && ui
+            .add(Button::new("Figure-eight").selected(preview.path.is_some()))
+            .on_hover_text("Moves the camera automatically along a figure-eight.")
+            .clicked()
+    {
+        preview.toggle_path(&mut editor_tab.map.groups.user, pipe.cur_time);
+    }
 
     // Editor time
     let increase_by_hotkey =
@@ -284,6 +407,32 @@ fn render_buttons(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
             ),
         );
     });
+
+    // Editor guides
+    ui.menu_button("Guides", |ui| {
+        let guides = &mut editor_tab.map.user.options.guides;
+        let mut changed = ui.checkbox(&mut guides.locked, "Lock guides").changed();
+        ui.label(format!("{} guides", guides.guides.len()));
+        if ui
+            .add_enabled(
+                !guides.locked && !guides.guides.is_empty(),
+                Button::new("Remove all guides"),
+            )
+            .clicked()
+        {
+            guides.guides.clear();
+            changed = true;
+        }
+        if changed {
+            editor_tab.client.set_guides(guides.clone());
+        }
+    })
+    .response
+    .on_hover_text(
+        "Drag guides out of the rulers at the top & left edge of the canvas.\n\
+        Dragging a guide back onto a ruler removes it.\n\
+        Quads & sounds snap to guides while holding `alt`.",
+    );
 }
 
 pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_state: &mut UiState) {
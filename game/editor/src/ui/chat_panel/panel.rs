@@ -1,8 +1,13 @@
-use egui::{Color32, Frame, Key, Layout, ScrollArea, TextEdit, scroll_area::ScrollBarVisibility};
+use egui::{
+    Button, Color32, Frame, Key, Layout, Modifiers, RichText, ScrollArea, Stroke, TextEdit,
+    scroll_area::ScrollBarVisibility,
+    text::{CCursor, CCursorRange},
+};
 use egui_extras::{Size, StripBuilder};
 use ui_base::types::{UiRenderPipe, UiState};
 
 use crate::{
+    chat::{ChatMsgPart, MapPosLink, complete_mention, mention_completions, parse_msg},
     map::EditorChatState,
     ui::user_data::{EditorUiEvent, UserDataWithTab},
 };
@@ -20,6 +25,22 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
     let Some(chat_state) = &mut map.user.ui_values.chat_panel_open else {
         return;
     };
+    let camera = &mut map.groups.user;
+
+    let client = &pipe.user_data.editor_tab.client;
+    let names: Vec<&str> = client
+        .clients
+        .iter()
+        .map(|c| c.mapper_name.as_str())
+        .collect();
+    let completions = mention_completions(
+        &chat_state.msg,
+        client
+            .clients
+            .iter()
+            .filter(|c| c.server_id != client.server_id)
+            .map(|c| c.mapper_name.as_str()),
+    );
 
     let res = {
         let mut panel = egui::SidePanel::right("chat_panel")
@@ -28,10 +49,12 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
         panel = panel.default_width(500.0);
 
         let mut close_chat = None;
+        let mut jump_to: Option<MapPosLink> = None;
 
         let res = panel.show_inside(ui, |ui| {
             StripBuilder::new(ui)
                 .size(Size::remainder())
+                .size(Size::exact(if completions.is_some() { 30.0 } else { 0.0 }))
                 .size(Size::exact(30.0))
                 .cell_layout(Layout::top_down(egui::Align::Min).with_cross_justify(true))
                 .vertical(|mut strip| {
@@ -40,23 +63,66 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                             .scroll_bar_visibility(ScrollBarVisibility::AlwaysVisible)
                             .stick_to_bottom(true)
                             .show(ui, |ui| {
-                                // small workaround to align text to bottom
-                                ui.add_space(ui.available_height());
-                                for (author, msg) in
-                                    pipe.user_data.editor_tab.client.msgs.iter().rev()
-                                {
+                                for msg in client.msgs.iter() {
                                     Frame::default()
                                         .fill(Color32::from_black_alpha(150))
+                                        .stroke(if msg.mentions_me {
+                                            Stroke::new(1.0, Color32::GOLD)
+                                        } else {
+                                            Stroke::NONE
+                                        })
                                         .inner_margin(10.0)
                                         .corner_radius(5.0)
                                         .show(ui, |ui| {
-                                            ui.label(author);
-                                            ui.colored_label(Color32::WHITE, msg);
+                                            ui.label(&msg.from);
+                                            ui.horizontal_wrapped(|ui| {
+                                                ui.spacing_mut().item_spacing.x = 0.0;
+                                                for part in parse_msg(&msg.msg, &names) {
+                                                    match part {
+                                                        ChatMsgPart::Text(text) => {
+                                                            ui.colored_label(Color32::WHITE, text);
+                                                        }
+                                                        ChatMsgPart::Mention(name) => {
+                                                            ui.label(
+                                                                RichText::new(format!("@{name}"))
+                                                                    .color(Color32::LIGHT_BLUE)
+                                                                    .strong(),
+                                                            );
+                                                        }
+                                                        ChatMsgPart::PosLink(link) => {
+                                                            if ui
+                                                                .link(format!(
+                                                                    "\u{f3c5} {:.1}, {:.1}",
+                                                                    link.pos.x, link.pos.y
+                                                                ))
+                                                                .on_hover_text(
+                                                                    "Move the camera there",
+                                                                )
+                                                                .clicked()
+                                                            {
+                                                                jump_to = Some(link);
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            });
                                         });
                                     ui.add_space(10.0);
                                 }
                             });
                     });
+                    strip.cell(|ui| {
+                        let Some((at, completions)) = &completions else {
+                            return;
+                        };
+                        ui.horizontal(|ui| {
+                            for name in completions {
+                                if ui.button(format!("@{name}")).clicked() {
+                                    complete_mention(&mut chat_state.msg, *at, name);
+                                }
+                            }
+                        });
+                    });
                     strip.cell(|ui| {
                         ui.style_mut().wrap_mode = None;
                         let is_enter = ui.input(|i| i.key_pressed(Key::Enter));
@@ -65,17 +131,64 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                                 || i.pointer.any_pressed()
                                 || i.pointer.any_released()
                         });
-                        let inp = ui.add(TextEdit::singleline(&mut chat_state.msg));
-                        if inp.lost_focus() && !pointer_action {
-                            close_chat =
-                                Some(is_enter.then(|| std::mem::take(&mut chat_state.msg)));
-                        } else if !pointer_action {
-                            inp.request_focus();
-                        }
+                        // tab completes the mention that is currently typed
+                        let completed = completions.as_ref().is_some_and(|(at, completions)| {
+                            let tab = ui.input_mut(|i| i.consume_key(Modifiers::NONE, Key::Tab));
+                            if tab {
+                                complete_mention(&mut chat_state.msg, *at, completions[0]);
+                            }
+                            tab
+                        });
+                        ui.horizontal(|ui| {
+                            if ui
+                                .add(Button::new("\u{f3c5}"))
+                                .on_hover_text(
+                                    "Insert a link to the current camera position.\n\
+                                    Other mappers can click it to look at the same spot.",
+                                )
+                                .clicked()
+                            {
+                                if !chat_state.msg.is_empty() && !chat_state.msg.ends_with(' ') {
+                                    chat_state.msg.push(' ');
+                                }
+                                chat_state.msg.push_str(
+                                    &MapPosLink {
+                                        pos: camera.pos,
+                                        zoom: camera.zoom,
+                                    }
+                                    .encode(),
+                                );
+                            }
+                            let mut inp = TextEdit::singleline(&mut chat_state.msg)
+                                .desired_width(f32::INFINITY)
+                                .show(ui);
+                            if completed {
+                                let index = chat_state.msg.chars().count();
+                                inp.state
+                                    .cursor
+                                    .set_char_range(Some(CCursorRange::one(CCursor {
+                                        index,
+                                        ..Default::default()
+                                    })));
+                                inp.state.store(ui.ctx(), inp.response.id);
+                            }
+                            let inp = inp.response;
+                            if inp.lost_focus() && !pointer_action {
+                                close_chat =
+                                    Some(is_enter.then(|| std::mem::take(&mut chat_state.msg)));
+                            } else if !pointer_action {
+                                inp.request_focus();
+                            }
+                        });
                     });
                 });
         });
 
+        if let Some(link) = jump_to {
+            camera.pos = link.pos;
+            camera.zoom = link.zoom;
+        }
+
         if let Some(msg) = close_chat {
             if let Some(msg) = msg {
                 pipe.user_data.ui_events.push(EditorUiEvent::Chat { msg });
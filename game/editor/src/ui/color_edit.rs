@@ -0,0 +1,214 @@
+use std::hash::Hash;
+
+use egui::{Button, Color32, CursorIcon, Key, Popup, TextEdit, TextStyle, vec2};
+
+use crate::{
+    client::EditorClient,
+    color_palette::{EditorColorPalette, color_from_hex, color_to_hex, untint_color},
+    map::{EditorEyedropper, EditorMapProps},
+};
+
+/// The palette & eyedropper of the map, shared by all color fields.
+pub struct ColorEditCtx<'a> {
+    pub palette: &'a mut EditorColorPalette,
+    pub eyedropper: &'a mut Option<EditorEyedropper>,
+    pub eyedropper_untint: &'a mut bool,
+    pub client: &'a EditorClient,
+}
+
+impl<'a> ColorEditCtx<'a> {
+    pub fn new(map_props: &'a mut EditorMapProps, client: &'a EditorClient) -> Self {
+        Self {
+            palette: &mut map_props.options.palette,
+            eyedropper: &mut map_props.ui_values.eyedropper,
+            eyedropper_untint: &mut map_props.ui_values.eyedropper_untint,
+            client,
+        }
+    }
+
+    fn add_recent(&mut self, color: [u8; 4]) {
+        if self.palette.add_recent(color) {
+            self.client.set_palette(self.palette.clone());
+        }
+    }
+}
+
+fn swatch_button(ui: &mut egui::Ui, color: [u8; 4]) -> egui::Response {
+    ui.add(
+        Button::new("")
+            .fill(Color32::from_rgba_unmultiplied(
+                color[0], color[1], color[2], color[3],
+            ))
+            .min_size(vec2(16.0, 16.0)),
+    )
+    .on_hover_text(color_to_hex(color))
+}
+
+/// A color button with a hex input, an eyedropper & the palette of the map.
+///
+/// `tint` is the color of the layer or quad the field belongs to,
+/// the eyedropper can remove it from the picked color.
+/// Returns whether the color changed.
+pub fn color_edit(
+    ui: &mut egui::Ui,
+    ctx: &mut ColorEditCtx,
+    id_salt: impl Hash,
+    color: &mut [u8; 4],
+    tint: Option<[u8; 4]>,
+) -> bool {
+    let id = ui.id().with(id_salt);
+    let mut changed = false;
+
+    if let Some(picked) = ctx
+        .eyedropper
+        .as_ref()
+        .filter(|eyedropper| eyedropper.field == id)
+        .and_then(|eyedropper| eyedropper.picked)
+    {
+        let tint = ctx
+            .eyedropper
+            .take()
+            .and_then(|eyedropper| eyedropper.tint)
+            .filter(|_| *ctx.eyedropper_untint);
+        let picked = tint
+            .and_then(|tint| untint_color(picked, tint))
+            .unwrap_or(picked);
+        // the rendered frame has no alpha, keep the one of the field
+        *color = [picked[0], picked[1], picked[2], color[3]];
+        ctx.add_recent(*color);
+        changed = true;
+    }
+
+    ui.horizontal(|ui| {
+        // colors changed by the picker are remembered once it was closed
+        let picker_dirty_id = id.with("picker-dirty");
+        if ui.color_edit_button_srgba_unmultiplied(color).changed() {
+            ui.data_mut(|d| d.insert_temp(picker_dirty_id, true));
+            changed = true;
+        } else if !Popup::is_any_open(ui.ctx())
+            && ui.data_mut(|d| d.remove_temp::<bool>(picker_dirty_id).is_some())
+        {
+            ctx.add_recent(*color);
+        }
+
+        // the text is kept while editing, even if it's not a valid color yet
+        let hex_id = id.with("hex");
+        let mut text = ui
+            .data(|d| d.get_temp::<String>(hex_id))
+            .unwrap_or_else(|| color_to_hex(*color));
+        let res = ui.add(
+            TextEdit::singleline(&mut text)
+                .desired_width(75.0)
+                .font(TextStyle::Monospace),
+        );
+        if res.changed()
+            && let Some(hex_color) = color_from_hex(&text)
+        {
+            *color = hex_color;
+            changed = true;
+        }
+        if res.has_focus() {
+            ui.data_mut(|d| d.insert_temp(hex_id, text));
+        } else {
+            ui.data_mut(|d| d.remove_temp::<String>(hex_id));
+        }
+        if res.lost_focus() {
+            ctx.add_recent(*color);
+        }
+        res.on_hover_text("`#rrggbbaa`, alpha is optional.");
+
+        let eyedropper_active = ctx
+            .eyedropper
+            .as_ref()
+            .is_some_and(|eyedropper| eyedropper.field == id);
+        if ui
+            .add(Button::new("\u{f1fb}").selected(eyedropper_active))
+            .on_hover_text("Pick a color from the map, `escape` cancels.")
+            .clicked()
+        {
+            *ctx.eyedropper = (!eyedropper_active).then_some(EditorEyedropper {
+                field: id,
+                tint,
+                readback: None,
+                picked: None,
+            });
+        } else if eyedropper_active {
+            ui.ctx().set_cursor_icon(CursorIcon::Crosshair);
+            if ui.input(|i| i.key_pressed(Key::Escape)) {
+                *ctx.eyedropper = None;
+            }
+        }
+
+        ui.menu_button("\u{f53f}", |ui| {
+            if tint.is_some() {
+                ui.checkbox(ctx.eyedropper_untint, "Eyedropper removes the tint")
+                    .on_hover_text(
+                        "Picks the color of the texture below, \
+                        instead of the color it is rendered with.",
+                    );
+                ui.separator();
+            }
+
+            ui.label("Recent");
+            let mut apply = None;
+            ui.horizontal_wrapped(|ui| {
+                for recent in ctx.palette.recent.iter() {
+                    if swatch_button(ui, *recent).clicked() {
+                        apply = Some(*recent);
+                    }
+                }
+            });
+            ui.separator();
+
+            ui.label("Swatches");
+            let mut remove = None;
+            for swatch in ctx.palette.swatches.iter() {
+                ui.horizontal(|ui| {
+                    if swatch_button(ui, swatch.color).clicked() {
+                        apply = Some(swatch.color);
+                    }
+                    ui.label(&swatch.name);
+                    if ui
+                        .small_button("\u{f1f8}")
+                        .on_hover_text("Remove")
+                        .clicked()
+                    {
+                        remove = Some(swatch.name.clone());
+                    }
+                });
+            }
+            let name_id = id.with("swatch-name");
+            let mut name = ui
+                .data(|d| d.get_temp::<String>(name_id))
+                .unwrap_or_default();
+            ui.horizontal(|ui| {
+                ui.add(
+                    TextEdit::singleline(&mut name)
+                        .hint_text("Name")
+                        .desired_width(100.0),
+                );
+                if ui
+                    .add_enabled(!name.is_empty(), Button::new("Save color"))
+                    .clicked()
+                {
+                    ctx.palette.set_swatch(&name, *color);
+                    ctx.client.set_palette(ctx.palette.clone());
+                    name.clear();
+                }
+            });
+            ui.data_mut(|d| d.insert_temp(name_id, name));
+
+            if let Some(name) = remove {
+                ctx.palette.remove_swatch(&name);
+                ctx.client.set_palette(ctx.palette.clone());
+            }
+            if let Some(apply) = apply {
+                *color = apply;
+                ctx.add_recent(apply);
+                changed = true;
+            }
+        });
+    });
+
+    changed
+}
@@ -13,8 +13,13 @@ use crate::{
         ActAddRemGroup, ActChangeGroupAttr, ActChangeGroupName, ActChangePhysicsGroupAttr,
         ActMoveGroup, ActRemGroup, EditorAction,
     },
-    map::{EditorGroups, EditorMap, EditorMapInterface, EditorPhysicsLayer},
-    ui::{group_and_layer::shared::copy_tiles, user_data::UserDataWithTab},
+    map::{
+        EditorDesignLayerInterface, EditorGroups, EditorMap, EditorMapInterface, EditorPhysicsLayer,
+    },
+    ui::{
+        group_and_layer::shared::copy_tiles,
+        user_data::{EditorUiEvent, UserDataWithTab},
+    },
 };
 
 #[derive(Debug)]
@@ -223,6 +228,8 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                 let name_cmp = group_editor.name.clone();
 
                 let mut delete_group = false;
+                let mut export_layers: Option<Option<Vec<usize>>> = None;
+                let mut save_template: Option<String> = None;
                 let mut move_group = None;
 
                 let res = window.show(ui.ctx(), |ui| {
@@ -309,6 +316,38 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                                 delete_group = true;
                             }
                             ui.end_row();
+                            // export
+                            if ui.button("Export group to file").clicked() {
+                                export_layers = Some(None);
+                            }
+                            let selected_layers: Vec<_> = group
+                                .layers
+                                .iter()
+                                .enumerate()
+                                .filter(|(_, layer)| layer.is_selected())
+                                .map(|(l, _)| l)
+                                .collect();
+                            if ui
+                                .add_enabled(
+                                    !selected_layers.is_empty(),
+                                    egui::Button::new("Export selected layers"),
+                                )
+                                .clicked()
+                            {
+                                export_layers = Some(Some(selected_layers));
+                            }
+                            ui.end_row();
+                            if ui
+                                .button("Save as template")
+                                .on_hover_text(
+                                    "Saves the group to the group templates, \
+                                    so it can be inserted into any map.",
+                                )
+                                .clicked()
+                            {
+                                save_template = Some(group_editor.name.clone());
+                            }
+                            ui.end_row();
 
                             ui.label("Move group");
                             ui.end_row();
@@ -354,6 +393,22 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                     move_group.and_then(|mv| group_move_to_act(mv, is_background, g, map))
                 {
                     tab.client.execute(EditorAction::MoveGroup(move_act), None);
+                } else if let Some(layers) = export_layers {
+                    pipe.user_data
+                        .ui_events
+                        .push(EditorUiEvent::ExportGroupDialog {
+                            is_background,
+                            group_index: g,
+                            layers,
+                        });
+                } else if let Some(name) = save_template {
+                    let state = map
+                        .user
+                        .ui_values
+                        .group_templates_open
+                        .get_or_insert_with(Default::default);
+                    state.name = name;
+                    state.save_group = Some((is_background, g));
                 }
 
                 res
@@ -5,7 +5,7 @@ use egui::{Button, Checkbox, Color32, ComboBox, DragValue, InnerResponse, Popup}
 use legacy_map::mapdef_06::DdraceTileNum;
 use map::{
     map::groups::layers::{
-        design::MapLayerTile,
+        design::{MapLayerBlendMode, MapLayerTile},
         physics::{MapLayerPhysics, MapLayerTilePhysicsBase, MapLayerTilePhysicsTele},
         tiles::{MapTileLayerPhysicsTiles, TileBase},
     },
@@ -38,6 +38,7 @@ use crate::{
     },
     tools::tile_layer::auto_mapper::{ResourceHashTy, TileLayerAutoMapper},
     ui::{
+        color_edit::{ColorEditCtx, color_edit},
         group_and_layer::{
             resource_selector::ResourceSelectionMode,
             shared::{animations_panel_open_warning, copy_tiles},
@@ -107,6 +108,31 @@ fn render_layer_move(
     move_layer
 }
 
+fn render_blend_mode(ui: &mut egui::Ui, id: &str, blend_mode: &mut MapLayerBlendMode) {
+    fn name(blend_mode: MapLayerBlendMode) -> &'static str {
+        match blend_mode {
+            MapLayerBlendMode::Normal => "Normal",
+            MapLayerBlendMode::Multiply => "Multiply",
+            MapLayerBlendMode::Additive => "Additive",
+            MapLayerBlendMode::Screen => "Screen",
+        }
+    }
+    ui.label("Blend mode");
+    ComboBox::new(id, "")
+        .selected_text(name(*blend_mode))
+        .show_ui(ui, |ui| {
+            for mode in [
+                MapLayerBlendMode::Normal,
+                MapLayerBlendMode::Multiply,
+                MapLayerBlendMode::Additive,
+                MapLayerBlendMode::Screen,
+            ] {
+                ui.selectable_value(blend_mode, mode, name(mode));
+            }
+        });
+    ui.end_row();
+}
+
 fn layer_move_to_act(
     mv: MoveLayer,
     is_background: bool,
@@ -476,6 +502,7 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
             let mut move_layer = None;
             let mut auto_tile = None;
 
+            let mut color_edit_ctx = ColorEditCtx::new(&mut map.user, &tab.client);
             let res = window.show(ui.ctx(), |ui| {
                 egui::Grid::new("design group attr grid")
                     .num_columns(2)
@@ -514,7 +541,15 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                                 (anim_color.value.b().to_num::<f32>() * 255.0) as u8,
                                 (anim_color.value.a().to_num::<f32>() * 255.0) as u8,
                             ];
-                            ui.color_edit_button_srgba_unmultiplied(&mut color);
+                            // the animated color tints the tiles
+                            let tint = color;
+                            color_edit(
+                                ui,
+                                &mut color_edit_ctx,
+                                "tile-layer-anim-color",
+                                &mut color,
+                                Some(tint),
+                            );
                             anim_color.value = nfvec4::new(
                                 nffixed::from_num(color[0] as f32 / 255.0),
                                 nffixed::from_num(color[1] as f32 / 255.0),
@@ -536,6 +571,8 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                         ui.label("High detail");
                         toggle_ui(ui, &mut attr.high_detail);
                         ui.end_row();
+                        // blend mode
+                        render_blend_mode(ui, "tile-layer-blend-mode", &mut attr.blend_mode);
                         // w
                         ui.label("Width");
                         let mut w = attr.width.get();
@@ -580,7 +617,15 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                             (attr.color.b().to_num::<f32>() * 255.0) as u8,
                             (attr.color.a().to_num::<f32>() * 255.0) as u8,
                         ];
-                        ui.color_edit_button_srgba_unmultiplied(&mut color);
+                        // the layer color tints the tiles
+                        let tint = color;
+                        color_edit(
+                            ui,
+                            &mut color_edit_ctx,
+                            "tile-layer-color",
+                            &mut color,
+                            Some(tint),
+                        );
                         attr.color = nfvec4::new(
                             nffixed::from_num(color[0] as f32 / 255.0),
                             nffixed::from_num(color[1] as f32 / 255.0),
@@ -1268,6 +1313,8 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                         ui.label("High detail");
                         toggle_ui(ui, &mut attr.high_detail);
                         ui.end_row();
+                        // blend mode
+                        render_blend_mode(ui, "quad-layer-blend-mode", &mut attr.blend_mode);
                         // image
                         if ui
                             .add(
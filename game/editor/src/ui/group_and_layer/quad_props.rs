@@ -18,7 +18,11 @@ use crate::{
         quad_layer::shared::QuadPointerDownPoint,
         tool::{ActiveTool, ActiveToolQuads},
     },
-    ui::{group_and_layer::shared::animations_panel_open_warning, user_data::UserDataWithTab},
+    ui::{
+        color_edit::{ColorEditCtx, color_edit},
+        group_and_layer::shared::animations_panel_open_warning,
+        user_data::UserDataWithTab,
+    },
 };
 
 pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_state: &mut UiState) {
@@ -126,6 +130,7 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
             can_change_color_anim: bool,
             animations_panel_open: bool,
             animations: &mut EditorAnimations,
+            color_edit_ctx: &mut ColorEditCtx,
             pointer_is_used: &mut bool,
         ) -> InnerResponse<bool> {
             let anim_pos = can_change_pos_anim
@@ -194,7 +199,15 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                                 (color_anim.value.b().to_num::<f32>() * 255.0) as u8,
                                 (color_anim.value.a().to_num::<f32>() * 255.0) as u8,
                             ];
-                            ui.color_edit_button_srgba_unmultiplied(&mut color);
+                            // the animated color tints the texture
+                            let tint = color;
+                            color_edit(
+                                ui,
+                                color_edit_ctx,
+                                "quad-anim-color",
+                                &mut color,
+                                Some(tint),
+                            );
                             color_anim.value = nfvec4::new(
                                 nffixed::from_num(color[0] as f32 / 255.0),
                                 nffixed::from_num(color[1] as f32 / 255.0),
@@ -449,7 +462,15 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                             (quad.colors[c].b().to_num::<f32>() * 255.0) as u8,
                             (quad.colors[c].a().to_num::<f32>() * 255.0) as u8,
                         ];
-                        ui.color_edit_button_srgba_unmultiplied(&mut color);
+                        // the corner color tints the texture
+                        let tint = color;
+                        color_edit(
+                            ui,
+                            color_edit_ctx,
+                            ("quad-corner-color", c),
+                            &mut color,
+                            Some(tint),
+                        );
                         quad.colors[c] = nfvec4::new(
                             nffixed::from_num(color[0] as f32 / 255.0),
                             nffixed::from_num(color[1] as f32 / 255.0),
@@ -487,6 +508,7 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                         true,
                         animations_panel_open,
                         &mut map.animations,
+                        &mut ColorEditCtx::new(&mut map.user, &pipe.user_data.editor_tab.client),
                         pipe.user_data.pointer_is_used,
                     )
                 });
@@ -564,6 +586,7 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                         can_change_color_anim,
                         animations_panel_open,
                         &mut map.animations,
+                        &mut ColorEditCtx::new(&mut map.user, &pipe.user_data.editor_tab.client),
                         pipe.user_data.pointer_is_used,
                     )
                 });
@@ -0,0 +1,185 @@
+use std::rc::Rc;
+
+use egui::{Button, Color32, Grid, Rect, ScrollArea, Sense, Window, vec2};
+use ui_base::types::{UiRenderPipe, UiState};
+
+use crate::{
+    group_templates::{EditorGroupThumbnail, check_template_name, load_group_templates},
+    ui::user_data::{EditorUiEvent, UserDataWithTab},
+};
+
+const THUMBNAIL_SIZE: f32 = 96.0;
+const TEMPLATES_PER_ROW: usize = 3;
+
+fn render_thumbnail(ui: &mut egui::Ui, thumbnail: &EditorGroupThumbnail) -> egui::Response {
+    let (rect, res) = ui.allocate_exact_size(vec2(THUMBNAIL_SIZE, THUMBNAIL_SIZE), Sense::click());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 4.0, Color32::from_gray(30));
+    if thumbnail.width == 0 || thumbnail.height == 0 {
+        return res;
+    }
+    let cell = THUMBNAIL_SIZE / thumbnail.width.max(thumbnail.height) as f32;
+    // center the thumbnail
+    let offset = rect.min
+        + vec2(
+            (THUMBNAIL_SIZE - thumbnail.width as f32 * cell) / 2.0,
+            (THUMBNAIL_SIZE - thumbnail.height as f32 * cell) / 2.0,
+        );
+    for (index, [r, g, b, a]) in thumbnail.pixels.iter().copied().enumerate() {
+        if a == 0 {
+            continue;
+        }
+        let x = (index % thumbnail.width) as f32;
+        let y = (index / thumbnail.width) as f32;
+        painter.rect_filled(
+            Rect::from_min_size(offset + vec2(x * cell, y * cell), vec2(cell, cell)),
+            0.0,
+            Color32::from_rgba_unmultiplied(r, g, b, a),
+        );
+    }
+    res
+}
+
+/// Lists the group templates of the user & the built-in ones,
+/// a template is inserted as new group into the map.
+pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_state: &mut UiState) {
+    let io = pipe.user_data.io;
+    let tab = &mut *pipe.user_data.editor_tab;
+    let Some(state) = &mut tab.map.user.ui_values.group_templates_open else {
+        return;
+    };
+
+    if let Some(task) = state.loading_task.take_if(|task| task.is_finished()) {
+        match task.get() {
+            Ok(templates) => state.templates = Some(Rc::new(templates)),
+            Err(err) => {
+                log::error!("{err}");
+                state.templates = Some(Default::default());
+            }
+        }
+    }
+    if state.templates.is_none() && state.loading_task.is_none() {
+        let fs = io.fs.clone();
+        state.loading_task = Some(
+            io.rt
+                .spawn(async move { Ok(load_group_templates(&fs).await) }),
+        );
+    }
+
+    let mut open = true;
+    let window_res = Window::new("Group templates")
+        .open(&mut open)
+        .resizable(true)
+        .show(ui.ctx(), |ui| {
+            if let Some((is_background, group_index)) = state.save_group {
+                ui.label(format!(
+                    "Save the {} group #{group_index} as template:",
+                    if is_background {
+                        "background"
+                    } else {
+                        "foreground"
+                    }
+                ));
+                ui.horizontal(|ui| {
+                    ui.label("Name:");
+                    ui.text_edit_singleline(&mut state.name);
+                    let name_check = check_template_name(&state.name);
+                    let exists = state.templates.as_ref().is_some_and(|templates| {
+                        templates
+                            .iter()
+                            .any(|template| !template.built_in && template.name == state.name)
+                    });
+                    let mut btn = ui.add_enabled(name_check.is_ok(), Button::new("Save"));
+                    if let Err(err) = name_check {
+                        btn = btn.on_disabled_hover_text(err.to_string());
+                    } else if exists {
+                        btn = btn.on_hover_text("Overwrites the existing template.");
+                    }
+                    if btn.clicked() {
+                        pipe.user_data
+                            .ui_events
+                            .push(EditorUiEvent::SaveGroupTemplate {
+                                name: state.name.clone(),
+                                is_background,
+                                group_index,
+                            });
+                        state.save_group = None;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        state.save_group = None;
+                    }
+                });
+                ui.separator();
+            }
+
+            ui.checkbox(
+                &mut state.insert_as_background,
+                "Insert as background group",
+            );
+            if ui.button("Reload").clicked() {
+                state.templates = None;
+            }
+            ui.separator();
+
+            let Some(templates) = state.templates.clone() else {
+                ui.label("Loading templates...");
+                return;
+            };
+            if templates.is_empty() {
+                ui.label(
+                    "No templates yet. Use \"Save as template\" \
+                    in the properties of a design group.",
+                );
+                return;
+            }
+            ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                Grid::new("group-templates-grid")
+                    .num_columns(TEMPLATES_PER_ROW)
+                    .spacing([8.0, 8.0])
+                    .show(ui, |ui| {
+                        for (index, template) in templates.iter().enumerate() {
+                            ui.vertical(|ui| {
+                                let insert = render_thumbnail(ui, &template.thumbnail)
+                                    .on_hover_text("Click to insert the template.")
+                                    .clicked();
+                                ui.label(&template.name);
+                                ui.horizontal(|ui| {
+                                    if insert || ui.button("Insert").clicked() {
+                                        pipe.user_data.ui_events.push(
+                                            EditorUiEvent::InsertGroupTemplate {
+                                                file: Box::new(template.file.clone()),
+                                                is_background: state.insert_as_background,
+                                            },
+                                        );
+                                    }
+                                    if ui
+                                        .add_enabled(!template.built_in, Button::new("Delete"))
+                                        .on_disabled_hover_text(
+                                            "Built-in templates can't be deleted.",
+                                        )
+                                        .clicked()
+                                    {
+                                        pipe.user_data.ui_events.push(
+                                            EditorUiEvent::DeleteGroupTemplate {
+                                                name: template.name.clone(),
+                                            },
+                                        );
+                                    }
+                                });
+                            });
+                            if (index + 1) % TEMPLATES_PER_ROW == 0 {
+                                ui.end_row();
+                            }
+                        }
+                    });
+            });
+        });
+
+    if let Some(window_res) = &window_res {
+        ui_state.add_blur_rect(window_res.response.rect, 0.0);
+        *pipe.user_data.pointer_is_used |= ui.rect_contains_pointer(window_res.response.rect);
+    }
+    if !open {
+        tab.map.user.ui_values.group_templates_open = None;
+    }
+}
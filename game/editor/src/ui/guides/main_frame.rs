@@ -0,0 +1,184 @@
+use egui::{Color32, CursorIcon, Rect, Stroke};
+use math::math::vector::vec2;
+use ui_base::types::UiRenderPipe;
+
+use crate::{
+    guides::{EditorGuide, EditorGuideAxis},
+    map::EditorMapInterface,
+    ui::user_data::UserDataWithTab,
+    utils::{ui_pos_to_world_pos, world_pos_to_ui_pos},
+};
+
+/// The size of the rulers at the top & left edge of the canvas.
+const RULER_SIZE: f32 = 12.0;
+/// How close the pointer has to be to a guide to grab it.
+const GUIDE_GRAB_DISTANCE: f32 = 4.0;
+
+/// Renders the rulers & the guides over the canvas.
+///
+/// New guides are dragged out of the rulers, dragging
+/// a guide back onto a ruler removes it.
+pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
+    let canvas = ui.available_rect_before_wrap();
+    let screen = ui.ctx().screen_rect();
+    let canvas_handle = pipe.user_data.canvas_handle;
+    let tab = &mut *pipe.user_data.editor_tab;
+
+    // guides are in the coordinates of the active layer, like the grid
+    let (offset, parallax) = tab
+        .map
+        .active_layer()
+        .map(|layer| layer.get_offset_and_parallax())
+        .unwrap_or((vec2::default(), vec2::new(100.0, 100.0)));
+    let zoom = tab.map.groups.user.zoom;
+    let center = tab.map.groups.user.pos;
+    let parallax_aware_zoom = tab.map.groups.user.parallax_aware_zoom;
+    let to_world = |pos: egui::Pos2| {
+        ui_pos_to_world_pos(
+            canvas_handle,
+            &screen,
+            zoom,
+            vec2::new(pos.x, pos.y),
+            center.x,
+            center.y,
+            offset.x,
+            offset.y,
+            parallax.x,
+            parallax.y,
+            parallax_aware_zoom,
+        )
+    };
+    let to_ui = |pos: vec2| {
+        world_pos_to_ui_pos(
+            canvas_handle,
+            &screen,
+            zoom,
+            pos,
+            center.x,
+            center.y,
+            offset.x,
+            offset.y,
+            parallax.x,
+            parallax.y,
+            parallax_aware_zoom,
+        )
+    };
+    let guide_ui_pos = |guide: &EditorGuide| match guide.axis {
+        EditorGuideAxis::Horizontal => to_ui(vec2::new(0.0, guide.pos)).y,
+        EditorGuideAxis::Vertical => to_ui(vec2::new(guide.pos, 0.0)).x,
+    };
+
+    let top_ruler = Rect::from_min_size(canvas.min, egui::vec2(canvas.width(), RULER_SIZE));
+    let left_ruler = Rect::from_min_size(canvas.min, egui::vec2(RULER_SIZE, canvas.height()));
+    ui.painter()
+        .rect_filled(top_ruler, 0.0, Color32::from_black_alpha(150));
+    ui.painter()
+        .rect_filled(left_ruler, 0.0, Color32::from_black_alpha(150));
+
+    let guides = &mut tab.map.user.options.guides;
+    let guide_drag = &mut tab.map.user.ui_values.guide_drag;
+    let (pointer, primary_pressed, primary_down) = ui.input(|i| {
+        (
+            i.pointer.latest_pos(),
+            i.pointer.primary_pressed(),
+            i.pointer.primary_down(),
+        )
+    });
+    let pointer = pointer.filter(|pointer| {
+        canvas.contains(*pointer) && (guide_drag.is_some() || !*pipe.user_data.pointer_is_used)
+    });
+
+    let hovered = pointer.and_then(|pointer| {
+        guides.guides.iter().position(|guide| {
+            let pointer_pos = match guide.axis {
+                EditorGuideAxis::Horizontal => pointer.y,
+                EditorGuideAxis::Vertical => pointer.x,
+            };
+            (guide_ui_pos(guide) - pointer_pos).abs() <= GUIDE_GRAB_DISTANCE
+        })
+    });
+
+    if let Some(pointer) = pointer
+        && !guides.locked
+        && guide_drag.is_none()
+    {
+        let new_guide = if top_ruler.contains(pointer) {
+            Some(EditorGuide {
+                axis: EditorGuideAxis::Horizontal,
+                pos: to_world(pointer).y,
+            })
+        } else if left_ruler.contains(pointer) {
+            Some(EditorGuide {
+                axis: EditorGuideAxis::Vertical,
+                pos: to_world(pointer).x,
+            })
+        } else {
+            None
+        };
+        if new_guide.is_some() || hovered.is_some() {
+            *pipe.user_data.pointer_is_used = true;
+            ui.ctx().set_cursor_icon(
+                match new_guide
+                    .as_ref()
+                    .or(hovered.and_then(|index| guides.guides.get(index)))
+                    .map(|guide| guide.axis)
+                {
+                    Some(EditorGuideAxis::Vertical) => CursorIcon::ResizeHorizontal,
+                    _ => CursorIcon::ResizeVertical,
+                },
+            );
+        }
+        if primary_pressed {
+            if let Some(guide) = new_guide {
+                guides.guides.push(guide);
+                *guide_drag = Some(guides.guides.len() - 1);
+            } else {
+                *guide_drag = hovered;
+            }
+        }
+    }
+
+    if let Some(index) = *guide_drag {
+        *pipe.user_data.pointer_is_used = true;
+        if primary_down {
+            if let Some((guide, pointer)) = guides.guides.get_mut(index).zip(pointer) {
+                let pos = to_world(pointer);
+                guide.pos = match guide.axis {
+                    EditorGuideAxis::Horizontal => pos.y,
+                    EditorGuideAxis::Vertical => pos.x,
+                };
+            }
+        } else {
+            // dropped onto a ruler or outside of the canvas
+            let inner =
+                Rect::from_min_max(canvas.min + egui::vec2(RULER_SIZE, RULER_SIZE), canvas.max);
+            if pointer.is_none_or(|pointer| !inner.contains(pointer)) && index < guides.guides.len()
+            {
+                guides.guides.remove(index);
+            }
+            *guide_drag = None;
+            tab.client.set_guides(guides.clone());
+        }
+    }
+
+    for (index, guide) in guides.guides.iter().enumerate() {
+        let color = if *guide_drag == Some(index) || (!guides.locked && hovered == Some(index)) {
+            Color32::LIGHT_BLUE
+        } else if guides.locked {
+            Color32::from_rgb(0, 150, 200).gamma_multiply(0.5)
+        } else {
+            Color32::from_rgb(0, 150, 200)
+        };
+        let stroke = Stroke::new(1.0, color);
+        let pos = guide_ui_pos(guide);
+        match guide.axis {
+            EditorGuideAxis::Horizontal if canvas.y_range().contains(pos) => {
+                ui.painter().hline(canvas.x_range(), pos, stroke);
+            }
+            EditorGuideAxis::Vertical if canvas.x_range().contains(pos) => {
+                ui.painter().vline(pos, canvas.y_range(), stroke);
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use egui::{Button, Color32, RichText, ScrollArea};
+use ui_base::types::{UiRenderPipe, UiState};
+
+use crate::ui::user_data::UserDataWithTab;
+
+pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_state: &mut UiState) {
+    let tab = &mut *pipe.user_data.editor_tab;
+    let open = tab.map.user.ui_values.history_panel_open;
+
+    // the local server's history can be read directly
+    tab.client.subscribe_history(open && tab.server.is_none());
+    if !open {
+        return;
+    }
+
+    let history = if let Some(server) = &tab.server {
+        Some(server.history_info())
+    } else {
+        tab.client.history.clone()
+    };
+
+    let res = egui::SidePanel::right("history_panel")
+        .resizable(true)
+        .width_range(250.0..=600.0)
+        .default_width(300.0)
+        .show_inside(ui, |ui| {
+            ui.heading("History");
+            ui.separator();
+
+            let Some(history) = history else {
+                ui.label("Waiting for the server…");
+                return;
+            };
+
+            let mut jump_to = None;
+            ScrollArea::vertical().stick_to_bottom(true).show(ui, |ui| {
+                if ui
+                    .add(Button::new("Initial state").selected(history.cur.is_none()))
+                    .clicked()
+                {
+                    jump_to = Some(None);
+                }
+                for (index, entry) in history.entries.iter().enumerate() {
+                    let is_undone = history.cur.is_none_or(|cur| index > cur);
+                    let secs = entry.time.as_secs();
+                    let mut text = format!(
+                        "{:02}:{:02}  {}",
+                        secs / 60,
+                        secs % 60,
+                        if entry.coalesced > 1 {
+                            format!("{} older actions", entry.coalesced)
+                        } else {
+                            entry.label.clone()
+                        }
+                    );
+                    if let Some(identifier) = &entry.identifier {
+                        text.push_str(&format!(" ({identifier})"));
+                    }
+                    let mut text = RichText::new(text);
+                    if is_undone {
+                        text = text.color(Color32::GRAY).italics();
+                    }
+                    if ui
+                        .add(Button::new(text).selected(history.cur == Some(index)))
+                        .on_hover_text(format!("{} action(s)", entry.action_count))
+                        .clicked()
+                    {
+                        jump_to = Some(Some(index));
+                    }
+                }
+            });
+
+            if let Some(index) = jump_to
+                && index != history.cur
+            {
+                tab.client.jump_history(index);
+            }
+        });
+
+    ui_state.add_blur_rect(res.response.rect, 0.0);
+}
@@ -324,6 +324,17 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
                         binds_per_event,
                         pipe.user_data.hotkeys,
                     );
+                    binds_changed |= hotkey_button(
+                        ui,
+                        "Toggle parallax preview",
+                        "",
+                        EditorHotkeyEvent::Preferences(
+                            EditorHotkeyEventPreferences::ToggleParallaxPreview,
+                        ),
+                        options,
+                        binds_per_event,
+                        pipe.user_data.hotkeys,
+                    );
                     binds_changed |= hotkey_button(
                         ui,
                         "Increase map time speed factor",
@@ -416,6 +427,24 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
                         binds_per_event,
                         pipe.user_data.hotkeys,
                     );
+                    binds_changed |= hotkey_button(
+                        ui,
+                        "Toggle history panel",
+                        "",
+                        EditorHotkeyEvent::Panels(EditorHotkeyEventPanels::ToggleHistory),
+                        options,
+                        binds_per_event,
+                        pipe.user_data.hotkeys,
+                    );
+                    binds_changed |= hotkey_button(
+                        ui,
+                        "Toggle number usage panel",
+                        "",
+                        EditorHotkeyEvent::Panels(EditorHotkeyEventPanels::ToggleNumberUsage),
+                        options,
+                        binds_per_event,
+                        pipe.user_data.hotkeys,
+                    );
                     ui.separator();
                     ui.separator();
                     ui.end_row();
@@ -506,6 +535,15 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
                         binds_per_event,
                         pipe.user_data.hotkeys,
                     );
+                    binds_changed |= hotkey_button(
+                        ui,
+                        "Find & replace tiles",
+                        "",
+                        EditorHotkeyEvent::Edit(EditorHotkeyEventEdit::FindReplaceTiles),
+                        options,
+                        binds_per_event,
+                        pipe.user_data.hotkeys,
+                    );
                     ui.separator();
                     ui.separator();
                     ui.end_row();
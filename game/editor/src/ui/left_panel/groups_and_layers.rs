@@ -4,6 +4,7 @@ use crate::actions::actions::{
     ActAddSoundLayer, ActAddTileLayer, EditorAction,
 };
 use crate::client::EditorClient;
+use crate::layer_filter::{EditorLayerFilter, LayerFilterInfo, LayerResource, LayerTypeFilter};
 use crate::map::{EditorLayer, EditorLayerUnionRef, EditorMap, EditorPhysicsLayer};
 use crate::ui::user_data::UserDataWithTab;
 use crate::utils::ui_pos_to_world_pos;
@@ -12,10 +13,10 @@ use crate::{
         EditorCommonLayerOrGroupAttrInterface, EditorDesignLayerInterface, EditorGroup,
         EditorMapInterface, EditorMapSetGroup, EditorMapSetLayer, EditorResources,
     },
-    ui::utils::{group_name, layer_name, layer_name_phy},
+    ui::utils::{group_name, highlight_matches, layer_name, layer_name_phy},
 };
 
-use egui::{Button, Color32, Layout, collapsing_header::CollapsingState};
+use egui::{Button, Color32, ComboBox, Layout, TextEdit, collapsing_header::CollapsingState};
 use egui_extras::{Size, StripBuilder};
 use map::map::groups::MapGroup;
 use map::map::groups::layers::design::{
@@ -196,10 +197,90 @@ fn check_layer_clicked_tile(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataW
     }
 }
 
+fn resource_name(resources: &EditorResources, resource: LayerResource) -> String {
+    match resource {
+        LayerResource::Image(index) => {
+            format!("\u{f03e} {}", resources.images[index].def.name.as_str())
+        }
+        LayerResource::ImageArray(index) => {
+            format!(
+                "\u{f302} {}",
+                resources.image_arrays[index].def.name.as_str()
+            )
+        }
+        LayerResource::Sound(index) => {
+            format!("\u{1f3b5} {}", resources.sounds[index].def.name.as_str())
+        }
+    }
+}
+
+fn render_filter(ui: &mut egui::Ui, filter: &mut EditorLayerFilter, resources: &EditorResources) {
+    ui.horizontal(|ui| {
+        ui.label("\u{f002}");
+        ui.add(
+            TextEdit::singleline(&mut filter.text)
+                .hint_text("Filter groups & layers")
+                .desired_width(ui.available_width() - 30.0),
+        );
+        if filter.is_active()
+            && ui
+                .button("\u{f00d}")
+                .on_hover_text("Clear the filter")
+                .clicked()
+        {
+            *filter = Default::default();
+        }
+    });
+    ui.horizontal_wrapped(|ui| {
+        for (ty, text) in [
+            (LayerTypeFilter::All, "All"),
+            (LayerTypeFilter::Tile, "\u{f00a}"),
+            (LayerTypeFilter::Quad, "\u{f61f}"),
+            (LayerTypeFilter::Sound, "\u{1f3b5}"),
+        ] {
+            ui.selectable_value(&mut filter.ty, ty, text);
+        }
+        ui.toggle_value(&mut filter.animated, "\u{f70c}")
+            .on_hover_text("Only layers with animations");
+        // the resource might have been removed in the meantime
+        if filter.resource.is_some_and(|resource| match resource {
+            LayerResource::Image(index) => index >= resources.images.len(),
+            LayerResource::ImageArray(index) => index >= resources.image_arrays.len(),
+            LayerResource::Sound(index) => index >= resources.sounds.len(),
+        }) {
+            filter.resource = None;
+        }
+        ComboBox::from_id_salt("layer-filter-resource")
+            .selected_text(
+                filter
+                    .resource
+                    .map(|resource| resource_name(resources, resource))
+                    .unwrap_or_else(|| "Any resource".to_string()),
+            )
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut filter.resource, None, "Any resource");
+                let all_resources = (0..resources.images.len())
+                    .map(LayerResource::Image)
+                    .chain((0..resources.image_arrays.len()).map(LayerResource::ImageArray))
+                    .chain((0..resources.sounds.len()).map(LayerResource::Sound));
+                for resource in all_resources {
+                    ui.selectable_value(
+                        &mut filter.resource,
+                        Some(resource),
+                        resource_name(resources, resource),
+                    );
+                }
+            });
+    });
+}
+
 pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
     let tab = &mut *pipe.user_data.editor_tab;
     let map = &mut tab.map;
 
+    render_filter(ui, &mut map.user.ui_values.layer_filter, &map.resources);
+    let filter = map.user.ui_values.layer_filter.clone();
+
     let mut activated_layer = None;
     let mut selected_layers = Vec::new();
     let mut selected_groups = Vec::new();
@@ -213,6 +294,31 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
         let mut selected_layers = Vec::new();
         let mut selected_groups = Vec::new();
         for (g, group) in groups.iter_mut().enumerate() {
+            let group_name = group_name(group, g);
+            let layer_names: Vec<_> = group
+                .layers
+                .iter()
+                .enumerate()
+                .map(|(l, layer)| {
+                    if layer.name().is_empty() {
+                        layer_name(ui, resources, layer, l).1.text
+                    } else {
+                        layer.name().to_string()
+                    }
+                })
+                .collect();
+            let filter_res = filter.filter_group(
+                &group_name,
+                &group
+                    .layers
+                    .iter()
+                    .zip(layer_names.iter())
+                    .map(|(layer, name)| LayerFilterInfo::design(layer, name))
+                    .collect::<Vec<_>>(),
+            );
+            if !filter_res.visible {
+                continue;
+            }
             CollapsingState::load_with_default_open(ui.ctx(), format!("{id}-{g}").into(), true)
                 .show_header(ui, |ui| {
                     ui.with_layout(Layout::right_to_left(egui::Align::Min), |ui| {
@@ -224,7 +330,12 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
                             group.editor_attr_mut().hidden = !hidden;
                         }
                         ui.vertical_centered_justified(|ui| {
-                            let btn = Button::new(group_name(group, g)).frame(false);
+                            let btn = Button::new(highlight_matches(
+                                ui,
+                                &group_name,
+                                &filter_res.name_match,
+                            ))
+                            .frame(false);
                             if ui.add(btn).secondary_clicked() {
                                 selected_groups.push(g);
                             }
@@ -233,8 +344,14 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
                 })
                 .body(|ui| {
                     for (l, layer) in group.layers.iter_mut().enumerate() {
+                        let Some(name_match) = &filter_res.layers[l] else {
+                            continue;
+                        };
                         let (icon, layer_btn) = {
-                            let (icon, name) = layer_name(ui, resources, layer, l);
+                            let (icon, mut name) = layer_name(ui, resources, layer, l);
+                            if !name_match.is_empty() && !layer.name().is_empty() {
+                                name = highlight_matches(ui, layer.name(), name_match);
+                            }
 
                             let mut btn = egui::Button::new(name);
                             if layer.editor_attr().active {
@@ -294,6 +411,7 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
                                     color_anim: None,
                                     color_anim_offset: time::Duration::ZERO,
                                     image_array: None,
+                                    blend_mode: Default::default(),
                                 },
                                 tiles: vec![Default::default(); 50 * 50],
                                 name: "".into(),
@@ -315,6 +433,7 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
                                 attr: MapLayerQuadsAttrs {
                                     image: None,
                                     high_detail: false,
+                                    blend_mode: Default::default(),
                                 },
                                 quads: vec![],
                                 name: "".into(),
@@ -470,6 +589,26 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
                                     .id_salt("scroll-phy".to_string())
                                     .show(ui, |ui| {
                                         let group = &mut map.groups.physics;
+                                        let layer_names: Vec<_> = group
+                                            .layers
+                                            .iter()
+                                            .enumerate()
+                                            .map(|(l, layer)| layer_name_phy(layer, l))
+                                            .collect();
+                                        let filter_res = filter.filter_group(
+                                            "Physics",
+                                            &group
+                                                .layers
+                                                .iter()
+                                                .zip(layer_names.iter())
+                                                .map(|(layer, name)| {
+                                                    LayerFilterInfo::physics(layer, name)
+                                                })
+                                                .collect::<Vec<_>>(),
+                                        );
+                                        if !filter_res.visible {
+                                            return;
+                                        }
                                         CollapsingState::load_with_default_open(
                                             ui.ctx(),
                                             "physics-group".into(),
@@ -491,8 +630,12 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
                                                         group.editor_attr_mut().hidden = !hidden;
                                                     }
                                                     ui.vertical_centered_justified(|ui| {
-                                                        let btn =
-                                                            Button::new("Physics").frame(false);
+                                                        let btn = Button::new(highlight_matches(
+                                                            ui,
+                                                            "Physics",
+                                                            &filter_res.name_match,
+                                                        ))
+                                                        .frame(false);
                                                         if ui.add(btn).secondary_clicked() {
                                                             selected_groups
                                                                 .push(EditorMapSetGroup::Physics);
@@ -505,9 +648,16 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
                                             for (l, layer) in
                                                 map.groups.physics.layers.iter_mut().enumerate()
                                             {
+                                                let Some(name_match) = &filter_res.layers[l] else {
+                                                    continue;
+                                                };
                                                 let layer_btn = {
                                                     let mut btn =
-                                                        egui::Button::new(layer_name_phy(layer, l));
+                                                        egui::Button::new(highlight_matches(
+                                                            ui,
+                                                            &layer_names[l],
+                                                            name_match,
+                                                        ));
                                                     if layer.editor_attr().active {
                                                         btn = btn.selected(true);
                                                     }
@@ -12,6 +12,16 @@ use super::{
 
 #[instrument(level = "trace", skip_all)]
 pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &mut UiState) {
+    super::region_locks::main_frame::render(
+        ui,
+        pipe.user_data.canvas_handle,
+        &mut pipe.user_data.editor_tabs,
+    );
+    super::map_diff_panel::overlay::render(
+        ui,
+        pipe.user_data.canvas_handle,
+        &mut pipe.user_data.editor_tabs,
+    );
     super::mapper_cursors::main_frame::render(
         ui,
         pipe.user_data.canvas_handle,
@@ -63,8 +73,15 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
         super::group_and_layer::sound_props::render(ui, &mut pipe, ui_state);
 
         super::chat_panel::panel::render(ui, &mut pipe, ui_state);
+        super::history_panel::panel::render(ui, &mut pipe, ui_state);
+        super::number_usage_panel::panel::render(ui, &mut pipe, ui_state);
+        super::map_diff_panel::panel::render(ui, &mut pipe, ui_state);
+        super::tile_replace::dialog::render(ui, &mut pipe, ui_state);
+        super::physics_from_design::dialog::render(ui, &mut pipe, ui_state);
+        super::group_templates::panel::render(ui, &mut pipe, ui_state);
         super::assets_store_panel::panel::render(ui, &mut pipe, ui_state);
 
+        super::guides::main_frame::render(ui, &mut pipe);
         super::tool_overlays::tile_brush::render(ui, &mut pipe);
 
         super::hotkey_panel::panel::render(ui, &mut pipe);
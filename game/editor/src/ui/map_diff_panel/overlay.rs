@@ -0,0 +1,88 @@
+use camera::CameraInterface;
+use egui::Color32;
+use graphics::handles::canvas::canvas::GraphicsCanvasHandle;
+use graphics_types::rendering::State;
+
+use crate::{
+    map::EditorMapInterface,
+    map_diff::{MapDiffChange, MapDiffLayerIndex},
+    tools::tile_layer::shared::TILE_VISUAL_SIZE,
+    ui::user_data::EditorTabsRefMut,
+};
+
+pub fn change_color(change: MapDiffChange) -> Color32 {
+    match change {
+        MapDiffChange::Added => Color32::GREEN,
+        MapDiffChange::Removed => Color32::RED,
+        MapDiffChange::Changed => Color32::YELLOW,
+    }
+}
+
+/// Highlights the tiles that differ from the compared map file.
+pub fn render(
+    ui: &mut egui::Ui,
+    canvas_handle: &GraphicsCanvasHandle,
+    tabs: &mut EditorTabsRefMut<'_>,
+) {
+    let Some(tab) = tabs.active_tab() else {
+        return;
+    };
+    let Some((diff_state, diff)) = tab
+        .map
+        .user
+        .ui_values
+        .map_diff_panel_open
+        .as_ref()
+        .and_then(|state| state.diff.as_ref().map(|(_, diff)| (state, diff)))
+    else {
+        return;
+    };
+
+    let size = ui.ctx().screen_rect().size();
+    let clip_rect = ui.clip_rect();
+    for layer in diff
+        .layers
+        .iter()
+        .filter(|layer| !layer.tiles.is_empty() && !diff_state.hidden_layers.contains(&layer.index))
+    {
+        let group_attr = match layer.index {
+            MapDiffLayerIndex::Design(index) => {
+                let Some(group) = if index.is_background {
+                    &tab.map.groups.background
+                } else {
+                    &tab.map.groups.foreground
+                }
+                .get(index.group_index) else {
+                    continue;
+                };
+                Some(group.attr)
+            }
+            MapDiffLayerIndex::Physics { .. } => None,
+        };
+
+        let mut state = State::new();
+        tab.map
+            .game_camera()
+            .project(canvas_handle, &mut state, group_attr.as_ref());
+        let (x0, y0, x1, y1) = state.get_canvas_mapping();
+
+        let width_scale = size.x / (x1 - x0);
+        let height_scale = size.y / (y1 - y0);
+        let to_screen = |x: f32, y: f32| {
+            egui::pos2(
+                (x * TILE_VISUAL_SIZE - x0) * width_scale,
+                (y * TILE_VISUAL_SIZE - y0) * height_scale,
+            )
+        };
+
+        let painter = ui.painter();
+        for &(x, y, change) in layer.tiles.iter() {
+            let (x, y) = (x as f32, y as f32);
+            let rect = egui::Rect::from_min_max(to_screen(x, y), to_screen(x + 1.0, y + 1.0));
+            if !rect.intersects(clip_rect) {
+                continue;
+            }
+            painter.rect_filled(rect, 0.0, change_color(change).gamma_multiply(0.35));
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use std::{rc::Rc, time::Duration};
+
+use egui::{Button, Grid, RichText, ScrollArea};
+use map::map::Map;
+use ui_base::types::{UiRenderPipe, UiState};
+
+use crate::{
+    actions::actions::EditorActionGroup,
+    map_diff::{MapDiff, MapDiffChange, MapDiffCounts, MapDiffLayer, revert_layer_actions},
+    ui::{map_diff_panel::overlay::change_color, user_data::UserDataWithTab},
+};
+
+/// Comparing big maps is not free, so the diff is only redone after this time.
+const DIFF_RECHECK_INTERVAL: Duration = Duration::from_secs(3);
+
+fn fmt_counts(counts: &MapDiffCounts) -> String {
+    format!("+{} -{} ~{}", counts.added, counts.removed, counts.modified)
+}
+
+fn layer_summary(layer: &MapDiffLayer) -> String {
+    match layer.change {
+        MapDiffChange::Added => "only in the current map".to_string(),
+        MapDiffChange::Removed => "only in the file".to_string(),
+        MapDiffChange::Changed => {
+            let mut parts = Vec::new();
+            if !layer.tiles.is_empty() {
+                parts.push(format!(
+                    "tiles +{} -{} ~{}",
+                    layer.tile_count(MapDiffChange::Added),
+                    layer.tile_count(MapDiffChange::Removed),
+                    layer.tile_count(MapDiffChange::Changed)
+                ));
+            }
+            if !layer.entries.is_empty() {
+                parts.push(format!("entries {}", fmt_counts(&layer.entries)));
+            }
+            if layer.resized {
+                parts.push("resized".to_string());
+            }
+            if layer.attr_changed {
+                parts.push("properties".to_string());
+            }
+            parts.join(", ")
+        }
+    }
+}
+
+pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_state: &mut UiState) {
+    let cur_time = pipe.cur_time;
+    let tab = &mut *pipe.user_data.editor_tab;
+    let Some(state) = &tab.map.user.ui_values.map_diff_panel_open else {
+        return;
+    };
+    let cur_map: Option<Map> = state
+        .diff
+        .as_ref()
+        .is_none_or(|(time, _)| cur_time.saturating_sub(*time) >= DIFF_RECHECK_INTERVAL)
+        .then(|| tab.map.clone().into());
+    let Some(state) = &mut tab.map.user.ui_values.map_diff_panel_open else {
+        return;
+    };
+    if let Some(cur_map) = cur_map {
+        state.diff = Some((cur_time, Rc::new(MapDiff::new(&cur_map, &state.file))));
+    }
+    let Some(diff) = state.diff.as_ref().map(|(_, diff)| diff.clone()) else {
+        return;
+    };
+
+    let mut close = false;
+    let mut revert = None;
+    let res = egui::SidePanel::right("map_diff_panel")
+        .resizable(true)
+        .width_range(250.0..=600.0)
+        .default_width(350.0)
+        .show_inside(ui, |ui| {
+            ui.heading("Map diff");
+            ui.label(format!("Compared with: {}", state.path.display()));
+            ui.horizontal(|ui| {
+                if ui.button("Refresh").clicked() {
+                    state.diff = None;
+                }
+                if ui.button("Close").clicked() {
+                    close = true;
+                }
+            });
+            ui.separator();
+
+            if diff.is_empty() {
+                ui.label("The map does not differ from the file.");
+                return;
+            }
+
+            Grid::new("map_diff_summary_grid")
+                .num_columns(2)
+                .striped(true)
+                .show(ui, |ui| {
+                    for (name, counts) in [
+                        ("Groups", &diff.groups),
+                        ("Images", &diff.images),
+                        ("Image arrays", &diff.image_arrays),
+                        ("Sounds", &diff.sounds),
+                        ("Animations", &diff.animations),
+                    ] {
+                        ui.label(name);
+                        ui.label(fmt_counts(counts));
+                        ui.end_row();
+                    }
+                })
+                .response
+                .on_hover_text(
+                    "Added (only in the current map), \
+                    removed (only in the file) & modified entries.",
+                );
+
+            ui.separator();
+            ScrollArea::vertical().show(ui, |ui| {
+                Grid::new("map_diff_layers_grid")
+                    .num_columns(4)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for layer in diff.layers.iter() {
+                            if layer.tiles.is_empty() {
+                                ui.label("");
+                            } else {
+                                let mut visible = !state.hidden_layers.contains(&layer.index);
+                                if ui
+                                    .checkbox(&mut visible, "")
+                                    .on_hover_text("Highlight the tiles that differ.")
+                                    .changed()
+                                {
+                                    if visible {
+                                        state.hidden_layers.retain(|index| *index != layer.index);
+                                    } else {
+                                        state.hidden_layers.push(layer.index);
+                                    }
+                                }
+                            }
+                            ui.label(RichText::new(&layer.name).color(change_color(layer.change)));
+                            ui.label(layer_summary(layer));
+                            if ui
+                                .add_enabled(layer.can_revert(), Button::new("Revert"))
+                                .on_hover_text(
+                                    "Replaces the tiles, quads or sounds of the layer \
+                                    with the ones of the file.",
+                                )
+                                .on_disabled_hover_text(
+                                    "Only the content of layers that exist in both maps \
+                                    with the same size can be reverted.",
+                                )
+                                .clicked()
+                            {
+                                revert = Some(layer.clone());
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+        });
+    ui_state.add_blur_rect(res.response.rect, 0.0);
+
+    if close {
+        tab.map.user.ui_values.map_diff_panel_open = None;
+        return;
+    }
+    if let Some(layer) = revert {
+        let file = state.file.clone();
+        let cur_map: Map = tab.map.clone().into();
+        let actions = revert_layer_actions(&cur_map, &file, &layer);
+        if !actions.is_empty() {
+            tab.client.execute_group(EditorActionGroup {
+                actions,
+                identifier: None,
+            });
+        }
+        if let Some(state) = &mut tab.map.user.ui_values.map_diff_panel_open {
+            state.diff = None;
+        }
+    }
+}
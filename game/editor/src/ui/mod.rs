@@ -5,16 +5,25 @@ pub mod auto_saver;
 pub mod bottom_panel;
 pub mod chat_panel;
 pub mod close_modal;
+pub mod color_edit;
 pub mod dbg_panel;
 pub mod dotted_rect;
 pub mod group_and_layer;
+pub mod group_templates;
+pub mod guides;
+pub mod history_panel;
 pub mod hotkey_panel;
 pub mod left_panel;
 pub mod main_frame;
+pub mod map_diff_panel;
 pub mod mapper_cursors;
+pub mod number_usage_panel;
 pub mod page;
+pub mod physics_from_design;
+pub mod region_locks;
 pub mod server_config_variables;
 pub mod server_settings;
+pub mod tile_replace;
 pub mod tool_overlays;
 pub mod top_menu;
 pub mod top_tabs;
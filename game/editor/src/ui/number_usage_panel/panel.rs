@@ -0,0 +1,236 @@
+use std::time::Duration;
+
+use egui::{Button, Color32, DragValue, Grid, Modal, RichText, ScrollArea};
+use ui_base::types::{UiRenderPipe, UiState};
+
+use crate::{
+    actions::actions::EditorActionGroup,
+    physics_numbers::{
+        PhysicsNumberKind, PhysicsNumberLayer, compact_mapping, mapping_conflicts, remap_mapping,
+    },
+    ui::user_data::UserDataWithTab,
+};
+
+/// Counting the tiles of big layers is not free, so it is only redone after this time.
+const USAGE_RECHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_state: &mut UiState) {
+    let cur_time = pipe.cur_time;
+    let tab = &mut *pipe.user_data.editor_tab;
+    let Some(state) = &mut tab.map.user.ui_values.number_usage_panel_open else {
+        return;
+    };
+    let physics = &mut tab.map.groups.physics;
+
+    let number_layers: Vec<(usize, PhysicsNumberKind)> = physics
+        .layers
+        .iter()
+        .enumerate()
+        .filter_map(|(index, layer)| {
+            PhysicsNumberLayer::from_layer(layer).map(|layer| (index, layer.kind()))
+        })
+        .collect();
+    if state
+        .layer_index
+        .is_none_or(|layer_index| !number_layers.iter().any(|(index, _)| *index == layer_index))
+    {
+        state.layer_index = number_layers.first().map(|(index, _)| *index);
+    }
+
+    let mut renumber = None;
+    let res = egui::SidePanel::right("number_usage_panel")
+        .resizable(true)
+        .width_range(200.0..=500.0)
+        .default_width(250.0)
+        .show_inside(ui, |ui| {
+            ui.heading("Number usage");
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                for (index, kind) in number_layers.iter() {
+                    if ui
+                        .add(Button::new(kind.name()).selected(state.layer_index == Some(*index)))
+                        .clicked()
+                    {
+                        state.layer_index = Some(*index);
+                    }
+                }
+            });
+            let Some((layer_index, layer)) = state.layer_index.and_then(|index| {
+                physics
+                    .layers
+                    .get(index)
+                    .and_then(PhysicsNumberLayer::from_layer)
+                    .map(|layer| (index, layer))
+            }) else {
+                ui.label("The physics group has no tele, switch or tune layer.");
+                return;
+            };
+
+            if state.usage.as_ref().is_none_or(|(time, index, _)| {
+                *index != layer_index || cur_time.saturating_sub(*time) >= USAGE_RECHECK_INTERVAL
+            }) {
+                state.usage = Some((cur_time, layer_index, layer.usage()));
+            }
+            let Some((_, _, usage)) = &state.usage else {
+                return;
+            };
+            let kind = layer.kind();
+
+            ui.separator();
+            ScrollArea::vertical()
+                .max_height(ui.available_height() - 100.0)
+                .show(ui, |ui| {
+                    if usage.is_empty() {
+                        ui.label("No numbers are used.");
+                        return;
+                    }
+                    Grid::new("number_usage_grid")
+                        .num_columns(3)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.label("Number");
+                            ui.label("Sources");
+                            ui.label(if let PhysicsNumberKind::Tune = kind {
+                                "Settings"
+                            } else {
+                                "Destinations"
+                            });
+                            ui.end_row();
+
+                            for (number, number_usage) in usage.iter() {
+                                let mut text = RichText::new(format!("#{number}"));
+                                if number_usage.is_unreferenced() {
+                                    text = text.color(Color32::RED);
+                                }
+                                let mut btn =
+                                    ui.add(Button::new(text).selected(state.remap_from == *number));
+                                if number_usage.is_unreferenced() {
+                                    btn = btn.on_hover_text(
+                                        "This number has no sources or no destinations.",
+                                    );
+                                }
+                                if btn.clicked() {
+                                    state.remap_from = *number;
+                                }
+                                ui.label(number_usage.sources.to_string());
+                                ui.label(if let PhysicsNumberKind::Tune = kind {
+                                    if number_usage.has_metadata {
+                                        "yes"
+                                    } else {
+                                        "no"
+                                    }
+                                    .to_string()
+                                } else {
+                                    number_usage.destinations.to_string()
+                                });
+                                ui.end_row();
+                            }
+                        });
+                });
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.add(
+                    DragValue::new(&mut state.remap_from)
+                        .range(1..=u8::MAX)
+                        .prefix("From: "),
+                );
+                ui.add(
+                    DragValue::new(&mut state.remap_to)
+                        .range(1..=u8::MAX)
+                        .prefix("To: "),
+                );
+            });
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(
+                        state.remap_from != state.remap_to && usage.contains_key(&state.remap_from),
+                        Button::new("Renumber"),
+                    )
+                    .clicked()
+                {
+                    renumber = Some((layer_index, remap_mapping(state.remap_from, state.remap_to)));
+                }
+                if ui
+                    .button("Compact")
+                    .on_hover_text("Removes all gaps between the used numbers.")
+                    .clicked()
+                {
+                    renumber = Some((layer_index, compact_mapping(usage)));
+                }
+            });
+        });
+    ui_state.add_blur_rect(res.response.rect, 0.0);
+
+    // numbers that are already in use must be merged explicitly
+    if let Some((_, mapping)) = &renumber
+        && state
+            .usage
+            .as_ref()
+            .is_some_and(|(_, _, usage)| !mapping_conflicts(usage, mapping).is_empty())
+    {
+        state.pending_merge = renumber.take().map(|(_, mapping)| mapping);
+    }
+
+    if let Some(mapping) = &state.pending_merge {
+        let conflicts = state
+            .usage
+            .as_ref()
+            .map(|(_, _, usage)| mapping_conflicts(usage, mapping))
+            .unwrap_or_default();
+        let mut merge = None;
+        Modal::new("number-usage-merge-confirm".into()).show(ui.ctx(), |ui| {
+            ui.label(format!(
+                "The number(s) {} are already in use.",
+                conflicts
+                    .iter()
+                    .map(|number| format!("#{number}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+            ui.label("Merging keeps the names & settings of the numbers that are already in use.");
+            ui.horizontal(|ui| {
+                if ui.button("Merge").clicked() {
+                    merge = Some(true);
+                }
+                if ui.button("Abort").clicked() {
+                    merge = Some(false);
+                }
+            });
+        });
+        *pipe.user_data.pointer_is_used = true;
+        if let Some(merge) = merge {
+            if merge && let Some(layer_index) = state.layer_index {
+                renumber = Some((layer_index, mapping.clone()));
+            }
+            state.pending_merge = None;
+        }
+    }
+
+    if let Some((layer_index, mapping)) = renumber {
+        let Some(layer) = physics
+            .layers
+            .get(layer_index)
+            .and_then(PhysicsNumberLayer::from_layer)
+        else {
+            return;
+        };
+        let actions: Vec<_> = layer.renumber_actions(
+            layer_index,
+            physics.attr.width,
+            physics.attr.height,
+            &mapping,
+        );
+        if !actions.is_empty() {
+            tab.client.execute_group(EditorActionGroup {
+                actions,
+                identifier: None,
+            });
+        }
+        state.usage = None;
+        physics.user.active_tele_in_use = None;
+        physics.user.active_switch_in_use = None;
+        physics.user.active_tune_zone_in_use = None;
+    }
+}
@@ -0,0 +1,228 @@
+use egui::{Button, Color32, ComboBox, DragValue, Grid, Window};
+use ui_base::types::{UiRenderPipe, UiState};
+
+use crate::{
+    actions::actions::EditorActionGroup,
+    event::EditorEventLayerIndex,
+    map::{EditorLayer, EditorPhysicsLayer},
+    physics_from_design::{
+        PhysicsFromDesignMapping, PhysicsFromDesignOptions, physics_from_design,
+    },
+    tile_replace::parse_tile_indices,
+    ui::{user_data::UserDataWithTab, utils::group_name},
+};
+
+/// Parses the tile indices of a mapping,
+/// an empty text maps no tiles.
+fn parse_mapping_indices(text: &str) -> Option<Vec<u8>> {
+    if text.trim().is_empty() {
+        Some(Vec::new())
+    } else {
+        parse_tile_indices(text)
+    }
+}
+
+/// Generates the physics game layer from a design tile layer.
+pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_state: &mut UiState) {
+    let tab = &mut *pipe.user_data.editor_tab;
+    let Some(mut state) = tab.map.user.ui_values.physics_from_design_open.take() else {
+        return;
+    };
+
+    // all design tile layers
+    let tile_layers: Vec<(EditorEventLayerIndex, String)> = [
+        (true, &tab.map.groups.background),
+        (false, &tab.map.groups.foreground),
+    ]
+    .into_iter()
+    .flat_map(|(is_background, groups)| {
+        groups
+            .iter()
+            .enumerate()
+            .flat_map(move |(group_index, group)| {
+                group
+                    .layers
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, layer)| matches!(layer, EditorLayer::Tile(_)))
+                    .map(move |(layer_index, layer)| {
+                        let name = match layer.name() {
+                            "" => {
+                                format!("{} / Layer #{layer_index}", group_name(group, group_index))
+                            }
+                            name => format!("{} / \"{name}\"", group_name(group, group_index)),
+                        };
+                        (
+                            EditorEventLayerIndex {
+                                is_background,
+                                group_index,
+                                layer_index,
+                            },
+                            name,
+                        )
+                    })
+            })
+    })
+    .collect();
+    if state
+        .layer
+        .is_some_and(|index| !tile_layers.iter().any(|(i, _)| *i == index))
+    {
+        state.layer = None;
+    }
+
+    let unhookable = parse_mapping_indices(&state.unhookable);
+    let death = parse_mapping_indices(&state.death);
+
+    let mut open = true;
+    let mut generate = false;
+    let window_res = Window::new("Generate physics from layer")
+        .open(&mut open)
+        .resizable(false)
+        .show(ui.ctx(), |ui| {
+            Grid::new("physics-from-design-grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("Design layer:");
+                    ComboBox::new("physics-from-design-layer", "")
+                        .selected_text(
+                            state
+                                .layer
+                                .and_then(|index| {
+                                    tile_layers
+                                        .iter()
+                                        .find(|(i, _)| *i == index)
+                                        .map(|(_, name)| name.clone())
+                                })
+                                .unwrap_or_else(|| "None".to_string()),
+                        )
+                        .show_ui(ui, |ui| {
+                            for (index, name) in tile_layers.iter() {
+                                ui.selectable_value(&mut state.layer, Some(*index), name);
+                            }
+                        });
+                    ui.end_row();
+                    ui.label("Unhookable:");
+                    ui.text_edit_singleline(&mut state.unhookable)
+                        .on_hover_text("Design tile indices, e.g. `1, 5, 10-12`.");
+                    ui.end_row();
+                    ui.label("Death:");
+                    ui.text_edit_singleline(&mut state.death)
+                        .on_hover_text("Design tile indices, e.g. `1, 5, 10-12`.");
+                    ui.end_row();
+                    ui.label("Offset:");
+                    ui.horizontal(|ui| {
+                        ui.add(DragValue::new(&mut state.offset_x).prefix("x: "));
+                        ui.add(DragValue::new(&mut state.offset_y).prefix("y: "));
+                    })
+                    .response
+                    .on_hover_text(
+                        "Where the top left tile of the design layer \
+                        lands in the physics group.",
+                    );
+                    ui.end_row();
+                    ui.label("Clear empty:");
+                    ui.checkbox(&mut state.clear_empty, "").on_hover_text(
+                        "Removes the game tiles below empty tiles of the design layer.",
+                    );
+                    ui.end_row();
+                });
+            ui.label("All other non-empty tiles become hookable.");
+            ui.separator();
+
+            let design = state.layer.and_then(|index| {
+                let groups = if index.is_background {
+                    &tab.map.groups.background
+                } else {
+                    &tab.map.groups.foreground
+                };
+                groups
+                    .get(index.group_index)
+                    .and_then(|group| group.layers.get(index.layer_index))
+                    .and_then(|layer| match layer {
+                        EditorLayer::Tile(layer) => Some(&layer.layer),
+                        _ => None,
+                    })
+            });
+            let physics = &tab.map.groups.physics;
+            let game_layer =
+                physics
+                    .layers
+                    .iter()
+                    .enumerate()
+                    .find_map(|(index, layer)| match layer {
+                        EditorPhysicsLayer::Game(layer) => Some((index, &layer.layer.tiles)),
+                        _ => None,
+                    });
+
+            // dry run, the changes are only applied when generating
+            let preview = match (design, game_layer, &unhookable, &death) {
+                (None, ..) => Err("Select a design tile layer.".to_string()),
+                (_, None, ..) => Err("The map has no game layer.".to_string()),
+                (_, _, None, _) | (_, _, _, None) => {
+                    Err("The tile indices of the mapping are invalid.".to_string())
+                }
+                (
+                    Some(design),
+                    Some((game_layer_index, game_tiles)),
+                    Some(unhookable),
+                    Some(death),
+                ) => physics_from_design(
+                    design,
+                    game_layer_index,
+                    game_tiles,
+                    physics.attr.width,
+                    physics.attr.height,
+                    &PhysicsFromDesignOptions {
+                        mapping: PhysicsFromDesignMapping {
+                            unhookable: unhookable.clone(),
+                            death: death.clone(),
+                        },
+                        offset_x: state.offset_x,
+                        offset_y: state.offset_y,
+                        clear_empty: state.clear_empty,
+                    },
+                )
+                .map_err(|err| err.to_string()),
+            };
+            match &preview {
+                Ok(res) => {
+                    ui.label(format!("{} game tile(s) will change.", res.changed));
+                }
+                Err(err) => {
+                    ui.colored_label(Color32::RED, err);
+                }
+            }
+            if let Some(changed) = state.last_generate {
+                ui.label(format!("Changed {changed} game tile(s)."));
+            }
+            let can_generate = preview.as_ref().is_ok_and(|res| res.action.is_some());
+            if ui
+                .add_enabled(can_generate, Button::new("Generate"))
+                .on_hover_text("Can be undone at once.")
+                .clicked()
+            {
+                generate = true;
+            }
+            preview.ok()
+        });
+
+    if generate
+        && let Some(Some(res)) = window_res.as_ref().and_then(|res| res.inner.as_ref())
+        && let Some(action) = &res.action
+    {
+        tab.client.execute_group(EditorActionGroup {
+            actions: vec![action.clone()],
+            identifier: None,
+        });
+        state.last_generate = Some(res.changed);
+    }
+
+    if let Some(window_res) = &window_res {
+        ui_state.add_blur_rect(window_res.response.rect, 0.0);
+        *pipe.user_data.pointer_is_used |= ui.rect_contains_pointer(window_res.response.rect);
+    }
+    if open {
+        tab.map.user.ui_values.physics_from_design_open = Some(state);
+    }
+}
@@ -0,0 +1,121 @@
+use camera::CameraInterface;
+use egui::{Color32, Stroke};
+use graphics::handles::canvas::canvas::GraphicsCanvasHandle;
+use graphics_types::rendering::State;
+
+use crate::{
+    locks::EditorLockLayer,
+    map::{EditorLayer, EditorMapInterface},
+    tools::tile_layer::shared::TILE_VISUAL_SIZE,
+    ui::{dotted_rect::draw_dotted_rect, user_data::EditorTabsRefMut},
+};
+
+/// Renders a hatched overlay over all areas locked by other users.
+pub fn render(
+    ui: &mut egui::Ui,
+    canvas_handle: &GraphicsCanvasHandle,
+    tabs: &mut EditorTabsRefMut<'_>,
+) {
+    if let Some(tab) = tabs.active_tab() {
+        for lock in tab
+            .client
+            .locks
+            .iter()
+            .filter(|lock| lock.owner != tab.client.server_id)
+        {
+            let (group_attr, width, height) = match &lock.layer {
+                EditorLockLayer::Design(index) => {
+                    let Some((group, layer)) = if index.is_background {
+                        &tab.map.groups.background
+                    } else {
+                        &tab.map.groups.foreground
+                    }
+                    .get(index.group_index)
+                    .and_then(|group| {
+                        group
+                            .layers
+                            .get(index.layer_index)
+                            .map(|layer| (group, layer))
+                    }) else {
+                        continue;
+                    };
+                    // only tile layers have an area that can be visualized
+                    let EditorLayer::Tile(layer) = layer else {
+                        continue;
+                    };
+                    (
+                        Some(group.attr),
+                        layer.layer.attr.width.get(),
+                        layer.layer.attr.height.get(),
+                    )
+                }
+                EditorLockLayer::Physics { .. } => (
+                    None,
+                    tab.map.groups.physics.attr.width.get(),
+                    tab.map.groups.physics.attr.height.get(),
+                ),
+            };
+            let (x, y, w, h) = lock
+                .rect
+                .map(|rect| (rect.x, rect.y, rect.w, rect.h))
+                .unwrap_or((0, 0, width, height));
+
+            let mut state = State::new();
+            tab.map
+                .game_camera()
+                .project(canvas_handle, &mut state, group_attr.as_ref());
+
+            let size = ui.ctx().screen_rect().size();
+            let (x0, y0, x1, y1) = state.get_canvas_mapping();
+
+            let width_scale = size.x / (x1 - x0);
+            let height_scale = size.y / (y1 - y0);
+            let to_screen = |x: u16, y: u16| {
+                egui::pos2(
+                    (x as f32 * TILE_VISUAL_SIZE - x0) * width_scale,
+                    (y as f32 * TILE_VISUAL_SIZE - y0) * height_scale,
+                )
+            };
+            let rect = egui::Rect::from_min_max(
+                to_screen(x, y),
+                to_screen(x.saturating_add(w), y.saturating_add(h)),
+            );
+            if !rect.intersects(ui.clip_rect()) {
+                continue;
+            }
+
+            let color = tab
+                .client
+                .clients
+                .iter()
+                .find(|c| c.server_id == lock.owner)
+                .map(|c| Color32::from_rgb(c.color[0], c.color[1], c.color[2]))
+                .unwrap_or(Color32::RED);
+
+            // diagonal hatching, clipped to the locked area
+            let painter = ui.painter().with_clip_rect(rect.intersect(ui.clip_rect()));
+            let stroke = Stroke::new(1.0, color.gamma_multiply(0.6));
+            const HATCH_SPACING: f32 = 12.0;
+            let mut off = 0.0;
+            while off < rect.width() + rect.height() {
+                painter.line_segment(
+                    [
+                        egui::pos2(rect.min.x + off, rect.min.y),
+                        egui::pos2(rect.min.x + off - rect.height(), rect.max.y),
+                    ],
+                    stroke,
+                );
+                off += HATCH_SPACING;
+            }
+
+            draw_dotted_rect(ui, rect, 4.0, color);
+            ui.painter().text(
+                rect.min,
+                egui::Align2::LEFT_BOTTOM,
+                format!("Locked by {}", lock.owner_name),
+                Default::default(),
+                color,
+            );
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use egui::{Button, CollapsingHeader, DragValue, Grid, ScrollArea, Window};
+use math::math::vector::vec2;
+use ui_base::types::{UiRenderPipe, UiState};
+
+use crate::{
+    actions::actions::EditorActionGroup,
+    map::{EditorLayerUnionRef, EditorMapGroupsInterface, EditorMapInterface},
+    tile_replace::{TileReplaceLayer, parse_tile_indices, replace_tiles},
+    ui::{
+        user_data::UserDataWithTab,
+        utils::{group_name, layer_name_phy},
+    },
+};
+
+fn layer_name(layer: &EditorLayerUnionRef) -> String {
+    match layer {
+        EditorLayerUnionRef::Physics {
+            layer, layer_index, ..
+        } => format!("Physics {}", layer_name_phy(layer, *layer_index)),
+        EditorLayerUnionRef::Design {
+            layer,
+            group,
+            group_index,
+            layer_index,
+            ..
+        } => match layer.name() {
+            "" => format!("{} / Layer #{layer_index}", group_name(group, *group_index)),
+            name => format!("{} / \"{name}\"", group_name(group, *group_index)),
+        },
+    }
+}
+
+/// Finds & replaces tile indices in the selected tile layers,
+/// or in the active layer if no layer is selected.
+pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_state: &mut UiState) {
+    let tab = &mut *pipe.user_data.editor_tab;
+    let Some(mut state) = tab.map.user.ui_values.tile_replace_open.take() else {
+        return;
+    };
+
+    let mut layers: Vec<_> = tab.map.groups.selected_layers();
+    if layers.is_empty() {
+        layers.extend(tab.map.active_layer());
+    }
+    let layers: Vec<(String, TileReplaceLayer)> = layers
+        .iter()
+        .filter_map(|layer| {
+            TileReplaceLayer::from_union(layer).map(|tile_layer| (layer_name(layer), tile_layer))
+        })
+        .collect();
+    let find = parse_tile_indices(&state.find);
+
+    let mut open = true;
+    let mut jump_to = None;
+    let mut replace = false;
+    let window_res = Window::new("Find & replace tiles")
+        .open(&mut open)
+        .resizable(true)
+        .show(ui.ctx(), |ui| {
+            Grid::new("tile-replace-grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("Find:");
+                    ui.text_edit_singleline(&mut state.find)
+                        .on_hover_text("Tile indices, e.g. `1, 5, 10-12`.");
+                    ui.end_row();
+                    ui.label("Replace with:");
+                    ui.add(DragValue::new(&mut state.replace_with).range(0..=u8::MAX))
+                        .on_hover_text("`0` replaces the tiles with air.");
+                    ui.end_row();
+                    ui.label("Keep rotation:");
+                    ui.checkbox(&mut state.keep_flags, "")
+                        .on_hover_text("Keeps the rotation & mirroring of the replaced tiles.");
+                    ui.end_row();
+                });
+
+            if layers.is_empty() {
+                ui.label(
+                    "Select tile layers in the layer list \
+                    (SHIFT/CTRL + click) to search them.",
+                );
+            } else {
+                ui.label(format!("Searching {} tile layer(s).", layers.len()));
+            }
+            ui.horizontal(|ui| {
+                let can_search = find.is_some() && !layers.is_empty();
+                if ui.add_enabled(can_search, Button::new("Find")).clicked()
+                    && let Some(find) = &find
+                {
+                    state.results = layers
+                        .iter()
+                        .map(|(name, layer)| (name.clone(), layer.find(find)))
+                        .filter(|(_, found)| !found.is_empty())
+                        .collect();
+                    state.last_replace = None;
+                }
+                if ui
+                    .add_enabled(can_search, Button::new("Replace all"))
+                    .on_hover_text(
+                        "Physics tiles are only replaced by tiles of the same kind, \
+                        e.g. a teleporter can't become a hookable tile.",
+                    )
+                    .clicked()
+                {
+                    replace = true;
+                }
+            });
+
+            if let Some((replaced, incompatible)) = state.last_replace {
+                ui.label(format!("Replaced {replaced} tile(s)."));
+                if incompatible > 0 {
+                    ui.label(format!(
+                        "{incompatible} physics tile(s) were kept, \
+                        because the new tile does not fit into their layer."
+                    ));
+                }
+            }
+
+            ui.separator();
+            ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                if state.results.is_empty() {
+                    ui.label("No tiles found.");
+                }
+                for (name, found) in state.results.iter() {
+                    CollapsingHeader::new(format!("{name} ({})", found.len()))
+                        .id_salt(name)
+                        .show(ui, |ui| {
+                            for tile in found.iter() {
+                                if ui
+                                    .button(format!("{}, {} (#{})", tile.x, tile.y, tile.index))
+                                    .on_hover_text("Jump to the tile.")
+                                    .clicked()
+                                {
+                                    jump_to =
+                                        Some(vec2::new(tile.x as f32 + 0.5, tile.y as f32 + 0.5));
+                                }
+                            }
+                        });
+                }
+            });
+        });
+
+    if replace && let Some(find) = &find {
+        let layers: Vec<_> = layers.into_iter().map(|(_, layer)| layer).collect();
+        let res = replace_tiles(&layers, find, state.replace_with, state.keep_flags);
+        if !res.actions.is_empty() {
+            tab.client.execute_group(EditorActionGroup {
+                actions: res.actions,
+                identifier: None,
+            });
+        }
+        state.results.clear();
+        state.last_replace = Some((res.replaced, res.incompatible));
+    }
+
+    if let Some(pos) = jump_to {
+        tab.map.groups.user.pos = pos;
+    }
+    if let Some(window_res) = &window_res {
+        ui_state.add_blur_rect(window_res.response.rect, 0.0);
+        *pipe.user_data.pointer_is_used |= ui.rect_contains_pointer(window_res.response.rect);
+    }
+    if open {
+        tab.map.user.ui_values.tile_replace_open = Some(state);
+    }
+}
@@ -1,8 +1,11 @@
 use egui::{Color32, FontId, Shape};
 use ui_base::types::UiRenderPipe;
 
+use math::math::vector::vec2;
+use vanilla::collision::collision::Tunings;
+
 use crate::{
-    map::EditorMapInterface,
+    map::{EditorLayerUnionRef, EditorMapInterface},
     tools::{
         quad_layer::{self, selection::QuadPointerDownState},
         sound_layer,
@@ -10,9 +13,50 @@ use crate::{
         tool::{ActiveTool, ActiveToolQuads, ActiveToolSounds, ActiveToolTiles},
     },
     ui::user_data::UserDataWithTab,
+    utils::world_pos_to_ui_pos,
 };
 
+/// Labels the measurements of the measure tool, for all tools.
+fn render_measurements(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
+    let map = &pipe.user_data.editor_tab.map;
+    let tunings = map
+        .active_layer()
+        .is_some_and(|layer| matches!(layer, EditorLayerUnionRef::Physics { .. }))
+        .then(Tunings::default);
+    for measurement in pipe.user_data.tools.tiles.measure.measurements() {
+        let center = (measurement.start + measurement.end) / 2.0;
+        let pos = world_pos_to_ui_pos(
+            pipe.user_data.canvas_handle,
+            &ui.ctx().screen_rect(),
+            map.groups.user.zoom,
+            center,
+            map.groups.user.pos.x,
+            map.groups.user.pos.y,
+            0.0,
+            0.0,
+            100.0,
+            100.0,
+            map.groups.user.parallax_aware_zoom,
+        ) + vec2::new(10.0, 10.0);
+
+        let bg = ui.painter().add(Shape::Noop);
+        let rect = ui.painter().text(
+            egui::pos2(pos.x, pos.y),
+            egui::Align2::LEFT_TOP,
+            measurement.label(tunings.as_ref()),
+            FontId::monospace(16.0),
+            Color32::WHITE,
+        );
+        ui.painter().set(
+            bg,
+            Shape::rect_filled(rect.expand(5.0), 5.0, Color32::from_black_alpha(125)),
+        );
+    }
+}
+
 pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
+    render_measurements(ui, pipe);
+
     let tools = &mut *pipe.user_data.tools;
 
     let pos = |ui_pos: egui::Pos2| {
@@ -105,6 +149,7 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
                     );
                 }
             }
+            ActiveToolTiles::Measure => {}
         },
         ActiveTool::Quads(ty) => {
             let count = match ty {
@@ -1,7 +1,7 @@
 use std::{path::PathBuf, time::Duration};
 
 use base::hash::fmt_hash;
-use egui::{Align2, Button, DragValue, Grid, Popup, TextEdit, Window};
+use egui::{Align2, Button, DragValue, Grid, Popup, ProgressBar, TextEdit, Window};
 use egui_file_dialog::{DialogMode, DialogState};
 use network::network::utils::create_certifified_keys;
 use ui_base::types::{UiRenderPipe, UiState};
@@ -12,13 +12,53 @@ use crate::{
         EditorHotkeyEvent, EditorHotkeyEventEdit, EditorHotkeyEventFile, EditorHotkeyEventPanels,
         EditorHotkeyEventPreferences,
     },
+    notifications::EditorNotification,
+    resource_optimizer::ResourceOptimizeOptions,
     tab::EditorAdminPanelState,
     ui::user_data::{
         EditorMenuDialogJoinProps, EditorMenuDialogMode, EditorMenuHostDialogMode,
-        EditorMenuHostNetworkOptions, EditorUiEvent, EditorUiEventHostMap, UserData,
+        EditorMenuHostNetworkOptions, EditorMenuOptimizeResources, EditorUiEvent,
+        EditorUiEventHostMap, UserData,
     },
 };
 
+fn fmt_size(bytes: usize) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    }
+}
+
+/// The options next to the file list of the save dialog.
+fn render_optimize_options(ui: &mut egui::Ui, optimize: &mut Option<ResourceOptimizeOptions>) {
+    ui.set_max_width(200.0);
+    let mut enabled = optimize.is_some();
+    ui.checkbox(&mut enabled, "Optimize resources")
+        .on_hover_text(
+            "Re-encodes the images of the map without metadata before saving.\n\
+            The projected savings are shown before anything is saved.",
+        );
+    if enabled != optimize.is_some() {
+        *optimize = enabled.then(Default::default);
+    }
+    let Some(options) = optimize else {
+        return;
+    };
+    let mut downscale = options.max_size.is_some();
+    ui.checkbox(&mut downscale, "Downscale large images");
+    if downscale != options.max_size.is_some() {
+        options.max_size = downscale.then_some(1024);
+    }
+    if let Some(max_size) = &mut options.max_size {
+        ui.horizontal(|ui| {
+            ui.label("Max size:");
+            ui.add(DragValue::new(max_size).range(16..=4096));
+        });
+    }
+    ui.checkbox(&mut options.palette, "Palette for images with few colors");
+}
+
 pub fn render(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPipe<UserData>) {
     let style = ui.style();
     // 4.0 is some margin for strokes
@@ -62,6 +102,36 @@ pub fn render(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPipe
                         {
                             *menu_dialog_mode = EditorMenuDialogMode::save(pipe.user_data.io);
                         }
+                        // only useful for joined maps, the host has the map already
+                        let can_save_copy =
+                            pipe.user_data.editor_tabs.active_tab().is_some_and(|tab| {
+                                tab.server.is_none() && tab.client.snapshot_download.is_none()
+                            });
+                        if ui
+                            .add_enabled(can_save_copy, Button::new("Save local copy"))
+                            .on_hover_text(
+                                "Downloads the current map of the server \
+                                & saves it as a new map.",
+                            )
+                            .clicked()
+                        {
+                            pipe.user_data.ui_events.push(EditorUiEvent::SaveLocalCopy);
+                        }
+                        ui.separator();
+                        if ui.button("Import group").clicked() {
+                            *menu_dialog_mode =
+                                EditorMenuDialogMode::import_group(pipe.user_data.io);
+                        }
+                        if ui
+                            .button("Compare with file")
+                            .on_hover_text(
+                                "Highlights the differences between the current map \
+                                & a saved map file.",
+                            )
+                            .clicked()
+                        {
+                            *menu_dialog_mode = EditorMenuDialogMode::diff_map(pipe.user_data.io);
+                        }
                         ui.separator();
                         if ui.button("Host map").clicked() {
                             *menu_dialog_mode = EditorMenuDialogMode::host(pipe.user_data.io);
@@ -132,6 +202,55 @@ pub fn render(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPipe
                         {
                             pipe.user_data.ui_events.push(EditorUiEvent::Redo);
                         }
+                        ui.separator();
+                        if let Some(tab) = pipe.user_data.editor_tabs.active_tab()
+                            && ui
+                                .add(
+                                    Button::new("Find & replace tiles")
+                                        .selected(
+                                            tab.map.user.ui_values.tile_replace_open.is_some(),
+                                        )
+                                        .shortcut_text(binds.fmt_ev_bind(
+                                            per_ev,
+                                            &EditorHotkeyEvent::Edit(
+                                                EditorHotkeyEventEdit::FindReplaceTiles,
+                                            ),
+                                        )),
+                                )
+                                .clicked()
+                        {
+                            let open = &mut tab.map.user.ui_values.tile_replace_open;
+                            *open = match open {
+                                Some(_) => None,
+                                None => Some(Default::default()),
+                            };
+                        }
+                        if let Some(tab) = pipe.user_data.editor_tabs.active_tab()
+                            && ui
+                                .add(Button::new("Generate physics from layer").selected(
+                                    tab.map.user.ui_values.physics_from_design_open.is_some(),
+                                ))
+                                .clicked()
+                        {
+                            let open = &mut tab.map.user.ui_values.physics_from_design_open;
+                            *open = match open {
+                                Some(_) => None,
+                                None => Some(Default::default()),
+                            };
+                        }
+                        if let Some(tab) = pipe.user_data.editor_tabs.active_tab()
+                            && ui
+                                .add(Button::new("Group templates").selected(
+                                    tab.map.user.ui_values.group_templates_open.is_some(),
+                                ))
+                                .clicked()
+                        {
+                            let open = &mut tab.map.user.ui_values.group_templates_open;
+                            *open = match open {
+                                Some(_) => None,
+                                None => Some(Default::default()),
+                            };
+                        }
                     });
 
                     let hotkeys_open = &mut pipe.user_data.editor_options.hotkeys_open;
@@ -209,6 +328,15 @@ pub fn render(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPipe
                             tab.admin_panel.open = !tab.admin_panel.open;
                         }
 
+                        if let Some(download) = &tab.client.snapshot_download {
+                            ui.add(
+                                ProgressBar::new(download.progress())
+                                    .desired_width(150.0)
+                                    .show_percentage()
+                                    .text("Downloading map"),
+                            );
+                        }
+
                         if tab.admin_panel.open {
                             Window::new("Admin panel")
                                 .anchor(Align2::CENTER_CENTER, (0.0, 0.0))
@@ -257,6 +385,32 @@ pub fn render(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPipe
                                                     *auto_save = Duration::from_secs(secs);
                                                 }
 
+                                                ui.label("Write backups.");
+                                                let mut do_backups =
+                                                    state.state.backup_every_nth_action.is_some();
+                                                ui.checkbox(&mut do_backups, "");
+                                                ui.end_row();
+                                                if !do_backups {
+                                                    state.state.backup_every_nth_action = None;
+                                                } else if state
+                                                    .state
+                                                    .backup_every_nth_action
+                                                    .is_none()
+                                                {
+                                                    state.state.backup_every_nth_action = Some(100);
+                                                }
+                                                if let Some(every_nth_action) =
+                                                    &mut state.state.backup_every_nth_action
+                                                {
+                                                    ui.label("Backup every n actions:");
+                                                    ui.add(
+                                                        DragValue::new(every_nth_action)
+                                                            .range(1..=u32::MAX)
+                                                            .update_while_editing(false),
+                                                    );
+                                                    ui.end_row();
+                                                }
+
                                                 if ui.button("Apply").clicked() {
                                                     pipe.user_data.ui_events.push(
                                                         EditorUiEvent::AdminChangeConfig {
@@ -327,8 +481,12 @@ pub fn render(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPipe
                     *menu_dialog_mode = EditorMenuDialogMode::open(pipe.user_data.io);
                 }
 
+                let is_map_save = matches!(menu_dialog_mode, EditorMenuDialogMode::Save { .. });
                 if let EditorMenuDialogMode::Open { file_dialog }
                 | EditorMenuDialogMode::Save { file_dialog }
+                | EditorMenuDialogMode::ExportGroup { file_dialog, .. }
+                | EditorMenuDialogMode::ImportGroup { file_dialog }
+                | EditorMenuDialogMode::DiffMap { file_dialog }
                 | EditorMenuDialogMode::Host {
                     mode: EditorMenuHostDialogMode::SelectMap { file_dialog },
                 } = menu_dialog_mode
@@ -336,11 +494,22 @@ pub fn render(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPipe
                     *pipe.user_data.pointer_is_used = true;
                     if file_dialog.state() == DialogState::Open {
                         let mode = file_dialog.mode();
-                        if let Some(selected) = file_dialog.update(ui.ctx()).picked() {
-                            let selected: PathBuf = selected.into();
+                        let optimize = &mut pipe.user_data.editor_options.optimize_resources;
+                        let picked = if is_map_save {
+                            file_dialog
+                                .update_with_right_panel_ui(ui.ctx(), &mut |ui, _| {
+                                    render_optimize_options(ui, optimize)
+                                })
+                                .picked()
+                                .map(PathBuf::from)
+                        } else {
+                            file_dialog.update(ui.ctx()).picked().map(PathBuf::from)
+                        };
+                        if let Some(selected) = picked {
                             if let EditorMenuDialogMode::Open { .. }
                             | EditorMenuDialogMode::Save { .. } = menu_dialog_mode
                             {
+                                let mut next_mode = EditorMenuDialogMode::None;
                                 match mode {
                                     DialogMode::PickFile => {
                                         pipe.user_data
@@ -351,11 +520,53 @@ pub fn render(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPipe
                                         todo!()
                                     }
                                     DialogMode::SaveFile => {
-                                        pipe.user_data
-                                            .ui_events
-                                            .push(EditorUiEvent::SaveFile { name: selected });
+                                        let tp = pipe.user_data.tp;
+                                        let io = pipe.user_data.io;
+                                        match (
+                                            pipe.user_data.editor_options.optimize_resources,
+                                            pipe.user_data.editor_tabs.active_tab(),
+                                        ) {
+                                            (Some(options), Some(tab)) => {
+                                                next_mode =
+                                                    EditorMenuDialogMode::optimize_resources(
+                                                        io, tp, &tab.map, selected, options,
+                                                    );
+                                            }
+                                            _ => {
+                                                pipe.user_data.ui_events.push(
+                                                    EditorUiEvent::SaveFile { name: selected },
+                                                );
+                                            }
+                                        }
                                     }
                                 }
+                                *menu_dialog_mode = next_mode;
+                            } else if let EditorMenuDialogMode::ExportGroup {
+                                is_background,
+                                group_index,
+                                layers,
+                                ..
+                            } = menu_dialog_mode
+                            {
+                                pipe.user_data.ui_events.push(EditorUiEvent::ExportGroup {
+                                    name: selected,
+                                    is_background: *is_background,
+                                    group_index: *group_index,
+                                    layers: layers.take(),
+                                });
+                                *menu_dialog_mode = EditorMenuDialogMode::None;
+                            } else if let EditorMenuDialogMode::ImportGroup { .. } =
+                                menu_dialog_mode
+                            {
+                                pipe.user_data.ui_events.push(EditorUiEvent::ImportGroup {
+                                    name: selected,
+                                    is_background: false,
+                                });
+                                *menu_dialog_mode = EditorMenuDialogMode::None;
+                            } else if let EditorMenuDialogMode::DiffMap { .. } = menu_dialog_mode {
+                                pipe.user_data
+                                    .ui_events
+                                    .push(EditorUiEvent::DiffMap { name: selected });
                                 *menu_dialog_mode = EditorMenuDialogMode::None;
                             } else if let EditorMenuDialogMode::Host { mode } = menu_dialog_mode {
                                 let (cert, private_key) = create_certifified_keys();
@@ -576,6 +787,133 @@ pub fn render(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPipe
                     } else {
                         false
                     };
+                } else if let EditorMenuDialogMode::OptimizeResources(state) = menu_dialog_mode {
+                    *pipe.user_data.pointer_is_used = true;
+                    if let Some(task) = state.task.take_if(|task| task.is_finished()) {
+                        match task.get() {
+                            Ok(report) => state.report = Some(report),
+                            Err(err) => {
+                                pipe.user_data.notifications.push(EditorNotification::Error(
+                                    format!("Failed to optimize the resources: {err}"),
+                                ));
+                                *menu_dialog_mode = EditorMenuDialogMode::None;
+                                return;
+                            }
+                        }
+                    }
+
+                    let window = egui::Window::new("Optimize resources")
+                        .resizable(false)
+                        .collapsible(false)
+                        .anchor(Align2::CENTER_CENTER, (0.0, 0.0));
+
+                    let mut apply = false;
+                    let mut skip = false;
+                    let mut cancel = false;
+                    window.show(ui.ctx(), |ui| {
+                        let Some(report) = &state.report else {
+                            ui.horizontal(|ui| {
+                                ui.spinner();
+                                ui.label("Optimizing images…");
+                            });
+                            if ui.button("Cancel").clicked() {
+                                cancel = true;
+                            }
+                            return;
+                        };
+                        if report.entries.is_empty() {
+                            ui.label("No image could be made smaller.");
+                        } else {
+                            Grid::new("optimize-resources-report")
+                                .num_columns(4)
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    ui.strong("Image");
+                                    ui.strong("Dimensions");
+                                    ui.strong("Size");
+                                    ui.strong("Savings");
+                                    ui.end_row();
+                                    for entry in report.entries.iter() {
+                                        ui.label(&entry.name);
+                                        if (entry.old_width, entry.old_height)
+                                            != (entry.width, entry.height)
+                                        {
+                                            ui.label(format!(
+                                                "{}x{} → {}x{}",
+                                                entry.old_width,
+                                                entry.old_height,
+                                                entry.width,
+                                                entry.height
+                                            ));
+                                        } else {
+                                            ui.label(format!("{}x{}", entry.width, entry.height));
+                                        }
+                                        ui.label(format!(
+                                            "{} → {}",
+                                            fmt_size(entry.old_size),
+                                            fmt_size(entry.file.len())
+                                        ));
+                                        ui.label(format!(
+                                            "{} ({:.0}%)",
+                                            fmt_size(entry.savings()),
+                                            entry.savings() as f64 * 100.0
+                                                / entry.old_size.max(1) as f64
+                                        ));
+                                        ui.end_row();
+                                    }
+                                });
+                            ui.label(format!(
+                                "Total: saves {} of {}",
+                                fmt_size(report.total_savings()),
+                                fmt_size(report.total_old_size())
+                            ));
+                        }
+                        for (name, err) in report.errors.iter() {
+                            ui.colored_label(
+                                ui.visuals().warn_fg_color,
+                                format!("{name} was skipped: {err}"),
+                            );
+                        }
+                        ui.label("The images in the editor stay untouched.");
+                        ui.horizontal(|ui| {
+                            if ui
+                                .add_enabled(
+                                    !report.entries.is_empty(),
+                                    Button::new("Apply & save"),
+                                )
+                                .clicked()
+                            {
+                                apply = true;
+                            }
+                            if ui.button("Save without optimizing").clicked() {
+                                skip = true;
+                            }
+                            if ui.button("Cancel").clicked() {
+                                cancel = true;
+                            }
+                        });
+                    });
+
+                    if apply || skip || cancel {
+                        let EditorMenuDialogMode::OptimizeResources(state) =
+                            std::mem::replace(menu_dialog_mode, EditorMenuDialogMode::None)
+                        else {
+                            return;
+                        };
+                        let EditorMenuOptimizeResources { path, report, .. } = *state;
+                        if let Some(report) = report.filter(|_| apply) {
+                            pipe.user_data
+                                .ui_events
+                                .push(EditorUiEvent::SaveFileOptimized {
+                                    name: path,
+                                    report: Box::new(report),
+                                });
+                        } else if skip {
+                            pipe.user_data
+                                .ui_events
+                                .push(EditorUiEvent::SaveFile { name: path });
+                        }
+                    }
                 }
 
                 pipe.user_data
@@ -618,6 +956,16 @@ pub fn render(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPipe
                 if cur_hotkeys.remove(&EditorHotkeyEvent::Edit(EditorHotkeyEventEdit::Undo)) {
                     pipe.user_data.ui_events.push(EditorUiEvent::Undo);
                 }
+                if cur_hotkeys.remove(&EditorHotkeyEvent::Edit(
+                    EditorHotkeyEventEdit::FindReplaceTiles,
+                )) && let Some(tab) = pipe.user_data.editor_tabs.active_tab()
+                {
+                    let open = &mut tab.map.user.ui_values.tile_replace_open;
+                    *open = match open {
+                        Some(_) => None,
+                        None => Some(Default::default()),
+                    };
+                }
             });
         });
 
@@ -0,0 +1,187 @@
+use egui::Button;
+use math::math::vector::{ffixed, vec2};
+use ui_base::types::UiRenderPipe;
+
+use crate::{
+    actions::actions::{
+        ActQuadLayerAddQuads, ActQuadLayerAddRemQuads, ActSoundLayerAddRemSounds,
+        ActSoundLayerAddSounds, EditorAction,
+    },
+    clipboard::{
+        EditorClipboard, EditorClipboardItems, EditorClipboardLayer, EditorClipboardPasteMode,
+    },
+    explain::TEXT_CLIPBOARD,
+    map::{EditorLayer, EditorLayerUnionRef, EditorMapInterface},
+    notifications::EditorNotification,
+    tools::tool::{ActiveTool, ActiveToolQuads, Tools},
+    ui::user_data::UserDataWithTab,
+    utils::ui_pos_to_world_pos,
+};
+
+/// The items selected by the active tool, at their world positions.
+fn selected_items(tools: &Tools, layer: &EditorLayer) -> Option<EditorClipboardItems> {
+    match (&tools.active_tool, layer) {
+        (ActiveTool::Quads(ActiveToolQuads::Brush), EditorLayer::Quad(_)) => {
+            tools.quads.brush.brush.as_ref().map(|brush| {
+                let mut quads = brush.quads.clone();
+                for point in quads.iter_mut().flat_map(|q| q.points.iter_mut()) {
+                    point.x += ffixed::from_num(brush.origin.x);
+                    point.y += ffixed::from_num(brush.origin.y);
+                }
+                EditorClipboardItems::Quads(quads)
+            })
+        }
+        (ActiveTool::Quads(ActiveToolQuads::Selection), EditorLayer::Quad(layer)) => {
+            tools.quads.selection.range.as_ref().map(|range| {
+                EditorClipboardItems::Quads(
+                    range
+                        .quads
+                        .keys()
+                        .filter_map(|&index| layer.layer.quads.get(index).copied())
+                        .collect(),
+                )
+            })
+        }
+        (ActiveTool::Sounds(_), EditorLayer::Sound(_)) => {
+            tools.sounds.brush.brush.as_ref().map(|brush| {
+                let mut sounds = brush.sounds.clone();
+                for sound in sounds.iter_mut() {
+                    sound.pos.x += ffixed::from_num(brush.origin.x);
+                    sound.pos.y += ffixed::from_num(brush.origin.y);
+                }
+                EditorClipboardItems::Sounds(sounds)
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Copies the selected quads or sounds into the OS clipboard
+/// & pastes them from there into the active layer.
+pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
+    let map = &pipe.user_data.editor_tab.map;
+    let Some(EditorLayerUnionRef::Design {
+        layer,
+        group,
+        group_index,
+        layer_index,
+        is_background,
+    }) = map.active_layer()
+    else {
+        return;
+    };
+    let (is_sound_layer, layer_resource) = match layer {
+        EditorLayer::Quad(layer) => (false, layer.layer.attr.image),
+        EditorLayer::Sound(layer) => (true, layer.layer.attr.sound),
+        _ => return,
+    };
+    let clipboard_layer = EditorClipboardLayer::new(map, is_sound_layer, layer_resource);
+    let items = selected_items(pipe.user_data.tools, layer);
+
+    // text inputs handle the clipboard themselves
+    let (copy_ev, paste_ev) = if ui.ctx().wants_keyboard_input() {
+        (false, None)
+    } else {
+        ui.input(|i| {
+            (
+                i.events.iter().any(|ev| matches!(ev, egui::Event::Copy)),
+                i.events.iter().find_map(|ev| {
+                    if let egui::Event::Paste(text) = ev {
+                        Some((text.clone(), i.modifiers.shift))
+                    } else {
+                        None
+                    }
+                }),
+            )
+        })
+    };
+
+    let copy_btn = ui
+        .add_enabled(items.is_some(), Button::new("\u{f0c5}"))
+        .on_hover_ui(|ui| {
+            let mut cache = egui_commonmark::CommonMarkCache::default();
+            egui_commonmark::CommonMarkViewer::new().show(ui, &mut cache, TEXT_CLIPBOARD);
+        });
+    if let Some(items) = items
+        && (copy_btn.clicked() || copy_ev)
+    {
+        ui.ctx()
+            .copy_text(EditorClipboard::new(items, &clipboard_layer).encode());
+    }
+
+    let Some((text, at_original_pos)) = paste_ev else {
+        return;
+    };
+    // not an editor clipboard, e.g. just text
+    let Some(clipboard) = EditorClipboard::decode(&text) else {
+        return;
+    };
+    let mode = if at_original_pos {
+        EditorClipboardPasteMode::AtOriginalPos
+    } else {
+        let pos = ui
+            .input(|i| i.pointer.latest_pos())
+            .unwrap_or_else(|| ui.ctx().screen_rect().center());
+        EditorClipboardPasteMode::AtCursor(ui_pos_to_world_pos(
+            pipe.user_data.canvas_handle,
+            &ui.ctx().screen_rect(),
+            map.groups.user.zoom,
+            vec2::new(pos.x, pos.y),
+            map.groups.user.pos.x,
+            map.groups.user.pos.y,
+            group.attr.offset.x.to_num(),
+            group.attr.offset.y.to_num(),
+            group.attr.parallax.x.to_num(),
+            group.attr.parallax.y.to_num(),
+            map.groups.user.parallax_aware_zoom,
+        ))
+    };
+    let paste = clipboard.paste(&clipboard_layer, mode);
+
+    let client = &pipe.user_data.editor_tab.client;
+    for warning in paste.warnings {
+        client
+            .notifications
+            .push(EditorNotification::Warning(warning));
+    }
+    match (paste.items, layer) {
+        (EditorClipboardItems::Quads(quads), EditorLayer::Quad(layer)) => {
+            client.execute(
+                EditorAction::QuadLayerAddQuads(ActQuadLayerAddQuads {
+                    base: ActQuadLayerAddRemQuads {
+                        is_background,
+                        group_index,
+                        layer_index,
+                        index: layer.layer.quads.len(),
+                        quads,
+                    },
+                }),
+                Some(&format!("quad-paste design {layer_index}")),
+            );
+        }
+        (EditorClipboardItems::Sounds(sounds), EditorLayer::Sound(layer)) => {
+            client.execute(
+                EditorAction::SoundLayerAddSounds(ActSoundLayerAddSounds {
+                    base: ActSoundLayerAddRemSounds {
+                        is_background,
+                        group_index,
+                        layer_index,
+                        index: layer.layer.sounds.len(),
+                        sounds,
+                    },
+                }),
+                Some(&format!("sound-paste design {layer_index}")),
+            );
+        }
+        (EditorClipboardItems::Quads(_), _) => {
+            client.notifications.push(EditorNotification::Warning(
+                "The clipboard contains quads, select a quad layer to paste them.".to_string(),
+            ));
+        }
+        (EditorClipboardItems::Sounds(_), _) => {
+            client.notifications.push(EditorNotification::Warning(
+                "The clipboard contains sounds, select a sound layer to paste them.".to_string(),
+            ));
+        }
+    }
+}
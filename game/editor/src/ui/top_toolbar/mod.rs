@@ -1,3 +1,4 @@
+pub mod clipboard;
 pub mod speedup;
 pub mod switch;
 pub mod tele;
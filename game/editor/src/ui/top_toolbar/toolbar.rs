@@ -8,15 +8,18 @@ use crate::{
         ActQuadLayerAddQuads, ActQuadLayerAddRemQuads, ActSoundLayerAddRemSounds,
         ActSoundLayerAddSounds, EditorAction,
     },
+    event::EditorEventLayerIndex,
     explain::{
         TEXT_ADD_QUAD, TEXT_ADD_SOUND, TEXT_QUAD_BRUSH, TEXT_QUAD_SELECTION, TEXT_SOUND_BRUSH,
         TEXT_TILE_ALLOW_UNUSED, TEXT_TILE_BRUSH, TEXT_TILE_BRUSH_MIRROR, TEXT_TILE_DESTRUCTIVE,
-        TEXT_TILE_SELECT,
+        TEXT_TILE_LOCK, TEXT_TILE_MEASURE, TEXT_TILE_MEASURE_CLEAR, TEXT_TILE_SELECT,
+        TEXT_TILE_UNLOCK,
     },
     hotkeys::{
         EditorHotkeyEvent, EditorHotkeyEventSharedTool, EditorHotkeyEventTileBrush,
         EditorHotkeyEventTileTool, EditorHotkeyEventTools,
     },
+    locks::{EditorLockLayer, EditorLockRect},
     map::{EditorLayer, EditorLayerUnionRef, EditorMapInterface},
     tools::tool::{ActiveTool, ActiveToolQuads, ActiveToolSounds, ActiveToolTiles},
     ui::user_data::UserDataWithTab,
@@ -33,6 +36,25 @@ fn render_toolbar_tiles(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithT
     let ActiveTool::Tiles(tool) = tools.active_tool else {
         return;
     };
+    if let ActiveToolTiles::Measure = tool {
+        // clear measurements
+        let btn = Button::new("\u{f2ed}");
+        if ui
+            .add_enabled(!tools.tiles.measure.pinned.is_empty(), btn)
+            .on_hover_ui(|ui| {
+                let mut cache = egui_commonmark::CommonMarkCache::default();
+                egui_commonmark::CommonMarkViewer::new().show(
+                    ui,
+                    &mut cache,
+                    TEXT_TILE_MEASURE_CLEAR,
+                );
+            })
+            .clicked()
+        {
+            tools.tiles.measure.clear();
+        }
+        return;
+    }
     let is_active = (matches!(tool, ActiveToolTiles::Brush) && tools.tiles.brush.brush.is_some())
         || (matches!(tool, ActiveToolTiles::Selection) && tools.tiles.selection.range.is_some());
 
@@ -97,6 +119,7 @@ fn render_toolbar_tiles(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithT
                         );
                     }
                 }
+                ActiveToolTiles::Measure => {}
             }
         }
         // mirror x
@@ -156,6 +179,7 @@ fn render_toolbar_tiles(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithT
                         );
                     }
                 }
+                ActiveToolTiles::Measure => {}
             }
         }
         match tool {
@@ -333,6 +357,7 @@ fn render_toolbar_tiles(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithT
                     }
                 }
             }
+            ActiveToolTiles::Measure => {}
         }
     });
 
@@ -405,6 +430,73 @@ fn render_toolbar_tiles(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithT
     {
         tools.tiles.brush.allow_unused = !tools.tiles.brush.allow_unused
     }
+
+    // region locks
+    let active_layer = pipe
+        .user_data
+        .editor_tab
+        .map
+        .active_layer()
+        .map(|layer| match layer {
+            EditorLayerUnionRef::Physics { layer_index, .. } => {
+                EditorLockLayer::Physics { layer_index }
+            }
+            EditorLayerUnionRef::Design {
+                group_index,
+                layer_index,
+                is_background,
+                ..
+            } => EditorLockLayer::Design(EditorEventLayerIndex {
+                is_background,
+                group_index,
+                layer_index,
+            }),
+        });
+    let client = &pipe.user_data.editor_tab.client;
+    if let Some(active_layer) = active_layer {
+        let btn = Button::new("\u{f023}");
+        if ui
+            .add(btn)
+            .on_hover_ui(|ui| {
+                let mut cache = egui_commonmark::CommonMarkCache::default();
+                egui_commonmark::CommonMarkViewer::new().show(ui, &mut cache, TEXT_TILE_LOCK);
+            })
+            .clicked()
+        {
+            let rect = tools
+                .tiles
+                .selection
+                .range
+                .as_ref()
+                .filter(|_| matches!(tool, ActiveToolTiles::Selection))
+                .map(|range| EditorLockRect {
+                    x: range.x,
+                    y: range.y,
+                    w: range.w.get(),
+                    h: range.h.get(),
+                });
+            client.lock(active_layer, rect);
+        }
+
+        let own_locks: Vec<_> = client
+            .own_locks()
+            .filter(|lock| lock.layer == active_layer)
+            .map(|lock| lock.id)
+            .collect();
+        let btn = Button::new("\u{f09c}");
+        if ui
+            .add_enabled(!own_locks.is_empty(), btn)
+            .on_hover_ui(|ui| {
+                let mut cache = egui_commonmark::CommonMarkCache::default();
+                egui_commonmark::CommonMarkViewer::new().show(ui, &mut cache, TEXT_TILE_UNLOCK);
+            })
+            .clicked()
+        {
+            for id in own_locks {
+                client.unlock(id);
+            }
+        }
+    }
 }
 
 pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_state: &mut UiState) {
@@ -458,6 +550,25 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                             {
                                 *tool = ActiveToolTiles::Selection;
                             }
+                            // measure
+                            let mut btn = Button::new("\u{f545}");
+                            if matches!(tool, ActiveToolTiles::Measure) {
+                                btn = btn.selected(true);
+                            }
+                            if ui
+                                .add(btn)
+                                .on_hover_ui(|ui| {
+                                    let mut cache = egui_commonmark::CommonMarkCache::default();
+                                    egui_commonmark::CommonMarkViewer::new().show(
+                                        ui,
+                                        &mut cache,
+                                        TEXT_TILE_MEASURE,
+                                    );
+                                })
+                                .clicked()
+                            {
+                                *tool = ActiveToolTiles::Measure;
+                            }
                         }
                         ActiveTool::Quads(tool) => {
                             // brush
@@ -689,6 +800,8 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                                         );
                                     }
                                 }
+
+                                super::clipboard::render(ui, pipe);
                             });
                         });
                     })
@@ -789,6 +902,8 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                                         );
                                     }
                                 }
+
+                                super::clipboard::render(ui, pipe);
                             });
                         });
                     })
@@ -1,7 +1,7 @@
 use std::{collections::HashSet, path::PathBuf, sync::Arc};
 
 use base::linked_hash_map_view::FxLinkedHashMap;
-use base_io::io::Io;
+use base_io::{io::Io, runtime::IoRuntimeTask};
 use config::config::ConfigEngine;
 use ed25519_dalek::SigningKey;
 use egui::{Align2, InputState};
@@ -22,10 +22,13 @@ use sound::scene_object::SceneObject;
 
 use crate::{
     event::ActionDbg,
+    group_file::{EditorGroupFile, GROUP_FILE_EXTENSION},
     hotkeys::{BindsPerEvent, EditorBindsFile, EditorHotkeyEvent},
     image_store_container::ImageStoreContainer,
+    map::EditorMap,
     notifications::EditorNotifications,
     options::EditorOptions,
+    resource_optimizer::{ResourceOptimizeOptions, ResourceOptimizeReport},
     sound_store_container::SoundStoreContainer,
     tab::{EditorAdminPanelStateAuthed, EditorTab},
     tools::{tile_layer::auto_mapper::TileLayerAutoMapper, tool::Tools},
@@ -53,12 +56,53 @@ pub enum EditorUiEvent {
     SaveFile {
         name: PathBuf,
     },
+    /// Saves the map with the optimized images of the report.
+    SaveFileOptimized {
+        name: PathBuf,
+        report: Box<ResourceOptimizeReport>,
+    },
+    /// Opens the file dialog to export a design group.
+    ExportGroupDialog {
+        is_background: bool,
+        group_index: usize,
+        /// only export these layers of the group
+        layers: Option<Vec<usize>>,
+    },
+    ExportGroup {
+        name: PathBuf,
+        is_background: bool,
+        group_index: usize,
+        layers: Option<Vec<usize>>,
+    },
+    ImportGroup {
+        name: PathBuf,
+        is_background: bool,
+    },
+    SaveGroupTemplate {
+        name: String,
+        is_background: bool,
+        group_index: usize,
+    },
+    /// Inserts the group of a template as new group.
+    InsertGroupTemplate {
+        file: Box<EditorGroupFile>,
+        is_background: bool,
+    },
+    DeleteGroupTemplate {
+        name: String,
+    },
+    /// Compares the current map with the map file.
+    DiffMap {
+        name: PathBuf,
+    },
     SaveCurMap,
     SaveMapAndClose {
         tab: String,
     },
     SaveAll,
     SaveAllAndClose,
+    /// Downloads the map of the server & saves it as a new map
+    SaveLocalCopy,
     HostMap(Box<EditorUiEventHostMap>),
     Join {
         ip_port: String,
@@ -111,12 +155,39 @@ pub struct EditorMenuDialogJoinProps {
     pub color: [u8; 3],
 }
 
+/// The optimization step of saving a map,
+/// the report is shown before anything is saved.
+pub struct EditorMenuOptimizeResources {
+    pub path: PathBuf,
+    pub task: Option<IoRuntimeTask<ResourceOptimizeReport>>,
+    pub report: Option<ResourceOptimizeReport>,
+}
+
 pub enum EditorMenuDialogMode {
     None,
-    Open { file_dialog: Box<FileDialog> },
-    Save { file_dialog: Box<FileDialog> },
-    Host { mode: EditorMenuHostDialogMode },
+    Open {
+        file_dialog: Box<FileDialog>,
+    },
+    Save {
+        file_dialog: Box<FileDialog>,
+    },
+    ExportGroup {
+        file_dialog: Box<FileDialog>,
+        is_background: bool,
+        group_index: usize,
+        layers: Option<Vec<usize>>,
+    },
+    ImportGroup {
+        file_dialog: Box<FileDialog>,
+    },
+    DiffMap {
+        file_dialog: Box<FileDialog>,
+    },
+    Host {
+        mode: EditorMenuHostDialogMode,
+    },
     Join(EditorMenuDialogJoinProps),
+    OptimizeResources(Box<EditorMenuOptimizeResources>),
 }
 
 impl EditorMenuDialogMode {
@@ -214,6 +285,85 @@ impl EditorMenuDialogMode {
 
         Self::Save { file_dialog }
     }
+    /// Optimizes the images of the map on the thread pool,
+    /// before the map is saved to `path`.
+    pub fn optimize_resources(
+        io: &Io,
+        tp: &Arc<rayon::ThreadPool>,
+        map: &EditorMap,
+        path: PathBuf,
+        options: ResourceOptimizeOptions,
+    ) -> Self {
+        let sources = ResourceOptimizeReport::sources(map);
+        let tp = tp.clone();
+        let task = io
+            .rt
+            .spawn(async move { Ok(ResourceOptimizeReport::new(sources, options, &tp)) });
+        Self::OptimizeResources(Box::new(EditorMenuOptimizeResources {
+            path,
+            task: Some(task),
+            report: None,
+        }))
+    }
+    pub fn export_group(
+        io: &Io,
+        is_background: bool,
+        group_index: usize,
+        layers: Option<Vec<usize>>,
+    ) -> Self {
+        let mut open_path = io.fs.get_save_path();
+        open_path.push("map/groups");
+
+        let mut file_dialog = Box::new(Self::icons(
+            FileDialog::new()
+                .title("Export Group")
+                .anchor(Align2::CENTER_CENTER, (0.0, 0.0))
+                .movable(false)
+                .initial_directory(open_path)
+                .default_file_name(&format!("group.{GROUP_FILE_EXTENSION}")),
+        ));
+
+        file_dialog.save_file();
+
+        Self::ExportGroup {
+            file_dialog,
+            is_background,
+            group_index,
+            layers,
+        }
+    }
+    pub fn import_group(io: &Io) -> Self {
+        let mut open_path = io.fs.get_save_path();
+        open_path.push("map/groups");
+
+        let mut file_dialog = Box::new(Self::icons(
+            FileDialog::new()
+                .title("Import Group")
+                .anchor(Align2::CENTER_CENTER, (0.0, 0.0))
+                .movable(false)
+                .initial_directory(open_path),
+        ));
+
+        file_dialog.pick_file();
+
+        Self::ImportGroup { file_dialog }
+    }
+    pub fn diff_map(io: &Io) -> Self {
+        let mut open_path = io.fs.get_save_path();
+        open_path.push("map/maps");
+
+        let mut file_dialog = Box::new(Self::icons(
+            FileDialog::new()
+                .title("Compare with map file")
+                .anchor(Align2::CENTER_CENTER, (0.0, 0.0))
+                .movable(false)
+                .initial_directory(open_path),
+        ));
+
+        file_dialog.pick_file();
+
+        Self::DiffMap { file_dialog }
+    }
     pub fn host(io: &Io) -> Self {
         let mut open_path = io.fs.get_save_path();
         open_path.push("map/maps");
@@ -1,5 +1,8 @@
 use egui::{Color32, FontId, RichText, TextFormat, text::LayoutJob};
-use map::skeleton::groups::layers::design::MapLayerSkeleton;
+use map::{
+    map::groups::layers::physics::MapLayerPhysicsMeta,
+    skeleton::groups::layers::design::MapLayerSkeleton,
+};
 
 use crate::map::{EditorGroup, EditorLayer, EditorPhysicsLayer, EditorResources};
 
@@ -70,8 +73,12 @@ pub fn layer_name(
 
 pub fn layer_name_phy(layer: &EditorPhysicsLayer, index: usize) -> String {
     let layer_name = match layer {
-        EditorPhysicsLayer::Arbitrary(_) => {
-            todo!()
+        EditorPhysicsLayer::Arbitrary(layer) => {
+            if MapLayerPhysicsMeta::from_arbitrary(&layer.buf).is_ok() {
+                "Platforms"
+            } else {
+                "Arbitrary"
+            }
         }
         EditorPhysicsLayer::Game(_) => "Game",
         EditorPhysicsLayer::Front(_) => "Front",
@@ -82,3 +89,23 @@ pub fn layer_name_phy(layer: &EditorPhysicsLayer, index: usize) -> String {
     };
     format!("#{index} {layer_name}")
 }
+
+/// The text with the chars at the matched indices highlighted.
+pub fn highlight_matches(ui: &egui::Ui, text: &str, matched: &[usize]) -> LayoutJob {
+    let text_color = ui.style().visuals.text_color();
+    let highlight_color = ui.style().visuals.warn_fg_color;
+    let mut job = LayoutJob::default();
+    for (index, c) in text.chars().enumerate() {
+        let color = if matched.contains(&index) {
+            highlight_color
+        } else {
+            text_color
+        };
+        job.append(
+            c.encode_utf8(&mut [0; 4]),
+            0.0,
+            TextFormat::simple(FontId::default(), color),
+        );
+    }
+    job
+}
@@ -81,3 +81,44 @@ pub fn ui_pos_to_world_pos(
     )
     .0
 }
+
+/// The inverse of [`ui_pos_to_world_pos`].
+pub fn world_pos_to_ui_pos(
+    canvas_handle: &GraphicsCanvasHandle,
+    ui_canvas: &UiCanvasSize,
+    zoom: f32,
+    inp: vec2,
+    center_x: f32,
+    center_y: f32,
+    offset_x: f32,
+    offset_y: f32,
+    parallax_x: f32,
+    parallax_y: f32,
+    parallax_aware_zoom: bool,
+) -> vec2 {
+    let mut fake_state = State::new();
+    Camera::new(
+        vec2::new(center_x, center_y),
+        zoom,
+        None,
+        parallax_aware_zoom,
+    )
+    .project(
+        canvas_handle,
+        &mut fake_state,
+        Some(&MapGroupAttr {
+            offset: fvec2::new(ffixed::from_num(offset_x), ffixed::from_num(offset_y)),
+            parallax: fvec2::new(ffixed::from_num(parallax_x), ffixed::from_num(parallax_y)),
+            clipping: None,
+        }),
+    );
+    let (tl_x, tl_y, br_x, br_y) = fake_state.get_canvas_mapping();
+
+    let size = ui_canvas
+        .size()
+        .clamp(vec2(0.01, 0.01), vec2(f32::MAX, f32::MAX));
+    let x_ratio = (inp.x - tl_x) / (br_x - tl_x);
+    let y_ratio = (inp.y - tl_y) / (br_y - tl_y);
+
+    vec2::new(x_ratio * size.x, y_ratio * size.y)
+}
@@ -0,0 +1,43 @@
+use std::{fmt::Write, path::Path};
+
+/// Adds the blake3 hashes of all files in `dir` to the manifest,
+/// sorted by path, so the manifest is reproducible.
+fn add_files(root: &Path, dir: &Path, manifest: &mut String) -> std::io::Result<()> {
+    let mut entries = std::fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        // e.g. the `.git` file of the submodule
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            add_files(root, &path, manifest)?;
+        } else {
+            let hash = blake3::hash(&std::fs::read(&path)?);
+            let rel_path = path
+                .strip_prefix(root)
+                .unwrap()
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            writeln!(manifest, "{} {rel_path}", hash.to_hex()).unwrap();
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    let data_dir = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("../../data");
+    println!("cargo:rerun-if-changed={}", data_dir.display());
+
+    let mut manifest = String::new();
+    // the data dir is a submodule, without it the manifest is empty
+    if data_dir.is_dir() {
+        add_files(&data_dir, &data_dir, &mut manifest).expect("failed to hash the data dir");
+    }
+
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    std::fs::write(Path::new(&out_dir).join("data_manifest.txt"), manifest).unwrap();
+}
@@ -0,0 +1,324 @@
+use std::{
+    fmt::Display,
+    net::{Ipv6Addr, SocketAddr, ToSocketAddrs},
+    str::FromStr,
+};
+
+use anyhow::anyhow;
+use hiarc::Hiarc;
+use serde::{Deserialize, Serialize};
+
+/// The port that is used if the address has none.
+pub const DEFAULT_SERVER_PORT: u16 = 8303;
+/// How many servers the connect history keeps.
+pub const MAX_CONNECT_HISTORY: usize = 20;
+
+/// Url schemes the address bar accepts, as used by ddnet.
+const SERVER_URL_SCHEMES: [&str; 4] = ["ddnet", "ddrs", "tw-0.6+udp", "tw-0.7+udp"];
+
+/// A server address as typed into the address bar.
+///
+/// Accepts `host:port`, `host`, `[ipv6]:port`, `[ipv6]`, a bare ipv6
+/// & all of them as url, e.g. `ddnet://1.2.3.4:8303`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerAddr {
+    /// Host name or ip, ipv6 without brackets.
+    pub host: String,
+    pub port: u16,
+}
+
+impl ServerAddr {
+    /// Resolves the host name, blocks for host names that are no ip.
+    ///
+    /// Ipv4 addresses are preferred.
+    pub fn resolve(&self) -> anyhow::Result<SocketAddr> {
+        let addresses: Vec<_> = (self.host.as_str(), self.port).to_socket_addrs()?.collect();
+        addresses
+            .iter()
+            .find(|addr| addr.is_ipv4())
+            .or(addresses.first())
+            .copied()
+            .ok_or_else(|| anyhow!("{} could not be resolved", self.host))
+    }
+}
+
+impl FromStr for ServerAddr {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut addr = s.trim();
+        if let Some((scheme, rest)) = addr.split_once("://") {
+            if !SERVER_URL_SCHEMES.contains(&scheme.to_ascii_lowercase().as_str()) {
+                return Err(anyhow!("unknown url scheme: {scheme}"));
+            }
+            addr = rest.trim_end_matches('/');
+        }
+
+        let parse_port = |port: &str| {
+            port.parse::<u16>()
+                .map_err(|err| anyhow!("invalid port {port}: {err}"))
+        };
+        let (host, port) = if let Some(rest) = addr.strip_prefix('[') {
+            let (host, rest) = rest
+                .split_once(']')
+                .ok_or_else(|| anyhow!("missing closing bracket of the ipv6 address"))?;
+            host.parse::<Ipv6Addr>()
+                .map_err(|err| anyhow!("invalid ipv6 address {host}: {err}"))?;
+            let port = match rest {
+                "" => DEFAULT_SERVER_PORT,
+                rest => parse_port(
+                    rest.strip_prefix(':')
+                        .ok_or_else(|| anyhow!("expected a port after the ipv6 address"))?,
+                )?,
+            };
+            (host, port)
+        } else if addr.parse::<Ipv6Addr>().is_ok() {
+            (addr, DEFAULT_SERVER_PORT)
+        } else if let Some((host, port)) = addr.split_once(':') {
+            (host, parse_port(port)?)
+        } else {
+            (addr, DEFAULT_SERVER_PORT)
+        };
+
+        if host.is_empty() {
+            return Err(anyhow!("the address is empty"));
+        }
+        if host.contains(|c: char| c.is_whitespace() || c == '/' || c == ':')
+            && host.parse::<Ipv6Addr>().is_err()
+        {
+            return Err(anyhow!("invalid host name: {host}"));
+        }
+
+        Ok(Self {
+            host: host.to_string(),
+            port,
+        })
+    }
+}
+
+impl Display for ServerAddr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.host.parse::<Ipv6Addr>().is_ok() {
+            write!(f, "[{}]:{}", self.host, self.port)
+        } else {
+            write!(f, "{}:{}", self.host, self.port)
+        }
+    }
+}
+
+/// A server of the connect history or of an exported list.
+#[derive(Debug, Hiarc, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerListEntry {
+    pub addr: String,
+    /// The hex formatted hash of the server certificate, if known.
+    #[serde(default)]
+    pub fingerprint: Option<String>,
+    /// Unix timestamp in seconds of the last connect,
+    /// only set for history entries.
+    #[serde(default)]
+    pub last_connect: Option<u64>,
+}
+
+impl ServerListEntry {
+    /// Entries are the same server if either the address or
+    /// the fingerprint of the certificate is equal.
+    pub fn is_same_server(&self, other: &Self) -> bool {
+        self.addr == other.addr
+            || self
+                .fingerprint
+                .as_ref()
+                .is_some_and(|fingerprint| other.fingerprint.as_ref() == Some(fingerprint))
+    }
+}
+
+/// Newest entries first.
+pub type ConnectHistory = Vec<ServerListEntry>;
+
+/// Adds a successful connect to the history.
+pub fn add_connect(history: &mut ConnectHistory, entry: ServerListEntry) {
+    history.retain(|other| !other.is_same_server(&entry));
+    history.insert(0, entry);
+    history.truncate(MAX_CONNECT_HISTORY);
+}
+
+/// Adds all imported entries that are not in the list yet.
+///
+/// For duplicates the newer connect time is kept.
+pub fn merge_server_list(
+    list: &mut Vec<ServerListEntry>,
+    imported: impl IntoIterator<Item = ServerListEntry>,
+) {
+    for entry in imported {
+        if let Some(existing) = list.iter_mut().find(|other| other.is_same_server(&entry)) {
+            existing.last_connect = existing.last_connect.max(entry.last_connect);
+            if existing.fingerprint.is_none() {
+                existing.fingerprint = entry.fingerprint;
+            }
+        } else {
+            list.push(entry);
+        }
+    }
+}
+
+/// Merges the imported entries into the history,
+/// only the newest connects are kept.
+pub fn merge_connect_history(
+    history: &mut ConnectHistory,
+    imported: impl IntoIterator<Item = ServerListEntry>,
+) {
+    merge_server_list(history, imported);
+    history.sort_by(|a, b| b.last_connect.cmp(&a.last_connect));
+    history.truncate(MAX_CONNECT_HISTORY);
+}
+
+/// The shareable file of favorites & history.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ServerListExport {
+    #[serde(default)]
+    pub favorites: Vec<ServerListEntry>,
+    #[serde(default)]
+    pub history: ConnectHistory,
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        DEFAULT_SERVER_PORT, MAX_CONNECT_HISTORY, ServerAddr, ServerListEntry, add_connect,
+        merge_connect_history, merge_server_list,
+    };
+
+    fn entry(addr: &str, fingerprint: Option<&str>, last_connect: Option<u64>) -> ServerListEntry {
+        ServerListEntry {
+            addr: addr.to_string(),
+            fingerprint: fingerprint.map(|fingerprint| fingerprint.to_string()),
+            last_connect,
+        }
+    }
+
+    #[test]
+    fn parse_addresses() {
+        let parse = |s: &str| s.parse::<ServerAddr>().map(|addr| addr.to_string());
+
+        assert_eq!(parse("1.2.3.4:8304").unwrap(), "1.2.3.4:8304");
+        assert_eq!(
+            parse("1.2.3.4").unwrap(),
+            format!("1.2.3.4:{DEFAULT_SERVER_PORT}")
+        );
+        assert_eq!(parse(" example.com:8305 ").unwrap(), "example.com:8305");
+        assert_eq!(parse("example.com").unwrap(), "example.com:8303");
+        assert_eq!(parse("[::1]:8304").unwrap(), "[::1]:8304");
+        assert_eq!(parse("[2001:db8::1]").unwrap(), "[2001:db8::1]:8303");
+        assert_eq!(parse("2001:db8::1").unwrap(), "[2001:db8::1]:8303");
+        assert_eq!(parse("ddnet://1.2.3.4:8304/").unwrap(), "1.2.3.4:8304");
+        assert_eq!(parse("tw-0.6+udp://[::1]:8304").unwrap(), "[::1]:8304");
+        assert_eq!(parse("DDNet://example.com").unwrap(), "example.com:8303");
+
+        assert_eq!(
+            "[::1]:8304".parse::<ServerAddr>().unwrap(),
+            ServerAddr {
+                host: "::1".to_string(),
+                port: 8304
+            }
+        );
+
+        for invalid in [
+            "",
+            ":8303",
+            "1.2.3.4:port",
+            "1.2.3.4:70000",
+            "[::1",
+            "[::1]8303",
+            "[example.com]:8303",
+            "http://1.2.3.4:8303",
+            "exa mple.com",
+        ] {
+            assert!(parse(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn resolve_ip() {
+        let addr: ServerAddr = "[::1]:8304".parse().unwrap();
+        assert_eq!(addr.resolve().unwrap(), "[::1]:8304".parse().unwrap());
+    }
+
+    #[test]
+    fn history_without_duplicates() {
+        let mut history = Vec::new();
+        for i in 0..MAX_CONNECT_HISTORY + 5 {
+            add_connect(
+                &mut history,
+                entry(&format!("1.2.3.4:{}", 8000 + i), None, Some(i as u64)),
+            );
+        }
+        assert_eq!(history.len(), MAX_CONNECT_HISTORY);
+        assert_eq!(
+            history[0].last_connect,
+            Some(MAX_CONNECT_HISTORY as u64 + 4)
+        );
+
+        // a reconnect moves the server to the front
+        add_connect(&mut history, entry("1.2.3.4:8010", None, Some(100)));
+        assert_eq!(history.len(), MAX_CONNECT_HISTORY);
+        assert_eq!(history[0].addr, "1.2.3.4:8010");
+        assert_eq!(
+            history
+                .iter()
+                .filter(|entry| entry.addr == "1.2.3.4:8010")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn merge_without_duplicates() {
+        let mut list = vec![
+            entry("1.2.3.4:8303", Some("aa"), Some(10)),
+            entry("5.6.7.8:8303", None, Some(20)),
+        ];
+        merge_server_list(
+            &mut list,
+            [
+                // same address
+                entry("1.2.3.4:8303", None, Some(30)),
+                // same fingerprint, different address
+                entry("[::1]:8303", Some("aa"), Some(5)),
+                // fingerprint is learned
+                entry("5.6.7.8:8303", Some("bb"), None),
+                // new server
+                entry("9.9.9.9:8303", Some("cc"), Some(1)),
+                // duplicate inside the import
+                entry("9.9.9.9:8303", None, Some(2)),
+            ],
+        );
+        assert_eq!(
+            list,
+            vec![
+                entry("1.2.3.4:8303", Some("aa"), Some(30)),
+                entry("5.6.7.8:8303", Some("bb"), Some(20)),
+                entry("9.9.9.9:8303", Some("cc"), Some(2)),
+            ]
+        );
+
+        // merging again changes nothing
+        let before = list.clone();
+        merge_server_list(&mut list, before.clone());
+        assert_eq!(list, before);
+
+        let mut history = vec![entry("1.2.3.4:8303", None, Some(10))];
+        merge_connect_history(
+            &mut history,
+            [
+                entry("5.6.7.8:8303", None, Some(20)),
+                entry("1.2.3.4:8303", None, Some(5)),
+            ],
+        );
+        assert_eq!(
+            history,
+            vec![
+                entry("5.6.7.8:8303", None, Some(20)),
+                entry("1.2.3.4:8303", None, Some(10)),
+            ]
+        );
+    }
+}
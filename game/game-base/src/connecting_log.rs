@@ -2,14 +2,33 @@
 
 use std::{collections::VecDeque, net::SocketAddr};
 
+use base::hash::Hash;
 use hiarc::{Hiarc, hiarc_safer_arc_mutex};
 
 #[derive(Debug, Clone, Hiarc)]
 pub enum ConnectModes {
-    Connecting { addr: SocketAddr },
-    Queue { msg: String },
-    ConnectingErr { msg: String },
-    DisconnectErr { msg: String },
+    Connecting {
+        addr: SocketAddr,
+    },
+    Queue {
+        msg: String,
+    },
+    DownloadPrompt {
+        downloads: Vec<String>,
+    },
+    /// The server presents a different certificate than
+    /// on the last connect, the user must confirm it.
+    CertMismatch {
+        addr: SocketAddr,
+        pinned: String,
+        presented: Hash,
+    },
+    ConnectingErr {
+        msg: String,
+    },
+    DisconnectErr {
+        msg: String,
+    },
 }
 
 #[hiarc_safer_arc_mutex]
@@ -0,0 +1,222 @@
+//! The manifest of the files in the data directory, generated at build time.
+//!
+//! Used to find game files that are missing or were modified,
+//! e.g. by a broken update or a failing disk.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use anyhow::anyhow;
+use base::hash::{Hash, decode_hash, generate_hash_for};
+use base_io_traits::fs_traits::{FileSystemInterface, FileSystemPath, FileSystemType};
+
+/// One line per file: `<blake3 hash as hex> <path relative to the data dir>`.
+pub const DATA_MANIFEST: &str = include_str!(concat!(env!("OUT_DIR"), "/data_manifest.txt"));
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataManifestEntry {
+    pub path: PathBuf,
+    pub hash: Hash,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataFileMismatchKind {
+    Missing,
+    Corrupted,
+}
+
+/// A game file that does not match the manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataFileMismatch {
+    pub path: PathBuf,
+    /// The hash of the file in the manifest.
+    pub hash: Hash,
+    pub kind: DataFileMismatchKind,
+    /// The copy in the data directory is intact, only a file
+    /// that shadows it is broken, see [`restore_data_file`].
+    ///
+    /// Always the case for the bundled data directory.
+    pub restorable: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DataManifest {
+    pub entries: Vec<DataManifestEntry>,
+}
+
+impl DataManifest {
+    pub fn parse(manifest: &str) -> anyhow::Result<Self> {
+        let entries = manifest
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let (hash, path) = line
+                    .split_once(' ')
+                    .ok_or_else(|| anyhow!("invalid manifest line: {line}"))?;
+                let hash = decode_hash(hash).ok_or_else(|| anyhow!("invalid hash: {hash}"))?;
+                Ok(DataManifestEntry {
+                    path: path.into(),
+                    hash,
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { entries })
+    }
+
+    /// The manifest of the data directory this client was built with.
+    pub fn bundled() -> Self {
+        Self::parse(DATA_MANIFEST).expect("the build script wrote an invalid manifest")
+    }
+
+    /// Reads every file like the game would load it
+    /// & compares it against the manifest.
+    ///
+    /// `checked` counts the files that were already checked.
+    pub async fn verify(
+        &self,
+        fs: &dyn FileSystemInterface,
+        checked: &AtomicUsize,
+    ) -> Vec<DataFileMismatch> {
+        let mut mismatches = Vec::new();
+        for entry in &self.entries {
+            let kind = match fs.read_file(&entry.path).await {
+                Ok(file) if generate_hash_for(&file) == entry.hash => None,
+                Ok(_) => Some(DataFileMismatchKind::Corrupted),
+                Err(_) => Some(DataFileMismatchKind::Missing),
+            };
+            if let Some(kind) = kind {
+                let restorable = read_data_dir_file(fs, &entry.path, &entry.hash)
+                    .await
+                    .is_ok();
+                mismatches.push(DataFileMismatch {
+                    path: entry.path.clone(),
+                    hash: entry.hash,
+                    kind,
+                    restorable,
+                });
+            }
+            checked.fetch_add(1, Ordering::Relaxed);
+        }
+        mismatches
+    }
+}
+
+async fn read_data_dir_file(
+    fs: &dyn FileSystemInterface,
+    path: &Path,
+    hash: &Hash,
+) -> anyhow::Result<Vec<u8>> {
+    let file = fs
+        .read_file_in(path, FileSystemPath::OfType(FileSystemType::Read))
+        .await?;
+    anyhow::ensure!(
+        generate_hash_for(&file) == *hash,
+        "{path:?} is also corrupted in the data directory"
+    );
+    Ok(file)
+}
+
+/// Writes the intact copy of the data directory over the broken file,
+/// which makes the game load the intact file again.
+pub async fn restore_data_file(
+    fs: &dyn FileSystemInterface,
+    mismatch: &DataFileMismatch,
+) -> anyhow::Result<()> {
+    let file = read_data_dir_file(fs, &mismatch.path, &mismatch.hash).await?;
+    if let Some(dir) = mismatch.path.parent() {
+        fs.create_dir(dir).await?;
+    }
+    fs.write_file(&mismatch.path, file).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::{path::Path, sync::atomic::AtomicUsize};
+
+    use base::hash::{fmt_hash, generate_hash_for};
+    use base_fs::filesys::{FileSystem, ScopedDirFileSystem};
+    use base_io_traits::fs_traits::FileSystemInterface;
+
+    use super::{DataFileMismatchKind, DataManifest, restore_data_file};
+
+    #[test]
+    fn verify_and_restore() {
+        let rt = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        // the config dir is shared between test runs,
+        // so restored files must not end up at the same path twice
+        let dir = Path::new(data_dir.path().file_name().unwrap());
+        std::fs::create_dir_all(data_dir.path().join(dir)).unwrap();
+
+        let files = [("a.txt", "intact"), ("b.txt", "intact"), ("c.txt", "gone")];
+        let manifest: String = files
+            .iter()
+            .map(|(name, content)| {
+                format!(
+                    "{} {}\n",
+                    fmt_hash(&generate_hash_for(content.as_bytes())),
+                    dir.join(name).to_string_lossy()
+                )
+            })
+            .collect();
+        let manifest = DataManifest::parse(&manifest).unwrap();
+        assert_eq!(manifest.entries.len(), 3);
+        std::fs::write(data_dir.path().join(dir).join("a.txt"), "intact").unwrap();
+        std::fs::write(data_dir.path().join(dir).join("b.txt"), "intbct").unwrap();
+
+        let g = rt.enter();
+        let scoped_data_dir = ScopedDirFileSystem::new(data_dir.path()).unwrap();
+        drop(g);
+        let fs = FileSystem::new_with_data_dir(
+            &rt,
+            "ddnet-test",
+            "ddnet-test",
+            "ddnet-test",
+            "ddnet-test",
+            scoped_data_dir,
+        )
+        .unwrap();
+
+        let checked = AtomicUsize::default();
+        let mismatches = rt.block_on(manifest.verify(&fs, &checked));
+        assert_eq!(checked.into_inner(), 3);
+        assert_eq!(mismatches.len(), 2);
+        assert_eq!(mismatches[0].path, dir.join("b.txt"));
+        assert_eq!(mismatches[0].kind, DataFileMismatchKind::Corrupted);
+        assert_eq!(mismatches[1].path, dir.join("c.txt"));
+        assert_eq!(mismatches[1].kind, DataFileMismatchKind::Missing);
+        // the data dir itself is broken, only a download helps
+        assert!(mismatches.iter().all(|m| !m.restorable));
+        assert!(rt.block_on(restore_data_file(&fs, &mismatches[0])).is_err());
+
+        // a broken file in the config dir shadows the intact one
+        let path = dir.join("a.txt");
+        rt.block_on(fs.create_dir(dir)).unwrap();
+        rt.block_on(fs.write_file(&path, b"broken".to_vec()))
+            .unwrap();
+        let mismatches = rt.block_on(manifest.verify(&fs, &AtomicUsize::default()));
+        assert_eq!(mismatches.len(), 3);
+        assert_eq!(mismatches[0].path, path);
+        assert!(mismatches[0].restorable);
+
+        rt.block_on(restore_data_file(&fs, &mismatches[0])).unwrap();
+        let mismatches = rt.block_on(manifest.verify(&fs, &AtomicUsize::default()));
+        assert_eq!(mismatches.len(), 2);
+        assert!(mismatches.iter().all(|m| m.path != path));
+    }
+
+    #[test]
+    fn invalid_manifest() {
+        assert!(DataManifest::parse("").unwrap().entries.is_empty());
+        assert!(DataManifest::parse("skins/default.png").is_err());
+        assert!(DataManifest::parse("abc skins/default.png").is_err());
+        // the manifest of the build is always valid
+        DataManifest::bundled();
+    }
+}
@@ -1,10 +1,13 @@
 pub mod assets_url;
 pub mod browser_favorite_player;
+pub mod browser_server_list;
 pub mod config_helper;
 pub mod connecting_log;
+pub mod data_manifest;
 pub mod game_types;
 pub mod indexmap_tests;
 pub mod local_server_info;
+pub mod local_stats;
 pub mod network;
 pub mod player_input;
 pub mod server_browser;
@@ -0,0 +1,411 @@
+//! Statistics of the local player, like kills, deaths & accuracy.
+//!
+//! They are only saved on this machine & never sent anywhere.
+
+use std::{collections::BTreeMap, path::Path, time::Duration};
+
+use anyhow::anyhow;
+use base_io_traits::fs_traits::FileSystemInterface;
+use game_interface::types::weapons::WeaponType;
+use serde::{Deserialize, Serialize};
+
+pub const LOCAL_STATS_PATH: &str = "local_stats.json";
+
+/// The version of the file format, increased on
+/// changes that old clients can't read.
+pub const LOCAL_STATS_VERSION: u64 = 1;
+
+/// How many days are kept separately,
+/// older days only count to the totals.
+pub const LOCAL_STATS_DAYS: u32 = 30;
+
+/// The days since the unix epoch, the stats are grouped by them.
+pub fn stats_day(since_epoch: Duration) -> u32 {
+    (since_epoch.as_secs() / (60 * 60 * 24)) as u32
+}
+
+/// An event of the local player, that counts to the stats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LocalStatsEvent {
+    Fire(WeaponType),
+    /// Another character was hit by the weapon.
+    Hit(WeaponType),
+    Kill,
+    Death,
+    Capture,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WeaponAccuracy {
+    pub shots: u64,
+    pub hits: u64,
+}
+
+impl WeaponAccuracy {
+    /// Hits per shot, `None` without shots.
+    ///
+    /// A single shot can hit multiple characters (shotgun, grenade),
+    /// so this is capped at `1.0`.
+    pub fn accuracy(&self) -> Option<f64> {
+        (self.shots > 0).then(|| (self.hits as f64 / self.shots as f64).min(1.0))
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LocalStatsCounters {
+    pub kills: u64,
+    pub deaths: u64,
+    pub captures: u64,
+    pub playtime: Duration,
+    pub weapons: BTreeMap<WeaponType, WeaponAccuracy>,
+}
+
+impl LocalStatsCounters {
+    pub fn add_event(&mut self, ev: LocalStatsEvent) {
+        match ev {
+            LocalStatsEvent::Fire(weapon) => self.weapons.entry(weapon).or_default().shots += 1,
+            LocalStatsEvent::Hit(weapon) => self.weapons.entry(weapon).or_default().hits += 1,
+            LocalStatsEvent::Kill => self.kills += 1,
+            LocalStatsEvent::Death => self.deaths += 1,
+            LocalStatsEvent::Capture => self.captures += 1,
+        }
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        self.kills += other.kills;
+        self.deaths += other.deaths;
+        self.captures += other.captures;
+        self.playtime += other.playtime;
+        for (weapon, other) in &other.weapons {
+            let accuracy = self.weapons.entry(*weapon).or_default();
+            accuracy.shots += other.shots;
+            accuracy.hits += other.hits;
+        }
+    }
+
+    /// Kills per death, the kills if there were no deaths.
+    pub fn kd(&self) -> f64 {
+        self.kills as f64 / self.deaths.max(1) as f64
+    }
+
+    /// The accuracy over all weapons.
+    pub fn accuracy(&self) -> Option<f64> {
+        self.weapons
+            .values()
+            .fold(WeaponAccuracy::default(), |acc, weapon| WeaponAccuracy {
+                shots: acc.shots + weapon.shots,
+                hits: acc.hits + weapon.hits,
+            })
+            .accuracy()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalStatsDay {
+    /// See [`stats_day`].
+    pub day: u32,
+    pub counters: LocalStatsCounters,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LocalServerStats {
+    /// The last known name of the server.
+    pub name: String,
+    /// The last [`LOCAL_STATS_DAYS`] days, oldest first.
+    pub days: Vec<LocalStatsDay>,
+    /// The sum of all older days.
+    pub older: LocalStatsCounters,
+}
+
+impl LocalServerStats {
+    pub fn total(&self) -> LocalStatsCounters {
+        let mut total = self.older.clone();
+        for day in &self.days {
+            total.merge(&day.counters);
+        }
+        total
+    }
+
+    /// Moves the days that are too old into [`Self::older`].
+    fn rollup(&mut self, today: u32) {
+        let keep = self
+            .days
+            .iter()
+            .position(|day| day.day + LOCAL_STATS_DAYS > today)
+            .unwrap_or(self.days.len());
+        for day in self.days.drain(..keep) {
+            self.older.merge(&day.counters);
+        }
+    }
+
+    fn day_mut(&mut self, today: u32) -> &mut LocalStatsCounters {
+        self.rollup(today);
+        if self.days.last().is_none_or(|day| day.day != today) {
+            self.days.push(LocalStatsDay {
+                day: today,
+                counters: Default::default(),
+            });
+        }
+        &mut self.days.last_mut().unwrap().counters
+    }
+}
+
+/// The stats of all servers, by the address of the server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalStats {
+    pub version: u64,
+    #[serde(default)]
+    pub servers: BTreeMap<String, LocalServerStats>,
+}
+
+impl Default for LocalStats {
+    fn default() -> Self {
+        Self {
+            version: LOCAL_STATS_VERSION,
+            servers: Default::default(),
+        }
+    }
+}
+
+impl LocalStats {
+    fn server_mut(&mut self, addr: &str, name: &str) -> &mut LocalServerStats {
+        let server = self.servers.entry(addr.to_string()).or_default();
+        if !name.is_empty() {
+            server.name = name.to_string();
+        }
+        server
+    }
+
+    pub fn add_event(&mut self, addr: &str, name: &str, today: u32, ev: LocalStatsEvent) {
+        self.server_mut(addr, name).day_mut(today).add_event(ev);
+    }
+
+    pub fn add_playtime(&mut self, addr: &str, name: &str, today: u32, playtime: Duration) {
+        self.server_mut(addr, name).day_mut(today).playtime += playtime;
+    }
+
+    /// Moves the days that are too old into the totals of the servers.
+    pub fn rollup(&mut self, today: u32) {
+        for server in self.servers.values_mut() {
+            server.rollup(today);
+        }
+    }
+
+    fn servers<'a>(
+        &'a self,
+        addr: Option<&'a str>,
+    ) -> impl Iterator<Item = &'a LocalServerStats> + 'a {
+        self.servers
+            .iter()
+            .filter(move |(server_addr, _)| addr.is_none_or(|addr| addr == server_addr.as_str()))
+            .map(|(_, server)| server)
+    }
+
+    /// All time stats of a single server or of all servers for `None`.
+    pub fn total(&self, addr: Option<&str>) -> LocalStatsCounters {
+        let mut total = LocalStatsCounters::default();
+        for server in self.servers(addr) {
+            total.merge(&server.total());
+        }
+        total
+    }
+
+    /// The stats of each of the last [`LOCAL_STATS_DAYS`] days,
+    /// oldest first & ending with `today`.
+    ///
+    /// Only a single server or all servers for `None`.
+    pub fn daily(&self, addr: Option<&str>, today: u32) -> Vec<LocalStatsCounters> {
+        let mut daily = vec![LocalStatsCounters::default(); LOCAL_STATS_DAYS as usize];
+        let first_day = (today + 1).saturating_sub(LOCAL_STATS_DAYS);
+        for day in self.servers(addr).flat_map(|server| server.days.iter()) {
+            if (first_day..=today).contains(&day.day) {
+                daily[(day.day - first_day) as usize].merge(&day.counters);
+            }
+        }
+        daily
+    }
+
+    /// Reads the stats, older file formats are migrated.
+    ///
+    /// Files of newer clients can't be read, they must not be overwritten.
+    pub fn from_json(file: &[u8]) -> anyhow::Result<Self> {
+        let value: serde_json::Value = serde_json::from_slice(file)?;
+        let version = value
+            .get("version")
+            .and_then(|version| version.as_u64())
+            .ok_or_else(|| anyhow!("the stats file has no version"))?;
+        anyhow::ensure!(
+            version <= LOCAL_STATS_VERSION,
+            "the stats file is from a newer client (version {version}, \
+            this client supports up to version {LOCAL_STATS_VERSION})"
+        );
+        let mut stats: Self = serde_json::from_value(value)?;
+        // new counters don't need a new version, they default to 0,
+        // only incompatible changes are migrated here
+        stats.version = LOCAL_STATS_VERSION;
+        Ok(stats)
+    }
+
+    /// A missing file starts without stats.
+    pub async fn load(fs: &dyn FileSystemInterface, path: &Path) -> anyhow::Result<Self> {
+        if !fs.file_exists(path).await {
+            return Ok(Self::default());
+        }
+        Self::from_json(&fs.read_file(path).await?)
+    }
+
+    pub async fn save(&self, fs: &dyn FileSystemInterface, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs.create_dir(dir).await?;
+        }
+        fs.write_file(path, serde_json::to_vec_pretty(self)?)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use game_interface::types::weapons::WeaponType;
+
+    use super::{
+        LOCAL_STATS_DAYS, LOCAL_STATS_VERSION, LocalStats, LocalStatsEvent, WeaponAccuracy,
+        stats_day,
+    };
+
+    #[test]
+    fn rollup() {
+        let mut stats = LocalStats::default();
+        let today = stats_day(Duration::from_secs(60 * 60 * 24 * 1000 + 5));
+        assert_eq!(today, 1000);
+        let first = today - LOCAL_STATS_DAYS;
+
+        stats.add_event("1.2.3.4:8303", "", first, LocalStatsEvent::Kill);
+        stats.add_event("1.2.3.4:8303", "ctf", first + 1, LocalStatsEvent::Kill);
+        stats.add_event("1.2.3.4:8303", "", first + 1, LocalStatsEvent::Death);
+        stats.add_playtime("1.2.3.4:8303", "", first + 1, Duration::from_secs(60));
+        stats.add_event("5.6.7.8:8303", "dm", today, LocalStatsEvent::Kill);
+        stats.add_event("5.6.7.8:8303", "", today, LocalStatsEvent::Capture);
+        for _ in 0..4 {
+            stats.add_event(
+                "5.6.7.8:8303",
+                "",
+                today,
+                LocalStatsEvent::Fire(WeaponType::Gun),
+            );
+        }
+        stats.add_event(
+            "5.6.7.8:8303",
+            "",
+            today,
+            LocalStatsEvent::Hit(WeaponType::Gun),
+        );
+        // shotgun pellets hit more than once per shot
+        stats.add_event(
+            "5.6.7.8:8303",
+            "",
+            today,
+            LocalStatsEvent::Fire(WeaponType::Shotgun),
+        );
+        for _ in 0..3 {
+            stats.add_event(
+                "5.6.7.8:8303",
+                "",
+                today,
+                LocalStatsEvent::Hit(WeaponType::Shotgun),
+            );
+        }
+
+        // the name is kept if the new one is unknown
+        assert_eq!(stats.servers["1.2.3.4:8303"].name, "ctf");
+        assert_eq!(stats.servers["1.2.3.4:8303"].days.len(), 2);
+        // the oldest day is out of range, so it only counts to the totals
+        stats.rollup(today);
+        assert_eq!(stats.servers["1.2.3.4:8303"].days.len(), 1);
+        assert_eq!(stats.servers["1.2.3.4:8303"].older.kills, 1);
+        assert_eq!(stats.servers["5.6.7.8:8303"].days.len(), 1);
+
+        let total = stats.total(None);
+        assert_eq!(total.kills, 3);
+        assert_eq!(total.deaths, 1);
+        assert_eq!(total.captures, 1);
+        assert_eq!(total.playtime, Duration::from_secs(60));
+        assert_eq!(total.kd(), 3.0);
+        assert_eq!(
+            total.weapons[&WeaponType::Gun],
+            WeaponAccuracy { shots: 4, hits: 1 }
+        );
+        assert_eq!(total.weapons[&WeaponType::Gun].accuracy(), Some(0.25));
+        assert_eq!(total.weapons[&WeaponType::Shotgun].accuracy(), Some(1.0));
+        assert_eq!(total.accuracy(), Some(0.8));
+        assert_eq!(stats.total(Some("1.2.3.4:8303")).kills, 2);
+        assert_eq!(stats.total(Some("unknown")).kills, 0);
+
+        let daily = stats.daily(None, today);
+        assert_eq!(daily.len(), LOCAL_STATS_DAYS as usize);
+        assert_eq!(daily[0].kills, 1);
+        assert_eq!(daily[0].playtime, Duration::from_secs(60));
+        assert_eq!(daily[LOCAL_STATS_DAYS as usize - 1].kills, 1);
+        assert_eq!(daily.iter().map(|day| day.kills).sum::<u64>(), 2);
+        assert_eq!(stats.daily(Some("5.6.7.8:8303"), today)[0].kills, 0);
+
+        // the totals don't change by rolling up
+        let total = stats.total(None);
+        stats.rollup(today + LOCAL_STATS_DAYS);
+        assert!(stats.servers.values().all(|server| server.days.is_empty()));
+        assert_eq!(stats.total(None), total);
+        assert!(
+            stats
+                .daily(None, today + LOCAL_STATS_DAYS)
+                .iter()
+                .all(|day| day.kills == 0)
+        );
+    }
+
+    #[test]
+    fn schema_migration() {
+        let mut stats = LocalStats::default();
+        stats.add_event("1.2.3.4:8303", "dm", 10, LocalStatsEvent::Kill);
+        stats.add_event(
+            "1.2.3.4:8303",
+            "dm",
+            10,
+            LocalStatsEvent::Fire(WeaponType::Laser),
+        );
+        let file = serde_json::to_vec(&stats).unwrap();
+        assert_eq!(LocalStats::from_json(&file).unwrap(), stats);
+
+        // counters that didn't exist yet default to 0,
+        // unknown ones are ignored
+        let stats = LocalStats::from_json(
+            br#"{
+                "version": 1,
+                "servers": {
+                    "1.2.3.4:8303": {
+                        "name": "dm",
+                        "days": [{ "day": 10, "counters": { "kills": 2, "future": 1 } }]
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(stats.version, LOCAL_STATS_VERSION);
+        let server = &stats.servers["1.2.3.4:8303"];
+        assert_eq!(server.days[0].counters.kills, 2);
+        assert_eq!(server.days[0].counters.deaths, 0);
+        assert_eq!(server.older.kills, 0);
+
+        // newer clients & broken files must not be overwritten
+        let newer = format!(
+            r#"{{ "version": {}, "servers": {{}} }}"#,
+            LOCAL_STATS_VERSION + 1
+        );
+        assert!(LocalStats::from_json(newer.as_bytes()).is_err());
+        assert!(LocalStats::from_json(br#"{ "servers": {} }"#).is_err());
+        assert!(LocalStats::from_json(b"not json").is_err());
+    }
+}
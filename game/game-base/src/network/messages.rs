@@ -93,6 +93,9 @@ pub struct MsgSvServerInfo {
     /// the map that is currently played on
     pub map: NetworkReducedAsciiString<MAX_MAP_NAME_LEN>,
     pub map_blake3_hash: Hash,
+    /// The size of the map file in bytes, so the client can
+    /// inform the user before downloading it.
+    pub map_size: u64,
     /// The game mod to play, see the config variable to
     /// read about reserved names
     pub game_mod: GameModification,
@@ -204,6 +207,10 @@ pub enum AddLocalPlayerResponseError {
     PlayerIdAlreadyUsedByClient,
     #[error("The client was not yet connected to the server and ready.")]
     ClientWasNotReady,
+    #[error("Reached max players per account.")]
+    MaxPlayersPerAccount,
+    #[error("Server full (reserved slots).")]
+    ReservedSlots,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -279,4 +286,8 @@ pub enum MsgClLoadVotes {
         /// The blake3 hash as if the votes were serialized (e.g. as json)
         cached_votes: Option<Hash>,
     },
+    /// Which players can be voted and the rules for player votes.
+    ///
+    /// Not cached, since it changes whenever players join or leave.
+    Players,
 }
@@ -197,7 +197,7 @@ pub struct FilterCache {
     community_name: ServerTypeFilterCache,
 }
 
-#[derive(Debug, Hiarc, Default)]
+#[derive(Debug, Hiarc, Default, Clone)]
 pub struct ServerBrowserList {
     pub servers: Vec<ServerBrowserServer>,
 
@@ -257,6 +257,12 @@ pub struct ServerBrowserData {
 
     cache: FilterCache,
     filtered_sorted: Option<Arc<Vec<ServerBrowserServer>>>,
+
+    /// Addresses of the servers of the trusted communities.
+    community_addresses: Arc<HashSet<SocketAddr>>,
+
+    /// The servers found in the local network.
+    lan_servers: Arc<Vec<ServerBrowserServer>>,
 }
 
 #[hiarc_safer_rc_refcell]
@@ -294,6 +300,10 @@ impl ServerBrowserData {
 
             cache: Default::default(),
             filtered_sorted: Default::default(),
+
+            community_addresses: Default::default(),
+
+            lan_servers: Default::default(),
         }
     }
 
@@ -303,10 +313,67 @@ impl ServerBrowserData {
 
     pub fn set_servers(&mut self, servers: Vec<ServerBrowserServer>, time: Duration) {
         if self.list.time.is_none_or(|list_time| list_time < time) {
+            let community_addresses = self.community_addresses.clone();
+            let lan_servers = self.lan_servers.clone();
             *self = Self::from_servers(servers, time);
+            self.community_addresses = community_addresses;
+            self.lan_servers = lan_servers;
         }
     }
 
+    pub fn set_lan_servers(&mut self, servers: Vec<ServerBrowserServer>) {
+        self.lan_servers = Arc::new(servers);
+    }
+
+    /// The servers found in the local network, they are not part of the list.
+    pub fn lan_servers(&self) -> Arc<Vec<ServerBrowserServer>> {
+        self.lan_servers.clone()
+    }
+
+    /// Sets the servers of the communities listed in the ddnet info.
+    pub fn set_community_addresses(&mut self, addresses: HashSet<SocketAddr>) {
+        self.community_addresses = Arc::new(addresses);
+    }
+
+    /// Whether the server belongs to one of the trusted communities.
+    pub fn is_community_server(&self, addr: SocketAddr) -> bool {
+        self.community_addresses.contains(&addr)
+    }
+
+    /// Replaces the info of a server that is already in the list,
+    /// e.g. after the info of this single server was requested again.
+    ///
+    /// Returns `false` if the server is not in the list.
+    pub fn update_server(&mut self, server: ServerBrowserServer) -> bool {
+        let list = Arc::make_mut(&mut self.list);
+        let Some(index) = server
+            .addresses
+            .iter()
+            .find_map(|addr| match addr {
+                SocketAddr::V4(addr) => list.ipv4.get(addr),
+                SocketAddr::V6(addr) => list.ipv6.get(addr),
+            })
+            .copied()
+        else {
+            return false;
+        };
+        let cur_server = &mut list.servers[index];
+        list.player_count =
+            list.player_count - cur_server.info.players.len() + server.info.players.len();
+        // the addresses stay the same, else the lookup would break
+        cur_server.info = server.info;
+        cur_server.location = server.location;
+
+        self.clear_filter_cache();
+        true
+    }
+
+    /// Forces the next [`Self::filtered_and_sorted`] to filter again,
+    /// e.g. because the favorite servers changed.
+    pub fn clear_filter_cache(&mut self) {
+        self.filtered_sorted = None;
+    }
+
     pub fn find(&self, addr: SocketAddr) -> Option<ServerBrowserServer> {
         self.list.find(addr)
     }
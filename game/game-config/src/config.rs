@@ -1,14 +1,18 @@
 use std::collections::HashMap;
 
 use config::config::ConfigPath;
+use config::migration::{Migration, Migrations};
 use config::{ConfigInterface, config_default};
 use config::{config::ConfigEngine, types::ConfRgb};
 use game_interface::interface::MAX_MAP_NAME_LEN;
 use game_interface::{
     client_commands::MAX_TEAM_NAME_LEN,
-    types::character_info::{
-        MAX_ASSET_NAME_LEN, MAX_CHARACTER_CLAN_LEN, MAX_CHARACTER_NAME_LEN, MAX_FLAG_NAME_LEN,
-        MAX_LANG_NAME_LEN, NetworkLaserInfo, NetworkSkinInfo,
+    types::{
+        character_info::{
+            MAX_ASSET_NAME_LEN, MAX_CHARACTER_CLAN_LEN, MAX_CHARACTER_NAME_LEN, MAX_FLAG_NAME_LEN,
+            MAX_LANG_NAME_LEN, NetworkLaserInfo, NetworkSkinInfo,
+        },
+        weapons::WeaponType,
     },
 };
 use serde::de::DeserializeOwned;
@@ -41,6 +45,11 @@ pub struct ConfigDummy {
     /// To where the mini screen is anchored.
     #[default = ConfigDummyScreenAnchor::TopRight]
     pub screen_anchor: ConfigDummyScreenAnchor,
+    /// Delay in milliseconds before copied moves of the main player
+    /// are applied to the dummy. 0 copies them instantly.
+    #[conf_valid(range(min = 0, max = 1000))]
+    #[default = 0]
+    pub copy_moves_delay: u64,
 }
 
 #[config_default]
@@ -177,6 +186,10 @@ pub struct ConfigDemoRecorder {
     pub crf: u8,
     /// Config related to rendering graphics & sound.
     pub render: ConfigRender,
+    /// The preset that the graphics settings currently match.
+    /// Changing any setting of the preset switches to
+    /// [`ConfigGraphicsPreset::Custom`].
+    pub graphics_preset: ConfigGraphicsPreset,
     /// Sound configs used during rendering sound & graphics.
     pub snd: ConfigSoundRender,
     #[conf_valid(range(min = 0.0, max = 1.0))]
@@ -185,6 +198,54 @@ pub struct ConfigDemoRecorder {
     pub global_sound_volume: f64,
 }
 
+/// A keyword that highlights chat messages of other players
+/// and plays a notification sound.
+#[config_default]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigChatNotifyRule {
+    /// The keyword, matched case-insensitive as whole word.
+    /// An empty keyword matches the names of the own players.
+    #[conf_valid(length(max = 64))]
+    #[default = ""]
+    pub keyword: String,
+    /// Whether the rule is active while playing.
+    #[default = true]
+    pub ingame: bool,
+    /// Whether the rule is active while the menu is open.
+    #[default = true]
+    pub menu: bool,
+    /// Don't play the sound while the client window is focused,
+    /// the message is still highlighted.
+    #[default = false]
+    pub mute_when_focused: bool,
+}
+
+#[config_default]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigChat {
+    /// Rules for chat messages that should notify the player.
+    #[conf_valid(length(max = 32))]
+    #[default = vec![ConfigChatNotifyRule::default()]]
+    pub notify_rules: Vec<ConfigChatNotifyRule>,
+}
+
+/// Ghosts of previous race runs, that are replayed while racing.
+#[config_default]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigGhost {
+    /// Whether to show ghosts at all.
+    #[default = true]
+    pub show: bool,
+    /// Whether to show the ghost of the map record
+    /// next to the ghost of the own best run.
+    #[default = true]
+    pub show_record: bool,
+    /// Whether to record the own runs and
+    /// save them as ghost when they are a new personal best.
+    #[default = true]
+    pub record: bool,
+}
+
 /// Config related to rendering graphics & sound.
 #[config_default]
 #[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
@@ -224,6 +285,261 @@ pub struct ConfigRender {
     /// character.
     #[default = false]
     pub spec_dyncam: bool,
+    /// The crosshair at the cursor of the own character.
+    pub crosshair: ConfigCrosshair,
+    /// The amount of particles that are rendered in percent.
+    #[conf_valid(range(min = 0, max = 100))]
+    #[default = 100]
+    pub particles: u8,
+    /// Whether to show a small view of the own flag stand
+    /// while carrying the enemy flag.
+    #[default = true]
+    pub flag_stand_view: bool,
+    /// The height of the flag stand view in percent of the screen height.
+    #[conf_valid(range(min = 10, max = 50))]
+    #[default = 25]
+    pub flag_stand_view_size: u8,
+    /// Client side overrides of the skins of other players.
+    pub skin_overrides: ConfigSkinOverrides,
+    /// The health, armor & ammo of the own character.
+    pub hud: ConfigHud,
+}
+
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    ConfigInterface,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub enum ConfigForcedSkins {
+    /// All players keep their own skin.
+    #[default]
+    None,
+    /// Only players of other teams are forced to the configured skin.
+    Enemies,
+    /// All players are forced to the configured skin,
+    /// the own team uses the team color.
+    All,
+}
+
+#[config_default]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigSkinOverrides {
+    /// Which players are forced to the configured skin & colors.
+    pub force: ConfigForcedSkins,
+    /// The skin of forced players. Empty keeps their skin,
+    /// only the colors are forced.
+    #[conf_valid(length(max = MAX_ASSET_NAME_LEN))]
+    #[default = ""]
+    pub skin: String,
+    /// The color of forced players of the own team.
+    #[default = ConfRgb { r: 70, g: 130, b: 255 }]
+    pub team_color: ConfRgb,
+    /// The color of forced players of other teams.
+    #[default = ConfRgb { r: 255, g: 70, b: 70 }]
+    pub enemy_color: ConfRgb,
+    /// Gives every player without custom colors a distinct color.
+    /// Forced colors are always preferred.
+    #[default = false]
+    pub randomize_colors: bool,
+}
+
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    ConfigInterface,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub enum ConfigCrosshairMode {
+    /// Only the cursors of the weapons, no crosshair.
+    #[default]
+    WeaponCursor,
+    /// The crosshair is rendered on top of the weapon cursors.
+    Combined,
+    /// The crosshair replaces the weapon cursors entirely.
+    Crosshair,
+}
+
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    ConfigInterface,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub enum ConfigCrosshairShape {
+    /// Four arms of `size` length, `gap` away from the center.
+    #[default]
+    Cross,
+    /// Like [`ConfigCrosshairShape::Cross`] with a dot in the center.
+    CrossDot,
+    /// Like [`ConfigCrosshairShape::Cross`] without the top arm.
+    T,
+    /// A single square, `thickness` wide.
+    Dot,
+    /// A ring with a radius of `size`.
+    Circle,
+}
+
+/// How the crosshair looks.
+///
+/// All sizes are in pixels (scaled by the ingame ui scale),
+/// so the crosshair stays crisp at any zoom.
+#[config_default]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ConfigInterface, PartialEq)]
+pub struct ConfigCrosshairStyle {
+    pub shape: ConfigCrosshairShape,
+    #[conf_valid(range(min = 1, max = 128))]
+    #[default = 8]
+    pub size: u32,
+    #[conf_valid(range(min = 1, max = 32))]
+    #[default = 2]
+    pub thickness: u32,
+    /// The space between the center & the arms.
+    #[conf_valid(range(min = 0, max = 64))]
+    #[default = 3]
+    pub gap: u32,
+    #[default = Default::default()]
+    pub color: ConfRgb,
+    #[conf_valid(range(min = 0.0, max = 1.0))]
+    #[default = 1.0]
+    pub alpha: f64,
+    /// The width of the outline, `0` disables it.
+    #[conf_valid(range(min = 0, max = 8))]
+    #[default = 1]
+    pub outline: u32,
+    #[default = ConfRgb::black()]
+    pub outline_color: ConfRgb,
+}
+
+#[config_default]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigCrosshair {
+    pub mode: ConfigCrosshairMode,
+    /// The style for all weapons, if `per_weapon` is disabled.
+    pub style: ConfigCrosshairStyle,
+    /// Use a different style for every weapon.
+    #[default = false]
+    pub per_weapon: bool,
+    pub hammer: ConfigCrosshairStyle,
+    pub gun: ConfigCrosshairStyle,
+    pub shotgun: ConfigCrosshairStyle,
+    pub grenade: ConfigCrosshairStyle,
+    pub laser: ConfigCrosshairStyle,
+    pub ninja: ConfigCrosshairStyle,
+}
+
+impl ConfigCrosshair {
+    pub fn style(&self, weapon: WeaponType, is_ninja: bool) -> &ConfigCrosshairStyle {
+        if !self.per_weapon {
+            &self.style
+        } else if is_ninja {
+            &self.ninja
+        } else {
+            self.weapon_style(weapon)
+        }
+    }
+
+    /// The style of a specific weapon, regardless of `per_weapon`.
+    pub fn weapon_style(&self, weapon: WeaponType) -> &ConfigCrosshairStyle {
+        match weapon {
+            WeaponType::Hammer => &self.hammer,
+            WeaponType::Gun => &self.gun,
+            WeaponType::Shotgun => &self.shotgun,
+            WeaponType::Grenade => &self.grenade,
+            WeaponType::Laser => &self.laser,
+        }
+    }
+}
+
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    ConfigInterface,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub enum ConfigHudStatusStyle {
+    /// One icon per point, like in teeworlds.
+    #[default]
+    Pips,
+    /// A continuous bar per value.
+    Bars,
+}
+
+#[config_default]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigHud {
+    /// How health, armor & ammo are shown.
+    pub style: ConfigHudStatusStyle,
+    /// Health below this value pulses as a warning.
+    /// A value of `0` disables the warning.
+    #[conf_valid(range(min = 0, max = 10))]
+    #[default = 4]
+    pub health_warning: u32,
+    /// Armor below this value pulses as a warning.
+    /// A value of `0` disables the warning.
+    #[conf_valid(range(min = 0, max = 10))]
+    #[default = 0]
+    pub armor_warning: u32,
+    /// Health & armor flash briefly when they change.
+    #[default = true]
+    pub flash_on_change: bool,
+    /// Shows below the ammo when the active weapon can fire again.
+    #[default = true]
+    pub fire_delay_indicator: bool,
+}
+
+/// A group of graphics settings, see [`crate::graphics_preset`].
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    ConfigInterface,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub enum ConfigGraphicsPreset {
+    /// For weak or integrated graphics cards.
+    Low,
+    /// The default settings.
+    #[default]
+    Medium,
+    /// For dedicated graphics cards with plenty of memory.
+    High,
+    /// The settings were changed manually.
+    Custom,
 }
 
 #[config_default]
@@ -250,10 +566,29 @@ pub struct ConfigClient {
     pub spatial_chat: ConfigSpatialChat,
     /// Configurations for the demo video encoder.
     pub recorder: ConfigDemoRecorder,
+    /// Ghosts of previous race runs.
+    pub ghost: ConfigGhost,
+    /// Replay the highlight of a round in slow motion before the scoreboard
+    /// is shown, if the server sends one.
+    #[default = true]
+    pub killcam: bool,
+    /// The playback speed of the highlight in percent.
+    #[conf_valid(range(min = 10, max = 100))]
+    #[default = 50]
+    pub killcam_speed: u64,
+    /// Chat related settings.
+    pub chat: ConfigChat,
+    /// Throttling of the client while it's in the background or idle.
+    pub idle: ConfigIdle,
+    /// How the frames are limited & timed.
+    pub frame_pacing: ConfigFramePacing,
     /// Apply input for prediction directly. Might cause miss prediction.
     pub instant_input: bool,
     /// Predict other entities that are not local as if the ping is 0.
     pub anti_ping: bool,
+    /// How far other entities are rendered behind the newest snapshot,
+    /// if they are not predicted.
+    pub interpolation_buffer: ConfigInterpolationBuffer,
     /// The rendering mod to use, whenever possible.
     /// Empty string, "default", "native", "vanilla" & "ddnet"
     /// are reserved names and won't cause any mod to load.
@@ -271,6 +606,118 @@ pub struct ConfigClient {
     #[default = true]
     /// Enables the auto update if available
     pub auto_updater: bool,
+    /// Settings that are applied while connected to a server.
+    /// The key is the certificate fingerprint of the server formatted as string.
+    /// These are preferred over [`ConfigClient::server_profiles_addrs`].
+    pub server_profiles_certs: HashMap<String, ConfigServerProfile>,
+    /// Settings that are applied while connected to a server.
+    /// The key is the address of the server.
+    pub server_profiles_addrs: HashMap<String, ConfigServerProfile>,
+}
+
+#[config_default]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigInterpolationBuffer {
+    /// The smallest buffer the automatic adjustment uses.
+    /// Time unit is milliseconds.
+    #[conf_valid(range(min = 0, max = 1000))]
+    #[default = 20]
+    pub min_ms: u64,
+    /// The biggest buffer the automatic adjustment uses,
+    /// even if the jitter of the connection is higher.
+    /// Time unit is milliseconds.
+    #[conf_valid(range(min = 0, max = 1000))]
+    #[default = 250]
+    pub max_ms: u64,
+    /// A fixed buffer instead of the automatic adjustment to the
+    /// jitter & loss of the snapshots, `0` means automatic.
+    /// Time unit is milliseconds.
+    #[conf_valid(range(min = 0, max = 1000))]
+    #[default = 0]
+    pub manual_ms: u64,
+}
+
+#[config_default]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigIdle {
+    /// Reduce the render rate while the window is unfocused
+    /// or no input arrived for a while.
+    /// The connection & the game simulation keep running.
+    #[default = true]
+    pub enabled: bool,
+    /// Minutes without any input after which the client is idle.
+    #[conf_valid(range(min = 1, max = 600))]
+    #[default = 5]
+    pub timeout_minutes: u64,
+    /// The render rate while the client is in the background or idle.
+    #[conf_valid(range(min = 1, max = 240))]
+    #[default = 10]
+    pub background_fps: u64,
+    /// Mute all sounds while the client is in the background or idle.
+    #[default = false]
+    pub mute: bool,
+    /// Never throttle while connected to a server (or recording a demo there),
+    /// e.g. for streaming.
+    #[default = false]
+    pub never_throttle_ingame: bool,
+}
+
+/// What limits the frame rate of the client.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    ConfigInterface,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub enum ConfigFrameLimiter {
+    /// The refresh rate of the monitor.
+    Vsync,
+    /// [`ConfigClient::refresh_rate`], `0` means uncapped.
+    #[default]
+    Cap,
+    /// As many frames as possible.
+    Uncapped,
+}
+
+#[config_default]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigFramePacing {
+    pub limiter: ConfigFrameLimiter,
+    /// Delays the input sampling to just before the simulation,
+    /// so that a frame is presented right when it's due
+    /// instead of starting its work then.
+    #[default = false]
+    pub reduce_latency: bool,
+    /// The last part of the wait for the next frame that is spent
+    /// busy waiting instead of sleeping. Makes the frame times more
+    /// exact, but costs cpu time. Time unit is microseconds.
+    #[conf_valid(range(min = 0, max = 5000))]
+    #[default = 1000]
+    pub spin_micros: u64,
+}
+
+/// A subset of the settings that overrides the normal settings
+/// while connected to a specific server.
+#[config_default]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigServerProfile {
+    /// The skin of the main player.
+    #[default = Default::default()]
+    pub skin: ConfigPlayerSkin,
+    /// The binds of the main player.
+    #[default = Vec::new()]
+    pub binds: Vec<String>,
+    /// See [`ConfigClient::instant_input`].
+    pub instant_input: bool,
+    /// See [`ConfigClient::anti_ping`].
+    pub anti_ping: bool,
 }
 
 #[config_default]
@@ -444,6 +891,20 @@ pub struct ConfigMap {
     pub physics_layer_opacity: u8,
     #[default = true]
     pub text_entities: bool,
+    /// Skip rendering parts of background tile layers,
+    /// which are fully hidden behind opaque tile layers.
+    #[default = true]
+    pub occlusion_culling: bool,
+    /// Load & download the high quality variants of map images,
+    /// if the map has any.
+    #[default = false]
+    pub hq_assets: bool,
+    /// The texture memory in MiB, up to which high quality variants are used.
+    /// Textures that are already loaded count towards this limit,
+    /// images that don't fit anymore use their normal variant.
+    #[conf_valid(range(min = 0, max = 65536))]
+    #[default = 1024]
+    pub hq_assets_texture_memory: u32,
 }
 
 #[config_default]
@@ -594,6 +1055,17 @@ pub struct ConfigServer {
     #[conf_valid(length(max = MAX_MAP_NAME_LEN))]
     #[default = "ctf1"]
     pub map: String,
+    /// The countdown in seconds before the map changes,
+    /// e.g. after a successful map vote.
+    /// `0` changes the map immediately.
+    #[default = 5]
+    #[conf_valid(range(min = 0, max = 600))]
+    pub map_change_countdown_secs: u32,
+    /// The countdown in seconds before the server shuts down
+    /// by the `shutdown` rcon command.
+    #[default = 10]
+    #[conf_valid(range(min = 0, max = 600))]
+    pub shutdown_countdown_secs: u32,
     #[default = "0.0.0.0"]
     pub bind_addr_v4: String,
     #[default = "::0"]
@@ -636,6 +1108,46 @@ pub struct ConfigServer {
     #[default = 2]
     #[conf_valid(range(min = 1, max = 1000000))]
     pub max_players_per_client: u32,
+    /// The maximum allowed players of a single account
+    /// over all its clients, `0` means unlimited.
+    ///
+    /// Never lower than [`Self::max_players_per_client`],
+    /// so a client with its dummies can always join.
+    #[default = 2]
+    #[conf_valid(range(min = 0, max = 1000000))]
+    pub max_players_per_account: u32,
+    /// The maximum join attempts per ip within a minute,
+    /// `0` disables the join throttling.
+    #[default = 10]
+    #[conf_valid(range(min = 0, max = 1000000))]
+    pub max_join_attempts_per_ip: u32,
+    /// The time in seconds an ip is banned for,
+    /// if it joined too often.
+    /// Doubles for every repeated violation.
+    #[default = 60]
+    #[conf_valid(range(min = 1, max = 1000000))]
+    pub join_throttle_ban_secs: u32,
+    /// The maximum time in seconds an ip is banned for,
+    /// if it repeatedly joined too often.
+    #[default = 3600]
+    #[conf_valid(range(min = 1, max = 1000000))]
+    pub join_throttle_max_ban_secs: u32,
+    /// Ingame slots that can only be joined by whitelisted accounts
+    /// or with the reserved slots password.
+    #[default = 0]
+    #[conf_valid(range(min = 0, max = 1000000))]
+    pub reserved_slots: u32,
+    /// Password that allows to join the reserved slots.
+    /// An empty password disables it.
+    #[default = ""]
+    pub reserved_slots_password: String,
+    /// Joins that end up as spectator don't need a reserved slot.
+    #[default = true]
+    pub reserved_slots_spectators_exempt: bool,
+    /// File the accounts that can join the reserved slots are saved to.
+    /// An empty path keeps the whitelist in memory only.
+    #[default = "reserved_slots_whitelist.json"]
+    pub reserved_slots_whitelist_path: String,
     /// Only clients with a valid account can connect.
     /// This is only active if accounts were enabled
     /// in the database configuration.
@@ -643,6 +1155,15 @@ pub struct ConfigServer {
     pub account_only: bool,
     #[default = false]
     pub register: bool,
+    /// Whether to announce the server in the local network,
+    /// so clients list it in their LAN tab.
+    #[default = true]
+    pub lan_announce: bool,
+    /// The broadcast addresses the server is announced to.
+    /// If the server is in multiple networks, add the
+    /// broadcast address of each (e.g. `192.168.0.255`).
+    #[default = vec!["255.255.255.255".to_string()]]
+    pub lan_announce_addrs: Vec<String>,
     /// The game mod module to load
     /// empty string, "default", "native", "vanilla" & "ddnet"
     /// are reserved names and will not cause
@@ -663,6 +1184,14 @@ pub struct ConfigServer {
     #[conf_valid(range(min = 1, max = 100))]
     #[default = 2]
     pub ticks_per_snapshot: u64,
+    /// The most ticks that may pass between two snapshots, if the
+    /// connection of a client is bad (loss, ping variance).
+    /// The snapshot rate of such a client is reduced down to this
+    /// and recovers once the connection improves.
+    /// A value not higher than `ticks_per_snapshot` disables the adaption.
+    #[conf_valid(range(min = 1, max = 100))]
+    #[default = 8]
+    pub max_ticks_per_snapshot: u64,
     /// Train a packet dictionary. (for compression)
     /// Don't activate this if you don't know what this means
     #[default = false]
@@ -678,6 +1207,9 @@ pub struct ConfigServer {
     /// Path to the map votes file.
     #[default = "map_votes.json"]
     pub map_votes_path: String,
+    /// Whether kick and spectator votes require a reason.
+    #[default = false]
+    pub vote_reason_required: bool,
     /// Path to the server provided asset files.
     /// The dictionary structure should match the one from
     /// the data directory.
@@ -694,6 +1226,19 @@ pub struct ConfigServer {
     /// before being able to join the server
     #[default = ""]
     pub password: String,
+    /// Password to login to the remote console as admin.
+    /// An empty password disables the login.
+    #[default = ""]
+    pub rcon_password: String,
+    /// Password to login to the remote console as moderator.
+    /// Moderators can only kick, mute & control votes.
+    /// An empty password disables the login.
+    #[default = ""]
+    pub rcon_moderator_password: String,
+    /// File to which all executed remote console commands are appended.
+    /// An empty path disables the audit log.
+    #[default = "rcon_audit.log"]
+    pub rcon_audit_log_path: String,
 }
 
 /// Sound configs used during rendering sound & graphics.
@@ -711,6 +1256,34 @@ pub struct ConfigSoundRender {
     #[conf_valid(range(min = 0.0, max = 1.0))]
     #[default = 1.0]
     pub map_sound_volume: f64,
+    /// The sound volume for notifications, e.g. chat highlights
+    #[conf_valid(range(min = 0.0, max = 1.0))]
+    #[default = 1.0]
+    pub notification_sound_volume: f64,
+    /// Fade in the music stems of the map (sound layers named
+    /// `music_intensity_1` to `music_intensity_3`) the more intense the game gets.
+    #[default = true]
+    pub music_intensity: bool,
+    /// The music intensity while a flag is carried.
+    #[conf_valid(range(min = 0, max = 3))]
+    #[default = 1]
+    pub music_intensity_flag_taken: u8,
+    /// The music intensity while the round is about to end.
+    #[conf_valid(range(min = 0, max = 3))]
+    #[default = 2]
+    pub music_intensity_low_time: u8,
+    /// The remaining round time in seconds, below which the round is about to end.
+    #[conf_valid(range(min = 1, max = 600))]
+    #[default = 30]
+    pub music_intensity_low_time_secs: u32,
+    /// The music intensity during sudden death.
+    #[conf_valid(range(min = 0, max = 3))]
+    #[default = 3]
+    pub music_intensity_sudden_death: u8,
+    /// How long the music stems take to fade in or out, in milliseconds.
+    #[conf_valid(range(min = 0, max = 10000))]
+    #[default = 2000]
+    pub music_intensity_crossfade_ms: u32,
 }
 
 #[config_default]
@@ -744,6 +1317,35 @@ pub struct ConfigDebug {
     /// Log some sync related stuff from the internal server & client
     /// only use in release mode
     pub client_server_sync_log: ConfigDebugClientServerSyncLog,
+    /// Records the timings of the own inputs to diagnose input delay
+    /// & shows them as histogram in the debug hud.
+    #[default = false]
+    pub input_timings: bool,
+    /// Logs a warning if a single loaded asset (a skin, weapon etc.)
+    /// uses more memory, in MiB. `0` disables the warning.
+    #[default = 16]
+    pub container_item_warn_mib: u64,
+}
+
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    ConfigInterface,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub enum ConfigThemeVariant {
+    /// By the local time, night from 20:00 until 06:00.
+    #[default]
+    Auto,
+    Day,
+    Night,
 }
 
 #[config_default]
@@ -760,6 +1362,9 @@ pub struct ConfigMenu {
     /// - seasons
     #[default = "autumn"]
     pub background_map: String,
+    /// The variant of the background map.
+    /// Themes without a night variant always show the day variant.
+    pub theme_variant: ConfigThemeVariant,
 }
 
 #[config_default]
@@ -789,6 +1394,12 @@ pub struct ConfigGame {
 }
 
 impl ConfigGame {
+    /// The first versioned format, unversioned files are identical.
+    pub const MIGRATIONS: Migrations = Migrations(&[Migration {
+        version: 1,
+        steps: &[],
+    }]);
+
     pub fn new() -> ConfigGame {
         Self::default()
     }
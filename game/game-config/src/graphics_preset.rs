@@ -0,0 +1,213 @@
+use config::config::ConfigEngine;
+use graphics_types::gpu::{CurGpu, GpuType};
+
+use crate::config::{ConfigGame, ConfigGraphicsPreset};
+
+const GIB: u64 = 1024 * 1024 * 1024;
+/// Dedicated graphics cards with at least this much memory
+/// use the high preset.
+const HIGH_PRESET_MIN_MEMORY: u64 = 4 * GIB;
+/// Dedicated graphics cards with less memory use the low preset.
+const DISCRETE_MEDIUM_PRESET_MIN_MEMORY: u64 = GIB;
+/// Integrated graphics cards share the memory with the system,
+/// they need a bit more to use the medium preset.
+const INTEGRATED_MEDIUM_PRESET_MIN_MEMORY: u64 = 2 * GIB;
+
+/// The settings that are changed by a graphics preset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GraphicsPresetSettings {
+    /// `1` means off.
+    pub msaa_samples: u32,
+    /// In percent.
+    pub particles: u8,
+    pub hq_assets: bool,
+    pub background_show_tile_layers: bool,
+    pub show_quads: bool,
+}
+
+impl GraphicsPresetSettings {
+    fn current(config_engine: &ConfigEngine, config_game: &ConfigGame) -> Self {
+        Self {
+            // `0` & `1` both disable msaa
+            msaa_samples: config_engine.gl.msaa_samples.max(1),
+            particles: config_game.cl.render.particles,
+            hq_assets: config_game.map.hq_assets,
+            background_show_tile_layers: config_game.map.background_show_tile_layers,
+            show_quads: config_game.map.show_quads,
+        }
+    }
+
+    fn apply(&self, config_engine: &mut ConfigEngine, config_game: &mut ConfigGame) {
+        config_engine.gl.msaa_samples = self.msaa_samples;
+        config_game.cl.render.particles = self.particles;
+        config_game.map.hq_assets = self.hq_assets;
+        config_game.map.background_show_tile_layers = self.background_show_tile_layers;
+        config_game.map.show_quads = self.show_quads;
+    }
+}
+
+impl ConfigGraphicsPreset {
+    /// The settings of the preset, `None` for [`ConfigGraphicsPreset::Custom`].
+    pub fn settings(self) -> Option<GraphicsPresetSettings> {
+        match self {
+            Self::Low => Some(GraphicsPresetSettings {
+                msaa_samples: 1,
+                particles: 25,
+                hq_assets: false,
+                background_show_tile_layers: false,
+                show_quads: false,
+            }),
+            Self::Medium => Some(GraphicsPresetSettings {
+                msaa_samples: 1,
+                particles: 100,
+                hq_assets: false,
+                background_show_tile_layers: true,
+                show_quads: true,
+            }),
+            Self::High => Some(GraphicsPresetSettings {
+                msaa_samples: 4,
+                particles: 100,
+                hq_assets: true,
+                background_show_tile_layers: true,
+                show_quads: true,
+            }),
+            Self::Custom => None,
+        }
+    }
+
+    /// The preset that fits the graphics card best,
+    /// used on the first start of the client.
+    pub fn detect(gpu: &CurGpu) -> Self {
+        match gpu.ty {
+            GpuType::Discrete if gpu.device_local_memory >= HIGH_PRESET_MIN_MEMORY => Self::High,
+            // if the memory is unknown, assume an average card
+            GpuType::Discrete
+                if gpu.device_local_memory == 0
+                    || gpu.device_local_memory >= DISCRETE_MEDIUM_PRESET_MIN_MEMORY =>
+            {
+                Self::Medium
+            }
+            GpuType::Integrated
+                if gpu.device_local_memory >= INTEGRATED_MEDIUM_PRESET_MIN_MEMORY =>
+            {
+                Self::Medium
+            }
+            GpuType::Discrete
+            | GpuType::Integrated
+            | GpuType::Virtual
+            | GpuType::Cpu
+            | GpuType::Invalid => Self::Low,
+        }
+    }
+}
+
+impl ConfigGame {
+    /// Selects the preset and applies its settings.
+    pub fn apply_graphics_preset(
+        &mut self,
+        config_engine: &mut ConfigEngine,
+        preset: ConfigGraphicsPreset,
+    ) {
+        if let Some(settings) = preset.settings() {
+            settings.apply(config_engine, self);
+        }
+        self.cl.graphics_preset = preset;
+    }
+
+    /// Switches to [`ConfigGraphicsPreset::Custom`],
+    /// if any setting differs from the selected preset.
+    pub fn update_graphics_preset(&mut self, config_engine: &ConfigEngine) {
+        if self.cl.graphics_preset.settings().is_some_and(|settings| {
+            settings != GraphicsPresetSettings::current(config_engine, self)
+        }) {
+            self.cl.graphics_preset = ConfigGraphicsPreset::Custom;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use config::config::ConfigEngine;
+    use graphics_types::gpu::{CurGpu, GpuType};
+
+    use crate::config::{ConfigGame, ConfigGraphicsPreset};
+
+    use super::GIB;
+
+    fn gpu(ty: GpuType, device_local_memory: u64) -> CurGpu {
+        CurGpu {
+            name: "test".to_string(),
+            msaa_sampling_count: 8,
+            ty,
+            device_local_memory,
+        }
+    }
+
+    #[test]
+    fn preset_values() {
+        let mut config_engine = ConfigEngine::default();
+        let mut config_game = ConfigGame::default();
+
+        // the default settings are the medium preset
+        assert_eq!(config_game.cl.graphics_preset, ConfigGraphicsPreset::Medium);
+        config_game.update_graphics_preset(&config_engine);
+        assert_eq!(config_game.cl.graphics_preset, ConfigGraphicsPreset::Medium);
+
+        config_game.apply_graphics_preset(&mut config_engine, ConfigGraphicsPreset::Low);
+        assert_eq!(config_engine.gl.msaa_samples, 1);
+        assert_eq!(config_game.cl.render.particles, 25);
+        assert!(!config_game.map.hq_assets);
+        assert!(!config_game.map.background_show_tile_layers);
+        assert!(!config_game.map.show_quads);
+
+        config_game.apply_graphics_preset(&mut config_engine, ConfigGraphicsPreset::High);
+        assert_eq!(config_engine.gl.msaa_samples, 4);
+        assert_eq!(config_game.cl.render.particles, 100);
+        assert!(config_game.map.hq_assets);
+        assert!(config_game.map.background_show_tile_layers);
+        assert!(config_game.map.show_quads);
+        config_game.update_graphics_preset(&config_engine);
+        assert_eq!(config_game.cl.graphics_preset, ConfigGraphicsPreset::High);
+
+        // changing a setting of the preset switches to custom
+        config_game.cl.render.particles = 50;
+        config_game.update_graphics_preset(&config_engine);
+        assert_eq!(config_game.cl.graphics_preset, ConfigGraphicsPreset::Custom);
+
+        // custom keeps all settings
+        config_game.apply_graphics_preset(&mut config_engine, ConfigGraphicsPreset::Custom);
+        assert_eq!(config_engine.gl.msaa_samples, 4);
+        assert_eq!(config_game.cl.render.particles, 50);
+    }
+
+    #[test]
+    fn detect_preset() {
+        let detect = |ty, memory| ConfigGraphicsPreset::detect(&gpu(ty, memory));
+
+        assert_eq!(
+            detect(GpuType::Discrete, 8 * GIB),
+            ConfigGraphicsPreset::High
+        );
+        assert_eq!(
+            detect(GpuType::Discrete, 2 * GIB),
+            ConfigGraphicsPreset::Medium
+        );
+        assert_eq!(
+            detect(GpuType::Discrete, GIB / 2),
+            ConfigGraphicsPreset::Low
+        );
+        assert_eq!(detect(GpuType::Discrete, 0), ConfigGraphicsPreset::Medium);
+
+        // integrated cards never use the high preset
+        assert_eq!(
+            detect(GpuType::Integrated, 16 * GIB),
+            ConfigGraphicsPreset::Medium
+        );
+        assert_eq!(detect(GpuType::Integrated, GIB), ConfigGraphicsPreset::Low);
+        assert_eq!(detect(GpuType::Integrated, 0), ConfigGraphicsPreset::Low);
+
+        assert_eq!(detect(GpuType::Cpu, 8 * GIB), ConfigGraphicsPreset::Low);
+        assert_eq!(detect(GpuType::Virtual, 8 * GIB), ConfigGraphicsPreset::Low);
+        assert_eq!(detect(GpuType::Invalid, 0), ConfigGraphicsPreset::Low);
+    }
+}
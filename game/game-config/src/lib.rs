@@ -1 +1,3 @@
 pub mod config;
+pub mod graphics_preset;
+pub mod server_profile;
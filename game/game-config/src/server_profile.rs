@@ -0,0 +1,151 @@
+use crate::config::{ConfigGame, ConfigServerProfile};
+
+impl ConfigGame {
+    /// The settings profile for a server.
+    ///
+    /// A profile for the certificate fingerprint is preferred
+    /// over a profile for the address.
+    pub fn server_profile(
+        &self,
+        addr: &str,
+        cert_fingerprint: Option<&str>,
+    ) -> Option<&ConfigServerProfile> {
+        cert_fingerprint
+            .and_then(|cert| self.cl.server_profiles_certs.get(cert))
+            .or_else(|| self.cl.server_profiles_addrs.get(addr))
+    }
+
+    /// The current settings as server profile.
+    pub fn current_server_profile(&self) -> ConfigServerProfile {
+        let player = self.players.get(self.profiles.main as usize);
+        ConfigServerProfile {
+            skin: player.map(|p| p.skin.clone()).unwrap_or_default(),
+            binds: player.map(|p| p.binds.clone()).unwrap_or_default(),
+            instant_input: self.cl.instant_input,
+            anti_ping: self.cl.anti_ping,
+        }
+    }
+
+    fn set_server_profile(&mut self, profile: ConfigServerProfile) {
+        if let Some(player) = self.players.get_mut(self.profiles.main as usize) {
+            player.skin = profile.skin;
+            player.binds = profile.binds;
+        }
+        self.cl.instant_input = profile.instant_input;
+        self.cl.anti_ping = profile.anti_ping;
+    }
+}
+
+/// A server profile that was applied to the config.
+///
+/// Holds the settings from before the profile was applied,
+/// so they can be restored once the client disconnects.
+#[derive(Debug)]
+pub struct AppliedServerProfile {
+    original: ConfigServerProfile,
+}
+
+impl AppliedServerProfile {
+    /// Applies the profile of the server, if the user saved one for it.
+    pub fn apply(
+        config: &mut ConfigGame,
+        addr: &str,
+        cert_fingerprint: Option<&str>,
+    ) -> Option<Self> {
+        let profile = config.server_profile(addr, cert_fingerprint)?.clone();
+        let original = config.current_server_profile();
+        config.set_server_profile(profile);
+        Some(Self { original })
+    }
+
+    /// Restores the settings from before the profile was applied.
+    pub fn revert(self, config: &mut ConfigGame) {
+        config.set_server_profile(self.original);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config::{ConfigGame, ConfigServerProfile};
+
+    use super::AppliedServerProfile;
+
+    const ADDR: &str = "127.0.0.1:8303";
+    const CERT: &str = "abcdef";
+
+    fn profile(skin: &str) -> ConfigServerProfile {
+        let mut profile = ConfigServerProfile::default();
+        profile.skin.name = skin.to_string();
+        profile.binds = vec![format!("bind f {skin}")];
+        profile.anti_ping = true;
+        profile
+    }
+
+    fn main_skin(config: &ConfigGame) -> &str {
+        &config.players[config.profiles.main as usize].skin.name
+    }
+
+    #[test]
+    fn apply_and_revert() {
+        let mut config = ConfigGame::default();
+        config
+            .cl
+            .server_profiles_addrs
+            .insert(ADDR.to_string(), profile("fng"));
+        let original_binds = config.players[0].binds.clone();
+
+        let applied = AppliedServerProfile::apply(&mut config, ADDR, None).unwrap();
+        assert_eq!(main_skin(&config), "fng");
+        assert_eq!(config.players[0].binds, ["bind f fng"]);
+        assert!(config.cl.anti_ping);
+        // the dummy is untouched
+        assert_eq!(config.players[1].skin.name, "default");
+
+        applied.revert(&mut config);
+        assert_eq!(main_skin(&config), "default");
+        assert_eq!(config.players[0].binds, original_binds);
+        assert!(!config.cl.anti_ping);
+        // the profile itself is kept
+        assert!(config.cl.server_profiles_addrs.contains_key(ADDR));
+    }
+
+    #[test]
+    fn no_profile_keeps_settings() {
+        let mut config = ConfigGame::default();
+        config
+            .cl
+            .server_profiles_addrs
+            .insert("127.0.0.1:8304".to_string(), profile("fng"));
+        assert!(AppliedServerProfile::apply(&mut config, ADDR, Some(CERT)).is_none());
+        assert_eq!(main_skin(&config), "default");
+    }
+
+    #[test]
+    fn fingerprint_over_address() {
+        let mut config = ConfigGame::default();
+        config
+            .cl
+            .server_profiles_addrs
+            .insert(ADDR.to_string(), profile("race"));
+        config
+            .cl
+            .server_profiles_certs
+            .insert(CERT.to_string(), profile("fng"));
+
+        let applied = AppliedServerProfile::apply(&mut config, ADDR, Some(CERT)).unwrap();
+        assert_eq!(main_skin(&config), "fng");
+        applied.revert(&mut config);
+
+        // a different certificate on the same address falls back to the address
+        let applied = AppliedServerProfile::apply(&mut config, ADDR, Some("012345")).unwrap();
+        assert_eq!(main_skin(&config), "race");
+        applied.revert(&mut config);
+
+        // the fingerprint matches, even if the address changed
+        let applied =
+            AppliedServerProfile::apply(&mut config, "10.0.0.1:8303", Some(CERT)).unwrap();
+        assert_eq!(main_skin(&config), "fng");
+        applied.revert(&mut config);
+        assert_eq!(main_skin(&config), "default");
+    }
+}
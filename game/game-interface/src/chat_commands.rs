@@ -5,11 +5,23 @@ use command_parser::parser::CommandArg;
 use hiarc::Hiarc;
 use serde::{Deserialize, Serialize};
 
+/// Help text of a single chat command,
+/// e.g. for autocompletion on the client.
+#[derive(Debug, Hiarc, Default, Clone, Serialize, Deserialize)]
+pub struct ChatCommandInfo {
+    /// the arguments in human readable form, e.g. `<player_id> [reason]`
+    pub usage: NetworkString<1024>,
+    /// short description of what the command does
+    pub description: NetworkString<1024>,
+}
+
 /// Commands supported by the server.
 #[derive(Debug, Hiarc, Default, Clone, Serialize, Deserialize)]
 pub struct ChatCommands {
     /// list of commands and their required args
     pub cmds: HashMap<NetworkString<1024>, Vec<CommandArg>>,
+    /// help text for the commands in [`ChatCommands::cmds`]
+    pub infos: HashMap<NetworkString<1024>, ChatCommandInfo>,
     /// list of prefixes that trigger a chat command (e.g. `/` for slash commands)
     pub prefixes: Vec<char>,
 }
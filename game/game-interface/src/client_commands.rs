@@ -1,3 +1,5 @@
+use std::fmt::Display;
+
 use base::network_string::NetworkString;
 use hiarc::Hiarc;
 use rustc_hash::FxHashSet;
@@ -51,3 +53,112 @@ pub enum ClientCommand {
     /// The client requests to switch to a freecam mode
     SetCameraMode(ClientCameraMode),
 }
+
+/// Why a side can't be joined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinSideBlocked {
+    /// The player already plays on this side.
+    AlreadyJoined,
+    /// The side would have more players than the other side.
+    Unbalanced,
+}
+
+impl Display for JoinSideBlocked {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JoinSideBlocked::AlreadyJoined => write!(f, "You are already in this team."),
+            JoinSideBlocked::Unbalanced => {
+                write!(f, "This team has too many players, teams are balanced.")
+            }
+        }
+    }
+}
+
+/// Whether a player can join `side`.
+///
+/// `own_side` is `None` for spectators, `red` & `blue` are
+/// the players per side, including the player itself.
+/// If the sides are balanced, a side can only be joined
+/// as long as it doesn't end up with more players than the other side.
+pub fn can_join_side(
+    side: MatchSide,
+    own_side: Option<MatchSide>,
+    red: usize,
+    blue: usize,
+    balanced_sides: bool,
+) -> Result<(), JoinSideBlocked> {
+    if own_side == Some(side) {
+        return Err(JoinSideBlocked::AlreadyJoined);
+    }
+    // the player leaves its current side
+    let (red, blue) = match own_side {
+        Some(MatchSide::Red) => (red.saturating_sub(1), blue),
+        Some(MatchSide::Blue) => (red, blue.saturating_sub(1)),
+        None => (red, blue),
+    };
+    let (joined, other) = match side {
+        MatchSide::Red => (red, blue),
+        MatchSide::Blue => (blue, red),
+    };
+    if balanced_sides && joined > other {
+        Err(JoinSideBlocked::Unbalanced)
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::render::game::game_match::MatchSide;
+
+    use super::{JoinSideBlocked, can_join_side};
+
+    #[test]
+    fn join_side_from_spectators() {
+        // equal sides can always be joined
+        assert_eq!(can_join_side(MatchSide::Red, None, 2, 2, true), Ok(()));
+        assert_eq!(can_join_side(MatchSide::Blue, None, 2, 2, true), Ok(()));
+
+        // only the smaller side is allowed
+        assert_eq!(
+            can_join_side(MatchSide::Red, None, 3, 2, true),
+            Err(JoinSideBlocked::Unbalanced)
+        );
+        assert_eq!(can_join_side(MatchSide::Blue, None, 3, 2, true), Ok(()));
+
+        // without balancing everything goes
+        assert_eq!(can_join_side(MatchSide::Red, None, 3, 0, false), Ok(()));
+    }
+
+    #[test]
+    fn switch_sides() {
+        assert_eq!(
+            can_join_side(MatchSide::Red, Some(MatchSide::Red), 1, 3, true),
+            Err(JoinSideBlocked::AlreadyJoined)
+        );
+        assert_eq!(
+            can_join_side(MatchSide::Blue, Some(MatchSide::Blue), 1, 3, false),
+            Err(JoinSideBlocked::AlreadyJoined)
+        );
+
+        // 3 vs 2 => 2 vs 3 only makes the other side larger
+        assert_eq!(
+            can_join_side(MatchSide::Blue, Some(MatchSide::Red), 3, 2, true),
+            Err(JoinSideBlocked::Unbalanced)
+        );
+        // 3 vs 1 => 2 vs 2
+        assert_eq!(
+            can_join_side(MatchSide::Blue, Some(MatchSide::Red), 3, 1, true),
+            Ok(())
+        );
+        // 2 vs 2 => 1 vs 3
+        assert_eq!(
+            can_join_side(MatchSide::Blue, Some(MatchSide::Red), 2, 2, true),
+            Err(JoinSideBlocked::Unbalanced)
+        );
+        assert_eq!(
+            can_join_side(MatchSide::Blue, Some(MatchSide::Red), 2, 2, false),
+            Ok(())
+        );
+    }
+}
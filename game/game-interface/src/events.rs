@@ -1,6 +1,6 @@
-use std::time::Duration;
+use std::{ops::Deref, time::Duration};
 
-use base::network_string::MtPoolNetworkString;
+use base::network_string::{MtPoolNetworkString, NetworkString};
 use bitflags::bitflags;
 use hiarc::Hiarc;
 use math::math::vector::vec2;
@@ -15,11 +15,12 @@ use crate::{
     types::{
         character_info::{MAX_ASSET_NAME_LEN, MAX_CHARACTER_NAME_LEN, NetworkSkinInfo},
         flag::FlagType,
+        game::GameTickType,
         id_gen::{IdGenerator, IdGeneratorIdType},
         id_types::{CharacterId, PlayerId, StageId},
         player_info::PlayerDropReason,
         resource_key::MtPoolNetworkResourceKey,
-        weapons::WeaponType,
+        weapons::{EnumCount, WeaponType},
     },
 };
 
@@ -38,7 +39,7 @@ impl From<IdGeneratorIdType> for EventId {
 pub type EventIdGenerator = IdGenerator;
 
 /// Sounds that a ninja spawns
-#[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Hiarc, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum GameBuffNinjaEventSound {
     /// a pickup spawned
     Spawn,
@@ -54,7 +55,7 @@ pub enum GameBuffNinjaEventSound {
 #[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
 pub enum GameBuffNinjaEventEffect {}
 
-#[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Hiarc, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum GameBuffSoundEvent {
     Ninja(GameBuffNinjaEventSound),
 }
@@ -64,7 +65,7 @@ pub enum GameBuffEffectEvent {
     Ninja(GameBuffNinjaEventEffect),
 }
 
-#[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Hiarc, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum GameDebuffFrozenEventSound {
     /// user (tried to) used attack
     Attack,
@@ -73,7 +74,7 @@ pub enum GameDebuffFrozenEventSound {
 #[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
 pub enum GameDebuffFrozenEventEffect {}
 
-#[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Hiarc, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum GameDebuffSoundEvent {
     Frozen(GameDebuffFrozenEventSound),
 }
@@ -83,7 +84,7 @@ pub enum GameDebuffEffectEvent {
     Frozen(GameDebuffFrozenEventEffect),
 }
 
-#[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Hiarc, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum GameCharacterEventSound {
     WeaponSwitch {
         new_weapon: WeaponType,
@@ -126,11 +127,15 @@ pub enum GameCharacterEventEffect {
     Spawn,
     Death,
     AirJump,
-    DamageIndicator { vel: vec2 },
+    DamageIndicator {
+        vel: vec2,
+    },
     HammerHit,
+    /// The character took damage from a damage tile (e.g. lava or acid).
+    TileDamage,
 }
 
-#[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Hiarc, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum GameCharacterSoundEvent {
     Sound(GameCharacterEventSound),
     Buff(GameBuffSoundEvent),
@@ -144,7 +149,7 @@ pub enum GameCharacterEffectEvent {
     Debuff(GameDebuffEffectEvent),
 }
 
-#[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Hiarc, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum GameGrenadeEventSound {
     /// pickup spawned
     Spawn,
@@ -156,9 +161,14 @@ pub enum GameGrenadeEventSound {
 #[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
 pub enum GameGrenadeEventEffect {
     Explosion,
+    /// The grenade bounced off a wall at the position of the event.
+    Bounce {
+        /// How often the grenade can still bounce.
+        remaining_bounces: u32,
+    },
 }
 
-#[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Hiarc, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum GameLaserEventSound {
     /// pickup spawned
     Spawn,
@@ -168,9 +178,16 @@ pub enum GameLaserEventSound {
 }
 
 #[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
-pub enum GameLaserEventEffect {}
+pub enum GameLaserEventEffect {
+    /// The laser hit a wall at the position of the event.
+    Bounce {
+        /// How often the laser can still bounce,
+        /// `0` for the last bounce and the final wall contact.
+        remaining_bounces: u32,
+    },
+}
 
-#[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Hiarc, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum GameShotgunEventSound {
     /// pickup spawned
     Spawn,
@@ -187,7 +204,7 @@ pub enum GameFlagEventCollectTy {
     Opponent,
 }
 
-#[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Hiarc, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum GameFlagEventSound {
     /// a flag was collected by a character
     Collect(FlagType),
@@ -202,7 +219,7 @@ pub enum GameFlagEventSound {
 #[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
 pub enum GameFlagEventEffect {}
 
-#[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Hiarc, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum GamePickupHeartEventSound {
     Spawn,
     /// a pickup was collected by a character
@@ -212,7 +229,7 @@ pub enum GamePickupHeartEventSound {
 #[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
 pub enum GamePickupHeartEventEffect {}
 
-#[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Hiarc, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum GamePickupArmorEventSound {
     Spawn,
     /// a pickup was collected by a character
@@ -222,7 +239,7 @@ pub enum GamePickupArmorEventSound {
 #[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
 pub enum GamePickupArmorEventEffect {}
 
-#[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Hiarc, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum GamePickupSoundEvent {
     Heart(GamePickupHeartEventSound),
     Armor(GamePickupArmorEventSound),
@@ -234,7 +251,19 @@ pub enum GamePickupEffectEvent {
     Armor(GamePickupArmorEventEffect),
 }
 
+/// The size of the payload of a [`GameCustomEffectEvent`].
+pub const CUSTOM_EFFECT_PAYLOAD_LEN: usize = 16;
+
+/// An effect of a game mod that the default client doesn't know.
+/// Render mods can register particle effects for its id.
 #[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
+pub struct GameCustomEffectEvent {
+    pub id: u32,
+    /// Arbitrary data for the effect, e.g. a direction or a color.
+    pub payload: [u8; CUSTOM_EFFECT_PAYLOAD_LEN],
+}
+
+#[derive(Debug, Hiarc, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum GameWorldEntitySoundEvent {
     Character(GameCharacterSoundEvent),
     Grenade(GameGrenadeEventSound),
@@ -252,6 +281,7 @@ pub enum GameWorldEntityEffectEvent {
     Shotgun(GameShotgunEventEffect),
     Flag(GameFlagEventEffect),
     Pickup(GamePickupEffectEvent),
+    Custom(GameCustomEffectEvent),
 }
 
 #[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
@@ -271,6 +301,16 @@ pub struct GameWorldSoundEvent {
     /// assets are used.
     pub owner_id: Option<CharacterId>,
     pub ev: GameWorldEntitySoundEvent,
+    /// How many identical sounds were merged into this event,
+    /// the client plays the sound once but slightly louder.
+    pub count: u32,
+}
+
+impl GameWorldSoundEvent {
+    /// How much louder the sound is played because of [`Self::count`].
+    pub fn batch_volume(&self) -> f64 {
+        (1.0 + (self.count.max(1) as f64).log2() * 0.1).min(1.5)
+    }
 }
 
 #[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
@@ -365,6 +405,17 @@ pub enum GameWorldAction {
         character: CharacterId,
         finish_time: Duration,
     },
+    /// An event indicating that a player
+    /// crossed a checkpoint of a race.
+    ///
+    /// Clients use it e.g. to show split times.
+    RaceCheckpoint {
+        character: CharacterId,
+        /// The index of the checkpoint in the map.
+        index: u8,
+        /// The race time when the checkpoint was crossed.
+        time: Duration,
+    },
     /// An event indicating that team of players
     /// finished a race.
     ///
@@ -385,6 +436,145 @@ pub enum GameWorldNotificationEvent {
     Action(GameWorldAction),
     /// Message of the day
     Motd { msg: MtPoolNetworkString<1024> },
+    /// A short message that is displayed at the top of the screen
+    Broadcast { msg: MtPoolNetworkString<256> },
+    /// A tutorial hint of the map, e.g. because the player entered its zone.
+    ///
+    /// `{bind:<action>}` in the message is replaced by the client
+    /// with the keys the player bound to the action.
+    Hint { msg: MtPoolNetworkString<1024> },
+    /// The round is over, contains the statistics
+    /// of all players that took part in the round.
+    GameOver {
+        stats: PoolVec<GameWorldPlayerStats>,
+        /// The moment that decided the round, which clients can
+        /// replay before the scoreboard is shown.
+        highlight: Option<GameWorldHighlight>,
+    },
+}
+
+/// A short range of ticks worth replaying, e.g. the winning capture.
+///
+/// The ticks are relative to the tick of the event that contains the highlight,
+/// so clients can find them in their own recordings.
+#[derive(Debug, Hiarc, Clone, Serialize, Deserialize)]
+pub struct GameWorldHighlight {
+    /// Ticks from the start of the highlight until the event.
+    pub start_ticks_ago: GameTickType,
+    /// Ticks from the end of the highlight until the event.
+    pub end_ticks_ago: GameTickType,
+    /// The characters that took part, the main actor first.
+    pub characters: PoolVec<CharacterId>,
+}
+
+/// How often a weapon was used and how often it hit.
+#[derive(Debug, Hiarc, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameWorldWeaponStats {
+    /// Shots fired, every bullet of a shotgun counts as a shot.
+    pub shots: u32,
+    /// How often this weapon damaged another character.
+    pub hits: u32,
+}
+
+impl GameWorldWeaponStats {
+    /// The ratio of hits to shots in the range `[0, 1]`.
+    pub fn accuracy(&self) -> f32 {
+        if self.shots == 0 {
+            0.0
+        } else {
+            (self.hits as f32 / self.shots as f32).min(1.0)
+        }
+    }
+}
+
+/// The statistics of a single player during a round.
+#[derive(Debug, Hiarc, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameWorldPlayerStats {
+    pub id: CharacterId,
+    /// The name is included, since the player might have left already.
+    pub name: NetworkString<MAX_CHARACTER_NAME_LEN>,
+    /// Indexed by [`WeaponType`].
+    pub weapons: [GameWorldWeaponStats; WeaponType::COUNT],
+    pub kills: u32,
+    pub deaths: u32,
+    pub damage_dealt: u32,
+    pub damage_taken: u32,
+    pub flag_carry_time: Duration,
+    pub flag_captures: u32,
+    pub flag_returns: u32,
+    /// How long the player hooked teammates,
+    /// if the game counts hook assistance.
+    pub hook_assist_time: Duration,
+    pub longest_killstreak: u32,
+    /// The player left before the round was over.
+    pub disconnected: bool,
+}
+
+/// A character dealt damage to another character.
+///
+/// This event is only interesting for the attacker (e.g. to render hit markers),
+/// so implementations should only send it to the attacker and
+/// players that spectate the attacker.
+#[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
+pub struct GameWorldHitEvent {
+    pub attacker: CharacterId,
+    pub target: CharacterId,
+    /// The sum of the health and armor the target lost.
+    pub damage: u32,
+    pub weapon: GameWorldActionKillWeapon,
+    /// The hit killed the target.
+    pub killing_blow: bool,
+}
+
+/// The maximum size of the payload of a [`GameWorldCustomEvent`].
+pub const MAX_CUSTOM_EVENT_PAYLOAD_LEN: usize = 512;
+
+/// The payload of a [`GameWorldCustomEvent`], that checks its
+/// max length at deserialization & creation time.
+#[derive(Debug, Default, Hiarc, Clone, PartialEq, Eq, Serialize)]
+pub struct GameCustomEventPayload(Vec<u8>);
+
+impl GameCustomEventPayload {
+    /// `None` if the payload is longer than [`MAX_CUSTOM_EVENT_PAYLOAD_LEN`].
+    pub fn new(payload: impl Into<Vec<u8>>) -> Option<Self> {
+        let payload = payload.into();
+        (payload.len() <= MAX_CUSTOM_EVENT_PAYLOAD_LEN).then_some(Self(payload))
+    }
+}
+
+impl Deref for GameCustomEventPayload {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'de> Deserialize<'de> for GameCustomEventPayload {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        <Vec<u8> as Deserialize>::deserialize(deserializer).and_then(|payload| {
+            let len = payload.len();
+            Self::new(payload).ok_or_else(|| {
+                serde::de::Error::invalid_length(len, &"a payload length lower than the maximum")
+            })
+        })
+    }
+}
+
+/// An event of a game mod that the default client doesn't know,
+/// e.g. that a boss spawned.
+///
+/// It is recorded in demos like every other event, but only
+/// the render mod of the game mod interprets it by its id.
+/// Everyone else ignores it.
+#[derive(Debug, Hiarc, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameWorldCustomEvent {
+    /// Chosen by the game mod.
+    pub id: u32,
+    pub payload: GameCustomEventPayload,
 }
 
 #[derive(Debug, Hiarc, Clone, Serialize, Deserialize)]
@@ -392,6 +582,8 @@ pub enum GameWorldEvent {
     Sound(GameWorldSoundEvent),
     Effect(GameWorldEffectEvent),
     Notification(GameWorldNotificationEvent),
+    Hit(GameWorldHitEvent),
+    Custom(GameWorldCustomEvent),
 }
 
 /// # ID (Event-ID)
@@ -90,6 +90,11 @@ pub struct GameStateServerOptions {
     /// Whether the client should show a "Pick a side"-button to
     /// switch between red & blue sides.
     pub use_vanilla_sides: bool,
+    /// Whether the sides are kept balanced, so a side can only
+    /// be joined if it doesn't have more players than the other.
+    ///
+    /// See also [`crate::client_commands::can_join_side`].
+    pub balanced_sides: bool,
     /// Whether the game server uses accounts where the ingame name
     /// and the account name are split and the client should show
     /// an extra UI tab for changing the account name and display
@@ -281,6 +286,23 @@ pub trait GameStateInterface: GameStateCreate {
     /// It should not be expected that this is called more than once per second.
     fn network_stats(&mut self, stats: PoolFxLinkedHashMap<PlayerId, PlayerNetworkStats>);
 
+    /// The player is about to be moved to a new game state,
+    /// e.g. because the server changes the map.
+    /// The returned opaque blob is passed to
+    /// [`GameStateInterface::apply_player_handover`] of the new game state,
+    /// after the player joined it again.
+    ///
+    /// Teams & spectators are already restored by the server,
+    /// so this is only needed for mod specific state.
+    /// The blob should be small, returning `None` is always fine.
+    fn player_handover(&self, player_id: &PlayerId) -> Option<Vec<u8>>;
+
+    /// Applies a blob previously returned by [`GameStateInterface::player_handover`]
+    /// of the previous game state.
+    /// The implementation must be able to deal with invalid blobs,
+    /// e.g. because the previous game state was a different mod.
+    fn apply_player_handover(&mut self, player_id: &PlayerId, handover: Vec<u8>);
+
     /// Common settings of the current game.
     ///
     /// Usually used only for server browser info.
@@ -33,7 +33,12 @@ pub struct RconEntries {
     pub vars: HashMap<NetworkString<65536>, RconEntry>,
 }
 
-#[derive(Debug, Hiarc, Default, Clone, Copy, Serialize, Deserialize)]
+/// The rights of a client in the remote console.
+///
+/// Ordered by the rights, a higher level has all rights of the lower levels.
+#[derive(
+    Debug, Hiarc, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
 pub enum AuthLevel {
     #[default]
     None,
@@ -17,6 +17,8 @@ pub enum TickEvent {
         until: Option<chrono::DateTime<chrono::Utc>>,
         reason: PlayerBanReason,
     },
+    /// The chat messages of the player should (not) be dropped.
+    Mute { player_id: PlayerId, muted: bool },
 }
 
 /// The tick result contains per tick data
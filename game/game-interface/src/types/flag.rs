@@ -1,7 +1,7 @@
 use hiarc::Hiarc;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Hiarc, Default, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Hiarc, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FlagType {
     #[default]
     Red,
@@ -231,6 +231,10 @@ pub struct CharacterInputState {
 ///   can be queried all the time (current cursor, hold hook button, hold fire button etc.)
 #[derive(Debug, Hiarc, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct CharacterInput {
+    /// The cursor relative to the character.
+    /// In a free camera mode (e.g. as spectator), this is the
+    /// position of the camera instead, which the server uses to decide
+    /// what is visible for the player.
     pub cursor: InputVarState<CharacterInputCursor>,
     pub viewport: InputVarState<CharacterInputViewport>,
     pub dyn_cam_offset: InputVarState<CharacterInputDynCamOffset>,
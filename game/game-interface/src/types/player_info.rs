@@ -1,3 +1,5 @@
+use std::net::IpAddr;
+
 pub use base::hash::Hash;
 use base::network_string::NetworkString;
 pub use ddnet_accounts_types::account_id::AccountId;
@@ -42,6 +44,8 @@ pub struct PlayerClientInfo {
     pub unique_identifier: PlayerUniqueId,
     /// Initial unreliable network statistic (might be guessed.).
     pub initial_network_stats: PlayerNetworkStats,
+    /// The ip of the client, `None` if unknown (e.g. for bots).
+    pub ip: Option<IpAddr>,
 }
 
 #[derive(Debug, Hiarc, Clone, Serialize, Deserialize)]
@@ -15,7 +15,7 @@ use crate::{
     types::{
         character_info::{NetworkCharacterInfo, NetworkLaserInfo, NetworkSkinInfo},
         emoticons::EmoticonType,
-        game::GameTickType,
+        game::{GameTickType, NonZeroGameTickType},
         id_types::{CharacterId, StageId},
         weapons::WeaponType,
     },
@@ -31,6 +31,9 @@ pub enum CharacterBuff {
     /// for ddrace this is basically the /spec mode
     /// no hook or weapon is rendered
     Ghost,
+    /// the character can't be damaged,
+    /// e.g. shortly after it spawned
+    SpawnProtection,
 }
 
 #[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize)]
@@ -153,6 +156,9 @@ pub struct CharacterRenderInfo {
     /// In ddrace this are the solo parts.
     #[doc(alias = "solo")]
     pub phased: bool,
+    /// The character plays in practice mode,
+    /// e.g. ddrace's `/practice`.
+    pub practice: bool,
 }
 
 /// The camera mode of the local player
@@ -210,6 +216,15 @@ pub struct CharacterPlayerInfo {
     pub ingame_mode: PlayerIngameMode,
 }
 
+/// The rank of a player on the server,
+/// based on the points the server tracks, e.g. for accounts.
+#[derive(Debug, Hiarc, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CharacterServerRank {
+    /// `1` is the best rank.
+    pub rank: u64,
+    pub points: i64,
+}
+
 pub const MAX_SCORE_STR_LEN: usize = 32;
 /// General information about the character
 #[derive(Debug, Hiarc, Clone, Serialize, Deserialize)]
@@ -255,6 +270,16 @@ pub struct CharacterInfo {
     pub browser_score: PoolNetworkString<MAX_SCORE_STR_LEN>,
     /// Which Tee eyes to show in the browser (e.g. for afk Tees).
     pub browser_eye: TeeEye,
+
+    /// The character is controlled by a server-side bot
+    /// instead of a connected client.
+    pub is_bot: bool,
+
+    /// The rank of the player on this game server, if the server
+    /// tracks points.
+    ///
+    /// The scoreboard shows a rank column if any character has one.
+    pub server_rank: Option<CharacterServerRank>,
 }
 
 /// The local character info for vanilla based mods
@@ -265,6 +290,10 @@ pub struct LocalCharacterVanilla {
 
     /// A value of `None` means unlimited
     pub ammo_of_weapon: Option<u32>,
+    /// The ticks until the active weapon can fire again &
+    /// the full delay of its last shot.
+    /// A value of `None` means it can fire.
+    pub fire_delay: Option<(GameTickType, NonZeroGameTickType)>,
 }
 
 /// The local character info for ddrace based mods
@@ -16,6 +16,10 @@ pub struct FlagRenderInfo {
     /// owned/carried by a character.
     pub owner_id: Option<CharacterId>,
 
+    /// The time that is left until the dropped flag returns to its base,
+    /// relative to the whole return time (in `(0, 1]`).
+    pub return_time_left: Option<f32>,
+
     /// Whether the entity is phased, e.g. cannot hit any entitiy
     /// except the owner.
     ///
@@ -11,6 +11,7 @@ use crate::{
     types::{
         character_info::{MAX_ASSET_NAME_LEN, MAX_CHARACTER_NAME_LEN, NetworkSkinInfo},
         game::GameTickType,
+        id_types::CharacterId,
         resource_key::PoolNetworkResourceKey,
     },
 };
@@ -41,14 +42,28 @@ pub enum MatchRoundGameOverWinBy {
 /// If the game round has a game round countdown for this character,
 /// this should be set to [`MatchRoundTimeType::TimeLimit`].
 /// Else it should be set to [`MatchRoundTimeType::Normal`].
-/// If the round is over, but a winner must be decided [`MatchRoundTimeType::SuddenDeath`].
+/// If the round is over, but a winner must be decided [`MatchRoundTimeType::SuddenDeath`]
+/// or [`MatchRoundTimeType::Overtime`], if the overtime has a time limit.
+/// Before the round started [`MatchRoundTimeType::Warmup`].
 #[derive(Debug, Hiarc, Clone, Serialize, Deserialize)]
 pub enum MatchRoundTimeType {
     Normal,
+    Warmup {
+        /// Ticks until the round is started,
+        /// even if not enough players are ready.
+        ticks_left: GameTickType,
+        ready_players: u32,
+        total_players: u32,
+    },
     TimeLimit {
         ticks_left: GameTickType,
     },
     SuddenDeath,
+    Overtime {
+        ticks_left: GameTickType,
+        /// The how many-th overtime of the round, starting at `1`.
+        overtime: u32,
+    },
     GameOver {
         winner: MatchRoundGameOverWinner,
         by: MatchRoundGameOverWinBy,
@@ -68,5 +83,11 @@ pub enum GameRenderInfo {
         /// Whether to show a warning that the current sides have
         /// an unequal amount of players
         unbalanced: bool,
+        /// The characters that will be moved to the other side
+        /// to balance the sides, unless someone switches voluntarily.
+        balance_suggestion: Vec<CharacterId>,
+        /// The suggested characters are moved when the round ends,
+        /// instead of after a timeout.
+        balance_at_round_end: bool,
     },
 }
@@ -126,6 +126,31 @@ pub struct PlayerVoteKey {
     pub reason: NetworkString<MAX_VOTE_REASON_LEN>,
 }
 
+/// Why the reason of a player vote was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoteReasonError {
+    /// The server requires a reason, but none was given.
+    Missing,
+    /// The reason is longer than [`MAX_VOTE_REASON_LEN`].
+    TooLong,
+}
+
+/// Validates the reason of a player vote.
+///
+/// Surrounding whitespace is removed, a reason that only
+/// consists of whitespace counts as missing.
+pub fn validate_vote_reason(
+    reason: &str,
+    required: bool,
+) -> Result<NetworkString<MAX_VOTE_REASON_LEN>, VoteReasonError> {
+    let reason = reason.trim();
+    if required && reason.is_empty() {
+        Err(VoteReasonError::Missing)
+    } else {
+        NetworkString::new(reason).map_err(|_| VoteReasonError::TooLong)
+    }
+}
+
 /// Information to identify a random unfinished map vote send to a server.
 #[derive(Debug, Hiarc, Clone, Serialize, Deserialize)]
 pub struct RandomUnfinishedMapKey {
@@ -202,3 +227,36 @@ pub struct VoteState {
     /// Number of clients that are allowed to participate in this vote.
     pub allowed_to_vote_count: u64,
 }
+
+#[cfg(test)]
+mod test {
+    use super::{MAX_VOTE_REASON_LEN, VoteReasonError, validate_vote_reason};
+
+    #[test]
+    fn vote_reason() {
+        assert_eq!(validate_vote_reason("", false).unwrap().as_str(), "");
+        assert_eq!(
+            validate_vote_reason("  afk ", false).unwrap().as_str(),
+            "afk"
+        );
+        assert_eq!(validate_vote_reason("afk", true).unwrap().as_str(), "afk");
+
+        assert_eq!(
+            validate_vote_reason("", true).unwrap_err(),
+            VoteReasonError::Missing
+        );
+        assert_eq!(
+            validate_vote_reason(" \t ", true).unwrap_err(),
+            VoteReasonError::Missing
+        );
+
+        let max = "a".repeat(MAX_VOTE_REASON_LEN);
+        assert!(validate_vote_reason(&max, true).is_ok());
+        // the limit counts characters, not bytes
+        assert!(validate_vote_reason(&"ä".repeat(MAX_VOTE_REASON_LEN), true).is_ok());
+        assert_eq!(
+            validate_vote_reason(&format!("{max}a"), false).unwrap_err(),
+            VoteReasonError::TooLong
+        );
+    }
+}
@@ -0,0 +1,456 @@
+//! Servers announce themselves in the local network, so clients
+//! find them without the master server.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::ErrorKind,
+    net::{Ipv4Addr, SocketAddr, UdpSocket},
+    time::Duration,
+};
+
+use anyhow::anyhow;
+use base::hash::Hash;
+use game_base::server_browser::{ServerBrowserInfo, ServerBrowserServer};
+use serde::{Deserialize, Serialize};
+
+/// The port clients listen on for announces.
+pub const LAN_DISCOVERY_PORT: u16 = 8309;
+/// How often a server announces itself.
+pub const LAN_ANNOUNCE_INTERVAL: Duration = Duration::from_secs(2);
+/// A server that did not announce itself for this long is removed.
+pub const LAN_SERVER_TIMEOUT: Duration = Duration::from_secs(7);
+/// The maximum size of an announce packet.
+pub const MAX_LAN_ANNOUNCE_SIZE: usize = 4 * 1024;
+/// How many servers are remembered at most.
+const MAX_LAN_SERVERS: usize = 256;
+/// How many packets are handled per update at most,
+/// so a flood of packets can't stall the caller.
+const MAX_PACKETS_PER_UPDATE: usize = 64;
+
+const LAN_ANNOUNCE_MAGIC: &[u8; 8] = b"ddrs-lan";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanAnnounce {
+    /// The port of the game server, the ip is the one of the sender.
+    pub port: u16,
+    pub info: ServerBrowserInfo,
+}
+
+impl LanAnnounce {
+    /// Drops players from the info until the announce fits into a packet.
+    ///
+    /// `None` if it does not fit even without players.
+    pub fn encode(mut self) -> Option<Vec<u8>> {
+        loop {
+            let mut packet = LAN_ANNOUNCE_MAGIC.to_vec();
+            serde_json::to_writer(&mut packet, &self).ok()?;
+            if packet.len() <= MAX_LAN_ANNOUNCE_SIZE {
+                return Some(packet);
+            }
+            if self.info.players.is_empty() {
+                return None;
+            }
+            self.info.players.truncate(self.info.players.len() / 2);
+        }
+    }
+
+    pub fn decode(packet: &[u8]) -> anyhow::Result<Self> {
+        let json = packet
+            .strip_prefix(LAN_ANNOUNCE_MAGIC.as_slice())
+            .ok_or_else(|| anyhow!("packet is not a lan announce"))?;
+        Ok(serde_json::from_slice(json)?)
+    }
+}
+
+/// Sends & receives the packets of the LAN discovery.
+pub trait LanTransport {
+    /// Sends the packet to all hosts in the local network.
+    fn broadcast(&self, packet: &[u8]) -> anyhow::Result<()>;
+    /// The next received packet & its sender.
+    ///
+    /// `None` if no packet is pending, this must never block.
+    fn try_recv(&self) -> anyhow::Result<Option<(Vec<u8>, SocketAddr)>>;
+}
+
+/// The LAN discovery over UDP broadcasts (ipv4 only).
+#[derive(Debug)]
+pub struct UdpLanTransport {
+    socket: UdpSocket,
+    targets: Vec<SocketAddr>,
+}
+
+impl UdpLanTransport {
+    /// For servers, sends announces to the broadcast addresses.
+    ///
+    /// The limited broadcast (`255.255.255.255`) usually only reaches the network
+    /// of the default interface, directed broadcasts (e.g. `192.168.0.255`)
+    /// reach the networks of the other interfaces.
+    pub fn announcer(broadcast_addrs: &[Ipv4Addr], port: u16) -> anyhow::Result<Self> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+        socket.set_broadcast(true)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            socket,
+            targets: broadcast_addrs
+                .iter()
+                .map(|addr| SocketAddr::from((*addr, port)))
+                .collect(),
+        })
+    }
+
+    /// For clients, receives the announces of all interfaces.
+    ///
+    /// Multiple clients on the same machine can listen at once.
+    pub fn listener(port: u16) -> anyhow::Result<Self> {
+        let socket = socket2::Socket::new(
+            socket2::Domain::IPV4,
+            socket2::Type::DGRAM,
+            Some(socket2::Protocol::UDP),
+        )?;
+        socket.set_reuse_address(true)?;
+        #[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+        socket.set_reuse_port(true)?;
+        socket.set_nonblocking(true)?;
+        socket.bind(&SocketAddr::from((Ipv4Addr::UNSPECIFIED, port)).into())?;
+        Ok(Self {
+            socket: socket.into(),
+            targets: Vec::new(),
+        })
+    }
+}
+
+impl LanTransport for UdpLanTransport {
+    fn broadcast(&self, packet: &[u8]) -> anyhow::Result<()> {
+        // try all targets, even if one of them fails
+        let mut res = Ok(());
+        for target in &self.targets {
+            if let Err(err) = self.socket.send_to(packet, target) {
+                res = Err(anyhow!("failed to announce to {target}: {err}"));
+            }
+        }
+        res
+    }
+
+    fn try_recv(&self) -> anyhow::Result<Option<(Vec<u8>, SocketAddr)>> {
+        let mut buf = vec![0; MAX_LAN_ANNOUNCE_SIZE];
+        match self.socket.recv_from(&mut buf) {
+            Ok((size, from)) => {
+                buf.truncate(size);
+                Ok(Some((buf, from)))
+            }
+            Err(err) if err.kind() == ErrorKind::WouldBlock => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// Announces a server periodically.
+#[derive(Debug)]
+pub struct LanAnnouncer<T> {
+    transport: T,
+    last_announce: Option<Duration>,
+}
+
+impl<T: LanTransport> LanAnnouncer<T> {
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport,
+            last_announce: None,
+        }
+    }
+
+    pub fn should_announce(&self, cur_time: Duration) -> bool {
+        self.last_announce
+            .is_none_or(|last| cur_time.saturating_sub(last) >= LAN_ANNOUNCE_INTERVAL)
+    }
+
+    pub fn announce(&mut self, cur_time: Duration, announce: LanAnnounce) -> anyhow::Result<()> {
+        self.last_announce = Some(cur_time);
+        let packet = announce
+            .encode()
+            .ok_or_else(|| anyhow!("the server info is too big for a lan announce"))?;
+        self.transport.broadcast(&packet)
+    }
+}
+
+#[derive(Debug)]
+struct LanServer {
+    info: ServerBrowserInfo,
+    /// The addresses & when they were announced the last time.
+    addresses: BTreeMap<SocketAddr, Duration>,
+}
+
+/// The servers found in the local network.
+///
+/// Servers are identified by their certificate, so a server that
+/// is announced over multiple interfaces is listed once, with all its addresses.
+#[derive(Debug, Default)]
+pub struct LanServers {
+    servers: HashMap<Hash, LanServer>,
+}
+
+impl LanServers {
+    pub fn on_announce(
+        &mut self,
+        packet: &[u8],
+        from: SocketAddr,
+        cur_time: Duration,
+    ) -> anyhow::Result<()> {
+        let announce = LanAnnounce::decode(packet)?;
+        let id = announce.info.cert_sha256_fingerprint;
+        if !self.servers.contains_key(&id) && self.servers.len() >= MAX_LAN_SERVERS {
+            anyhow::bail!("too many lan servers");
+        }
+        let addr = SocketAddr::new(from.ip(), announce.port);
+        match self.servers.get_mut(&id) {
+            Some(server) => {
+                server.info = announce.info;
+                server.addresses.insert(addr, cur_time);
+            }
+            None => {
+                self.servers.insert(
+                    id,
+                    LanServer {
+                        info: announce.info,
+                        addresses: [(addr, cur_time)].into(),
+                    },
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes the addresses that were not announced anymore
+    /// & the servers without addresses.
+    ///
+    /// Returns `true` if anything was removed.
+    pub fn expire(&mut self, cur_time: Duration) -> bool {
+        let mut expired = false;
+        self.servers.retain(|_, server| {
+            server.addresses.retain(|_, last_seen| {
+                let alive = cur_time.saturating_sub(*last_seen) < LAN_SERVER_TIMEOUT;
+                expired |= !alive;
+                alive
+            });
+            !server.addresses.is_empty()
+        });
+        expired
+    }
+
+    /// The servers, sorted by name.
+    pub fn servers(&self) -> Vec<ServerBrowserServer> {
+        let mut servers: Vec<_> = self
+            .servers
+            .values()
+            .map(|server| ServerBrowserServer {
+                info: server.info.clone(),
+                addresses: server.addresses.keys().copied().collect(),
+                location: "default".try_into().unwrap(),
+                legacy_server: false,
+            })
+            .collect();
+        servers.sort_by(|s1, s2| {
+            s1.info
+                .name
+                .cmp(&s2.info.name)
+                .then_with(|| s1.addresses.cmp(&s2.addresses))
+        });
+        servers
+    }
+}
+
+/// Listens for the announces of the servers in the local network.
+#[derive(Debug)]
+pub struct LanListener<T> {
+    transport: T,
+    servers: LanServers,
+}
+
+impl<T: LanTransport> LanListener<T> {
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport,
+            servers: Default::default(),
+        }
+    }
+
+    /// Handles the pending announces & removes the servers that
+    /// stopped announcing themselves.
+    ///
+    /// Returns `true` if the servers changed.
+    pub fn update(&mut self, cur_time: Duration) -> bool {
+        let mut changed = false;
+        for _ in 0..MAX_PACKETS_PER_UPDATE {
+            match self.transport.try_recv() {
+                Ok(Some((packet, from))) => {
+                    match self.servers.on_announce(&packet, from, cur_time) {
+                        Ok(()) => changed = true,
+                        Err(err) => log::debug!("ignored lan packet from {from}: {err}"),
+                    }
+                }
+                Ok(None) => break,
+                Err(err) => {
+                    log::debug!("failed to receive lan packet: {err}");
+                    break;
+                }
+            }
+        }
+        self.servers.expire(cur_time) || changed
+    }
+
+    pub fn servers(&self) -> Vec<ServerBrowserServer> {
+        self.servers.servers()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, collections::VecDeque, net::SocketAddr, rc::Rc, time::Duration};
+
+    use game_base::server_browser::{ServerBrowserInfo, ServerBrowserPlayer};
+
+    use super::{
+        LAN_ANNOUNCE_INTERVAL, LAN_SERVER_TIMEOUT, LanAnnounce, LanAnnouncer, LanListener,
+        LanTransport, MAX_LAN_ANNOUNCE_SIZE,
+    };
+
+    type Packets = Rc<RefCell<VecDeque<(Vec<u8>, SocketAddr)>>>;
+
+    /// Delivers the broadcasts in-process, as if they were sent from `addr`.
+    struct LoopbackTransport {
+        addr: SocketAddr,
+        packets: Packets,
+    }
+
+    impl LanTransport for LoopbackTransport {
+        fn broadcast(&self, packet: &[u8]) -> anyhow::Result<()> {
+            self.packets
+                .borrow_mut()
+                .push_back((packet.to_vec(), self.addr));
+            Ok(())
+        }
+
+        fn try_recv(&self) -> anyhow::Result<Option<(Vec<u8>, SocketAddr)>> {
+            Ok(self.packets.borrow_mut().pop_front())
+        }
+    }
+
+    fn info(name: &str, cert: u8) -> ServerBrowserInfo {
+        ServerBrowserInfo {
+            name: name.try_into().unwrap(),
+            game_type: Default::default(),
+            version: Default::default(),
+            map: Default::default(),
+            players: Default::default(),
+            max_ingame_players: 8,
+            max_players: 8,
+            max_players_per_client: 1,
+            passworded: false,
+            tournament_mode: false,
+            cert_sha256_fingerprint: [cert; 32],
+            requires_account: false,
+        }
+    }
+
+    fn announce(name: &str, cert: u8, port: u16) -> LanAnnounce {
+        LanAnnounce {
+            port,
+            info: info(name, cert),
+        }
+    }
+
+    #[test]
+    fn announce_parse() {
+        let packet = announce("lan", 1, 8310).encode().unwrap();
+        let decoded = LanAnnounce::decode(&packet).unwrap();
+        assert_eq!(decoded.port, 8310);
+        assert_eq!(decoded.info.name.as_str(), "lan");
+
+        assert!(LanAnnounce::decode(b"ddrs-lan{").is_err());
+        assert!(LanAnnounce::decode(b"{\"port\":8310}").is_err());
+
+        // too many players are dropped
+        let mut big = announce("lan", 1, 8310);
+        big.info.players = vec![
+            ServerBrowserPlayer {
+                score: Default::default(),
+                skin: Default::default(),
+                name: "player".try_into().unwrap(),
+                clan: Default::default(),
+                account_name: None,
+                flag: Default::default(),
+            };
+            1000
+        ];
+        let packet = big.encode().unwrap();
+        assert!(packet.len() <= MAX_LAN_ANNOUNCE_SIZE);
+        let decoded = LanAnnounce::decode(&packet).unwrap();
+        assert!(!decoded.info.players.is_empty() && decoded.info.players.len() < 1000);
+    }
+
+    #[test]
+    fn discovery_and_expiry() {
+        let packets: Packets = Default::default();
+        let transport = |addr: &str| LoopbackTransport {
+            addr: addr.parse().unwrap(),
+            packets: packets.clone(),
+        };
+        let mut server1 = LanAnnouncer::new(transport("192.168.0.2:40000"));
+        // the same server on a second interface
+        let mut server1_other = LanAnnouncer::new(transport("10.0.0.2:40001"));
+        let mut server2 = LanAnnouncer::new(transport("192.168.0.3:40000"));
+        let mut listener = LanListener::new(transport("0.0.0.0:0"));
+        let secs = Duration::from_secs;
+
+        assert!(!listener.update(secs(0)));
+        assert!(listener.servers().is_empty());
+
+        server1.announce(secs(0), announce("b", 1, 8310)).unwrap();
+        server1_other
+            .announce(secs(0), announce("b", 1, 8310))
+            .unwrap();
+        server2.announce(secs(0), announce("a", 2, 8312)).unwrap();
+        assert!(listener.update(secs(0)));
+        let servers = listener.servers();
+        assert_eq!(servers.len(), 2);
+        assert_eq!(servers[0].info.name.as_str(), "a");
+        assert_eq!(
+            servers[0].addresses,
+            vec!["192.168.0.3:8312".parse::<SocketAddr>().unwrap()]
+        );
+        assert_eq!(
+            servers[1].addresses,
+            vec![
+                "10.0.0.2:8310".parse::<SocketAddr>().unwrap(),
+                "192.168.0.2:8310".parse().unwrap()
+            ]
+        );
+
+        // announces are rate limited
+        assert!(!server1.should_announce(secs(1)));
+        assert!(server1.should_announce(LAN_ANNOUNCE_INTERVAL));
+
+        // only server 1 keeps announcing
+        server1.announce(secs(5), announce("b", 1, 8310)).unwrap();
+        assert!(listener.update(secs(5)));
+        assert_eq!(listener.servers().len(), 2);
+
+        assert!(listener.update(LAN_SERVER_TIMEOUT));
+        let servers = listener.servers();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(
+            servers[0].addresses,
+            vec!["192.168.0.2:8310".parse::<SocketAddr>().unwrap()]
+        );
+        assert!(!listener.update(secs(8)));
+
+        // garbage is ignored
+        packets
+            .borrow_mut()
+            .push_back((b"garbage".to_vec(), "192.168.0.9:1".parse().unwrap()));
+        assert!(!listener.update(secs(8)));
+
+        assert!(listener.update(secs(5) + LAN_SERVER_TIMEOUT));
+        assert!(listener.servers().is_empty());
+    }
+}
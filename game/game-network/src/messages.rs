@@ -40,6 +40,17 @@ pub struct MsgSvInputAck {
     pub logic_overhead: Duration,
 }
 
+/// The tick the server applied the inputs of an input packet for,
+/// only sent to clients that requested it,
+/// see [`ClientToServerMessage::InputTimings`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct MsgSvInputTiming {
+    pub id: u64,
+    /// `None` if the inputs were dropped, e.g. because they were for a tick
+    /// too far in the future.
+    pub applied_monotonic_tick: Option<GameTickType>,
+}
+
 /// List of votes.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MsgSvLoadVotes {
@@ -50,6 +61,13 @@ pub enum MsgSvLoadVotes {
     Misc {
         votes: BTreeMap<NetworkString<MAX_CATEGORY_NAME_LEN>, BTreeMap<MiscVoteKey, MiscVote>>,
     },
+    Players {
+        /// Players that the requesting client can not
+        /// start a player vote against.
+        immune: Vec<PlayerId>,
+        /// Whether player votes require a reason.
+        reason_required: bool,
+    },
 }
 
 /// Type of votes to reset.
@@ -75,6 +93,7 @@ pub enum MsgSvStartVoteResult {
     MiscVoteDoesNotExist,
     CantVoteAsSpectator,
     RandomUnfinishedMapUnsupported,
+    ReasonRequired,
 }
 
 /// List of votes.
@@ -125,6 +144,15 @@ pub enum ServerToClientMessage<'a> {
         /// actually contained player inputs.
         input_ack: PoolCow<'a, [MsgSvInputAck]>,
     },
+    /// Debug information about when the server applied the inputs
+    /// of the client, see [`MsgSvInputTiming`].
+    InputTimings(Vec<MsgSvInputTiming>),
+    /// The number of ticks between two snapshots the server
+    /// currently sends to this client.
+    /// Sent whenever the server adapts the rate to the connection.
+    SnapshotRate {
+        ticks_per_snapshot: GameTickType,
+    },
     Events {
         /// see Snapshot variant
         game_monotonic_tick: GameTickType,
@@ -133,6 +161,9 @@ pub enum ServerToClientMessage<'a> {
     // a load event, e.g. because of a map change
     Load(MsgSvServerInfo),
     Chat(MsgSvChatMsg),
+    /// A message of the server itself,
+    /// e.g. the countdown of a map change or shutdown.
+    Announcement(NetworkString<1024>),
     /// A value of `None` must be interpreted as no vote active.
     StartVoteRes(MsgSvStartVoteResult),
     Vote(Option<VoteState>),
@@ -182,6 +213,8 @@ pub enum ClientToServerPlayerMessage<'a> {
     },
 }
 
+pub const MAX_RCON_PASSWORD_LEN: usize = 128;
+
 #[derive(Serialize, Deserialize)]
 pub enum ClientToServerMessage<'a> {
     Custom(PoolCow<'a, [u8]>),
@@ -209,4 +242,13 @@ pub enum ClientToServerMessage<'a> {
     /// Notify the server that the clients wants no
     /// more spatial chat packets.
     SpatialChatDeactivated,
+    /// Login to the remote console with a password.
+    RconAuth {
+        password: NetworkString<MAX_RCON_PASSWORD_LEN>,
+    },
+    /// Whether the server should send [`ServerToClientMessage::InputTimings`]
+    /// to diagnose input delay.
+    InputTimings {
+        enabled: bool,
+    },
 }
@@ -8,13 +8,15 @@ use std::{
 use base::linked_hash_map_view::FxLinkedHashMap;
 use game_base::network::messages::PlayerInputChainable;
 use game_interface::types::{
-    id_types::PlayerId, network_stats::PlayerNetworkStats, player_info::PlayerDropReason,
+    id_types::PlayerId,
+    network_stats::PlayerNetworkStats,
+    player_info::{AccountId, PlayerDropReason},
 };
-use game_network::messages::MsgSvInputAck;
+use game_network::messages::{MsgSvInputAck, MsgSvInputTiming};
 use network::network::connection::NetworkConnectionId;
 use pool::{datatypes::PoolFxLinkedHashMap, pool::Pool};
 
-use crate::server_game::ClientAuth;
+use crate::{server_game::ClientAuth, snapshot_rate::SnapshotRateController};
 
 #[derive(Debug)]
 pub struct ServerPasswordClient {
@@ -114,14 +116,26 @@ pub struct ServerClient {
     pub client_snap_storage: BTreeMap<u64, ClientSnapshotStorage>,
 
     pub inputs_to_ack: Vec<MsgSvInputAck>,
+    /// `Some` if the client requested the input timings.
+    pub input_timings: Option<Vec<MsgSvInputTiming>>,
 
     pub network_stats: PlayerNetworkStats,
+    /// Packets lost & sent in total at the last network stats update.
+    pub packets_lost_sent: (u64, u64),
+
+    pub snapshot_rate: SnapshotRateController,
+    /// The snapshot rate the client was informed about.
+    pub announced_ticks_per_snapshot: Option<u64>,
 
     pub loaded_map_votes: bool,
     pub loaded_misc_votes: bool,
 
     pub ip: IpAddr,
     pub auth: ClientAuth,
+    /// The account of the client, if it has one.
+    pub account_id: Option<AccountId>,
+    /// Chat messages of muted clients are dropped.
+    pub muted: bool,
 
     pub requested_account_rename: bool,
     pub requested_account_details: bool,
@@ -148,14 +162,21 @@ impl ServerClient {
             client_snap_storage: Default::default(),
 
             inputs_to_ack: Default::default(),
+            input_timings: None,
 
             loaded_map_votes: false,
             loaded_misc_votes: false,
 
             ip,
             auth,
+            account_id: None,
+            muted: false,
 
             network_stats,
+            packets_lost_sent: (0, 0),
+
+            snapshot_rate: Default::default(),
+            announced_ticks_per_snapshot: None,
 
             requested_account_rename: false,
             requested_account_details: false,
@@ -171,6 +192,8 @@ pub struct Clients {
     pub network_queued_clients: FxLinkedHashMap<NetworkConnectionId, ServerNetworkQueuedClient>,
     pub network_clients: HashMap<NetworkConnectionId, ServerNetworkClient>,
     pub clients: HashMap<NetworkConnectionId, ServerClient>,
+    /// Connections that entered the reserved slots password.
+    pub reserved_password_clients: HashSet<NetworkConnectionId>,
 
     // pools
     players_pool: Pool<FxLinkedHashMap<PlayerId, ServerClientPlayer>>,
@@ -189,6 +212,7 @@ impl Clients {
             network_queued_clients: Default::default(),
             network_clients: Default::default(),
             clients: Default::default(),
+            reserved_password_clients: Default::default(),
         }
     }
 
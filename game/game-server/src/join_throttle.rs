@@ -0,0 +1,366 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Display,
+    net::IpAddr,
+    time::Duration,
+};
+
+use game_config::config::ConfigServer;
+
+/// The window in which join attempts are counted.
+const JOIN_ATTEMPTS_WINDOW: Duration = Duration::from_secs(60);
+/// Violations are forgotten, if the ip did not violate
+/// the limit for this long.
+const VIOLATION_DECAY: Duration = Duration::from_secs(60 * 60 * 24);
+
+#[derive(Debug, Clone, Copy)]
+pub struct JoinThrottleConfig {
+    /// `0` disables the throttling.
+    pub max_join_attempts_per_minute: u32,
+    /// Ban time of the first violation.
+    pub ban: Duration,
+    pub max_ban: Duration,
+}
+
+impl JoinThrottleConfig {
+    pub fn new(config: &ConfigServer) -> Self {
+        Self {
+            max_join_attempts_per_minute: config.max_join_attempts_per_ip,
+            ban: Duration::from_secs(config.join_throttle_ban_secs as u64),
+            max_ban: Duration::from_secs(config.join_throttle_max_ban_secs as u64),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinDecision {
+    Allow,
+    /// Joined too often and is banned for the remaining time.
+    Throttled {
+        remaining: Duration,
+    },
+}
+
+impl Display for JoinDecision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JoinDecision::Allow => write!(f, "allowed"),
+            JoinDecision::Throttled { remaining } => {
+                write!(f, "throttled for {}s", remaining.as_secs())
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct IpThrottle {
+    attempts: VecDeque<Duration>,
+    violations: u32,
+    last_violation: Duration,
+    banned_until: Option<Duration>,
+    last_decision: JoinDecision,
+}
+
+/// The throttle state of a single ip, used by the rcon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JoinThrottleEntry {
+    pub ip: IpAddr,
+    /// Join attempts within the last minute.
+    pub attempts: usize,
+    pub violations: u32,
+    pub remaining_ban: Option<Duration>,
+    pub last_decision: JoinDecision,
+}
+
+impl Display for JoinThrottleEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ip: {}, attempts: {}, violations: {}, last decision: {}",
+            self.ip, self.attempts, self.violations, self.last_decision
+        )?;
+        if let Some(remaining) = self.remaining_ban {
+            write!(f, ", banned for {}s", remaining.as_secs())?;
+        }
+        Ok(())
+    }
+}
+
+/// Limits how often an ip can join the server.
+///
+/// Ips that join too often are banned temporarily,
+/// the ban time doubles for every repeated violation.
+#[derive(Debug, Default)]
+pub struct JoinThrottle {
+    ips: HashMap<IpAddr, IpThrottle>,
+}
+
+impl JoinThrottle {
+    fn remaining_ban(throttle: &IpThrottle, now: Duration) -> Option<Duration> {
+        throttle
+            .banned_until
+            .filter(|until| now < *until)
+            .map(|until| until - now)
+    }
+
+    /// Records a join attempt of the ip and decides if it may join.
+    ///
+    /// `now` must be monotonic.
+    pub fn on_join_attempt(
+        &mut self,
+        ip: IpAddr,
+        now: Duration,
+        config: &JoinThrottleConfig,
+    ) -> JoinDecision {
+        if config.max_join_attempts_per_minute == 0 {
+            return JoinDecision::Allow;
+        }
+        let throttle = self.ips.entry(ip).or_insert_with(|| IpThrottle {
+            attempts: Default::default(),
+            violations: 0,
+            last_violation: now,
+            banned_until: None,
+            last_decision: JoinDecision::Allow,
+        });
+
+        let decision = if let Some(remaining) = Self::remaining_ban(throttle, now) {
+            JoinDecision::Throttled { remaining }
+        } else {
+            while throttle
+                .attempts
+                .front()
+                .is_some_and(|attempt| now.saturating_sub(*attempt) >= JOIN_ATTEMPTS_WINDOW)
+            {
+                throttle.attempts.pop_front();
+            }
+            throttle.attempts.push_back(now);
+
+            if throttle.attempts.len() > config.max_join_attempts_per_minute as usize {
+                if throttle.violations > 0
+                    && now.saturating_sub(throttle.last_violation) >= VIOLATION_DECAY
+                {
+                    throttle.violations = 0;
+                }
+                throttle.violations += 1;
+                throttle.last_violation = now;
+                throttle.attempts.clear();
+
+                let ban = config
+                    .ban
+                    .saturating_mul(2u32.saturating_pow(throttle.violations - 1))
+                    .min(config.max_ban);
+                throttle.banned_until = Some(now + ban);
+                JoinDecision::Throttled { remaining: ban }
+            } else {
+                JoinDecision::Allow
+            }
+        };
+        throttle.last_decision = decision;
+        decision
+    }
+
+    /// The state of all ips that joined recently or are still remembered
+    /// because of violations.
+    pub fn entries(&self, now: Duration) -> Vec<JoinThrottleEntry> {
+        let mut entries: Vec<_> = self
+            .ips
+            .iter()
+            .map(|(ip, throttle)| JoinThrottleEntry {
+                ip: *ip,
+                attempts: throttle
+                    .attempts
+                    .iter()
+                    .filter(|attempt| now.saturating_sub(**attempt) < JOIN_ATTEMPTS_WINDOW)
+                    .count(),
+                violations: throttle.violations,
+                remaining_ban: Self::remaining_ban(throttle, now),
+                last_decision: throttle.last_decision,
+            })
+            .collect();
+        entries.sort_by_key(|entry| entry.ip);
+        entries
+    }
+
+    /// Clears the throttle of the given ip or of all ips.
+    ///
+    /// Returns the ips that were cleared.
+    pub fn clear(&mut self, ip: Option<IpAddr>) -> Vec<IpAddr> {
+        match ip {
+            Some(ip) => self.ips.remove(&ip).map(|_| ip).into_iter().collect(),
+            None => self.ips.drain().map(|(ip, _)| ip).collect(),
+        }
+    }
+
+    /// Forgets ips that neither joined recently,
+    /// nor are banned or have recent violations.
+    pub fn cleanup(&mut self, now: Duration) {
+        self.ips.retain(|_, throttle| {
+            throttle
+                .attempts
+                .back()
+                .is_some_and(|attempt| now.saturating_sub(*attempt) < JOIN_ATTEMPTS_WINDOW)
+                || Self::remaining_ban(throttle, now).is_some()
+                || (throttle.violations > 0
+                    && now.saturating_sub(throttle.last_violation) < VIOLATION_DECAY)
+        });
+    }
+}
+
+/// The maximum players a single account can have over all its clients.
+///
+/// A client with all its dummies can always join.
+pub fn max_players_per_account(config: &ConfigServer) -> Option<usize> {
+    (config.max_players_per_account > 0).then(|| {
+        config
+            .max_players_per_account
+            .max(config.max_players_per_client) as usize
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::{net::IpAddr, time::Duration};
+
+    use game_config::config::ConfigServer;
+
+    use super::{
+        JOIN_ATTEMPTS_WINDOW, JoinDecision, JoinThrottle, JoinThrottleConfig,
+        max_players_per_account,
+    };
+
+    fn config() -> JoinThrottleConfig {
+        JoinThrottleConfig {
+            max_join_attempts_per_minute: 5,
+            ban: Duration::from_secs(60),
+            max_ban: Duration::from_secs(200),
+        }
+    }
+
+    #[test]
+    fn join_flood() {
+        let config = config();
+        let mut throttle = JoinThrottle::default();
+        let ip: IpAddr = "127.0.0.1".parse().unwrap();
+        let other_ip: IpAddr = "127.0.0.2".parse().unwrap();
+        let mut now = Duration::from_secs(1000);
+
+        for _ in 0..5 {
+            assert_eq!(
+                throttle.on_join_attempt(ip, now, &config),
+                JoinDecision::Allow
+            );
+            now += Duration::from_secs(1);
+        }
+        // the sixth attempt within a minute is throttled
+        assert_eq!(
+            throttle.on_join_attempt(ip, now, &config),
+            JoinDecision::Throttled {
+                remaining: Duration::from_secs(60)
+            }
+        );
+        // other ips are not affected
+        assert_eq!(
+            throttle.on_join_attempt(other_ip, now, &config),
+            JoinDecision::Allow
+        );
+
+        now += Duration::from_secs(10);
+        assert_eq!(
+            throttle.on_join_attempt(ip, now, &config),
+            JoinDecision::Throttled {
+                remaining: Duration::from_secs(50)
+            }
+        );
+        let entry = throttle
+            .entries(now)
+            .into_iter()
+            .find(|entry| entry.ip == ip)
+            .unwrap();
+        assert_eq!(entry.violations, 1);
+        assert_eq!(entry.remaining_ban, Some(Duration::from_secs(50)));
+
+        // after the ban the ip can join again
+        now += Duration::from_secs(50);
+        assert_eq!(
+            throttle.on_join_attempt(ip, now, &config),
+            JoinDecision::Allow
+        );
+
+        // slow joins are never throttled
+        let mut throttle = JoinThrottle::default();
+        for _ in 0..100 {
+            assert_eq!(
+                throttle.on_join_attempt(ip, now, &config),
+                JoinDecision::Allow
+            );
+            now += JOIN_ATTEMPTS_WINDOW / 5;
+        }
+    }
+
+    #[test]
+    fn escalation() {
+        let config = config();
+        let mut throttle = JoinThrottle::default();
+        let ip: IpAddr = "::1".parse().unwrap();
+        let mut now = Duration::from_secs(1000);
+
+        let mut flood = |now: &mut Duration| {
+            let mut decision = JoinDecision::Allow;
+            while decision == JoinDecision::Allow {
+                decision = throttle.on_join_attempt(ip, *now, &config);
+            }
+            let JoinDecision::Throttled { remaining } = decision else {
+                unreachable!();
+            };
+            *now += remaining;
+            remaining
+        };
+        // the ban doubles for every violation, up to the maximum
+        assert_eq!(flood(&mut now), Duration::from_secs(60));
+        assert_eq!(flood(&mut now), Duration::from_secs(120));
+        assert_eq!(flood(&mut now), Duration::from_secs(200));
+        assert_eq!(flood(&mut now), Duration::from_secs(200));
+
+        // violations are forgotten after a long time
+        now += Duration::from_secs(60 * 60 * 24);
+        assert_eq!(flood(&mut now), Duration::from_secs(60));
+
+        // clearing removes the ban immediately
+        flood(&mut now);
+        now -= Duration::from_secs(1);
+        assert_eq!(throttle.clear(Some(ip)), vec![ip]);
+        assert_eq!(
+            throttle.on_join_attempt(ip, now, &config),
+            JoinDecision::Allow
+        );
+
+        throttle.cleanup(now + JOIN_ATTEMPTS_WINDOW);
+        assert!(throttle.entries(now).is_empty());
+    }
+
+    #[test]
+    fn disabled_and_account_limit() {
+        let config = JoinThrottleConfig {
+            max_join_attempts_per_minute: 0,
+            ..config()
+        };
+        let mut throttle = JoinThrottle::default();
+        let ip: IpAddr = "127.0.0.1".parse().unwrap();
+        for _ in 0..100 {
+            assert_eq!(
+                throttle.on_join_attempt(ip, Duration::ZERO, &config),
+                JoinDecision::Allow
+            );
+        }
+
+        let server_config = |max_players_per_account| ConfigServer {
+            max_players_per_client: 2,
+            max_players_per_account,
+            ..Default::default()
+        };
+        // a client with its dummy can always join
+        assert_eq!(max_players_per_account(&server_config(1)), Some(2));
+        assert_eq!(max_players_per_account(&server_config(4)), Some(4));
+        assert_eq!(max_players_per_account(&server_config(0)), None);
+    }
+}
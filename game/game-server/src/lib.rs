@@ -2,10 +2,14 @@
 
 pub mod auto_map_votes;
 pub mod client;
+pub mod join_throttle;
 pub mod local_server;
+pub mod map_change;
 pub mod map_votes;
 pub mod network_plugins;
 pub mod rcon;
+pub mod reserved_slots;
 pub mod server;
 pub mod server_game;
+pub mod snapshot_rate;
 pub mod spatial_chat;
@@ -0,0 +1,380 @@
+//! Graceful map changes & shutdowns of the server.
+//!
+//! A change is announced with a countdown first. On a map change the
+//! teams & spectator states of all players are handed over to the
+//! game state of the next map, so the players land in the same team
+//! again without reconnecting.
+
+use std::{collections::HashMap, hash::Hash, time::Duration};
+
+use base::network_string::NetworkReducedAsciiString;
+use game_interface::{
+    client_commands::ClientCommand,
+    interface::MAX_MAP_NAME_LEN,
+    types::{
+        id_types::PlayerId,
+        render::{game::game_match::MatchSide, scoreboard::ScoreboardGameType},
+    },
+};
+
+/// The remaining seconds of a countdown that are always announced.
+const ANNOUNCE_LAST_SECS: u64 = 5;
+/// Besides the last seconds, every multiple of this is announced.
+const ANNOUNCE_EVERY_SECS: u64 = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerChangeKind {
+    Map(NetworkReducedAsciiString<MAX_MAP_NAME_LEN>),
+    Shutdown { reason: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerChangeUpdate {
+    None,
+    /// Announce the change to all clients.
+    Announce(String),
+    /// The countdown is over, the change must be executed now.
+    Execute(ServerChangeKind),
+}
+
+/// A map change or shutdown that waits for its countdown.
+#[derive(Debug)]
+pub struct PendingServerChange {
+    kind: ServerChangeKind,
+    at: Duration,
+    last_announced_secs: Option<u64>,
+}
+
+impl PendingServerChange {
+    pub fn new(kind: ServerChangeKind, now: Duration, countdown: Duration) -> Self {
+        Self {
+            kind,
+            at: now + countdown,
+            last_announced_secs: None,
+        }
+    }
+
+    pub fn kind(&self) -> &ServerChangeKind {
+        &self.kind
+    }
+
+    fn announcement(&self, secs: u64) -> String {
+        let unit = if secs == 1 { "second" } else { "seconds" };
+        match &self.kind {
+            ServerChangeKind::Map(map) => {
+                format!("Changing map to {} in {secs} {unit}.", map.as_str())
+            }
+            ServerChangeKind::Shutdown { reason } if reason.is_empty() => {
+                format!("Server shuts down in {secs} {unit}.")
+            }
+            ServerChangeKind::Shutdown { reason } => {
+                format!("Server shuts down in {secs} {unit}: {reason}")
+            }
+        }
+    }
+
+    /// `now` must be monotonic.
+    pub fn update(&mut self, now: Duration) -> ServerChangeUpdate {
+        if now >= self.at {
+            return ServerChangeUpdate::Execute(self.kind.clone());
+        }
+        let remaining = self.at - now;
+        // round up, so the countdown never announces 0 seconds
+        let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+        let should_announce = match self.last_announced_secs {
+            // the first announcement is always sent
+            None => true,
+            Some(last_secs) => {
+                secs < last_secs && (secs <= ANNOUNCE_LAST_SECS || secs % ANNOUNCE_EVERY_SECS == 0)
+            }
+        };
+        if should_announce {
+            self.last_announced_secs = Some(secs);
+            ServerChangeUpdate::Announce(self.announcement(secs))
+        } else {
+            ServerChangeUpdate::None
+        }
+    }
+}
+
+/// The team of a player before the map change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandoverTeam {
+    Spectator,
+    Side(MatchSide),
+}
+
+/// The state of a single player that survives a map change.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlayerHandover {
+    pub team: Option<HandoverTeam>,
+    /// Mod specific state, see
+    /// [`game_interface::interface::GameStateInterface::player_handover`].
+    pub mod_data: Option<Vec<u8>>,
+}
+
+impl PlayerHandover {
+    /// The command that puts the player into its previous team.
+    pub fn client_command(&self) -> Option<ClientCommand> {
+        self.team.map(|team| match team {
+            HandoverTeam::Spectator => ClientCommand::JoinSpectator,
+            HandoverTeam::Side(side) => ClientCommand::JoinSide(side),
+        })
+    }
+}
+
+/// Reads the teams of all players from the scoreboard of the game.
+pub fn player_teams(game: &ScoreboardGameType) -> HashMap<PlayerId, HandoverTeam> {
+    match game {
+        ScoreboardGameType::SidedPlay {
+            red_stages,
+            blue_stages,
+            spectator_players,
+            ..
+        } => red_stages
+            .values()
+            .flat_map(|stage| stage.characters.iter())
+            .map(|c| (c.id, HandoverTeam::Side(MatchSide::Red)))
+            .chain(
+                blue_stages
+                    .values()
+                    .flat_map(|stage| stage.characters.iter())
+                    .map(|c| (c.id, HandoverTeam::Side(MatchSide::Blue))),
+            )
+            .chain(
+                spectator_players
+                    .iter()
+                    .map(|c| (c.id, HandoverTeam::Spectator)),
+            )
+            .collect(),
+        ScoreboardGameType::SoloPlay {
+            spectator_players, ..
+        } => spectator_players
+            .iter()
+            .map(|c| (c.id, HandoverTeam::Spectator))
+            .collect(),
+    }
+}
+
+/// The handovers of all players of the previous map,
+/// until they joined the game again.
+///
+/// Players are identified by their connection `C`
+/// and the id their client gave them.
+#[derive(Debug)]
+pub struct PlayerHandovers<C> {
+    players: HashMap<(C, u64), PlayerHandover>,
+}
+
+impl<C> Default for PlayerHandovers<C> {
+    fn default() -> Self {
+        Self {
+            players: Default::default(),
+        }
+    }
+}
+
+impl<C: Hash + Eq + Copy> PlayerHandovers<C> {
+    /// Collects the handovers of the given players
+    /// (`(connection, client player id, player id)`).
+    pub fn collect(
+        players: impl IntoIterator<Item = (C, u64, PlayerId)>,
+        teams: &HashMap<PlayerId, HandoverTeam>,
+        mod_data: impl Fn(&PlayerId) -> Option<Vec<u8>>,
+    ) -> Self {
+        Self {
+            players: players
+                .into_iter()
+                .map(|(con_id, id, player_id)| {
+                    (
+                        (con_id, id),
+                        PlayerHandover {
+                            team: teams.get(&player_id).copied(),
+                            mod_data: mod_data(&player_id),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Takes the handover of a player that joined the game again.
+    pub fn take(&mut self, con_id: &C, id: u64) -> Option<PlayerHandover> {
+        self.players.remove(&(*con_id, id))
+    }
+
+    /// The client left before all its players joined again.
+    pub fn remove_client(&mut self, con_id: &C) {
+        self.players
+            .retain(|(player_con_id, _), _| player_con_id != con_id);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.players.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use game_interface::{
+        client_commands::ClientCommand,
+        types::{
+            game::GameEntityId,
+            id_types::PlayerId,
+            render::{
+                game::game_match::MatchSide,
+                scoreboard::{
+                    ScoreboardCharacterInfo, ScoreboardConnectionType, ScoreboardGameType,
+                    ScoreboardScoreType, ScoreboardStageInfo,
+                },
+            },
+        },
+    };
+    use pool::{
+        datatypes::{PoolFxLinkedHashMap, PoolVec},
+        recycle::Recycle,
+    };
+
+    use super::{
+        HandoverTeam, PendingServerChange, PlayerHandovers, ServerChangeKind, ServerChangeUpdate,
+        player_teams,
+    };
+
+    fn id(id: &str) -> GameEntityId {
+        id.parse().unwrap()
+    }
+
+    fn character(player_id: PlayerId) -> ScoreboardCharacterInfo {
+        ScoreboardCharacterInfo {
+            id: player_id,
+            score: ScoreboardScoreType::None,
+            ping: ScoreboardConnectionType::Bot,
+        }
+    }
+
+    fn stage(player_id: PlayerId) -> ScoreboardStageInfo {
+        let mut characters = PoolVec::new_without_pool();
+        characters.push(character(player_id));
+        ScoreboardStageInfo {
+            characters,
+            name: Recycle::new_without_pool(),
+            max_size: 0,
+            color: Default::default(),
+            score: ScoreboardScoreType::None,
+        }
+    }
+
+    #[test]
+    fn map_change_keeps_teams() {
+        let red: PlayerId = id("1").into();
+        let blue: PlayerId = id("2").into();
+        let spectator: PlayerId = id("3").into();
+
+        let mut red_stages = PoolFxLinkedHashMap::new_without_pool();
+        red_stages.insert(id("10").into(), stage(red));
+        let mut blue_stages = PoolFxLinkedHashMap::new_without_pool();
+        blue_stages.insert(id("11").into(), stage(blue));
+        let mut spectator_players = PoolVec::new_without_pool();
+        spectator_players.push(character(spectator));
+        let game = ScoreboardGameType::SidedPlay {
+            red_stages,
+            blue_stages,
+            spectator_players,
+            ignore_stage: id("12").into(),
+            red_side_name: Recycle::new_without_pool(),
+            blue_side_name: Recycle::new_without_pool(),
+        };
+
+        // three clients with a single player each,
+        // the blue player's mod has some state to hand over
+        let teams = player_teams(&game);
+        let mut handovers = PlayerHandovers::collect(
+            [(1u64, 0, red), (2, 0, blue), (3, 5, spectator)],
+            &teams,
+            |player_id| (*player_id == blue).then(|| vec![1, 2, 3]),
+        );
+
+        // the new game state gives the players new ids,
+        // the handover follows the connection and client id instead
+        let red = handovers.take(&1, 0).unwrap();
+        assert_eq!(red.team, Some(HandoverTeam::Side(MatchSide::Red)));
+        assert!(matches!(
+            red.client_command(),
+            Some(ClientCommand::JoinSide(MatchSide::Red))
+        ));
+        assert_eq!(red.mod_data, None);
+
+        let blue = handovers.take(&2, 0).unwrap();
+        assert!(matches!(
+            blue.client_command(),
+            Some(ClientCommand::JoinSide(MatchSide::Blue))
+        ));
+        assert_eq!(blue.mod_data, Some(vec![1, 2, 3]));
+
+        assert!(handovers.take(&3, 0).is_none());
+        let spectator = handovers.take(&3, 5).unwrap();
+        assert!(matches!(
+            spectator.client_command(),
+            Some(ClientCommand::JoinSpectator)
+        ));
+
+        // handovers are only applied once
+        assert!(handovers.take(&1, 0).is_none());
+        assert!(handovers.is_empty());
+    }
+
+    #[test]
+    fn disconnected_client() {
+        let player: PlayerId = id("1").into();
+        let other: PlayerId = id("2").into();
+        let mut handovers = PlayerHandovers::collect(
+            [(1u64, 0, player), (1, 1, other)],
+            &Default::default(),
+            |_| None,
+        );
+        // players without a known team join the default team
+        assert!(handovers.take(&1, 0).unwrap().client_command().is_none());
+        handovers.remove_client(&1);
+        assert!(handovers.is_empty());
+    }
+
+    #[test]
+    fn countdown() {
+        let mut change = PendingServerChange::new(
+            ServerChangeKind::Shutdown {
+                reason: "update".to_string(),
+            },
+            Duration::ZERO,
+            Duration::from_secs(12),
+        );
+
+        let mut announced = Vec::new();
+        let mut now = Duration::ZERO;
+        loop {
+            match change.update(now) {
+                ServerChangeUpdate::None => {}
+                ServerChangeUpdate::Announce(msg) => announced.push(msg),
+                ServerChangeUpdate::Execute(kind) => {
+                    assert!(matches!(kind, ServerChangeKind::Shutdown { .. }));
+                    break;
+                }
+            }
+            now += Duration::from_millis(50);
+        }
+        assert_eq!(now, Duration::from_secs(12));
+        assert_eq!(
+            announced,
+            vec![
+                "Server shuts down in 12 seconds: update",
+                "Server shuts down in 10 seconds: update",
+                "Server shuts down in 5 seconds: update",
+                "Server shuts down in 4 seconds: update",
+                "Server shuts down in 3 seconds: update",
+                "Server shuts down in 2 seconds: update",
+                "Server shuts down in 1 second: update",
+            ]
+        );
+    }
+}
@@ -1,6 +1,6 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt::Display, net::IpAddr, time::Duration};
 
-use base_io::io::Io;
+use base_io::{io::Io, runtime::IoRuntimeTask};
 use game_interface::{
     rcon_entries::AuthLevel,
     types::player_info::{AccountId, PlayerUniqueId},
@@ -9,12 +9,81 @@ use rand::Rng;
 
 use crate::client::ServerClient;
 
+/// Failed password logins of an ip before further logins are refused.
+const MAX_FAILED_AUTHS: u32 = 3;
+/// How long logins are refused after too many failed logins.
+const FAILED_AUTH_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RconAuthError {
+    WrongPassword,
+    /// Too many failed logins from this ip.
+    TooManyAttempts,
+}
+
+impl Display for RconAuthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RconAuthError::WrongPassword => write!(f, "Wrong password."),
+            RconAuthError::TooManyAttempts => {
+                write!(f, "Too many failed logins, try again later.")
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct FailedAuths {
+    count: u32,
+    last: Duration,
+}
+
+/// A single executed rcon command in the audit log.
+///
+/// Written as one tab separated line: `time ip auth_level name cmd`.
+/// Tabs, new lines and backslashes in the name and command are escaped,
+/// so every record stays a single line.
+#[derive(Debug)]
+pub struct RconAuditRecord {
+    pub time: chrono::DateTime<chrono::Utc>,
+    pub ip: IpAddr,
+    pub name: String,
+    pub auth_level: AuthLevel,
+    pub cmd: String,
+}
+
+impl Display for RconAuditRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn escape(s: &str) -> String {
+            s.replace('\\', "\\\\")
+                .replace('\t', "\\t")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r")
+        }
+        writeln!(
+            f,
+            "{}\t{}\t{:?}\t{}\t{}",
+            self.time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            self.ip,
+            self.auth_level,
+            escape(&self.name),
+            escape(&self.cmd)
+        )
+    }
+}
+
 /// Everything the server needs for rcon
 #[derive(Debug)]
 pub struct Rcon {
     pub auths: HashMap<AccountId, AuthLevel>,
     /// gives full access, mostly interesting for internal servers
     pub rcon_secret: [u8; 32],
+
+    failed_auths: HashMap<IpAddr, FailedAuths>,
+
+    /// Records that are not written to the audit log yet.
+    audit_queue: String,
+    audit_task: Option<IoRuntimeTask<()>>,
 }
 
 impl Rcon {
@@ -34,7 +103,15 @@ impl Rcon {
 
         let mut rcon_secret: [u8; 32] = Default::default();
         rand::rng().fill(&mut rcon_secret);
-        Rcon { auths, rcon_secret }
+        Rcon {
+            auths,
+            rcon_secret,
+
+            failed_auths: Default::default(),
+
+            audit_queue: Default::default(),
+            audit_task: None,
+        }
     }
 
     pub fn try_rcon_auth(
@@ -55,6 +132,87 @@ impl Rcon {
             false
         }
     }
+
+    /// Login with the admin or moderator password.
+    ///
+    /// Empty passwords are disabled.
+    /// After too many failed logins the ip has to wait before
+    /// the next login is checked.
+    pub fn try_password_auth(
+        &mut self,
+        ip: IpAddr,
+        password: &str,
+        admin_password: &str,
+        moderator_password: &str,
+        now: Duration,
+    ) -> Result<AuthLevel, RconAuthError> {
+        if let Some(failed) = self.failed_auths.get(&ip) {
+            if now.saturating_sub(failed.last) >= FAILED_AUTH_TIMEOUT {
+                self.failed_auths.remove(&ip);
+            } else if failed.count >= MAX_FAILED_AUTHS {
+                return Err(RconAuthError::TooManyAttempts);
+            }
+        }
+
+        let auth_level = if !admin_password.is_empty() && password == admin_password {
+            Some(AuthLevel::Admin)
+        } else if !moderator_password.is_empty() && password == moderator_password {
+            Some(AuthLevel::Moderator)
+        } else {
+            None
+        };
+
+        match auth_level {
+            Some(auth_level) => {
+                self.failed_auths.remove(&ip);
+                Ok(auth_level)
+            }
+            None => {
+                let failed = self.failed_auths.entry(ip).or_insert(FailedAuths {
+                    count: 0,
+                    last: now,
+                });
+                failed.count += 1;
+                failed.last = now;
+                Err(RconAuthError::WrongPassword)
+            }
+        }
+    }
+
+    /// Queues a record for the audit log.
+    pub fn audit(&mut self, record: RconAuditRecord) {
+        self.audit_queue.push_str(&record.to_string());
+    }
+
+    /// Appends the queued records to the audit log,
+    /// once the previous write finished.
+    ///
+    /// An empty path disables the audit log.
+    pub fn flush_audit_log(&mut self, io: &Io, path: &str) {
+        if path.is_empty() {
+            self.audit_queue.clear();
+            return;
+        }
+        if self.audit_queue.is_empty()
+            || self
+                .audit_task
+                .as_ref()
+                .is_some_and(|task| !task.is_finished())
+        {
+            return;
+        }
+        if let Some(Err(err)) = self.audit_task.take().map(|task| task.get()) {
+            log::error!(target: "rcon", "failed to write the audit log: {err}");
+        }
+
+        let fs = io.fs.clone();
+        let path = path.to_string();
+        let records = std::mem::take(&mut self.audit_queue);
+        self.audit_task = Some(io.rt.spawn(async move {
+            fs.append_file(path.as_ref(), records.into_bytes()).await?;
+            Ok(())
+        }));
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -71,4 +229,175 @@ pub enum ServerRconCommand {
     AddMiscVote,
     RemoveMiscVote,
     RecordDemo,
+    MuteId,
+    UnmuteId,
+    /// Ends the current vote with the given result.
+    VoteForce,
+    /// Lists the ips that are throttled for joining too often.
+    JoinThrottles,
+    /// Clears the join throttle of an ip or of all ips.
+    ClearJoinThrottle,
+    /// Lists the accounts that can join the reserved slots.
+    Whitelist,
+    /// Allows an account to join the reserved slots.
+    WhitelistAdd,
+    /// Removes an account from the reserved slots whitelist.
+    WhitelistRemove,
+    /// Shuts the server down after a countdown.
+    Shutdown,
+}
+
+impl ServerRconCommand {
+    /// The auth level required to execute this command.
+    ///
+    /// Moderators can only moderate players,
+    /// everything that changes the server is for admins.
+    pub fn auth_level(&self) -> AuthLevel {
+        match self {
+            ServerRconCommand::KickId
+            | ServerRconCommand::Status
+            | ServerRconCommand::MuteId
+            | ServerRconCommand::UnmuteId
+            | ServerRconCommand::VoteForce
+            | ServerRconCommand::JoinThrottles
+            | ServerRconCommand::Whitelist => AuthLevel::Moderator,
+            ServerRconCommand::BanId
+            | ServerRconCommand::ConfVariable
+            | ServerRconCommand::Exec
+            | ServerRconCommand::Load
+            | ServerRconCommand::AddMiscVote
+            | ServerRconCommand::RemoveMiscVote
+            | ServerRconCommand::RecordDemo
+            | ServerRconCommand::ClearJoinThrottle
+            | ServerRconCommand::WhitelistAdd
+            | ServerRconCommand::WhitelistRemove
+            | ServerRconCommand::Shutdown => AuthLevel::Admin,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{net::IpAddr, time::Duration};
+
+    use chrono::{TimeZone, Utc};
+    use game_interface::rcon_entries::AuthLevel;
+
+    use super::{
+        FAILED_AUTH_TIMEOUT, MAX_FAILED_AUTHS, RconAuditRecord, RconAuthError, ServerRconCommand,
+    };
+
+    fn rcon() -> super::Rcon {
+        super::Rcon {
+            auths: Default::default(),
+            rcon_secret: Default::default(),
+            failed_auths: Default::default(),
+            audit_queue: Default::default(),
+            audit_task: None,
+        }
+    }
+
+    #[test]
+    fn permissions() {
+        let allowed = |cmd: ServerRconCommand, auth: AuthLevel| auth >= cmd.auth_level();
+
+        assert!(allowed(ServerRconCommand::KickId, AuthLevel::Moderator));
+        assert!(allowed(ServerRconCommand::Status, AuthLevel::Moderator));
+        assert!(allowed(ServerRconCommand::MuteId, AuthLevel::Moderator));
+        assert!(allowed(ServerRconCommand::UnmuteId, AuthLevel::Moderator));
+        assert!(allowed(ServerRconCommand::VoteForce, AuthLevel::Moderator));
+        assert!(allowed(
+            ServerRconCommand::JoinThrottles,
+            AuthLevel::Moderator
+        ));
+        assert!(allowed(ServerRconCommand::KickId, AuthLevel::Admin));
+        assert!(!allowed(ServerRconCommand::KickId, AuthLevel::None));
+
+        for cmd in [
+            ServerRconCommand::BanId,
+            ServerRconCommand::ConfVariable,
+            ServerRconCommand::Exec,
+            ServerRconCommand::Load,
+            ServerRconCommand::AddMiscVote,
+            ServerRconCommand::RemoveMiscVote,
+            ServerRconCommand::RecordDemo,
+            ServerRconCommand::ClearJoinThrottle,
+            ServerRconCommand::Shutdown,
+        ] {
+            assert!(!allowed(cmd, AuthLevel::Moderator), "{cmd:?}");
+            assert!(allowed(cmd, AuthLevel::Admin), "{cmd:?}");
+        }
+    }
+
+    #[test]
+    fn password_auth() {
+        let mut rcon = rcon();
+        let ip: IpAddr = "127.0.0.1".parse().unwrap();
+        let now = Duration::from_secs(100);
+
+        assert_eq!(
+            rcon.try_password_auth(ip, "admin", "admin", "mod", now),
+            Ok(AuthLevel::Admin)
+        );
+        assert_eq!(
+            rcon.try_password_auth(ip, "mod", "admin", "mod", now),
+            Ok(AuthLevel::Moderator)
+        );
+        // empty passwords are disabled
+        assert_eq!(
+            rcon.try_password_auth(ip, "", "admin", "", now),
+            Err(RconAuthError::WrongPassword)
+        );
+        assert_eq!(
+            rcon.try_password_auth(ip, "", "", "", now),
+            Err(RconAuthError::WrongPassword)
+        );
+    }
+
+    #[test]
+    fn failed_auths_are_rate_limited() {
+        let mut rcon = rcon();
+        let ip: IpAddr = "127.0.0.1".parse().unwrap();
+        let other_ip: IpAddr = "127.0.0.2".parse().unwrap();
+        let mut now = Duration::from_secs(100);
+
+        for _ in 0..MAX_FAILED_AUTHS {
+            assert_eq!(
+                rcon.try_password_auth(ip, "guess", "admin", "", now),
+                Err(RconAuthError::WrongPassword)
+            );
+            now += Duration::from_secs(1);
+        }
+        // even the right password is refused now
+        assert_eq!(
+            rcon.try_password_auth(ip, "admin", "admin", "", now),
+            Err(RconAuthError::TooManyAttempts)
+        );
+        // other ips are not affected
+        assert_eq!(
+            rcon.try_password_auth(other_ip, "admin", "admin", "", now),
+            Ok(AuthLevel::Admin)
+        );
+
+        now += FAILED_AUTH_TIMEOUT;
+        assert_eq!(
+            rcon.try_password_auth(ip, "admin", "admin", "", now),
+            Ok(AuthLevel::Admin)
+        );
+    }
+
+    #[test]
+    fn audit_record_format() {
+        let record = RconAuditRecord {
+            time: Utc.with_ymd_and_hms(2024, 5, 17, 13, 37, 0).unwrap(),
+            ip: "10.0.0.1".parse().unwrap(),
+            name: "nameless\ttee".to_string(),
+            auth_level: AuthLevel::Moderator,
+            cmd: "kick_id 3\nexec \\evil".to_string(),
+        };
+        assert_eq!(
+            record.to_string(),
+            "2024-05-17T13:37:00Z\t10.0.0.1\tModerator\tnameless\\ttee\tkick_id 3\\nexec \\\\evil\n"
+        );
+    }
 }
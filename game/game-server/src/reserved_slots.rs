@@ -0,0 +1,240 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use base_io::{io::Io, runtime::IoRuntimeTask};
+use game_config::config::ConfigServer;
+use game_interface::types::player_info::AccountId;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReservedSlotsConfig {
+    /// Ingame slots that only privileged clients can join.
+    pub reserved: u32,
+    /// Joins that end up as spectator don't use an ingame slot.
+    pub spectators_exempt: bool,
+}
+
+impl ReservedSlotsConfig {
+    pub fn new(config: &ConfigServer) -> Self {
+        Self {
+            reserved: config.reserved_slots,
+            spectators_exempt: config.reserved_slots_spectators_exempt,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReservedSlotDecision {
+    Allow,
+    /// The player takes one of the reserved slots.
+    Reserved,
+    /// Only the reserved slots are left.
+    Full,
+}
+
+impl Display for ReservedSlotDecision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReservedSlotDecision::Allow => write!(f, "allowed"),
+            ReservedSlotDecision::Reserved => write!(f, "allowed (reserved slot)"),
+            ReservedSlotDecision::Full => write!(f, "Server full (reserved slots)"),
+        }
+    }
+}
+
+/// Decides if another player can join the game.
+///
+/// `ingame_players` are the players that are not spectators.
+/// Privileged clients are whitelisted or know the reserved slots password.
+pub fn reserved_slot_decision(
+    ingame_players: usize,
+    max_ingame_players: usize,
+    config: &ReservedSlotsConfig,
+    privileged: bool,
+) -> ReservedSlotDecision {
+    let normal_slots = max_ingame_players.saturating_sub(config.reserved as usize);
+    if config.reserved == 0 || ingame_players < normal_slots {
+        ReservedSlotDecision::Allow
+    } else if config.spectators_exempt && ingame_players >= max_ingame_players {
+        // the game is full anyway, the player only spectates
+        ReservedSlotDecision::Allow
+    } else if privileged {
+        ReservedSlotDecision::Reserved
+    } else {
+        ReservedSlotDecision::Full
+    }
+}
+
+/// The accounts that can always use the reserved slots.
+///
+/// Saved as json object of account ids & an optional note.
+#[derive(Debug, Default)]
+pub struct Whitelist {
+    accounts: BTreeMap<AccountId, String>,
+    save_task: Option<IoRuntimeTask<()>>,
+}
+
+impl Whitelist {
+    pub fn new(io: &Io, path: &str) -> Self {
+        if path.is_empty() {
+            return Self::default();
+        }
+        let fs = io.fs.clone();
+        let path = path.to_string();
+        let accounts = io
+            .rt
+            .spawn(async move {
+                let file = fs.read_file(path.as_ref()).await?;
+                Ok(serde_json::from_slice::<BTreeMap<AccountId, String>>(
+                    &file,
+                )?)
+            })
+            .get()
+            .unwrap_or_default();
+        Self {
+            accounts,
+            save_task: None,
+        }
+    }
+
+    pub fn contains(&self, account_id: &AccountId) -> bool {
+        self.accounts.contains_key(account_id)
+    }
+
+    /// Returns `false` if the account was already whitelisted,
+    /// the note is updated in that case.
+    pub fn add(&mut self, account_id: AccountId, note: String) -> bool {
+        self.accounts.insert(account_id, note).is_none()
+    }
+
+    pub fn remove(&mut self, account_id: &AccountId) -> bool {
+        self.accounts.remove(account_id).is_some()
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = (&AccountId, &String)> {
+        self.accounts.iter()
+    }
+
+    /// Writes the whitelist to the given path.
+    ///
+    /// An empty path keeps the whitelist in memory only.
+    pub fn save(&mut self, io: &Io, path: &str) {
+        if path.is_empty() {
+            return;
+        }
+        if let Some(Err(err)) = self
+            .save_task
+            .take()
+            .filter(|task| task.is_finished())
+            .map(|task| task.get())
+        {
+            log::error!(target: "server", "failed to write the whitelist: {err}");
+        }
+
+        let fs = io.fs.clone();
+        let path = path.to_string();
+        let file = serde_json::to_vec_pretty(&self.accounts);
+        self.save_task = Some(io.rt.spawn(async move {
+            fs.write_file(path.as_ref(), file?).await?;
+            Ok(())
+        }));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ReservedSlotDecision, ReservedSlotsConfig, reserved_slot_decision};
+
+    /// Joins the players in order and returns which ones could join.
+    fn join_all(
+        players: &[bool],
+        max_ingame_players: usize,
+        config: &ReservedSlotsConfig,
+    ) -> Vec<ReservedSlotDecision> {
+        let mut ingame = 0;
+        players
+            .iter()
+            .map(|privileged| {
+                let decision =
+                    reserved_slot_decision(ingame, max_ingame_players, config, *privileged);
+                if decision != ReservedSlotDecision::Full {
+                    ingame += 1;
+                }
+                decision
+            })
+            .collect()
+    }
+
+    #[test]
+    fn slot_accounting() {
+        use ReservedSlotDecision::*;
+        let config = ReservedSlotsConfig {
+            reserved: 2,
+            spectators_exempt: false,
+        };
+
+        // normal players fill the normal slots, after that only
+        // whitelisted players can join until the game is full.
+        assert_eq!(
+            join_all(
+                &[false, true, false, false, true, false, true, true],
+                5,
+                &config
+            ),
+            [
+                Allow, Allow, Allow, Full, Reserved, Full, Reserved, Reserved
+            ]
+        );
+
+        // without reserved slots every player can join
+        let no_reserved = ReservedSlotsConfig {
+            reserved: 0,
+            ..config
+        };
+        assert_eq!(
+            join_all(&[false, false, false], 2, &no_reserved),
+            [Allow, Allow, Allow]
+        );
+
+        // more reserved slots than ingame slots
+        assert_eq!(
+            join_all(
+                &[false, true],
+                1,
+                &ReservedSlotsConfig {
+                    reserved: 4,
+                    ..config
+                }
+            ),
+            [Full, Reserved]
+        );
+    }
+
+    #[test]
+    fn spectators_exempt() {
+        let config = ReservedSlotsConfig {
+            reserved: 1,
+            spectators_exempt: true,
+        };
+        // the last normal slot is reserved
+        assert_eq!(
+            reserved_slot_decision(3, 4, &config, false),
+            ReservedSlotDecision::Full
+        );
+        assert_eq!(
+            reserved_slot_decision(3, 4, &config, true),
+            ReservedSlotDecision::Reserved
+        );
+        // once the game is full, joins become spectators
+        assert_eq!(
+            reserved_slot_decision(4, 4, &config, false),
+            ReservedSlotDecision::Allow
+        );
+        let config = ReservedSlotsConfig {
+            spectators_exempt: false,
+            ..config
+        };
+        assert_eq!(
+            reserved_slot_decision(4, 4, &config, false),
+            ReservedSlotDecision::Full
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
     fmt::Debug,
-    net::{IpAddr, SocketAddr},
+    net::{IpAddr, Ipv4Addr, SocketAddr},
     num::NonZeroUsize,
     path::PathBuf,
     sync::{Arc, Weak, atomic::AtomicBool},
@@ -73,9 +73,16 @@ use crate::{
         ClientSnapshotForDiff, ClientSnapshotStorage, Clients, ServerClient, ServerClientPlayer,
         ServerNetworkClient, ServerNetworkQueuedClient, ServerPasswordClient,
     },
+    join_throttle::{JoinDecision, JoinThrottle, JoinThrottleConfig, max_players_per_account},
+    map_change::{
+        PendingServerChange, PlayerHandovers, ServerChangeKind, ServerChangeUpdate, player_teams,
+    },
     map_votes::{MapVotes, ServerMapVotes},
     network_plugins::{accounts_only::AccountsOnly, cert_ban::CertBans},
-    rcon::{Rcon, ServerRconCommand},
+    rcon::{Rcon, RconAuditRecord, ServerRconCommand},
+    reserved_slots::{
+        ReservedSlotDecision, ReservedSlotsConfig, Whitelist, reserved_slot_decision,
+    },
     server_game::{
         ClientAuth, RESERVED_DDNET_NAMES, RESERVED_VANILLA_NAMES, ServerExtraVoteInfo, ServerGame,
         ServerVote,
@@ -124,15 +131,16 @@ use game_interface::{
     vote_commands::{VoteCommand, VoteCommandResultEvent},
     votes::{
         MAX_CATEGORY_NAME_LEN, MapVote, MapVoteDetails, MapVoteKey, MiscVote, MiscVoteKey,
-        VoteIdentifierType, VoteState, VoteType, Voted,
+        VoteIdentifierType, VoteState, VoteType, Voted, validate_vote_reason,
     },
 };
 
 use game_network::{
     game_event_generator::{GameEventGenerator, GameEvents},
+    lan_discovery::{LAN_DISCOVERY_PORT, LanAnnounce, LanAnnouncer, UdpLanTransport},
     messages::{
-        ClientToServerMessage, ClientToServerPlayerMessage, MsgSvInputAck, MsgSvLoadVotes,
-        MsgSvResetVotes, MsgSvStartVoteResult, ServerToClientMessage,
+        ClientToServerMessage, ClientToServerPlayerMessage, MsgSvInputAck, MsgSvInputTiming,
+        MsgSvLoadVotes, MsgSvResetVotes, MsgSvStartVoteResult, ServerToClientMessage,
     },
 };
 
@@ -184,6 +192,8 @@ pub struct Server {
     // network
     network: QuinnNetworks,
     connection_bans: Arc<ConnectionBans>,
+    join_throttle: JoinThrottle,
+    reserved_slots_whitelist: Whitelist,
 
     is_open: Arc<AtomicBool>,
 
@@ -206,6 +216,7 @@ pub struct Server {
     last_register_time: Option<Duration>,
     register_task: Option<IoRuntimeTask<()>>,
     last_register_serial: u32,
+    lan_announcer: Option<LanAnnouncer<UdpLanTransport>>,
 
     last_network_stats_time: Duration,
 
@@ -217,6 +228,10 @@ pub struct Server {
     // rcon
     rcon: Rcon,
 
+    // map changes & shutdown
+    pending_change: Option<PendingServerChange>,
+    player_handovers: PlayerHandovers<NetworkConnectionId>,
+
     // server side demos
     demo_recorder: Option<DemoRecorder>,
 
@@ -394,6 +409,58 @@ impl Server {
                     cmd: ServerRconCommand::KickId,
                 },
             ),
+            (
+                "mute_id".try_into().unwrap(),
+                Command {
+                    rcon: RconEntry {
+                        args: vec![CommandArg {
+                            ty: CommandArgType::Number,
+                            user_ty: Some("PLAYER_ID".try_into().unwrap()),
+                        }],
+                        description: "Drops all chat messages of the user with \
+                            the given player id"
+                            .try_into()
+                            .unwrap(),
+                        usage: "mute_id <player_id>".try_into().unwrap(),
+                    },
+                    cmd: ServerRconCommand::MuteId,
+                },
+            ),
+            (
+                "unmute_id".try_into().unwrap(),
+                Command {
+                    rcon: RconEntry {
+                        args: vec![CommandArg {
+                            ty: CommandArgType::Number,
+                            user_ty: Some("PLAYER_ID".try_into().unwrap()),
+                        }],
+                        description: "Unmutes the user with the given player id"
+                            .try_into()
+                            .unwrap(),
+                        usage: "unmute_id <player_id>".try_into().unwrap(),
+                    },
+                    cmd: ServerRconCommand::UnmuteId,
+                },
+            ),
+            (
+                "vote_force".try_into().unwrap(),
+                Command {
+                    rcon: RconEntry {
+                        args: vec![CommandArg {
+                            ty: CommandArgType::TextFrom(vec![
+                                "yes".try_into().unwrap(),
+                                "no".try_into().unwrap(),
+                            ]),
+                            user_ty: None,
+                        }],
+                        description: "Ends the current vote with the given result"
+                            .try_into()
+                            .unwrap(),
+                        usage: "vote_force <yes|no>".try_into().unwrap(),
+                    },
+                    cmd: ServerRconCommand::VoteForce,
+                },
+            ),
             (
                 "status".try_into().unwrap(),
                 Command {
@@ -408,6 +475,108 @@ impl Server {
                     cmd: ServerRconCommand::Status,
                 },
             ),
+            (
+                "join_throttles".try_into().unwrap(),
+                Command {
+                    rcon: RconEntry {
+                        args: Default::default(),
+                        description: "List the ips that joined recently \
+                            or are throttled for joining too often"
+                            .try_into()
+                            .unwrap(),
+                        usage: "join_throttles".try_into().unwrap(),
+                    },
+                    cmd: ServerRconCommand::JoinThrottles,
+                },
+            ),
+            (
+                "clear_join_throttle".try_into().unwrap(),
+                Command {
+                    rcon: RconEntry {
+                        args: vec![CommandArg {
+                            ty: CommandArgType::Text,
+                            user_ty: Some("IP".try_into().unwrap()),
+                        }],
+                        description: "Clears the join throttle and the ban \
+                            of the given ip, or of all ips"
+                            .try_into()
+                            .unwrap(),
+                        usage: "clear_join_throttle <ip|all>".try_into().unwrap(),
+                    },
+                    cmd: ServerRconCommand::ClearJoinThrottle,
+                },
+            ),
+            (
+                "whitelist".try_into().unwrap(),
+                Command {
+                    rcon: RconEntry {
+                        args: Default::default(),
+                        description: "List the accounts that can join \
+                            the reserved slots"
+                            .try_into()
+                            .unwrap(),
+                        usage: "whitelist".try_into().unwrap(),
+                    },
+                    cmd: ServerRconCommand::Whitelist,
+                },
+            ),
+            (
+                "whitelist_add".try_into().unwrap(),
+                Command {
+                    rcon: RconEntry {
+                        args: vec![
+                            CommandArg {
+                                ty: CommandArgType::Number,
+                                user_ty: Some("ACCOUNT_ID".try_into().unwrap()),
+                            },
+                            CommandArg {
+                                ty: CommandArgType::Text,
+                                user_ty: Some("NOTE".try_into().unwrap()),
+                            },
+                        ],
+                        description: "Allows the given account to join \
+                            the reserved slots, the note is shown in the list"
+                            .try_into()
+                            .unwrap(),
+                        usage: "whitelist_add <account_id> <note>".try_into().unwrap(),
+                    },
+                    cmd: ServerRconCommand::WhitelistAdd,
+                },
+            ),
+            (
+                "whitelist_remove".try_into().unwrap(),
+                Command {
+                    rcon: RconEntry {
+                        args: vec![CommandArg {
+                            ty: CommandArgType::Number,
+                            user_ty: Some("ACCOUNT_ID".try_into().unwrap()),
+                        }],
+                        description: "Removes the given account from \
+                            the reserved slots whitelist"
+                            .try_into()
+                            .unwrap(),
+                        usage: "whitelist_remove <account_id>".try_into().unwrap(),
+                    },
+                    cmd: ServerRconCommand::WhitelistRemove,
+                },
+            ),
+            (
+                "shutdown".try_into().unwrap(),
+                Command {
+                    rcon: RconEntry {
+                        args: vec![CommandArg {
+                            ty: CommandArgType::Text,
+                            user_ty: Some("REASON".try_into().unwrap()),
+                        }],
+                        description: "Shuts the server down after a countdown, \
+                            the reason is shown to all players"
+                            .try_into()
+                            .unwrap(),
+                        usage: "shutdown <reason>".try_into().unwrap(),
+                    },
+                    cmd: ServerRconCommand::Shutdown,
+                },
+            ),
             (
                 "record_demo".try_into().unwrap(),
                 Command {
@@ -534,7 +703,7 @@ impl Server {
         let Syn::Text(file_path) = &cmd.args[0].0 else {
             panic!("Command parser returned a non requested command arg");
         };
-        *config = game_config_fs::fs::load_in(&io.clone().into(), file_path.as_ref())?;
+        *config = game_config_fs::fs::load_in(&io.clone().into(), file_path.as_ref())?.config;
         Ok(format!("New config file was loaded from {file_path}"))
     }
 
@@ -862,6 +1031,8 @@ impl Server {
         let config_mod = config_mod_task.get().ok();
 
         let rcon = Rcon::new(&io);
+        let reserved_slots_whitelist =
+            Whitelist::new(&io, &config_game.sv.reserved_slots_whitelist_path);
 
         // write local server info if required.
         {
@@ -900,6 +1071,8 @@ impl Server {
 
             network: network_server,
             connection_bans,
+            join_throttle: Default::default(),
+            reserved_slots_whitelist,
 
             is_open,
 
@@ -941,6 +1114,7 @@ impl Server {
             last_register_time: None,
             register_task: None,
             last_register_serial: 0,
+            lan_announcer: Self::lan_announcer(&config_game.sv),
 
             last_network_stats_time: time.now(),
 
@@ -954,6 +1128,10 @@ impl Server {
             // rcon
             rcon,
 
+            // map changes & shutdown
+            pending_change: None,
+            player_handovers: Default::default(),
+
             // server side demo recorder
             demo_recorder: None,
 
@@ -1009,6 +1187,49 @@ impl Server {
         client.players.len() < config_game.sv.max_players_per_client as usize
     }
 
+    fn can_account_join_another_player(&self, account_id: AccountId) -> bool {
+        max_players_per_account(&self.config_game.sv).is_none_or(|max_players| {
+            self.clients
+                .clients
+                .values()
+                .filter(|client| client.account_id == Some(account_id))
+                .map(|client| client.players.len())
+                .sum::<usize>()
+                < max_players
+        })
+    }
+
+    /// Players that are inside the game, spectators are not counted.
+    fn ingame_player_count(&self) -> usize {
+        self.game_server
+            .game
+            .collect_characters_info()
+            .values()
+            .filter(|c| c.player_info.is_some() && c.stage_id.is_some())
+            .count()
+    }
+
+    fn reserved_slot_decision(
+        &self,
+        con_id: &NetworkConnectionId,
+        account_id: Option<AccountId>,
+    ) -> ReservedSlotDecision {
+        let config = ReservedSlotsConfig::new(&self.config_game.sv);
+        // don't collect the characters if the feature is not used
+        if config.reserved == 0 {
+            return ReservedSlotDecision::Allow;
+        }
+        let privileged = account_id
+            .is_some_and(|account_id| self.reserved_slots_whitelist.contains(&account_id))
+            || self.clients.reserved_password_clients.contains(con_id);
+        reserved_slot_decision(
+            self.ingame_player_count(),
+            self.game_server.game.settings().max_ingame_players as usize,
+            &config,
+            privileged,
+        )
+    }
+
     fn can_client_player_id_join(client: &ServerClient, id: u64) -> bool {
         client.players.values().all(|p| p.id != id)
     }
@@ -1032,6 +1253,7 @@ impl Server {
             let server_info = MsgSvServerInfo {
                 map: self.game_server.map.name.as_str().try_into().unwrap(),
                 map_blake3_hash: self.game_server.map_blake3_hash,
+                map_size: self.game_server.map.map_file.len() as u64,
                 required_resources: self.game_server.required_resources.clone(),
                 game_mod: self.game_server.game_mod.clone(),
                 render_mod: self.game_server.render_mod.clone(),
@@ -1118,6 +1340,8 @@ impl Server {
     ) -> Option<PoolFxLinkedHashMap<PlayerId, ServerClientPlayer>> {
         // remove client from password player list (if in)
         self.clients.password_clients.remove(con_id);
+        self.clients.reserved_password_clients.remove(con_id);
+        self.player_handovers.remove_client(con_id);
 
         // find client in queued clients
         if self.clients.network_queued_clients.contains_key(con_id) {
@@ -1240,6 +1464,17 @@ impl Server {
     ) -> Option<PlayerId> {
         if let Some(client) = self.clients.clients.get_mut(con_id) {
             let player_id = self.game_server.player_join(con_id, &player_info);
+            // the player joined again after a map change
+            if let Some(handover) = self.player_handovers.take(con_id, player_info.id) {
+                if let Some(cmd) = handover.client_command() {
+                    self.game_server.game.client_command(&player_id, cmd);
+                }
+                if let Some(mod_data) = handover.mod_data {
+                    self.game_server
+                        .game
+                        .apply_player_handover(&player_id, mod_data);
+                }
+            }
             client.players.insert(
                 player_id,
                 ServerClientPlayer {
@@ -1310,6 +1545,7 @@ impl Server {
                     }
                 }
                 ClientToServerPlayerMessage::Chat(msg) => {
+                    let muted = player.muted;
                     fn prepare_msg(msg: &str) -> String {
                         msg.trim_matches(char::is_whitespace)
                             .replace(|c: char| c.is_control(), "")
@@ -1336,8 +1572,9 @@ impl Server {
                                             .unwrap(),
                                     }),
                                 );
-                            } else if let Some(own_char_info) =
-                                self.game_server.cached_character_infos.get(player_id)
+                            } else if !muted
+                                && let Some(own_char_info) =
+                                    self.game_server.cached_character_infos.get(player_id)
                             {
                                 let msg = NetChatMsg {
                                     sender: ChatPlayerInfo {
@@ -1526,7 +1763,15 @@ impl Server {
                                             .get(&key.voted_player_id),
                                     )
                                     .is_some_and(|(c1, c2)| c1.stage_id == c2.stage_id);
-                                if !is_same_player && enough_players_to_vote && is_same_stage {
+                                let has_valid_reason = validate_vote_reason(
+                                    &key.reason,
+                                    self.config_game.sv.vote_reason_required,
+                                )
+                                .is_ok();
+                                if !has_valid_reason {
+                                    Either::Right(MsgSvStartVoteResult::ReasonRequired)
+                                } else if !is_same_player && enough_players_to_vote && is_same_stage
+                                {
                                     if let Some((kick_con_id, voted_player, character_info)) =
                                         self.game_server.players.get(&key.voted_player_id).and_then(
                                             |p| {
@@ -1694,12 +1939,21 @@ impl Server {
                 ClientToServerPlayerMessage::RconExec { ident_text, args } => {
                     let auth_level = player.auth.level;
                     if matches!(auth_level, AuthLevel::Moderator | AuthLevel::Admin) {
-                        let res = self.handle_rcon_commands(
-                            Some(player_id),
+                        let line = format!("{} {}", ident_text.as_str(), args.as_str());
+                        self.rcon.audit(RconAuditRecord {
+                            time: chrono::Utc::now(),
+                            ip: player.ip,
+                            name: self
+                                .game_server
+                                .cached_character_infos
+                                .get(player_id)
+                                .map(|c| c.info.name.to_string())
+                                .unwrap_or_default(),
                             auth_level,
-                            &format!("{} {}", ident_text.as_str(), args.as_str()),
-                            false,
-                        );
+                            cmd: line.clone(),
+                        });
+                        let res =
+                            self.handle_rcon_commands(Some(player_id), auth_level, &line, false);
                         self.network.send_in_order_to(
                             &ServerToClientMessage::RconExecResult { results: res },
                             con_id,
@@ -1829,6 +2083,13 @@ impl Server {
             let Some(chain_cmd) = self.rcon_chain.by_ident(&cmd.ident) else {
                 return Err(anyhow!("Command {} not found", cmd.ident));
             };
+            if auth < chain_cmd.cmd.auth_level() {
+                return Err(anyhow!(
+                    "Command {} requires {:?} rights",
+                    cmd.ident,
+                    chain_cmd.cmd.auth_level()
+                ));
+            }
 
             fn ban_or_kick(
                 cmd: &parser::Command,
@@ -1909,6 +2170,41 @@ impl Server {
                     )?;
                     anyhow::Ok(res)
                 }
+                ServerRconCommand::MuteId | ServerRconCommand::UnmuteId => {
+                    let mute = matches!(chain_cmd.cmd, ServerRconCommand::MuteId);
+                    let mut res = String::new();
+                    ban_or_kick(&cmd, &self.game_server, &mut self.clients, |c, _| {
+                        c.muted = mute;
+                        let text: String = c
+                            .players
+                            .keys()
+                            .map(|id| id.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        res = format!(
+                            "{} the following id(s): {text}",
+                            if mute { "Muted" } else { "Unmuted" }
+                        );
+                    })?;
+                    anyhow::Ok(res)
+                }
+                ServerRconCommand::VoteForce => {
+                    let Syn::Text(result) = &cmd.args[0].0 else {
+                        panic!("Command parser returned a non requested command arg");
+                    };
+                    let Some(vote) = &mut self.game_server.cur_vote else {
+                        return Err(anyhow!("No vote is running"));
+                    };
+                    // the vote is evaluated in the next update
+                    let (yes_votes, no_votes) = if result == "yes" {
+                        (vote.state.allowed_to_vote_count, 0)
+                    } else {
+                        (0, vote.state.allowed_to_vote_count)
+                    };
+                    vote.state.yes_votes = yes_votes;
+                    vote.state.no_votes = no_votes;
+                    Ok(format!("Forced the vote to {result}"))
+                }
                 ServerRconCommand::Status => {
                     let mut res: Vec<String> = Default::default();
                     for client in self.clients.clients.values() {
@@ -1922,6 +2218,102 @@ impl Server {
                     }
                     Ok(res.join("\n"))
                 }
+                ServerRconCommand::JoinThrottles => {
+                    let entries = self.join_throttle.entries(self.time.now());
+                    if entries.is_empty() {
+                        Ok("No ip joined recently".to_string())
+                    } else {
+                        Ok(entries
+                            .into_iter()
+                            .map(|entry| entry.to_string())
+                            .collect::<Vec<_>>()
+                            .join("\n"))
+                    }
+                }
+                ServerRconCommand::ClearJoinThrottle => {
+                    let Syn::Text(ip) = &cmd.args[0].0 else {
+                        panic!("Command parser returned a non requested command arg");
+                    };
+                    let ip = if ip == "all" {
+                        None
+                    } else {
+                        Some(
+                            ip.parse::<IpAddr>()
+                                .map_err(|err| anyhow!("Invalid ip {ip}: {err}"))?,
+                        )
+                    };
+                    let ips = self.join_throttle.clear(ip);
+                    for ip in &ips {
+                        self.connection_bans.unban_ip(*ip);
+                    }
+                    let text: String = ips
+                        .into_iter()
+                        .map(|ip| ip.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    Ok(format!(
+                        "Cleared the join throttle of the following ip(s): {text}"
+                    ))
+                }
+                ServerRconCommand::Whitelist => {
+                    let entries: Vec<_> = self
+                        .reserved_slots_whitelist
+                        .entries()
+                        .map(|(account_id, note)| format!("account id: {account_id}, note: {note}"))
+                        .collect();
+                    if entries.is_empty() {
+                        Ok("The whitelist is empty".to_string())
+                    } else {
+                        Ok(entries.join("\n"))
+                    }
+                }
+                ServerRconCommand::WhitelistAdd => {
+                    let (Syn::Number(account_id), Syn::Text(note)) =
+                        (&cmd.args[0].0, &cmd.args[1].0)
+                    else {
+                        panic!("Command parser returned a non requested command arg");
+                    };
+                    let account_id: AccountId = account_id.parse()?;
+                    let added = self.reserved_slots_whitelist.add(account_id, note.clone());
+                    self.reserved_slots_whitelist
+                        .save(&self.io, &self.config_game.sv.reserved_slots_whitelist_path);
+                    Ok(if added {
+                        format!("Added account {account_id} to the whitelist")
+                    } else {
+                        format!("Updated the note of account {account_id}")
+                    })
+                }
+                ServerRconCommand::WhitelistRemove => {
+                    let Syn::Number(account_id) = &cmd.args[0].0 else {
+                        panic!("Command parser returned a non requested command arg");
+                    };
+                    let account_id: AccountId = account_id.parse()?;
+                    if self.reserved_slots_whitelist.remove(&account_id) {
+                        self.reserved_slots_whitelist
+                            .save(&self.io, &self.config_game.sv.reserved_slots_whitelist_path);
+                        Ok(format!("Removed account {account_id} from the whitelist"))
+                    } else {
+                        Err(anyhow!("Account {account_id} is not whitelisted"))
+                    }
+                }
+                ServerRconCommand::Shutdown => {
+                    let Syn::Text(reason) = &cmd.args[0].0 else {
+                        panic!("Command parser returned a non requested command arg");
+                    };
+                    let countdown =
+                        Duration::from_secs(self.config_game.sv.shutdown_countdown_secs as u64);
+                    self.pending_change = Some(PendingServerChange::new(
+                        ServerChangeKind::Shutdown {
+                            reason: reason.clone(),
+                        },
+                        self.time.now(),
+                        countdown,
+                    ));
+                    Ok(format!(
+                        "The server shuts down in {} seconds",
+                        countdown.as_secs()
+                    ))
+                }
                 ServerRconCommand::ConfVariable => {
                     handle_config_variable_cmd(&cmd, &mut self.config_game)
                         .map(|msg| format!("Updated value for {}: {}", cmd.cmd_text, msg))
@@ -2125,7 +2517,13 @@ impl Server {
                         return Err(anyhow!("Command {} not found", cmd.ident));
                     };
 
-                    if let ServerRconCommand::ConfVariable = chain_cmd.cmd {
+                    if auth < chain_cmd.cmd.auth_level() {
+                        Err(anyhow!(
+                            "Command {} requires {:?} rights",
+                            cmd.ident,
+                            chain_cmd.cmd.auth_level()
+                        ))
+                    } else if let ServerRconCommand::ConfVariable = chain_cmd.cmd {
                         handle_config_variable_cmd(cmd, &mut self.config_game)
                             .map(|msg| format!("Current value for {}: {}", cmd.cmd_text, msg))
                     } else {
@@ -2224,8 +2622,18 @@ impl Server {
                 // ignore
             }
             ClientToServerMessage::PasswordResponse(password) => {
-                // check password
-                if self.config_game.sv.password == password.as_str() {
+                // check password, the reserved slots password
+                // also works as server password.
+                let reserved_password = &self.config_game.sv.reserved_slots_password;
+                let is_reserved_password =
+                    !reserved_password.is_empty() && reserved_password == password.as_str();
+                if is_reserved_password
+                    || self.config_game.sv.password.is_empty()
+                    || self.config_game.sv.password == password.as_str()
+                {
+                    if is_reserved_password {
+                        self.clients.reserved_password_clients.insert(*con_id);
+                    }
                     // client can connect
                     if let Some(player) = self.clients.password_clients.remove(con_id) {
                         self.try_client_connect(
@@ -2249,11 +2657,34 @@ impl Server {
                         .as_ref()
                         .map(|c| c.public_keys())
                         .unwrap_or_default();
+                    let account_id = self.clients.network_clients.get(con_id).and_then(|client| {
+                        Self::user_id(&account_server_public_keys, &client.auth).account_id
+                    });
+                    if let Some(account_id) = account_id
+                        && !self.can_account_join_another_player(account_id)
+                    {
+                        self.network.kick(
+                            con_id,
+                            KickType::Kick(
+                                "Too many players of this account are connected.".into(),
+                            ),
+                        );
+                        return;
+                    }
+                    if self.clients.network_clients.contains_key(con_id) {
+                        let decision = self.reserved_slot_decision(con_id, account_id);
+                        if decision == ReservedSlotDecision::Full {
+                            self.network
+                                .kick(con_id, KickType::Kick(decision.to_string()));
+                            return;
+                        }
+                    }
                     let client = self.clients.try_client_ready(con_id);
                     let check_vote = client.is_some();
                     if let Some(client) = client {
                         let user_id = Self::user_id(&account_server_public_keys, &client.auth);
                         let unique_identifier = Self::user_id_to_player_unique_id(&user_id);
+                        client.account_id = user_id.account_id;
 
                         let send_rcon = self.rcon.try_rcon_auth(
                             client,
@@ -2262,6 +2693,7 @@ impl Server {
                         );
 
                         let initial_network_stats = client.network_stats;
+                        let ip = client.ip;
 
                         let mut joined_players: Vec<(u64, PlayerId)> = Default::default();
                         let mut non_joined_players: Vec<u64> = Default::default();
@@ -2276,6 +2708,7 @@ impl Server {
                                             id: player.id,
                                             unique_identifier,
                                             initial_network_stats,
+                                            ip: Some(ip),
                                         },
                                         false,
                                     )
@@ -2289,6 +2722,11 @@ impl Server {
                                         &self.config_game,
                                     )
                                     && Self::can_client_player_id_join(client, player.id)
+                                    && client.account_id.is_none_or(|account_id| {
+                                        self.can_account_join_another_player(account_id)
+                                    })
+                                    && self.reserved_slot_decision(con_id, client.account_id)
+                                        != ReservedSlotDecision::Full
                                 {
                                     let player_id = self
                                         .add_player_for_client(
@@ -2298,6 +2736,7 @@ impl Server {
                                                 id: player.id,
                                                 unique_identifier,
                                                 initial_network_stats,
+                                                ip: Some(ip),
                                             },
                                             true,
                                         )
@@ -2398,7 +2837,18 @@ impl Server {
                                 Self::can_client_join_another_player(client, &self.config_game);
                             let can_join_player_with_id =
                                 Self::can_client_player_id_join(client, client_id);
-                            if can_join_another_player && can_join_player_with_id {
+                            let can_account_join_another_player =
+                                client.account_id.is_none_or(|account_id| {
+                                    self.can_account_join_another_player(account_id)
+                                });
+                            let can_join_reserved_slots = self
+                                .reserved_slot_decision(con_id, client.account_id)
+                                != ReservedSlotDecision::Full;
+                            if can_join_another_player
+                                && can_join_player_with_id
+                                && can_account_join_another_player
+                                && can_join_reserved_slots
+                            {
                                 let player_info = PlayerClientInfo {
                                     info: player_info.player_info,
                                     id: client_id,
@@ -2413,6 +2863,7 @@ impl Server {
                                         ),
                                     ),
                                     initial_network_stats: client.network_stats,
+                                    ip: Some(client.ip),
                                 };
                                 Ok(self
                                     .add_player_for_client(con_id, player_info, true)
@@ -2421,6 +2872,10 @@ impl Server {
                                 Err(AddLocalPlayerResponseError::MaxPlayersPerClient)
                             } else if !can_join_player_with_id {
                                 Err(AddLocalPlayerResponseError::PlayerIdAlreadyUsedByClient)
+                            } else if !can_account_join_another_player {
+                                Err(AddLocalPlayerResponseError::MaxPlayersPerAccount)
+                            } else if !can_join_reserved_slots {
+                                Err(AddLocalPlayerResponseError::ReservedSlots)
                             } else {
                                 panic!(
                                     "Unhandled error variant during connecting another local player"
@@ -2460,6 +2915,7 @@ impl Server {
                         logic_overhead: *timestamp,
                     });
 
+                    let mut applied_monotonic_tick = None;
                     for (player_id, inp_chain) in inputs.iter() {
                         if let Some(player) = client.players.get_mut(player_id) {
                             let Some(def_inp) = (if let Some(diff_id) = inp_chain.diff_id {
@@ -2503,11 +2959,13 @@ impl Server {
                                         player.input_storage.insert(id, inp);
                                     }
 
-                                    self.game_server.player_inp(
+                                    let applied_tick = self.game_server.player_inp(
                                         player_id,
                                         inp.inp,
                                         inp.for_monotonic_tick,
                                     );
+                                    applied_monotonic_tick =
+                                        applied_monotonic_tick.max(applied_tick);
                                 }
 
                                 offset += def_len;
@@ -2515,6 +2973,12 @@ impl Server {
                             }
                         }
                     }
+                    if let Some(input_timings) = &mut client.input_timings {
+                        input_timings.push(MsgSvInputTiming {
+                            id,
+                            applied_monotonic_tick,
+                        });
+                    }
                     for MsgClSnapshotAck { snap_id } in snap_ack.iter() {
                         Self::client_snap_ack(client, *snap_id);
                     }
@@ -2566,6 +3030,31 @@ impl Server {
                                 }
                             }
                         }
+                        MsgClLoadVotes::Players => {
+                            let ip = client.ip;
+                            // players of admins, moderators and the own network can not be voted
+                            let immune = self
+                                .game_server
+                                .players
+                                .iter()
+                                .filter(|(_, p)| {
+                                    self.clients.clients.get(&p.network_id).is_some_and(|c| {
+                                        matches!(
+                                            c.auth.level,
+                                            AuthLevel::Admin | AuthLevel::Moderator
+                                        ) || c.ip == ip
+                                    })
+                                })
+                                .map(|(id, _)| *id)
+                                .collect();
+                            self.network.send_unordered_to(
+                                &ServerToClientMessage::LoadVotes(MsgSvLoadVotes::Players {
+                                    immune,
+                                    reason_required: self.config_game.sv.vote_reason_required,
+                                }),
+                                con_id,
+                            );
+                        }
                     }
                 }
             }
@@ -2686,6 +3175,54 @@ impl Server {
                     }
                 }
             }
+            ClientToServerMessage::RconAuth { password } => {
+                if let Some(client) = self.clients.clients.get_mut(con_id) {
+                    let res = self.rcon.try_password_auth(
+                        client.ip,
+                        password.as_str(),
+                        &self.config_game.sv.rcon_password,
+                        &self.config_game.sv.rcon_moderator_password,
+                        self.time.now(),
+                    );
+                    let result = match res {
+                        Ok(auth_level) => {
+                            // never downgrade an existing auth
+                            client.auth.level = client.auth.level.max(auth_level);
+                            let auth_level = client.auth.level;
+                            self.rcon.audit(RconAuditRecord {
+                                time: chrono::Utc::now(),
+                                ip: client.ip,
+                                name: client
+                                    .players
+                                    .keys()
+                                    .next()
+                                    .and_then(|id| self.game_server.cached_character_infos.get(id))
+                                    .map(|c| c.info.name.to_string())
+                                    .unwrap_or_default(),
+                                auth_level,
+                                cmd: "login".to_string(),
+                            });
+                            self.send_rcon_commands(con_id);
+                            Ok(NetworkString::new_lossy(format!(
+                                "Logged in as {auth_level:?}."
+                            )))
+                        }
+                        Err(err) => {
+                            log::info!(target: "rcon", "failed rcon login from {}: {err}", client.ip);
+                            Err(NetworkString::new_lossy(err.to_string()))
+                        }
+                    };
+                    self.network.send_in_order_to(
+                        &ServerToClientMessage::RconExecResult {
+                            results: vec![result],
+                        },
+                        con_id,
+                        NetworkInOrderChannel::Custom(
+                            7302, // reads as "rcon"
+                        ),
+                    );
+                }
+            }
             ClientToServerMessage::SpatialChat { opus_frames, id } => {
                 if let Some(spatial_chat) = &mut self.game_server.spatial_world
                     && let Some((player_id, auth)) = self
@@ -2713,6 +3250,11 @@ impl Server {
                     spatial_chat.on_client_drop(con_id);
                 }
             }
+            ClientToServerMessage::InputTimings { enabled } => {
+                if let Some(client) = self.clients.clients.get_mut(con_id) {
+                    client.input_timings = enabled.then(Default::default);
+                }
+            }
         }
     }
 
@@ -2836,21 +3378,36 @@ impl Server {
         }
     }
 
-    pub fn register(&mut self) {
-        let master_servers = [
-            //"https://master1.ddnet.org/ddnet/15/register",
-            "https://pg.ddnet.org:4444/ddnet/15/register",
-        ];
-
-        let http_v4 = self.io.http.clone();
-        let http_v6 = self.http_v6.clone();
-        let port_v4 = self.server_port_v4;
-        let port_v6 = self.server_port_v6;
+    fn lan_announcer(config: &ConfigServer) -> Option<LanAnnouncer<UdpLanTransport>> {
+        if !config.lan_announce {
+            return None;
+        }
+        let addrs: Vec<Ipv4Addr> = config
+            .lan_announce_addrs
+            .iter()
+            .filter_map(|addr| match addr.parse() {
+                Ok(addr) => Some(addr),
+                Err(err) => {
+                    log::warn!("invalid lan announce address {addr}: {err}");
+                    None
+                }
+            })
+            .collect();
+        match UdpLanTransport::announcer(&addrs, LAN_DISCOVERY_PORT) {
+            Ok(transport) => Some(LanAnnouncer::new(transport)),
+            Err(err) => {
+                log::warn!("failed to announce the server in the local network: {err}");
+                None
+            }
+        }
+    }
 
+    /// The info about the server, for the server browser.
+    fn browser_info(&self) -> ServerBrowserInfo {
         let characters = &self.game_server.cached_character_infos;
 
         let settings = self.game_server.game.settings();
-        let mut register_info = ServerBrowserInfo {
+        ServerBrowserInfo {
             name: self.config_game.sv.name.as_str().try_into().unwrap(),
             game_type: self.game_server.game.info.mod_name.clone(),
             version: self.game_server.game.info.version.clone(),
@@ -2882,7 +3439,21 @@ impl Server {
             passworded: !self.config_game.sv.password.is_empty(),
             cert_sha256_fingerprint: self.cert_sha256_fingerprint,
             requires_account: self.accounts_only,
-        };
+        }
+    }
+
+    pub fn register(&mut self) {
+        let master_servers = [
+            //"https://master1.ddnet.org/ddnet/15/register",
+            "https://pg.ddnet.org:4444/ddnet/15/register",
+        ];
+
+        let http_v4 = self.io.http.clone();
+        let http_v6 = self.http_v6.clone();
+        let port_v4 = self.server_port_v4;
+        let port_v6 = self.server_port_v6;
+
+        let mut register_info = self.browser_info();
 
         if let Some(LocalServerState::Ready(ready)) = self
             .shared_info
@@ -3030,6 +3601,31 @@ impl Server {
         );
     }
 
+    /// Clients that would only get a reserved slot are asked
+    /// for the reserved slots password, unless they are whitelisted.
+    fn requires_reserved_slots_password(
+        &self,
+        con_id: &NetworkConnectionId,
+        cert: &Arc<x509_cert::Certificate>,
+    ) -> bool {
+        if self.config_game.sv.reserved_slots_password.is_empty() {
+            return false;
+        }
+        let account_id = Self::user_id(
+            &self
+                .account_server_certs_downloader
+                .as_ref()
+                .map(|c| c.public_keys())
+                .unwrap_or_default(),
+            &ClientAuth {
+                cert: cert.clone(),
+                level: Default::default(),
+            },
+        )
+        .account_id;
+        self.reserved_slot_decision(con_id, account_id) == ReservedSlotDecision::Full
+    }
+
     fn send_password_info(
         &mut self,
         con_id: &NetworkConnectionId,
@@ -3068,6 +3664,21 @@ impl Server {
                 self.register();
                 self.last_register_time = Some(cur_time);
             }
+            if self
+                .lan_announcer
+                .as_ref()
+                .is_some_and(|announcer| announcer.should_announce(cur_time))
+            {
+                let announce = LanAnnounce {
+                    port: self.server_port_v4,
+                    info: self.browser_info(),
+                };
+                if let Some(announcer) = &mut self.lan_announcer
+                    && let Err(err) = announcer.announce(cur_time, announce)
+                {
+                    log::debug!("{err}");
+                }
+            }
 
             if self
                 .has_new_events_server
@@ -3083,7 +3694,24 @@ impl Server {
                                 initial_network_stats,
                                 addr,
                             } => {
-                                if self.config_game.sv.password.is_empty() {
+                                let now = self.time.now();
+                                self.join_throttle.cleanup(now);
+                                if let JoinDecision::Throttled { remaining } =
+                                    self.join_throttle.on_join_attempt(
+                                        addr.ip(),
+                                        now,
+                                        &JoinThrottleConfig::new(&self.config_game.sv),
+                                    )
+                                {
+                                    let ty = BanType::Custom("joining too often".to_string());
+                                    let until = Some(chrono::Utc::now() + remaining);
+                                    // other connections of this ip are not kicked
+                                    self.connection_bans.ban_ip(addr.ip(), ty.clone(), until);
+                                    self.network
+                                        .kick(&con_id, KickType::Ban(Banned { msg: ty, until }));
+                                } else if self.config_game.sv.password.is_empty()
+                                    && !self.requires_reserved_slots_password(&con_id, &cert)
+                                {
                                     self.send_server_info(
                                         &con_id,
                                         &timestamp,
@@ -3139,6 +3767,23 @@ impl Server {
                                 };
                                 if let Some(client) = self.clients.clients.get_mut(&con_id) {
                                     client.network_stats = network_stats;
+
+                                    let (packets_lost, packets_sent) = client.packets_lost_sent;
+                                    let packets_lost =
+                                        stats.packets_lost.saturating_sub(packets_lost);
+                                    let packets_sent =
+                                        stats.packets_sent.saturating_sub(packets_sent);
+                                    client.packets_lost_sent =
+                                        (stats.packets_lost, stats.packets_sent);
+                                    client.snapshot_rate.update(
+                                        &PlayerNetworkStats {
+                                            ping: stats.ping,
+                                            packet_loss: packets_lost as f32
+                                                / packets_sent.clamp(1, u64::MAX) as f32,
+                                        },
+                                        self.config_game.sv.ticks_per_snapshot,
+                                        self.config_game.sv.max_ticks_per_snapshot,
+                                    );
                                 } else if let Some(client) =
                                     self.clients.network_clients.get_mut(&con_id)
                                 {
@@ -3342,6 +3987,15 @@ impl Server {
                                 }
                             }
                         }
+                        TickEvent::Mute { player_id, muted } => {
+                            if let Some(client) =
+                                self.game_server.players.get(&player_id).and_then(|player| {
+                                    self.clients.clients.get_mut(&player.network_id)
+                                })
+                            {
+                                client.muted = muted;
+                            }
+                        }
                     }
                 }
 
@@ -3386,7 +4040,20 @@ impl Server {
                     let snap_id = client.snap_id;
                     client.snap_id += 1;
 
-                    if client.snap_id % self.config_game.sv.ticks_per_snapshot == 0 {
+                    // intermediate snapshots are dropped for clients with a bad connection
+                    let ticks_per_snapshot = client.snapshot_rate.ticks_per_snapshot(
+                        self.config_game.sv.ticks_per_snapshot,
+                        self.config_game.sv.max_ticks_per_snapshot,
+                    );
+                    if client.announced_ticks_per_snapshot != Some(ticks_per_snapshot) {
+                        client.announced_ticks_per_snapshot = Some(ticks_per_snapshot);
+                        self.network.send_unordered_to(
+                            &ServerToClientMessage::SnapshotRate { ticks_per_snapshot },
+                            con_id,
+                        );
+                    }
+
+                    if client.snap_id % ticks_per_snapshot == 0 {
                         let mut snap = self.game_server.game.snapshot_for(snap_client);
 
                         // this should be smaller than the number of snapshots saved on the client
@@ -3456,6 +4123,16 @@ impl Server {
                             con_id,
                         );
                         client.inputs_to_ack.clear();
+                        if let Some(input_timings) = client
+                            .input_timings
+                            .as_mut()
+                            .filter(|input_timings| !input_timings.is_empty())
+                        {
+                            self.network.send_unordered_to(
+                                &ServerToClientMessage::InputTimings(std::mem::take(input_timings)),
+                                con_id,
+                            );
+                        }
                     }
 
                     // events building
@@ -3482,6 +4159,27 @@ impl Server {
                 self.game_server.game.clear_events();
             }
 
+            // map change or shutdown after the countdown
+            if let Some(change) = &mut self.pending_change {
+                match change.update(cur_time) {
+                    ServerChangeUpdate::None => {}
+                    ServerChangeUpdate::Announce(msg) => {
+                        log::info!(target: "server", "{msg}");
+                        self.broadcast_in_order(
+                            ServerToClientMessage::Announcement(NetworkString::new_lossy(msg)),
+                            NetworkInOrderChannel::Global,
+                        );
+                    }
+                    ServerChangeUpdate::Execute(kind) => {
+                        self.pending_change = None;
+                        match kind {
+                            ServerChangeKind::Map(map) => self.change_map(&map),
+                            ServerChangeKind::Shutdown { reason } => self.shutdown(&reason),
+                        }
+                    }
+                }
+            }
+
             self.game_server.cached_character_infos =
                 self.game_server.game.collect_characters_info();
 
@@ -3557,6 +4255,9 @@ impl Server {
             }
             std::mem::swap(&mut self.db_requests_helper, &mut self.db_requests);
 
+            self.rcon
+                .flush_audit_log(&self.io, &self.config_game.sv.rcon_audit_log_path);
+
             // time and sleeps
             cur_time = self.time.now();
 
@@ -3646,6 +4347,7 @@ impl Server {
                 let server_info = MsgSvServerInfo {
                     map: self.game_server.map.name.as_str().try_into().unwrap(),
                     map_blake3_hash: self.game_server.map_blake3_hash,
+                    map_size: self.game_server.map.map_file.len() as u64,
                     required_resources: self.game_server.required_resources.clone(),
                     game_mod: self.game_server.game_mod.clone(),
                     render_mod: self.game_server.render_mod.clone(),
@@ -3679,12 +4381,69 @@ impl Server {
         }
     }
 
+    /// Changes the map after the configured countdown.
     fn load_map(&mut self, map: &NetworkReducedAsciiString<MAX_MAP_NAME_LEN>) {
+        if let Some(change) = &self.pending_change
+            && matches!(change.kind(), ServerChangeKind::Shutdown { .. })
+        {
+            log::info!(target: "server", "ignored map change, because the server shuts down");
+            return;
+        }
+        let countdown = Duration::from_secs(self.config_game.sv.map_change_countdown_secs as u64);
+        if countdown.is_zero() {
+            self.change_map(map);
+        } else {
+            self.pending_change = Some(PendingServerChange::new(
+                ServerChangeKind::Map(map.clone()),
+                self.time.now(),
+                countdown,
+            ));
+        }
+    }
+
+    /// Changes the map immediately. The teams of the players
+    /// are handed over to the next map.
+    fn change_map(&mut self, map: &NetworkReducedAsciiString<MAX_MAP_NAME_LEN>) {
+        let teams = player_teams(&self.game_server.game.collect_scoreboard_info().game);
+        let game = &self.game_server.game;
+        self.player_handovers = PlayerHandovers::collect(
+            self.clients.clients.iter().flat_map(|(con_id, client)| {
+                client
+                    .players
+                    .iter()
+                    .map(|(player_id, player)| (*con_id, player.id, *player_id))
+            }),
+            &teams,
+            |player_id| game.player_handover(player_id),
+        );
+
         self.config_game.sv.map = map.to_string();
         if let Err(err) = self.load_impl(None, map) {
+            self.player_handovers = Default::default();
             log::error!("Fatal error during map load: {err}");
         }
     }
+
+    /// Kicks all clients with the given reason and stops the server.
+    fn shutdown(&mut self, reason: &str) {
+        let msg = if reason.is_empty() {
+            "Server shut down".to_string()
+        } else {
+            format!("Server shut down: {reason}")
+        };
+        log::info!(target: "server", "{msg}");
+        self.clients
+            .password_clients
+            .keys()
+            .chain(self.clients.network_queued_clients.keys())
+            .chain(self.clients.network_clients.keys())
+            .chain(self.clients.clients.keys())
+            .for_each(|con_id| {
+                self.network.kick(con_id, KickType::Kick(msg.clone()));
+            });
+        self.is_open
+            .store(false, std::sync::atomic::Ordering::Relaxed);
+    }
 }
 
 pub fn load_config() -> (Io, ConfigEngine, ConfigGame) {
@@ -3703,8 +4462,10 @@ pub fn load_config() -> (Io, ConfigEngine, ConfigGame) {
 
     (
         io,
-        config_engine.unwrap_or_default(),
-        config_game.unwrap_or_default(),
+        config_engine
+            .map(|loaded| loaded.config)
+            .unwrap_or_default(),
+        config_game.map(|loaded| loaded.config).unwrap_or_default(),
     )
 }
 
@@ -645,45 +645,50 @@ impl ServerGame {
         self.game.player_drop(player_id, reason);
     }
 
+    /// Returns the tick the input is applied for,
+    /// `None` if the input was dropped.
     pub fn player_inp(
         &mut self,
         player_id: &PlayerId,
         player_input: PlayerInput,
         for_monotonic_tick: GameTickType,
-    ) {
-        if let Some(player) = self.players.get_mut(player_id) {
-            let cur_monotonic_tick = self.cur_monotonic_tick;
-
-            // `<=` is intentional here. If the input is really for a previous tick,
-            // then at least check whether the input is still newer than what already exists.
-            if for_monotonic_tick <= cur_monotonic_tick + 1 {
-                if let Some(diff) =
-                    player
-                        .inp
-                        .try_overwrite(&player_input.inp, player_input.version(), false)
-                {
-                    let mut inps = self.inps_pool.new();
-                    inps.insert(
-                        *player_id,
-                        CharacterInputInfo {
-                            inp: player.inp.inp,
-                            diff,
-                        },
-                    );
-                    self.game.set_player_inputs(inps);
-                }
-            } else if for_monotonic_tick > cur_monotonic_tick + 1
-                && (for_monotonic_tick - cur_monotonic_tick) < self.game.game_tick_speed().get() * 3
+    ) -> Option<GameTickType> {
+        let player = self.players.get_mut(player_id)?;
+        let cur_monotonic_tick = self.cur_monotonic_tick;
+
+        // `<=` is intentional here. If the input is really for a previous tick,
+        // then at least check whether the input is still newer than what already exists.
+        if for_monotonic_tick <= cur_monotonic_tick + 1 {
+            if let Some(diff) =
+                player
+                    .inp
+                    .try_overwrite(&player_input.inp, player_input.version(), false)
             {
-                let inp = self
-                    .queued_inputs
-                    .entry(for_monotonic_tick)
-                    .or_insert_with_keep_order(Default::default);
-                let entry = inp
-                    .entry(*player_id)
-                    .or_insert_with_keep_order(Default::default);
-                entry.try_overwrite(&player_input.inp, player_input.version(), false);
+                let mut inps = self.inps_pool.new();
+                inps.insert(
+                    *player_id,
+                    CharacterInputInfo {
+                        inp: player.inp.inp,
+                        diff,
+                    },
+                );
+                self.game.set_player_inputs(inps);
             }
+            Some(cur_monotonic_tick + 1)
+        } else if for_monotonic_tick > cur_monotonic_tick + 1
+            && (for_monotonic_tick - cur_monotonic_tick) < self.game.game_tick_speed().get() * 3
+        {
+            let inp = self
+                .queued_inputs
+                .entry(for_monotonic_tick)
+                .or_insert_with_keep_order(Default::default);
+            let entry = inp
+                .entry(*player_id)
+                .or_insert_with_keep_order(Default::default);
+            entry.try_overwrite(&player_input.inp, player_input.version(), false);
+            Some(for_monotonic_tick)
+        } else {
+            None
         }
     }
 
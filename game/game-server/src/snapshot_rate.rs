@@ -0,0 +1,279 @@
+use std::time::Duration;
+
+use game_interface::types::network_stats::PlayerNetworkStats;
+
+/// Weight of a new loss sample in the smoothed loss.
+const LOSS_SMOOTHING: f32 = 0.5;
+/// Smoothed loss at or above which the link counts as congested.
+const LOSS_CONGESTED: f32 = 0.05;
+/// Loss at or below which the link counts as clean.
+/// The smoothed loss must also be below [`LOSS_CONGESTED`].
+const LOSS_CLEAN: f32 = 0.01;
+/// Ping change between two samples at or above which the link counts as congested.
+const JITTER_CONGESTED: Duration = Duration::from_millis(40);
+/// Ping change between two samples at or below which the link counts as clean.
+const JITTER_CLEAN: Duration = Duration::from_millis(10);
+
+/// Consecutive congested samples before the rate is halved.
+const BACKOFF_SAMPLES: u32 = 3;
+/// Consecutive clean samples before the rate is doubled again.
+const RECOVER_SAMPLES: u32 = 2;
+/// Upper limit of clean samples a recovery can require.
+const MAX_RECOVER_SAMPLES: u32 = 16;
+/// A back off that happens this many samples after a recovery
+/// counts as oscillation and makes the next recovery more careful.
+const OSCILLATION_SAMPLES: u32 = 4;
+
+/// Adapts the snapshot rate of a single client to the quality of its link.
+///
+/// The rate is always `ticks_per_snapshot << backoff`, so every step halves
+/// or doubles it. Snapshots between two sends are simply dropped.
+///
+/// The loss is smoothed and a back off needs multiple congested samples,
+/// so single spikes do not change the rate.
+/// Loss and ping changes between the hysteresis thresholds hold the current
+/// rate. A link that gets congested right after a recovery requires
+/// more clean samples before the next recovery, until it was stable
+/// at the full rate for a while.
+#[derive(Debug)]
+pub struct SnapshotRateController {
+    backoff: u32,
+    loss: f32,
+    last_ping: Option<Duration>,
+
+    congested_samples: u32,
+    clean_samples: u32,
+    /// Clean samples in a row, not reset by recovering.
+    clean_streak: u32,
+    recover_samples: u32,
+    /// Samples since the rate was doubled the last time.
+    since_recover: Option<u32>,
+}
+
+impl Default for SnapshotRateController {
+    fn default() -> Self {
+        Self {
+            backoff: 0,
+            loss: 0.0,
+            last_ping: None,
+
+            congested_samples: 0,
+            clean_samples: 0,
+            clean_streak: 0,
+            recover_samples: RECOVER_SAMPLES,
+            since_recover: None,
+        }
+    }
+}
+
+impl SnapshotRateController {
+    fn max_backoff(ticks_per_snapshot: u64, max_ticks_per_snapshot: u64) -> u32 {
+        let ticks_per_snapshot = ticks_per_snapshot.max(1);
+        (max_ticks_per_snapshot / ticks_per_snapshot)
+            .checked_ilog2()
+            .unwrap_or_default()
+    }
+
+    /// Feeds the network stats of the last interval into the controller.
+    ///
+    /// `stats.packet_loss` must be the loss of this interval only,
+    /// not of the whole connection.
+    pub fn update(
+        &mut self,
+        stats: &PlayerNetworkStats,
+        ticks_per_snapshot: u64,
+        max_ticks_per_snapshot: u64,
+    ) {
+        let jitter = self
+            .last_ping
+            .map(|ping| ping.abs_diff(stats.ping))
+            .unwrap_or_default();
+        self.last_ping = Some(stats.ping);
+        self.loss += (stats.packet_loss - self.loss) * LOSS_SMOOTHING;
+        self.since_recover = self.since_recover.map(|samples| samples + 1);
+
+        let max_backoff = Self::max_backoff(ticks_per_snapshot, max_ticks_per_snapshot);
+        self.backoff = self.backoff.min(max_backoff);
+
+        if self.loss >= LOSS_CONGESTED || jitter >= JITTER_CONGESTED {
+            self.clean_samples = 0;
+            self.clean_streak = 0;
+            self.congested_samples += 1;
+            if self.congested_samples >= BACKOFF_SAMPLES && self.backoff < max_backoff {
+                self.congested_samples = 0;
+                self.backoff += 1;
+                if self
+                    .since_recover
+                    .take()
+                    .is_some_and(|samples| samples <= OSCILLATION_SAMPLES)
+                {
+                    self.recover_samples = (self.recover_samples * 2).min(MAX_RECOVER_SAMPLES);
+                }
+            }
+        } else if stats.packet_loss <= LOSS_CLEAN && jitter <= JITTER_CLEAN {
+            self.congested_samples = 0;
+            self.clean_samples += 1;
+            self.clean_streak += 1;
+            if self.backoff == 0 && self.clean_streak >= MAX_RECOVER_SAMPLES {
+                self.recover_samples = RECOVER_SAMPLES;
+            }
+            if self.clean_samples >= self.recover_samples && self.backoff > 0 {
+                self.clean_samples = 0;
+                self.backoff -= 1;
+                self.since_recover = Some(0);
+            }
+        } else {
+            self.congested_samples = 0;
+            self.clean_samples = 0;
+            self.clean_streak = 0;
+        }
+    }
+
+    /// The ticks between two snapshots for this client.
+    ///
+    /// `ticks_per_snapshot` is the full rate,
+    /// `max_ticks_per_snapshot` the lowest allowed rate.
+    pub fn ticks_per_snapshot(&self, ticks_per_snapshot: u64, max_ticks_per_snapshot: u64) -> u64 {
+        let ticks_per_snapshot = ticks_per_snapshot.max(1);
+        let backoff = self.backoff.min(Self::max_backoff(
+            ticks_per_snapshot,
+            max_ticks_per_snapshot,
+        ));
+        ticks_per_snapshot << backoff
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use game_interface::types::network_stats::PlayerNetworkStats;
+
+    use super::SnapshotRateController;
+
+    const FULL: u64 = 2;
+    const MIN: u64 = 16;
+
+    /// Drives the controller with `(loss, ping in ms)` samples
+    /// and returns the rate after every sample.
+    fn run(controller: &mut SnapshotRateController, trace: &[(f32, u64)]) -> Vec<u64> {
+        trace
+            .iter()
+            .map(|&(packet_loss, ping)| {
+                controller.update(
+                    &PlayerNetworkStats {
+                        ping: Duration::from_millis(ping),
+                        packet_loss,
+                    },
+                    FULL,
+                    MIN,
+                );
+                controller.ticks_per_snapshot(FULL, MIN)
+            })
+            .collect()
+    }
+
+    fn changes(rates: &[u64]) -> usize {
+        rates.windows(2).filter(|w| w[0] != w[1]).count()
+    }
+
+    #[test]
+    fn clean_link_keeps_full_rate() {
+        let mut controller = SnapshotRateController::default();
+        let rates = run(&mut controller, &[(0.0, 50); 30]);
+        assert!(rates.iter().all(|&rate| rate == FULL));
+    }
+
+    #[test]
+    fn loss_backs_off_to_min_rate() {
+        let mut controller = SnapshotRateController::default();
+        let rates = run(&mut controller, &[(0.2, 50); 10]);
+        assert_eq!(rates, [2, 2, 4, 4, 4, 8, 8, 8, 16, 16]);
+    }
+
+    #[test]
+    fn single_spikes_are_ignored() {
+        let mut controller = SnapshotRateController::default();
+        let mut trace = vec![(0.0, 50); 5];
+        trace.push((0.3, 50));
+        trace.extend([(0.0, 50); 5]);
+        trace.push((0.0, 200));
+        trace.extend([(0.0, 50); 5]);
+        let rates = run(&mut controller, &trace);
+        assert!(rates.iter().all(|&rate| rate == FULL));
+    }
+
+    #[test]
+    fn recovers_quickly() {
+        let mut controller = SnapshotRateController::default();
+        run(&mut controller, &[(0.3, 50); 10]);
+        assert_eq!(controller.ticks_per_snapshot(FULL, MIN), MIN);
+
+        let rates = run(&mut controller, &[(0.0, 50); 8]);
+        assert_eq!(rates, [16, 16, 16, 8, 8, 4, 4, 2]);
+    }
+
+    #[test]
+    fn moderate_loss_holds_rate() {
+        let mut controller = SnapshotRateController::default();
+        let mut trace = vec![(0.1, 50); 3];
+        // between the thresholds
+        trace.extend([(0.03, 50); 20]);
+        let rates = run(&mut controller, &trace);
+        assert!(rates[2..].iter().all(|&rate| rate == 4));
+    }
+
+    #[test]
+    fn ping_variance_backs_off() {
+        let mut controller = SnapshotRateController::default();
+        let trace: Vec<_> = (0..7)
+            .map(|i| (0.0, if i % 2 == 0 { 50 } else { 150 }))
+            .collect();
+        let rates = run(&mut controller, &trace);
+        assert_eq!(rates, [2, 2, 2, 4, 4, 4, 8]);
+    }
+
+    #[test]
+    fn alternating_loss_does_not_oscillate() {
+        let mut controller = SnapshotRateController::default();
+        let trace: Vec<_> = (0..40)
+            .map(|i| (if i % 2 == 0 { 0.2 } else { 0.0 }, 50))
+            .collect();
+        let rates = run(&mut controller, &trace);
+        assert!(rates.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(*rates.last().unwrap(), MIN);
+    }
+
+    #[test]
+    fn bandwidth_limited_link_settles() {
+        // a link that only loses packets at the full rate
+        let mut controller = SnapshotRateController::default();
+        let mut rates = Vec::new();
+        for _ in 0..60 {
+            let loss = if controller.ticks_per_snapshot(FULL, MIN) == FULL {
+                0.2
+            } else {
+                0.0
+            };
+            rates.extend(run(&mut controller, &[(loss, 50)]));
+        }
+        // every probe of the full rate comes later than the previous one
+        let probes: Vec<_> = (1..rates.len())
+            .filter(|&i| rates[i] == FULL && rates[i - 1] != FULL)
+            .collect();
+        assert!(probes.len() >= 3);
+        assert!(probes.windows(3).all(|w| w[2] - w[1] > w[1] - w[0]));
+        assert!(changes(&rates[30..]) <= 4);
+    }
+
+    #[test]
+    fn respects_config() {
+        let mut controller = SnapshotRateController::default();
+        run(&mut controller, &[(0.5, 50); 10]);
+        // rate adaption disabled
+        assert_eq!(controller.ticks_per_snapshot(FULL, FULL), FULL);
+        assert_eq!(controller.ticks_per_snapshot(FULL, 1), FULL);
+        // lowest rate is not a power of two of the full rate
+        assert_eq!(controller.ticks_per_snapshot(FULL, 12), 8);
+    }
+}
@@ -136,6 +136,12 @@ pub mod state_wasm {
         #[wasm_func_auto_call]
         fn network_stats(&mut self, stats: PoolFxLinkedHashMap<PlayerId, PlayerNetworkStats>) {}
 
+        #[wasm_func_auto_call]
+        fn player_handover(&self, player_id: &PlayerId) -> Option<Vec<u8>> {}
+
+        #[wasm_func_auto_call]
+        fn apply_player_handover(&mut self, player_id: &PlayerId, handover: Vec<u8>) {}
+
         #[wasm_func_auto_call]
         fn settings(&self) -> GameStateSettings {}
 
@@ -265,6 +265,18 @@ impl GameStateInterface for GameStateWasmManager {
         self.state.as_mut().network_stats(stats)
     }
 
+    #[instrument(level = "trace", skip_all)]
+    fn player_handover(&self, player_id: &PlayerId) -> Option<Vec<u8>> {
+        self.state.as_ref().player_handover(player_id)
+    }
+
+    #[instrument(level = "trace", skip_all)]
+    fn apply_player_handover(&mut self, player_id: &PlayerId, handover: Vec<u8>) {
+        self.state
+            .as_mut()
+            .apply_player_handover(player_id, handover)
+    }
+
     #[instrument(level = "trace", skip_all)]
     fn settings(&self) -> GameStateSettings {
         self.state.as_ref().settings()
@@ -0,0 +1,212 @@
+use std::{io::Write, time::Duration};
+
+use base::hash::Hash;
+use demo::utils::{decomp, deser};
+use game_interface::types::{
+    character_info::NetworkCharacterInfo, game::NonZeroGameTickType, weapons::WeaponType,
+};
+use math::math::{
+    mix,
+    vector::{dvec2, vec2},
+};
+use serde::{Deserialize, Serialize};
+
+/// Every ghost file starts with this magic, followed by the version.
+const GHOST_MAGIC: &[u8; 8] = b"ddnghost";
+const GHOST_VERSION: u32 = 1;
+
+/// The state of the ghost's character in a single tick.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GhostTick {
+    pub pos: vec2,
+    pub vel: vec2,
+    pub cursor: dvec2,
+    pub move_dir: i32,
+    pub weapon: WeaponType,
+    /// `None` if the hook is not used.
+    pub hook: Option<vec2>,
+}
+
+impl GhostTick {
+    /// Interpolates between this and the next tick.
+    ///
+    /// Discrete values like the weapon are taken from this tick.
+    pub fn lerp(&self, next: &Self, intra_tick: f64) -> Self {
+        Self {
+            pos: mix(&self.pos, &next.pos, intra_tick as f32),
+            vel: mix(&self.vel, &next.vel, intra_tick as f32),
+            cursor: mix(&self.cursor, &next.cursor, intra_tick),
+            move_dir: self.move_dir,
+            weapon: self.weapon,
+            hook: match (self.hook, next.hook) {
+                (Some(hook), Some(next_hook)) => Some(mix(&hook, &next_hook, intra_tick as f32)),
+                (hook, _) => hook,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GhostHeader {
+    /// The map on which the ghost was recorded.
+    pub map_hash: Hash,
+    pub ticks_per_second: NonZeroGameTickType,
+    /// Name & skin of the player.
+    pub character_info: NetworkCharacterInfo,
+    pub finish_time: Duration,
+}
+
+/// A finished race run, that can be replayed as ghost.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GhostFile {
+    pub header: GhostHeader,
+    /// One entry per race tick, starting at the race start.
+    pub ticks: Vec<GhostTick>,
+}
+
+impl GhostFile {
+    pub fn to_bytes(&self) -> anyhow::Result<Vec<u8>> {
+        let mut res: Vec<u8> = Default::default();
+        res.extend(GHOST_MAGIC);
+        res.extend(GHOST_VERSION.to_le_bytes());
+
+        let mut encoder = zstd::Encoder::new(&mut res, 0)?;
+        bincode::serde::encode_into_std_write(self, &mut encoder, bincode::config::standard())?;
+        encoder.finish()?.flush()?;
+
+        Ok(res)
+    }
+
+    pub fn from_bytes(data: &[u8]) -> anyhow::Result<Self> {
+        let data = data
+            .strip_prefix(GHOST_MAGIC)
+            .ok_or_else(|| anyhow::anyhow!("not a ghost file"))?;
+        let (version, data) = data
+            .split_first_chunk::<4>()
+            .ok_or_else(|| anyhow::anyhow!("ghost file is truncated"))?;
+        let version = u32::from_le_bytes(*version);
+        anyhow::ensure!(
+            version == GHOST_VERSION,
+            "ghost file version {version} is not supported"
+        );
+
+        let mut writer: Vec<u8> = Default::default();
+        let (ghost, _): (Self, usize) = deser(decomp(data, &mut writer)?)?;
+        anyhow::ensure!(!ghost.ticks.is_empty(), "ghost file has no ticks");
+        Ok(ghost)
+    }
+
+    /// The race time of the last tick of the ghost.
+    pub fn len(&self) -> Duration {
+        Duration::from_secs_f64(
+            self.ticks.len().saturating_sub(1) as f64 / self.header.ticks_per_second.get() as f64,
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ticks.is_empty()
+    }
+
+    /// The interpolated state of the ghost at the given race time.
+    ///
+    /// Returns `None` once the ghost finished its run.
+    pub fn at(&self, race_time: Duration) -> Option<GhostTick> {
+        let tick = race_time.as_secs_f64() * self.header.ticks_per_second.get() as f64;
+        let index = tick.floor() as usize;
+        let intra_tick = tick.fract();
+        match (self.ticks.get(index), self.ticks.get(index + 1)) {
+            (Some(cur), Some(next)) => Some(cur.lerp(next, intra_tick)),
+            (Some(cur), None) if intra_tick == 0.0 => Some(*cur),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use game_interface::types::{
+        character_info::NetworkCharacterInfo, game::NonZeroGameTickType, weapons::WeaponType,
+    };
+    use math::math::vector::{dvec2, vec2};
+
+    use super::{GhostFile, GhostHeader, GhostTick};
+
+    fn ghost(ticks: Vec<GhostTick>) -> GhostFile {
+        GhostFile {
+            header: GhostHeader {
+                map_hash: [7; 32],
+                ticks_per_second: NonZeroGameTickType::new(50).unwrap(),
+                character_info: NetworkCharacterInfo::explicit_default(),
+                finish_time: Duration::from_millis(20 * (ticks.len() as u64 - 1)),
+            },
+            ticks,
+        }
+    }
+
+    fn tick(x: f32, hook: Option<vec2>) -> GhostTick {
+        GhostTick {
+            pos: vec2::new(x, 2.0),
+            vel: vec2::new(x / 2.0, 0.0),
+            cursor: dvec2::new(x as f64, -10.0),
+            move_dir: 1,
+            weapon: WeaponType::Hammer,
+            hook,
+        }
+    }
+
+    #[test]
+    fn interpolation() {
+        let mut last = tick(4.0, None);
+        last.weapon = WeaponType::Gun;
+        let ghost = ghost(vec![
+            tick(0.0, Some(vec2::new(10.0, 0.0))),
+            tick(2.0, Some(vec2::new(20.0, 0.0))),
+            last,
+        ]);
+
+        assert_eq!(ghost.at(Duration::ZERO), Some(ghost.ticks[0]));
+        assert_eq!(ghost.at(Duration::from_millis(20)), Some(ghost.ticks[1]));
+
+        let half = ghost.at(Duration::from_millis(10)).unwrap();
+        assert_eq!(half.pos, vec2::new(1.0, 2.0));
+        assert_eq!(half.vel, vec2::new(0.5, 0.0));
+        assert_eq!(half.cursor, dvec2::new(1.0, -10.0));
+        assert_eq!(half.hook, Some(vec2::new(15.0, 0.0)));
+
+        // discrete values are not interpolated
+        let three_quarters = ghost.at(Duration::from_millis(35)).unwrap();
+        assert_eq!(three_quarters.pos, vec2::new(3.5, 2.0));
+        assert_eq!(three_quarters.weapon, WeaponType::Hammer);
+        assert_eq!(three_quarters.hook, Some(vec2::new(20.0, 0.0)));
+
+        // the ghost finished
+        assert_eq!(ghost.len(), Duration::from_millis(40));
+        assert_eq!(ghost.at(Duration::from_millis(40)), Some(last));
+        assert_eq!(ghost.at(Duration::from_millis(41)), None);
+        assert_eq!(ghost.at(Duration::from_secs(60)), None);
+    }
+
+    #[test]
+    fn file_round_trip() {
+        let ghost = ghost((0..500).map(|i| tick(i as f32, None)).collect());
+
+        let bytes = ghost.to_bytes().unwrap();
+        let read = GhostFile::from_bytes(&bytes).unwrap();
+        assert_eq!(read.header.map_hash, ghost.header.map_hash);
+        assert_eq!(read.header.ticks_per_second, ghost.header.ticks_per_second);
+        assert_eq!(read.header.finish_time, ghost.header.finish_time);
+        assert_eq!(
+            read.header.character_info.name,
+            ghost.header.character_info.name
+        );
+        assert_eq!(read.ticks, ghost.ticks);
+
+        assert!(GhostFile::from_bytes(&bytes[..4]).is_err());
+        assert!(GhostFile::from_bytes(&bytes[..bytes.len() / 2]).is_err());
+        let mut other_version = bytes.clone();
+        other_version[8] += 1;
+        assert!(GhostFile::from_bytes(&other_version).is_err());
+    }
+}
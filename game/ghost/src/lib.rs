@@ -1 +1,3 @@
+pub mod file;
 pub mod recorder;
+pub mod splits;
@@ -74,7 +74,10 @@ impl GhostRecorder {
         for world in events.worlds.values() {
             for event in world.events.values() {
                 match event {
-                    GameWorldEvent::Sound(_) | GameWorldEvent::Effect(_) => {
+                    GameWorldEvent::Sound(_)
+                    | GameWorldEvent::Effect(_)
+                    | GameWorldEvent::Hit(_)
+                    | GameWorldEvent::Custom(_) => {
                         // ignore
                     }
                     GameWorldEvent::Notification(event) => match event {
@@ -95,12 +98,15 @@ impl GhostRecorder {
                                     }
                                 }
                             }
-                            GameWorldAction::Custom(_) => {
+                            GameWorldAction::RaceCheckpoint { .. } | GameWorldAction::Custom(_) => {
                                 // ignore
                             }
                         },
                         GameWorldNotificationEvent::System(_)
-                        | GameWorldNotificationEvent::Motd { .. } => {
+                        | GameWorldNotificationEvent::Motd { .. }
+                        | GameWorldNotificationEvent::Broadcast { .. }
+                        | GameWorldNotificationEvent::Hint { .. }
+                        | GameWorldNotificationEvent::GameOver { .. } => {
                             // ignore
                         }
                     },
@@ -0,0 +1,139 @@
+use std::{collections::BTreeMap, io::Write, time::Duration};
+
+use base::hash::Hash;
+use demo::utils::{decomp, deser};
+use serde::{Deserialize, Serialize};
+
+/// Every splits file starts with this magic, followed by the version.
+const SPLITS_MAGIC: &[u8; 8] = b"ddnsplit";
+const SPLITS_VERSION: u32 = 1;
+
+/// The best race times at every checkpoint of a map.
+///
+/// Every checkpoint keeps its own best time, which is
+/// not necessarily from the same run as the best finish.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BestSplits {
+    /// The map on which the splits were driven.
+    pub map_hash: Hash,
+    /// Checkpoint index to the best time at that checkpoint.
+    pub splits: BTreeMap<u8, Duration>,
+    pub finish_time: Option<Duration>,
+}
+
+impl BestSplits {
+    pub fn new(map_hash: Hash) -> Self {
+        Self {
+            map_hash,
+            splits: Default::default(),
+            finish_time: None,
+        }
+    }
+
+    pub fn best(&self, index: u8) -> Option<Duration> {
+        self.splits.get(&index).copied()
+    }
+
+    /// Updates the best splits with a finished run.
+    ///
+    /// Splits after the finish time can't be part of the run and are ignored.
+    /// Returns `true` if anything improved.
+    pub fn finish_run(&mut self, splits: &[(u8, Duration)], finish_time: Duration) -> bool {
+        let mut improved = false;
+        for &(index, time) in splits.iter().filter(|(_, time)| *time <= finish_time) {
+            match self.splits.get_mut(&index) {
+                Some(best) if *best <= time => {}
+                Some(best) => {
+                    *best = time;
+                    improved = true;
+                }
+                None => {
+                    self.splits.insert(index, time);
+                    improved = true;
+                }
+            }
+        }
+        if self.finish_time.is_none_or(|best| finish_time < best) {
+            self.finish_time = Some(finish_time);
+            improved = true;
+        }
+        improved
+    }
+
+    pub fn to_bytes(&self) -> anyhow::Result<Vec<u8>> {
+        let mut res: Vec<u8> = Default::default();
+        res.extend(SPLITS_MAGIC);
+        res.extend(SPLITS_VERSION.to_le_bytes());
+
+        let mut encoder = zstd::Encoder::new(&mut res, 0)?;
+        bincode::serde::encode_into_std_write(self, &mut encoder, bincode::config::standard())?;
+        encoder.finish()?.flush()?;
+
+        Ok(res)
+    }
+
+    pub fn from_bytes(data: &[u8]) -> anyhow::Result<Self> {
+        let data = data
+            .strip_prefix(SPLITS_MAGIC)
+            .ok_or_else(|| anyhow::anyhow!("not a splits file"))?;
+        let (version, data) = data
+            .split_first_chunk::<4>()
+            .ok_or_else(|| anyhow::anyhow!("splits file is truncated"))?;
+        let version = u32::from_le_bytes(*version);
+        anyhow::ensure!(
+            version == SPLITS_VERSION,
+            "splits file version {version} is not supported"
+        );
+
+        let mut writer: Vec<u8> = Default::default();
+        let (splits, _): (Self, usize) = deser(decomp(data, &mut writer)?)?;
+        Ok(splits)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::BestSplits;
+
+    #[test]
+    fn update_rules() {
+        let s = Duration::from_secs;
+        let mut best = BestSplits::new([3; 32]);
+        assert!(best.finish_run(&[(1, s(10)), (2, s(20))], s(30)));
+        assert_eq!(best.best(1), Some(s(10)));
+        assert_eq!(best.best(2), Some(s(20)));
+        assert_eq!(best.finish_time, Some(s(30)));
+
+        // a slower run with a faster first split only improves that split
+        assert!(best.finish_run(&[(1, s(9)), (2, s(22))], s(35)));
+        assert_eq!(best.best(1), Some(s(9)));
+        assert_eq!(best.best(2), Some(s(20)));
+        assert_eq!(best.finish_time, Some(s(30)));
+
+        // nothing improved
+        assert!(!best.finish_run(&[(1, s(9)), (2, s(21))], s(30)));
+
+        // new checkpoints are added, splits after the finish are ignored
+        assert!(best.finish_run(&[(3, s(25)), (4, s(40))], s(29)));
+        assert_eq!(best.best(3), Some(s(25)));
+        assert_eq!(best.best(4), None);
+        assert_eq!(best.finish_time, Some(s(29)));
+    }
+
+    #[test]
+    fn file_round_trip() {
+        let mut best = BestSplits::new([3; 32]);
+        best.finish_run(&[(1, Duration::from_millis(1234))], Duration::from_secs(5));
+
+        let bytes = best.to_bytes().unwrap();
+        let read = BestSplits::from_bytes(&bytes).unwrap();
+        assert_eq!(read.map_hash, best.map_hash);
+        assert_eq!(read.splits, best.splits);
+        assert_eq!(read.finish_time, best.finish_time);
+
+        assert!(BestSplits::from_bytes(&bytes[..4]).is_err());
+        assert!(BestSplits::from_bytes(b"ddnghost").is_err());
+    }
+}
@@ -1488,6 +1488,7 @@ impl CDatafileWrapper {
                 version: Default::default(),
                 credits: Default::default(),
                 memo: Default::default(),
+                theme_ambiance: Default::default(),
             },
         };
 
@@ -1885,6 +1886,7 @@ impl CDatafileWrapper {
                 version: settings.map_version.clone(),
                 credits: settings.credits.clone(),
                 memo: Default::default(),
+                theme_ambiance: Default::default(),
             };
             map.config = Config {
                 commands: settings
@@ -2201,6 +2203,7 @@ impl CDatafileWrapper {
                                         } else {
                                             None
                                         },
+                                        blend_mode: Default::default(),
                                     },
                                     tiles: tiles
                                         .iter()
@@ -2232,6 +2235,7 @@ impl CDatafileWrapper {
                                         None
                                     },
                                     high_detail: (attr.layer.flags & LayerFlag::Detail as i32) != 0,
+                                    blend_mode: Default::default(),
                                 },
                                 quads: quads
                                     .iter()
@@ -593,6 +593,7 @@ impl Client {
                         id_generator.peek_next_id(),
                         None,
                         Default::default(),
+                        Default::default(),
                     ),
 
                     base: ClientBase {
@@ -863,6 +864,7 @@ impl Client {
                             // TODO: armor shields
                             _ => PickupType::PowerupArmor,
                         },
+                        dropped: None,
                     },
                     reusable_core: PoolPickupReusableCore::new_without_pool(),
                     game_el_id: pickup_id,
@@ -911,6 +913,8 @@ impl Client {
                             ty,
                             carrier: None,
                             drop_ticks: None,
+                            return_ticks: 0,
+                            regrab_cooldown: None,
                             non_linear_event: 0,
                         },
                         reusable_core: PoolFlagReusableCore::new_without_pool(),
@@ -961,6 +965,8 @@ impl Client {
                         MatchState::SuddenDeath {
                             round_ticks_passed: round_ticks_passed as u64,
                             by_cooldown: false,
+                            overtime: 1,
+                            overtime_ticks_left: Default::default(),
                         }
                     } else if is_paused {
                         MatchState::Paused {
@@ -1022,6 +1028,8 @@ impl Client {
                             .get(&id)
                             .map(|d| d.client_id)
                             .unwrap_or(player.server_client.id),
+                        is_bot: false,
+                        server_rank: None,
                     };
 
                     let ddnet_char = ddnet_characters.get(&id);
@@ -1276,6 +1284,7 @@ impl Client {
                                 &mut CorePipe {
                                     characters: &mut FakeCharacters,
                                     input: &inp,
+                                    hook_options: Default::default(),
                                 },
                                 collision,
                                 CoreEvents {
@@ -1288,6 +1297,7 @@ impl Client {
                                 &mut CorePipe {
                                     characters: &mut FakeCharacters,
                                     input: &inp,
+                                    hook_options: Default::default(),
                                 },
                                 collision,
                             );
@@ -1456,6 +1466,8 @@ impl Client {
                                 .get(&id)
                                 .map(|d| d.client_id)
                                 .unwrap_or(player.server_client.id),
+                            is_bot: false,
+                            server_rank: None,
                         };
                         snapshot.spectator_players.insert(
                             player_id,
@@ -1540,6 +1552,8 @@ impl Client {
                                     .get(&id)
                                     .map(|d| d.client_id)
                                     .unwrap_or(player.server_client.id),
+                                is_bot: false,
+                                server_rank: None,
                             };
                             let ty = SnapshotCharacterPlayerTy::Player(PlayerNetworkStats {
                                 ping: Duration::from_millis(
@@ -1852,6 +1866,7 @@ impl Client {
                         events::GameWorldEvent::Sound(events::GameWorldSoundEvent {
                             pos: Some(vec2::new(common.x as f32, common.y as f32) / 32.0),
                             owner_id: Some(player_id),
+                            count: 1,
                             ev: match sound_id {
                                 enums::Sound::GunFire => {
                                     events::GameWorldEntitySoundEvent::Character(
@@ -3162,6 +3177,23 @@ impl Client {
                     }),
                 );
             }
+            (_, SystemOrGame::Game(Game::SvBroadcast(broadcast))) => {
+                let events = base
+                    .events
+                    .worlds
+                    .entry(base.stage_0_id)
+                    .or_insert_with_keep_order(|| events::GameWorldEvents {
+                        events: mt_datatypes::PoolFxLinkedHashMap::new_without_pool(),
+                    });
+                events.events.insert(
+                    base.event_id_generator.next_id(),
+                    events::GameWorldEvent::Notification(GameWorldNotificationEvent::Broadcast {
+                        msg: MtPoolNetworkString::from_without_pool(NetworkString::new_lossy(
+                            String::from_utf8_lossy(broadcast.message),
+                        )),
+                    }),
+                );
+            }
             (_, SystemOrGame::Game(Game::SvEmoticon(emoticon))) => {
                 base.emoticons
                     .insert(emoticon.client_id, (time.now(), emoticon.emoticon));
@@ -4285,6 +4317,10 @@ impl Client {
                                             .collect(),
                                     }
                                 }
+                                MsgClLoadVotes::Players => MsgSvLoadVotes::Players {
+                                    immune: Default::default(),
+                                    reason_required: false,
+                                },
                             };
 
                             self.server_network.send_in_order_to(
@@ -4297,6 +4333,8 @@ impl Client {
                         ClientToServerMessage::AccountRequestInfo => {}
                         ClientToServerMessage::SpatialChat { .. } => {}
                         ClientToServerMessage::SpatialChatDeactivated => {}
+                        ClientToServerMessage::RconAuth { .. } => {}
+                        ClientToServerMessage::InputTimings { .. } => {}
                     },
                 }
             }
@@ -4499,6 +4537,7 @@ impl Client {
                             let server_info = MsgSvServerInfo {
                                 map: map_name.try_into().unwrap(),
                                 map_blake3_hash: map_hash,
+                                map_size: map.len() as u64,
                                 required_resources: Default::default(),
                                 game_mod: GameModification::Ddnet,
                                 render_mod: RenderModification::Native,
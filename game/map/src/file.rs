@@ -2,6 +2,82 @@ use std::{collections::HashMap, path::PathBuf};
 
 use assets_base::tar::{TarEntries, tar_entry_to_file, tar_file_entries, tar_reader};
 
+/// A single entry of the map file.
+///
+/// The sections are listed in the order they are read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MapSection {
+    Header,
+    Resources,
+    PhysicsGroup,
+    BackgroundGroups,
+    ForegroundGroups,
+    BlendModes,
+    Animations,
+    Config,
+    Meta,
+}
+
+impl MapSection {
+    /// The path of the section inside the map file.
+    pub fn path(&self) -> &'static str {
+        match self {
+            MapSection::Header => "header.txt",
+            MapSection::Resources => "resource_index.json.zst",
+            MapSection::PhysicsGroup => "groups/physics.twmap_bincode.zst",
+            MapSection::BackgroundGroups => "groups/background.twmap_bincode.zst",
+            MapSection::ForegroundGroups => "groups/foreground.twmap_bincode.zst",
+            MapSection::BlendModes => "groups/blend_modes.json.zst",
+            MapSection::Animations => "animations.twmap_bincode.zst",
+            MapSection::Config => "config.json.zst",
+            MapSection::Meta => "meta.json.zst",
+        }
+    }
+
+    /// A human readable name of the section.
+    pub fn name(&self) -> &'static str {
+        match self {
+            MapSection::Header => "header",
+            MapSection::Resources => "resource index",
+            MapSection::PhysicsGroup => "physics group",
+            MapSection::BackgroundGroups => "background groups",
+            MapSection::ForegroundGroups => "foreground groups",
+            MapSection::BlendModes => "blend modes",
+            MapSection::Animations => "animations",
+            MapSection::Config => "config",
+            MapSection::Meta => "meta data",
+        }
+    }
+}
+
+impl std::fmt::Display for MapSection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Reading a section of the map failed, either because it is missing
+/// or because it is corrupt.
+#[derive(Debug)]
+pub struct MapSectionError {
+    pub section: MapSection,
+    pub err: anyhow::Error,
+}
+
+impl std::fmt::Display for MapSectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "map section {} ({}) is invalid: {}",
+            self.section,
+            self.section.path(),
+            self.err
+        )
+    }
+}
+
+impl std::error::Error for MapSectionError {}
+
 /// The map file reader wraps around file in memory.
 pub struct MapFileReader {
     pub(crate) entries: TarEntries,
@@ -20,4 +96,32 @@ impl MapFileReader {
             .map(|(path, entry)| anyhow::Ok((path.clone(), tar_entry_to_file(entry)?.to_vec())))
             .collect::<anyhow::Result<_>>()
     }
+
+    /// Reads an optional section, all errors are wrapped in a [`MapSectionError`].
+    pub(crate) fn read_optional_section<T>(
+        &self,
+        section: MapSection,
+        read: impl FnOnce(&[u8]) -> anyhow::Result<T>,
+    ) -> anyhow::Result<Option<T>> {
+        self.entries
+            .get(std::path::Path::new(section.path()))
+            .map(|entry| tar_entry_to_file(entry).and_then(read))
+            .transpose()
+            .map_err(|err| MapSectionError { section, err }.into())
+    }
+
+    /// Reads a section, all errors are wrapped in a [`MapSectionError`].
+    pub(crate) fn read_section<T>(
+        &self,
+        section: MapSection,
+        read: impl FnOnce(&[u8]) -> anyhow::Result<T>,
+    ) -> anyhow::Result<T> {
+        self.read_optional_section(section, read)?.ok_or_else(|| {
+            MapSectionError {
+                section,
+                err: anyhow::anyhow!("was not found in map file"),
+            }
+            .into()
+        })
+    }
 }
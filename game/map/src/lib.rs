@@ -20,8 +20,10 @@ mod test {
     use base_io::io::IoFileSys;
     use flate2::Compression;
 
+    use assets_base::tar::{new_tar, tar_add_file};
+
     use crate::{
-        file::MapFileReader,
+        file::{MapFileReader, MapSection, MapSectionError},
         map::{Map, groups::MapGroup},
     };
 
@@ -211,4 +213,106 @@ mod test {
         //compression_tests_for_map("ctf1");
         compression_tests_for_map("arctic");
     }
+
+    /// Writes ctf1 again, with one section replaced by `corrupt`.
+    fn ctf1_with_section(
+        tp: &rayon::ThreadPool,
+        section: MapSection,
+        corrupt: impl FnOnce(&mut Vec<u8>),
+    ) -> MapFileReader {
+        let file = include_bytes!("../../../data/map/maps/ctf1.twmap.tar");
+        let map = Map::read(&MapFileReader::new(file.to_vec()).unwrap(), tp).unwrap();
+        let mut files = MapFileReader::new(map.write(tp).unwrap())
+            .unwrap()
+            .read_all()
+            .unwrap();
+        corrupt(files.get_mut(std::path::Path::new(section.path())).unwrap());
+
+        let mut builder = new_tar();
+        for section in [
+            MapSection::Header,
+            MapSection::Resources,
+            MapSection::PhysicsGroup,
+            MapSection::BackgroundGroups,
+            MapSection::ForegroundGroups,
+            MapSection::Animations,
+            MapSection::Config,
+            MapSection::Meta,
+        ] {
+            tar_add_file(
+                &mut builder,
+                section.path(),
+                &files[std::path::Path::new(section.path())],
+            );
+        }
+        MapFileReader::new(builder.into_inner().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn corrupt_section() {
+        let tp = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+
+        let reader = ctf1_with_section(&tp, MapSection::BackgroundGroups, |file| {
+            let mid = file.len() / 2;
+            file[mid] = !file[mid];
+        });
+        let progress = std::sync::Mutex::new(Vec::new());
+        let err = Map::read_with_progress(&reader, &tp, &|section| {
+            progress.lock().unwrap().push(section)
+        })
+        .unwrap_err();
+        let err = err.downcast_ref::<MapSectionError>().unwrap();
+        assert_eq!(err.section, MapSection::BackgroundGroups);
+        assert!(err.to_string().contains("background groups"));
+        // stops before reading the sections after the groups
+        let progress = progress.into_inner().unwrap();
+        assert!(!progress.contains(&MapSection::Animations));
+        assert_eq!(progress[..2], [MapSection::Header, MapSection::Resources]);
+
+        // the physics parse does not need the design groups
+        assert!(Map::read_physics_group_and_config(&reader).is_ok());
+
+        let reader = ctf1_with_section(&tp, MapSection::PhysicsGroup, |file| {
+            file.truncate(file.len() - 8);
+        });
+        let err = Map::read_physics_group_and_config(&reader).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<MapSectionError>().unwrap().section,
+            MapSection::PhysicsGroup
+        );
+    }
+
+    #[test]
+    fn section_progress() {
+        let tp = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+        let reader = ctf1_with_section(&tp, MapSection::Meta, |_| {});
+        let progress = std::sync::Mutex::new(Vec::new());
+        Map::read_with_progress(&reader, &tp, &|section| {
+            progress.lock().unwrap().push(section)
+        })
+        .unwrap();
+        let mut progress = progress.into_inner().unwrap();
+        // the groups are read in parallel
+        progress[2..5].sort_by_key(|section| *section as usize);
+        assert_eq!(
+            progress,
+            [
+                MapSection::Header,
+                MapSection::Resources,
+                MapSection::PhysicsGroup,
+                MapSection::BackgroundGroups,
+                MapSection::ForegroundGroups,
+                MapSection::BlendModes,
+                MapSection::Animations,
+                MapSection::Config,
+                MapSection::Meta,
+            ]
+        );
+    }
 }
@@ -5,11 +5,11 @@ pub mod groups;
 pub mod metadata;
 pub mod resources;
 
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 
 use anyhow::anyhow;
 use assets_base::{
-    tar::{new_tar, tar_add_file, tar_entry_to_file},
+    tar::{new_tar, tar_add_file},
     verify::{json::verify_json, ogg_vorbis::verify_ogg_vorbis, txt::verify_txt},
 };
 use base::{
@@ -23,7 +23,7 @@ use image_utils::png::is_png_image_valid;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    file::MapFileReader,
+    file::{MapFileReader, MapSection},
     header::Header,
     map::groups::{MapGroup, MapGroupPhysics},
     utils::{deserialize_twmap_bincode, serialize_twmap_bincode, verify_twmap_bincode},
@@ -208,15 +208,10 @@ impl Map {
 
     /// Read the map resources.
     pub fn read_resources(reader: &MapFileReader) -> anyhow::Result<Resources> {
-        let file = tar_entry_to_file(
-            reader
-                .entries
-                .get(Path::new("resource_index.json.zst"))
-                .ok_or_else(|| anyhow!("resource index was not found in map file"))?,
-        )?;
-        let resources_file = Self::decompress_resources(file)?;
-        let resources = Self::deserialize_resources(&resources_file)?;
-        Ok(resources)
+        reader.read_section(MapSection::Resources, |file| {
+            let resources_file = Self::decompress_resources(file)?;
+            Self::deserialize_resources(&resources_file)
+        })
     }
 
     /// All maps that the client knows MUST be of type "twmap", even if the version changes etc.
@@ -226,24 +221,23 @@ impl Map {
 
     /// All maps that the client knows MUST be of type "twmap", even if the version changes etc.
     pub fn read_twmap_header(reader: &MapFileReader) -> anyhow::Result<Header> {
-        let file = tar_entry_to_file(
-            reader
-                .entries
-                .get(Path::new("header.txt"))
-                .ok_or_else(|| anyhow!("header was not found in map file"))?,
-        )?;
-        let header = Self::deserialize_header(file)?;
-        Ok(header)
+        reader.read_section(MapSection::Header, Self::deserialize_header)
     }
 
-    /// Read the map resources (and validate the file header).
-    pub fn read_resources_and_header(reader: &MapFileReader) -> anyhow::Result<Resources> {
+    /// Reads the header and checks the type and version.
+    fn read_and_validate_twmap_header(reader: &MapFileReader) -> anyhow::Result<()> {
         let header = Self::read_twmap_header(reader)?;
         anyhow::ensure!(
             Self::validate_twmap_header_type(&header),
             "header validation failed."
         );
         anyhow::ensure!(header.version == Header::VERSION, "file version mismatch.");
+        Ok(())
+    }
+
+    /// Read the map resources (and validate the file header).
+    pub fn read_resources_and_header(reader: &MapFileReader) -> anyhow::Result<Resources> {
+        Self::read_and_validate_twmap_header(reader)?;
 
         let resources = Self::read_resources(reader)?;
         Ok(resources)
@@ -251,68 +245,49 @@ impl Map {
 
     /// Read the map animations.
     pub fn read_animations(reader: &MapFileReader) -> anyhow::Result<Animations> {
-        let file = tar_entry_to_file(
-            reader
-                .entries
-                .get(Path::new("animations.twmap_bincode.zst"))
-                .ok_or_else(|| anyhow!("animations were not found in map file"))?,
-        )?;
-        let animations_file = Self::decompress_animations(file)?;
-        let animations = Self::deserialize_animations(&animations_file)?;
-        Ok(animations)
+        reader.read_section(MapSection::Animations, |file| {
+            let animations_file = Self::decompress_animations(file)?;
+            Self::deserialize_animations(&animations_file)
+        })
     }
 
     /// Read the map config.
     pub fn read_config(reader: &MapFileReader) -> anyhow::Result<Config> {
-        let file = tar_entry_to_file(
-            reader
-                .entries
-                .get(Path::new("config.json.zst"))
-                .ok_or_else(|| anyhow!("config was not found in map file"))?,
-        )?;
-        let config_file = Self::decompress_config(file)?;
-        let config = Self::deserialize_config(&config_file)?;
-        Ok(config)
+        reader.read_section(MapSection::Config, |file| {
+            let config_file = Self::decompress_config(file)?;
+            Self::deserialize_config(&config_file)
+        })
     }
 
     /// Read the map meta data.
     pub fn read_meta(reader: &MapFileReader) -> anyhow::Result<Metadata> {
-        let file = tar_entry_to_file(
-            reader
-                .entries
-                .get(Path::new("meta.json.zst"))
-                .ok_or_else(|| anyhow!("meta was not found in map file"))?,
-        )?;
-        let meta_file = Self::decompress_resources(file)?;
-        let meta_data = Self::deserialize_meta(&meta_file)?;
-        Ok(meta_data)
+        reader.read_section(MapSection::Meta, |file| {
+            let meta_file = Self::decompress_meta(file)?;
+            Self::deserialize_meta(&meta_file)
+        })
     }
 
     /// Read a map file
     pub fn read(reader: &MapFileReader, tp: &rayon::ThreadPool) -> anyhow::Result<Self> {
-        let header = Self::read_twmap_header(reader)?;
-        anyhow::ensure!(
-            Self::validate_twmap_header_type(&header),
-            "header validation failed."
-        );
-        anyhow::ensure!(header.version == Header::VERSION, "file version mismatch.");
-
-        let resources = Self::read_resources(reader)?;
+        Self::read_with_progress(reader, tp, &|_| {})
+    }
 
-        let groups = MapGroups::read(reader, tp)?;
-        let animations = Self::read_animations(reader)?;
-        let config = Self::read_config(reader)?;
-        let meta = Self::read_meta(reader)?;
+    /// Read a map file section by section.
+    ///
+    /// `progress` is called before a section is read, the first
+    /// corrupt section stops the reading with a [`MapSectionError`].
+    pub fn read_with_progress(
+        reader: &MapFileReader,
+        tp: &rayon::ThreadPool,
+        progress: &(dyn Fn(MapSection) + Sync),
+    ) -> anyhow::Result<Self> {
+        progress(MapSection::Header);
+        Self::read_and_validate_twmap_header(reader)?;
 
-        Self::validate_resource_and_anim_indices(&resources, &animations, &groups)?;
+        progress(MapSection::Resources);
+        let resources = Self::read_resources(reader)?;
 
-        Ok(Self {
-            resources,
-            groups,
-            animations,
-            config,
-            meta,
-        })
+        Self::read_with_resources(resources, reader, tp, progress)
     }
 
     /// Read only the physics group and the config (skips all other stuff).
@@ -321,12 +296,7 @@ impl Map {
     pub fn read_physics_group_and_config(
         reader: &MapFileReader,
     ) -> anyhow::Result<(MapGroupPhysics, Config)> {
-        let header = Self::read_twmap_header(reader)?;
-        anyhow::ensure!(
-            Self::validate_twmap_header_type(&header),
-            "header validation failed."
-        );
-        anyhow::ensure!(header.version == Header::VERSION, "file version mismatch.");
+        Self::read_and_validate_twmap_header(reader)?;
 
         let groups = MapGroups::read_physics_group(reader)?;
 
@@ -336,16 +306,20 @@ impl Map {
     }
 
     /// Read a map file, whos resources were already loaded (the file header was read/checked too).
-    /// See [`Map::read_resources_and_header`]
+    /// See [`Map::read_resources_and_header`] & [`Map::read_with_progress`]
     pub fn read_with_resources(
         resources: Resources,
         reader: &MapFileReader,
         tp: &rayon::ThreadPool,
+        progress: &(dyn Fn(MapSection) + Sync),
     ) -> anyhow::Result<Self> {
-        let groups = MapGroups::read(reader, tp)?;
+        let groups = MapGroups::read(reader, tp, progress)?;
 
+        progress(MapSection::Animations);
         let animations = Self::read_animations(reader)?;
+        progress(MapSection::Config);
         let config = Self::read_config(reader)?;
+        progress(MapSection::Meta);
         let meta = Self::read_meta(reader)?;
 
         Self::validate_resource_and_anim_indices(&resources, &animations, &groups)?;
@@ -460,17 +434,20 @@ impl Map {
 
         let mut builder = new_tar();
 
-        tar_add_file(&mut builder, "header.txt", &header?);
-        tar_add_file(&mut builder, "resource_index.json.zst", &resources?);
+        tar_add_file(&mut builder, MapSection::Header.path(), &header?);
+        tar_add_file(&mut builder, MapSection::Resources.path(), &resources?);
 
-        let (physics, bg, fg) = groups?;
-        tar_add_file(&mut builder, "groups/physics.twmap_bincode.zst", &physics);
-        tar_add_file(&mut builder, "groups/background.twmap_bincode.zst", &bg);
-        tar_add_file(&mut builder, "groups/foreground.twmap_bincode.zst", &fg);
+        let (physics, bg, fg, blend_modes) = groups?;
+        tar_add_file(&mut builder, MapSection::PhysicsGroup.path(), &physics);
+        tar_add_file(&mut builder, MapSection::BackgroundGroups.path(), &bg);
+        tar_add_file(&mut builder, MapSection::ForegroundGroups.path(), &fg);
+        if let Some(blend_modes) = blend_modes {
+            tar_add_file(&mut builder, MapSection::BlendModes.path(), &blend_modes);
+        }
 
-        tar_add_file(&mut builder, "animations.twmap_bincode.zst", &animations?);
-        tar_add_file(&mut builder, "config.json.zst", &config?);
-        tar_add_file(&mut builder, "meta.json.zst", &meta?);
+        tar_add_file(&mut builder, MapSection::Animations.path(), &animations?);
+        tar_add_file(&mut builder, MapSection::Config.path(), &config?);
+        tar_add_file(&mut builder, MapSection::Meta.path(), &meta?);
 
         Ok(builder.into_inner()?)
     }
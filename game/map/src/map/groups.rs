@@ -1,21 +1,23 @@
+mod file_layout;
 pub mod layers;
 
-use std::path::Path;
-
 use anyhow::anyhow;
-use assets_base::tar::tar_entry_to_file;
 use base::join_all;
 use hiarc::Hiarc;
 use math::math::vector::{ffixed, fvec2, ufvec2};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    file::MapFileReader,
+    file::{MapFileReader, MapSection, MapSectionError},
     types::NonZeroU16MinusOne,
     utils::{deserialize_twmap_bincode, serialize_twmap_bincode},
 };
 
-use self::layers::{design::MapLayer, physics::MapLayerPhysics, tiles::TileBase};
+use self::layers::{
+    design::{MapLayer, MapLayerBlendMode},
+    physics::{MapLayerPhysics, MapLayerPhysicsMeta},
+    tiles::TileBase,
+};
 
 #[derive(Debug, Hiarc, Clone, Default, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MapGroupAttrClipping {
@@ -195,6 +197,96 @@ impl MapGroupPhysics {
                 )
             })
     }
+
+    /// The metadata of the arbitrary layer.
+    ///
+    /// `None` if there is no arbitrary layer or it contains something else.
+    pub fn get_meta(&self) -> Option<MapLayerPhysicsMeta> {
+        self.layers.iter().find_map(|layer| {
+            if let MapLayerPhysics::Arbitrary(buf) = layer {
+                MapLayerPhysicsMeta::from_arbitrary(buf).ok()
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// The blend modes of all design layers, indexed by group & layer.
+///
+/// Stored in an optional entry of the map file,
+/// that only exists if any layer is not blended normally.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MapGroupsBlendModes {
+    pub background: Vec<Vec<MapLayerBlendMode>>,
+    pub foreground: Vec<Vec<MapLayerBlendMode>>,
+}
+
+impl MapGroupsBlendModes {
+    fn layer_blend_mode(layer: &MapLayer) -> MapLayerBlendMode {
+        match layer {
+            MapLayer::Tile(layer) => layer.attr.blend_mode,
+            MapLayer::Quad(layer) => layer.attr.blend_mode,
+            MapLayer::Abritrary(_) | MapLayer::Sound(_) => MapLayerBlendMode::Normal,
+        }
+    }
+
+    fn collect(groups: &[MapGroup]) -> Vec<Vec<MapLayerBlendMode>> {
+        groups
+            .iter()
+            .map(|group| group.layers.iter().map(Self::layer_blend_mode).collect())
+            .collect()
+    }
+
+    /// Returns `None` if all layers are blended normally.
+    pub fn new(groups: &MapGroups) -> Option<Self> {
+        let groups_are_normal = |groups: &[MapGroup]| {
+            groups.iter().all(|group| {
+                group
+                    .layers
+                    .iter()
+                    .all(|layer| Self::layer_blend_mode(layer) == MapLayerBlendMode::Normal)
+            })
+        };
+        (!groups_are_normal(&groups.background) || !groups_are_normal(&groups.foreground)).then(
+            || Self {
+                background: Self::collect(&groups.background),
+                foreground: Self::collect(&groups.foreground),
+            },
+        )
+    }
+
+    fn apply_to(modes: &[Vec<MapLayerBlendMode>], groups: &mut [MapGroup]) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            modes.len() == groups.len(),
+            "blend modes did not match the group count"
+        );
+        for (modes, group) in modes.iter().zip(groups.iter_mut()) {
+            anyhow::ensure!(
+                modes.len() == group.layers.len(),
+                "blend modes did not match the layer count"
+            );
+            for (&mode, layer) in modes.iter().zip(group.layers.iter_mut()) {
+                match layer {
+                    MapLayer::Tile(layer) => layer.attr.blend_mode = mode,
+                    MapLayer::Quad(layer) => layer.attr.blend_mode = mode,
+                    MapLayer::Abritrary(_) | MapLayer::Sound(_) => {
+                        anyhow::ensure!(
+                            mode == MapLayerBlendMode::Normal,
+                            "only tile & quad layers support blend modes"
+                        );
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets the blend modes of the layers in the groups.
+    pub fn apply(&self, groups: &mut MapGroups) -> anyhow::Result<()> {
+        Self::apply_to(&self.background, &mut groups.background)?;
+        Self::apply_to(&self.foreground, &mut groups.foreground)
+    }
 }
 
 #[derive(Debug, Hiarc, Clone)]
@@ -230,14 +322,14 @@ impl MapGroups {
     }
 
     fn deserialize_design_groups(uncompressed_file: &[u8]) -> anyhow::Result<Vec<MapGroup>> {
-        deserialize_twmap_bincode::<Vec<MapGroup>>(uncompressed_file)
+        deserialize_twmap_bincode::<file_layout::FileGroups>(uncompressed_file)?.try_into()
     }
 
     fn serialize_design_groups<W: std::io::Write>(
         grps: &Vec<MapGroup>,
         writer: &mut W,
     ) -> anyhow::Result<usize> {
-        serialize_twmap_bincode(grps, writer)
+        serialize_twmap_bincode(&file_layout::to_file_layout(grps), writer)
     }
 
     /// Deserializes the foreground groups
@@ -280,71 +372,93 @@ impl MapGroups {
         crate::utils::compress(uncompressed_file)
     }
 
-    /// Read the map's game group.
-    pub(crate) fn read(reader: &MapFileReader, tp: &rayon::ThreadPool) -> anyhow::Result<Self> {
-        let physics_file = tar_entry_to_file(
-            reader
-                .entries
-                .get(Path::new("groups/physics.twmap_bincode.zst"))
-                .ok_or_else(|| anyhow!("physics group was not found in map file"))?,
-        )?;
-        let bg_file = tar_entry_to_file(
-            reader
-                .entries
-                .get(Path::new("groups/background.twmap_bincode.zst"))
-                .ok_or_else(|| anyhow!("background groups was not found in map file"))?,
-        )?;
-        let fg_file = tar_entry_to_file(
-            reader
-                .entries
-                .get(Path::new("groups/foreground.twmap_bincode.zst"))
-                .ok_or_else(|| anyhow!("foreground groups was not found in map file"))?,
-        )?;
+    /// Deserializes the blend modes of the design layers
+    pub fn deserialize_blend_modes(
+        uncompressed_file: &[u8],
+    ) -> anyhow::Result<MapGroupsBlendModes> {
+        Ok(serde_json::from_slice(uncompressed_file)?)
+    }
+
+    /// Serializes the blend modes of the design layers
+    pub fn serialize_blend_modes<W: std::io::Write>(
+        blend_modes: &MapGroupsBlendModes,
+        writer: &mut W,
+    ) -> anyhow::Result<()> {
+        Ok(serde_json::to_writer(writer, blend_modes)?)
+    }
+
+    /// Read the map's groups.
+    ///
+    /// The sections are decoded in parallel, but errors are reported
+    /// in section order.
+    pub(crate) fn read(
+        reader: &MapFileReader,
+        tp: &rayon::ThreadPool,
+        progress: &(dyn Fn(MapSection) + Sync),
+    ) -> anyhow::Result<Self> {
         let (physics_group, background_groups, foreground_groups) = tp.install(|| {
             join_all!(
                 || {
-                    let physics_group_file = Self::decompress_physics_group(physics_file)?;
-                    let physics_group = Self::deserialize_physics_group(&physics_group_file)?;
-                    anyhow::Ok(physics_group)
+                    progress(MapSection::PhysicsGroup);
+                    Self::read_physics_group(reader)
                 },
                 || {
-                    let bg_group_file = Self::decompress_design_group(bg_file)?;
-
-                    let background_groups = Self::deserialize_background_groups(&bg_group_file)?;
-                    anyhow::Ok(background_groups)
+                    progress(MapSection::BackgroundGroups);
+                    reader.read_section(MapSection::BackgroundGroups, |file| {
+                        let bg_group_file = Self::decompress_design_group(file)?;
+                        Self::deserialize_background_groups(&bg_group_file)
+                    })
                 },
                 || {
-                    let fg_group_file = Self::decompress_design_group(fg_file)?;
-
-                    let foreground_groups = Self::deserialize_foreground_groups(&fg_group_file)?;
-                    anyhow::Ok(foreground_groups)
+                    progress(MapSection::ForegroundGroups);
+                    reader.read_section(MapSection::ForegroundGroups, |file| {
+                        let fg_group_file = Self::decompress_design_group(file)?;
+                        Self::deserialize_foreground_groups(&fg_group_file)
+                    })
                 }
             )
         });
 
-        Ok(Self {
+        let mut groups = Self {
             physics: physics_group?,
             background: background_groups?,
             foreground: foreground_groups?,
-        })
+        };
+
+        // maps without special blend modes don't have this file
+        progress(MapSection::BlendModes);
+        if let Some(blend_modes) = reader.read_optional_section(MapSection::BlendModes, |file| {
+            let file = crate::utils::decompress(file)?;
+            Self::deserialize_blend_modes(&file)
+        })? {
+            blend_modes
+                .apply(&mut groups)
+                .map_err(|err| MapSectionError {
+                    section: MapSection::BlendModes,
+                    err,
+                })?;
+        }
+
+        Ok(groups)
     }
 
     /// Returns the physics group
     pub fn read_physics_group(reader: &MapFileReader) -> anyhow::Result<MapGroupPhysics> {
-        let physics_file = tar_entry_to_file(
-            reader
-                .entries
-                .get(Path::new("groups/physics.twmap_bincode.zst"))
-                .ok_or_else(|| anyhow!("physics group was not found in map file"))?,
-        )?;
-        let physics_group_file = Self::decompress_physics_group(physics_file)?;
-
-        let physics_group = Self::deserialize_physics_group(&physics_group_file)?;
-        anyhow::Ok(physics_group)
+        reader.read_section(MapSection::PhysicsGroup, |file| {
+            let physics_group_file = Self::decompress_physics_group(file)?;
+            Self::deserialize_physics_group(&physics_group_file)
+        })
     }
 
-    /// Write a map file to a writer
-    pub fn write(&self, tp: &rayon::ThreadPool) -> anyhow::Result<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+    /// Write a map file to a writer.
+    ///
+    /// Returns the physics group, the background groups, the foreground groups
+    /// and the blend modes, if any layer is not blended normally.
+    #[allow(clippy::type_complexity)]
+    pub fn write(
+        &self,
+        tp: &rayon::ThreadPool,
+    ) -> anyhow::Result<(Vec<u8>, Vec<u8>, Vec<u8>, Option<Vec<u8>>)> {
         let (physics, bg_fg) = tp.install(|| {
             tp.join(
                 || {
@@ -376,7 +490,185 @@ impl MapGroups {
             )
         });
 
+        let blend_modes = MapGroupsBlendModes::new(self)
+            .map(|blend_modes| {
+                let mut serialized_blend_modes: Vec<u8> = Default::default();
+                Self::serialize_blend_modes(&blend_modes, &mut serialized_blend_modes)?;
+                crate::utils::compress(&serialized_blend_modes)
+            })
+            .transpose()?;
+
         let (bg, fg) = bg_fg?;
-        Ok((physics?, bg, fg))
+        Ok((physics?, bg, fg, blend_modes))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use assets_base::tar::{new_tar, tar_add_file};
+    use math::math::vector::{ffixed, fvec2, nffixed, nfvec4, uffixed, ufvec2};
+
+    use crate::{file::MapFileReader, types::NonZeroU16MinusOne};
+
+    use super::{
+        MapGroup, MapGroupAttr, MapGroupPhysics, MapGroupPhysicsAttr, MapGroups,
+        MapGroupsBlendModes,
+        layers::{
+            design::{MapLayer, MapLayerBlendMode, MapLayerQuad, MapLayerQuadsAttrs, MapLayerTile},
+            physics::{
+                MapLayerPhysics, MapLayerPhysicsMeta, MapLayerTilePhysicsBase, MapPhysicsPlatform,
+                MapPhysicsPlatformCrush,
+            },
+            tiles::MapTileLayerAttr,
+        },
+    };
+
+    fn groups(tile_mode: MapLayerBlendMode, quad_mode: MapLayerBlendMode) -> MapGroups {
+        let size = NonZeroU16MinusOne::new(2).unwrap();
+        MapGroups {
+            physics: MapGroupPhysics {
+                attr: MapGroupPhysicsAttr {
+                    width: size,
+                    height: size,
+                },
+                layers: vec![MapLayerPhysics::Game(MapLayerTilePhysicsBase {
+                    tiles: vec![Default::default(); 4],
+                })],
+            },
+            background: vec![MapGroup {
+                attr: MapGroupAttr::default(),
+                layers: vec![
+                    MapLayer::Tile(MapLayerTile {
+                        attr: MapTileLayerAttr {
+                            width: size,
+                            height: size,
+                            color: nfvec4::new(
+                                nffixed::from_num(1),
+                                nffixed::from_num(1),
+                                nffixed::from_num(1),
+                                nffixed::from_num(1),
+                            ),
+                            high_detail: false,
+                            color_anim: None,
+                            color_anim_offset: Default::default(),
+                            image_array: None,
+                            blend_mode: tile_mode,
+                        },
+                        tiles: vec![Default::default(); 4],
+                        name: "shadow".into(),
+                    }),
+                    MapLayer::Abritrary(vec![1, 2, 3]),
+                ],
+                name: "bg".into(),
+            }],
+            foreground: vec![MapGroup {
+                attr: MapGroupAttr::default(),
+                layers: vec![MapLayer::Quad(MapLayerQuad {
+                    attr: MapLayerQuadsAttrs {
+                        image: None,
+                        high_detail: true,
+                        blend_mode: quad_mode,
+                    },
+                    quads: vec![Default::default()],
+                    name: "glow".into(),
+                })],
+                name: "fg".into(),
+            }],
+        }
+    }
+
+    fn write_and_read(groups: &MapGroups) -> (MapGroups, bool) {
+        let tp = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+        let (physics, bg, fg, blend_modes) = groups.write(&tp).unwrap();
+        let has_blend_modes = blend_modes.is_some();
+
+        let mut builder = new_tar();
+        tar_add_file(&mut builder, "groups/physics.twmap_bincode.zst", &physics);
+        tar_add_file(&mut builder, "groups/background.twmap_bincode.zst", &bg);
+        tar_add_file(&mut builder, "groups/foreground.twmap_bincode.zst", &fg);
+        if let Some(blend_modes) = blend_modes {
+            tar_add_file(&mut builder, "groups/blend_modes.json.zst", &blend_modes);
+        }
+        let reader = MapFileReader::new(builder.into_inner().unwrap()).unwrap();
+        (
+            MapGroups::read(&reader, &tp, &|_| {}).unwrap(),
+            has_blend_modes,
+        )
+    }
+
+    #[test]
+    fn blend_modes_round_trip() {
+        let normal = groups(MapLayerBlendMode::Normal, MapLayerBlendMode::Normal);
+        assert!(MapGroupsBlendModes::new(&normal).is_none());
+        let (read, has_blend_modes) = write_and_read(&normal);
+        assert!(!has_blend_modes);
+        assert_eq!(read.background, normal.background);
+        assert_eq!(read.foreground, normal.foreground);
+
+        let blended = groups(MapLayerBlendMode::Multiply, MapLayerBlendMode::Screen);
+        let (read, has_blend_modes) = write_and_read(&blended);
+        assert!(has_blend_modes);
+        assert_eq!(read.background, blended.background);
+        assert_eq!(read.foreground, blended.foreground);
+
+        // the group files don't contain the blend modes,
+        // so older versions can still read them
+        let mut serialized_normal = Vec::new();
+        MapGroups::serialize_background_groups(&normal.background, &mut serialized_normal).unwrap();
+        let mut serialized_blended = Vec::new();
+        MapGroups::serialize_background_groups(&blended.background, &mut serialized_blended)
+            .unwrap();
+        assert_eq!(serialized_normal, serialized_blended);
+    }
+
+    #[test]
+    fn invalid_blend_modes() {
+        let mut groups = groups(MapLayerBlendMode::Normal, MapLayerBlendMode::Normal);
+        // the arbitrary layer can't be blended
+        let blend_modes = MapGroupsBlendModes {
+            background: vec![vec![MapLayerBlendMode::Normal, MapLayerBlendMode::Additive]],
+            foreground: vec![vec![MapLayerBlendMode::Normal]],
+        };
+        assert!(blend_modes.apply(&mut groups).is_err());
+        // layer count mismatch
+        let blend_modes = MapGroupsBlendModes {
+            background: vec![vec![MapLayerBlendMode::Normal]],
+            foreground: vec![vec![MapLayerBlendMode::Normal]],
+        };
+        assert!(blend_modes.apply(&mut groups).is_err());
+    }
+
+    #[test]
+    fn physics_meta_round_trip() {
+        let mut groups = groups(MapLayerBlendMode::Normal, MapLayerBlendMode::Normal);
+        assert_eq!(groups.physics.get_meta(), None);
+
+        let meta = MapLayerPhysicsMeta {
+            platforms: vec![MapPhysicsPlatform {
+                pos: fvec2::new(ffixed::from_num(1), ffixed::from_num(0.5)),
+                size: ufvec2::new(uffixed::from_num(3), uffixed::from_num(1)),
+                pos_anim: 0,
+                pos_anim_offset: time::Duration::milliseconds(250),
+                hookable: true,
+                crush: MapPhysicsPlatformCrush::Kill,
+            }],
+        };
+        groups
+            .physics
+            .layers
+            .push(MapLayerPhysics::Arbitrary(meta.to_arbitrary().unwrap()));
+        let (read, _) = write_and_read(&groups);
+        assert_eq!(read.physics.get_meta(), Some(meta));
+
+        // arbitrary layers of other formats have no metadata
+        groups.physics.layers.pop();
+        groups
+            .physics
+            .layers
+            .push(MapLayerPhysics::Arbitrary(vec![1, 2, 3]));
+        assert_eq!(groups.physics.get_meta(), None);
     }
 }
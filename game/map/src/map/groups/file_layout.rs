@@ -0,0 +1,182 @@
+//! The layout of the design groups inside the map file.
+//!
+//! It equals the serde layout of [`MapGroup`], except that the
+//! blend modes of the layers are left out. This way maps that
+//! don't use blend modes stay readable by older versions.
+
+use math::math::vector::nfvec4;
+use serde::{Deserialize, Serialize};
+
+use crate::types::NonZeroU16MinusOne;
+
+use super::{
+    MapGroup, MapGroupAttr,
+    layers::{
+        design::{MapLayer, MapLayerQuad, MapLayerQuadsAttrs, MapLayerSound, MapLayerTile, Quad},
+        tiles::{MapTileLayerAttr, Tile},
+    },
+};
+
+#[derive(Serialize, Deserialize)]
+struct FileTileLayerAttr {
+    width: NonZeroU16MinusOne,
+    height: NonZeroU16MinusOne,
+    color: nfvec4,
+    high_detail: bool,
+    color_anim: Option<usize>,
+    color_anim_offset: time::Duration,
+    image_array: Option<usize>,
+}
+
+impl From<&MapTileLayerAttr> for FileTileLayerAttr {
+    fn from(attr: &MapTileLayerAttr) -> Self {
+        Self {
+            width: attr.width,
+            height: attr.height,
+            color: attr.color,
+            high_detail: attr.high_detail,
+            color_anim: attr.color_anim,
+            color_anim_offset: attr.color_anim_offset,
+            image_array: attr.image_array,
+        }
+    }
+}
+
+impl From<FileTileLayerAttr> for MapTileLayerAttr {
+    fn from(attr: FileTileLayerAttr) -> Self {
+        Self {
+            width: attr.width,
+            height: attr.height,
+            color: attr.color,
+            high_detail: attr.high_detail,
+            color_anim: attr.color_anim,
+            color_anim_offset: attr.color_anim_offset,
+            image_array: attr.image_array,
+            blend_mode: Default::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct FileQuadLayerAttr {
+    image: Option<usize>,
+    high_detail: bool,
+}
+
+impl From<&MapLayerQuadsAttrs> for FileQuadLayerAttr {
+    fn from(attr: &MapLayerQuadsAttrs) -> Self {
+        Self {
+            image: attr.image,
+            high_detail: attr.high_detail,
+        }
+    }
+}
+
+impl From<FileQuadLayerAttr> for MapLayerQuadsAttrs {
+    fn from(attr: FileQuadLayerAttr) -> Self {
+        Self {
+            image: attr.image,
+            high_detail: attr.high_detail,
+            blend_mode: Default::default(),
+        }
+    }
+}
+
+/// Must keep the variant order of [`MapLayer`].
+#[derive(Serialize)]
+enum FileLayerRef<'a> {
+    Abritrary(&'a Vec<u8>),
+    Tile((FileTileLayerAttr, &'a Vec<Tile>, &'a String)),
+    Quad((FileQuadLayerAttr, &'a Vec<Quad>, &'a String)),
+    Sound(&'a MapLayerSound),
+}
+
+#[derive(Serialize)]
+struct FileGroupRef<'a> {
+    attr: &'a MapGroupAttr,
+    layers: Vec<FileLayerRef<'a>>,
+    name: &'a String,
+}
+
+/// Must keep the variant order of [`MapLayer`].
+#[derive(Deserialize)]
+enum FileLayer {
+    Abritrary(Vec<u8>),
+    Tile((FileTileLayerAttr, Vec<Tile>, String)),
+    Quad((FileQuadLayerAttr, Vec<Quad>, String)),
+    Sound(MapLayerSound),
+}
+
+#[derive(Deserialize)]
+struct FileGroup {
+    attr: MapGroupAttr,
+    layers: Vec<FileLayer>,
+    name: String,
+}
+
+/// Borrows the groups in the layout of the map file.
+pub(super) fn to_file_layout(groups: &[MapGroup]) -> impl Serialize + '_ {
+    groups
+        .iter()
+        .map(|group| FileGroupRef {
+            attr: &group.attr,
+            layers: group
+                .layers
+                .iter()
+                .map(|layer| match layer {
+                    MapLayer::Abritrary(layer) => FileLayerRef::Abritrary(layer),
+                    MapLayer::Tile(layer) => {
+                        FileLayerRef::Tile(((&layer.attr).into(), &layer.tiles, &layer.name))
+                    }
+                    MapLayer::Quad(layer) => {
+                        FileLayerRef::Quad(((&layer.attr).into(), &layer.quads, &layer.name))
+                    }
+                    MapLayer::Sound(layer) => FileLayerRef::Sound(layer),
+                })
+                .collect(),
+            name: &group.name,
+        })
+        .collect::<Vec<_>>()
+}
+
+/// The groups in the layout of the map file, without blend modes.
+#[derive(Deserialize)]
+#[serde(transparent)]
+pub(super) struct FileGroups(Vec<FileGroup>);
+
+impl TryFrom<FileGroups> for Vec<MapGroup> {
+    type Error = anyhow::Error;
+
+    fn try_from(groups: FileGroups) -> anyhow::Result<Self> {
+        groups
+            .0
+            .into_iter()
+            .map(|group| {
+                anyhow::Ok(MapGroup {
+                    attr: group.attr,
+                    layers: group
+                        .layers
+                        .into_iter()
+                        .map(|layer| {
+                            anyhow::Ok(match layer {
+                                FileLayer::Abritrary(layer) => MapLayer::Abritrary(layer),
+                                FileLayer::Tile((attr, tiles, name)) => MapLayer::Tile(
+                                    MapLayerTile::new_validated(attr.into(), tiles, name)?,
+                                ),
+                                FileLayer::Quad((attr, quads, name)) => {
+                                    MapLayer::Quad(MapLayerQuad {
+                                        attr: attr.into(),
+                                        quads,
+                                        name,
+                                    })
+                                }
+                                FileLayer::Sound(layer) => MapLayer::Sound(layer),
+                            })
+                        })
+                        .collect::<anyhow::Result<_>>()?,
+                    name: group.name,
+                })
+            })
+            .collect()
+    }
+}
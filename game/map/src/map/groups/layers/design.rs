@@ -4,6 +4,23 @@ use serde::{Deserialize, Serialize};
 
 use super::tiles::{MapTileLayerAttr, Tile};
 
+/// How a design layer is blended with everything rendered before it.
+///
+/// Multiply & screen ignore the alpha of the layer's texture,
+/// so transparent texels should be black.
+#[derive(Debug, Hiarc, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MapLayerBlendMode {
+    /// Usual alpha blending.
+    #[default]
+    Normal,
+    /// Darkens everything below, e.g. for shadows.
+    Multiply,
+    /// Adds the layer's color weighted by its alpha, e.g. for glow.
+    Additive,
+    /// Brightens everything below, but softer than additive.
+    Screen,
+}
+
 #[derive(Debug, Hiarc, Clone, PartialEq, Eq)]
 pub struct MapLayerTile {
     pub attr: MapTileLayerAttr,
@@ -30,16 +47,25 @@ impl<'de> Deserialize<'de> for MapLayerTile {
         let (attr, tiles, name) =
             <(MapTileLayerAttr, Vec<Tile>, String)>::deserialize(deserializer)?;
 
-        // validate the design tile layer
-        if attr.width.get() as u64 * attr.height.get() as u64 != tiles.len() as u64 {
-            return Err(serde::de::Error::custom(format!(
-                "could not validate design tile layer. \
-                width & height did not match tile layer count {} - {} vs {}",
-                attr.width.get(),
-                attr.height.get(),
-                tiles.len()
-            )));
-        }
+        Self::new_validated(attr, tiles, name).map_err(serde::de::Error::custom)
+    }
+}
+
+impl MapLayerTile {
+    /// Creates the layer, if the tile count matches the layer's size.
+    pub(crate) fn new_validated(
+        attr: MapTileLayerAttr,
+        tiles: Vec<Tile>,
+        name: String,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            attr.width.get() as u64 * attr.height.get() as u64 == tiles.len() as u64,
+            "could not validate design tile layer. \
+            width & height did not match tile layer count {} - {} vs {}",
+            attr.width.get(),
+            attr.height.get(),
+            tiles.len()
+        );
 
         Ok(Self { attr, tiles, name })
     }
@@ -67,6 +93,9 @@ pub struct MapLayerQuadsAttrs {
 
     /// is a high detail layer
     pub high_detail: bool,
+
+    /// not part of the group files, see [`crate::map::groups::MapGroupsBlendModes`]
+    pub blend_mode: MapLayerBlendMode,
 }
 
 #[derive(Debug, Hiarc, Clone, Serialize, Deserialize, PartialEq, Eq)]
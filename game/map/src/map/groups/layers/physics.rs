@@ -1,8 +1,12 @@
 use base::linked_hash_map_view::FxLinkedHashMap;
 use hiarc::Hiarc;
+use math::math::vector::{fvec2, ufvec2};
 use serde::{Deserialize, Serialize};
 
-use crate::map::command_value::CommandValue;
+use crate::{
+    map::command_value::CommandValue,
+    utils::{deserialize_twmap_bincode, serialize_twmap_bincode},
+};
 
 use super::tiles::{
     MapTileLayerPhysicsTilesRef, SpeedupTile, SwitchTile, TeleTile, Tile, TuneTile,
@@ -47,6 +51,57 @@ pub struct MapLayerTilePhysicsTune {
     pub tune_zones: FxLinkedHashMap<u8, MapLayerTilePhysicsTuneZone>,
 }
 
+/// What happens to a character, that a platform squeezes into a wall.
+#[derive(Debug, Hiarc, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum MapPhysicsPlatformCrush {
+    /// The character is pushed as far as the wall allows,
+    /// the platform moves through the rest of it.
+    #[default]
+    Push,
+    /// The character dies.
+    Kill,
+}
+
+/// A solid rectangle, that moves along a pos animation.
+///
+/// The rotation of the animation is ignored.
+#[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MapPhysicsPlatform {
+    /// The center of the platform, before the animation is applied. In tiles.
+    pub pos: fvec2,
+    /// In tiles.
+    pub size: ufvec2,
+
+    /// Index into the pos animations of the map.
+    pub pos_anim: usize,
+    pub pos_anim_offset: time::Duration,
+
+    pub hookable: bool,
+    pub crush: MapPhysicsPlatformCrush,
+}
+
+/// Metadata of the physics group, stored in the arbitrary physics layer.
+#[derive(Debug, Hiarc, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MapLayerPhysicsMeta {
+    pub platforms: Vec<MapPhysicsPlatform>,
+}
+
+impl MapLayerPhysicsMeta {
+    /// Reads the metadata from the buffer of an arbitrary physics layer.
+    ///
+    /// Fails if the layer contains something else.
+    pub fn from_arbitrary(buf: &[u8]) -> anyhow::Result<Self> {
+        deserialize_twmap_bincode(buf)
+    }
+
+    /// The buffer of the arbitrary physics layer, that contains the metadata.
+    pub fn to_arbitrary(&self) -> anyhow::Result<Vec<u8>> {
+        let mut buf = Vec::new();
+        serialize_twmap_bincode(self, &mut buf)?;
+        Ok(buf)
+    }
+}
+
 #[derive(Debug, Hiarc, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum MapLayerPhysics {
     Arbitrary(Vec<u8>),
@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::types::NonZeroU16MinusOne;
 
+use super::design::MapLayerBlendMode;
+
 #[derive(Debug, Hiarc, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MapTileLayerAttr {
     pub width: NonZeroU16MinusOne,
@@ -19,6 +21,9 @@ pub struct MapTileLayerAttr {
     pub color_anim_offset: time::Duration,
 
     pub image_array: Option<usize>,
+
+    /// not part of the group files, see [`crate::map::groups::MapGroupsBlendModes`]
+    pub blend_mode: MapLayerBlendMode,
 }
 
 #[derive(
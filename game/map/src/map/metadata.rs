@@ -1,6 +1,13 @@
 use hiarc::Hiarc;
 use serde::{Deserialize, Serialize};
 
+/// The ambiance of the menu theme a map prefers.
+#[derive(Debug, Hiarc, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ThemeAmbiance {
+    Day,
+    Night,
+}
+
 /// The meta data is not useful for the game.
 /// They simply exist for completeness
 #[derive(Debug, Hiarc, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -10,4 +17,8 @@ pub struct Metadata {
     pub version: String,
     pub credits: String,
     pub memo: String,
+    /// The ambiance of the menu theme shown while the map loads,
+    /// `None` lets the client decide.
+    #[serde(default)]
+    pub theme_ambiance: Option<ThemeAmbiance>,
 }
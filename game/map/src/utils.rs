@@ -30,6 +30,9 @@ pub fn compress(uncompressed_file: &[u8]) -> anyhow::Result<Vec<u8>> {
     // Compression level 15 seems to be a good trait performance vs map size
     // Tested with the test benchmark in this crate on some maps.
     let mut enc = zstd::Encoder::new(&mut write_data, 15)?;
+    // The content checksum is verified while decompressing,
+    // so a corrupt map section is reported instead of parsed.
+    enc.include_checksum(true)?;
     enc.write_all(uncompressed_file)?;
     enc.finish()?;
     Ok(write_data)
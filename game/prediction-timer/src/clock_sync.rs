@@ -0,0 +1,326 @@
+use std::{collections::VecDeque, time::Duration};
+
+use tracing::instrument;
+
+/// How long samples are respected.
+const SAMPLE_WINDOW: Duration = Duration::from_secs(30);
+/// Samples further off the estimation than this times the median
+/// are outliers.
+const OUTLIER_FACTOR: f64 = 3.0;
+/// The smallest distance to the estimation that counts as outlier,
+/// for very exact samples the median is close to zero.
+const MIN_OUTLIER_DISTANCE: f64 = 0.002;
+/// Until this many samples arrived, the offset directly follows the estimation.
+const MIN_SAMPLES: usize = 5;
+/// Clocks don't drift more than this (seconds per second),
+/// higher estimations come from bad samples.
+const MAX_DRIFT: f64 = 0.001;
+
+#[derive(Debug, Clone, Copy)]
+struct ClockSample {
+    /// local time the sample arrived at
+    local: f64,
+    rtt: f64,
+    /// server time minus local time
+    offset: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ClockEstimation {
+    local: f64,
+    offset: f64,
+    drift: f64,
+}
+
+impl ClockEstimation {
+    fn offset_at(&self, local: f64) -> f64 {
+        self.offset + (local - self.local) * self.drift
+    }
+
+    /// Least squares fit of the offsets over the local time.
+    fn fit<'a>(samples: impl Iterator<Item = &'a ClockSample> + Clone) -> Option<Self> {
+        let count = samples.clone().count();
+        if count == 0 {
+            return None;
+        }
+        let local = samples.clone().map(|s| s.local).sum::<f64>() / count as f64;
+        let offset = samples.clone().map(|s| s.offset).sum::<f64>() / count as f64;
+        let variance = samples
+            .clone()
+            .map(|s| (s.local - local).powi(2))
+            .sum::<f64>();
+        let drift = if variance > 0.0 {
+            samples
+                .map(|s| (s.local - local) * (s.offset - offset))
+                .sum::<f64>()
+                / variance
+        } else {
+            0.0
+        };
+        Some(Self {
+            local,
+            offset,
+            drift: drift.clamp(-MAX_DRIFT, MAX_DRIFT),
+        })
+    }
+}
+
+/// Estimates the clock of the server from ping samples.
+///
+/// Only the samples with the lowest round trip time are used, since
+/// they are the most exact, the drift between both clocks is estimated
+/// by a linear fit over them.
+/// The applied offset follows the estimation smoothly & only jumps
+/// if the error gets bigger than [`ClockSync::STEP_THRESHOLD`].
+#[derive(Debug, Default)]
+pub struct ClockSync {
+    /// the sample with the lowest rtt of each second
+    samples: VecDeque<ClockSample>,
+    estimation: Option<ClockEstimation>,
+    applied_offset: Option<f64>,
+    last_update: Duration,
+    steps: u64,
+}
+
+impl ClockSync {
+    /// Errors bigger than this are corrected at once, smaller ones smoothly.
+    pub const STEP_THRESHOLD: Duration = Duration::from_millis(20);
+    /// How fast the offset is smoothly corrected in seconds per second.
+    pub const SLEW_PER_SEC: f64 = 0.005;
+
+    /// Add a ping sample.
+    ///
+    /// `sent` & `received` are the local times of the request & the answer,
+    /// `server_sent` is the server's time when it sent the answer,
+    /// `server_wait` the time the request waited on the server.
+    #[instrument(level = "trace", skip_all)]
+    pub fn add_sample(
+        &mut self,
+        sent: Duration,
+        received: Duration,
+        server_sent: Duration,
+        server_wait: Duration,
+    ) {
+        let Some(rtt) = received
+            .checked_sub(sent)
+            .and_then(|rtt| rtt.checked_sub(server_wait))
+        else {
+            return;
+        };
+        let local = received.as_secs_f64();
+        let rtt = rtt.as_secs_f64();
+        // assumes that both directions take the same time
+        let sample = ClockSample {
+            local,
+            rtt,
+            offset: server_sent.as_secs_f64() + rtt / 2.0 - local,
+        };
+        match self.samples.back_mut() {
+            Some(last) if last.local.floor() == local.floor() => {
+                if sample.rtt < last.rtt {
+                    *last = sample;
+                }
+            }
+            _ => self.samples.push_back(sample),
+        }
+        while self
+            .samples
+            .front()
+            .is_some_and(|s| local - s.local > SAMPLE_WINDOW.as_secs_f64())
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    fn estimate(&self) -> Option<ClockEstimation> {
+        // the faster half of the samples
+        let mut rtts: Vec<_> = self.samples.iter().map(|s| s.rtt).collect();
+        rtts.sort_by(f64::total_cmp);
+        let max_rtt = *rtts.get(rtts.len() / 2)?;
+        let samples = self.samples.iter().filter(|s| s.rtt <= max_rtt);
+        let estimation = ClockEstimation::fit(samples.clone())?;
+
+        // refit without the outliers
+        let distance = |s: &ClockSample| (s.offset - estimation.offset_at(s.local)).abs();
+        let mut distances: Vec<_> = samples.clone().map(distance).collect();
+        distances.sort_by(f64::total_cmp);
+        let max_distance =
+            (distances[distances.len() / 2] * OUTLIER_FACTOR).max(MIN_OUTLIER_DISTANCE);
+        ClockEstimation::fit(samples.filter(|s| distance(s) <= max_distance)).or(Some(estimation))
+    }
+
+    /// Moves the applied offset towards the current estimation.
+    #[instrument(level = "trace", skip_all)]
+    pub fn update(&mut self, cur_time: Duration) {
+        self.estimation = self.estimate();
+        let Some(estimation) = self.estimation else {
+            return;
+        };
+        let target = estimation.offset_at(cur_time.as_secs_f64());
+        self.applied_offset = Some(match self.applied_offset {
+            Some(applied) if (target - applied).abs() <= Self::STEP_THRESHOLD.as_secs_f64() => {
+                let passed = cur_time.saturating_sub(self.last_update).as_secs_f64();
+                let max_change = Self::SLEW_PER_SEC * passed;
+                applied + (target - applied).clamp(-max_change, max_change)
+            }
+            Some(_) if self.samples.len() >= MIN_SAMPLES => {
+                self.steps += 1;
+                target
+            }
+            _ => target,
+        });
+        self.last_update = cur_time;
+    }
+
+    /// The server's time at the given local time, if enough samples arrived.
+    pub fn server_time(&self, local_time: Duration) -> Option<Duration> {
+        self.applied_offset
+            .map(|offset| Duration::from_secs_f64((local_time.as_secs_f64() + offset).max(0.0)))
+    }
+
+    /// The local time at which the server had the given time.
+    pub fn local_time(&self, server_time: Duration) -> Option<Duration> {
+        self.applied_offset
+            .map(|offset| Duration::from_secs_f64((server_time.as_secs_f64() - offset).max(0.0)))
+    }
+
+    /// The lowest round trip time of the respected samples.
+    pub fn min_rtt(&self) -> Option<Duration> {
+        self.samples
+            .iter()
+            .map(|s| s.rtt)
+            .min_by(f64::total_cmp)
+            .map(Duration::from_secs_f64)
+    }
+
+    /// The estimated drift of the server's clock in seconds per second.
+    pub fn drift(&self) -> Option<f64> {
+        self.estimation.map(|estimation| estimation.drift)
+    }
+
+    /// How often the offset had to jump, since the error was too big.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use math::math::Rng;
+
+    use super::ClockSync;
+
+    /// The server clock is ahead by this many seconds when the client starts.
+    const OFFSET: f64 = 5.0;
+
+    fn server_offset(local: f64, drift: f64) -> f64 {
+        OFFSET + local * drift
+    }
+
+    /// Sends a ping every 50ms, the network adds `jitter` at most,
+    /// every 10th ping has a lag spike & every 47th is answered by a
+    /// server clock that hangs behind.
+    fn feed(
+        sync: &mut ClockSync,
+        rng: &mut Rng,
+        secs: std::ops::Range<u64>,
+        drift: f64,
+        jitter: f64,
+    ) {
+        for i in secs.start * 20..secs.end * 20 {
+            let sent = i as f64 / 20.0;
+            let up = 0.02 + rng.random_float() as f64 * jitter;
+            let wait = rng.random_float() as f64 * 0.02;
+            let down =
+                0.02 + rng.random_float() as f64 * jitter + if i % 10 == 0 { 0.3 } else { 0.0 };
+            let server_sent_local = sent + up + wait;
+            let mut server_sent = server_sent_local + server_offset(server_sent_local, drift);
+            if i % 47 == 0 {
+                server_sent -= 0.1;
+            }
+            let received = server_sent_local + down;
+
+            sync.add_sample(
+                Duration::from_secs_f64(sent),
+                Duration::from_secs_f64(received),
+                Duration::from_secs_f64(server_sent),
+                Duration::from_secs_f64(wait),
+            );
+            sync.update(Duration::from_secs_f64(received));
+        }
+    }
+
+    fn offset_error(sync: &ClockSync, local: f64, drift: f64) -> f64 {
+        let server = sync
+            .server_time(Duration::from_secs_f64(local))
+            .unwrap()
+            .as_secs_f64();
+        (server - local - server_offset(local, drift)).abs()
+    }
+
+    #[test]
+    fn estimation_with_outliers() {
+        let mut rng = Rng::new(0);
+        let mut sync = ClockSync::default();
+        assert!(sync.server_time(Duration::ZERO).is_none());
+
+        feed(&mut sync, &mut rng, 0..60, 0.0, 0.01);
+        assert!(offset_error(&sync, 60.0, 0.0) < 0.002);
+        assert!(sync.drift().unwrap().abs() < 0.0001);
+        assert!(sync.min_rtt().unwrap() < Duration::from_millis(42));
+        assert_eq!(sync.steps(), 0);
+
+        // mapping back & forth
+        let local = Duration::from_secs(60);
+        let server = sync.server_time(local).unwrap();
+        assert!(sync.local_time(server).unwrap().abs_diff(local) < Duration::from_micros(1));
+    }
+
+    #[test]
+    fn drift_estimation() {
+        let mut rng = Rng::new(1);
+        let mut sync = ClockSync::default();
+        // the server clock runs 200us per second faster
+        let drift = 0.0002;
+        feed(&mut sync, &mut rng, 0..600, drift, 0.005);
+        assert!((sync.drift().unwrap() - drift).abs() < 0.00005);
+        // after 10 minutes the offset changed by 120ms, but it was followed smoothly
+        assert!(offset_error(&sync, 600.0, drift) < 0.002);
+        assert_eq!(sync.steps(), 0);
+    }
+
+    #[test]
+    fn slew_and_step() {
+        let mut rng = Rng::new(2);
+        let mut sync = ClockSync::default();
+        feed(&mut sync, &mut rng, 0..30, 0.0, 0.0);
+        let before = sync.server_time(Duration::from_secs(30)).unwrap();
+
+        // small errors are corrected smoothly
+        let mut shifted = ClockSync::default();
+        feed(&mut shifted, &mut rng, 0..30, 0.0, 0.0);
+        shifted.applied_offset = shifted.applied_offset.map(|offset| offset - 0.01);
+        shifted.update(Duration::from_secs(30));
+        let mut prev = shifted.server_time(Duration::from_secs(30)).unwrap();
+        assert!(before.abs_diff(prev) > Duration::from_millis(9));
+        for i in 1..=60 {
+            let time = Duration::from_millis(30_000 + i * 50);
+            shifted.update(time);
+            let cur = shifted.server_time(Duration::from_secs(30)).unwrap();
+            // 5ms per second
+            assert!(cur.abs_diff(prev) <= Duration::from_micros(251));
+            prev = cur;
+        }
+        assert!(before.abs_diff(prev) < Duration::from_micros(100));
+        assert_eq!(shifted.steps(), 0);
+
+        // big errors are corrected at once
+        shifted.applied_offset = shifted.applied_offset.map(|offset| offset - 0.1);
+        shifted.update(Duration::from_secs(32));
+        let cur = shifted.server_time(Duration::from_secs(30)).unwrap();
+        assert!(before.abs_diff(cur) < Duration::from_micros(100));
+        assert_eq!(shifted.steps(), 1);
+    }
+}
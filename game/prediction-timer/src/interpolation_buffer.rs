@@ -0,0 +1,335 @@
+use std::{collections::VecDeque, time::Duration};
+
+use tracing::instrument;
+
+/// How many snapshots are respected to measure the jitter & loss.
+const SNAP_WINDOW: usize = 100;
+/// The ratio of snapshots that should arrive before they are needed.
+const JITTER_PERCENTILE: f64 = 0.95;
+/// The most snapshots in a row that are bridged for packet loss.
+const MAX_LOSS_INTERVALS: u32 = 4;
+/// The accepted chance that more snapshots in a row are lost than the
+/// buffer can bridge.
+const ACCEPTED_LOSS_CHANCE: f64 = 0.01;
+
+/// The limits in which the interpolation buffer is adjusted.
+#[derive(Debug, Clone, Copy)]
+pub struct InterpolationBufferSettings {
+    pub min: Duration,
+    pub max: Duration,
+    /// Use this buffer instead of the automatic adjustment.
+    pub manual: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SnapArrival {
+    /// arrival time minus the time of the snapshot's tick,
+    /// only the differences between snapshots matter.
+    transit: f64,
+    /// the time since the previous snapshot's tick, if this snapshot is newer.
+    gap: Option<f64>,
+}
+
+/// Decides how far the unpredicted game is rendered behind the newest snapshot,
+/// so that the next snapshot to interpolate to has usually already arrived.
+///
+/// The buffer follows the jitter of the snapshot arrivals & the snapshot loss,
+/// changes are ramped, so the rendered game only runs slightly slower
+/// or faster instead of jumping.
+#[derive(Debug, Default)]
+pub struct InterpolationBuffer {
+    snaps: VecDeque<SnapArrival>,
+    last_snap_time: Option<f64>,
+
+    target: Duration,
+    current: Option<Duration>,
+    last_update: Duration,
+}
+
+impl InterpolationBuffer {
+    /// How fast the buffer grows in seconds per second.
+    /// A missing snapshot causes a visible stutter, so it grows quickly.
+    pub const GROW_PER_SEC: f64 = 0.1;
+    /// How fast the buffer shrinks in seconds per second.
+    /// Slower than growing, so single jitter spikes don't cause pumping.
+    pub const SHRINK_PER_SEC: f64 = 0.025;
+
+    /// Add a received snapshot.
+    ///
+    /// `arrival` is the network timestamp the snapshot arrived at,
+    /// `tick_time` is the time one tick takes in the physics.
+    #[instrument(level = "trace", skip_all)]
+    pub fn add_snap(&mut self, arrival: Duration, monotonic_tick: u64, tick_time: Duration) {
+        let snap_time = monotonic_tick as f64 * tick_time.as_secs_f64();
+        let gap = self
+            .last_snap_time
+            .map(|last| snap_time - last)
+            .filter(|gap| *gap > 0.0);
+        if gap.is_some() || self.last_snap_time.is_none() {
+            self.last_snap_time = Some(snap_time);
+        }
+
+        self.snaps.push_back(SnapArrival {
+            transit: arrival.as_secs_f64() - snap_time,
+            gap,
+        });
+        while self.snaps.len() > SNAP_WINDOW {
+            self.snaps.pop_front();
+        }
+    }
+
+    /// The usual time between two snapshots.
+    ///
+    /// The median, so lost snapshots don't count in.
+    pub fn snap_interval(&self) -> Duration {
+        let mut gaps: Vec<_> = self.snaps.iter().filter_map(|snap| snap.gap).collect();
+        if gaps.is_empty() {
+            return Duration::ZERO;
+        }
+        gaps.sort_by(f64::total_cmp);
+        Duration::from_secs_f64(gaps[gaps.len() / 2])
+    }
+
+    /// How much later than the earliest snapshots most snapshots arrive.
+    pub fn snap_jitter(&self) -> Duration {
+        let mut transits: Vec<_> = self.snaps.iter().map(|snap| snap.transit).collect();
+        if transits.is_empty() {
+            return Duration::ZERO;
+        }
+        transits.sort_by(f64::total_cmp);
+        let index = ((transits.len() - 1) as f64 * JITTER_PERCENTILE).round() as usize;
+        Duration::from_secs_f64(transits[index] - transits[0])
+    }
+
+    /// The ratio of snapshots that never arrived,
+    /// detected by gaps bigger than the usual interval.
+    pub fn snap_loss(&self) -> f64 {
+        let interval = self.snap_interval().as_secs_f64();
+        if interval <= 0.0 {
+            return 0.0;
+        }
+        let (expected, lost) = self
+            .snaps
+            .iter()
+            .filter_map(|snap| snap.gap)
+            .map(|gap| (gap / interval).round().max(1.0))
+            .fold((0.0, 0.0), |(expected, lost), snaps| {
+                (expected + snaps, lost + snaps - 1.0)
+            });
+        if expected > 0.0 { lost / expected } else { 0.0 }
+    }
+
+    /// How many snapshot intervals are added to bridge lost snapshots.
+    fn extra_intervals_by_snap_loss(loss: f64) -> u32 {
+        if loss <= 0.0 {
+            0
+        } else if loss >= 1.0 {
+            MAX_LOSS_INTERVALS
+        } else {
+            // the chance that `n + 1` snapshots in a row are lost is `loss^(n + 1)`
+            ((ACCEPTED_LOSS_CHANCE.ln() / loss.ln()).ceil() as u32)
+                .saturating_sub(1)
+                .min(MAX_LOSS_INTERVALS)
+        }
+    }
+
+    /// The buffer that the current buffer ramps to.
+    pub fn calc_target(&self, settings: &InterpolationBufferSettings) -> Duration {
+        if let Some(manual) = settings.manual {
+            return manual;
+        }
+        let min = settings.min.min(settings.max);
+        let interval = self.snap_interval();
+        let loss_intervals = Self::extra_intervals_by_snap_loss(self.snap_loss());
+        (interval * (1 + loss_intervals) + self.snap_jitter()).clamp(min, settings.max)
+    }
+
+    /// Ramps the buffer towards the target & returns the buffer to use.
+    #[instrument(level = "trace", skip_all)]
+    pub fn update(
+        &mut self,
+        cur_time: Duration,
+        settings: &InterpolationBufferSettings,
+    ) -> Duration {
+        let target = self.calc_target(settings);
+        let current = match self.current {
+            Some(current) => {
+                let passed = cur_time.saturating_sub(self.last_update).as_secs_f64();
+                let current = current.as_secs_f64();
+                let target = target.as_secs_f64();
+                Duration::from_secs_f64(if target > current {
+                    (current + Self::GROW_PER_SEC * passed).min(target)
+                } else {
+                    (current - Self::SHRINK_PER_SEC * passed).max(target)
+                })
+            }
+            None => target,
+        };
+        self.target = target;
+        self.current = Some(current);
+        self.last_update = cur_time;
+        current
+    }
+
+    /// The buffer of the last [`InterpolationBuffer::update`].
+    pub fn current(&self) -> Duration {
+        self.current.unwrap_or_default()
+    }
+
+    /// The target of the last [`InterpolationBuffer::update`].
+    pub fn target(&self) -> Duration {
+        self.target
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{InterpolationBuffer, InterpolationBufferSettings};
+
+    const TICK_TIME: Duration = Duration::from_millis(20);
+    const LATENCY: Duration = Duration::from_millis(40);
+
+    /// Extra delays in ms of snapshots recorded on a wifi connection.
+    const WIFI_TRACE: [u64; 20] = [2, 5, 1, 3, 40, 8, 2, 1, 0, 4, 6, 2, 35, 3, 1, 9, 2, 0, 5, 3];
+
+    const SETTINGS: InterpolationBufferSettings = InterpolationBufferSettings {
+        min: Duration::ZERO,
+        max: Duration::from_millis(500),
+        manual: None,
+    };
+
+    fn assert_ms(val: Duration, ms: u64) {
+        assert!(
+            val.abs_diff(Duration::from_millis(ms)) < Duration::from_micros(100),
+            "{val:?} != {ms}ms"
+        );
+    }
+
+    /// Feeds one snapshot per tick & updates the buffer once per tick.
+    /// Returns the times & buffers of all updates.
+    fn feed(
+        buffer: &mut InterpolationBuffer,
+        ticks: std::ops::Range<u64>,
+        delay_ms: impl Fn(u64) -> Option<u64>,
+    ) -> Vec<(Duration, Duration)> {
+        ticks
+            .map(|tick| {
+                let time = TICK_TIME * tick as u32 + LATENCY;
+                if let Some(delay) = delay_ms(tick) {
+                    buffer.add_snap(time + Duration::from_millis(delay), tick, TICK_TIME);
+                }
+                (time, buffer.update(time, &SETTINGS))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn steady_connection() {
+        let mut buffer = InterpolationBuffer::default();
+        feed(&mut buffer, 0..200, |_| Some(0));
+        assert_ms(buffer.snap_interval(), 20);
+        assert_ms(buffer.snap_jitter(), 0);
+        assert_eq!(buffer.snap_loss(), 0.0);
+        assert_ms(buffer.current(), 20);
+    }
+
+    #[test]
+    fn jitter_trace() {
+        let mut buffer = InterpolationBuffer::default();
+        feed(&mut buffer, 0..500, |tick| {
+            Some(WIFI_TRACE[tick as usize % WIFI_TRACE.len()])
+        });
+        // the two big spikes make up the last 10% of the trace
+        assert_ms(buffer.snap_jitter(), 35);
+        assert_ms(buffer.target(), 20 + 35);
+        assert_ms(buffer.current(), 20 + 35);
+    }
+
+    #[test]
+    fn loss_trace() {
+        let mut buffer = InterpolationBuffer::default();
+        // every fifth snapshot is lost
+        feed(&mut buffer, 0..500, |tick| (tick % 5 != 0).then_some(0));
+        assert_ms(buffer.snap_interval(), 20);
+        assert!((buffer.snap_loss() - 0.2).abs() < 0.02);
+        // two lost snapshots in a row are bridged
+        assert_ms(buffer.target(), 20 * 3);
+    }
+
+    #[test]
+    fn bounds_and_manual() {
+        let mut buffer = InterpolationBuffer::default();
+        feed(&mut buffer, 0..200, |tick| {
+            Some(WIFI_TRACE[tick as usize % WIFI_TRACE.len()])
+        });
+
+        let bounded = InterpolationBufferSettings {
+            min: Duration::from_millis(60),
+            max: Duration::from_millis(100),
+            manual: None,
+        };
+        assert_ms(buffer.calc_target(&bounded), 60);
+        let bounded = InterpolationBufferSettings {
+            min: Duration::ZERO,
+            max: Duration::from_millis(30),
+            ..bounded
+        };
+        assert_ms(buffer.calc_target(&bounded), 30);
+        let manual = InterpolationBufferSettings {
+            manual: Some(Duration::from_millis(150)),
+            ..bounded
+        };
+        assert_ms(buffer.calc_target(&manual), 150);
+    }
+
+    #[test]
+    fn ramp_rate() {
+        let check_ramp = |updates: &[(Duration, Duration)]| {
+            for window in updates.windows(2) {
+                let [(prev_time, prev), (time, cur)] = window else {
+                    unreachable!()
+                };
+                let passed = (*time - *prev_time).as_secs_f64();
+                let change = cur.as_secs_f64() - prev.as_secs_f64();
+                assert!(change <= InterpolationBuffer::GROW_PER_SEC * passed + 1e-9);
+                assert!(-change <= InterpolationBuffer::SHRINK_PER_SEC * passed + 1e-9);
+            }
+        };
+
+        let mut buffer = InterpolationBuffer::default();
+        feed(&mut buffer, 0..200, |_| Some(0));
+        assert_ms(buffer.current(), 20);
+
+        // the connection gets worse, the buffer grows over a few frames
+        let updates = feed(&mut buffer, 200..400, |tick| {
+            Some(WIFI_TRACE[tick as usize % WIFI_TRACE.len()])
+        });
+        check_ramp(&updates);
+        assert!(
+            updates.iter().any(
+                |(_, cur)| *cur > Duration::from_millis(20) && *cur < Duration::from_millis(55)
+            )
+        );
+        assert_ms(buffer.current(), 55);
+
+        // the connection is stable again, the buffer shrinks slowly
+        let updates = feed(&mut buffer, 400..800, |_| Some(0));
+        check_ramp(&updates);
+        assert_ms(buffer.target(), 20);
+        assert_ms(buffer.current(), 20);
+        let shrink_start = updates
+            .iter()
+            .rfind(|(_, cur)| *cur >= Duration::from_millis(55))
+            .map(|(time, _)| *time)
+            .unwrap();
+        let shrink_end = updates
+            .iter()
+            .find(|(_, cur)| *cur <= Duration::from_millis(20))
+            .map(|(time, _)| *time)
+            .unwrap();
+        // shrinking by 35ms takes 1.4s
+        assert!(shrink_end - shrink_start >= Duration::from_millis(1390));
+    }
+}
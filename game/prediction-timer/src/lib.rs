@@ -1 +1,3 @@
+pub mod clock_sync;
+pub mod interpolation_buffer;
 pub mod prediction_timing;
@@ -9,6 +9,7 @@ use client_render_game::render_game::{
     RenderGame, RenderGameCreateOptions, RenderGameInput, RenderGameInterface,
 };
 use config::config::ConfigDebug;
+use game_base::connecting_log::ConnectingLog;
 use game_config::config::ConfigMap;
 use game_interface::chat_commands::ChatCommands;
 use graphics::{graphics::graphics::Graphics, handles::canvas::canvas::GraphicsCanvasHandle};
@@ -88,6 +89,7 @@ impl RenderGameWasmManager {
         config: &ConfigDebug,
         render_mod: RenderGameMod,
         props: RenderGameCreateOptions,
+        log: Option<ConnectingLog>,
     ) -> anyhow::Result<Self> {
         let fs_change_watcher = io
             .fs
@@ -104,6 +106,7 @@ impl RenderGameWasmManager {
                     map_file,
                     config,
                     props,
+                    log,
                 )
                 .map_err(|err| anyhow!(err))?;
                 RenderGameWrapper::Native(Box::new(state))
@@ -0,0 +1,81 @@
+/// join & leave messages & periodic announcements of the server
+pub mod announcements {
+    use std::fmt::Write;
+
+    use hiarc::Hiarc;
+
+    /// How announcements are sent, see [`AnnouncementScheduler::tick`].
+    #[derive(Debug, Hiarc, Clone, Copy)]
+    pub struct AnnouncementOptions {
+        /// The number of announcements.
+        pub count: usize,
+        /// A value of `0` means no announcements.
+        pub interval_ticks: u64,
+        /// Announcements pause while fewer players are online.
+        pub min_players: usize,
+    }
+
+    /// Replaces the placeholders of a join or leave message:
+    /// `{name}` by the name of the player &
+    /// `{players}` by the number of players on the server.
+    ///
+    /// Unknown placeholders are kept as they are.
+    pub fn format_player_msg(template: &str, name: &str, player_count: usize) -> String {
+        let mut res = String::with_capacity(template.len() + name.len());
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            res.push_str(&rest[..start]);
+            rest = &rest[start..];
+            if let Some(after) = rest.strip_prefix("{name}") {
+                res.push_str(name);
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix("{players}") {
+                let _ = write!(res, "{player_count}");
+                rest = after;
+            } else {
+                res.push('{');
+                rest = &rest[1..];
+            }
+        }
+        res.push_str(rest);
+        res
+    }
+
+    /// Sends the announcements in turn, one every interval.
+    ///
+    /// The time only passes while enough players are online,
+    /// so an empty server is not announced to.
+    #[derive(Debug, Default)]
+    pub struct AnnouncementScheduler {
+        ticks_passed: u64,
+        next: usize,
+    }
+
+    impl AnnouncementScheduler {
+        /// Advances the scheduler by one tick.
+        ///
+        /// Returns the index of the announcement that is due.
+        pub fn tick(
+            &mut self,
+            options: &AnnouncementOptions,
+            player_count: usize,
+        ) -> Option<usize> {
+            if options.count == 0 || options.interval_ticks == 0 {
+                self.ticks_passed = 0;
+                return None;
+            }
+            if player_count < options.min_players {
+                return None;
+            }
+            self.ticks_passed += 1;
+            if self.ticks_passed < options.interval_ticks {
+                return None;
+            }
+            self.ticks_passed = 0;
+            // the list might have been shortened in the meantime
+            let index = self.next % options.count;
+            self.next = (index + 1) % options.count;
+            Some(index)
+        }
+    }
+}
@@ -0,0 +1,204 @@
+/// server side plausibility checks of the player inputs
+pub mod anti_cheat {
+    use std::collections::VecDeque;
+
+    use bitflags::bitflags;
+    use game_interface::types::input::{
+        CharacterInput, CharacterInputConsumableDiff, CharacterInputState,
+        cursor::CharacterInputCursor,
+    };
+    use math::math::{
+        distance,
+        vector::{dvec2, vec2},
+    };
+
+    use crate::state::state::TICKS_PER_SECOND;
+
+    /// Cursors closer to the character than this are ignored
+    /// for the aim snap detection, their angle is too unstable.
+    const AIM_SNAP_MIN_CURSOR_LEN: f64 = 32.0;
+    /// An angle change of the cursor between two inputs,
+    /// that together with a shot counts as aim snap.
+    const AIM_SNAP_ANGLE: f64 = std::f64::consts::PI * 2.0 / 3.0;
+    const AIM_SNAP_SUSPICION: f32 = 10.0;
+
+    /// More presses (jump, fire & hook) per second than there are
+    /// ticks can't come from a human.
+    const MAX_PRESSES_PER_SECOND: u64 = TICKS_PER_SECOND;
+    const INPUT_FREQUENCY_SUSPICION: f32 = 25.0;
+
+    /// How many inputs must repeat to count as pattern.
+    const PATTERN_WINDOW: usize = 32;
+    /// The longest checked period of a pattern.
+    const PATTERN_MAX_PERIOD: usize = 8;
+    const PATTERN_SUSPICION: f32 = 25.0;
+
+    /// How much suspicion is removed every tick.
+    const SUSPICION_DECAY: f32 = 0.2;
+
+    /// The character moved more than this between two inputs.
+    /// Faster than any movement, so it was teleported.
+    const TELEPORT_DISTANCE: f32 = 32.0 * 10.0;
+
+    bitflags! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+        pub struct InputDetections: u8 {
+            /// The cursor jumped to a completely different direction
+            /// and a shot was fired at the same time.
+            const AIM_SNAP = (1 << 0);
+            /// More presses than physically possible.
+            const INPUT_FREQUENCY = (1 << 1);
+            /// The same inputs repeat perfectly.
+            const REPEATING_PATTERN = (1 << 2);
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct PatternSample {
+        cursor: CharacterInputCursor,
+        state: CharacterInputState,
+    }
+
+    /// Accumulates the suspicion of a single player.
+    ///
+    /// Every detection adds to the suspicion, which decays over time,
+    /// so only frequent detections reach the threshold.
+    #[derive(Debug)]
+    pub struct InputPlausibility {
+        skip_next: bool,
+        prev_cursor: Option<dvec2>,
+        prev_pos: Option<vec2>,
+
+        /// Presses of the inputs of the last second.
+        presses: VecDeque<u64>,
+        pattern: VecDeque<PatternSample>,
+
+        suspicion: f32,
+        detections: InputDetections,
+    }
+
+    impl Default for InputPlausibility {
+        fn default() -> Self {
+            Self {
+                // the first input after joining contains
+                // everything that happened before.
+                skip_next: true,
+                prev_cursor: None,
+                prev_pos: None,
+
+                presses: Default::default(),
+                pattern: Default::default(),
+
+                suspicion: 0.0,
+                detections: Default::default(),
+            }
+        }
+    }
+
+    impl InputPlausibility {
+        /// The next input is not checked, e.g. after a teleport.
+        pub fn skip_next(&mut self) {
+            self.skip_next = true;
+        }
+
+        pub fn suspicion(&self) -> f32 {
+            self.suspicion
+        }
+
+        /// All detections since the last reset.
+        pub fn detections(&self) -> InputDetections {
+            self.detections
+        }
+
+        pub fn reset_suspicion(&mut self) {
+            self.suspicion = 0.0;
+            self.detections = InputDetections::empty();
+        }
+
+        pub fn decay(&mut self) {
+            self.suspicion = (self.suspicion - SUSPICION_DECAY).max(0.0);
+        }
+
+        fn is_aim_snap(prev_cursor: &dvec2, cursor: &dvec2) -> bool {
+            let (prev_len, len) = (prev_cursor.length(), cursor.length());
+            if prev_len < AIM_SNAP_MIN_CURSOR_LEN || len < AIM_SNAP_MIN_CURSOR_LEN {
+                return false;
+            }
+            let cos = (dvec2::dot(prev_cursor, cursor) / (prev_len * len)).clamp(-1.0, 1.0);
+            cos.acos() >= AIM_SNAP_ANGLE
+        }
+
+        fn is_repeating(pattern: &VecDeque<PatternSample>) -> bool {
+            (2..=PATTERN_MAX_PERIOD).any(|period| {
+                let moves = (1..period).any(|i| pattern[i].cursor != pattern[0].cursor);
+                moves && (period..pattern.len()).all(|i| pattern[i] == pattern[i - period])
+            })
+        }
+
+        /// Checks the input of a tick.
+        ///
+        /// `pos` is the position of the character,
+        /// `None` if the player has no living character.
+        pub fn check(
+            &mut self,
+            inp: &CharacterInput,
+            diff: &CharacterInputConsumableDiff,
+            pos: Option<vec2>,
+        ) -> InputDetections {
+            let Some(pos) = pos else {
+                self.skip_next();
+                return InputDetections::empty();
+            };
+            let teleported = self
+                .prev_pos
+                .replace(pos)
+                .is_some_and(|prev_pos| distance(&prev_pos, &pos) > TELEPORT_DISTANCE);
+            let cursor = inp.cursor.to_vec2();
+            let prev_cursor = self.prev_cursor.replace(cursor);
+            if std::mem::take(&mut self.skip_next) || teleported {
+                self.presses.clear();
+                self.pattern.clear();
+                return InputDetections::empty();
+            }
+
+            let mut detections = InputDetections::empty();
+
+            if diff.fire.is_some()
+                && prev_cursor.is_some_and(|prev_cursor| Self::is_aim_snap(&prev_cursor, &cursor))
+            {
+                detections |= InputDetections::AIM_SNAP;
+                self.suspicion += AIM_SNAP_SUSPICION;
+            }
+
+            self.presses.push_back(
+                diff.jump.map(|n| n.get()).unwrap_or_default()
+                    + diff.fire.map(|(n, _)| n.get()).unwrap_or_default()
+                    + diff.hook.map(|(n, _)| n.get()).unwrap_or_default(),
+            );
+            if self.presses.len() > TICKS_PER_SECOND as usize {
+                self.presses.pop_front();
+            }
+            if self.presses.iter().sum::<u64>() > MAX_PRESSES_PER_SECOND {
+                detections |= InputDetections::INPUT_FREQUENCY;
+                self.suspicion += INPUT_FREQUENCY_SUSPICION;
+                self.presses.clear();
+            }
+
+            self.pattern.push_back(PatternSample {
+                cursor: *inp.cursor,
+                state: inp.state,
+            });
+            if self.pattern.len() > PATTERN_WINDOW {
+                self.pattern.pop_front();
+            }
+            if self.pattern.len() == PATTERN_WINDOW && Self::is_repeating(&self.pattern) {
+                detections |= InputDetections::REPEATING_PATTERN;
+                self.suspicion += PATTERN_SUSPICION;
+                self.pattern.clear();
+            }
+
+            self.detections |= detections;
+            detections
+        }
+    }
+}
@@ -0,0 +1,215 @@
+/// bans & mutes of accounts and ip ranges
+pub mod bans {
+    use std::{collections::BTreeMap, fmt::Display, net::IpAddr, str::FromStr, time::Duration};
+
+    use anyhow::anyhow;
+    use ddnet_accounts_types::account_id::AccountId;
+    use ipnet::IpNet;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum BanKind {
+        /// The player can't join.
+        Ban,
+        /// The chat messages of the player are dropped.
+        Mute,
+    }
+
+    impl BanKind {
+        /// How the kind is stored in the database.
+        pub fn to_db(self) -> i64 {
+            match self {
+                BanKind::Ban => 0,
+                BanKind::Mute => 1,
+            }
+        }
+
+        pub fn from_db(kind: i64) -> anyhow::Result<Self> {
+            match kind {
+                0 => Ok(BanKind::Ban),
+                1 => Ok(BanKind::Mute),
+                _ => Err(anyhow!("unknown ban kind: {kind}")),
+            }
+        }
+    }
+
+    impl Display for BanKind {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                BanKind::Ban => write!(f, "ban"),
+                BanKind::Mute => write!(f, "mute"),
+            }
+        }
+    }
+
+    /// Who is banned or muted.
+    ///
+    /// Written as the plain account id or as ip (range), e.g. `1.2.3.0/24`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum BanTarget {
+        Account(AccountId),
+        Ip(IpNet),
+    }
+
+    impl Display for BanTarget {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                BanTarget::Account(account_id) => write!(f, "{account_id}"),
+                BanTarget::Ip(ip) => write!(f, "{ip}"),
+            }
+        }
+    }
+
+    impl FromStr for BanTarget {
+        type Err = anyhow::Error;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            if let Ok(account_id) = s.parse() {
+                Ok(BanTarget::Account(account_id))
+            } else if let Ok(ip) = s.parse() {
+                Ok(BanTarget::Ip(ip))
+            } else if let Ok(ip) = s.parse::<IpAddr>() {
+                Ok(BanTarget::Ip(ip.into()))
+            } else {
+                Err(anyhow!(
+                    "{s} is neither an account id nor an ip (range), e.g. 1.2.3.0/24"
+                ))
+            }
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct BanEntry {
+        pub reason: String,
+        /// When the entry expires, relative to the clock of the [`BanList`].
+        /// `None` for permanent entries.
+        until: Option<Duration>,
+    }
+
+    /// Who a joined player is, to find the matching entries.
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct BanIdentity {
+        pub account_id: Option<AccountId>,
+        pub ip: Option<IpAddr>,
+    }
+
+    impl BanIdentity {
+        /// The target for new entries of this player,
+        /// the account if any, else the single ip.
+        pub fn target(&self) -> Option<BanTarget> {
+            self.account_id
+                .map(BanTarget::Account)
+                .or_else(|| self.ip.map(|ip| BanTarget::Ip(ip.into())))
+        }
+    }
+
+    /// The ban state of a joined player.
+    #[derive(Debug, Default)]
+    pub struct PlayerBanState {
+        pub identity: BanIdentity,
+        /// Whether the server was told to mute the player.
+        pub muted: bool,
+        pub kicked: bool,
+    }
+
+    /// The active bans & mutes.
+    ///
+    /// The list has its own clock that is advanced by the game,
+    /// all durations are relative to it.
+    #[derive(Debug, Default)]
+    pub struct BanList {
+        now: Duration,
+        entries: BTreeMap<(BanKind, BanTarget), BanEntry>,
+    }
+
+    impl BanList {
+        /// Advances the clock & removes the expired entries.
+        pub fn advance(&mut self, by: Duration) {
+            self.now += by;
+            let now = self.now;
+            self.entries
+                .retain(|_, entry| entry.until.is_none_or(|until| until > now));
+        }
+
+        /// `duration` is `None` for permanent entries.
+        /// An existing entry for the same target is replaced.
+        pub fn add(
+            &mut self,
+            kind: BanKind,
+            target: BanTarget,
+            duration: Option<Duration>,
+            reason: String,
+        ) {
+            self.entries.insert(
+                (kind, target),
+                BanEntry {
+                    reason,
+                    until: duration.map(|duration| self.now + duration),
+                },
+            );
+        }
+
+        pub fn remove(&mut self, kind: BanKind, target: &BanTarget) -> bool {
+            self.entries.remove(&(kind, *target)).is_some()
+        }
+
+        /// Finds the entry that applies to the player.
+        ///
+        /// An entry for the account takes precedence over
+        /// entries for the ip, the most specific ip range wins.
+        pub fn find(&self, kind: BanKind, identity: &BanIdentity) -> Option<&BanEntry> {
+            identity
+                .account_id
+                .and_then(|account_id| self.entries.get(&(kind, BanTarget::Account(account_id))))
+                .or_else(|| {
+                    let ip = identity.ip?;
+                    self.entries
+                        .iter()
+                        .filter_map(|((entry_kind, target), entry)| match target {
+                            BanTarget::Ip(net) if *entry_kind == kind && net.contains(&ip) => {
+                                Some((net.prefix_len(), entry))
+                            }
+                            _ => None,
+                        })
+                        .max_by_key(|(prefix_len, _)| *prefix_len)
+                        .map(|(_, entry)| entry)
+                })
+        }
+
+        /// The remaining time of the entry, `None` for permanent entries.
+        pub fn remaining(&self, entry: &BanEntry) -> Option<Duration> {
+            entry.until.map(|until| until.saturating_sub(self.now))
+        }
+
+        pub fn entries(&self) -> impl Iterator<Item = (BanKind, &BanTarget, &BanEntry)> {
+            self.entries
+                .iter()
+                .map(|((kind, target), entry)| (*kind, target, entry))
+        }
+    }
+
+    /// E.g. `for 1h 5min` or `permanently`.
+    pub fn fmt_remaining(remaining: Option<Duration>) -> String {
+        match remaining {
+            Some(remaining) => {
+                // round up, a ban never shows 0 minutes
+                let mins = (remaining.as_secs_f64() / 60.0).ceil() as u64;
+                match (mins / 60, mins % 60) {
+                    (0, mins) => format!("for {mins}min"),
+                    (hours, 0) => format!("for {hours}h"),
+                    (hours, mins) => format!("for {hours}h {mins}min"),
+                }
+            }
+            None => "permanently".to_string(),
+        }
+    }
+
+    /// The message a banned player is kicked with.
+    pub fn ban_msg(list: &BanList, entry: &BanEntry) -> String {
+        let remaining = fmt_remaining(list.remaining(entry));
+        if entry.reason.is_empty() {
+            format!("You are banned {remaining}.")
+        } else {
+            format!("You are banned {remaining}: {}", entry.reason)
+        }
+    }
+}
@@ -0,0 +1,168 @@
+pub mod bots {
+    use std::collections::VecDeque;
+
+    use base::linked_hash_map_view::FxLinkedHashMap;
+    use game_interface::types::{
+        id_types::PlayerId,
+        input::{CharacterInput, CharacterInputInfo, cursor::CharacterInputCursor},
+    };
+    use math::math::{
+        distance,
+        vector::{dvec2, vec2},
+    };
+
+    /// A waypoint counts as reached if the bot is closer than this.
+    const WAYPOINT_REACHED_DISTANCE: f32 = 64.0;
+    /// How many ticks a patrolling bot waits between two jumps.
+    const PATROL_JUMP_INTERVAL: u64 = 25;
+
+    /// What a bot does every tick.
+    #[derive(Debug, Clone)]
+    pub enum BotMode {
+        /// Does not send any input.
+        Idle,
+        /// Walks from one waypoint to the next in a loop
+        /// and jumps if the next waypoint is higher.
+        Patrol { waypoints: Vec<vec2> },
+        /// Replays the inputs of another player with a delay,
+        /// like the classic dummy.
+        Mirror {
+            target: PlayerId,
+            delay_ticks: usize,
+        },
+    }
+
+    #[derive(Debug)]
+    struct Bot {
+        mode: BotMode,
+
+        /// The last input that was sent for this bot.
+        inp: CharacterInput,
+        /// Inputs of the mirrored player that are not replayed yet.
+        queue: VecDeque<CharacterInput>,
+        /// The waypoint the bot currently patrols to.
+        cur_waypoint: usize,
+    }
+
+    /// Server-side players without a client.
+    ///
+    /// The bots occupy normal player slots, this only generates
+    /// the input of the bots every tick.
+    #[derive(Debug, Default)]
+    pub struct Bots {
+        bots: FxLinkedHashMap<PlayerId, Bot>,
+        ticks: u64,
+    }
+
+    impl Bots {
+        pub fn add(&mut self, id: PlayerId, mode: BotMode) {
+            self.bots.insert(
+                id,
+                Bot {
+                    mode,
+                    inp: Default::default(),
+                    queue: Default::default(),
+                    cur_waypoint: 0,
+                },
+            );
+        }
+
+        pub fn remove(&mut self, id: &PlayerId) -> bool {
+            self.bots.remove(id).is_some()
+        }
+
+        pub fn is_bot(&self, id: &PlayerId) -> bool {
+            self.bots.contains_key(id)
+        }
+
+        pub fn ids(&self) -> Vec<PlayerId> {
+            self.bots.keys().copied().collect()
+        }
+
+        pub fn len(&self) -> usize {
+            self.bots.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.bots.is_empty()
+        }
+
+        fn patrol_inp(
+            mut inp: CharacterInput,
+            cur_waypoint: &mut usize,
+            waypoints: &[vec2],
+            pos: Option<vec2>,
+            ticks: u64,
+        ) -> CharacterInput {
+            let Some(pos) = pos.filter(|_| !waypoints.is_empty()) else {
+                return inp;
+            };
+            let mut target = waypoints[*cur_waypoint % waypoints.len()];
+            if distance(&pos, &target) < WAYPOINT_REACHED_DISTANCE {
+                *cur_waypoint = (*cur_waypoint + 1) % waypoints.len();
+                target = waypoints[*cur_waypoint];
+            }
+
+            let dir = target - pos;
+            inp.state.dir.set(if dir.x > 16.0 {
+                1
+            } else if dir.x < -16.0 {
+                -1
+            } else {
+                0
+            });
+            let jump = dir.y < -32.0 && ticks % PATROL_JUMP_INTERVAL == 0;
+            if jump && !*inp.state.jump {
+                inp.consumable.jump.add(1);
+            }
+            inp.state.jump.set(jump);
+            inp.cursor.set(CharacterInputCursor::from_vec2(&dvec2::new(
+                dir.x as f64,
+                dir.y as f64,
+            )));
+            inp
+        }
+
+        /// Generates the input of all bots for the current tick.
+        ///
+        /// `input_of` returns the current input of a player,
+        /// `pos_of` the position of a player's character.
+        pub fn tick(
+            &mut self,
+            input_of: impl Fn(&PlayerId) -> Option<CharacterInput>,
+            pos_of: impl Fn(&PlayerId) -> Option<vec2>,
+        ) -> Vec<(PlayerId, CharacterInputInfo)> {
+            let ticks = self.ticks;
+            self.ticks += 1;
+
+            self.bots
+                .iter_mut()
+                .filter_map(|(id, bot)| {
+                    let inp = match &bot.mode {
+                        BotMode::Idle => return None,
+                        BotMode::Patrol { waypoints } => Self::patrol_inp(
+                            bot.inp,
+                            &mut bot.cur_waypoint,
+                            waypoints,
+                            pos_of(id),
+                            ticks,
+                        ),
+                        BotMode::Mirror {
+                            target,
+                            delay_ticks,
+                        } => {
+                            bot.queue.push_back(input_of(target)?);
+                            if bot.queue.len() <= *delay_ticks {
+                                return None;
+                            }
+                            bot.queue.pop_front()?
+                        }
+                    };
+                    let diff = inp.consumable.diff(&bot.inp.consumable);
+                    bot.inp = inp;
+                    Some((*id, CharacterInputInfo { inp, diff }))
+                })
+                .collect()
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::{BTreeMap, HashMap};
+
+use base::network_string::NetworkString;
+use command_parser::parser::{self, CommandArg, CommandParseResult, CommandType};
+use game_interface::chat_commands::{ChatCommandInfo, ChatCommands};
+
+/// How many commands a single page of `/help` lists.
+pub const HELP_PAGE_SIZE: usize = 5;
+
+/// Who is allowed to use a chat command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChatCommandPermission {
+    /// Every client, including spectators.
+    Everyone,
+    /// Only players that currently have a character in their stage.
+    Playing,
+}
+
+/// A single chat command, the schema for the parser,
+/// the help text and the handler of type `T`.
+#[derive(Debug, Clone)]
+pub struct ChatCommand<T> {
+    pub args: Vec<CommandArg>,
+    /// The amount of leading [`ChatCommand::args`] that must be given,
+    /// the remaining ones are optional.
+    pub required_args: usize,
+    pub usage: NetworkString<1024>,
+    pub description: NetworkString<1024>,
+    pub permission: ChatCommandPermission,
+    pub cmd: T,
+}
+
+/// All chat commands of the mod, builds the parser input,
+/// validates parsed commands and generates the help text.
+#[derive(Debug)]
+pub struct ChatCommandRegistry<T> {
+    cmds: BTreeMap<NetworkString<1024>, ChatCommand<T>>,
+    pub parser: HashMap<NetworkString<1024>, Vec<CommandArg>>,
+}
+
+impl<T: Copy> ChatCommandRegistry<T> {
+    pub fn new(cmds: BTreeMap<NetworkString<1024>, ChatCommand<T>>) -> Self {
+        let parser = cmds
+            .iter()
+            .map(|(name, cmd)| (name.clone(), cmd.args.clone()))
+            .collect();
+        Self { cmds, parser }
+    }
+
+    pub fn by_ident(&self, ident: &str) -> Option<&ChatCommand<T>> {
+        self.cmds.get(ident)
+    }
+
+    pub fn cmd_list(&self) -> &BTreeMap<NetworkString<1024>, ChatCommand<T>> {
+        &self.cmds
+    }
+
+    /// The commands as they are sent to the clients.
+    pub fn export(&self, prefixes: Vec<char>) -> ChatCommands {
+        ChatCommands {
+            cmds: self.parser.clone(),
+            infos: self
+                .cmds
+                .iter()
+                .map(|(name, cmd)| {
+                    (
+                        name.clone(),
+                        ChatCommandInfo {
+                            usage: cmd.usage.clone(),
+                            description: cmd.description.clone(),
+                        },
+                    )
+                })
+                .collect(),
+            prefixes,
+        }
+    }
+
+    fn usage_of(name: &str, cmd: &ChatCommand<T>) -> String {
+        if cmd.usage.is_empty() {
+            format!("/{name}")
+        } else {
+            format!("/{name} {}", cmd.usage.as_str())
+        }
+    }
+
+    /// Checks the parser output against the registered commands.
+    ///
+    /// Each entry is either the handler and the parsed command,
+    /// or a message that explains the user what was wrong.
+    pub fn validate(&self, cmds: Vec<CommandType>) -> Vec<Result<(T, parser::Command), String>> {
+        let mut res = Vec::new();
+        let mut cmds = cmds.into_iter().peekable();
+        while let Some(cmd) = cmds.next() {
+            match cmd {
+                CommandType::Full(cmd) => {
+                    let Some(chat_cmd) = self.by_ident(&cmd.ident) else {
+                        res.push(Err(format!("Unknown command /{}, see /help.", cmd.ident)));
+                        continue;
+                    };
+                    // the parser treats surplus arguments as the next command
+                    if let Some(CommandType::Partial(CommandParseResult::InvalidCommandIdent {
+                        ..
+                    })) = cmds.peek()
+                    {
+                        cmds.next();
+                        res.push(Err(format!(
+                            "Too many arguments. Usage: {}",
+                            Self::usage_of(&cmd.ident, chat_cmd)
+                        )));
+                        continue;
+                    }
+                    res.push(Ok((chat_cmd.cmd, cmd)));
+                }
+                CommandType::Partial(CommandParseResult::InvalidArg {
+                    partial_cmd, err, ..
+                }) => {
+                    let Some(chat_cmd) = self.by_ident(&partial_cmd.ident) else {
+                        res.push(Err(format!(
+                            "Unknown command /{}, see /help.",
+                            partial_cmd.ident
+                        )));
+                        continue;
+                    };
+                    if partial_cmd.args.len() >= chat_cmd.required_args
+                        && err.ends_with("but not found.")
+                    {
+                        // only optional arguments are missing
+                        res.push(Ok((chat_cmd.cmd, partial_cmd)));
+                    } else {
+                        res.push(Err(format!(
+                            "{err} Usage: {}",
+                            Self::usage_of(&partial_cmd.ident, chat_cmd)
+                        )));
+                    }
+                }
+                CommandType::Partial(CommandParseResult::InvalidCommandIdent { err, .. }) => {
+                    res.push(Err(format!("Unknown command /{err}, see /help.")));
+                }
+                CommandType::Partial(cmd) => {
+                    res.push(Err(cmd.to_string()));
+                }
+            }
+        }
+        res
+    }
+
+    /// The help text for `/help [command|page]`,
+    /// one chat message per entry.
+    pub fn help(&self, arg: Option<&str>) -> Vec<String> {
+        if let Some(name) = arg.filter(|arg| arg.parse::<usize>().is_err()) {
+            let name = name.trim_start_matches('/');
+            return match self.by_ident(name) {
+                Some(cmd) => vec![
+                    format!("Usage: {}", Self::usage_of(name, cmd)),
+                    cmd.description.to_string(),
+                ],
+                None => vec![format!("Unknown command /{name}, see /help.")],
+            };
+        }
+
+        let pages = self.cmds.len().div_ceil(HELP_PAGE_SIZE).max(1);
+        let page = arg
+            .and_then(|arg| arg.parse::<usize>().ok())
+            .unwrap_or(1)
+            .clamp(1, pages);
+
+        let mut res = vec![format!("Commands (page {page}/{pages}):")];
+        res.extend(
+            self.cmds
+                .iter()
+                .skip((page - 1) * HELP_PAGE_SIZE)
+                .take(HELP_PAGE_SIZE)
+                .map(|(name, cmd)| {
+                    format!(
+                        "{} - {}",
+                        Self::usage_of(name, cmd),
+                        cmd.description.as_str()
+                    )
+                }),
+        );
+        if page < pages {
+            res.push(format!(
+                "See /help {} for more, /help <command> for details.",
+                page + 1
+            ));
+        }
+        res
+    }
+}
@@ -21,7 +21,7 @@ pub mod collision {
         vector::{ivec2, vec2},
     };
 
-    use crate::state::state::TICKS_PER_SECOND;
+    use crate::{platforms::platforms::Platforms, state::state::TICKS_PER_SECOND};
 
     #[derive(Debug, Hiarc, Copy, Clone, Serialize, Deserialize, ConfigInterface)]
     pub struct Tunings {
@@ -37,6 +37,8 @@ pub mod collision {
         pub hook_fire_speed: f32,
         pub hook_drag_accel: f32,
         pub hook_drag_speed: f32,
+        /// Multiplies the drag of hooked characters.
+        pub hook_drag_strength: f32,
         pub gravity: f32,
         pub velramp_start: f32,
         pub velramp_range: f32,
@@ -51,10 +53,17 @@ pub mod collision {
         pub grenade_curvature: f32,
         pub grenade_speed: f32,
         pub grenade_lifetime: f32,
+        /// How often a grenade bounces off walls before it explodes
+        /// on the next contact.
+        pub grenade_bounce_num: f32,
+        /// The fraction of the speed a grenade keeps after a bounce.
+        pub grenade_bounce_elasticity: f32,
         pub laser_reach: f32,
         pub laser_bounce_delay: f32,
         pub laser_bounce_num: f32,
         pub laser_bounce_cost: f32,
+        /// The maximum time in seconds a laser exists, `0` for no limit.
+        pub laser_lifetime: f32,
         pub laser_damage: f32,
         pub player_collision: f32,
         pub player_hooking: f32,
@@ -87,6 +96,7 @@ pub mod collision {
                 hook_fire_speed: 80.0,
                 hook_drag_accel: 3.0,
                 hook_drag_speed: 15.0,
+                hook_drag_strength: 1.0,
                 gravity: 0.5,
                 velramp_start: 550.0,
                 velramp_range: 2000.0,
@@ -101,10 +111,13 @@ pub mod collision {
                 grenade_curvature: 7.0,
                 grenade_speed: 1000.0,
                 grenade_lifetime: 2.0,
+                grenade_bounce_num: 0.0,
+                grenade_bounce_elasticity: 0.5,
                 laser_reach: 800.0,
                 laser_bounce_delay: 150.0,
                 laser_bounce_num: 1.0,
                 laser_bounce_cost: 0.0,
+                laser_lifetime: 0.0,
                 laser_damage: 5.0,
                 player_collision: 1.0,
                 player_hooking: 1.0,
@@ -124,6 +137,47 @@ pub mod collision {
         }
     }
 
+    /// The factor to convert a speed in units per tick at
+    /// [`TICKS_PER_SECOND`] to units per tick at the given tick rate.
+    pub fn tick_scale(ticks_per_second: u64) -> f32 {
+        TICKS_PER_SECOND as f32 / ticks_per_second as f32
+    }
+
+    impl Tunings {
+        /// The tunings are the values at [`TICKS_PER_SECOND`].
+        /// This converts the values that are applied once per tick
+        /// to another tick rate, so characters move along the same
+        /// paths independent of the tick rate.
+        ///
+        /// Speeds & impulses are in units per tick, accelerations
+        /// in units per tick², frictions are applied once per tick.
+        /// Everything that is specified in seconds stays the same.
+        pub fn per_tick(&self, ticks_per_second: u64) -> Self {
+            if ticks_per_second == TICKS_PER_SECOND {
+                return *self;
+            }
+            let s = tick_scale(ticks_per_second);
+            let speed = |v: f32| v * s;
+            let accel = |v: f32| v * s * s;
+            let friction = |v: f32| v.powf(s);
+            Self {
+                ground_control_speed: speed(self.ground_control_speed),
+                ground_control_accel: accel(self.ground_control_accel),
+                ground_friction: friction(self.ground_friction),
+                ground_jump_impulse: speed(self.ground_jump_impulse),
+                air_jump_impulse: speed(self.air_jump_impulse),
+                air_control_speed: speed(self.air_control_speed),
+                air_control_accel: accel(self.air_control_accel),
+                air_friction: friction(self.air_friction),
+                hook_fire_speed: speed(self.hook_fire_speed),
+                hook_drag_accel: accel(self.hook_drag_accel),
+                hook_drag_speed: speed(self.hook_drag_speed),
+                gravity: accel(self.gravity),
+                ..*self
+            }
+        }
+    }
+
     #[derive(
         Debug, Hiarc, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
     )]
@@ -173,7 +227,12 @@ pub mod collision {
         width: u32,
         height: u32,
 
+        /// The tunings at [`TICKS_PER_SECOND`],
+        /// see [`Collision::get_tick_tune_at`].
         pub(crate) tune_zones: [Tunings; u8::MAX as usize + 1],
+        ticks_per_second: u64,
+
+        pub(crate) platforms: Platforms,
     }
 
     // TODO: use u8 or an enum for tile indices, instead of i32
@@ -189,6 +248,7 @@ pub mod collision {
             let mut front_layer = None;
             let mut tune_layer = None;
             let mut tele_layer = None;
+            let mut switch_layer = None;
             physics_group
                 .layers
                 .into_iter()
@@ -204,7 +264,9 @@ pub mod collision {
                         tele_layer = load_all_layers.then_some(layer);
                     }
                     MapLayerPhysics::Speedup(_) => {}
-                    MapLayerPhysics::Switch(_) => {}
+                    MapLayerPhysics::Switch(layer) => {
+                        switch_layer = load_all_layers.then_some(layer);
+                    }
                     MapLayerPhysics::Tune(layer) => {
                         tune_layer = load_all_layers.then_some(layer);
                     }
@@ -267,6 +329,7 @@ pub mod collision {
                 },
                 tune_tiles,
                 tune_zones: tune_zones.try_into().unwrap(),
+                ticks_per_second: TICKS_PER_SECOND,
                 front_tiles: front_layer
                     .map(|l| l.tiles.to_vec())
                     .unwrap_or_else(|| vec![Default::default(); game_layer.tiles.len()]),
@@ -274,7 +337,10 @@ pub mod collision {
                     .map(|l| l.base.tiles.to_vec())
                     .unwrap_or_else(|| vec![Default::default(); game_layer.tiles.len()]),
                 speedup_tiles: vec![Default::default(); game_layer.tiles.len()],
-                switch_tiles: vec![Default::default(); game_layer.tiles.len()],
+                switch_tiles: switch_layer
+                    .map(|l| l.base.tiles.to_vec())
+                    .unwrap_or_else(|| vec![Default::default(); game_layer.tiles.len()]),
+                platforms: Default::default(),
             }))
         }
 
@@ -306,7 +372,7 @@ pub mod collision {
 
         #[inline(always)]
         pub fn check_point(&self, x: i32, y: i32) -> bool {
-            self.is_solid(x, y)
+            self.is_solid(x, y) || self.platforms.at(x, y).is_some()
         }
 
         pub fn check_pointf(&self, x: f32, y: f32) -> bool {
@@ -314,12 +380,25 @@ pub mod collision {
         }
 
         #[inline(always)]
-        pub fn test_box(&self, pos: &ivec2, size_param: &ivec2) -> bool {
+        fn test_box_tiles(&self, pos: &ivec2, size_param: &ivec2) -> bool {
             let size = *size_param / 2;
-            self.check_point(pos.x - size.x, pos.y + size.y)
-                || self.check_point(pos.x + size.x, pos.y + size.y)
-                || self.check_point(pos.x - size.x, pos.y - size.y)
-                || self.check_point(pos.x + size.x, pos.y - size.y)
+            self.is_solid(pos.x - size.x, pos.y + size.y)
+                || self.is_solid(pos.x + size.x, pos.y + size.y)
+                || self.is_solid(pos.x - size.x, pos.y - size.y)
+                || self.is_solid(pos.x + size.x, pos.y - size.y)
+        }
+
+        #[inline(always)]
+        pub fn test_box(&self, pos: &ivec2, size_param: &ivec2) -> bool {
+            self.test_box_tiles(pos, size_param) || self.platforms.blocks_box(pos, size_param, None)
+        }
+
+        /// Like [`Collision::test_box`], but platforms that overlap
+        /// the box at `start` are ignored.
+        #[inline(always)]
+        fn test_moving_box(&self, pos: &ivec2, size_param: &ivec2, start: &ivec2) -> bool {
+            self.test_box_tiles(pos, size_param)
+                || self.platforms.blocks_box(pos, size_param, Some(start))
         }
 
         pub fn move_point(
@@ -385,6 +464,7 @@ pub mod collision {
             if vel_distance > 0.00001 {
                 let mut last_pos_x = round_to_int(pos.x);
                 let mut last_pos_y = round_to_int(pos.y);
+                let start = ivec2::new(last_pos_x, last_pos_y);
 
                 let mut prev_last_pos_x = last_pos_x;
                 let mut prev_last_pos_y = last_pos_y;
@@ -418,15 +498,23 @@ pub mod collision {
                     }
 
                     if matches!(last_collision_coords, CollisionCoords::Unknown) {
-                        if self.test_box(&ivec2::new(new_pos_x, new_pos_y), size) {
+                        if self.test_moving_box(&ivec2::new(new_pos_x, new_pos_y), size, &start) {
                             let mut hits = 0;
 
-                            if self.test_box(&ivec2::new(last_pos_x, new_pos_y), size) {
+                            if self.test_moving_box(
+                                &ivec2::new(last_pos_x, new_pos_y),
+                                size,
+                                &start,
+                            ) {
                                 last_collision_coords = CollisionCoords::X;
                                 hits += 1;
                             }
 
-                            if self.test_box(&ivec2::new(new_pos_x, last_pos_y), size) {
+                            if self.test_moving_box(
+                                &ivec2::new(new_pos_x, last_pos_y),
+                                size,
+                                &start,
+                            ) {
                                 if matches!(last_collision_coords, CollisionCoords::X) {
                                     last_collision_coords = CollisionCoords::XY;
                                 } else {
@@ -636,7 +724,16 @@ pub mod collision {
                 }
 
                 if collisions.contains(CollisionTypes::SOLID) {
-                    if self.check_point(ix, iy) {
+                    if let Some(platform) = self.platforms.at(ix, iy) {
+                        *out_collision = pos;
+                        *out_before_collision = last_pos;
+                        return CollisionTile::Solid(if self.platforms.hookable(platform) {
+                            DdraceTileNum::Solid
+                        } else {
+                            DdraceTileNum::NoHook
+                        });
+                    }
+                    if self.is_solid(ix, iy) {
                         if !collisions.contains(CollisionTypes::HOOK_TROUGH)
                             || !self.is_hook_through(ix, iy, offset_x, offset_y, pos_0, pos_1)
                         {
@@ -707,9 +804,48 @@ pub mod collision {
             }
         }
 
+        /// Whether the game or the front layer has a tile with the index at the position.
+        pub fn is_tile_at(&self, pos: &vec2, index: u8) -> bool {
+            let tile_index = self.tile_indexf(pos.x, pos.y);
+            self.tiles[tile_index].index == index || self.front_tiles[tile_index].index == index
+        }
+
+        /// The number of the switch tile at the position, `None` if there is none.
+        pub fn switch_number_at(&self, pos: &vec2) -> Option<u8> {
+            let switch_tile = &self.switch_tiles[self.tile_indexf(pos.x, pos.y)];
+            (switch_tile.base.index > 0).then_some(switch_tile.number)
+        }
+
         pub fn get_tune_at(&self, pos: &vec2) -> &Tunings {
             let tune_tile = &self.tune_tiles[self.tile_indexf(pos.x, pos.y)];
             &self.tune_zones[tune_tile.number as usize]
         }
+
+        /// The tunings at the position, converted to the tick rate
+        /// of the simulation, see [`Tunings::per_tick`].
+        ///
+        /// Must be used by everything that applies the tunings per tick.
+        pub fn get_tick_tune_at(&self, pos: &vec2) -> Tunings {
+            self.get_tune_at(pos).per_tick(self.ticks_per_second)
+        }
+
+        pub fn ticks_per_second(&self) -> u64 {
+            self.ticks_per_second
+        }
+
+        pub fn set_ticks_per_second(&mut self, ticks_per_second: u64) {
+            self.ticks_per_second = ticks_per_second;
+        }
+
+        pub fn platforms(&self) -> &Platforms {
+            &self.platforms
+        }
+
+        /// Moves the platforms by one tick, must be called before the stages tick.
+        pub fn tick_platforms(&mut self) {
+            if !self.platforms.is_empty() {
+                self.platforms.tick(self.ticks_per_second);
+            }
+        }
     }
 }
@@ -23,10 +23,133 @@ pub mod config {
         Ctf,
     }
 
+    #[derive(
+        Debug,
+        Hiarc,
+        Default,
+        Clone,
+        Copy,
+        Serialize,
+        Deserialize,
+        ConfigInterface,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+    )]
+    pub enum ConfigBotMode {
+        #[default]
+        Idle,
+        /// Walks between the spawn points of the map.
+        Patrol,
+    }
+
+    #[derive(
+        Debug,
+        Hiarc,
+        Default,
+        Clone,
+        Copy,
+        Serialize,
+        Deserialize,
+        ConfigInterface,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+    )]
+    pub enum ConfigOvertimeMode {
+        /// The first score after the time limit wins.
+        #[default]
+        GoldenGoal,
+        /// The overtime lasts `overtime_secs`, the leading player
+        /// or side wins when it ends.
+        Timed,
+    }
+
+    #[derive(
+        Debug,
+        Hiarc,
+        Default,
+        Clone,
+        Copy,
+        Serialize,
+        Deserialize,
+        ConfigInterface,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+    )]
+    pub enum ConfigAntiCheatAction {
+        /// The inputs of the players are not checked.
+        Off,
+        /// Only writes a warning to the server log.
+        #[default]
+        Log,
+        /// Flags the account of the player in the database,
+        /// without telling the player.
+        Flag,
+        Kick,
+    }
+
+    /// A weapon characters have when they spawn.
+    #[config_default]
+    #[derive(Debug, Hiarc, Clone, Serialize, Deserialize, ConfigInterface)]
+    pub struct ConfigLoadoutWeapon {
+        /// One of `hammer`, `gun`, `shotgun`, `grenade` or `laser`.
+        #[default = "gun"]
+        #[conf_valid(length(max = 32))]
+        pub weapon: String,
+        /// A value of `-1` means unlimited ammo.
+        #[default = 10]
+        #[conf_valid(range(min = -1, max = 10))]
+        pub ammo: i32,
+    }
+
+    /// The weapons & the selected weapon of spawned characters.
+    /// Weapons that are not part of the loadout can still be picked up.
+    #[config_default]
+    #[derive(Debug, Hiarc, Clone, Serialize, Deserialize, ConfigInterface)]
+    pub struct ConfigSpawnLoadout {
+        #[default = vec![
+            ConfigLoadoutWeapon {
+                weapon: "hammer".to_string(),
+                ammo: -1,
+            },
+            ConfigLoadoutWeapon {
+                weapon: "gun".to_string(),
+                ammo: 10,
+            },
+        ]]
+        #[conf_valid(length(max = 5))]
+        pub weapons: Vec<ConfigLoadoutWeapon>,
+        /// The weapon that is selected after spawning,
+        /// must be part of `weapons`.
+        #[default = "gun"]
+        #[conf_valid(length(max = 32))]
+        pub active_weapon: String,
+    }
+
+    /// The spawn loadouts of the game types.
+    #[config_default]
+    #[derive(Debug, Hiarc, Clone, Serialize, Deserialize, ConfigInterface)]
+    pub struct ConfigSpawnLoadouts {
+        pub dm: ConfigSpawnLoadout,
+        pub ctf: ConfigSpawnLoadout,
+    }
+
     #[config_default]
     #[derive(Debug, Hiarc, Clone, Serialize, Deserialize, ConfigInterface)]
     pub struct ConfigVanilla {
         pub game_type: ConfigGameType,
+        /// How many ticks the game simulates per second.
+        /// Higher rates give a finer hit registration, the tunings
+        /// are scaled so characters move the same at every rate.
+        /// Only applies when the map is loaded.
+        #[default = 50]
+        #[conf_valid(range(min = 25, max = 100))]
+        pub ticks_per_second: u64,
         #[default = 100]
         pub score_limit: u64,
         /// A value of `0` means no time limit.
@@ -39,8 +162,72 @@ pub mod config {
         /// Time unit is seconds.
         #[default = 60]
         pub auto_side_balance_secs: u64,
+        /// Moves the players that are suggested for balancing
+        /// to the other side when the round ends,
+        /// instead of after `auto_side_balance_secs`.
+        pub auto_side_balance_at_round_end: bool,
+        /// How long a player that was moved by the balancing
+        /// is not moved again.
+        ///
+        /// Time unit is seconds.
+        #[default = 300]
+        pub side_balance_immunity_secs: u64,
+        /// How long the warmup before the first round lasts at most.
+        /// The round starts earlier, if enough players are ready.
+        /// A value of `0` means no warmup.
+        ///
+        /// Time unit is seconds.
+        #[default = 0]
+        pub warmup_secs: u64,
+        /// How many percent of the players (without bots) must be ready
+        /// to end the warmup.
+        #[default = 100]
+        #[conf_valid(range(min = 1, max = 100))]
+        pub warmup_ready_percentage: u32,
+        /// How a round continues if the scores are tied
+        /// when the time limit is reached.
+        pub overtime_mode: ConfigOvertimeMode,
+        /// How long a timed overtime lasts.
+        ///
+        /// Time unit is seconds.
+        #[default = 60]
+        #[conf_valid(range(min = 1, max = 3600))]
+        pub overtime_secs: u64,
+        /// How often a timed overtime is repeated,
+        /// if the scores are still tied when it ends.
+        /// Afterwards the next score wins.
+        #[conf_valid(range(min = 0, max = 100))]
+        pub overtime_repeats: u32,
+        /// Reaching the score limit ends a timed overtime early.
+        #[default = true]
+        pub overtime_score_limit: bool,
+        /// Characters that die during the overtime don't respawn,
+        /// the last player or side standing wins.
+        pub overtime_no_respawn: bool,
+        /// The flags return to their stands when an overtime starts.
+        pub overtime_reset_flags: bool,
         pub allow_stages: bool,
         pub friendly_fire: bool,
+        /// Only send entities to a client that are in the view of its players
+        /// or of the characters they spectate.
+        /// Prevents dead players & spectators from seeing the whole map.
+        pub view_culling: bool,
+        /// Entities that are farther away from the characters
+        /// (or the camera) of a client are not sent to it,
+        /// which saves bandwidth on large maps.
+        /// Should be bigger than the view of the players.
+        /// A value of `0` sends all entities.
+        ///
+        /// Unit is tiles.
+        #[default = 0]
+        pub interest_radius: u32,
+        /// Entities that were already sent stay until they are
+        /// this much farther away than `interest_radius`,
+        /// so they don't flicker at the border.
+        ///
+        /// Unit is tiles.
+        #[default = 8]
+        pub interest_hysteresis: u32,
         pub laser_hit_self: bool,
         /// The maximum allowed players that are allowed to join the game.
         /// All other connected clients will instead be spectators.
@@ -52,6 +239,152 @@ pub mod config {
         /// even if not in range. Since this potentially allows cheating
         /// this is false for vanilla
         pub allow_player_vote_cam: bool,
+        /// How many server-side bots are spawned,
+        /// as soon as the first player joined.
+        ///
+        /// Bots occupy normal player slots.
+        #[conf_valid(range(min = 0, max = 1000))]
+        pub bot_count: u32,
+        /// What the bots spawned by `bot_count` do.
+        pub bot_mode: ConfigBotMode,
+        /// Bots can't be voted to join the spectators
+        /// and are not followed by the player vote cam.
+        #[default = true]
+        pub bots_exclude_from_votes: bool,
+        /// Bots are not counted when balancing the sides.
+        #[default = true]
+        pub bots_exclude_from_balancing: bool,
+        /// Adds the statistics of every finished round
+        /// to the accounts of the players in the database.
+        pub save_round_stats: bool,
+        /// Ignores the config variables & commands of the map,
+        /// e.g. the game type or global tunes.
+        pub ignore_map_settings: bool,
+        /// What happens to players whose inputs reached
+        /// the suspicion threshold of the anti-cheat.
+        pub anti_cheat_action: ConfigAntiCheatAction,
+        /// The suspicion at which the anti-cheat action is taken.
+        /// Every detection adds between 10 & 25 suspicion,
+        /// every second 10 suspicion decay.
+        #[default = 100]
+        #[conf_valid(range(min = 1, max = 100000))]
+        pub anti_cheat_threshold: u32,
+        /// How many percent of the carrier's velocity a dropped flag keeps.
+        #[default = 50]
+        #[conf_valid(range(min = 0, max = 100))]
+        pub flag_drop_velocity_percentage: u32,
+        /// How many percent of its velocity a dropped flag keeps,
+        /// when it bounces off a wall.
+        #[default = 50]
+        #[conf_valid(range(min = 0, max = 100))]
+        pub flag_bounce_percentage: u32,
+        /// After how long a dropped flag returns to its base.
+        ///
+        /// Time unit is seconds.
+        #[default = 30]
+        #[conf_valid(range(min = 1, max = 3600))]
+        pub flag_return_secs: u32,
+        /// How long the player that dropped a flag can't pick it up again.
+        ///
+        /// Time unit is milliseconds.
+        #[default = 500]
+        #[conf_valid(range(min = 0, max = 60000))]
+        pub flag_regrab_delay_ms: u32,
+        /// Characters drop their strongest weapon on death,
+        /// as a pickup with the ammo they had left.
+        /// Hammer & gun are never dropped.
+        pub weapon_drop_on_death: bool,
+        /// After how long a dropped weapon disappears.
+        ///
+        /// Time unit is seconds.
+        #[default = 10]
+        #[conf_valid(range(min = 1, max = 3600))]
+        pub weapon_drop_despawn_secs: u32,
+        /// Whether players can hook other players.
+        #[default = true]
+        pub player_hooking: bool,
+        /// How many percent of the normal drag a hook applies
+        /// to the hooked player.
+        /// Multiplies the `hook_drag_strength` tune.
+        #[default = 100]
+        #[conf_valid(range(min = 0, max = 1000))]
+        pub hook_drag_percentage: u32,
+        /// Hooking a teammate counts as hook assistance
+        /// in the round statistics.
+        pub hook_assist_stats: bool,
+        /// The damage a hooked enemy takes every `hook_damage_interval_ms`.
+        /// A value of `0` means hooks don't damage.
+        #[conf_valid(range(min = 0, max = 10))]
+        pub hook_damage: u32,
+        /// Time unit is milliseconds.
+        #[default = 1000]
+        #[conf_valid(range(min = 20, max = 60000))]
+        pub hook_damage_interval_ms: u32,
+        /// The index of damage tiles in the game & front layer.
+        /// Characters standing on them take damage periodically.
+        /// A value of `0` means there are no damage tiles.
+        #[conf_valid(range(min = 0, max = 255))]
+        pub damage_tile_index: u32,
+        /// The damage a character takes every `damage_tile_interval_ms`,
+        /// while it stands on a damage tile.
+        /// The armor is always damaged first.
+        #[default = 1]
+        #[conf_valid(range(min = 1, max = 10))]
+        pub damage_tile_damage: u32,
+        /// Time unit is milliseconds.
+        #[default = 500]
+        #[conf_valid(range(min = 20, max = 60000))]
+        pub damage_tile_interval_ms: u32,
+        /// How long spawned characters are immune to damage.
+        /// Firing a weapon or hooking an enemy ends it early,
+        /// flag carriers are never protected.
+        ///
+        /// Time unit is milliseconds.
+        #[conf_valid(range(min = 0, max = 60000))]
+        pub spawn_protection_ms: u32,
+        /// The weapons characters have when they spawn, per game type.
+        pub spawn_loadouts: ConfigSpawnLoadouts,
+        /// How long players that were kicked by a vote are banned.
+        /// A value of `0` means vote kicks don't ban.
+        ///
+        /// Time unit is minutes.
+        #[default = 5]
+        #[conf_valid(range(min = 0, max = 10080))]
+        pub vote_kick_ban_mins: u32,
+        /// The message of the day, shown to players when they join.
+        /// An empty message shows nothing.
+        #[default = "alpha version vanilla."]
+        #[conf_valid(length(max = 1024))]
+        pub motd: String,
+        /// The chat message when a player joins.
+        /// `{name}` is replaced by the name of the player,
+        /// `{players}` by the number of players on the server.
+        /// An empty message uses the default join message.
+        #[default = ""]
+        #[conf_valid(length(max = 256))]
+        pub join_msg: String,
+        /// The chat message when a player leaves,
+        /// with the same placeholders as `join_msg`.
+        /// An empty message uses the default leave message.
+        #[default = ""]
+        #[conf_valid(length(max = 256))]
+        pub leave_msg: String,
+        /// Messages that are sent to the chat in turn,
+        /// one every `announcement_interval_mins`.
+        #[default = Vec::new()]
+        #[conf_valid(length(max = 32))]
+        pub announcements: Vec<String>,
+        /// A value of `0` means no announcements.
+        ///
+        /// Time unit is minutes.
+        #[default = 5]
+        #[conf_valid(range(min = 0, max = 1440))]
+        pub announcement_interval_mins: u32,
+        /// Announcements pause while fewer players (without bots)
+        /// are on the server.
+        #[default = 1]
+        #[conf_valid(range(min = 0, max = 1000))]
+        pub announcement_min_players: u32,
     }
 
     /// Wraps vanilla config for the console chain
@@ -20,7 +20,8 @@ pub mod character {
             GameBuffNinjaEventSound, GameBuffSoundEvent, GameCharacterEffectEvent,
             GameCharacterEventEffect, GameCharacterEventSound, GameCharacterSoundEvent,
             GameWorldAction, GameWorldActionKillWeapon, GameWorldEntityEffectEvent,
-            GameWorldEntitySoundEvent, GameWorldEvent, GameWorldNotificationEvent, KillFlags,
+            GameWorldEntitySoundEvent, GameWorldEvent, GameWorldHitEvent,
+            GameWorldNotificationEvent, KillFlags,
         },
         pooling::GamePooling,
         types::{
@@ -39,26 +40,26 @@ pub mod character {
     };
     use hiarc::{Hiarc, hiarc_safer_rc_refcell};
     use legacy_map::mapdef_06::DdraceTileNum;
-    use map::map::groups::layers::tiles::Tile;
+    use map::map::groups::layers::{physics::MapPhysicsPlatformCrush, tiles::Tile};
     use pool::{datatypes::PoolFxLinkedHashMap, mt_pool::Pool as MtPool};
     use rustc_hash::FxHashSet;
 
     use super::{
         core::character_core::{Core, CoreEvents, CorePipe, CoreReusable, PHYSICAL_SIZE},
-        hook::character_hook::{CharacterHook, Hook, HookedCharacters},
+        hook::character_hook::{CharacterHook, Hook, HookState, HookedCharacters},
         player::player::{PlayerInfo, Players, SpectatorPlayer, SpectatorPlayers},
         pos::character_pos::{CharacterPos, CharacterPositionPlayfield},
         score::character_score::{CharacterScore, CharacterScores},
     };
     use crate::{
-        collision::collision::{Collision, CollisionTile, CollisionTypes, HitTile},
+        collision::collision::{Collision, CollisionTile, CollisionTypes, HitTile, tick_scale},
         entities::entity::entity::{DropMode, Entity, EntityInterface, EntityTickResult},
         events::events::{CharacterDespawnType, CharacterEvent, CharacterTickEvent},
+        loadout::loadout::SpawnLoadout,
         simulation_pipe::simulation_pipe::{
             GameWorldPendingEvents, SimulationEventWorldEntityType, SimulationPipeCharacter,
             SimulationWorldEvents,
         },
-        state::state::TICKS_PER_SECOND,
         types::types::GameOptions,
         weapons::definitions::weapon_def::Weapon,
     };
@@ -84,6 +85,7 @@ pub mod character {
 
     pub enum DamageBy {
         Ninja,
+        Hook,
         Weapon {
             weapon: WeaponType,
             flags: KillFlags,
@@ -98,6 +100,16 @@ pub mod character {
         pub interact_val: f32,
     }
 
+    /// Damage tiles hurt characters periodically,
+    /// while they stand on them.
+    #[derive(Debug, Hiarc, Clone, Copy)]
+    pub struct DamageTileOptions {
+        /// The tile index in the game & front layer.
+        pub index: u8,
+        pub damage: u32,
+        pub interval_ticks: GameTickType,
+    }
+
     #[derive(Debug, Hiarc, Default, Serialize, Deserialize, Copy, Clone)]
     pub struct CharacterCoreMod {}
 
@@ -119,6 +131,10 @@ pub mod character {
         pub cur_emoticon: Option<EmoticonType>,
 
         pub side: Option<MatchSide>,
+        /// How many ticks the character is already on its current side.
+        pub side_ticks: GameTickType,
+        /// The character is not moved by the side balancing, while this is active.
+        pub balance_immunity: GameTickCooldown,
 
         pub eye: TeeEye,
         pub normal_eye_in: GameTickCooldown,
@@ -133,6 +149,11 @@ pub mod character {
         /// this is a hint, not a logic variable.
         pub is_timeout: bool,
 
+        /// The character is immune to damage, while this is active.
+        pub spawn_protection: GameTickCooldown,
+        /// The next damage tile damage is delayed, while this is active.
+        pub damage_tile_in: GameTickCooldown,
+
         pub modifications: CharacterCoreMod,
     }
 
@@ -461,8 +482,16 @@ pub mod character {
             side: Option<MatchSide>,
             game_options: GameOptions,
         ) -> Self {
-            let (core, reusable_core, pos) =
-                Self::respawn(None, character_pool, side, player_input, &player_info, pos);
+            let (mut core, reusable_core, pos) = Self::respawn(
+                None,
+                character_pool,
+                side,
+                player_input,
+                &player_info,
+                pos,
+                &game_options.spawn_loadout(),
+            );
+            core.spawn_protection = game_options.spawn_protection_ticks().into();
 
             if let CharacterPlayerTy::Player { players, .. } = &ty {
                 players.insert(
@@ -503,22 +532,11 @@ pub mod character {
             }
         }
 
-        fn respawn_weapons(reusable_core: &mut CharacterReusableCore) {
-            let gun = Weapon {
-                cur_ammo: Some(10),
-                next_ammo_regeneration_tick: 0.into(),
-            };
-            let hammer = Weapon {
-                cur_ammo: None,
-                next_ammo_regeneration_tick: 0.into(),
-            };
+        fn respawn_weapons(reusable_core: &mut CharacterReusableCore, loadout: &SpawnLoadout) {
             reusable_core.weapons.clear();
-            reusable_core.weapons.insert(WeaponType::Hammer, hammer);
-            reusable_core.weapons.insert(WeaponType::Gun, gun);
-        }
-
-        fn default_active_weapon() -> WeaponType {
-            WeaponType::Gun
+            for (ty, weapon) in loadout.weapons() {
+                reusable_core.weapons.insert(ty, weapon);
+            }
         }
 
         /// Call this and you can't forget to reset anything important
@@ -530,18 +548,19 @@ pub mod character {
             player_input: CharacterInput,
             player_info: &PlayerInfo,
             pos: vec2,
+            loadout: &SpawnLoadout,
         ) -> (CharacterCore, PoolCharacterReusableCore, vec2) {
             let mut core = CharacterCore {
                 side,
                 health: 10,
                 armor: 0,
                 input: player_input,
-                active_weapon: Self::default_active_weapon(),
+                active_weapon: loadout.active_weapon,
                 ..Default::default()
             };
             let mut reusable_core = character_pool.character_reusable_cores_pool.new();
 
-            Self::respawn_weapons(&mut reusable_core);
+            Self::respawn_weapons(&mut reusable_core, loadout);
 
             core.default_eye = player_info.player_info.default_eyes;
             core.eye = core.default_eye;
@@ -551,6 +570,8 @@ pub mod character {
                 core.default_eye_reset_in = prev_core.default_eye_reset_in;
                 core.eye = prev_core.default_eye;
                 core.normal_eye_in = prev_core.normal_eye_in;
+                core.side_ticks = prev_core.side_ticks;
+                core.balance_immunity = prev_core.balance_immunity;
             }
             (core, reusable_core, pos)
         }
@@ -572,7 +593,7 @@ pub mod character {
         ) {
             self.phased = CharacterPhasedState::Dead(CharacterPhaseDead::new(
                 self.base.game_element_id,
-                (TICKS_PER_SECOND / 2).into(),
+                (self.game_options.ticks_per_second() / 2).into(),
                 *self.pos.pos(),
                 self.phased_characters.clone(),
                 killer_id,
@@ -596,7 +617,7 @@ pub mod character {
         pub fn despawn_to_respawn(&mut self, create_events: bool) {
             self.phased = CharacterPhasedState::Dead(CharacterPhaseDead::new(
                 self.base.game_element_id,
-                (TICKS_PER_SECOND / 10).into(),
+                (self.game_options.ticks_per_second() / 10).into(),
                 *self.pos.pos(),
                 self.phased_characters.clone(),
                 None,
@@ -656,13 +677,13 @@ pub mod character {
                 interact_cursor_dir: vec2::default(),
                 interact_val: 0.0,
             });
-            buff.remaining_tick = (15 * TICKS_PER_SECOND).into();
-            self.core.normal_eye_in = TICKS_PER_SECOND.into();
+            buff.remaining_tick = (15 * self.game_options.ticks_per_second()).into();
+            self.core.normal_eye_in = self.game_options.ticks_per_second().into();
             self.core.eye = TeeEye::Angry;
             if !had_ninja {
                 self.core
                     .attack_recoil
-                    .advance_ticks_passed_to(TICKS_PER_SECOND);
+                    .advance_ticks_passed_to(self.game_options.ticks_per_second());
             }
         }
 
@@ -690,7 +711,7 @@ pub mod character {
                 self.reusable_core.debuffs.insert(
                     CharacterDebuff::Freeze,
                     BuffProps {
-                        remaining_tick: (TICKS_PER_SECOND * 3).into(),
+                        remaining_tick: (self.game_options.ticks_per_second() * 3).into(),
                         interact_tick: 0.into(),
                         interact_cursor_dir: Default::default(),
                         interact_val: 0.0,
@@ -734,6 +755,71 @@ pub mod character {
             res
         }
 
+        /// Moves the character with the platforms, kills it if a platform crushed it.
+        #[must_use]
+        fn handle_platforms(&mut self, collision: &Collision) -> CharacterDamageResult {
+            let crush = self.core.core.physics_platforms(&mut self.pos, collision);
+            if matches!(crush, Some(MapPhysicsPlatformCrush::Kill)) {
+                self.die(None, GameWorldActionKillWeapon::World, Default::default());
+                return CharacterDamageResult::Death;
+            }
+            CharacterDamageResult::None
+        }
+
+        /// Damages the character, while it stands on a damage tile.
+        #[must_use]
+        fn handle_damage_tiles(&mut self, collision: &Collision) -> CharacterDamageResult {
+            let Some(options) = self.game_options.damage_tile_options() else {
+                return CharacterDamageResult::None;
+            };
+            if self.core.spawn_protection.is_some()
+                || self.core.damage_tile_in.is_some()
+                || !collision.is_tile_at(self.pos.pos(), options.index)
+            {
+                return CharacterDamageResult::None;
+            }
+            self.core.damage_tile_in = options.interval_ticks.into();
+            self.take_tile_damage(options.damage)
+        }
+
+        /// Unlike other damage, damage of tiles
+        /// only hurts the health once the armor is gone.
+        #[must_use]
+        fn take_tile_damage(&mut self, dmg_amount: u32) -> CharacterDamageResult {
+            let core = &mut self.core;
+            let armor_dmg = dmg_amount.min(core.armor);
+            core.armor -= armor_dmg;
+            let health_dmg = (dmg_amount - armor_dmg).min(core.health);
+            core.health -= health_dmg;
+
+            let pos = *self.pos.pos();
+            self.create_damage_indicators(&pos, (armor_dmg + health_dmg) as usize);
+            self.push_effect(
+                pos,
+                GameWorldEntityEffectEvent::Character(GameCharacterEffectEvent::Effect(
+                    GameCharacterEventEffect::TileDamage,
+                )),
+            );
+
+            if self.core.health == 0 {
+                self.die(None, GameWorldActionKillWeapon::World, Default::default());
+                return CharacterDamageResult::Death;
+            }
+
+            self.push_sound(
+                pos,
+                GameWorldEntitySoundEvent::Character(GameCharacterSoundEvent::Sound(
+                    GameCharacterEventSound::Pain {
+                        long: dmg_amount > 2,
+                    },
+                )),
+            );
+            self.core.normal_eye_in = (self.game_options.ticks_per_second() / 2).into();
+            self.core.eye = TeeEye::Pain;
+
+            CharacterDamageResult::Damage
+        }
+
         fn set_weapon(&mut self, new_weapon: WeaponType) {
             if self.core.active_weapon == new_weapon {
                 return;
@@ -804,7 +890,7 @@ pub mod character {
         }
 
         fn create_damage_indicators(&mut self, pos: &vec2, amount: usize) {
-            self.core.last_dmg_indicator = (TICKS_PER_SECOND / 2).into();
+            self.core.last_dmg_indicator = (self.game_options.ticks_per_second() / 2).into();
 
             let start_offset = -PI * 3.0 / 4.0;
             for _ in 0..amount {
@@ -849,7 +935,11 @@ pub mod character {
             by: DamageBy,
         ) -> CharacterDamageResult {
             let core = &mut self_char.core;
-            core.core.vel += *force;
+            // forces are impulses at the reference tick rate
+            core.core.vel += *force * tick_scale(self_char.game_options.ticks_per_second());
+            if core.spawn_protection.is_some() {
+                dmg_amount = 0;
+            }
             let old_health = core.health;
             let old_armor = core.armor;
             if dmg_amount > 0 {
@@ -879,6 +969,28 @@ pub mod character {
                     DamageTypes::CharacterInMatchSide { char_id, .. } => char_id,
                 };
 
+                let (weapon, flags) = match by {
+                    DamageBy::Ninja => (GameWorldActionKillWeapon::Ninja, Default::default()),
+                    // there is no kill icon for hooks
+                    DamageBy::Hook => (GameWorldActionKillWeapon::World, Default::default()),
+                    DamageBy::Weapon { weapon, flags } => {
+                        (GameWorldActionKillWeapon::Weapon { weapon }, flags)
+                    }
+                };
+
+                if indicator_amount > 0 {
+                    self_char.simulation_events.push_world(
+                        SimulationEventWorldEntityType::Character {
+                            ev: CharacterEvent::Damage {
+                                id: *self_char_id,
+                                attacker: *id,
+                                damage: indicator_amount as u32,
+                                weapon,
+                            },
+                        },
+                    );
+                }
+
                 if *id != *self_char_id {
                     self_char.push_sound(
                         *self_char.pos.pos(),
@@ -886,17 +998,23 @@ pub mod character {
                             GameCharacterEventSound::Hit { strong: false },
                         )),
                     );
+                    if indicator_amount > 0 {
+                        // only the attacker receives this event
+                        self_char.game_pending_events.push(GameWorldEvent::Hit(
+                            GameWorldHitEvent {
+                                attacker: *id,
+                                target: *self_char_id,
+                                damage: indicator_amount as u32,
+                                weapon,
+                                killing_blow: self_char.core.health == 0,
+                            },
+                        ));
+                    }
                 }
 
                 let core = &mut self_char.core;
                 // check for death
                 if core.health == 0 {
-                    let (weapon, flags) = match by {
-                        DamageBy::Ninja => (GameWorldActionKillWeapon::Ninja, Default::default()),
-                        DamageBy::Weapon { weapon, flags } => {
-                            (GameWorldActionKillWeapon::Weapon { weapon }, flags)
-                        }
-                    };
                     self_char.die(Some(killer_id), weapon, flags);
 
                     return CharacterDamageResult::Death;
@@ -912,7 +1030,7 @@ pub mod character {
                 );
 
                 let core = &mut self_char.core;
-                core.normal_eye_in = (TICKS_PER_SECOND / 2).into();
+                core.normal_eye_in = (self_char.game_options.ticks_per_second() / 2).into();
                 core.eye = TeeEye::Pain;
 
                 CharacterDamageResult::Damage
@@ -974,7 +1092,7 @@ pub mod character {
                 && let FriendlyFireTy::Dmg = friendly_fire_ty
             {
                 killer.core.eye = TeeEye::Happy;
-                killer.core.normal_eye_in = (TICKS_PER_SECOND / 2).into();
+                killer.core.normal_eye_in = (killer.game_options.ticks_per_second() / 2).into();
             }
             res
         }
@@ -1045,13 +1163,16 @@ pub mod character {
                             },
                         )),
                     );
-                    self.core.no_ammo_sound = TICKS_PER_SECOND.into();
+                    self.core.no_ammo_sound = self.game_options.ticks_per_second().into();
                 }
                 return;
             }
 
             let proj_start_pos = *self.pos.pos() + direction * PHYSICAL_SIZE * 0.75;
 
+            // attacking ends the spawn protection
+            self.core.spawn_protection = Default::default();
+
             // TODO: check all branches. make sure no code/TODO comments are in, before removing this comment
 
             self.core.attack_recoil = match self.core.active_weapon {
@@ -1126,7 +1247,9 @@ pub mod character {
                     } else {
                         tune.hammer_fire_delay
                     };
-                    ((fire_delay * TICKS_PER_SECOND as f32 / 1000.0).ceil() as GameTickType).into()
+                    ((fire_delay * self.game_options.ticks_per_second() as f32 / 1000.0).ceil()
+                        as GameTickType)
+                        .into()
                 }
                 WeaponType::Gun => {
                     let tunings = pipe.collision.get_tune_at(&proj_start_pos);
@@ -1144,7 +1267,9 @@ pub mod character {
                     );
 
                     let fire_delay = tunings.gun_fire_delay;
-                    ((fire_delay * TICKS_PER_SECOND as f32 / 1000.0).ceil() as GameTickType).into()
+                    ((fire_delay * self.game_options.ticks_per_second() as f32 / 1000.0).ceil()
+                        as GameTickType)
+                        .into()
                 }
                 WeaponType::Shotgun => {
                     let shot_spreed: i32 = 2;
@@ -1175,7 +1300,9 @@ pub mod character {
                         .collision
                         .get_tune_at(&proj_start_pos)
                         .shotgun_fire_delay;
-                    ((fire_delay * TICKS_PER_SECOND as f32 / 1000.0).ceil() as GameTickType).into()
+                    ((fire_delay * self.game_options.ticks_per_second() as f32 / 1000.0).ceil()
+                        as GameTickType)
+                        .into()
                 }
                 WeaponType::Grenade => {
                     let tunings = pipe.collision.get_tune_at(&proj_start_pos);
@@ -1192,7 +1319,9 @@ pub mod character {
                         )),
                     );
                     let fire_delay = tunings.grenade_fire_delay;
-                    ((fire_delay * TICKS_PER_SECOND as f32 / 1000.0).ceil() as GameTickType).into()
+                    ((fire_delay * self.game_options.ticks_per_second() as f32 / 1000.0).ceil()
+                        as GameTickType)
+                        .into()
                 }
                 WeaponType::Laser => {
                     pipe.entity_events.push(CharacterTickEvent::Laser {
@@ -1209,10 +1338,27 @@ pub mod character {
                     );
 
                     let fire_delay = pipe.collision.get_tune_at(&proj_start_pos).laser_fire_delay;
-                    ((fire_delay * TICKS_PER_SECOND as f32 / 1000.0).ceil() as GameTickType).into()
+                    ((fire_delay * self.game_options.ticks_per_second() as f32 / 1000.0).ceil()
+                        as GameTickType)
+                        .into()
                 }
             };
 
+            self.simulation_events
+                .push_world(SimulationEventWorldEntityType::Character {
+                    ev: CharacterEvent::Fire {
+                        id: self.base.game_element_id,
+                        weapon: self.core.active_weapon,
+                        shots: match self.core.active_weapon {
+                            WeaponType::Shotgun => 5,
+                            WeaponType::Hammer
+                            | WeaponType::Gun
+                            | WeaponType::Grenade
+                            | WeaponType::Laser => 1,
+                        },
+                    },
+                });
+
             let cur_weapon = self
                 .reusable_core
                 .weapons
@@ -1237,13 +1383,18 @@ pub mod character {
                 return;
             };
 
+            // attacking ends the spawn protection
+            self.core.spawn_protection = Default::default();
+
             let fire_delay = collision.get_tune_at(self.pos.pos()).ninja_fire_delay;
-            self.core.attack_recoil =
-                ((fire_delay * TICKS_PER_SECOND as f32 / 1000.0).ceil() as GameTickType).into();
+            self.core.attack_recoil = ((fire_delay * self.game_options.ticks_per_second() as f32
+                / 1000.0)
+                .ceil() as GameTickType)
+                .into();
 
             let cursor = cursor.to_vec2();
             buff.interact_cursor_dir = normalize(&vec2::new(cursor.x as f32, cursor.y as f32));
-            buff.interact_tick = (TICKS_PER_SECOND / 5).into();
+            buff.interact_tick = (self.game_options.ticks_per_second() / 5).into();
             buff.interact_val = length(&self.core.core.vel);
             self.reusable_core.interactions.clear();
 
@@ -1322,7 +1473,7 @@ pub mod character {
                 {
                     self.core
                         .attack_recoil
-                        .advance_ticks_passed_to(TICKS_PER_SECOND);
+                        .advance_ticks_passed_to(self.game_options.ticks_per_second());
                 }
                 buff.remaining_tick.is_some()
             });
@@ -1345,7 +1496,9 @@ pub mod character {
                 self.core.core.vel = buff.interact_cursor_dir * buff.interact_val;
             } else {
                 // Set velocity
-                let mut vel = buff.interact_cursor_dir * 50.0;
+                let mut vel = buff.interact_cursor_dir
+                    * 50.0
+                    * tick_scale(self.game_options.ticks_per_second());
                 let old_pos = *self.pos.pos();
                 let mut new_pos = *self.pos.pos();
                 pipe.collision.move_box(
@@ -1428,7 +1581,7 @@ pub mod character {
             // ammo regen
             let ammo_regen_time = match self.core.active_weapon {
                 WeaponType::Hammer => None,
-                WeaponType::Gun => Some(TICKS_PER_SECOND / 2),
+                WeaponType::Gun => Some(self.game_options.ticks_per_second() / 2),
                 WeaponType::Shotgun => None,
                 WeaponType::Grenade => None,
                 WeaponType::Laser => None,
@@ -1479,6 +1632,7 @@ pub mod character {
         }
 
         fn handle_emoticon_queue(&mut self) {
+            let ticks_per_second = self.game_options.ticks_per_second();
             let core = &mut self.core;
             self.reusable_core
                 .queued_emoticon
@@ -1501,9 +1655,9 @@ pub mod character {
                             | EmoticonType::DEVILTEE
                             | EmoticonType::ZOMG => TeeEye::Angry,
                         };
-                        core.normal_eye_in = (2 * TICKS_PER_SECOND).into();
+                        core.normal_eye_in = (2 * ticks_per_second).into();
 
-                        core.emoticon_tick = (2 * TICKS_PER_SECOND).into();
+                        core.emoticon_tick = (2 * ticks_per_second).into();
                         core.cur_emoticon = Some(*emoticon);
                         false
                     } else {
@@ -1512,6 +1666,58 @@ pub mod character {
                 });
         }
 
+        /// Periodically damages a hooked enemy, if the server enabled hook damage.
+        fn handle_hook_damage(&mut self, pipe: &mut SimulationPipeCharacter) {
+            let Some((damage, interval)) = self.game_options.hook_damage() else {
+                return;
+            };
+            let (
+                Hook::Active {
+                    hook_tick,
+                    hook_state: HookState::HookGrabbed,
+                    ..
+                },
+                Some(hooked_id),
+            ) = self.phased.hook().get()
+            else {
+                return;
+            };
+            if hook_tick <= 0 || hook_tick as GameTickType % interval != 0 {
+                return;
+            }
+
+            let self_id = self.base.game_element_id;
+            let self_pos = *self.pos.pos();
+            let hooked_char = pipe.characters.get_other_character_by_id_mut(&hooked_id);
+            // teammates are never damaged by hooks
+            if self.core.side.is_some() && hooked_char.core.side == self.core.side {
+                return;
+            }
+            Self::take_damage(
+                &mut ((self_id, &mut *self), (hooked_id, &mut *hooked_char)),
+                &hooked_id,
+                &vec2::default(),
+                &self_pos,
+                damage,
+                DamageTypes::Character(&self_id),
+                DamageBy::Hook,
+            );
+        }
+
+        /// Hooking an enemy ends the spawn protection.
+        fn handle_spawn_protection(&mut self, pipe: &mut SimulationPipeCharacter) {
+            if self.core.spawn_protection.is_none() {
+                return;
+            }
+            let Some(hooked_id) = self.phased.hook().hooked_char() else {
+                return;
+            };
+            let hooked_char = pipe.characters.get_other_character_by_id_mut(&hooked_id);
+            if self.core.side.is_none() || hooked_char.core.side != self.core.side {
+                self.core.spawn_protection = Default::default();
+            }
+        }
+
         /// For modifications
         fn mod_tick(&mut self) {}
 
@@ -1522,6 +1728,8 @@ pub mod character {
                 self.core.last_dmg_angle = 0.0;
             }
             self.core.emoticon_tick.tick();
+            self.core.spawn_protection.tick();
+            self.core.damage_tile_in.tick();
 
             self.handle_emoticon_queue();
         }
@@ -1597,10 +1805,17 @@ pub mod character {
             self.handle_weapon_switch(None, None);
 
             let old_pos = *self.pos.pos();
+            let platforms_res = self.handle_platforms(pipe.collision);
+            if matches!(platforms_res, CharacterDamageResult::Death) {
+                return EntityTickResult::RemoveEntity;
+            }
+
+            let hook_options = self.game_options.hook_options();
             let (core, input) = self.core.get_core_mut_and_input(&self.reusable_core);
             let mut core_pipe = CorePipe {
                 characters: pipe.characters,
                 input,
+                hook_options,
             };
             core.physics_tick(
                 &mut self.pos,
@@ -1624,7 +1839,13 @@ pub mod character {
             if matches!(tiles_res, CharacterDamageResult::Death) {
                 return EntityTickResult::RemoveEntity;
             }
+            let damage_tiles_res = self.handle_damage_tiles(pipe.collision);
+            if matches!(damage_tiles_res, CharacterDamageResult::Death) {
+                return EntityTickResult::RemoveEntity;
+            }
 
+            self.handle_spawn_protection(pipe);
+            self.handle_hook_damage(pipe);
             self.handle_buffs_and_debuffs(pipe);
             self.handle_weapons(pipe);
 
@@ -1637,6 +1858,7 @@ pub mod character {
             let mut core_pipe = CorePipe {
                 characters: pipe.characters,
                 input: &self.core.input,
+                hook_options: self.game_options.hook_options(),
             };
             self.core
                 .core
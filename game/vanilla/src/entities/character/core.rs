@@ -15,6 +15,7 @@ pub mod character_core {
     };
     use hiarc::Hiarc;
     use legacy_map::mapdef_06::DdraceTileNum;
+    use map::map::groups::layers::physics::MapPhysicsPlatformCrush;
     use num::FromPrimitive;
 
     use crate::{
@@ -27,7 +28,6 @@ pub mod character_core {
         simulation_pipe::simulation_pipe::{
             GameWorldPendingEvents, SimulationPipeCharactersGetter,
         },
-        state::state::TICKS_PER_SECOND,
         world::world::GameWorld,
     };
 
@@ -111,11 +111,35 @@ pub mod character_core {
         pub has_endless: bool,
 
         pub move_restrictions: i32,
+
+        /// The velocity of the platform the character stands on,
+        /// it's added to [`Core::vel`] once the character leaves the platform.
+        pub platform_vel: vec2,
+    }
+
+    /// The server options for hooking other characters.
+    #[derive(Debug, Hiarc, Copy, Clone)]
+    pub struct HookOptions {
+        /// Whether characters can hook other characters at all.
+        pub player_hooking: bool,
+        /// Multiplies the drag of hooked characters,
+        /// on top of the `hook_drag_strength` tune.
+        pub drag_strength: f32,
+    }
+
+    impl Default for HookOptions {
+        fn default() -> Self {
+            Self {
+                player_hooking: true,
+                drag_strength: 1.0,
+            }
+        }
     }
 
     pub struct CorePipe<'a> {
         pub characters: &'a mut dyn SimulationPipeCharactersGetter,
         pub input: &'a CharacterInput,
+        pub hook_options: HookOptions,
     }
 
     impl CorePipe<'_> {
@@ -226,7 +250,7 @@ pub mod character_core {
         }
 
         fn get_gravity(collision: &Collision, pos: &vec2) -> f32 {
-            let tuning = collision.get_tune_at(pos);
+            let tuning = collision.get_tick_tune_at(pos);
             tuning.gravity
         }
 
@@ -264,7 +288,7 @@ pub mod character_core {
 
             self.vel.y += Self::get_gravity(collision, pos.pos());
 
-            let tuning = collision.get_tune_at(pos.pos());
+            let tuning = collision.get_tick_tune_at(pos.pos());
             let max_speed = if grounded {
                 tuning.ground_control_speed
             } else {
@@ -363,6 +387,20 @@ pub mod character_core {
                 self.vel.x *= friction;
             }
 
+            // a hook that grabbed a platform moves with it
+            let (mut hook_tmp, hooked_char) = char_hook.get();
+            if let Hook::Active {
+                hook_pos,
+                hook_state: HookState::HookGrabbed,
+                ..
+            } = &mut hook_tmp
+                && hooked_char.is_none()
+                && let Some(platform) = collision.platforms.hooked_at(hook_pos)
+            {
+                *hook_pos += collision.platforms.carry(platform);
+                char_hook.set(hook_tmp, hooked_char);
+            }
+
             // do hook
             let (mut hook_tmp, mut hooked_char) = char_hook.get();
             if let Hook::None = hook_tmp {
@@ -381,9 +419,9 @@ pub mod character_core {
                     hook_tmp = Hook::WaitsForRelease;
                     hooked_char = None;
                 } else if *hook_state == HookState::HookFlying {
-                    let hook_old_tunings = collision.get_tune_at(hook_pos);
+                    let hook_old_tunings = collision.get_tick_tune_at(hook_pos);
                     let mut new_pos = *hook_pos + *hook_dir * hook_old_tunings.hook_fire_speed;
-                    let hook_new_tunings = collision.get_tune_at(hook_pos);
+                    let hook_new_tunings = collision.get_tick_tune_at(hook_pos);
                     if (!self.new_hook
                         && distance_squared(pos.pos(), &new_pos)
                             > hook_new_tunings.hook_length.powf(2.0))
@@ -410,7 +448,10 @@ pub mod character_core {
                     );
 
                     // Check against other players first
-                    if !self.hook_hit_disabled && tuning.player_hooking > 0.0 {
+                    if !self.hook_hit_disabled
+                        && pipe.hook_options.player_hooking
+                        && tuning.player_hooking > 0.0
+                    {
                         let mut distance_hook = 0.0;
                         let (is_super, solo) = (self.is_super, self.solo);
                         let hook_len = length(&(new_pos - *hook_pos));
@@ -513,7 +554,7 @@ pub mod character_core {
                     }
 
                     // don't do this hook rutine when we are hook to a character
-                    let hook_tunings = collision.get_tune_at(hook_pos);
+                    let hook_tunings = collision.get_tick_tune_at(hook_pos);
                     if hooked_char.is_none()
                         && distance_squared(&*hook_pos, pos.pos()) > 46.0 * 46.0
                     {
@@ -548,8 +589,9 @@ pub mod character_core {
                     // release hook (max default hook time is 1.25 s)
                     *hook_tick += 1;
                     if hooked_char.is_some() {
-                        let hook_duration =
-                            (TICKS_PER_SECOND as f32 * hook_tunings.hook_duration) as i32;
+                        let hook_duration = (collision.ticks_per_second() as f32
+                            * hook_tunings.hook_duration)
+                            as i32;
                         if *hook_tick > hook_duration {
                             hooked_char = None;
                             hook_tmp = Hook::WaitsForRelease;
@@ -573,8 +615,9 @@ pub mod character_core {
             pipe: &mut CorePipe,
         ) {
             let hooked_player = char_hook.hooked_char();
+            let hook_options = pipe.hook_options;
 
-            let tunings = collision.get_tune_at(pos.pos());
+            let tunings = collision.get_tick_tune_at(pos.pos());
             const PHY_RANGE_COLLISION: i32 = (physical_size() * 1.25) as i32;
             let mut ids = pos.in_range(PHY_RANGE_COLLISION);
             let _ = pipe.get_other_character_id_and_cores_iter_by_ids_mut(
@@ -627,14 +670,18 @@ pub mod character_core {
                         if distance_sqr_pos > 0.0 {
                             let dir = normalize(&(*pos.pos() - *char_pos.pos()));
                             // handle hook influence
-                            let other_tunings = collision.get_tune_at(char_pos.pos());
+                            let other_tunings = collision.get_tick_tune_at(char_pos.pos());
                             if !self.hook_hit_disabled
                                 && hooked_player == *char_id
+                                && hook_options.player_hooking
                                 && other_tunings.player_hooking > 0.0
                             {
                                 let dist = distance_sqr_pos.sqrt();
                                 if dist > physical_size() * 1.50 {
+                                    let drag_strength = other_tunings.hook_drag_strength
+                                        * hook_options.drag_strength;
                                     let hook_accel = other_tunings.hook_drag_accel
+                                        * drag_strength
                                         * (dist / other_tunings.hook_length);
                                     let drag_speed = other_tunings.hook_drag_speed;
 
@@ -692,6 +739,62 @@ pub mod character_core {
             }
         }
 
+        /// Moves the character with the platform it stands on & pushes it
+        /// out of the platforms, that moved into it.
+        /// Must be called before [`Core::physics_tick`].
+        ///
+        /// Returns how the character is crushed, if a platform squeezed it into a wall.
+        pub fn physics_platforms(
+            &mut self,
+            pos: &mut CharacterPos,
+            collision: &Collision,
+        ) -> Option<MapPhysicsPlatformCrush> {
+            let platforms = &collision.platforms;
+            if platforms.is_empty() {
+                return None;
+            }
+            let size = physical_size_vec2();
+            let int_pos = |pos: &vec2| ivec2::new(round_to_int(pos.x), round_to_int(pos.y));
+            let mut new_pos = *pos.pos();
+
+            let ridden = platforms.ridden_by(&new_pos, &size);
+            match ridden {
+                Some(platform) => {
+                    // walls stop the character, even if the platform moves on
+                    let mut carry = platforms.carry(platform);
+                    collision.move_box(&mut new_pos, &mut carry, &size, 0.0);
+                }
+                None => {
+                    self.vel += self.platform_vel;
+                }
+            }
+            self.platform_vel = ridden
+                .map(|platform| platforms.delta(platform))
+                .unwrap_or_default();
+
+            let mut crush = None;
+            for platform in 0..platforms.len() {
+                let rect = platforms.rect(platform);
+                if !rect.overlaps_box(&int_pos(&new_pos), &size)
+                    || platforms
+                        .prev_rect(platform)
+                        .overlaps_box(&int_pos(&new_pos), &size)
+                {
+                    continue;
+                }
+                let mut push = platforms.push_out(platform, &new_pos, &size);
+                collision.move_box(&mut new_pos, &mut push, &size, 0.0);
+                if rect.overlaps_box(&int_pos(&new_pos), &size)
+                    && crush != Some(MapPhysicsPlatformCrush::Kill)
+                {
+                    crush = Some(platforms.crush(platform));
+                }
+            }
+
+            pos.move_pos(new_pos);
+            crush
+        }
+
         fn velocity_ramp(value: f32, start: f32, range: f32, curvature: f32) -> f32 {
             if value < start {
                 return 1.0;
@@ -705,9 +808,10 @@ pub mod character_core {
             pipe: &mut CorePipe,
             collision: &Collision,
         ) {
-            let tuning = collision.get_tune_at(char_pos.pos());
+            let tuning = collision.get_tick_tune_at(char_pos.pos());
+            // the ramp is in units per second
             let ramp_value = Self::velocity_ramp(
-                length(&self.vel) * 50.0,
+                length(&self.vel) * collision.ticks_per_second() as f32,
                 tuning.velramp_start,
                 tuning.velramp_range,
                 tuning.velramp_curvature,
@@ -733,7 +837,7 @@ pub mod character_core {
 
             self.vel.x *= 1.0 / ramp_value;
 
-            let tuning = collision.get_tune_at(char_pos.pos());
+            let tuning = collision.get_tick_tune_at(char_pos.pos());
             if self.is_super
                 || (tuning.player_collision > 0.0 && !self.collision_disabled && !self.solo)
             {
@@ -819,7 +923,7 @@ pub mod character_core {
             let mut do_break = false;
 
             loop {
-                let tune = collision.get_tune_at(&old_pos);
+                let tune = collision.get_tick_tune_at(&old_pos);
                 old_pos = new_pos;
                 new_pos = old_pos + dir * tune.hook_fire_speed;
 
@@ -11,7 +11,7 @@ pub mod player {
     use game_interface::types::input::CharacterInput;
     use game_interface::types::network_stats::PlayerNetworkStats;
     use game_interface::types::player_info::PlayerUniqueId;
-    use game_interface::types::render::character::TeeEye;
+    use game_interface::types::render::character::{CharacterServerRank, TeeEye};
     use game_interface::types::snapshot::SnapshotLocalPlayer;
     use hiarc::{HiFnMut, HiFnOnce};
     use hiarc::{Hiarc, hiarc_safer_rc_refcell};
@@ -46,6 +46,10 @@ pub mod player {
         pub account_name: Option<NetworkReducedAsciiString<MAX_ACCOUNT_NAME_LEN>>,
         /// The id given by the client to this player
         pub id: u64,
+        /// The player is a server-side bot without a client.
+        pub is_bot: bool,
+        /// Filled lazily by the database of the server.
+        pub server_rank: Option<CharacterServerRank>,
     }
 
     pub type Player = PlayerCharacterInfo;
@@ -80,6 +84,13 @@ pub mod player {
             })
         }
 
+        pub fn len(&self) -> usize {
+            self.players.len()
+        }
+        pub fn is_empty(&self) -> bool {
+            self.players.is_empty()
+        }
+
         pub(in super::super::super::character) fn insert(&mut self, id: PlayerId, player: Player) {
             self.players.insert(id, player);
         }
@@ -188,6 +199,15 @@ pub mod player {
         pub fn contains_key(&self, id: &PlayerId) -> bool {
             self.players.get(id).is_some()
         }
+        pub fn player_input(&self, id: &PlayerId) -> Option<CharacterInput> {
+            self.players.get(id).map(|p| p.player_input)
+        }
+        /// Adds the characters the spectator currently spectates to `ids`.
+        pub fn spectated_characters_into(&self, id: &PlayerId, ids: &mut FxHashSet<CharacterId>) {
+            if let Some(spectator) = self.players.get(id) {
+                ids.extend(spectator.spectated_characters.iter().copied());
+            }
+        }
         pub fn any_with_name(&self, except_id: Option<PlayerId>, name: &str) -> bool {
             self.players
                 .iter()
@@ -195,6 +215,13 @@ pub mod player {
                 .any(|p| p.player_info.player_info.name.as_str() == name)
         }
 
+        pub fn len(&self) -> usize {
+            self.players.len()
+        }
+        pub fn is_empty(&self) -> bool {
+            self.players.is_empty()
+        }
+
         pub fn insert(&mut self, id: PlayerId, player: SpectatorPlayer) {
             self.players.insert(id, player);
         }
@@ -19,8 +19,9 @@ pub mod flag {
     use serde::{Deserialize, Serialize};
 
     use crate::{
+        collision::collision::tick_scale,
         entities::{
-            character::core::character_core,
+            character::{character::CharactersViewMut, core::character_core},
             entity::entity::{DropMode, Entity, EntityInterface, EntityTickResult},
         },
         events::events::FlagEvent,
@@ -28,10 +29,25 @@ pub mod flag {
             GameWorldPendingEvents, SimulationEventWorldEntity, SimulationEventWorldEntityType,
             SimulationPipeFlag, SimulationWorldEvent, SimulationWorldEvents,
         },
-        state::state::TICKS_PER_SECOND,
         world::world::GameWorld,
     };
 
+    /// Friction that slows down a dropped flag while it lies on the ground.
+    const GROUND_FRICTION: f32 = 0.9;
+
+    /// The physics & timers of dropped flags, see the `flag_*` config variables.
+    #[derive(Debug, Hiarc, Clone, Copy)]
+    pub struct FlagOptions {
+        /// The factor of the carrier's velocity that a dropped flag keeps.
+        pub drop_vel_factor: f32,
+        /// The factor of the velocity that a flag keeps when it bounces off a wall.
+        pub elasticity: f32,
+        /// After how many ticks a dropped flag returns to its base.
+        pub return_ticks: GameTickType,
+        /// How many ticks the last carrier can't pick up the dropped flag.
+        pub regrab_delay_ticks: GameTickType,
+    }
+
     #[derive(Debug, Hiarc, Default, Serialize, Deserialize)]
     pub struct FlagReusableCore {}
 
@@ -59,7 +75,13 @@ pub mod flag {
         pub ty: FlagType,
 
         pub carrier: Option<CharacterId>,
+        /// The remaining ticks until a dropped flag returns to its base.
         pub drop_ticks: Option<GameTickType>,
+        /// The return time of the current drop in ticks.
+        pub return_ticks: GameTickType,
+        /// The last carrier & the remaining ticks until
+        /// it can pick up the flag again.
+        pub regrab_cooldown: Option<(CharacterId, GameTickType)>,
 
         /// If the flag is teleported, this is increased
         pub non_linear_event: u64,
@@ -107,11 +129,50 @@ pub mod flag {
             // prediction cannot move the flag so much, since that lerps weirdly.
             if !is_prediction {
                 self.core.pos = self.core.spawn_pos;
+                self.core.vel = Default::default();
                 self.core.drop_ticks = None;
                 self.core.carrier = None;
+                self.core.regrab_cooldown = None;
             }
         }
 
+        /// The carrier loses the flag, which keeps a part of its velocity.
+        pub(crate) fn release(&mut self, options: &FlagOptions) {
+            let Some(carrier) = self.core.carrier.take() else {
+                return;
+            };
+            self.game_pending_events.push_sound(
+                Some(carrier),
+                None,
+                GameWorldEntitySoundEvent::Flag(GameFlagEventSound::Drop),
+            );
+            self.core.vel = self.core.vel * options.drop_vel_factor;
+            self.core.drop_ticks = Some(options.return_ticks);
+            self.core.return_ticks = options.return_ticks;
+            self.core.regrab_cooldown =
+                (options.regrab_delay_ticks > 0).then_some((carrier, options.regrab_delay_ticks));
+        }
+
+        fn move_dropped(&mut self, pipe: &SimulationPipeFlag) {
+            self.core.vel.y += pipe.collision.get_tick_tune_at(&self.core.pos).gravity;
+
+            let grounded = pipe.collision.check_pointf(
+                self.core.pos.x,
+                self.core.pos.y + Self::PHYSICAL_SIZE / 2.0 + 5.0,
+            );
+            if grounded {
+                self.core.vel.x *=
+                    GROUND_FRICTION.powf(tick_scale(pipe.collision.ticks_per_second()));
+            }
+
+            pipe.collision.move_box(
+                &mut self.core.pos,
+                &mut self.core.vel,
+                &ivec2::new(Self::PHYSICAL_SIZE as i32, Self::PHYSICAL_SIZE as i32),
+                pipe.options.elasticity,
+            );
+        }
+
         /// check for capture
         fn check_captured(&mut self, carrier: CharacterId, other_team_flags: &Flags) {
             for other_flag in other_team_flags.values() {
@@ -159,16 +220,11 @@ pub mod flag {
             if let Some(carrier) = self.core.carrier {
                 if let Some(character) = pipe.characters.characters().get(&carrier) {
                     self.core.pos = *character.pos.pos();
+                    self.core.vel = character.core.core.vel;
 
                     self.check_captured(carrier, pipe.other_team_flags);
                 } else {
-                    self.game_pending_events.push_sound(
-                        Some(carrier),
-                        None,
-                        GameWorldEntitySoundEvent::Flag(GameFlagEventSound::Drop),
-                    );
-                    self.core.drop_ticks = Some(TICKS_PER_SECOND * 30);
-                    self.core.carrier = None;
+                    self.release(pipe.options);
                 }
             } else {
                 if pipe.collision.is_death(self.core.pos.x, self.core.pos.y)
@@ -177,10 +233,18 @@ pub mod flag {
                     self.reset(pipe.is_prediction);
                 }
 
-                // check if a char picked this flag up
+                // check if a char picked this flag up,
+                // the last carrier has to wait for the cooldown
+                let regrab_blocked = self.core.regrab_cooldown.map(|(id, _)| id);
+                let (characters, filter, filter_val) =
+                    pipe.characters.characters_mut().into_inner();
                 let intersection = GameWorld::intersect_character(
                     pipe.field,
-                    pipe.characters.characters_mut(),
+                    CharactersViewMut::new(
+                        characters,
+                        move |id| filter(id) && Some(*id) != regrab_blocked,
+                        filter_val,
+                    ),
                     &self.core.pos,
                     Self::PHYSICAL_SIZE as i32,
                 );
@@ -191,6 +255,14 @@ pub mod flag {
                     });
                     if char_in_side {
                         if self.core.pos != self.core.spawn_pos {
+                            self.simulation_events.push_world(
+                                SimulationEventWorldEntityType::Flag {
+                                    id: self.base.game_element_id,
+                                    ev: FlagEvent::Return {
+                                        by: intersection.base.game_element_id,
+                                    },
+                                },
+                            );
                             self.game_pending_events.push_sound(
                                 Some(intersection.base.game_element_id),
                                 None,
@@ -208,7 +280,17 @@ pub mod flag {
                             )),
                         );
                         self.core.carrier = Some(intersection.base.game_element_id);
+                        // flag carriers can't be protected
+                        intersection.core.spawn_protection = Default::default();
                         self.core.drop_ticks = None;
+                        self.core.regrab_cooldown = None;
+                    }
+                }
+
+                if let Some((_, ticks)) = &mut self.core.regrab_cooldown {
+                    *ticks = ticks.saturating_sub(1);
+                    if *ticks == 0 {
+                        self.core.regrab_cooldown = None;
                     }
                 }
 
@@ -226,14 +308,7 @@ pub mod flag {
                         std::cmp::Ordering::Greater => {
                             *drop_ticks -= 1;
 
-                            self.core.vel.y += pipe.collision.get_tune_at(&self.core.pos).gravity;
-
-                            pipe.collision.move_box(
-                                &mut self.core.pos,
-                                &mut self.core.vel,
-                                &ivec2::new(Self::PHYSICAL_SIZE as i32, Self::PHYSICAL_SIZE as i32),
-                                0.5,
-                            );
+                            self.move_dropped(pipe);
                         }
                         std::cmp::Ordering::Less => {
                             // ignore
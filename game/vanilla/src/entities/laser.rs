@@ -1,6 +1,9 @@
 pub mod laser {
     use base::linked_hash_map_view::FxLinkedHashMap;
-    use game_interface::events::{GameLaserEventSound, GameWorldEntitySoundEvent, KillFlags};
+    use game_interface::events::{
+        GameLaserEventEffect, GameLaserEventSound, GameWorldEntityEffectEvent,
+        GameWorldEntitySoundEvent, KillFlags,
+    };
     use game_interface::types::game::{
         GameTickCooldownAndLastActionCounter, GameTickType, NonZeroGameTickType,
     };
@@ -26,7 +29,6 @@ pub mod laser {
         GameWorldPendingEvents, SimulationEventWorldEntityType, SimulationPipeLaser,
         SimulationWorldEvents,
     };
-    use crate::state::state::TICKS_PER_SECOND;
     use crate::world::world::GameWorld;
 
     #[derive(Debug, Hiarc, Default, Serialize, Deserialize)]
@@ -58,6 +60,8 @@ pub mod laser {
         pub energy: f32,
         pub bounces: usize,
         pub next_eval_in: GameTickCooldownAndLastActionCounter,
+        /// Ticks since the laser was fired.
+        pub ticks_alive: GameTickType,
         // TODO: int m_Owner;
         // TODO: int m_TeamMask;
 
@@ -108,6 +112,7 @@ pub mod laser {
                 dir: *dir,
                 energy: start_energy,
                 next_eval_in: Default::default(),
+                ticks_alive: 0,
 
                 can_hit_others,
                 can_hit_own,
@@ -267,8 +272,9 @@ pub mod laser {
         fn do_bounce(&mut self, pipe: &mut SimulationPipeLaser) -> bool {
             let tuning = pipe.collision.get_tune_at(&self.core.pos);
             let delay = tuning.laser_bounce_delay;
-            self.core.next_eval_in =
-                ((TICKS_PER_SECOND as f32 * delay / 1000.0).ceil() as GameTickType).into();
+            self.core.next_eval_in = ((pipe.collision.ticks_per_second() as f32 * delay / 1000.0)
+                .ceil() as GameTickType)
+                .into();
 
             if self.core.energy < 0.0 {
                 return false;
@@ -334,6 +340,13 @@ pub mod laser {
                         Some(core.pos),
                         GameWorldEntitySoundEvent::Laser(GameLaserEventSound::Bounce),
                     );
+                    self.game_pending_events.push_effect(
+                        Some(pipe.characters_helper.owner_character),
+                        core.pos,
+                        GameWorldEntityEffectEvent::Laser(GameLaserEventEffect::Bounce {
+                            remaining_bounces: bounce_num.saturating_sub(core.bounces) as u32,
+                        }),
+                    );
                 }
             } else {
                 let cur_pos = self.core.pos;
@@ -361,6 +374,15 @@ pub mod laser {
         }
 
         fn tick(&mut self, pipe: &mut SimulationPipeLaser) -> EntityTickResult {
+            self.core.ticks_alive += 1;
+            let lifetime = pipe.collision.get_tune_at(&self.core.pos).laser_lifetime;
+            if lifetime > 0.0
+                && self.core.ticks_alive as f32
+                    > lifetime * pipe.collision.ticks_per_second() as f32
+            {
+                return EntityTickResult::RemoveEntity;
+            }
+
             if self
                 .core
                 .next_eval_in
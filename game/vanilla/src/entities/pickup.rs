@@ -11,14 +11,18 @@ pub mod pickup {
             GamePickupHeartEventSound, GamePickupSoundEvent, GameShotgunEventSound,
             GameWorldEntitySoundEvent,
         },
-        types::{id_types::PickupId, pickup::PickupType, weapons::WeaponType},
+        types::{game::GameTickType, id_types::PickupId, pickup::PickupType, weapons::WeaponType},
     };
     use hiarc::Hiarc;
-    use math::math::{lerp, vector::vec2};
+    use math::math::{
+        lerp,
+        vector::{ivec2, vec2},
+    };
     use pool::{datatypes::PoolFxLinkedHashMap, pool::Pool, recycle::Recycle, traits::Recyclable};
     use serde::{Deserialize, Serialize};
 
     use crate::{
+        collision::collision::tick_scale,
         entities::entity::entity::{DropMode, Entity, EntityInterface, EntityTickResult},
         events::events::PickupEvent,
         simulation_pipe::simulation_pipe::{
@@ -48,10 +52,31 @@ pub mod pickup {
 
     pub type PoolPickupReusableCore = Recycle<PickupReusableCore>;
 
+    /// The ammo a weapon pickup gives, if it is not a dropped weapon.
+    const WEAPON_PICKUP_AMMO: u32 = 10;
+    /// How much of its velocity a dropped weapon keeps,
+    /// when it bounces off a wall.
+    const DROPPED_ELASTICITY: f32 = 0.5;
+    /// Friction that slows down a dropped weapon while it lies on the ground.
+    const DROPPED_GROUND_FRICTION: f32 = 0.9;
+
+    /// A weapon that a character dropped on death.
+    #[derive(Debug, Hiarc, Copy, Clone, Serialize, Deserialize)]
+    pub struct DroppedPickup {
+        pub vel: vec2,
+        /// The ammo of the weapon when its owner died.
+        pub ammo: u32,
+        /// The remaining ticks until the pickup disappears.
+        pub despawn_ticks: GameTickType,
+    }
+
     #[derive(Debug, Hiarc, Copy, Clone, Serialize, Deserialize)]
     pub struct PickupCore {
         pub pos: vec2,
         pub ty: PickupType,
+        /// `Some` for weapons dropped by dead characters,
+        /// which don't respawn once collected.
+        pub dropped: Option<DroppedPickup>,
     }
 
     #[derive(Debug, Hiarc)]
@@ -101,7 +126,11 @@ pub mod pickup {
 
             Self {
                 base: Entity::new(game_el_id),
-                core: PickupCore { pos: *pos, ty },
+                core: PickupCore {
+                    pos: *pos,
+                    ty,
+                    dropped: None,
+                },
                 reusable_core: pool.pickup_reusable_cores_pool.new(),
 
                 game_pending_events: game_pending_events.clone(),
@@ -109,9 +138,48 @@ pub mod pickup {
             }
         }
 
+        pub const DROPPED_PHYSICAL_SIZE: f32 = 14.0;
+
         pub fn lerped_pos(pickup1: &Pickup, pickup2: &Pickup, ratio: f64) -> vec2 {
             lerp(&pickup1.core.pos, &pickup2.core.pos, ratio as f32)
         }
+
+        /// Falls & bounces off the tiles, similar to a dropped flag.
+        ///
+        /// Returns `true` if the pickup should despawn.
+        fn move_dropped(&mut self, pipe: &SimulationPipePickup) -> bool {
+            let Some(dropped) = &mut self.core.dropped else {
+                return false;
+            };
+            if dropped.despawn_ticks == 0
+                || pipe.collision.is_death(self.core.pos.x, self.core.pos.y)
+                || Entity::<PickupId>::outside_of_playfield(&self.core.pos, pipe.collision)
+            {
+                return true;
+            }
+            dropped.despawn_ticks -= 1;
+
+            dropped.vel.y += pipe.collision.get_tick_tune_at(&self.core.pos).gravity;
+            let grounded = pipe.collision.check_pointf(
+                self.core.pos.x,
+                self.core.pos.y + Self::DROPPED_PHYSICAL_SIZE / 2.0 + 5.0,
+            );
+            if grounded {
+                dropped.vel.x *=
+                    DROPPED_GROUND_FRICTION.powf(tick_scale(pipe.collision.ticks_per_second()));
+            }
+
+            pipe.collision.move_box(
+                &mut self.core.pos,
+                &mut dropped.vel,
+                &ivec2::new(
+                    Self::DROPPED_PHYSICAL_SIZE as i32,
+                    Self::DROPPED_PHYSICAL_SIZE as i32,
+                ),
+                DROPPED_ELASTICITY,
+            );
+            false
+        }
     }
 
     impl EntityInterface<PickupCore, PickupReusableCore, SimulationPipePickup<'_>> for Pickup {
@@ -182,11 +250,20 @@ pub mod pickup {
                         }
                     }
                     PickupType::PowerupWeapon(weapon) => {
+                        // dropped weapons give the ammo their owner had left
+                        let ammo = self
+                            .core
+                            .dropped
+                            .map_or(WEAPON_PICKUP_AMMO, |dropped| dropped.ammo);
                         let res = if let Some(weapon) = char.reusable_core.weapons.get_mut(&weapon)
                         {
                             // check if ammo can be refilled
-                            if weapon.cur_ammo.is_some_and(|val| val < 10) {
-                                weapon.cur_ammo = Some(10);
+                            if let Some(cur_ammo) = weapon
+                                .cur_ammo
+                                .as_mut()
+                                .filter(|val| **val < WEAPON_PICKUP_AMMO)
+                            {
+                                *cur_ammo = (*cur_ammo + ammo).min(WEAPON_PICKUP_AMMO);
                                 EntityTickResult::RemoveEntity
                             } else {
                                 EntityTickResult::None
@@ -197,7 +274,7 @@ pub mod pickup {
                             char.reusable_core.weapons.insert_sorted(
                                 weapon,
                                 Weapon {
-                                    cur_ammo: Some(10),
+                                    cur_ammo: Some(ammo),
                                     next_ammo_regeneration_tick: 0.into(),
                                 },
                             );
@@ -263,8 +340,12 @@ pub mod pickup {
             }
         }
 
-        fn tick_deferred(&mut self, _pipe: &mut SimulationPipePickup) -> EntityTickResult {
-            EntityTickResult::None
+        fn tick_deferred(&mut self, pipe: &mut SimulationPipePickup) -> EntityTickResult {
+            if self.move_dropped(pipe) {
+                EntityTickResult::RemoveEntity
+            } else {
+                EntityTickResult::None
+            }
         }
 
         fn drop_mode(&mut self, mode: DropMode) {
@@ -274,7 +355,8 @@ pub mod pickup {
 
     impl Drop for Pickup {
         fn drop(&mut self) {
-            if matches!(self.base.drop_mode, DropMode::None) {
+            // dropped weapons are gone for good
+            if matches!(self.base.drop_mode, DropMode::None) && self.core.dropped.is_none() {
                 self.simulation_events
                     .push_world(SimulationEventWorldEntityType::Pickup {
                         id: self.base.game_element_id,
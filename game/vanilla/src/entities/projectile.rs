@@ -27,7 +27,6 @@ pub mod projectile {
         GameWorldPendingEvents, SimulationEventWorldEntityType, SimulationPipeProjectile,
         SimulationWorldEvents,
     };
-    use crate::state::state::TICKS_PER_SECOND;
     use crate::world::world::GameWorld;
 
     #[derive(Debug, Hiarc, Default, Serialize, Deserialize)]
@@ -59,6 +58,8 @@ pub mod projectile {
         pub is_explosive: bool,
         pub ty: WeaponWithProjectile,
         pub side: Option<MatchSide>,
+        /// How often the projectile bounced off walls.
+        pub bounces: u32,
     }
 
     #[derive(Debug, Hiarc, Clone)]
@@ -104,6 +105,7 @@ pub mod projectile {
                 is_explosive: explosive,
                 ty,
                 side,
+                bounces: 0,
             };
             Self {
                 base: Entity::new(game_el_id),
@@ -160,6 +162,40 @@ pub mod projectile {
             calc_pos_and_vel(pos, &mut core.vel, curvature, speed, time)
         }
 
+        /// The projectile hit a wall, reflects it at the position
+        /// before the collision if it can still bounce.
+        fn try_bounce(
+            &mut self,
+            collision: &Collision,
+            owner: CharacterId,
+            before_collision: &vec2,
+        ) -> bool {
+            let WeaponWithProjectile::Grenade = self.core.ty else {
+                return false;
+            };
+            let tuning = collision.get_tune_at(before_collision);
+            let bounce_num = tuning.grenade_bounce_num as u32;
+            if self.core.bounces >= bounce_num {
+                return false;
+            }
+
+            let mut tmp_pos = *before_collision;
+            let mut tmp_dir = normalize(&self.core.vel) * 4.0;
+            collision.move_point(&mut tmp_pos, &mut tmp_dir, 1.0, &mut 0);
+            self.core.vel =
+                normalize(&tmp_dir) * length(&self.core.vel) * tuning.grenade_bounce_elasticity;
+            self.core.bounces += 1;
+
+            self.game_pending_events.push_effect(
+                Some(owner),
+                *before_collision,
+                GameWorldEntityEffectEvent::Grenade(GameGrenadeEventEffect::Bounce {
+                    remaining_bounces: bounce_num - self.core.bounces,
+                }),
+            );
+            true
+        }
+
         fn create_explosion(&mut self, no_dmg: bool, pipe: &mut SimulationPipeProjectile) {
             // deal damage
             let radius = 135;
@@ -221,7 +257,7 @@ pub mod projectile {
         }
 
         fn tick(&mut self, pipe: &mut SimulationPipeProjectile) -> EntityTickResult {
-            let ticks_per_second = TICKS_PER_SECOND;
+            let ticks_per_second = pipe.collision.ticks_per_second();
             let prev_pos = self.core.pos;
             let mut cur_pos = self.core.pos;
             Self::advance_pos_and_dir(
@@ -230,17 +266,18 @@ pub mod projectile {
                 &mut cur_pos,
                 1.0 / (ticks_per_second as f32),
             );
-            let mut dummy_pos = Default::default();
+            let mut before_collision_pos = Default::default();
             let collide = pipe.collision.intersect_line(
                 &prev_pos,
                 &cur_pos.clone(),
                 &mut cur_pos,
-                &mut dummy_pos,
+                &mut before_collision_pos,
                 CollisionTypes::SOLID | CollisionTypes::WEAPON_TELE,
             );
 
             self.core.life_span -= 1;
 
+            let owner = pipe.characters_helper.owner_character;
             let intersection = GameWorld::intersect_character_on_line(
                 pipe.field,
                 pipe.characters_helper.get_characters_except_owner(),
@@ -249,7 +286,14 @@ pub mod projectile {
                 6.0,
             );
 
-            let res = if intersection.is_some()
+            let res = if intersection.is_none()
+                && self.core.life_span >= 0
+                && matches!(collide, CollisionTile::Solid(_))
+                && self.try_bounce(pipe.collision, owner, &before_collision_pos)
+            {
+                cur_pos = before_collision_pos;
+                EntityTickResult::None
+            } else if intersection.is_some()
                 || !matches!(collide, CollisionTile::None)
                 || self.core.life_span < 0
                 || Entity::<ProjectileId>::outside_of_playfield(&cur_pos, pipe.collision)
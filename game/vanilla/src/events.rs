@@ -3,7 +3,7 @@ pub mod events {
         events::GameWorldActionKillWeapon,
         types::{
             flag::FlagType, game::GameTickCooldown, id_types::CharacterId, pickup::PickupType,
-            render::projectiles::WeaponWithProjectile,
+            render::projectiles::WeaponWithProjectile, weapons::WeaponType,
         },
     };
     use hiarc::Hiarc;
@@ -54,6 +54,10 @@ pub mod events {
             by: CharacterId,
             pos: vec2,
         },
+        /// A character returned the flag of its own side.
+        Return {
+            by: CharacterId,
+        },
     }
 
     #[derive(Debug, Hiarc, Serialize, Deserialize)]
@@ -81,6 +85,20 @@ pub mod events {
             killer_id: Option<CharacterId>,
             weapon: GameWorldActionKillWeapon,
         },
+        /// A character fired a weapon.
+        Fire {
+            id: CharacterId,
+            weapon: WeaponType,
+            /// How many projectiles were fired at once.
+            shots: u32,
+        },
+        /// A character took damage from another character (or itself).
+        Damage {
+            id: CharacterId,
+            attacker: CharacterId,
+            damage: u32,
+            weapon: GameWorldActionKillWeapon,
+        },
         Mod(CharacterEventMod),
     }
 
@@ -2,17 +2,28 @@
 #![allow(clippy::module_inception)]
 #![allow(clippy::multiple_bound_locations)]
 
+pub mod announcements;
+pub mod anti_cheat;
+pub mod bans;
+pub mod bots;
+pub mod chat_commands;
 pub mod collision;
 pub mod command_chain;
 pub mod config;
 pub mod entities;
 pub mod events;
 pub mod game_objects;
+pub mod loadout;
+pub mod map_settings;
 pub mod match_manager;
 pub mod match_state;
+pub mod match_stats;
+pub mod platforms;
+pub mod practice;
 pub mod reusable;
 pub mod simulation_pipe;
 pub mod snapshot;
+pub mod sound_batching;
 pub mod spawns;
 /// basic sql support
 pub mod sql;
@@ -31,39 +42,133 @@ mod test {
 
     use base::linked_hash_map_view::FxLinkedHashMap;
     use base_io::{io::create_runtime, runtime::IoRuntime};
+    use command_parser::parser::{CommandArg, CommandArgType, ParserCache, parse};
     use game_database::dummy::DummyDb;
     use game_interface::{
+        chat_commands::ClientChatCommand,
+        client_commands::{ClientCameraMode, ClientCommand},
+        events::{
+            EventClientInfo, GameCharacterEventSound, GameCharacterSoundEvent,
+            GameCustomEventPayload, GameEvents, GameGrenadeEventEffect, GameGrenadeEventSound,
+            GameLaserEventEffect, GameWorldActionKillWeapon, GameWorldCustomEvent,
+            GameWorldEffectEvent, GameWorldEntityEffectEvent, GameWorldEntitySoundEvent,
+            GameWorldEvent, GameWorldNotificationEvent, GameWorldSoundEvent,
+            GameWorldSystemMessage, MAX_CUSTOM_EVENT_PAYLOAD_LEN,
+        },
         interface::{GameStateCreate, GameStateCreateOptions, GameStateInterface},
+        rcon_entries::{AuthLevel, ExecRconInput},
+        tick_result::TickEvent,
         types::{
             character_info::NetworkCharacterInfo,
-            id_types::PlayerId,
+            id_gen::IdGenerator,
+            id_types::{CharacterId, PlayerId, StageId},
             input::{CharacterInput, CharacterInputInfo, cursor::CharacterInputCursor},
             network_stats::PlayerNetworkStats,
-            player_info::{PlayerClientInfo, PlayerUniqueId},
+            pickup::PickupType,
+            player_info::{PlayerClientInfo, PlayerDropReason, PlayerKickReason, PlayerUniqueId},
+            render::{
+                character::{CharacterDebuff, CharacterServerRank},
+                game::game_match::MatchSide,
+                projectiles::WeaponWithProjectile,
+            },
+            snapshot::SnapshotClientInfo,
+            weapons::WeaponType,
+        },
+    };
+    use legacy_map::mapdef_06::DdraceTileNum;
+    use map::{
+        file::MapFileReader,
+        map::{
+            Map,
+            animations::{AnimPointCurveType, AnimPointPos, PosAnimation, TimeDuration},
+            command_value::CommandValue,
+            groups::layers::{
+                physics::{
+                    MapLayerPhysics, MapLayerPhysicsMeta, MapLayerTilePhysicsBase,
+                    MapLayerTilePhysicsSwitch, MapPhysicsPlatform, MapPhysicsPlatformCrush,
+                },
+                tiles::SwitchTile,
+            },
         },
     };
     use math::math::{
-        Rng,
-        vector::{dvec2, ivec2, vec2},
+        Rng, distance, length, normalize,
+        vector::{dvec2, ffixed, fvec2, fvec3, ivec2, uffixed, ufvec2, vec2},
     };
-    use pool::pool::Pool;
+    use pool::{datatypes::PoolFxLinkedHashSet, pool::Pool};
 
-    use crate::{config::config::ConfigVanilla, state::state::GameState};
+    use crate::{
+        announcements::announcements::{
+            AnnouncementOptions, AnnouncementScheduler, format_player_msg,
+        },
+        anti_cheat::anti_cheat::{InputDetections, InputPlausibility},
+        bans::bans::{BanIdentity, BanKind, BanList, BanTarget},
+        bots::bots::BotMode,
+        chat_commands::{ChatCommand, ChatCommandPermission, ChatCommandRegistry},
+        collision::collision::Tunings,
+        config::config::{
+            ConfigAntiCheatAction, ConfigGameType, ConfigLoadoutWeapon, ConfigOvertimeMode,
+            ConfigSpawnLoadout, ConfigSpawnLoadouts, ConfigVanilla,
+        },
+        entities::{
+            character::character::{
+                BuffProps, Character, CharacterPhasedState, DamageBy, DamageTypes, FriendlyFireTy,
+                WeaponsExt,
+            },
+            character::{
+                hook::character_hook::{Hook, HookState},
+                score::character_score::CharacterScores,
+            },
+            entity::entity::{EntityInterface, EntityTickResult},
+            flag::flag::{Flag, FlagOptions},
+        },
+        loadout::loadout::SpawnLoadout,
+        map_settings::map_settings::{MapSettingError, apply_map_settings},
+        match_manager::match_manager::{
+            HIGHLIGHT_LEAD_TICKS, HIGHLIGHT_MAX_AGE_TICKS, HIGHLIGHT_TAIL_TICKS, MatchHighlight,
+            MatchHighlights,
+        },
+        match_state::match_state::{Match, MatchState, MatchType, MatchWinner},
+        simulation_pipe::simulation_pipe::{
+            SimulationPipeFlag, SimulationPipeLaser, SimulationPipePickup,
+            SimulationPipeProjectile, SimulationPipeStage,
+        },
+        snapshot::snapshot::{Snapshot, SnapshotFor},
+        sound_batching::sound_batching::{
+            MAX_WORLD_SOUNDS_PER_TICK, SOUND_MERGE_RADIUS, batch_sound_events,
+        },
+        state::state::{GameDbQueries, GameState, TICKS_PER_SECOND},
+        types::types::{GameOptions, GameType},
+        weapons::definitions::weapon_def::Weapon,
+        world::world::GameWorld,
+    };
 
     fn get_game<const NUM_PLAYERS: usize>() -> GameState {
+        get_game_with_config::<NUM_PLAYERS>(Default::default())
+    }
+
+    /// `max_ingame_players` of the config is overwritten by `NUM_PLAYERS`.
+    fn get_game_with_config<const NUM_PLAYERS: usize>(config: ConfigVanilla) -> GameState {
         let file = include_bytes!("../../../data/map/maps/ctf1.twmap.tar");
+        get_game_with_map::<NUM_PLAYERS>(file.to_vec(), config)
+    }
 
+    /// `max_ingame_players` of the config is overwritten by `NUM_PLAYERS`.
+    fn get_game_with_map<const NUM_PLAYERS: usize>(
+        map: Vec<u8>,
+        config: ConfigVanilla,
+    ) -> GameState {
         let rt = create_runtime();
         let io_rt = IoRuntime::new(rt);
         let (game, _) = GameState::new(
-            file.to_vec(),
+            map,
             "ctf1".try_into().unwrap(),
             GameStateCreateOptions {
                 hint_max_characters: Some(NUM_PLAYERS),
                 config: Some(
                     serde_json::to_vec(&ConfigVanilla {
                         max_ingame_players: NUM_PLAYERS as u32,
-                        ..Default::default()
+                        ..config
                     })
                     .unwrap(),
                 ),
@@ -76,8 +181,8 @@ mod test {
         game
     }
 
-    #[test]
-    fn ticks() {
+    /// If `use_bots` is `true`, patrolling bots are used instead of random input.
+    fn bench_ticks(use_bots: bool) {
         const NUM_PLAYERS: usize = 64;
         let mut game = get_game::<NUM_PLAYERS>();
 
@@ -111,13 +216,23 @@ mod test {
             inps.insert(*id, CharacterInputInfo { inp: new_inp, diff });
         };
 
-        let ids: Vec<_> = (0..NUM_PLAYERS)
+        if use_bots {
+            let waypoints = game.spawns.spawns.clone();
+            for _ in 0..NUM_PLAYERS {
+                game.add_bot(BotMode::Patrol {
+                    waypoints: waypoints.clone(),
+                });
+            }
+        }
+
+        let ids: Vec<_> = (0..if use_bots { 0 } else { NUM_PLAYERS })
             .map(|index| {
                 let id = game.player_join(&PlayerClientInfo {
                     info: NetworkCharacterInfo::explicit_default(),
                     id: 0,
                     unique_identifier: PlayerUniqueId::Account(0),
                     initial_network_stats: PlayerNetworkStats::default(),
+                    ip: None,
                 });
 
                 for _ in 0..2 {
@@ -158,34 +273,3335 @@ mod test {
     }
 
     #[test]
-    fn move_box() {
-        let game = get_game::<1>();
-        pub const PHYSICAL_SIZE: f32 = 28.0;
-        const fn physical_size_vec2() -> ivec2 {
-            ivec2 {
-                x: PHYSICAL_SIZE as i32,
-                y: PHYSICAL_SIZE as i32,
+    fn ticks() {
+        bench_ticks(false);
+    }
+
+    #[test]
+    fn ticks_bots() {
+        bench_ticks(true);
+    }
+
+    #[test]
+    fn mirror_bots() {
+        const DELAY_TICKS: usize = 5;
+        let mut game = get_game::<4>();
+
+        let player_id = game.player_join(&PlayerClientInfo {
+            info: NetworkCharacterInfo::explicit_default(),
+            id: 0,
+            unique_identifier: PlayerUniqueId::Account(0),
+            initial_network_stats: PlayerNetworkStats::default(),
+            ip: None,
+        });
+        let bot_ids: Vec<_> = (0..2)
+            .map(|_| {
+                game.add_bot(BotMode::Mirror {
+                    target: player_id,
+                    delay_ticks: DELAY_TICKS,
+                })
+            })
+            .collect();
+
+        let character_inp = |game: &GameState, id: &PlayerId| {
+            let stage_id = game.game.players.player(id).unwrap().stage_id();
+            let stage = game.game.stages.get(&stage_id).unwrap();
+            stage.world.characters.get(id).unwrap().core.input
+        };
+
+        let game_inps: Pool<FxLinkedHashMap<PlayerId, CharacterInputInfo>> = Pool::with_capacity(1);
+        let mut sent_inps: Vec<CharacterInput> = Vec::new();
+        let mut inp = CharacterInput::default();
+        for tick in 0..50 {
+            let mut new_inp = inp;
+            new_inp.state.dir.set((tick % 3) as i32 - 1);
+            new_inp.state.jump.set(tick % 4 == 0);
+            if tick % 7 == 0 {
+                new_inp.consumable.jump.add(1);
+            }
+            new_inp
+                .cursor
+                .set(CharacterInputCursor::from_vec2(&dvec2::new(
+                    tick as f64 + 1.0,
+                    1.0,
+                )));
+            let diff = new_inp.consumable.diff(&inp.consumable);
+            inp = new_inp;
+            sent_inps.push(inp);
+
+            let mut game_inps = game_inps.new();
+            game_inps.insert(player_id, CharacterInputInfo { inp, diff });
+            game.set_player_inputs(game_inps);
+            game.tick(Default::default());
+            game.clear_events();
+
+            for bot_id in &bot_ids {
+                let bot_inp = character_inp(&game, bot_id);
+                if tick < DELAY_TICKS {
+                    assert_eq!(bot_inp, CharacterInput::default());
+                } else {
+                    assert_eq!(bot_inp, sent_inps[tick - DELAY_TICKS]);
+                }
             }
         }
-        let bench = || {
-            let now = Instant::now();
-            let iterations = 1000000;
-            let mut pos = Default::default();
-            let mut vel = Default::default();
-            for _ in 0..iterations {
-                pos = vec2::new(10.0, 10.0) * 32.0;
-                vel = vec2::new(10.0, 10.0) * 32.0;
-                game.collision
-                    .move_box(&mut pos, &mut vel, &physical_size_vec2(), 0.0);
+
+        let infos = game.collect_characters_info();
+        assert!(!infos.get(&player_id).unwrap().is_bot);
+        assert!(bot_ids.iter().all(|id| infos.get(id).unwrap().is_bot));
+    }
+
+    #[test]
+    fn hit_events_only_for_attacker() {
+        // only two players are ingame, the third is a spectator
+        let mut game = get_game::<2>();
+        let [attacker, target, spectator] = [0, 1, 2].map(|id| {
+            game.player_join(&PlayerClientInfo {
+                info: NetworkCharacterInfo::explicit_default(),
+                id: 0,
+                unique_identifier: PlayerUniqueId::Account(id),
+                initial_network_stats: PlayerNetworkStats::default(),
+                ip: None,
+            })
+        });
+        assert!(game.game.players.player(&spectator).is_none());
+        game.clear_events();
+
+        let stage_id = game.game.players.player(&target).unwrap().stage_id();
+        let target_char = game
+            .game
+            .stages
+            .get_mut(&stage_id)
+            .unwrap()
+            .world
+            .characters
+            .get_mut(&target)
+            .unwrap();
+        Character::take_damage_from(
+            target_char,
+            &target,
+            attacker,
+            &Default::default(),
+            &Default::default(),
+            FriendlyFireTy::Dmg,
+            3,
+            DamageTypes::Character(&attacker),
+            DamageBy::Weapon {
+                weapon: WeaponType::Gun,
+                flags: Default::default(),
+            },
+        );
+
+        let hits_for = |game: &GameState, ids: &[PlayerId]| {
+            let mut client_player_ids = PoolFxLinkedHashSet::new_without_pool();
+            client_player_ids.extend(ids.iter().copied());
+            let events = game.events_for(EventClientInfo {
+                client_player_ids,
+                everything: false,
+                other_stages: false,
+            });
+            events
+                .worlds
+                .values()
+                .flat_map(|world| world.events.values())
+                .filter_map(|ev| match ev {
+                    GameWorldEvent::Hit(hit) => Some(*hit),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let hits = hits_for(&game, &[attacker]);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].attacker, attacker);
+        assert_eq!(hits[0].target, target);
+        assert_eq!(hits[0].damage, 3);
+        assert!(!hits[0].killing_blow);
+        assert!(matches!(
+            hits[0].weapon,
+            GameWorldActionKillWeapon::Weapon {
+                weapon: WeaponType::Gun
             }
-            let took = now.elapsed();
-            println!("{} s - pos: {:?}, vel: {:?}", took.as_secs_f64(), pos, vel);
-            let calls_per_second = iterations as f64 / took.as_secs_f64();
-            println!("{calls_per_second} call/s");
+        ));
+
+        assert!(hits_for(&game, &[target]).is_empty());
+        assert!(hits_for(&game, &[spectator]).is_empty());
+
+        // spectating the attacker shows the hits of the attacker
+        game.client_command(
+            &spectator,
+            ClientCommand::SetCameraMode(ClientCameraMode::FreeCam(
+                [attacker].into_iter().collect(),
+            )),
+        );
+        assert_eq!(hits_for(&game, &[spectator]).len(), 1);
+    }
+
+    #[test]
+    fn custom_events_round_trip() {
+        let mut game = get_game::<1>();
+        let player_id = game.player_join(&PlayerClientInfo {
+            info: NetworkCharacterInfo::explicit_default(),
+            id: 0,
+            unique_identifier: PlayerUniqueId::Account(0),
+            initial_network_stats: PlayerNetworkStats::default(),
+            ip: None,
+        });
+        game.clear_events();
+
+        // a game mod emits its event into the world
+        let stage_id = game.game.players.player(&player_id).unwrap().stage_id();
+        let payload = GameCustomEventPayload::new(b"boss spawned".to_vec()).unwrap();
+        game.game
+            .stages
+            .get_mut(&stage_id)
+            .unwrap()
+            .world
+            .game_pending_events
+            .push_custom(7, payload.clone());
+
+        let mut client_player_ids = PoolFxLinkedHashSet::new_without_pool();
+        client_player_ids.insert(player_id);
+        let events = game.events_for(EventClientInfo {
+            client_player_ids,
+            everything: false,
+            other_stages: false,
+        });
+
+        // demos & the network serialize the events like this
+        let events = bincode::serde::encode_to_vec(&events, bincode::config::standard()).unwrap();
+        let (events, _): (GameEvents, _) =
+            bincode::serde::decode_from_slice(&events, bincode::config::standard()).unwrap();
+        let custom_events: Vec<_> = events
+            .worlds
+            .values()
+            .flat_map(|world| world.events.values())
+            .filter_map(|ev| match ev {
+                GameWorldEvent::Custom(ev) => Some(ev.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(custom_events, vec![GameWorldCustomEvent { id: 7, payload }]);
+
+        // the payload is capped, also for received events
+        let too_long = vec![0u8; MAX_CUSTOM_EVENT_PAYLOAD_LEN + 1];
+        assert!(GameCustomEventPayload::new(too_long.clone()).is_none());
+        let too_long =
+            bincode::serde::encode_to_vec(&too_long, bincode::config::standard()).unwrap();
+        assert!(
+            bincode::serde::decode_from_slice::<GameCustomEventPayload, _>(
+                &too_long,
+                bincode::config::standard()
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn game_over_stats() {
+        let mut game = get_game::<3>();
+        let [attacker, target, leaver] = [0, 1, 2].map(|id| {
+            game.player_join(&PlayerClientInfo {
+                info: NetworkCharacterInfo::explicit_default(),
+                id: 0,
+                unique_identifier: PlayerUniqueId::Account(id),
+                initial_network_stats: PlayerNetworkStats::default(),
+                ip: None,
+            })
+        });
+        for _ in 0..2 {
+            game.tick(Default::default());
+        }
+        let stage_id = game.game.players.player(&attacker).unwrap().stage_id();
+
+        // the attacker fires the gun once
+        let game_inps: Pool<FxLinkedHashMap<PlayerId, CharacterInputInfo>> = Pool::with_capacity(1);
+        let mut inp = CharacterInput::default();
+        inp.consumable
+            .fire
+            .add(1, CharacterInputCursor::from_vec2(&dvec2::new(1.0, 0.0)));
+        let mut inps = game_inps.new();
+        inps.insert(
+            attacker,
+            CharacterInputInfo {
+                inp,
+                diff: inp.consumable.diff(&Default::default()),
+            },
+        );
+        game.set_player_inputs(inps);
+        game.tick(Default::default());
+
+        // one hit on the target, then kill the leaver and the target
+        for (victim, dmg) in [(target, 3), (leaver, 20), (target, 20)] {
+            let victim_char = game
+                .game
+                .stages
+                .get_mut(&stage_id)
+                .unwrap()
+                .world
+                .characters
+                .get_mut(&victim)
+                .unwrap();
+            Character::take_damage_from(
+                victim_char,
+                &victim,
+                attacker,
+                &Default::default(),
+                &Default::default(),
+                FriendlyFireTy::Dmg,
+                dmg,
+                DamageTypes::Character(&attacker),
+                DamageBy::Weapon {
+                    weapon: WeaponType::Gun,
+                    flags: Default::default(),
+                },
+            );
+            game.tick(Default::default());
+        }
+
+        game.player_drop(&leaver, PlayerDropReason::Disconnect);
+        game.clear_events();
+
+        let stage = game.game.stages.get_mut(&stage_id).unwrap();
+        stage.match_manager.game_match.state = MatchState::GameOver {
+            winner: MatchWinner::Character(attacker),
+            new_game_in: 10.into(),
+            round_ticks_passed: 0,
+            by_cooldown: false,
         };
-        bench();
-        bench();
-        bench();
-        bench();
+        game.tick(Default::default());
+
+        let events = game.events_for(EventClientInfo {
+            client_player_ids: PoolFxLinkedHashSet::new_without_pool(),
+            everything: true,
+            other_stages: true,
+        });
+        let stats = events
+            .worlds
+            .values()
+            .flat_map(|world| world.events.values())
+            .find_map(|ev| match ev {
+                GameWorldEvent::Notification(GameWorldNotificationEvent::GameOver {
+                    stats,
+                    ..
+                }) => Some(stats.iter().cloned().collect::<Vec<_>>()),
+                _ => None,
+            })
+            .unwrap();
+        let row = |id: PlayerId| stats.iter().find(|row| row.id == id).unwrap();
+
+        let attacker_row = row(attacker);
+        assert_eq!(attacker_row.weapons[WeaponType::Gun as usize].shots, 1);
+        assert_eq!(attacker_row.weapons[WeaponType::Gun as usize].hits, 3);
+        assert_eq!(attacker_row.kills, 2);
+        assert_eq!(attacker_row.deaths, 0);
+        assert_eq!(attacker_row.damage_dealt, 20);
+        assert_eq!(attacker_row.damage_taken, 0);
+        assert_eq!(attacker_row.longest_killstreak, 2);
+        assert!(!attacker_row.disconnected);
+
+        let target_row = row(target);
+        assert_eq!(target_row.deaths, 1);
+        assert_eq!(target_row.damage_taken, 10);
+        assert!(!target_row.disconnected);
+
+        let leaver_row = row(leaver);
+        assert_eq!(leaver_row.deaths, 1);
+        assert_eq!(leaver_row.damage_taken, 10);
+        assert!(leaver_row.disconnected);
+
+        // the stats are only sent once
+        game.clear_events();
+        game.tick(Default::default());
+        let events = game.events_for(EventClientInfo {
+            client_player_ids: PoolFxLinkedHashSet::new_without_pool(),
+            everything: true,
+            other_stages: true,
+        });
+        assert!(
+            !events
+                .worlds
+                .values()
+                .flat_map(|world| world.events.values())
+                .any(|ev| matches!(
+                    ev,
+                    GameWorldEvent::Notification(GameWorldNotificationEvent::GameOver { .. })
+                ))
+        );
+
+        // counters reset when the round restarts
+        for _ in 0..10 {
+            game.tick(Default::default());
+        }
+        let stage = game.game.stages.get(&stage_id).unwrap();
+        assert!(matches!(
+            stage.match_manager.game_match.state,
+            MatchState::Running { .. }
+        ));
+        assert!(stage.match_manager.stats.get(&attacker).is_none());
+    }
+
+    fn join_players<const NUM_PLAYERS: usize>(game: &mut GameState) -> [PlayerId; NUM_PLAYERS] {
+        std::array::from_fn(|id| {
+            game.player_join(&PlayerClientInfo {
+                info: NetworkCharacterInfo::explicit_default(),
+                id: 0,
+                unique_identifier: PlayerUniqueId::Account(id as _),
+                initial_network_stats: PlayerNetworkStats::default(),
+                ip: None,
+            })
+        })
+    }
+
+    fn ready(game: &mut GameState, id: &PlayerId) {
+        game.client_command(
+            id,
+            ClientCommand::Chat(ClientChatCommand {
+                raw: "ready".try_into().unwrap(),
+            }),
+        );
+    }
+
+    fn chat(game: &mut GameState, id: &PlayerId, raw: &str) {
+        game.client_command(
+            id,
+            ClientCommand::Chat(ClientChatCommand {
+                raw: raw.try_into().unwrap(),
+            }),
+        );
+    }
+
+    #[test]
+    fn practice_save_load() {
+        let mut game = get_game::<2>();
+        let [player, teammate] = join_players::<2>(&mut game);
+        for _ in 0..2 {
+            game.tick(Default::default());
+        }
+        let stage_id = game.game.players.player(&player).unwrap().stage_id();
+        fn character<'a>(
+            game: &'a mut GameState,
+            stage_id: &StageId,
+            id: &PlayerId,
+        ) -> &'a mut Character {
+            game.game
+                .stages
+                .get_mut(stage_id)
+                .unwrap()
+                .world
+                .characters
+                .get_mut(id)
+                .unwrap()
+        }
+
+        // no saving outside of practice
+        chat(&mut game, &player, "savepos");
+        chat(&mut game, &player, "practice");
+        assert!(!game.game.stages[&stage_id].practice.is_enabled());
+        chat(&mut game, &player, "loadpos");
+        chat(&mut game, &teammate, "practice");
+        assert!(game.game.stages[&stage_id].practice.is_enabled());
+        chat(&mut game, &player, "loadpos");
+
+        let saved_pos = vec2::new(100.0, 200.0);
+        let saved_vel = vec2::new(3.0, -4.0);
+        let c = character(&mut game, &stage_id, &player);
+        c.pos.move_pos(saved_pos);
+        c.core.core.vel = saved_vel;
+        c.reusable_core.weapons.insert(
+            WeaponType::Laser,
+            Weapon {
+                cur_ammo: Some(3),
+                next_ammo_regeneration_tick: 0.into(),
+            },
+        );
+        c.core.active_weapon = WeaponType::Laser;
+        c.reusable_core.debuffs.insert(
+            CharacterDebuff::Freeze,
+            BuffProps {
+                remaining_tick: 50.into(),
+                interact_tick: 0.into(),
+                interact_cursor_dir: Default::default(),
+                interact_val: 0.0,
+            },
+        );
+        chat(&mut game, &player, "savepos");
+
+        let c = character(&mut game, &stage_id, &player);
+        c.pos.move_pos(vec2::new(500.0, 500.0));
+        c.core.core.vel = vec2::new(0.0, 0.0);
+        c.reusable_core.weapons.remove(&WeaponType::Laser);
+        c.core.active_weapon = WeaponType::Hammer;
+        c.reusable_core.debuffs.remove(&CharacterDebuff::Freeze);
+        chat(&mut game, &player, "loadpos");
+
+        let c = character(&mut game, &stage_id, &player);
+        assert_eq!(*c.pos.pos(), saved_pos);
+        assert_eq!(c.core.core.vel, saved_vel);
+        assert_eq!(c.core.active_weapon, WeaponType::Laser);
+        assert_eq!(
+            c.reusable_core
+                .weapons
+                .get(&WeaponType::Laser)
+                .and_then(|weapon| weapon.cur_ammo),
+            Some(3)
+        );
+        assert!(
+            c.reusable_core
+                .debuffs
+                .contains_key(&CharacterDebuff::Freeze)
+        );
+
+        // others see the practice marker
+        let stages = game.all_stages(0.0);
+        let render_info = stages
+            .get(&stage_id)
+            .and_then(|stage| stage.world.characters.get(&teammate))
+            .unwrap();
+        assert!(render_info.practice);
+    }
+
+    #[test]
+    fn practice_rounds_dont_count() {
+        let mut game = get_game::<2>();
+        let [player, teammate] = join_players::<2>(&mut game);
+        for _ in 0..2 {
+            game.tick(Default::default());
+        }
+        let stage_id = game.game.players.player(&player).unwrap().stage_id();
+
+        let game_over = |game: &mut GameState| {
+            let stage = game.game.stages.get_mut(&stage_id).unwrap();
+            stage.match_manager.game_match.state = MatchState::GameOver {
+                winner: MatchWinner::Character(player),
+                new_game_in: 10.into(),
+                round_ticks_passed: 0,
+                by_cooldown: false,
+            };
+            let mut pipe =
+                SimulationPipeStage::new(false, &game.collision, &stage_id, &game.world_pool);
+            stage.tick(&mut pipe);
+            std::mem::take(&mut stage.match_manager.finished_stats)
+        };
+
+        assert!(!game_over(&mut game).is_empty());
+        for _ in 0..10 {
+            game.tick(Default::default());
+        }
+
+        chat(&mut game, &player, "practice");
+        chat(&mut game, &teammate, "practice");
+        assert!(game.game.stages[&stage_id].practice.is_enabled());
+        assert!(game_over(&mut game).is_empty());
+
+        // practice ends with the round
+        for _ in 0..10 {
+            game.tick(Default::default());
+        }
+        let stage = game.game.stages.get(&stage_id).unwrap();
+        assert!(matches!(
+            stage.match_manager.game_match.state,
+            MatchState::Running { .. }
+        ));
+        assert!(!stage.practice.is_enabled());
+    }
+
+    /// A single input of a trace: position, cursor, fire & jump presses.
+    type TraceInput = (vec2, dvec2, u64, u64);
+
+    fn trace_input(
+        prev: &CharacterInput,
+        cursor: dvec2,
+        fire: u64,
+        jump: u64,
+    ) -> CharacterInputInfo {
+        let mut inp = *prev;
+        let cursor = CharacterInputCursor::from_vec2(&cursor);
+        inp.cursor.set(cursor);
+        inp.state.fire.set(fire > 0);
+        inp.consumable.fire.add(fire, cursor);
+        inp.consumable.jump.add(jump);
+        let diff = inp.consumable.diff(&prev.consumable);
+        CharacterInputInfo { inp, diff }
+    }
+
+    /// Feeds the trace to the anti-cheat, one input per tick.
+    fn check_trace(
+        anti_cheat: &mut InputPlausibility,
+        trace: impl IntoIterator<Item = TraceInput>,
+    ) -> InputDetections {
+        let mut prev = CharacterInput::default();
+        let mut detections = InputDetections::empty();
+        for (pos, cursor, fire, jump) in trace {
+            let CharacterInputInfo { inp, diff } = trace_input(&prev, cursor, fire, jump);
+            detections |= anti_cheat.check(&inp, &diff, Some(pos));
+            anti_cheat.decay();
+            prev = inp;
+        }
+        detections
+    }
+
+    #[test]
+    fn anti_cheat_legit_trace() {
+        let mut rng = Rng::new(0);
+        let mut anti_cheat = InputPlausibility::default();
+        let trace = (0..TICKS_PER_SECOND * 30).map(|t| {
+            // a slowly wandering cursor with a bit of shaking
+            let angle = t as f64 * 0.02;
+            let len = 200.0 + (t as f64 * 0.013).sin() * 50.0;
+            let shake = rng.random_int_in(0..=4) as f64;
+            let cursor = dvec2::new(angle.cos() * len + shake, angle.sin() * len - shake);
+            let fire = (rng.random_int_in(0..=20) == 0) as u64;
+            let jump = (t % 40 == 0) as u64;
+            let pos = vec2::new((t as f32 * 0.5) % 300.0, 100.0);
+            (pos, cursor, fire, jump)
+        });
+        assert_eq!(
+            check_trace(&mut anti_cheat, trace),
+            InputDetections::empty()
+        );
+        assert_eq!(anti_cheat.suspicion(), 0.0);
+    }
+
+    #[test]
+    fn anti_cheat_bot_traces() {
+        // flicks to the opposite side & fires instantly
+        let mut anti_cheat = InputPlausibility::default();
+        let trace = (0..TICKS_PER_SECOND * 2).map(|t| {
+            let side = if (t / 5) % 2 == 0 { 1.0 } else { -1.0 };
+            let cursor = dvec2::new(200.0 * side, 10.0);
+            (vec2::default(), cursor, (t % 5 == 0) as u64, 0)
+        });
+        assert_eq!(
+            check_trace(&mut anti_cheat, trace),
+            InputDetections::AIM_SNAP
+        );
+        assert!(anti_cheat.suspicion() >= 100.0);
+
+        // more presses than ticks
+        let mut anti_cheat = InputPlausibility::default();
+        let trace = (0..TICKS_PER_SECOND * 2).map(|t| {
+            let cursor = dvec2::new(200.0, t as f64);
+            (vec2::default(), cursor, 2, 1)
+        });
+        assert_eq!(
+            check_trace(&mut anti_cheat, trace),
+            InputDetections::INPUT_FREQUENCY
+        );
+        assert!(anti_cheat.suspicion() >= 100.0);
+
+        // the same few inputs over and over
+        let mut anti_cheat = InputPlausibility::default();
+        let cursors = [
+            dvec2::new(100.0, 0.0),
+            dvec2::new(0.0, 100.0),
+            dvec2::new(-100.0, 0.0),
+            dvec2::new(0.0, -100.0),
+        ];
+        let trace = (0..TICKS_PER_SECOND as usize * 5)
+            .map(|t| (vec2::default(), cursors[t % cursors.len()], 0, 0));
+        assert_eq!(
+            check_trace(&mut anti_cheat, trace),
+            InputDetections::REPEATING_PATTERN
+        );
+        assert!(anti_cheat.suspicion() >= 100.0);
+
+        // the detections decay
+        for _ in 0..TICKS_PER_SECOND * 60 {
+            anti_cheat.decay();
+        }
+        assert_eq!(anti_cheat.suspicion(), 0.0);
+    }
+
+    #[test]
+    fn anti_cheat_ignores_join_and_teleports() {
+        // the first input contains all presses before the join
+        let mut anti_cheat = InputPlausibility::default();
+        let trace = [
+            (vec2::default(), dvec2::new(200.0, 0.0), 200, 200),
+            (vec2::default(), dvec2::new(200.0, 1.0), 0, 0),
+        ];
+        assert_eq!(
+            check_trace(&mut anti_cheat, trace),
+            InputDetections::empty()
+        );
+
+        // every flick happens together with a teleport
+        let mut anti_cheat = InputPlausibility::default();
+        let trace = (0..TICKS_PER_SECOND * 2).map(|t| {
+            let side = if (t / 5) % 2 == 0 { 1.0 } else { -1.0 };
+            let cursor = dvec2::new(200.0 * side, 10.0);
+            let pos = vec2::new(if side > 0.0 { 0.0 } else { 1000.0 }, 0.0);
+            (pos, cursor, (t % 5 == 0) as u64, 0)
+        });
+        assert_eq!(
+            check_trace(&mut anti_cheat, trace),
+            InputDetections::empty()
+        );
+
+        // without a living character there is nothing to check
+        let mut anti_cheat = InputPlausibility::default();
+        let mut prev = CharacterInput::default();
+        for _ in 0..TICKS_PER_SECOND {
+            let CharacterInputInfo { inp, diff } = trace_input(&prev, dvec2::new(200.0, 0.0), 5, 5);
+            assert_eq!(
+                anti_cheat.check(&inp, &diff, None),
+                InputDetections::empty()
+            );
+            prev = inp;
+        }
+    }
+
+    #[test]
+    fn anti_cheat_kick() {
+        let mut game = get_game_with_config::<1>(ConfigVanilla {
+            anti_cheat_action: ConfigAntiCheatAction::Kick,
+            anti_cheat_threshold: 20,
+            ..Default::default()
+        });
+        let [player] = join_players::<1>(&mut game);
+        for _ in 0..2 {
+            game.tick(Default::default());
+        }
+
+        let game_inps: Pool<FxLinkedHashMap<PlayerId, CharacterInputInfo>> = Pool::with_capacity(1);
+        let mut prev = CharacterInput::default();
+        let mut kicked = false;
+        for t in 0..TICKS_PER_SECOND {
+            let mut inps = game_inps.new();
+            let inp = trace_input(&prev, dvec2::new(200.0, t as f64), 2, 1);
+            prev = inp.inp;
+            inps.insert(player, inp);
+            game.set_player_inputs(inps);
+            kicked |=
+                game.tick(Default::default()).events.iter().any(
+                    |ev| matches!(ev, TickEvent::Kick { player_id, .. } if *player_id == player),
+                );
+        }
+        assert!(kicked);
+    }
+
+    #[test]
+    fn warmup_ready_threshold() {
+        let mut game = get_game_with_config::<3>(ConfigVanilla {
+            warmup_secs: 60,
+            warmup_ready_percentage: 60,
+            ..Default::default()
+        });
+        let [first, second, third] = join_players::<3>(&mut game);
+        for _ in 0..2 {
+            game.tick(Default::default());
+        }
+        let stage_id = game.game.players.player(&first).unwrap().stage_id();
+        let state = |game: &GameState| {
+            game.game
+                .stages
+                .get(&stage_id)
+                .unwrap()
+                .match_manager
+                .game_match
+                .state
+        };
+        assert!(matches!(
+            state(&game),
+            MatchState::Warmup {
+                ready_players: 0,
+                total_players: 3,
+                ..
+            }
+        ));
+
+        // kills don't count and the ammo is infinite during warmup
+        let stage = game.game.stages.get_mut(&stage_id).unwrap();
+        let victim_char = stage.world.characters.get_mut(&third).unwrap();
+        Character::take_damage_from(
+            victim_char,
+            &third,
+            first,
+            &Default::default(),
+            &Default::default(),
+            FriendlyFireTy::Dmg,
+            20,
+            DamageTypes::Character(&first),
+            DamageBy::Weapon {
+                weapon: WeaponType::Gun,
+                flags: Default::default(),
+            },
+        );
+        game.tick(Default::default());
+        let stage = game.game.stages.get(&stage_id).unwrap();
+        let first_char = stage.world.characters.get(&first).unwrap();
+        assert_eq!(first_char.score.get(), 0);
+        assert!(
+            first_char
+                .reusable_core
+                .weapons
+                .get(&WeaponType::Gun)
+                .unwrap()
+                .cur_ammo
+                .is_none()
+        );
+
+        // toggling the ready state twice makes the player unready again
+        ready(&mut game, &first);
+        ready(&mut game, &first);
+        game.tick(Default::default());
+        assert!(matches!(
+            state(&game),
+            MatchState::Warmup {
+                ready_players: 0,
+                ..
+            }
+        ));
+
+        ready(&mut game, &first);
+        game.tick(Default::default());
+        assert!(matches!(
+            state(&game),
+            MatchState::Warmup {
+                ready_players: 1,
+                total_players: 3,
+                ..
+            }
+        ));
+
+        // 2 of 3 players are more than 60%
+        ready(&mut game, &second);
+        game.tick(Default::default());
+        assert!(matches!(state(&game), MatchState::Running { .. }));
+
+        // readying after the start does nothing
+        ready(&mut game, &third);
+        game.tick(Default::default());
+        assert!(matches!(state(&game), MatchState::Running { .. }));
+    }
+
+    #[test]
+    fn warmup_timeout() {
+        let mut game = get_game_with_config::<2>(ConfigVanilla {
+            warmup_secs: 1,
+            ..Default::default()
+        });
+
+        // the warmup only runs out if players are in the game
+        for _ in 0..TICKS_PER_SECOND * 2 {
+            game.tick(Default::default());
+        }
+        let stage_id = game.stage_0_id;
+        let state = |game: &GameState| {
+            game.game
+                .stages
+                .get(&stage_id)
+                .unwrap()
+                .match_manager
+                .game_match
+                .state
+        };
+        assert!(matches!(
+            state(&game),
+            MatchState::Warmup {
+                total_players: 0,
+                ..
+            }
+        ));
+
+        // nobody gets ready, so the timeout starts the round
+        join_players::<2>(&mut game);
+        for _ in 0..TICKS_PER_SECOND - 1 {
+            game.tick(Default::default());
+        }
+        assert!(matches!(state(&game), MatchState::Warmup { .. }));
+        game.tick(Default::default());
+        assert!(matches!(state(&game), MatchState::Running { .. }));
+    }
+
+    #[test]
+    fn warmup_start_resets_round() {
+        let mut game = get_game_with_config::<2>(ConfigVanilla {
+            warmup_secs: 60,
+            ..Default::default()
+        });
+        let ids = join_players::<2>(&mut game);
+        for _ in 0..2 {
+            game.tick(Default::default());
+        }
+        let stage_id = game.game.players.player(&ids[0]).unwrap().stage_id();
+
+        let stage = game.game.stages.get_mut(&stage_id).unwrap();
+        for id in &ids {
+            let character = stage.world.characters.get_mut(id).unwrap();
+            character.score.set(3);
+            assert!(!character.phased.is_phased());
+        }
+
+        for id in &ids {
+            ready(&mut game, id);
+        }
+        game.tick(Default::default());
+
+        let stage = game.game.stages.get(&stage_id).unwrap();
+        assert!(matches!(
+            stage.match_manager.game_match.state,
+            MatchState::Running { .. }
+        ));
+        for id in &ids {
+            let character = stage.world.characters.get(id).unwrap();
+            assert_eq!(character.score.get(), 0);
+            // the character respawns at a spawn point
+            assert!(character.phased.is_phased());
+        }
+
+        for _ in 0..TICKS_PER_SECOND {
+            game.tick(Default::default());
+        }
+        let stage = game.game.stages.get(&stage_id).unwrap();
+        assert!(
+            ids.iter()
+                .all(|id| !stage.world.characters.get(id).unwrap().phased.is_phased())
+        );
+    }
+
+    /// A sided match that reaches the time limit after one second.
+    fn sided_match(config: ConfigVanilla) -> (Match, GameOptions, CharacterScores) {
+        (
+            Match {
+                ty: MatchType::Sided {
+                    scores: Default::default(),
+                },
+                state: MatchState::Running {
+                    round_ticks_passed: 0,
+                    round_ticks_left: TICKS_PER_SECOND.into(),
+                },
+                balance_tick: Default::default(),
+            },
+            GameOptions::new(GameType::Sided, config),
+            CharacterScores::default(),
+        )
+    }
+
+    fn set_side_scores(game_match: &mut Match, red: i64, blue: i64) {
+        game_match.ty = MatchType::Sided {
+            scores: [red, blue],
+        };
+    }
+
+    fn tick_match(
+        game_match: &mut Match,
+        game_options: &GameOptions,
+        scores: &CharacterScores,
+        ticks: u64,
+    ) {
+        for _ in 0..ticks {
+            game_match.tick(game_options, scores);
+        }
+    }
+
+    #[test]
+    fn overtime_golden_goal() {
+        let (mut game_match, game_options, scores) = sided_match(ConfigVanilla {
+            time_limit_secs: 1,
+            overtime_mode: ConfigOvertimeMode::GoldenGoal,
+            ..Default::default()
+        });
+
+        // tied at the time limit
+        set_side_scores(&mut game_match, 1, 1);
+        tick_match(&mut game_match, &game_options, &scores, TICKS_PER_SECOND);
+        assert!(matches!(
+            game_match.state,
+            MatchState::SuddenDeath {
+                overtime: 1,
+                overtime_ticks_left,
+                ..
+            } if overtime_ticks_left.is_none()
+        ));
+
+        // the golden goal never runs out
+        tick_match(
+            &mut game_match,
+            &game_options,
+            &scores,
+            TICKS_PER_SECOND * 60,
+        );
+        assert!(matches!(game_match.state, MatchState::SuddenDeath { .. }));
+
+        // the first score wins
+        set_side_scores(&mut game_match, 1, 2);
+        game_match.win_check(&game_options, &scores, false);
+        assert!(matches!(
+            game_match.state,
+            MatchState::GameOver {
+                winner: MatchWinner::Side(MatchSide::Blue),
+                by_cooldown: true,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn overtime_repeats() {
+        let (mut game_match, game_options, scores) = sided_match(ConfigVanilla {
+            time_limit_secs: 1,
+            overtime_mode: ConfigOvertimeMode::Timed,
+            overtime_secs: 1,
+            overtime_repeats: 1,
+            score_limit: 10,
+            ..Default::default()
+        });
+        let overtime = |game_match: &Match| match game_match.state {
+            MatchState::SuddenDeath {
+                overtime,
+                overtime_ticks_left,
+                ..
+            } => Some((overtime, overtime_ticks_left.is_some())),
+            _ => None,
+        };
+
+        set_side_scores(&mut game_match, 1, 1);
+        tick_match(&mut game_match, &game_options, &scores, TICKS_PER_SECOND);
+        assert_eq!(overtime(&game_match), Some((1, true)));
+
+        // a lead below the score limit does not end a timed overtime
+        set_side_scores(&mut game_match, 2, 1);
+        game_match.win_check(&game_options, &scores, false);
+        assert_eq!(overtime(&game_match), Some((1, true)));
+
+        // still tied, the overtime is repeated once
+        set_side_scores(&mut game_match, 2, 2);
+        tick_match(&mut game_match, &game_options, &scores, TICKS_PER_SECOND);
+        assert_eq!(overtime(&game_match), Some((2, true)));
+
+        // afterwards the next score wins
+        tick_match(&mut game_match, &game_options, &scores, TICKS_PER_SECOND);
+        assert_eq!(overtime(&game_match), Some((3, false)));
+        tick_match(
+            &mut game_match,
+            &game_options,
+            &scores,
+            TICKS_PER_SECOND * 60,
+        );
+        assert_eq!(overtime(&game_match), Some((3, false)));
+
+        set_side_scores(&mut game_match, 3, 2);
+        game_match.win_check(&game_options, &scores, false);
+        assert!(matches!(
+            game_match.state,
+            MatchState::GameOver {
+                winner: MatchWinner::Side(MatchSide::Red),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn overtime_end_and_score_limit() {
+        let config = ConfigVanilla {
+            time_limit_secs: 1,
+            overtime_mode: ConfigOvertimeMode::Timed,
+            overtime_secs: 1,
+            score_limit: 10,
+            ..Default::default()
+        };
+
+        // the leader wins when the overtime ends
+        let (mut game_match, game_options, scores) = sided_match(config.clone());
+        tick_match(&mut game_match, &game_options, &scores, TICKS_PER_SECOND);
+        set_side_scores(&mut game_match, 0, 1);
+        game_match.win_check(&game_options, &scores, false);
+        assert!(matches!(game_match.state, MatchState::SuddenDeath { .. }));
+        tick_match(&mut game_match, &game_options, &scores, TICKS_PER_SECOND);
+        assert!(matches!(
+            game_match.state,
+            MatchState::GameOver {
+                winner: MatchWinner::Side(MatchSide::Blue),
+                by_cooldown: true,
+                ..
+            }
+        ));
+
+        // the score limit ends the overtime early
+        let (mut game_match, game_options, scores) = sided_match(config.clone());
+        tick_match(&mut game_match, &game_options, &scores, TICKS_PER_SECOND);
+        set_side_scores(&mut game_match, 10, 0);
+        game_match.win_check(&game_options, &scores, false);
+        assert!(matches!(
+            game_match.state,
+            MatchState::GameOver {
+                winner: MatchWinner::Side(MatchSide::Red),
+                by_cooldown: false,
+                ..
+            }
+        ));
+
+        // unless the score limit is ignored during overtimes
+        let (mut game_match, game_options, scores) = sided_match(ConfigVanilla {
+            overtime_score_limit: false,
+            ..config
+        });
+        tick_match(&mut game_match, &game_options, &scores, TICKS_PER_SECOND);
+        set_side_scores(&mut game_match, 10, 0);
+        game_match.win_check(&game_options, &scores, false);
+        assert!(matches!(game_match.state, MatchState::SuddenDeath { .. }));
+    }
+
+    #[test]
+    fn sided_balance_plan() {
+        let mut game = get_game_with_config::<5>(ConfigVanilla {
+            game_type: ConfigGameType::Ctf,
+            auto_side_balance_secs: 0,
+            auto_side_balance_at_round_end: true,
+            side_balance_immunity_secs: 1,
+            ..Default::default()
+        });
+        let players = join_players::<5>(&mut game);
+        for _ in 0..10 {
+            game.tick(Default::default());
+        }
+        let stage_id = game.game.players.player(&players[0]).unwrap().stage_id();
+        let stage = game.game.stages.get_mut(&stage_id).unwrap();
+        let round_ticks = stage.match_manager.game_match.state.passed_ticks();
+        assert!(round_ticks >= 2);
+
+        // (score, ticks on the side)
+        let setups = [
+            (0, round_ticks + 100),
+            (5, round_ticks + 100),
+            (10, 0),
+            (10, 1),
+            (3, round_ticks + 100),
+        ];
+        for (id, (score, side_ticks)) in players.iter().zip(setups) {
+            let character = stage.world.characters.get_mut(id).unwrap();
+            character.core.side = Some(MatchSide::Red);
+            character.core.side_ticks = side_ticks;
+            character.score.set(score);
+        }
+        let [p0, _, p2, p3, p4] = players;
+        let plan = |game: &GameState| {
+            let stage = game.game.stages.get(&stage_id).unwrap();
+            stage.match_manager.sided_balance_plan(&stage.world)
+        };
+
+        // players that joined during the round first, the most recent one first,
+        // even if they have a higher score
+        assert_eq!(plan(&game), [p2, p3]);
+        // a single move is enough for 4 vs 1
+        let stage = game.game.stages.get_mut(&stage_id).unwrap();
+        stage.world.characters.get_mut(&p4).unwrap().core.side = Some(MatchSide::Blue);
+        assert_eq!(plan(&game), [p2]);
+        // balanced enough
+        let stage = game.game.stages.get_mut(&stage_id).unwrap();
+        stage.world.characters.get_mut(&p3).unwrap().core.side = Some(MatchSide::Blue);
+        assert!(plan(&game).is_empty());
+        // then the lowest score
+        let stage = game.game.stages.get_mut(&stage_id).unwrap();
+        for id in [p3, p4] {
+            stage.world.characters.get_mut(&id).unwrap().core.side = Some(MatchSide::Red);
+        }
+        stage
+            .world
+            .characters
+            .get_mut(&p2)
+            .unwrap()
+            .core
+            .balance_immunity = 10.into();
+        assert_eq!(plan(&game), [p3, p0]);
+        let stage = game.game.stages.get_mut(&stage_id).unwrap();
+        stage
+            .world
+            .characters
+            .get_mut(&p2)
+            .unwrap()
+            .core
+            .balance_immunity = Default::default();
+
+        // the plan is executed at the round end
+        let stage = game.game.stages.get_mut(&stage_id).unwrap();
+        stage.match_manager.game_match.state = MatchState::GameOver {
+            winner: MatchWinner::Side(MatchSide::Red),
+            new_game_in: 1.into(),
+            round_ticks_passed: round_ticks,
+            by_cooldown: false,
+        };
+        game.tick(Default::default());
+        let stage = game.game.stages.get_mut(&stage_id).unwrap();
+        for id in [p2, p3] {
+            let character = stage.world.characters.get(&id).unwrap();
+            assert_eq!(character.core.side, Some(MatchSide::Blue));
+            assert!(character.core.balance_immunity.is_some());
+        }
+
+        // moved players are immune for a while
+        for character in stage.world.characters.values_mut() {
+            character.core.side = Some(MatchSide::Red);
+        }
+        for _ in 0..TICKS_PER_SECOND {
+            let plan = plan(&game);
+            assert_eq!(plan.len(), 2);
+            assert!(!plan.contains(&p2) && !plan.contains(&p3));
+            game.tick(Default::default());
+        }
+        let mut plan = plan(&game);
+        plan.sort();
+        let mut expected = [p2, p3];
+        expected.sort();
+        assert_eq!(plan, expected);
+    }
+
+    /// The system messages that were sent to the player.
+    fn system_msgs(game: &GameState, player: PlayerId) -> Vec<String> {
+        let mut client_player_ids = PoolFxLinkedHashSet::new_without_pool();
+        client_player_ids.insert(player);
+        let events = game.events_for(EventClientInfo {
+            client_player_ids,
+            everything: false,
+            other_stages: false,
+        });
+        events
+            .worlds
+            .values()
+            .flat_map(|world| world.events.values())
+            .filter_map(|ev| match ev {
+                GameWorldEvent::Notification(GameWorldNotificationEvent::System(
+                    GameWorldSystemMessage::Custom(msg),
+                )) => Some(msg.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn chat_command_registry() {
+        let cmd =
+            |args: Vec<CommandArgType>, required_args: usize, usage: &str, cmd: u8| ChatCommand {
+                args: args
+                    .into_iter()
+                    .map(|ty| CommandArg { ty, user_ty: None })
+                    .collect(),
+                required_args,
+                usage: usage.try_into().unwrap(),
+                description: format!("description {cmd}").as_str().try_into().unwrap(),
+                permission: ChatCommandPermission::Everyone,
+                cmd,
+            };
+        let registry = ChatCommandRegistry::new(
+            vec![
+                ("ready".try_into().unwrap(), cmd(vec![], 0, "", 0)),
+                (
+                    "timeout".try_into().unwrap(),
+                    cmd(vec![CommandArgType::Number], 1, "<seconds>", 1),
+                ),
+                (
+                    "whisper".try_into().unwrap(),
+                    cmd(
+                        vec![CommandArgType::Number, CommandArgType::Text],
+                        1,
+                        "<player_id> [text]",
+                        2,
+                    ),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let cache = ParserCache::default();
+        let validate = |raw: &str| registry.validate(parse(raw, &registry.parser, &cache));
+
+        let res = validate("ready");
+        assert!(matches!(res.as_slice(), [Ok((0, _))]));
+        let res = validate("timeout 5");
+        assert!(matches!(res.as_slice(), [Ok((1, _))]));
+        // optional args can be left out
+        let res = validate("whisper 3");
+        assert!(matches!(res.as_slice(), [Ok((2, _))]));
+
+        let res = validate("timeout");
+        let [Err(err)] = res.as_slice() else {
+            panic!("{res:?}")
+        };
+        assert!(err.ends_with("Usage: /timeout <seconds>"), "{err}");
+        let res = validate("ready now");
+        let [Err(err)] = res.as_slice() else {
+            panic!("{res:?}")
+        };
+        assert_eq!(err, "Too many arguments. Usage: /ready");
+        let res = validate("unknown");
+        let [Err(err)] = res.as_slice() else {
+            panic!("{res:?}")
+        };
+        assert_eq!(err, "Unknown command /unknown, see /help.");
+
+        let exported = registry.export(vec!['/']);
+        assert_eq!(exported.cmds.len(), 3);
+        assert_eq!(exported.cmds["whisper"].len(), 2);
+        assert_eq!(
+            exported.infos["whisper"].usage.as_str(),
+            "<player_id> [text]"
+        );
+        assert_eq!(
+            exported.infos["timeout"].description.as_str(),
+            "description 1"
+        );
+        assert_eq!(exported.prefixes, vec!['/']);
+
+        assert_eq!(
+            registry.help(Some("timeout"))[0],
+            "Usage: /timeout <seconds>"
+        );
+        assert_eq!(registry.help(None)[0], "Commands (page 1/1):");
+    }
+
+    #[test]
+    fn chat_help() {
+        let mut game = get_game::<2>();
+        let [player, _] = join_players::<2>(&mut game);
+        game.tick(Default::default());
+        system_msgs(&game, player);
+
+        chat(&mut game, &player, "help");
+        let msgs = system_msgs(&game, player);
+        assert_eq!(msgs[0], "Commands (page 1/2):");
+        assert_eq!(msgs.len(), 1 + crate::chat_commands::HELP_PAGE_SIZE + 1);
+        chat(&mut game, &player, "help 2");
+        let msgs = system_msgs(&game, player);
+        assert_eq!(msgs[0], "Commands (page 2/2):");
+        assert!(
+            msgs.iter().any(|msg| msg.starts_with("/savepos")),
+            "{msgs:?}"
+        );
+
+        chat(&mut game, &player, "help practice");
+        assert_eq!(system_msgs(&game, player)[0], "Usage: /practice");
+        chat(&mut game, &player, "ready twice");
+        assert_eq!(
+            system_msgs(&game, player),
+            vec!["Too many arguments. Usage: /ready".to_string()]
+        );
+        chat(&mut game, &player, "unknown");
+        assert_eq!(
+            system_msgs(&game, player),
+            vec!["Unknown command /unknown, see /help.".to_string()]
+        );
+        assert!(game.chat_commands.infos.contains_key("help"));
+    }
+
+    #[test]
+    fn rcon_permissions() {
+        let mut game = get_game::<4>();
+        let mut exec = |raw: &str, auth_level: AuthLevel| {
+            game.rcon_command(
+                None,
+                ExecRconInput {
+                    raw: raw.try_into().unwrap(),
+                    auth_level,
+                },
+            )
+            .remove(0)
+        };
+
+        assert!(exec("info", AuthLevel::Moderator).is_ok());
+        assert!(exec("info", AuthLevel::None).is_err());
+
+        let err = exec("bots.remove_all", AuthLevel::Moderator).unwrap_err();
+        assert!(err.contains("requires Admin rights"), "{err}");
+        assert!(exec("bots.remove_all", AuthLevel::Admin).is_ok());
+    }
+
+    #[test]
+    fn ban_expiry() {
+        let mut bans = BanList::default();
+        let target = BanTarget::Account(1);
+        let identity = BanIdentity {
+            account_id: Some(1),
+            ip: None,
+        };
+        bans.add(
+            BanKind::Ban,
+            target,
+            Some(Duration::from_secs(60)),
+            "".to_string(),
+        );
+        bans.add(BanKind::Mute, target, None, "".to_string());
+
+        bans.advance(Duration::from_secs(59));
+        assert!(bans.find(BanKind::Ban, &identity).is_some());
+        bans.advance(Duration::from_secs(1));
+        assert!(bans.find(BanKind::Ban, &identity).is_none());
+        // permanent entries never expire
+        bans.advance(Duration::from_secs(60 * 60 * 24 * 365));
+        assert!(bans.find(BanKind::Mute, &identity).is_some());
+    }
+
+    #[test]
+    fn ban_account_over_ip() {
+        let mut bans = BanList::default();
+        bans.add(
+            BanKind::Ban,
+            "1.2.0.0/16".parse().unwrap(),
+            None,
+            "range".to_string(),
+        );
+        bans.add(
+            BanKind::Ban,
+            "1.2.3.0/24".parse().unwrap(),
+            None,
+            "subnet".to_string(),
+        );
+        bans.add(
+            BanKind::Ban,
+            "7".parse().unwrap(),
+            None,
+            "account".to_string(),
+        );
+
+        let find = |account_id, ip: &str| {
+            bans.find(
+                BanKind::Ban,
+                &BanIdentity {
+                    account_id,
+                    ip: Some(ip.parse().unwrap()),
+                },
+            )
+            .map(|entry| entry.reason.as_str())
+        };
+        assert_eq!(find(Some(7), "1.2.3.4"), Some("account"));
+        assert_eq!(find(Some(8), "1.2.3.4"), Some("subnet"));
+        assert_eq!(find(None, "1.2.4.4"), Some("range"));
+        assert_eq!(find(None, "1.3.0.1"), None);
+        assert!(bans.find(BanKind::Mute, &BanIdentity::default()).is_none());
+    }
+
+    #[test]
+    fn ban_rejects_join() {
+        let mut game = get_game::<2>();
+        let res = game.rcon_command(
+            None,
+            ExecRconInput {
+                raw: "bans.ban 1.2.3.0/24 90 griefing".try_into().unwrap(),
+                auth_level: AuthLevel::Moderator,
+            },
+        );
+        assert!(res[0].is_ok(), "{res:?}");
+
+        let join = |game: &mut GameState, ip: &str| {
+            game.player_join(&PlayerClientInfo {
+                info: NetworkCharacterInfo::explicit_default(),
+                id: 0,
+                unique_identifier: PlayerUniqueId::Account(1),
+                initial_network_stats: PlayerNetworkStats::default(),
+                ip: Some(ip.parse().unwrap()),
+            })
+        };
+        let banned = join(&mut game, "1.2.3.4");
+        let allowed = join(&mut game, "4.3.2.1");
+        let events = game.tick(Default::default()).events;
+        let kicks: Vec<_> = events
+            .iter()
+            .filter_map(|ev| match ev {
+                TickEvent::Kick {
+                    player_id,
+                    reason: PlayerKickReason::Custom(reason),
+                } => Some((*player_id, reason.to_string())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(kicks.len(), 1);
+        assert_eq!(kicks[0].0, banned);
+        assert_ne!(kicks[0].0, allowed);
+        assert_eq!(kicks[0].1, "You are banned for 1h 30min: griefing");
+    }
+
+    /// ctf1 with the given config variables & commands.
+    fn map_with_settings(config_variables: &[(&str, &str)], commands: &[&str]) -> Vec<u8> {
+        let file = include_bytes!("../../../data/map/maps/ctf1.twmap.tar");
+        let tp = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+        let mut map = Map::read(&MapFileReader::new(file.to_vec()).unwrap(), &tp).unwrap();
+        let value = |value: &str| CommandValue {
+            value: value.to_string(),
+            comment: None,
+        };
+        map.config.config_variables = config_variables
+            .iter()
+            .map(|(name, val)| (name.to_string(), value(val)))
+            .collect();
+        map.config.commands = commands.iter().map(|cmd| value(cmd)).collect();
+        map.write(&tp).unwrap()
+    }
+
+    #[test]
+    fn map_settings() {
+        let map = map_with_settings(
+            &[
+                ("vanilla.game_type", "ctf"),
+                ("vanilla.score_limit", "7"),
+                ("vanilla.max_ingame_players", "64"),
+                ("vanilla.time_limit_secs", "not a number"),
+            ],
+            &["tune gravity 1", "tune gravity", "echo hello"],
+        );
+
+        let mut map_config = Map::read_config(&MapFileReader::new(map.clone()).unwrap()).unwrap();
+        let mut config = ConfigVanilla::default();
+        let mut tunes = Tunings::default();
+        let rejected: Vec<_> = apply_map_settings(&map_config, &mut config, &mut tunes)
+            .into_iter()
+            .map(|rejected| (rejected.setting, rejected.err))
+            .collect();
+        assert!(matches!(
+            &rejected[..],
+            [
+                (_, MapSettingError::NotAllowed),
+                (_, MapSettingError::InvalidValue(_)),
+                (_, MapSettingError::InvalidValue(_)),
+                (_, MapSettingError::UnknownCommand),
+            ]
+        ));
+        assert_eq!(rejected[0].0, "vanilla.max_ingame_players 64");
+        assert_eq!(rejected[3].0, "echo hello");
+        assert_eq!(config.game_type, ConfigGameType::Ctf);
+        assert_eq!(config.score_limit, 7);
+        assert_eq!(config.time_limit_secs, 0);
+        assert_eq!(config.max_ingame_players, 16);
+        assert_eq!(tunes.gravity, 1.0);
+
+        // the same through the game state
+        let game = get_game_with_map::<4>(map.clone(), Default::default());
+        assert_eq!(game.game_options.game_ty(), ConfigGameType::Ctf);
+        assert_eq!(game.game_options.score_limit(), 7);
+        assert_eq!(game.game_options.max_ingame_players(), 4);
+        assert_eq!(game.collision.tune_zones[0].gravity, 1.0);
+
+        // the server ignores the map
+        let game = get_game_with_map::<4>(
+            map,
+            ConfigVanilla {
+                ignore_map_settings: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(game.game_options.game_ty(), ConfigGameType::Dm);
+        assert_eq!(game.game_options.score_limit(), 100);
+        assert_eq!(game.collision.tune_zones[0].gravity, 0.5);
+
+        // maps can't change settings outside of the whitelist
+        map_config.config_variables.clear();
+        map_config.commands.clear();
+        for name in [
+            "vanilla.ignore_map_settings",
+            "vanilla.bot_count",
+            "sv.name",
+        ] {
+            map_config.config_variables.insert(
+                name.to_string(),
+                CommandValue {
+                    value: "1".to_string(),
+                    comment: None,
+                },
+            );
+        }
+        let rejected = apply_map_settings(&map_config, &mut config, &mut tunes);
+        assert_eq!(rejected.len(), 3);
+        assert!(
+            rejected
+                .iter()
+                .all(|rejected| rejected.err == MapSettingError::NotAllowed)
+        );
+        assert!(!config.ignore_map_settings);
+        assert_eq!(config.bot_count, 0);
+    }
+
+    #[test]
+    fn dummy_join() {
+        let mut game = get_game::<3>();
+        // main player & dummy of the same client share the unique identifier
+        let [main, dummy] = [0, 1].map(|id| {
+            game.player_join(&PlayerClientInfo {
+                info: NetworkCharacterInfo::explicit_default(),
+                id,
+                unique_identifier: PlayerUniqueId::Account(0),
+                initial_network_stats: PlayerNetworkStats::default(),
+                ip: None,
+            })
+        });
+        assert_ne!(main, dummy);
+        for _ in 0..2 {
+            game.tick(Default::default());
+        }
+        let stage_id = game.game.players.player(&main).unwrap().stage_id();
+        assert_eq!(
+            game.game.players.player(&dummy).unwrap().stage_id(),
+            stage_id
+        );
+
+        // both are local players of the client
+        let mut client_ids = PoolFxLinkedHashSet::new_without_pool();
+        client_ids.insert(main);
+        client_ids.insert(dummy);
+        let snapshot = game.snap_shot_manager.snapshot_for(
+            &game,
+            SnapshotFor::Client(SnapshotClientInfo::ForPlayerIds(client_ids)),
+        );
+        assert!(snapshot.local_players.contains_key(&main));
+        assert!(snapshot.local_players.contains_key(&dummy));
+
+        // only the main player's statistics are persisted for the account
+        let stage = game.game.stages.get_mut(&stage_id).unwrap();
+        stage.match_manager.game_match.state = MatchState::GameOver {
+            winner: MatchWinner::Character(main),
+            new_game_in: 10.into(),
+            round_ticks_passed: 0,
+            by_cooldown: false,
+        };
+        let mut pipe =
+            SimulationPipeStage::new(false, &game.collision, &stage_id, &game.world_pool);
+        stage.tick(&mut pipe);
+        assert_eq!(stage.match_manager.finished_stats.len(), 1);
+        assert_eq!(stage.match_manager.finished_stats[0].1.id, main);
+
+        // the dummy leaves, the main player stays
+        game.player_drop(&dummy, PlayerDropReason::Disconnect);
+        assert!(game.game.players.player(&dummy).is_none());
+        assert!(game.game.players.player(&main).is_some());
+
+        // a rejoining dummy is a new player
+        let rejoined = game.player_join(&PlayerClientInfo {
+            info: NetworkCharacterInfo::explicit_default(),
+            id: 1,
+            unique_identifier: PlayerUniqueId::Account(0),
+            initial_network_stats: PlayerNetworkStats::default(),
+            ip: None,
+        });
+        assert_ne!(rejoined, main);
+        assert_ne!(rejoined, dummy);
+    }
+
+    #[test]
+    fn view_culling() {
+        let mut game = get_game_with_config::<2>(ConfigVanilla {
+            view_culling: true,
+            ..Default::default()
+        });
+        let [first, second] = join_players::<2>(&mut game);
+        // the game is full, so this player is a spectator
+        let spectator = game.player_join(&PlayerClientInfo {
+            info: NetworkCharacterInfo::explicit_default(),
+            id: 0,
+            unique_identifier: PlayerUniqueId::Account(2),
+            initial_network_stats: PlayerNetworkStats::default(),
+            ip: None,
+        });
+        assert!(game.game.spectator_players.contains_key(&spectator));
+        for _ in 0..2 {
+            game.tick(Default::default());
+        }
+
+        // move the second character far away from the first one
+        let stage_id = game.game.players.player(&first).unwrap().stage_id();
+        let stage = game.game.stages.get_mut(&stage_id).unwrap();
+        let first_pos = *stage.world.characters.get(&first).unwrap().pos.pos();
+        stage
+            .world
+            .characters
+            .get_mut(&second)
+            .unwrap()
+            .pos
+            .move_pos(first_pos + vec2::new(200.0, 0.0) * 32.0);
+
+        let snapped_characters = |game: &GameState, client: SnapshotClientInfo| {
+            let snapshot = game
+                .snap_shot_manager
+                .snapshot_for(game, SnapshotFor::Client(client));
+            snapshot
+                .stages
+                .values()
+                .flat_map(|stage| stage.world.characters.keys().copied())
+                .collect::<Vec<_>>()
+        };
+        let for_player = |id: PlayerId| {
+            let mut ids = PoolFxLinkedHashSet::new_without_pool();
+            ids.insert(id);
+            SnapshotClientInfo::ForPlayerIds(ids)
+        };
+
+        // the free camera of the spectator looks at the first character
+        let game_inps: Pool<FxLinkedHashMap<PlayerId, CharacterInputInfo>> = Pool::with_capacity(1);
+        let mut inp = CharacterInput::default();
+        inp.cursor.set(CharacterInputCursor::from_vec2(&dvec2::new(
+            first_pos.x as f64 / 32.0,
+            first_pos.y as f64 / 32.0,
+        )));
+        let mut inps = game_inps.new();
+        inps.insert(
+            spectator,
+            CharacterInputInfo {
+                inp,
+                diff: Default::default(),
+            },
+        );
+        game.set_player_inputs(inps);
+        assert_eq!(
+            snapped_characters(&game, for_player(spectator)),
+            vec![first]
+        );
+
+        // following the second character only shows its surroundings
+        game.client_command(
+            &spectator,
+            ClientCommand::SetCameraMode(ClientCameraMode::FreeCam([second].into_iter().collect())),
+        );
+        assert_eq!(
+            snapped_characters(&game, for_player(spectator)),
+            vec![second]
+        );
+
+        // players always see their own character
+        assert_eq!(snapped_characters(&game, for_player(first)), vec![first]);
+        assert_eq!(snapped_characters(&game, for_player(second)), vec![second]);
+
+        let mut everything = snapped_characters(&game, SnapshotClientInfo::Everything);
+        everything.sort();
+        assert_eq!(everything, vec![first, second]);
+    }
+
+    #[test]
+    fn interest_management() {
+        const NUM_PLAYERS: usize = 64;
+        let mut game = get_game_with_config::<NUM_PLAYERS>(ConfigVanilla {
+            game_type: ConfigGameType::Ctf,
+            interest_radius: 10,
+            interest_hysteresis: 2,
+            ..Default::default()
+        });
+        let ids = join_players::<NUM_PLAYERS>(&mut game);
+        game.tick(Default::default());
+
+        // spread the players over the whole map
+        let width = game.collision.get_playfield_width() as f32;
+        let height = game.collision.get_playfield_height() as f32;
+        for (index, id) in ids.iter().enumerate() {
+            let stage_id = game.game.players.player(id).unwrap().stage_id();
+            let x = (index % 8) as f32 + 0.5;
+            let y = (index / 8) as f32 + 0.5;
+            game.game
+                .stages
+                .get_mut(&stage_id)
+                .unwrap()
+                .world
+                .characters
+                .get_mut(id)
+                .unwrap()
+                .pos
+                .move_pos(vec2::new(x * width / 8.0, y * height / 8.0) * 32.0);
+        }
+
+        let snapshot_size = |snapshot: &Snapshot| {
+            bincode::serde::encode_to_vec(snapshot, bincode::config::standard())
+                .unwrap()
+                .len()
+        };
+        let flag_count = |snapshot: &Snapshot| {
+            snapshot
+                .stages
+                .values()
+                .map(|stage| stage.world.red_flags.len() + stage.world.blue_flags.len())
+                .sum::<usize>()
+        };
+
+        let mut full_size = 0;
+        let mut culled_size = 0;
+        for _ in 0..3 {
+            let everything = game
+                .snap_shot_manager
+                .snapshot_for(&game, SnapshotFor::Client(SnapshotClientInfo::Everything));
+            let flags = flag_count(&everything);
+            assert!(flags > 0);
+
+            for id in &ids {
+                let mut client_ids = PoolFxLinkedHashSet::new_without_pool();
+                client_ids.insert(*id);
+                let snapshot = game.snap_shot_manager.snapshot_for(
+                    &game,
+                    SnapshotFor::Client(SnapshotClientInfo::ForPlayerIds(client_ids)),
+                );
+                // flags & the own character are always included
+                assert_eq!(flag_count(&snapshot), flags);
+                assert!(
+                    snapshot
+                        .stages
+                        .values()
+                        .any(|stage| stage.world.characters.contains_key(id))
+                );
+
+                full_size += snapshot_size(&everything);
+                culled_size += snapshot_size(&snapshot);
+            }
+            game.tick(Default::default());
+        }
+        println!("snapshot bytes without interest management: {full_size}, with: {culled_size}");
+        assert!(culled_size < full_size);
+    }
+
+    #[test]
+    fn flag_drop_physics() {
+        let mut game = get_game_with_config::<2>(ConfigVanilla {
+            game_type: ConfigGameType::Ctf,
+            flag_return_secs: 1,
+            flag_regrab_delay_ms: 200,
+            ..Default::default()
+        });
+        let [player, _] = join_players::<2>(&mut game);
+        for _ in 0..2 {
+            game.tick(Default::default());
+        }
+        let options = game.game_options.flag_options();
+        assert_eq!(options.return_ticks, TICKS_PER_SECOND);
+        assert_eq!(options.regrab_delay_ticks, TICKS_PER_SECOND / 5);
+
+        let stage_id = game.game.players.player(&player).unwrap().stage_id();
+        let collision = &game.collision;
+        let world = &mut game.game.stages.get_mut(&stage_id).unwrap().world;
+        let character = world.characters.get_mut(&player).unwrap();
+        let char_pos = *character.pos.pos();
+        let carrier_vel = vec2::new(6.0, -6.0);
+        character.core.core.vel = carrier_vel;
+        // the flag of the other side
+        let (flags, other_team_flags) = match character.core.side.unwrap() {
+            MatchSide::Red => (&mut world.blue_flags, &world.red_flags),
+            MatchSide::Blue => (&mut world.red_flags, &world.blue_flags),
+        };
+        let flag = flags.values_mut().next().unwrap();
+        let mut tick = |flag: &mut Flag, options: &FlagOptions| {
+            flag.tick_deferred(&mut SimulationPipeFlag::new(
+                collision,
+                &mut world.characters,
+                &world.play_field,
+                other_team_flags,
+                options,
+                false,
+            ));
+        };
+
+        flag.core.carrier = Some(player);
+        tick(flag, &options);
+        assert_eq!(flag.core.pos, char_pos);
+        assert_eq!(flag.core.vel, carrier_vel);
+
+        // the dropped flag keeps half of the carrier's velocity
+        flag.release(&options);
+        assert_eq!(flag.core.carrier, None);
+        assert_eq!(flag.core.vel, carrier_vel * 0.5);
+        assert_eq!(flag.core.drop_ticks, Some(options.return_ticks));
+        let gravity = collision.get_tune_at(&char_pos).gravity;
+        let mut expected_vel = carrier_vel * 0.5;
+        let mut expected_pos = char_pos;
+        for _ in 0..2 {
+            tick(flag, &options);
+            expected_vel.y += gravity;
+            expected_pos += expected_vel;
+            assert!(distance(&flag.core.pos, &expected_pos) < 0.01);
+        }
+
+        // the dropper can't pick it up again until the delay is over
+        for _ in 2..options.regrab_delay_ticks {
+            flag.core.pos = char_pos;
+            flag.core.vel = Default::default();
+            tick(flag, &options);
+            assert_eq!(flag.core.carrier, None);
+        }
+        flag.core.pos = char_pos;
+        tick(flag, &options);
+        assert_eq!(flag.core.carrier, Some(player));
+        assert_eq!(flag.core.drop_ticks, None);
+
+        // returns to its base after the return time
+        let options = FlagOptions {
+            regrab_delay_ticks: options.return_ticks * 2,
+            ..options
+        };
+        flag.release(&options);
+        for ticks_left in (0..options.return_ticks).rev() {
+            flag.core.pos = char_pos;
+            flag.core.vel = Default::default();
+            tick(flag, &options);
+            assert_eq!(flag.core.drop_ticks, Some(ticks_left));
+        }
+        tick(flag, &options);
+        assert_eq!(flag.core.drop_ticks, None);
+        assert_eq!(flag.core.pos, flag.core.spawn_pos);
+        assert_eq!(flag.core.regrab_cooldown, None);
+    }
+
+    #[test]
+    fn hook_drag_options() {
+        let mut game = get_game::<2>();
+        let [hooker, target] = join_players::<2>(&mut game);
+        for _ in 0..2 {
+            game.tick(Default::default());
+        }
+        let stage_id = game.game.players.player(&hooker).unwrap().stage_id();
+
+        // no gravity, friction or speed limits,
+        // so the pull of the hook is the only change of the velocity
+        let tunes = &mut game.collision.tune_zones[0];
+        tunes.gravity = 0.0;
+        tunes.ground_friction = 1.0;
+        tunes.air_friction = 1.0;
+        tunes.hook_drag_speed = 1000.0;
+        tunes.velramp_start = f32::MAX;
+
+        // two spawns close to each other, the hooker is above the target,
+        // so the pull never goes into the ground
+        let spawns: Vec<vec2> = game
+            .spawns
+            .spawns
+            .iter()
+            .chain(game.spawns.spawns_red.iter())
+            .chain(game.spawns.spawns_blue.iter())
+            .copied()
+            .collect();
+        let (hooker_pos, target_pos) = spawns
+            .iter()
+            .flat_map(|a| spawns.iter().map(move |b| (*a, *b)))
+            .filter(|(a, b)| a.y <= b.y && (60.0..300.0).contains(&distance(a, b)))
+            .min_by(|(a1, b1), (a2, b2)| distance(a1, b1).total_cmp(&distance(a2, b2)))
+            .unwrap();
+
+        // returns the velocity of the target & whether the hook is attached to it
+        let hook_tick = |game: &mut GameState, hook_state: HookState| {
+            let world = &mut game.game.stages.get_mut(&stage_id).unwrap().world;
+            let dir = normalize(&(target_pos - hooker_pos));
+            let c = world.characters.get_mut(&hooker).unwrap();
+            c.pos.move_pos(hooker_pos);
+            c.core.core.vel = vec2::default();
+            c.core.input.state.hook.set(true);
+            c.phased.hook_mut().set(
+                Hook::Active {
+                    hook_pos: target_pos - dir * 40.0,
+                    hook_dir: dir,
+                    hook_tele_base: vec2::default(),
+                    hook_tick: 0,
+                    hook_state,
+                },
+                (hook_state == HookState::HookGrabbed).then_some(target),
+            );
+            let c = world.characters.get_mut(&target).unwrap();
+            c.pos.move_pos(target_pos);
+            c.core.core.vel = vec2::default();
+
+            game.tick(Default::default());
+
+            let world = &game.game.stages[&stage_id].world;
+            (
+                world.characters.get(&target).unwrap().core.core.vel,
+                world
+                    .characters
+                    .get(&hooker)
+                    .unwrap()
+                    .phased
+                    .hook()
+                    .hooked_char()
+                    == Some(target),
+            )
+        };
+        let config = game.game_options.config_clone();
+
+        let (base_vel, attached) = hook_tick(&mut game, HookState::HookGrabbed);
+        assert!(attached);
+        assert!(length(&base_vel) > 0.1);
+
+        game.game_options.replace_conf(ConfigVanilla {
+            hook_drag_percentage: 200,
+            ..config.clone()
+        });
+        let (vel, _) = hook_tick(&mut game, HookState::HookGrabbed);
+        assert!(distance(&vel, &(base_vel * 2.0)) < 0.02);
+
+        // the tune of the zone multiplies the config
+        game.collision.tune_zones[0].hook_drag_strength = 0.5;
+        let (vel, _) = hook_tick(&mut game, HookState::HookGrabbed);
+        assert!(distance(&vel, &base_vel) < 0.02);
+        game.collision.tune_zones[0].hook_drag_strength = 1.0;
+
+        // disabled hooking neither attaches nor pulls
+        game.game_options.replace_conf(ConfigVanilla {
+            player_hooking: false,
+            ..config.clone()
+        });
+        let (_, attached) = hook_tick(&mut game, HookState::HookFlying);
+        assert!(!attached);
+        let (vel, _) = hook_tick(&mut game, HookState::HookGrabbed);
+        assert_eq!(vel, vec2::default());
+
+        game.game_options.replace_conf(config);
+        let (_, attached) = hook_tick(&mut game, HookState::HookFlying);
+        assert!(attached);
+    }
+
+    #[test]
+    fn spawn_protection() {
+        let mut game = get_game_with_config::<2>(ConfigVanilla {
+            spawn_protection_ms: 1000,
+            ..Default::default()
+        });
+        let [attacker, target] = join_players::<2>(&mut game);
+        for _ in 0..2 {
+            game.tick(Default::default());
+        }
+        let stage_id = game.game.players.player(&target).unwrap().stage_id();
+
+        let is_protected = |game: &GameState, id: &PlayerId| {
+            let snapshot = game
+                .snap_shot_manager
+                .snapshot_for(game, SnapshotFor::Client(SnapshotClientInfo::Everything));
+            snapshot.stages[&stage_id].world.characters[id]
+                .core
+                .spawn_protection
+                .is_some()
+        };
+        let damage = |game: &mut GameState| {
+            let target_char = game
+                .game
+                .stages
+                .get_mut(&stage_id)
+                .unwrap()
+                .world
+                .characters
+                .get_mut(&target)
+                .unwrap();
+            Character::take_damage_from(
+                target_char,
+                &target,
+                attacker,
+                &Default::default(),
+                &Default::default(),
+                FriendlyFireTy::Dmg,
+                3,
+                DamageTypes::Character(&attacker),
+                DamageBy::Weapon {
+                    weapon: WeaponType::Gun,
+                    flags: Default::default(),
+                },
+            );
+            target_char.core.health
+        };
+
+        assert!(is_protected(&game, &attacker));
+        assert!(is_protected(&game, &target));
+        assert_eq!(damage(&mut game), 10);
+
+        // firing a weapon ends the protection early
+        let game_inps: Pool<FxLinkedHashMap<PlayerId, CharacterInputInfo>> = Pool::with_capacity(1);
+        let mut inp = CharacterInput::default();
+        inp.consumable
+            .fire
+            .add(1, CharacterInputCursor::from_vec2(&dvec2::new(0.0, -1.0)));
+        let mut inps = game_inps.new();
+        inps.insert(
+            attacker,
+            CharacterInputInfo {
+                inp,
+                diff: inp.consumable.diff(&Default::default()),
+            },
+        );
+        game.set_player_inputs(inps);
+        game.tick(Default::default());
+        assert!(!is_protected(&game, &attacker));
+        assert!(is_protected(&game, &target));
+
+        // otherwise it lasts for the configured time
+        let remaining = game.game.stages[&stage_id]
+            .world
+            .characters
+            .get(&target)
+            .unwrap()
+            .core
+            .spawn_protection
+            .get()
+            .unwrap()
+            .get();
+        assert!(remaining < TICKS_PER_SECOND);
+        for _ in 1..remaining {
+            game.tick(Default::default());
+        }
+        assert!(is_protected(&game, &target));
+        assert_eq!(damage(&mut game), 10);
+        game.tick(Default::default());
+        assert!(!is_protected(&game, &target));
+        assert_eq!(damage(&mut game), 7);
+    }
+
+    #[test]
+    fn spawn_loadout() {
+        let loadout = |weapons: &[(&str, i32)], active_weapon: &str| ConfigSpawnLoadout {
+            weapons: weapons
+                .iter()
+                .map(|(weapon, ammo)| ConfigLoadoutWeapon {
+                    weapon: weapon.to_string(),
+                    ammo: *ammo,
+                })
+                .collect(),
+            active_weapon: active_weapon.to_string(),
+        };
+
+        let mut game = get_game_with_config::<1>(ConfigVanilla {
+            game_type: ConfigGameType::Dm,
+            spawn_loadouts: ConfigSpawnLoadouts {
+                dm: loadout(&[("hammer", -1), ("Shotgun", 5), ("laser", -1)], "laser"),
+                // the loadout of other game types is ignored
+                ctf: loadout(&[("gun", 10)], "gun"),
+            },
+            ..Default::default()
+        });
+        let [player] = join_players::<1>(&mut game);
+        for _ in 0..2 {
+            game.tick(Default::default());
+        }
+        let stage_id = game.game.players.player(&player).unwrap().stage_id();
+        let character = game.game.stages[&stage_id]
+            .world
+            .characters
+            .get(&player)
+            .unwrap();
+        let mut weapons: Vec<_> = character
+            .reusable_core
+            .weapons
+            .iter()
+            .map(|(ty, weapon)| (*ty, weapon.cur_ammo))
+            .collect();
+        weapons.sort();
+        assert_eq!(
+            weapons,
+            vec![
+                (WeaponType::Hammer, None),
+                (WeaponType::Shotgun, Some(5)),
+                (WeaponType::Laser, None),
+            ]
+        );
+        assert_eq!(character.core.active_weapon, WeaponType::Laser);
+
+        // the default is hammer & gun
+        assert_eq!(
+            SpawnLoadout::from_config(&Default::default()).unwrap(),
+            SpawnLoadout::default()
+        );
+
+        // invalid loadouts are rejected when the game is created
+        let invalid = |dm: ConfigSpawnLoadout| {
+            let file = include_bytes!("../../../data/map/maps/ctf1.twmap.tar");
+            GameState::new(
+                file.to_vec(),
+                "ctf1".try_into().unwrap(),
+                GameStateCreateOptions {
+                    config: Some(
+                        serde_json::to_vec(&ConfigVanilla {
+                            game_type: ConfigGameType::Dm,
+                            spawn_loadouts: ConfigSpawnLoadouts {
+                                dm,
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        })
+                        .unwrap(),
+                    ),
+                    ..Default::default()
+                },
+                IoRuntime::new(create_runtime()),
+                Arc::new(DummyDb),
+            )
+            .err()
+            .unwrap()
+            .to_string()
+        };
+        let err = invalid(loadout(&[("hammer", -1), ("rocket", 3)], "hammer"));
+        assert!(err.contains("spawn_loadouts.dm"), "{err}");
+        assert!(err.contains("unknown weapon \"rocket\""), "{err}");
+        let err = invalid(loadout(&[("hammer", -1)], "gun"));
+        assert!(err.contains("not part of the loadout"), "{err}");
+        let err = invalid(loadout(&[("gun", 10), ("gun", 5)], "gun"));
+        assert!(err.contains("twice"), "{err}");
+    }
+
+    /// ctf1 with a tile of the given index in the game layer at the position.
+    /// A map without walls, except for a floor at `floor_row`.
+    fn map_with_floor(floor_row: usize) -> Vec<u8> {
+        let file = include_bytes!("../../../data/map/maps/ctf1.twmap.tar");
+        let tp = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+        let mut map = Map::read(&MapFileReader::new(file.to_vec()).unwrap(), &tp).unwrap();
+        let width = map.groups.physics.attr.width.get() as usize;
+        for layer in map.groups.physics.layers.iter_mut() {
+            if let MapLayerPhysics::Game(layer) = layer {
+                for (index, tile) in layer.tiles.iter_mut().enumerate() {
+                    if index / width == floor_row {
+                        tile.index = DdraceTileNum::Solid as u8;
+                    } else if tile.index == DdraceTileNum::Solid as u8
+                        || tile.index == DdraceTileNum::Death as u8
+                        || tile.index == DdraceTileNum::NoHook as u8
+                    {
+                        tile.index = 0;
+                    }
+                }
+            }
+        }
+        map.write(&tp).unwrap()
+    }
+
+    /// Jumps to the right from the floor, until the character lands again.
+    /// Returns the height of the apex & the range of the jump.
+    fn jump_arc(ticks_per_second: u64) -> (f32, f32) {
+        const FLOOR_ROW: usize = 14;
+        let mut game = get_game_with_map::<1>(
+            map_with_floor(FLOOR_ROW),
+            ConfigVanilla {
+                ticks_per_second,
+                ..Default::default()
+            },
+        );
+        let [player] = join_players::<1>(&mut game);
+        for _ in 0..2 {
+            game.tick(Default::default());
+        }
+        let stage_id = game.game.players.player(&player).unwrap().stage_id();
+
+        // standing on the floor
+        let start = vec2::new(10.5 * 32.0, FLOOR_ROW as f32 * 32.0 - 14.0);
+        let world = &mut game.game.stages.get_mut(&stage_id).unwrap().world;
+        let c = world.characters.get_mut(&player).unwrap();
+        c.pos.move_pos(start);
+        c.core.core.vel = vec2::default();
+        c.core.input.state.dir.set(1);
+        c.core.input.state.jump.set(true);
+
+        let mut apex = start.y;
+        for tick in 0..ticks_per_second * 5 {
+            game.tick(Default::default());
+            let world = &game.game.stages[&stage_id].world;
+            let pos = *world.characters.get(&player).unwrap().pos.pos();
+            apex = apex.min(pos.y);
+            if tick > 0 && pos.y >= start.y - 1.0 {
+                return (start.y - apex, pos.x - start.x);
+            }
+        }
+        panic!("the character never landed");
+    }
+
+    #[test]
+    fn tick_rate_jump_arc() {
+        let (apex_50, range_50) = jump_arc(50);
+        assert!(apex_50 > 100.0, "{apex_50}");
+        assert!(range_50 > 100.0, "{range_50}");
+
+        // the tunings are scaled, so the arc stays the same
+        let (apex, range) = jump_arc(100);
+        assert!((apex / apex_50 - 1.0).abs() < 0.05, "{apex_50} vs {apex}");
+        assert!(
+            (range / range_50 - 1.0).abs() < 0.05,
+            "{range_50} vs {range}"
+        );
+
+        // & every rate is deterministic
+        assert_eq!(jump_arc(100), jump_arc(100));
+    }
+
+    fn map_with_tile(pos: &vec2, index: u8) -> Vec<u8> {
+        let file = include_bytes!("../../../data/map/maps/ctf1.twmap.tar");
+        let tp = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+        let mut map = Map::read(&MapFileReader::new(file.to_vec()).unwrap(), &tp).unwrap();
+        let width = map.groups.physics.attr.width.get() as usize;
+        let tile_index = (pos.y / 32.0) as usize * width + (pos.x / 32.0) as usize;
+        for layer in map.groups.physics.layers.iter_mut() {
+            if let MapLayerPhysics::Game(layer) = layer {
+                layer.tiles[tile_index].index = index;
+            }
+        }
+        map.write(&tp).unwrap()
+    }
+
+    #[test]
+    fn damage_tiles() {
+        const DAMAGE_TILE: u8 = 200;
+        let game = get_game::<1>();
+        let pos = *game
+            .spawns
+            .spawns
+            .iter()
+            .chain(game.spawns.spawns_red.iter())
+            .chain(game.spawns.spawns_blue.iter())
+            .next()
+            .unwrap();
+        let mut game = get_game_with_map::<1>(
+            map_with_tile(&pos, DAMAGE_TILE),
+            ConfigVanilla {
+                damage_tile_index: DAMAGE_TILE as u32,
+                damage_tile_damage: 3,
+                damage_tile_interval_ms: 200,
+                ..Default::default()
+            },
+        );
+        let [player] = join_players::<1>(&mut game);
+        for _ in 0..2 {
+            game.tick(Default::default());
+        }
+        let stage_id = game.game.players.player(&player).unwrap().stage_id();
+
+        let world = &mut game.game.stages.get_mut(&stage_id).unwrap().world;
+        let c = world.characters.get_mut(&player).unwrap();
+        c.core.armor = 5;
+        c.core.spawn_protection = 20.into();
+
+        // returns the health & armor, `None` if the character died
+        let stand_tick = |game: &mut GameState| {
+            let world = &mut game.game.stages.get_mut(&stage_id).unwrap().world;
+            let c = world.characters.get_mut(&player).unwrap();
+            c.pos.move_pos(pos);
+            c.core.core.vel = vec2::default();
+
+            game.tick(Default::default());
+
+            let world = &game.game.stages[&stage_id].world;
+            let c = world.characters.get(&player).unwrap();
+            (!matches!(c.phased, CharacterPhasedState::Dead(_)))
+                .then_some((c.core.health, c.core.armor))
+        };
+
+        // immune during the spawn protection
+        for _ in 0..19 {
+            assert_eq!(stand_tick(&mut game), Some((10, 5)));
+        }
+        // the armor is damaged first, then every 10 ticks
+        let mut expected = [(10, 2), (9, 0), (6, 0), (3, 0)].into_iter();
+        let mut cur = None;
+        for tick in 0..40 {
+            if tick % 10 == 0 {
+                cur = expected.next();
+            }
+            assert_eq!(stand_tick(&mut game), cur);
+        }
+        assert_eq!(stand_tick(&mut game), None);
+    }
+
+    /// ctf1 with a hint of the switch zone at the position.
+    fn map_with_hint(pos: &vec2, zone: u8, hint: &str) -> Vec<u8> {
+        let file = include_bytes!("../../../data/map/maps/ctf1.twmap.tar");
+        let tp = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+        let mut map = Map::read(&MapFileReader::new(file.to_vec()).unwrap(), &tp).unwrap();
+        let width = map.groups.physics.attr.width.get() as usize;
+        let height = map.groups.physics.attr.height.get() as usize;
+        let mut tiles = vec![SwitchTile::default(); width * height];
+        let tile = &mut tiles[(pos.y / 32.0) as usize * width + (pos.x / 32.0) as usize];
+        tile.base.index = 1;
+        tile.number = zone;
+        let layers = &mut map.groups.physics.layers;
+        layers.retain(|layer| !matches!(layer, MapLayerPhysics::Switch(_)));
+        layers.push(MapLayerPhysics::Switch(MapLayerTilePhysicsSwitch {
+            base: MapLayerTilePhysicsBase { tiles },
+            switch_names: Default::default(),
+        }));
+        map.config.commands = [format!("hint {zone} {hint}")]
+            .into_iter()
+            .map(|value| CommandValue {
+                value,
+                comment: None,
+            })
+            .collect();
+        map.write(&tp).unwrap()
+    }
+
+    #[test]
+    fn map_hints() {
+        let game = get_game::<1>();
+        let pos = *game.spawns.spawns_red.first().unwrap();
+        let mut game = get_game_with_map::<3>(
+            map_with_hint(&pos, 3, "press {bind:+jump} twice to double jump"),
+            Default::default(),
+        );
+        let [first, second] = join_players::<2>(&mut game);
+        for _ in 0..2 {
+            game.tick(Default::default());
+        }
+
+        // the hints that were sent to the player
+        let hints = |game: &GameState, player: PlayerId| {
+            let mut client_player_ids = PoolFxLinkedHashSet::new_without_pool();
+            client_player_ids.insert(player);
+            let events = game.events_for(EventClientInfo {
+                client_player_ids,
+                everything: false,
+                other_stages: false,
+            });
+            events
+                .worlds
+                .values()
+                .flat_map(|world| world.events.values())
+                .filter_map(|ev| match ev {
+                    GameWorldEvent::Notification(GameWorldNotificationEvent::Hint { msg }) => {
+                        Some(msg.to_string())
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let stand_ticks = |game: &mut GameState, player: PlayerId| {
+            let mut shown = hints(game, player);
+            for _ in 0..5 {
+                let stage_id = game.game.players.player(&player).unwrap().stage_id();
+                let world = &mut game.game.stages.get_mut(&stage_id).unwrap().world;
+                let c = world.characters.get_mut(&player).unwrap();
+                c.pos.move_pos(pos);
+                c.core.core.vel = vec2::default();
+                game.tick(Default::default());
+                shown.extend(hints(game, player));
+            }
+            shown
+        };
+
+        // once for every player
+        assert_eq!(
+            stand_ticks(&mut game, first),
+            ["press {bind:+jump} twice to double jump"]
+        );
+        assert_eq!(stand_ticks(&mut game, second).len(), 1);
+        assert!(stand_ticks(&mut game, first).is_empty());
+
+        // rejoining doesn't show the hint again
+        game.player_drop(&first, PlayerDropReason::Disconnect);
+        let rejoined = game.player_join(&PlayerClientInfo {
+            info: NetworkCharacterInfo::explicit_default(),
+            id: 0,
+            unique_identifier: PlayerUniqueId::Account(0),
+            initial_network_stats: PlayerNetworkStats::default(),
+            ip: None,
+        });
+        let other = game.player_join(&PlayerClientInfo {
+            info: NetworkCharacterInfo::explicit_default(),
+            id: 0,
+            unique_identifier: PlayerUniqueId::Account(2),
+            initial_network_stats: PlayerNetworkStats::default(),
+            ip: None,
+        });
+        for _ in 0..2 {
+            game.tick(Default::default());
+        }
+        assert!(stand_ticks(&mut game, rejoined).is_empty());
+        assert_eq!(stand_ticks(&mut game, other).len(), 1);
+    }
+
+    /// ctf1 without solid tiles, except two walls at the given rows
+    /// that form a horizontal corridor through the whole map.
+    fn map_with_corridor(top_row: usize, bottom_row: usize) -> Vec<u8> {
+        let file = include_bytes!("../../../data/map/maps/ctf1.twmap.tar");
+        let tp = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+        let mut map = Map::read(&MapFileReader::new(file.to_vec()).unwrap(), &tp).unwrap();
+        let width = map.groups.physics.attr.width.get() as usize;
+        for layer in map.groups.physics.layers.iter_mut() {
+            if let MapLayerPhysics::Game(layer) = layer {
+                for (index, tile) in layer.tiles.iter_mut().enumerate() {
+                    let row = index / width;
+                    if row == top_row || row == bottom_row {
+                        tile.index = DdraceTileNum::Solid as u8;
+                    } else if tile.index == DdraceTileNum::Solid as u8
+                        || tile.index == DdraceTileNum::NoHook as u8
+                    {
+                        tile.index = 0;
+                    }
+                }
+            }
+        }
+        map.write(&tp).unwrap()
+    }
+
+    const CORRIDOR_TOP_ROW: usize = 10;
+    const CORRIDOR_BOTTOM_ROW: usize = 14;
+    /// The last free y coordinates below the top & above the bottom wall.
+    const CORRIDOR_CEILING: f32 = (CORRIDOR_TOP_ROW + 1) as f32 * 32.0 - 0.5;
+    const CORRIDOR_FLOOR: f32 = CORRIDOR_BOTTOM_ROW as f32 * 32.0 - 0.5;
+
+    fn bounce_effects(world: &GameWorld) -> Vec<(vec2, u32)> {
+        world
+            .game_pending_events
+            .take()
+            .into_iter()
+            .filter_map(|ev| match ev {
+                GameWorldEvent::Effect(GameWorldEffectEvent {
+                    ev:
+                        GameWorldEntityEffectEvent::Laser(GameLaserEventEffect::Bounce {
+                            remaining_bounces,
+                        })
+                        | GameWorldEntityEffectEvent::Grenade(GameGrenadeEventEffect::Bounce {
+                            remaining_bounces,
+                        }),
+                    pos,
+                    ..
+                }) => Some((pos * 32.0, remaining_bounces)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Fires a laser diagonally down the corridor and ticks it until it's removed.
+    /// Returns the ticks it existed & the bounce effects.
+    fn fire_laser_in_corridor(tunes: impl FnOnce(&mut Tunings)) -> (usize, Vec<(vec2, u32)>) {
+        let mut game = get_game_with_map::<1>(
+            map_with_corridor(CORRIDOR_TOP_ROW, CORRIDOR_BOTTOM_ROW),
+            Default::default(),
+        );
+        tunes(&mut game.collision.tune_zones[0]);
+        let owner = game.id_generator.next_id();
+        let laser_id = game.id_generator.next_id();
+        let stage_id = game.stage_0_id;
+        let collision = &game.collision;
+        let world = &mut game.game.stages.get_mut(&stage_id).unwrap().world;
+        world.game_pending_events.clear();
+
+        let reach = collision.tune_zones[0].laser_reach;
+        world.insert_new_laser(
+            laser_id,
+            owner,
+            &(vec2::new(10.5, 12.5) * 32.0),
+            &normalize(&vec2::new(1.0, 1.0)),
+            reach,
+            true,
+            false,
+            None,
+        );
+        let mut ticks = 0;
+        while let Some(laser) = world.lasers.get_mut(&laser_id) {
+            ticks += 1;
+            assert!(ticks < TICKS_PER_SECOND as usize * 10);
+            if laser.laser.tick(&mut SimulationPipeLaser::new(
+                collision,
+                &mut world.characters,
+                owner,
+                &world.play_field,
+            )) == EntityTickResult::RemoveEntity
+            {
+                world.lasers.remove(&laser_id);
+            }
+        }
+        (ticks, bounce_effects(world))
+    }
+
+    #[test]
+    fn laser_bounces() {
+        let (_, bounces) = fire_laser_in_corridor(|tunes| {
+            tunes.laser_bounce_num = 3.0;
+            tunes.laser_bounce_delay = 0.0;
+        });
+        // 3 bounces & the final wall contact
+        assert_eq!(
+            bounces
+                .iter()
+                .map(|(_, remaining)| *remaining)
+                .collect::<Vec<_>>(),
+            [2, 1, 0, 0]
+        );
+        // alternating between floor & ceiling, moving one corridor height
+        // to the right each time, since the laser goes down in 45°.
+        let first_x = 10.5 * 32.0 + (CORRIDOR_FLOOR - 12.5 * 32.0);
+        let corridor_height = CORRIDOR_FLOOR - CORRIDOR_CEILING;
+        for (index, (pos, _)) in bounces.iter().enumerate() {
+            let expected = vec2::new(
+                first_x + index as f32 * corridor_height,
+                if index % 2 == 0 {
+                    CORRIDOR_FLOOR
+                } else {
+                    CORRIDOR_CEILING
+                },
+            );
+            assert!((pos.y - expected.y).abs() <= 1.5, "{pos:?} {expected:?}");
+            assert!((pos.x - expected.x).abs() <= 6.0, "{pos:?} {expected:?}");
+        }
+
+        // less bounces if they cost energy
+        let (_, bounces) = fire_laser_in_corridor(|tunes| {
+            tunes.laser_bounce_num = 3.0;
+            tunes.laser_bounce_delay = 0.0;
+            tunes.laser_reach = 400.0;
+            tunes.laser_bounce_cost = 150.0;
+        });
+        assert_eq!(bounces.len(), 2);
+
+        // lives longer than the bounce delay without a lifetime
+        let (ticks, bounces) = fire_laser_in_corridor(|tunes| {
+            tunes.laser_bounce_num = 3.0;
+        });
+        assert_eq!(bounces.len(), 4);
+        assert!(ticks > TICKS_PER_SECOND as usize / 2);
+        let (ticks, bounces) = fire_laser_in_corridor(|tunes| {
+            tunes.laser_bounce_num = 3.0;
+            tunes.laser_lifetime = 0.1;
+        });
+        assert_eq!(ticks, (TICKS_PER_SECOND as f32 * 0.1) as usize + 1);
+        assert_eq!(bounces.len(), 1);
+    }
+
+    #[test]
+    fn grenade_bounces() {
+        let mut game = get_game_with_map::<1>(
+            map_with_corridor(CORRIDOR_TOP_ROW, CORRIDOR_BOTTOM_ROW),
+            Default::default(),
+        );
+        game.collision.tune_zones[0].grenade_bounce_num = 2.0;
+        let owner = game.id_generator.next_id();
+        let proj_id = game.id_generator.next_id();
+        let stage_id = game.stage_0_id;
+        let collision = &game.collision;
+        let world = &mut game.game.stages.get_mut(&stage_id).unwrap().world;
+        world.game_pending_events.clear();
+
+        world.insert_new_projectile(
+            proj_id,
+            owner,
+            &(vec2::new(10.5, 12.5) * 32.0),
+            &normalize(&vec2::new(1.0, 1.0)),
+            TICKS_PER_SECOND as i32 * 10,
+            1,
+            0.0,
+            true,
+            WeaponWithProjectile::Grenade,
+            None,
+        );
+        let mut ticks = 0;
+        let mut effects = Vec::new();
+        while let Some(proj) = world.projectiles.get_mut(&proj_id) {
+            ticks += 1;
+            assert!(ticks < TICKS_PER_SECOND as usize * 10);
+            let speed = length(&proj.projectile.core.vel);
+            let res = proj.projectile.tick(&mut SimulationPipeProjectile::new(
+                collision,
+                &mut world.characters,
+                owner,
+                &world.play_field,
+            ));
+            if res == EntityTickResult::RemoveEntity {
+                world.projectiles.remove(&proj_id);
+                break;
+            }
+            let bounces = bounce_effects(world);
+            if !bounces.is_empty() {
+                // the grenade keeps half of its speed
+                let new_speed =
+                    length(&world.projectiles.get(&proj_id).unwrap().projectile.core.vel);
+                assert!(new_speed < speed * 0.55, "{new_speed} {speed}");
+            }
+            effects.extend(bounces);
+        }
+
+        // bounces twice from the floor, then explodes on the third contact
+        assert_eq!(
+            effects
+                .iter()
+                .map(|(_, remaining)| *remaining)
+                .collect::<Vec<_>>(),
+            [1, 0]
+        );
+        assert!(effects[0].0.x < effects[1].0.x);
+        for (pos, _) in &effects {
+            assert!((pos.y - CORRIDOR_FLOOR).abs() <= 1.5, "{pos:?}");
+        }
+        let explosions = world
+            .game_pending_events
+            .take()
+            .into_iter()
+            .filter(|ev| {
+                matches!(
+                    ev,
+                    GameWorldEvent::Effect(GameWorldEffectEvent {
+                        ev: GameWorldEntityEffectEvent::Grenade(GameGrenadeEventEffect::Explosion),
+                        ..
+                    })
+                )
+            })
+            .count();
+        assert_eq!(explosions, 1);
+    }
+
+    #[test]
+    fn move_box() {
+        let game = get_game::<1>();
+        pub const PHYSICAL_SIZE: f32 = 28.0;
+        const fn physical_size_vec2() -> ivec2 {
+            ivec2 {
+                x: PHYSICAL_SIZE as i32,
+                y: PHYSICAL_SIZE as i32,
+            }
+        }
+        let bench = || {
+            let now = Instant::now();
+            let iterations = 1000000;
+            let mut pos = Default::default();
+            let mut vel = Default::default();
+            for _ in 0..iterations {
+                pos = vec2::new(10.0, 10.0) * 32.0;
+                vel = vec2::new(10.0, 10.0) * 32.0;
+                game.collision
+                    .move_box(&mut pos, &mut vel, &physical_size_vec2(), 0.0);
+            }
+            let took = now.elapsed();
+            println!("{} s - pos: {:?}, vel: {:?}", took.as_secs_f64(), pos, vel);
+            let calls_per_second = iterations as f64 / took.as_secs_f64();
+            println!("{calls_per_second} call/s");
+        };
+        bench();
+        bench();
+        bench();
+        bench();
+    }
+
+    fn character_ids(count: usize) -> Vec<CharacterId> {
+        let id_gen = IdGenerator::new();
+        (0..count).map(|_| id_gen.next_id()).collect()
+    }
+
+    fn sound(
+        owner: CharacterId,
+        pos: Option<vec2>,
+        ev: GameWorldEntitySoundEvent,
+    ) -> GameWorldEvent {
+        GameWorldEvent::Sound(GameWorldSoundEvent {
+            pos,
+            owner_id: Some(owner),
+            ev,
+            count: 1,
+        })
+    }
+
+    fn sounds(
+        evs: &[GameWorldEvent],
+        is_player: impl Fn(&CharacterId) -> bool,
+    ) -> Vec<GameWorldSoundEvent> {
+        batch_sound_events(evs, is_player)
+            .into_iter()
+            .filter_map(|ev| match ev.as_ref() {
+                GameWorldEvent::Sound(ev) => Some(*ev),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sound_batching_merge_radius() {
+        let explosion = GameWorldEntitySoundEvent::Grenade(GameGrenadeEventSound::Explosion);
+        let jump = GameWorldEntitySoundEvent::Character(GameCharacterSoundEvent::Sound(
+            GameCharacterEventSound::GroundJump,
+        ));
+        let ids = character_ids(2);
+        let origin = vec2::new(10.0, 10.0);
+        let close = origin + vec2::new(SOUND_MERGE_RADIUS * 0.9, 0.0);
+        let far = origin + vec2::new(SOUND_MERGE_RADIUS * 3.0, 0.0);
+
+        // 20 grenades explode in one spot
+        let evs: Vec<_> = (0..20)
+            .map(|_| sound(ids[0], Some(origin), explosion))
+            .collect();
+        let batched = sounds(&evs, |_| false);
+        assert_eq!(batched.len(), 1);
+        assert_eq!(batched[0].count, 20);
+        assert!(batched[0].batch_volume() > 1.0);
+        assert!(batched[0].batch_volume() <= 1.5);
+
+        let evs = [
+            sound(ids[0], Some(origin), explosion),
+            sound(ids[0], Some(close), explosion),
+            // too far away
+            sound(ids[0], Some(far), explosion),
+            // a different sound
+            sound(ids[0], Some(origin), jump),
+            // a different owner
+            sound(ids[1], Some(origin), explosion),
+        ];
+        let batched = sounds(&evs, |_| false);
+        assert_eq!(batched.len(), 4);
+        assert_eq!(batched[0].count, 2);
+        // the merged sound is played in the middle
+        assert!(distance(&batched[0].pos.unwrap(), &((origin + close) / 2.0)) < 0.001);
+        assert!(batched[1..].iter().all(|ev| ev.count == 1));
+
+        // other events are kept in order
+        let evs = [
+            sound(ids[0], Some(origin), explosion),
+            GameWorldEvent::Effect(GameWorldEffectEvent {
+                pos: origin,
+                owner_id: None,
+                ev: GameWorldEntityEffectEvent::Grenade(GameGrenadeEventEffect::Explosion),
+            }),
+            sound(ids[0], Some(origin), explosion),
+        ];
+        let batched = batch_sound_events(&evs, |_| false);
+        assert_eq!(batched.len(), 2);
+        assert!(matches!(batched[0].as_ref(), GameWorldEvent::Sound(ev) if ev.count == 2));
+        assert!(matches!(batched[1].as_ref(), GameWorldEvent::Effect(_)));
+    }
+
+    #[test]
+    fn sound_batching_cap_with_priorities() {
+        let explosion = GameWorldEntitySoundEvent::Grenade(GameGrenadeEventSound::Explosion);
+        let pain = GameWorldEntitySoundEvent::Character(GameCharacterSoundEvent::Sound(
+            GameCharacterEventSound::Pain { long: false },
+        ));
+        let ids = character_ids(MAX_WORLD_SOUNDS_PER_TICK * 2 + 1);
+        let player = ids[0];
+
+        // explosions all over the map by different players,
+        // the own pain sound & a global sound come last
+        let mut evs: Vec<_> = ids[1..]
+            .iter()
+            .enumerate()
+            .map(|(i, owner)| sound(*owner, Some(vec2::new(i as f32 * 10.0, 0.0)), explosion))
+            .collect();
+        evs.push(sound(player, Some(vec2::new(5.0, 0.0)), pain));
+        evs.push(GameWorldEvent::Sound(GameWorldSoundEvent {
+            pos: None,
+            owner_id: Some(ids[1]),
+            ev: explosion,
+            count: 1,
+        }));
+
+        let batched = sounds(&evs, |id| *id == player);
+        assert_eq!(batched.len(), MAX_WORLD_SOUNDS_PER_TICK + 2);
+        // the first world sounds pass
+        assert!(
+            batched[..MAX_WORLD_SOUNDS_PER_TICK]
+                .iter()
+                .all(|ev| matches!(ev.ev, GameWorldEntitySoundEvent::Grenade(_)))
+        );
+        // own damage always passes
+        assert!(
+            batched
+                .iter()
+                .any(|ev| ev.owner_id == Some(player) && ev.ev == pain)
+        );
+        // global sounds always pass
+        assert!(batched.iter().any(|ev| ev.pos.is_none()));
+
+        // without the cap being reached, nothing is dropped
+        let batched = sounds(&evs[MAX_WORLD_SOUNDS_PER_TICK..], |id| *id == player);
+        assert_eq!(batched.len(), MAX_WORLD_SOUNDS_PER_TICK + 2);
+    }
+
+    #[test]
+    fn announcement_templates() {
+        assert_eq!(
+            format_player_msg("{name} joined, {players} players online", "nameless", 3),
+            "nameless joined, 3 players online"
+        );
+        // placeholders in the name are not replaced
+        assert_eq!(
+            format_player_msg("{name} - {players}", "{players}", 1),
+            "{players} - 1"
+        );
+        // unknown placeholders are kept
+        assert_eq!(
+            format_player_msg("{{name}}: {unknown} {", "a", 1),
+            "{a}: {unknown} {"
+        );
+        assert_eq!(format_player_msg("", "a", 1), "");
+    }
+
+    #[test]
+    fn announcement_scheduler_cadence() {
+        let options = AnnouncementOptions {
+            count: 3,
+            interval_ticks: 10,
+            min_players: 2,
+        };
+        let mut scheduler = AnnouncementScheduler::default();
+        let due: Vec<_> = (1..=40)
+            .filter_map(|tick| scheduler.tick(&options, 2).map(|index| (tick, index)))
+            .collect();
+        // rotates through the list
+        assert_eq!(due, [(10, 0), (20, 1), (30, 2), (40, 0)]);
+
+        // the time doesn't pass while too few players are online
+        for _ in 0..5 {
+            assert_eq!(scheduler.tick(&options, 2), None);
+        }
+        for _ in 0..100 {
+            assert_eq!(scheduler.tick(&options, 1), None);
+        }
+        for _ in 0..4 {
+            assert_eq!(scheduler.tick(&options, 2), None);
+        }
+        assert_eq!(scheduler.tick(&options, 2), Some(1));
+
+        // the list was shortened at runtime
+        let shortened = AnnouncementOptions {
+            count: 1,
+            ..options
+        };
+        let due: Vec<_> = (0..20)
+            .filter_map(|_| scheduler.tick(&shortened, 2))
+            .collect();
+        assert_eq!(due, [0, 0]);
+
+        // disabled
+        let disabled = AnnouncementOptions {
+            interval_ticks: 0,
+            ..options
+        };
+        assert!((0..100).all(|_| scheduler.tick(&disabled, 2).is_none()));
+        let empty = AnnouncementOptions {
+            count: 0,
+            ..options
+        };
+        assert!((0..100).all(|_| scheduler.tick(&empty, 2).is_none()));
+    }
+
+    #[test]
+    fn match_highlight_selection() {
+        let id_gen = IdGenerator::default();
+        let [a, b, c, d]: [CharacterId; 4] = std::array::from_fn(|_| id_gen.next_id());
+        let advance = |highlights: &mut MatchHighlights, ticks: u64| {
+            (0..ticks).for_each(|_| highlights.tick());
+        };
+
+        let mut highlights = MatchHighlights::default();
+        assert_eq!(highlights.select(), None);
+
+        // a triple kill beats the single kills around it
+        advance(&mut highlights, 200);
+        highlights.kill(a, b);
+        for victim in [a, b, d] {
+            advance(&mut highlights, 30);
+            highlights.kill(c, victim);
+        }
+        // suicides are ignored
+        highlights.kill(d, d);
+        advance(&mut highlights, 100);
+        highlights.kill(a, c);
+        advance(&mut highlights, 50);
+        // kills at 200, 230, 260, 290 & 390, selected at 440
+        assert_eq!(
+            highlights.select(),
+            Some(MatchHighlight {
+                start_ticks_ago: 440 - (230 - HIGHLIGHT_LEAD_TICKS),
+                end_ticks_ago: 440 - (290 + HIGHLIGHT_TAIL_TICKS),
+                characters: vec![c, a, b, d],
+            })
+        );
+
+        // kills that clients can't replay anymore are forgotten,
+        // equal kills prefer the final one
+        advance(&mut highlights, HIGHLIGHT_MAX_AGE_TICKS);
+        highlights.kill(b, a);
+        advance(&mut highlights, 10);
+        highlights.kill(d, c);
+        assert_eq!(
+            highlights.select(),
+            Some(MatchHighlight {
+                start_ticks_ago: HIGHLIGHT_LEAD_TICKS,
+                end_ticks_ago: 0,
+                characters: vec![d, c],
+            })
+        );
+
+        // the last capture wins over kills
+        highlights.capture(a);
+        advance(&mut highlights, 100);
+        highlights.capture(b);
+        advance(&mut highlights, 10);
+        highlights.kill(c, d);
+        highlights.kill(c, a);
+        assert_eq!(
+            highlights.select(),
+            Some(MatchHighlight {
+                start_ticks_ago: 10 + HIGHLIGHT_LEAD_TICKS,
+                end_ticks_ago: 0,
+                characters: vec![b],
+            })
+        );
+
+        // a new round
+        highlights.reset();
+        assert_eq!(highlights.select(), None);
+    }
+
+    #[test]
+    fn join_msg_and_announcements() {
+        let mut game = get_game_with_config::<2>(ConfigVanilla {
+            join_msg: "{name} joined, {players} online".to_string(),
+            announcements: vec!["first".to_string(), "second".to_string()],
+            announcement_interval_mins: 1,
+            announcement_min_players: 1,
+            ..Default::default()
+        });
+
+        // no announcements on an empty server
+        for _ in 0..TICKS_PER_SECOND * 60 {
+            game.tick(Default::default());
+        }
+
+        let player = game.player_join(&PlayerClientInfo {
+            info: NetworkCharacterInfo::explicit_default(),
+            id: 0,
+            unique_identifier: PlayerUniqueId::Account(0),
+            initial_network_stats: PlayerNetworkStats::default(),
+            ip: None,
+        });
+        let has_msg = |game: &GameState, msg: &str| {
+            system_msgs(game, player)
+                .iter()
+                .any(|system_msg| system_msg == msg)
+        };
+        assert!(has_msg(&game, "TODO joined, 1 online"));
+        game.clear_events();
+
+        for _ in 0..TICKS_PER_SECOND * 60 - 1 {
+            game.tick(Default::default());
+        }
+        assert!(!has_msg(&game, "first"));
+        game.tick(Default::default());
+        assert!(has_msg(&game, "first"));
+    }
+
+    #[test]
+    fn weapon_drop_on_death() {
+        let mut game = get_game_with_config::<2>(ConfigVanilla {
+            weapon_drop_on_death: true,
+            weapon_drop_despawn_secs: 1,
+            ..Default::default()
+        });
+        let [attacker, victim] = join_players::<2>(&mut game);
+        for _ in 0..2 {
+            game.tick(Default::default());
+        }
+        let stage_id = game.game.players.player(&victim).unwrap().stage_id();
+
+        let world = &mut game.game.stages.get_mut(&stage_id).unwrap().world;
+        let victim_char = world.characters.get_mut(&victim).unwrap();
+        victim_char.reusable_core.weapons.insert_sorted(
+            WeaponType::Grenade,
+            Weapon {
+                cur_ammo: Some(7),
+                next_ammo_regeneration_tick: 0.into(),
+            },
+        );
+        let death_pos = *victim_char.pos.pos();
+        Character::take_damage_from(
+            victim_char,
+            &victim,
+            attacker,
+            &Default::default(),
+            &Default::default(),
+            FriendlyFireTy::Dmg,
+            20,
+            DamageTypes::Character(&attacker),
+            DamageBy::Weapon {
+                weapon: WeaponType::Grenade,
+                flags: Default::default(),
+            },
+        );
+        assert!(matches!(victim_char.phased, CharacterPhasedState::Dead(_)));
+        game.tick(Default::default());
+
+        // the grenade launcher is dropped with its ammo
+        let collision = &game.collision;
+        let world = &mut game.game.stages.get_mut(&stage_id).unwrap().world;
+        let mut dropped_ids = world
+            .pickups
+            .iter()
+            .filter(|(_, pickup)| pickup.core.dropped.is_some())
+            .map(|(id, _)| *id);
+        let pickup_id = dropped_ids.next().unwrap();
+        assert!(dropped_ids.next().is_none());
+        let pickup = world.pickups.get_mut(&pickup_id).unwrap();
+        assert_eq!(
+            pickup.core.ty,
+            PickupType::PowerupWeapon(WeaponType::Grenade)
+        );
+        assert!(distance(&pickup.core.pos, &death_pos) < 32.0);
+        let dropped = pickup.core.dropped.unwrap();
+        assert_eq!(dropped.ammo, 7);
+        assert_eq!(dropped.despawn_ticks, TICKS_PER_SECOND);
+
+        // it lies on the floor until the despawn tick
+        for ticks_left in (0..TICKS_PER_SECOND).rev() {
+            assert_eq!(
+                pickup.tick_deferred(&mut SimulationPipePickup::new(
+                    collision,
+                    &mut world.characters,
+                    &world.play_field,
+                )),
+                EntityTickResult::None
+            );
+            assert_eq!(pickup.core.dropped.unwrap().despawn_ticks, ticks_left);
+        }
+        assert_eq!(
+            pickup.tick_deferred(&mut SimulationPipePickup::new(
+                collision,
+                &mut world.characters,
+                &world.play_field,
+            )),
+            EntityTickResult::RemoveEntity
+        );
+
+        // collecting it gives the inherited ammo
+        pickup.core.pos = *world.characters.get(&attacker).unwrap().pos.pos();
+        assert_eq!(
+            pickup.tick(&mut SimulationPipePickup::new(
+                collision,
+                &mut world.characters,
+                &world.play_field,
+            )),
+            EntityTickResult::RemoveEntity
+        );
+        assert_eq!(
+            world.characters[&attacker].reusable_core.weapons[&WeaponType::Grenade].cur_ammo,
+            Some(7)
+        );
+
+        // & it does not respawn
+        world.pickups.remove(&pickup_id);
+        game.tick(Default::default());
+        assert!(
+            game.game.stages[&stage_id]
+                .world
+                .inactive_game_objects
+                .pickups
+                .weapons[WeaponType::Grenade as usize]
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn server_rank() {
+        let mut game = get_game::<3>();
+        let join = |game: &mut GameState, account_id| {
+            game.player_join(&PlayerClientInfo {
+                info: NetworkCharacterInfo::explicit_default(),
+                id: 0,
+                unique_identifier: PlayerUniqueId::Account(account_id),
+                initial_network_stats: PlayerNetworkStats::default(),
+                ip: None,
+            })
+        };
+        let ranked = join(&mut game, 1);
+        let unranked = join(&mut game, 2);
+        let rank = CharacterServerRank {
+            rank: 3,
+            points: 42,
+        };
+
+        // the database answers
+        let mut query = game.game_db.io_rt.spawn(async move {
+            Ok(GameDbQueries::ServerRank {
+                account_id: 1,
+                rank: Some(rank),
+            })
+        });
+        query.blocking_wait_finished();
+        game.game_db.cur_queries.push(query);
+        game.tick(Default::default());
+
+        // the rank is sent to the clients
+        let snapshot = game.snapshot_for(SnapshotClientInfo::Everything);
+        let mut client = get_game::<3>();
+        client.build_from_snapshot(&snapshot);
+        let infos = client.collect_characters_info();
+        assert_eq!(infos.get(&ranked).unwrap().server_rank, Some(rank));
+        assert_eq!(infos.get(&unranked).unwrap().server_rank, None);
+
+        // & cached for when the player comes back
+        game.player_drop(&ranked, PlayerDropReason::Disconnect);
+        let rejoined = join(&mut game, 1);
+        let infos = game.collect_characters_info();
+        assert_eq!(infos.get(&rejoined).unwrap().server_rank, Some(rank));
+    }
+
+    /// [`map_with_floor`] with platforms, that move `offset` tiles
+    /// within a second & then start over.
+    fn map_with_platforms(
+        floor_row: usize,
+        offset: vec2,
+        platforms: Vec<MapPhysicsPlatform>,
+    ) -> Vec<u8> {
+        let tp = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+        let mut map =
+            Map::read(&MapFileReader::new(map_with_floor(floor_row)).unwrap(), &tp).unwrap();
+        let point = |time: TimeDuration, offset: vec2| AnimPointPos {
+            time,
+            curve_type: AnimPointCurveType::Linear,
+            value: fvec3::new(
+                ffixed::from_num(offset.x),
+                ffixed::from_num(offset.y),
+                ffixed::from_num(0),
+            ),
+        };
+        let pos_anim = map.animations.pos.len();
+        map.animations.pos.push(PosAnimation {
+            points: vec![
+                point(TimeDuration::ZERO, vec2::default()),
+                point(TimeDuration::SECOND, offset),
+            ],
+            synchronized: false,
+            name: "platform".to_string(),
+        });
+        let meta = MapLayerPhysicsMeta {
+            platforms: platforms
+                .into_iter()
+                .map(|platform| MapPhysicsPlatform {
+                    pos_anim,
+                    ..platform
+                })
+                .collect(),
+        };
+        map.groups
+            .physics
+            .layers
+            .push(MapLayerPhysics::Arbitrary(meta.to_arbitrary().unwrap()));
+        map.write(&tp).unwrap()
+    }
+
+    fn platform(pos: vec2, crush: MapPhysicsPlatformCrush) -> MapPhysicsPlatform {
+        MapPhysicsPlatform {
+            pos: fvec2::new(ffixed::from_num(pos.x), ffixed::from_num(pos.y)),
+            size: ufvec2::new(uffixed::from_num(4), uffixed::from_num(1)),
+            pos_anim: 0,
+            pos_anim_offset: TimeDuration::ZERO,
+            hookable: true,
+            crush,
+        }
+    }
+
+    #[test]
+    fn platform_riding() {
+        const FLOOR_ROW: usize = 14;
+        let mut game = get_game_with_map::<1>(
+            map_with_platforms(
+                FLOOR_ROW,
+                vec2::new(5.0, 0.0),
+                vec![platform(
+                    vec2::new(10.0, 8.0),
+                    MapPhysicsPlatformCrush::Push,
+                )],
+            ),
+            Default::default(),
+        );
+        // the inherited velocity is not slowed down
+        game.collision.tune_zones[0].air_friction = 1.0;
+        let [player] = join_players::<1>(&mut game);
+        for _ in 0..2 {
+            game.tick(Default::default());
+        }
+        let stage_id = game.game.players.player(&player).unwrap().stage_id();
+
+        // slightly above the platform
+        let world = &mut game.game.stages.get_mut(&stage_id).unwrap().world;
+        let c = world.characters.get_mut(&player).unwrap();
+        c.pos.move_pos(vec2::new(10.0 * 32.0, 7.5 * 32.0 - 16.0));
+        c.core.core.vel = vec2::default();
+        game.tick(Default::default());
+
+        let char_x = |game: &GameState| {
+            game.game.stages[&stage_id]
+                .world
+                .characters
+                .get(&player)
+                .unwrap()
+                .pos
+                .pos()
+                .x
+        };
+        let start_x = char_x(&game);
+        let start_platform = game.collision.platforms().rect(0).min.x;
+        for _ in 0..20 {
+            game.tick(Default::default());
+        }
+        // 5 tiles per second
+        let platform_vel = game.collision.platforms().delta(0);
+        assert!(
+            (platform_vel.x - 5.0 * 32.0 / 50.0).abs() < 0.01,
+            "{platform_vel:?}"
+        );
+        let moved = char_x(&game) - start_x;
+        let platform_moved = game.collision.platforms().rect(0).min.x - start_platform;
+        // positions are whole units, but don't drift away from the platform
+        assert!(
+            (moved - platform_moved).abs() <= 1.0,
+            "{moved} vs {platform_moved}"
+        );
+        let c = game.game.stages[&stage_id]
+            .world
+            .characters
+            .get(&player)
+            .unwrap();
+        assert_eq!(c.core.core.platform_vel, platform_vel);
+        assert!(c.core.core.vel.x.abs() < 0.01);
+
+        // jumping off keeps the velocity of the platform
+        let world = &mut game.game.stages.get_mut(&stage_id).unwrap().world;
+        let c = world.characters.get_mut(&player).unwrap();
+        c.core.input.state.jump.set(true);
+        for _ in 0..3 {
+            game.tick(Default::default());
+        }
+        let c = game.game.stages[&stage_id]
+            .world
+            .characters
+            .get(&player)
+            .unwrap();
+        assert_eq!(c.core.core.platform_vel, vec2::default());
+        assert!(c.core.core.vel.y < 0.0);
+        assert!(
+            (c.core.core.vel.x - platform_vel.x).abs() < 0.01,
+            "{:?}",
+            c.core.core.vel
+        );
+    }
+
+    #[test]
+    fn platform_crush() {
+        const FLOOR_ROW: usize = 14;
+        // a platform moves down onto a character, that stands on the floor
+        let crushed = |crush: MapPhysicsPlatformCrush| {
+            let mut game = get_game_with_map::<1>(
+                map_with_platforms(
+                    FLOOR_ROW,
+                    vec2::new(0.0, 5.0),
+                    vec![platform(vec2::new(10.0, 11.0), crush)],
+                ),
+                Default::default(),
+            );
+            let [player] = join_players::<1>(&mut game);
+            for _ in 0..2 {
+                game.tick(Default::default());
+            }
+            let stage_id = game.game.players.player(&player).unwrap().stage_id();
+            let world = &mut game.game.stages.get_mut(&stage_id).unwrap().world;
+            let c = world.characters.get_mut(&player).unwrap();
+            c.pos
+                .move_pos(vec2::new(10.0 * 32.0, FLOOR_ROW as f32 * 32.0 - 16.0));
+            c.core.core.vel = vec2::default();
+
+            // the platform passes the floor within a second
+            for _ in 0..45 {
+                game.tick(Default::default());
+            }
+            let c = game.game.stages[&stage_id]
+                .world
+                .characters
+                .get(&player)
+                .unwrap();
+            matches!(c.phased, CharacterPhasedState::Dead(_))
+        };
+        assert!(crushed(MapPhysicsPlatformCrush::Kill));
+        // the character ends up inside of the platform, until it passed
+        assert!(!crushed(MapPhysicsPlatformCrush::Push));
     }
 }
@@ -0,0 +1,128 @@
+/// the weapons characters have when they spawn
+pub mod loadout {
+    use anyhow::anyhow;
+    use game_interface::types::{emoticons::EnumCount, weapons::WeaponType};
+    use hiarc::Hiarc;
+    use num_traits::FromPrimitive;
+
+    use crate::{
+        config::config::{ConfigGameType, ConfigSpawnLoadout, ConfigVanilla},
+        weapons::definitions::weapon_def::Weapon,
+    };
+
+    /// The name of the weapon in the config, e.g. `shotgun`.
+    pub fn weapon_name(weapon: WeaponType) -> &'static str {
+        match weapon {
+            WeaponType::Hammer => "hammer",
+            WeaponType::Gun => "gun",
+            WeaponType::Shotgun => "shotgun",
+            WeaponType::Grenade => "grenade",
+            WeaponType::Laser => "laser",
+        }
+    }
+
+    /// Parses the name of a weapon in the config, ignoring the case.
+    pub fn weapon_from_name(name: &str) -> anyhow::Result<WeaponType> {
+        (0..WeaponType::COUNT)
+            .filter_map(WeaponType::from_usize)
+            .find(|weapon| weapon_name(*weapon).eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| {
+                anyhow!(
+                    "unknown weapon \"{name}\", expected one of: {}",
+                    (0..WeaponType::COUNT)
+                        .filter_map(WeaponType::from_usize)
+                        .map(weapon_name)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+    }
+
+    /// The weapons & the selected weapon of spawned characters,
+    /// see [`ConfigSpawnLoadout`].
+    #[derive(Debug, Hiarc, Clone, Copy, PartialEq)]
+    pub struct SpawnLoadout {
+        /// Indexed by the [`WeaponType`],
+        /// `None` for weapons that are not part of the loadout.
+        pub weapons: [Option<Option<u32>>; WeaponType::COUNT],
+        pub active_weapon: WeaponType,
+    }
+
+    impl Default for SpawnLoadout {
+        /// Hammer & gun, with the gun selected.
+        fn default() -> Self {
+            let mut weapons = [None; WeaponType::COUNT];
+            weapons[WeaponType::Hammer as usize] = Some(None);
+            weapons[WeaponType::Gun as usize] = Some(Some(10));
+            Self {
+                weapons,
+                active_weapon: WeaponType::Gun,
+            }
+        }
+    }
+
+    impl SpawnLoadout {
+        /// The loadout of the game type of the config.
+        pub fn new(config: &ConfigVanilla) -> anyhow::Result<Self> {
+            let (name, loadout) = match config.game_type {
+                ConfigGameType::Dm => ("dm", &config.spawn_loadouts.dm),
+                ConfigGameType::Ctf => ("ctf", &config.spawn_loadouts.ctf),
+            };
+            Self::from_config(loadout)
+                .map_err(|err| anyhow!("invalid spawn loadout spawn_loadouts.{name}: {err}"))
+        }
+
+        pub fn from_config(config: &ConfigSpawnLoadout) -> anyhow::Result<Self> {
+            let mut weapons = [None; WeaponType::COUNT];
+            for (index, loadout_weapon) in config.weapons.iter().enumerate() {
+                let weapon = weapon_from_name(&loadout_weapon.weapon)
+                    .map_err(|err| anyhow!("weapons[{index}]: {err}"))?;
+                if weapons[weapon as usize].is_some() {
+                    return Err(anyhow!(
+                        "weapons[{index}]: {} is part of the loadout twice",
+                        weapon_name(weapon)
+                    ));
+                }
+                let ammo = match loadout_weapon.ammo {
+                    -1 => None,
+                    ammo @ 0..=10 => Some(ammo as u32),
+                    ammo => {
+                        return Err(anyhow!(
+                            "weapons[{index}]: the ammo must be between 0 & 10 or -1 \
+                            for unlimited ammo, but was {ammo}"
+                        ));
+                    }
+                };
+                weapons[weapon as usize] = Some(ammo);
+            }
+            let active_weapon = weapon_from_name(&config.active_weapon)
+                .map_err(|err| anyhow!("active_weapon: {err}"))?;
+            if weapons[active_weapon as usize].is_none() {
+                return Err(anyhow!(
+                    "active_weapon: {} is not part of the loadout",
+                    weapon_name(active_weapon)
+                ));
+            }
+            Ok(Self {
+                weapons,
+                active_weapon,
+            })
+        }
+
+        /// The weapons of the loadout, ready to be given to a character.
+        pub fn weapons(&self) -> impl Iterator<Item = (WeaponType, Weapon)> + '_ {
+            self.weapons.iter().enumerate().filter_map(|(index, ammo)| {
+                ammo.zip(WeaponType::from_usize(index))
+                    .map(|(cur_ammo, weapon)| {
+                        (
+                            weapon,
+                            Weapon {
+                                cur_ammo,
+                                next_ammo_regeneration_tick: 0.into(),
+                            },
+                        )
+                    })
+            })
+        }
+    }
+}
@@ -0,0 +1,165 @@
+pub mod map_settings {
+    use config::traits::{ConfigFromStrOperation, ConfigInterface};
+    use map::map::config::Config as MapConfig;
+    use rustc_hash::FxHashMap;
+    use thiserror::Error;
+
+    use crate::{
+        collision::collision::Tunings,
+        config::config::{ConfigVanilla, ConfigVanillaWrapper},
+    };
+
+    /// Config variables that a map is allowed to change.
+    ///
+    /// Everything else stays in the hands of the server operator.
+    pub const MAP_CONFIG_VARIABLES: &[&str] = &[
+        "vanilla.game_type",
+        "vanilla.score_limit",
+        "vanilla.time_limit_secs",
+        "vanilla.friendly_fire",
+        "vanilla.laser_hit_self",
+        "vanilla.damage_tile_index",
+        "vanilla.damage_tile_damage",
+        "vanilla.damage_tile_interval_ms",
+        "vanilla.spawn_protection_ms",
+    ];
+
+    #[derive(Error, Debug, Clone, PartialEq, Eq)]
+    pub enum MapSettingError {
+        #[error("maps are not allowed to change this setting")]
+        NotAllowed,
+        #[error("unknown command")]
+        UnknownCommand,
+        #[error("invalid value: {0}")]
+        InvalidValue(String),
+    }
+
+    /// A config variable or command of the map that was not applied.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct RejectedMapSetting {
+        /// The setting as written in the map.
+        pub setting: String,
+        pub err: MapSettingError,
+    }
+
+    fn apply_config_variable(
+        config: &mut ConfigVanilla,
+        name: &str,
+        val: &str,
+    ) -> Result<(), MapSettingError> {
+        if !MAP_CONFIG_VARIABLES.contains(&name) {
+            return Err(MapSettingError::NotAllowed);
+        }
+        let mut wrapper = ConfigVanillaWrapper {
+            vanilla: config.clone(),
+        };
+        wrapper
+            .try_set_from_str(
+                name.to_string(),
+                None,
+                Some(val.to_string()),
+                None,
+                ConfigFromStrOperation::Set,
+            )
+            .map_err(|err| MapSettingError::InvalidValue(err.to_string()))?;
+        *config = wrapper.vanilla;
+        Ok(())
+    }
+
+    /// Hint texts of the map by the number of the switch zone that shows them.
+    pub type MapHints = FxHashMap<u8, String>;
+
+    /// Hints are sent as notification, which limits their length.
+    pub const MAX_HINT_CHARS: usize = 1024;
+
+    /// Parses the arguments of `hint <zone> <text>`.
+    fn parse_hint(args: &str) -> Result<(u8, String), MapSettingError> {
+        let invalid = || MapSettingError::InvalidValue("expected `hint <zone> <text>`".to_string());
+        let (zone, text) = args
+            .trim()
+            .split_once(char::is_whitespace)
+            .ok_or_else(invalid)?;
+        let zone = zone
+            .parse::<u8>()
+            .ok()
+            .filter(|&zone| zone > 0)
+            .ok_or_else(invalid)?;
+        let text = text.trim();
+        if text.chars().count() > MAX_HINT_CHARS {
+            return Err(MapSettingError::InvalidValue(format!(
+                "hints can have at most {MAX_HINT_CHARS} characters"
+            )));
+        }
+        Ok((zone, text.to_string()))
+    }
+
+    /// The hints of the map, see [`MapHints`].
+    ///
+    /// Invalid hints are skipped, [`apply_map_settings`] reports them.
+    pub fn map_hints(map_config: &MapConfig) -> MapHints {
+        map_config
+            .commands
+            .iter()
+            .filter_map(|cmd| cmd.value.trim_start().strip_prefix("hint "))
+            .filter_map(|args| parse_hint(args).ok())
+            .collect()
+    }
+
+    fn apply_command(global_tunes: &mut Tunings, cmd: &str) -> Result<(), MapSettingError> {
+        let mut args = cmd.split_whitespace();
+        match args.next() {
+            Some("tune") => {
+                let (Some(name), Some(val), None) = (args.next(), args.next(), args.next()) else {
+                    return Err(MapSettingError::InvalidValue(
+                        "expected `tune <name> <value>`".to_string(),
+                    ));
+                };
+                global_tunes
+                    .try_set_from_str(
+                        name.to_string(),
+                        None,
+                        Some(val.to_string()),
+                        None,
+                        ConfigFromStrOperation::Set,
+                    )
+                    .map_err(|err| MapSettingError::InvalidValue(err.to_string()))?;
+                Ok(())
+            }
+            // hints are loaded by `map_hints`
+            Some("hint") => {
+                parse_hint(cmd.trim_start().strip_prefix("hint").unwrap_or_default()).map(|_| ())
+            }
+            _ => Err(MapSettingError::UnknownCommand),
+        }
+    }
+
+    /// Applies the settings of the map on top of the config of the server.
+    ///
+    /// Config variables are applied before the commands.
+    /// Settings that are not allowed or invalid are skipped
+    /// and returned together with the reason.
+    pub fn apply_map_settings(
+        map_config: &MapConfig,
+        config: &mut ConfigVanilla,
+        global_tunes: &mut Tunings,
+    ) -> Vec<RejectedMapSetting> {
+        let mut rejected = Vec::new();
+        for (name, val) in map_config.config_variables.iter() {
+            if let Err(err) = apply_config_variable(config, name, &val.value) {
+                rejected.push(RejectedMapSetting {
+                    setting: format!("{name} {}", val.value),
+                    err,
+                });
+            }
+        }
+        for cmd in map_config.commands.iter() {
+            if let Err(err) = apply_command(global_tunes, &cmd.value) {
+                rejected.push(RejectedMapSetting {
+                    setting: cmd.value.clone(),
+                    err,
+                });
+            }
+        }
+        rejected
+    }
+}
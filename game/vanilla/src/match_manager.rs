@@ -1,14 +1,24 @@
 /// everything related to a single match/round/race-run
 pub mod match_manager {
-    use std::time::Duration;
-
-    use game_interface::types::{game::GameTickType, render::game::game_match::MatchSide};
+    use game_interface::{
+        events::{
+            GameWorldEvent, GameWorldHighlight, GameWorldNotificationEvent, GameWorldPlayerStats,
+        },
+        types::{
+            game::GameTickType, id_types::CharacterId, player_info::PlayerUniqueId,
+            render::game::game_match::MatchSide,
+        },
+    };
     use hiarc::{Hiarc, hi_closure};
+    use pool::mt_datatypes::PoolVec;
+    use rustc_hash::FxHashSet;
 
     use crate::{
         config::config::ConfigGameType,
+        entities::character::character::{Character, CharacterPhasedState},
         events::events::{CharacterEvent, CharacterEventMod, FlagEvent},
-        match_state::match_state::{Match, MatchState, MatchType},
+        match_state::match_state::{Match, MatchState, MatchType, MatchWinner},
+        match_stats::match_stats::MatchStats,
         simulation_pipe::simulation_pipe::{
             SimulationEventWorldEntityType, SimulationStageEvents, SimulationWorldEvent,
         },
@@ -17,16 +27,154 @@ pub mod match_manager {
         world::world::GameWorld,
     };
 
+    /// How long a highlight starts before the capture or the first kill.
+    pub const HIGHLIGHT_LEAD_TICKS: GameTickType = TICKS_PER_SECOND * 3;
+    /// How long a highlight continues after the capture or the last kill.
+    pub const HIGHLIGHT_TAIL_TICKS: GameTickType = TICKS_PER_SECOND / 2;
+    /// Kills of the same killer within this time count as one multi-kill.
+    pub const HIGHLIGHT_MULTI_KILL_TICKS: GameTickType = TICKS_PER_SECOND * 3;
+    /// Older moments are not picked anymore, clients only
+    /// keep a limited amount of ticks to replay.
+    pub const HIGHLIGHT_MAX_AGE_TICKS: GameTickType = TICKS_PER_SECOND * 20;
+
+    #[derive(Debug, Hiarc, Clone, Copy, PartialEq, Eq)]
+    struct HighlightKill {
+        tick: GameTickType,
+        killer: CharacterId,
+        victim: CharacterId,
+    }
+
+    /// The highlight of a round, see [`GameWorldHighlight`].
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct MatchHighlight {
+        pub start_ticks_ago: GameTickType,
+        pub end_ticks_ago: GameTickType,
+        /// The main actor first.
+        pub characters: Vec<CharacterId>,
+    }
+
+    /// Remembers the moments of a round that could decide it,
+    /// to pick the highlight at the end of the round.
+    #[derive(Debug, Hiarc, Default)]
+    pub struct MatchHighlights {
+        /// Ticks since the round started.
+        tick: GameTickType,
+        last_capture: Option<(GameTickType, CharacterId)>,
+        kills: Vec<HighlightKill>,
+    }
+
+    impl MatchHighlights {
+        pub fn tick(&mut self) {
+            self.tick += 1;
+            let tick = self.tick;
+            self.kills
+                .retain(|kill| tick - kill.tick <= HIGHLIGHT_MAX_AGE_TICKS);
+        }
+
+        pub fn capture(&mut self, by: CharacterId) {
+            self.last_capture = Some((self.tick, by));
+        }
+
+        /// Suicides are no highlights.
+        pub fn kill(&mut self, killer: CharacterId, victim: CharacterId) {
+            if killer != victim {
+                self.kills.push(HighlightKill {
+                    tick: self.tick,
+                    killer,
+                    victim,
+                });
+            }
+        }
+
+        pub fn reset(&mut self) {
+            *self = Default::default();
+        }
+
+        /// Picks the highlight of the round:
+        /// the last flag capture, or else the window with the most kills
+        /// of a single killer, where later windows win ties.
+        pub fn select(&self) -> Option<MatchHighlight> {
+            let now = self.tick;
+            let (first, last, characters) = if let Some((tick, by)) = self
+                .last_capture
+                .filter(|(tick, _)| now - tick <= HIGHLIGHT_MAX_AGE_TICKS)
+            {
+                (tick, tick, vec![by])
+            } else {
+                let (start, _) = self
+                    .kills
+                    .iter()
+                    .enumerate()
+                    .map(|(index, kill)| {
+                        let count = self.kills[index..]
+                            .iter()
+                            .filter(|other| {
+                                other.killer == kill.killer
+                                    && other.tick - kill.tick <= HIGHLIGHT_MULTI_KILL_TICKS
+                            })
+                            .count();
+                        (index, count)
+                    })
+                    .max_by_key(|&(index, count)| (count, index))?;
+                let first = &self.kills[start];
+                let window: Vec<_> = self.kills[start..]
+                    .iter()
+                    .filter(|other| {
+                        other.killer == first.killer
+                            && other.tick - first.tick <= HIGHLIGHT_MULTI_KILL_TICKS
+                    })
+                    .collect();
+                let mut characters = vec![first.killer];
+                for kill in &window {
+                    if !characters.contains(&kill.victim) {
+                        characters.push(kill.victim);
+                    }
+                }
+                (first.tick, window.last()?.tick, characters)
+            };
+
+            Some(MatchHighlight {
+                start_ticks_ago: now - first.saturating_sub(HIGHLIGHT_LEAD_TICKS),
+                end_ticks_ago: now - (last + HIGHLIGHT_TAIL_TICKS).min(now),
+                characters,
+            })
+        }
+    }
+
     #[derive(Debug, Hiarc)]
     pub struct MatchManager {
         pub(crate) game_options: GameOptions,
         simulation_events: SimulationStageEvents,
 
         pub(crate) game_match: Match,
+
+        pub(crate) stats: MatchStats,
+        /// The statistics were already sent for the current game over.
+        stats_sent: bool,
+        /// The statistics of the last finished round,
+        /// which were not persisted yet.
+        ///
+        /// Only contains the main players of the clients, since a
+        /// dummy shares the unique identifier with its main player.
+        pub(crate) finished_stats: Vec<(PlayerUniqueId, GameWorldPlayerStats)>,
+
+        /// The moments of the current round for the highlight.
+        pub(crate) highlights: MatchHighlights,
+
+        /// The players that are ready to end the warmup.
+        ready_players: FxHashSet<CharacterId>,
+        /// The overtime that was started last, `0` outside of overtimes.
+        overtime: u32,
     }
 
     impl MatchManager {
-        pub fn new(game_options: GameOptions, simulation_events: &SimulationStageEvents) -> Self {
+        /// If `warmup` is `true` and a warmup time is configured,
+        /// the match starts with a warmup.
+        pub fn new(
+            game_options: GameOptions,
+            simulation_events: &SimulationStageEvents,
+            warmup: bool,
+        ) -> Self {
             Self {
                 game_match: Match {
                     ty: match game_options.ty() {
@@ -35,22 +183,38 @@ pub mod match_manager {
                             scores: Default::default(),
                         },
                     },
-                    state: MatchState::Running {
-                        round_ticks_passed: Default::default(),
-                        round_ticks_left: game_options
-                            .time_limit()
-                            .map(|time| {
-                                ((time.as_micros() * TICKS_PER_SECOND as u128)
-                                    / Duration::from_secs(1).as_micros())
-                                    as GameTickType
-                            })
-                            .unwrap_or_default()
-                            .into(),
+                    state: match game_options.warmup_time().filter(|_| warmup) {
+                        Some(time) => MatchState::Warmup {
+                            ticks_left: game_options.time_to_ticks(time).into(),
+                            ready_players: 0,
+                            total_players: 0,
+                        },
+                        None => Self::running_state(&game_options),
                     },
                     balance_tick: Default::default(),
                 },
                 game_options,
                 simulation_events: simulation_events.clone(),
+
+                stats: Default::default(),
+                stats_sent: false,
+                finished_stats: Default::default(),
+
+                highlights: Default::default(),
+
+                ready_players: Default::default(),
+                overtime: 0,
+            }
+        }
+
+        fn running_state(game_options: &GameOptions) -> MatchState {
+            MatchState::Running {
+                round_ticks_passed: Default::default(),
+                round_ticks_left: game_options
+                    .time_limit()
+                    .map(|time| game_options.time_to_ticks(time))
+                    .unwrap_or_default()
+                    .into(),
             }
         }
 
@@ -71,15 +235,25 @@ pub mod match_manager {
         }
 
         fn handle_events(&mut self, world: &mut GameWorld) {
+            if matches!(self.game_match.state, MatchState::Warmup { .. }) {
+                // no scores and no statistics during warmup
+                return;
+            }
             let game_match = &mut self.game_match;
             let game_options = &self.game_options;
+            let stats = &mut self.stats;
+            let highlights = &mut self.highlights;
             self.simulation_events
-                .for_each(hi_closure!([game_match: &mut Match, game_options: &GameOptions, world: &mut GameWorld], |ev: &SimulationWorldEvent| -> () {
+                .for_each(hi_closure!([game_match: &mut Match, game_options: &GameOptions, world: &mut GameWorld, stats: &mut MatchStats, highlights: &mut MatchHighlights], |ev: &SimulationWorldEvent| -> () {
                     match ev {
                         SimulationWorldEvent::Entity(entity_ev) => match &entity_ev.ev {
                             SimulationEventWorldEntityType::Character { ev, .. } => {
+                                stats.handle_character_event(world, ev);
                                 match ev {
                                     CharacterEvent::Despawn { killer_id, id: victim_id, .. } => {
+                                        if let Some(killer_id) = killer_id {
+                                            highlights.kill(*killer_id, *victim_id);
+                                        }
                                         if let Some(char) = killer_id.and_then(|killer_id| world.characters.get_mut(&killer_id)) {
                                             if Some(*victim_id) == *killer_id {
                                                 char.score.set(char.score.get() - 1);
@@ -93,14 +267,19 @@ pub mod match_manager {
                                             game_match.win_check(game_options, &world.scores, false);
                                         }
                                     }
+                                    CharacterEvent::Fire { .. } | CharacterEvent::Damage { .. } => {
+                                        // only for statistics
+                                    }
                                     CharacterEvent::Mod(mod_ev) => {
                                         MatchManager::mod_event(world, game_match,game_options, mod_ev);
                                     }
                                 }
                             },
                             SimulationEventWorldEntityType::Flag { ev, .. } => {
+                                stats.handle_flag_event(world, ev);
                                 match ev {
                                     FlagEvent::Capture { by, .. } => {
+                                        highlights.capture(*by);
                                         if let Some(char) = world.characters.get_mut(by) {
                                             char.score.set(char.score.get() + 5);
                                             if let (MatchType::Sided { scores }, Some(score)) = (&mut game_match.ty, char.core.side) {
@@ -120,7 +299,7 @@ pub mod match_manager {
                                     },
                                     FlagEvent:: Despawn {
                                       ..
-                                    } => {
+                                    } | FlagEvent::Return { .. } => {
                                         // ignore
                                     }
                                 }
@@ -139,71 +318,318 @@ pub mod match_manager {
             red.abs_diff(blue) > 1
         }
 
+        /// The characters that should switch to the other side to balance the sides.
+        ///
+        /// Characters that joined their side during the current round are preferred,
+        /// then the ones with the lowest score and then the most recent joiners.
+        /// Characters that were moved by the balancing recently are immune.
+        pub fn sided_balance_plan(&self, world: &GameWorld) -> Vec<CharacterId> {
+            let (red, blue) = world.count_sides();
+            if red.abs_diff(blue) <= 1 {
+                return Vec::new();
+            }
+            let side = if red > blue {
+                MatchSide::Red
+            } else {
+                MatchSide::Blue
+            };
+
+            let round_ticks = self.game_match.state.passed_ticks();
+            let exclude_bots = self.game_options.bots_exclude_from_balancing();
+            let mut candidates: Vec<_> = world
+                .characters
+                .iter()
+                .filter(|(_, character)| {
+                    character.core.side == Some(side)
+                        && (!exclude_bots || !character.player_info.is_bot)
+                        && character.core.balance_immunity.is_none()
+                })
+                .map(|(id, character)| {
+                    (
+                        character.core.side_ticks > round_ticks,
+                        character.score.get(),
+                        character.core.side_ticks,
+                        *id,
+                    )
+                })
+                .collect();
+            candidates.sort();
+            candidates
+                .into_iter()
+                .take(red.abs_diff(blue) / 2)
+                .map(|(_, _, _, id)| id)
+                .collect()
+        }
+
+        /// Moves the characters of the balance plan to the other side.
+        fn sided_balance(&mut self, world: &mut GameWorld) {
+            let immunity = self.game_options.sided_balance_immunity_ticks();
+            for id in self.sided_balance_plan(world) {
+                if let Some(character) = world.characters.get_mut(&id) {
+                    character.core.side = character.core.side.map(|side| match side {
+                        MatchSide::Red => MatchSide::Blue,
+                        MatchSide::Blue => MatchSide::Red,
+                    });
+                    character.core.side_ticks = 0;
+                    character.core.balance_immunity = immunity.into();
+                }
+            }
+        }
+
+        fn tick_sides(world: &mut GameWorld) {
+            for character in world.characters.values_mut() {
+                if character.core.side.is_some() {
+                    character.core.side_ticks += 1;
+                }
+                character.core.balance_immunity.tick();
+            }
+        }
+
         fn auto_sided_balance(&mut self, world: &mut GameWorld) {
-            if Self::needs_sided_balance(world) {
+            // balanced when the round ends instead
+            if Self::needs_sided_balance(world) && !self.game_options.sided_balance_at_round_end() {
                 if self.game_match.balance_tick.is_none() {
                     self.game_match.balance_tick = self
                         .game_options
                         .sided_balance_time()
-                        .map(|time| {
-                            ((time.as_micros() * TICKS_PER_SECOND as u128)
-                                / Duration::from_secs(1).as_micros())
-                                as GameTickType
-                        })
+                        .map(|time| self.game_options.time_to_ticks(time))
                         .unwrap_or_default()
                         .into();
                 } else if self.game_match.balance_tick.tick().unwrap_or_default() {
                     // force auto balance
-                    let (red, blue) = world.count_sides();
+                    self.sided_balance(world);
+                }
+            } else {
+                self.game_match.balance_tick = Default::default();
+            }
+        }
 
-                    let diff = red.abs_diff(blue);
+        /// Sends the statistics of all players to the clients.
+        fn send_stats(&mut self, world: &GameWorld) {
+            self.stats.add_characters(world);
 
-                    let side = if red > blue {
-                        MatchSide::Red
-                    } else {
-                        MatchSide::Blue
-                    };
-                    let join_side = match side {
-                        MatchSide::Red => MatchSide::Blue,
-                        MatchSide::Blue => MatchSide::Red,
-                    };
+            let mut stats = PoolVec::new_without_pool();
+            for (id, player) in self.stats.rows() {
+                let ev = player.to_event(*id, self.game_options.ticks_per_second());
+                if player.client_player_id == 0 {
+                    self.finished_stats
+                        .push((player.unique_identifier, ev.clone()));
+                }
+                stats.push(ev);
+            }
+            let highlight = self
+                .highlights
+                .select()
+                .map(|highlight| GameWorldHighlight {
+                    start_ticks_ago: highlight.start_ticks_ago,
+                    end_ticks_ago: highlight.end_ticks_ago,
+                    characters: {
+                        let mut characters = PoolVec::new_without_pool();
+                        characters.extend(highlight.characters);
+                        characters
+                    },
+                });
+            world.game_pending_events.push(GameWorldEvent::Notification(
+                GameWorldNotificationEvent::GameOver { stats, highlight },
+            ));
+        }
+
+        /// Toggles whether the player is ready to end the warmup.
+        ///
+        /// Returns the new ready state, or `None` if there is no warmup.
+        pub fn toggle_ready(&mut self, id: &CharacterId) -> Option<bool> {
+            if !matches!(self.game_match.state, MatchState::Warmup { .. }) {
+                return None;
+            }
+            if self.ready_players.remove(id) {
+                Some(false)
+            } else {
+                self.ready_players.insert(*id);
+                Some(true)
+            }
+        }
+
+        /// Updates the ready players & gives infinite ammo.
+        ///
+        /// Returns `true` if the warmup is over.
+        fn tick_warmup(&mut self, world: &mut GameWorld) -> bool {
+            let MatchState::Warmup {
+                ticks_left,
+                ready_players,
+                total_players,
+            } = &mut self.game_match.state
+            else {
+                return false;
+            };
+
+            for character in world.characters.values_mut() {
+                for weapon in character.reusable_core.weapons.values_mut() {
+                    weapon.cur_ammo = None;
+                }
+            }
+
+            self.ready_players.retain(|id| {
+                world
+                    .characters
+                    .get(id)
+                    .is_some_and(|character| !character.player_info.is_bot)
+            });
+            *ready_players = self.ready_players.len() as u32;
+            *total_players = world
+                .characters
+                .values()
+                .filter(|character| !character.player_info.is_bot)
+                .count() as u32;
+
+            // the warmup only runs out if someone is playing
+            if *total_players == 0 {
+                return false;
+            }
+            let enough_ready = *ready_players as u64 * 100
+                >= *total_players as u64 * self.game_options.warmup_ready_percentage() as u64;
+            ticks_left.tick().unwrap_or_default() || enough_ready
+        }
+
+        fn is_alive(character: &Character) -> bool {
+            matches!(character.phased, CharacterPhasedState::Normal(_))
+        }
+
+        /// Whether dead characters must wait for the next round.
+        ///
+        /// If nobody is alive anymore during an overtime without respawns,
+        /// all characters respawn, so the overtime can still be decided.
+        pub fn respawn_disabled(&self, world: &GameWorld) -> bool {
+            matches!(self.game_match.state, MatchState::SuddenDeath { .. })
+                && self.game_options.overtime_no_respawn()
+                && world.characters.values().any(Self::is_alive)
+        }
 
-                    world
+        /// The last player or side that is alive wins an overtime
+        /// without respawns.
+        fn last_standing_check(&mut self, world: &GameWorld) {
+            match self.game_match.ty {
+                MatchType::Solo => {
+                    let mut alive = world
                         .characters
-                        .values_mut()
-                        .filter(|character| character.core.side == Some(side))
-                        .take(diff / 2)
-                        .for_each(|character| character.core.side = Some(join_side));
+                        .iter()
+                        .filter(|(_, character)| Self::is_alive(character));
+                    if world.characters.len() > 1
+                        && let (Some((id, _)), None) = (alive.next(), alive.next())
+                    {
+                        self.game_match.game_over(
+                            &self.game_options,
+                            MatchWinner::Character(*id),
+                            true,
+                        );
+                    }
+                }
+                MatchType::Sided { .. } => {
+                    let (red, blue) = world.count_sides();
+                    let [red_alive, blue_alive] = [MatchSide::Red, MatchSide::Blue].map(|side| {
+                        world
+                            .characters
+                            .values()
+                            .filter(|character| {
+                                character.core.side == Some(side) && Self::is_alive(character)
+                            })
+                            .count()
+                    });
+                    if red == 0 || blue == 0 {
+                        // nobody to win against
+                    } else if red_alive > 0 && blue_alive == 0 {
+                        self.game_match.game_over(
+                            &self.game_options,
+                            MatchWinner::Side(MatchSide::Red),
+                            true,
+                        );
+                    } else if blue_alive > 0 && red_alive == 0 {
+                        self.game_match.game_over(
+                            &self.game_options,
+                            MatchWinner::Side(MatchSide::Blue),
+                            true,
+                        );
+                    }
+                }
+            }
+        }
+
+        /// Applies the overtime rules of the game options.
+        fn tick_overtime(&mut self, world: &mut GameWorld) {
+            match self.game_match.state {
+                MatchState::SuddenDeath { overtime, .. } => {
+                    if overtime != self.overtime {
+                        self.overtime = overtime;
+                        if self.game_options.overtime_reset_flags() {
+                            world
+                                .red_flags
+                                .values_mut()
+                                .chain(world.blue_flags.values_mut())
+                                .for_each(|flag| flag.reset(false));
+                        }
+                    }
+                    if self.game_options.overtime_no_respawn() {
+                        self.last_standing_check(world);
+                    }
+                }
+                MatchState::PausedSuddenDeath { .. } => {}
+                MatchState::Warmup { .. }
+                | MatchState::Running { .. }
+                | MatchState::Paused { .. }
+                | MatchState::GameOver { .. } => {
+                    self.overtime = 0;
                 }
-            } else {
-                self.game_match.balance_tick = Default::default();
             }
         }
 
+        /// Starts a new round, the scores are reset and
+        /// all characters respawn.
+        fn start_round(&mut self, world: &mut GameWorld) {
+            if self.game_options.sided_balance_at_round_end() {
+                self.sided_balance(world);
+            }
+            self.game_match.state = Self::running_state(&self.game_options);
+            self.highlights.reset();
+            world.characters.values_mut().for_each(|char| {
+                char.score.set(0);
+                char.despawn_to_respawn(false);
+            });
+        }
+
         /// returns true, if match needs a restart
         #[must_use]
         pub fn tick(&mut self, world: &mut GameWorld) -> bool {
+            self.highlights.tick();
             self.handle_events(world);
+            self.tick_overtime(world);
+            if matches!(self.game_match.ty, MatchType::Sided { .. }) {
+                Self::tick_sides(world);
+            }
+
+            match self.game_match.state {
+                MatchState::Warmup { .. } => {
+                    if self.tick_warmup(world) {
+                        self.start_round(world);
+                        return true;
+                    }
+                }
+                MatchState::Running { .. } | MatchState::SuddenDeath { .. } => {
+                    self.stats.tick_flags(world);
+                    if self.game_options.hook_assist_stats() {
+                        self.stats.tick_hook_assists(world);
+                    }
+                }
+                MatchState::GameOver { .. } => {
+                    if !self.stats_sent {
+                        self.stats_sent = true;
+                        self.send_stats(world);
+                    }
+                }
+                MatchState::Paused { .. } | MatchState::PausedSuddenDeath { .. } => {}
+            }
 
             if let MatchState::GameOver { new_game_in, .. } = &mut self.game_match.state {
                 if new_game_in.tick().unwrap_or_default() {
-                    self.game_match.state = MatchState::Running {
-                        round_ticks_passed: Default::default(),
-                        round_ticks_left: self
-                            .game_options
-                            .time_limit()
-                            .map(|time| {
-                                ((time.as_micros() * TICKS_PER_SECOND as u128)
-                                    / Duration::from_secs(1).as_micros())
-                                    as GameTickType
-                            })
-                            .unwrap_or_default()
-                            .into(),
-                    };
-                    world.characters.values_mut().for_each(|char| {
-                        char.score.set(0);
-                        char.despawn_to_respawn(false);
-                    });
+                    self.start_round(world);
                     true
                 } else {
                     false
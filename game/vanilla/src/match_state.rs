@@ -15,8 +15,8 @@ pub mod match_state {
     use serde::{Deserialize, Serialize};
 
     use crate::{
-        entities::character::score::character_score::CharacterScores,
-        state::state::TICKS_PER_SECOND, types::types::GameOptions, world::world::GameWorld,
+        entities::character::score::character_score::CharacterScores, types::types::GameOptions,
+        world::world::GameWorld,
     };
 
     #[derive(Debug, Hiarc, Serialize, Deserialize, Clone, Copy)]
@@ -27,6 +27,16 @@ pub mod match_state {
 
     #[derive(Debug, Hiarc, Serialize, Deserialize, Clone, Copy)]
     pub enum MatchState {
+        /// The round did not start yet.
+        /// No scores are counted and the weapons have infinite ammo.
+        Warmup {
+            /// The round starts when this cooldown ends,
+            /// even if not enough players are ready.
+            ticks_left: GameTickCooldown,
+            ready_players: u32,
+            /// All players of the stage, without bots.
+            total_players: u32,
+        },
         Running {
             /// How long the game round is running.
             round_ticks_passed: GameTickType,
@@ -37,15 +47,26 @@ pub mod match_state {
             round_ticks_passed: GameTickType,
             round_ticks_left: GameTickCooldown,
         },
+        /// The scores were tied when the time limit was reached.
         SuddenDeath {
             /// How long the game round is running.
             round_ticks_passed: GameTickType,
             by_cooldown: bool,
+            /// The how many-th overtime of the round, starting at `1`.
+            overtime: u32,
+            /// The ticks until a timed overtime ends.
+            /// If there are none, the next score wins.
+            overtime_ticks_left: GameTickCooldown,
         },
         PausedSuddenDeath {
             /// How long the game round is running.
             round_ticks_passed: GameTickType,
             by_cooldown: bool,
+            /// The how many-th overtime of the round, starting at `1`.
+            overtime: u32,
+            /// The ticks until a timed overtime ends.
+            /// If there are none, the next score wins.
+            overtime_ticks_left: GameTickCooldown,
         },
         GameOver {
             winner: MatchWinner,
@@ -59,6 +80,7 @@ pub mod match_state {
     impl MatchState {
         pub fn passed_ticks(&self) -> GameTickType {
             match self {
+                MatchState::Warmup { .. } => 0,
                 MatchState::Running {
                     round_ticks_passed, ..
                 } => *round_ticks_passed,
@@ -83,6 +105,15 @@ pub mod match_state {
             pools: &GamePooling,
         ) -> MatchRoundTimeType {
             match self {
+                MatchState::Warmup {
+                    ticks_left,
+                    ready_players,
+                    total_players,
+                } => MatchRoundTimeType::Warmup {
+                    ticks_left: ticks_left.get().map(|t| t.get()).unwrap_or_default(),
+                    ready_players: *ready_players,
+                    total_players: *total_players,
+                },
                 MatchState::Running {
                     round_ticks_left, ..
                 }
@@ -94,9 +125,22 @@ pub mod match_state {
                         ticks_left: ticks_left.get(),
                     })
                     .unwrap_or(MatchRoundTimeType::Normal),
-                MatchState::SuddenDeath { .. } | MatchState::PausedSuddenDeath { .. } => {
-                    MatchRoundTimeType::SuddenDeath
+                MatchState::SuddenDeath {
+                    overtime,
+                    overtime_ticks_left,
+                    ..
                 }
+                | MatchState::PausedSuddenDeath {
+                    overtime,
+                    overtime_ticks_left,
+                    ..
+                } => overtime_ticks_left
+                    .get()
+                    .map(|ticks_left| MatchRoundTimeType::Overtime {
+                        ticks_left: ticks_left.get(),
+                        overtime: *overtime,
+                    })
+                    .unwrap_or(MatchRoundTimeType::SuddenDeath),
                 MatchState::GameOver {
                     winner,
                     by_cooldown,
@@ -180,75 +224,123 @@ pub mod match_state {
 
     impl Match {
         // TODO: random 4 seconds
-        const TICKS_UNTIL_NEW_GAME: GameTickType = TICKS_PER_SECOND * 4;
+        const SECS_UNTIL_NEW_GAME: GameTickType = 4;
+
+        pub(crate) fn game_over(
+            &mut self,
+            game_options: &GameOptions,
+            winner: MatchWinner,
+            by_cooldown: bool,
+        ) {
+            self.state = MatchState::GameOver {
+                winner,
+                new_game_in: (Self::SECS_UNTIL_NEW_GAME * game_options.ticks_per_second()).into(),
+                round_ticks_passed: self.state.passed_ticks(),
+                by_cooldown,
+            };
+        }
+
+        /// Starts the `overtime`-th overtime.
+        ///
+        /// Timed overtimes are only repeated `overtime_repeats` times,
+        /// afterwards the next score wins.
+        fn start_overtime(&mut self, game_options: &GameOptions, overtime: u32) {
+            let overtime_ticks_left = game_options
+                .overtime_ticks()
+                .filter(|_| overtime <= game_options.overtime_repeats().saturating_add(1))
+                .unwrap_or_default()
+                .into();
+            self.state = MatchState::SuddenDeath {
+                round_ticks_passed: self.state.passed_ticks(),
+                by_cooldown: true,
+                overtime,
+                overtime_ticks_left,
+            };
+        }
+
+        /// The winner if there is a single leader & the leading score.
+        fn leader(&self, scores: &CharacterScores) -> Option<(Option<MatchWinner>, i64)> {
+            match self.ty {
+                MatchType::Solo => {
+                    scores
+                        .leading_characters()
+                        .map(|(leading_characters, score)| {
+                            (
+                                leading_characters
+                                    .iter()
+                                    .next()
+                                    .copied()
+                                    .filter(|_| leading_characters.len() == 1)
+                                    .map(MatchWinner::Character),
+                                score,
+                            )
+                        })
+                }
+                MatchType::Sided { scores } => Some(match scores[0].cmp(&scores[1]) {
+                    std::cmp::Ordering::Less => {
+                        (Some(MatchWinner::Side(MatchSide::Blue)), scores[1])
+                    }
+                    std::cmp::Ordering::Equal => (None, scores[0]),
+                    std::cmp::Ordering::Greater => {
+                        (Some(MatchWinner::Side(MatchSide::Red)), scores[0])
+                    }
+                }),
+            }
+        }
 
-        // TODO: sudden death solo
+        /// `round_time_limit_reached` is `true` if the time limit
+        /// of the round or of a timed overtime was reached.
         pub fn win_check(
             &mut self,
             game_options: &GameOptions,
             scores: &CharacterScores,
             round_time_limit_reached: bool,
         ) {
-            let cur_tick = self.state.passed_ticks();
-            let round_time_limit_reached = round_time_limit_reached
-                | matches!(
-                    self.state,
-                    MatchState::SuddenDeath { .. } | MatchState::PausedSuddenDeath { .. }
-                );
-            match self.ty {
-                MatchType::Solo => {
-                    if let Some((leading_characters, score)) = scores.leading_characters() {
-                        // check if the character has hit a specific score
-                        if let Some(leading_characters) = ((score >= 0
-                            && score as u64 >= game_options.score_limit())
-                            || round_time_limit_reached)
-                            .then_some(leading_characters)
-                        {
-                            if leading_characters.len() == 1 {
-                                // TODO:
-                                self.state = MatchState::GameOver {
-                                    winner: MatchWinner::Character(
-                                        leading_characters.iter().next().copied().unwrap(),
-                                    ),
-                                    new_game_in: Self::TICKS_UNTIL_NEW_GAME.into(),
-                                    round_ticks_passed: cur_tick,
-                                    by_cooldown: round_time_limit_reached,
-                                }
-                            } else if round_time_limit_reached {
-                                self.state = MatchState::SuddenDeath {
-                                    round_ticks_passed: cur_tick,
-                                    by_cooldown: round_time_limit_reached,
-                                };
-                            }
-                        }
+            let Some((leader, score)) = self.leader(scores) else {
+                return;
+            };
+            let score_limit_reached = score >= 0 && score as u64 >= game_options.score_limit();
+
+            match self.state {
+                MatchState::Warmup { .. }
+                | MatchState::Running { .. }
+                | MatchState::Paused { .. } => {
+                    if let Some(winner) = leader
+                        && (score_limit_reached || round_time_limit_reached)
+                    {
+                        self.game_over(game_options, winner, round_time_limit_reached);
+                    } else if round_time_limit_reached {
+                        self.start_overtime(game_options, 1);
                     }
                 }
-                MatchType::Sided { scores } => {
-                    let leading_side = match scores[0].cmp(&scores[1]) {
-                        std::cmp::Ordering::Less => Some((scores[1], MatchSide::Blue)),
-                        std::cmp::Ordering::Equal => None,
-                        std::cmp::Ordering::Greater => Some((scores[0], MatchSide::Red)),
-                    };
-
-                    if let Some(side) = leading_side.and_then(|(score, side)| {
-                        ((score >= 0 && score as u64 >= game_options.score_limit())
-                            || round_time_limit_reached)
-                            .then_some(side)
-                    }) {
-                        // TODO:
-                        self.state = MatchState::GameOver {
-                            winner: MatchWinner::Side(side),
-                            new_game_in: Self::TICKS_UNTIL_NEW_GAME.into(),
-                            round_ticks_passed: cur_tick,
-                            by_cooldown: round_time_limit_reached,
-                        };
+                MatchState::SuddenDeath {
+                    overtime,
+                    overtime_ticks_left,
+                    ..
+                }
+                | MatchState::PausedSuddenDeath {
+                    overtime,
+                    overtime_ticks_left,
+                    ..
+                } => {
+                    let golden_goal = overtime_ticks_left.is_none();
+                    if let Some(winner) = leader
+                        && (golden_goal
+                            || round_time_limit_reached
+                            || (score_limit_reached && game_options.overtime_score_limit()))
+                    {
+                        self.game_over(
+                            game_options,
+                            winner,
+                            golden_goal || round_time_limit_reached,
+                        );
                     } else if round_time_limit_reached {
-                        self.state = MatchState::SuddenDeath {
-                            round_ticks_passed: cur_tick,
-                            by_cooldown: round_time_limit_reached,
-                        };
+                        self.start_overtime(game_options, overtime + 1);
                     }
                 }
+                MatchState::GameOver { .. } => {
+                    // already decided
+                }
             }
         }
 
@@ -264,9 +356,17 @@ pub mod match_state {
                     }
                 }
                 MatchState::SuddenDeath {
-                    round_ticks_passed, ..
+                    round_ticks_passed,
+                    overtime_ticks_left,
+                    ..
                 } => {
                     *round_ticks_passed += 1;
+                    if overtime_ticks_left.tick().unwrap_or_default() {
+                        self.win_check(game_options, scores, true);
+                    }
+                }
+                MatchState::Warmup { .. } => {
+                    // handled by the match manager
                 }
                 MatchState::Paused { .. }
                 | MatchState::PausedSuddenDeath { .. }
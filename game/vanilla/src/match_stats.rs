@@ -0,0 +1,242 @@
+/// statistics of the players during a single match/round
+pub mod match_stats {
+    use std::time::Duration;
+
+    use base::{linked_hash_map_view::FxLinkedHashMap, network_string::NetworkString};
+    use game_interface::{
+        events::{GameWorldActionKillWeapon, GameWorldPlayerStats, GameWorldWeaponStats},
+        types::{
+            character_info::MAX_CHARACTER_NAME_LEN,
+            game::GameTickType,
+            id_types::CharacterId,
+            player_info::PlayerUniqueId,
+            weapons::{EnumCount, WeaponType},
+        },
+    };
+    use hiarc::Hiarc;
+
+    use crate::{
+        entities::character::character::CharacterPhasedState,
+        events::events::{CharacterEvent, FlagEvent},
+        world::world::GameWorld,
+    };
+
+    #[derive(Debug, Hiarc, Clone)]
+    pub struct PlayerMatchStats {
+        pub name: NetworkString<MAX_CHARACTER_NAME_LEN>,
+        pub unique_identifier: PlayerUniqueId,
+        /// The id the client gave this player.
+        /// `0` is the main player of a client, others are e.g. dummies.
+        pub client_player_id: u64,
+
+        pub weapons: [GameWorldWeaponStats; WeaponType::COUNT],
+        pub kills: u32,
+        pub deaths: u32,
+        pub damage_dealt: u32,
+        pub damage_taken: u32,
+        pub flag_carry_ticks: GameTickType,
+        pub flag_captures: u32,
+        pub flag_returns: u32,
+        /// How long the player hooked teammates.
+        pub hook_assist_ticks: GameTickType,
+        /// Kills since the last death.
+        pub killstreak: u32,
+        pub longest_killstreak: u32,
+        pub disconnected: bool,
+    }
+
+    impl PlayerMatchStats {
+        pub fn to_event(&self, id: CharacterId, ticks_per_second: u64) -> GameWorldPlayerStats {
+            GameWorldPlayerStats {
+                id,
+                name: self.name.clone(),
+                weapons: self.weapons,
+                kills: self.kills,
+                deaths: self.deaths,
+                damage_dealt: self.damage_dealt,
+                damage_taken: self.damage_taken,
+                flag_carry_time: Duration::from_secs_f64(
+                    self.flag_carry_ticks as f64 / ticks_per_second as f64,
+                ),
+                flag_captures: self.flag_captures,
+                flag_returns: self.flag_returns,
+                hook_assist_time: Duration::from_secs_f64(
+                    self.hook_assist_ticks as f64 / ticks_per_second as f64,
+                ),
+                longest_killstreak: self.longest_killstreak,
+                disconnected: self.disconnected,
+            }
+        }
+    }
+
+    /// Collects the statistics of all players of a stage
+    /// from the simulation events.
+    ///
+    /// Players keep their row if they leave the round,
+    /// so the row is complete at game over.
+    #[derive(Debug, Hiarc, Default)]
+    pub struct MatchStats {
+        players: FxLinkedHashMap<CharacterId, PlayerMatchStats>,
+    }
+
+    impl MatchStats {
+        /// Get the row of the player, creates the row if the player
+        /// has a character in the world.
+        fn player<'a>(
+            players: &'a mut FxLinkedHashMap<CharacterId, PlayerMatchStats>,
+            world: &GameWorld,
+            id: &CharacterId,
+        ) -> Option<&'a mut PlayerMatchStats> {
+            if !players.contains_key(id) {
+                let character = world.characters.get(id)?;
+                players.insert(
+                    *id,
+                    PlayerMatchStats {
+                        name: character.player_info.player_info.name.clone(),
+                        unique_identifier: character.player_info.unique_identifier,
+                        client_player_id: character.player_info.id,
+                        weapons: Default::default(),
+                        kills: 0,
+                        deaths: 0,
+                        damage_dealt: 0,
+                        damage_taken: 0,
+                        flag_carry_ticks: 0,
+                        flag_captures: 0,
+                        flag_returns: 0,
+                        hook_assist_ticks: 0,
+                        killstreak: 0,
+                        longest_killstreak: 0,
+                        disconnected: false,
+                    },
+                );
+            }
+            players.get_mut(id)
+        }
+
+        pub fn get(&self, id: &CharacterId) -> Option<&PlayerMatchStats> {
+            self.players.get(id)
+        }
+
+        pub fn handle_character_event(&mut self, world: &GameWorld, ev: &CharacterEvent) {
+            let players = &mut self.players;
+            match ev {
+                CharacterEvent::Despawn { id, killer_id, .. } => {
+                    if let Some(victim) = Self::player(players, world, id) {
+                        victim.deaths += 1;
+                        victim.killstreak = 0;
+                    }
+                    if let Some(killer) = killer_id
+                        .filter(|killer_id| *killer_id != *id)
+                        .and_then(|killer_id| Self::player(players, world, &killer_id))
+                    {
+                        killer.kills += 1;
+                        killer.killstreak += 1;
+                        killer.longest_killstreak =
+                            killer.longest_killstreak.max(killer.killstreak);
+                    }
+                }
+                CharacterEvent::Fire { id, weapon, shots } => {
+                    if let Some(player) = Self::player(players, world, id) {
+                        player.weapons[*weapon as usize].shots += *shots;
+                    }
+                }
+                CharacterEvent::Damage {
+                    id,
+                    attacker,
+                    damage,
+                    weapon,
+                } => {
+                    if let Some(victim) = Self::player(players, world, id) {
+                        victim.damage_taken += *damage;
+                    }
+                    if *attacker != *id
+                        && let Some(attacker) = Self::player(players, world, attacker)
+                    {
+                        attacker.damage_dealt += *damage;
+                        if let GameWorldActionKillWeapon::Weapon { weapon } = weapon {
+                            attacker.weapons[*weapon as usize].hits += 1;
+                        }
+                    }
+                }
+                CharacterEvent::Mod(_) => {}
+            }
+        }
+
+        pub fn handle_flag_event(&mut self, world: &GameWorld, ev: &FlagEvent) {
+            let players = &mut self.players;
+            match ev {
+                FlagEvent::Capture { by, .. } => {
+                    if let Some(player) = Self::player(players, world, by) {
+                        player.flag_captures += 1;
+                    }
+                }
+                FlagEvent::Return { by } => {
+                    if let Some(player) = Self::player(players, world, by) {
+                        player.flag_returns += 1;
+                    }
+                }
+                FlagEvent::Collect { .. } | FlagEvent::Despawn { .. } => {}
+            }
+        }
+
+        /// Counts the ticks the flags are carried.
+        pub fn tick_flags(&mut self, world: &GameWorld) {
+            let players = &mut self.players;
+            for flag in world
+                .get_red_flags()
+                .values()
+                .chain(world.get_blue_flags().values())
+            {
+                if let Some(player) = flag
+                    .core
+                    .carrier
+                    .and_then(|carrier| Self::player(players, world, &carrier))
+                {
+                    player.flag_carry_ticks += 1;
+                }
+            }
+        }
+
+        /// Counts the ticks the players hook their teammates.
+        pub fn tick_hook_assists(&mut self, world: &GameWorld) {
+            let players = &mut self.players;
+            for (id, character) in world.characters.iter() {
+                let CharacterPhasedState::Normal(normal) = &character.phased else {
+                    continue;
+                };
+                let Some(side) = character.core.side else {
+                    continue;
+                };
+                let hooks_teammate = normal.hook.hooked_char().is_some_and(|hooked_id| {
+                    world
+                        .characters
+                        .get(&hooked_id)
+                        .is_some_and(|hooked| hooked.core.side == Some(side))
+                });
+                if hooks_teammate && let Some(player) = Self::player(players, world, id) {
+                    player.hook_assist_ticks += 1;
+                }
+            }
+        }
+
+        /// The player left the game, the row is kept.
+        ///
+        /// Must be called before the character is removed from the world.
+        pub fn mark_disconnected(&mut self, world: &GameWorld, id: &CharacterId) {
+            if let Some(player) = Self::player(&mut self.players, world, id) {
+                player.disconnected = true;
+            }
+        }
+
+        /// Makes sure every character in the world has a row.
+        pub fn add_characters(&mut self, world: &GameWorld) {
+            for id in world.characters.keys() {
+                Self::player(&mut self.players, world, id);
+            }
+        }
+
+        pub fn rows(&self) -> impl Iterator<Item = (&CharacterId, &PlayerMatchStats)> {
+            self.players.iter()
+        }
+    }
+}
@@ -0,0 +1,290 @@
+/// moving platforms of the physics group
+pub mod platforms {
+    use anyhow::anyhow;
+    use hiarc::Hiarc;
+    use map::map::{
+        animations::{AnimPointPos, PosAnimation, TimeDuration},
+        groups::{MapGroupPhysics, layers::physics::MapPhysicsPlatformCrush},
+    };
+    use math::math::{
+        round_to_int,
+        vector::{ivec2, vec2},
+    };
+    use serde::{Deserialize, Serialize};
+
+    /// The area of a platform in world coordinates, `max` is exclusive.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct PlatformRect {
+        pub min: vec2,
+        pub max: vec2,
+    }
+
+    impl PlatformRect {
+        fn new(center: vec2, half_size: vec2) -> Self {
+            Self {
+                min: center - half_size,
+                max: center + half_size,
+            }
+        }
+
+        pub fn contains(&self, x: i32, y: i32) -> bool {
+            let (x, y) = (x as f32, y as f32);
+            x >= self.min.x && x < self.max.x && y >= self.min.y && y < self.max.y
+        }
+
+        /// Whether a box, that is tested like in [`Collision::test_box`], overlaps the area.
+        ///
+        /// [`Collision::test_box`]: crate::collision::collision::Collision::test_box
+        pub fn overlaps_box(&self, pos: &ivec2, size: &ivec2) -> bool {
+            let half = *size / 2;
+            (pos.x + half.x) as f32 >= self.min.x
+                && ((pos.x - half.x) as f32) < self.max.x
+                && (pos.y + half.y) as f32 >= self.min.y
+                && ((pos.y - half.y) as f32) < self.max.y
+        }
+    }
+
+    /// The position of a platform in the current & the previous tick.
+    #[derive(Debug, Hiarc, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+    pub struct PlatformState {
+        pub pos: vec2,
+        pub prev_pos: vec2,
+    }
+
+    #[derive(Debug, Clone)]
+    struct Platform {
+        /// The center, before the animation is applied.
+        pos: vec2,
+        half_size: vec2,
+
+        anim: PosAnimation,
+        anim_offset: TimeDuration,
+
+        hookable: bool,
+        crush: MapPhysicsPlatformCrush,
+    }
+
+    impl Platform {
+        /// Evaluates the animation like the map renders it,
+        /// but always loops & ignores the rotation.
+        fn pos_at(&self, time: TimeDuration) -> vec2 {
+            let points = &self.anim.points;
+            let (Some(first), Some(last)) = (points.first(), points.last()) else {
+                return self.pos;
+            };
+            let anim_len = last.time.saturating_sub(first.time);
+            let value = if anim_len.is_zero() {
+                first.value
+            } else {
+                let time = time + self.anim_offset;
+                let time = TimeDuration::nanoseconds(
+                    time.whole_nanoseconds()
+                        .rem_euclid(anim_len.as_nanos() as i128) as i64,
+                ) + first.time;
+                let index = points.partition_point(|point| time >= point.time);
+                AnimPointPos::eval_curve(
+                    &points[index.saturating_sub(1)],
+                    &points[index.min(points.len() - 1)],
+                    time,
+                )
+            };
+            self.pos + vec2::new(value.x.to_num::<f32>(), value.y.to_num::<f32>()) * 32.0
+        }
+    }
+
+    /// The platforms of the map, they move with the ticks of the game
+    /// & are shared by all stages.
+    ///
+    /// Platforms are solid for everything that collides with the map.
+    /// - Characters standing on a platform move with it & keep
+    ///   its velocity when they leave it.
+    /// - A platform pushes the characters it moved into, in the direction it moved.
+    ///   If a wall is in the way, the [`MapPhysicsPlatformCrush`] of the platform decides.
+    /// - Boxes, that already overlap a platform, are not blocked by it, so squeezed
+    ///   characters can move out of it.
+    /// - Hooks grab hookable platforms & move with them, other platforms
+    ///   behave like unhookable tiles.
+    #[derive(Debug, Default)]
+    pub struct Platforms {
+        platforms: Vec<Platform>,
+
+        /// How many ticks the platforms moved.
+        pub(crate) anim_ticks: u64,
+        pub(crate) states: Vec<PlatformState>,
+    }
+
+    impl Platforms {
+        /// Creates the platforms of the metadata of the physics group,
+        /// see [`MapGroupPhysics::get_meta`].
+        pub fn new(
+            physics_group: &MapGroupPhysics,
+            pos_anims: &[PosAnimation],
+        ) -> anyhow::Result<Self> {
+            let platforms = physics_group
+                .get_meta()
+                .map(|meta| meta.platforms)
+                .unwrap_or_default()
+                .into_iter()
+                .enumerate()
+                .map(|(index, platform)| {
+                    let anim = pos_anims.get(platform.pos_anim).ok_or_else(|| {
+                        anyhow!(
+                            "platform {index} uses the pos animation {}, \
+                            but there are only {}.",
+                            platform.pos_anim,
+                            pos_anims.len()
+                        )
+                    })?;
+                    Ok(Platform {
+                        pos: vec2::new(platform.pos.x.to_num(), platform.pos.y.to_num()) * 32.0,
+                        half_size: vec2::new(platform.size.x.to_num(), platform.size.y.to_num())
+                            * 16.0,
+                        anim: anim.clone(),
+                        anim_offset: platform.pos_anim_offset,
+                        hookable: platform.hookable,
+                        crush: platform.crush,
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+
+            let states = platforms
+                .iter()
+                .map(|platform| {
+                    let pos = platform.pos_at(TimeDuration::ZERO);
+                    PlatformState { pos, prev_pos: pos }
+                })
+                .collect();
+            Ok(Self {
+                platforms,
+                anim_ticks: 0,
+                states,
+            })
+        }
+
+        pub fn tick(&mut self, ticks_per_second: u64) {
+            self.anim_ticks += 1;
+            let time = TimeDuration::nanoseconds(
+                (self.anim_ticks as i128 * TimeDuration::SECOND.whole_nanoseconds()
+                    / ticks_per_second as i128) as i64,
+            );
+            for (platform, state) in self.platforms.iter().zip(self.states.iter_mut()) {
+                state.prev_pos = state.pos;
+                state.pos = platform.pos_at(time);
+            }
+        }
+
+        /// Restores the platforms of a snapshot.
+        ///
+        /// Snapshots of another map are ignored.
+        pub fn restore(&mut self, anim_ticks: u64, states: &[PlatformState]) {
+            if states.len() == self.states.len() {
+                self.anim_ticks = anim_ticks;
+                self.states.copy_from_slice(states);
+            }
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.platforms.is_empty()
+        }
+
+        pub fn len(&self) -> usize {
+            self.platforms.len()
+        }
+
+        pub fn hookable(&self, index: usize) -> bool {
+            self.platforms[index].hookable
+        }
+
+        pub fn crush(&self, index: usize) -> MapPhysicsPlatformCrush {
+            self.platforms[index].crush
+        }
+
+        pub fn rect(&self, index: usize) -> PlatformRect {
+            PlatformRect::new(self.states[index].pos, self.platforms[index].half_size)
+        }
+
+        pub fn prev_rect(&self, index: usize) -> PlatformRect {
+            PlatformRect::new(self.states[index].prev_pos, self.platforms[index].half_size)
+        }
+
+        /// How far the platform moved in the last tick.
+        pub fn delta(&self, index: usize) -> vec2 {
+            self.states[index].pos - self.states[index].prev_pos
+        }
+
+        /// How far the platform moved in the last tick, in whole units.
+        ///
+        /// Positions are quantized every tick, moving them by this
+        /// keeps them in place relative to the platform.
+        pub fn carry(&self, index: usize) -> vec2 {
+            let state = &self.states[index];
+            vec2::new(
+                (round_to_int(state.pos.x) - round_to_int(state.prev_pos.x)) as f32,
+                (round_to_int(state.pos.y) - round_to_int(state.prev_pos.y)) as f32,
+            )
+        }
+
+        /// The platform at the position.
+        pub fn at(&self, x: i32, y: i32) -> Option<usize> {
+            (0..self.len()).find(|&index| self.rect(index).contains(x, y))
+        }
+
+        /// Whether a platform blocks a box, that moves away from `start`.
+        ///
+        /// Platforms that overlap the box at `start` don't block it.
+        pub fn blocks_box(&self, pos: &ivec2, size: &ivec2, start: Option<&ivec2>) -> bool {
+            (0..self.len()).any(|index| {
+                let rect = self.rect(index);
+                rect.overlaps_box(pos, size)
+                    && start.is_none_or(|start| !rect.overlaps_box(start, size))
+            })
+        }
+
+        /// The platform a box stood on, before the platform moved in the last tick.
+        ///
+        /// A box stands on a platform, if it's at most 5 units above it.
+        pub fn ridden_by(&self, pos: &vec2, size: &ivec2) -> Option<usize> {
+            let pos = ivec2::new(round_to_int(pos.x), round_to_int(pos.y));
+            let half = *size / 2;
+            let feet_y = pos.y + half.y + 5;
+            (0..self.len()).find(|&index| {
+                let rect = self.prev_rect(index);
+                !rect.overlaps_box(&pos, size)
+                    && (rect.contains(pos.x - half.x, feet_y)
+                        || rect.contains(pos.x + half.x, feet_y))
+            })
+        }
+
+        /// The platform a hook grabbed, before the platform moved in the last tick.
+        pub fn hooked_at(&self, hook_pos: &vec2) -> Option<usize> {
+            let (x, y) = (round_to_int(hook_pos.x), round_to_int(hook_pos.y));
+            (0..self.len()).find(|&index| self.prev_rect(index).contains(x, y))
+        }
+
+        /// The shortest push in the direction the platform moved,
+        /// that moves a box out of the platform.
+        pub fn push_out(&self, index: usize, pos: &vec2, size: &ivec2) -> vec2 {
+            let rect = self.rect(index);
+            let delta = self.delta(index);
+            let half = vec2::new((size.x / 2) as f32, (size.y / 2) as f32);
+            let push = |delta: f32, pos: f32, half: f32, min: f32, max: f32| {
+                if delta > 0.0 {
+                    Some((max + half).ceil() - pos)
+                } else if delta < 0.0 {
+                    Some((min - 1.0 - half).floor() - pos)
+                } else {
+                    None
+                }
+            };
+            match (
+                push(delta.x, pos.x, half.x, rect.min.x, rect.max.x),
+                push(delta.y, pos.y, half.y, rect.min.y, rect.max.y),
+            ) {
+                (Some(x), Some(y)) if x.abs() < y.abs() => vec2::new(x, 0.0),
+                (_, Some(y)) => vec2::new(0.0, y),
+                (Some(x), None) => vec2::new(x, 0.0),
+                (None, None) => vec2::default(),
+            }
+        }
+    }
+}
@@ -0,0 +1,169 @@
+/// ddrace like practice mode of a stage
+pub mod practice {
+    use game_interface::types::{
+        id_types::PlayerId, render::character::CharacterDebuff, weapons::WeaponType,
+    };
+    use hiarc::Hiarc;
+    use math::math::vector::vec2;
+    use rustc_hash::{FxHashMap, FxHashSet};
+
+    use crate::{
+        entities::character::{
+            character::{BuffProps, Character, CharacterPhasedState},
+            hook::character_hook::Hook,
+        },
+        weapons::definitions::weapon_def::Weapon,
+    };
+
+    /// The saved state of a character, that can be restored in practice mode.
+    #[derive(Debug, Hiarc, Clone)]
+    pub struct PracticeCheckpoint {
+        pub pos: vec2,
+        pub vel: vec2,
+        pub active_weapon: WeaponType,
+        pub weapons: Vec<(WeaponType, Weapon)>,
+        pub freeze: Option<BuffProps>,
+    }
+
+    impl PracticeCheckpoint {
+        /// Returns `None` if the character is not alive.
+        pub fn save(character: &Character) -> Option<Self> {
+            if character.phased.is_phased() {
+                return None;
+            }
+            Some(Self {
+                pos: *character.pos.pos(),
+                vel: character.core.core.vel,
+                active_weapon: character.core.active_weapon,
+                weapons: character
+                    .reusable_core
+                    .weapons
+                    .iter()
+                    .map(|(ty, weapon)| (*ty, *weapon))
+                    .collect(),
+                freeze: character
+                    .reusable_core
+                    .debuffs
+                    .get(&CharacterDebuff::Freeze)
+                    .copied(),
+            })
+        }
+
+        /// Restores the character to this checkpoint.
+        ///
+        /// Returns `false` if the character is not alive.
+        pub fn load(&self, character: &mut Character) -> bool {
+            if character.phased.is_phased() {
+                return false;
+            }
+            character.pos.move_pos(self.pos);
+            character.core.core.vel = self.vel;
+            if let CharacterPhasedState::Normal(normal) = &mut character.phased {
+                normal.hook.set(Hook::None, None);
+            }
+
+            let weapons = &mut character.reusable_core.weapons;
+            weapons.clear();
+            weapons.extend(self.weapons.iter().copied());
+            character.core.active_weapon = self.active_weapon;
+            character.core.queued_weapon = None;
+
+            let debuffs = &mut character.reusable_core.debuffs;
+            match self.freeze {
+                Some(freeze) => {
+                    debuffs.insert(CharacterDebuff::Freeze, freeze);
+                }
+                None => {
+                    debuffs.remove(&CharacterDebuff::Freeze);
+                }
+            }
+            true
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PracticeVote {
+        /// More players of the stage have to vote.
+        Voted {
+            votes: usize,
+            needed: usize,
+        },
+        Enabled,
+        AlreadyEnabled,
+    }
+
+    /// Once all players of a stage voted for it, practice is enabled
+    /// and stays enabled until the next round of the stage.
+    ///
+    /// Rounds played in practice don't count for the stats of the players.
+    #[derive(Debug, Hiarc, Default)]
+    pub struct StagePractice {
+        enabled: bool,
+        votes: FxHashSet<PlayerId>,
+        checkpoints: FxHashMap<PlayerId, PracticeCheckpoint>,
+    }
+
+    impl StagePractice {
+        pub fn is_enabled(&self) -> bool {
+            self.enabled
+        }
+
+        /// Only for states that are restored from a snapshot,
+        /// which don't know the votes anyway.
+        pub(crate) fn set_enabled(&mut self, enabled: bool) {
+            self.enabled = enabled;
+        }
+
+        /// `players` are all players of the stage, that have to agree.
+        pub fn vote<'a>(
+            &mut self,
+            player_id: &PlayerId,
+            players: impl Iterator<Item = &'a PlayerId>,
+        ) -> PracticeVote {
+            if self.enabled {
+                return PracticeVote::AlreadyEnabled;
+            }
+            self.votes.insert(*player_id);
+            let players: FxHashSet<_> = players.collect();
+            self.votes.retain(|id| players.contains(id));
+            if self.votes.len() >= players.len() {
+                self.enabled = true;
+                self.votes.clear();
+                PracticeVote::Enabled
+            } else {
+                PracticeVote::Voted {
+                    votes: self.votes.len(),
+                    needed: players.len(),
+                }
+            }
+        }
+
+        /// Returns `false` if practice is disabled or the character is not alive.
+        pub fn save_pos(&mut self, player_id: &PlayerId, character: &Character) -> bool {
+            if !self.enabled {
+                return false;
+            }
+            let Some(checkpoint) = PracticeCheckpoint::save(character) else {
+                return false;
+            };
+            self.checkpoints.insert(*player_id, checkpoint);
+            true
+        }
+
+        /// Returns `false` if practice is disabled, there is no checkpoint
+        /// or the character is not alive.
+        pub fn load_pos(&self, player_id: &PlayerId, character: &mut Character) -> bool {
+            self.enabled
+                && self
+                    .checkpoints
+                    .get(player_id)
+                    .is_some_and(|checkpoint| checkpoint.load(character))
+        }
+
+        /// Forgets the votes & checkpoints of players that left the stage.
+        pub fn retain_players(&mut self, in_stage: impl Fn(&PlayerId) -> bool) {
+            self.votes.retain(|id| in_stage(id));
+            self.checkpoints.retain(|id, _| in_stage(id));
+        }
+    }
+}
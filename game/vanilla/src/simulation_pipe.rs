@@ -4,8 +4,9 @@ pub mod simulation_pipe {
 
     use base::linked_hash_map_view::FxLinkedHashMap;
     use game_interface::events::{
-        EventIdGenerator, GameWorldEffectEvent, GameWorldEntityEffectEvent,
-        GameWorldEntitySoundEvent, GameWorldEvent, GameWorldEvents, GameWorldSoundEvent,
+        EventIdGenerator, GameCustomEventPayload, GameWorldCustomEvent, GameWorldEffectEvent,
+        GameWorldEntityEffectEvent, GameWorldEntitySoundEvent, GameWorldEvent, GameWorldEvents,
+        GameWorldSoundEvent,
     };
     use game_interface::pooling::GamePooling;
     use game_interface::types::id_types::{
@@ -21,7 +22,7 @@ pub mod simulation_pipe {
     use crate::entities::character::pos::character_pos::{
         CharacterPos, CharacterPositionPlayfield,
     };
-    use crate::entities::flag::flag::Flags;
+    use crate::entities::flag::flag::{FlagOptions, Flags};
     use crate::events::events::{
         CharacterTickEvent, FlagEvent, LaserEvent, PickupEvent, ProjectileEvent,
     };
@@ -63,6 +64,7 @@ pub mod simulation_pipe {
                 ev,
                 owner_id,
                 pos: pos.map(|pos| pos / 32.0),
+                count: 1,
             }));
         }
 
@@ -79,6 +81,13 @@ pub mod simulation_pipe {
             }));
         }
 
+        /// Pushes an event of a game mod, that only its render mod interprets.
+        /// Vanilla never emits these.
+        pub fn push_custom(&mut self, id: u32, payload: GameCustomEventPayload) {
+            self.evs
+                .push(GameWorldEvent::Custom(GameWorldCustomEvent { id, payload }));
+        }
+
         pub fn take(&mut self) -> Vec<GameWorldEvent> {
             std::mem::take(&mut self.evs)
         }
@@ -566,13 +575,20 @@ pub mod simulation_pipe {
     }
 
     pub struct SimulationPipePickup<'a> {
+        pub collision: &'a Collision,
+
         pub characters: SimulationPipeOwnerlessCharacters<'a>,
         pub field: &'a CharacterPositionPlayfield,
     }
 
     impl<'a> SimulationPipePickup<'a> {
-        pub fn new(characters: &'a mut Characters, field: &'a CharacterPositionPlayfield) -> Self {
+        pub fn new(
+            collision: &'a Collision,
+            characters: &'a mut Characters,
+            field: &'a CharacterPositionPlayfield,
+        ) -> Self {
             Self {
+                collision,
                 characters: SimulationPipeOwnerlessCharacters { characters },
                 field,
             }
@@ -586,6 +602,7 @@ pub mod simulation_pipe {
         pub field: &'a CharacterPositionPlayfield,
 
         pub other_team_flags: &'a Flags,
+        pub options: &'a FlagOptions,
 
         pub is_prediction: bool,
     }
@@ -596,6 +613,7 @@ pub mod simulation_pipe {
             characters: &'a mut Characters,
             field: &'a CharacterPositionPlayfield,
             other_team_flags: &'a Flags,
+            options: &'a FlagOptions,
             is_prediction: bool,
         ) -> Self {
             Self {
@@ -604,6 +622,7 @@ pub mod simulation_pipe {
                 field,
                 is_prediction,
                 other_team_flags,
+                options,
             }
         }
     }
@@ -1,5 +1,5 @@
 pub mod snapshot {
-    use std::{num::NonZeroU16, rc::Rc};
+    use std::{cell::RefCell, num::NonZeroU16, rc::Rc};
 
     use crate::{
         entities::character::character::CharacterSpectateMode, reusable::CloneWithCopyableElements,
@@ -25,8 +25,11 @@ pub mod snapshot {
         },
     };
     use hiarc::{Hiarc, hi_closure};
-    use math::math::vector::{ubvec4, vec2};
-    use rustc_hash::FxHashSet;
+    use math::math::{
+        distance_squared,
+        vector::{ubvec4, vec2},
+    };
+    use rustc_hash::{FxHashMap, FxHashSet};
 
     use crate::{
         collision::collision::Tunings,
@@ -51,6 +54,7 @@ pub mod snapshot {
         },
         game_objects::game_objects::GameObjectDefinitions,
         match_state::match_state::Match,
+        platforms::platforms::PlatformState,
         simulation_pipe::simulation_pipe::GamePendingEvents,
         spawns::GameSpawns,
         stage::stage::Stages,
@@ -75,6 +79,78 @@ pub mod snapshot {
         Hotreload,
     }
 
+    /// The area around a camera that a client can see.
+    #[derive(Debug, Clone, Copy)]
+    pub struct SnapshotView {
+        pub stage_id: StageId,
+        pub center: vec2,
+        /// Half of the width & height of the view.
+        pub half_size: vec2,
+    }
+
+    /// Only entities close to the views are snapped.
+    #[derive(Debug, Clone, Copy)]
+    pub struct SnapshotInterestRadius {
+        pub radius: f32,
+        /// Entities that were snapped before stay snapped,
+        /// until they are this much further away than `radius`.
+        pub hysteresis: f32,
+    }
+
+    /// Filters the entities of a snapshot for a single client.
+    ///
+    /// The characters of the client are always snapped.
+    #[derive(Debug, Default)]
+    pub struct SnapshotViewFilter {
+        pub views: Vec<SnapshotView>,
+        pub always_snapped: FxHashSet<CharacterId>,
+        /// Entities outside of all views are not snapped.
+        pub cull_to_views: bool,
+        pub interest: Option<SnapshotInterestRadius>,
+    }
+
+    impl SnapshotViewFilter {
+        /// `was_snapped` is `true` if the entity was part
+        /// of the previous snapshot of the client.
+        pub fn snaps(&self, stage_id: &StageId, pos: &vec2, was_snapped: bool) -> bool {
+            let mut views = self.views.iter().filter(|view| view.stage_id == *stage_id);
+            let in_view = !self.cull_to_views
+                || views.clone().any(|view| {
+                    (pos.x - view.center.x).abs() <= view.half_size.x
+                        && (pos.y - view.center.y).abs() <= view.half_size.y
+                });
+            in_view
+                && self.interest.is_none_or(|interest| {
+                    let radius = if was_snapped {
+                        interest.radius + interest.hysteresis
+                    } else {
+                        interest.radius
+                    };
+                    views.any(|view| distance_squared(&view.center, pos) <= radius * radius)
+                })
+        }
+
+        pub fn snaps_character(
+            &self,
+            stage_id: &StageId,
+            id: &CharacterId,
+            pos: &vec2,
+            was_snapped: bool,
+        ) -> bool {
+            self.always_snapped.contains(id) || self.snaps(stage_id, pos, was_snapped)
+        }
+    }
+
+    /// The entities that were snapped to a client, used for the
+    /// hysteresis of the interest management.
+    #[derive(Debug, Default)]
+    pub struct SnapshotInterest {
+        characters: FxHashSet<CharacterId>,
+        projectiles: FxHashSet<ProjectileId>,
+        lasers: FxHashSet<LaserId>,
+        pickups: FxHashSet<PickupId>,
+    }
+
     #[derive(Debug, Serialize, Deserialize)]
     pub enum SnapshotCharacterPlayerTy {
         None,
@@ -240,6 +316,7 @@ pub mod snapshot {
         pub game_el_id: StageId,
         pub stage_name: PoolNetworkString<MAX_TEAM_NAME_LEN>,
         pub stage_color: ubvec4,
+        pub practice: bool,
     }
 
     #[derive(Serialize, Deserialize)]
@@ -310,6 +387,13 @@ pub mod snapshot {
         }
     }
 
+    /// The moving platforms of the map.
+    #[derive(Debug, Default, Serialize, Deserialize)]
+    pub struct SnapshotPlatforms {
+        pub anim_ticks: u64,
+        pub platforms: Vec<PlatformState>,
+    }
+
     #[derive(Debug, Serialize, Deserialize)]
     pub struct Snapshot {
         pub stages: PoolFxLinkedHashMap<StageId, SnapshotStage>,
@@ -322,6 +406,8 @@ pub mod snapshot {
         pub voted_player: Option<PlayerId>,
 
         pub global_tune_zone: Tunings,
+
+        pub platforms: SnapshotPlatforms,
     }
 
     impl Snapshot {
@@ -330,6 +416,7 @@ pub mod snapshot {
             id_generator_id: GameEntityId,
             voted_player: Option<PlayerId>,
             global_tune_zone: Tunings,
+            platforms: SnapshotPlatforms,
         ) -> Self {
             Self {
                 stages: pool.stages_pool.new(),
@@ -338,6 +425,7 @@ pub mod snapshot {
                 id_generator_id,
                 voted_player,
                 global_tune_zone,
+                platforms,
             }
         }
     }
@@ -353,6 +441,9 @@ pub mod snapshot {
         // pools
         pub(crate) snapshot_pool: SnapshotPool,
         world_pool: SnapshotWorldPool,
+
+        /// The entities of the last snapshot of every client.
+        interests: RefCell<FxHashMap<PlayerId, SnapshotInterest>>,
     }
 
     impl SnapshotManager {
@@ -363,17 +454,47 @@ pub mod snapshot {
                     options.hint_max_local_players.unwrap_or(4),
                 ),
                 world_pool: SnapshotWorldPool::new(options.hint_max_local_players.unwrap_or(64)),
+
+                interests: Default::default(),
             }
         }
 
+        /// Forgets the snapped entities of a client,
+        /// e.g. because the player left.
+        pub fn remove_interest(&self, id: &PlayerId) {
+            self.interests.borrow_mut().remove(id);
+        }
+
+        /// If `filter` is `Some`, only entities that pass the filter are snapped.
+        /// Flags are always snapped, since they are part of the standings.
+        ///
+        /// `interest` contains the entities of the previous snapshot of the client
+        /// and is replaced with the entities of this snapshot.
         pub(crate) fn build_stages(
             &self,
             stages: &mut PoolFxLinkedHashMap<StageId, SnapshotStage>,
             game: &GameState,
+            filter: Option<&SnapshotViewFilter>,
+            interest: &mut SnapshotInterest,
         ) {
-            game.game.stages.values().for_each(|stage| {
+            let prev_interest = std::mem::take(interest);
+            game.game.stages.iter().for_each(|(stage_id, stage)| {
+                let snaps = |pos: &vec2, was_snapped: bool| {
+                    filter.is_none_or(|f| f.snaps(stage_id, pos, was_snapped))
+                };
                 let mut characters = self.world_pool.characters_pool.new();
                 stage.world.characters.iter().for_each(|(id, char)| {
+                    if filter.is_some_and(|f| {
+                        !f.snaps_character(
+                            stage_id,
+                            id,
+                            char.pos.pos(),
+                            prev_interest.characters.contains(id),
+                        )
+                    }) {
+                        return;
+                    }
+                    interest.characters.insert(*id);
                     let mode_to_snap_mode = |s: &CharacterSpectateMode| match s {
                         &CharacterSpectateMode::Free(pos) => {
                             SnapshotCharacterSpectateMode::Free(pos)
@@ -428,6 +549,13 @@ pub mod snapshot {
                 });
                 let mut projectiles = self.world_pool.projectiles_pool.new();
                 stage.world.get_projectiles().iter().for_each(|(id, proj)| {
+                    if !snaps(
+                        &proj.projectile.core.pos,
+                        prev_interest.projectiles.contains(id),
+                    ) {
+                        return;
+                    }
+                    interest.projectiles.insert(*id);
                     let mut snap_proj = SnapshotProjectile {
                         core: proj.projectile.core,
                         reusable_core: self.world_pool.projectile_reusable_cores_pool.new(),
@@ -441,6 +569,13 @@ pub mod snapshot {
                 });
                 let mut lasers = self.world_pool.lasers_pool.new();
                 stage.world.get_lasers().iter().for_each(|(id, laser)| {
+                    let was_snapped = prev_interest.lasers.contains(id);
+                    if !snaps(&laser.laser.core.pos, was_snapped)
+                        && !snaps(&laser.laser.core.from, was_snapped)
+                    {
+                        return;
+                    }
+                    interest.lasers.insert(*id);
                     let mut snap_laser = SnapshotLaser {
                         core: laser.laser.core,
                         reusable_core: self.world_pool.laser_reusable_cores_pool.new(),
@@ -454,6 +589,10 @@ pub mod snapshot {
                 });
                 let mut pickups = self.world_pool.pickups_pool.new();
                 stage.world.get_pickups().iter().for_each(|(id, pickup)| {
+                    if !snaps(&pickup.core.pos, prev_interest.pickups.contains(id)) {
+                        return;
+                    }
+                    interest.pickups.insert(*id);
                     let mut snap_pickup = SnapshotPickup {
                         core: pickup.core,
                         reusable_core: self.world_pool.pickup_reusable_cores_pool.new(),
@@ -550,6 +689,7 @@ pub mod snapshot {
                             name
                         },
                         stage_color: stage.stage_color,
+                        practice: stage.practice.is_enabled(),
                     },
                 );
             });
@@ -561,11 +701,19 @@ pub mod snapshot {
                 game.id_generator.peek_next_id(),
                 game.game.voted_player,
                 game.collision.tune_zones[0],
+                SnapshotPlatforms {
+                    anim_ticks: game.collision.platforms.anim_ticks,
+                    platforms: game.collision.platforms.states.clone(),
+                },
             );
+            let mut filter = None;
+            let mut interest_of = None;
             if let SnapshotFor::Client(client) = snap_for {
                 match client {
                     SnapshotClientInfo::ForPlayerIds(ids)
                     | SnapshotClientInfo::OtherStagesForPlayerIds(ids) => {
+                        filter = game.snapshot_view_filter(ids.iter());
+                        interest_of = ids.iter().next().copied();
                         res.local_players.reserve(ids.len());
                         ids.iter().for_each(|id| {
                             if let Some(p) = game.game.players.player(id).and_then(|p| {
@@ -592,7 +740,16 @@ pub mod snapshot {
                     }
                 }
             }
-            self.build_stages(&mut res.stages, game);
+            // the interest is remembered by the first player of the client
+            let mut interest = interest_of
+                .and_then(|id| self.interests.borrow_mut().remove(&id))
+                .unwrap_or_default();
+            self.build_stages(&mut res.stages, game, filter.as_ref(), &mut interest);
+            if let Some(id) =
+                interest_of.filter(|_| filter.as_ref().is_some_and(|f| f.interest.is_some()))
+            {
+                self.interests.borrow_mut().insert(id, interest);
+            }
 
             let mut spectator_players = game.spectator_player_clone_pool.new();
             game.game
@@ -711,6 +868,7 @@ pub mod snapshot {
 
                 let match_manager = &mut state_stage.match_manager;
                 match_manager.game_match = snap_stage.match_manager.game_match;
+                state_stage.practice.set_enabled(snap_stage.practice);
 
                 // go through all characters of the stage, add missing ones
                 snap_stage.world.characters.values().for_each(|char| {
@@ -1070,6 +1228,10 @@ pub mod snapshot {
             write_game_state.game.voted_player = snapshot.voted_player;
 
             write_game_state.collision.tune_zones[0] = snapshot.global_tune_zone;
+            write_game_state
+                .collision
+                .platforms
+                .restore(snapshot.platforms.anim_ticks, &snapshot.platforms.platforms);
 
             snapshot.local_players
         }
@@ -0,0 +1,107 @@
+/// merging of identical sound events & a limit of sounds per tick
+pub mod sound_batching {
+    use std::borrow::Cow;
+
+    use game_interface::{
+        events::{GameWorldEvent, GameWorldSoundEvent},
+        types::id_types::CharacterId,
+    };
+    use math::math::distance;
+
+    /// Identical sounds in the same tick that are closer than this (in tiles)
+    /// are merged into one.
+    pub const SOUND_MERGE_RADIUS: f32 = 2.0;
+    /// The most world sounds that are sent per tick, after merging.
+    pub const MAX_WORLD_SOUNDS_PER_TICK: usize = 16;
+
+    /// Sounds of a higher priority class (lower value) are never dropped
+    /// in favor of sounds of a lower class.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum SoundPriority {
+        /// Sounds of the characters the client plays with or spectates,
+        /// e.g. the own pain sound. Always sent.
+        Player,
+        /// Sounds without position, e.g. a flag capture. Always sent.
+        Global,
+        /// All other positional sounds,
+        /// at most [`MAX_WORLD_SOUNDS_PER_TICK`] are sent per tick.
+        World,
+    }
+
+    impl SoundPriority {
+        pub fn of(ev: &GameWorldSoundEvent, is_player: impl Fn(&CharacterId) -> bool) -> Self {
+            if ev.owner_id.as_ref().is_some_and(is_player) {
+                Self::Player
+            } else if ev.pos.is_none() {
+                Self::Global
+            } else {
+                Self::World
+            }
+        }
+    }
+
+    fn can_merge(merged: &GameWorldSoundEvent, ev: &GameWorldSoundEvent) -> bool {
+        merged.ev == ev.ev
+            && merged.owner_id == ev.owner_id
+            && match (&merged.pos, &ev.pos) {
+                (Some(merged_pos), Some(pos)) => distance(merged_pos, pos) <= SOUND_MERGE_RADIUS,
+                (None, None) => true,
+                _ => false,
+            }
+    }
+
+    /// Merges identical sounds of a single tick & drops the world sounds
+    /// that exceed [`MAX_WORLD_SOUNDS_PER_TICK`].
+    ///
+    /// `is_player` decides if a sound's owner is relevant for the client.
+    /// All other events are kept as they are & the order is preserved.
+    pub fn batch_sound_events<'a>(
+        evs: &'a [GameWorldEvent],
+        is_player: impl Fn(&CharacterId) -> bool,
+    ) -> Vec<Cow<'a, GameWorldEvent>> {
+        let mut res: Vec<Cow<'a, GameWorldEvent>> = Vec::with_capacity(evs.len());
+        // indices of the sounds in `res`
+        let mut sounds: Vec<usize> = Vec::new();
+        for ev in evs {
+            let GameWorldEvent::Sound(ev) = ev else {
+                res.push(Cow::Borrowed(ev));
+                continue;
+            };
+            let merged = sounds
+                .iter()
+                .copied()
+                .find(|&index| match res[index].as_ref() {
+                    GameWorldEvent::Sound(merged) => can_merge(merged, ev),
+                    _ => false,
+                });
+            match merged.map(|index| res[index].to_mut()) {
+                Some(GameWorldEvent::Sound(merged)) => {
+                    // the merged sound is played at the center of all sounds
+                    if let (Some(merged_pos), Some(pos)) = (&mut merged.pos, &ev.pos) {
+                        let merged_count = merged.count.max(1) as f32;
+                        let count = ev.count.max(1) as f32;
+                        *merged_pos =
+                            (*merged_pos * merged_count + *pos * count) / (merged_count + count);
+                    }
+                    merged.count = merged.count.max(1) + ev.count.max(1);
+                }
+                _ => {
+                    sounds.push(res.len());
+                    res.push(Cow::Owned(GameWorldEvent::Sound(*ev)));
+                }
+            }
+        }
+
+        let mut world_sounds = 0;
+        res.retain(|ev| match ev.as_ref() {
+            GameWorldEvent::Sound(ev)
+                if SoundPriority::of(ev, &is_player) == SoundPriority::World =>
+            {
+                world_sounds += 1;
+                world_sounds <= MAX_WORLD_SOUNDS_PER_TICK
+            }
+            _ => true,
+        });
+        res
+    }
+}
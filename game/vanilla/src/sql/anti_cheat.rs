@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use ddnet_accounts_types::account_id::AccountId;
+use game_database::{
+    StatementArgs,
+    statement::{Statement, StatementBuilder},
+    traits::{DbInterface, DbKind},
+};
+
+use crate::anti_cheat::anti_cheat::InputDetections;
+
+#[derive(Debug, StatementArgs)]
+struct StatementArg {
+    account_id: AccountId,
+    aim_snaps: i64,
+    input_frequencies: i64,
+    repeating_patterns: i64,
+}
+
+type StatementResult = ();
+
+/// Flags an account that reached the anti-cheat threshold,
+/// without telling the player.
+#[derive(Clone)]
+pub struct AntiCheatFlag(Arc<Statement<StatementArg, StatementResult>>);
+
+impl AntiCheatFlag {
+    pub async fn new(db: Arc<dyn DbInterface>, account_db: Option<DbKind>) -> anyhow::Result<Self> {
+        let kind = account_db.ok_or_else(|| anyhow!("No account db specified"))?;
+        let builder = StatementBuilder::<_, StatementArg, StatementResult>::new(
+            kind,
+            if matches!(kind, DbKind::MySql(_)) {
+                include_str!("mysql/anti_cheat/add_anti_cheat_flag.sql")
+            } else {
+                include_str!("sqlite/anti_cheat/add_anti_cheat_flag.sql")
+            },
+            |arg| {
+                vec![
+                    arg.account_id,
+                    arg.aim_snaps,
+                    arg.input_frequencies,
+                    arg.repeating_patterns,
+                ]
+            },
+        );
+
+        let stm = Arc::new(Statement::new(db.clone(), builder).await?);
+
+        Ok(Self(stm))
+    }
+
+    pub async fn execute(
+        &self,
+        account_id: AccountId,
+        detections: InputDetections,
+    ) -> anyhow::Result<u64> {
+        self.0
+            .execute(StatementArg {
+                account_id,
+                aim_snaps: detections.contains(InputDetections::AIM_SNAP) as i64,
+                input_frequencies: detections.contains(InputDetections::INPUT_FREQUENCY) as i64,
+                repeating_patterns: detections.contains(InputDetections::REPEATING_PATTERN) as i64,
+            })
+            .await
+    }
+}
@@ -0,0 +1,152 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::anyhow;
+use game_database::{
+    StatementArgs, StatementResult,
+    statement::{Statement, StatementBuilder},
+    traits::{DbInterface, DbKind},
+};
+
+use crate::bans::bans::{BanKind, BanTarget};
+
+#[derive(Debug, StatementArgs)]
+struct AddArg {
+    kind: i64,
+    target: String,
+    reason: String,
+    /// `0` for permanent entries
+    duration_secs: i64,
+}
+
+#[derive(Debug, StatementArgs)]
+struct RemoveArg {
+    kind: i64,
+    target: String,
+}
+
+#[derive(Debug, StatementArgs)]
+struct LoadArg {}
+
+#[derive(Debug, StatementResult)]
+struct LoadResult {
+    kind: i64,
+    target: String,
+    reason: String,
+    /// `0` for permanent entries
+    remaining_secs: i64,
+}
+
+/// A ban or mute loaded from the database.
+#[derive(Debug)]
+pub struct LoadedBan {
+    pub kind: BanKind,
+    pub target: BanTarget,
+    pub reason: String,
+    /// `None` for permanent entries.
+    pub remaining: Option<Duration>,
+}
+
+/// Persists the bans & mutes, expired entries are never loaded.
+#[derive(Clone)]
+pub struct Bans {
+    add: Arc<Statement<AddArg, ()>>,
+    remove: Arc<Statement<RemoveArg, ()>>,
+    load: Arc<Statement<LoadArg, LoadResult>>,
+}
+
+impl Bans {
+    pub async fn new(db: Arc<dyn DbInterface>, account_db: Option<DbKind>) -> anyhow::Result<Self> {
+        let kind = account_db.ok_or_else(|| anyhow!("No account db specified"))?;
+        let is_mysql = matches!(kind, DbKind::MySql(_));
+
+        let builder = StatementBuilder::<_, AddArg, ()>::new(
+            kind,
+            if is_mysql {
+                include_str!("mysql/bans/add_ban.sql")
+            } else {
+                include_str!("sqlite/bans/add_ban.sql")
+            },
+            |arg| {
+                vec![
+                    arg.kind,
+                    arg.target,
+                    arg.reason,
+                    arg.duration_secs,
+                    arg.duration_secs,
+                ]
+            },
+        );
+        let add = Arc::new(Statement::new(db.clone(), builder).await?);
+
+        let builder = StatementBuilder::<_, RemoveArg, ()>::new(
+            kind,
+            if is_mysql {
+                include_str!("mysql/bans/remove_ban.sql")
+            } else {
+                include_str!("sqlite/bans/remove_ban.sql")
+            },
+            |arg| vec![arg.kind, arg.target],
+        );
+        let remove = Arc::new(Statement::new(db.clone(), builder).await?);
+
+        let builder = StatementBuilder::<_, LoadArg, LoadResult>::new(
+            kind,
+            if is_mysql {
+                include_str!("mysql/bans/load_bans.sql")
+            } else {
+                include_str!("sqlite/bans/load_bans.sql")
+            },
+            |_| vec![],
+        );
+        let load = Arc::new(Statement::new(db, builder).await?);
+
+        Ok(Self { add, remove, load })
+    }
+
+    /// `duration` is `None` for permanent entries.
+    pub async fn add(
+        &self,
+        kind: BanKind,
+        target: BanTarget,
+        duration: Option<Duration>,
+        reason: String,
+    ) -> anyhow::Result<u64> {
+        self.add
+            .execute(AddArg {
+                kind: kind.to_db(),
+                target: target.to_string(),
+                reason,
+                duration_secs: duration
+                    .map(|duration| duration.as_secs().max(1) as i64)
+                    .unwrap_or_default(),
+            })
+            .await
+    }
+
+    pub async fn remove(&self, kind: BanKind, target: BanTarget) -> anyhow::Result<u64> {
+        self.remove
+            .execute(RemoveArg {
+                kind: kind.to_db(),
+                target: target.to_string(),
+            })
+            .await
+    }
+
+    /// All entries that did not expire yet.
+    pub async fn load(&self) -> anyhow::Result<Vec<LoadedBan>> {
+        self.load
+            .fetch_all(LoadArg {})
+            .await?
+            .into_iter()
+            .map(|ban| {
+                Ok(LoadedBan {
+                    kind: BanKind::from_db(ban.kind)?,
+                    target: ban.target.parse()?,
+                    reason: ban.reason,
+                    remaining: (ban.remaining_secs != 0)
+                        .then(|| Duration::from_secs(ban.remaining_secs.max(1) as u64)),
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,14 @@
+SELECT
+    (
+        SELECT
+            COUNT(*) + 1
+        FROM
+            user_round_stats AS better
+        WHERE
+            better.kills > stats.kills
+    ) AS player_rank,
+    stats.kills AS points
+FROM
+    user_round_stats AS stats
+WHERE
+    stats.user_id = ?;
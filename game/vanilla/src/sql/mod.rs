@@ -1,3 +1,7 @@
 pub mod account_created;
 pub mod account_info;
+pub mod anti_cheat;
+pub mod bans;
+pub mod round_stats;
 pub mod save;
+pub mod server_rank;
@@ -0,0 +1,14 @@
+INSERT INTO user_anti_cheat_flags (
+    user_id,
+    flags,
+    aim_snaps,
+    input_frequencies,
+    repeating_patterns
+)
+VALUES
+    (?, 1, ?, ?, ?) AS new_flag ON DUPLICATE KEY
+UPDATE
+    flags = user_anti_cheat_flags.flags + 1,
+    aim_snaps = user_anti_cheat_flags.aim_snaps + new_flag.aim_snaps,
+    input_frequencies = user_anti_cheat_flags.input_frequencies + new_flag.input_frequencies,
+    repeating_patterns = user_anti_cheat_flags.repeating_patterns + new_flag.repeating_patterns;
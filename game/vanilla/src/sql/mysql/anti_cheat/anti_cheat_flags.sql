@@ -0,0 +1,10 @@
+CREATE TABLE user_anti_cheat_flags (
+    id BIGINT NOT NULL AUTO_INCREMENT,
+    user_id BIGINT NOT NULL,
+    flags BIGINT NOT NULL DEFAULT 0,
+    aim_snaps BIGINT NOT NULL DEFAULT 0,
+    input_frequencies BIGINT NOT NULL DEFAULT 0,
+    repeating_patterns BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY(id),
+    UNIQUE KEY(user_id)
+);
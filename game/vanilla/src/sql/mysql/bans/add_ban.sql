@@ -0,0 +1,11 @@
+INSERT INTO bans (
+    kind,
+    target,
+    reason,
+    expires
+)
+VALUES
+    (?, ?, ?, IF(? > 0, UNIX_TIMESTAMP() + ?, 0)) AS new_ban ON DUPLICATE KEY
+UPDATE
+    reason = new_ban.reason,
+    expires = new_ban.expires;
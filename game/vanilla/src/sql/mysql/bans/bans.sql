@@ -0,0 +1,9 @@
+CREATE TABLE bans (
+    id BIGINT NOT NULL AUTO_INCREMENT,
+    kind BIGINT NOT NULL,
+    target VARCHAR(64) NOT NULL,
+    reason VARCHAR(256) NOT NULL,
+    expires BIGINT NOT NULL,
+    PRIMARY KEY(id),
+    UNIQUE KEY(kind, target)
+);
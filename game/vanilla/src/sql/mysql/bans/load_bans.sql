@@ -0,0 +1,10 @@
+SELECT
+    kind,
+    target,
+    reason,
+    IF(expires = 0, 0, expires - UNIX_TIMESTAMP()) AS remaining_secs
+FROM
+    bans
+WHERE
+    expires = 0
+    OR expires > UNIX_TIMESTAMP();
@@ -0,0 +1,5 @@
+DELETE FROM
+    bans
+WHERE
+    kind = ?
+    AND target = ?;
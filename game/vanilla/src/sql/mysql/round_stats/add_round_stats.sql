@@ -0,0 +1,31 @@
+INSERT INTO user_round_stats (
+    user_id,
+    rounds,
+    kills,
+    deaths,
+    shots,
+    hits,
+    damage_dealt,
+    damage_taken,
+    flag_carry_millis,
+    flag_captures,
+    flag_returns,
+    longest_killstreak
+)
+VALUES
+    (?, 1, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) AS new_stats ON DUPLICATE KEY
+UPDATE
+    rounds = user_round_stats.rounds + 1,
+    kills = user_round_stats.kills + new_stats.kills,
+    deaths = user_round_stats.deaths + new_stats.deaths,
+    shots = user_round_stats.shots + new_stats.shots,
+    hits = user_round_stats.hits + new_stats.hits,
+    damage_dealt = user_round_stats.damage_dealt + new_stats.damage_dealt,
+    damage_taken = user_round_stats.damage_taken + new_stats.damage_taken,
+    flag_carry_millis = user_round_stats.flag_carry_millis + new_stats.flag_carry_millis,
+    flag_captures = user_round_stats.flag_captures + new_stats.flag_captures,
+    flag_returns = user_round_stats.flag_returns + new_stats.flag_returns,
+    longest_killstreak = GREATEST(
+        user_round_stats.longest_killstreak,
+        new_stats.longest_killstreak
+    );
@@ -0,0 +1,17 @@
+CREATE TABLE user_round_stats (
+    id BIGINT NOT NULL AUTO_INCREMENT,
+    user_id BIGINT NOT NULL,
+    rounds BIGINT NOT NULL DEFAULT 0,
+    kills BIGINT NOT NULL DEFAULT 0,
+    deaths BIGINT NOT NULL DEFAULT 0,
+    shots BIGINT NOT NULL DEFAULT 0,
+    hits BIGINT NOT NULL DEFAULT 0,
+    damage_dealt BIGINT NOT NULL DEFAULT 0,
+    damage_taken BIGINT NOT NULL DEFAULT 0,
+    flag_carry_millis BIGINT NOT NULL DEFAULT 0,
+    flag_captures BIGINT NOT NULL DEFAULT 0,
+    flag_returns BIGINT NOT NULL DEFAULT 0,
+    longest_killstreak BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY(id),
+    UNIQUE KEY(user_id)
+);
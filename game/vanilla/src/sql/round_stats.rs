@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use ddnet_accounts_types::account_id::AccountId;
+use game_database::{
+    StatementArgs,
+    statement::{Statement, StatementBuilder},
+    traits::{DbInterface, DbKind},
+};
+use game_interface::events::GameWorldPlayerStats;
+
+#[derive(Debug, StatementArgs)]
+struct StatementArg {
+    account_id: AccountId,
+    kills: i64,
+    deaths: i64,
+    shots: i64,
+    hits: i64,
+    damage_dealt: i64,
+    damage_taken: i64,
+    flag_carry_millis: i64,
+    flag_captures: i64,
+    flag_returns: i64,
+    longest_killstreak: i64,
+}
+
+type StatementResult = ();
+
+/// Adds the statistics of a finished round to the
+/// statistics of an account.
+#[derive(Clone)]
+pub struct RoundStats(Arc<Statement<StatementArg, StatementResult>>);
+
+impl RoundStats {
+    pub async fn new(db: Arc<dyn DbInterface>, account_db: Option<DbKind>) -> anyhow::Result<Self> {
+        let kind = account_db.ok_or_else(|| anyhow!("No account db specified"))?;
+        let builder = StatementBuilder::<_, StatementArg, StatementResult>::new(
+            kind,
+            if matches!(kind, DbKind::MySql(_)) {
+                include_str!("mysql/round_stats/add_round_stats.sql")
+            } else {
+                include_str!("sqlite/round_stats/add_round_stats.sql")
+            },
+            |arg| {
+                vec![
+                    arg.account_id,
+                    arg.kills,
+                    arg.deaths,
+                    arg.shots,
+                    arg.hits,
+                    arg.damage_dealt,
+                    arg.damage_taken,
+                    arg.flag_carry_millis,
+                    arg.flag_captures,
+                    arg.flag_returns,
+                    arg.longest_killstreak,
+                ]
+            },
+        );
+
+        let stm = Arc::new(Statement::new(db.clone(), builder).await?);
+
+        Ok(Self(stm))
+    }
+
+    pub async fn execute(
+        &self,
+        account_id: AccountId,
+        stats: &GameWorldPlayerStats,
+    ) -> anyhow::Result<u64> {
+        self.0
+            .execute(StatementArg {
+                account_id,
+                kills: stats.kills as i64,
+                deaths: stats.deaths as i64,
+                shots: stats.weapons.iter().map(|w| w.shots as i64).sum(),
+                hits: stats.weapons.iter().map(|w| w.hits as i64).sum(),
+                damage_dealt: stats.damage_dealt as i64,
+                damage_taken: stats.damage_taken as i64,
+                flag_carry_millis: stats.flag_carry_time.as_millis() as i64,
+                flag_captures: stats.flag_captures as i64,
+                flag_returns: stats.flag_returns as i64,
+                longest_killstreak: stats.longest_killstreak as i64,
+            })
+            .await
+    }
+}
@@ -29,12 +29,103 @@ impl SetupSaves {
     }
 }
 
+#[derive(Clone)]
+pub struct SetupRoundStats {
+    stmts: HashMap<DbKind, Vec<SqlText>>,
+}
+
+impl SetupRoundStats {
+    pub async fn new(db: Arc<dyn DbInterface>) -> anyhow::Result<Self> {
+        let mut stmts: HashMap<_, Vec<_>> = Default::default();
+        let kinds = db.kinds();
+
+        if kinds.contains(&DbKind::MySql(DbKindExtra::Main)) {
+            stmts
+                .entry(DbKind::MySql(DbKindExtra::Main))
+                .or_default()
+                .push(include_str!("mysql/round_stats/round_stats.sql").into());
+        }
+        if kinds.contains(&DbKind::Sqlite(DbKindExtra::Main)) {
+            stmts
+                .entry(DbKind::Sqlite(DbKindExtra::Main))
+                .or_default()
+                .push(include_str!("sqlite/round_stats/round_stats.sql").into());
+        }
+
+        Ok(Self { stmts })
+    }
+}
+
+#[derive(Clone)]
+pub struct SetupAntiCheatFlags {
+    stmts: HashMap<DbKind, Vec<SqlText>>,
+}
+
+impl SetupAntiCheatFlags {
+    pub async fn new(db: Arc<dyn DbInterface>) -> anyhow::Result<Self> {
+        let mut stmts: HashMap<_, Vec<_>> = Default::default();
+        let kinds = db.kinds();
+
+        if kinds.contains(&DbKind::MySql(DbKindExtra::Main)) {
+            stmts
+                .entry(DbKind::MySql(DbKindExtra::Main))
+                .or_default()
+                .push(include_str!("mysql/anti_cheat/anti_cheat_flags.sql").into());
+        }
+        if kinds.contains(&DbKind::Sqlite(DbKindExtra::Main)) {
+            stmts
+                .entry(DbKind::Sqlite(DbKindExtra::Main))
+                .or_default()
+                .push(include_str!("sqlite/anti_cheat/anti_cheat_flags.sql").into());
+        }
+
+        Ok(Self { stmts })
+    }
+}
+
+#[derive(Clone)]
+pub struct SetupBans {
+    stmts: HashMap<DbKind, Vec<SqlText>>,
+}
+
+impl SetupBans {
+    pub async fn new(db: Arc<dyn DbInterface>) -> anyhow::Result<Self> {
+        let mut stmts: HashMap<_, Vec<_>> = Default::default();
+        let kinds = db.kinds();
+
+        if kinds.contains(&DbKind::MySql(DbKindExtra::Main)) {
+            stmts
+                .entry(DbKind::MySql(DbKindExtra::Main))
+                .or_default()
+                .push(include_str!("mysql/bans/bans.sql").into());
+        }
+        if kinds.contains(&DbKind::Sqlite(DbKindExtra::Main)) {
+            stmts
+                .entry(DbKind::Sqlite(DbKindExtra::Main))
+                .or_default()
+                .push(include_str!("sqlite/bans/bans.sql").into());
+        }
+
+        Ok(Self { stmts })
+    }
+}
+
 pub async fn setup(db: Arc<dyn DbInterface>) -> anyhow::Result<()> {
     let setup_saves = SetupSaves::new(db.clone()).await?;
+    let setup_round_stats = SetupRoundStats::new(db.clone()).await?;
+    let setup_anti_cheat_flags = SetupAntiCheatFlags::new(db.clone()).await?;
+    let setup_bans = SetupBans::new(db.clone()).await?;
 
     db.setup(
         "game-server-vanilla",
-        vec![(1, setup_saves.stmts)].into_iter().collect(),
+        vec![
+            (1, setup_saves.stmts),
+            (2, setup_round_stats.stmts),
+            (3, setup_anti_cheat_flags.stmts),
+            (4, setup_bans.stmts),
+        ]
+        .into_iter()
+        .collect(),
     )
     .await
 }
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use ddnet_accounts_types::account_id::AccountId;
+use game_database::{
+    StatementArgs, StatementResult,
+    statement::{Statement, StatementBuilder},
+    traits::{DbInterface, DbKind},
+};
+use game_interface::types::render::character::CharacterServerRank;
+
+#[derive(Debug, StatementArgs)]
+struct StatementArg {
+    account_id: AccountId,
+}
+
+#[derive(Debug, StatementResult)]
+struct RankResult {
+    player_rank: i64,
+    points: i64,
+}
+
+/// The rank of an account by the kills of all its rounds,
+/// see [`super::round_stats::RoundStats`].
+#[derive(Clone)]
+pub struct ServerRank(Arc<Statement<StatementArg, RankResult>>);
+
+impl ServerRank {
+    pub async fn new(db: Arc<dyn DbInterface>, account_db: Option<DbKind>) -> anyhow::Result<Self> {
+        let kind = account_db.ok_or_else(|| anyhow!("No account db specified"))?;
+        let builder = StatementBuilder::<_, StatementArg, RankResult>::new(
+            kind,
+            include_str!("generic/server_rank/server_rank.sql"),
+            |arg| vec![arg.account_id],
+        );
+
+        let stm = Arc::new(Statement::new(db.clone(), builder).await?);
+
+        Ok(Self(stm))
+    }
+
+    /// `None` if the account did not finish a round yet.
+    pub async fn fetch(
+        &self,
+        account_id: AccountId,
+    ) -> anyhow::Result<Option<CharacterServerRank>> {
+        Ok(self
+            .0
+            .fetch_optional(StatementArg { account_id })
+            .await?
+            .map(|res| CharacterServerRank {
+                rank: res.player_rank.max(1) as u64,
+                points: res.points,
+            }))
+    }
+}
@@ -0,0 +1,15 @@
+INSERT INTO user_anti_cheat_flags (
+    user_id,
+    flags,
+    aim_snaps,
+    input_frequencies,
+    repeating_patterns
+)
+VALUES
+    (?, 1, ?, ?, ?) ON CONFLICT(user_id) DO
+UPDATE
+SET
+    flags = user_anti_cheat_flags.flags + 1,
+    aim_snaps = user_anti_cheat_flags.aim_snaps + excluded.aim_snaps,
+    input_frequencies = user_anti_cheat_flags.input_frequencies + excluded.input_frequencies,
+    repeating_patterns = user_anti_cheat_flags.repeating_patterns + excluded.repeating_patterns;
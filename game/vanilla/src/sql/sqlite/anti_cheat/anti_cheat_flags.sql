@@ -0,0 +1,9 @@
+CREATE TABLE user_anti_cheat_flags (
+    id INTEGER AUTO_INCREMENT,
+    user_id INTEGER NOT NULL UNIQUE,
+    flags INTEGER NOT NULL DEFAULT 0,
+    aim_snaps INTEGER NOT NULL DEFAULT 0,
+    input_frequencies INTEGER NOT NULL DEFAULT 0,
+    repeating_patterns INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY(id)
+);
@@ -0,0 +1,20 @@
+INSERT INTO bans (
+    kind,
+    target,
+    reason,
+    expires
+)
+VALUES
+    (
+        ?,
+        ?,
+        ?,
+        CASE
+            WHEN ? > 0 THEN CAST(strftime('%s', 'now') AS INTEGER) + ?
+            ELSE 0
+        END
+    ) ON CONFLICT(kind, target) DO
+UPDATE
+SET
+    reason = excluded.reason,
+    expires = excluded.expires;
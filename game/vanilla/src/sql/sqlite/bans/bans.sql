@@ -0,0 +1,9 @@
+CREATE TABLE bans (
+    id INTEGER AUTO_INCREMENT,
+    kind INTEGER NOT NULL,
+    target VARCHAR(64) NOT NULL,
+    reason VARCHAR(256) NOT NULL,
+    expires INTEGER NOT NULL,
+    PRIMARY KEY(id),
+    UNIQUE(kind, target)
+);
@@ -0,0 +1,13 @@
+SELECT
+    kind,
+    target,
+    reason,
+    CASE
+        WHEN expires = 0 THEN 0
+        ELSE expires - CAST(strftime('%s', 'now') AS INTEGER)
+    END AS remaining_secs
+FROM
+    bans
+WHERE
+    expires = 0
+    OR expires > CAST(strftime('%s', 'now') AS INTEGER);
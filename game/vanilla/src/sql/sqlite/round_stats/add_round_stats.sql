@@ -0,0 +1,32 @@
+INSERT INTO user_round_stats (
+    user_id,
+    rounds,
+    kills,
+    deaths,
+    shots,
+    hits,
+    damage_dealt,
+    damage_taken,
+    flag_carry_millis,
+    flag_captures,
+    flag_returns,
+    longest_killstreak
+)
+VALUES
+    (?, 1, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) ON CONFLICT(user_id) DO
+UPDATE
+SET
+    rounds = user_round_stats.rounds + 1,
+    kills = user_round_stats.kills + excluded.kills,
+    deaths = user_round_stats.deaths + excluded.deaths,
+    shots = user_round_stats.shots + excluded.shots,
+    hits = user_round_stats.hits + excluded.hits,
+    damage_dealt = user_round_stats.damage_dealt + excluded.damage_dealt,
+    damage_taken = user_round_stats.damage_taken + excluded.damage_taken,
+    flag_carry_millis = user_round_stats.flag_carry_millis + excluded.flag_carry_millis,
+    flag_captures = user_round_stats.flag_captures + excluded.flag_captures,
+    flag_returns = user_round_stats.flag_returns + excluded.flag_returns,
+    longest_killstreak = MAX(
+        user_round_stats.longest_killstreak,
+        excluded.longest_killstreak
+    );
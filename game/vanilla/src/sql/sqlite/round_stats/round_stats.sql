@@ -0,0 +1,16 @@
+CREATE TABLE user_round_stats (
+    id INTEGER AUTO_INCREMENT,
+    user_id INTEGER NOT NULL UNIQUE,
+    rounds INTEGER NOT NULL DEFAULT 0,
+    kills INTEGER NOT NULL DEFAULT 0,
+    deaths INTEGER NOT NULL DEFAULT 0,
+    shots INTEGER NOT NULL DEFAULT 0,
+    hits INTEGER NOT NULL DEFAULT 0,
+    damage_dealt INTEGER NOT NULL DEFAULT 0,
+    damage_taken INTEGER NOT NULL DEFAULT 0,
+    flag_carry_millis INTEGER NOT NULL DEFAULT 0,
+    flag_captures INTEGER NOT NULL DEFAULT 0,
+    flag_returns INTEGER NOT NULL DEFAULT 0,
+    longest_killstreak INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY(id)
+);
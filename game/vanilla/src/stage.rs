@@ -14,6 +14,7 @@ pub mod stage {
         game_objects::game_objects::GameObjectDefinitions,
         match_manager::match_manager::MatchManager,
         match_state::match_state::MatchState,
+        practice::practice::StagePractice,
         simulation_pipe::simulation_pipe::{GameStagePendingEventsRaii, SimulationStageEvents},
         spawns::GameSpawns,
         types::types::GameOptions,
@@ -34,6 +35,7 @@ pub mod stage {
         pub match_manager: MatchManager,
         pub stage_name: NetworkString<MAX_TEAM_NAME_LEN>,
         pub stage_color: ubvec4,
+        pub practice: StagePractice,
 
         pub(crate) game_pending_events: GameStagePendingEventsRaii,
         pub(crate) simulation_events: SimulationStageEvents,
@@ -73,9 +75,10 @@ pub mod stage {
                     Default::default(),
                     spawn_default_entities,
                 ),
-                match_manager: MatchManager::new(game_options, &simulation_events),
+                match_manager: MatchManager::new(game_options, &simulation_events, true),
                 stage_name,
                 stage_color,
+                practice: Default::default(),
                 game_pending_events,
                 simulation_events,
 
@@ -90,12 +93,18 @@ pub mod stage {
                 .game_match
                 .tick(&self.match_manager.game_options, &self.world.scores);
 
-            if let MatchState::Running { .. } | MatchState::SuddenDeath { .. } =
-                self.match_manager.game_match.state
+            if let MatchState::Warmup { .. }
+            | MatchState::Running { .. }
+            | MatchState::SuddenDeath { .. } = self.match_manager.game_match.state
             {
+                self.world.respawn_disabled = self.match_manager.respawn_disabled(&self.world);
                 self.world.tick(pipe);
             }
-            if !pipe.is_prediction && self.match_manager.tick(&mut self.world) {
+            let new_round = !pipe.is_prediction && self.match_manager.tick(&mut self.world);
+            if self.practice.is_enabled() {
+                self.match_manager.finished_stats.clear();
+            }
+            if new_round {
                 let characters = std::mem::replace(
                     &mut self.world.characters,
                     self.world.world_pool.character_pool.character_pool.new(),
@@ -116,8 +125,14 @@ pub mod stage {
                 );
                 self.world.characters = characters;
                 let game_options = self.match_manager.game_options.clone();
-                self.match_manager = MatchManager::new(game_options, &self.simulation_events);
+                self.match_manager =
+                    MatchManager::new(game_options, &self.simulation_events, false);
+                // practice only ends with the round
+                self.practice = Default::default();
             }
+            let characters = &self.world.characters;
+            self.practice
+                .retain_players(|id| characters.contains_key(id));
 
             self.simulation_events.clear();
         }
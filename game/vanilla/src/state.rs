@@ -18,7 +18,7 @@ pub mod state {
     use game_interface::account_info::MAX_ACCOUNT_NAME_LEN;
     use game_interface::chat_commands::ChatCommands;
     use game_interface::client_commands::{
-        ClientCameraMode, ClientCommand, JoinStage, MAX_TEAM_NAME_LEN,
+        ClientCameraMode, ClientCommand, JoinStage, MAX_TEAM_NAME_LEN, can_join_side,
     };
     use game_interface::events::{
         EventClientInfo, EventId, EventIdGenerator, GameEvents, GameWorldEvent, GameWorldEvents,
@@ -28,14 +28,16 @@ pub mod state {
     use game_interface::pooling::GamePooling;
     use game_interface::rcon_entries::{AuthLevel, ExecRconInput, RconEntries, RconEntry};
     use game_interface::settings::GameStateSettings;
-    use game_interface::tick_result::TickResult;
+    use game_interface::tick_result::{TickEvent, TickResult};
     use game_interface::types::character_info::{
         MAX_ASSET_NAME_LEN, MAX_CHARACTER_NAME_LEN, NetworkCharacterInfo, NetworkLaserInfo,
         NetworkSkinInfo,
     };
     use game_interface::types::emoticons::EmoticonType;
     use game_interface::types::fixed_zoom_level::FixedZoomLevel;
-    use game_interface::types::game::{GameTickCooldown, GameTickType};
+    use game_interface::types::game::{
+        GameTickCooldown, GameTickCooldownAndLastActionCounter, GameTickType,
+    };
     use game_interface::types::id_gen::{IdGenerator, IdGeneratorIdType};
     use game_interface::types::id_types::{
         CharacterId, CtfFlagId, LaserId, PickupId, PlayerId, ProjectileId, StageId,
@@ -44,7 +46,9 @@ pub mod state {
         CharacterInput, CharacterInputConsumableDiff, CharacterInputFlags, CharacterInputInfo,
     };
     use game_interface::types::network_stats::PlayerNetworkStats;
-    use game_interface::types::player_info::{PlayerClientInfo, PlayerDropReason, PlayerUniqueId};
+    use game_interface::types::player_info::{
+        PlayerBanReason, PlayerClientInfo, PlayerDropReason, PlayerKickReason, PlayerUniqueId,
+    };
     use game_interface::types::render::game::GameRenderInfo;
     use game_interface::types::render::game::game_match::{
         FlagCarrierCharacter, LeadingCharacter, MatchSide, MatchStandings,
@@ -58,7 +62,6 @@ pub mod state {
     use hiarc::hi_closure;
     use map::file::MapFileReader;
     use map::map::Map;
-    use map::map::config::ConfigVariables;
     use math::math::lerp;
     use math::math::vector::{ubvec4, vec2};
     use pool::datatypes::{PoolFxHashMap, PoolFxLinkedHashMap, PoolVec};
@@ -72,8 +75,8 @@ pub mod state {
     use game_interface::types::render::character::{
         CharacterBuff, CharacterBuffInfo, CharacterDebuff, CharacterDebuffInfo,
         CharacterHookRenderInfo, CharacterInfo, CharacterPlayerInfo, CharacterRenderInfo,
-        LocalCharacterRenderInfo, LocalCharacterVanilla, PlayerCameraMode, PlayerIngameMode,
-        TeeEye,
+        CharacterServerRank, LocalCharacterRenderInfo, LocalCharacterVanilla, PlayerCameraMode,
+        PlayerIngameMode, TeeEye,
     };
     use game_interface::types::render::flag::FlagRenderInfo;
     use game_interface::types::render::laser::LaserRenderInfo;
@@ -87,11 +90,20 @@ pub mod state {
     use game_interface::types::snapshot::{SnapshotClientInfo, SnapshotLocalPlayers};
     use legacy_map::mapdef_06::EntityTiles;
     use pool::rc::PoolRc;
-    use rustc_hash::FxHashMap;
+    use rustc_hash::{FxHashMap, FxHashSet};
 
+    use crate::announcements::announcements::{AnnouncementScheduler, format_player_msg};
+    use crate::anti_cheat::anti_cheat::InputPlausibility;
+    use crate::bans::bans::{
+        BanIdentity, BanKind, BanList, BanTarget, PlayerBanState, ban_msg, fmt_remaining,
+    };
+    use crate::bots::bots::{BotMode, Bots};
+    use crate::chat_commands::{ChatCommand, ChatCommandPermission, ChatCommandRegistry};
     use crate::collision::collision::Tunings;
     use crate::command_chain::{Command, CommandChain};
-    use crate::config::config::{ConfigGameType, ConfigVanilla, ConfigVanillaWrapper};
+    use crate::config::config::{
+        ConfigAntiCheatAction, ConfigBotMode, ConfigGameType, ConfigVanilla, ConfigVanillaWrapper,
+    };
     use crate::entities::character::character::{self, CharacterPlayerTy, CharacterSpectateMode};
     use crate::entities::character::core::character_core::Core;
     use crate::entities::character::player::player::{
@@ -102,21 +114,36 @@ pub mod state {
     use crate::entities::pickup::pickup::Pickup;
     use crate::entities::projectile::projectile::{self};
     use crate::game_objects::game_objects::GameObjectDefinitions;
+    use crate::loadout::loadout::SpawnLoadout;
+    use crate::map_settings::map_settings::{MapHints, apply_map_settings, map_hints};
     use crate::match_manager::match_manager::MatchManager;
     use crate::match_state::match_state::{MatchState, MatchType};
+    use crate::platforms::platforms::Platforms;
+    use crate::practice::practice::PracticeVote;
     use crate::simulation_pipe::simulation_pipe::{GamePendingEvents, GameStagePendingEvents};
-    use crate::snapshot::snapshot::{Snapshot, SnapshotFor, SnapshotManager, SnapshotStage};
+    use crate::snapshot::snapshot::{
+        Snapshot, SnapshotFor, SnapshotInterestRadius, SnapshotManager, SnapshotStage,
+        SnapshotView, SnapshotViewFilter,
+    };
+    use crate::sound_batching::sound_batching::batch_sound_events;
     use crate::sql::account_created::{self, AccountCreated};
     use crate::sql::account_info::{AccountInfo, StatementResult};
+    use crate::sql::anti_cheat::AntiCheatFlag;
+    use crate::sql::bans::{Bans, LoadedBan};
+    use crate::sql::round_stats::RoundStats;
     use crate::sql::save;
+    use crate::sql::server_rank::ServerRank;
     use crate::stage::stage::Stages;
     use crate::types::types::{GameOptions, GameType};
     use crate::weapons::definitions::weapon_def::Weapon;
 
     use super::super::{
-        collision::collision::Collision, entities::character::character::Character,
-        simulation_pipe::simulation_pipe::SimulationPipeStage, spawns::GameSpawns,
-        stage::stage::GameStage, world::world::WorldPool,
+        collision::collision::{Collision, tick_scale},
+        entities::character::character::Character,
+        simulation_pipe::simulation_pipe::SimulationPipeStage,
+        spawns::GameSpawns,
+        stage::stage::GameStage,
+        world::world::WorldPool,
     };
 
     use thiserror::Error;
@@ -127,21 +154,69 @@ pub mod state {
         InvalidStage,
     }
 
+    /// The tick rate the tunings & physics constants are made for,
+    /// see [`ConfigVanilla::ticks_per_second`] for the actual rate.
     pub(crate) const TICKS_PER_SECOND: u64 = 50;
 
+    /// The largest view (in tiles) a client can request with its viewport.
+    const MAX_VIEW_SIZE: (f32, f32) = (80.0, 50.0);
+    /// Tiles around a view that are snapped too,
+    /// so entities don't pop up at the edges.
+    const VIEW_MARGIN: f32 = 4.0;
+
     #[derive(Debug, Clone, Copy)]
     pub enum VanillaRconCommandCheat {
         WeaponsAll,
         Tune,
     }
 
+    #[derive(Debug, Clone, Copy)]
+    pub enum VanillaRconCommandBots {
+        Add,
+        Mirror,
+        RemoveAll,
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    pub enum VanillaRconCommandBans {
+        Ban,
+        Unban,
+        Mute,
+        Unmute,
+        List,
+    }
+
     #[derive(Debug, Clone, Copy)]
     pub enum VanillaRconCommand {
         Info,
         Cheats(VanillaRconCommandCheat),
+        Bots(VanillaRconCommandBots),
+        Bans(VanillaRconCommandBans),
         ConfVariable,
     }
 
+    impl VanillaRconCommand {
+        /// The auth level required to execute this command.
+        pub fn auth_level(&self) -> AuthLevel {
+            match self {
+                VanillaRconCommand::Info | VanillaRconCommand::Bans(_) => AuthLevel::Moderator,
+                VanillaRconCommand::Cheats(_)
+                | VanillaRconCommand::Bots(_)
+                | VanillaRconCommand::ConfVariable => AuthLevel::Admin,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    pub enum VanillaChatCommand {
+        Help,
+        AccountInfo,
+        Ready,
+        Practice,
+        SavePos,
+        LoadPos,
+    }
+
     pub struct Game {
         pub(crate) stages: Stages,
 
@@ -167,10 +242,30 @@ pub mod state {
             affected_rows: account_created::StatementAffected,
             err: Option<anyhow::Error>,
         },
+        RoundStats {
+            account_id: AccountId,
+        },
+        AntiCheatFlag {
+            account_id: AccountId,
+        },
+        BansLoaded {
+            bans: Vec<LoadedBan>,
+        },
+        BanSaved {
+            target: BanTarget,
+        },
+        ServerRank {
+            account_id: AccountId,
+            rank: Option<CharacterServerRank>,
+        },
     }
 
     pub struct GameStatements {
         account_created: AccountCreated,
+        round_stats: Option<RoundStats>,
+        anti_cheat_flag: Option<AntiCheatFlag>,
+        bans: Option<Bans>,
+        server_rank: Option<ServerRank>,
     }
 
     pub struct GameDb {
@@ -180,6 +275,10 @@ pub mod state {
 
         pub(crate) cur_queries: Vec<IoRuntimeTask<GameDbQueries>>,
         pub(crate) cur_queries_helper: Vec<IoRuntimeTask<GameDbQueries>>,
+
+        /// The last known ranks of accounts, so rejoining players
+        /// show their rank before the database answered.
+        pub(crate) server_ranks: FxHashMap<AccountId, CharacterServerRank>,
     }
 
     /// A game state is a collection of game related attributes such as the world,
@@ -207,14 +306,36 @@ pub mod state {
 
         // game
         pub(crate) game_options: GameOptions,
+        map_hints: MapHints,
+        /// The hint zones every player entered already,
+        /// by unique identifier, so reconnecting doesn't show them again.
+        shown_hints: FxHashMap<PlayerUniqueId, FxHashSet<u8>>,
+        announcements: AnnouncementScheduler,
 
         pub(crate) chat_commands: ChatCommands,
+        pub(crate) chat_registry: ChatCommandRegistry<VanillaChatCommand>,
         pub(crate) rcon_chain: CommandChain<VanillaRconCommand>,
         cache: ParserCache,
         map_name: NetworkReducedAsciiString<MAX_MAP_NAME_LEN>,
 
+        // bots
+        pub(crate) bots: Bots,
+        /// Whether the bots of the config were spawned already.
+        config_bots_spawned: bool,
+
         // db
-        game_db: GameDb,
+        pub(crate) game_db: GameDb,
+
+        // anti-cheat, only players that joined through the interface are checked,
+        // so bots & the client's prediction are ignored.
+        anti_cheat: FxHashMap<PlayerId, (PlayerUniqueId, InputPlausibility)>,
+
+        // bans & mutes, like the anti-cheat only for players
+        // that joined through the interface.
+        pub(crate) bans: BanList,
+        ban_states: FxHashMap<PlayerId, PlayerBanState>,
+        /// Events for the server, returned by the next tick.
+        pending_tick_events: Vec<TickEvent>,
 
         // pooling
         pub(crate) world_pool: WorldPool,
@@ -306,35 +427,6 @@ pub mod state {
             (res, res_cmds)
         }
 
-        fn handle_map_config_variables(
-            config: &mut ConfigVanilla,
-            config_variables: ConfigVariables,
-        ) {
-            for (cmd, val) in config_variables {
-                if cmd == "vanilla.game_type" {
-                    if let Err(err) = config.try_set_from_str(
-                        cmd.clone(),
-                        None,
-                        Some(val.value.clone()),
-                        None,
-                        config::traits::ConfigFromStrOperation::Set,
-                    ) {
-                        log::warn!(
-                            "Failed to set config variable {cmd} to {}: {err}",
-                            val.value
-                        );
-                    }
-                } else {
-                    log::warn!(
-                        "UNSUPPORTED: config variable {} {} \
-                        is not supported by this game mod and ignored",
-                        cmd,
-                        val.value
-                    );
-                }
-            }
-        }
-
         fn new_impl(
             map: Vec<u8>,
             map_name: NetworkReducedAsciiString<MAX_MAP_NAME_LEN>,
@@ -407,6 +499,169 @@ pub mod state {
                         cmd: VanillaRconCommand::Cheats(VanillaRconCommandCheat::Tune),
                     },
                 ),
+                (
+                    "bots.add".try_into().unwrap(),
+                    Command {
+                        rcon: RconEntry {
+                            description: "Adds server-side bots that idle \
+                                or patrol between the spawn points"
+                                .try_into()
+                                .unwrap(),
+                            usage: "<count> <mode>".try_into().unwrap(),
+                            args: vec![
+                                CommandArg {
+                                    ty: CommandArgType::Number,
+                                    user_ty: None,
+                                },
+                                CommandArg {
+                                    ty: CommandArgType::TextFrom(vec![
+                                        "idle".try_into().unwrap(),
+                                        "patrol".try_into().unwrap(),
+                                    ]),
+                                    user_ty: None,
+                                },
+                            ],
+                        },
+                        cmd: VanillaRconCommand::Bots(VanillaRconCommandBots::Add),
+                    },
+                ),
+                (
+                    "bots.mirror".try_into().unwrap(),
+                    Command {
+                        rcon: RconEntry {
+                            description: "Adds a server-side bot that repeats \
+                                the inputs of a player after the given amount of ticks"
+                                .try_into()
+                                .unwrap(),
+                            usage: "<player_id> <delay_ticks>".try_into().unwrap(),
+                            args: vec![
+                                CommandArg {
+                                    ty: CommandArgType::Number,
+                                    user_ty: None,
+                                },
+                                CommandArg {
+                                    ty: CommandArgType::Number,
+                                    user_ty: None,
+                                },
+                            ],
+                        },
+                        cmd: VanillaRconCommand::Bots(VanillaRconCommandBots::Mirror),
+                    },
+                ),
+                (
+                    "bots.remove_all".try_into().unwrap(),
+                    Command {
+                        rcon: RconEntry {
+                            args: Default::default(),
+                            description: "Removes all server-side bots".try_into().unwrap(),
+                            usage: "".try_into().unwrap(),
+                        },
+                        cmd: VanillaRconCommand::Bots(VanillaRconCommandBots::RemoveAll),
+                    },
+                ),
+                (
+                    "bans.ban".try_into().unwrap(),
+                    Command {
+                        rcon: RconEntry {
+                            description: "Bans an account or ip (range) for the given minutes, \
+                                `0` bans permanently"
+                                .try_into()
+                                .unwrap(),
+                            usage: "<account_id|ip_range> <minutes> <reason>"
+                                .try_into()
+                                .unwrap(),
+                            args: vec![
+                                CommandArg {
+                                    ty: CommandArgType::Text,
+                                    user_ty: Some("TARGET".try_into().unwrap()),
+                                },
+                                CommandArg {
+                                    ty: CommandArgType::Number,
+                                    user_ty: Some("MINUTES".try_into().unwrap()),
+                                },
+                                CommandArg {
+                                    ty: CommandArgType::Text,
+                                    user_ty: Some("REASON".try_into().unwrap()),
+                                },
+                            ],
+                        },
+                        cmd: VanillaRconCommand::Bans(VanillaRconCommandBans::Ban),
+                    },
+                ),
+                (
+                    "bans.unban".try_into().unwrap(),
+                    Command {
+                        rcon: RconEntry {
+                            description: "Removes the ban of an account or ip (range)"
+                                .try_into()
+                                .unwrap(),
+                            usage: "<account_id|ip_range>".try_into().unwrap(),
+                            args: vec![CommandArg {
+                                ty: CommandArgType::Text,
+                                user_ty: Some("TARGET".try_into().unwrap()),
+                            }],
+                        },
+                        cmd: VanillaRconCommand::Bans(VanillaRconCommandBans::Unban),
+                    },
+                ),
+                (
+                    "bans.mute".try_into().unwrap(),
+                    Command {
+                        rcon: RconEntry {
+                            description: "Mutes an account or ip (range) for the given minutes, \
+                                `0` mutes permanently"
+                                .try_into()
+                                .unwrap(),
+                            usage: "<account_id|ip_range> <minutes> <reason>"
+                                .try_into()
+                                .unwrap(),
+                            args: vec![
+                                CommandArg {
+                                    ty: CommandArgType::Text,
+                                    user_ty: Some("TARGET".try_into().unwrap()),
+                                },
+                                CommandArg {
+                                    ty: CommandArgType::Number,
+                                    user_ty: Some("MINUTES".try_into().unwrap()),
+                                },
+                                CommandArg {
+                                    ty: CommandArgType::Text,
+                                    user_ty: Some("REASON".try_into().unwrap()),
+                                },
+                            ],
+                        },
+                        cmd: VanillaRconCommand::Bans(VanillaRconCommandBans::Mute),
+                    },
+                ),
+                (
+                    "bans.unmute".try_into().unwrap(),
+                    Command {
+                        rcon: RconEntry {
+                            description: "Removes the mute of an account or ip (range)"
+                                .try_into()
+                                .unwrap(),
+                            usage: "<account_id|ip_range>".try_into().unwrap(),
+                            args: vec![CommandArg {
+                                ty: CommandArgType::Text,
+                                user_ty: Some("TARGET".try_into().unwrap()),
+                            }],
+                        },
+                        cmd: VanillaRconCommand::Bans(VanillaRconCommandBans::Unmute),
+                    },
+                ),
+                (
+                    "bans.list".try_into().unwrap(),
+                    Command {
+                        rcon: RconEntry {
+                            args: Default::default(),
+                            description: "Lists all bans & mutes with their remaining time"
+                                .try_into()
+                                .unwrap(),
+                            usage: "".try_into().unwrap(),
+                        },
+                        cmd: VanillaRconCommand::Bans(VanillaRconCommandBans::List),
+                    },
+                ),
             ];
 
             let mut rcon_vars: Vec<_> = Default::default();
@@ -485,6 +740,51 @@ pub mod state {
                         );
                     }
 
+                    let round_stats = match RoundStats::new(db.clone(), options.account_db).await {
+                        Ok(round_stats) => Some(round_stats),
+                        Err(err) => {
+                            log::warn!(
+                                target: "sql",
+                                "failed to prepare round_stats sql: {err}"
+                            );
+                            None
+                        }
+                    };
+
+                    let anti_cheat_flag =
+                        match AntiCheatFlag::new(db.clone(), options.account_db).await {
+                            Ok(anti_cheat_flag) => Some(anti_cheat_flag),
+                            Err(err) => {
+                                log::warn!(
+                                    target: "sql",
+                                    "failed to prepare anti_cheat_flag sql: {err}"
+                                );
+                                None
+                            }
+                        };
+
+                    let bans = match Bans::new(db.clone(), options.account_db).await {
+                        Ok(bans) => Some(bans),
+                        Err(err) => {
+                            log::warn!(
+                                target: "sql",
+                                "failed to prepare bans sql: {err}"
+                            );
+                            None
+                        }
+                    };
+
+                    let server_rank = match ServerRank::new(db.clone(), options.account_db).await {
+                        Ok(server_rank) => Some(server_rank),
+                        Err(err) => {
+                            log::warn!(
+                                target: "sql",
+                                "failed to prepare server_rank sql: {err}"
+                            );
+                            None
+                        }
+                    };
+
                     let account_created = match AccountCreated::new(db, options.account_db).await {
                         Ok(account_created) => Some(account_created),
                         Err(err) => {
@@ -496,8 +796,13 @@ pub mod state {
                         }
                     };
 
-                    let statements =
-                        account_created.map(|account_created| GameStatements { account_created });
+                    let statements = account_created.map(|account_created| GameStatements {
+                        account_created,
+                        round_stats,
+                        anti_cheat_flag,
+                        bans,
+                        server_rank,
+                    });
 
                     Ok(statements.zip(acc_info.ok()))
                 } else {
@@ -505,46 +810,41 @@ pub mod state {
                 }
             });
 
-            let (physics_group, map_config) =
-                Map::read_physics_group_and_config(&MapFileReader::new(map)?)?;
+            let map_reader = MapFileReader::new(map)?;
+            let (physics_group, map_config) = Map::read_physics_group_and_config(&map_reader)?;
+            let platforms =
+                Platforms::new(&physics_group, &Map::read_animations(&map_reader)?.pos)?;
 
             let w = physics_group.attr.width.get() as u32;
             let h = physics_group.attr.height.get() as u32;
 
             let tiles = physics_group.get_game_layer_tiles().clone();
 
+            // deserialized configs are not validated
+            config.ticks_per_second = config.ticks_per_second.clamp(25, 100);
             let mut collision = Collision::new(physics_group, true)?;
+            collision.set_ticks_per_second(config.ticks_per_second);
+            collision.platforms = platforms;
 
-            // Always handle config variables before commands.
-            Self::handle_map_config_variables(&mut config, map_config.config_variables);
-            for cmd in map_config.commands {
-                if let Some((cmd, val)) =
-                    cmd.value
-                        .split_once(char::is_whitespace)
-                        .and_then(|(s1, s2)| {
-                            (s1.trim() == "tune")
-                                .then(|| s2.split_once(char::is_whitespace))
-                                .flatten()
-                        })
+            if config.ignore_map_settings {
+                log::info!("Settings of the map are ignored by the server config.");
+            } else {
+                for rejected in
+                    apply_map_settings(&map_config, &mut config, &mut collision.tune_zones[0])
                 {
-                    if let Err(err) = collision.tune_zones[0].try_set_from_str(
-                        cmd.to_string(),
-                        None,
-                        Some(val.to_string()),
-                        None,
-                        config::traits::ConfigFromStrOperation::Set,
-                    ) {
-                        log::warn!("Failed to apply global tune: {err}");
-                    }
-                } else {
                     log::warn!(
-                        "UNSUPPORTED: command {} \
-                        is not supported by this game mod and ignored",
-                        cmd.value
+                        "Map setting \"{}\" was rejected: {}",
+                        rejected.setting,
+                        rejected.err
                     );
                 }
             }
 
+            // the game type might have been changed by the map
+            SpawnLoadout::new(&config)?;
+
+            let map_hints = map_hints(&map_config);
+
             let game_objects = GameObjectDefinitions::new(&tiles, w, h);
 
             let mut spawns: Vec<vec2> = Default::default();
@@ -570,12 +870,84 @@ pub mod state {
 
             let has_accounts = account_info.is_some();
 
-            let chat_commands = ChatCommands {
-                cmds: vec![("account_info".try_into().unwrap(), vec![])]
-                    .into_iter()
-                    .collect(),
-                prefixes: vec!['/'],
+            let chat_cmd = |usage: &str,
+                            description: &str,
+                            permission: ChatCommandPermission,
+                            cmd: VanillaChatCommand| ChatCommand {
+                args: Default::default(),
+                required_args: 0,
+                usage: usage.try_into().unwrap(),
+                description: description.try_into().unwrap(),
+                permission,
+                cmd,
             };
+            let chat_registry = ChatCommandRegistry::new(
+                vec![
+                    (
+                        "help".try_into().unwrap(),
+                        ChatCommand {
+                            args: vec![CommandArg {
+                                ty: CommandArgType::Text,
+                                user_ty: Some("command or page".try_into().unwrap()),
+                            }],
+                            ..chat_cmd(
+                                "[command|page]",
+                                "Lists the chat commands or explains a single one",
+                                ChatCommandPermission::Everyone,
+                                VanillaChatCommand::Help,
+                            )
+                        },
+                    ),
+                    (
+                        "account_info".try_into().unwrap(),
+                        chat_cmd(
+                            "",
+                            "Shows information about your account",
+                            ChatCommandPermission::Playing,
+                            VanillaChatCommand::AccountInfo,
+                        ),
+                    ),
+                    (
+                        "ready".try_into().unwrap(),
+                        chat_cmd(
+                            "",
+                            "Toggles whether you are ready for the next round",
+                            ChatCommandPermission::Playing,
+                            VanillaChatCommand::Ready,
+                        ),
+                    ),
+                    (
+                        "practice".try_into().unwrap(),
+                        chat_cmd(
+                            "",
+                            "Votes for practice mode in your team",
+                            ChatCommandPermission::Playing,
+                            VanillaChatCommand::Practice,
+                        ),
+                    ),
+                    (
+                        "savepos".try_into().unwrap(),
+                        chat_cmd(
+                            "",
+                            "Saves your position in practice mode",
+                            ChatCommandPermission::Playing,
+                            VanillaChatCommand::SavePos,
+                        ),
+                    ),
+                    (
+                        "loadpos".try_into().unwrap(),
+                        chat_cmd(
+                            "",
+                            "Loads your saved position in practice mode",
+                            ChatCommandPermission::Playing,
+                            VanillaChatCommand::LoadPos,
+                        ),
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+            );
+            let chat_commands = chat_registry.export(vec!['/']);
 
             let mut game = Self {
                 game: Game {
@@ -614,11 +986,19 @@ pub mod state {
 
                 // game
                 game_options: GameOptions::new(game_type, config.clone()),
+                map_hints,
+                shown_hints: Default::default(),
+                announcements: Default::default(),
                 chat_commands: chat_commands.clone(),
+                chat_registry,
                 rcon_chain,
                 cache,
                 map_name,
 
+                // bots
+                bots: Default::default(),
+                config_bots_spawned: false,
+
                 // db
                 game_db: GameDb {
                     io_rt,
@@ -627,8 +1007,15 @@ pub mod state {
 
                     cur_queries: Default::default(),
                     cur_queries_helper: Default::default(),
+
+                    server_ranks: Default::default(),
                 },
 
+                anti_cheat: Default::default(),
+                bans: Default::default(),
+                ban_states: Default::default(),
+                pending_tick_events: Default::default(),
+
                 // pool
                 world_pool: WorldPool::new(options.hint_max_characters.unwrap_or(64)),
                 spectator_player_clone_pool: Pool::with_capacity(2),
@@ -643,8 +1030,24 @@ pub mod state {
             };
             game.stage_0_id = game.add_stage(Default::default(), ubvec4::new(0, 0, 0, 0));
 
+            if let Some(bans) = game
+                .game_db
+                .statements
+                .as_ref()
+                .and_then(|s| s.bans.clone())
+            {
+                game.game_db
+                    .cur_queries
+                    .push(game.game_db.io_rt.spawn(async move {
+                        Ok(GameDbQueries::BansLoaded {
+                            bans: bans.load().await?,
+                        })
+                    }));
+            }
+
+            // the initial commands come from the server's config
             for cmd in remaining_cmds {
-                match game.handle_full_command(None, cmd) {
+                match game.handle_full_command(None, AuthLevel::Admin, cmd) {
                     Ok(res) => initial_args_res.push(Ok(NetworkString::new_lossy(res))),
                     Err(err) => {
                         initial_args_res.push(Err(NetworkString::new_lossy(err.to_string())))
@@ -652,10 +1055,11 @@ pub mod state {
                 }
             }
 
+            let balanced_sides = game.game_options.sided_balance_enabled();
             Ok((
                 game,
                 GameStateStaticInfo {
-                    ticks_in_a_second: NonZero::new(TICKS_PER_SECOND).unwrap(),
+                    ticks_in_a_second: NonZero::new(game.game_options.ticks_per_second()).unwrap(),
                     chat_commands,
                     rcon_commands,
 
@@ -668,6 +1072,7 @@ pub mod state {
                         physics_group_name: "vanilla".try_into().unwrap(),
                         allow_stages: config.allow_stages,
                         use_vanilla_sides: Self::is_sided_from_conf(config.game_type),
+                        balanced_sides,
                         use_account_name: has_accounts,
                         forced_ingame_camera_zoom: Some(FixedZoomLevel::new_lossy(1.0)),
                         allows_voted_player_miniscreen: config.allow_player_vote_cam,
@@ -841,6 +1246,7 @@ pub mod state {
         }
 
         fn tick_impl(&mut self, is_prediction: bool) {
+            self.collision.tick_platforms();
             for stage in self.game.stages.values_mut() {
                 let stage_id = stage.game_element_id;
                 let mut sim_pipe = SimulationPipeStage::new(
@@ -851,6 +1257,148 @@ pub mod state {
                 );
 
                 stage.tick(&mut sim_pipe);
+
+                let finished_stats = std::mem::take(&mut stage.match_manager.finished_stats);
+                if self.game_options.save_round_stats()
+                    && let Some(round_stats) = self
+                        .game_db
+                        .statements
+                        .as_ref()
+                        .and_then(|s| s.round_stats.as_ref())
+                {
+                    for (unique_identifier, stats) in finished_stats {
+                        let PlayerUniqueId::Account(account_id) = unique_identifier else {
+                            continue;
+                        };
+                        let round_stats = round_stats.clone();
+                        self.game_db
+                            .cur_queries
+                            .push(self.game_db.io_rt.spawn(async move {
+                                round_stats.execute(account_id, &stats).await?;
+                                Ok(GameDbQueries::RoundStats { account_id })
+                            }));
+                    }
+                }
+            }
+        }
+
+        /// Decays the suspicion of all players and handles the players
+        /// that reached the threshold.
+        fn anti_cheat_tick(&mut self) {
+            let action = self.game_options.anti_cheat_action();
+            let threshold = self.game_options.anti_cheat_threshold() as f32;
+            let mut suspicious = Vec::new();
+            for (player_id, (unique_identifier, anti_cheat)) in self.anti_cheat.iter_mut() {
+                anti_cheat.decay();
+                if anti_cheat.suspicion() >= threshold {
+                    suspicious.push((*player_id, *unique_identifier, anti_cheat.detections()));
+                    anti_cheat.reset_suspicion();
+                }
+            }
+
+            for (player_id, unique_identifier, detections) in suspicious {
+                log::warn!(
+                    target: "anti-cheat",
+                    "player {player_id} ({unique_identifier:?}) reached \
+                    the suspicion threshold: {detections:?}"
+                );
+                match action {
+                    ConfigAntiCheatAction::Off | ConfigAntiCheatAction::Log => {}
+                    ConfigAntiCheatAction::Flag => {
+                        let (PlayerUniqueId::Account(account_id), Some(anti_cheat_flag)) = (
+                            unique_identifier,
+                            self.game_db
+                                .statements
+                                .as_ref()
+                                .and_then(|s| s.anti_cheat_flag.clone()),
+                        ) else {
+                            continue;
+                        };
+                        self.game_db
+                            .cur_queries
+                            .push(self.game_db.io_rt.spawn(async move {
+                                anti_cheat_flag.execute(account_id, detections).await?;
+                                Ok(GameDbQueries::AntiCheatFlag { account_id })
+                            }));
+                    }
+                    ConfigAntiCheatAction::Kick => {
+                        self.pending_tick_events.push(TickEvent::Kick {
+                            player_id,
+                            reason: PlayerKickReason::Custom(
+                                "Kicked by the anti-cheat.".try_into().unwrap(),
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+
+        /// Adds a ban or mute & persists it, if a database is used.
+        fn add_ban(
+            &mut self,
+            kind: BanKind,
+            target: BanTarget,
+            duration: Option<Duration>,
+            reason: String,
+        ) {
+            self.bans.add(kind, target, duration, reason.clone());
+            if let Some(bans) = self
+                .game_db
+                .statements
+                .as_ref()
+                .and_then(|s| s.bans.clone())
+            {
+                self.game_db
+                    .cur_queries
+                    .push(self.game_db.io_rt.spawn(async move {
+                        bans.add(kind, target, duration, reason).await?;
+                        Ok(GameDbQueries::BanSaved { target })
+                    }));
+            }
+        }
+
+        fn remove_ban(&mut self, kind: BanKind, target: BanTarget) -> bool {
+            let removed = self.bans.remove(kind, &target);
+            if let Some(bans) = self
+                .game_db
+                .statements
+                .as_ref()
+                .and_then(|s| s.bans.clone())
+            {
+                self.game_db
+                    .cur_queries
+                    .push(self.game_db.io_rt.spawn(async move {
+                        bans.remove(kind, target).await?;
+                        Ok(GameDbQueries::BanSaved { target })
+                    }));
+            }
+            removed
+        }
+
+        /// Expires bans & mutes and applies them to the joined players.
+        fn bans_tick(&mut self) {
+            self.bans
+                .advance(Duration::from_secs(1) / self.game_options.ticks_per_second() as u32);
+            for (player_id, state) in self.ban_states.iter_mut() {
+                if !state.kicked
+                    && let Some(entry) = self.bans.find(BanKind::Ban, &state.identity)
+                {
+                    state.kicked = true;
+                    self.pending_tick_events.push(TickEvent::Kick {
+                        player_id: *player_id,
+                        reason: PlayerKickReason::Custom(NetworkString::new_lossy(ban_msg(
+                            &self.bans, entry,
+                        ))),
+                    });
+                }
+                let muted = self.bans.find(BanKind::Mute, &state.identity).is_some();
+                if state.muted != muted {
+                    state.muted = muted;
+                    self.pending_tick_events.push(TickEvent::Mute {
+                        player_id: *player_id,
+                        muted,
+                    });
+                }
             }
         }
 
@@ -869,13 +1417,81 @@ pub mod state {
             }
         }
 
-        fn query_tick(&mut self) {
-            self.game_db.cur_queries_helper.clear();
-            for query in self.game_db.cur_queries.drain(..) {
-                if query.is_finished() {
-                    match query.get() {
-                        Ok(query) => match query {
-                            GameDbQueries::AccountInfo {
+        /// Shows the hint of a zone the first time a player enters it.
+        fn hint_tick(&mut self) {
+            if self.map_hints.is_empty() {
+                return;
+            }
+            for stage in self.game.stages.values() {
+                for (player_id, character) in stage.world.characters.iter() {
+                    if character.player_info.is_bot {
+                        continue;
+                    }
+                    let Some((zone, hint)) = self
+                        .collision
+                        .switch_number_at(character.pos.pos())
+                        .and_then(|zone| self.map_hints.get(&zone).map(|hint| (zone, hint)))
+                    else {
+                        continue;
+                    };
+                    if !self
+                        .shown_hints
+                        .entry(character.player_info.unique_identifier)
+                        .or_default()
+                        .insert(zone)
+                    {
+                        continue;
+                    }
+                    let mut msg = self.game_pools.mt_network_string_common_pool.new();
+                    if msg.try_set(hint.as_str()).is_ok() {
+                        self.player_events.entry(*player_id).or_default().push(
+                            GameWorldEvent::Notification(GameWorldNotificationEvent::Hint { msg }),
+                        );
+                    }
+                }
+            }
+        }
+
+        /// Players on the server, without bots.
+        fn human_player_count(&self) -> usize {
+            (self.game.players.len() + self.game.spectator_players.len())
+                .saturating_sub(self.bots.len())
+        }
+
+        /// A chat message to all players.
+        fn global_system_msg(&self, msg: &str) {
+            let mut s = self.game_pools.mt_network_string_common_pool.new();
+            if s.try_set(msg).is_ok() {
+                self.game
+                    .stages
+                    .get(&self.stage_0_id)
+                    .unwrap()
+                    .game_pending_events
+                    .push(GameWorldEvent::Notification(
+                        GameWorldNotificationEvent::System(GameWorldSystemMessage::Custom(s)),
+                    ));
+            }
+        }
+
+        fn announcement_tick(&mut self) {
+            let options = self.game_options.announcement_options();
+            let player_count = self.human_player_count();
+            if let Some(msg) = self
+                .announcements
+                .tick(&options, player_count)
+                .and_then(|index| self.game_options.announcement(index))
+            {
+                self.global_system_msg(&msg);
+            }
+        }
+
+        fn query_tick(&mut self) {
+            self.game_db.cur_queries_helper.clear();
+            for query in self.game_db.cur_queries.drain(..) {
+                if query.is_finished() {
+                    match query.get() {
+                        Ok(query) => match query {
+                            GameDbQueries::AccountInfo {
                                 player_id,
                                 account_info: info,
                             } => {
@@ -953,6 +1569,38 @@ pub mod state {
                                     );
                                 }
                             }
+                            GameDbQueries::RoundStats { account_id } => {
+                                log::debug!("Saved round statistics for account {account_id}");
+                                // the points changed
+                                Self::push_server_rank_task(&mut self.game_db, account_id);
+                            }
+                            GameDbQueries::AntiCheatFlag { account_id } => {
+                                log::info!(
+                                    target: "anti-cheat",
+                                    "Flagged account {account_id}"
+                                );
+                            }
+                            GameDbQueries::BansLoaded { bans } => {
+                                log::info!("Loaded {} ban(s) & mute(s)", bans.len());
+                                for ban in bans {
+                                    self.bans
+                                        .add(ban.kind, ban.target, ban.remaining, ban.reason);
+                                }
+                            }
+                            GameDbQueries::BanSaved { target } => {
+                                log::debug!("Saved bans of {target}");
+                            }
+                            GameDbQueries::ServerRank { account_id, rank } => {
+                                match rank {
+                                    Some(rank) => {
+                                        self.game_db.server_ranks.insert(account_id, rank);
+                                    }
+                                    None => {
+                                        self.game_db.server_ranks.remove(&account_id);
+                                    }
+                                }
+                                self.server_rank_changed(account_id, rank);
+                            }
                         },
                         Err(err) => {
                             log::warn!("query failed: {err}");
@@ -987,7 +1635,8 @@ pub mod state {
                 let stage = stages.get_mut(&player.stage_id()).unwrap();
                 if matches!(
                     stage.match_manager.game_match.state,
-                    MatchState::Running { .. }
+                    MatchState::Warmup { .. }
+                        | MatchState::Running { .. }
                         | MatchState::Paused { .. }
                         | MatchState::SuddenDeath { .. }
                         | MatchState::PausedSuddenDeath { .. }
@@ -1040,6 +1689,21 @@ pub mod state {
             }
         }
 
+        fn push_server_rank_task(game_db: &mut GameDb, account_id: AccountId) {
+            if let Some(server_rank) = game_db
+                .statements
+                .as_ref()
+                .and_then(|s| s.server_rank.clone())
+            {
+                game_db.cur_queries.push(game_db.io_rt.spawn(async move {
+                    Ok(GameDbQueries::ServerRank {
+                        account_id,
+                        rank: server_rank.fetch(account_id).await?,
+                    })
+                }));
+            }
+        }
+
         fn cmd_account_info(game_db: &mut GameDb, player_id: &PlayerId, character: &Character) {
             Self::push_account_info_task(
                 game_db,
@@ -1048,33 +1712,187 @@ pub mod state {
             )
         }
 
-        fn handle_chat_commands(&mut self, player_id: &PlayerId, cmds: Vec<CommandType>) {
-            let Some(server_player) = self.game.players.player(player_id) else {
-                return;
-            };
-            let Some(character) = self
+        fn cmd_ready(&mut self, player_id: &PlayerId, stage_id: &StageId) {
+            let Some(ready) = self
                 .game
                 .stages
-                .get(&server_player.stage_id())
-                .and_then(|stage| stage.world.characters.get(player_id))
+                .get_mut(stage_id)
+                .and_then(|stage| stage.match_manager.toggle_ready(player_id))
             else {
                 return;
             };
-            for cmd in cmds {
-                match cmd {
-                    CommandType::Full(cmd) => {
-                        match cmd.ident.as_str() {
-                            "account_info" => {
-                                Self::cmd_account_info(&mut self.game_db, player_id, character);
-                            }
-                            _ => {
-                                // TODO: send command not found text
-                            }
+            let events = self.player_events.entry(*player_id).or_default();
+            events.push(GameWorldEvent::Notification(
+                GameWorldNotificationEvent::System(GameWorldSystemMessage::Custom({
+                    let mut s = self.game_pools.mt_network_string_common_pool.new();
+                    s.try_set(if ready {
+                        "You are ready."
+                    } else {
+                        "You are not ready anymore."
+                    })
+                    .unwrap();
+                    s
+                })),
+            ));
+        }
+
+        fn player_system_msg(&mut self, player_id: &PlayerId, msg: &str) {
+            let events = self.player_events.entry(*player_id).or_default();
+            events.push(GameWorldEvent::Notification(
+                GameWorldNotificationEvent::System(GameWorldSystemMessage::Custom({
+                    let mut s = self.game_pools.mt_network_string_common_pool.new();
+                    s.try_set(msg).unwrap();
+                    s
+                })),
+            ));
+        }
+
+        fn cmd_practice(&mut self, player_id: &PlayerId, stage_id: &StageId) {
+            let Some(stage) = self.game.stages.get_mut(stage_id) else {
+                return;
+            };
+            let players = stage
+                .world
+                .characters
+                .iter()
+                .filter(|(_, character)| !character.player_info.is_bot)
+                .map(|(id, _)| id);
+            match stage.practice.vote(player_id, players) {
+                PracticeVote::Voted { votes, needed } => {
+                    self.player_system_msg(
+                        player_id,
+                        &format!(
+                            "You voted for practice mode ({votes}/{needed}). \
+                            All players of the team have to agree."
+                        ),
+                    );
+                }
+                PracticeVote::Enabled => {
+                    stage.game_pending_events.push(GameWorldEvent::Notification(
+                        GameWorldNotificationEvent::System(GameWorldSystemMessage::Custom({
+                            let mut s = self.game_pools.mt_network_string_common_pool.new();
+                            s.try_set(
+                                "Practice mode enabled for your team, \
+                                    this round does not count. \
+                                    Use /savepos & /loadpos.",
+                            )
+                            .unwrap();
+                            s
+                        })),
+                    ));
+                }
+                PracticeVote::AlreadyEnabled => {
+                    self.player_system_msg(
+                        player_id,
+                        "Practice mode is already enabled until the next round.",
+                    );
+                }
+            }
+        }
+
+        fn cmd_save_pos(&mut self, player_id: &PlayerId, stage_id: &StageId) {
+            let Some(stage) = self.game.stages.get_mut(stage_id) else {
+                return;
+            };
+            let Some(character) = stage.world.characters.get(player_id) else {
+                return;
+            };
+            let msg = if !stage.practice.is_enabled() {
+                "Saving positions requires practice mode, see /practice."
+            } else if stage.practice.save_pos(player_id, character) {
+                "Position saved."
+            } else {
+                "You can only save your position while alive."
+            };
+            self.player_system_msg(player_id, msg);
+        }
+
+        fn cmd_load_pos(&mut self, player_id: &PlayerId, stage_id: &StageId) {
+            let Some(stage) = self.game.stages.get_mut(stage_id) else {
+                return;
+            };
+            let Some(character) = stage.world.characters.get_mut(player_id) else {
+                return;
+            };
+            let msg = if !stage.practice.is_enabled() {
+                "Loading positions requires practice mode, see /practice."
+            } else if stage.practice.load_pos(player_id, character) {
+                if let Some((_, anti_cheat)) = self.anti_cheat.get_mut(player_id) {
+                    anti_cheat.skip_next();
+                }
+                "Position loaded."
+            } else {
+                "No saved position, or you are not alive."
+            };
+            self.player_system_msg(player_id, msg);
+        }
+
+        fn handle_chat_commands(&mut self, player_id: &PlayerId, cmds: Vec<CommandType>) {
+            let stage_id = self
+                .game
+                .players
+                .player(player_id)
+                .map(|server_player| server_player.stage_id());
+            let playing = stage_id.is_some_and(|stage_id| {
+                self.game
+                    .stages
+                    .get(&stage_id)
+                    .is_some_and(|stage| stage.world.characters.contains_key(player_id))
+            });
+            for cmd in self.chat_registry.validate(cmds) {
+                let (chat_cmd, cmd) = match cmd {
+                    Ok(cmd) => cmd,
+                    Err(msg) => {
+                        self.player_system_msg(player_id, &msg);
+                        continue;
+                    }
+                };
+                let permission = self
+                    .chat_registry
+                    .by_ident(&cmd.ident)
+                    .map(|cmd| cmd.permission)
+                    .unwrap_or(ChatCommandPermission::Playing);
+                let stage_id = match stage_id {
+                    Some(stage_id) if playing => stage_id,
+                    _ if permission == ChatCommandPermission::Everyone => self.stage_0_id,
+                    _ => {
+                        self.player_system_msg(
+                            player_id,
+                            &format!("/{} can only be used while playing.", cmd.ident),
+                        );
+                        continue;
+                    }
+                };
+                match chat_cmd {
+                    VanillaChatCommand::Help => {
+                        let arg = cmd.args.first().and_then(|(arg, _)| match arg {
+                            Syn::Text(text) => Some(text.as_str()),
+                            _ => None,
+                        });
+                        for msg in self.chat_registry.help(arg) {
+                            self.player_system_msg(player_id, &msg);
                         }
                     }
-                    CommandType::Partial(_) => {
-                        // TODO: ignore for now
-                        // send back feedback to user
+                    VanillaChatCommand::AccountInfo => {
+                        let character = self
+                            .game
+                            .stages
+                            .get(&stage_id)
+                            .and_then(|stage| stage.world.characters.get(player_id))
+                            .unwrap();
+                        Self::cmd_account_info(&mut self.game_db, player_id, character);
+                    }
+                    VanillaChatCommand::Ready => {
+                        self.cmd_ready(player_id, &stage_id);
+                    }
+                    VanillaChatCommand::Practice => {
+                        self.cmd_practice(player_id, &stage_id);
+                    }
+                    VanillaChatCommand::SavePos => {
+                        self.cmd_save_pos(player_id, &stage_id);
+                    }
+                    VanillaChatCommand::LoadPos => {
+                        self.cmd_load_pos(player_id, &stage_id);
                     }
                 }
             }
@@ -1083,11 +1901,19 @@ pub mod state {
         fn handle_full_command(
             &mut self,
             player_id: Option<&PlayerId>,
+            auth: AuthLevel,
             mut cmd: parser::Command,
         ) -> anyhow::Result<String> {
             let Some(chain_cmd) = self.rcon_chain.by_ident(&cmd.ident) else {
                 return Err(anyhow!("Rcon command {} was not found", cmd.ident));
             };
+            if auth < chain_cmd.cmd.auth_level() {
+                return Err(anyhow!(
+                    "Rcon command {} requires {:?} rights",
+                    cmd.ident,
+                    chain_cmd.cmd.auth_level()
+                ));
+            }
 
             match chain_cmd.cmd {
                 VanillaRconCommand::Info => {
@@ -1159,6 +1985,108 @@ pub mod state {
                         }
                     }
                 },
+                VanillaRconCommand::Bots(bots) => match bots {
+                    VanillaRconCommandBots::Add => {
+                        let Some(Syn::Text(mode)) = cmd.args.pop().map(|(name, _)| name) else {
+                            panic!("Expected a text, this is an implementation bug");
+                        };
+                        let Some(Syn::Number(count)) = cmd.args.pop().map(|(name, _)| name) else {
+                            panic!("Expected a number, this is an implementation bug");
+                        };
+                        let count: usize = count.parse()?;
+                        let mode = match mode.as_str() {
+                            "patrol" => self.bot_patrol_mode(),
+                            _ => BotMode::Idle,
+                        };
+                        for _ in 0..count {
+                            self.add_bot(mode.clone());
+                        }
+                        Ok(format!("Added {count} bot(s)."))
+                    }
+                    VanillaRconCommandBots::Mirror => {
+                        let Some(Syn::Number(delay_ticks)) = cmd.args.pop().map(|(name, _)| name)
+                        else {
+                            panic!("Expected a number, this is an implementation bug");
+                        };
+                        let Some(Syn::Number(target)) = cmd.args.pop().map(|(name, _)| name) else {
+                            panic!("Expected a number, this is an implementation bug");
+                        };
+                        let target = PlayerId::from(target.parse::<IdGeneratorIdType>()?);
+                        if self.game.players.player(&target).is_none() {
+                            return Err(anyhow!("The given player was not found in this game"));
+                        }
+                        let id = self.add_bot(BotMode::Mirror {
+                            target,
+                            delay_ticks: delay_ticks.parse()?,
+                        });
+                        Ok(format!("Added bot {id} mirroring player {target}."))
+                    }
+                    VanillaRconCommandBots::RemoveAll => {
+                        let ids = self.bots.ids();
+                        for id in &ids {
+                            self.player_drop(id, PlayerDropReason::Disconnect);
+                        }
+                        Ok(format!("Removed {} bot(s).", ids.len()))
+                    }
+                },
+                VanillaRconCommand::Bans(bans) => match bans {
+                    VanillaRconCommandBans::Ban | VanillaRconCommandBans::Mute => {
+                        let Some(Syn::Text(reason)) = cmd.args.pop().map(|(name, _)| name) else {
+                            panic!("Expected a text, this is an implementation bug");
+                        };
+                        let Some(Syn::Number(mins)) = cmd.args.pop().map(|(name, _)| name) else {
+                            panic!("Expected a number, this is an implementation bug");
+                        };
+                        let Some(Syn::Text(target)) = cmd.args.pop().map(|(name, _)| name) else {
+                            panic!("Expected a text, this is an implementation bug");
+                        };
+                        let kind = match bans {
+                            VanillaRconCommandBans::Ban => BanKind::Ban,
+                            _ => BanKind::Mute,
+                        };
+                        let target: BanTarget = target.parse()?;
+                        let mins: u64 = mins.parse()?;
+                        let duration = (mins > 0).then(|| Duration::from_secs(mins * 60));
+                        self.add_ban(kind, target, duration, reason);
+                        Ok(format!(
+                            "Added {kind} of {target} {}.",
+                            fmt_remaining(duration)
+                        ))
+                    }
+                    VanillaRconCommandBans::Unban | VanillaRconCommandBans::Unmute => {
+                        let Some(Syn::Text(target)) = cmd.args.pop().map(|(name, _)| name) else {
+                            panic!("Expected a text, this is an implementation bug");
+                        };
+                        let kind = match bans {
+                            VanillaRconCommandBans::Unban => BanKind::Ban,
+                            _ => BanKind::Mute,
+                        };
+                        let target: BanTarget = target.parse()?;
+                        if self.remove_ban(kind, target) {
+                            Ok(format!("Removed {kind} of {target}."))
+                        } else {
+                            Err(anyhow!("There is no {kind} of {target}."))
+                        }
+                    }
+                    VanillaRconCommandBans::List => {
+                        let list: Vec<_> = self
+                            .bans
+                            .entries()
+                            .map(|(kind, target, entry)| {
+                                format!(
+                                    "{kind} {target} {}: {}",
+                                    fmt_remaining(self.bans.remaining(entry)),
+                                    entry.reason
+                                )
+                            })
+                            .collect();
+                        if list.is_empty() {
+                            Ok("No bans or mutes.".to_string())
+                        } else {
+                            Ok(list.join("\n"))
+                        }
+                    }
+                },
                 VanillaRconCommand::ConfVariable => {
                     let mut config = ConfigVanillaWrapper {
                         vanilla: self.game_options.config_clone(),
@@ -1167,6 +2095,7 @@ pub mod state {
                         .map(|msg| format!("Updated value for {}: {}", cmd.cmd_text, msg))
                     {
                         Ok(res) => {
+                            SpawnLoadout::new(&config.vanilla)?;
                             self.game_options.replace_conf(config.vanilla);
                             Ok(res)
                         }
@@ -1179,14 +2108,14 @@ pub mod state {
         fn handle_rcon_commands(
             &mut self,
             player_id: Option<&PlayerId>,
-            _auth: AuthLevel,
+            auth: AuthLevel,
             cmds: Vec<CommandType>,
         ) -> Vec<Result<NetworkString<65536>, NetworkString<65536>>> {
             let mut res: Vec<Result<NetworkString<65536>, NetworkString<65536>>> =
                 Default::default();
             for cmd in cmds {
                 let handle_cmd = || match cmd {
-                    CommandType::Full(cmd) => self.handle_full_command(player_id, cmd),
+                    CommandType::Full(cmd) => self.handle_full_command(player_id, auth, cmd),
                     CommandType::Partial(cmd) => {
                         let Some(cmd) = cmd.ref_cmd_partial() else {
                             return Err(anyhow!("This command was invalid: {cmd}"));
@@ -1195,7 +2124,13 @@ pub mod state {
                             return Err(anyhow!("Command {} not found", cmd.ident));
                         };
 
-                        if let VanillaRconCommand::ConfVariable = chain_cmd.cmd {
+                        if auth < chain_cmd.cmd.auth_level() {
+                            Err(anyhow!(
+                                "Rcon command {} requires {:?} rights",
+                                cmd.ident,
+                                chain_cmd.cmd.auth_level()
+                            ))
+                        } else if let VanillaRconCommand::ConfVariable = chain_cmd.cmd {
                             let mut config = ConfigVanillaWrapper {
                                 vanilla: self.game_options.config_clone(),
                             };
@@ -1203,6 +2138,7 @@ pub mod state {
                                 .map(|msg| format!("Current value for {}: {}", cmd.cmd_text, msg))
                             {
                                 Ok(res) => {
+                                    SpawnLoadout::new(&config.vanilla)?;
                                     self.game_options.replace_conf(config.vanilla);
                                     Ok(res)
                                 }
@@ -1329,6 +2265,10 @@ pub mod state {
                             pos,
                             ty: prev_flag.core.ty,
                             owner_id: flag.core.carrier,
+                            return_time_left: flag
+                                .core
+                                .drop_ticks
+                                .map(|ticks| ticks as f32 / flag.core.return_ticks.max(1) as f32),
                             phased: false,
                         },
                     ))
@@ -1419,8 +2359,11 @@ pub mod state {
             let lerped_pos = character::lerp_core_pos(prev_character, character, intra_tick_ratio);
             CharacterRenderInfo {
                 lerped_pos: lerped_pos / 32.0,
+                // the velocity at the reference tick rate,
+                // which the thresholds of the renderers are made for
                 lerped_vel: character::lerp_core_vel(prev_character, character, intra_tick_ratio)
-                    / 32.0,
+                    / 32.0
+                    / tick_scale(self.game_options.ticks_per_second()),
                 lerped_hook: {
                     // try special logic for when a character is hooked first.
                     let hooked_char = prev_character.phased.hook().hooked_char();
@@ -1504,8 +2447,24 @@ pub mod state {
                                     remaining_time: None,
                                 },
                             ),
+                            CharacterBuff::SpawnProtection => (
+                                CharacterBuff::SpawnProtection,
+                                CharacterBuffInfo {
+                                    remaining_time: None,
+                                },
+                            ),
                         }
                     }));
+                    if let Some(ticks) = prev_character.core.spawn_protection.get() {
+                        buffs.insert(
+                            CharacterBuff::SpawnProtection,
+                            CharacterBuffInfo {
+                                remaining_time: Some(Duration::from_millis(
+                                    ticks.get() * 1000 / self.game_options.ticks_per_second(),
+                                )),
+                            },
+                        );
+                    }
                     buffs
                 },
                 debuffs: {
@@ -1534,6 +2493,7 @@ pub mod state {
                         .map(|tick| (tick, emoticon))
                 }),
                 phased: false,
+                practice: prev_stage.practice.is_enabled(),
             }
         }
 
@@ -1568,68 +2528,444 @@ pub mod state {
             render_infos
         }
 
-        fn game_event_to_world_event(
-            game_event: &GameWorldEvent,
-            world_events: &mut FxLinkedHashMap<EventId, GameWorldEvent>,
-            event_id_generator: &EventIdGenerator,
-        ) {
-            world_events.insert(event_id_generator.next_id(), game_event.clone());
+        fn game_event_to_world_event(
+            game_event: &GameWorldEvent,
+            world_events: &mut FxLinkedHashMap<EventId, GameWorldEvent>,
+            event_id_generator: &EventIdGenerator,
+        ) {
+            world_events.insert(event_id_generator.next_id(), game_event.clone());
+        }
+
+        fn check_stage_remove(&mut self, stage_id: StageId) {
+            if let Some(stage) = self.game.stages.get(&stage_id)
+                && stage_id != self.stage_0_id
+                && !stage
+                    .world
+                    .characters
+                    .values()
+                    .any(|c| c.is_player_character().is_some())
+            {
+                self.game.stages.remove(&stage_id);
+            }
+        }
+
+        fn add_from_spectator(
+            &mut self,
+            player_id: &PlayerId,
+            stage_id: StageId,
+            side: Option<MatchSide>,
+        ) {
+            let mut default_eyes = TeeEye::Normal;
+            let default_eyes = &mut default_eyes;
+            let mut default_eyes_reset_in = GameTickCooldown::default();
+            let default_eyes_reset_in = &mut default_eyes_reset_in;
+            if !self.game.spectator_players.handle_mut(
+                player_id,
+                hi_closure!(
+                    [
+                        default_eyes: &mut TeeEye,
+                        default_eyes_reset_in: &mut GameTickCooldown,
+                    ],
+                    |player: &mut SpectatorPlayer| -> () {
+                        *default_eyes = player.default_eye;
+                        *default_eyes_reset_in = player.default_eye_reset_in;
+                    }
+                ),
+            ) {
+                return;
+            }
+            let player = self.game.spectator_players.remove(player_id).unwrap();
+            Self::add_char_to_stage(
+                &mut self.game.stages,
+                &stage_id,
+                player_id,
+                player.player_info,
+                player.player_input,
+                self.game.players.clone(),
+                self.game.spectator_players.clone(),
+                player.network_stats,
+                side,
+                0,
+                *default_eyes,
+                *default_eyes_reset_in,
+                &self.game_pools,
+            );
+        }
+
+        fn player_join_impl(
+            &mut self,
+            client_player_info: &PlayerClientInfo,
+            is_bot: bool,
+        ) -> PlayerId {
+            if let Some((timeout_player_id, character_info)) = self
+                .game
+                .timeout_players
+                .remove(&(client_player_info.unique_identifier, client_player_info.id))
+                .and_then(|(id, _)| self.game.players.player(&id).map(|char| (id, char)))
+            {
+                let char = self
+                    .game
+                    .stages
+                    .get_mut(&character_info.stage_id())
+                    .unwrap()
+                    .world
+                    .characters
+                    .get_mut(&timeout_player_id)
+                    .unwrap();
+                char.core.is_timeout = false;
+                return timeout_player_id;
+            }
+
+            let player_id = self.id_generator.next_id();
+            let stage_0_id = self.stage_0_id;
+
+            let character_info = self.check_player_info(client_player_info.info.clone(), None);
+
+            if let Some(join_msg) = self.game_options.join_msg() {
+                // the joining player is not added yet
+                let player_count = self.human_player_count() + usize::from(!is_bot);
+                self.global_system_msg(&format_player_msg(
+                    &join_msg,
+                    character_info.name.as_str(),
+                    player_count,
+                ));
+            } else {
+                self.game
+                    .stages
+                    .get(&stage_0_id)
+                    .unwrap()
+                    .game_pending_events
+                    .push(GameWorldEvent::Notification(
+                        GameWorldNotificationEvent::System(GameWorldSystemMessage::PlayerJoined {
+                            id: player_id,
+                            name: {
+                                let mut s = self.game_pools.mt_network_string_name_pool.new();
+                                s.try_set(character_info.name.as_str()).unwrap();
+                                s
+                            },
+                            skin: {
+                                let mut skin = self.game_pools.mt_resource_key_pool.new();
+                                (*skin).clone_from(&character_info.skin);
+                                skin
+                            },
+                            skin_info: character_info.skin_info,
+                        }),
+                    ));
+            }
+
+            if client_player_info.id == 0
+                && !is_bot
+                && let Some(motd) = self.game_options.motd()
+            {
+                let mut msg = self.game_pools.mt_network_string_common_pool.new();
+                if msg.try_set(motd).is_ok() {
+                    let events = self.player_events.entry(player_id).or_default();
+                    events.push(GameWorldEvent::Notification(
+                        GameWorldNotificationEvent::Motd { msg },
+                    ));
+                }
+            }
+
+            let player_info =
+                PlayerInfo {
+                    player_info: PoolRc::from_item_without_pool(character_info),
+                    version: 0,
+                    unique_identifier: client_player_info.unique_identifier,
+                    account_name: None,
+                    id: client_player_info.id,
+                    is_bot,
+                    server_rank: client_player_info.unique_identifier.is_account_then(
+                        |account_id| self.game_db.server_ranks.get(&account_id).copied(),
+                    ),
+                };
+            if self
+                .game
+                .stages
+                .get(&self.stage_0_id)
+                .unwrap()
+                .world
+                .characters
+                .len()
+                < self.game_options.max_ingame_players() as usize
+            {
+                // spawn and send character info
+                let default_eyes = player_info.player_info.default_eyes;
+                Self::add_char_to_stage(
+                    &mut self.game.stages,
+                    &stage_0_id,
+                    &player_id,
+                    player_info,
+                    Default::default(),
+                    self.game.players.clone(),
+                    self.game.spectator_players.clone(),
+                    client_player_info.initial_network_stats,
+                    None,
+                    0,
+                    default_eyes,
+                    Default::default(),
+                    &self.game_pools,
+                );
+            } else {
+                self.game.spectator_players.insert(
+                    player_id,
+                    SpectatorPlayer::new(
+                        player_info,
+                        Default::default(),
+                        &player_id,
+                        self.game_pools.character_id_hashset_pool.new(),
+                        client_player_info.info.default_eyes,
+                        Default::default(),
+                        client_player_info.initial_network_stats,
+                    ),
+                );
+            }
+
+            Self::push_account_info_task(
+                &mut self.game_db,
+                &player_id,
+                &client_player_info.unique_identifier,
+            );
+            if let PlayerUniqueId::Account(account_id) = client_player_info.unique_identifier {
+                Self::push_server_rank_task(&mut self.game_db, account_id);
+            }
+
+            player_id
+        }
+
+        fn bot_patrol_mode(&self) -> BotMode {
+            BotMode::Patrol {
+                waypoints: self
+                    .spawns
+                    .spawns
+                    .iter()
+                    .chain(self.spawns.spawns_red.iter())
+                    .chain(self.spawns.spawns_blue.iter())
+                    .copied()
+                    .collect(),
+            }
+        }
+
+        /// Adds a server-side bot, that joins like a normal player.
+        pub fn add_bot(&mut self, mode: BotMode) -> PlayerId {
+            let mut info = NetworkCharacterInfo::explicit_default();
+            info.name = "bot".try_into().unwrap();
+            let player_id = self.player_join_impl(
+                &PlayerClientInfo {
+                    info,
+                    id: self.bots.len() as u64 + 1,
+                    unique_identifier: PlayerUniqueId::CertFingerprint(Default::default()),
+                    initial_network_stats: Default::default(),
+                    ip: None,
+                },
+                true,
+            );
+            self.bots.add(player_id, mode);
+            player_id
+        }
+
+        /// Spawns the bots of the config once the first player joined.
+        fn spawn_config_bots(&mut self) {
+            if self.config_bots_spawned {
+                return;
+            }
+            self.config_bots_spawned = true;
+
+            let config = self.game_options.config_clone();
+            let mode = match config.bot_mode {
+                ConfigBotMode::Idle => BotMode::Idle,
+                ConfigBotMode::Patrol => self.bot_patrol_mode(),
+            };
+            for _ in 0..config.bot_count {
+                self.add_bot(mode.clone());
+            }
+        }
+
+        fn bots_tick(&mut self) {
+            if self.bots.is_empty() {
+                return;
+            }
+            let game = &self.game;
+            let character = |id: &PlayerId| {
+                game.players.player(id).and_then(|player| {
+                    game.stages
+                        .get(&player.stage_id())
+                        .and_then(|stage| stage.world.characters.get(id))
+                })
+            };
+            let inps = self.bots.tick(
+                |id| character(id).map(|character| character.core.input),
+                |id| character(id).map(|character| *character.pos.pos()),
+            );
+            for (player_id, CharacterInputInfo { inp, diff }) in inps {
+                self.set_player_inp_impl(&player_id, &inp, diff);
+            }
+        }
+
+        /// The characters whose attacker-scoped events (e.g. hits) are sent
+        /// to the given players: their own characters and the characters they spectate.
+        fn event_viewed_characters<'a>(
+            &self,
+            player_ids: impl Iterator<Item = &'a PlayerId>,
+        ) -> FxHashSet<CharacterId> {
+            let mut ids: FxHashSet<CharacterId> = Default::default();
+            for player_id in player_ids {
+                ids.insert(*player_id);
+                if let Some(player) = self.game.players.player(player_id) {
+                    let Some(character) = self
+                        .game
+                        .stages
+                        .get(&player.stage_id())
+                        .and_then(|stage| stage.world.characters.get(player_id))
+                    else {
+                        continue;
+                    };
+                    let spectate_mode = match &character.phased {
+                        character::CharacterPhasedState::Normal(normal) => {
+                            normal.ingame_spectate.as_ref()
+                        }
+                        character::CharacterPhasedState::PhasedSpectate(mode) => Some(mode),
+                        character::CharacterPhasedState::Dead(_) => None,
+                    };
+                    if let Some(CharacterSpectateMode::Follows { ids: follows, .. }) = spectate_mode
+                    {
+                        ids.extend(follows.iter().copied());
+                    }
+                } else {
+                    self.game
+                        .spectator_players
+                        .spectated_characters_into(player_id, &mut ids);
+                }
+            }
+            ids
         }
 
-        fn check_stage_remove(&mut self, stage_id: StageId) {
-            if let Some(stage) = self.game.stages.get(&stage_id)
-                && stage_id != self.stage_0_id
-                && !stage
-                    .world
-                    .characters
-                    .values()
-                    .any(|c| c.is_player_character().is_some())
-            {
-                self.game.stages.remove(&stage_id);
-            }
+        fn view_half_size(inp: &CharacterInput) -> vec2 {
+            let viewport = inp.viewport.to_vec2();
+            vec2::new(
+                (viewport.x as f32).min(MAX_VIEW_SIZE.0) / 2.0 + VIEW_MARGIN,
+                (viewport.y as f32).min(MAX_VIEW_SIZE.1) / 2.0 + VIEW_MARGIN,
+            ) * 32.0
         }
 
-        fn add_from_spectator(
-            &mut self,
-            player_id: &PlayerId,
-            stage_id: StageId,
-            side: Option<MatchSide>,
+        /// In a free camera mode the cursor is the camera position.
+        fn free_camera_pos(inp: &CharacterInput) -> vec2 {
+            let pos = inp.cursor.to_vec2();
+            vec2::new(pos.x as f32, pos.y as f32) * 32.0
+        }
+
+        fn push_follow_views<'a>(
+            &self,
+            ids: impl Iterator<Item = &'a CharacterId>,
+            half_size: vec2,
+            views: &mut Vec<SnapshotView>,
         ) {
-            let mut default_eyes = TeeEye::Normal;
-            let default_eyes = &mut default_eyes;
-            let mut default_eyes_reset_in = GameTickCooldown::default();
-            let default_eyes_reset_in = &mut default_eyes_reset_in;
-            if !self.game.spectator_players.handle_mut(
-                player_id,
-                hi_closure!(
-                    [
-                        default_eyes: &mut TeeEye,
-                        default_eyes_reset_in: &mut GameTickCooldown,
-                    ],
-                    |player: &mut SpectatorPlayer| -> () {
-                        *default_eyes = player.default_eye;
-                        *default_eyes_reset_in = player.default_eye_reset_in;
+            for id in ids {
+                let Some(stage_id) = self.game.players.player(id).map(|p| p.stage_id()) else {
+                    continue;
+                };
+                if let Some(character) = self
+                    .game
+                    .stages
+                    .get(&stage_id)
+                    .and_then(|stage| stage.world.characters.get(id))
+                {
+                    views.push(SnapshotView {
+                        stage_id,
+                        center: *character.pos.pos(),
+                        half_size,
+                    });
+                }
+            }
+        }
+
+        /// The views of the given players, which limit what is snapped to them.
+        /// Players see the surroundings of their character,
+        /// spectators either their free camera or the characters they follow.
+        ///
+        /// Returns `None` if neither view culling nor interest management is enabled.
+        pub(crate) fn snapshot_view_filter<'a>(
+            &self,
+            player_ids: impl Iterator<Item = &'a PlayerId>,
+        ) -> Option<SnapshotViewFilter> {
+            let interest = self
+                .game_options
+                .interest_radius()
+                .map(|(radius, hysteresis)| SnapshotInterestRadius {
+                    radius: radius as f32 * 32.0,
+                    hysteresis: hysteresis as f32 * 32.0,
+                });
+            if !self.game_options.view_culling() && interest.is_none() {
+                return None;
+            }
+            let mut filter = SnapshotViewFilter {
+                cull_to_views: self.game_options.view_culling(),
+                interest,
+                ..Default::default()
+            };
+            for player_id in player_ids {
+                filter.always_snapped.insert(*player_id);
+                if let Some(player) = self.game.players.player(player_id) {
+                    let stage_id = player.stage_id();
+                    let Some(character) = self
+                        .game
+                        .stages
+                        .get(&stage_id)
+                        .and_then(|stage| stage.world.characters.get(player_id))
+                    else {
+                        continue;
+                    };
+                    let inp = &character.core.input;
+                    let half_size = Self::view_half_size(inp);
+                    let spectate_mode = match &character.phased {
+                        character::CharacterPhasedState::Normal(normal) => {
+                            normal.ingame_spectate.as_ref()
+                        }
+                        character::CharacterPhasedState::PhasedSpectate(mode) => Some(mode),
+                        character::CharacterPhasedState::Dead(_) => None,
+                    };
+                    match spectate_mode {
+                        Some(CharacterSpectateMode::Follows { ids, .. }) => {
+                            self.push_follow_views(ids.iter(), half_size, &mut filter.views);
+                        }
+                        Some(CharacterSpectateMode::Free(_)) => {
+                            filter.views.push(SnapshotView {
+                                stage_id,
+                                center: Self::free_camera_pos(inp),
+                                half_size,
+                            });
+                        }
+                        None => {
+                            let offset = inp.dyn_cam_offset.to_vec2();
+                            filter.views.push(SnapshotView {
+                                stage_id,
+                                center: *character.pos.pos()
+                                    + vec2::new(offset.x as f32, offset.y as f32) * 32.0,
+                                half_size,
+                            });
+                        }
                     }
-                ),
-            ) {
-                return;
+                } else if let Some(inp) = self.game.spectator_players.player_input(player_id) {
+                    let half_size = Self::view_half_size(&inp);
+                    let mut ids: FxHashSet<CharacterId> = Default::default();
+                    self.game
+                        .spectator_players
+                        .spectated_characters_into(player_id, &mut ids);
+                    if ids.is_empty() {
+                        filter.views.push(SnapshotView {
+                            stage_id: self.stage_0_id,
+                            center: Self::free_camera_pos(&inp),
+                            half_size,
+                        });
+                    } else {
+                        self.push_follow_views(ids.iter(), half_size, &mut filter.views);
+                    }
+                }
             }
-            let player = self.game.spectator_players.remove(player_id).unwrap();
-            Self::add_char_to_stage(
-                &mut self.game.stages,
-                &stage_id,
-                player_id,
-                player.player_info,
-                player.player_input,
-                self.game.players.clone(),
-                self.game.spectator_players.clone(),
-                player.network_stats,
-                side,
-                0,
-                *default_eyes,
-                *default_eyes_reset_in,
-                &self.game_pools,
-            );
+            Some(filter)
+        }
+
+        fn is_excluded_bot_vote(&self, player_id: &PlayerId) -> bool {
+            self.game_options.bots_exclude_from_votes() && self.bots.is_bot(player_id)
         }
 
         fn check_player_info(
@@ -1834,6 +3170,8 @@ pub mod state {
                                 player_info: is_player,
                                 browser_score: score,
                                 browser_eye: TeeEye::Normal,
+                                is_bot: info.is_bot,
+                                server_rank: info.server_rank,
                                 account_name: info.account_name.as_ref().map(|account_name| {
                                     let mut name =
                                         self.game_pools.network_string_account_name_pool.new();
@@ -2020,6 +3358,8 @@ pub mod state {
 
             for (stage_id, prev_stage) in self.prev_game.stages.iter() {
                 let stage = self.game.stages.get(stage_id);
+                let unbalanced = self.game_options.sided_balance_enabled()
+                    && MatchManager::needs_sided_balance(&prev_stage.world);
 
                 stages.insert(
                     *stage_id,
@@ -2089,8 +3429,15 @@ pub mod state {
                                 .game_match
                                 .state
                                 .round_ticks_left(&prev_stage.world, &self.game_pools),
-                            unbalanced: self.game_options.sided_balance_time().is_some()
-                                && MatchManager::needs_sided_balance(&prev_stage.world),
+                            unbalanced,
+                            balance_suggestion: if unbalanced {
+                                prev_stage
+                                    .match_manager
+                                    .sided_balance_plan(&prev_stage.world)
+                            } else {
+                                Vec::new()
+                            },
+                            balance_at_round_end: self.game_options.sided_balance_at_round_end(),
                         },
                         game_ticks_passed: prev_stage.match_manager.game_match.state.passed_ticks(),
                     },
@@ -2123,6 +3470,15 @@ pub mod state {
                         .weapons
                         .get(&player_char.core.active_weapon)
                         .and_then(|w| w.cur_ammo),
+                    fire_delay: match player_char.core.attack_recoil {
+                        GameTickCooldownAndLastActionCounter::Cooldown {
+                            ticks_left,
+                            initial_cooldown_len,
+                            ..
+                        } => Some((ticks_left.get(), initial_cooldown_len)),
+                        GameTickCooldownAndLastActionCounter::None
+                        | GameTickCooldownAndLastActionCounter::LastActionCounter { .. } => None,
+                    },
                 })
             } else {
                 // spectators get nothing
@@ -2136,122 +3492,50 @@ pub mod state {
         }
 
         fn player_join(&mut self, client_player_info: &PlayerClientInfo) -> PlayerId {
-            if let Some((timeout_player_id, character_info)) = self
-                .game
-                .timeout_players
-                .remove(&(client_player_info.unique_identifier, client_player_info.id))
-                .and_then(|(id, _)| self.game.players.player(&id).map(|char| (id, char)))
-            {
-                let char = self
-                    .game
-                    .stages
-                    .get_mut(&character_info.stage_id())
-                    .unwrap()
-                    .world
-                    .characters
-                    .get_mut(&timeout_player_id)
-                    .unwrap();
-                char.core.is_timeout = false;
-                return timeout_player_id;
-            }
-
-            let player_id = self.id_generator.next_id();
-            let stage_0_id = self.stage_0_id;
-
-            let character_info = self.check_player_info(client_player_info.info.clone(), None);
-
-            self.game
-                .stages
-                .get(&stage_0_id)
-                .unwrap()
-                .game_pending_events
-                .push(GameWorldEvent::Notification(
-                    GameWorldNotificationEvent::System(GameWorldSystemMessage::PlayerJoined {
-                        id: player_id,
-                        name: {
-                            let mut s = self.game_pools.mt_network_string_name_pool.new();
-                            s.try_set(character_info.name.as_str()).unwrap();
-                            s
-                        },
-                        skin: {
-                            let mut skin = self.game_pools.mt_resource_key_pool.new();
-                            (*skin).clone_from(&character_info.skin);
-                            skin
-                        },
-                        skin_info: character_info.skin_info,
-                    }),
-                ));
-
-            if client_player_info.id == 0 {
-                let events = self.player_events.entry(player_id).or_default();
-
-                let mut msg = self.game_pools.mt_network_string_common_pool.new();
-                msg.try_set("alpha version vanilla.").unwrap();
-
-                events.push(GameWorldEvent::Notification(
-                    GameWorldNotificationEvent::Motd { msg },
-                ));
-            }
-
-            let player_info = PlayerInfo {
-                player_info: PoolRc::from_item_without_pool(character_info),
-                version: 0,
-                unique_identifier: client_player_info.unique_identifier,
-                account_name: None,
-                id: client_player_info.id,
-            };
-            if self
-                .game
-                .stages
-                .get(&self.stage_0_id)
-                .unwrap()
-                .world
-                .characters
-                .len()
-                < self.game_options.max_ingame_players() as usize
-            {
-                // spawn and send character info
-                let default_eyes = player_info.player_info.default_eyes;
-                Self::add_char_to_stage(
-                    &mut self.game.stages,
-                    &stage_0_id,
-                    &player_id,
-                    player_info,
-                    Default::default(),
-                    self.game.players.clone(),
-                    self.game.spectator_players.clone(),
-                    client_player_info.initial_network_stats,
-                    None,
-                    0,
-                    default_eyes,
-                    Default::default(),
-                    &self.game_pools,
-                );
-            } else {
-                self.game.spectator_players.insert(
+            let player_id = self.player_join_impl(client_player_info, false);
+            if self.game_options.anti_cheat_action() != ConfigAntiCheatAction::Off {
+                self.anti_cheat.insert(
                     player_id,
-                    SpectatorPlayer::new(
-                        player_info,
-                        Default::default(),
-                        &player_id,
-                        self.game_pools.character_id_hashset_pool.new(),
-                        client_player_info.info.default_eyes,
-                        Default::default(),
-                        client_player_info.initial_network_stats,
-                    ),
+                    (client_player_info.unique_identifier, Default::default()),
                 );
             }
-
-            Self::push_account_info_task(
-                &mut self.game_db,
-                &player_id,
-                &client_player_info.unique_identifier,
+            self.ban_states.insert(
+                player_id,
+                PlayerBanState {
+                    identity: BanIdentity {
+                        account_id: match client_player_info.unique_identifier {
+                            PlayerUniqueId::Account(account_id) => Some(account_id),
+                            PlayerUniqueId::CertFingerprint(_) => None,
+                        },
+                        ip: client_player_info.ip,
+                    },
+                    ..Default::default()
+                },
             );
-
+            self.spawn_config_bots();
             player_id
         }
 
         fn player_drop(&mut self, player_id: &PlayerId, reason: PlayerDropReason) {
+            self.bots.remove(player_id);
+            self.anti_cheat.remove(player_id);
+            if let Some(state) = self.ban_states.remove(player_id)
+                && let PlayerDropReason::Banned {
+                    reason: PlayerBanReason::Vote,
+                    ..
+                } = &reason
+                && let Some(duration) = self.game_options.vote_kick_ban()
+                && let Some(target) = state.identity.target()
+            {
+                self.add_ban(
+                    BanKind::Ban,
+                    target,
+                    Some(duration),
+                    "Kicked by vote".to_string(),
+                );
+            }
+            self.snap_shot_manager.remove_interest(player_id);
+
             let name = if let Some(server_player) = self.game.players.player(player_id) {
                 let stage = self.game.stages.get_mut(&server_player.stage_id()).unwrap();
 
@@ -2268,6 +3552,10 @@ pub mod state {
                 let skin_info = character.player_info.player_info.skin_info;
 
                 character.despawn_completely_silent();
+                stage
+                    .match_manager
+                    .stats
+                    .mark_disconnected(&stage.world, player_id);
                 stage.world.characters.remove(player_id);
 
                 Some((name, skin, skin_info, server_player.stage_id()))
@@ -2286,20 +3574,28 @@ pub mod state {
             };
 
             if let Some((name, skin, skin_info, stage_id)) = name {
-                let stage = self.game.stages.get(&stage_id).unwrap();
-                stage.game_pending_events.push(GameWorldEvent::Notification(
-                    GameWorldNotificationEvent::System(GameWorldSystemMessage::PlayerLeft {
-                        id: *player_id,
-                        name: {
-                            let mut s = self.game_pools.mt_network_string_name_pool.new();
-                            s.try_set(name.as_str()).unwrap();
-                            s
-                        },
-                        skin,
-                        skin_info,
-                        reason,
-                    }),
-                ));
+                if let Some(leave_msg) = self.game_options.leave_msg() {
+                    self.global_system_msg(&format_player_msg(
+                        &leave_msg,
+                        name.as_str(),
+                        self.human_player_count(),
+                    ));
+                } else {
+                    let stage = self.game.stages.get(&stage_id).unwrap();
+                    stage.game_pending_events.push(GameWorldEvent::Notification(
+                        GameWorldNotificationEvent::System(GameWorldSystemMessage::PlayerLeft {
+                            id: *player_id,
+                            name: {
+                                let mut s = self.game_pools.mt_network_string_name_pool.new();
+                                s.try_set(name.as_str()).unwrap();
+                                s
+                            },
+                            skin,
+                            skin_info,
+                            reason,
+                        }),
+                    ));
+                }
 
                 self.check_stage_remove(stage_id);
             }
@@ -2479,6 +3775,43 @@ pub mod state {
             }
         }
 
+        /// Updates the rank of all players of the account.
+        fn server_rank_changed(
+            &mut self,
+            account_id: AccountId,
+            rank: Option<CharacterServerRank>,
+        ) {
+            let is_account = |unique_identifier: &PlayerUniqueId| {
+                unique_identifier
+                    .is_account_then(|char_account_id| {
+                        (char_account_id == account_id).then_some(true)
+                    })
+                    .unwrap_or_default()
+            };
+
+            let mut players = self.player_clone_pool.new();
+            self.game.players.pooled_clone_into(&mut players);
+
+            for (player_id, char_info) in players.drain(..) {
+                let stage = self.game.stages.get_mut(&char_info.stage_id()).unwrap();
+                let character = stage.world.characters.get_mut(&player_id).unwrap();
+
+                if is_account(&character.player_info.unique_identifier) {
+                    character.player_info.server_rank = rank;
+                }
+            }
+
+            let mut players = self.spectator_player_clone_pool.new();
+            self.game.spectator_players.pooled_clone_into(&mut players);
+
+            for (player_id, mut player) in players.drain() {
+                if is_account(&player.player_info.unique_identifier) {
+                    player.player_info.server_rank = rank;
+                    self.game.spectator_players.insert(player_id, player);
+                }
+            }
+        }
+
         fn network_stats(&mut self, mut stats: PoolFxLinkedHashMap<PlayerId, PlayerNetworkStats>) {
             let mut players = self.player_clone_pool.new();
             self.game.players.pooled_clone_into(&mut players);
@@ -2522,6 +3855,13 @@ pub mod state {
             }
         }
 
+        fn player_handover(&self, _player_id: &PlayerId) -> Option<Vec<u8>> {
+            // teams & spectators are restored by the server
+            None
+        }
+
+        fn apply_player_handover(&mut self, _player_id: &PlayerId, _handover: Vec<u8>) {}
+
         fn settings(&self) -> GameStateSettings {
             GameStateSettings {
                 max_ingame_players: self.game_options.max_ingame_players(),
@@ -2547,7 +3887,7 @@ pub mod state {
                 ClientCommand::Chat(cmd) => {
                     let cmds = command_parser::parser::parse(
                         &cmd.raw,
-                        &self.chat_commands.cmds,
+                        &self.chat_registry.parser,
                         &self.cache,
                     );
                     self.handle_chat_commands(player_id, cmds);
@@ -2638,13 +3978,33 @@ pub mod state {
                 }
                 ClientCommand::JoinSide(side) => {
                     if Self::is_sided_from_conf(self.game_options.game_ty()) {
-                        if let Some(player) = self.game.players.player(player_id) {
+                        let player = self.game.players.player(player_id);
+                        let stage_id = player
+                            .as_ref()
+                            .map(|player| player.stage_id())
+                            .unwrap_or(self.stage_0_id);
+                        let world = &self.game.stages.get(&stage_id).unwrap().world;
+                        let (red, blue) = world.count_sides();
+                        let own_side = world
+                            .characters
+                            .get(player_id)
+                            .and_then(|character| character.core.side);
+                        if let Err(err) = can_join_side(
+                            side,
+                            own_side,
+                            red,
+                            blue,
+                            self.game_options.sided_balance_enabled(),
+                        ) {
+                            self.player_system_msg(player_id, &err.to_string());
+                        } else if let Some(player) = player {
                             let stage = self.game.stages.get_mut(&player.stage_id()).unwrap();
                             if let Some(character) = stage.world.characters.get_mut(player_id)
                                 && character.core.side != Some(side)
                             {
                                 character.despawn_to_respawn(true);
                                 character.core.side = Some(side);
+                                character.core.side_ticks = 0;
                             }
                         } else {
                             self.add_from_spectator(player_id, self.stage_0_id, Some(side));
@@ -2706,7 +4066,8 @@ pub mod state {
         fn vote_command(&mut self, cmd: VoteCommand) -> VoteCommandResult {
             match cmd {
                 VoteCommand::JoinSpectator(player_id) => {
-                    if let Some(player) = self.game.players.player(&player_id)
+                    if !self.is_excluded_bot_vote(&player_id)
+                        && let Some(player) = self.game.players.player(&player_id)
                         && let Some(mut character) = self
                             .game
                             .stages
@@ -2734,7 +4095,8 @@ pub mod state {
         }
 
         fn voted_player(&mut self, player_id: Option<PlayerId>) {
-            self.game.voted_player = player_id;
+            self.game.voted_player =
+                player_id.filter(|player_id| !self.is_excluded_bot_vote(player_id));
         }
 
         fn set_player_inputs(
@@ -2742,6 +4104,19 @@ pub mod state {
             mut inps: PoolFxLinkedHashMap<PlayerId, CharacterInputInfo>,
         ) {
             for (player_id, CharacterInputInfo { inp, diff }) in inps.drain() {
+                if self.game_options.anti_cheat_action() != ConfigAntiCheatAction::Off
+                    && let Some((_, anti_cheat)) = self.anti_cheat.get_mut(&player_id)
+                {
+                    let pos = self.game.players.player(&player_id).and_then(|player| {
+                        self.game
+                            .stages
+                            .get(&player.stage_id())
+                            .and_then(|stage| stage.world.characters.get(&player_id))
+                            .filter(|character| !character.phased.is_phased())
+                            .map(|character| *character.pos.pos())
+                    });
+                    anti_cheat.check(&inp, &diff, pos);
+                }
                 self.set_player_inp_impl(&player_id, &inp, diff)
             }
         }
@@ -2769,8 +4144,8 @@ pub mod state {
         fn set_player_eye(&mut self, player_id: &PlayerId, eye: TeeEye, duration: Duration) {
             let normal_in = (duration.as_millis().clamp(0, GameTickType::MAX as u128)
                 as GameTickType
-                / TICKS_PER_SECOND)
-                .max(1);
+                / self.game_options.ticks_per_second())
+            .max(1);
             if let Some(player) = self.game.players.player(player_id) {
                 let stages = &mut self.game.stages;
                 let character = stages
@@ -2792,15 +4167,22 @@ pub mod state {
         }
 
         fn tick(&mut self, options: TickOptions) -> TickResult {
+            if !options.is_future_tick_prediction {
+                self.bots_tick();
+            }
             self.tick_impl(options.is_future_tick_prediction);
 
             if !options.is_future_tick_prediction {
+                self.anti_cheat_tick();
+                self.bans_tick();
                 self.player_tick();
+                self.hint_tick();
+                self.announcement_tick();
                 self.query_tick();
             }
 
             TickResult {
-                events: PoolVec::new_without_pool(),
+                events: PoolVec::from_without_pool(std::mem::take(&mut self.pending_tick_events)),
             }
         }
 
@@ -2844,9 +4226,10 @@ pub mod state {
                         character.player_info.id,
                     );
                     if !self.game.timeout_players.contains_key(&key) {
-                        self.game
-                            .timeout_players
-                            .insert(key, (*id, (TICKS_PER_SECOND * 120).into()));
+                        self.game.timeout_players.insert(
+                            key,
+                            (*id, (self.game_options.ticks_per_second() * 120).into()),
+                        );
                     } else {
                         self.player_drop(id, PlayerDropReason::Disconnect);
                     }
@@ -2874,16 +4257,28 @@ pub mod state {
 
             let game_pools = &self.game_pools;
             let event_id_generator = &self.event_id_generator;
+            // hits are only sent to the attacker and its spectators
+            let everything = &client.everything;
+            let viewed_characters = self.event_viewed_characters(client.client_player_ids.iter());
+            let viewed_characters = &viewed_characters;
 
             self.game.game_pending_events.for_each(hi_closure!([
                 game_pools: &GamePooling,
                 event_id_generator: &EventIdGenerator,
                 worlds_events_ref: &mut MtPoolFxLinkedHashMap<StageId, GameWorldEvents>,
+                everything: &bool,
+                viewed_characters: &FxHashSet<CharacterId>,
             ], |world_id: &StageId, evs: &Vec<GameWorldEvent>|
              -> () {
                 let mut world_events = game_pools.world_events_pool.new();
-                for game_event in evs.iter() {
-                    GameState::game_event_to_world_event(game_event, &mut world_events, event_id_generator);
+                for game_event in batch_sound_events(evs, |id| viewed_characters.contains(id)) {
+                    if let GameWorldEvent::Hit(hit) = game_event.as_ref()
+                        && !*everything
+                        && !viewed_characters.contains(&hit.attacker)
+                    {
+                        continue;
+                    }
+                    GameState::game_event_to_world_event(&game_event, &mut world_events, event_id_generator);
                 }
                 if !world_events.is_empty() {
                     worlds_events_ref.insert(
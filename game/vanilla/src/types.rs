@@ -4,7 +4,17 @@ pub mod types {
     use hiarc::{Hiarc, hiarc_safer_rc_refcell};
     use serde::{Deserialize, Serialize};
 
-    use crate::config::config::{ConfigGameType, ConfigVanilla};
+    use crate::{
+        announcements::announcements::AnnouncementOptions,
+        config::config::{
+            ConfigAntiCheatAction, ConfigGameType, ConfigOvertimeMode, ConfigVanilla,
+        },
+        entities::{
+            character::{character::DamageTileOptions, core::character_core::HookOptions},
+            flag::flag::FlagOptions,
+        },
+        loadout::loadout::SpawnLoadout,
+    };
 
     #[derive(Debug, Hiarc, Clone, Copy, Default, Serialize, Deserialize)]
     pub enum GameType {
@@ -18,12 +28,35 @@ pub mod types {
     pub struct GameOptions {
         ty: GameType,
         config: ConfigVanilla,
+        /// Fixed when the game is created, changing the
+        /// tick rate of a running game requires a map reload.
+        ticks_per_second: u64,
+        /// The loadout of the game type, the config is
+        /// validated when the game is created.
+        spawn_loadout: SpawnLoadout,
     }
 
     #[hiarc_safer_rc_refcell]
     impl GameOptions {
         pub fn new(ty: GameType, config: ConfigVanilla) -> Self {
-            Self { ty, config }
+            Self {
+                ty,
+                ticks_per_second: config.ticks_per_second,
+                spawn_loadout: SpawnLoadout::new(&config).unwrap_or_default(),
+                config,
+            }
+        }
+
+        pub fn ticks_per_second(&self) -> u64 {
+            self.ticks_per_second
+        }
+        /// Converts a time in milliseconds to ticks.
+        pub fn ms_to_ticks(&self, ms: u64) -> u64 {
+            ms * self.ticks_per_second / 1000
+        }
+        pub fn time_to_ticks(&self, time: Duration) -> u64 {
+            ((time.as_micros() * self.ticks_per_second as u128)
+                / Duration::from_secs(1).as_micros()) as u64
         }
 
         pub fn ty(&self) -> GameType {
@@ -52,9 +85,60 @@ pub mod types {
                 None
             }
         }
+        pub fn sided_balance_at_round_end(&self) -> bool {
+            self.config.auto_side_balance_at_round_end
+        }
+        /// The sides are balanced, either after a timeout or at the round end.
+        pub fn sided_balance_enabled(&self) -> bool {
+            self.sided_balance_time().is_some() || self.sided_balance_at_round_end()
+        }
+        /// How long players that were moved by the balancing are not moved again.
+        pub fn sided_balance_immunity_ticks(&self) -> u64 {
+            self.config.side_balance_immunity_secs * self.ticks_per_second
+        }
+        pub fn warmup_time(&self) -> Option<Duration> {
+            if self.config.warmup_secs > 0 {
+                Some(Duration::from_secs(self.config.warmup_secs))
+            } else {
+                None
+            }
+        }
+        pub fn warmup_ready_percentage(&self) -> u32 {
+            self.config.warmup_ready_percentage
+        }
+        /// The duration of a timed overtime in ticks,
+        /// `None` if the first score after the time limit wins.
+        pub fn overtime_ticks(&self) -> Option<u64> {
+            match self.config.overtime_mode {
+                ConfigOvertimeMode::GoldenGoal => None,
+                ConfigOvertimeMode::Timed => {
+                    Some(self.config.overtime_secs * self.ticks_per_second)
+                }
+            }
+        }
+        pub fn overtime_repeats(&self) -> u32 {
+            self.config.overtime_repeats
+        }
+        pub fn overtime_score_limit(&self) -> bool {
+            self.config.overtime_score_limit
+        }
+        pub fn overtime_no_respawn(&self) -> bool {
+            self.config.overtime_no_respawn
+        }
+        pub fn overtime_reset_flags(&self) -> bool {
+            self.config.overtime_reset_flags
+        }
         pub fn friendly_fire(&self) -> bool {
             self.config.friendly_fire
         }
+        pub fn view_culling(&self) -> bool {
+            self.config.view_culling
+        }
+        /// The radius & hysteresis of the interest management in tiles.
+        pub fn interest_radius(&self) -> Option<(u32, u32)> {
+            (self.config.interest_radius > 0)
+                .then_some((self.config.interest_radius, self.config.interest_hysteresis))
+        }
         pub fn laser_hit_self(&self) -> bool {
             self.config.laser_hit_self
         }
@@ -64,11 +148,108 @@ pub mod types {
         pub fn tournament_mode(&self) -> bool {
             self.config.tournament_mode
         }
+        pub fn bots_exclude_from_votes(&self) -> bool {
+            self.config.bots_exclude_from_votes
+        }
+        pub fn bots_exclude_from_balancing(&self) -> bool {
+            self.config.bots_exclude_from_balancing
+        }
+        pub fn save_round_stats(&self) -> bool {
+            self.config.save_round_stats
+        }
+        pub fn anti_cheat_action(&self) -> ConfigAntiCheatAction {
+            self.config.anti_cheat_action
+        }
+        pub fn anti_cheat_threshold(&self) -> u32 {
+            self.config.anti_cheat_threshold
+        }
+        pub fn vote_kick_ban(&self) -> Option<Duration> {
+            (self.config.vote_kick_ban_mins > 0)
+                .then(|| Duration::from_secs(self.config.vote_kick_ban_mins as u64 * 60))
+        }
+        pub fn hook_options(&self) -> HookOptions {
+            HookOptions {
+                player_hooking: self.config.player_hooking,
+                drag_strength: self.config.hook_drag_percentage as f32 / 100.0,
+            }
+        }
+        pub fn hook_assist_stats(&self) -> bool {
+            self.config.hook_assist_stats
+        }
+        /// The damage & the interval in ticks, in which hooked enemies are damaged.
+        pub fn hook_damage(&self) -> Option<(u32, u64)> {
+            (self.config.hook_damage > 0).then(|| {
+                (
+                    self.config.hook_damage,
+                    self.ms_to_ticks(self.config.hook_damage_interval_ms as u64)
+                        .max(1),
+                )
+            })
+        }
+        pub fn damage_tile_options(&self) -> Option<DamageTileOptions> {
+            (self.config.damage_tile_index > 0).then(|| DamageTileOptions {
+                index: self.config.damage_tile_index as u8,
+                damage: self.config.damage_tile_damage,
+                interval_ticks: self
+                    .ms_to_ticks(self.config.damage_tile_interval_ms as u64)
+                    .max(1),
+            })
+        }
+        /// How long spawned characters are immune to damage.
+        pub fn spawn_protection_ticks(&self) -> u64 {
+            self.ms_to_ticks(self.config.spawn_protection_ms as u64)
+        }
+        pub fn spawn_loadout(&self) -> SpawnLoadout {
+            self.spawn_loadout
+        }
+        pub fn flag_options(&self) -> FlagOptions {
+            FlagOptions {
+                drop_vel_factor: self.config.flag_drop_velocity_percentage as f32 / 100.0,
+                elasticity: self.config.flag_bounce_percentage as f32 / 100.0,
+                return_ticks: self.config.flag_return_secs as u64 * self.ticks_per_second,
+                regrab_delay_ticks: self.ms_to_ticks(self.config.flag_regrab_delay_ms as u64),
+            }
+        }
+
+        /// The time until a weapon dropped on death disappears in ticks,
+        /// `None` if characters don't drop weapons.
+        pub fn weapon_drop_despawn_ticks(&self) -> Option<u64> {
+            self.config
+                .weapon_drop_on_death
+                .then(|| self.config.weapon_drop_despawn_secs as u64 * self.ticks_per_second)
+        }
+
+        pub fn motd(&self) -> Option<String> {
+            (!self.config.motd.is_empty()).then(|| self.config.motd.clone())
+        }
+        /// The template of the join message, `None` for the default message.
+        pub fn join_msg(&self) -> Option<String> {
+            (!self.config.join_msg.is_empty()).then(|| self.config.join_msg.clone())
+        }
+        /// The template of the leave message, `None` for the default message.
+        pub fn leave_msg(&self) -> Option<String> {
+            (!self.config.leave_msg.is_empty()).then(|| self.config.leave_msg.clone())
+        }
+        pub fn announcement_options(&self) -> AnnouncementOptions {
+            AnnouncementOptions {
+                count: self.config.announcements.len(),
+                interval_ticks: self.config.announcement_interval_mins as u64
+                    * 60
+                    * self.ticks_per_second,
+                min_players: self.config.announcement_min_players as usize,
+            }
+        }
+        pub fn announcement(&self, index: usize) -> Option<String> {
+            self.config.announcements.get(index).cloned()
+        }
 
         pub fn config_clone(&self) -> ConfigVanilla {
             self.config.clone()
         }
+        /// The config must have a valid spawn loadout,
+        /// see [`SpawnLoadout::new`].
         pub fn replace_conf(&mut self, config: ConfigVanilla) {
+            self.spawn_loadout = SpawnLoadout::new(&config).unwrap_or_default();
             self.config = config;
         }
     }
@@ -43,12 +43,12 @@ pub mod world {
                 score::character_score::CharacterScores,
             },
             entity::entity::{EntityInterface, EntityTickResult},
-            flag::flag::{Flag, FlagPool, Flags},
+            flag::flag::{Flag, FlagOptions, FlagPool, Flags},
             laser::laser::{Laser, LaserPool, Lasers, WorldLaser},
-            pickup::pickup::{Pickup, PickupPool, Pickups},
+            pickup::pickup::{DroppedPickup, Pickup, PickupPool, Pickups},
             projectile::projectile::{Projectile, ProjectilePool, WorldProjectile},
         },
-        events::events::{CharacterTickEvent, FlagEvent, PickupEvent},
+        events::events::{CharacterEvent, CharacterTickEvent, FlagEvent, PickupEvent},
         game_objects::game_objects::{GameObjectDefinitions, GameObjectDefinitionsBase},
         simulation_pipe::simulation_pipe::{
             GameWorldPendingEvents, SimulationEventWorldEntity, SimulationEventWorldEntityType,
@@ -56,7 +56,6 @@ pub mod world {
             SimulationPipeProjectile, SimulationWorldEvent, SimulationWorldEvents,
         },
         spawns::GameSpawns,
-        state::state::TICKS_PER_SECOND,
         types::types::{GameOptions, GameType},
     };
 
@@ -213,6 +212,10 @@ pub mod world {
         pub(crate) hooks: HookedCharacters,
         pub(crate) scores: CharacterScores,
 
+        /// Dead characters stay dead, e.g. during an overtime
+        /// without respawns.
+        pub(crate) respawn_disabled: bool,
+
         game_options: GameOptions,
     }
 
@@ -272,7 +275,7 @@ pub mod world {
                 for pickup in &game_object_definitions.pickups.ninjas {
                     inactive_game_objects.pickups.ninjas.push(GameObjectWorld {
                         pos: *pickup,
-                        respawn_in_ticks: TICKS_PER_SECOND * 90,
+                        respawn_in_ticks: game_options.ticks_per_second() * 90,
                     });
                 }
 
@@ -326,6 +329,8 @@ pub mod world {
                 hooks,
                 scores,
 
+                respawn_disabled: false,
+
                 game_options,
             }
         }
@@ -334,17 +339,21 @@ pub mod world {
         pub(crate) fn count_sides(&self) -> (usize, usize) {
             let mut red = 0;
             let mut blue = 0;
-            self.characters.iter().for_each(|(_, char)| {
-                match char.core.side {
-                    Some(side) => match side {
-                        MatchSide::Red => red += 1,
-                        MatchSide::Blue => blue += 1,
-                    },
-                    None => {
-                        // ignore
+            let exclude_bots = self.game_options.bots_exclude_from_balancing();
+            self.characters
+                .iter()
+                .filter(|(_, char)| !exclude_bots || !char.player_info.is_bot)
+                .for_each(|(_, char)| {
+                    match char.core.side {
+                        Some(side) => match side {
+                            MatchSide::Red => red += 1,
+                            MatchSide::Blue => blue += 1,
+                        },
+                        None => {
+                            // ignore
+                        }
                     }
-                }
-            });
+                });
 
             (red, blue)
         }
@@ -655,6 +664,7 @@ pub mod world {
             other_team_flags: &Flags,
             characters: &mut Characters,
             play_field: &CharacterPositionPlayfield,
+            options: &FlagOptions,
             pipe: &mut SimulationPipeStage,
         ) {
             flags.retain_with_order(|_, flag| {
@@ -663,6 +673,7 @@ pub mod world {
                     characters,
                     play_field,
                     other_team_flags,
+                    options,
                     pipe.is_prediction,
                 )) != EntityTickResult::RemoveEntity
             });
@@ -673,6 +684,7 @@ pub mod world {
             other_team_flags: &Flags,
             characters: &mut Characters,
             play_field: &CharacterPositionPlayfield,
+            options: &FlagOptions,
             pipe: &mut SimulationPipeStage,
         ) {
             flags.retain_with_order(|_, flag| {
@@ -681,23 +693,26 @@ pub mod world {
                     characters,
                     play_field,
                     other_team_flags,
+                    options,
                     pipe.is_prediction,
                 )) != EntityTickResult::RemoveEntity
             })
         }
 
-        fn tick_pickups(&mut self) {
+        fn tick_pickups(&mut self, pipe: &mut SimulationPipeStage) {
             self.pickups.retain_with_order(|_, pickup| {
                 pickup.tick(&mut SimulationPipePickup::new(
+                    pipe.collision,
                     &mut self.characters,
                     &self.play_field,
                 )) != EntityTickResult::RemoveEntity
             });
         }
 
-        fn post_tick_pickups(&mut self) {
+        fn post_tick_pickups(&mut self, pipe: &mut SimulationPipeStage) {
             self.pickups.retain_with_order(|_, pickup| {
                 pickup.tick_deferred(&mut SimulationPipePickup::new(
+                    pipe.collision,
                     &mut self.characters,
                     &self.play_field,
                 )) != EntityTickResult::RemoveEntity
@@ -776,7 +791,7 @@ pub mod world {
                                     &proj_id,
                                     pos,
                                     dir,
-                                    (lifetime * TICKS_PER_SECOND as f32) as i32,
+                                    (lifetime * pipe.collision.ticks_per_second() as f32) as i32,
                                     1,
                                     0.0,
                                     match ty {
@@ -961,8 +976,9 @@ pub mod world {
 
         fn handle_simulation_events(&mut self) {
             let inactive_game_objects = &mut self.inactive_game_objects;
+            let ticks_per_second = self.game_options.ticks_per_second();
             self.simulation_events
-                .for_each_evs(hi_closure!([inactive_game_objects: &mut GameObjectsWorld], |evs: &Vec<SimulationWorldEvent>| -> () {
+                .for_each_evs(hi_closure!([inactive_game_objects: &mut GameObjectsWorld, ticks_per_second: u64], |evs: &Vec<SimulationWorldEvent>| -> () {
                     for ev in evs.iter() {
                         let SimulationWorldEvent::Entity(SimulationEventWorldEntity { ev, .. }) = ev;
                         match ev {
@@ -975,7 +991,7 @@ pub mod world {
                                 PickupEvent::Despawn { pos, ty, .. } => {
                                     let pos =
                                         ivec2::new((pos.x / 32.0) as i32, (pos.y / 32.0) as i32);
-                                    let respawn_ticks = TICKS_PER_SECOND * 15;
+                                    let respawn_ticks = ticks_per_second * 15;
                                     match ty {
                                         PickupType::PowerupHealth => {
                                             inactive_game_objects.pickups.hearts.push(
@@ -997,7 +1013,7 @@ pub mod world {
                                             inactive_game_objects.pickups.ninjas.push(
                                                 GameObjectWorld {
                                                     pos,
-                                                    respawn_in_ticks: TICKS_PER_SECOND * 90,
+                                                    respawn_in_ticks: ticks_per_second * 90,
                                                 },
                                             )
                                         }
@@ -1019,7 +1035,7 @@ pub mod world {
                                 FlagEvent::Despawn { pos, ty, .. } => {
                                     let pos =
                                         ivec2::new((pos.x / 32.0) as i32, (pos.y / 32.0) as i32);
-                                    let respawn_ticks = TICKS_PER_SECOND * 15;
+                                    let respawn_ticks = ticks_per_second * 15;
                                     match ty {
                                         FlagType::Red => {
                                             inactive_game_objects.pickups.red_flags.push(
@@ -1040,7 +1056,8 @@ pub mod world {
                                     }
                                 }
                                 FlagEvent::Collect { .. } |
-                                FlagEvent::Capture { .. } => {
+                                FlagEvent::Capture { .. } |
+                                FlagEvent::Return { .. } => {
                                     // ignore
                                 }
                             },
@@ -1049,6 +1066,67 @@ pub mod world {
                 }));
         }
 
+        /// The characters that died drop their strongest weapon,
+        /// if enabled.
+        fn drop_weapons_of_dead_characters(&mut self) {
+            let Some(despawn_ticks) = self.game_options.weapon_drop_despawn_ticks() else {
+                return;
+            };
+            let Some(id_generator) = &self.id_generator else {
+                return;
+            };
+            let mut dead_characters: Vec<CharacterId> = Default::default();
+            self.simulation_events
+                .for_each_evs(hi_closure!([dead_characters: &mut Vec<CharacterId>], |evs: &Vec<SimulationWorldEvent>| -> () {
+                    for ev in evs.iter() {
+                        let SimulationWorldEvent::Entity(SimulationEventWorldEntity { ev, .. }) = ev;
+                        if let SimulationEventWorldEntityType::Character {
+                            ev: CharacterEvent::Despawn { id, .. },
+                        } = ev
+                        {
+                            dead_characters.push(*id);
+                        }
+                    }
+                }));
+            for id in dead_characters {
+                let Some(character) = self.characters.get(&id) else {
+                    continue;
+                };
+                // weapons are sorted, hammer & gun are part of every spawn
+                let Some((&weapon, ammo)) = character
+                    .reusable_core
+                    .weapons
+                    .iter()
+                    .rev()
+                    .find(|(ty, _)| !matches!(ty, WeaponType::Hammer | WeaponType::Gun))
+                    .map(|(ty, weapon)| (ty, weapon.cur_ammo.unwrap_or(10)))
+                else {
+                    continue;
+                };
+                if ammo == 0 {
+                    continue;
+                }
+                let pos = *character.pos.pos();
+                let vel = character.core.core.vel * 0.5;
+
+                let pickup_id = id_generator.next_id();
+                let mut pickup = Pickup::new(
+                    &pickup_id,
+                    &pos,
+                    PickupType::PowerupWeapon(weapon),
+                    &self.world_pool.pickup_pool,
+                    &self.game_pending_events,
+                    &self.simulation_events,
+                );
+                pickup.core.dropped = Some(DroppedPickup {
+                    vel,
+                    ammo,
+                    despawn_ticks,
+                });
+                self.pickups.insert(pickup_id, pickup);
+            }
+        }
+
         fn check_inactive_game_objects(&mut self) {
             if let Some(id_generator) = &self.id_generator {
                 let mut add_pickup = |obj: &mut GameObjectWorld, ty: PickupType| {
@@ -1106,10 +1184,12 @@ pub mod world {
                 character.core.input,
                 &character.player_info,
                 self.get_spawn_pos(character.core.side),
+                &self.game_options.spawn_loadout(),
             );
 
             let character = self.characters.to_back(character_id).unwrap();
             character.core = core;
+            character.core.spawn_protection = self.game_options.spawn_protection_ticks().into();
             character.reusable_core = reusable_core;
             character.pos.move_pos(pos);
 
@@ -1137,6 +1217,7 @@ pub mod world {
                     match &mut character.phased {
                         CharacterPhasedState::Normal(_)
                         | CharacterPhasedState::PhasedSpectate(_) => false,
+                        CharacterPhasedState::Dead(_) if self.respawn_disabled => true,
                         CharacterPhasedState::Dead(dead) => {
                             if dead.respawn_in_ticks.tick().unwrap_or_default() {
                                 self.on_character_spawn(id);
@@ -1156,6 +1237,8 @@ pub mod world {
             self.check_character_respawn();
             self.check_inactive_game_objects();
 
+            let flag_options = self.game_options.flag_options();
+
             self.tick_characters(pipe);
             self.tick_projectiles(pipe);
             Self::tick_flags(
@@ -1163,6 +1246,7 @@ pub mod world {
                 &self.blue_flags,
                 &mut self.characters,
                 &self.play_field,
+                &flag_options,
                 pipe,
             );
             Self::tick_flags(
@@ -1170,9 +1254,10 @@ pub mod world {
                 &self.red_flags,
                 &mut self.characters,
                 &self.play_field,
+                &flag_options,
                 pipe,
             );
-            self.tick_pickups();
+            self.tick_pickups(pipe);
             self.tick_lasers(pipe);
 
             self.post_tick_characters(pipe);
@@ -1182,6 +1267,7 @@ pub mod world {
                 &self.blue_flags,
                 &mut self.characters,
                 &self.play_field,
+                &flag_options,
                 pipe,
             );
             Self::post_tick_flags(
@@ -1189,12 +1275,14 @@ pub mod world {
                 &self.red_flags,
                 &mut self.characters,
                 &self.play_field,
+                &flag_options,
                 pipe,
             );
-            self.post_tick_pickups();
+            self.post_tick_pickups(pipe);
             self.post_tick_lasers(pipe);
 
             self.handle_simulation_events();
+            self.drop_weapons_of_dead_characters();
         }
     }
 }
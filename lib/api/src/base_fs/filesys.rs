@@ -94,6 +94,18 @@ impl FileSystemInterface for FileSystem {
             .map_err(|err| std::io::Error::new(std::io::ErrorKind::Unsupported, err))
     }
 
+    async fn append_file(&self, _file_path: &Path, _data: Vec<u8>) -> std::io::Result<()> {
+        todo!("not implemented")
+    }
+
+    async fn remove_file(&self, _file_path: &Path) -> std::io::Result<()> {
+        todo!("not implemented")
+    }
+
+    async fn rename_file(&self, _from: &Path, _to: &Path) -> std::io::Result<()> {
+        todo!("not implemented")
+    }
+
     async fn create_dir(&self, dir_path: &Path) -> std::io::Result<()> {
         let mut res;
         let id = self.id.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
@@ -119,6 +119,7 @@ impl GraphicsBackendInterface for GraphicsBackend {
             auto: Gpu {
                 name: "wasm".to_string(),
                 ty: GpuType::Invalid,
+                device_local_memory: 0,
             },
             cur: CurGpu {
                 msaa_sampling_count: 1,
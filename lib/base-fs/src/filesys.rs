@@ -591,6 +591,49 @@ impl FileSystem {
         Ok(())
     }
 
+    pub async fn append_file_for_fs(
+        fs: &ScopedDirFileSystem,
+        file_path: &Path,
+        data: Vec<u8>,
+    ) -> std::io::Result<()> {
+        use virtual_fs::AsyncWriteExt;
+        let file_path = fs.get_path(file_path);
+        let mut file = fs.fs.open(
+            &file_path,
+            &OpenOptionsConfig {
+                read: false,
+                write: true,
+                create_new: false,
+                create: true,
+                append: true,
+                truncate: false,
+            },
+        )?;
+        file.write_all(&data).await?;
+        file.flush().await?;
+        Ok(())
+    }
+
+    pub async fn remove_file_in_fs(
+        fs: &ScopedDirFileSystem,
+        file_path: &Path,
+    ) -> std::io::Result<()> {
+        let file_path = fs.get_path(file_path);
+        virtual_fs::FileSystem::remove_file(&fs.fs, &file_path)?;
+        Ok(())
+    }
+
+    pub async fn rename_file_in_fs(
+        fs: &ScopedDirFileSystem,
+        from: &Path,
+        to: &Path,
+    ) -> std::io::Result<()> {
+        let from = fs.get_path(from);
+        let to = fs.get_path(to);
+        fs.fs.rename(&from, &to).await?;
+        Ok(())
+    }
+
     pub async fn read_file_in_fs(
         fs: &ScopedDirFileSystem,
         file_path: &Path,
@@ -706,6 +749,39 @@ impl FileSystemInterface for FileSystem {
         Self::write_file_for_fs(fs, file_path, data).await
     }
 
+    async fn append_file(&self, file_path: &Path, data: Vec<u8>) -> std::io::Result<()> {
+        let _g = self
+            .max_operations_semaphore
+            .acquire()
+            .await
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::BrokenPipe, err.to_string()))?;
+        let fs = self.get_scoped_fs(FileSystemPath::OfType(FileSystemType::ReadWrite));
+
+        Self::append_file_for_fs(fs, file_path, data).await
+    }
+
+    async fn remove_file(&self, file_path: &Path) -> std::io::Result<()> {
+        let _g = self
+            .max_operations_semaphore
+            .acquire()
+            .await
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::BrokenPipe, err.to_string()))?;
+        let fs = self.get_scoped_fs(FileSystemPath::OfType(FileSystemType::ReadWrite));
+
+        Self::remove_file_in_fs(fs, file_path).await
+    }
+
+    async fn rename_file(&self, from: &Path, to: &Path) -> std::io::Result<()> {
+        let _g = self
+            .max_operations_semaphore
+            .acquire()
+            .await
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::BrokenPipe, err.to_string()))?;
+        let fs = self.get_scoped_fs(FileSystemPath::OfType(FileSystemType::ReadWrite));
+
+        Self::rename_file_in_fs(fs, from, to).await
+    }
+
     async fn create_dir(&self, dir_path: &Path) -> std::io::Result<()> {
         let _g = self
             .max_operations_semaphore
@@ -5,10 +5,11 @@ use async_trait::async_trait;
 use base::hash::Hash;
 use base::hash::generate_hash_for;
 use base_io_traits::http_traits::HttpError;
-use base_io_traits::http_traits::{HttpClientInterface, HttpHeaderValue};
-use bytes::Bytes;
+use base_io_traits::http_traits::{HttpBinaryRange, HttpClientInterface, HttpHeaderValue};
+use bytes::{Bytes, BytesMut};
 use http_body_util::{BodyExt, Limited};
-use reqwest::header::CONTENT_TYPE;
+use reqwest::StatusCode;
+use reqwest::header::{CONTENT_RANGE, CONTENT_TYPE, RANGE};
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use tokio::sync::Mutex;
 use url::Url;
@@ -62,6 +63,14 @@ impl HttpClient {
             .map_err(|err| HttpError::Other(err.to_string()))?
             .to_bytes())
     }
+
+    /// Parses the offset and the total size of a `Content-Range` header,
+    /// e.g. `bytes 100-199/200`.
+    fn parse_content_range(content_range: &str) -> Option<(u64, Option<u64>)> {
+        let (range, total_len) = content_range.strip_prefix("bytes ")?.split_once('/')?;
+        let (start, _) = range.split_once('-')?;
+        Some((start.parse().ok()?, total_len.parse().ok()))
+    }
 }
 
 #[async_trait]
@@ -97,6 +106,63 @@ impl HttpClientInterface for HttpClient {
         Ok(res)
     }
 
+    async fn download_binary_range(
+        &self,
+        url: Url,
+        hash: &Hash,
+        offset: u64,
+    ) -> anyhow::Result<HttpBinaryRange, HttpError> {
+        (url.scheme() == "https" || url.scheme() == "http")
+            .then_some(())
+            .ok_or_else(|| HttpError::Other("url must be http or https".into()))?;
+        let mut req = self.client.get(url.clone());
+        if offset > 0 {
+            req = req.header(RANGE, format!("bytes={offset}-"));
+        }
+        let mut res = Self::map_err(Self::map_err(req.send().await)?.error_for_status())?;
+
+        // servers without range support send the whole file
+        let (offset, total_len) = if res.status() == StatusCode::PARTIAL_CONTENT {
+            res.headers()
+                .get(CONTENT_RANGE)
+                .and_then(|range| range.to_str().ok())
+                .and_then(Self::parse_content_range)
+                .ok_or_else(|| HttpError::Other("invalid content range".into()))?
+        } else {
+            (0, res.content_length())
+        };
+
+        let mut data = BytesMut::new();
+        let complete = loop {
+            match res.chunk().await {
+                Ok(Some(chunk)) => {
+                    if data.len() + chunk.len() > 1024 * 1024 * 1024 {
+                        return Err(HttpError::Other("file is too big".into()));
+                    }
+                    data.extend_from_slice(&chunk);
+                }
+                Ok(None) => break true,
+                // keep what was received so far
+                Err(_) => break false,
+            }
+        };
+        let data = data.freeze();
+
+        if complete && offset == 0 {
+            generate_hash_for(&data)
+                .eq(hash)
+                .then_some(())
+                .ok_or_else(|| HttpError::Other(format!("file hash mismatched for {url}")))?;
+        }
+
+        Ok(HttpBinaryRange {
+            offset,
+            total_len,
+            data,
+            complete,
+        })
+    }
+
     async fn post_json(&self, url: Url, data: Vec<u8>) -> anyhow::Result<Vec<u8>, HttpError> {
         if url.scheme() != "https" {
             return Err(HttpError::Other("url must be http or https".to_string()));
@@ -199,6 +265,18 @@ impl HttpClientInterface for LazyHttpClient {
         self.get_client().await.download_binary(url, hash).await
     }
 
+    async fn download_binary_range(
+        &self,
+        url: Url,
+        hash: &Hash,
+        offset: u64,
+    ) -> anyhow::Result<HttpBinaryRange, HttpError> {
+        self.get_client()
+            .await
+            .download_binary_range(url, hash, offset)
+            .await
+    }
+
     async fn post_json(&self, url: Url, data: Vec<u8>) -> anyhow::Result<Vec<u8>, HttpError> {
         self.get_client().await.post_json(url, data).await
     }
@@ -222,6 +300,20 @@ mod test {
 
     use super::{HttpClient, LazyHttpClient};
 
+    #[test]
+    fn content_range() {
+        assert_eq!(
+            HttpClient::parse_content_range("bytes 100-199/200"),
+            Some((100, Some(200)))
+        );
+        assert_eq!(
+            HttpClient::parse_content_range("bytes 100-199/*"),
+            Some((100, None))
+        );
+        assert_eq!(HttpClient::parse_content_range("bytes */200"), None);
+        assert_eq!(HttpClient::parse_content_range("100-199/200"), None);
+    }
+
     #[test]
     fn http_create_bench() {
         let benchmark = Benchmark::new(true);
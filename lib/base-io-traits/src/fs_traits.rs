@@ -54,6 +54,14 @@ pub trait FileSystemInterface: Debug + Sync + Send {
     async fn file_exists(&self, file_path: &Path) -> bool;
     /// Write a file to the read-write file system
     async fn write_file(&self, file_path: &Path, data: Vec<u8>) -> std::io::Result<()>;
+    /// Append to a file in the read-write file system,
+    /// the file is created if it does not exist.
+    async fn append_file(&self, file_path: &Path, data: Vec<u8>) -> std::io::Result<()>;
+    /// Remove a file from the read-write file system
+    async fn remove_file(&self, file_path: &Path) -> std::io::Result<()>;
+    /// Rename a file in the read-write file system,
+    /// an existing file at `to` is replaced.
+    async fn rename_file(&self, from: &Path, to: &Path) -> std::io::Result<()>;
     /// Create a directory recursively to the read-write file system
     async fn create_dir(&self, dir_path: &Path) -> std::io::Result<()>;
 
@@ -47,6 +47,22 @@ pub enum HttpError {
     Other(String),
 }
 
+/// A part of a binary file,
+/// see [`HttpClientInterface::download_binary_range`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpBinaryRange {
+    /// The offset of [`Self::data`] inside the whole file.
+    ///
+    /// Is `0` if the server ignored the range request.
+    pub offset: u64,
+    /// The size of the whole file, if the server sent it.
+    pub total_len: Option<u64>,
+    pub data: Bytes,
+    /// `false` if the transfer was interrupted,
+    /// [`Self::data`] then contains everything that was received so far.
+    pub complete: bool,
+}
+
 #[async_trait]
 pub trait HttpClientInterface: Debug + Send + Sync {
     async fn download_text(&self, url: Url) -> anyhow::Result<String, HttpError>;
@@ -56,6 +72,26 @@ pub trait HttpClientInterface: Debug + Send + Sync {
     /// Downloads binary data. This only allows reading binary data where the hash is already known
     async fn download_binary(&self, url: Url, hash: &Hash) -> anyhow::Result<Bytes, HttpError>;
 
+    /// Downloads binary data starting at `offset` of the file with the given hash.
+    ///
+    /// Only a complete download of the whole file is verified against the hash,
+    /// partial data must be verified by the caller once the file is complete.
+    /// Clients without range support download the whole file.
+    async fn download_binary_range(
+        &self,
+        url: Url,
+        hash: &Hash,
+        _offset: u64,
+    ) -> anyhow::Result<HttpBinaryRange, HttpError> {
+        let data = self.download_binary(url, hash).await?;
+        Ok(HttpBinaryRange {
+            offset: 0,
+            total_len: Some(data.len() as u64),
+            data,
+            complete: true,
+        })
+    }
+
     /// Post a json body and return arbitrary bytes returned as a response.
     async fn post_json(&self, url: Url, data: Vec<u8>) -> anyhow::Result<Vec<u8>, HttpError>;
 
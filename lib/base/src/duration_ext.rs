@@ -3,6 +3,10 @@ use std::time::Duration;
 /// Duration to strings for race timers.
 pub trait DurationToRaceStr {
     fn to_race_string(&self) -> String;
+    /// The difference to a reference time, e.g. a best split.
+    ///
+    /// Slower times are prefixed with `+`, faster ones with `-`.
+    fn to_race_delta_string(&self, reference: &Duration) -> String;
 }
 
 impl DurationToRaceStr for Duration {
@@ -29,4 +33,39 @@ impl DurationToRaceStr for Duration {
             ms / 10
         )
     }
+
+    fn to_race_delta_string(&self, reference: &Duration) -> String {
+        let (sign, delta) = if self < reference {
+            ('-', *reference - *self)
+        } else {
+            ('+', *self - *reference)
+        };
+        let centis = delta.subsec_millis() / 10;
+        let secs = delta.as_secs();
+        if secs >= 60 {
+            format!("{sign}{}:{:0>2}.{centis:0>2}", secs / 60, secs % 60)
+        } else {
+            format!("{sign}{secs}.{centis:0>2}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::DurationToRaceStr;
+
+    #[test]
+    fn race_delta() {
+        let ms = Duration::from_millis;
+        assert_eq!(ms(10_500).to_race_delta_string(&ms(10_000)), "+0.50");
+        assert_eq!(ms(9_750).to_race_delta_string(&ms(10_000)), "-0.25");
+        assert_eq!(ms(10_000).to_race_delta_string(&ms(10_000)), "+0.00");
+        // only full hundredths are shown
+        assert_eq!(ms(10_009).to_race_delta_string(&ms(10_000)), "+0.00");
+        assert_eq!(ms(3_000).to_race_delta_string(&ms(15_120)), "-12.12");
+        assert_eq!(ms(75_050).to_race_delta_string(&ms(10_000)), "+1:05.05");
+        assert_eq!(ms(1_000).to_race_delta_string(&ms(3_601_000)), "-60:00.00");
+    }
 }
@@ -1,14 +1,32 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use base_io::io::IoFileSys;
-use config::config::ConfigEngine;
+use config::{
+    config::ConfigEngine,
+    migration::{LoadedConfig, load_config},
+};
+
+const CONFIG_FILE: &str = "cfg_engine.json";
 
-pub fn save(config: &ConfigEngine, io: &IoFileSys) {
-    let save_str = config.to_json_string();
+/// Saves the config.
+///
+/// A config file of a newer version is only overwritten if `force` is `true`,
+/// since this build would drop all settings it does not know.
+pub fn save(config: &ConfigEngine, io: &IoFileSys, force: bool) {
+    let save_str = ConfigEngine::MIGRATIONS.to_versioned_json(config);
 
     if let Ok(save_str) = save_str {
         let fs_clone = io.fs.clone();
         io.rt.spawn_without_lifetime(async move {
+            if !force
+                && let Ok(content) = fs_clone.read_file(CONFIG_FILE.as_ref()).await
+                && !ConfigEngine::MIGRATIONS.may_overwrite(&content)
+            {
+                log::warn!("{CONFIG_FILE} was written by a newer version and is not overwritten.");
+                return Ok(());
+            }
             fs_clone
-                .write_file("cfg_engine.json".as_ref(), save_str.as_bytes().to_vec())
+                .write_file(CONFIG_FILE.as_ref(), save_str.as_bytes().to_vec())
                 .await
                 .unwrap();
             Ok(())
@@ -16,11 +34,32 @@ pub fn save(config: &ConfigEngine, io: &IoFileSys) {
     }
 }
 
-pub fn load(io: &IoFileSys) -> anyhow::Result<ConfigEngine> {
+/// Loads the config & migrates it to the current version.
+///
+/// If the file was migrated, a backup of the old file is written next to it.
+pub fn load(io: &IoFileSys) -> anyhow::Result<LoadedConfig<ConfigEngine>> {
     let fs = io.fs.clone();
-    let config_file = io
-        .rt
-        .spawn(async move { Ok(fs.read_file("cfg_engine.json".as_ref()).await?) });
-    let res = config_file.get()?;
-    ConfigEngine::from_json_slice(&res)
+    let timestamp_secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    io.rt
+        .spawn(async move {
+            let content = fs.read_file(CONFIG_FILE.as_ref()).await?;
+            let loaded = load_config::<ConfigEngine>(
+                CONFIG_FILE,
+                &content,
+                &ConfigEngine::MIGRATIONS,
+                timestamp_secs,
+            )?;
+            if let Some(backup) = &loaded.backup {
+                fs.write_file(backup.file_name.as_ref(), backup.content.clone())
+                    .await?;
+            }
+            for warning in &loaded.warnings {
+                log::warn!("{warning}");
+            }
+            Ok(loaded)
+        })
+        .get()
 }
@@ -1,6 +1,10 @@
 use std::collections::HashMap;
 
-use crate::{self as config, types::ConfRgb};
+use crate::{
+    self as config,
+    migration::{Migration, Migrations},
+    types::ConfRgb,
+};
 
 use anyhow::anyhow;
 use atomic_enum::atomic_enum;
@@ -65,6 +69,11 @@ pub struct ConfigInput {
 pub struct ConfigGfx {
     #[default = "Vulkan"]
     pub backend: String,
+    /// Starts the backend without optional features like multisampling,
+    /// threaded rendering or debug extensions.
+    /// Selected automatically after the backend failed to initialize.
+    #[default = false]
+    pub safe_mode: bool,
 }
 
 #[config_default]
@@ -169,6 +178,14 @@ pub struct ConfigNetwork {
     pub timeout: std::time::Duration,
     #[default = false]
     pub disable_retry_on_connect: bool,
+    /// Partially downloaded files, e.g. of interrupted map downloads,
+    /// are kept for resuming until they are older than this.
+    #[default = std::time::Duration::from_secs(60 * 60 * 24 * 7)]
+    pub partial_download_max_age: std::time::Duration,
+    /// Downloads of a single file from a game server, e.g. a map or
+    /// a wasm module, are aborted if the file is larger (in bytes).
+    #[default = 256 * 1024 * 1024]
+    pub max_download_size: u64,
 }
 
 #[config_default]
@@ -229,6 +246,12 @@ pub struct ConfigEngine {
 }
 
 impl ConfigEngine {
+    /// The first versioned format, unversioned files are identical.
+    pub const MIGRATIONS: Migrations = Migrations(&[Migration {
+        version: 1,
+        steps: &[],
+    }]);
+
     pub fn new() -> ConfigEngine {
         ConfigEngine {
             inp: ConfigInput::default(),
@@ -1,4 +1,5 @@
 pub mod config;
+pub mod migration;
 pub mod parsing;
 pub mod traits;
 pub mod types;
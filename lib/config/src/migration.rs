@@ -0,0 +1,422 @@
+//! Versioning of config files.
+//!
+//! Every config file stores the version of its format in [`CONFIG_VERSION_KEY`],
+//! files without a version are version `0`.
+//! Older files are upgraded step by step by the registered [`Migration`]s,
+//! before they are deserialized.
+
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+pub const CONFIG_VERSION_KEY: &str = "config_version";
+
+/// A single change of the config format.
+///
+/// Paths are the keys of the nested json objects, joined by `.`.
+#[derive(Debug, Clone, Copy)]
+pub enum MigrationStep {
+    /// Renames a key or moves a whole section.
+    ///
+    /// If the target's parent is not a section, the value is dropped.
+    Move {
+        from: &'static str,
+        to: &'static str,
+    },
+    /// Maps the old names of the variants of an enum to the new ones.
+    ///
+    /// Unknown values are kept, they fall back to the default
+    /// when the config is deserialized.
+    RenameValues {
+        path: &'static str,
+        values: &'static [(&'static str, &'static str)],
+    },
+    /// Removes a key that has no replacement.
+    Remove { path: &'static str },
+}
+
+/// The changes from `version - 1` to `version`.
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub version: u64,
+    pub steps: &'static [MigrationStep],
+}
+
+#[derive(Error, Debug)]
+pub enum MigrationError {
+    #[error(
+        "The config file has version {found}, but this build only supports versions up to {supported}"
+    )]
+    NewerVersion { found: u64, supported: u64 },
+    #[error("The config file is not a json object")]
+    NotAnObject,
+}
+
+#[derive(Debug)]
+pub struct MigratedConfig {
+    pub value: Value,
+    /// The version of the file before the migration.
+    pub from_version: u64,
+    /// Keys that could not be migrated, e.g. because they had unexpected values.
+    pub failed_keys: Vec<String>,
+}
+
+/// All migrations of a config, ordered by version.
+#[derive(Debug, Clone, Copy)]
+pub struct Migrations(pub &'static [Migration]);
+
+fn get_path_mut<'a>(value: &'a mut Value, path: &str) -> Option<&'a mut Value> {
+    path.split('.')
+        .try_fold(value, |value, key| value.as_object_mut()?.get_mut(key))
+}
+
+fn take_path(value: &mut Value, path: &str) -> Option<Value> {
+    let (parent, key) = match path.rsplit_once('.') {
+        Some((parent, key)) => (get_path_mut(value, parent)?, key),
+        None => (value, path),
+    };
+    parent.as_object_mut()?.remove(key)
+}
+
+/// Inserts the value, missing parent sections are created.
+///
+/// Returns the value back, if a parent is not a section.
+fn insert_path(value: &mut Value, path: &str, new_value: Value) -> Result<(), Value> {
+    let mut keys = path.split('.').peekable();
+    let mut cur = value;
+    while let Some(key) = keys.next() {
+        let Some(obj) = cur.as_object_mut() else {
+            return Err(new_value);
+        };
+        if keys.peek().is_none() {
+            obj.insert(key.to_string(), new_value);
+            return Ok(());
+        }
+        cur = obj.entry(key).or_insert_with(|| Value::Object(Map::new()));
+    }
+    Err(new_value)
+}
+
+impl Migrations {
+    pub fn current_version(&self) -> u64 {
+        self.0.last().map(|m| m.version).unwrap_or_default()
+    }
+
+    pub fn file_version(value: &Value) -> u64 {
+        value
+            .get(CONFIG_VERSION_KEY)
+            .and_then(Value::as_u64)
+            .unwrap_or_default()
+    }
+
+    /// Upgrades the config file to the current version.
+    pub fn migrate(&self, mut value: Value) -> Result<MigratedConfig, MigrationError> {
+        if !value.is_object() {
+            return Err(MigrationError::NotAnObject);
+        }
+        let from_version = Self::file_version(&value);
+        let current_version = self.current_version();
+        if from_version > current_version {
+            return Err(MigrationError::NewerVersion {
+                found: from_version,
+                supported: current_version,
+            });
+        }
+
+        let mut failed_keys = Vec::new();
+        for migration in self.0.iter().filter(|m| m.version > from_version) {
+            for step in migration.steps {
+                match *step {
+                    MigrationStep::Move { from, to } => {
+                        if let Some(moved) = take_path(&mut value, from)
+                            && insert_path(&mut value, to, moved).is_err()
+                        {
+                            failed_keys.push(from.to_string());
+                        }
+                    }
+                    MigrationStep::RenameValues { path, values } => {
+                        if let Some(val) = get_path_mut(&mut value, path) {
+                            match val
+                                .as_str()
+                                .and_then(|s| values.iter().find(|(old, _)| *old == s))
+                            {
+                                Some((_, new)) => *val = Value::String(new.to_string()),
+                                // already a valid value
+                                None if values.iter().any(|(_, new)| val == new) => {}
+                                None => failed_keys.push(path.to_string()),
+                            }
+                        }
+                    }
+                    MigrationStep::Remove { path } => {
+                        take_path(&mut value, path);
+                    }
+                }
+            }
+        }
+        if let Some(obj) = value.as_object_mut() {
+            obj.insert(CONFIG_VERSION_KEY.to_string(), current_version.into());
+        }
+
+        Ok(MigratedConfig {
+            value,
+            from_version,
+            failed_keys,
+        })
+    }
+
+    /// Serializes the config including the current version.
+    pub fn to_versioned_json<T: Serialize>(&self, config: &T) -> anyhow::Result<String> {
+        let mut value = serde_json::to_value(config)?;
+        if let Some(obj) = value.as_object_mut() {
+            obj.insert(
+                CONFIG_VERSION_KEY.to_string(),
+                self.current_version().into(),
+            );
+        }
+        Ok(serde_json::to_string_pretty(&value)?)
+    }
+
+    /// Whether the config file in `content` may be overwritten by this build.
+    pub fn may_overwrite(&self, content: &[u8]) -> bool {
+        serde_json::from_slice::<Value>(content)
+            .map(|value| Self::file_version(&value) <= self.current_version())
+            .unwrap_or(true)
+    }
+}
+
+/// The keys of the config file that the config does not know,
+/// these would be silently dropped otherwise.
+pub fn unknown_keys(file: &Value, known: &Value) -> Vec<String> {
+    fn collect(file: &Value, known: &Value, prefix: &str, res: &mut Vec<String>) {
+        let (Some(file), Some(known)) = (file.as_object(), known.as_object()) else {
+            return;
+        };
+        for (key, val) in file {
+            let path = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{prefix}.{key}")
+            };
+            match known.get(key) {
+                Some(known) => collect(val, known, &path, res),
+                None if path != CONFIG_VERSION_KEY => res.push(path),
+                None => {}
+            }
+        }
+    }
+    let mut res = Vec::new();
+    collect(file, known, "", &mut res);
+    res
+}
+
+/// A copy of the file before it was migrated.
+#[derive(Debug)]
+pub struct ConfigBackup {
+    pub file_name: String,
+    pub content: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub struct LoadedConfig<T> {
+    pub config: T,
+    /// The file before it was migrated, if it was migrated.
+    pub backup: Option<ConfigBackup>,
+    /// E.g. keys that could not be migrated.
+    pub warnings: Vec<String>,
+}
+
+/// Migrates & deserializes the content of the config file `file_name`.
+///
+/// A file of a newer version is loaded as good as possible.
+pub fn load_config<T: Serialize + DeserializeOwned>(
+    file_name: &str,
+    content: &[u8],
+    migrations: &Migrations,
+    timestamp_secs: u64,
+) -> anyhow::Result<LoadedConfig<T>> {
+    let value: Value = serde_json::from_slice(content)?;
+    let mut warnings = Vec::new();
+    let mut backup = None;
+    let value = match migrations.migrate(value.clone()) {
+        Ok(migrated) => {
+            if migrated.from_version < migrations.current_version() {
+                backup = Some(ConfigBackup {
+                    file_name: format!(
+                        "{file_name}.v{}.{timestamp_secs}.bak",
+                        migrated.from_version
+                    ),
+                    content: content.to_vec(),
+                });
+            }
+            warnings.extend(
+                migrated
+                    .failed_keys
+                    .into_iter()
+                    .map(|key| format!("{file_name}: could not migrate {key}")),
+            );
+            migrated.value
+        }
+        Err(err @ MigrationError::NewerVersion { .. }) => {
+            warnings.push(format!(
+                "{file_name}: {err}. It is not overwritten, unless the client \
+                is started with --force-config"
+            ));
+            value
+        }
+        Err(err) => return Err(err.into()),
+    };
+    let config: T = serde_json::from_value(value.clone())?;
+    warnings.extend(
+        unknown_keys(&value, &serde_json::to_value(&config)?)
+            .into_iter()
+            .map(|key| format!("{file_name}: unknown key {key} is dropped")),
+    );
+    Ok(LoadedConfig {
+        config,
+        backup,
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::{Value, json};
+
+    use crate::config::ConfigEngine;
+
+    use super::{
+        CONFIG_VERSION_KEY, Migration, MigrationError, MigrationStep, Migrations, load_config,
+        unknown_keys,
+    };
+
+    /// A config format that went through all kinds of changes.
+    const TEST_MIGRATIONS: Migrations = Migrations(&[
+        Migration {
+            version: 1,
+            steps: &[
+                MigrationStep::Move {
+                    from: "gfx.window_width",
+                    to: "gfx.width",
+                },
+                MigrationStep::Move {
+                    from: "snd",
+                    to: "sound",
+                },
+            ],
+        },
+        Migration {
+            version: 2,
+            steps: &[
+                MigrationStep::RenameValues {
+                    path: "gfx.mode",
+                    values: &[("Window", "Windowed"), ("Full", "Fullscreen")],
+                },
+                MigrationStep::RenameValues {
+                    path: "gfx.vsync",
+                    values: &[("On", "Enabled")],
+                },
+                MigrationStep::Remove { path: "gfx.legacy" },
+                MigrationStep::Move {
+                    from: "sound.volume",
+                    to: "gfx.mode.volume",
+                },
+            ],
+        },
+    ]);
+
+    fn fixture(content: &str) -> Value {
+        serde_json::from_str(content).unwrap()
+    }
+
+    #[test]
+    fn step_by_step() {
+        let v0 = fixture(include_str!("../fixtures/migration/test_v0.json"));
+        let v1 = fixture(include_str!("../fixtures/migration/test_v1.json"));
+        let v2 = fixture(include_str!("../fixtures/migration/test_v2.json"));
+
+        let migrated = TEST_MIGRATIONS.migrate(v0).unwrap();
+        assert_eq!(migrated.from_version, 0);
+        assert_eq!(migrated.value, v2);
+        // the enum value was unknown, the move target is not a section
+        assert_eq!(migrated.failed_keys, ["gfx.vsync", "sound.volume"]);
+
+        // each version only applies the newer migrations
+        let migrated = TEST_MIGRATIONS.migrate(v1).unwrap();
+        assert_eq!(migrated.from_version, 1);
+        assert_eq!(migrated.value, v2);
+
+        let migrated = TEST_MIGRATIONS.migrate(v2.clone()).unwrap();
+        assert_eq!(migrated.value, v2);
+        assert!(migrated.failed_keys.is_empty());
+    }
+
+    #[test]
+    fn newer_versions_are_refused() {
+        let mut v2 = fixture(include_str!("../fixtures/migration/test_v2.json"));
+        v2[CONFIG_VERSION_KEY] = 3.into();
+        assert!(matches!(
+            TEST_MIGRATIONS.migrate(v2.clone()),
+            Err(MigrationError::NewerVersion {
+                found: 3,
+                supported: 2
+            })
+        ));
+        let content = serde_json::to_vec(&v2).unwrap();
+        assert!(!TEST_MIGRATIONS.may_overwrite(&content));
+        assert!(
+            TEST_MIGRATIONS.may_overwrite(include_bytes!("../fixtures/migration/test_v0.json"))
+        );
+    }
+
+    #[test]
+    fn engine_migrations() {
+        let content = include_bytes!("../fixtures/migration/engine_v0.json");
+        let loaded = load_config::<ConfigEngine>(
+            "cfg_engine.json",
+            content,
+            &ConfigEngine::MIGRATIONS,
+            1234,
+        )
+        .unwrap();
+        assert!(loaded.config.dbg.bench);
+        assert_eq!(loaded.config.ui.scale, 1.5);
+
+        // the file is backed up before it's migrated
+        let backup = loaded.backup.unwrap();
+        assert_eq!(backup.file_name, "cfg_engine.json.v0.1234.bak");
+        assert_eq!(backup.content, content);
+        assert_eq!(
+            loaded.warnings,
+            ["cfg_engine.json: unknown key dbg.removed_var is dropped"]
+        );
+
+        // saving writes the current version, which needs no backup
+        let saved = ConfigEngine::MIGRATIONS
+            .to_versioned_json(&loaded.config)
+            .unwrap();
+        let loaded = load_config::<ConfigEngine>(
+            "cfg_engine.json",
+            saved.as_bytes(),
+            &ConfigEngine::MIGRATIONS,
+            1234,
+        )
+        .unwrap();
+        assert!(loaded.backup.is_none());
+        assert!(loaded.warnings.is_empty());
+    }
+
+    #[test]
+    fn unknown() {
+        let file = json!({
+            "config_version": 1,
+            "a": { "b": 1, "c": 2 },
+            "d": 3,
+            "e": { "f": 4 },
+        });
+        let known = json!({
+            "a": { "b": 1 },
+            "e": 4,
+        });
+        assert_eq!(unknown_keys(&file, &known), ["a.c", "d"]);
+    }
+}
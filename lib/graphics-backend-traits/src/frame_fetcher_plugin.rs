@@ -13,6 +13,15 @@ pub enum FetchCanvasIndex {
     Offscreen(OffscreenCanvasId),
 }
 
+/// A part of a canvas, in pixels from the top left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FetchCanvasRegion {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
 #[derive(Debug, Hiarc, Error)]
 pub enum FetchCanvasError {
     #[error("canvas with the id, which was obtained by `current_fetch_index`, was not found.")]
@@ -42,6 +51,12 @@ pub trait BackendFrameFetcher: Debug + Sync + Send + 'static {
     /// the backend must respect it for every frame.
     fn current_fetch_index(&self) -> FetchCanvasIndex;
 
+    /// Only fetch a part of the canvas, e.g. a single pixel.
+    /// The region is clamped to the canvas, `None` fetches the whole canvas.
+    fn current_fetch_region(&self) -> Option<FetchCanvasRegion> {
+        None
+    }
+
     /// informs that fetching failed for some reason
     fn fetch_err(&self, err: FetchCanvasError);
 }
@@ -21,6 +21,10 @@ use pool::{mixed_pool::PoolSyncPoint, mt_datatypes::PoolVec};
 use crate::{
     backend_thread::{BackendThread, BackendThreadInitData},
     backends::vulkan::vulkan::{VulkanBackendLoadedIo, VulkanBackendLoadingIo},
+    device_lost::{
+        DeviceLostInjector, GpuResourcesInvalidated, GpuResourcesInvalidatedListener,
+        GpuResourcesInvalidatedListeners,
+    },
     window::{BackendDisplayRequirements, BackendRawDisplayHandle, BackendWindow},
 };
 
@@ -30,7 +34,7 @@ use super::backend_mt::GraphicsBackendMultiThreaded;
 
 use graphics_types::{
     commands::{AllCommands, CommandCanvasResized, CommandsMisc},
-    gpu::Gpus,
+    gpu::{GpuTimings, Gpus},
     types::{
         GraphicsBackendMemory, GraphicsMemoryAllocationMode, GraphicsMemoryAllocationType,
         WindowProps,
@@ -75,8 +79,10 @@ pub struct GraphicsBackendMemoryUsage {
 #[derive(Debug)]
 pub struct GraphicsBackendLoading {
     memory_usage: GraphicsBackendMemoryUsage,
+    gpu_timings: Arc<GpuTimings>,
 
     backend: BackendThread,
+    device_lost_injector: DeviceLostInjector,
 
     custom_pipes: Option<CustomPipelines>,
 
@@ -101,6 +107,8 @@ impl GraphicsBackendLoading {
         let buffer_memory_usage: Arc<AtomicU64> = Default::default();
         let stream_memory_usage: Arc<AtomicU64> = Default::default();
         let staging_memory_usage: Arc<AtomicU64> = Default::default();
+        let gpu_timings: Arc<GpuTimings> = Default::default();
+        let device_lost_injector = DeviceLostInjector::default();
 
         let backend = BackendThread::new(
             backend.clone(),
@@ -115,6 +123,8 @@ impl GraphicsBackendLoading {
             buffer_memory_usage.clone(),
             stream_memory_usage.clone(),
             staging_memory_usage.clone(),
+            gpu_timings.clone(),
+            device_lost_injector.clone(),
             io,
         )?;
         benchmark.bench("initializing the backend instance");
@@ -126,8 +136,10 @@ impl GraphicsBackendLoading {
                 stream_memory_usage,
                 staging_memory_usage,
             },
+            gpu_timings,
 
             backend,
+            device_lost_injector,
             custom_pipes,
 
             config_dbg: *config_dbg,
@@ -145,10 +157,22 @@ pub struct GraphicsBackendBase {
 
     window_props: WindowProps,
     memory_usage: GraphicsBackendMemoryUsage,
+    #[hiarc_skip_unsafe]
+    gpu_timings: Arc<GpuTimings>,
 
     #[hiarc_skip_unsafe]
     custom_pipes: Option<CustomPipelines>,
     pipeline_names: HashMap<String, usize>,
+
+    #[hiarc_skip_unsafe]
+    device_lost_injector: DeviceLostInjector,
+    device_recreations: u32,
+    /// Notified after the current frame.
+    #[hiarc_skip_unsafe]
+    pending_invalidation: Option<GpuResourcesInvalidated>,
+    /// The recreated device renders to a fake surface,
+    /// until the window notifies the backend again.
+    needs_window_surface: bool,
 }
 
 impl GraphicsBackendBase {
@@ -193,7 +217,9 @@ impl GraphicsBackendBase {
         )?;
         benchmark.bench("gl backend loading");
 
-        let backend_mt = Arc::new(GraphicsBackendMultiThreaded { backend_mt });
+        let backend_mt = Arc::new(GraphicsBackendMultiThreaded {
+            backend_mt: RwLock::new(backend_mt),
+        });
 
         // clear first frame
         let cmd_swap = CommandsMisc::Swap;
@@ -226,9 +252,15 @@ impl GraphicsBackendBase {
                 canvas_height,
             },
             memory_usage: backend_loading.memory_usage,
+            gpu_timings: backend_loading.gpu_timings,
 
             custom_pipes: backend_loading.custom_pipes,
             pipeline_names,
+
+            device_lost_injector: backend_loading.device_lost_injector,
+            device_recreations: 0,
+            pending_invalidation: None,
+            needs_window_surface: false,
         };
         res.run_cmds(&buffer, &stream_data)?;
         benchmark.bench("gl first swap");
@@ -244,8 +276,17 @@ impl GraphicsBackendBase {
         self.backend_cmds_in_use.clear();
         buffer.replace(&mut self.backend_cmds_in_use);
 
-        self.backend
-            .run_cmds(stream_data, &mut self.backend_cmds_in_use)?;
+        if let Some(backend_mt) = self
+            .backend
+            .run_cmds(stream_data, &mut self.backend_cmds_in_use)?
+        {
+            *self.backend_mt.backend_mt.write() = backend_mt;
+            self.device_recreations += 1;
+            self.pending_invalidation = Some(GpuResourcesInvalidated {
+                recreations: self.device_recreations,
+            });
+            self.needs_window_surface = true;
+        }
 
         Ok(())
     }
@@ -351,11 +392,31 @@ impl GraphicsBackendBase {
 }
 
 #[derive(Debug, Hiarc)]
-pub struct GraphicsBackend(RefCell<GraphicsBackendBase>);
+pub struct GraphicsBackend(
+    RefCell<GraphicsBackendBase>,
+    #[hiarc_skip_unsafe] RefCell<GpuResourcesInvalidatedListeners>,
+);
 
 impl GraphicsBackend {
     pub fn new(backend_base: GraphicsBackendBase) -> Rc<Self> {
-        Rc::new(Self(RefCell::new(backend_base)))
+        Rc::new(Self(RefCell::new(backend_base), Default::default()))
+    }
+
+    /// Called after the device was lost & recreated,
+    /// the listener has to upload its textures, buffers etc. again.
+    pub fn add_resources_invalidated_listener(&self, listener: GpuResourcesInvalidatedListener) {
+        self.1.borrow_mut().add(listener);
+    }
+
+    /// Lets the next frame fail with a lost device, to test the recovery.
+    pub fn inject_device_lost(&self) {
+        self.0.borrow().device_lost_injector.inject();
+    }
+
+    /// Whether the device was recreated & the window has to create its surface again,
+    /// see [`GraphicsBackend::window_created_ntfy`].
+    pub fn take_needs_window_surface(&self) -> bool {
+        std::mem::take(&mut self.0.borrow_mut().needs_window_surface)
     }
 
     #[must_use]
@@ -377,6 +438,12 @@ impl GraphicsBackend {
         self.0.borrow().memory_usage.clone()
     }
 
+    /// The gpu times of the render passes, see [`GpuTimings`].
+    #[must_use]
+    pub fn gpu_timings(&self) -> Arc<GpuTimings> {
+        self.0.borrow().gpu_timings.clone()
+    }
+
     pub fn window_created_ntfy(
         &self,
         window: BackendWindow,
@@ -392,7 +459,15 @@ impl GraphicsBackend {
 
 impl GraphicsBackendInterface for GraphicsBackend {
     fn run_cmds(&self, buffer: &BackendCommands, stream_data: &GraphicsStreamedData) {
-        self.0.borrow_mut().run_cmds(buffer, stream_data).unwrap(); // TODO: unwrap?
+        let invalidated = {
+            let mut backend = self.0.borrow_mut();
+            backend.run_cmds(buffer, stream_data).unwrap(); // TODO: unwrap?
+            backend.pending_invalidation.take()
+        };
+        // the listeners usually use the backend themselves
+        if let Some(ev) = invalidated {
+            self.1.borrow().notify(&ev);
+        }
     }
 
     fn mem_alloc(&self, alloc_type: GraphicsMemoryAllocationType) -> GraphicsBackendMemory {
@@ -433,6 +508,6 @@ impl GraphicsBackendInterface for GraphicsBackend {
     }
 
     fn gpus(&self) -> Arc<Gpus> {
-        self.0.borrow().backend_mt.backend_mt.gpus()
+        self.0.borrow().backend_mt.backend_mt.read().gpus()
     }
 }
@@ -8,6 +8,7 @@ use graphics_types::{
     types::{GraphicsBackendMemory, GraphicsMemoryAllocationMode, GraphicsMemoryAllocationType},
 };
 use hiarc::Hiarc;
+use parking_lot::RwLock;
 
 #[derive(Debug, Hiarc)]
 pub enum GraphicsBackendMtType {
@@ -30,6 +31,7 @@ impl GraphicsBackendMtType {
                 auto: Gpu {
                     name: "null".to_string(),
                     ty: GpuType::Invalid,
+                    device_local_memory: 0,
                 },
                 gpus: Default::default(),
                 cur: CurGpu {
@@ -45,7 +47,8 @@ impl GraphicsBackendMtType {
 /// The multi-threaded backend part to [`graphics::graphics_mt::GraphicsMultiThreaded`]
 #[derive(Debug, Hiarc)]
 pub struct GraphicsBackendMultiThreaded {
-    pub backend_mt: GraphicsBackendMtType,
+    /// Replaced if the device was recreated.
+    pub backend_mt: RwLock<GraphicsBackendMtType>,
 }
 
 impl GraphicsBackendMtInterface for GraphicsBackendMultiThreaded {
@@ -54,7 +57,7 @@ impl GraphicsBackendMtInterface for GraphicsBackendMultiThreaded {
         alloc_type: GraphicsMemoryAllocationType,
         mode: GraphicsMemoryAllocationMode,
     ) -> GraphicsBackendMemory {
-        self.backend_mt.unwrap().mem_alloc(alloc_type, mode)
+        self.backend_mt.read().unwrap().mem_alloc(alloc_type, mode)
     }
 
     fn try_flush_mem(
@@ -63,6 +66,7 @@ impl GraphicsBackendMtInterface for GraphicsBackendMultiThreaded {
         do_expensive_flushing: bool,
     ) -> anyhow::Result<()> {
         self.backend_mt
+            .read()
             .unwrap()
             .try_flush_mem(mem, do_expensive_flushing)
     }
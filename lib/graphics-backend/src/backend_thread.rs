@@ -21,6 +21,7 @@ use graphics_types::{
         AllCommands, GRAPHICS_DEFAULT_UNIFORM_SIZE, GRAPHICS_MAX_UNIFORM_RENDER_COUNT,
         StreamDataMax,
     },
+    gpu::GpuTimings,
     rendering::GlVertex,
 };
 use hiarc::Hiarc;
@@ -41,6 +42,8 @@ use crate::{
         },
     },
     cache::get_backend_cache,
+    device_lost::{DeviceLostDecision, DeviceLostInjector, DeviceLostRecovery, is_device_lost},
+    safe_mode::GfxInitFailureMarker,
     window::{BackendDisplayRequirements, BackendWindow},
 };
 
@@ -105,7 +108,12 @@ pub enum BackendThreadBackendEvent {
         buffer_memory_usage: Arc<AtomicU64>,
         stream_memory_usage: Arc<AtomicU64>,
         staging_memory_usage: Arc<AtomicU64>,
+        #[hiarc_skip_unsafe]
+        gpu_timings: Arc<GpuTimings>,
         write_files: BackendWriteFiles,
+        #[hiarc_skip_unsafe]
+        device_lost_injector: DeviceLostInjector,
+        io: IoFileSys,
     },
     FinishInit {
         data: BackendThreadInitData,
@@ -137,6 +145,9 @@ pub enum BackendThreadBackendEvent {
 #[derive(Debug, Hiarc)]
 pub enum BackendThreadFrontendEvent {
     InitFromMainThread(BackendThreadInitFromMainThread),
+    /// The device was lost & recreated, renders to a fake surface
+    /// until the window notifies the backend again.
+    DeviceRecreated(BackendThreadInitFromMainThread),
     BuffersFromBackend {
         streamed_data: GraphicsStreamedDataSyncSend,
         /// empty cmd buffer, can be reused
@@ -144,6 +155,131 @@ pub enum BackendThreadFrontendEvent {
     },
 }
 
+/// Everything to create the vulkan backend again,
+/// after the device was lost.
+struct VulkanRecreateData {
+    display_requirements: BackendDisplayRequirements,
+    config_dbg: ConfigDebug,
+    config_gl: ConfigBackend,
+    custom_pipes: Option<CustomPipelines>,
+    texture_memory_usage: Arc<AtomicU64>,
+    buffer_memory_usage: Arc<AtomicU64>,
+    stream_memory_usage: Arc<AtomicU64>,
+    staging_memory_usage: Arc<AtomicU64>,
+    gpu_timings: Arc<GpuTimings>,
+    write_files: BackendWriteFiles,
+    runtime_threadpool: Arc<rayon::ThreadPool>,
+    device_lost_injector: DeviceLostInjector,
+}
+
+impl VulkanRecreateData {
+    fn recreate(
+        &self,
+        loaded_io: VulkanBackendLoadedIo,
+        window_width: u32,
+        window_height: u32,
+    ) -> anyhow::Result<Box<VulkanBackend>> {
+        let options = Options {
+            dbg: &self.config_dbg,
+            gl: &self.config_gl,
+        };
+        let loading = VulkanBackendLoading::new(
+            self.display_requirements.clone(),
+            self.texture_memory_usage.clone(),
+            self.buffer_memory_usage.clone(),
+            self.stream_memory_usage.clone(),
+            self.staging_memory_usage.clone(),
+            self.gpu_timings.clone(),
+            &options,
+            self.custom_pipes.clone(),
+        )?;
+        let main_thread_init =
+            VulkanBackend::init_with_fake_surface(&VulkanBackend::main_thread_data(&loading))?;
+        let mut backend = VulkanBackend::new(
+            loading,
+            loaded_io,
+            &self.runtime_threadpool,
+            main_thread_init,
+            window_width,
+            window_height,
+            &options,
+            self.write_files.clone(),
+        )?;
+        backend.device_lost_injector = self.device_lost_injector.clone();
+        Ok(backend)
+    }
+}
+
+enum InUseDataPerBackend {
+    Vulkan(VulkanInUseStreamData),
+    Null,
+}
+
+/// The streamed data the frontend writes to, backed by the memory of `stream_data`.
+fn vulkan_streamed_data(
+    backend: &VulkanBackend,
+    stream_data: &VulkanInUseStreamData,
+) -> GraphicsStreamedData {
+    let mem = unsafe {
+        stream_data.cur_stream_vertex_buffer.memories[0]
+            .mapped_memory
+            .get_mem_typed::<GlVertex>(StreamDataMax::MaxVertices as usize)
+    };
+
+    let mut graphics_uniform_data = backend.props.graphics_uniform_buffers.new();
+    graphics_uniform_data.extend(stream_data.cur_stream_uniform_buffers.memories.iter().map(
+        |uni| unsafe {
+            GraphicsStreamedUniformData::new(GraphicsStreamedUniformRawData::Raw(
+                GraphicsStreamUniformRawDataStatic::new(
+                    uni.mapped_memory
+                        .get_mem(GRAPHICS_MAX_UNIFORM_RENDER_COUNT * GRAPHICS_DEFAULT_UNIFORM_SIZE),
+                    Box::new(stream_data.cur_stream_uniform_buffers.clone()),
+                ),
+            ))
+        },
+    ));
+
+    GraphicsStreamedData::new(
+        GraphicsStreamVertices::Static(GraphicsStreamVerticesStatic::new(
+            mem,
+            Box::new(stream_data.cur_stream_vertex_buffer.clone()),
+        )),
+        graphics_uniform_data,
+    )
+}
+
+fn null_stream_data() -> GraphicsStreamedData {
+    GraphicsStreamedData::new(
+        GraphicsStreamVertices::Vec({
+            let mut res = Vec::with_capacity(4096);
+            res.resize_with(4096, Default::default);
+            res
+        }),
+        {
+            let mut res = PoolVec::new_without_pool();
+            res.resize_with(64, || {
+                GraphicsStreamedUniformData::new(GraphicsStreamedUniformRawData::Vector(vec![
+                    0;
+                    1024
+                ]))
+            });
+            res
+        },
+    )
+}
+
+fn run_backend_cmds(
+    backend: &mut dyn DriverBackendInterface,
+    cmds: &mut Vec<AllCommands>,
+) -> anyhow::Result<()> {
+    backend.start_commands(cmds.len());
+
+    for cmd in cmds.drain(..) {
+        backend.run_command(cmd)?;
+    }
+    backend.end_commands()
+}
+
 #[derive(Debug, Hiarc)]
 struct FileWriterDrop {
     write_files: BackendWriteFiles,
@@ -188,6 +324,8 @@ impl BackendThread {
         buffer_memory_usage: Arc<AtomicU64>,
         stream_memory_usage: Arc<AtomicU64>,
         staging_memory_usage: Arc<AtomicU64>,
+        gpu_timings: Arc<GpuTimings>,
+        device_lost_injector: DeviceLostInjector,
         io: IoFileSys,
     ) -> anyhow::Result<Self> {
         let (events, recv) = std::sync::mpsc::channel();
@@ -205,7 +343,10 @@ impl BackendThread {
             buffer_memory_usage,
             stream_memory_usage,
             staging_memory_usage,
+            gpu_timings,
             write_files: write_files.clone(),
+            device_lost_injector,
+            io: io.clone(),
         })?;
 
         let thread = thread_priority::ThreadBuilder::default()
@@ -309,22 +450,42 @@ impl BackendThread {
         self.sync_points.push(sync_point);
     }
 
+    /// Returns the new multi-threaded backend, if the device was recreated.
     pub fn run_cmds(
         &self,
         stream_data: &GraphicsStreamedData,
         cmds: &mut Vec<AllCommands>,
-    ) -> anyhow::Result<()> {
-        let BackendThreadFrontendEvent::BuffersFromBackend {
-            streamed_data: stream_data_cmd,
-            cmds: mut cmds_cmd,
-        } = self.recv_events.recv()?
-        else {
-            return Err(anyhow!(
-                "frontend commands other than stream data is not supported yet, also there must be a stream data command every frame"
-            ));
+    ) -> anyhow::Result<Option<GraphicsBackendMtType>> {
+        let mut recreated = None;
+        let (stream_data_cmd, mut cmds_cmd) = loop {
+            match self.recv_events.recv()? {
+                BackendThreadFrontendEvent::BuffersFromBackend {
+                    streamed_data,
+                    cmds,
+                } => break (streamed_data, cmds),
+                BackendThreadFrontendEvent::DeviceRecreated(init) => {
+                    recreated = Some(match init {
+                        BackendThreadInitFromMainThread::Vulkan(data) => {
+                            GraphicsBackendMtType::Vulkan(VulkanBackend::create_mt_backend(&data))
+                        }
+                        BackendThreadInitFromMainThread::Null => {
+                            GraphicsBackendMtType::Null(NullBackend::get_mt_backend())
+                        }
+                    });
+                }
+                BackendThreadFrontendEvent::InitFromMainThread(_) => {
+                    return Err(anyhow!(
+                        "frontend commands other than stream data is not supported yet, also there must be a stream data command every frame"
+                    ));
+                }
+            }
         };
 
         std::mem::swap(cmds, &mut cmds_cmd);
+        if recreated.is_some() {
+            // these commands use resources of the lost device
+            cmds_cmd.clear();
+        }
 
         let stream_data_cmd = GraphicsStreamedData::from_sync_send_wrapper(stream_data_cmd);
         let stream_data = stream_data
@@ -339,7 +500,7 @@ impl BackendThread {
             cmds: cmds_cmd,
             stream_data: stream_data.try_into_sync_send_wrapper()?,
         })?;
-        Ok(())
+        Ok(recreated)
     }
 
     pub fn attach_frame_fetcher(
@@ -374,11 +535,23 @@ impl BackendThread {
             buffer_memory_usage,
             stream_memory_usage,
             staging_memory_usage,
+            gpu_timings,
             write_files,
+            device_lost_injector,
+            io,
         } = load_ev
         else {
             return Err(anyhow!("first event is always the load event"));
         };
+        let recreate_display_requirements = display_requirements.clone();
+        let recreate_custom_pipes = custom_pipes.clone();
+        let recreate_memory_usage = (
+            texture_memory_usage.clone(),
+            buffer_memory_usage.clone(),
+            stream_memory_usage.clone(),
+            staging_memory_usage.clone(),
+        );
+        let recreate_gpu_timings = gpu_timings.clone();
         let backend_loading = match backend_ty.to_ascii_lowercase().as_str() {
             "null" => GraphicsBackendLoadingType::Null(NullBackend {}),
             // "vulkan"
@@ -394,6 +567,7 @@ impl BackendThread {
                     buffer_memory_usage,
                     stream_memory_usage,
                     staging_memory_usage,
+                    gpu_timings,
                     &options,
                     custom_pipes,
                 )?;
@@ -424,6 +598,7 @@ impl BackendThread {
             ));
         };
 
+        let mut vulkan_recreate = None;
         let mut backend = match data {
             BackendThreadInitData::Vulkan {
                 data,
@@ -439,7 +614,27 @@ impl BackendThread {
                 let BackendThreadMainThreadInit::Vulkan(main_thread_init) = main_thread_init else {
                     return Err(anyhow!("main thread init data was not of type vulkan"));
                 };
-                GraphicsBackendType::Vulkan(VulkanBackend::new(
+                let (
+                    texture_memory_usage,
+                    buffer_memory_usage,
+                    stream_memory_usage,
+                    staging_memory_usage,
+                ) = recreate_memory_usage;
+                vulkan_recreate = Some(VulkanRecreateData {
+                    display_requirements: recreate_display_requirements,
+                    config_dbg: dbg,
+                    config_gl: gl.clone(),
+                    custom_pipes: recreate_custom_pipes,
+                    texture_memory_usage,
+                    buffer_memory_usage,
+                    stream_memory_usage,
+                    staging_memory_usage,
+                    gpu_timings: recreate_gpu_timings,
+                    write_files: write_files.clone(),
+                    runtime_threadpool: runtime_threadpool.clone(),
+                    device_lost_injector: device_lost_injector.clone(),
+                });
+                let mut backend = VulkanBackend::new(
                     *loading,
                     data,
                     &runtime_threadpool,
@@ -448,75 +643,26 @@ impl BackendThread {
                     window_height,
                     &Options { dbg: &dbg, gl: &gl },
                     write_files,
-                )?)
+                )?;
+                backend.device_lost_injector = device_lost_injector;
+                GraphicsBackendType::Vulkan(backend)
             }
             BackendThreadInitData::Null => GraphicsBackendType::Null(NullBackend {}),
         };
-
-        enum InUseDataPerBackend {
-            Vulkan(VulkanInUseStreamData),
-            Null,
-        }
+        let mut device_lost_recovery = DeviceLostRecovery::default();
 
         let (mut stream_data, mut next_in_use_data, mut in_use_data) = match &mut backend {
             GraphicsBackendType::Vulkan(backend) => {
                 let stream_data = backend.get_stream_data()?;
                 let next_stream_data = backend.get_stream_data()?;
-
-                let mem = unsafe {
-                    stream_data.cur_stream_vertex_buffer.memories[0]
-                        .mapped_memory
-                        .get_mem_typed::<GlVertex>(StreamDataMax::MaxVertices as usize)
-                };
-
-                let mut graphics_uniform_data = backend.props.graphics_uniform_buffers.new();
-                graphics_uniform_data.extend(
-                    stream_data
-                        .cur_stream_uniform_buffers
-                        .memories
-                        .iter()
-                        .map(|uni| unsafe {
-                            GraphicsStreamedUniformData::new(GraphicsStreamedUniformRawData::Raw(
-                                GraphicsStreamUniformRawDataStatic::new(
-                                    uni.mapped_memory.get_mem(
-                                        GRAPHICS_MAX_UNIFORM_RENDER_COUNT
-                                            * GRAPHICS_DEFAULT_UNIFORM_SIZE,
-                                    ),
-                                    Box::new(stream_data.cur_stream_uniform_buffers.clone()),
-                                ),
-                            ))
-                        }),
-                );
-
                 (
-                    GraphicsStreamedData::new(
-                        GraphicsStreamVertices::Static(GraphicsStreamVerticesStatic::new(
-                            mem,
-                            Box::new(stream_data.cur_stream_vertex_buffer.clone()),
-                        )),
-                        graphics_uniform_data,
-                    ),
+                    vulkan_streamed_data(backend, &stream_data),
                     InUseDataPerBackend::Vulkan(stream_data),
                     InUseDataPerBackend::Vulkan(next_stream_data),
                 )
             }
             GraphicsBackendType::Null(_) => (
-                GraphicsStreamedData::new(
-                    GraphicsStreamVertices::Vec({
-                        let mut res = Vec::with_capacity(4096);
-                        res.resize_with(4096, Default::default);
-                        res
-                    }),
-                    {
-                        let mut res = PoolVec::new_without_pool();
-                        res.resize_with(64, || {
-                            GraphicsStreamedUniformData::new(
-                                GraphicsStreamedUniformRawData::Vector(vec![0; 1024]),
-                            )
-                        });
-                        res
-                    },
-                ),
+                null_stream_data(),
                 InUseDataPerBackend::Null,
                 InUseDataPerBackend::Null,
             ),
@@ -553,80 +699,63 @@ impl BackendThread {
                         }
                     }
                     in_use_data = next_in_use_data;
-                    let backend_ref = backend.as_mut();
-                    backend_ref.start_commands(cmds.len());
-
-                    for cmd in cmds.drain(..) {
-                        backend_ref.run_command(cmd)?;
+                    if let Err(err) = run_backend_cmds(backend.as_mut(), &mut cmds) {
+                        let (GraphicsBackendType::Vulkan(lost_backend), Some(recreate)) =
+                            (&backend, &vulkan_recreate)
+                        else {
+                            return Err(err);
+                        };
+                        if !is_device_lost(&err) {
+                            return Err(err);
+                        }
+                        cmds.clear();
+
+                        let recreated = match device_lost_recovery.device_lost() {
+                            DeviceLostDecision::Recreate => {
+                                log::error!("{err:#}, recreating the device.");
+                                let loaded_io = lost_backend.loaded_io();
+                                let (window_width, window_height) = lost_backend.window_size();
+                                // the lost device & all its resources must be destroyed first
+                                in_use_data = InUseDataPerBackend::Null;
+                                backend = GraphicsBackendType::Null(NullBackend {});
+                                recreate.recreate(loaded_io, window_width, window_height)
+                            }
+                            DeviceLostDecision::SafeMode => Err(err),
+                        };
+                        let mut recreated = match recreated {
+                            Ok(recreated) => recreated,
+                            Err(err) => {
+                                log::error!("{err:#}, the next launch uses the safe mode.");
+                                GfxInitFailureMarker::record_device_lost(
+                                    &io,
+                                    "Vulkan",
+                                    &recreate.config_gl.gpu,
+                                    &err,
+                                );
+                                return Err(err);
+                            }
+                        };
+                        // like the first frame after the init
+                        in_use_data = InUseDataPerBackend::Vulkan(recreated.get_stream_data()?);
+                        sender.send(BackendThreadFrontendEvent::DeviceRecreated(
+                            BackendThreadInitFromMainThread::Vulkan(
+                                recreated.get_main_thread_data(),
+                            ),
+                        ))?;
+                        backend = GraphicsBackendType::Vulkan(recreated);
                     }
-                    backend_ref.end_commands()?;
 
                     (stream_data, next_in_use_data) = match &mut backend {
                         GraphicsBackendType::Vulkan(backend) => {
                             let stream_data = backend.get_stream_data()?;
-
-                            let mem = unsafe {
-                                stream_data.cur_stream_vertex_buffer.memories[0]
-                                    .mapped_memory
-                                    .get_mem_typed::<GlVertex>(StreamDataMax::MaxVertices as usize)
-                            };
-
-                            let mut graphics_uniform_data =
-                                backend.props.graphics_uniform_buffers.new();
-                            graphics_uniform_data.extend(
-                                stream_data.cur_stream_uniform_buffers.memories.iter().map(
-                                    |uni| unsafe {
-                                        GraphicsStreamedUniformData::new(
-                                            GraphicsStreamedUniformRawData::Raw(
-                                                GraphicsStreamUniformRawDataStatic::new(
-                                                    uni.mapped_memory.get_mem(
-                                                        GRAPHICS_MAX_UNIFORM_RENDER_COUNT
-                                                            * GRAPHICS_DEFAULT_UNIFORM_SIZE,
-                                                    ),
-                                                    Box::new(
-                                                        stream_data
-                                                            .cur_stream_uniform_buffers
-                                                            .clone(),
-                                                    ),
-                                                ),
-                                            ),
-                                        )
-                                    },
-                                ),
-                            );
-
                             (
-                                GraphicsStreamedData::new(
-                                    GraphicsStreamVertices::Static(
-                                        GraphicsStreamVerticesStatic::new(
-                                            mem,
-                                            Box::new(stream_data.cur_stream_vertex_buffer.clone()),
-                                        ),
-                                    ),
-                                    graphics_uniform_data,
-                                ),
+                                vulkan_streamed_data(backend, &stream_data),
                                 InUseDataPerBackend::Vulkan(stream_data),
                             )
                         }
-                        GraphicsBackendType::Null(_) => (
-                            GraphicsStreamedData::new(
-                                GraphicsStreamVertices::Vec({
-                                    let mut res = Vec::with_capacity(4096);
-                                    res.resize_with(4096, Default::default);
-                                    res
-                                }),
-                                {
-                                    let mut res = PoolVec::new_without_pool();
-                                    res.resize_with(64, || {
-                                        GraphicsStreamedUniformData::new(
-                                            GraphicsStreamedUniformRawData::Vector(vec![0; 1024]),
-                                        )
-                                    });
-                                    res
-                                },
-                            ),
-                            InUseDataPerBackend::Null,
-                        ),
+                        GraphicsBackendType::Null(_) => {
+                            (null_stream_data(), InUseDataPerBackend::Null)
+                        }
                     };
 
                     sender.send(BackendThreadFrontendEvent::BuffersFromBackend {
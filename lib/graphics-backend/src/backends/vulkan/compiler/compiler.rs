@@ -22,7 +22,7 @@ pub enum ShaderCompilerType {
     WgslInSpvOut,
 }
 
-#[derive(Debug, Hiarc)]
+#[derive(Debug, Hiarc, Clone)]
 pub struct ShaderCompiler {
     pub(crate) ty: ShaderCompilerType,
     #[hiarc_skip_unsafe]
@@ -0,0 +1,444 @@
+use std::{
+    collections::VecDeque,
+    ops::Range,
+    sync::{Arc, atomic::Ordering},
+};
+
+use anyhow::anyhow;
+use ash::vk;
+use graphics_types::gpu::{GpuTimerPass, GpuTimings};
+use hiarc::Hiarc;
+use strum::EnumCount;
+
+use super::logical_device::LogicalDevice;
+
+/// Further markers of a frame are ignored.
+const MAX_MARKERS_PER_FRAME: usize = 32;
+/// The markers & the frame end.
+const QUERIES_PER_FRAME: u32 = MAX_MARKERS_PER_FRAME as u32 + 1;
+/// The number of frames the timings are averaged over.
+const AVERAGE_WINDOW: usize = 60;
+
+/// Reads the results of timestamp queries,
+/// abstracts the device for the bookkeeping of [`GpuTimerRing`].
+pub trait TimestampQueryReader {
+    /// Fills `timestamps` with the results of the queries starting at `first_query`.
+    ///
+    /// Queries that were never written are `None`.
+    fn read(&mut self, first_query: u32, timestamps: &mut [Option<u64>]) -> anyhow::Result<()>;
+}
+
+#[derive(Debug, Default, Clone)]
+struct FrameQueries {
+    markers: Vec<GpuTimerPass>,
+    /// The frame was submitted, but its results were not read yet.
+    pending: bool,
+}
+
+/// The bookkeeping of the timestamp queries of all frames in flight.
+///
+/// Every frame in flight owns a fixed range of queries.
+/// The results are read when the frame slot is used again,
+/// so the gpu already finished it and reading never stalls.
+#[derive(Debug)]
+pub struct GpuTimerRing {
+    frames: Vec<FrameQueries>,
+    cur_frame: Option<usize>,
+
+    /// Nanoseconds per timestamp tick.
+    timestamp_period: f64,
+    history: VecDeque<[u64; GpuTimerPass::COUNT]>,
+    timestamps: Vec<Option<u64>>,
+}
+
+impl GpuTimerRing {
+    pub fn new(frames_in_flight: usize, timestamp_period: f32) -> Self {
+        Self {
+            frames: vec![Default::default(); frames_in_flight],
+            cur_frame: None,
+
+            timestamp_period: timestamp_period as f64,
+            history: Default::default(),
+            timestamps: Default::default(),
+        }
+    }
+
+    pub fn query_count(&self) -> u32 {
+        self.frames.len() as u32 * QUERIES_PER_FRAME
+    }
+
+    /// Drops all pending results, e.g. after the swapchain was recreated.
+    pub fn resize(&mut self, frames_in_flight: usize) {
+        self.frames = vec![Default::default(); frames_in_flight];
+        self.cur_frame = None;
+    }
+
+    /// Starts a frame in the given slot.
+    ///
+    /// Reads the results of the previous frame of this slot first,
+    /// the caller must make sure the gpu finished that frame.
+    ///
+    /// Returns the queries of the slot, which must be reset before they are written.
+    pub fn begin_frame(
+        &mut self,
+        frame_index: usize,
+        reader: &mut impl TimestampQueryReader,
+    ) -> anyhow::Result<Range<u32>> {
+        let frame = self
+            .frames
+            .get_mut(frame_index)
+            .ok_or_else(|| anyhow!("frame index {frame_index} is out of range"))?;
+        let first_query = frame_index as u32 * QUERIES_PER_FRAME;
+        self.cur_frame = None;
+
+        if frame.pending {
+            frame.pending = false;
+            self.timestamps.clear();
+            // the markers & the frame end
+            self.timestamps.resize(frame.markers.len() + 1, None);
+            reader.read(first_query, &mut self.timestamps)?;
+
+            if self.history.len() >= AVERAGE_WINDOW {
+                self.history.pop_front();
+            }
+            self.history.push_back(Self::pass_times(
+                &frame.markers,
+                &self.timestamps,
+                self.timestamp_period,
+            ));
+        }
+        frame.markers.clear();
+        self.cur_frame = Some(frame_index);
+
+        Ok(first_query..first_query + QUERIES_PER_FRAME)
+    }
+
+    /// The query for the next marker of the current frame.
+    ///
+    /// `None` outside of a frame or if the frame has too many markers.
+    pub fn marker(&mut self, pass: GpuTimerPass) -> Option<u32> {
+        let frame_index = self.cur_frame?;
+        let frame = &mut self.frames[frame_index];
+        (frame.markers.len() < MAX_MARKERS_PER_FRAME).then(|| {
+            frame.markers.push(pass);
+            frame_index as u32 * QUERIES_PER_FRAME + frame.markers.len() as u32 - 1
+        })
+    }
+
+    /// Ends the current frame, returns the query for the frame end.
+    pub fn end_frame(&mut self) -> Option<u32> {
+        let frame_index = self.cur_frame.take()?;
+        let frame = &mut self.frames[frame_index];
+        frame.pending = true;
+        Some(frame_index as u32 * QUERIES_PER_FRAME + frame.markers.len() as u32)
+    }
+
+    /// The gpu time of every pass in nanoseconds.
+    ///
+    /// A pass lasts until the next written timestamp.
+    /// Markers that were never written (e.g. because nothing was
+    /// rendered after them) count as zero.
+    fn pass_times(
+        markers: &[GpuTimerPass],
+        timestamps: &[Option<u64>],
+        timestamp_period: f64,
+    ) -> [u64; GpuTimerPass::COUNT] {
+        let mut res = [0; GpuTimerPass::COUNT];
+        for (i, pass) in markers.iter().enumerate() {
+            let Some(start) = timestamps[i] else {
+                continue;
+            };
+            let Some(end) = timestamps[i + 1..].iter().find_map(|t| *t) else {
+                continue;
+            };
+            res[*pass as usize] += (end.saturating_sub(start) as f64 * timestamp_period) as u64;
+        }
+        res
+    }
+
+    /// The pass times in nanoseconds, averaged over the last frames.
+    pub fn averages(&self) -> [u64; GpuTimerPass::COUNT] {
+        let mut res = [0; GpuTimerPass::COUNT];
+        if self.history.is_empty() {
+            return res;
+        }
+        for frame in &self.history {
+            for (avg, time) in res.iter_mut().zip(frame) {
+                *avg += time;
+            }
+        }
+        res.map(|time| time / self.history.len() as u64)
+    }
+}
+
+struct QueryPoolReader<'a> {
+    device: &'a LogicalDevice,
+    query_pool: vk::QueryPool,
+}
+
+impl TimestampQueryReader for QueryPoolReader<'_> {
+    fn read(&mut self, first_query: u32, timestamps: &mut [Option<u64>]) -> anyhow::Result<()> {
+        // the value & its availability
+        let mut results = vec![[0u64; 2]; timestamps.len()];
+        match unsafe {
+            self.device.device.get_query_pool_results(
+                self.query_pool,
+                first_query,
+                &mut results,
+                vk::QueryResultFlags::TYPE_64 | vk::QueryResultFlags::WITH_AVAILABILITY,
+            )
+        } {
+            // not ready only means that some queries were never written
+            Ok(()) | Err(vk::Result::NOT_READY) => {}
+            Err(err) => return Err(anyhow!("reading the timestamp queries failed: {err}")),
+        }
+        for (timestamp, [value, available]) in timestamps.iter_mut().zip(results) {
+            *timestamp = (available != 0).then_some(value);
+        }
+        Ok(())
+    }
+}
+
+/// Measures the gpu time of the [`GpuTimerPass`]es with timestamp queries.
+#[derive(Debug, Hiarc)]
+pub struct GpuTimer {
+    #[hiarc_skip_unsafe]
+    query_pool: vk::QueryPool,
+    #[hiarc_skip_unsafe]
+    ring: GpuTimerRing,
+    #[hiarc_skip_unsafe]
+    timings: Arc<GpuTimings>,
+
+    device: Arc<LogicalDevice>,
+}
+
+impl GpuTimer {
+    /// `None` if the device does not support timestamp queries.
+    pub fn new(
+        device: Arc<LogicalDevice>,
+        frames_in_flight: usize,
+        timings: Arc<GpuTimings>,
+    ) -> anyhow::Result<Option<Self>> {
+        let limits = &device.phy_device.limits;
+        timings
+            .supported
+            .store(limits.timestamps_supported, Ordering::Relaxed);
+        if !limits.timestamps_supported {
+            return Ok(None);
+        }
+
+        let ring = GpuTimerRing::new(frames_in_flight, limits.timestamp_period);
+        let query_pool = Self::create_query_pool(&device, ring.query_count())?;
+        Ok(Some(Self {
+            query_pool,
+            ring,
+            timings,
+            device,
+        }))
+    }
+
+    fn create_query_pool(
+        device: &LogicalDevice,
+        query_count: u32,
+    ) -> anyhow::Result<vk::QueryPool> {
+        let create_info = vk::QueryPoolCreateInfo::default()
+            .query_type(vk::QueryType::TIMESTAMP)
+            .query_count(query_count);
+        unsafe { device.device.create_query_pool(&create_info, None) }
+            .map_err(|err| anyhow!("creating the timestamp query pool failed: {err}"))
+    }
+
+    /// Recreates the query pool for the new amount of frames in flight.
+    ///
+    /// The device must be idle.
+    pub fn recreate(&mut self, frames_in_flight: usize) -> anyhow::Result<()> {
+        self.ring.resize(frames_in_flight);
+        let query_pool = Self::create_query_pool(&self.device, self.ring.query_count())?;
+        let old_query_pool = std::mem::replace(&mut self.query_pool, query_pool);
+        unsafe {
+            self.device.device.destroy_query_pool(old_query_pool, None);
+        }
+        Ok(())
+    }
+
+    /// Reads the results of the previous frame of this slot
+    /// and resets its queries in the main command buffer.
+    pub fn begin_frame(
+        &mut self,
+        frame_index: usize,
+        command_buffer: vk::CommandBuffer,
+    ) -> anyhow::Result<()> {
+        let queries = self.ring.begin_frame(
+            frame_index,
+            &mut QueryPoolReader {
+                device: &self.device,
+                query_pool: self.query_pool,
+            },
+        )?;
+        for (pass_time, avg) in self.timings.passes.iter().zip(self.ring.averages()) {
+            pass_time.store(avg, Ordering::Relaxed);
+        }
+
+        unsafe {
+            self.device.device.cmd_reset_query_pool(
+                command_buffer,
+                self.query_pool,
+                queries.start,
+                queries.len() as u32,
+            );
+        }
+        Ok(())
+    }
+
+    /// The query pool & the query the marker writes to.
+    pub fn marker(&mut self, pass: GpuTimerPass) -> Option<(vk::QueryPool, u32)> {
+        self.ring.marker(pass).map(|query| (self.query_pool, query))
+    }
+
+    /// Writes the frame end, after all passes were recorded into the main command buffer.
+    pub fn end_frame(&mut self, command_buffer: vk::CommandBuffer) {
+        if let Some(query) = self.ring.end_frame() {
+            unsafe {
+                self.device.device.cmd_write_timestamp(
+                    command_buffer,
+                    vk::PipelineStageFlags::BOTTOM_OF_PIPE,
+                    self.query_pool,
+                    query,
+                );
+            }
+        }
+    }
+}
+
+impl Drop for GpuTimer {
+    fn drop(&mut self) {
+        unsafe {
+            self.device.device.destroy_query_pool(self.query_pool, None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use graphics_types::gpu::GpuTimerPass;
+    use strum::EnumCount;
+
+    use super::{GpuTimerRing, QUERIES_PER_FRAME, TimestampQueryReader};
+
+    /// Queries that were written by the "gpu".
+    #[derive(Debug, Default)]
+    struct MockQueries {
+        written: HashMap<u32, u64>,
+        reads: Vec<u32>,
+    }
+
+    impl TimestampQueryReader for MockQueries {
+        fn read(&mut self, first_query: u32, timestamps: &mut [Option<u64>]) -> anyhow::Result<()> {
+            self.reads.push(first_query);
+            for (i, timestamp) in timestamps.iter_mut().enumerate() {
+                *timestamp = self.written.get(&(first_query + i as u32)).copied();
+            }
+            Ok(())
+        }
+    }
+
+    /// Records a frame, where every marker lasts `pass_time` ticks.
+    fn frame(
+        ring: &mut GpuTimerRing,
+        queries: &mut MockQueries,
+        frame_index: usize,
+        passes: &[GpuTimerPass],
+        pass_time: u64,
+    ) {
+        let range = ring.begin_frame(frame_index, queries).unwrap();
+        // reset
+        queries.written.retain(|query, _| !range.contains(query));
+        let mut time = 1000;
+        for pass in passes {
+            let query = ring.marker(*pass).unwrap();
+            assert!(range.contains(&query));
+            queries.written.insert(query, time);
+            time += pass_time;
+        }
+        let query = ring.end_frame().unwrap();
+        assert!(range.contains(&query));
+        queries.written.insert(query, time);
+    }
+
+    #[test]
+    fn ring_buffered_readback() {
+        let mut ring = GpuTimerRing::new(2, 2.0);
+        let mut queries = MockQueries::default();
+        let passes = [GpuTimerPass::MapBackground, GpuTimerPass::Ui];
+        assert_eq!(ring.query_count(), 2 * QUERIES_PER_FRAME);
+
+        // markers outside of frames are ignored
+        assert!(ring.marker(GpuTimerPass::Ui).is_none());
+        assert!(ring.end_frame().is_none());
+
+        // nothing to read while the slots were never used
+        frame(&mut ring, &mut queries, 0, &passes, 10);
+        frame(&mut ring, &mut queries, 1, &passes, 10);
+        assert!(queries.reads.is_empty());
+        assert_eq!(ring.averages(), [0; GpuTimerPass::COUNT]);
+
+        // using a slot again reads its previous frame
+        frame(&mut ring, &mut queries, 0, &passes, 30);
+        assert_eq!(queries.reads, vec![0]);
+        assert_eq!(
+            ring.averages()[GpuTimerPass::MapBackground as usize],
+            10 * 2
+        );
+        assert_eq!(ring.averages()[GpuTimerPass::Ui as usize], 10 * 2);
+        assert_eq!(ring.averages()[GpuTimerPass::Entities as usize], 0);
+
+        frame(&mut ring, &mut queries, 1, &passes, 30);
+        frame(&mut ring, &mut queries, 0, &passes, 30);
+        assert_eq!(queries.reads, vec![0, QUERIES_PER_FRAME, 0]);
+        // averaged over (10 + 10 + 30) / 3 ticks
+        assert_eq!(ring.averages()[GpuTimerPass::Ui as usize], 50 * 2 / 3);
+
+        // recreating the swapchain drops pending results
+        ring.resize(3);
+        assert_eq!(ring.query_count(), 3 * QUERIES_PER_FRAME);
+        assert!(ring.marker(GpuTimerPass::Ui).is_none());
+        ring.begin_frame(1, &mut queries).unwrap();
+        assert_eq!(queries.reads.len(), 3);
+        assert!(ring.begin_frame(3, &mut queries).is_err());
+    }
+
+    #[test]
+    fn unwritten_markers() {
+        let mut ring = GpuTimerRing::new(1, 1.0);
+        let mut queries = MockQueries::default();
+
+        ring.begin_frame(0, &mut queries).unwrap();
+        let background = ring.marker(GpuTimerPass::MapBackground).unwrap();
+        // nothing was rendered for the entities, so the marker was never written
+        ring.marker(GpuTimerPass::Entities).unwrap();
+        let particles = ring.marker(GpuTimerPass::Particles).unwrap();
+        // the same pass twice is summed up
+        let background2 = ring.marker(GpuTimerPass::MapBackground).unwrap();
+        let end = ring.end_frame().unwrap();
+        queries.written.insert(background, 100);
+        queries.written.insert(particles, 150);
+        queries.written.insert(background2, 170);
+        queries.written.insert(end, 200);
+
+        ring.begin_frame(0, &mut queries).unwrap();
+        let averages = ring.averages();
+        assert_eq!(averages[GpuTimerPass::MapBackground as usize], 50 + 30);
+        assert_eq!(averages[GpuTimerPass::Entities as usize], 0);
+        assert_eq!(averages[GpuTimerPass::Particles as usize], 20);
+        assert_eq!(averages[GpuTimerPass::Ui as usize], 0);
+
+        // too many markers are ignored
+        for _ in 0..super::MAX_MARKERS_PER_FRAME {
+            assert!(ring.marker(GpuTimerPass::Ui).is_some());
+        }
+        assert!(ring.marker(GpuTimerPass::Ui).is_none());
+        assert_eq!(ring.end_frame(), Some(QUERIES_PER_FRAME - 1));
+    }
+}
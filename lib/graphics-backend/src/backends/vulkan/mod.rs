@@ -15,6 +15,7 @@ pub mod frame;
 pub mod frame_collection;
 pub mod frame_resources;
 pub mod framebuffer;
+pub mod gpu_timer;
 pub mod image;
 pub mod image_view;
 pub mod instance;
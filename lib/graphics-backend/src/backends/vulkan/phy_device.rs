@@ -11,7 +11,7 @@ use log::{info, warn};
 
 use super::{
     Options, instance::Instance, vulkan_config::Config, vulkan_dbg::is_verbose_mode,
-    vulkan_limits::Limits,
+    vulkan_device::Device, vulkan_limits::Limits,
 };
 
 #[derive(Debug, Hiarc)]
@@ -302,6 +302,22 @@ impl PhyDevice {
             ));
         }
 
+        limits.timestamp_period = device_prop.limits.timestamp_period;
+        limits.timestamps_supported = device_prop.limits.timestamp_period > 0.0
+            && queue_prop_list[queue_node_index as usize].timestamp_valid_bits > 0;
+
+        let mem_properties = unsafe {
+            instance
+                .vk_instance
+                .get_physical_device_memory_properties(cur_device)
+        };
+        let device_local_memory: u64 = mem_properties.memory_heaps
+            [..mem_properties.memory_heap_count as usize]
+            .iter()
+            .filter(|heap| heap.flags.contains(vk::MemoryHeapFlags::DEVICE_LOCAL))
+            .map(|heap| heap.size)
+            .sum();
+
         let res = Self {
             instance,
 
@@ -310,8 +326,9 @@ impl PhyDevice {
                 auto: auto_gpu.unwrap(),
                 cur: CurGpu {
                     name: renderer_name.clone(),
-                    msaa_sampling_count: limits.max_multi_sample.as_raw(),
+                    msaa_sampling_count: Device::get_max_sample_count(&limits).as_raw(),
                     ty: found_gpu_type,
+                    device_local_memory,
                 },
             }),
             limits,
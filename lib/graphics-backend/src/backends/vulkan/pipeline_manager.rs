@@ -193,24 +193,37 @@ impl<'a> PipelineManager<'a> {
             SupportedBlendModes::Additive => vk::BlendFactor::ONE,
             SupportedBlendModes::Alpha => vk::BlendFactor::SRC_ALPHA,
             SupportedBlendModes::None => vk::BlendFactor::SRC_COLOR,
+            SupportedBlendModes::AlphaAdditive => vk::BlendFactor::SRC_ALPHA,
+            SupportedBlendModes::Multiply => vk::BlendFactor::DST_COLOR,
+            SupportedBlendModes::Screen => vk::BlendFactor::ONE_MINUS_DST_COLOR,
         };
 
         let dst_blend_factor_color = match blend_mode {
             SupportedBlendModes::Additive => vk::BlendFactor::ONE_MINUS_SRC_ALPHA,
             SupportedBlendModes::Alpha => vk::BlendFactor::ONE_MINUS_SRC_ALPHA,
             SupportedBlendModes::None => vk::BlendFactor::SRC_COLOR,
+            SupportedBlendModes::AlphaAdditive => vk::BlendFactor::ONE,
+            SupportedBlendModes::Multiply => vk::BlendFactor::ONE_MINUS_SRC_ALPHA,
+            SupportedBlendModes::Screen => vk::BlendFactor::ONE,
         };
 
+        // the layer blend modes keep the alpha of what is below
         let src_blend_factor_alpha = match blend_mode {
             SupportedBlendModes::Additive => vk::BlendFactor::ONE,
             SupportedBlendModes::Alpha => vk::BlendFactor::SRC_ALPHA,
             SupportedBlendModes::None => vk::BlendFactor::SRC_COLOR,
+            SupportedBlendModes::AlphaAdditive
+            | SupportedBlendModes::Multiply
+            | SupportedBlendModes::Screen => vk::BlendFactor::ZERO,
         };
 
         let dst_blend_factor_alpha = match blend_mode {
             SupportedBlendModes::Additive => vk::BlendFactor::ZERO,
             SupportedBlendModes::Alpha => vk::BlendFactor::ONE_MINUS_SRC_ALPHA,
             SupportedBlendModes::None => vk::BlendFactor::SRC_COLOR,
+            SupportedBlendModes::AlphaAdditive
+            | SupportedBlendModes::Multiply
+            | SupportedBlendModes::Screen => vk::BlendFactor::ONE,
         };
 
         color_blend_attachment.src_color_blend_factor = src_blend_factor_color;
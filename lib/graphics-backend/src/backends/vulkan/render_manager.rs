@@ -67,6 +67,9 @@ impl<'a> RenderManager<'a> {
             BlendType::None => SupportedBlendModes::None as usize,
             BlendType::Alpha => SupportedBlendModes::Alpha as usize,
             BlendType::Additive => SupportedBlendModes::Additive as usize,
+            BlendType::AlphaAdditive => SupportedBlendModes::AlphaAdditive as usize,
+            BlendType::Multiply => SupportedBlendModes::Multiply as usize,
+            BlendType::Screen => SupportedBlendModes::Screen as usize,
         }
     }
 
@@ -15,7 +15,7 @@ use crossbeam::channel::{Receiver, bounded, unbounded};
 use graphics_backend_traits::{
     frame_fetcher_plugin::{
         BackendFrameFetcher, BackendPresentedImageDataRgba, FetchCanvasError, FetchCanvasIndex,
-        OffscreenCanvasId,
+        FetchCanvasRegion, OffscreenCanvasId,
     },
     plugin::{BackendCustomPipeline, BackendRenderExecuteInterface},
     traits::{DriverBackendInterface, GraphicsBackendMtInterface},
@@ -40,7 +40,7 @@ use graphics_types::{
         GRAPHICS_DEFAULT_UNIFORM_SIZE, GRAPHICS_MAX_UNIFORM_RENDER_COUNT,
         GRAPHICS_UNIFORM_INSTANCE_COUNT, GlVertexTex3DStream, RenderSpriteInfo, StreamDataMax,
     },
-    gpu::Gpus,
+    gpu::{GpuTimerPass, GpuTimings, Gpus},
     rendering::{GlVertex, State, StateTexture},
     types::{
         GraphicsBackendMemory, GraphicsBackendMemoryAllocation, GraphicsBackendMemoryStatic,
@@ -60,6 +60,7 @@ use crate::{
     backends::{
         null::mem_alloc_lazy, types::BackendWriteFiles, vulkan::pipeline_cache::PipelineCache,
     },
+    device_lost::{DeviceLostError, DeviceLostInjector},
     window::{
         BackendDisplayRequirements, BackendSurface, BackendSurfaceAndHandles, BackendSwapchain,
         BackendWindow,
@@ -83,6 +84,7 @@ use super::{
         FrameResources, FrameResourcesPool, RenderThreadFrameResources,
         RenderThreadFrameResourcesPool,
     },
+    gpu_timer::GpuTimer,
     image::Image,
     instance::Instance,
     logical_device::LogicalDevice,
@@ -229,6 +231,9 @@ pub(crate) struct VulkanBackendProps {
 
     uniform_buffer_descr_pools: Arc<parking_lot::Mutex<DeviceDescriptorPools>>,
 
+    #[hiarc_skip_unsafe]
+    gpu_timings: Arc<GpuTimings>,
+
     /************************
      * ERROR MANAGEMENT
      ************************/
@@ -419,6 +424,7 @@ impl VulkanBackendLoading {
         buffer_memory_usage: Arc<AtomicU64>,
         stream_memory_usage: Arc<AtomicU64>,
         staging_memory_usage: Arc<AtomicU64>,
+        gpu_timings: Arc<GpuTimings>,
 
         options: &Options,
 
@@ -491,6 +497,8 @@ impl VulkanBackendLoading {
                     DescriptorPoolType::Uniform,
                 )?,
 
+                gpu_timings,
+
                 custom_pipes: VulkanCustomPipes::new(custom_pipes.unwrap_or_default()),
             },
         };
@@ -547,6 +555,8 @@ pub struct VulkanBackend {
 
     main_render_command_buffer: Option<AutoCommandBuffer>,
     pub(crate) frame: Arc<parking_lot::Mutex<Frame>>,
+    /// `None` if the device does not support timestamp queries.
+    gpu_timer: Option<GpuTimer>,
 
     order_id_gen: usize,
     cur_frame: u64,
@@ -581,6 +591,8 @@ pub struct VulkanBackend {
     frame_resources_pool: FrameResourcesPool,
 
     pipeline_cache: Option<PipelineCache>,
+
+    pub(crate) device_lost_injector: DeviceLostInjector,
 }
 
 impl VulkanBackend {
@@ -696,6 +708,7 @@ impl VulkanBackend {
             CommandsMisc::CanvasResized(cmd) => self.cmd_canvas_resized(&cmd),
             CommandsMisc::Multisampling(cmd) => self.cmd_mutli_sampling(cmd),
             CommandsMisc::VSync(cmd) => self.cmd_vsync(cmd),
+            CommandsMisc::GpuTimerMarker(pass) => self.cmd_gpu_timer_marker(pass),
         }
     }
 
@@ -897,6 +910,7 @@ impl VulkanBackend {
     fn get_presented_image_data_impl(
         &mut self,
         fetch_index: FetchCanvasIndex,
+        fetch_region: Option<FetchCanvasRegion>,
     ) -> anyhow::Result<BackendPresentedImageDataRgba, FetchCanvasError> {
         let width: u32;
         let height: u32;
@@ -919,8 +933,21 @@ impl VulkanBackend {
             render.surf_format.format == vk::Format::R8G8B8A8_UNORM || is_b8_g8_r8_a8;
         if uses_rgba_like_format && self.last_presented_swap_chain_image_index != u32::MAX {
             let viewport = render.native.swap_img_and_viewport_extent;
-            width = viewport.width;
-            height = viewport.height;
+            // e.g. the window might have been resized since the region was requested
+            let (offset_x, offset_y) = match fetch_region {
+                Some(region) => {
+                    let x = region.x.min(viewport.width.saturating_sub(1));
+                    let y = region.y.min(viewport.height.saturating_sub(1));
+                    width = region.width.min(viewport.width - x);
+                    height = region.height.min(viewport.height - y);
+                    (x as i32, y as i32)
+                }
+                None => {
+                    width = viewport.width;
+                    height = viewport.height;
+                    (0, 0)
+                }
+            };
 
             let image_total_size: usize = width as usize * height as usize * 4;
 
@@ -1003,7 +1030,16 @@ impl VulkanBackend {
                 let mut image_blit_region = vk::ImageBlit::default();
                 image_blit_region.src_subresource.aspect_mask = vk::ImageAspectFlags::COLOR;
                 image_blit_region.src_subresource.layer_count = 1;
-                image_blit_region.src_offsets[1] = blit_size;
+                image_blit_region.src_offsets[0] = vk::Offset3D {
+                    x: offset_x,
+                    y: offset_y,
+                    z: 0,
+                };
+                image_blit_region.src_offsets[1] = vk::Offset3D {
+                    x: offset_x + blit_size.x,
+                    y: offset_y + blit_size.y,
+                    z: 1,
+                };
                 image_blit_region.dst_subresource.aspect_mask = vk::ImageAspectFlags::COLOR;
                 image_blit_region.dst_subresource.layer_count = 1;
                 image_blit_region.dst_offsets[1] = blit_size;
@@ -1030,6 +1066,11 @@ impl VulkanBackend {
                 let mut image_copy_region = vk::ImageCopy::default();
                 image_copy_region.src_subresource.aspect_mask = vk::ImageAspectFlags::COLOR;
                 image_copy_region.src_subresource.layer_count = 1;
+                image_copy_region.src_offset = vk::Offset3D {
+                    x: offset_x,
+                    y: offset_y,
+                    z: 0,
+                };
                 image_copy_region.dst_subresource.aspect_mask = vk::ImageAspectFlags::COLOR;
                 image_copy_region.dst_subresource.layer_count = 1;
                 image_copy_region.extent.width = width;
@@ -1103,7 +1144,9 @@ impl VulkanBackend {
                         .fence(&mut self.current_frame_resources),
                 )
             }
-            .map_err(|err| anyhow!("Queue submit failed: {err}"))?;
+            .map_err(|err| {
+                anyhow::Error::new(err).context(format!("Queue submit failed: {err}"))
+            })?;
             unsafe {
                 self.props.ash_vk.vk_device.device.wait_for_fences(
                     &[fetch_frame_buffer
@@ -1254,7 +1297,7 @@ impl VulkanBackend {
         Ok(())
     }
 
-    fn execute_memory_command_buffer(&mut self) {
+    fn execute_memory_command_buffer(&mut self) -> anyhow::Result<()> {
         if let Some(memory_command_buffer) = self.props.device.memory_command_buffer.take() {
             let command_buffer = memory_command_buffer.command_buffer;
             drop(memory_command_buffer);
@@ -1263,12 +1306,11 @@ impl VulkanBackend {
             let submit_info = vk::SubmitInfo::default().command_buffers(&command_buffers);
             unsafe {
                 let queue = &self.props.queue.queues.lock();
-                self.props
-                    .ash_vk
-                    .vk_device
-                    .device
-                    .queue_submit(queue.graphics_queue, &[submit_info], vk::Fence::null())
-                    .unwrap();
+                self.props.ash_vk.vk_device.device.queue_submit(
+                    queue.graphics_queue,
+                    &[submit_info],
+                    vk::Fence::null(),
+                )?;
             }
             unsafe {
                 let queue = &self.props.queue.queues.lock();
@@ -1276,10 +1318,10 @@ impl VulkanBackend {
                     .ash_vk
                     .vk_device
                     .device
-                    .queue_wait_idle(queue.graphics_queue)
-                    .unwrap();
+                    .queue_wait_idle(queue.graphics_queue)?;
             }
         }
+        Ok(())
     }
 
     fn flush_memory_ranges(&mut self) {
@@ -1413,6 +1455,34 @@ impl VulkanBackend {
         Ok(())
     }
 
+    /// Starts a new command group, so the render thread can
+    /// write the timestamp before the following commands.
+    fn cmd_gpu_timer_marker(&mut self, pass: GpuTimerPass) -> anyhow::Result<()> {
+        let Some(query) = self
+            .gpu_timer
+            .as_mut()
+            .and_then(|gpu_timer| gpu_timer.marker(pass))
+        else {
+            return Ok(());
+        };
+        if let Some(current_command_group) =
+            self.current_command_groups.get(&self.render.cur_canvas())
+        {
+            self.new_command_group(
+                current_command_group.canvas_index,
+                current_command_group.render_pass_index,
+                current_command_group.render_pass,
+            )?;
+        }
+        if let Some(current_command_group) = self
+            .current_command_groups
+            .get_mut(&self.render.cur_canvas())
+        {
+            current_command_group.timestamp_query = Some(query);
+        }
+        Ok(())
+    }
+
     fn new_command_group(
         &mut self,
         canvas_index: FrameCanvasIndex,
@@ -1489,6 +1559,9 @@ impl VulkanBackend {
         self.upload_non_flushed_buffers();
 
         FrameCollector::collect(self)?;
+        if let Some(gpu_timer) = &mut self.gpu_timer {
+            gpu_timer.end_frame(command_buffer);
+        }
 
         // add frame resources
         self.frame_resources.insert(
@@ -1573,6 +1646,10 @@ impl VulkanBackend {
                 .map_err(|err| anyhow!("could not reset fences {err}"))
         }?;
 
+        if self.device_lost_injector.take() {
+            return Err(anyhow::Error::new(DeviceLostError)
+                .context("Submitting to graphics queue failed (injected)"));
+        }
         unsafe {
             let queue = &self.props.queue.queues.lock();
             self.props.ash_vk.vk_device.device.queue_submit(
@@ -1582,7 +1659,9 @@ impl VulkanBackend {
                     .fence(&mut self.current_frame_resources),
             )
         }
-        .map_err(|err| anyhow!("Submitting to graphics queue failed: {err}"))?;
+        .map_err(|err| {
+            anyhow::Error::new(err).context(format!("Submitting to graphics queue failed: {err}"))
+        })?;
 
         std::mem::swap(
             &mut self.render.busy_acquire_image_semaphores[self.render.cur_image_index as usize],
@@ -1602,6 +1681,7 @@ impl VulkanBackend {
             for i in keys.iter() {
                 // get current frame and fill the frame fetcher with it
                 let fetch_index = self.frame_fetchers.get(i).unwrap().current_fetch_index();
+                let fetch_region = self.frame_fetchers.get(i).unwrap().current_fetch_region();
                 // ignore offscreen canvases that requested to skip this frame
                 if let FetchCanvasIndex::Offscreen(index) = fetch_index
                     && self
@@ -1610,7 +1690,7 @@ impl VulkanBackend {
                 {
                     continue;
                 }
-                let img_data = self.get_presented_image_data_impl(fetch_index);
+                let img_data = self.get_presented_image_data_impl(fetch_index, fetch_region);
                 if let Ok(img_data) = img_data {
                     let frame_fetcher = self.frame_fetchers.get(i).unwrap();
                     frame_fetcher.next_frame(img_data);
@@ -1640,7 +1720,10 @@ impl VulkanBackend {
             None
         } else {
             queue_present_res
-                .map_err(|err| anyhow!("Presenting graphics queue failed: {err}"))?
+                .map_err(|err| {
+                    anyhow::Error::new(err)
+                        .context(format!("Presenting graphics queue failed: {err}"))
+                })?
                 .then_some(vk::Result::SUBOPTIMAL_KHR)
         };
 
@@ -1706,8 +1789,9 @@ impl VulkanBackend {
             return Ok(());
         }
 
-        let (next_image_index, is_suboptimal) =
-            acquire_res.map_err(|err| anyhow!("Acquiring next image failed: {err}"))?;
+        let (next_image_index, is_suboptimal) = acquire_res.map_err(|err| {
+            anyhow::Error::new(err).context(format!("Acquiring next image failed: {err}"))
+        })?;
         if is_suboptimal {
             self.recreate_swap_chain = match &self.render.onscreen.inner_type {
                 RenderSetupNativeType::Swapchain(swapchain) => {
@@ -1784,17 +1868,20 @@ impl VulkanBackend {
             AutoCommandBufferType::Primary,
             &mut self.current_frame_resources.render,
         )?);
-        self.frame.lock().new_frame(
-            self.main_render_command_buffer
-                .as_ref()
-                .unwrap()
-                .command_buffer,
-        );
+        let main_command_buffer = self
+            .main_render_command_buffer
+            .as_ref()
+            .unwrap()
+            .command_buffer;
+        self.frame.lock().new_frame(main_command_buffer);
+        if let Some(gpu_timer) = &mut self.gpu_timer {
+            gpu_timer.begin_frame(self.render.cur_image_index as usize, main_command_buffer)?;
+        }
         Ok(())
     }
 
     fn pure_memory_frame(&mut self) -> anyhow::Result<()> {
-        self.execute_memory_command_buffer();
+        self.execute_memory_command_buffer()?;
 
         // reset streamed data
         self.upload_non_flushed_buffers();
@@ -2107,6 +2194,10 @@ impl VulkanBackend {
         if old_swap_chain_image_count != self.render.onscreen.swap_chain_image_count() {
             self.cleanup_vulkan::<false>();
             self.init_vulkan()?;
+
+            if let Some(gpu_timer) = &mut self.gpu_timer {
+                gpu_timer.recreate(self.render.onscreen.swap_chain_image_count())?;
+            }
         }
 
         Ok(())
@@ -2921,6 +3012,35 @@ impl VulkanBackend {
         Ok(())
     }
 
+    /// Init data for a backend that renders to a fake surface,
+    /// until the main thread created the surface of the window.
+    pub fn init_with_fake_surface(
+        data: &VulkanMainThreadData,
+    ) -> anyhow::Result<VulkanMainThreadInit> {
+        let surface = unsafe {
+            BackendWindow::create_fake_headless_surface().create_vk_surface(
+                &data.instance.vk_entry,
+                &data.instance.vk_instance,
+                &data.mem_allocator,
+            )
+        }?;
+        Ok(VulkanMainThreadInit { surface })
+    }
+
+    /// The io of this backend, to create a new backend
+    /// after the device was lost.
+    pub fn loaded_io(&self) -> VulkanBackendLoadedIo {
+        VulkanBackendLoadedIo {
+            shader_compiler: (*self.render.shader_compiler).clone(),
+            // the cache of the lost device is not trusted
+            pipeline_cache: None,
+        }
+    }
+
+    pub fn window_size(&self) -> (u32, u32) {
+        (self.window_width, self.window_height)
+    }
+
     pub fn new(
         mut loading: VulkanBackendLoading,
         loaded_io: VulkanBackendLoadedIo,
@@ -3073,6 +3193,12 @@ impl VulkanBackend {
         );
         benchmark.bench("creating the vk streamed buffers & pools");
 
+        let gpu_timer = GpuTimer::new(
+            loading.props.ash_vk.vk_device.clone(),
+            render.onscreen.swap_chain_image_count(),
+            loading.props.gpu_timings.clone(),
+        )?;
+
         let mut res = Box::new(Self {
             props: loading.props,
             ash_surf: VulkanBackendSurfaceAsh {
@@ -3124,6 +3250,7 @@ impl VulkanBackend {
             offscreen_canvases_frame_fetching_skips: Default::default(),
 
             frame: Frame::new(),
+            gpu_timer,
 
             window_width,
             window_height,
@@ -3142,6 +3269,8 @@ impl VulkanBackend {
             frame_resources_pool,
 
             pipeline_cache,
+
+            device_lost_injector: Default::default(),
         });
         benchmark.bench("creating vk backend instance");
 
@@ -3254,6 +3383,16 @@ impl VulkanBackend {
                             &mut frame_resource,
                         )
                         .unwrap();
+                    if let Some((query_pool, query)) = cmd_group.timestamp_query {
+                        unsafe {
+                            device.device.cmd_write_timestamp(
+                                command_buffer.command_buffer,
+                                vk::PipelineStageFlags::BOTTOM_OF_PIPE,
+                                query_pool,
+                                query,
+                            );
+                        }
+                    }
                     for mut next_cmd in cmd_group.cmds.drain(..) {
                         let cmd = next_cmd.raw_render_command.take().unwrap();
                         if let Err(err) = command_cb_render(
@@ -3428,15 +3567,14 @@ impl DriverBackendInterface for VulkanBackend {
 
 impl Drop for VulkanBackend {
     fn drop(&mut self) {
-        unsafe {
+        // a lost device can't become idle anymore,
+        // its resources are destroyed anyway.
+        if let Err(err) = unsafe {
             let _g = self.props.queue.queues.lock();
-            self.props
-                .ash_vk
-                .vk_device
-                .device
-                .device_wait_idle()
-                .unwrap()
-        };
+            self.props.ash_vk.vk_device.device.device_wait_idle()
+        } {
+            log::warn!("waiting for the device to become idle failed: {err}");
+        }
 
         self.cleanup_vulkan::<true>();
 
@@ -568,23 +568,24 @@ impl Device {
         vk::SampleCountFlags::TYPE_1
     }
 
+    /// The highest sample count the device supports that is not above `ms_count`.
+    ///
+    /// Devices don't need to support all sample counts below their maximum,
+    /// so unsupported counts fall back to the next lower supported one.
     pub fn get_sample_count(ms_count: u32, limits: &Limits) -> vk::SampleCountFlags {
-        let max_sample_count: vk::SampleCountFlags = Self::get_max_sample_count(limits);
-        if ms_count >= 64 && max_sample_count >= vk::SampleCountFlags::TYPE_64 {
-            return vk::SampleCountFlags::TYPE_64;
-        } else if ms_count >= 32 && max_sample_count >= vk::SampleCountFlags::TYPE_32 {
-            return vk::SampleCountFlags::TYPE_32;
-        } else if ms_count >= 16 && max_sample_count >= vk::SampleCountFlags::TYPE_16 {
-            return vk::SampleCountFlags::TYPE_16;
-        } else if ms_count >= 8 && max_sample_count >= vk::SampleCountFlags::TYPE_8 {
-            return vk::SampleCountFlags::TYPE_8;
-        } else if ms_count >= 4 && max_sample_count >= vk::SampleCountFlags::TYPE_4 {
-            return vk::SampleCountFlags::TYPE_4;
-        } else if ms_count >= 2 && max_sample_count >= vk::SampleCountFlags::TYPE_2 {
-            return vk::SampleCountFlags::TYPE_2;
-        }
-
-        vk::SampleCountFlags::TYPE_1
+        [
+            vk::SampleCountFlags::TYPE_64,
+            vk::SampleCountFlags::TYPE_32,
+            vk::SampleCountFlags::TYPE_16,
+            vk::SampleCountFlags::TYPE_8,
+            vk::SampleCountFlags::TYPE_4,
+            vk::SampleCountFlags::TYPE_2,
+        ]
+        .into_iter()
+        .find(|&sample_count| {
+            ms_count >= sample_count.as_raw() && limits.max_multi_sample.contains(sample_count)
+        })
+        .unwrap_or(vk::SampleCountFlags::TYPE_1)
     }
 
     pub fn image_barrier(
@@ -993,3 +994,91 @@ impl Device {
         ))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use ash::vk;
+
+    use super::{Device, Limits};
+
+    fn limits(max_multi_sample: vk::SampleCountFlags) -> Limits {
+        Limits {
+            max_multi_sample,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn sample_count_clamping() {
+        let all = limits(
+            vk::SampleCountFlags::TYPE_1
+                | vk::SampleCountFlags::TYPE_2
+                | vk::SampleCountFlags::TYPE_4
+                | vk::SampleCountFlags::TYPE_8,
+        );
+        assert_eq!(
+            Device::get_max_sample_count(&all),
+            vk::SampleCountFlags::TYPE_8
+        );
+        assert_eq!(
+            Device::get_sample_count(0, &all),
+            vk::SampleCountFlags::TYPE_1
+        );
+        assert_eq!(
+            Device::get_sample_count(1, &all),
+            vk::SampleCountFlags::TYPE_1
+        );
+        assert_eq!(
+            Device::get_sample_count(2, &all),
+            vk::SampleCountFlags::TYPE_2
+        );
+        assert_eq!(
+            Device::get_sample_count(4, &all),
+            vk::SampleCountFlags::TYPE_4
+        );
+        // non power of two counts are rounded down
+        assert_eq!(
+            Device::get_sample_count(6, &all),
+            vk::SampleCountFlags::TYPE_4
+        );
+        // clamped to the device limit
+        assert_eq!(
+            Device::get_sample_count(8, &all),
+            vk::SampleCountFlags::TYPE_8
+        );
+        assert_eq!(
+            Device::get_sample_count(64, &all),
+            vk::SampleCountFlags::TYPE_8
+        );
+
+        // unsupported counts below the maximum fall back to a lower supported count
+        let sparse = limits(
+            vk::SampleCountFlags::TYPE_1
+                | vk::SampleCountFlags::TYPE_4
+                | vk::SampleCountFlags::TYPE_16,
+        );
+        assert_eq!(
+            Device::get_sample_count(2, &sparse),
+            vk::SampleCountFlags::TYPE_1
+        );
+        assert_eq!(
+            Device::get_sample_count(8, &sparse),
+            vk::SampleCountFlags::TYPE_4
+        );
+        assert_eq!(
+            Device::get_sample_count(32, &sparse),
+            vk::SampleCountFlags::TYPE_16
+        );
+
+        // no multi sampling at all
+        let none = limits(vk::SampleCountFlags::TYPE_1);
+        assert_eq!(
+            Device::get_max_sample_count(&none),
+            vk::SampleCountFlags::TYPE_1
+        );
+        assert_eq!(
+            Device::get_sample_count(8, &none),
+            vk::SampleCountFlags::TYPE_1
+        );
+    }
+}
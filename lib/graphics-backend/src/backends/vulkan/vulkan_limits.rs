@@ -12,4 +12,9 @@ pub struct Limits {
     pub max_multi_sample: vk::SampleCountFlags,
 
     pub min_uniform_align: u32,
+
+    /// Nanoseconds per timestamp tick.
+    pub timestamp_period: f32,
+    /// The graphics queue supports timestamp queries.
+    pub timestamps_supported: bool,
 }
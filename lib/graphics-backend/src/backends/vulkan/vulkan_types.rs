@@ -209,6 +209,9 @@ pub enum SupportedBlendModes {
     Alpha = 0,
     None = 1,
     Additive = 2,
+    AlphaAdditive = 3,
+    Multiply = 4,
+    Screen = 5,
 }
 
 #[derive(Debug, Hiarc, FromPrimitive, Copy, Clone, PartialEq, EnumCount)]
@@ -295,7 +298,7 @@ pub type PipelinesColorMasks = [PipelinesSampler; ColorWriteMaskType::COUNT];
 
 #[derive(Debug, Hiarc)]
 pub struct PipelineContainer {
-    // 3 blend modes - 2 viewport & scissor modes - 2 texture modes - 4 stencil modes - 3 color mask types - 3 sampler modes
+    // 6 blend modes - 2 viewport & scissor modes - 2 texture modes - 4 stencil modes - 3 color mask types - 3 sampler modes
     pub pipelines: Box<
         [[[[PipelinesColorMasks; StencilOpType::COUNT]; MAX_TEXTURE_MODES]; CanvasClipModes::COUNT];
             SupportedBlendModes::COUNT],
@@ -356,6 +359,11 @@ pub struct ThreadCommandGroup {
 
     pub in_order_id: usize,
 
+    /// The timestamp that is written before the commands,
+    /// see [`super::gpu_timer::GpuTimer`].
+    #[hiarc_skip_unsafe]
+    pub timestamp_query: Option<(vk::QueryPool, u32)>,
+
     pub cmds: Vec<RenderCommandExecuteBuffer>,
 }
 
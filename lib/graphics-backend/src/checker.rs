@@ -313,6 +313,9 @@ impl GraphicsContainersAPI {
             CommandsMisc::VSync(_) => {
                 // Nothing to do
             }
+            CommandsMisc::GpuTimerMarker(_) => {
+                // Nothing to do
+            }
         }
     }
 
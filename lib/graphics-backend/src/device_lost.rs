@@ -0,0 +1,148 @@
+//! Recovery from a lost graphics device, e.g. after the driver
+//! was reset or the gpu crashed.
+//!
+//! The backend recreates the device, the swapchain & all its caches once,
+//! the owners of textures & buffers are told to upload them again.
+//! If the device is lost a second time, the backend gives up & the next
+//! launch starts in the safe mode.
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+use ash::vk;
+use thiserror::Error;
+
+/// How often the device is recreated, before the backend gives up.
+pub const MAX_DEVICE_RECREATIONS: u32 = 1;
+
+/// The device was lost & all its resources are gone.
+#[derive(Error, Debug, Clone, Copy, Default)]
+#[error("the graphics device was lost")]
+pub struct DeviceLostError;
+
+/// Whether the error, or any error it was caused by, is a lost device.
+pub fn is_device_lost(err: &anyhow::Error) -> bool {
+    err.chain().any(|err| {
+        err.is::<DeviceLostError>()
+            || err
+                .downcast_ref::<vk::Result>()
+                .is_some_and(|err| *err == vk::Result::ERROR_DEVICE_LOST)
+    })
+}
+
+/// Sent after the device was recreated.
+///
+/// All textures, buffers, shader storages & offscreen canvases,
+/// that were created before, do not exist anymore.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GpuResourcesInvalidated {
+    /// How often the device was recreated so far.
+    pub recreations: u32,
+}
+
+pub type GpuResourcesInvalidatedListener = Box<dyn Fn(&GpuResourcesInvalidated)>;
+
+/// Everyone that has to upload its resources again
+/// after the device was recreated.
+#[derive(Default)]
+pub struct GpuResourcesInvalidatedListeners {
+    listeners: Vec<GpuResourcesInvalidatedListener>,
+}
+
+impl std::fmt::Debug for GpuResourcesInvalidatedListeners {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GpuResourcesInvalidatedListeners")
+            .field("listeners", &self.listeners.len())
+            .finish()
+    }
+}
+
+impl GpuResourcesInvalidatedListeners {
+    pub fn add(&mut self, listener: GpuResourcesInvalidatedListener) {
+        self.listeners.push(listener);
+    }
+
+    pub fn notify(&self, ev: &GpuResourcesInvalidated) {
+        for listener in &self.listeners {
+            listener(ev);
+        }
+    }
+}
+
+/// Lets the next submit to the graphics queue fail with a lost device.
+///
+/// Only meant for testing the recovery.
+#[derive(Debug, Clone, Default)]
+pub struct DeviceLostInjector(Arc<AtomicBool>);
+
+impl DeviceLostInjector {
+    pub fn inject(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether a lost device was injected, resets the injection.
+    pub fn take(&self) -> bool {
+        self.0.swap(false, Ordering::SeqCst)
+    }
+}
+
+/// What the backend does with a lost device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceLostDecision {
+    /// Recreate the device & all its resources.
+    Recreate,
+    /// Give up & start the next launch in safe mode.
+    SafeMode,
+}
+
+/// Counts how often the device was lost.
+#[derive(Debug, Default)]
+pub struct DeviceLostRecovery {
+    recreations: u32,
+}
+
+impl DeviceLostRecovery {
+    pub fn device_lost(&mut self) -> DeviceLostDecision {
+        if self.recreations >= MAX_DEVICE_RECREATIONS {
+            DeviceLostDecision::SafeMode
+        } else {
+            self.recreations += 1;
+            DeviceLostDecision::Recreate
+        }
+    }
+
+    pub fn recreations(&self) -> u32 {
+        self.recreations
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::anyhow;
+    use ash::vk;
+
+    use super::{DeviceLostDecision, DeviceLostError, DeviceLostRecovery, is_device_lost};
+
+    #[test]
+    fn device_lost_twice() {
+        assert!(is_device_lost(
+            &anyhow::Error::new(DeviceLostError).context("Submitting to graphics queue failed")
+        ));
+        assert!(is_device_lost(
+            &anyhow::Error::new(vk::Result::ERROR_DEVICE_LOST).context("Presenting failed")
+        ));
+        assert!(!is_device_lost(&anyhow::Error::new(
+            vk::Result::ERROR_OUT_OF_DATE_KHR
+        )));
+        assert!(!is_device_lost(&anyhow!("ERROR_DEVICE_LOST")));
+
+        let mut recovery = DeviceLostRecovery::default();
+        assert_eq!(recovery.device_lost(), DeviceLostDecision::Recreate);
+        assert_eq!(recovery.recreations(), 1);
+        // the recreated device was lost again
+        assert_eq!(recovery.device_lost(), DeviceLostDecision::SafeMode);
+        assert_eq!(recovery.recreations(), 1);
+    }
+}
@@ -19,12 +19,14 @@ pub mod backend_thread;
 mod backends;
 pub mod cache;
 pub mod checker;
+pub mod device_lost;
+pub mod safe_mode;
 pub mod utils;
 pub mod window;
 
 #[cfg(test)]
 mod tests {
-    use std::{rc::Rc, sync::Arc};
+    use std::{cell::RefCell, rc::Rc, sync::Arc};
 
     use base::benchmark::Benchmark;
     use base_fs::filesys::FileSystem;
@@ -53,6 +55,7 @@ mod tests {
             GraphicsBackend, GraphicsBackendBase, GraphicsBackendIoLoading, GraphicsBackendLoading,
         },
         backends::vulkan::compiler::compiler::ShaderCompiler,
+        device_lost::GpuResourcesInvalidated,
     };
 
     fn prepare_backend(
@@ -352,6 +355,49 @@ mod tests {
         backend.run_cmds(&cmds, &stream_data);
     }
 
+    #[test]
+    fn vk_device_lost() {
+        let (backend, stream_data) = prepare_backend(1, Default::default());
+
+        let invalidated: Rc<RefCell<Vec<GpuResourcesInvalidated>>> = Default::default();
+        let listener_invalidated = invalidated.clone();
+        backend.add_resources_invalidated_listener(Box::new(move |ev| {
+            listener_invalidated.borrow_mut().push(*ev)
+        }));
+
+        let cmds = BackendCommands::default();
+        let swap = || {
+            cmds.add_cmd(AllCommands::Misc(CommandsMisc::Swap));
+            backend.run_cmds(&cmds, &stream_data);
+        };
+
+        // the frontend learns about the recreated device one frame later
+        backend.inject_device_lost();
+        swap();
+        swap();
+        swap();
+        assert_eq!(
+            *invalidated.borrow(),
+            vec![GpuResourcesInvalidated { recreations: 1 }]
+        );
+
+        // the recreated device renders to a fake surface until the window is back
+        assert!(backend.take_needs_window_surface());
+        let config_wnd = config::config::ConfigWindow::default();
+        backend
+            .window_created_ntfy(
+                crate::window::BackendWindow::Headless {
+                    width: config_wnd.window_width as u32,
+                    height: config_wnd.window_height as u32,
+                },
+                &Default::default(),
+            )
+            .unwrap();
+        swap();
+        swap();
+        assert_eq!(invalidated.borrow().len(), 1);
+    }
+
     #[test]
     fn shader_compile() {
         let workspace_root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../");
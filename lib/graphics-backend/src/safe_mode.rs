@@ -0,0 +1,207 @@
+//! Recovery from graphics backends that fail to initialize,
+//! e.g. because of old drivers or broken Vulkan ICDs.
+//!
+//! A failed initialization is recorded in a marker file, the next launch
+//! then offers (or after repeated failures selects) the safe mode.
+//! A device that is lost twice in a row selects the safe mode, too.
+
+use base_io::io::IoFileSys;
+use config::config::{ConfigBackend, ConfigDebug, GfxDebugModes};
+use serde::{Deserialize, Serialize};
+
+const FAILURE_MARKER_FILE: &str = "gfx_init_failure.json";
+
+/// After this many failed launches in a row the safe mode
+/// is selected without asking.
+pub const AUTO_SAFE_MODE_FAILURES: u32 = 2;
+
+/// How the next launch initializes the backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GfxSafeModeDecision {
+    /// Start with all features.
+    Full,
+    /// Ask the user whether to start in safe mode.
+    Offer,
+    /// Start in safe mode without asking.
+    Auto,
+}
+
+/// Written when the graphics backend failed to initialize &
+/// removed again once it initialized successfully.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GfxInitFailureMarker {
+    /// How many launches in a row failed.
+    pub failures: u32,
+    /// Whether the last failed launch already ran in safe mode.
+    pub safe_mode: bool,
+    /// The backend that failed, e.g. `Vulkan`.
+    pub backend: String,
+    /// The graphics card that was selected.
+    pub gpu: String,
+    /// The error of the backend, e.g. contains the
+    /// extensions if the instance creation failed.
+    pub err: String,
+}
+
+impl GfxInitFailureMarker {
+    /// Adds a failed launch on top of the previous failures.
+    pub fn escalate(
+        prev: Option<&Self>,
+        safe_mode: bool,
+        backend: &str,
+        gpu: &str,
+        err: &str,
+    ) -> Self {
+        Self {
+            failures: prev.map(|prev| prev.failures).unwrap_or_default() + 1,
+            safe_mode,
+            backend: backend.to_string(),
+            gpu: gpu.to_string(),
+            err: err.to_string(),
+        }
+    }
+
+    pub fn decision(&self) -> GfxSafeModeDecision {
+        if self.failures == 0 {
+            GfxSafeModeDecision::Full
+        } else if self.safe_mode || self.failures >= AUTO_SAFE_MODE_FAILURES {
+            // if even the safe mode failed, a full launch
+            // is not going to succeed either.
+            GfxSafeModeDecision::Auto
+        } else {
+            GfxSafeModeDecision::Offer
+        }
+    }
+
+    /// The marker of the previous launch, if it failed.
+    pub fn load(io: &IoFileSys) -> Option<Self> {
+        let fs = io.fs.clone();
+        io.rt
+            .spawn(async move {
+                let content = fs.read_file(FAILURE_MARKER_FILE.as_ref()).await?;
+                Ok(serde_json::from_slice(&content)?)
+            })
+            .get()
+            .ok()
+    }
+
+    /// Records a failed initialization of the backend.
+    pub fn record_failure(
+        io: &IoFileSys,
+        safe_mode: bool,
+        backend: &str,
+        gpu: &str,
+        err: &anyhow::Error,
+    ) {
+        let marker = Self::escalate(
+            Self::load(io).as_ref(),
+            safe_mode,
+            backend,
+            gpu,
+            &err.to_string(),
+        );
+        marker.write(io);
+    }
+
+    /// Records a device that was lost again after it was recreated,
+    /// the next launch starts in safe mode without asking.
+    pub fn record_device_lost(io: &IoFileSys, backend: &str, gpu: &str, err: &anyhow::Error) {
+        let mut marker = Self::escalate(
+            Self::load(io).as_ref(),
+            false,
+            backend,
+            gpu,
+            &err.to_string(),
+        );
+        marker.failures = marker.failures.max(AUTO_SAFE_MODE_FAILURES);
+        marker.write(io);
+    }
+
+    fn write(&self, io: &IoFileSys) {
+        let marker = self.clone();
+        let fs = io.fs.clone();
+        // the client exits right after, so wait for the file
+        let res = io
+            .rt
+            .spawn(async move {
+                fs.write_file(
+                    FAILURE_MARKER_FILE.as_ref(),
+                    serde_json::to_vec_pretty(&marker)?,
+                )
+                .await?;
+                Ok(())
+            })
+            .get();
+        if let Err(err) = res {
+            log::error!("failed to write {FAILURE_MARKER_FILE}: {err}");
+        }
+    }
+
+    /// The backend initialized successfully, forget about previous failures.
+    pub fn clear(io: &IoFileSys) {
+        let fs = io.fs.clone();
+        io.rt.spawn_without_lifetime(async move {
+            if fs.file_exists(FAILURE_MARKER_FILE.as_ref()).await {
+                fs.remove_file(FAILURE_MARKER_FILE.as_ref()).await?;
+            }
+            Ok(())
+        });
+    }
+}
+
+/// The backend configs for the safe mode.
+///
+/// Disables everything the backend can start without:
+/// debug extensions & validation layers, multisampling,
+/// threaded rendering & creating all pipelines up front.
+///
+/// Vulkan is the only backend that renders to a window,
+/// so there is no other backend to fall back to.
+pub fn safe_mode_config(dbg: &ConfigDebug, gl: &ConfigBackend) -> (ConfigDebug, ConfigBackend) {
+    (
+        ConfigDebug {
+            gfx: GfxDebugModes::None,
+            ..*dbg
+        },
+        ConfigBackend {
+            msaa_samples: 0,
+            thread_count: 1,
+            full_pipeline_creation: false,
+            ..gl.clone()
+        },
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AUTO_SAFE_MODE_FAILURES, GfxInitFailureMarker, GfxSafeModeDecision};
+
+    #[test]
+    fn failure_marker_escalation() {
+        assert_eq!(
+            GfxInitFailureMarker::default().decision(),
+            GfxSafeModeDecision::Full
+        );
+
+        // the first failure only offers the safe mode
+        let marker = GfxInitFailureMarker::escalate(None, false, "Vulkan", "auto", "no device");
+        assert_eq!(marker.failures, 1);
+        assert_eq!(marker.decision(), GfxSafeModeDecision::Offer);
+
+        // declined the safe mode & failed again
+        let mut marker =
+            GfxInitFailureMarker::escalate(Some(&marker), false, "Vulkan", "auto", "no device");
+        assert_eq!(marker.failures, 2);
+        assert_eq!(marker.decision(), GfxSafeModeDecision::Auto);
+        for _ in AUTO_SAFE_MODE_FAILURES..5 {
+            marker =
+                GfxInitFailureMarker::escalate(Some(&marker), false, "Vulkan", "auto", "no device");
+            assert_eq!(marker.decision(), GfxSafeModeDecision::Auto);
+        }
+        assert_eq!(marker.err, "no device");
+
+        // a failed safe mode launch never goes back to a full launch
+        let marker = GfxInitFailureMarker::escalate(None, true, "Vulkan", "gpu", "lost");
+        assert_eq!(marker.decision(), GfxSafeModeDecision::Auto);
+    }
+}
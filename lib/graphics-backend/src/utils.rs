@@ -1,6 +1,7 @@
 use std::{
     ops::{Deref, DerefMut},
     path::PathBuf,
+    time::Duration,
 };
 
 use config::config::{ConfigEngine, ConfigMonitor, ConfigWindow};
@@ -149,6 +150,11 @@ pub trait AppWithGraphics {
 
     // Copied from `FromNativeImpl`
     fn run(&mut self, native: &mut dyn NativeImpl);
+    /// How long the native event loop should wait for os events
+    /// before the next frame, `None` to start it immediately.
+    fn frame_wait(&mut self) -> Option<Duration> {
+        None
+    }
     /// New width and height in pixels!
     fn resized(&mut self, _native: &mut dyn NativeImpl, _new_width: u32, _new_height: u32) {}
     /// The window options changed, usually the implementor does not need to do anything.
@@ -203,8 +209,18 @@ impl<T> DerefMut for GraphicsApp<T> {
 
 impl<T: AppWithGraphics + InputEventHandler> FromNativeImpl for GraphicsApp<T> {
     fn run(&mut self, native: &mut dyn NativeImpl) {
+        let (_, graphics_backend, config) = self.0.get_graphics_data();
+        // the device was recreated after it was lost
+        if graphics_backend.take_needs_window_surface()
+            && let Err(err) = client_graphics_window_created_ntfy(graphics_backend, native, config)
+        {
+            log::error!("failed to create the window surface for the recreated device: {err}");
+        }
         self.0.run(native)
     }
+    fn frame_wait(&mut self) -> Option<Duration> {
+        self.0.frame_wait()
+    }
     fn resized(&mut self, native: &mut dyn NativeImpl, new_width: u32, new_height: u32) {
         let (graphics, graphics_backend, config) = self.0.get_graphics_data();
         client_graphics_resized_update_config(
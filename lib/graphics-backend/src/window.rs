@@ -21,7 +21,7 @@ use crate::backends::vulkan::{
     vulkan_allocator::VulkanAllocator,
 };
 
-#[derive(Debug, Hiarc)]
+#[derive(Debug, Hiarc, Clone)]
 pub struct BackendDisplayRequirements {
     pub extensions: Vec<String>,
     pub is_headless: bool,
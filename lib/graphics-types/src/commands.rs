@@ -4,6 +4,7 @@ use pool::mt_datatypes::{PoolString, PoolVec};
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    gpu::GpuTimerPass,
     rendering::{
         ColorRgba, GlColor, GlColorf, GlPoint, RenderModeGlass, SPoint, State, StateTexture,
     },
@@ -403,6 +404,9 @@ pub enum CommandsMisc {
     CanvasResized(CommandCanvasResized),
     Multisampling(CommandMultiSampling),
     VSync(CommandVsync),
+    /// Starts measuring the gpu time of the pass,
+    /// if the backend supports it.
+    GpuTimerMarker(GpuTimerPass),
 }
 
 #[derive(Debug, Serialize, Deserialize, Hiarc)]
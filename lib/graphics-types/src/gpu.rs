@@ -1,5 +1,8 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
 use hiarc::Hiarc;
 use serde::{Deserialize, Serialize};
+use strum::{EnumCount, EnumIter, IntoEnumIterator, IntoStaticStr};
 
 #[derive(Debug, Hiarc, Copy, Clone, Serialize, Deserialize, PartialEq, PartialOrd)]
 pub enum GpuType {
@@ -23,6 +26,9 @@ pub struct CurGpu {
     pub name: String,
     pub msaa_sampling_count: u32,
     pub ty: GpuType,
+    /// The size of all device local memory heaps in bytes,
+    /// `0` if unknown.
+    pub device_local_memory: u64,
 }
 
 #[derive(Debug, Hiarc, Clone, Serialize, Deserialize)]
@@ -31,3 +37,46 @@ pub struct Gpus {
     pub auto: Gpu,
     pub cur: CurGpu,
 }
+
+/// A section of a frame whose gpu time is measured.
+///
+/// A section lasts until the next marker or the end of the frame,
+/// see [`crate::commands::CommandsMisc::GpuTimerMarker`].
+#[derive(
+    Debug,
+    Hiarc,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    EnumCount,
+    EnumIter,
+    IntoStaticStr,
+)]
+pub enum GpuTimerPass {
+    MapBackground,
+    Entities,
+    Particles,
+    Ui,
+}
+
+/// The gpu times of the [`GpuTimerPass`]es, averaged over the last frames.
+///
+/// Written by the backend, read by debug overlays.
+#[derive(Debug, Default)]
+pub struct GpuTimings {
+    /// `false` if the device does not support timestamp queries,
+    /// the pass times are never filled then.
+    pub supported: AtomicBool,
+    /// In nanoseconds, indexed by [`GpuTimerPass`].
+    pub passes: [AtomicU64; GpuTimerPass::COUNT],
+}
+
+impl GpuTimings {
+    /// The pass times in nanoseconds.
+    pub fn pass_times(&self) -> impl Iterator<Item = (GpuTimerPass, u64)> + '_ {
+        GpuTimerPass::iter().map(|pass| (pass, self.passes[pass as usize].load(Ordering::Relaxed)))
+    }
+}
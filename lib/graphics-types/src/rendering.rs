@@ -9,6 +9,12 @@ pub enum BlendType {
     #[default]
     Alpha,
     Additive,
+    /// Adds the color weighted by its alpha (non premultiplied).
+    AlphaAdditive,
+    /// Multiplies the destination by the color.
+    Multiply,
+    /// Multiplies the inverse of the destination by the inverse of the color.
+    Screen,
 }
 
 #[derive(Debug, Hiarc, Default, Copy, Clone, PartialEq, Eq, EnumCount, Serialize, Deserialize)]
@@ -3,11 +3,16 @@ pub mod graphics {
         cell::RefCell,
         fmt::Debug,
         rc::Rc,
-        sync::{Arc, Mutex},
+        sync::{
+            Arc, Mutex,
+            atomic::{AtomicU64, Ordering},
+        },
     };
 
     use graphics_backend_traits::{
-        frame_fetcher_plugin::{BackendFrameFetcher, BackendPresentedImageDataRgba},
+        frame_fetcher_plugin::{
+            BackendFrameFetcher, BackendPresentedImageDataRgba, FetchCanvasIndex, FetchCanvasRegion,
+        },
         traits::GraphicsBackendInterface,
     };
     use graphics_base_traits::traits::GraphicsStreamedData;
@@ -147,6 +152,89 @@ pub mod graphics {
 
     type ScreenshotHelper = Option<(Box<dyn ScreenshotCb>, Arc<ScreenshotFetcher>)>;
 
+    #[derive(Debug)]
+    struct PixelFetcher {
+        x: u32,
+        y: u32,
+        data: Mutex<Option<anyhow::Result<[u8; 4]>>>,
+    }
+
+    impl BackendFrameFetcher for PixelFetcher {
+        fn next_frame(&self, frame_data: BackendPresentedImageDataRgba) {
+            let pixel = frame_data
+                .dest_data_buffer
+                .get(0..4)
+                // the alpha of the presented frame has no meaning
+                .map(|pixel| [pixel[0], pixel[1], pixel[2], 255])
+                .ok_or_else(|| anyhow::anyhow!("the fetched frame was empty"));
+            *self.data.lock().unwrap() = Some(pixel);
+        }
+
+        fn current_fetch_index(&self) -> FetchCanvasIndex {
+            FetchCanvasIndex::Onscreen
+        }
+
+        fn current_fetch_region(&self) -> Option<FetchCanvasRegion> {
+            Some(FetchCanvasRegion {
+                x: self.x,
+                y: self.y,
+                width: 1,
+                height: 1,
+            })
+        }
+
+        fn fetch_err(&self, err: graphics_backend_traits::frame_fetcher_plugin::FetchCanvasError) {
+            *self.data.lock().unwrap() = Some(Err(err.into()));
+        }
+    }
+
+    /// Reads back a single pixel of the onscreen canvas,
+    /// after the next frame was presented.
+    ///
+    /// Only the pixel is copied from the gpu, unlike a screenshot.
+    #[derive(Debug)]
+    pub struct PixelReadback {
+        name: String,
+        fetcher: Arc<PixelFetcher>,
+        backend_handle: GraphicsBackendHandle,
+    }
+
+    impl PixelReadback {
+        /// `x` & `y` are in pixels from the top left corner of the window.
+        pub fn new(backend_handle: &GraphicsBackendHandle, x: u32, y: u32) -> anyhow::Result<Self> {
+            static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+            let name = format!("pixel-readback-{}", NEXT_ID.fetch_add(1, Ordering::Relaxed));
+            let fetcher = Arc::new(PixelFetcher {
+                x,
+                y,
+                data: Default::default(),
+            });
+            backend_handle
+                .backend
+                .attach_frame_fetcher(name.clone(), fetcher.clone())?;
+            Ok(Self {
+                name,
+                fetcher,
+                backend_handle: backend_handle.clone(),
+            })
+        }
+
+        /// The rgba value of the pixel, `None` as long as no frame was presented.
+        pub fn try_take(&self) -> Option<anyhow::Result<[u8; 4]>> {
+            self.fetcher.data.lock().unwrap().take()
+        }
+    }
+
+    impl Drop for PixelReadback {
+        fn drop(&mut self) {
+            // the backend might already be gone
+            let _ = self
+                .backend_handle
+                .backend
+                .detach_frame_fetcher(self.name.clone());
+        }
+    }
+
     #[derive(Debug, Hiarc, Clone)]
     pub struct Graphics {
         pub backend_handle: GraphicsBackendHandle,
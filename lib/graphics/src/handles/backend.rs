@@ -10,7 +10,7 @@ pub mod backend {
             AllCommands, CommandClear, CommandIndicesForQuadsRequiredNotify, CommandsMisc,
             CommandsRender,
         },
-        gpu::Gpus,
+        gpu::{GpuTimerPass, Gpus},
         rendering::ColorRgba,
         types::{GraphicsBackendMemory, GraphicsMemoryAllocationType},
     };
@@ -79,6 +79,12 @@ pub mod backend {
             })));
         }
 
+        /// Starts measuring the gpu time of the pass,
+        /// it lasts until the next marker or the end of the frame.
+        pub fn gpu_timer_marker(&self, pass: GpuTimerPass) {
+            self.add_cmd(AllCommands::Misc(CommandsMisc::GpuTimerMarker(pass)));
+        }
+
         pub fn indices_for_quads_required_notify(&self, quad_count_required: u64) {
             let cmd = CommandIndicesForQuadsRequiredNotify {
                 quad_count_required,
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, io, num::NonZeroU32};
+use std::{borrow::Cow, collections::HashMap, io, num::NonZeroU32};
 
 use image::RgbaImage;
 
@@ -140,6 +140,17 @@ impl Default for PngValidatorOptions {
     }
 }
 
+impl PngValidatorOptions {
+    /// The limits for the optional high quality variants of resources.
+    pub fn hq() -> Self {
+        Self {
+            max_width: 4096.try_into().unwrap(),
+            max_height: 4096.try_into().unwrap(),
+            ..Default::default()
+        }
+    }
+}
+
 pub fn is_png_image_valid(file: &[u8], options: PngValidatorOptions) -> anyhow::Result<()> {
     let mut mem = Vec::new();
     let img = load_png_image_as_rgba(file, |w, h, ppp| {
@@ -220,3 +231,112 @@ pub fn resize_rgba(
     )
     .to_vec()
 }
+
+/// A re-encoded png file.
+#[derive(Debug, Clone)]
+pub struct OptimizedPng {
+    pub file: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+    pub original_width: u32,
+    pub original_height: u32,
+}
+
+/// Width & height so that neither exceeds `max_size`, keeping the aspect ratio.
+pub fn downscaled_size(width: u32, height: u32, max_size: u32) -> (u32, u32) {
+    let max_size = max_size.max(1);
+    if width <= max_size && height <= max_size {
+        return (width, height);
+    }
+    let scale = max_size as f64 / width.max(height) as f64;
+    (
+        ((width as f64 * scale).round() as u32).clamp(1, max_size),
+        ((height as f64 * scale).round() as u32).clamp(1, max_size),
+    )
+}
+
+/// The palette & alpha values of an rgba image,
+/// if it has at most 256 different colors.
+fn rgba_to_palette(raw_bytes: &[u8]) -> Option<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+    let mut colors: HashMap<[u8; 4], u8> = Default::default();
+    let mut indices = Vec::with_capacity(raw_bytes.len() / 4);
+    for pixel in raw_bytes.chunks_exact(4) {
+        let pixel: [u8; 4] = pixel.try_into().unwrap();
+        let next_index = colors.len();
+        let index = match colors.get(&pixel) {
+            Some(index) => *index,
+            None => {
+                if next_index >= 256 {
+                    return None;
+                }
+                colors.insert(pixel, next_index as u8);
+                next_index as u8
+            }
+        };
+        indices.push(index);
+    }
+    let mut palette = vec![[0u8; 4]; colors.len()];
+    for (color, index) in colors {
+        palette[index as usize] = color;
+    }
+    let rgb = palette.iter().flat_map(|c| [c[0], c[1], c[2]]).collect();
+    let alpha = palette.iter().map(|c| c[3]).collect();
+    Some((rgb, alpha, indices))
+}
+
+/// Re-encodes a png file. All ancillary chunks (text, time, color profiles etc.)
+/// are dropped, images larger than `max_size` are downscaled with a lanczos filter
+/// and if `palette` is `true`, images with at most 256 colors are saved as indexed png.
+pub fn optimize_png(
+    file: &[u8],
+    max_size: Option<u32>,
+    palette: bool,
+) -> anyhow::Result<OptimizedPng> {
+    let mut mem = Vec::new();
+    let img = load_png_image_as_rgba(file, |w, h, ppp| {
+        mem.resize(w * h * ppp, 0);
+        &mut mem
+    })?;
+    let (width, height) = (img.width, img.height);
+    let (new_width, new_height) = max_size
+        .map(|max_size| downscaled_size(width, height, max_size))
+        .unwrap_or((width, height));
+    let data = if (new_width, new_height) != (width, height) {
+        resize_rgba(
+            Cow::Borrowed(img.data),
+            width,
+            height,
+            new_width,
+            new_height,
+        )
+    } else {
+        img.data.to_vec()
+    };
+
+    let indexed = palette.then(|| rgba_to_palette(&data)).flatten();
+    let file = if let Some((rgb, alpha, indices)) = indexed {
+        let mut res: Vec<u8> = Default::default();
+        let mut encoder = png::Encoder::new(&mut res, new_width, new_height);
+        encoder.set_color(png::ColorType::Indexed);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_compression(png::Compression::High);
+        encoder.set_palette(rgb);
+        if alpha.iter().any(|a| *a != 255) {
+            encoder.set_trns(alpha);
+        }
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&indices)?;
+        writer.finish()?;
+        res
+    } else {
+        save_png_image_ex(&data, new_width, new_height, true)?
+    };
+
+    Ok(OptimizedPng {
+        file,
+        width: new_width,
+        height: new_height,
+        original_width: width,
+        original_height: height,
+    })
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 // no Arc needed in this module interface
 
 use app::NativeApp;
@@ -61,6 +61,11 @@ pub trait NativeImpl {
 
 pub trait FromNativeImpl: AsMut<dyn InputEventHandler> {
     fn run(&mut self, native: &mut dyn NativeImpl);
+    /// How long the native event loop should wait for os events
+    /// before the next frame, `None` to start it immediately.
+    fn frame_wait(&mut self) -> Option<Duration> {
+        None
+    }
     /// New width and height in pixels!
     fn resized(&mut self, native: &mut dyn NativeImpl, new_width: u32, new_height: u32);
     /// The window options changed, usually the implementor does not need to do anything.
@@ -1,4 +1,4 @@
-use std::{cell::Cell, collections::VecDeque};
+use std::{cell::Cell, collections::VecDeque, time::Instant};
 
 use anyhow::anyhow;
 use base::benchmark::Benchmark;
@@ -825,7 +825,15 @@ impl WinitWrapper {
                             native_user.run(window);
 
                             if !window.suspended {
-                                window.window.request_redraw();
+                                // wait for os events until the next frame is due
+                                match native_user.frame_wait() {
+                                    Some(wait) => event_loop.set_control_flow(
+                                        winit::event_loop::ControlFlow::WaitUntil(
+                                            Instant::now() + wait,
+                                        ),
+                                    ),
+                                    None => window.window.request_redraw(),
+                                }
                             }
 
                             // check internal events
@@ -926,12 +934,16 @@ impl WinitWrapper {
             fn new_events(
                 &mut self,
                 event_loop: &winit::event_loop::ActiveEventLoop,
-                _cause: winit::event::StartCause,
+                cause: winit::event::StartCause,
             ) {
-                if let Self::Some { window, .. } = self
-                    && window.destroy.get()
-                {
-                    event_loop.exit();
+                if let Self::Some { window, .. } = self {
+                    if window.destroy.get() {
+                        event_loop.exit();
+                    } else if matches!(cause, winit::event::StartCause::ResumeTimeReached { .. }) {
+                        // the next frame is due
+                        event_loop.set_control_flow(winit::event_loop::ControlFlow::Poll);
+                        window.window.request_redraw();
+                    }
                 }
             }
         }
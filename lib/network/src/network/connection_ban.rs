@@ -137,4 +137,17 @@ impl ConnectionBans {
 
         ids
     }
+
+    /// Removes the ban of exactly this ip, if any.
+    pub fn unban_ip(&self, ip: IpAddr) {
+        let mut state = self.state.lock().unwrap();
+        match ip {
+            IpAddr::V4(ip) => {
+                state.ipv4_bans.remove(&ip.into());
+            }
+            IpAddr::V6(ip) => {
+                state.ipv6_bans.remove(&ip.into());
+            }
+        }
+    }
 }
@@ -68,6 +68,13 @@ impl<'de> Deserialize<'de> for SoundBackendMemory {
 }
 
 impl SoundBackendMemory {
+    pub fn as_slice(&self) -> &[u8] {
+        match self {
+            SoundBackendMemory::FlushableVector { data, .. } => data.as_slice(),
+            SoundBackendMemory::Vector { data } => data.as_slice(),
+        }
+    }
+
     pub fn as_mut_slice(&mut self) -> &mut [u8] {
         match self {
             SoundBackendMemory::FlushableVector { data, .. } => data.as_mut_slice(),
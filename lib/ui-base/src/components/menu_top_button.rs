@@ -1,5 +1,7 @@
 use egui::{Color32, Pos2, Response, Shape, Stroke};
 
+use crate::focus::FocusableResponse;
+
 pub struct MenuTopButtonProps {
     active: bool,
     text: String,
@@ -28,7 +30,8 @@ pub fn menu_top_button(
                 ui.button(props.text)
             }
         })
-        .inner;
+        .inner
+        .focusable(ui);
     if props.active {
         ui.painter().add(Shape::line_segment(
             [
@@ -46,7 +49,7 @@ pub fn menu_top_button(
 
 #[must_use]
 pub fn menu_top_button_icon(ui: &mut egui::Ui, props: MenuTopButtonProps) -> Response {
-    let res = ui.button(&props.text);
+    let res = ui.button(&props.text).focusable(ui);
     if props.active {
         ui.painter().add(Shape::line_segment(
             [
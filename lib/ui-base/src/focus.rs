@@ -0,0 +1,664 @@
+//! Mouse free navigation of the uis.
+//!
+//! Pages register their focusable widgets every frame
+//! (see [`FocusableResponse`] & [`register_list`]),
+//! [`UiFocus`] then moves the focus between them in the same
+//! order on every page and draws a focus ring around the focused widget.
+
+use egui::{Color32, Event, Id, Key, LayerId, Modifiers, Order, Rect, Sense, Stroke, StrokeKind};
+
+/// Typing into a list after this many seconds starts a new search.
+const SEARCH_TIMEOUT_SECS: f64 = 1.0;
+
+const FOCUS_NODES_ID: &str = "ui-focus-nodes";
+const FOCUS_LIST_NAV_ID: &str = "ui-focus-list-nav";
+
+/// The actions the keyboard & gamepads navigate with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavAction {
+    Next,
+    Previous,
+    Up,
+    Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    /// Clicks the focused widget.
+    Activate,
+    /// Leaves the current dialog or page.
+    Back,
+}
+
+impl NavAction {
+    pub fn from_key(key: Key, modifiers: Modifiers) -> Option<Self> {
+        Some(match key {
+            Key::Tab if modifiers.shift => Self::Previous,
+            Key::Tab => Self::Next,
+            Key::ArrowUp => Self::Up,
+            Key::ArrowDown => Self::Down,
+            Key::ArrowLeft => Self::Left,
+            Key::ArrowRight => Self::Right,
+            Key::PageUp => Self::PageUp,
+            Key::PageDown => Self::PageDown,
+            Key::Enter => Self::Activate,
+            Key::Escape => Self::Back,
+            _ => return None,
+        })
+    }
+
+    /// The key that triggers the action.
+    pub fn key(self) -> (Key, Modifiers) {
+        match self {
+            Self::Next => (Key::Tab, Modifiers::NONE),
+            Self::Previous => (Key::Tab, Modifiers::SHIFT),
+            Self::Up => (Key::ArrowUp, Modifiers::NONE),
+            Self::Down => (Key::ArrowDown, Modifiers::NONE),
+            Self::Left => (Key::ArrowLeft, Modifiers::NONE),
+            Self::Right => (Key::ArrowRight, Modifiers::NONE),
+            Self::PageUp => (Key::PageUp, Modifiers::NONE),
+            Self::PageDown => (Key::PageDown, Modifiers::NONE),
+            Self::Activate => (Key::Enter, Modifiers::NONE),
+            Self::Back => (Key::Escape, Modifiers::NONE),
+        }
+    }
+}
+
+/// The gamepad buttons that are used for the navigation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GamepadButton {
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+    /// A on xbox like gamepads.
+    South,
+    /// B on xbox like gamepads.
+    East,
+    LeftShoulder,
+    RightShoulder,
+    Start,
+    Select,
+}
+
+impl GamepadButton {
+    pub fn nav_action(self) -> NavAction {
+        match self {
+            Self::DPadUp => NavAction::Up,
+            Self::DPadDown => NavAction::Down,
+            Self::DPadLeft => NavAction::Left,
+            Self::DPadRight => NavAction::Right,
+            Self::South => NavAction::Activate,
+            Self::East => NavAction::Back,
+            Self::LeftShoulder => NavAction::PageUp,
+            Self::RightShoulder => NavAction::PageDown,
+            Self::Start => NavAction::Next,
+            Self::Select => NavAction::Previous,
+        }
+    }
+}
+
+/// A widget that can be focused.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FocusNode {
+    pub id: Id,
+    pub rect: Rect,
+    /// Windows & popups are above the pages,
+    /// only the nodes of the top most order are navigable.
+    pub order: Order,
+    /// The part of a composed page, see [`begin_section`].
+    pub section: u32,
+    /// Lists handle up, down, page-up/-down & typing themselves,
+    /// see [`register_list`].
+    pub is_list: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+struct FrameNodes {
+    nodes: Vec<FocusNode>,
+    section: u32,
+}
+
+fn add_node(ctx: &egui::Context, id: Id, rect: Rect, order: Order, is_list: bool) {
+    ctx.data_mut(|d| {
+        let frame = d.get_temp_mut_or_default::<FrameNodes>(Id::new(FOCUS_NODES_ID));
+        let section = frame.section;
+        frame.nodes.push(FocusNode {
+            id,
+            rect,
+            order,
+            section,
+            is_list,
+        });
+    });
+}
+
+/// Starts the next part of a composed page (e.g. the left bar, the top bar & the content).
+///
+/// The parts are navigated in the order they were started,
+/// regardless of their position on the screen.
+pub fn begin_section(ui: &egui::Ui) {
+    ui.ctx().data_mut(|d| {
+        d.get_temp_mut_or_default::<FrameNodes>(Id::new(FOCUS_NODES_ID))
+            .section += 1;
+    });
+}
+
+pub trait FocusableResponse {
+    /// Makes the widget reachable by the keyboard & gamepads.
+    fn focusable(self, ui: &egui::Ui) -> Self;
+}
+
+impl FocusableResponse for egui::Response {
+    fn focusable(self, ui: &egui::Ui) -> Self {
+        add_node(ui.ctx(), self.id, self.rect, self.layer_id.order, false);
+        self
+    }
+}
+
+/// The input of a focused list.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListNav {
+    pub prev: bool,
+    pub next: bool,
+    pub page_up: bool,
+    pub page_down: bool,
+    /// Enter was pressed, the key event is still passed to the ui.
+    pub activate: bool,
+    /// The text typed into the list.
+    pub search: Option<String>,
+}
+
+impl ListNav {
+    /// Returns `true` if the action is handled by the list.
+    fn apply(&mut self, action: NavAction) -> bool {
+        match action {
+            NavAction::Up => self.prev = true,
+            NavAction::Down => self.next = true,
+            NavAction::PageUp => self.page_up = true,
+            NavAction::PageDown => self.page_down = true,
+            NavAction::Activate => self.activate = true,
+            NavAction::Next
+            | NavAction::Previous
+            | NavAction::Left
+            | NavAction::Right
+            | NavAction::Back => return false,
+        }
+        true
+    }
+
+    /// The entry that should be selected, `None` if the selection does not change.
+    ///
+    /// A search selects the first entry that starts with the typed text.
+    pub fn next_selection<'a>(
+        &self,
+        selected: Option<usize>,
+        names: impl Iterator<Item = &'a str>,
+        page_len: usize,
+    ) -> Option<usize> {
+        let mut len = 0;
+        let mut found = None;
+        let search = self.search.as_ref().map(|search| search.to_lowercase());
+        for (index, name) in names.enumerate() {
+            len += 1;
+            if found.is_none()
+                && let Some(search) = &search
+                && name.to_lowercase().starts_with(search)
+            {
+                found = Some(index);
+            }
+        }
+        if found.is_some() || len == 0 {
+            return found;
+        }
+
+        let last = len - 1;
+        let page_len = page_len.max(1);
+        let Some(selected) = selected else {
+            return (self.prev || self.next || self.page_up || self.page_down).then_some(0);
+        };
+        if self.prev {
+            Some(selected.saturating_sub(1))
+        } else if self.next {
+            Some((selected + 1).min(last))
+        } else if self.page_up {
+            Some(selected.saturating_sub(page_len))
+        } else if self.page_down {
+            Some((selected + page_len).min(last))
+        } else {
+            None
+        }
+    }
+}
+
+/// Registers a list, whose entries are navigated by the list itself
+/// (e.g. because only the visible entries are rendered).
+///
+/// Returns the input, if the list is focused.
+pub fn register_list(ui: &mut egui::Ui, id: Id, rect: Rect) -> ListNav {
+    // only makes the list known to egui's focus
+    let res = ui.interact(rect, id, Sense::hover());
+    add_node(ui.ctx(), id, rect, res.layer_id.order, true);
+    ui.ctx()
+        .data_mut(|d| d.get_temp::<(Id, ListNav)>(Id::new(FOCUS_LIST_NAV_ID)))
+        .and_then(|(list_id, nav)| (list_id == id).then_some(nav))
+        .unwrap_or_default()
+}
+
+/// The order in which [`NavAction::Next`] visits the nodes.
+///
+/// Only the nodes of the top most order are visited.
+/// The sections are visited in order, inside a section
+/// the nodes are visited row by row from left to right.
+pub fn focus_order(nodes: &[FocusNode]) -> Vec<usize> {
+    let Some(top_order) = nodes.iter().map(|node| node.order).max() else {
+        return Vec::new();
+    };
+    let mut sorted: Vec<usize> = (0..nodes.len())
+        .filter(|&index| nodes[index].order == top_order)
+        .collect();
+    sorted.sort_by(|&a, &b| {
+        let (a, b) = (&nodes[a], &nodes[b]);
+        a.section
+            .cmp(&b.section)
+            .then(a.rect.top().total_cmp(&b.rect.top()))
+    });
+
+    // a node belongs to the row of the node above it,
+    // if their rects overlap vertically
+    let mut res = Vec::with_capacity(sorted.len());
+    let mut row_start = 0;
+    for i in 0..sorted.len() {
+        let first = &nodes[sorted[row_start]];
+        let node = &nodes[sorted[i]];
+        if node.section != first.section || node.rect.center().y > first.rect.bottom() {
+            let mut row = sorted[row_start..i].to_vec();
+            row.sort_by(|&a, &b| nodes[a].rect.left().total_cmp(&nodes[b].rect.left()));
+            res.extend(row);
+            row_start = i;
+        }
+    }
+    let mut row = sorted[row_start..].to_vec();
+    row.sort_by(|&a, &b| nodes[a].rect.left().total_cmp(&nodes[b].rect.left()));
+    res.extend(row);
+    res
+}
+
+/// The closest node in the direction, preferring nodes that are in line.
+fn nearest_in_direction(
+    nodes: &[FocusNode],
+    order: &[usize],
+    from: usize,
+    dir: egui::Vec2,
+) -> Option<usize> {
+    let from_center = nodes[from].rect.center();
+    order
+        .iter()
+        .copied()
+        .filter(|&index| index != from)
+        .filter_map(|index| {
+            let diff = nodes[index].rect.center() - from_center;
+            let primary = diff.dot(dir);
+            let secondary = diff.dot(dir.rot90()).abs();
+            (primary > 1.0).then_some((index, primary + secondary * 2.0))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(index, _)| index)
+}
+
+/// The node that is focused after the action.
+pub fn navigate(
+    nodes: &[FocusNode],
+    order: &[usize],
+    focused: Option<Id>,
+    action: NavAction,
+) -> Option<Id> {
+    let len = order.len();
+    let first = *order.first()?;
+    let Some(pos) = focused.and_then(|id| order.iter().position(|&index| nodes[index].id == id))
+    else {
+        return match action {
+            NavAction::Activate | NavAction::Back => focused,
+            NavAction::Previous => Some(nodes[order[len - 1]].id),
+            _ => Some(nodes[first].id),
+        };
+    };
+    let cur = order[pos];
+    let section_nodes = || {
+        order
+            .iter()
+            .copied()
+            .filter(move |&index| nodes[index].section == nodes[cur].section)
+    };
+    let target = match action {
+        NavAction::Next => Some(order[(pos + 1) % len]),
+        NavAction::Previous => Some(order[(pos + len - 1) % len]),
+        NavAction::Up => nearest_in_direction(nodes, order, cur, -egui::Vec2::Y),
+        NavAction::Down => nearest_in_direction(nodes, order, cur, egui::Vec2::Y),
+        NavAction::Left => nearest_in_direction(nodes, order, cur, -egui::Vec2::X),
+        NavAction::Right => nearest_in_direction(nodes, order, cur, egui::Vec2::X),
+        NavAction::PageUp => section_nodes().next(),
+        NavAction::PageDown => section_nodes().last(),
+        NavAction::Activate | NavAction::Back => None,
+    };
+    Some(nodes[target.unwrap_or(cur)].id)
+}
+
+/// The focus state of an ui, see the module docs.
+#[derive(Debug, Default)]
+pub struct UiFocus {
+    /// The nodes of the last frame.
+    nodes: Vec<FocusNode>,
+    focused: Option<Id>,
+
+    actions: Vec<NavAction>,
+    gamepad_events: Vec<Event>,
+
+    search: String,
+    last_search_time: f64,
+
+    back: bool,
+    /// The focus ring is only shown while navigating without the mouse.
+    show_ring: bool,
+}
+
+impl UiFocus {
+    /// Gamepad buttons trigger the same actions as their keys.
+    pub fn gamepad_button(&mut self, button: GamepadButton) {
+        let (key, modifiers) = button.nav_action().key();
+        self.gamepad_events
+            .extend([true, false].map(|pressed| Event::Key {
+                key,
+                physical_key: None,
+                pressed,
+                repeat: false,
+                modifiers,
+            }));
+    }
+
+    /// Whether back (escape) was pressed this frame.
+    ///
+    /// Only the first caller gets `true`, so the top most dialog
+    /// or page handles it.
+    pub fn take_back(&mut self) -> bool {
+        std::mem::take(&mut self.back)
+    }
+
+    /// Takes the navigation keys out of the input, before egui handles them.
+    pub fn begin_frame(&mut self, ctx: &egui::Context, input: &mut egui::RawInput) {
+        input.events.append(&mut self.gamepad_events);
+        self.back = false;
+
+        if let Some(id) = ctx.memory(|m| m.focused())
+            && self.nodes.iter().any(|node| node.id == id)
+        {
+            self.focused = Some(id);
+        }
+        let egui_focused = ctx.memory(|m| m.focused());
+        let text_input = ctx.wants_keyboard_input();
+        let focused_node = self
+            .focused
+            .and_then(|id| self.nodes.iter().find(|node| node.id == id));
+        // widgets that are not registered keep egui's navigation
+        let owns_focus = egui_focused.is_none() || egui_focused == focused_node.map(|node| node.id);
+        let handles_keys = !self.nodes.is_empty() && owns_focus;
+        let focused_list = focused_node.filter(|node| node.is_list).map(|node| node.id);
+
+        let time = input.time.unwrap_or_default();
+        let mut list_nav = ListNav::default();
+        let mut actions = Vec::new();
+        let mut back = false;
+        let mut show_ring = self.show_ring;
+        let search = &mut self.search;
+        let last_search_time = &mut self.last_search_time;
+        input.events.retain(|ev| match ev {
+            Event::PointerMoved(_) | Event::PointerButton { .. } => {
+                show_ring = false;
+                true
+            }
+            Event::Key {
+                key,
+                pressed,
+                modifiers,
+                ..
+            } => {
+                let Some(action) = NavAction::from_key(*key, *modifiers) else {
+                    return true;
+                };
+                if action == NavAction::Back {
+                    // e.g. text edits lose their focus on escape instead
+                    back |= *pressed && owns_focus && !text_input;
+                    return true;
+                }
+                if !handles_keys
+                    || (text_input && !matches!(action, NavAction::Next | NavAction::Previous))
+                {
+                    return true;
+                }
+                if *pressed {
+                    show_ring = true;
+                    if !(focused_list.is_some() && list_nav.apply(action))
+                        && action != NavAction::Activate
+                    {
+                        actions.push(action);
+                    }
+                }
+                // egui clicks the focused widget on enter
+                action == NavAction::Activate
+            }
+            Event::Text(text) if handles_keys && !text_input && focused_list.is_some() => {
+                if time - *last_search_time > SEARCH_TIMEOUT_SECS {
+                    search.clear();
+                }
+                *last_search_time = time;
+                search.push_str(text);
+                list_nav.search = Some(search.clone());
+                false
+            }
+            _ => true,
+        });
+        self.actions.extend(actions);
+        self.back = back;
+        self.show_ring = show_ring;
+
+        ctx.data_mut(|d| {
+            d.insert_temp(Id::new(FOCUS_NODES_ID), FrameNodes::default());
+            match focused_list {
+                Some(id) => d.insert_temp(Id::new(FOCUS_LIST_NAV_ID), (id, list_nav)),
+                None => d.remove::<(Id, ListNav)>(Id::new(FOCUS_LIST_NAV_ID)),
+            }
+        });
+    }
+
+    /// Moves the focus & draws the focus ring, must be called
+    /// after the ui was rendered.
+    pub fn end_frame(&mut self, ctx: &egui::Context) {
+        self.nodes = ctx
+            .data_mut(|d| d.get_temp::<FrameNodes>(Id::new(FOCUS_NODES_ID)))
+            .unwrap_or_default()
+            .nodes;
+        let order = focus_order(&self.nodes);
+
+        // e.g. a dialog opened or the page changed
+        if self
+            .focused
+            .is_some_and(|id| !order.iter().any(|&index| self.nodes[index].id == id))
+        {
+            self.focused = None;
+        }
+
+        let prev_focused = self.focused;
+        for action in self.actions.drain(..) {
+            self.focused = navigate(&self.nodes, &order, self.focused, action);
+        }
+        if self.focused != prev_focused
+            && let Some(id) = self.focused
+        {
+            ctx.memory_mut(|m| m.request_focus(id));
+        }
+
+        if self.show_ring
+            && let Some(node) = self
+                .focused
+                .and_then(|id| self.nodes.iter().find(|node| node.id == id))
+        {
+            ctx.layer_painter(LayerId::new(Order::Foreground, Id::new("ui-focus-ring")))
+                .rect_stroke(
+                    node.rect.expand(2.0),
+                    4.0,
+                    Stroke::new(2.0, Color32::LIGHT_BLUE),
+                    StrokeKind::Outside,
+                );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use egui::{Id, Order, Rect, pos2, vec2};
+
+    use super::{FocusNode, ListNav, NavAction, focus_order, navigate};
+
+    fn node(name: &str, section: u32, x: f32, y: f32, order: Order) -> FocusNode {
+        FocusNode {
+            id: Id::new(name),
+            rect: Rect::from_min_size(pos2(x, y), vec2(30.0, 20.0)),
+            order,
+            section,
+            is_list: false,
+        }
+    }
+
+    /// A main menu like page: a left bar, a top bar & a grid of content.
+    fn composed_page() -> Vec<FocusNode> {
+        vec![
+            // the content is registered in random order
+            node("content-b1", 3, 140.0, 82.0, Order::Background),
+            node("content-a1", 3, 100.0, 80.0, Order::Background),
+            node("content-a2", 3, 100.0, 120.0, Order::Background),
+            node("content-b2", 3, 140.0, 118.0, Order::Background),
+            // the left bar is on the left, but vertical
+            node("left-1", 1, 0.0, 0.0, Order::Background),
+            node("left-2", 1, 0.0, 40.0, Order::Background),
+            node("left-3", 1, 0.0, 80.0, Order::Background),
+            // the top bar is right to left
+            node("top-2", 2, 200.0, 0.0, Order::Background),
+            node("top-1", 2, 100.0, 0.0, Order::Background),
+        ]
+    }
+
+    fn names(nodes: &[FocusNode], order: &[usize]) -> Vec<Id> {
+        order.iter().map(|&index| nodes[index].id).collect()
+    }
+
+    fn ids(names: &[&str]) -> Vec<Id> {
+        names.iter().map(Id::new).collect()
+    }
+
+    #[test]
+    fn composed_page_order() {
+        let nodes = composed_page();
+        let order = focus_order(&nodes);
+        assert_eq!(
+            names(&nodes, &order),
+            ids(&[
+                "left-1",
+                "left-2",
+                "left-3",
+                "top-1",
+                "top-2",
+                "content-a1",
+                "content-b1",
+                "content-a2",
+                "content-b2",
+            ])
+        );
+
+        // dialogs hide the page below them
+        let mut nodes = nodes;
+        nodes.push(node("dialog-ok", 0, 140.0, 200.0, Order::Middle));
+        nodes.push(node("dialog-cancel", 0, 100.0, 200.0, Order::Middle));
+        let order = focus_order(&nodes);
+        assert_eq!(names(&nodes, &order), ids(&["dialog-cancel", "dialog-ok"]));
+        assert_eq!(focus_order(&[]), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn navigation() {
+        let nodes = composed_page();
+        let order = focus_order(&nodes);
+        let nav = |focused: &str, action| {
+            navigate(&nodes, &order, Some(Id::new(focused)), action).unwrap()
+        };
+
+        // nothing focused focuses the first node
+        assert_eq!(
+            navigate(&nodes, &order, None, NavAction::Down),
+            Some(Id::new("left-1"))
+        );
+        assert_eq!(
+            navigate(&nodes, &order, None, NavAction::Previous),
+            Some(Id::new("content-b2"))
+        );
+        assert_eq!(navigate(&nodes, &[], None, NavAction::Next), None);
+
+        // tab wraps around
+        assert_eq!(nav("content-b2", NavAction::Next), Id::new("left-1"));
+        assert_eq!(nav("left-1", NavAction::Previous), Id::new("content-b2"));
+        assert_eq!(nav("left-3", NavAction::Next), Id::new("top-1"));
+
+        // arrows move spatially
+        assert_eq!(nav("content-a1", NavAction::Right), Id::new("content-b1"));
+        assert_eq!(nav("content-a1", NavAction::Down), Id::new("content-a2"));
+        assert_eq!(nav("content-a1", NavAction::Left), Id::new("left-3"));
+        assert_eq!(nav("content-a1", NavAction::Up), Id::new("top-1"));
+        // nothing in that direction keeps the focus
+        assert_eq!(nav("left-1", NavAction::Up), Id::new("left-1"));
+
+        // page up/down jump inside the section
+        assert_eq!(
+            nav("content-b1", NavAction::PageDown),
+            Id::new("content-b2")
+        );
+        assert_eq!(nav("content-b1", NavAction::PageUp), Id::new("content-a1"));
+        assert_eq!(nav("left-2", NavAction::Activate), Id::new("left-2"));
+    }
+
+    #[test]
+    fn list_selection() {
+        let names = ["alpha", "beta", "Bravo", "charlie"];
+        let select = |nav: ListNav, selected| nav.next_selection(selected, names.into_iter(), 2);
+
+        assert_eq!(select(ListNav::default(), Some(1)), None);
+        let next = ListNav {
+            next: true,
+            ..Default::default()
+        };
+        assert_eq!(select(next.clone(), None), Some(0));
+        assert_eq!(select(next, Some(3)), Some(3));
+        let prev = ListNav {
+            prev: true,
+            ..Default::default()
+        };
+        assert_eq!(select(prev, Some(0)), Some(0));
+        let page_down = ListNav {
+            page_down: true,
+            ..Default::default()
+        };
+        assert_eq!(select(page_down, Some(1)), Some(3));
+        let page_up = ListNav {
+            page_up: true,
+            ..Default::default()
+        };
+        assert_eq!(select(page_up, Some(3)), Some(1));
+
+        // type to search is case insensitive
+        let search = |text: &str| ListNav {
+            search: Some(text.to_string()),
+            ..Default::default()
+        };
+        assert_eq!(select(search("br"), Some(0)), Some(2));
+        assert_eq!(select(search("C"), None), Some(3));
+        assert_eq!(select(search("x"), Some(1)), None);
+    }
+}
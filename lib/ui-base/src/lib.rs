@@ -3,6 +3,7 @@
 pub mod better_frame;
 pub mod components;
 pub mod custom_callback;
+pub mod focus;
 pub mod font_data;
 pub mod remember_mut;
 pub mod style;
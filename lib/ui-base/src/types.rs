@@ -3,7 +3,7 @@ use std::{collections::HashMap, rc::Rc, sync::Arc, time::Duration};
 use egui::{Color32, CornerRadius, FontDefinitions, Pos2, Rect, Vec2};
 use serde::{Deserialize, Serialize};
 
-use crate::custom_callback::CustomCallbackTrait;
+use crate::{custom_callback::CustomCallbackTrait, focus::UiFocus};
 
 pub struct UiRenderPipe<'a, U: 'a> {
     pub cur_time: Duration,
@@ -97,6 +97,9 @@ pub struct UiState {
     /// glass shapes of this frame, if empty, then
     /// all glass rendering is skipped.
     pub glass_shapes: Vec<GlassShape>,
+
+    /// Keyboard & gamepad navigation.
+    pub focus: UiFocus,
 }
 
 impl Default for UiState {
@@ -110,6 +113,8 @@ impl Default for UiState {
 
             blur_shapes: Default::default(),
             glass_shapes: Default::default(),
+
+            focus: Default::default(),
         }
     }
 }
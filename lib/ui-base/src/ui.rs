@@ -390,6 +390,8 @@ impl UiContainer {
             });
         }
 
+        self.ui_state.focus.begin_frame(egui_ctx, &mut input);
+
         #[instrument(level = "trace", skip_all)]
         fn egui_run<U>(
             egui_ctx: &egui::Context,
@@ -417,6 +419,7 @@ impl UiContainer {
                     }
                     render_wrapped(ui, &mut render_func, pipe, ui_state);
                 });
+                ui_state.focus.end_frame(egui_ctx);
             })
         }
 
@@ -0,0 +1,442 @@
+//! Headless simulation of a local game for performance tracking.
+//!
+//! Started with `ddnet-rs --bench [options]`, see [`BenchOptions::from_args`].
+
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    num::NonZeroUsize,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::anyhow;
+use base::linked_hash_map_view::FxLinkedHashMap;
+use base_fs::filesys::FileSystem;
+use base_http::http::HttpClient;
+use base_io::io::{Io, IoFileSys};
+use camera::Camera;
+use client_containers::utils::{RenderGameContainers, load_containers};
+use client_render_base::{
+    map::render_pipe::GameTimeInfo, render::particle_manager::ParticleManager,
+};
+use client_render_game::components::{
+    game_objects::{GameObjectsRender, GameObjectsRenderPipe},
+    players::{PlayerRenderPipe, Players},
+};
+use config::config::{ConfigBackend, ConfigDebug, ConfigGfx, ConfigSound};
+use game_database::dummy::DummyDb;
+use game_interface::{
+    interface::{GameStateCreate, GameStateCreateOptions, GameStateInterface},
+    rcon_entries::{AuthLevel, ExecRconInput},
+    types::{
+        character_info::NetworkCharacterInfo,
+        id_types::PlayerId,
+        input::{CharacterInput, CharacterInputInfo, cursor::CharacterInputCursor},
+        network_stats::PlayerNetworkStats,
+        player_info::{PlayerClientInfo, PlayerUniqueId},
+    },
+};
+use graphics::graphics::graphics::Graphics;
+use graphics_backend::{
+    backend::{
+        GraphicsBackend, GraphicsBackendBase, GraphicsBackendIoLoading, GraphicsBackendLoading,
+    },
+    window::{BackendRawDisplayHandle, BackendWindow},
+};
+use graphics_types::types::WindowProps;
+use map::{file::MapFileReader, map::Map};
+use math::math::{Rng, vector::dvec2};
+use pool::pool::Pool;
+use sound::sound::SoundManager;
+use sound_backend::sound_backend::SoundBackend;
+use ui_base::{
+    font_data::{UiFontData, UiFontDataLoading},
+    ui::UiCreator,
+};
+use vanilla::{
+    collision::collision::Collision, config::config::ConfigVanilla, state::state::GameState,
+};
+
+#[derive(Debug, Clone)]
+pub struct BenchOptions {
+    /// Name of the map in `map/maps/`.
+    pub map: String,
+    /// Server-side bots that patrol between the spawn points.
+    pub bots: usize,
+    /// Players that send random inputs generated from [`BenchOptions::seed`].
+    pub players: usize,
+    pub ticks: u64,
+    pub seed: u64,
+    /// Whether a frame is prepared with the null graphics backend after every tick.
+    pub render: bool,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            map: "ctf1".to_string(),
+            bots: 16,
+            players: 16,
+            ticks: 1000,
+            seed: 0,
+            render: true,
+        }
+    }
+}
+
+impl BenchOptions {
+    /// Parses the arguments that follow `--bench`,
+    /// e.g. `--map ctf1 --bots 8 --players 8 --ticks 500 --seed 1 --no-render`.
+    pub fn from_args(args: &[String]) -> anyhow::Result<Self> {
+        let mut options = Self::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| anyhow!("{arg} expects a value"))
+                    .cloned()
+            };
+            match arg.as_str() {
+                "--map" => options.map = value()?,
+                "--bots" => options.bots = value()?.parse()?,
+                "--players" => options.players = value()?.parse()?,
+                "--ticks" => options.ticks = value()?.parse()?,
+                "--seed" => options.seed = value()?.parse()?,
+                "--no-render" => options.render = false,
+                _ => anyhow::bail!("unknown benchmark argument: {arg}"),
+            }
+        }
+        anyhow::ensure!(options.ticks > 0, "at least one tick must be simulated");
+        Ok(options)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BenchResult {
+    pub options: BenchOptions,
+    pub ticks_per_second: f64,
+    pub avg_tick_time: Duration,
+    /// `None` if rendering was disabled.
+    pub avg_frame_prep_time: Option<Duration>,
+    /// Allocations during the simulation, only known with the `alloc_stats` feature.
+    pub allocations: Option<u64>,
+    /// Hash over the final character positions,
+    /// equal for runs with the same options.
+    pub state_hash: u64,
+}
+
+impl BenchResult {
+    /// The result as a single json line.
+    pub fn to_json(&self) -> String {
+        serde_json::json!({
+            "map": self.options.map,
+            "bots": self.options.bots,
+            "players": self.options.players,
+            "ticks": self.options.ticks,
+            "seed": self.options.seed,
+            "ticks_per_second": self.ticks_per_second,
+            "avg_tick_time_us": self.avg_tick_time.as_secs_f64() * 1_000_000.0,
+            "avg_frame_prep_time_us": self
+                .avg_frame_prep_time
+                .map(|time| time.as_secs_f64() * 1_000_000.0),
+            "allocations": self.allocations,
+            "state_hash": format!("{:016x}", self.state_hash),
+        })
+        .to_string()
+    }
+}
+
+#[cfg(feature = "alloc_stats")]
+fn allocations() -> Option<u64> {
+    let alloc: &stats_alloc::StatsAlloc<std::alloc::System> = &stats_alloc::INSTRUMENTED_SYSTEM;
+    Some(alloc.stats().allocations as u64)
+}
+
+#[cfg(not(feature = "alloc_stats"))]
+fn allocations() -> Option<u64> {
+    None
+}
+
+/// The components that prepare a frame with the null backend.
+struct BenchRender {
+    graphics: Graphics,
+    // keep the backend alive
+    _backend: std::rc::Rc<GraphicsBackend>,
+    containers: RenderGameContainers,
+    players: Players,
+    game_objects: GameObjectsRender,
+    particles: ParticleManager,
+    collision: Box<Collision>,
+}
+
+impl BenchRender {
+    fn new(io: &Io, tp: &Arc<rayon::ThreadPool>, map: &Map) -> anyhow::Result<Self> {
+        let config_gfx = ConfigGfx {
+            backend: "null".to_string(),
+            safe_mode: false,
+        };
+        let io_loading = GraphicsBackendIoLoading::new(&config_gfx, &io.clone().into());
+        let backend_loading = GraphicsBackendLoading::new(
+            &config_gfx,
+            &ConfigDebug::default(),
+            &ConfigBackend::default(),
+            BackendRawDisplayHandle::Headless,
+            None,
+            io.clone().into(),
+        )?;
+        let (width, height) = (1280, 720);
+        let (backend_base, stream_data) = GraphicsBackendBase::new(
+            io_loading,
+            backend_loading,
+            tp,
+            BackendWindow::Headless { width, height },
+        )?;
+        let backend = GraphicsBackend::new(backend_base);
+        let graphics = Graphics::new(
+            backend.clone(),
+            stream_data,
+            WindowProps {
+                canvas_width: width,
+                canvas_height: height,
+                window_width: width as f64,
+                window_height: height as f64,
+            },
+        );
+
+        let sound = SoundManager::new(SoundBackend::new(&ConfigSound {
+            backend: "None".to_string(),
+            limits: Default::default(),
+        })?)?;
+        let scene = sound.scene_handle.create(Default::default());
+        let containers = load_containers(io, tp, None, None, true, &graphics, &sound, &scene);
+
+        let font_data =
+            UiFontData::new(UiFontDataLoading::new(&io.clone().into()))?.into_font_definitions();
+        let mut creator = UiCreator::default();
+        creator.load_font(&font_data);
+
+        Ok(Self {
+            players: Players::new(&graphics, &creator),
+            game_objects: GameObjectsRender::new(&graphics),
+            particles: ParticleManager::new(&graphics, &Duration::ZERO),
+            collision: Collision::new(map.groups.physics.clone(), true)?,
+            containers,
+            graphics,
+            _backend: backend,
+        })
+    }
+
+    fn render(&mut self, game: &GameState, cur_time: &Duration) {
+        let game_time_info = GameTimeInfo {
+            ticks_per_second: 50.try_into().unwrap(),
+            intra_tick_time: Default::default(),
+        };
+        let character_infos = game.collect_characters_info();
+        for stage in game.all_stages(0.0).values() {
+            let camera = Camera::new(
+                stage
+                    .world
+                    .characters
+                    .values()
+                    .next()
+                    .map(|character| character.lerped_pos)
+                    .unwrap_or_default(),
+                1.0,
+                None,
+                true,
+            );
+            let containers = &mut self.containers;
+            self.game_objects.render(&mut GameObjectsRenderPipe {
+                particle_manager: &mut self.particles,
+                cur_time,
+                game_time_info: &game_time_info,
+                character_infos: &character_infos,
+                projectiles: &stage.world.projectiles,
+                flags: &stage.world.ctf_flags,
+                lasers: &stage.world.lasers,
+                pickups: &stage.world.pickups,
+                ctf_container: &mut containers.ctf_container,
+                game_container: &mut containers.game_container,
+                ninja_container: &mut containers.ninja_container,
+                weapon_container: &mut containers.weapon_container,
+                local_character_id: None,
+                camera: &camera,
+                phased_alpha: 0.5,
+                phased: false,
+            });
+            self.players.render(&mut PlayerRenderPipe {
+                cur_time,
+                game_time_info: &game_time_info,
+                render_infos: &stage.world.characters,
+                character_infos: &character_infos,
+                skins: &mut containers.skin_container,
+                ninjas: &mut containers.ninja_container,
+                freezes: &mut containers.freeze_container,
+                hooks: &mut containers.hook_container,
+                weapons: &mut containers.weapon_container,
+                emoticons: &mut containers.emoticons_container,
+                particle_manager: &mut self.particles,
+                collision: &self.collision,
+                camera: &camera,
+                spatial_sound: false,
+                sound_playback_speed: 1.0,
+                ingame_sound_volume: 0.0,
+                own_character: None,
+                phased_alpha: 0.5,
+                phased: false,
+            });
+        }
+        self.graphics.swap();
+    }
+}
+
+/// Hash over the positions of all characters.
+fn state_hash(game: &GameState) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for (stage_id, stage) in game.all_stages(0.0).iter() {
+        stage_id.hash(&mut hasher);
+        for (id, character) in stage.world.characters.iter() {
+            id.hash(&mut hasher);
+            character.lerped_pos.x.to_bits().hash(&mut hasher);
+            character.lerped_pos.y.to_bits().hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+/// Random inputs for a player, like a very confused human would do.
+fn random_input(
+    rng: &mut Rng,
+    inps: &mut FxLinkedHashMap<PlayerId, CharacterInputInfo>,
+    inp: &mut CharacterInput,
+    id: &PlayerId,
+) {
+    let mut new_inp = *inp;
+    new_inp.state.fire.set(rng.random_int_in(0..=1) != 0);
+    new_inp.state.hook.set(rng.random_int_in(0..=1) != 0);
+    new_inp.state.jump.set(rng.random_int_in(0..=1) != 0);
+    new_inp.state.dir.set(rng.random_int_in(0..=2) as i32 - 1);
+    new_inp
+        .cursor
+        .set(CharacterInputCursor::from_vec2(&dvec2::new(
+            rng.random_float() as f64 - 0.5,
+            rng.random_float() as f64 - 0.5,
+        )));
+
+    let diff = new_inp.consumable.diff(&inp.consumable);
+    *inp = new_inp;
+    inps.insert(*id, CharacterInputInfo { inp: new_inp, diff });
+}
+
+/// Simulates the local game & optionally prepares a frame after every tick.
+pub fn run(options: &BenchOptions) -> anyhow::Result<BenchResult> {
+    let io = IoFileSys::new(|rt| {
+        Arc::new(
+            FileSystem::new(rt, "org", "", "DDNet-Rs-Alpha", "DDNet-Accounts")
+                .expect("most likely you are missing a data directory"),
+        )
+    });
+    let io = Io::from(io, Arc::new(HttpClient::new()));
+    let tp = Arc::new(
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(
+                std::thread::available_parallelism()
+                    .unwrap_or(NonZeroUsize::new(2).unwrap())
+                    .get(),
+            )
+            .build()?,
+    );
+
+    let fs = io.fs.clone();
+    let map_path = format!("map/maps/{}.twmap.tar", options.map);
+    let map_file = io
+        .rt
+        .spawn(async move { Ok(fs.read_file(map_path.as_ref()).await?) })
+        .get()?;
+
+    let mut render = if options.render {
+        let map = Map::read(&MapFileReader::new(map_file.clone())?, &tp)?;
+        Some(BenchRender::new(&io, &tp, &map)?)
+    } else {
+        None
+    };
+
+    let max_characters = (options.bots + options.players).max(1);
+    let (mut game, _) = GameState::new(
+        map_file,
+        options.map.as_str().try_into()?,
+        GameStateCreateOptions {
+            hint_max_characters: Some(max_characters),
+            config: Some(serde_json::to_vec(&ConfigVanilla {
+                max_ingame_players: max_characters as u32,
+                ..Default::default()
+            })?),
+            ..Default::default()
+        },
+        io.rt.clone(),
+        Arc::new(DummyDb),
+    )
+    .map_err(|err| anyhow!("failed to create the game: {}", err.as_str()))?;
+
+    if options.bots > 0 {
+        for res in game.rcon_command(
+            None,
+            ExecRconInput {
+                raw: format!("bots.add {} patrol", options.bots)
+                    .as_str()
+                    .try_into()?,
+                auth_level: AuthLevel::Admin,
+            },
+        ) {
+            res.map_err(|err| anyhow!("failed to add bots: {}", err.as_str()))?;
+        }
+    }
+    let ids: Vec<_> = (0..options.players)
+        .map(|index| {
+            game.player_join(&PlayerClientInfo {
+                info: NetworkCharacterInfo::explicit_default(),
+                id: index as u64,
+                unique_identifier: PlayerUniqueId::Account(index as u64),
+                initial_network_stats: PlayerNetworkStats::default(),
+                ip: None,
+            })
+        })
+        .collect();
+
+    let mut rng = Rng::new(options.seed);
+    let mut inps = vec![CharacterInput::default(); ids.len()];
+    let game_inps: Pool<FxLinkedHashMap<PlayerId, CharacterInputInfo>> = Pool::with_capacity(1);
+
+    let allocations_start = allocations();
+    let mut tick_time = Duration::ZERO;
+    let mut frame_time = Duration::ZERO;
+    for tick in 0..options.ticks {
+        let tick_start = Instant::now();
+        let mut game_inps = game_inps.new();
+        for (inp, id) in inps.iter_mut().zip(ids.iter()) {
+            random_input(&mut rng, &mut game_inps, inp, id);
+        }
+        game.set_player_inputs(game_inps);
+        game.tick(Default::default());
+        game.clear_events();
+        tick_time += tick_start.elapsed();
+
+        if let Some(render) = &mut render {
+            let frame_start = Instant::now();
+            render.render(&game, &(Duration::from_millis(20) * tick as u32));
+            frame_time += frame_start.elapsed();
+        }
+    }
+    let allocations = allocations_start
+        .zip(allocations())
+        .map(|(start, end)| end.saturating_sub(start));
+
+    Ok(BenchResult {
+        options: options.clone(),
+        ticks_per_second: options.ticks as f64 / tick_time.as_secs_f64().max(f64::EPSILON),
+        avg_tick_time: tick_time / options.ticks as u32,
+        avg_frame_prep_time: render.is_some().then(|| frame_time / options.ticks as u32),
+        allocations,
+        state_hash: state_hash(&game),
+    })
+}
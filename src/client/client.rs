@@ -1,34 +1,53 @@
 use std::{
-    borrow::Borrow, cell::RefCell, net::SocketAddr, num::NonZeroUsize, path::PathBuf, rc::Rc,
-    sync::Arc, time::Duration,
+    borrow::Borrow,
+    cell::{Cell, RefCell},
+    collections::HashSet,
+    net::SocketAddr,
+    num::NonZeroUsize,
+    path::PathBuf,
+    rc::Rc,
+    sync::Arc,
+    time::{Duration, SystemTime},
 };
 
 use anyhow::anyhow;
 use base::{
-    benchmark::Benchmark, linked_hash_map_view::FxLinkedHashMap, network_string::NetworkString,
-    steady_clock::SteadyClock,
+    benchmark::Benchmark, hash::fmt_hash, linked_hash_map_view::FxLinkedHashMap,
+    network_string::NetworkString, steady_clock::SteadyClock,
 };
 use base_fs::filesys::FileSystem;
 
 use base_http::http::HttpClient;
-use base_io::io::{Io, IoFileSys};
-use binds::binds::{BindActionsHotkey, BindActionsLocalPlayer};
+use base_io::{
+    io::{Io, IoFileSys},
+    runtime::IoRuntimeTask,
+};
+use binds::binds::{BindActionsHotkey, BindActionsLocalPlayer, substitute_bind_keys};
 use camera::Camera;
 use client_accounts::accounts::{Accounts, AccountsLoading};
 use client_console::console::{
     console::{ConsoleEvents, ConsoleRenderPipe},
     local_console::{LocalConsole, LocalConsoleBuilder, LocalConsoleEvent},
     remote_console::RemoteConsoleEvent,
+    watch::{ConsoleWatches, config_var_value},
 };
 use client_containers::{
     container::ContainerLoadOptions,
     skins::{SKIN_CONTAINER_PATH, SkinContainer},
 };
 use client_demo::{DemoVideoEncodeProperties, DemoViewer, DemoViewerSettings, EncoderSettings};
-use client_map::client_map::{ClientMapFile, ClientMapLoading, GameMap};
-use client_notifications::overlay::ClientNotifications;
+use client_map::{
+    cert_pins::{CERT_PINS_PATH, CertPins},
+    client_map::{ClientMapFile, ClientMapLoading, GameMap},
+    download_trust::{DOWNLOAD_TRUST_PATH, DownloadTrust},
+};
+use client_notifications::{
+    overlay::ClientNotifications,
+    toasts::{Toast, ToastSeverity},
+};
 use client_render_base::{
     map::{
+        hq_assets::MapHqAssets,
         map::RenderMap,
         map_pipeline::MapPipeline,
         render_pipe::{GameTimeInfo, RenderPipeline, RenderPipelineBase},
@@ -38,11 +57,14 @@ use client_render_base::{
 use client_render_game::render_game::{
     EmoteWheelInput, ObservedAnchoredSize, ObservedPlayer, PlayerFeedbackEvent, RenderForPlayer,
     RenderGameCreateOptions, RenderGameForPlayer, RenderGameInput, RenderGameInterface,
-    RenderGameSettings, RenderModTy, RenderPlayerCameraMode, SpectatorSelectionInput,
+    RenderGameSettings, RenderModTy, RenderPlayerCameraMode, ScoreboardInput,
+    SpectatorSelectionInput,
 };
 use client_types::{
     cert::ServerCertMode,
+    chat::ChatNotifyKeyword,
     console::{ConsoleEntry, entries_to_parser},
+    log::{LogBuffer, LogView},
 };
 use client_ui::{
     chat::user_data::{ChatEvent, ChatMode},
@@ -52,7 +74,7 @@ use client_ui::{
     hud::user_data::RenderDateTime,
     ingame_menu::{
         account_info::AccountInfo,
-        client_info::{ActiveClientInfo, ClientInfo},
+        client_info::{ActiveClientInfo, ClientInfo, SideInfo, SidesInfo},
         page::IngameMenuUi,
         raw_input_info::{self, RawInputInfo},
         server_info::GameServerInfo,
@@ -64,8 +86,10 @@ use client_ui::{
         monitors::{UiMonitor, UiMonitorVideoMode, UiMonitors},
         page::MainMenuUi,
         player_settings_ntfy::PlayerSettingsSync,
-        spatial_chat::SpatialChat,
+        setup_wizard::launch_setup_wizard,
+        spatial_chat::{EntitiesEvent, SpatialChat},
     },
+    scoreboard::user_data::{ScoreboardEvent, ScoreboardPlayerContext},
     spectator_selection::user_data::SpectatorSelectionEvent,
     utils::render_tee_for_ui,
 };
@@ -75,12 +99,16 @@ use ddnet_info_proxy::DdnetInfoProxy;
 use demo::recorder::DemoRecorder;
 use editor::editor::{EditorInterface, EditorResult};
 use egui::{CursorIcon, FontDefinitions};
-use game_config::config::{Config, ConfigGame, ConfigMap};
+use game_config::{
+    config::{Config, ConfigGame, ConfigGraphicsPreset, ConfigMap},
+    server_profile::AppliedServerProfile,
+};
 use graphics::graphics::graphics::Graphics;
 use graphics_backend::{
     backend::{
         GraphicsBackend, GraphicsBackendBase, GraphicsBackendIoLoading, GraphicsBackendLoading,
     },
+    safe_mode::{GfxInitFailureMarker, GfxSafeModeDecision, safe_mode_config},
     utils::{
         AppWithGraphics, GraphicsApp, client_window_config_to_native_window_options,
         client_window_props_changed_update_config,
@@ -91,7 +119,7 @@ use graphics_backend::{
 use editor_wasm::editor::editor_wasm_manager::{EditorState, EditorWasmManager};
 use game_interface::{
     client_commands::{ClientCameraMode, ClientCommand, JoinStage, MAX_TEAM_NAME_LEN},
-    events::EventClientInfo,
+    events::{EventClientInfo, GameWorldEvent, GameWorldNotificationEvent},
     interface::GameStateInterface,
     types::{
         character_info::NetworkCharacterInfo,
@@ -100,21 +128,25 @@ use game_interface::{
         input::{
             CharacterInputConsumableDiff, CharacterInputInfo, dyn_cam::CharacterInputDynCamOffset,
         },
+        player_info::PlayerUniqueId,
         render::{
-            character::{CharacterInfo, PlayerCameraMode, PlayerIngameMode, TeeEye},
+            character::{
+                CharacterInfo, LocalCharacterRenderInfo, PlayerCameraMode, PlayerIngameMode, TeeEye,
+            },
             game::game_match::MatchSide,
-            scoreboard::ScoreboardGameType,
+            scoreboard::{ScoreboardCharacterInfo, ScoreboardGameType, ScoreboardScoreType},
             stage::StageRenderInfo,
         },
         snapshot::SnapshotClientInfo,
         ticks::TickOptions,
     },
-    votes::{VoteIdentifierType, VoteType, Voted},
+    votes::{PlayerVoteKey, VoteIdentifierType, VoteType, Voted},
 };
 use game_server::{local_server::start_local_server, server::Server};
 use graphics_types::rendering::ColorRgba;
 use input_binds::binds::{BindKey, Binds};
 use legacy_proxy::LegacyProxy;
+use map::map::metadata::ThemeAmbiance;
 use math::math::{
     length, normalize, normalize_pre_length,
     vector::{dvec2, vec2},
@@ -131,6 +163,7 @@ use pool::{
     datatypes::{PoolFxLinkedHashMap, StringPool},
     pool::Pool,
 };
+use prediction_timer::interpolation_buffer::InterpolationBufferSettings;
 use rayon::ThreadPool;
 use sound::{scene_object::SceneObject, sound::SoundManager};
 use sound_backend::sound_backend::SoundBackend;
@@ -156,32 +189,47 @@ use crate::{
 
 use game_base::{
     assets_url::HTTP_RESOURCE_URL,
+    browser_favorite_player::FavoritePlayers,
+    browser_server_list::{
+        ConnectHistory, ServerListEntry, ServerListExport, add_connect, merge_connect_history,
+        merge_server_list,
+    },
     connecting_log::{ConnectModes, ConnectingLog},
     game_types::{intra_tick_time, intra_tick_time_to_ratio, is_next_tick, time_until_tick},
     local_server_info::{LocalServerInfo, LocalServerState, LocalServerStateReady},
+    local_stats::{LOCAL_STATS_PATH, LocalStats},
     network::messages::{GameModification, MsgClAddLocalPlayer, MsgClChatMsg, MsgClLoadVotes},
     player_input::PlayerInput,
     server_browser::ServerBrowserData,
 };
 
-use game_network::messages::{ClientToServerMessage, ClientToServerPlayerMessage};
+use game_network::{
+    lan_discovery::{LAN_DISCOVERY_PORT, LanListener, UdpLanTransport},
+    messages::{ClientToServerMessage, ClientToServerPlayerMessage},
+};
 
 use super::{
+    frame_pacing::{FrameLimiter, limiter_refresh_rate},
     game::{
+        active::TOAST_ACTION_VOTE_YES,
         data::{ClientConnectedPlayer, GameData},
         types::{DisconnectAutoCleanup, GameBase, GameConnect, GameMsgPipeline},
     },
     game_events::{GameEventPipeline, GameEventsClient},
+    idle::{self, IdleThrottle},
     input::input_handling::{InputEv, InputHandling, InputHandlingEvent},
+    local_stats::LocalStatsRecorder,
     localplayer::ClientPlayerInputPerTick,
+    menu_theme::{local_hour, select_ambiance, theme_map_name},
     overlays::client_stats::{ClientStats, ClientStatsRenderPipe, DebugHudRenderPipe},
+    server_lists::{ServerListTask, export_server_list, import_server_list},
     spatial_chat::spatial_chat::{self, SpatialChatGameWorldTy, SpatialChatGameWorldTyRef},
 };
 
 type UiManager = UiManagerBase<Config>;
 
 pub fn ddnet_main(
-    start_arguments: Vec<String>,
+    mut start_arguments: Vec<String>,
     time: SteadyClock,
     shared_info: Arc<LocalServerInfo>,
     app: NativeApp,
@@ -193,11 +241,26 @@ pub fn ddnet_main(
         )
     });
 
-    let mut config_engine = config_fs::load(&io).unwrap_or_default();
+    // allows overwriting config files of newer versions
+    let force_config = start_arguments.iter().any(|arg| arg == "--force-config");
+    start_arguments.retain(|arg| arg != "--force-config");
+
+    let mut config_warnings = Vec::new();
+    let mut config_engine = config_fs::load(&io)
+        .map(|loaded| {
+            config_warnings.extend(loaded.warnings);
+            loaded.config
+        })
+        .unwrap_or_default();
 
     let benchmark = Benchmark::new(config_engine.dbg.bench);
 
-    let mut config_game = game_config_fs::fs::load(&io).unwrap_or_default();
+    let mut config_game = game_config_fs::fs::load(&io)
+        .map(|loaded| {
+            config_warnings.extend(loaded.warnings);
+            loaded.config
+        })
+        .unwrap_or_default();
     benchmark.bench("loading client config");
 
     let mut has_startup_errors = false;
@@ -268,6 +331,27 @@ pub fn ddnet_main(
         None
     };
 
+    // the graphics backend failed to initialize on the last launch
+    if let Some(marker) = GfxInitFailureMarker::load(&io) {
+        match marker.decision() {
+            GfxSafeModeDecision::Full => {}
+            GfxSafeModeDecision::Offer => {
+                if !config_engine.gfx.safe_mode && offer_gfx_safe_mode(&marker) {
+                    config_engine.gfx.safe_mode = true;
+                }
+            }
+            GfxSafeModeDecision::Auto => {
+                if !config_engine.gfx.safe_mode {
+                    log::warn!(
+                        "graphics backend failed to initialize {} times, starting in safe mode",
+                        marker.failures
+                    );
+                }
+                config_engine.gfx.safe_mode = true;
+            }
+        }
+    }
+
     let graphics_backend_io_loading = GraphicsBackendIoLoading::new(&config_engine.gfx, &io);
     // first prepare all io tasks of all components
     benchmark.bench("load_io of graphics backend");
@@ -287,6 +371,8 @@ pub fn ddnet_main(
         graphics_backend_loading: None,
         local_console_builder,
         has_startup_errors,
+        config_warnings,
+        force_config,
     };
     Native::run_loop::<GraphicsApp<ClientNativeImpl>, _>(
         client,
@@ -303,6 +389,30 @@ pub fn ddnet_main(
     Ok(())
 }
 
+/// Asks whether to start in safe mode, since the graphics
+/// backend failed to initialize on the last launch.
+#[cfg(not(target_os = "android"))]
+fn offer_gfx_safe_mode(marker: &GfxInitFailureMarker) -> bool {
+    use native_dialog::{MessageDialogBuilder, MessageLevel};
+    MessageDialogBuilder::default()
+        .set_level(MessageLevel::Warning)
+        .set_title("Graphics failed to start")
+        .set_text(format!(
+            "The graphics failed to start on the last launch:\n{}\n\n\
+            Start in safe mode? It disables multisampling, \
+            threaded rendering & debug extensions.",
+            marker.err
+        ))
+        .confirm()
+        .show()
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "android")]
+fn offer_gfx_safe_mode(_marker: &GfxInitFailureMarker) -> bool {
+    false
+}
+
 #[cfg(feature = "alloc_track")]
 fn track_report() {
     let total_consumption = std::cell::Cell::new(0);
@@ -332,6 +442,12 @@ fn stats_report() {
     .unwrap();
 }
 
+/// The highlight of a round that is replayed by the demo player.
+struct KillcamPlayback {
+    /// Applied once the demo player finished loading.
+    speed: Option<f64>,
+}
+
 #[derive(Debug)]
 enum ConnectLocalServerResult {
     Connect {
@@ -358,6 +474,9 @@ struct ClientNativeLoadingImpl {
 
     local_console_builder: Option<LocalConsoleBuilder>,
     has_startup_errors: bool,
+    /// E.g. config keys that could not be migrated.
+    config_warnings: Vec<String>,
+    force_config: bool,
 }
 
 struct ClientNativeImpl {
@@ -377,14 +496,25 @@ struct ClientNativeImpl {
     sound_backend: Rc<SoundBackend>,
     game: Game,
     connecting_log: ConnectingLog,
+    /// The remembered download decisions for untrusted servers.
+    download_trust: DownloadTrust,
+    /// The certificates of the servers connected to before.
+    cert_pins: CertPins,
+    /// The stats of the local player, only saved locally.
+    local_stats: LocalStatsRecorder,
     demo_player: Option<DemoViewer>,
+    /// The demo player shows the highlight of a round.
+    killcam: Option<KillcamPlayback>,
     client_stats: ClientStats,
     notifications: ClientNotifications,
     thread_pool: Arc<ThreadPool>,
     io: Io,
     config: Config,
+    /// Overwrite config files, even if a newer version wrote them.
+    force_config: bool,
     cur_time: Duration,
-    last_refresh_rate_time: Duration,
+    /// Plans & waits for the frames.
+    frame_limiter: FrameLimiter,
 
     editor: EditorState,
 
@@ -393,6 +523,10 @@ struct ClientNativeImpl {
 
     local_console: LocalConsole,
     console_logs: String,
+    /// The captured log records for the logs tab of the local console.
+    log_view: LogView,
+    /// The config variables of the `watch` command.
+    console_watches: ConsoleWatches,
 
     ui_manager: UiManager,
     ui_events: UiEvents,
@@ -411,11 +545,30 @@ struct ClientNativeImpl {
     votes: Votes,
 
     menu_map: ClientMapLoading,
+    /// The theme & variant the menu map should show.
+    menu_map_theme: (String, ThemeAmbiance),
+    /// The variant the menu map is loaded for,
+    /// differs if the wanted variant is missing.
+    menu_map_ambiance: ThemeAmbiance,
 
     global_binds: Binds<BindActionsHotkey>,
 
     legacy_proxy_thread: Option<LegacyProxy>,
 
+    /// The settings profile of the server the client is connected to.
+    applied_server_profile: Option<AppliedServerProfile>,
+
+    /// Whether the window has the input focus.
+    window_focused: bool,
+    /// Throttles rendering while the player is away.
+    idle: IdleThrottle,
+    /// Servers that already showed their message of the day in this session.
+    motd_shown_servers: HashSet<SocketAddr>,
+    /// Export or import of a server list file.
+    server_list_task: Option<IoRuntimeTask<ServerListTask>>,
+    /// Finds the servers in the local network.
+    lan_listener: Option<LanListener<UdpLanTransport>>,
+
     // pools & helpers
     string_pool: StringPool,
 
@@ -426,6 +579,9 @@ struct ClientNativeImpl {
     #[cfg(feature = "auto_updater")]
     auto_updater: Option<auto_updater::AutoUpdater>,
 
+    /// Set by the graphics backend after it recreated a lost device.
+    gpu_resources_invalidated: Rc<Cell<bool>>,
+
     // put graphics at the end, so it's dropped last
     graphics: Graphics,
     graphics_backend: Rc<GraphicsBackend>,
@@ -541,8 +697,209 @@ impl ClientNativeImpl {
             .multi_sampling(self.config.engine.gl.msaa_samples);
     }
 
+    /// An os event that might change the input of the active local player.
+    fn on_os_input_event(&mut self) {
+        if let Game::Active(game) = &mut self.game
+            && let Some(input_timings) = &mut game.input_timings
+        {
+            input_timings.on_os_event(self.time.now());
+        }
+    }
+
+    /// Loads the map of the menu theme in the given variant.
+    fn load_menu_map(
+        sound: &SoundManager,
+        graphics: &Graphics,
+        graphics_backend: &Rc<GraphicsBackend>,
+        time: &SteadyClock,
+        io: &Io,
+        thread_pool: &Arc<ThreadPool>,
+        config_engine: &ConfigEngine,
+        config_game: &ConfigGame,
+        font_data: &FontDefinitions,
+        ambiance: ThemeAmbiance,
+    ) -> ClientMapLoading {
+        let menu_map_path = format!("themes/{}", config_game.menu.background_map.as_str());
+        ClientMapLoading::new(
+            sound,
+            graphics,
+            graphics_backend,
+            time,
+            menu_map_path.as_ref(),
+            &theme_map_name(ambiance).try_into().unwrap(),
+            None,
+            io,
+            thread_pool,
+            GameModification::Native,
+            true,
+            &config_engine.dbg,
+            &config_engine.net,
+            Default::default(),
+            RenderGameCreateOptions {
+                physics_group_name: "vanilla".try_into().unwrap(),
+                resource_http_download_url: None,
+                resource_download_server: None,
+                fonts: font_data.clone(),
+                sound_props: Default::default(),
+                render_mod: RenderModTy::Native,
+                required_resources: Default::default(),
+                client_local_infos: Default::default(),
+                hq_assets: MapHqAssets::new(&config_game.map),
+            },
+            Default::default(),
+        )
+    }
+
+    /// Reloads the menu map if the theme or its variant changed.
+    fn update_menu_map_theme(&mut self) {
+        let ambiance = select_ambiance(
+            self.config.game.menu.theme_variant,
+            self.game.theme_ambiance(),
+            local_hour(),
+        );
+        let theme = &self.config.game.menu.background_map;
+        let load_ambiance = if self.menu_map_theme.0 != *theme || self.menu_map_theme.1 != ambiance
+        {
+            self.menu_map_theme = (theme.clone(), ambiance);
+            ambiance
+        } else if self.menu_map.err().is_err() && self.menu_map_ambiance != ThemeAmbiance::Day {
+            // missing variants fall back to the base theme
+            ThemeAmbiance::Day
+        } else {
+            return;
+        };
+        self.menu_map_ambiance = load_ambiance;
+        self.menu_map = Self::load_menu_map(
+            &self.sound,
+            &self.graphics,
+            &self.graphics_backend,
+            &self.time,
+            &self.io,
+            &self.thread_pool,
+            &self.config.engine,
+            &self.config.game,
+            &self.font_data,
+            load_ambiance,
+        );
+    }
+
+    /// The graphics device was recreated after it was lost,
+    /// everything that uploaded textures or buffers loads them again.
+    fn reload_after_gpu_reset(&mut self) {
+        self.skin_container.reload_after_gpu_reset();
+        self.menu_map = Self::load_menu_map(
+            &self.sound,
+            &self.graphics,
+            &self.graphics_backend,
+            &self.time,
+            &self.io,
+            &self.thread_pool,
+            &self.config.engine,
+            &self.config.game,
+            &self.font_data,
+            self.menu_map_ambiance,
+        );
+
+        // the map & its containers are loaded again by reconnecting
+        let connect = match &self.game {
+            Game::Loading(game) => Some(&game.connect),
+            Game::WaitingForFirstSnapshot(game) | Game::Active(game) => Some(&game.connect),
+            _ => None,
+        }
+        .map(|connect| {
+            (
+                connect.addr,
+                connect.server_fingerprint(),
+                connect.rcon_secret,
+            )
+        });
+        match connect {
+            Some((addr, Some(cert_hash), rcon_secret)) => {
+                self.ui_events.push(UiEvent::Connect {
+                    addr,
+                    cert_hash,
+                    rcon_secret,
+                    can_start_internal_server: false,
+                    can_connect_internal_server: true,
+                    as_spectator: false,
+                });
+            }
+            Some((_, None, _)) => {
+                self.game = Game::None;
+            }
+            None => {}
+        }
+        self.notifications.add_err(
+            "The graphics driver was reset, all resources were reloaded.",
+            Duration::from_secs(10),
+        );
+    }
+
+    /// Applies a finished export or import of a server list.
+    fn update_server_list_task(&mut self) {
+        if !self
+            .server_list_task
+            .as_ref()
+            .is_some_and(|task| task.is_finished())
+        {
+            return;
+        }
+        match self.server_list_task.take().unwrap().get() {
+            Ok(ServerListTask::Exported(path)) => {
+                self.notifications.add_success(
+                    format!("Exported the server list to {}", path.display()),
+                    Duration::from_secs(5),
+                );
+            }
+            Ok(ServerListTask::Imported(list)) => {
+                let menu = &mut self.config.game.menu;
+                let mut favorites: Vec<_> = menu
+                    .favorite_servers
+                    .iter()
+                    .map(|addr| ServerListEntry {
+                        addr: addr.clone(),
+                        fingerprint: self
+                            .browser_data
+                            .find_str(addr)
+                            .map(|server| fmt_hash(&server.info.cert_sha256_fingerprint)),
+                        last_connect: None,
+                    })
+                    .collect();
+                let favorite_count = favorites.len();
+                merge_server_list(&mut favorites, list.favorites);
+                let added_favorites = favorites.len() - favorite_count;
+                menu.favorite_servers = favorites.into_iter().map(|entry| entry.addr).collect();
+                self.browser_data.clear_filter_cache();
+
+                let mut history = self.config.storage::<ConnectHistory>("connect-history");
+                merge_connect_history(&mut history, list.history);
+                self.config.set_storage("connect-history", &history);
+
+                self.notifications.add_success(
+                    format!("Imported the server list, {added_favorites} new favorites"),
+                    Duration::from_secs(5),
+                );
+            }
+            Err(err) => {
+                self.notifications.add_err(
+                    format!("Server list export or import failed: {err}"),
+                    Duration::from_secs(10),
+                );
+            }
+        }
+    }
+
+    fn update_lan_servers(&mut self) {
+        if let Some(listener) = &mut self.lan_listener
+            && listener.update(self.cur_time)
+        {
+            self.browser_data.set_lan_servers(listener.servers());
+        }
+    }
+
     #[instrument(level = "trace", skip_all)]
     fn render_menu_background_map(&mut self) {
+        self.update_menu_map_theme();
         if let Some(map) = self.menu_map.continue_loading() {
             let intra_tick_time = self.time.now();
             let ClientMapFile::Menu { render } = &map else {
@@ -564,7 +921,9 @@ impl ClientNativeImpl {
                         include_last_anim_point: false,
                         camera: &Camera::new(vec2::new(21.0, 15.0), 1.0, None, true),
                         map_sound_volume: self.config.game.snd.render.map_sound_volume
-                            * self.config.game.snd.global_volume,
+                            * self.config.game.snd.global_volume
+                            * self.idle.volume(&self.config.game.cl.idle),
+                        music_intensity: None,
                     },
                     buffered_map: &render.data.buffered_map,
                 },
@@ -572,6 +931,31 @@ impl ClientNativeImpl {
         }
     }
 
+    /// Replays the highlight of the round in slow motion,
+    /// if the server sent one at the game over.
+    fn start_killcam(&mut self) {
+        let Game::Active(game) = &mut self.game else {
+            return;
+        };
+        let Some(ticks) = game.killcam.take() else {
+            return;
+        };
+        if !self.config.game.cl.killcam || self.demo_player.is_some() {
+            return;
+        }
+        match game.replay.to_demo_range(ticks) {
+            Ok(demo) => {
+                self.demo_player = Some(demo);
+                self.killcam = Some(KillcamPlayback {
+                    speed: Some(self.config.game.cl.killcam_speed as f64 / 100.0),
+                });
+            }
+            Err(err) => {
+                log::debug!("could not replay the highlight of the round: {err}");
+            }
+        }
+    }
+
     #[instrument(level = "trace", skip_all)]
     fn render_game(&mut self, native: &mut dyn NativeImpl) {
         let remote_console_open = self.game.remote_console_open();
@@ -583,6 +967,7 @@ impl ClientNativeImpl {
                 render,
                 game: game_state,
                 unpredicted_game,
+                ..
             } = &mut game.map;
             let is_menu_open = self.ui_manager.ui.ui_state.is_ui_open
                 || self.local_console.ui.ui_state.is_ui_open
@@ -639,17 +1024,28 @@ impl ClientNativeImpl {
                     .predicted_game_monotonic_tick
                     .saturating_sub(sub_ticks);
 
-                let last_snaps = &game.game_data.last_snaps;
-                let mut it = last_snaps.range(0..=first_tick);
-                let snap_range1 = it.next_back();
-                let snap_range2 = it.next_back();
-                let snap_range = snap_range1
-                    .zip(snap_range2.or(snap_range1))
-                    .map(|((tick2, _), (tick1, _))| tick2.saturating_sub(*tick1))
-                    .unwrap_or(1)
-                    .max(1);
-                // depending on how many snapshots arrive, lower the first tick based on that
-                let first_tick = first_tick.saturating_sub(snap_range);
+                let buffer_config = &self.config.game.cl.interpolation_buffer;
+                let max = Duration::from_millis(buffer_config.max_ms);
+                // the server announces a lower rate before the snapshots get sparse
+                let announced_interval =
+                    tick_time * game.game_data.ticks_per_snapshot.unwrap_or(1).max(1) as u32;
+                let buffer = game.game_data.interpolation_buffer.update(
+                    self.cur_time,
+                    &InterpolationBufferSettings {
+                        min: Duration::from_millis(buffer_config.min_ms)
+                            .max(announced_interval)
+                            .min(max),
+                        max,
+                        manual: (buffer_config.manual_ms > 0)
+                            .then(|| Duration::from_millis(buffer_config.manual_ms)),
+                    },
+                );
+                // depending on the jitter & loss of the snapshots, lower the first tick
+                let render_tick = (first_tick as f64 + intra_tick_ratio
+                    - buffer.as_secs_f64() / tick_time.as_secs_f64())
+                .max(0.0);
+                let first_tick = render_tick.floor() as GameTickType;
+                let render_intra_tick_ratio = render_tick.fract();
 
                 let prev_snap = game.game_data.last_snaps.range(0..=first_tick).next_back();
                 let prev_tick = prev_snap.map(|(tick, _)| *tick).unwrap_or(first_tick);
@@ -664,7 +1060,7 @@ impl ClientNativeImpl {
                 let tick_diff = next_tick.saturating_sub(prev_tick).max(1);
 
                 let unpredicted_intra_tick_ratio =
-                    (first_tick as f64 + intra_tick_ratio) / tick_diff as f64;
+                    (first_tick as f64 + render_intra_tick_ratio) / tick_diff as f64;
 
                 unpredicted_game.from_snapshots(&game.game_data.last_snaps, prev_tick + first_tick);
                 (
@@ -720,6 +1116,54 @@ impl ClientNativeImpl {
                     camera_mode: active_local_player_info
                         .map(|p| p.cam_mode.clone())
                         .unwrap_or_else(|| PlayerCameraMode::Default),
+                    sides: match &scoreboard_info.game {
+                        ScoreboardGameType::SidedPlay {
+                            red_stages,
+                            blue_stages,
+                            ignore_stage,
+                            red_side_name,
+                            blue_side_name,
+                            ..
+                        } => {
+                            let stage_id = active_local_player_id
+                                .and_then(|id| character_infos.get(&id))
+                                .and_then(|c| c.stage_id)
+                                .unwrap_or(*ignore_stage);
+                            let red = red_stages
+                                .get(&stage_id)
+                                .map(|s| s.characters.as_slice())
+                                .unwrap_or_default();
+                            let blue = blue_stages
+                                .get(&stage_id)
+                                .map(|s| s.characters.as_slice())
+                                .unwrap_or_default();
+                            let side_info =
+                                |name: String, characters: &[ScoreboardCharacterInfo]| {
+                                    SideInfo::new(
+                                        name,
+                                        characters.iter().map(|c| match c.score {
+                                            ScoreboardScoreType::Points(score) => score,
+                                            ScoreboardScoreType::RaceFinishTime(_)
+                                            | ScoreboardScoreType::None => 0,
+                                        }),
+                                    )
+                                };
+                            let is_own =
+                                |c: &ScoreboardCharacterInfo| Some(c.id) == active_local_player_id;
+                            Some(SidesInfo {
+                                red: side_info(red_side_name.to_string(), red),
+                                blue: side_info(blue_side_name.to_string(), blue),
+                                own_side: if red.iter().any(is_own) {
+                                    Some(MatchSide::Red)
+                                } else if blue.iter().any(is_own) {
+                                    Some(MatchSide::Blue)
+                                } else {
+                                    None
+                                },
+                            })
+                        }
+                        ScoreboardGameType::SoloPlay { .. } => None,
+                    },
                 });
             }
 
@@ -817,6 +1261,54 @@ impl ClientNativeImpl {
                 );
             }
 
+            // the message of the day only pops up once per server
+            let has_motd = events.values().any(|events| {
+                events.worlds.values().any(|world| {
+                    world.events.values().any(|event| {
+                        matches!(
+                            event,
+                            GameWorldEvent::Notification(GameWorldNotificationEvent::Motd { .. })
+                        )
+                    })
+                })
+            });
+            let auto_show_motd = has_motd && self.motd_shown_servers.insert(game.connect.addr);
+
+            // hints are shown as toasts, so they can be dismissed
+            for (_, events) in events
+                .iter()
+                .filter(|((_, by_prediction), _)| !by_prediction)
+            {
+                for ev in events
+                    .worlds
+                    .values()
+                    .flat_map(|world| world.events.values())
+                {
+                    if let GameWorldEvent::Notification(GameWorldNotificationEvent::Hint { msg }) =
+                        ev
+                    {
+                        let binds = self
+                            .config
+                            .game
+                            .players
+                            .get(self.config.game.profiles.main as usize)
+                            .map(|p| p.binds.as_slice())
+                            .unwrap_or_default();
+                        self.notifications.add(
+                            Toast::new(
+                                ToastSeverity::Info,
+                                substitute_bind_keys(msg.as_str(), binds),
+                                Duration::ZERO,
+                            )
+                            .with_icon("\u{f0eb}")
+                            .sticky(),
+                        );
+                    }
+                }
+            }
+
+            // the notification rules are only needed for new chat messages
+            let has_chat_msgs = !game.game_data.chat_msgs.is_empty();
             let mut render_game_input = RenderGameInput {
                 players: game.render_players_pool.new(),
                 dummies: game.game_data.player_ids_pool.new(),
@@ -826,6 +1318,23 @@ impl ClientNativeImpl {
                     chat_msgs.append(&mut game.game_data.chat_msgs);
                     chat_msgs
                 },
+                auto_show_motd,
+                chat_notify: if has_chat_msgs {
+                    self.config
+                        .game
+                        .cl
+                        .chat
+                        .notify_rules
+                        .iter()
+                        .filter(|rule| if is_menu_open { rule.menu } else { rule.ingame })
+                        .map(|rule| ChatNotifyKeyword {
+                            keyword: rule.keyword.clone(),
+                            sound: !(rule.mute_when_focused && self.window_focused),
+                        })
+                        .collect()
+                } else {
+                    Vec::new()
+                },
                 vote: game.game_data.vote.as_ref().map(|(v, voted, timestamp)| {
                     (
                         v.clone(),
@@ -841,21 +1350,66 @@ impl ClientNativeImpl {
                 stages,
                 scoreboard_info: None,
                 date_time: None,
+                race_splits: None,
                 game_time_info: GameTimeInfo {
                     ticks_per_second: main_game.game_tick_speed(),
                     intra_tick_time: game.game_data.intra_tick_time,
                 },
-                settings: RenderGameSettings::new(
-                    &self.config.game.cl.render,
-                    &self.config.game.snd.render,
-                    self.graphics.canvas_handle.pixels_per_point(),
-                    1.0,
-                    self.config.game.cl.anti_ping,
-                    self.config.game.snd.global_volume,
-                ),
+                settings: {
+                    let mut settings = RenderGameSettings::new(
+                        &self.config.game.cl.render,
+                        &self.config.game.snd.render,
+                        self.graphics.canvas_handle.pixels_per_point(),
+                        1.0,
+                        self.config.game.cl.anti_ping,
+                        self.config.game.snd.global_volume
+                            * self.idle.volume(&self.config.game.cl.idle),
+                    );
+                    // skip expensive effects while the player is away
+                    if self.idle.state().is_throttled() {
+                        settings.particle_amount = 0.0;
+                    }
+                    settings
+                },
                 ext: main_game.collect_render_ext(),
             };
 
+            // what the player context menu of the scoreboard needs to know
+            let scoreboard_player_context = {
+                let spatial_chat_config = &self.config.game.cl.spatial_chat;
+                ScoreboardPlayerContext {
+                    local_players: game.game_data.local.local_players.keys().copied().collect(),
+                    friends: self
+                        .config
+                        .storage::<FavoritePlayers>("favorite-players")
+                        .into_iter()
+                        .map(|friend| friend.name.to_string())
+                        .collect(),
+                    chat_muted: self.config.storage("chat-muted-players"),
+                    spatial_chat: self
+                        .spatial_chat
+                        .spatial_chat
+                        .get_entities()
+                        .into_iter()
+                        .map(|(id, entity)| {
+                            let settings = match entity.unique_id {
+                                PlayerUniqueId::Account(account_id) => spatial_chat_config
+                                    .account_players
+                                    .get(&format!("acc_{account_id}")),
+                                PlayerUniqueId::CertFingerprint(hash) => spatial_chat_config
+                                    .account_certs
+                                    .get(&format!("cert_{}", fmt_hash(&hash))),
+                            };
+                            (id, settings.is_some_and(|settings| settings.muted))
+                        })
+                        .collect(),
+                    vote_immune: game.game_data.player_votes_immune.iter().copied().collect(),
+                    vote_kick_blocked: game.game_data.vote.is_some()
+                        || game.game_data.player_votes_reason_required,
+                    spectate_allowed: false,
+                }
+            };
+
             type CharacterInfos = PoolFxLinkedHashMap<CharacterId, CharacterInfo>;
             type StageRenderInfos = PoolFxLinkedHashMap<StageId, StageRenderInfo>;
             let mut fill_for_player = {
@@ -1029,6 +1583,18 @@ impl ClientNativeImpl {
                                 } else {
                                     None
                                 },
+                                scoreboard_input: (client_player.show_scoreboard && !is_menu_open)
+                                    .then(|| ScoreboardInput {
+                                        inp: self.inp_manager.clone_inp().egui,
+                                        player_context: ScoreboardPlayerContext {
+                                            spectate_allowed: is_spectator
+                                                || matches!(
+                                                    local_player_render_info,
+                                                    LocalCharacterRenderInfo::Unavailable
+                                                ),
+                                            ..scoreboard_player_context.clone()
+                                        },
+                                    }),
                                 chat_show_all: client_player.show_chat_all,
                                 scoreboard_active: client_player.show_scoreboard
                                     || force_scoreboard_visible,
@@ -1200,6 +1766,17 @@ impl ClientNativeImpl {
                 });
             }
 
+            if let Some(ghost_viewer) = &mut game.ghost_viewer {
+                ghost_viewer.update(
+                    &self.config.game,
+                    active_local_player_id.as_ref(),
+                    &mut render_game_input,
+                );
+            }
+            if let Some(race_splits) = &mut game.race_splits {
+                race_splits.update(&self.cur_time, &mut render_game_input);
+            }
+
             let res = render.render(&self.config.game.map, &self.cur_time, render_game_input);
 
             // handle results
@@ -1254,6 +1831,9 @@ impl ClientNativeImpl {
                         PlayerFeedbackEvent::EmoteWheel(ev) => {
                             local_player.last_emote_wheel_selection = Some(ev);
                         }
+                        PlayerFeedbackEvent::Stats(ev) => {
+                            self.local_stats.add_event(ev);
+                        }
                         PlayerFeedbackEvent::SpectatorSelection(ev) => match ev {
                             SpectatorSelectionEvent::FreeView => {
                                 let phased = self.config.game.cl.phased_ingame_spectate;
@@ -1311,6 +1891,87 @@ impl ClientNativeImpl {
                                     !self.config.game.cl.phased_ingame_spectate;
                             }
                         },
+                        PlayerFeedbackEvent::Scoreboard(ev) => match ev {
+                            ScoreboardEvent::MuteChat { name, muted } => {
+                                let mut chat_muted: Vec<String> =
+                                    self.config.storage("chat-muted-players");
+                                chat_muted.retain(|n| *n != name);
+                                if muted {
+                                    chat_muted.push(name);
+                                }
+                                self.config.set_storage("chat-muted-players", &chat_muted);
+                            }
+                            ScoreboardEvent::MuteSpatialChat { id, muted } => {
+                                let spatial_chat = &self.spatial_chat.spatial_chat;
+                                if let Some(entity) = spatial_chat.get_entities().get(&id) {
+                                    let settings = &mut self.config.game.cl.spatial_chat;
+                                    let player = match entity.unique_id {
+                                        PlayerUniqueId::Account(account_id) => settings
+                                            .account_players
+                                            .entry(format!("acc_{account_id}")),
+                                        PlayerUniqueId::CertFingerprint(hash) => settings
+                                            .account_certs
+                                            .entry(format!("cert_{}", fmt_hash(&hash))),
+                                    };
+                                    player.or_default().muted = muted;
+                                    spatial_chat.push_entity_event(if muted {
+                                        EntitiesEvent::Mute(id)
+                                    } else {
+                                        EntitiesEvent::Unmute(id)
+                                    });
+                                }
+                            }
+                            ScoreboardEvent::AddFriend(friend) => {
+                                let mut friends: FavoritePlayers =
+                                    self.config.storage("favorite-players");
+                                if !friends.iter().any(|f| f.name == friend.name) {
+                                    friends.push(friend);
+                                    self.config.set_storage("favorite-players", &friends);
+                                }
+                            }
+                            ScoreboardEvent::RemoveFriend { name } => {
+                                let mut friends: FavoritePlayers =
+                                    self.config.storage("favorite-players");
+                                friends.retain(|f| f.name.as_str() != name);
+                                self.config.set_storage("favorite-players", &friends);
+                            }
+                            ScoreboardEvent::Spectate(character_id) => {
+                                let phased = self.config.game.cl.phased_ingame_spectate;
+                                let ids = [character_id].into_iter().collect();
+                                let mode = if phased {
+                                    ClientCameraMode::PhasedFreeCam(ids)
+                                } else {
+                                    ClientCameraMode::FreeCam(ids)
+                                };
+                                game.map.game.client_command(
+                                    &player_id,
+                                    ClientCommand::SetCameraMode(mode.clone()),
+                                );
+                                game.network.send_unordered_to_server(
+                                    &ClientToServerMessage::PlayerMsg((
+                                        player_id,
+                                        ClientToServerPlayerMessage::SwitchToCamera(mode),
+                                    )),
+                                );
+                            }
+                            ScoreboardEvent::VoteKick(voted_player_id) => {
+                                game.network.send_unordered_to_server(
+                                    &ClientToServerMessage::PlayerMsg((
+                                        player_id,
+                                        ClientToServerPlayerMessage::StartVote(
+                                            VoteIdentifierType::VoteKickPlayer(PlayerVoteKey {
+                                                voted_player_id,
+                                                reason: Default::default(),
+                                            }),
+                                        ),
+                                    )),
+                                );
+                            }
+                            ScoreboardEvent::PlatformOutput(output) => {
+                                self.inp_manager
+                                    .handle_platform_output(native, output, false);
+                            }
+                        },
                     }
                 }
             }
@@ -1323,6 +1984,10 @@ impl ClientNativeImpl {
 
     #[instrument(level = "trace", skip_all)]
     fn render(&mut self, native: &mut dyn NativeImpl) {
+        if matches!(self.game, Game::None | Game::Err(_)) {
+            self.revert_server_profile();
+        }
+
         // first unload editor => then reload. else native library doesn't get a reload
         if self.editor.should_reload() {
             let is_open = self.editor.is_open();
@@ -1372,10 +2037,18 @@ impl ClientNativeImpl {
             }
         } else {
             self.render_game(native);
+            self.start_killcam();
 
             // if demo viewer is active, render it
             if let Some(demo_player) = &mut self.demo_player {
                 if let Some(demo_viewer) = demo_player.try_get_mut() {
+                    if let Some(speed) = self
+                        .killcam
+                        .as_mut()
+                        .and_then(|killcam| killcam.speed.take())
+                    {
+                        demo_viewer.set_speed(speed);
+                    }
                     if demo_viewer
                         .render(
                             if self.local_console.ui.ui_state.is_ui_open
@@ -1387,12 +2060,24 @@ impl ClientNativeImpl {
                             },
                             &self.config.game.cl.render,
                             &self.config.game.snd.render,
-                            self.config.game.snd.global_volume,
+                            self.config.game.snd.global_volume
+                                * self.idle.volume(&self.config.game.cl.idle),
                         )
                         .is_err()
                         || demo_viewer.is_closed()
+                        || (self.killcam.is_some() && demo_viewer.is_finished())
                     {
+                        if let Some((file_name, frames)) = demo_viewer.finished_video() {
+                            self.notifications.add_success(
+                                format!(
+                                    "Rendered {frames} frames to {}",
+                                    file_name.to_string_lossy()
+                                ),
+                                Duration::from_secs(10),
+                            );
+                        }
                         self.demo_player = None;
+                        self.killcam = None;
                     }
                 } else if let Err(err) = demo_player.continue_loading(
                     &self.sound,
@@ -1407,6 +2092,7 @@ impl ClientNativeImpl {
                     self.notifications
                         .add_err(err.to_string(), Duration::from_secs(10));
                     self.demo_player = None;
+                    self.killcam = None;
                 }
             } else if self.ui_manager.ui.ui_state.is_ui_open {
                 // fill raw input if ui needs raw input
@@ -1473,6 +2159,7 @@ impl ClientNativeImpl {
                             );
                         }
                         UiEvent::PlayDemo { name } => {
+                            self.killcam = None;
                             self.demo_player = Some(DemoViewer::new(
                                 &self.io,
                                 &self.thread_pool,
@@ -1482,6 +2169,7 @@ impl ClientNativeImpl {
                             ));
                         }
                         UiEvent::EncodeDemoToVideo { name, video_name } => {
+                            self.killcam = None;
                             self.demo_player = Some(DemoViewer::new(
                                 &self.io,
                                 &self.thread_pool,
@@ -1535,6 +2223,7 @@ impl ClientNativeImpl {
                             if let Game::Active(game) = &mut self.game {
                                 match game.replay.to_demo() {
                                     Ok(demo) => {
+                                        self.killcam = None;
                                         self.demo_player = Some(demo);
                                     }
                                     Err(err) => {
@@ -1565,6 +2254,7 @@ impl ClientNativeImpl {
                             cert_hash,
                             can_start_internal_server,
                             can_connect_internal_server,
+                            as_spectator,
                         } => {
                             // if localhost, then get the cert, rcon pw & port from the shared info
                             match self.connect_internal_server(
@@ -1577,7 +2267,7 @@ impl ClientNativeImpl {
                                     server_cert,
                                     rcon_secret,
                                 } => {
-                                    self.connect_game(addr, server_cert, rcon_secret);
+                                    self.connect_game(addr, server_cert, rcon_secret, as_spectator);
                                 }
                                 ConnectLocalServerResult::KeepConnecting { .. } => {
                                     self.ui_events.push(UiEvent::Connect {
@@ -1586,6 +2276,7 @@ impl ClientNativeImpl {
                                         cert_hash,
                                         can_start_internal_server: false,
                                         can_connect_internal_server: true,
+                                        as_spectator,
                                     });
                                 }
                                 ConnectLocalServerResult::ErrOrNotLocalServerAddr { .. } => {
@@ -1594,6 +2285,7 @@ impl ClientNativeImpl {
                                         addr,
                                         ServerCertMode::Hash(cert_hash),
                                         rcon_secret,
+                                        as_spectator,
                                     );
                                 }
                             }
@@ -1971,6 +2663,7 @@ impl ClientNativeImpl {
                                     rcon_secret: Default::default(),
                                     can_start_internal_server: false,
                                     can_connect_internal_server: false,
+                                    as_spectator: false,
                                 });
                                 self.legacy_proxy_thread = Some(legacy_proxy);
                             }
@@ -1990,6 +2683,76 @@ impl ClientNativeImpl {
                                 }
                             }
                         }
+                        UiEvent::ExportServerList { name } => {
+                            let favorites = self
+                                .config
+                                .game
+                                .menu
+                                .favorite_servers
+                                .iter()
+                                .map(|addr| ServerListEntry {
+                                    addr: addr.clone(),
+                                    fingerprint: self.browser_data.find_str(addr).map(|server| {
+                                        fmt_hash(&server.info.cert_sha256_fingerprint)
+                                    }),
+                                    last_connect: None,
+                                })
+                                .collect();
+                            self.server_list_task = Some(export_server_list(
+                                &self.io,
+                                &name,
+                                ServerListExport {
+                                    favorites,
+                                    history: self.config.storage("connect-history"),
+                                },
+                            ));
+                        }
+                        UiEvent::ImportServerList { name } => {
+                            self.server_list_task = Some(import_server_list(&self.io, &name));
+                        }
+                        UiEvent::CertChangeAccepted { accepted } => {
+                            if let Some(ConnectModes::CertMismatch {
+                                addr, presented, ..
+                            }) = self.connecting_log.mode()
+                            {
+                                if accepted {
+                                    self.cert_pins.pin(&addr, &presented);
+                                    self.save_cert_pins();
+                                    self.connect_game(
+                                        addr,
+                                        ServerCertMode::Hash(presented),
+                                        None,
+                                        false,
+                                    );
+                                } else {
+                                    self.game = Game::None;
+                                    self.config.engine.ui.path.route("");
+                                }
+                            }
+                        }
+                        UiEvent::DownloadsAccepted { accepted, remember } => {
+                            if let Game::Connecting(connecting) = &mut self.game {
+                                if remember
+                                    && let Some(fingerprint) =
+                                        connecting.connect.server_fingerprint()
+                                {
+                                    self.download_trust.remember(&fingerprint, accepted);
+                                    let fs = self.io.fs.clone();
+                                    let download_trust = self.download_trust.clone();
+                                    self.io.rt.spawn_without_lifetime(async move {
+                                        download_trust
+                                            .save(&*fs, DOWNLOAD_TRUST_PATH.as_ref())
+                                            .await
+                                    });
+                                }
+                                if accepted {
+                                    connecting.downloads_accepted = true;
+                                } else {
+                                    self.game = Game::None;
+                                    self.config.engine.ui.path.route("");
+                                }
+                            }
+                        }
                     }
                 }
 
@@ -2013,6 +2776,7 @@ impl ClientNativeImpl {
                 render_custom_matches: &|_, _, _, _, _, _| {},
                 skin_container: &mut self.skin_container,
                 render_tee: &self.render_tee,
+                logs: Some(&mut self.log_view),
             };
             let platform_output = self.local_console.render(
                 self.inp_manager.take_inp().egui.unwrap_or_default(),
@@ -2090,6 +2854,7 @@ impl ClientNativeImpl {
                         },
                     skin_container: &mut self.skin_container,
                     render_tee: &self.render_tee,
+                    logs: None,
                 };
             let platform_output = game.remote_console.render(
                 self.inp_manager.take_inp().egui.unwrap_or_default(),
@@ -2126,6 +2891,19 @@ impl ClientNativeImpl {
                             }
                         }
                     }
+                    RemoteConsoleEvent::Login { password } => {
+                        if let Ok(password) = password.as_str().try_into() {
+                            game.network.send_in_order_to_server(
+                                &ClientToServerMessage::RconAuth { password },
+                                NetworkInOrderChannel::Custom(
+                                    7302, // reads as "rcon"
+                                ),
+                            );
+                        } else {
+                            self.notifications
+                                .add_err("rcon password too long.", Duration::from_secs(3));
+                        }
+                    }
                 }
             }
         }
@@ -2133,11 +2911,24 @@ impl ClientNativeImpl {
         // notifications (e.g. error popups)
         self.notifications.render();
 
+        let watches: Vec<_> = self
+            .console_watches
+            .list()
+            .into_iter()
+            .map(|name| {
+                let val = config_var_value(&mut self.config.engine, &mut self.config.game, &name)
+                    .unwrap_or_default();
+                (name, val)
+            })
+            .collect();
+
         // fps (& debug)
         self.client_stats.render(&mut ClientStatsRenderPipe {
             debug_hud: if let Game::Active(game) = &self.game {
                 Some(DebugHudRenderPipe {
                     prediction_timer: &game.game_data.prediction_timer,
+                    interpolation_buffer: &game.game_data.interpolation_buffer,
+                    clock_sync: &game.game_data.clock_sync,
                     byte_stats: &game.game_data.net_byte_stats,
                     ingame_timer: &game.game_data.last_game_tick,
                 })
@@ -2151,21 +2942,47 @@ impl ClientNativeImpl {
             },
             force_bottom: self.ui_manager.ui.ui_state.is_ui_open,
             show_fps: self.config.game.cl.show_fps,
+            watches: &watches,
+            input_timings: if let Game::Active(game) = &self.game {
+                game.input_timings.as_ref()
+            } else {
+                None
+            },
+            frame_pacing: &self.frame_limiter.stats,
         });
 
         self.sound.swap();
         self.graphics.swap();
     }
 
+    fn save_cert_pins(&self) {
+        let fs = self.io.fs.clone();
+        let cert_pins = self.cert_pins.clone();
+        self.io.rt.spawn_without_lifetime(async move {
+            cert_pins.save(&*fs, CERT_PINS_PATH.as_ref()).await
+        });
+    }
+
     fn connect_game(
         &mut self,
         addr: SocketAddr,
         server_cert: ServerCertMode,
         rcon_secret: Option<[u8; 32]>,
+        as_spectator: bool,
     ) {
         self.client_info.set_local_player_count(1);
         self.account_info.fill_account_info(None);
         self.config.engine.ui.path.route("connect");
+        self.revert_server_profile();
+        self.applied_server_profile = AppliedServerProfile::apply(
+            &mut self.config.game,
+            &addr.to_string(),
+            match &server_cert {
+                ServerCertMode::Hash(hash) => Some(fmt_hash(hash)),
+                ServerCertMode::Cert(_) | ServerCertMode::Unknown => None,
+            }
+            .as_deref(),
+        );
         self.connecting_log
             .set_mode(ConnectModes::Connecting { addr });
         self.game = Game::new(
@@ -2184,6 +3001,8 @@ impl ClientNativeImpl {
                 log: self.connecting_log.clone(),
                 server_cert,
                 browser_data: self.browser_data.clone(),
+                as_spectator,
+                security: Default::default(),
             },
             &self.accounts,
             DisconnectAutoCleanup {
@@ -2197,6 +3016,13 @@ impl ClientNativeImpl {
         .unwrap();
     }
 
+    /// Restores the settings that were overridden by the server's profile.
+    fn revert_server_profile(&mut self) {
+        if let Some(profile) = self.applied_server_profile.take() {
+            profile.revert(&mut self.config.game);
+        }
+    }
+
     fn handle_exec(
         io: &IoFileSys,
         file_path: PathBuf,
@@ -2285,7 +3111,7 @@ impl ClientNativeImpl {
                             server_cert,
                             rcon_secret,
                         } => {
-                            self.connect_game(addr, server_cert, rcon_secret);
+                            self.connect_game(addr, server_cert, rcon_secret, false);
                         }
                         ConnectLocalServerResult::KeepConnecting { addresses } => {
                             self.local_console.add_event(LocalConsoleEvent::Connect {
@@ -2307,7 +3133,7 @@ impl ClientNativeImpl {
                                     .then_some(addr)
                                 })
                             {
-                                self.connect_game(*addr, cert, None);
+                                self.connect_game(*addr, cert, None, false);
                             }
                         }
                     }
@@ -2479,6 +3305,33 @@ impl ClientNativeImpl {
                         }
                     }
                 }
+                LocalConsoleEvent::ExportInputTimings => {
+                    if let Game::Active(game) = &self.game
+                        && let Some(input_timings) = &game.input_timings
+                    {
+                        let csv = input_timings.to_csv();
+                        let path = format!(
+                            "input_timings/{}.csv",
+                            chrono::Local::now().format("%Y_%m_%d_%H_%M_%S")
+                        );
+                        let fs = self.io.fs.clone();
+                        let file_path = path.clone();
+                        self.io.rt.spawn_without_lifetime(async move {
+                            fs.create_dir("input_timings".as_ref()).await?;
+                            fs.write_file(file_path.as_ref(), csv.into_bytes()).await?;
+                            Ok(())
+                        });
+                        self.notifications.add_info(
+                            format!("Saved the input timings to {path}"),
+                            Duration::from_secs(5),
+                        );
+                    } else {
+                        self.notifications.add_err(
+                            "No input timings recorded, enable dbg.input_timings while ingame.",
+                            Duration::from_secs(5),
+                        );
+                    }
+                }
                 LocalConsoleEvent::Quit => native.quit(),
                 LocalConsoleEvent::ConfigVariable { name } => {
                     // some special cases
@@ -2596,6 +3449,18 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for GraphicsApp<ClientNative
 
         let font_loading = UiFontDataLoading::new(&io.clone().into());
         let accounts_loading = AccountsLoading::new(&io);
+        let fs = io.fs.clone();
+        let download_trust_task = io.rt.spawn(async move {
+            Ok(DownloadTrust::load(&*fs, DOWNLOAD_TRUST_PATH.as_ref()).await)
+        });
+        let fs = io.fs.clone();
+        let cert_pins_task = io
+            .rt
+            .spawn(async move { Ok(CertPins::load(&*fs, CERT_PINS_PATH.as_ref()).await) });
+        let fs = io.fs.clone();
+        let local_stats_task = io
+            .rt
+            .spawn(async move { LocalStats::load(&*fs, LOCAL_STATS_PATH.as_ref()).await });
         benchmark.bench("loading client files");
 
         let thread_pool = Arc::new(
@@ -2702,11 +3567,9 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for GraphicsApp<ClientNative
         ui_creator.load_font(&font_data);
         benchmark.bench("loading font");
 
-        let mut local_console = loading
-            .local_console_builder
-            .take()
-            .unwrap_or_default()
-            .build(&ui_creator);
+        let local_console_builder = loading.local_console_builder.take().unwrap_or_default();
+        let console_watches = local_console_builder.watches.clone();
+        let mut local_console = local_console_builder.build(&ui_creator);
         benchmark.bench("local console");
 
         // then prepare components allocations etc.
@@ -2717,13 +3580,42 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for GraphicsApp<ClientNative
             BackendWindow::Winit {
                 window: native.borrow_window(),
             },
-        )?;
+        )
+        .inspect_err(|err| {
+            GfxInitFailureMarker::record_failure(
+                &io.clone().into(),
+                loading.config_engine.gfx.safe_mode,
+                &loading.config_engine.gfx.backend,
+                &loading.config_engine.gl.gpu,
+                err,
+            )
+        })?;
+        GfxInitFailureMarker::clear(&io.clone().into());
         benchmark.bench("init of graphics backend");
 
         let window_props = graphics_backend.get_window_props();
         let graphics_backend = GraphicsBackend::new(graphics_backend);
+        let gpu_resources_invalidated: Rc<Cell<bool>> = Default::default();
+        let listener_invalidated = gpu_resources_invalidated.clone();
+        graphics_backend.add_resources_invalidated_listener(Box::new(move |ev| {
+            log::warn!(
+                "the graphics device was recreated ({} time(s)), reloading all resources.",
+                ev.recreations
+            );
+            listener_invalidated.set(true);
+        }));
         let mut graphics = Graphics::new(graphics_backend.clone(), stream_data, window_props);
 
+        if first_time_setup {
+            let preset = ConfigGraphicsPreset::detect(&graphics.backend_handle.gpus().cur);
+            loading
+                .config_game
+                .apply_graphics_preset(&mut loading.config_engine, preset);
+            if !loading.config_engine.gfx.safe_mode {
+                graphics.multi_sampling(loading.config_engine.gl.msaa_samples);
+            }
+        }
+
         benchmark.bench("init of graphics");
 
         let scene = sound.scene_handle.create(Default::default());
@@ -2748,35 +3640,23 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for GraphicsApp<ClientNative
 
         benchmark.bench("init of components");
 
-        let menu_map_path = format!(
-            "themes/{}",
-            loading.config_game.menu.background_map.as_str()
-        );
-        let menu_map = ClientMapLoading::new(
+        let menu_map_ambiance =
+            select_ambiance(loading.config_game.menu.theme_variant, None, local_hour());
+        let menu_map = Self::load_menu_map(
             &sound,
             &graphics,
             &graphics_backend,
             &loading.time,
-            menu_map_path.as_ref(),
-            &"day".try_into().unwrap(),
-            None,
             &io,
             &thread_pool,
-            GameModification::Native,
-            true,
-            &loading.config_engine.dbg,
-            Default::default(),
-            RenderGameCreateOptions {
-                physics_group_name: "vanilla".try_into().unwrap(),
-                resource_http_download_url: None,
-                resource_download_server: None,
-                fonts: font_data.clone(),
-                sound_props: Default::default(),
-                render_mod: RenderModTy::Native,
-                required_resources: Default::default(),
-                client_local_infos: Default::default(),
-            },
-            Default::default(),
+            &loading.config_engine,
+            &loading.config_game,
+            &font_data,
+            menu_map_ambiance,
+        );
+        let menu_map_theme = (
+            loading.config_game.menu.background_map.clone(),
+            menu_map_ambiance,
         );
         benchmark.bench("menu map");
 
@@ -2788,6 +3668,7 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for GraphicsApp<ClientNative
             graphics_memory_usage.buffer_memory_usage,
             graphics_memory_usage.stream_memory_usage,
             graphics_memory_usage.staging_memory_usage,
+            graphics_backend.gpu_timings(),
             &ui_creator,
         );
         let mut notifications = ClientNotifications::new(&graphics, &loading.time, &ui_creator);
@@ -2798,6 +3679,9 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for GraphicsApp<ClientNative
                 Duration::from_secs(5),
             );
         }
+        for warning in &loading.config_warnings {
+            notifications.add_warn(warning.as_str(), Duration::from_secs(10));
+        }
 
         let loading_page = Box::new(LoadingPage::new());
         let page_err = UiWasmManagerErrorPageErr::default();
@@ -2814,6 +3698,9 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for GraphicsApp<ClientNative
         benchmark.bench("steam");
 
         let connecting_log = ConnectingLog::default();
+        let download_trust = download_trust_task.get().unwrap_or_default();
+        let cert_pins = cert_pins_task.get().unwrap_or_default();
+        let local_stats = local_stats_task.get();
         let ui_events = UiEvents::new();
         let client_info = ClientInfo::default();
 
@@ -2911,7 +3798,6 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for GraphicsApp<ClientNative
         benchmark.bench("registering ui paths");
 
         let cur_time = loading.time.now();
-        let last_refresh_rate_time = cur_time;
 
         native.confine_mouse(true);
         benchmark.bench("mouse grab");
@@ -2976,6 +3862,8 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for GraphicsApp<ClientNative
             }),
 
             menu_map,
+            menu_map_theme,
+            menu_map_ambiance,
 
             cur_time,
             time: loading.time,
@@ -2985,6 +3873,7 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for GraphicsApp<ClientNative
             skin_container,
             render_tee,
 
+            gpu_resources_invalidated,
             graphics,
             graphics_backend,
 
@@ -2992,7 +3881,10 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for GraphicsApp<ClientNative
             sound_backend,
             game: Game::None,
             connecting_log,
+            download_trust,
+            cert_pins,
             demo_player: None,
+            killcam: None,
 
             client_stats,
             notifications,
@@ -3000,11 +3892,14 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for GraphicsApp<ClientNative
             thread_pool,
             io,
             config: Config::new(loading.config_game, loading.config_engine),
-            last_refresh_rate_time,
+            force_config: loading.force_config,
+            frame_limiter: FrameLimiter::new(&cur_time),
             editor: Default::default(),
 
             local_console,
             console_logs: Default::default(),
+            log_view: LogView::new(LogBuffer::global().clone()),
+            console_watches,
 
             ui_manager,
             ui_events,
@@ -3032,11 +3927,30 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for GraphicsApp<ClientNative
 
             legacy_proxy_thread: None,
 
+            applied_server_profile: None,
+
+            local_stats: LocalStatsRecorder::new(local_stats, &cur_time),
+
+            window_focused: true,
+            idle: IdleThrottle::new(&cur_time),
+            motd_shown_servers: Default::default(),
+            server_list_task: None,
+            lan_listener: match UdpLanTransport::listener(LAN_DISCOVERY_PORT) {
+                Ok(transport) => Some(LanListener::new(transport)),
+                Err(err) => {
+                    log::warn!("failed to listen for servers in the local network: {err}");
+                    None
+                }
+            },
+
             // pools & helpers
             string_pool: Pool::with_sized(256, || String::with_capacity(256)), // TODO: random values rn
         });
 
         client.handle_console_events(native);
+        if first_time_setup {
+            launch_setup_wizard(&mut client.config);
+        }
         benchmark.bench("finish init of client");
 
         Ok(client)
@@ -3048,16 +3962,32 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for GraphicsApp<ClientNative
     ) -> anyhow::Result<()> {
         let map_pipe = MapPipeline::new_boxed();
 
+        // the safe mode only overwrites the configs for the backend,
+        // so the user's settings are kept for the next full launch
+        let (config_dbg, config_gl) = if loading.config_engine.gfx.safe_mode {
+            safe_mode_config(&loading.config_engine.dbg, &loading.config_engine.gl)
+        } else {
+            (loading.config_engine.dbg, loading.config_engine.gl.clone())
+        };
         let graphics_backend_loading = GraphicsBackendLoading::new(
             &loading.config_engine.gfx,
-            &loading.config_engine.dbg,
-            &loading.config_engine.gl,
+            &config_dbg,
+            &config_gl,
             graphics_backend::window::BackendRawDisplayHandle::Winit {
                 handle: display_handle,
             },
             Some(Arc::new(parking_lot::RwLock::new(vec![map_pipe]))),
             loading.io.clone(),
-        )?;
+        )
+        .inspect_err(|err| {
+            GfxInitFailureMarker::record_failure(
+                &loading.io,
+                loading.config_engine.gfx.safe_mode,
+                &loading.config_engine.gfx.backend,
+                &loading.config_engine.gl.gpu,
+                err,
+            )
+        })?;
         loading.graphics_backend_loading = Some(graphics_backend_loading);
         Ok(())
     }
@@ -3070,6 +4000,8 @@ impl InputEventHandler for ClientNativeImpl {
         device: &native::native::DeviceId,
         key: PhysicalKey,
     ) {
+        self.idle.on_input(&self.time.now());
+        self.on_os_input_event();
         self.inp_manager.key_down(window, device, &key)
     }
 
@@ -3087,6 +4019,8 @@ impl InputEventHandler for ClientNativeImpl {
         if key == PhysicalKey::Code(KeyCode::Pause) {
             stats_report();
         }
+        self.idle.on_input(&self.time.now());
+        self.on_os_input_event();
         self.inp_manager.key_up(window, device, &key)
     }
 
@@ -3098,6 +4032,8 @@ impl InputEventHandler for ClientNativeImpl {
         y: f64,
         btn: &native::native::MouseButton,
     ) {
+        self.idle.on_input(&self.time.now());
+        self.on_os_input_event();
         self.inp_manager.mouse_down(window, device, x, y, btn)
     }
 
@@ -3109,6 +4045,8 @@ impl InputEventHandler for ClientNativeImpl {
         y: f64,
         btn: &native::native::MouseButton,
     ) {
+        self.idle.on_input(&self.time.now());
+        self.on_os_input_event();
         self.inp_manager.mouse_up(window, device, x, y, btn)
     }
 
@@ -3121,6 +4059,7 @@ impl InputEventHandler for ClientNativeImpl {
         xrel: f64,
         yrel: f64,
     ) {
+        self.idle.on_input(&self.time.now());
         self.inp_manager
             .mouse_move(window, device, x, y, xrel, yrel)
     }
@@ -3133,6 +4072,8 @@ impl InputEventHandler for ClientNativeImpl {
         y: f64,
         delta: &native::native::MouseScrollDelta,
     ) {
+        self.idle.on_input(&self.time.now());
+        self.on_os_input_event();
         self.inp_manager.scroll(window, device, x, y, delta)
     }
 
@@ -3154,7 +4095,27 @@ impl AppWithGraphics for ClientNativeImpl {
 
     #[instrument(level = "trace", skip_all)]
     fn run(&mut self, native: &mut dyn NativeImpl) {
+        // the native event loop already waited for most of the time
+        // until the next frame, collecting the os events meanwhile
+        self.frame_limiter
+            .update_config(&self.config.game.cl.frame_pacing);
+        self.frame_limiter.wait(&self.time);
+
+        if self.gpu_resources_invalidated.take() {
+            self.reload_after_gpu_reset();
+        }
+
         self.inp_manager.collect_events();
+        if let Some(input) = self.inp_manager.egui_input_mut() {
+            self.notifications.consume_input(input);
+        }
+
+        // never throttle during encoding
+        let never_throttle = self.demo_player.as_ref().is_some_and(|p| p.is_encoder())
+            || (self.config.game.cl.idle.never_throttle_ingame
+                && matches!(self.game, Game::Active(_)));
+        self.idle
+            .update(&self.config.game.cl.idle, &self.time.now(), never_throttle);
 
         let mut open_editor = false;
         self.inp_manager.handle_global_binds(
@@ -3206,15 +4167,22 @@ impl AppWithGraphics for ClientNativeImpl {
                     .log("Legacy proxy was shutdown gracefully.");
             }
         }
+        self.update_server_list_task();
+        self.update_lan_servers();
         self.game.update(
             &self.config.engine,
             &mut self.config.game,
             &self.ui_creator,
             &mut self.notifications,
             &self.local_console.entries,
+            &mut self.cert_pins,
             &self.cur_time,
         );
+        if self.cert_pins.take_changed() {
+            self.save_cert_pins();
+        }
 
+        let was_connecting = matches!(self.game, Game::Connecting(_));
         GameEventsClient::update(&mut GameEventPipeline {
             game: &mut self.game,
             msgs: &mut GameMsgPipeline {
@@ -3231,15 +4199,52 @@ impl AppWithGraphics for ClientNativeImpl {
                 account_info: &self.account_info,
                 spatial_chat: &mut self.spatial_chat,
                 notifications: &mut self.notifications,
+                download_trust: &self.download_trust,
             },
             game_server_info: &self.game_server_info,
             spatial_chat_scene: &self.scene,
         });
+        // successful connects are remembered in the history,
+        // except for the local server & the legacy proxy
+        if was_connecting
+            && let Game::Loading(game) = &self.game
+            && !game.connect.addr.ip().is_loopback()
+        {
+            let mut history = self.config.storage::<ConnectHistory>("connect-history");
+            add_connect(
+                &mut history,
+                ServerListEntry {
+                    addr: game.connect.addr.to_string(),
+                    fingerprint: game
+                        .connect
+                        .server_fingerprint()
+                        .map(|fingerprint| fmt_hash(&fingerprint)),
+                    last_connect: Some(
+                        SystemTime::now()
+                            .duration_since(SystemTime::UNIX_EPOCH)
+                            .unwrap_or_default()
+                            .as_secs(),
+                    ),
+                },
+            );
+            self.config.set_storage("connect-history", &history);
+        }
+        self.local_stats.update(
+            &self.io,
+            match &self.game {
+                Game::Active(game) => Some(&game.connect.addr),
+                _ => None,
+            },
+            &self.browser_data,
+            &self.cur_time,
+        );
         let has_input = !self.ui_manager.ui.ui_state.is_ui_open
             && !self.local_console.ui.ui_state.is_ui_open
             && !self.game.remote_console_open()
             && !self.editor.is_open()
             && self.demo_player.is_none();
+        // the toasts can be clicked while the ui is open
+        let toast_actions = self.notifications.take_activated_actions();
         if let Game::Active(game) = &mut self.game {
             // check loading of votes
             if self.votes.needs_map_votes() {
@@ -3269,83 +4274,106 @@ impl AppWithGraphics for ClientNativeImpl {
                 self.votes
                     .fill_misc_votes(game.game_data.misc_votes.clone());
             }
+            if self.votes.needs_player_votes() {
+                // the immune players change with the players on the server
+                if game.player_votes_requested.is_none_or(|requested| {
+                    self.cur_time.saturating_sub(requested) >= Duration::from_secs(1)
+                }) {
+                    game.player_votes_requested = Some(self.cur_time);
+                    game.network
+                        .send_unordered_to_server(&ClientToServerMessage::LoadVotes(
+                            MsgClLoadVotes::Players,
+                        ));
+                }
+                self.votes.fill_player_votes(
+                    game.game_data.local.local_players.keys().copied().collect(),
+                    game.game_data.player_votes_immune.iter().copied().collect(),
+                    game.game_data.player_votes_reason_required,
+                );
+            }
+            self.votes.set_vote_active(game.game_data.vote.is_some());
 
-            if has_input {
-                let evs = self.inp_manager.handle_player_binds(
+            let toast_evs = toast_actions.into_iter().filter_map(|action| {
+                (action == TOAST_ACTION_VOTE_YES).then_some(InputHandlingEvent::VoteYes)
+            });
+            let evs = if has_input {
+                self.inp_manager.handle_player_binds(
                     &mut game.game_data,
                     &mut self.ui_manager.ui,
                     &mut self.config.engine,
                     &mut self.config.game,
                     &self.graphics,
                     &self.local_console.entries,
-                );
+                )
+            } else {
+                Vec::new()
+            };
 
-                let player_id = game
-                    .game_data
-                    .local
-                    .active_local_player()
-                    .map(|(id, _)| *id);
+            let player_id = game
+                .game_data
+                .local
+                .active_local_player()
+                .map(|(id, _)| *id);
 
-                for ev in evs {
-                    match ev {
-                        InputHandlingEvent::Kill { local_player_id } => game
-                            .network
-                            .send_unordered_to_server(&ClientToServerMessage::PlayerMsg((
-                                local_player_id,
-                                ClientToServerPlayerMessage::Kill,
-                            ))),
-                        InputHandlingEvent::VoteYes | InputHandlingEvent::VoteNo => {
-                            if let Some(player_id) = player_id {
-                                let voted = if matches!(ev, InputHandlingEvent::VoteYes) {
-                                    Voted::Yes
-                                } else {
-                                    Voted::No
-                                };
-                                if let Some((_, cur_voted, _)) = &mut game.game_data.vote {
-                                    *cur_voted = Some(voted);
-                                    game.network.send_unordered_to_server(
-                                        &ClientToServerMessage::PlayerMsg((
-                                            player_id,
-                                            ClientToServerPlayerMessage::Voted(voted),
-                                        )),
-                                    );
-                                }
-                            }
-                        }
-                        InputHandlingEvent::Emoticon {
+            for ev in evs.into_iter().chain(toast_evs) {
+                match ev {
+                    InputHandlingEvent::Kill { local_player_id } => game
+                        .network
+                        .send_unordered_to_server(&ClientToServerMessage::PlayerMsg((
                             local_player_id,
-                            emoticon,
-                        } => {
-                            game.map
-                                .game
-                                .set_player_emoticon(&local_player_id, emoticon);
-                            game.network.send_unordered_to_server(
-                                &ClientToServerMessage::PlayerMsg((
-                                    local_player_id,
-                                    ClientToServerPlayerMessage::Emoticon(emoticon),
-                                )),
-                            );
-                        }
-                        InputHandlingEvent::ChangeEyes {
-                            local_player_id,
-                            eye,
-                        } => {
-                            game.map
-                                .game
-                                .set_player_eye(&local_player_id, eye, Duration::MAX);
-                            game.network.send_unordered_to_server(
-                                &ClientToServerMessage::PlayerMsg((
-                                    local_player_id,
-                                    ClientToServerPlayerMessage::ChangeEyes {
-                                        eye,
-                                        duration: Duration::MAX,
-                                    },
-                                )),
-                            );
+                            ClientToServerPlayerMessage::Kill,
+                        ))),
+                    InputHandlingEvent::VoteYes | InputHandlingEvent::VoteNo => {
+                        if let Some(player_id) = player_id {
+                            let voted = if matches!(ev, InputHandlingEvent::VoteYes) {
+                                Voted::Yes
+                            } else {
+                                Voted::No
+                            };
+                            if let Some((_, cur_voted, _)) = &mut game.game_data.vote {
+                                *cur_voted = Some(voted);
+                                game.network.send_unordered_to_server(
+                                    &ClientToServerMessage::PlayerMsg((
+                                        player_id,
+                                        ClientToServerPlayerMessage::Voted(voted),
+                                    )),
+                                );
+                            }
                         }
                     }
+                    InputHandlingEvent::Emoticon {
+                        local_player_id,
+                        emoticon,
+                    } => {
+                        game.map
+                            .game
+                            .set_player_emoticon(&local_player_id, emoticon);
+                        game.network
+                            .send_unordered_to_server(&ClientToServerMessage::PlayerMsg((
+                                local_player_id,
+                                ClientToServerPlayerMessage::Emoticon(emoticon),
+                            )));
+                    }
+                    InputHandlingEvent::ChangeEyes {
+                        local_player_id,
+                        eye,
+                    } => {
+                        game.map
+                            .game
+                            .set_player_eye(&local_player_id, eye, Duration::MAX);
+                        game.network
+                            .send_unordered_to_server(&ClientToServerMessage::PlayerMsg((
+                                local_player_id,
+                                ClientToServerPlayerMessage::ChangeEyes {
+                                    eye,
+                                    duration: Duration::MAX,
+                                },
+                            )));
+                    }
                 }
+            }
 
+            if has_input {
                 let player = game.game_data.local.active_local_player();
                 let needs_abs_cursor = player
                     .and_then(|(id, client_player)| {
@@ -3355,16 +4383,18 @@ impl AppWithGraphics for ClientNativeImpl {
                             .and_then(|c| c.player_info.as_ref().map(|p| (client_player, p)))
                     })
                     .is_some_and(|(client_player, p)| {
-                        client_player.spectator_selection_active
-                            && (game.map.game.info.options.has_ingame_freecam
-                                || match p.cam_mode {
-                                    PlayerCameraMode::Default => false,
-                                    PlayerCameraMode::Free => true,
-                                    PlayerCameraMode::LockedTo { locked_ingame, .. }
-                                    | PlayerCameraMode::LockedOn { locked_ingame, .. } => {
-                                        !locked_ingame
-                                    }
-                                })
+                        // the scoreboard allows actions on the players
+                        client_player.show_scoreboard
+                            || (client_player.spectator_selection_active
+                                && (game.map.game.info.options.has_ingame_freecam
+                                    || match p.cam_mode {
+                                        PlayerCameraMode::Default => false,
+                                        PlayerCameraMode::Free => true,
+                                        PlayerCameraMode::LockedTo { locked_ingame, .. }
+                                        | PlayerCameraMode::LockedOn { locked_ingame, .. } => {
+                                            !locked_ingame
+                                        }
+                                    }))
                     });
                 native.relative_mouse(!needs_abs_cursor);
 
@@ -3406,10 +4436,35 @@ impl AppWithGraphics for ClientNativeImpl {
                 &mut player_inputs,
                 &game.player_inputs_chainable_pool,
                 game.send_input_every_tick,
+                Duration::from_millis(self.config.game.cl.dummy.copy_moves_delay),
             );
 
+            let input_timings = self.config.game.dbg.input_timings;
+            if game.input_timings_requested != input_timings
+                && !game.game_data.local.local_players.is_empty()
+            {
+                game.input_timings_requested = input_timings;
+                game.input_timings = input_timings.then(Default::default);
+                game.network
+                    .send_unordered_to_server(&ClientToServerMessage::InputTimings {
+                        enabled: input_timings,
+                    });
+            }
             game.send_input(&player_inputs, time);
             let game_state = &mut game.map.game;
+            let active_player_id = game
+                .game_data
+                .local
+                .active_local_player()
+                .map(|(&id, _)| id);
+            let predicted_at = time.now();
+            let mut on_predicted = |id: &PlayerId, tick_inp: &PlayerInput| {
+                if let Some(input_timings) = &mut game.input_timings
+                    && Some(*id) == active_player_id
+                {
+                    input_timings.on_predicted(tick_inp.version(), predicted_at);
+                }
+            };
             // save the current input of all users for possible recalculations later
             let tick_inps = &mut game.game_data.input_per_tick;
 
@@ -3489,6 +4544,7 @@ impl AppWithGraphics for ClientNativeImpl {
                     tick_inps,
                     false,
                     |id, tick_inp, diff| {
+                        on_predicted(id, tick_inp);
                         inps.insert(
                             *id,
                             CharacterInputInfo {
@@ -3560,6 +4616,7 @@ impl AppWithGraphics for ClientNativeImpl {
                     tick_inps,
                     true,
                     |id, tick_inp, diff| {
+                        on_predicted(id, tick_inp);
                         pred_inps.insert(
                             *id,
                             CharacterInputInfo {
@@ -3613,16 +4670,21 @@ impl AppWithGraphics for ClientNativeImpl {
             &self.config.game,
         );
 
-        // sleep time related stuff
+        // frame pacing related stuff
         let cur_time = self.time.now();
 
+        let limiter_refresh_rate = limiter_refresh_rate(
+            self.config.game.cl.frame_pacing.limiter,
+            self.config.game.cl.refresh_rate,
+            self.config.engine.wnd.refresh_rate_mhz,
+        );
         // force limit fps in menus
         let refresh_rate = if self.ui_manager.ui.ui_state.is_ui_open && self.demo_player.is_none() {
             (self.config.engine.wnd.refresh_rate_mhz as u64)
                 .div_ceil(1000)
                 .clamp(60, u64::MAX)
-                .min(if self.config.game.cl.refresh_rate > 0 {
-                    self.config.game.cl.refresh_rate
+                .min(if limiter_refresh_rate > 0 {
+                    limiter_refresh_rate
                 } else {
                     u64::MAX
                 })
@@ -3631,31 +4693,22 @@ impl AppWithGraphics for ClientNativeImpl {
             if self.demo_player.as_ref().is_some_and(|p| p.is_encoder()) {
                 0
             } else {
-                self.config.game.cl.refresh_rate
+                limiter_refresh_rate
             }
         };
-        if refresh_rate > 0 {
-            let time_until_tick_nanos = Duration::from_secs(1).as_nanos() as u64 / refresh_rate;
-
-            let sleep_time_nanos = time_until_tick_nanos as i64
-                - (cur_time.as_nanos() as i64 - self.last_refresh_rate_time.as_nanos() as i64);
-            if sleep_time_nanos > 0 {
-                std::thread::sleep(Duration::from_nanos(sleep_time_nanos as u64));
-            }
-
-            self.last_refresh_rate_time = Duration::from_nanos(
-                // clamp to half of 60 FPS frame time
-                (cur_time.as_nanos() as i64
-                    + sleep_time_nanos.clamp(-16666666666 / 2, 16666666666 / 2))
-                    as u64,
-            );
-        } else {
-            self.last_refresh_rate_time = cur_time;
-        }
+        let refresh_rate = self
+            .idle
+            .refresh_rate(&self.config.game.cl.idle, refresh_rate);
+        self.frame_limiter
+            .frame_finished(&cur_time, idle::frame_interval(refresh_rate));
 
         self.inp_manager.new_frame();
     }
 
+    fn frame_wait(&mut self) -> Option<Duration> {
+        self.frame_limiter.coarse_wait(&self.time.now())
+    }
+
     fn destroy(mut self) {
         #[cfg(feature = "alloc_track")]
         track_report();
@@ -3664,12 +4717,25 @@ impl AppWithGraphics for ClientNativeImpl {
             self.config.engine.ui.path = Default::default();
         }
 
+        self.revert_server_profile();
+        self.local_stats.save_blocking(&self.io, &self.time.now());
+
         // destroy everything
-        config_fs::save(&self.config.engine, &self.io.clone().into());
-        game_config_fs::fs::save(&self.config.game, &self.io.clone().into());
+        config_fs::save(
+            &self.config.engine,
+            &self.io.clone().into(),
+            self.force_config,
+        );
+        game_config_fs::fs::save(
+            &self.config.game,
+            &self.io.clone().into(),
+            self.force_config,
+        );
     }
 
-    fn focus_changed(&mut self, _focused: bool) {
+    fn focus_changed(&mut self, focused: bool) {
+        self.window_focused = focused;
+        self.idle.focus_changed(focused, &self.time.now());
         // global binds don't allow keeping keys by tabbing out
         self.global_binds.reset_cur_keys();
     }
@@ -0,0 +1,459 @@
+//! Paces the frames of the client.
+//!
+//! After a frame the limiter plans when the next one is due.
+//! The time until then is spent waiting for os events in the
+//! native event loop, so input is collected as it arrives, and the
+//! last part is spent busy waiting in [`FrameLimiter::wait`] for exact
+//! frame times.
+
+use std::{collections::VecDeque, time::Duration};
+
+use base::steady_clock::SteadyClock;
+use game_config::config::{ConfigFrameLimiter, ConfigFramePacing};
+
+/// The clock the limiter waits on, simulated in tests.
+pub trait FrameClock {
+    fn now(&self) -> Duration;
+    /// A coarse sleep that might oversleep.
+    fn sleep(&self, duration: Duration);
+    /// A single iteration of busy waiting.
+    fn spin(&self);
+}
+
+impl FrameClock for SteadyClock {
+    fn now(&self) -> Duration {
+        SteadyClock::now(self)
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+
+    fn spin(&self) {
+        std::hint::spin_loop();
+    }
+}
+
+/// The render rate the limiter targets, `0` means unlimited.
+///
+/// `refresh_rate` is the user's cap, `monitor_refresh_rate_mhz`
+/// is `0` if the refresh rate of the monitor is unknown.
+pub fn limiter_refresh_rate(
+    limiter: ConfigFrameLimiter,
+    refresh_rate: u64,
+    monitor_refresh_rate_mhz: u32,
+) -> u64 {
+    match limiter {
+        ConfigFrameLimiter::Vsync if monitor_refresh_rate_mhz > 0 => {
+            (monitor_refresh_rate_mhz as u64).div_ceil(1000)
+        }
+        ConfigFrameLimiter::Vsync | ConfigFrameLimiter::Cap => refresh_rate,
+        ConfigFrameLimiter::Uncapped => 0,
+    }
+}
+
+/// The timings of a single frame.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FrameTiming {
+    /// From the start of the previous frame to the start of this one.
+    pub frame: Duration,
+    /// From the start of the previous frame until its work was done.
+    pub work: Duration,
+    /// Waiting for os events or sleeping before this frame.
+    pub sleep: Duration,
+    /// Busy waiting before this frame.
+    pub spin: Duration,
+    /// How much later than planned this frame started.
+    pub late: Duration,
+}
+
+/// The timings of the last frames, for the debug hud.
+#[derive(Debug, Default)]
+pub struct FramePacingStats {
+    frames: VecDeque<FrameTiming>,
+}
+
+impl FramePacingStats {
+    const MAX_FRAMES: usize = 120;
+
+    fn push(&mut self, timing: FrameTiming) {
+        if self.frames.len() >= Self::MAX_FRAMES {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(timing);
+    }
+
+    /// The average of all timings.
+    pub fn average(&self) -> FrameTiming {
+        let count = self.frames.len().max(1) as u32;
+        let sum = self
+            .frames
+            .iter()
+            .fold(FrameTiming::default(), |sum, timing| FrameTiming {
+                frame: sum.frame + timing.frame,
+                work: sum.work + timing.work,
+                sleep: sum.sleep + timing.sleep,
+                spin: sum.spin + timing.spin,
+                late: sum.late + timing.late,
+            });
+        FrameTiming {
+            frame: sum.frame / count,
+            work: sum.work / count,
+            sleep: sum.sleep / count,
+            spin: sum.spin / count,
+            late: sum.late / count,
+        }
+    }
+
+    /// The longest frame time.
+    pub fn max_frame_time(&self) -> Duration {
+        self.frames
+            .iter()
+            .map(|timing| timing.frame)
+            .max()
+            .unwrap_or_default()
+    }
+
+    pub fn fps(&self) -> f64 {
+        let frame = self.average().frame;
+        if frame.is_zero() {
+            0.0
+        } else {
+            1.0 / frame.as_secs_f64()
+        }
+    }
+}
+
+/// Plans & waits for the next frame, sleeping the coarse part
+/// of the wait & spinning the rest.
+#[derive(Debug)]
+pub struct FrameLimiter {
+    /// When the work of the current frame started.
+    frame_start: Duration,
+    /// When the work of the last frame was done.
+    frame_end: Duration,
+    /// When the next frame is due.
+    next_frame: Duration,
+    /// The smoothed time a frame needs for its work.
+    work_estimate: Duration,
+    /// The work time of the last frame.
+    last_work: Duration,
+
+    spin: Duration,
+    reduce_latency: bool,
+
+    pub stats: FramePacingStats,
+}
+
+impl FrameLimiter {
+    pub fn new(cur_time: &Duration) -> Self {
+        Self {
+            frame_start: *cur_time,
+            frame_end: *cur_time,
+            next_frame: *cur_time,
+            work_estimate: Duration::ZERO,
+            last_work: Duration::ZERO,
+
+            spin: Duration::from_millis(1),
+            reduce_latency: false,
+
+            stats: Default::default(),
+        }
+    }
+
+    pub fn update_config(&mut self, config: &ConfigFramePacing) {
+        self.spin = Duration::from_micros(config.spin_micros);
+        self.reduce_latency = config.reduce_latency;
+    }
+
+    /// When the work of the next frame should start.
+    ///
+    /// In the reduce latency mode the work starts just early enough
+    /// to be done when the frame is due.
+    fn wait_target(&self) -> Duration {
+        if self.reduce_latency {
+            self.next_frame
+                .saturating_sub(self.work_estimate)
+                .max(self.frame_end)
+        } else {
+            self.next_frame
+        }
+    }
+
+    /// Should be called when the work of a frame is done,
+    /// plans the next frame.
+    ///
+    /// `interval` is `None` if the frame rate is unlimited.
+    pub fn frame_finished(&mut self, cur_time: &Duration, interval: Option<Duration>) {
+        let work = cur_time.saturating_sub(self.frame_start);
+        self.last_work = work;
+        self.frame_end = *cur_time;
+        // rise fast & decay slowly, a too small estimate misses the frame
+        self.work_estimate = if work >= self.work_estimate {
+            work
+        } else {
+            (self.work_estimate * 15 + work) / 16
+        };
+
+        self.next_frame = match interval {
+            Some(interval) => {
+                let next_frame = self.next_frame + interval;
+                // never try to catch up missed frames
+                if next_frame < *cur_time {
+                    *cur_time
+                } else {
+                    next_frame
+                }
+            }
+            None => *cur_time,
+        };
+    }
+
+    /// How long the native event loop can wait for os events,
+    /// before the next frame must start to spin the rest of the wait.
+    pub fn coarse_wait(&self, cur_time: &Duration) -> Option<Duration> {
+        let remaining = self.wait_target().saturating_sub(*cur_time);
+        (remaining > self.spin).then(|| remaining - self.spin)
+    }
+
+    /// Waits until the work of the next frame should start.
+    ///
+    /// If the native event loop woke up too early, the
+    /// coarse part is slept here, the rest is spun.
+    pub fn wait(&mut self, clock: &impl FrameClock) {
+        let target = self.wait_target();
+        let mut now = clock.now();
+        if let Some(sleep) = self.coarse_wait(&now) {
+            clock.sleep(sleep);
+            now = clock.now();
+        }
+        let spin_start = now;
+        while now < target {
+            clock.spin();
+            now = clock.now();
+        }
+
+        self.stats.push(FrameTiming {
+            frame: now.saturating_sub(self.frame_start),
+            work: self.last_work,
+            sleep: spin_start.saturating_sub(self.frame_end),
+            spin: now.saturating_sub(spin_start),
+            late: now.saturating_sub(target),
+        });
+        self.frame_start = now;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::Cell, time::Duration};
+
+    use game_config::config::{ConfigFrameLimiter, ConfigFramePacing};
+
+    use super::{FrameClock, FrameLimiter, limiter_refresh_rate};
+
+    /// Every sleep oversleeps by `oversleep`,
+    /// every spin iteration takes `spin_step`.
+    struct SimClock {
+        now: Cell<Duration>,
+        oversleep: Duration,
+        spin_step: Duration,
+        spins: Cell<u32>,
+    }
+
+    impl SimClock {
+        fn new(oversleep: Duration) -> Self {
+            Self {
+                now: Cell::new(Duration::ZERO),
+                oversleep,
+                spin_step: Duration::from_micros(10),
+                spins: Cell::new(0),
+            }
+        }
+
+        fn advance(&self, by: Duration) {
+            self.now.set(self.now.get() + by);
+        }
+    }
+
+    impl FrameClock for SimClock {
+        fn now(&self) -> Duration {
+            self.now.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.advance(duration + self.oversleep);
+        }
+
+        fn spin(&self) {
+            self.spins.set(self.spins.get() + 1);
+            self.advance(self.spin_step);
+        }
+    }
+
+    /// Runs `frames` frames with the given work each and
+    /// returns the start times of the frames.
+    fn run_frames(
+        limiter: &mut FrameLimiter,
+        clock: &SimClock,
+        interval: Option<Duration>,
+        work: Duration,
+        frames: usize,
+    ) -> Vec<Duration> {
+        (0..frames)
+            .map(|_| {
+                limiter.wait(clock);
+                let start = clock.now();
+                clock.advance(work);
+                limiter.frame_finished(&clock.now(), interval);
+                start
+            })
+            .collect()
+    }
+
+    #[test]
+    fn refresh_rates() {
+        assert_eq!(
+            limiter_refresh_rate(ConfigFrameLimiter::Cap, 144, 60000),
+            144
+        );
+        assert_eq!(limiter_refresh_rate(ConfigFrameLimiter::Cap, 0, 60000), 0);
+        assert_eq!(
+            limiter_refresh_rate(ConfigFrameLimiter::Vsync, 144, 239760),
+            240
+        );
+        // unknown monitor rates fall back to the cap
+        assert_eq!(limiter_refresh_rate(ConfigFrameLimiter::Vsync, 144, 0), 144);
+        assert_eq!(
+            limiter_refresh_rate(ConfigFrameLimiter::Uncapped, 144, 60000),
+            0
+        );
+    }
+
+    #[test]
+    fn exact_frame_times_despite_oversleeping() {
+        // sleeping oversleeps by up to 500us, less than the spin margin
+        let clock = SimClock::new(Duration::from_micros(500));
+        let mut limiter = FrameLimiter::new(&clock.now());
+        let interval = Duration::from_micros(4000);
+        let starts = run_frames(
+            &mut limiter,
+            &clock,
+            Some(interval),
+            Duration::from_micros(1000),
+            100,
+        );
+
+        for (prev, next) in starts.iter().zip(starts.iter().skip(1)) {
+            let frame = *next - *prev;
+            assert!(
+                frame >= interval && frame <= interval + clock.spin_step,
+                "{frame:?}"
+            );
+        }
+        assert!(clock.spins.get() > 0);
+        let average = limiter.stats.average();
+        assert!(average.late <= clock.spin_step, "{average:?}");
+        // the first frame has no previous frame
+        assert!((limiter.stats.fps() - 250.0).abs() < 5.0);
+    }
+
+    #[test]
+    fn uncapped_never_waits() {
+        let clock = SimClock::new(Duration::from_millis(1));
+        let mut limiter = FrameLimiter::new(&clock.now());
+        run_frames(&mut limiter, &clock, None, Duration::from_micros(300), 10);
+
+        assert_eq!(clock.spins.get(), 0);
+        assert_eq!(clock.now(), Duration::from_micros(300) * 10);
+        assert_eq!(limiter.coarse_wait(&clock.now()), None);
+    }
+
+    #[test]
+    fn slow_frames_dont_catch_up() {
+        let clock = SimClock::new(Duration::ZERO);
+        let mut limiter = FrameLimiter::new(&clock.now());
+        let interval = Duration::from_millis(4);
+        run_frames(
+            &mut limiter,
+            &clock,
+            Some(interval),
+            Duration::from_millis(1),
+            5,
+        );
+
+        // a single slow frame
+        run_frames(
+            &mut limiter,
+            &clock,
+            Some(interval),
+            Duration::from_millis(20),
+            1,
+        );
+        let starts = run_frames(
+            &mut limiter,
+            &clock,
+            Some(interval),
+            Duration::from_millis(1),
+            3,
+        );
+        assert_eq!(starts[1] - starts[0], interval);
+        assert_eq!(starts[2] - starts[1], interval);
+        assert!(limiter.stats.max_frame_time() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn reduce_latency_starts_late() {
+        let interval = Duration::from_millis(4);
+        let work = Duration::from_millis(1);
+        let present_delay = |reduce_latency: bool| {
+            let clock = SimClock::new(Duration::ZERO);
+            let mut limiter = FrameLimiter::new(&clock.now());
+            limiter.update_config(&ConfigFramePacing {
+                reduce_latency,
+                ..Default::default()
+            });
+            let starts = run_frames(&mut limiter, &clock, Some(interval), work, 20);
+            // the time from input sampling to the frame being due
+            let start = *starts.last().unwrap();
+            let due = limiter.next_frame - interval;
+            (due.saturating_sub(start), starts)
+        };
+
+        let (delay, _) = present_delay(false);
+        assert_eq!(delay, Duration::ZERO);
+        let (delay, starts) = present_delay(true);
+        // the work starts a frame's work before the frame is due
+        assert_eq!(delay, work);
+        // while keeping the frame rate
+        assert_eq!(starts[19] - starts[18], interval);
+    }
+
+    #[test]
+    fn native_wait_leaves_spinning() {
+        let clock = SimClock::new(Duration::ZERO);
+        let mut limiter = FrameLimiter::new(&clock.now());
+        limiter.update_config(&ConfigFramePacing {
+            spin_micros: 1000,
+            ..Default::default()
+        });
+        run_frames(
+            &mut limiter,
+            &clock,
+            Some(Duration::from_millis(4)),
+            Duration::from_millis(1),
+            1,
+        );
+        assert_eq!(
+            limiter.coarse_wait(&clock.now()),
+            Some(Duration::from_millis(2))
+        );
+        // the native event loop waited exactly that long
+        clock.advance(Duration::from_millis(2));
+        limiter.wait(&clock);
+        assert_eq!(clock.now(), Duration::from_millis(4));
+        let last = *limiter.stats.frames.back().unwrap();
+        assert_eq!(last.sleep, Duration::from_millis(2));
+        assert_eq!(last.spin, Duration::from_millis(1));
+    }
+}
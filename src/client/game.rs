@@ -19,13 +19,22 @@ use base::{
 use base_io::{io::Io, runtime::IoRuntimeTask};
 use client_accounts::accounts::Accounts;
 use client_console::console::remote_console::{RemoteConsole, RemoteConsoleBuilder};
-use client_map::client_map::{ClientMapFile, ClientMapLoading};
+use client_ghost::{GhostViewer, splits::RaceSplitsTracker};
+use client_map::{
+    cert_pins::{CertPinCheck, CertPins},
+    client_map::{ClientMapFile, ClientMapLoading},
+    download_trust::DownloadTrustCheck,
+};
 use client_notifications::overlay::ClientNotifications;
+use client_render_base::map::hq_assets::MapHqAssets;
 use client_render_game::render_game::{RenderGameCreateOptions, RenderModTy};
 use client_replay::replay::Replay;
 use client_types::{cert::ServerCertMode, console::ConsoleEntry};
 use client_ui::{
-    ingame_menu::server_info::{GameInfo, GameServerInfo},
+    ingame_menu::{
+        constants::INGAME_MENU_SIDE_SELECT_QUERY,
+        server_info::{ConnectionSecurity, GameInfo, GameServerInfo},
+    },
     main_menu::page::MainMenuUi,
 };
 use config::config::ConfigEngine;
@@ -55,6 +64,7 @@ use game_network::{
     messages::{ClientToServerMessage, ServerToClientMessage},
 };
 use log::info;
+use map::map::metadata::ThemeAmbiance;
 use math::math::vector::vec2;
 use network::network::{
     packet_compressor::DefaultNetworkPacketCompressor,
@@ -91,6 +101,11 @@ pub struct ConnectingGame {
     pub connect: GameConnect,
     auto_cleanup: DisconnectAutoCleanup,
 
+    /// The server info that is held back until the user accepted
+    /// the downloads of an untrusted server.
+    pending_server_info: Option<(Box<MsgSvServerInfo>, Duration)>,
+    pub downloads_accepted: bool,
+
     base: GameBase,
 }
 
@@ -212,6 +227,48 @@ impl Game {
         }))
     }
 
+    /// Compares the server certificate with the one pinned on earlier connects.
+    ///
+    /// A changed certificate stops the connect, until the user trusts the new one.
+    fn check_cert_pin(
+        connect: &mut GameConnect,
+        config: &ConfigEngine,
+        cert_pins: &mut CertPins,
+    ) -> anyhow::Result<()> {
+        if config.dbg.untrusted_cert {
+            connect.security = ConnectionSecurity::Unverified;
+            return Ok(());
+        }
+        // without a certificate the connect fails anyway
+        let Some(fingerprint) = connect.server_fingerprint() else {
+            return Ok(());
+        };
+        connect.security = match cert_pins.check(&connect.addr, &fingerprint) {
+            CertPinCheck::Local => ConnectionSecurity::Verified,
+            CertPinCheck::FirstUse => {
+                connect
+                    .log
+                    .log("First connect to this server, remembering its certificate.");
+                ConnectionSecurity::FirstUse
+            }
+            CertPinCheck::Match => ConnectionSecurity::Pinned,
+            CertPinCheck::Mismatch { pinned } => {
+                connect
+                    .log
+                    .log("The server presents a different certificate than before.");
+                connect.log.set_mode(ConnectModes::CertMismatch {
+                    addr: connect.addr,
+                    pinned,
+                    presented: fingerprint,
+                });
+                return Err(anyhow!(
+                    "The certificate of the server changed since the last connect."
+                ));
+            }
+        };
+        Ok(())
+    }
+
     fn connect(
         base: GameBase,
         connect: GameConnect,
@@ -283,6 +340,9 @@ impl Game {
                 connect,
                 auto_cleanup,
 
+                pending_server_info: None,
+                downloads_accepted: false,
+
                 base,
             }),
             Err(err) => Self::Err(err),
@@ -348,6 +408,7 @@ impl Game {
                 game_mod,
                 false,
                 &config.dbg,
+                &config.net,
                 game_options,
                 props,
                 connect.log.clone(),
@@ -491,6 +552,7 @@ impl Game {
         ui_creator: &UiCreator,
         notifications: &mut ClientNotifications,
         entries: &[ConsoleEntry],
+        cert_pins: &mut CertPins,
         cur_time: &Duration,
     ) {
         let mut selfi = Self::None;
@@ -525,14 +587,17 @@ impl Game {
                             }
                             connect.server_cert = server_cert;
 
-                            Self::connect(
-                                base,
-                                connect,
-                                config,
-                                account,
-                                dicts_task.get().ok(),
-                                auto_cleanup,
-                            )
+                            match Self::check_cert_pin(&mut connect, config, cert_pins) {
+                                Ok(()) => Self::connect(
+                                    base,
+                                    connect,
+                                    config,
+                                    account,
+                                    dicts_task.get().ok(),
+                                    auto_cleanup,
+                                ),
+                                Err(err) => Self::Err(err),
+                            }
                         }
                         (Err(err1), Err(err2)) => Self::Err(anyhow!("{err1}. {err2}")),
                         (Err(err), Ok(_)) | (Ok(_), Err(err)) => Self::Err(err),
@@ -614,15 +679,27 @@ impl Game {
                         race_demo_recorder: None,
 
                         ghost_recorder: None,
-                        ghost_viewer: None,
+                        ghost_viewer: Some(GhostViewer::new(
+                            &demo_recorder_props.io,
+                            demo_recorder_props.base.map.as_str(),
+                            demo_recorder_props.base.map_hash,
+                            map.game.game_tick_speed(),
+                        )),
+                        race_splits: Some(RaceSplitsTracker::new(
+                            &demo_recorder_props.io,
+                            demo_recorder_props.base.map.as_str(),
+                            demo_recorder_props.base.map_hash,
+                        )),
 
                         replay,
+                        killcam: None,
 
                         game_data: GameData::new(base.time.now(), prediction_timer, local),
 
                         events: events_pool.new(),
                         map_votes_loaded: Default::default(),
                         misc_votes_loaded: Default::default(),
+                        player_votes_requested: None,
 
                         render_players_pool: Pool::with_capacity(64),
                         render_observers_pool: Pool::with_capacity(2),
@@ -651,6 +728,9 @@ impl Game {
 
                         resource_download_server,
                         send_input_every_tick,
+
+                        input_timings: None,
+                        input_timings_requested: false,
                     }))
                 } else {
                     map.continue_loading();
@@ -703,6 +783,7 @@ impl Game {
     ) {
         game_server_info.fill_game_info(GameInfo {
             map_name: info.map.to_string(),
+            security: connect.security,
         });
         game_server_info.fill_server_options(info.server_options.clone());
         pipe.spatial_chat.spatial_chat.support(info.spatial_chat);
@@ -745,6 +826,7 @@ impl Game {
                 &expected_local_players,
                 pipe.config_game,
             ),
+            hq_assets: MapHqAssets::new(&pipe.config_game.map),
         };
         network.server_connect_time = timestamp.saturating_sub(prediction_timer.ping_max());
         pipe.ui.is_ui_open = true;
@@ -789,6 +871,149 @@ impl Game {
         );
     }
 
+    /// The files that joining the server might download.
+    fn download_summary(info: &MsgSvServerInfo) -> Vec<String> {
+        let mut downloads = vec![format!(
+            "Map {} ({:.2} MiB)",
+            info.map.as_str(),
+            info.map_size as f64 / (1024.0 * 1024.0)
+        )];
+        if let GameModification::Wasm { name, .. } = &info.game_mod {
+            downloads.push(format!("Physics module {}", name.as_str()));
+        }
+        if let RenderModification::TryWasm { name, .. }
+        | RenderModification::RequiresWasm { name, .. } = &info.render_mod
+        {
+            downloads.push(format!("Render module {}", name.as_str()));
+        }
+        downloads
+    }
+
+    /// Continues connecting after the user accepted the downloads.
+    pub fn on_downloads_accepted(
+        &mut self,
+        pipe: &mut GameMsgPipeline<'_>,
+        game_server_info: &GameServerInfo,
+        spatial_chat_scene: &SceneObject,
+    ) {
+        if let Game::Connecting(connecting) = self
+            && connecting.downloads_accepted
+            && connecting.pending_server_info.is_some()
+        {
+            let Game::Connecting(mut connecting) = std::mem::replace(self, Self::None) else {
+                unreachable!()
+            };
+            let (info, timestamp) = connecting.pending_server_info.take().unwrap();
+            connecting.connect.log.set_mode(ConnectModes::Connecting {
+                addr: connecting.connect.addr,
+            });
+            *self = Self::load_server_info(
+                connecting,
+                *info,
+                timestamp,
+                pipe,
+                game_server_info,
+                spatial_chat_scene,
+            );
+        }
+    }
+
+    fn load_server_info(
+        connecting: ConnectingGame,
+        info: MsgSvServerInfo,
+        timestamp: Duration,
+        pipe: &mut GameMsgPipeline<'_>,
+        game_server_info: &GameServerInfo,
+        spatial_chat_scene: &SceneObject,
+    ) -> Self {
+        game_server_info.fill_game_info(GameInfo {
+            map_name: info.map.to_string(),
+            security: connecting.connect.security,
+        });
+        game_server_info.fill_server_options(info.server_options.clone());
+        pipe.spatial_chat.spatial_chat.support(info.spatial_chat);
+
+        let mut local_player_id_counter = 0;
+
+        let mut expected_local_players: FxLinkedHashMap<u64, ClientConnectedPlayer> =
+            Default::default();
+        expected_local_players.insert(
+            local_player_id_counter,
+            ClientConnectedPlayer::Connecting {
+                is_dummy: false,
+                owns_dummies: true,
+            },
+        );
+        let active_local_player_id = local_player_id_counter;
+        local_player_id_counter += 1;
+
+        let render_props = RenderGameCreateOptions {
+            physics_group_name: info.server_options.physics_group_name.clone(),
+            resource_http_download_url: Some(HTTP_RESOURCE_URL.try_into().unwrap()),
+            resource_download_server: info.resource_server_fallback.map(|port| {
+                Url::try_from(
+                    format!(
+                        "http://{}",
+                        SocketAddr::new(connecting.connect.addr.ip(), port)
+                    )
+                    .as_str(),
+                )
+                .unwrap()
+            }),
+            fonts: connecting.base.fonts.clone(),
+            sound_props: Default::default(),
+            render_mod: RenderModTy::render_mod(&info.render_mod, pipe.config_game),
+            client_local_infos: Self::character_net_infos(
+                &expected_local_players,
+                pipe.config_game,
+            ),
+            required_resources: info.required_resources.clone(),
+            hq_assets: MapHqAssets::new(&pipe.config_game.map),
+        };
+
+        connecting
+            .connect
+            .log
+            .log("Got initial server info, loading game.");
+        Self::load(
+            connecting.base,
+            connecting.network,
+            pipe.runtime_thread_pool,
+            pipe.io,
+            &info.map,
+            &info.map_blake3_hash,
+            info.required_resources,
+            info.game_mod,
+            info.render_mod,
+            timestamp,
+            info.hint_start_camera_pos,
+            pipe.config,
+            pipe.config_game,
+            connecting.connect,
+            GameStateCreateOptions {
+                hint_max_characters: None, // TODO: get from server
+                config: info.mod_config,
+                account_db: None,
+                initial_rcon_input: Default::default(),
+            },
+            render_props,
+            if info.spatial_chat {
+                {
+                    pipe.spatial_chat
+                        .create_world(spatial_chat_scene, pipe.config_game)
+                }
+            } else {
+                SpatialChatGameWorldTy::None
+            },
+            connecting.auto_cleanup,
+            expected_local_players,
+            local_player_id_counter,
+            active_local_player_id,
+            info.send_input_every_tick,
+            info.server_options,
+        )
+    }
+
     pub fn on_msg(
         &mut self,
         timestamp: Duration,
@@ -806,97 +1031,59 @@ impl Game {
             Game::PrepareConnect(game) => {
                 *self = Self::PrepareConnect(game);
             }
-            Game::Connecting(connecting) => match msg {
+            Game::Connecting(mut connecting) => match msg {
                 ServerToClientMessage::RequiresPassword => {
                     connecting.connect.log.log("Server requires a password.");
                     pipe.config.ui.path.route("connectpassword");
                     *self = Self::Connecting(connecting);
                 }
                 ServerToClientMessage::ServerInfo { info, overhead } => {
-                    game_server_info.fill_game_info(GameInfo {
-                        map_name: info.map.to_string(),
-                    });
-                    game_server_info.fill_server_options(info.server_options.clone());
-                    pipe.spatial_chat.spatial_chat.support(info.spatial_chat);
-
-                    let mut local_player_id_counter = 0;
-
-                    let mut expected_local_players: FxLinkedHashMap<u64, ClientConnectedPlayer> =
-                        Default::default();
-                    expected_local_players.insert(
-                        local_player_id_counter,
-                        ClientConnectedPlayer::Connecting {
-                            is_dummy: false,
-                            owns_dummies: true,
-                        },
-                    );
-                    let active_local_player_id = local_player_id_counter;
-                    local_player_id_counter += 1;
-
-                    let render_props = RenderGameCreateOptions {
-                        physics_group_name: info.server_options.physics_group_name.clone(),
-                        resource_http_download_url: Some(HTTP_RESOURCE_URL.try_into().unwrap()),
-                        resource_download_server: info.resource_server_fallback.map(|port| {
-                            Url::try_from(
-                                format!(
-                                    "http://{}",
-                                    SocketAddr::new(connecting.connect.addr.ip(), port)
-                                )
-                                .as_str(),
-                            )
-                            .unwrap()
-                        }),
-                        fonts: connecting.base.fonts.clone(),
-                        sound_props: Default::default(),
-                        render_mod: RenderModTy::render_mod(&info.render_mod, pipe.config_game),
-                        client_local_infos: Self::character_net_infos(
-                            &expected_local_players,
-                            pipe.config_game,
-                        ),
-                        required_resources: info.required_resources.clone(),
-                    };
-
-                    connecting
-                        .connect
-                        .log
-                        .log("Got initial server info, loading game.");
-                    *self = Self::load(
-                        connecting.base,
-                        connecting.network,
-                        pipe.runtime_thread_pool,
-                        pipe.io,
-                        &info.map,
-                        &info.map_blake3_hash,
-                        info.required_resources,
-                        info.game_mod,
-                        info.render_mod,
-                        timestamp.saturating_sub(overhead),
-                        info.hint_start_camera_pos,
-                        pipe.config,
-                        pipe.config_game,
-                        connecting.connect,
-                        GameStateCreateOptions {
-                            hint_max_characters: None, // TODO: get from server
-                            config: info.mod_config,
-                            account_db: None,
-                            initial_rcon_input: Default::default(),
-                        },
-                        render_props,
-                        if info.spatial_chat {
-                            {
-                                pipe.spatial_chat
-                                    .create_world(spatial_chat_scene, pipe.config_game)
-                            }
-                        } else {
-                            SpatialChatGameWorldTy::None
-                        },
-                        connecting.auto_cleanup,
-                        expected_local_players,
-                        local_player_id_counter,
-                        active_local_player_id,
-                        info.send_input_every_tick,
-                        info.server_options,
-                    );
+                    let timestamp = timestamp.saturating_sub(overhead);
+                    match pipe.download_trust.check(
+                        &connecting.connect.addr,
+                        connecting.connect.server_fingerprint().as_ref(),
+                        connecting
+                            .connect
+                            .browser_data
+                            .is_community_server(connecting.connect.addr),
+                    ) {
+                        DownloadTrustCheck::Trusted => {
+                            *self = Self::load_server_info(
+                                connecting,
+                                info,
+                                timestamp,
+                                pipe,
+                                game_server_info,
+                                spatial_chat_scene,
+                            );
+                        }
+                        DownloadTrustCheck::Declined => {
+                            let msg = "Downloads from this server were declined before.";
+                            connecting
+                                .connect
+                                .log
+                                .set_mode(ConnectModes::ConnectingErr {
+                                    msg: msg.to_string(),
+                                });
+                            pipe.config.ui.path.route("connect");
+                            *self = Self::Err(anyhow!(msg));
+                        }
+                        DownloadTrustCheck::Prompt => {
+                            connecting
+                                .connect
+                                .log
+                                .log("Server is not trusted, asking before downloading.");
+                            connecting
+                                .connect
+                                .log
+                                .set_mode(ConnectModes::DownloadPrompt {
+                                    downloads: Self::download_summary(&info),
+                                });
+                            pipe.config.ui.path.route("connect");
+                            connecting.pending_server_info = Some((Box::new(info), timestamp));
+                            *self = Self::Connecting(connecting);
+                        }
+                    }
                 }
                 ServerToClientMessage::QueueInfo(info) => {
                     connecting
@@ -992,8 +1179,17 @@ impl Game {
                             .unwrap_or(*game_monotonic_tick_diff);
 
                         is_waiting = false;
-                        pipe.ui.is_ui_open = false;
-                        pipe.config.ui.path.route("ingame");
+                        if game.server_options.use_vanilla_sides {
+                            // let the player pick a side first
+                            pipe.ui.is_ui_open = true;
+                            pipe.config.ui.path.route_queried(
+                                "ingame",
+                                vec![(INGAME_MENU_SIDE_SELECT_QUERY.to_string(), "1".to_string())],
+                            );
+                        } else {
+                            pipe.ui.is_ui_open = false;
+                            pipe.config.ui.path.route("ingame");
+                        }
                     }
                     game.on_msg(&timestamp, msg, pipe);
 
@@ -1025,6 +1221,14 @@ impl Game {
         self.get_remote_console()
             .is_some_and(|c| c.ui.ui_state.is_ui_open)
     }
+    /// The ambiance of the menu theme the map of the game prefers.
+    pub fn theme_ambiance(&self) -> Option<ThemeAmbiance> {
+        match self {
+            Game::Loading(game) => game.map.theme_ambiance(),
+            Game::WaitingForFirstSnapshot(game) | Game::Active(game) => game.map.theme_ambiance,
+            Game::None | Game::PrepareConnect(_) | Game::Connecting(_) | Game::Err(_) => None,
+        }
+    }
     pub fn active_game(&self) -> Option<&ActiveGame> {
         if let Game::Active(game) = self {
             Some(game)
@@ -1,11 +1,14 @@
-use std::{collections::BTreeMap, sync::Arc, time::Duration};
+use std::{collections::BTreeMap, ops::RangeInclusive, sync::Arc, time::Duration};
 
 use anyhow::anyhow;
 use base::{linked_hash_map_view::FxLinkedHashMap, steady_clock::SteadyClock};
 use client_console::console::remote_console::RemoteConsole;
-use client_ghost::GhostViewer;
+use client_ghost::{GhostViewer, splits::RaceSplitsTracker};
 use client_map::client_map::GameMap;
-use client_notifications::overlay::ClientNotifications;
+use client_notifications::{
+    overlay::ClientNotifications,
+    toasts::{Toast, ToastSeverity},
+};
 use client_render_game::render_game::{ObservedPlayer, RenderGameForPlayer};
 use client_replay::replay::Replay;
 use client_types::console::ConsoleEntry;
@@ -24,7 +27,7 @@ use game_base::{
 };
 use game_config::config::ConfigGame;
 use game_interface::{
-    events::GameEvents,
+    events::{GameEvents, GameWorldEvent, GameWorldNotificationEvent},
     types::{
         character_info::NetworkCharacterInfo,
         game::{GameTickType, NonZeroGameTickType},
@@ -33,6 +36,7 @@ use game_interface::{
         render::character::PlayerCameraMode,
         snapshot::SnapshotClientInfo,
     },
+    votes::VoteType,
 };
 use game_network::messages::{
     ClientToServerMessage, ClientToServerPlayerMessage, MsgSvLoadVotes, MsgSvResetVotes,
@@ -52,6 +56,7 @@ use tracing::instrument;
 use url::Url;
 
 use crate::{
+    client::input::input_timings::InputTimings,
     game::data::{ClientConnectedPlayer, SnapshotStorageItem},
     localplayer::{ClientPlayer, ClientPlayerZoomMode, ServerInputForDiff},
     spatial_chat::spatial_chat::SpatialChatGameWorldTy,
@@ -63,6 +68,9 @@ use super::{
     types::{GameBase, GameConnect, GameMsgPipeline, GameNetwork},
 };
 
+/// The toast action of a started vote, see [`ClientNotifications::take_activated_actions`].
+pub const TOAST_ACTION_VOTE_YES: &str = "vote-yes";
+
 pub struct ActiveGame {
     pub network: GameNetwork,
 
@@ -75,8 +83,11 @@ pub struct ActiveGame {
 
     pub ghost_recorder: Option<GhostRecorder>,
     pub ghost_viewer: Option<GhostViewer>,
+    pub race_splits: Option<RaceSplitsTracker>,
 
     pub replay: Replay,
+    /// The monotonic ticks of a round highlight that should be replayed.
+    pub killcam: Option<RangeInclusive<GameTickType>>,
 
     pub game_data: GameData,
 
@@ -84,6 +95,8 @@ pub struct ActiveGame {
 
     pub map_votes_loaded: bool,
     pub misc_votes_loaded: bool,
+    /// When the player vote rules were requested the last time.
+    pub player_votes_requested: Option<Duration>,
 
     pub render_players_pool: Pool<FxLinkedHashMap<PlayerId, RenderGameForPlayer>>,
     pub render_observers_pool: Pool<Vec<ObservedPlayer>>,
@@ -113,6 +126,11 @@ pub struct ActiveGame {
     pub base: GameBase,
 
     pub send_input_every_tick: bool,
+
+    /// Only recorded if enabled in the debug config.
+    pub input_timings: Option<InputTimings>,
+    /// Whether the server was asked to echo the ticks of the inputs.
+    pub input_timings_requested: bool,
 }
 
 impl ActiveGame {
@@ -199,6 +217,12 @@ impl ActiveGame {
             self.game_data
                 .sent_input_ids
                 .insert(self.game_data.input_id, cur_time);
+            if let Some(input_timings) = &mut self.input_timings
+                && let Some((player_id, _)) = self.game_data.local.active_local_player()
+                && let Some(input) = player_inputs.get(player_id).and_then(|inps| inps.last())
+            {
+                input_timings.on_sent(input, self.game_data.input_id, cur_time);
+            }
             self.network
                 .send_unordered_auto_to_server(&ClientToServerMessage::Inputs {
                     id: self.game_data.input_id,
@@ -209,6 +233,9 @@ impl ActiveGame {
             self.game_data.snap_acks.clear();
             self.game_data.input_id += 1;
         }
+        if let Some(input_timings) = &mut self.input_timings {
+            input_timings.end_frame();
+        }
     }
 
     fn ack_input(player: &mut ClientPlayer, input_id: u64) {
@@ -286,15 +313,28 @@ impl ActiveGame {
                     }
                 }
 
+                if let Some(input_timings) = &mut self.input_timings {
+                    for input in input_ack.iter() {
+                        input_timings.on_acked(input.id, *timestamp);
+                    }
+                }
+
                 // add the estimated ping to our prediction timer
+                let mut clock_sample: Option<(Duration, Duration)> = None;
                 for input in input_ack.iter() {
                     if let Some(sent_at) = self.game_data.sent_input_ids.remove(&input.id) {
-                        self.game_data.prediction_timer.add_ping(
-                            timestamp
-                                .saturating_sub(sent_at)
-                                .saturating_sub(input.logic_overhead),
-                            *timestamp,
-                        );
+                        let ping = timestamp
+                            .saturating_sub(sent_at)
+                            .saturating_sub(input.logic_overhead);
+                        self.game_data.prediction_timer.add_ping(ping, *timestamp);
+                        // the fastest ack is the most exact sample for the clock sync
+                        if clock_sample.is_none_or(|(best_sent_at, best_overhead)| {
+                            ping < timestamp
+                                .saturating_sub(best_sent_at)
+                                .saturating_sub(best_overhead)
+                        }) {
+                            clock_sample = Some((sent_at, input.logic_overhead));
+                        }
                     }
                 }
 
@@ -347,6 +387,22 @@ impl ActiveGame {
                     game_monotonic_tick,
                     std::mem::take(&mut *snapshot.clone().to_mut()),
                 );
+                if let Some((sent_at, logic_overhead)) = clock_sample {
+                    let clock_sync = &mut self.game_data.clock_sync;
+                    clock_sync.add_sample(
+                        sent_at,
+                        *timestamp,
+                        Duration::from_secs_f64(monotonic_tick as f64 * tick_time.as_secs_f64())
+                            + overhead_time,
+                        logic_overhead,
+                    );
+                    clock_sync.update(*timestamp);
+                }
+                self.game_data.interpolation_buffer.add_snap(
+                    timestamp.saturating_sub(overhead_time),
+                    monotonic_tick,
+                    tick_time,
+                );
                 while self
                     .game_data
                     .last_snaps
@@ -471,7 +527,19 @@ impl ActiveGame {
                             }
                             std::cmp::Ordering::Less => {
                                 if let Some(overhead_time) = overhead_time {
-                                    game_data.last_game_tick = timestamp
+                                    // with a synced clock, use the time the snapshot usually
+                                    // arrives at, so that the jitter of single snapshots
+                                    // doesn't move the game time.
+                                    let server_sent = Duration::from_secs_f64(
+                                        monotonic_tick as f64 * tick_time.as_secs_f64(),
+                                    ) + overhead_time;
+                                    let arrival = game_data
+                                        .clock_sync
+                                        .local_time(server_sent)
+                                        .zip(game_data.clock_sync.min_rtt())
+                                        .map(|(sent, rtt)| sent + rtt / 2)
+                                        .unwrap_or(*timestamp);
+                                    game_data.last_game_tick = arrival
                                         .saturating_sub(
                                             game_data
                                                 .prediction_timer
@@ -593,6 +661,18 @@ impl ActiveGame {
 
                 prediction_timer.add_snap(time_diff, timestamp);
             }
+            ServerToClientMessage::InputTimings(timings) => {
+                if let Some(input_timings) = &mut self.input_timings {
+                    for timing in timings {
+                        if let Some(tick) = timing.applied_monotonic_tick {
+                            input_timings.on_applied(timing.id, tick);
+                        }
+                    }
+                }
+            }
+            ServerToClientMessage::SnapshotRate { ticks_per_snapshot } => {
+                self.game_data.ticks_per_snapshot = Some(ticks_per_snapshot);
+            }
             ServerToClientMessage::Events {
                 events,
                 game_monotonic_tick,
@@ -606,8 +686,37 @@ impl ActiveGame {
                 if let Some(demo_recorder) = &mut self.race_demo_recorder {
                     demo_recorder.add_event(game_monotonic_tick, DemoEvent::Game(events.clone()));
                 }
+                if let (Some(ghost_viewer), Some((player_id, _))) = (
+                    &mut self.ghost_viewer,
+                    self.game_data.local.active_local_player(),
+                ) {
+                    ghost_viewer.on_event(&events, player_id);
+                }
+                if let (Some(race_splits), Some((player_id, _))) = (
+                    &mut self.race_splits,
+                    self.game_data.local.active_local_player(),
+                ) {
+                    race_splits.on_event(&events, player_id, &self.base.time.now());
+                }
                 self.replay
                     .add_event(game_monotonic_tick, DemoEvent::Game(events.clone()));
+                if let Some(highlight) = events
+                    .worlds
+                    .values()
+                    .flat_map(|world| world.events.values())
+                    .find_map(|ev| match ev {
+                        GameWorldEvent::Notification(GameWorldNotificationEvent::GameOver {
+                            highlight,
+                            ..
+                        }) => highlight.as_ref(),
+                        _ => None,
+                    })
+                {
+                    self.killcam = Some(
+                        game_monotonic_tick.saturating_sub(highlight.start_ticks_ago)
+                            ..=game_monotonic_tick.saturating_sub(highlight.end_ticks_ago),
+                    );
+                }
 
                 let event_id = events.event_id;
                 self.events.insert((game_monotonic_tick, false), events);
@@ -643,7 +752,24 @@ impl ActiveGame {
                     DemoEvent::Chat(Box::new(chat_msg.msg.clone())),
                 );
 
-                self.game_data.chat_msgs.push_back(chat_msg.msg);
+                // muted players are still recorded, only hidden from the chat
+                let chat_muted: Vec<String> = pipe
+                    .config
+                    .ui
+                    .storage
+                    .get("chat-muted-players")
+                    .and_then(|muted| serde_json::from_str(muted).ok())
+                    .unwrap_or_default();
+                if !chat_muted
+                    .iter()
+                    .any(|name| name.as_str() == chat_msg.msg.sender.name.as_str())
+                {
+                    self.game_data.chat_msgs.push_back(chat_msg.msg);
+                }
+            }
+            ServerToClientMessage::Announcement(msg) => {
+                pipe.notifications
+                    .add_info(msg.to_string(), Duration::from_secs(3));
             }
             ServerToClientMessage::StartVoteRes(res) => {
                 if let Some(msg) = match res {
@@ -688,6 +814,9 @@ impl ActiveGame {
                     MsgSvStartVoteResult::RandomUnfinishedMapUnsupported => {
                         Some("Random unfinished map votes are not supported.".to_string())
                     }
+                    MsgSvStartVoteResult::ReasonRequired => {
+                        Some("This server requires a reason for this vote.".to_string())
+                    }
                 } {
                     pipe.notifications.add_info(msg, Duration::from_secs(3));
                 }
@@ -698,6 +827,31 @@ impl ActiveGame {
                     .vote
                     .as_ref()
                     .and_then(|(_, voted, _)| *voted);
+                if self.game_data.vote.is_none()
+                    && let Some(vote) = &vote_state
+                {
+                    let text = match &vote.vote {
+                        VoteType::Map { key, .. } => {
+                            format!("Vote started: change map to {}", key.map.name.as_str())
+                        }
+                        VoteType::RandomUnfinishedMap { .. } => {
+                            "Vote started: random unfinished map".to_string()
+                        }
+                        VoteType::VoteKickPlayer { name, .. } => {
+                            format!("Vote started: kick {name}")
+                        }
+                        VoteType::VoteSpecPlayer { name, .. } => {
+                            format!("Vote started: move {name} to spectators")
+                        }
+                        VoteType::Misc { key, .. } => {
+                            format!("Vote started: {}", key.vote_key.display_name)
+                        }
+                    };
+                    pipe.notifications.add(
+                        Toast::new(ToastSeverity::Info, text, vote.remaining_time)
+                            .with_action("Vote yes", TOAST_ACTION_VOTE_YES),
+                    );
+                }
                 self.game_data.vote =
                     vote_state.map(|v| (PoolRc::from_item_without_pool(v), voted, *timestamp));
             }
@@ -712,6 +866,13 @@ impl ActiveGame {
                 MsgSvLoadVotes::Misc { votes } => {
                     self.game_data.misc_votes = votes;
                 }
+                MsgSvLoadVotes::Players {
+                    immune,
+                    reason_required,
+                } => {
+                    self.game_data.player_votes_immune = immune;
+                    self.game_data.player_votes_reason_required = reason_required;
+                }
             },
             ServerToClientMessage::ResetVotes(votes) => match votes {
                 MsgSvResetVotes::Map => {
@@ -818,6 +979,24 @@ impl ActiveGame {
                             .add_err(err.to_string(), Duration::from_secs(10));
                     }
                 }
+                // the player wanted to join as spectator, e.g. from the server browser
+                if std::mem::take(&mut self.connect.as_spectator) {
+                    for player in self.game_data.local.expected_local_players.values() {
+                        if let ClientConnectedPlayer::Connected {
+                            is_dummy: false,
+                            player_id,
+                            ..
+                        } = player
+                        {
+                            self.network.send_unordered_to_server(
+                                &ClientToServerMessage::PlayerMsg((
+                                    *player_id,
+                                    ClientToServerPlayerMessage::JoinSpectator,
+                                )),
+                            );
+                        }
+                    }
+                }
                 // make sure the client has an active local player
                 if !self
                     .game_data
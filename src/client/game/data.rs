@@ -42,14 +42,17 @@ use pool::{
     pool::Pool,
     rc::PoolRc,
 };
-use prediction_timer::prediction_timing::PredictionTimer;
+use prediction_timer::{
+    clock_sync::ClockSync, interpolation_buffer::InterpolationBuffer,
+    prediction_timing::PredictionTimer,
+};
 use tracing::instrument;
 
 use crate::{
     client::input::input_handling::DeviceToLocalPlayerIndex,
     localplayer::{
         ClientPlayer, ClientPlayerInputPerTick, LocalPlayers,
-        dummy_control::{DummyControlState, DummyHammerState},
+        dummy_control::{DummyControlState, DummyCopiedInput, DummyHammerState},
     },
 };
 
@@ -168,12 +171,18 @@ pub struct GameData {
 
     /// Last snapshots (only for unpredicted gameplay)
     pub last_snaps: BTreeMap<GameTickType, Vec<u8>>,
+    /// The ticks between two snapshots, as announced by the server.
+    pub ticks_per_snapshot: Option<GameTickType>,
 
     /// A tracker of sent inputs and their time
     /// used to evaluate the estimated RTT/ping.
     pub sent_input_ids: BTreeMap<u64, Duration>,
 
     pub prediction_timer: PredictionTimer,
+    /// How far the unpredicted game is rendered behind the snapshots.
+    pub interpolation_buffer: InterpolationBuffer,
+    /// The estimated clock of the server.
+    pub clock_sync: ClockSync,
     pub net_byte_stats: NetworkByteStats,
     pub last_keep_alive_id_and_time: (Option<u64>, Duration),
 
@@ -194,6 +203,9 @@ pub struct GameData {
     pub map_votes: BTreeMap<NetworkString<MAX_CATEGORY_NAME_LEN>, BTreeMap<MapVoteKey, MapVote>>,
     pub has_unfinished_map_votes: bool,
     pub misc_votes: BTreeMap<NetworkString<MAX_CATEGORY_NAME_LEN>, BTreeMap<MiscVoteKey, MiscVote>>,
+    /// Players that can not be voted by this client.
+    pub player_votes_immune: Vec<PlayerId>,
+    pub player_votes_reason_required: bool,
 
     pub cached_character_infos: PoolFxLinkedHashMap<CharacterId, CharacterInfo>,
 }
@@ -219,6 +231,7 @@ impl GameData {
 
             snap_storage: Default::default(),
             last_snaps: Default::default(),
+            ticks_per_snapshot: None,
 
             device_to_local_player_index: Default::default(),
             input_per_tick: Default::default(),
@@ -227,6 +240,8 @@ impl GameData {
 
             handled_snap_id: None,
             prediction_timer,
+            interpolation_buffer: Default::default(),
+            clock_sync: Default::default(),
             net_byte_stats: Default::default(),
 
             last_game_tick: cur_time,
@@ -246,6 +261,8 @@ impl GameData {
             map_votes: Default::default(),
             has_unfinished_map_votes: false,
             misc_votes: Default::default(),
+            player_votes_immune: Default::default(),
+            player_votes_reason_required: false,
 
             cached_character_infos: PoolFxLinkedHashMap::new_without_pool(),
         }
@@ -591,6 +608,7 @@ impl GameData {
         player_inputs: &mut FxLinkedHashMap<PlayerId, PoolVec<PlayerInputChainable>>,
         player_inputs_chainable_pool: &Pool<Vec<PlayerInputChainable>>,
         force_send_input_per_tick: bool,
+        copy_moves_delay: Duration,
     ) {
         let mut handle_character =
             |local_player_id: &CharacterId, local_player: &mut ClientPlayer, is_dummy: bool| {
@@ -674,26 +692,29 @@ impl GameData {
                 }
             };
 
-        let mut copied_input = None;
-
         // handle the active player first
         let active_player = self.local.active_local_player_mut();
         let active_player_id = active_player.as_ref().map(|&(&id, _)| id);
         if let Some((id, local_player)) = active_player {
             if self.dummy_control.dummy_copy_moves {
-                copied_input = Some((
-                    local_player
-                        .input
-                        .inp
-                        .consumable
-                        .diff(&local_player.sent_input.inp.consumable),
-                    local_player.input.inp.state,
-                    local_player.input.inp.cursor,
-                    local_player.input.inp.viewport,
-                ));
+                self.dummy_control.push_copied_input(
+                    cur_time,
+                    copy_moves_delay,
+                    DummyCopiedInput {
+                        consumable: local_player
+                            .input
+                            .inp
+                            .consumable
+                            .diff(&local_player.sent_input.inp.consumable),
+                        state: local_player.input.inp.state,
+                        cursor: local_player.input.inp.cursor,
+                        viewport: local_player.input.inp.viewport,
+                    },
+                );
             }
             handle_character(id, local_player, false);
         }
+        let copied_inputs = self.dummy_control.take_due_copied_inputs(cur_time);
 
         let local_players = &mut self.local.local_players;
         for (local_player_id, local_player) in local_players
@@ -720,26 +741,28 @@ impl GameData {
                     .input_method_flags
                     .set(CharacterInputMethodFlags::DUMMY);
             }
-            if let Some((consumable, state, cursor, viewport)) = &copied_input {
+            if let Some(last) = copied_inputs.last() {
                 let mut inp = local_player.input.inp;
-                if let Some((v, cursor)) = consumable.fire {
-                    inp.consumable.fire.add(v.get(), cursor);
-                }
-                if let Some((v, cursor)) = consumable.hook {
-                    inp.consumable.hook.add(v.get(), cursor);
-                }
-                if let Some(v) = consumable.weapon_req {
-                    inp.consumable.set_weapon_req(Some(v));
-                }
-                if let Some(v) = consumable.weapon_diff {
-                    inp.consumable.weapon_diff.add(v.get());
-                }
-                if let Some(v) = consumable.jump {
-                    inp.consumable.jump.add(v.get());
+                for DummyCopiedInput { consumable, .. } in &copied_inputs {
+                    if let Some((v, cursor)) = consumable.fire {
+                        inp.consumable.fire.add(v.get(), cursor);
+                    }
+                    if let Some((v, cursor)) = consumable.hook {
+                        inp.consumable.hook.add(v.get(), cursor);
+                    }
+                    if let Some(v) = consumable.weapon_req {
+                        inp.consumable.set_weapon_req(Some(v));
+                    }
+                    if let Some(v) = consumable.weapon_diff {
+                        inp.consumable.weapon_diff.add(v.get());
+                    }
+                    if let Some(v) = consumable.jump {
+                        inp.consumable.jump.add(v.get());
+                    }
                 }
-                inp.state = *state;
-                inp.cursor = *cursor;
-                inp.viewport = *viewport;
+                inp.state = last.state;
+                inp.cursor = last.cursor;
+                inp.viewport = last.viewport;
                 inp.state
                     .input_method_flags
                     .set(CharacterInputMethodFlags::DUMMY);
@@ -6,11 +6,15 @@ use std::{
     time::Duration,
 };
 
-use base::steady_clock::SteadyClock;
+use base::{
+    hash::{Hash, generate_hash_for},
+    steady_clock::SteadyClock,
+};
 use base_io::io::Io;
+use client_map::download_trust::DownloadTrust;
 use client_notifications::overlay::ClientNotifications;
 use client_types::{cert::ServerCertMode, console::ConsoleEntry};
-use client_ui::ingame_menu::account_info::AccountInfo;
+use client_ui::ingame_menu::{account_info::AccountInfo, server_info::ConnectionSecurity};
 use client_ui::{
     ingame_menu::{client_info::ClientInfo, votes::Votes},
     main_menu::{player_settings_ntfy::PlayerSettingsSync, spatial_chat},
@@ -72,6 +76,22 @@ pub struct GameConnect {
     pub log: ConnectingLog,
     pub server_cert: ServerCertMode,
     pub browser_data: ServerBrowserData,
+    /// Join the spectators as soon as the server accepted the player.
+    pub as_spectator: bool,
+    /// How the certificate of the server was verified,
+    /// known once the connect started.
+    pub security: ConnectionSecurity,
+}
+
+impl GameConnect {
+    /// The hash of the server certificate, if known.
+    pub fn server_fingerprint(&self) -> Option<Hash> {
+        match &self.server_cert {
+            ServerCertMode::Cert(cert) => Some(generate_hash_for(cert)),
+            ServerCertMode::Hash(hash) => Some(*hash),
+            ServerCertMode::Unknown => None,
+        }
+    }
 }
 
 pub struct GameNetwork {
@@ -102,4 +122,5 @@ pub struct GameMsgPipeline<'a> {
     pub string_pool: &'a StringPool,
     pub spatial_chat: &'a SpatialChat,
     pub notifications: &'a mut ClientNotifications,
+    pub download_trust: &'a DownloadTrust,
 }
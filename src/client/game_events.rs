@@ -29,6 +29,9 @@ pub struct GameEventsClient {}
 impl GameEventsClient {
     #[instrument(level = "trace", skip_all)]
     pub fn update(pipe: &mut GameEventPipeline<'_>) {
+        pipe.game
+            .on_downloads_accepted(pipe.msgs, pipe.game_server_info, pipe.spatial_chat_scene);
+
         let event_gen = match pipe.game {
             Game::None | Game::Err(_) | Game::PrepareConnect(_) => None,
             Game::Connecting(game) => Some((
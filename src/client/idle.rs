@@ -0,0 +1,206 @@
+use std::time::Duration;
+
+use game_config::config::ConfigIdle;
+
+/// Whether the player currently uses the client.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum IdleState {
+    #[default]
+    Active,
+    /// The window lost the focus.
+    Background,
+    /// No input arrived for [`ConfigIdle::timeout_minutes`].
+    Idle,
+}
+
+impl IdleState {
+    pub fn is_throttled(self) -> bool {
+        !matches!(self, Self::Active)
+    }
+}
+
+/// Throttles the render rate & sounds while the player
+/// is away, to save power.
+///
+/// Only rendering is affected, the network connection
+/// & the game simulation keep running.
+#[derive(Debug)]
+pub struct IdleThrottle {
+    last_input: Duration,
+    focused: bool,
+    state: IdleState,
+}
+
+impl IdleThrottle {
+    pub fn new(cur_time: &Duration) -> Self {
+        Self {
+            last_input: *cur_time,
+            focused: true,
+            state: IdleState::Active,
+        }
+    }
+
+    pub fn state(&self) -> IdleState {
+        self.state
+    }
+
+    /// Any input restores the full render rate instantly.
+    pub fn on_input(&mut self, cur_time: &Duration) {
+        self.last_input = *cur_time;
+        if self.focused {
+            self.state = IdleState::Active;
+        }
+    }
+
+    pub fn focus_changed(&mut self, focused: bool, cur_time: &Duration) {
+        self.focused = focused;
+        self.last_input = *cur_time;
+        self.state = if focused {
+            IdleState::Active
+        } else {
+            IdleState::Background
+        };
+    }
+
+    /// Should be called once per frame.
+    ///
+    /// `never_throttle` keeps the client active regardless,
+    /// e.g. while encoding a video.
+    pub fn update(
+        &mut self,
+        config: &ConfigIdle,
+        cur_time: &Duration,
+        never_throttle: bool,
+    ) -> IdleState {
+        self.state = if !config.enabled || never_throttle {
+            IdleState::Active
+        } else if !self.focused {
+            IdleState::Background
+        } else if cur_time.saturating_sub(self.last_input)
+            >= Duration::from_secs(config.timeout_minutes.saturating_mul(60))
+        {
+            IdleState::Idle
+        } else {
+            IdleState::Active
+        };
+        self.state
+    }
+
+    /// The render rate for the current state,
+    /// `0` means unlimited.
+    pub fn refresh_rate(&self, config: &ConfigIdle, refresh_rate: u64) -> u64 {
+        if !self.state.is_throttled() {
+            refresh_rate
+        } else if refresh_rate == 0 {
+            config.background_fps.max(1)
+        } else {
+            refresh_rate.min(config.background_fps.max(1))
+        }
+    }
+
+    /// Factor for all sound volumes.
+    pub fn volume(&self, config: &ConfigIdle) -> f64 {
+        if config.mute && self.state.is_throttled() {
+            0.0
+        } else {
+            1.0
+        }
+    }
+}
+
+/// The time between two frames, `None` if the render rate is unlimited.
+pub fn frame_interval(refresh_rate: u64) -> Option<Duration> {
+    (refresh_rate > 0).then(|| Duration::from_nanos(1_000_000_000 / refresh_rate))
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use game_config::config::ConfigIdle;
+
+    use super::{IdleState, IdleThrottle, frame_interval};
+
+    #[test]
+    fn state_transitions() {
+        let config = ConfigIdle::default();
+        let timeout = Duration::from_secs(config.timeout_minutes * 60);
+        let mut idle = IdleThrottle::new(&Duration::ZERO);
+
+        assert_eq!(
+            idle.update(&config, &Duration::from_secs(1), false),
+            IdleState::Active
+        );
+        assert_eq!(idle.update(&config, &timeout, false), IdleState::Idle);
+
+        // input restores instantly
+        idle.on_input(&timeout);
+        assert_eq!(idle.state(), IdleState::Active);
+        assert_eq!(idle.update(&config, &timeout, false), IdleState::Active);
+        assert_eq!(idle.update(&config, &(timeout * 2), false), IdleState::Idle);
+
+        // losing the focus throttles immediately, input does not restore it
+        idle.focus_changed(false, &(timeout * 2));
+        assert_eq!(idle.state(), IdleState::Background);
+        idle.on_input(&(timeout * 2));
+        assert_eq!(
+            idle.update(&config, &(timeout * 2), false),
+            IdleState::Background
+        );
+
+        // the exception & disabling always keep the client active
+        assert_eq!(
+            idle.update(&config, &(timeout * 2), true),
+            IdleState::Active
+        );
+        assert_eq!(
+            idle.update(
+                &ConfigIdle {
+                    enabled: false,
+                    ..config.clone()
+                },
+                &(timeout * 2),
+                false
+            ),
+            IdleState::Active
+        );
+
+        idle.focus_changed(true, &(timeout * 3));
+        assert_eq!(idle.state(), IdleState::Active);
+        assert_eq!(
+            idle.update(&config, &(timeout * 3), false),
+            IdleState::Active
+        );
+        assert_eq!(idle.update(&config, &(timeout * 4), false), IdleState::Idle);
+    }
+
+    #[test]
+    fn throttled_frame_interval() {
+        let config = ConfigIdle {
+            background_fps: 10,
+            mute: true,
+            ..Default::default()
+        };
+        let mut idle = IdleThrottle::new(&Duration::ZERO);
+
+        assert_eq!(idle.refresh_rate(&config, 0), 0);
+        assert_eq!(idle.refresh_rate(&config, 144), 144);
+        assert_eq!(idle.volume(&config), 1.0);
+
+        idle.focus_changed(false, &Duration::ZERO);
+        // unlimited is limited, too
+        assert_eq!(idle.refresh_rate(&config, 0), 10);
+        assert_eq!(idle.refresh_rate(&config, 144), 10);
+        // already lower rates are kept
+        assert_eq!(idle.refresh_rate(&config, 5), 5);
+        assert_eq!(idle.volume(&config), 0.0);
+
+        assert_eq!(frame_interval(0), None);
+        assert_eq!(frame_interval(10), Some(Duration::from_millis(100)));
+        assert_eq!(frame_interval(1), Some(Duration::from_secs(1)));
+        assert_eq!(
+            frame_interval(idle.refresh_rate(&config, 0)),
+            Some(Duration::from_millis(100))
+        );
+    }
+}
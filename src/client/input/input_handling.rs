@@ -177,6 +177,12 @@ impl InputHandling {
         self.inp.egui = Some(self.state.egui_input_mut().take());
     }
 
+    /// The collected ui input of the current frame,
+    /// `None` if it was already taken.
+    pub fn egui_input_mut(&mut self) -> Option<&mut egui::RawInput> {
+        self.inp.egui.as_mut()
+    }
+
     #[instrument(level = "trace", skip_all)]
     pub fn set_last_known_cursor(&mut self, config: &ConfigEngine, cursor: CursorIcon) {
         if !config.inp.dbg_mode {
@@ -319,6 +325,9 @@ impl InputHandling {
                     BindActionsLocalPlayer::ToggleDummyHammerFly => {
                         // only listen for press
                     }
+                    BindActionsLocalPlayer::ToggleGhost => {
+                        // only listen for press
+                    }
                     BindActionsLocalPlayer::VoteYes => {
                         // only listen for click
                     }
@@ -385,6 +394,7 @@ impl InputHandling {
                     }
                     BindActionsLocalPlayer::ToggleDummyCopyMoves => {
                         dummy_control.dummy_copy_moves = !dummy_control.dummy_copy_moves;
+                        dummy_control.clear_copied_inputs();
                     }
                     BindActionsLocalPlayer::ToggleDummyHammerFly => {
                         dummy_control.dummy_hammer = match dummy_control.dummy_hammer {
@@ -394,6 +404,9 @@ impl InputHandling {
                             DummyHammerState::Active { .. } => DummyHammerState::None,
                         };
                     }
+                    BindActionsLocalPlayer::ToggleGhost => {
+                        config_game.cl.ghost.show = !config_game.cl.ghost.show;
+                    }
                     BindActionsLocalPlayer::ZoomOut => {
                         local_player.zoom_state = Some(ClientPlayerZoomState {
                             mode: ClientPlayerZoomMode::ZoomingOut,
@@ -571,7 +584,7 @@ impl InputHandling {
             character.changes_by_reset();
             local_player.binds.reset_cur_keys();
         }
-        if !next_show_spectator_selection {
+        if !next_show_spectator_selection && !next_show_scoreboard {
             set(input, character);
         }
 
@@ -641,7 +654,7 @@ impl InputHandling {
                 let cursor = CharacterInputCursor::from_vec2(&cursor);
                 local_dummy.input.inp.cursor.set(cursor);
             }
-            if !next_show_spectator_selection {
+            if !next_show_spectator_selection && !next_show_scoreboard {
                 set(&mut local_dummy.input.inp, dummy);
             }
 
@@ -926,7 +939,8 @@ impl InputHandling {
                         },
                         InputEv::Move(move_ev)
                             if !local_player.emote_wheel_active
-                                && !local_player.spectator_selection_active =>
+                                && !local_player.spectator_selection_active
+                                && !local_player.show_scoreboard =>
                         {
                             let factor = config_game.inp.sensitivity() / 100.0;
 
@@ -1003,7 +1017,9 @@ impl InputHandling {
                         )),
                     );
 
-                    local_player.emote_wheel_active || local_player.spectator_selection_active
+                    local_player.emote_wheel_active
+                        || local_player.spectator_selection_active
+                        || local_player.show_scoreboard
                 } else {
                     true
                 }
@@ -0,0 +1,323 @@
+use std::{collections::VecDeque, fmt::Write, time::Duration};
+
+use game_base::network::messages::PlayerInputChainable;
+use game_interface::types::game::GameTickType;
+
+/// How many finished timings are kept for the histogram & the export.
+const MAX_FINISHED: usize = 1000;
+/// Inputs that were not ack'd in this time are considered lost.
+const MAX_IN_FLIGHT_TIME: Duration = Duration::from_secs(3);
+
+/// The timings of a single input,
+/// from the os event that caused it to the server applying it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputTiming {
+    pub os_event_at: Duration,
+    pub sent_at: Duration,
+    /// The first prediction tick that used the input.
+    pub predicted_at: Option<Duration>,
+    /// The server ack'd the packet that contained the input.
+    pub acked_at: Option<Duration>,
+    /// The tick the input was sent for.
+    pub for_tick: GameTickType,
+    /// The tick the server applied the input for,
+    /// only known if the server echoed it.
+    pub applied_tick: Option<GameTickType>,
+
+    version: u64,
+    packet_id: u64,
+}
+
+impl InputTiming {
+    /// From the os event to sending the input.
+    pub fn send_delay(&self) -> Duration {
+        self.sent_at.saturating_sub(self.os_event_at)
+    }
+
+    /// From the os event to the prediction using the input.
+    pub fn prediction_delay(&self) -> Option<Duration> {
+        self.predicted_at
+            .map(|predicted_at| predicted_at.saturating_sub(self.os_event_at))
+    }
+
+    /// From sending the input to the server's ack.
+    pub fn round_trip(&self) -> Option<Duration> {
+        self.acked_at
+            .map(|acked_at| acked_at.saturating_sub(self.sent_at))
+    }
+
+    /// From the os event to the server's ack.
+    pub fn total(&self) -> Option<Duration> {
+        self.acked_at
+            .map(|acked_at| acked_at.saturating_sub(self.os_event_at))
+    }
+
+    /// How many ticks later than requested the server applied the input.
+    pub fn late_ticks(&self) -> Option<GameTickType> {
+        self.applied_tick
+            .map(|applied_tick| applied_tick.saturating_sub(self.for_tick))
+    }
+}
+
+/// Records the timings of the inputs of the active local player,
+/// to diagnose input delay.
+///
+/// Only inputs that were caused by os events (key presses etc.) are recorded,
+/// changes of the cursor alone are ignored.
+#[derive(Debug, Default)]
+pub struct InputTimings {
+    /// The oldest os event of the current frame.
+    pending_os_event: Option<Duration>,
+    last_sent: Option<PlayerInputChainable>,
+    in_flight: VecDeque<InputTiming>,
+    finished: VecDeque<InputTiming>,
+}
+
+impl InputTimings {
+    pub fn on_os_event(&mut self, at: Duration) {
+        self.pending_os_event.get_or_insert(at);
+    }
+
+    /// All os events of the frame were handled,
+    /// the ones that did not change the input are ignored.
+    pub fn end_frame(&mut self) {
+        self.pending_os_event = None;
+    }
+
+    /// The newest input that was sent in the packet.
+    pub fn on_sent(&mut self, input: &PlayerInputChainable, packet_id: u64, at: Duration) {
+        while self
+            .in_flight
+            .front()
+            .is_some_and(|timing| at.saturating_sub(timing.sent_at) > MAX_IN_FLIGHT_TIME)
+        {
+            self.in_flight.pop_front();
+        }
+        // resent inputs keep their version
+        let last_sent = self.last_sent.replace(*input);
+        if last_sent.is_some_and(|last_sent| last_sent.inp.version() >= input.inp.version()) {
+            return;
+        }
+        let cursor_only = |last_sent: PlayerInputChainable| {
+            let (last, cur) = (&last_sent.inp.inp, &input.inp.inp);
+            last.state == cur.state && last.consumable == cur.consumable
+        };
+        if last_sent.is_some_and(cursor_only) {
+            return;
+        }
+        let Some(os_event_at) = self.pending_os_event.take() else {
+            return;
+        };
+        self.in_flight.push_back(InputTiming {
+            os_event_at,
+            sent_at: at,
+            predicted_at: None,
+            acked_at: None,
+            for_tick: input.for_monotonic_tick,
+            applied_tick: None,
+            version: input.inp.version(),
+            packet_id,
+        });
+    }
+
+    /// The prediction used the input with this version,
+    /// which includes all older inputs.
+    pub fn on_predicted(&mut self, version: u64, at: Duration) {
+        self.in_flight
+            .iter_mut()
+            .filter(|timing| timing.version <= version)
+            .for_each(|timing| {
+                timing.predicted_at.get_or_insert(at);
+            });
+    }
+
+    pub fn on_acked(&mut self, packet_id: u64, at: Duration) {
+        let Some(index) = self
+            .in_flight
+            .iter()
+            .position(|timing| timing.packet_id == packet_id)
+        else {
+            return;
+        };
+        let mut timing = self.in_flight.remove(index).unwrap();
+        timing.acked_at = Some(at);
+        while self.finished.len() >= MAX_FINISHED {
+            self.finished.pop_front();
+        }
+        self.finished.push_back(timing);
+    }
+
+    /// The server echoed the tick it applied the inputs of the packet for.
+    ///
+    /// The echo might arrive before or after the ack.
+    pub fn on_applied(&mut self, packet_id: u64, applied_tick: GameTickType) {
+        if let Some(timing) = self
+            .finished
+            .iter_mut()
+            .rev()
+            .chain(self.in_flight.iter_mut())
+            .find(|timing| timing.packet_id == packet_id)
+        {
+            timing.applied_tick = Some(applied_tick);
+        }
+    }
+
+    /// The ack'd inputs, oldest first.
+    pub fn finished(&self) -> impl Iterator<Item = &InputTiming> {
+        self.finished.iter()
+    }
+
+    /// The amount of ack'd inputs by their total time in buckets of `bucket_size`.
+    ///
+    /// The last bucket also contains all longer times.
+    pub fn histogram(&self, bucket_size: Duration, buckets: usize) -> Vec<usize> {
+        let mut res = vec![0; buckets];
+        if buckets == 0 {
+            return res;
+        }
+        for total in self.finished.iter().filter_map(|timing| timing.total()) {
+            let bucket = (total.as_nanos() / bucket_size.as_nanos().max(1)) as usize;
+            res[bucket.min(buckets - 1)] += 1;
+        }
+        res
+    }
+
+    /// All ack'd inputs as csv, times are in milliseconds.
+    pub fn to_csv(&self) -> String {
+        let ms = |time: Option<Duration>| {
+            time.map(|time| format!("{:.3}", time.as_secs_f64() * 1000.0))
+                .unwrap_or_default()
+        };
+        let tick = |tick: Option<GameTickType>| tick.map(|t| t.to_string()).unwrap_or_default();
+        let mut res =
+            "os_event,send_delay,prediction_delay,round_trip,total,for_tick,applied_tick,\
+            late_ticks\n"
+                .to_string();
+        for timing in self.finished.iter() {
+            let _ = writeln!(
+                res,
+                "{},{},{},{},{},{},{},{}",
+                ms(Some(timing.os_event_at)),
+                ms(Some(timing.send_delay())),
+                ms(timing.prediction_delay()),
+                ms(timing.round_trip()),
+                ms(timing.total()),
+                timing.for_tick,
+                tick(timing.applied_tick),
+                tick(timing.late_ticks()),
+            );
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use game_base::network::messages::PlayerInputChainable;
+    use game_interface::types::input::cursor::CharacterInputCursor;
+    use math::math::vector::dvec2;
+
+    use super::InputTimings;
+
+    /// The input of the simulated pipeline, every jump toggle is a key press.
+    fn input(version: u64, for_tick: u64, jump: bool, cursor_x: f64) -> PlayerInputChainable {
+        let mut inp = PlayerInputChainable {
+            for_monotonic_tick: for_tick,
+            ..Default::default()
+        };
+        for _ in 0..version {
+            inp.inp.inc_version();
+        }
+        inp.inp.inp.state.jump.set(jump);
+        inp.inp
+            .inp
+            .cursor
+            .set(CharacterInputCursor::from_vec2(&dvec2::new(cursor_x, 0.0)));
+        inp
+    }
+
+    #[test]
+    fn pipeline() {
+        let ms = Duration::from_millis;
+        let mut timings = InputTimings::default();
+
+        // a key press, the next frame sends & predicts it
+        timings.on_os_event(ms(100));
+        // later events of the same frame are part of the same input
+        timings.on_os_event(ms(104));
+        timings.on_sent(&input(1, 50, true, 0.0), 0, ms(110));
+        timings.on_predicted(1, ms(112));
+        timings.end_frame();
+        // resent inputs & cursor changes are not recorded
+        timings.on_os_event(ms(120));
+        timings.on_sent(&input(1, 51, true, 0.0), 1, ms(125));
+        timings.on_sent(&input(2, 52, true, 10.0), 2, ms(130));
+        timings.end_frame();
+        // the server applies it one tick late & echos it before the ack
+        timings.on_applied(0, 51);
+        timings.on_acked(1, ms(180));
+        timings.on_acked(2, ms(180));
+        timings.on_acked(0, ms(170));
+
+        let timing = *timings.finished().next().unwrap();
+        assert_eq!(timing.send_delay(), ms(10));
+        assert_eq!(timing.prediction_delay(), Some(ms(12)));
+        assert_eq!(timing.round_trip(), Some(ms(60)));
+        assert_eq!(timing.total(), Some(ms(70)));
+        assert_eq!(timing.late_ticks(), Some(1));
+
+        // os events that did not change the input are not used for later inputs
+        timings.on_os_event(ms(150));
+        timings.end_frame();
+        // inputs that are predicted by a newer version & whose echo arrives late
+        timings.on_os_event(ms(200));
+        timings.on_sent(&input(3, 60, false, 10.0), 3, ms(205));
+        timings.end_frame();
+        timings.on_os_event(ms(210));
+        timings.on_sent(&input(4, 61, true, 10.0), 4, ms(215));
+        timings.on_predicted(4, ms(220));
+        timings.end_frame();
+        timings.on_acked(3, ms(250));
+        timings.on_acked(4, ms(265));
+        timings.on_applied(3, 60);
+        let finished: Vec<_> = timings.finished().copied().collect();
+        assert_eq!(finished.len(), 3);
+        assert_eq!(finished[1].prediction_delay(), Some(ms(20)));
+        assert_eq!(finished[1].late_ticks(), Some(0));
+        assert_eq!(finished[2].prediction_delay(), Some(ms(10)));
+        assert_eq!(finished[2].late_ticks(), None);
+
+        // totals: 70, 50 & 55 ms
+        assert_eq!(timings.histogram(ms(20), 3), [0, 0, 3]);
+        assert_eq!(timings.histogram(ms(60), 2), [2, 1]);
+
+        let csv = timings.to_csv();
+        let mut lines = csv.lines();
+        assert!(lines.next().unwrap().starts_with("os_event,"));
+        assert_eq!(
+            lines.next().unwrap(),
+            "100.000,10.000,12.000,60.000,70.000,50,51,1"
+        );
+        assert_eq!(lines.count(), 2);
+    }
+
+    #[test]
+    fn lost_inputs() {
+        let ms = Duration::from_millis;
+        let mut timings = InputTimings::default();
+        timings.on_os_event(ms(0));
+        timings.on_sent(&input(1, 0, true, 0.0), 0, ms(0));
+        timings.end_frame();
+        // the packet was lost, later inputs drop it
+        timings.on_os_event(ms(5000));
+        timings.on_sent(&input(2, 250, false, 0.0), 1, ms(5000));
+        timings.end_frame();
+        timings.on_acked(0, ms(5010));
+        timings.on_acked(1, ms(5050));
+        let finished: Vec<_> = timings.finished().collect();
+        assert_eq!(finished.len(), 1);
+        assert_eq!(finished[0].total(), Some(ms(50)));
+    }
+}
@@ -1 +1,2 @@
 pub mod input_handling;
+pub mod input_timings;
@@ -0,0 +1,132 @@
+use std::{
+    net::SocketAddr,
+    time::{Duration, SystemTime},
+};
+
+use base_io::io::Io;
+use game_base::{
+    local_stats::{LOCAL_STATS_PATH, LocalStats, LocalStatsEvent, stats_day},
+    server_browser::ServerBrowserData,
+};
+
+/// How often changed stats are saved while playing.
+const SAVE_INTERVAL: Duration = Duration::from_secs(60);
+
+fn today() -> u32 {
+    stats_day(
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default(),
+    )
+}
+
+/// Records the stats of the local player on the current server.
+pub struct LocalStatsRecorder {
+    stats: LocalStats,
+    /// The stats file could not be read, it's never overwritten then.
+    read_only: bool,
+    changed: bool,
+    last_save: Duration,
+
+    /// Address & name of the server the player is on.
+    server: Option<(String, String)>,
+    last_playtime: Duration,
+}
+
+impl LocalStatsRecorder {
+    pub fn new(stats: anyhow::Result<LocalStats>, cur_time: &Duration) -> Self {
+        let (stats, read_only) = match stats {
+            Ok(mut stats) => {
+                stats.rollup(today());
+                (stats, false)
+            }
+            Err(err) => {
+                log::warn!("the local stats are not recorded: {err}");
+                (LocalStats::default(), true)
+            }
+        };
+        Self {
+            stats,
+            read_only,
+            changed: false,
+            last_save: *cur_time,
+
+            server: None,
+            last_playtime: *cur_time,
+        }
+    }
+
+    /// Events outside of a server, e.g. in demos, are ignored.
+    pub fn add_event(&mut self, ev: LocalStatsEvent) {
+        if let Some((addr, name)) = &self.server {
+            self.stats.add_event(addr, name, today(), ev);
+            self.changed = true;
+        }
+    }
+
+    /// Call this every frame, `server` is the address of
+    /// the server the player is currently playing on.
+    pub fn update(
+        &mut self,
+        io: &Io,
+        server: Option<&SocketAddr>,
+        browser_data: &ServerBrowserData,
+        cur_time: &Duration,
+    ) {
+        let addr = server.map(|server| server.to_string());
+        if self.server.as_ref().map(|(addr, _)| addr) != addr.as_ref() {
+            self.add_playtime(cur_time);
+            self.server = addr.map(|addr| {
+                let name = browser_data
+                    .find_str(&addr)
+                    .map(|server| server.info.name.to_string())
+                    .unwrap_or_default();
+                (addr, name)
+            });
+            self.save(io, cur_time);
+        } else if cur_time.saturating_sub(self.last_save) >= SAVE_INTERVAL {
+            self.add_playtime(cur_time);
+            self.save(io, cur_time);
+        }
+    }
+
+    fn add_playtime(&mut self, cur_time: &Duration) {
+        if let Some((addr, name)) = &self.server {
+            let playtime = cur_time.saturating_sub(self.last_playtime);
+            self.stats.add_playtime(addr, name, today(), playtime);
+            self.changed = true;
+        }
+        self.last_playtime = *cur_time;
+    }
+
+    fn save(&mut self, io: &Io, cur_time: &Duration) {
+        self.last_save = *cur_time;
+        if !self.changed || self.read_only {
+            return;
+        }
+        self.changed = false;
+        let fs = io.fs.clone();
+        let stats = self.stats.clone();
+        io.rt.spawn_without_lifetime(
+            async move { stats.save(&*fs, LOCAL_STATS_PATH.as_ref()).await },
+        );
+    }
+
+    /// Adds the remaining playtime & saves, blocks until the file is written.
+    pub fn save_blocking(&mut self, io: &Io, cur_time: &Duration) {
+        self.add_playtime(cur_time);
+        if !self.changed || self.read_only {
+            return;
+        }
+        self.changed = false;
+        let fs = io.fs.clone();
+        let stats = self.stats.clone();
+        if let Err(err) = io
+            .rt
+            .spawn(async move { stats.save(&*fs, LOCAL_STATS_PATH.as_ref()).await })
+            .get()
+        {
+            log::warn!("failed to save the local stats: {err}");
+        }
+    }
+}
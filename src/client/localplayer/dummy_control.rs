@@ -1,4 +1,9 @@
-use std::time::Duration;
+use std::{collections::VecDeque, time::Duration};
+
+use game_interface::types::input::{
+    CharacterInputConsumableDiff, CharacterInputState, InputVarState, cursor::CharacterInputCursor,
+    viewport::CharacterInputViewport,
+};
 
 #[derive(Debug, Default, Clone, Copy)]
 pub enum DummyHammerState {
@@ -9,9 +14,129 @@ pub enum DummyHammerState {
     },
 }
 
+/// The input of the main player that is copied to the dummy.
+#[derive(Debug, Clone, Copy)]
+pub struct DummyCopiedInput {
+    pub consumable: CharacterInputConsumableDiff,
+    pub state: CharacterInputState,
+    pub cursor: InputVarState<CharacterInputCursor>,
+    pub viewport: InputVarState<CharacterInputViewport>,
+}
+
 #[derive(Debug, Default)]
 pub struct DummyControlState {
     // dummy controls
     pub dummy_copy_moves: bool,
     pub dummy_hammer: DummyHammerState,
+
+    /// Copied inputs that are applied to the dummy
+    /// once their time is reached.
+    copied_inputs: VecDeque<(Duration, DummyCopiedInput)>,
+}
+
+impl DummyControlState {
+    /// Schedules a copied input, that is applied to the dummy after `delay`.
+    pub fn push_copied_input(
+        &mut self,
+        cur_time: Duration,
+        delay: Duration,
+        inp: DummyCopiedInput,
+    ) {
+        self.copied_inputs.push_back((cur_time + delay, inp));
+    }
+
+    /// Takes all copied inputs that are due in the order they were copied.
+    ///
+    /// The consumable actions of all these inputs must be applied,
+    /// for the rest the last input wins.
+    pub fn take_due_copied_inputs(&mut self, cur_time: Duration) -> Vec<DummyCopiedInput> {
+        let due = self
+            .copied_inputs
+            .iter()
+            .take_while(|(time, _)| *time <= cur_time)
+            .count();
+        self.copied_inputs
+            .drain(..due)
+            .map(|(_, inp)| inp)
+            .collect()
+    }
+
+    /// Forgets all copied inputs that are not applied yet.
+    pub fn clear_copied_inputs(&mut self) {
+        self.copied_inputs.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use game_interface::types::input::CharacterInputConsumable;
+
+    use super::{DummyControlState, DummyCopiedInput};
+
+    fn inp(jumps: u64) -> DummyCopiedInput {
+        let mut consumable = CharacterInputConsumable::default();
+        consumable.jump.add(jumps);
+        DummyCopiedInput {
+            consumable: consumable.diff(&Default::default()),
+            state: Default::default(),
+            cursor: Default::default(),
+            viewport: Default::default(),
+        }
+    }
+
+    fn jumps(inps: &[DummyCopiedInput]) -> Vec<u64> {
+        inps.iter()
+            .map(|inp| inp.consumable.jump.map(|j| j.get()).unwrap_or_default())
+            .collect()
+    }
+
+    #[test]
+    fn copied_inputs_without_delay() {
+        let mut control = DummyControlState::default();
+        let now = Duration::from_secs(1);
+        control.push_copied_input(now, Duration::ZERO, inp(1));
+        assert_eq!(jumps(&control.take_due_copied_inputs(now)), [1]);
+        assert!(control.take_due_copied_inputs(now).is_empty());
+    }
+
+    #[test]
+    fn copied_inputs_are_delayed() {
+        let mut control = DummyControlState::default();
+        let delay = Duration::from_millis(100);
+        let tick = Duration::from_millis(20);
+        let mut now = Duration::from_secs(1);
+
+        let mut applied = Vec::new();
+        for i in 0..10 {
+            control.push_copied_input(now, delay, inp(i + 1));
+            applied.push(jumps(&control.take_due_copied_inputs(now)));
+            now += tick;
+        }
+        // nothing is applied before the delay passed
+        assert!(applied[..5].iter().all(|inps| inps.is_empty()));
+        // afterwards exactly one input per tick in order
+        assert_eq!(applied[5..], [[1], [2], [3], [4], [5]]);
+    }
+
+    #[test]
+    fn late_frame_applies_all_due_inputs() {
+        let mut control = DummyControlState::default();
+        let delay = Duration::from_millis(50);
+        let now = Duration::from_secs(1);
+        for i in 0..4 {
+            control.push_copied_input(now + Duration::from_millis(10 * i), delay, inp(i + 1));
+        }
+        assert_eq!(
+            jumps(&control.take_due_copied_inputs(now + Duration::from_millis(75))),
+            [1, 2, 3]
+        );
+        control.clear_copied_inputs();
+        assert!(
+            control
+                .take_due_copied_inputs(now + Duration::from_secs(1))
+                .is_empty()
+        );
+    }
 }
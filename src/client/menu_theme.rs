@@ -0,0 +1,100 @@
+//! Picks the variant of the menu theme.
+//!
+//! A theme is a directory of maps, the `day` map is the base theme
+//! & every theme has it, the `night` map is an optional variant.
+
+use chrono::Timelike;
+use game_config::config::ConfigThemeVariant;
+use map::map::metadata::ThemeAmbiance;
+
+/// The first hour of the night.
+const NIGHT_START_HOUR: u32 = 20;
+/// The first hour of the day.
+const DAY_START_HOUR: u32 = 6;
+
+/// The name of the map of the variant inside the theme directory.
+pub fn theme_map_name(ambiance: ThemeAmbiance) -> &'static str {
+    match ambiance {
+        ThemeAmbiance::Day => "day",
+        ThemeAmbiance::Night => "night",
+    }
+}
+
+/// The variant for the hour of the local time.
+pub fn ambiance_by_hour(hour: u32) -> ThemeAmbiance {
+    if (DAY_START_HOUR..NIGHT_START_HOUR).contains(&hour) {
+        ThemeAmbiance::Day
+    } else {
+        ThemeAmbiance::Night
+    }
+}
+
+/// The current hour of the local time.
+pub fn local_hour() -> u32 {
+    chrono::Local::now().hour()
+}
+
+/// The variant to show.
+///
+/// The ambiance the loading map prefers wins over the setting
+/// of the user, `Auto` follows the local time.
+pub fn select_ambiance(
+    setting: ConfigThemeVariant,
+    map_ambiance: Option<ThemeAmbiance>,
+    hour: u32,
+) -> ThemeAmbiance {
+    match (map_ambiance, setting) {
+        (Some(ambiance), _) => ambiance,
+        (None, ConfigThemeVariant::Day) => ThemeAmbiance::Day,
+        (None, ConfigThemeVariant::Night) => ThemeAmbiance::Night,
+        (None, ConfigThemeVariant::Auto) => ambiance_by_hour(hour),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use game_config::config::ConfigThemeVariant;
+    use map::map::metadata::ThemeAmbiance;
+
+    use super::{ambiance_by_hour, select_ambiance};
+
+    #[test]
+    fn ambiance_by_time() {
+        for hour in [0, 3, 5, 20, 23] {
+            assert_eq!(ambiance_by_hour(hour), ThemeAmbiance::Night, "{hour}");
+        }
+        for hour in [6, 12, 19] {
+            assert_eq!(ambiance_by_hour(hour), ThemeAmbiance::Day, "{hour}");
+        }
+        assert_eq!(
+            select_ambiance(ConfigThemeVariant::Auto, None, 22),
+            ThemeAmbiance::Night
+        );
+        assert_eq!(
+            select_ambiance(ConfigThemeVariant::Auto, None, 10),
+            ThemeAmbiance::Day
+        );
+    }
+
+    #[test]
+    fn map_ambiance_precedence() {
+        // the setting overrides the time
+        assert_eq!(
+            select_ambiance(ConfigThemeVariant::Day, None, 22),
+            ThemeAmbiance::Day
+        );
+        assert_eq!(
+            select_ambiance(ConfigThemeVariant::Night, None, 10),
+            ThemeAmbiance::Night
+        );
+        // the map overrides both
+        assert_eq!(
+            select_ambiance(ConfigThemeVariant::Day, Some(ThemeAmbiance::Night), 10),
+            ThemeAmbiance::Night
+        );
+        assert_eq!(
+            select_ambiance(ConfigThemeVariant::Auto, Some(ThemeAmbiance::Day), 22),
+            ThemeAmbiance::Day
+        );
+    }
+}
@@ -1,9 +1,14 @@
 pub mod client;
+mod frame_pacing;
 pub mod game;
 mod game_events;
+mod idle;
 mod input;
+mod local_stats;
 pub mod localplayer;
+mod menu_theme;
 mod overlays;
+mod server_lists;
 pub mod spatial_chat;
 pub mod ui;
 
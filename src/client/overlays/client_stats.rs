@@ -1,5 +1,8 @@
 use std::{
-    sync::{Arc, atomic::AtomicU64},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
     time::Duration,
 };
 
@@ -14,8 +17,12 @@ use graphics::{
         stream::stream::GraphicsStreamHandle, texture::texture::GraphicsTextureHandle,
     },
 };
+use graphics_types::gpu::{GpuTimerPass, GpuTimings};
 
-use prediction_timer::prediction_timing::PredictionTimer;
+use prediction_timer::{
+    clock_sync::ClockSync, interpolation_buffer::InterpolationBuffer,
+    prediction_timing::PredictionTimer,
+};
 use tracing::instrument;
 use ui_base::{
     types::UiRenderPipe,
@@ -27,13 +34,17 @@ use math::math::{blend, vector::luffixed};
 
 use base::steady_clock::SteadyClock;
 
-use crate::game::data::NetworkByteStats;
+use crate::{
+    client::{frame_pacing::FramePacingStats, input::input_timings::InputTimings},
+    game::data::NetworkByteStats,
+};
 
 pub struct DebugHudData {
     texture_memory_usage: Arc<AtomicU64>,
     buffer_memory_usage: Arc<AtomicU64>,
     stream_memory_usage: Arc<AtomicU64>,
     staging_memory_usage: Arc<AtomicU64>,
+    gpu_timings: Arc<GpuTimings>,
 }
 
 impl DebugHudData {
@@ -42,12 +53,84 @@ impl DebugHudData {
         buffer_memory_usage: Arc<AtomicU64>,
         stream_memory_usage: Arc<AtomicU64>,
         staging_memory_usage: Arc<AtomicU64>,
+        gpu_timings: Arc<GpuTimings>,
     ) -> Self {
         Self {
             texture_memory_usage,
             buffer_memory_usage,
             stream_memory_usage,
             staging_memory_usage,
+            gpu_timings,
+        }
+    }
+
+    fn gpu_pass_color(pass: GpuTimerPass) -> Color32 {
+        match pass {
+            GpuTimerPass::MapBackground => Color32::from_rgb(80, 160, 255),
+            GpuTimerPass::Entities => Color32::from_rgb(255, 200, 60),
+            GpuTimerPass::Particles => Color32::from_rgb(255, 90, 90),
+            GpuTimerPass::Ui => Color32::from_rgb(120, 230, 120),
+        }
+    }
+
+    /// The gpu time of every pass & a bar that splits the frame's gpu time into them.
+    fn render_gpu_timings(&self, ui: &mut egui::Ui) {
+        ui.label("GPU time (ms):");
+        if !self.gpu_timings.supported.load(Ordering::Relaxed) {
+            ui.colored_label(
+                Color32::from_rgb(255, 0, 255),
+                "GPU timings are not supported by this device",
+            );
+            return;
+        }
+
+        let times: Vec<_> = self.gpu_timings.pass_times().collect();
+        let total: u64 = times.iter().map(|(_, time)| time).sum();
+
+        let (rect, _) =
+            ui.allocate_exact_size(egui::vec2(ui.available_width(), 8.0), egui::Sense::hover());
+        let mut x = rect.min.x;
+        for (pass, time) in times.iter().filter(|_| total > 0) {
+            let width = rect.width() * (*time as f32 / total as f32);
+            ui.painter().rect_filled(
+                egui::Rect::from_min_size(egui::pos2(x, rect.min.y), egui::vec2(width, 8.0)),
+                0.0,
+                Self::gpu_pass_color(*pass),
+            );
+            x += width;
+        }
+
+        for (pass, time) in times {
+            let name: &'static str = pass.into();
+            ui.label(format!("{name}:"));
+            ui.colored_label(
+                Self::gpu_pass_color(pass),
+                format!("{:.3}", time as f64 / 1_000_000.0),
+            );
+        }
+    }
+
+    /// The timings of the last frames, to check the frame limiter.
+    fn render_frame_pacing(ui: &mut egui::Ui, frame_pacing: &FramePacingStats) {
+        let ms = |time: Duration| format!("{:.2}", time.as_micros() as f64 / 1000.0);
+        let average = frame_pacing.average();
+
+        ui.label("Frame pacing");
+        ui.label("FPS:");
+        ui.colored_label(
+            Color32::from_rgb(255, 0, 255),
+            format!("{:.1}", frame_pacing.fps()),
+        );
+        for (name, time) in [
+            ("Frame time (ms):", average.frame),
+            ("Max frame time (ms):", frame_pacing.max_frame_time()),
+            ("Work (ms):", average.work),
+            ("Sleep (ms):", average.sleep),
+            ("Spin (ms):", average.spin),
+            ("Late (ms):", average.late),
+        ] {
+            ui.label(name);
+            ui.colored_label(Color32::from_rgb(255, 0, 255), ms(time));
         }
     }
 
@@ -55,6 +138,7 @@ impl DebugHudData {
         &mut self,
         ui: &mut egui::Ui,
         pipe: &mut UiRenderPipe<Option<DebugHudRenderPipe<'_>>>,
+        frame_pacing: &FramePacingStats,
     ) {
         StripBuilder::new(ui)
             .size(egui_extras::Size::remainder())
@@ -125,6 +209,42 @@ impl DebugHudData {
                             format!("{:.2}", dbg.prediction_timer.packet_loss() * 100.0),
                         );
 
+                        ui.label("Interpolation buffer / target (ms):");
+                        ui.colored_label(
+                            Color32::from_rgb(255, 0, 255),
+                            format!(
+                                "{:.2} / {:.2}",
+                                dbg.interpolation_buffer.current().as_micros() as f64 / 1000.0,
+                                dbg.interpolation_buffer.target().as_micros() as f64 / 1000.0
+                            ),
+                        );
+                        ui.label("Snapshot jitter (ms):");
+                        ui.colored_label(
+                            Color32::from_rgb(255, 0, 255),
+                            format!(
+                                "{:.2}",
+                                dbg.interpolation_buffer.snap_jitter().as_micros() as f64 / 1000.0
+                            ),
+                        );
+                        ui.label("Snapshot loss %:");
+                        ui.colored_label(
+                            Color32::from_rgb(255, 0, 255),
+                            format!("{:.2}", dbg.interpolation_buffer.snap_loss() * 100.0),
+                        );
+                        ui.label("Server clock drift (ppm):");
+                        ui.colored_label(
+                            Color32::from_rgb(255, 0, 255),
+                            dbg.clock_sync
+                                .drift()
+                                .map(|drift| format!("{:.1}", drift * 1_000_000.0))
+                                .unwrap_or_else(|| "-".to_string()),
+                        );
+                        ui.label("Server clock steps:");
+                        ui.colored_label(
+                            Color32::from_rgb(255, 0, 255),
+                            dbg.clock_sync.steps().to_string(),
+                        );
+
                         ui.label("Sent Kibit/s:");
                         ui.colored_label(
                             Color32::from_rgb(255, 0, 255),
@@ -145,6 +265,8 @@ impl DebugHudData {
                         );
                     }
 
+                    Self::render_frame_pacing(ui, frame_pacing);
+
                     ui.label("Graphics");
                     ui.label("Texture usage MiB:");
                     ui.colored_label(
@@ -201,6 +323,8 @@ impl DebugHudData {
                                 / 1024.0)
                         ),
                     );
+
+                    self.render_gpu_timings(ui);
                 })
             });
     }
@@ -208,6 +332,8 @@ impl DebugHudData {
 
 pub struct DebugHudRenderPipe<'a> {
     pub prediction_timer: &'a PredictionTimer,
+    pub interpolation_buffer: &'a InterpolationBuffer,
+    pub clock_sync: &'a ClockSync,
     pub byte_stats: &'a NetworkByteStats,
     pub ingame_timer: &'a Duration,
 }
@@ -262,6 +388,11 @@ pub struct ClientStatsRenderPipe<'a> {
     pub connection_issues: bool,
     pub force_bottom: bool,
     pub show_fps: bool,
+    /// The config variables of the `watch` command & their values.
+    pub watches: &'a [(String, String)],
+    /// Only if the input timings are recorded.
+    pub input_timings: Option<&'a InputTimings>,
+    pub frame_pacing: &'a FramePacingStats,
 }
 
 /// This component collects various client statistics and displays them optionally
@@ -286,6 +417,7 @@ impl ClientStats {
         buffer_memory_usage: Arc<AtomicU64>,
         stream_memory_usage: Arc<AtomicU64>,
         staging_memory_usage: Arc<AtomicU64>,
+        gpu_timings: Arc<GpuTimings>,
         creator: &UiCreator,
     ) -> Self {
         let mut ui = UiContainer::new(creator);
@@ -298,6 +430,7 @@ impl ClientStats {
                 buffer_memory_usage,
                 stream_memory_usage,
                 staging_memory_usage,
+                gpu_timings,
             ),
             ui,
             time: time.clone(),
@@ -318,10 +451,92 @@ impl ClientStats {
         );
     }
 
+    /// The watched config variables on the left side of the screen.
+    pub fn render_watches(ui: &mut egui::Ui, watches: &[(String, String)]) {
+        let text = watches
+            .iter()
+            .map(|(name, val)| format!("{name}: {val}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let galley = ui.painter().layout_no_wrap(
+            text,
+            FontId::monospace(12.0),
+            Color32::from_rgb(255, 255, 255),
+        );
+        let rect = egui::Align2::LEFT_CENTER.anchor_size(
+            ui.ctx().screen_rect().left_center() + egui::vec2(5.0, 0.0),
+            galley.size(),
+        );
+        ui.painter().rect_filled(
+            rect.expand(3.0),
+            3.0,
+            Color32::from_rgba_unmultiplied(0, 0, 0, 150),
+        );
+        ui.painter().galley(rect.min, galley, Color32::WHITE);
+    }
+
+    /// A histogram of the input timings from the os event to the server's ack
+    /// on the bottom left of the screen.
+    pub fn render_input_timings(ui: &mut egui::Ui, input_timings: &InputTimings) {
+        const BUCKET_SIZE: Duration = Duration::from_millis(10);
+        const BUCKETS: usize = 15;
+        const BAR_WIDTH: f32 = 8.0;
+        const HEIGHT: f32 = 60.0;
+
+        let histogram = input_timings.histogram(BUCKET_SIZE, BUCKETS);
+        let max = histogram.iter().copied().max().unwrap_or_default().max(1);
+        let size = egui::vec2(BAR_WIDTH * BUCKETS as f32, HEIGHT);
+        let rect = egui::Align2::LEFT_BOTTOM.anchor_size(
+            ui.ctx().screen_rect().left_bottom() + egui::vec2(5.0, -20.0),
+            size,
+        );
+        ui.painter().rect_filled(
+            rect.expand(3.0),
+            3.0,
+            Color32::from_rgba_unmultiplied(0, 0, 0, 150),
+        );
+        for (index, count) in histogram.into_iter().enumerate() {
+            let height = HEIGHT * count as f32 / max as f32;
+            ui.painter().rect_filled(
+                egui::Rect::from_min_size(
+                    egui::pos2(rect.min.x + index as f32 * BAR_WIDTH, rect.max.y - height),
+                    egui::vec2(BAR_WIDTH - 1.0, height),
+                ),
+                0.0,
+                Color32::from_rgb(255, 0, 255),
+            );
+        }
+
+        let mut totals: Vec<_> = input_timings
+            .finished()
+            .filter_map(|timing| timing.total())
+            .collect();
+        totals.sort();
+        let median = totals
+            .get(totals.len() / 2)
+            .map(|total| format!("{:.1}ms", total.as_secs_f64() * 1000.0))
+            .unwrap_or_else(|| "-".to_string());
+        ui.painter().text(
+            rect.left_bottom() + egui::vec2(0.0, 3.0),
+            egui::Align2::LEFT_TOP,
+            format!(
+                "Input to ack, {}ms buckets, median: {median}",
+                BUCKET_SIZE.as_millis()
+            ),
+            FontId::proportional(10.0),
+            Color32::WHITE,
+        );
+    }
+
     #[instrument(level = "trace", skip_all)]
     pub fn render(&mut self, pipe: &mut ClientStatsRenderPipe) {
         let dbg_hud_open = self.ui.ui_state.is_ui_open;
-        if !dbg_hud_open && !pipe.show_fps && !pipe.connection_issues {
+        if !dbg_hud_open
+            && !pipe.show_fps
+            && !pipe.connection_issues
+            && pipe.watches.is_empty()
+            && pipe.input_timings.is_none()
+        {
             return;
         }
 
@@ -341,6 +556,7 @@ impl ClientStats {
                             cur_time: inner_pipe.cur_time,
                             user_data: &mut pipe.debug_hud,
                         },
+                        pipe.frame_pacing,
                     );
                 }
                 if pipe.show_fps {
@@ -353,6 +569,12 @@ impl ClientStats {
                 if pipe.connection_issues {
                     Self::render_connection_issues(ui);
                 }
+                if !pipe.watches.is_empty() {
+                    Self::render_watches(ui, pipe.watches);
+                }
+                if let Some(input_timings) = pipe.input_timings {
+                    Self::render_input_timings(ui, input_timings);
+                }
             },
             &mut UiRenderPipe::new(self.time.now(), &mut ()),
             Default::default(),
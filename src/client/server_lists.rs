@@ -0,0 +1,41 @@
+//! Shareable files of the favorite servers & the connect history.
+
+use std::path::{Path, PathBuf};
+
+use base_io::{io::Io, runtime::IoRuntimeTask};
+use game_base::browser_server_list::ServerListExport;
+
+const SERVER_LISTS_DIR: &str = "server_lists";
+
+pub enum ServerListTask {
+    Exported(PathBuf),
+    Imported(ServerListExport),
+}
+
+fn server_list_path(name: &str) -> PathBuf {
+    Path::new(SERVER_LISTS_DIR).join(format!("{name}.json"))
+}
+
+pub fn export_server_list(
+    io: &Io,
+    name: &str,
+    list: ServerListExport,
+) -> IoRuntimeTask<ServerListTask> {
+    let fs = io.fs.clone();
+    let path = server_list_path(name);
+    io.rt.spawn(async move {
+        fs.create_dir(SERVER_LISTS_DIR.as_ref()).await?;
+        fs.write_file(&path, serde_json::to_vec_pretty(&list)?)
+            .await?;
+        Ok(ServerListTask::Exported(path))
+    })
+}
+
+pub fn import_server_list(io: &Io, name: &str) -> IoRuntimeTask<ServerListTask> {
+    let fs = io.fs.clone();
+    let path = server_list_path(name);
+    io.rt.spawn(async move {
+        let file = fs.read_file(&path).await?;
+        Ok(ServerListTask::Imported(serde_json::from_slice(&file)?))
+    })
+}
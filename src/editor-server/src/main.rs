@@ -28,6 +28,7 @@ use x509_cert::der::{Decode, Encode};
 fn prepare_backend(io: &Io, tp: &Arc<ThreadPool>) -> (Rc<GraphicsBackend>, GraphicsStreamedData) {
     let config_gfx = config::config::ConfigGfx {
         backend: "null".into(),
+        safe_mode: false,
     };
     let io_loading = GraphicsBackendIoLoading::new(&config_gfx, &io.clone().into());
 
@@ -1,6 +1,7 @@
 #![allow(clippy::too_many_arguments)]
 #![allow(clippy::module_inception)]
 
+pub mod bench;
 pub mod client;
 
 #[cfg(test)]
@@ -94,17 +95,46 @@ fn main_impl(app: NativeApp) {
     if !args.is_empty() {
         args.remove(0);
     }
+    if args.first().is_some_and(|arg| arg == "--bench") {
+        let res =
+            bench::BenchOptions::from_args(&args[1..]).and_then(|options| bench::run(&options));
+        match res {
+            Ok(res) => println!("{}", res.to_json()),
+            Err(err) => {
+                eprintln!("benchmark failed: {err}");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
     if let Err(err) = ddnet_main(args, time, shared_info, app) {
         panic!("exited client with an error: {} - {}", err, err.backtrace()); // TODO: panic or graceful closing?
     }
 }
 
+/// Logs to the terminal like [`env_logger::init`] & additionally
+/// captures the records for the logs tab of the local console.
+fn init_logger() {
+    let env_logger = env_logger::Builder::from_default_env().build();
+    let env_filter = env_logger.filter();
+    let logger = client_types::log::LogCapture::new(
+        env_logger,
+        env_filter,
+        log::LevelFilter::Info,
+        client_types::log::LogBuffer::global().clone(),
+    );
+    let max_level = logger.max_level();
+    if log::set_boxed_logger(Box::new(logger)).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
+
 #[allow(dead_code)]
 fn main() {
     if std::env::var("RUST_LOG").is_err() {
         unsafe { std::env::set_var("RUST_LOG", "info,symphonia=warn,df::tract=error") };
     }
-    env_logger::init();
+    init_logger();
     #[cfg(not(target_os = "android"))]
     main_impl(Default::default())
 }
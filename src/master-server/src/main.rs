@@ -53,6 +53,11 @@ const SERVER_TIMEOUT_SECONDS: u64 = 30;
 
 type ShortString = ArrayString<[u8; 64]>;
 
+#[derive(Debug, Deserialize)]
+struct ServerQuery {
+    addr: SocketAddr,
+}
+
 #[derive(Debug, Deserialize)]
 struct Register {
     address: RegisterAddr,
@@ -566,6 +571,27 @@ async fn handle_periodic_writeout(
     }
 }
 
+/// The server list in the format of the `out` file, but only with
+/// the server that registered the given address, if any.
+fn server_info_json(servers: &Servers, addr: SocketAddr) -> Option<String> {
+    let addr = Addr {
+        ip: addr.ip(),
+        port: addr.port(),
+        protocol: Protocol::VPg,
+    };
+    let server = servers.servers.get(&servers.addresses.get(&addr)?.secret)?;
+    let location = server
+        .addresses
+        .iter()
+        .filter_map(|addr| servers.addresses.get(addr).and_then(|info| info.location))
+        .next();
+    let mut serialized = SerializedServers::new();
+    serialized
+        .servers
+        .push(SerializedServer::new(server, location));
+    Some(json::to_string(&serialized).unwrap())
+}
+
 async fn send_challenge(
     connless_request_token_7: Option<[u8; 4]>,
     socket: Arc<tokio::net::UdpSocket>,
@@ -955,6 +981,31 @@ async fn main() {
             .body(json::to_string(&body).unwrap() + "\n")
     }
 
+    let server_info = {
+        let servers = servers.clone();
+        warp::path!("ddnet" / "15" / "server")
+            .and(warp::get())
+            .and(warp::query::<ServerQuery>())
+            .map(move |query: ServerQuery| {
+                let json = server_info_json(
+                    &servers.lock().unwrap_or_else(|poison| poison.into_inner()),
+                    query.addr,
+                );
+                let (http_status, body) = match json {
+                    Some(json) => (warp::http::StatusCode::OK, json),
+                    None => (
+                        warp::http::StatusCode::NOT_FOUND,
+                        json::to_string(&RegisterResponse::Error("server not found".into()))
+                            .unwrap(),
+                    ),
+                };
+                warp::http::Response::builder()
+                    .status(http_status)
+                    .header(warp::http::header::CONTENT_TYPE, "application/json")
+                    .body(body + "\n")
+            })
+    };
+
     let register = warp::path!("ddnet" / "15" / "register")
         .and(warp::post())
         .and(warp::header::headers_cloned())
@@ -987,9 +1038,8 @@ async fn main() {
                     }
                 })
             },
-        )
-        .recover(recover);
-    let server = warp::serve(register);
+        );
+    let server = warp::serve(register.or(server_info).recover(recover));
 
     let task_server = if let Some(path) = listen_unix {
         #[cfg(unix)]
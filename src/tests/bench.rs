@@ -0,0 +1,28 @@
+use crate::bench::{BenchOptions, run};
+
+#[test]
+fn bench_smoke() {
+    let options = BenchOptions {
+        bots: 4,
+        players: 4,
+        ticks: 100,
+        seed: 1,
+        ..Default::default()
+    };
+    let res = run(&options).unwrap();
+    assert!(res.ticks_per_second > 0.0);
+    assert!(res.avg_frame_prep_time.is_some());
+    assert_eq!(res.allocations.is_some(), cfg!(feature = "alloc_stats"));
+
+    // the same seed must lead to the same game state
+    let res_again = run(&BenchOptions {
+        render: false,
+        ..options
+    })
+    .unwrap();
+    assert!(res_again.avg_frame_prep_time.is_none());
+    assert_eq!(res.state_hash, res_again.state_hash);
+
+    let json: serde_json::Value = serde_json::from_str(&res.to_json()).unwrap();
+    assert_eq!(json["ticks"], 100);
+}
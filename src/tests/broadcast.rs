@@ -0,0 +1,41 @@
+use std::time::Duration;
+
+use client_render::broadcast::page::{BroadcastRender, BroadcastRenderPipe};
+use graphics::graphics::graphics::Graphics;
+use ui_base::ui::UiCreator;
+
+use super::utils::render_helper;
+
+pub fn test_broadcast(graphics: &Graphics, creator: &UiCreator, save_screenshot: impl Fn(&str)) {
+    let mut broadcast = BroadcastRender::new(graphics, creator);
+
+    let mut time_offset = Duration::ZERO;
+    let mut render = |base_name: &str, msg: &str| {
+        let render_internal = |_i: u64, time_offset: Duration| {
+            broadcast.msg = msg.into();
+            broadcast.started_at = Some(time_offset);
+            broadcast.render(&mut BroadcastRenderPipe {
+                cur_time: &time_offset,
+            });
+        };
+        render_helper(
+            graphics,
+            render_internal,
+            &mut time_offset,
+            base_name,
+            &save_screenshot,
+        );
+    };
+
+    render("broadcast_empty", "");
+    render(
+        "broadcast_short",
+        "[b]round starts[/b] in [color=yellow]10[/color] seconds",
+    );
+    render(
+        "broadcast_long",
+        "[center]hello hello \
+        wwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwww\
+        wwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwww[/center]",
+    );
+}
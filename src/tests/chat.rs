@@ -64,6 +64,7 @@ pub fn test_chat(
                 },
                 msg: "test".into(),
                 channel: NetChatMsgPlayerChannel::Global,
+                highlighted: false,
             }),
             add_time: Duration::MAX,
         },
@@ -81,6 +82,7 @@ pub fn test_chat(
                         bla bla bla bla bla bla"
                     .into(),
                 channel: NetChatMsgPlayerChannel::Global,
+                highlighted: false,
             }),
             add_time: Duration::MAX,
         },
@@ -66,6 +66,8 @@ pub fn test_hud(
                     time: PoolString::new_str_without_pool("22:14:14"),
                     date: PoolString::new_str_without_pool("Saturday, 27. September 2025"),
                 }),
+                race_splits: &None,
+                settings: &Default::default(),
             })
         };
         render_helper(
@@ -101,6 +103,7 @@ pub fn test_hud(
                 health: all,
                 armor: all,
                 ammo_of_weapon: if all > 10 { None } else { Some(all) },
+                fire_delay: None,
             });
             render(
                 &local_player_info,
@@ -116,6 +119,7 @@ pub fn test_hud(
         health: u32::MAX,
         armor: u32::MAX,
         ammo_of_weapon: Some(u32::MAX),
+        fire_delay: Some((10, 25.try_into().unwrap())),
     });
     render(
         &local_player_info,
@@ -221,6 +225,8 @@ pub fn test_hud(
                 },
                 round_time_type: MatchRoundTimeType::Normal,
                 unbalanced: false,
+                balance_suggestion: Vec::new(),
+                balance_at_round_end: false,
             }),
             &Default::default(),
             &format!("hud_game_sided{:0>6}", p),
@@ -247,6 +253,8 @@ pub fn test_hud(
                 },
                 round_time_type: MatchRoundTimeType::Normal,
                 unbalanced: false,
+                balance_suggestion: Vec::new(),
+                balance_at_round_end: false,
             }),
             &[(
                 character_id,
@@ -265,6 +273,8 @@ pub fn test_hud(
                     player_info: None,
                     browser_score: PoolNetworkString::new_without_pool(),
                     browser_eye: TeeEye::Normal,
+                    is_bot: false,
+                    server_rank: None,
                     account_name: Some(PoolNetworkString::from_without_pool(
                         "testname".try_into().unwrap(),
                     )),
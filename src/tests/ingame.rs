@@ -60,6 +60,8 @@ pub fn test_ingame(
                 player_info: None,
                 browser_score: PoolNetworkString::new_without_pool(),
                 browser_eye: TeeEye::Happy,
+                is_bot: false,
+                server_rank: None,
                 account_name: Some(PoolNetworkString::from_without_pool(
                     "testname".try_into().unwrap(),
                 )),
@@ -94,6 +96,7 @@ pub fn test_ingame(
                 game_ticks_passed: 0,
                 emoticon: None,
                 phased: false,
+                practice: false,
             },
         );
     }
@@ -215,6 +218,8 @@ pub fn test_ingame_skins(
                 player_info: None,
                 browser_score: PoolNetworkString::new_without_pool(),
                 browser_eye: TeeEye::Happy,
+                is_bot: false,
+                server_rank: None,
                 account_name: Some(PoolNetworkString::from_without_pool(
                     "testname".try_into().unwrap(),
                 )),
@@ -246,6 +251,7 @@ pub fn test_ingame_skins(
                 game_ticks_passed: 0,
                 emoticon: None,
                 phased: false,
+                practice: false,
             },
         );
     }
@@ -1,5 +1,7 @@
 pub mod actionfeed;
 pub mod base;
+pub mod bench;
+pub mod broadcast;
 pub mod chat;
 pub mod emote_wheel;
 pub mod hud;
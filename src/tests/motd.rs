@@ -38,10 +38,11 @@ pub fn test_motd(graphics: &Graphics, creator: &UiCreator, save_screenshot: impl
         wwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwww",
     );
     render(
-        "motd_commonmark",
-        "# hello\n\
-        __how are you__\n\
-        `i am fine`\
+        "motd_markup",
+        "[center][b]hello[/b][/center]\n\
+        [color=red]how are [color=#00ff00]you[/color][/color]\n\
+        [url=https://ddnet.org]website[/url] [b]unclosed\
         ",
     );
+    render("motd_scroll", &"line\n".repeat(100));
 }
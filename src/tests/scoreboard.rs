@@ -52,6 +52,8 @@ pub fn test_scoreboard(
                 player_info: None,
                 browser_score: PoolNetworkString::new_without_pool(),
                 browser_eye: TeeEye::Happy,
+                is_bot: false,
+                server_rank: None,
                 account_name: Some(PoolNetworkString::from_without_pool(
                     "testname".try_into().unwrap(),
                 )),
@@ -71,6 +73,8 @@ pub fn test_scoreboard(
                 flags_container: &mut containers.flags_container,
 
                 own_character_id: character_infos.front().unwrap().0,
+                round_stats: None,
+                interactive: None,
             });
         };
         render_helper(
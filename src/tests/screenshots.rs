@@ -21,6 +21,7 @@ use ui_base::{
 use super::{
     actionfeed::test_actionfeed,
     base::{Options, get_base},
+    broadcast::test_broadcast,
     chat::test_chat,
     emote_wheel::test_emote_wheel,
     hud::test_hud,
@@ -118,6 +119,9 @@ fn test_screenshots(
     test_motd(&graphics, &creator, |name| {
         save_screenshot(&graphics, &graphics_backend, name)
     });
+    test_broadcast(&graphics, &creator, |name| {
+        save_screenshot(&graphics, &graphics_backend, name)
+    });
     test_vote(
         &graphics,
         &creator,
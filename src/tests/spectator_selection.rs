@@ -65,6 +65,8 @@ pub fn test_spectator_selection(
                 player_info: None,
                 browser_score: PoolNetworkString::new_without_pool(),
                 browser_eye: TeeEye::Happy,
+                is_bot: false,
+                server_rank: None,
                 account_name: Some(PoolNetworkString::from_without_pool(
                     "testname".try_into().unwrap(),
                 )),